@@ -23,7 +23,7 @@ use frame_support::{pallet_prelude::*, transactional};
 use frame_system::pallet_prelude::*;
 use orml_traits::MultiCurrency;
 use primitives::{Balance, CurrencyId};
-use sp_runtime::SaturatedConversion;
+use sp_runtime::{traits::Zero, RuntimeDebug, SaturatedConversion};
 use sp_std::vec::Vec;
 
 pub use module::*;
@@ -35,6 +35,19 @@ pub mod weights;
 
 type ResourceId = chainbridge::ResourceId;
 
+/// The fee and limits of the transfers of a bridged asset to the bridge.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, Default)]
+pub struct BridgeLimits {
+	/// The fee of each transfer paid to the treasury.
+	pub fee: Balance,
+	/// The minimum amount of each transfer.
+	pub min_amount: Balance,
+	/// The maximum amount of each transfer.
+	pub max_amount: Balance,
+	/// The maximum total amount of the transfers in each volume period.
+	pub daily_limit: Balance,
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -54,6 +67,14 @@ pub mod module {
 		/// only be called by the bridge pallet
 		type BridgeOrigin: EnsureOrigin<Self::Origin, Success = Self::AccountId>;
 
+		/// The account to receive the transfer fees.
+		#[pallet::constant]
+		type TreasuryAccount: Get<Self::AccountId>;
+
+		/// The period of the volume limit of the transfers, e.g. a day.
+		#[pallet::constant]
+		type VolumePeriod: Get<Self::BlockNumber>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -64,6 +85,16 @@ pub mod module {
 		ResourceIdAlreadyRegistered,
 		ResourceIdNotRegistered,
 		ResourceIdCurrencyIdNotMatch,
+		/// The minimum amount is greater than the maximum amount
+		InvalidBridgeLimits,
+		/// The transfers of the bridged asset are paused
+		ResourcePaused,
+		/// The amount is below the minimum amount of each transfer
+		BelowMinimumAmount,
+		/// The amount exceeds the maximum amount of each transfer
+		ExceedMaximumAmount,
+		/// The transfers exceed the volume limit of the period
+		ExceedDailyLimit,
 	}
 
 	#[pallet::event]
@@ -71,6 +102,14 @@ pub mod module {
 	pub enum Event<T: Config> {
 		RegisterResourceId(ResourceId, CurrencyId),
 		UnregisterResourceId(ResourceId, CurrencyId),
+		/// Updated the fee and limits of the transfers of resource id. \[resource_id, limits\]
+		BridgeLimitsUpdated(ResourceId, Option<BridgeLimits>),
+		/// Paused the transfers of resource id. \[resource_id\]
+		ResourcePaused(ResourceId),
+		/// Unpaused the transfers of resource id. \[resource_id\]
+		ResourceUnpaused(ResourceId),
+		/// Charged the transfer fee. \[who, currency_id, fee\]
+		TransferFeeCharged(T::AccountId, CurrencyId, Balance),
 	}
 
 	#[pallet::pallet]
@@ -84,6 +123,27 @@ pub mod module {
 	#[pallet::getter(fn currency_ids)]
 	pub type CurrencyIds<T: Config> = StorageMap<_, Twox64Concat, ResourceId, CurrencyId, OptionQuery>;
 
+	/// The fee and limits of the transfers of the resource ids to the bridge.
+	///
+	/// BridgeLimitsOf: map ResourceId => Option<BridgeLimits>
+	#[pallet::storage]
+	#[pallet::getter(fn bridge_limits)]
+	pub type BridgeLimitsOf<T: Config> = StorageMap<_, Twox64Concat, ResourceId, BridgeLimits, OptionQuery>;
+
+	/// The volume period index and the total amount transferred to the bridge in the period.
+	///
+	/// PeriodVolumes: map ResourceId => (BlockNumber, Balance)
+	#[pallet::storage]
+	#[pallet::getter(fn period_volumes)]
+	pub type PeriodVolumes<T: Config> = StorageMap<_, Twox64Concat, ResourceId, (T::BlockNumber, Balance), ValueQuery>;
+
+	/// The resource ids of which the transfers are paused.
+	///
+	/// PausedResources: map ResourceId => Option<()>
+	#[pallet::storage]
+	#[pallet::getter(fn paused_resources)]
+	pub type PausedResources<T: Config> = StorageMap<_, Twox64Concat, ResourceId, (), OptionQuery>;
+
 	#[pallet::hooks]
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
 
@@ -124,11 +184,70 @@ pub mod module {
 			T::RegistorOrigin::ensure_origin(origin)?;
 			if let Some(currency_id) = CurrencyIds::<T>::take(resource_id) {
 				ResourceIds::<T>::remove(currency_id);
+				BridgeLimitsOf::<T>::remove(resource_id);
+				PeriodVolumes::<T>::remove(resource_id);
+				PausedResources::<T>::remove(resource_id);
 				Self::deposit_event(Event::UnregisterResourceId(resource_id, currency_id));
 			}
 			Ok(().into())
 		}
 
+		/// Set or remove the fee and limits of the transfers of the resource id to the bridge.
+		///
+		/// The dispatch origin of this call must be `RegistorOrigin`.
+		///
+		/// - `resource_id`: the resource id of the bridged asset.
+		/// - `limits`: the fee and limits, `None` to remove them.
+		#[pallet::weight(<T as Config>::WeightInfo::set_bridge_limits())]
+		#[transactional]
+		pub fn set_bridge_limits(
+			origin: OriginFor<T>,
+			resource_id: ResourceId,
+			limits: Option<BridgeLimits>,
+		) -> DispatchResultWithPostInfo {
+			T::RegistorOrigin::ensure_origin(origin)?;
+			ensure!(
+				CurrencyIds::<T>::contains_key(resource_id),
+				Error::<T>::ResourceIdNotRegistered
+			);
+			if let Some(limits) = limits {
+				ensure!(limits.min_amount <= limits.max_amount, Error::<T>::InvalidBridgeLimits);
+			}
+
+			BridgeLimitsOf::<T>::mutate_exists(resource_id, |maybe_limits| *maybe_limits = limits);
+			Self::deposit_event(Event::BridgeLimitsUpdated(resource_id, limits));
+			Ok(().into())
+		}
+
+		/// Pause or unpause the transfers of the resource id, both to and from the bridge.
+		///
+		/// The dispatch origin of this call must be `RegistorOrigin`.
+		///
+		/// - `resource_id`: the resource id of the bridged asset.
+		/// - `paused`: whether the transfers are paused.
+		#[pallet::weight(<T as Config>::WeightInfo::set_resource_paused())]
+		#[transactional]
+		pub fn set_resource_paused(
+			origin: OriginFor<T>,
+			resource_id: ResourceId,
+			paused: bool,
+		) -> DispatchResultWithPostInfo {
+			T::RegistorOrigin::ensure_origin(origin)?;
+			ensure!(
+				CurrencyIds::<T>::contains_key(resource_id),
+				Error::<T>::ResourceIdNotRegistered
+			);
+
+			if paused {
+				PausedResources::<T>::insert(resource_id, ());
+				Self::deposit_event(Event::ResourcePaused(resource_id));
+			} else {
+				PausedResources::<T>::remove(resource_id);
+				Self::deposit_event(Event::ResourceUnpaused(resource_id));
+			}
+			Ok(().into())
+		}
+
 		#[pallet::weight(<T as Config>::WeightInfo::transfer_origin_chain_token_to_bridge(recipient.len() as u32))]
 		#[transactional]
 		pub fn transfer_to_bridge(
@@ -166,6 +285,10 @@ pub mod module {
 		) -> DispatchResultWithPostInfo {
 			let bridge_account_id = T::BridgeOrigin::ensure_origin(origin)?;
 			let currency_id = Self::currency_ids(resource_id).ok_or(Error::<T>::ResourceIdNotRegistered)?;
+			ensure!(
+				!PausedResources::<T>::contains_key(resource_id),
+				Error::<T>::ResourcePaused
+			);
 
 			if Self::is_origin_chain_resource(resource_id) {
 				// transfer locked tokens from bridge account to receiver
@@ -195,6 +318,29 @@ impl<T: Config> Pallet<T> {
 
 		let bridge_account_id = chainbridge::Module::<T>::account_id();
 		let resource_id = Self::resource_ids(currency_id).ok_or(Error::<T>::ResourceIdNotRegistered)?;
+		ensure!(
+			!PausedResources::<T>::contains_key(resource_id),
+			Error::<T>::ResourcePaused
+		);
+
+		if let Some(limits) = Self::bridge_limits(resource_id) {
+			ensure!(amount >= limits.min_amount, Error::<T>::BelowMinimumAmount);
+			ensure!(amount <= limits.max_amount, Error::<T>::ExceedMaximumAmount);
+
+			let period = frame_system::Pallet::<T>::block_number() / T::VolumePeriod::get();
+			let (last_period, volume) = Self::period_volumes(resource_id);
+			let volume = if last_period == period { volume } else { Zero::zero() };
+			let volume = volume
+				.checked_add(amount)
+				.filter(|volume| *volume <= limits.daily_limit)
+				.ok_or(Error::<T>::ExceedDailyLimit)?;
+			PeriodVolumes::<T>::insert(resource_id, (period, volume));
+
+			if !limits.fee.is_zero() {
+				T::Currency::transfer(currency_id, &from, &T::TreasuryAccount::get(), limits.fee)?;
+				Self::deposit_event(Event::TransferFeeCharged(from.clone(), currency_id, limits.fee));
+			}
+		}
 
 		if Self::is_origin_chain_resource(resource_id) {
 			// transfer tokens to bridge account to lock
@@ -32,6 +32,7 @@ pub type BlockNumber = u64;
 pub type AccountId = u128;
 
 pub const ALICE: AccountId = 1;
+pub const TREASURY: AccountId = 3;
 
 mod ecosystem_chainsafe {
 	pub use super::super::*;
@@ -109,12 +110,19 @@ ord_parameter_types! {
 	pub const RegistorOrigin: AccountId = 12;
 }
 
+parameter_types! {
+	pub const TreasuryAccount: AccountId = TREASURY;
+	pub const VolumePeriod: BlockNumber = 100;
+}
+
 impl Config for Runtime {
 	type Event = Event;
 	type Currency = Tokens;
 	type NativeCurrencyId = ACA;
 	type RegistorOrigin = EnsureSignedBy<RegistorOrigin, AccountId>;
 	type BridgeOrigin = chainbridge::EnsureBridge<Runtime>;
+	type TreasuryAccount = TreasuryAccount;
+	type VolumePeriod = VolumePeriod;
 	type WeightInfo = ();
 }
 
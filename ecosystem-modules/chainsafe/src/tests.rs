@@ -238,3 +238,204 @@ fn transfer_from_bridge_work() {
 		assert_eq!(Tokens::free_balance(WETH::get(), &ChainBridge::account_id()), 0);
 	});
 }
+
+fn limits() -> BridgeLimits {
+	BridgeLimits {
+		fee: 5,
+		min_amount: 10,
+		max_amount: 100,
+		daily_limit: 150,
+	}
+}
+
+#[test]
+fn set_bridge_limits_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_noop!(
+			ChainSafeTransfer::set_bridge_limits(Origin::signed(ALICE), ACAResourceId::get(), Some(limits())),
+			BadOrigin,
+		);
+		assert_noop!(
+			ChainSafeTransfer::set_bridge_limits(
+				Origin::signed(RegistorOrigin::get()),
+				ACAResourceId::get(),
+				Some(limits())
+			),
+			Error::<Runtime>::ResourceIdNotRegistered,
+		);
+
+		assert_ok!(ChainSafeTransfer::register_resource_id(
+			Origin::signed(RegistorOrigin::get()),
+			ACAResourceId::get(),
+			ACA::get()
+		));
+		assert_noop!(
+			ChainSafeTransfer::set_bridge_limits(
+				Origin::signed(RegistorOrigin::get()),
+				ACAResourceId::get(),
+				Some(BridgeLimits {
+					min_amount: 101,
+					..limits()
+				})
+			),
+			Error::<Runtime>::InvalidBridgeLimits,
+		);
+
+		assert_ok!(ChainSafeTransfer::set_bridge_limits(
+			Origin::signed(RegistorOrigin::get()),
+			ACAResourceId::get(),
+			Some(limits())
+		));
+		System::assert_last_event(Event::ChainSafeTransfer(crate::Event::BridgeLimitsUpdated(
+			ACAResourceId::get(),
+			Some(limits()),
+		)));
+		assert_eq!(ChainSafeTransfer::bridge_limits(ACAResourceId::get()), Some(limits()));
+
+		assert_ok!(ChainSafeTransfer::set_bridge_limits(
+			Origin::signed(RegistorOrigin::get()),
+			ACAResourceId::get(),
+			None
+		));
+		assert_eq!(ChainSafeTransfer::bridge_limits(ACAResourceId::get()), None);
+	});
+}
+
+#[test]
+fn transfer_to_bridge_with_limits_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let dest_chain_id: chainbridge::ChainId = 0;
+
+		assert_ok!(ChainBridge::whitelist_chain(
+			Origin::signed(AdminOrigin::get()),
+			dest_chain_id
+		));
+		assert_ok!(ChainSafeTransfer::register_resource_id(
+			Origin::signed(RegistorOrigin::get()),
+			ACAResourceId::get(),
+			ACA::get()
+		));
+		assert_ok!(ChainSafeTransfer::set_bridge_limits(
+			Origin::signed(RegistorOrigin::get()),
+			ACAResourceId::get(),
+			Some(limits())
+		));
+
+		assert_noop!(
+			ChainSafeTransfer::do_transfer_to_bridge(&ALICE, ACA::get(), dest_chain_id, vec![1], 9),
+			Error::<Runtime>::BelowMinimumAmount,
+		);
+		assert_noop!(
+			ChainSafeTransfer::do_transfer_to_bridge(&ALICE, ACA::get(), dest_chain_id, vec![1], 101),
+			Error::<Runtime>::ExceedMaximumAmount,
+		);
+
+		assert_ok!(ChainSafeTransfer::do_transfer_to_bridge(
+			&ALICE,
+			ACA::get(),
+			dest_chain_id,
+			vec![1],
+			100
+		));
+		System::assert_has_event(Event::ChainSafeTransfer(crate::Event::TransferFeeCharged(
+			ALICE,
+			ACA::get(),
+			5,
+		)));
+		assert_eq!(Tokens::free_balance(ACA::get(), &ALICE), 895);
+		assert_eq!(Tokens::free_balance(ACA::get(), &TREASURY), 5);
+		assert_eq!(Tokens::free_balance(ACA::get(), &ChainBridge::account_id()), 100);
+		assert_eq!(ChainSafeTransfer::period_volumes(ACAResourceId::get()), (0, 100));
+
+		assert_noop!(
+			ChainSafeTransfer::do_transfer_to_bridge(&ALICE, ACA::get(), dest_chain_id, vec![1], 51),
+			Error::<Runtime>::ExceedDailyLimit,
+		);
+		assert_ok!(ChainSafeTransfer::do_transfer_to_bridge(
+			&ALICE,
+			ACA::get(),
+			dest_chain_id,
+			vec![1],
+			50
+		));
+		assert_eq!(ChainSafeTransfer::period_volumes(ACAResourceId::get()), (0, 150));
+
+		// the volume is reset in the next period
+		System::set_block_number(100);
+		assert_ok!(ChainSafeTransfer::do_transfer_to_bridge(
+			&ALICE,
+			ACA::get(),
+			dest_chain_id,
+			vec![1],
+			100
+		));
+		assert_eq!(ChainSafeTransfer::period_volumes(ACAResourceId::get()), (1, 100));
+		assert_eq!(Tokens::free_balance(ACA::get(), &ALICE), 735);
+		assert_eq!(Tokens::free_balance(ACA::get(), &TREASURY), 15);
+	});
+}
+
+#[test]
+fn set_resource_paused_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let dest_chain_id: chainbridge::ChainId = 0;
+
+		assert_ok!(ChainBridge::whitelist_chain(
+			Origin::signed(AdminOrigin::get()),
+			dest_chain_id
+		));
+		assert_ok!(ChainSafeTransfer::register_resource_id(
+			Origin::signed(RegistorOrigin::get()),
+			ACAResourceId::get(),
+			ACA::get()
+		));
+		assert_ok!(Tokens::deposit(ACA::get(), &ChainBridge::account_id(), 1000));
+
+		assert_noop!(
+			ChainSafeTransfer::set_resource_paused(Origin::signed(ALICE), ACAResourceId::get(), true),
+			BadOrigin,
+		);
+		assert_ok!(ChainSafeTransfer::set_resource_paused(
+			Origin::signed(RegistorOrigin::get()),
+			ACAResourceId::get(),
+			true
+		));
+		System::assert_last_event(Event::ChainSafeTransfer(crate::Event::ResourcePaused(
+			ACAResourceId::get(),
+		)));
+
+		assert_noop!(
+			ChainSafeTransfer::do_transfer_to_bridge(&ALICE, ACA::get(), dest_chain_id, vec![1], 10),
+			Error::<Runtime>::ResourcePaused,
+		);
+		assert_noop!(
+			ChainSafeTransfer::transfer_from_bridge(
+				Origin::signed(ChainBridge::account_id()),
+				ALICE,
+				500,
+				ACAResourceId::get()
+			),
+			Error::<Runtime>::ResourcePaused,
+		);
+
+		assert_ok!(ChainSafeTransfer::set_resource_paused(
+			Origin::signed(RegistorOrigin::get()),
+			ACAResourceId::get(),
+			false
+		));
+		System::assert_last_event(Event::ChainSafeTransfer(crate::Event::ResourceUnpaused(
+			ACAResourceId::get(),
+		)));
+		assert_ok!(ChainSafeTransfer::do_transfer_to_bridge(
+			&ALICE,
+			ACA::get(),
+			dest_chain_id,
+			vec![1],
+			10
+		));
+	});
+}
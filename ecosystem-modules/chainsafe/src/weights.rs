@@ -54,6 +54,8 @@ pub trait WeightInfo {
 	fn transfer_native_to_bridge(b: u32, ) -> Weight;
 	fn transfer_origin_chain_token_from_bridge() -> Weight;
 	fn transfer_other_chain_token_from_bridge() -> Weight;
+	fn set_bridge_limits() -> Weight;
+	fn set_resource_paused() -> Weight;
 }
 
 /// Weights for ecosystem_chainsafe using the Acala node and recommended hardware.
@@ -101,6 +103,16 @@ impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
             .saturating_add(T::DbWeight::get().reads(4 as Weight))
             .saturating_add(T::DbWeight::get().writes(3 as Weight))
 	}
+	fn set_bridge_limits() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_resource_paused() -> Weight {
+		(23_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 }
 
 // For backwards compatibility and tests
@@ -147,4 +159,14 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(4 as Weight))
             .saturating_add(RocksDbWeight::get().writes(3 as Weight))
 	}
+	fn set_bridge_limits() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_resource_paused() -> Weight {
+		(23_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
 }
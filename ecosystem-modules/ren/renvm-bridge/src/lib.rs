@@ -23,27 +23,30 @@ use codec::Encode;
 use frame_support::{
 	ensure,
 	pallet_prelude::*,
-	traits::{Currency, Get},
+	traits::{Currency, EnsureOrigin, Get},
+	transactional,
 };
 use frame_system::{ensure_none, ensure_signed, pallet_prelude::*};
 use orml_traits::BasicCurrency;
-use primitives::Balance;
+use primitives::{Balance, CurrencyId};
 use sp_core::ecdsa;
 use sp_io::{crypto::secp256k1_ecdsa_recover, hashing::keccak_256};
 use sp_runtime::{
-	traits::Zero,
+	traits::{One, Saturating, Zero},
 	transaction_validity::{
 		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity, ValidTransaction,
 	},
-	ArithmeticError, DispatchResult,
+	ArithmeticError, DispatchResult, Permill,
 };
 use sp_std::vec::Vec;
-use support::TransactionPayment;
+use support::{MintGuard, TransactionPayment};
 
 mod mock;
 mod tests;
+pub mod weights;
 
 pub use module::*;
+pub use weights::WeightInfo;
 
 type EcdsaSignature = ecdsa::Signature;
 type PublicKey = [u8; 20];
@@ -64,6 +67,11 @@ pub mod module {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 		type Currency: Currency<Self::AccountId>;
 		type BridgedTokenCurrency: BasicCurrency<Self::AccountId, Balance = Balance>;
+		/// The currency id of the bridged token, checked by `MintGuard`.
+		#[pallet::constant]
+		type BridgedTokenCurrencyId: Get<CurrencyId>;
+		/// The guard rejecting the mints while they are paused, e.g. the RenVM reserve falls short.
+		type MintGuard: MintGuard;
 		/// The RenVM Currency identifier
 		#[pallet::constant]
 		type CurrencyIdentifier: Get<[u8; 32]>;
@@ -75,6 +83,20 @@ pub mod module {
 		type UnsignedPriority: Get<TransactionPriority>;
 		/// Charge mint fee.
 		type ChargeTransactionPayment: TransactionPayment<Self::AccountId, BalanceOf<Self>, NegativeImbalanceOf<Self>>;
+		/// The origin which may set the bridge fees and the mint volume cap.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+		/// The account receiving the mint and burn fees.
+		#[pallet::constant]
+		type TreasuryAccount: Get<Self::AccountId>;
+		/// The number of blocks the previous RenVM public key keeps validating mint
+		/// signatures after a key rotation.
+		#[pallet::constant]
+		type KeyRotationOverlap: Get<Self::BlockNumber>;
+		/// The length of an epoch of the mint volume cap, in blocks.
+		#[pallet::constant]
+		type EpochLength: Get<Self::BlockNumber>;
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::error]
@@ -85,6 +107,8 @@ pub mod module {
 		InvalidMintSignature,
 		/// The mint signature has already been used.
 		SignatureAlreadyUsed,
+		/// The mint exceeds the mint volume cap of this epoch.
+		ExceedMintVolumeCap,
 	}
 
 	#[pallet::event]
@@ -97,6 +121,12 @@ pub mod module {
 		Burnt(T::AccountId, DestAddress, Balance),
 		/// Rotated key \[new_key\]
 		RotatedKey(PublicKey),
+		/// Bridge fee collected to treasury \[payer, fee\]
+		FeeCollected(T::AccountId, Balance),
+		/// Bridge fees updated \[mint_fee, burn_fee\]
+		FeesUpdated(Permill, Permill),
+		/// Mint volume cap of each epoch updated \[cap\]
+		MintVolumeCapUpdated(Option<Balance>),
 	}
 
 	/// The RenVM split public key
@@ -104,6 +134,12 @@ pub mod module {
 	#[pallet::getter(fn ren_vm_public_key)]
 	pub type RenVmPublicKey<T: Config> = StorageValue<_, PublicKey, OptionQuery>;
 
+	/// The previous RenVM split public key and the block number until which it is still
+	/// accepted for mint signatures.
+	#[pallet::storage]
+	#[pallet::getter(fn previous_ren_vm_public_key)]
+	pub type PreviousRenVmPublicKey<T: Config> = StorageValue<_, (PublicKey, T::BlockNumber), OptionQuery>;
+
 	/// Signature blacklist. This is required to prevent double claim.
 	#[pallet::storage]
 	#[pallet::getter(fn signatures)]
@@ -119,6 +155,26 @@ pub mod module {
 	#[pallet::getter(fn next_burn_event_id)]
 	type NextBurnEventId<T: Config> = StorageValue<_, u32, ValueQuery>;
 
+	/// The fee rate charged on mint
+	#[pallet::storage]
+	#[pallet::getter(fn mint_fee)]
+	pub type MintFee<T: Config> = StorageValue<_, Permill, ValueQuery>;
+
+	/// The fee rate charged on burn
+	#[pallet::storage]
+	#[pallet::getter(fn burn_fee)]
+	pub type BurnFee<T: Config> = StorageValue<_, Permill, ValueQuery>;
+
+	/// The cap of the amount minted in each epoch, `None` if uncapped
+	#[pallet::storage]
+	#[pallet::getter(fn mint_volume_cap)]
+	pub type MintVolumeCap<T: Config> = StorageValue<_, Balance, OptionQuery>;
+
+	/// The current epoch of the mint volume cap and the amount minted in it
+	#[pallet::storage]
+	#[pallet::getter(fn epoch_mint_volume)]
+	pub type EpochMintVolume<T: Config> = StorageValue<_, (T::BlockNumber, Balance), ValueQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig {
 		pub ren_vm_public_key: PublicKey,
@@ -170,7 +226,8 @@ pub mod module {
 		/// The dispatch origin of this call must be _None_.
 		///
 		/// Verify input by `validate_unsigned`
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::mint())]
+		#[transactional]
 		pub fn mint(
 			origin: OriginFor<T>,
 			who: T::AccountId,
@@ -180,27 +237,26 @@ pub mod module {
 			sig: EcdsaSignature,
 		) -> DispatchResultWithPostInfo {
 			ensure_none(origin)?;
-			Self::do_mint(&who, amount, &sig)?;
-
-			// TODO: update by benchmarks.
-			let weight: Weight = 10_000;
+			let minted = Self::do_mint(&who, amount, &sig)?;
 
 			// charge mint fee. Ignore the result, if it failed, only lost the fee.
 			let _ = T::ChargeTransactionPayment::charge_fee(
 				&who,
 				MINT_TX_LENGTH,
-				weight,
+				T::WeightInfo::mint(),
 				Zero::zero(),
 				Pays::Yes,
 				DispatchClass::Normal,
 			);
-			Self::deposit_event(Event::Minted(who, amount));
+			Self::deposit_event(Event::Minted(who, minted));
 
 			Ok(().into())
 		}
 
-		/// Allow a user to burn assets.
-		#[pallet::weight(10_000)]
+		/// Allow a user to burn assets, the burn fee is deducted from `amount` and only the rest
+		/// is released by RenVM.
+		#[pallet::weight(T::WeightInfo::burn())]
+		#[transactional]
 		pub fn burn(
 			origin: OriginFor<T>,
 			to: DestAddress,
@@ -213,8 +269,10 @@ pub mod module {
 				*id = id.checked_add(1).ok_or(ArithmeticError::Overflow)?;
 
 				T::BridgedTokenCurrency::withdraw(&sender, amount)?;
-				BurnEvents::<T>::insert(this_id, (frame_system::Pallet::<T>::block_number(), &to, amount));
-				Self::deposit_event(Event::Burnt(sender, to, amount));
+				let fee = Self::collect_fee(&sender, Self::burn_fee() * amount)?;
+				let released = amount.saturating_sub(fee);
+				BurnEvents::<T>::insert(this_id, (frame_system::Pallet::<T>::block_number(), &to, released));
+				Self::deposit_event(Event::Burnt(sender, to, released));
 
 				Ok(())
 			})?;
@@ -227,7 +285,7 @@ pub mod module {
 		/// The dispatch origin of this call must be _None_.
 		///
 		/// Verify input by `validate_unsigned`
-		#[pallet::weight(10_000)]
+		#[pallet::weight(T::WeightInfo::rotate_key())]
 		pub fn rotate_key(origin: OriginFor<T>, new_key: PublicKey, sig: EcdsaSignature) -> DispatchResultWithPostInfo {
 			ensure_none(origin)?;
 			Self::do_rotate_key(new_key, sig);
@@ -235,22 +293,83 @@ pub mod module {
 
 			Ok(().into())
 		}
+
+		/// Set the fee rates charged on mint and burn.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight(T::WeightInfo::set_fees())]
+		pub fn set_fees(origin: OriginFor<T>, mint_fee: Permill, burn_fee: Permill) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			MintFee::<T>::put(mint_fee);
+			BurnFee::<T>::put(burn_fee);
+			Self::deposit_event(Event::FeesUpdated(mint_fee, burn_fee));
+
+			Ok(().into())
+		}
+
+		/// Set the cap of the amount minted in each epoch, `None` to remove the cap.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight(T::WeightInfo::set_mint_volume_cap())]
+		pub fn set_mint_volume_cap(origin: OriginFor<T>, cap: Option<Balance>) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			MintVolumeCap::<T>::set(cap);
+			Self::deposit_event(Event::MintVolumeCapUpdated(cap));
+
+			Ok(().into())
+		}
 	}
 }
 
 impl<T: Config> Pallet<T> {
-	fn do_mint(sender: &T::AccountId, amount: Balance, sig: &EcdsaSignature) -> DispatchResult {
-		T::BridgedTokenCurrency::deposit(sender, amount)?;
+	fn do_mint(sender: &T::AccountId, amount: Balance, sig: &EcdsaSignature) -> Result<Balance, DispatchError> {
+		T::MintGuard::ensure_can_mint(T::BridgedTokenCurrencyId::get())?;
+		let epoch_mint_volume = Self::next_epoch_mint_volume(amount).ok_or(Error::<T>::ExceedMintVolumeCap)?;
+		EpochMintVolume::<T>::put(epoch_mint_volume);
+
+		let fee = Self::mint_fee() * amount;
+		let minted = amount.saturating_sub(fee);
+		T::BridgedTokenCurrency::deposit(sender, minted)?;
+		Self::collect_fee(sender, fee)?;
 		Signatures::<T>::insert(sig, ());
 
-		Ok(())
+		Ok(minted)
 	}
 
 	fn do_rotate_key(new_key: PublicKey, sig: EcdsaSignature) {
+		if let Some(old_key) = RenVmPublicKey::<T>::get() {
+			let expiry = frame_system::Pallet::<T>::block_number().saturating_add(T::KeyRotationOverlap::get());
+			PreviousRenVmPublicKey::<T>::put((old_key, expiry));
+		}
 		RenVmPublicKey::<T>::set(Some(new_key));
 		Signatures::<T>::insert(&sig, ());
 	}
 
+	/// Deposit the bridge fee paid by `payer` to the treasury, return the fee.
+	fn collect_fee(payer: &T::AccountId, fee: Balance) -> Result<Balance, DispatchError> {
+		if !fee.is_zero() {
+			T::BridgedTokenCurrency::deposit(&T::TreasuryAccount::get(), fee)?;
+			Self::deposit_event(Event::FeeCollected(payer.clone(), fee));
+		}
+
+		Ok(fee)
+	}
+
+	/// The epoch and its mint volume after minting `amount`, `None` if it exceeds the mint
+	/// volume cap.
+	fn next_epoch_mint_volume(amount: Balance) -> Option<(T::BlockNumber, Balance)> {
+		let now = frame_system::Pallet::<T>::block_number();
+		let epoch = now / T::EpochLength::get().max(One::one());
+		let (current_epoch, volume) = Self::epoch_mint_volume();
+		let volume = if current_epoch == epoch { volume } else { Zero::zero() };
+		let volume = volume.checked_add(amount)?;
+
+		match Self::mint_volume_cap() {
+			Some(cap) if volume > cap => None,
+			_ => Some((epoch, volume)),
+		}
+	}
+
 	// ABI-encode the values for creating the signature hash.
 	fn signable_mint_message(
 		p_hash: &[u8; 32],
@@ -293,7 +412,15 @@ impl<T: Config> Pallet<T> {
 		let addr = &keccak_256(&recoverd)[12..];
 
 		let pubkey = RenVmPublicKey::<T>::get().ok_or(Error::<T>::InvalidRenVmPublicKey)?;
-		ensure!(addr == pubkey, Error::<T>::InvalidMintSignature);
+		if addr == pubkey {
+			return Ok(());
+		}
+
+		// the previous key is still accepted in the overlap window after a key rotation
+		let now = frame_system::Pallet::<T>::block_number();
+		let is_previous_key = PreviousRenVmPublicKey::<T>::get()
+			.map_or(false, |(previous_key, expiry)| now < expiry && addr == previous_key);
+		ensure!(is_previous_key, Error::<T>::InvalidMintSignature);
 
 		Ok(())
 	}
@@ -341,6 +468,16 @@ impl<T: Config> frame_support::unsigned::ValidateUnsigned for Pallet<T> {
 					return InvalidTransaction::BadProof.into();
 				}
 
+				// check the mint volume cap
+				if Self::next_epoch_mint_volume(*amount).is_none() {
+					return InvalidTransaction::ExhaustsResources.into();
+				}
+
+				// check if the mints are paused
+				if T::MintGuard::ensure_can_mint(T::BridgedTokenCurrencyId::get()).is_err() {
+					return InvalidTransaction::Call.into();
+				}
+
 				ValidTransaction::with_tag_prefix("renvm-bridge")
 					.priority(T::UnsignedPriority::get())
 					.and_provides(sig)
@@ -21,16 +21,21 @@
 #![cfg(test)]
 
 use super::*;
-use frame_support::parameter_types;
+use frame_support::{ord_parameter_types, parameter_types};
+use frame_system::EnsureSignedBy;
 use orml_currencies::BasicCurrencyAdapter;
 use orml_traits::parameter_type_with_key;
 use primitives::{Amount, CurrencyId, TokenSymbol};
 use sp_core::H256;
-use sp_runtime::{testing::Header, traits::IdentityLookup, Perbill};
+use sp_runtime::{testing::Header, traits::IdentityLookup, DispatchError, Perbill};
+use std::cell::RefCell;
 
 pub type AccountId = H256;
 pub type BlockNumber = u64;
 
+pub const TREASURY: AccountId = H256([1u8; 32]);
+pub const ADMIN: AccountId = H256([2u8; 32]);
+
 mod renvm {
 	pub use super::super::*;
 }
@@ -110,6 +115,7 @@ impl orml_tokens::Config for Runtime {
 
 parameter_types! {
 	pub const GetNativeCurrencyId: CurrencyId = CurrencyId::Token(TokenSymbol::ACA);
+	pub const RENBTCCurrencyId: CurrencyId = CurrencyId::Token(TokenSymbol::RENBTC);
 }
 
 impl orml_currencies::Config for Runtime {
@@ -120,13 +126,44 @@ impl orml_currencies::Config for Runtime {
 	type WeightInfo = ();
 }
 
+ord_parameter_types! {
+	pub const Admin: AccountId = ADMIN;
+}
+
+parameter_types! {
+	pub const TreasuryAccount: AccountId = TREASURY;
+	pub const KeyRotationOverlap: BlockNumber = 10;
+	pub const EpochLength: BlockNumber = 100;
+}
+
+thread_local! {
+	pub static MINTS_PAUSED: RefCell<bool> = RefCell::new(false);
+}
+
+pub struct MockMintGuard;
+impl MintGuard for MockMintGuard {
+	fn ensure_can_mint(currency_id: CurrencyId) -> DispatchResult {
+		if currency_id == RENBTCCurrencyId::get() && MINTS_PAUSED.with(|v| *v.borrow()) {
+			return Err(DispatchError::Other("mints paused"));
+		}
+		Ok(())
+	}
+}
+
 impl Config for Runtime {
 	type Event = Event;
 	type Currency = Balances;
 	type BridgedTokenCurrency = BasicCurrencyAdapter<Runtime, Balances, i128, BlockNumber>;
+	type BridgedTokenCurrencyId = RENBTCCurrencyId;
+	type MintGuard = MockMintGuard;
 	type CurrencyIdentifier = RENBTCIdentifier;
 	type UnsignedPriority = UnsignedPriority;
 	type ChargeTransactionPayment = ();
+	type UpdateOrigin = EnsureSignedBy<Admin, AccountId>;
+	type TreasuryAccount = TreasuryAccount;
+	type KeyRotationOverlap = KeyRotationOverlap;
+	type EpochLength = EpochLength;
+	type WeightInfo = ();
 }
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
@@ -166,6 +203,8 @@ impl ExtBuilder {
 		.assimilate_storage::<Runtime>(&mut t)
 		.unwrap();
 
+		MINTS_PAUSED.with(|v| *v.borrow_mut() = false);
+
 		t.into()
 	}
 }
@@ -23,9 +23,9 @@
 use super::*;
 use frame_support::{assert_noop, assert_ok, unsigned::ValidateUnsigned};
 use hex_literal::hex;
-use mock::{AccountId, Balances, ExtBuilder, Origin, RenVmBridge, Runtime, System};
+use mock::{AccountId, Balances, ExtBuilder, Origin, RenVmBridge, Runtime, System, ADMIN, MINTS_PAUSED, TREASURY};
 use sp_core::H256;
-use sp_runtime::transaction_validity::TransactionValidityError;
+use sp_runtime::{traits::BadOrigin, transaction_validity::TransactionValidityError, DispatchError};
 
 fn mint_ren_btc(
 	who: AccountId,
@@ -190,6 +190,50 @@ fn mint_works() {
 	});
 }
 
+#[test]
+fn mint_is_rejected_while_mints_paused() {
+	ExtBuilder::default().build().execute_with(|| {
+		let to: H256 = hex!["d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"].into();
+
+		MINTS_PAUSED.with(|v| *v.borrow_mut() = true);
+		assert_noop!(
+			mint_ren_btc(
+				to.clone(),
+				hex!["67028f26328144de6ef80b8cd3b05e0cefb488762c340d1574c0542f752996cb"],
+				93963,
+				hex!["f6a75cc370a2dda6dfc8d016529766bb6099d7fa0d787d9fe5d3a7e60c9ac2a0"],
+				EcdsaSignature::from_slice(&hex!["defda6eef01da2e2a90ce30ba73e90d32204ae84cae782b485f01d16b69061e0381a69cafed3deb6112af044c42ed0f7c73ee0eec7b533334d31a06db50fc40e1b"]),
+			),
+			TransactionValidityError::Invalid(InvalidTransaction::Call)
+		);
+		assert_noop!(
+			RenVmBridge::mint(
+				Origin::none(),
+				to.clone(),
+				hex!["67028f26328144de6ef80b8cd3b05e0cefb488762c340d1574c0542f752996cb"],
+				93963,
+				hex!["f6a75cc370a2dda6dfc8d016529766bb6099d7fa0d787d9fe5d3a7e60c9ac2a0"],
+				EcdsaSignature::from_slice(&hex!["defda6eef01da2e2a90ce30ba73e90d32204ae84cae782b485f01d16b69061e0381a69cafed3deb6112af044c42ed0f7c73ee0eec7b533334d31a06db50fc40e1b"]),
+			),
+			DispatchError::Other("mints paused")
+		);
+		assert_eq!(Balances::free_balance(to.clone()), 0);
+
+		// the signature isn't used, the mint goes through once resumed.
+		MINTS_PAUSED.with(|v| *v.borrow_mut() = false);
+		assert_ok!(
+			mint_ren_btc(
+				to.clone(),
+				hex!["67028f26328144de6ef80b8cd3b05e0cefb488762c340d1574c0542f752996cb"],
+				93963,
+				hex!["f6a75cc370a2dda6dfc8d016529766bb6099d7fa0d787d9fe5d3a7e60c9ac2a0"],
+				EcdsaSignature::from_slice(&hex!["defda6eef01da2e2a90ce30ba73e90d32204ae84cae782b485f01d16b69061e0381a69cafed3deb6112af044c42ed0f7c73ee0eec7b533334d31a06db50fc40e1b"]),
+			)
+		);
+		assert_eq!(Balances::free_balance(to.clone()), 93963);
+	});
+}
+
 #[test]
 fn rotate_key_works() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -205,6 +249,141 @@ fn rotate_key_works() {
 	});
 }
 
+#[test]
+fn mint_and_burn_fees_go_to_treasury() {
+	ExtBuilder::default().build().execute_with(|| {
+		let to: H256 = hex!["d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"].into();
+
+		assert_noop!(
+			RenVmBridge::set_fees(Origin::signed(to.clone()), Permill::from_percent(10), Permill::from_percent(20)),
+			BadOrigin
+		);
+		assert_ok!(RenVmBridge::set_fees(
+			Origin::signed(ADMIN),
+			Permill::from_percent(10),
+			Permill::from_percent(20)
+		));
+		assert_eq!(RenVmBridge::mint_fee(), Permill::from_percent(10));
+		assert_eq!(RenVmBridge::burn_fee(), Permill::from_percent(20));
+
+		assert_ok!(
+			mint_ren_btc(
+				to.clone(),
+				hex!["67028f26328144de6ef80b8cd3b05e0cefb488762c340d1574c0542f752996cb"],
+				93963,
+				hex!["f6a75cc370a2dda6dfc8d016529766bb6099d7fa0d787d9fe5d3a7e60c9ac2a0"],
+				EcdsaSignature::from_slice(&hex!["defda6eef01da2e2a90ce30ba73e90d32204ae84cae782b485f01d16b69061e0381a69cafed3deb6112af044c42ed0f7c73ee0eec7b533334d31a06db50fc40e1b"]),
+			)
+		);
+		assert_eq!(Balances::free_balance(&to), 84567);
+		assert_eq!(Balances::free_balance(&TREASURY), 9396);
+
+		let dest: Vec<u8> = vec![2, 3, 4];
+		assert_ok!(RenVmBridge::burn(Origin::signed(to.clone()), dest.clone(), 1000));
+		assert_eq!(Balances::free_balance(&to), 83567);
+		assert_eq!(Balances::free_balance(&TREASURY), 9596);
+		assert_eq!(RenVmBridge::burn_events(0), Some((0, dest, 800)));
+	});
+}
+
+#[test]
+fn previous_key_validates_mint_in_overlap_window() {
+	ExtBuilder::default().build().execute_with(|| {
+		let to: H256 = hex!["d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"].into();
+		let old_key: PublicKey = hex!["4b939fc8ade87cb50b78987b1dda927460dc456a"];
+		let new_key: PublicKey = [1u8; 20];
+
+		System::set_block_number(1);
+		RenVmBridge::do_rotate_key(new_key, EcdsaSignature::from_slice(&[1u8; 65]));
+		assert_eq!(RenVmBridge::ren_vm_public_key(), Some(new_key));
+		assert_eq!(RenVmBridge::previous_ren_vm_public_key(), Some((old_key, 11)));
+
+		assert_ok!(
+			mint_ren_btc(
+				to.clone(),
+				hex!["67028f26328144de6ef80b8cd3b05e0cefb488762c340d1574c0542f752996cb"],
+				93963,
+				hex!["f6a75cc370a2dda6dfc8d016529766bb6099d7fa0d787d9fe5d3a7e60c9ac2a0"],
+				EcdsaSignature::from_slice(&hex!["defda6eef01da2e2a90ce30ba73e90d32204ae84cae782b485f01d16b69061e0381a69cafed3deb6112af044c42ed0f7c73ee0eec7b533334d31a06db50fc40e1b"]),
+			)
+		);
+		assert_eq!(Balances::free_balance(&to), 93963);
+
+		System::set_block_number(11);
+		assert_noop!(
+			mint_ren_btc(
+				to.clone(),
+				hex!["425673f98610064b76dbd334783f45ea192f0e954db75ba2ae6b6058a8143d67"],
+				87266,
+				hex!["fe125f912d2de05e3e34b96a0ce8a8e35d9ed883e830b978871f3e1f5d393726"],
+				EcdsaSignature::from_slice(&hex!["acd463fa396c54995e444234e96d793d3977e75f445da219c10bc4947c22622f325f24dfc31e8e56ec21f04fc7669e91db861778a8367444bde6dfb5f95e15ed1b"]),
+			),
+			TransactionValidityError::Invalid(InvalidTransaction::BadProof)
+		);
+	});
+}
+
+#[test]
+fn mint_volume_cap_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let to: H256 = hex!["d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"].into();
+
+		assert_noop!(
+			RenVmBridge::set_mint_volume_cap(Origin::signed(to.clone()), Some(100000)),
+			BadOrigin
+		);
+		assert_ok!(RenVmBridge::set_mint_volume_cap(Origin::signed(ADMIN), Some(100000)));
+		assert_eq!(RenVmBridge::mint_volume_cap(), Some(100000));
+
+		assert_ok!(
+			mint_ren_btc(
+				to.clone(),
+				hex!["67028f26328144de6ef80b8cd3b05e0cefb488762c340d1574c0542f752996cb"],
+				93963,
+				hex!["f6a75cc370a2dda6dfc8d016529766bb6099d7fa0d787d9fe5d3a7e60c9ac2a0"],
+				EcdsaSignature::from_slice(&hex!["defda6eef01da2e2a90ce30ba73e90d32204ae84cae782b485f01d16b69061e0381a69cafed3deb6112af044c42ed0f7c73ee0eec7b533334d31a06db50fc40e1b"]),
+			)
+		);
+		assert_eq!(RenVmBridge::epoch_mint_volume(), (0, 93963));
+
+		assert_noop!(
+			mint_ren_btc(
+				to.clone(),
+				hex!["425673f98610064b76dbd334783f45ea192f0e954db75ba2ae6b6058a8143d67"],
+				87266,
+				hex!["fe125f912d2de05e3e34b96a0ce8a8e35d9ed883e830b978871f3e1f5d393726"],
+				EcdsaSignature::from_slice(&hex!["acd463fa396c54995e444234e96d793d3977e75f445da219c10bc4947c22622f325f24dfc31e8e56ec21f04fc7669e91db861778a8367444bde6dfb5f95e15ed1b"]),
+			),
+			TransactionValidityError::Invalid(InvalidTransaction::ExhaustsResources)
+		);
+		assert_noop!(
+			RenVmBridge::mint(
+				Origin::none(),
+				to.clone(),
+				hex!["425673f98610064b76dbd334783f45ea192f0e954db75ba2ae6b6058a8143d67"],
+				87266,
+				hex!["fe125f912d2de05e3e34b96a0ce8a8e35d9ed883e830b978871f3e1f5d393726"],
+				EcdsaSignature::from_slice(&hex!["acd463fa396c54995e444234e96d793d3977e75f445da219c10bc4947c22622f325f24dfc31e8e56ec21f04fc7669e91db861778a8367444bde6dfb5f95e15ed1b"]),
+			),
+			Error::<Runtime>::ExceedMintVolumeCap
+		);
+
+		// the volume is reset in the next epoch
+		System::set_block_number(100);
+		assert_ok!(
+			mint_ren_btc(
+				to.clone(),
+				hex!["425673f98610064b76dbd334783f45ea192f0e954db75ba2ae6b6058a8143d67"],
+				87266,
+				hex!["fe125f912d2de05e3e34b96a0ce8a8e35d9ed883e830b978871f3e1f5d393726"],
+				EcdsaSignature::from_slice(&hex!["acd463fa396c54995e444234e96d793d3977e75f445da219c10bc4947c22622f325f24dfc31e8e56ec21f04fc7669e91db861778a8367444bde6dfb5f95e15ed1b"]),
+			)
+		);
+		assert_eq!(RenVmBridge::epoch_mint_volume(), (1, 87266));
+		assert_eq!(Balances::free_balance(&to), 93963 + 87266);
+	});
+}
+
 #[test]
 fn transaction_length_of_mint() {
 	ExtBuilder::default().build().execute_with(|| {
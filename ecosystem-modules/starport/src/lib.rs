@@ -32,12 +32,19 @@
 //!
 //! * Receive Notices from Compound chain: Receive, verify and execute "Notices", or actionable
 //!   requests from the Compound chain.
+//!
+//! * Risk controls: The update origin can cap the upload and download volumes of an asset per
+//!   `VolumePeriod`, in total and per account, and register the maximum supply cap the Notices can
+//!   set. The downloads of large amounts are delayed by `WithdrawalDelay` and claimed afterwards,
+//!   the update origin can cancel them in the meantime.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 
 mod mock;
 mod tests;
+pub mod weights;
+
 use frame_support::{
 	pallet_prelude::*, require_transactional, transactional, weights::PostDispatchInfo, BoundedVec, PalletId,
 };
@@ -47,14 +54,16 @@ use orml_traits::MultiCurrency;
 use primitives::{Balance, CashYieldIndex, CurrencyId, Moment, TokenSymbol};
 use sp_core::H256;
 use sp_runtime::{
-	traits::{AccountIdConversion, BlakeTwo256, Hash},
+	traits::{AccountIdConversion, BlakeTwo256, Hash, Saturating},
 	AccountId32, Perbill,
 };
 use sp_std::{convert::TryFrom, prelude::*};
 
 pub use module::*;
+pub use weights::WeightInfo;
 
 pub type CompoundAuthoritySignature = AccountId32;
+pub type WithdrawalId = u64;
 
 #[frame_support::pallet]
 pub mod module {
@@ -85,6 +94,21 @@ pub mod module {
 		/// The percentage threshold of authorities signatures required for Notices to take effect.
 		#[pallet::constant]
 		type PercentThresholdForAuthoritySignature: Get<Perbill>;
+
+		/// The origin which may update the Gateway limits and the supply cap registry, and cancel
+		/// the pending withdrawals.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The period of the upload and download volume caps.
+		#[pallet::constant]
+		type VolumePeriod: Get<Self::BlockNumber>;
+
+		/// The delay of the downloads of large amounts.
+		#[pallet::constant]
+		type WithdrawalDelay: Get<Self::BlockNumber>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::error]
@@ -106,11 +130,23 @@ pub mod module {
 		ExceededMaxNumberOfAuthorities,
 		/// Authorities cannot be empty
 		AuthoritiesListCannotBeEmpty,
+		/// The Notice sets a supply cap above the registered maximum.
+		ExceededMaxSupplyCap,
+
+		/// Gateway limits
+		/// The upload volume of the asset or the account exceeds the cap of the period.
+		ExceededUploadVolumeCap,
+		/// The download volume of the asset or the account exceeds the cap of the period.
+		ExceededDownloadVolumeCap,
+		/// The pending withdrawal does not exist.
+		WithdrawalNotFound,
+		/// The pending withdrawal is still delayed.
+		WithdrawalStillDelayed,
 	}
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
-	#[pallet::metadata(Balance = "Balance", T::AccountId = "AccountId")]
+	#[pallet::metadata(Balance = "Balance", T::AccountId = "AccountId", T::BlockNumber = "BlockNumber")]
 	pub enum Event<T: Config> {
 		/// User has locked some asset and uploaded them into Compound. [currency_id, amount, user]
 		AssetLockedTo(CurrencyId, Balance, T::AccountId),
@@ -127,6 +163,80 @@ pub mod module {
 
 		/// The future yield for CASH is set. [yield, yield_index, timestamp]
 		FutureYieldSet(Balance, CashYieldIndex, Moment),
+
+		/// The Gateway limits for an asset have been updated. [currency_id, limits]
+		GatewayLimitsSet(CurrencyId, Option<GatewayLimits>),
+
+		/// The maximum supply cap for an asset has been updated. [currency_id, max_cap]
+		MaxSupplyCapSet(CurrencyId, Option<Balance>),
+
+		/// The download of a large amount is delayed. [withdrawal_id, currency_id, amount, user,
+		/// unlock_at]
+		WithdrawalQueued(WithdrawalId, CurrencyId, Balance, T::AccountId, T::BlockNumber),
+
+		/// The pending withdrawal has been cancelled. [withdrawal_id]
+		WithdrawalCancelled(WithdrawalId),
+	}
+
+	/// The limits of the uploads and downloads of an asset via the Gateway.
+	#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+	pub struct GatewayLimits {
+		/// The maximum amount uploaded per `VolumePeriod`.
+		pub upload_cap: Balance,
+		/// The maximum amount downloaded per `VolumePeriod`.
+		pub download_cap: Balance,
+		/// The maximum amount uploaded by an account per `VolumePeriod`.
+		pub account_upload_cap: Balance,
+		/// The maximum amount downloaded to an account per `VolumePeriod`.
+		pub account_download_cap: Balance,
+		/// The downloads of at least this amount are delayed by `WithdrawalDelay`.
+		pub large_withdrawal_threshold: Balance,
+	}
+
+	/// The amounts uploaded and downloaded in a period.
+	#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, Default)]
+	pub struct Volume<BlockNumber> {
+		/// The first block of the period.
+		pub period_start: BlockNumber,
+		pub uploaded: Balance,
+		pub downloaded: Balance,
+	}
+
+	impl<BlockNumber: Copy + PartialEq> Volume<BlockNumber> {
+		/// Add `amount` to the volume of the period starting at `period_start`, returns false if
+		/// the volume would exceed `cap`.
+		pub fn try_add(&mut self, period_start: BlockNumber, amount: Balance, is_upload: bool, cap: Balance) -> bool {
+			if self.period_start != period_start {
+				*self = Volume {
+					period_start,
+					uploaded: 0,
+					downloaded: 0,
+				};
+			}
+
+			let volume = if is_upload {
+				&mut self.uploaded
+			} else {
+				&mut self.downloaded
+			};
+			match volume.checked_add(amount) {
+				Some(new_volume) if new_volume <= cap => {
+					*volume = new_volume;
+					true
+				}
+				_ => false,
+			}
+		}
+	}
+
+	/// The download of a large amount waiting for the delay.
+	#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+	pub struct PendingWithdrawal<AccountId, BlockNumber> {
+		pub currency_id: CurrencyId,
+		pub amount: Balance,
+		pub who: AccountId,
+		/// The block number the withdrawal can be claimed at.
+		pub unlock_at: BlockNumber,
 	}
 
 	#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
@@ -184,6 +294,39 @@ pub mod module {
 	pub type GatewayAuthorities<T: Config> =
 		StorageValue<_, BoundedVec<CompoundAuthoritySignature, T::MaxGatewayAuthorities>, ValueQuery>;
 
+	/// Stores the limits of the uploads and downloads for each asset type, no limits if not set.
+	#[pallet::storage]
+	#[pallet::getter(fn gateway_limits)]
+	pub type GatewayLimitsOf<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, GatewayLimits, OptionQuery>;
+
+	/// Stores the maximum supply cap the Notices can set for each asset type, no maximum if not
+	/// set.
+	#[pallet::storage]
+	#[pallet::getter(fn max_supply_caps)]
+	pub type MaxSupplyCaps<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Balance, OptionQuery>;
+
+	/// Stores the volume of each asset type in the current period.
+	#[pallet::storage]
+	#[pallet::getter(fn asset_volumes)]
+	pub type AssetVolumes<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Volume<T::BlockNumber>, ValueQuery>;
+
+	/// Stores the volume of each account and asset type in the current period.
+	#[pallet::storage]
+	#[pallet::getter(fn account_volumes)]
+	pub type AccountVolumes<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, CurrencyId, Volume<T::BlockNumber>, ValueQuery>;
+
+	/// The ID of the next pending withdrawal.
+	#[pallet::storage]
+	#[pallet::getter(fn next_withdrawal_id)]
+	pub type NextWithdrawalId<T: Config> = StorageValue<_, WithdrawalId, ValueQuery>;
+
+	/// Stores the downloads of large amounts waiting for the delay.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_withdrawals)]
+	pub type PendingWithdrawals<T: Config> =
+		StorageMap<_, Twox64Concat, WithdrawalId, PendingWithdrawal<T::AccountId, T::BlockNumber>, OptionQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig {
 		pub initial_authorities: Vec<CompoundAuthoritySignature>,
@@ -245,8 +388,7 @@ pub mod module {
 		/// Parameters:
 		/// - `currency_id`: collateral currency id.
 		/// - `locked_amount`: The amount of user asset to be "uploaded" onto the Compound chain.
-		//#[pallet::weight(< T as Config >::WeightInfo::lock())]
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::lock())]
 		#[transactional]
 		pub fn lock(
 			origin: OriginFor<T>,
@@ -266,8 +408,7 @@ pub mod module {
 		/// - `to`: The account ID the asset is uploaded to on the Compound chain.
 		/// - `currency_id`: collateral currency id.
 		/// - `locked_amount`: The amount of user asset to be "uploaded" onto the Compound chain.
-		//#[pallet::weight(< T as Config >::WeightInfo::lock_to())]
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::lock_to())]
 		#[transactional]
 		pub fn lock_to(
 			origin: OriginFor<T>,
@@ -285,8 +426,7 @@ pub mod module {
 		/// - `notice`: The Notice issued by Compound Gateway. Contains data to be invoked.
 		/// - `signatures`: Represents approvals by given authorities. Used to verify the
 		/// authenticity of the notice.
-		//#[pallet::weight(< T as Config >::WeightInfo::invoke())]
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::invoke(signatures.len() as u32))]
 		#[transactional]
 		pub fn invoke(
 			origin: OriginFor<T>,
@@ -311,6 +451,9 @@ pub mod module {
 
 			match notice.payload {
 				GatewayNoticePayload::SetSupplyCap(currency_id, amount) => {
+					if let Some(max_cap) = Self::max_supply_caps(currency_id) {
+						ensure!(amount <= max_cap, Error::<T>::ExceededMaxSupplyCap);
+					}
 					SupplyCaps::<T>::insert(&currency_id, amount);
 					Self::deposit_event(Event::<T>::SupplyCapSet(currency_id, amount));
 					Ok(().into())
@@ -351,10 +494,91 @@ pub mod module {
 			InvokedNoticeHashes::<T>::insert(&hash, ());
 
 			Ok(PostDispatchInfo {
-				actual_weight: Some(0),
+				actual_weight: None,
 				pays_fee: Pays::No,
 			})
 		}
+
+		/// Set the limits of the uploads and downloads of an asset, or remove them.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// Parameters:
+		/// - `currency_id`: The asset to limit.
+		/// - `limits`: The limits, `None` to remove them.
+		#[pallet::weight(T::WeightInfo::set_gateway_limits())]
+		#[transactional]
+		pub fn set_gateway_limits(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			limits: Option<GatewayLimits>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			GatewayLimitsOf::<T>::mutate_exists(currency_id, |maybe_limits| *maybe_limits = limits);
+			Self::deposit_event(Event::<T>::GatewayLimitsSet(currency_id, limits));
+			Ok(().into())
+		}
+
+		/// Register the maximum supply cap of an asset the Notices can set, or remove it. The
+		/// current supply cap is reduced to the maximum if it's above.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// Parameters:
+		/// - `currency_id`: The asset to register.
+		/// - `max_cap`: The maximum supply cap, `None` to remove it.
+		#[pallet::weight(T::WeightInfo::set_max_supply_cap())]
+		#[transactional]
+		pub fn set_max_supply_cap(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			max_cap: Option<Balance>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			MaxSupplyCaps::<T>::mutate_exists(currency_id, |maybe_max_cap| *maybe_max_cap = max_cap);
+			if let Some(max_cap) = max_cap {
+				SupplyCaps::<T>::mutate(currency_id, |supply_cap| *supply_cap = (*supply_cap).min(max_cap));
+			}
+			Self::deposit_event(Event::<T>::MaxSupplyCapSet(currency_id, max_cap));
+			Ok(().into())
+		}
+
+		/// Claim the download of a large amount after the delay. Anyone can claim it for the
+		/// user.
+		///
+		/// Parameters:
+		/// - `withdrawal_id`: The ID of the pending withdrawal.
+		#[pallet::weight(T::WeightInfo::claim_withdrawal())]
+		#[transactional]
+		pub fn claim_withdrawal(origin: OriginFor<T>, withdrawal_id: WithdrawalId) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let withdrawal = Self::pending_withdrawals(withdrawal_id).ok_or(Error::<T>::WithdrawalNotFound)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() >= withdrawal.unlock_at,
+				Error::<T>::WithdrawalStillDelayed
+			);
+
+			PendingWithdrawals::<T>::remove(withdrawal_id);
+			Self::do_release(withdrawal.currency_id, withdrawal.amount, withdrawal.who)
+		}
+
+		/// Cancel the pending withdrawal, e.g. if the Notice is found malicious.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// Parameters:
+		/// - `withdrawal_id`: The ID of the pending withdrawal.
+		#[pallet::weight(T::WeightInfo::cancel_withdrawal())]
+		#[transactional]
+		pub fn cancel_withdrawal(origin: OriginFor<T>, withdrawal_id: WithdrawalId) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				PendingWithdrawals::<T>::take(withdrawal_id).is_some(),
+				Error::<T>::WithdrawalNotFound
+			);
+			Self::deposit_event(Event::<T>::WithdrawalCancelled(withdrawal_id));
+			Ok(().into())
+		}
 	}
 }
 
@@ -375,6 +599,7 @@ impl<T: Config> Pallet<T> {
 			current_supply_cap >= locked_amount,
 			Error::<T>::InsufficientAssetSupplyCap
 		);
+		Self::add_volume(&from, currency_id, locked_amount, true)?;
 
 		// If the currency is CASH, it is burned
 		// All other tokens are transferred to the admin's account.
@@ -394,6 +619,39 @@ impl<T: Config> Pallet<T> {
 
 	#[require_transactional]
 	fn do_unlock(currency_id: CurrencyId, unlock_amount: Balance, to: T::AccountId) -> DispatchResultWithPostInfo {
+		Self::add_volume(&to, currency_id, unlock_amount, false)?;
+
+		// Large downloads are delayed, to be claimed later.
+		let is_large = Self::gateway_limits(currency_id)
+			.map_or(false, |limits| unlock_amount >= limits.large_withdrawal_threshold);
+		if is_large {
+			let withdrawal_id = Self::next_withdrawal_id();
+			let unlock_at = frame_system::Pallet::<T>::block_number().saturating_add(T::WithdrawalDelay::get());
+			NextWithdrawalId::<T>::put(withdrawal_id.saturating_add(1));
+			PendingWithdrawals::<T>::insert(
+				withdrawal_id,
+				PendingWithdrawal {
+					currency_id,
+					amount: unlock_amount,
+					who: to.clone(),
+					unlock_at,
+				},
+			);
+			Self::deposit_event(Event::<T>::WithdrawalQueued(
+				withdrawal_id,
+				currency_id,
+				unlock_amount,
+				to,
+				unlock_at,
+			));
+			return Ok(().into());
+		}
+
+		Self::do_release(currency_id, unlock_amount, to)
+	}
+
+	#[require_transactional]
+	fn do_release(currency_id: CurrencyId, unlock_amount: Balance, to: T::AccountId) -> DispatchResultWithPostInfo {
 		// If the currency is CASH, mint into the user's account
 		// All other tokens are transferred from the admin's account.
 		match currency_id {
@@ -415,6 +673,37 @@ impl<T: Config> Pallet<T> {
 		Ok(().into())
 	}
 
+	/// Adds the uploaded or downloaded amount to the volumes of the asset and the account in the
+	/// current period. Fails if the volumes exceed the caps.
+	fn add_volume(who: &T::AccountId, currency_id: CurrencyId, amount: Balance, is_upload: bool) -> DispatchResult {
+		let limits = match Self::gateway_limits(currency_id) {
+			Some(limits) => limits,
+			None => return Ok(()),
+		};
+
+		let now = frame_system::Pallet::<T>::block_number();
+		let period_start = now - now % T::VolumePeriod::get();
+		let (cap, account_cap) = if is_upload {
+			(limits.upload_cap, limits.account_upload_cap)
+		} else {
+			(limits.download_cap, limits.account_download_cap)
+		};
+
+		let mut asset_volume = Self::asset_volumes(currency_id);
+		let mut account_volume = Self::account_volumes(who, currency_id);
+		let within_caps = asset_volume.try_add(period_start, amount, is_upload, cap)
+			&& account_volume.try_add(period_start, amount, is_upload, account_cap);
+		if is_upload {
+			ensure!(within_caps, Error::<T>::ExceededUploadVolumeCap);
+		} else {
+			ensure!(within_caps, Error::<T>::ExceededDownloadVolumeCap);
+		}
+
+		AssetVolumes::<T>::insert(currency_id, asset_volume);
+		AccountVolumes::<T>::insert(who, currency_id, account_volume);
+		Ok(())
+	}
+
 	/// Verifies if the given signature is sufficient to prove the authenticity of the Notice.
 	fn verify_compound_authority_signature(signatures: Vec<CompoundAuthoritySignature>) -> bool {
 		let mut count: u32 = 0;
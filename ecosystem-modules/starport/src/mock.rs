@@ -113,6 +113,11 @@ parameter_types! {
 	pub const GetNativeCurrencyId: CurrencyId = ACALA;
 }
 
+parameter_types! {
+	pub const MaxMemoLength: u32 = 32;
+	pub const MaxBatchTransfers: u32 = 4;
+}
+
 impl module_currencies::Config for Runtime {
 	type Event = Event;
 	type MultiCurrency = Tokens;
@@ -121,6 +126,10 @@ impl module_currencies::Config for Runtime {
 	type WeightInfo = ();
 	type AddressMapping = MockAddressMapping;
 	type EVMBridge = ();
+	type UpdateOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxMemoLength = MaxMemoLength;
+	type MaxBatchTransfers = MaxBatchTransfers;
+	type FreezeChecker = ();
 }
 
 pub struct MockCashModule;
@@ -143,6 +152,8 @@ parameter_types! {
 	pub const StarportPalletId: PalletId = PalletId(*b"aca/stpt");
 	pub const MaxGatewayAuthorities: u32 = MAX_GATEWAY_AUTHORITIES;
 	pub const PercentThresholdForAuthoritySignature: Perbill = PERCENT_THRESHOLD_FOR_AUTHORITY_SIGNATURE;
+	pub const VolumePeriod: BlockNumber = 10;
+	pub const WithdrawalDelay: BlockNumber = 5;
 }
 
 impl Config for Runtime {
@@ -153,6 +164,10 @@ impl Config for Runtime {
 	type MaxGatewayAuthorities = MaxGatewayAuthorities;
 	type PercentThresholdForAuthoritySignature = PercentThresholdForAuthoritySignature;
 	type Cash = MockCashModule;
+	type UpdateOrigin = frame_system::EnsureRoot<AccountId>;
+	type VolumePeriod = VolumePeriod;
+	type WithdrawalDelay = WithdrawalDelay;
+	type WeightInfo = ();
 }
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
@@ -168,7 +183,7 @@ frame_support::construct_runtime!(
 		Starport: ecosystem_starport::{Pallet, Call, Storage, Event<T>},
 		PalletBalances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		Tokens: orml_tokens::{Pallet, Storage, Event<T>, Config<T>},
-		Currencies: module_currencies::{Pallet, Call, Event<T>},
+		Currencies: module_currencies::{Pallet, Call, Storage, Event<T>},
 	}
 );
 
@@ -26,6 +26,7 @@ use mock::{
 	AccountId, Currencies, Event, ExtBuilder, Origin, Runtime, Starport, StarportPalletId, System, ACALA, ALICE, BOB,
 	CASH, GATEWAY_ACCOUNT, INITIAL_BALANCE, KSM,
 };
+use sp_runtime::traits::BadOrigin;
 
 #[test]
 fn mock_initialize_token_works() {
@@ -464,3 +465,211 @@ fn notices_can_only_be_invoked_with_enough_signatures() {
 		);
 	});
 }
+
+fn gateway_limits(cap: Balance, account_cap: Balance, large_withdrawal_threshold: Balance) -> GatewayLimits {
+	GatewayLimits {
+		upload_cap: cap,
+		download_cap: cap,
+		account_upload_cap: account_cap,
+		account_download_cap: account_cap,
+		large_withdrawal_threshold,
+	}
+}
+
+#[test]
+fn gateway_limits_cap_volumes() {
+	ExtBuilder::default().build().execute_with(|| {
+		SupplyCaps::<Runtime>::insert(ACALA, INITIAL_BALANCE);
+		assert_ok!(Currencies::transfer(Origin::signed(ALICE), BOB, ACALA, 1000));
+
+		let limits = gateway_limits(600, 400, INITIAL_BALANCE);
+		assert_noop!(
+			Starport::set_gateway_limits(Origin::signed(ALICE), ACALA, Some(limits)),
+			BadOrigin
+		);
+		assert_ok!(Starport::set_gateway_limits(Origin::root(), ACALA, Some(limits)));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::Starport(crate::Event::GatewayLimitsSet(ACALA, Some(limits)))
+		);
+
+		// The upload volume of the account is capped.
+		assert_ok!(Starport::lock(Origin::signed(ALICE), ACALA, 400));
+		assert_noop!(
+			Starport::lock(Origin::signed(ALICE), ACALA, 1),
+			Error::<Runtime>::ExceededUploadVolumeCap
+		);
+
+		// The upload volume of the asset is capped.
+		assert_noop!(
+			Starport::lock(Origin::signed(BOB), ACALA, 300),
+			Error::<Runtime>::ExceededUploadVolumeCap
+		);
+		assert_ok!(Starport::lock(Origin::signed(BOB), ACALA, 200));
+
+		// The download volume of the account is capped.
+		let notice = GatewayNotice::new(
+			0,
+			GatewayNoticePayload::Unlock {
+				currency_id: ACALA,
+				amount: 401,
+				who: ALICE,
+			},
+		);
+		assert_noop!(
+			Starport::invoke(Origin::signed(GATEWAY_ACCOUNT), notice, mock::get_mock_signatures()),
+			Error::<Runtime>::ExceededDownloadVolumeCap
+		);
+
+		// The volumes are reset in the next period.
+		System::set_block_number(11);
+		assert_ok!(Starport::lock(Origin::signed(ALICE), ACALA, 400));
+		assert_eq!(
+			Starport::asset_volumes(ACALA),
+			Volume {
+				period_start: 10,
+				uploaded: 400,
+				downloaded: 0,
+			}
+		);
+
+		// No limits after they're removed.
+		assert_ok!(Starport::set_gateway_limits(Origin::root(), ACALA, None));
+		assert_eq!(Starport::gateway_limits(ACALA), None);
+		assert_ok!(Starport::lock(Origin::signed(BOB), ACALA, 800));
+	});
+}
+
+#[test]
+fn large_downloads_are_delayed() {
+	ExtBuilder::default().build().execute_with(|| {
+		SupplyCaps::<Runtime>::insert(ACALA, INITIAL_BALANCE);
+		assert_ok!(Starport::lock(Origin::signed(ALICE), ACALA, 1000));
+		assert_ok!(Starport::set_gateway_limits(
+			Origin::root(),
+			ACALA,
+			Some(gateway_limits(INITIAL_BALANCE, INITIAL_BALANCE, 500))
+		));
+
+		let mut notice = GatewayNotice::new(
+			0,
+			GatewayNoticePayload::Unlock {
+				currency_id: ACALA,
+				amount: 500,
+				who: ALICE,
+			},
+		);
+		assert_ok!(Starport::invoke(
+			Origin::signed(GATEWAY_ACCOUNT),
+			notice.clone(),
+			mock::get_mock_signatures()
+		));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::Starport(crate::Event::WithdrawalQueued(0, ACALA, 500, ALICE, 6))
+		);
+		assert_eq!(Currencies::free_balance(ACALA, &ALICE), INITIAL_BALANCE - 1000);
+
+		assert_noop!(
+			Starport::claim_withdrawal(Origin::signed(BOB), 0),
+			Error::<Runtime>::WithdrawalStillDelayed
+		);
+		System::set_block_number(6);
+		assert_ok!(Starport::claim_withdrawal(Origin::signed(BOB), 0));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::Starport(crate::Event::AssetUnlocked(ACALA, 500, ALICE))
+		);
+		assert_eq!(Currencies::free_balance(ACALA, &ALICE), INITIAL_BALANCE - 500);
+		assert_eq!(Starport::pending_withdrawals(0), None);
+
+		// Small downloads are not delayed.
+		notice.id = 1;
+		notice.payload = GatewayNoticePayload::Unlock {
+			currency_id: ACALA,
+			amount: 100,
+			who: ALICE,
+		};
+		assert_ok!(Starport::invoke(
+			Origin::signed(GATEWAY_ACCOUNT),
+			notice.clone(),
+			mock::get_mock_signatures()
+		));
+		assert_eq!(Currencies::free_balance(ACALA, &ALICE), INITIAL_BALANCE - 400);
+
+		// The pending withdrawal can be cancelled.
+		notice.id = 2;
+		notice.payload = GatewayNoticePayload::Unlock {
+			currency_id: ACALA,
+			amount: 500,
+			who: ALICE,
+		};
+		assert_ok!(Starport::invoke(
+			Origin::signed(GATEWAY_ACCOUNT),
+			notice,
+			mock::get_mock_signatures()
+		));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::Starport(crate::Event::WithdrawalQueued(1, ACALA, 500, ALICE, 11))
+		);
+		assert_noop!(Starport::cancel_withdrawal(Origin::signed(ALICE), 1), BadOrigin);
+		assert_ok!(Starport::cancel_withdrawal(Origin::root(), 1));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::Starport(crate::Event::WithdrawalCancelled(1))
+		);
+		assert_noop!(
+			Starport::claim_withdrawal(Origin::signed(ALICE), 1),
+			Error::<Runtime>::WithdrawalNotFound
+		);
+	});
+}
+
+#[test]
+fn max_supply_caps_bound_notices() {
+	ExtBuilder::default().build().execute_with(|| {
+		SupplyCaps::<Runtime>::insert(ACALA, 1000);
+
+		assert_noop!(
+			Starport::set_max_supply_cap(Origin::signed(ALICE), ACALA, Some(500)),
+			BadOrigin
+		);
+		assert_ok!(Starport::set_max_supply_cap(Origin::root(), ACALA, Some(500)));
+		assert_eq!(
+			System::events().iter().last().unwrap().event,
+			Event::Starport(crate::Event::MaxSupplyCapSet(ACALA, Some(500)))
+		);
+		// The current supply cap is reduced to the maximum.
+		assert_eq!(Starport::supply_caps(ACALA), 500);
+
+		let mut notice = GatewayNotice::new(0, GatewayNoticePayload::SetSupplyCap(ACALA, 600));
+		assert_noop!(
+			Starport::invoke(
+				Origin::signed(GATEWAY_ACCOUNT),
+				notice.clone(),
+				mock::get_mock_signatures()
+			),
+			Error::<Runtime>::ExceededMaxSupplyCap
+		);
+
+		// The notice can be invoked after the maximum is removed.
+		assert_ok!(Starport::set_max_supply_cap(Origin::root(), ACALA, None));
+		assert_ok!(Starport::invoke(
+			Origin::signed(GATEWAY_ACCOUNT),
+			notice.clone(),
+			mock::get_mock_signatures()
+		));
+		assert_eq!(Starport::supply_caps(ACALA), 600);
+
+		notice.id = 1;
+		notice.payload = GatewayNoticePayload::SetSupplyCap(ACALA, 500);
+		assert_ok!(Starport::set_max_supply_cap(Origin::root(), ACALA, Some(500)));
+		assert_ok!(Starport::invoke(
+			Origin::signed(GATEWAY_ACCOUNT),
+			notice,
+			mock::get_mock_signatures()
+		));
+		assert_eq!(Starport::supply_caps(ACALA), 500);
+	});
+}
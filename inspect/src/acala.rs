@@ -0,0 +1,268 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Pretty printer of the extrinsics and events of Acala runtimes.
+
+use crate::PrettyPrinter;
+use codec::{Decode, Encode};
+use frame_support::{dispatch::GetCallName, traits::IsSubType};
+use frame_system::EventRecord;
+use sp_core::{hashing::keccak_256, hexdisplay::HexDisplay};
+use sp_runtime::{
+	generic,
+	traits::{Block, SignedExtension},
+};
+use std::{fmt, fmt::Debug, marker::PhantomData};
+
+/// The function signatures of the well-known contracts, used to look up
+/// the selectors of EVM calls.
+const KNOWN_SIGNATURES: &[&str] = &[
+	// ERC20
+	"name()",
+	"symbol()",
+	"decimals()",
+	"totalSupply()",
+	"balanceOf(address)",
+	"allowance(address,address)",
+	"transfer(address,uint256)",
+	"transferFrom(address,address,uint256)",
+	"approve(address,uint256)",
+	// ERC721
+	"ownerOf(uint256)",
+	"tokenURI(uint256)",
+	"getApproved(uint256)",
+	"isApprovedForAll(address,address)",
+	"setApprovalForAll(address,bool)",
+	"safeTransferFrom(address,address,uint256)",
+	"safeTransferFrom(address,address,uint256,bytes)",
+	"supportsInterface(bytes4)",
+	// WETH
+	"deposit()",
+	"withdraw(uint256)",
+	// Uniswap V2 router
+	"addLiquidity(address,address,uint256,uint256,uint256,uint256,address,uint256)",
+	"removeLiquidity(address,address,uint256,uint256,uint256,address,uint256)",
+	"swapExactTokensForTokens(uint256,uint256,address[],address,uint256)",
+	"swapTokensForExactTokens(uint256,uint256,address[],address,uint256)",
+];
+
+/// Look up the function signature of the selector of the EVM call `input`.
+pub fn lookup_selector(input: &[u8]) -> Option<&'static str> {
+	let selector = input.get(0..4)?;
+	KNOWN_SIGNATURES
+		.iter()
+		.find(|signature| &keccak_256(signature.as_bytes())[0..4] == selector)
+		.copied()
+}
+
+/// A runtime extrinsic which can be decoded from the opaque extrinsic of
+/// the block.
+pub trait RuntimeExtrinsic: Decode + Debug {
+	/// The call of the extrinsic.
+	type Call;
+
+	/// The signer of the extrinsic, `None` for unsigned extrinsics.
+	fn signer(&self) -> Option<String>;
+	/// The call of the extrinsic.
+	fn call(&self) -> &Self::Call;
+}
+
+impl<Address, Call, Signature, Extra> RuntimeExtrinsic for generic::UncheckedExtrinsic<Address, Call, Signature, Extra>
+where
+	Address: Decode + Debug,
+	Call: Decode + Debug,
+	Signature: Decode,
+	Extra: SignedExtension,
+{
+	type Call = Call;
+
+	fn signer(&self) -> Option<String> {
+		self.signature.as_ref().map(|(address, _, _)| format!("{:?}", address))
+	}
+
+	fn call(&self) -> &Call {
+		&self.function
+	}
+}
+
+/// Pretty printer which decodes the extrinsics and events with the types of
+/// the runtime, and prints out the details of the honzon, dex and EVM calls.
+pub struct AcalaPrinter<Runtime, Xt>(PhantomData<(Runtime, Xt)>);
+
+impl<Runtime, Xt> Default for AcalaPrinter<Runtime, Xt> {
+	fn default() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<Runtime, Xt> AcalaPrinter<Runtime, Xt>
+where
+	Runtime: module_honzon::Config + module_dex::Config + module_evm::Config,
+	<Runtime as frame_system::Config>::Call: IsSubType<module_honzon::Call<Runtime>>
+		+ IsSubType<module_dex::Call<Runtime>>
+		+ IsSubType<module_evm::Call<Runtime>>,
+	Xt: RuntimeExtrinsic<Call = <Runtime as frame_system::Config>::Call>,
+{
+	fn fmt_call(&self, fmt: &mut fmt::Formatter, call: &<Runtime as frame_system::Config>::Call) -> fmt::Result {
+		if let Some(call) = <_ as IsSubType<module_honzon::Call<Runtime>>>::is_sub_type(call) {
+			match call {
+				module_honzon::Call::adjust_loan(currency_id, collateral_adjustment, debit_adjustment) => {
+					writeln!(fmt, " Honzon::adjust_loan")?;
+					writeln!(fmt, "  currency_id: {:?}", currency_id)?;
+					writeln!(fmt, "  collateral_adjustment: {}", collateral_adjustment)?;
+					return writeln!(fmt, "  debit_adjustment: {}", debit_adjustment);
+				}
+				module_honzon::Call::close_loan_has_debit_by_dex(currency_id, maybe_path) => {
+					writeln!(fmt, " Honzon::close_loan_has_debit_by_dex")?;
+					writeln!(fmt, "  currency_id: {:?}", currency_id)?;
+					return writeln!(fmt, "  path: {:?}", maybe_path);
+				}
+				_ => {}
+			}
+		}
+
+		if let Some(call) = <_ as IsSubType<module_dex::Call<Runtime>>>::is_sub_type(call) {
+			match call {
+				module_dex::Call::swap_with_exact_supply(path, supply_amount, min_target_amount) => {
+					writeln!(fmt, " Dex::swap_with_exact_supply")?;
+					writeln!(fmt, "  path: {:?}", path)?;
+					writeln!(fmt, "  supply_amount: {}", supply_amount)?;
+					return writeln!(fmt, "  min_target_amount: {}", min_target_amount);
+				}
+				module_dex::Call::swap_with_exact_target(path, target_amount, max_supply_amount) => {
+					writeln!(fmt, " Dex::swap_with_exact_target")?;
+					writeln!(fmt, "  path: {:?}", path)?;
+					writeln!(fmt, "  target_amount: {}", target_amount)?;
+					return writeln!(fmt, "  max_supply_amount: {}", max_supply_amount);
+				}
+				_ => {}
+			}
+		}
+
+		if let Some(call) = <_ as IsSubType<module_evm::Call<Runtime>>>::is_sub_type(call) {
+			match call {
+				module_evm::Call::call(target, input, value, gas_limit, storage_limit)
+				| module_evm::Call::call_evm(target, input, value, gas_limit, storage_limit)
+				| module_evm::Call::call_with_access_list(target, input, value, gas_limit, storage_limit, _) => {
+					writeln!(fmt, " EVM::{}", call.get_call_name())?;
+					writeln!(fmt, "  target: {:?}", target)?;
+					match lookup_selector(input) {
+						Some(signature) => writeln!(fmt, "  function: {}", signature)?,
+						None => writeln!(fmt, "  function: unknown")?,
+					}
+					writeln!(fmt, "  input: 0x{:?}", HexDisplay::from(input))?;
+					writeln!(fmt, "  value: {:?}", value)?;
+					writeln!(fmt, "  gas_limit: {}", gas_limit)?;
+					return writeln!(fmt, "  storage_limit: {}", storage_limit);
+				}
+				module_evm::Call::create(init, value, gas_limit, storage_limit)
+				| module_evm::Call::create2(init, _, value, gas_limit, storage_limit)
+				| module_evm::Call::create_with_access_list(init, value, gas_limit, storage_limit, _) => {
+					writeln!(fmt, " EVM::{}", call.get_call_name())?;
+					writeln!(fmt, "  init: 0x{:?}", HexDisplay::from(init))?;
+					writeln!(fmt, "  value: {:?}", value)?;
+					writeln!(fmt, "  gas_limit: {}", gas_limit)?;
+					return writeln!(fmt, "  storage_limit: {}", storage_limit);
+				}
+				_ => {}
+			}
+		}
+
+		writeln!(fmt, " {:?}", call)
+	}
+}
+
+impl<TBlock, Runtime, Xt> PrettyPrinter<TBlock> for AcalaPrinter<Runtime, Xt>
+where
+	TBlock: Block,
+	Runtime: module_honzon::Config + module_dex::Config + module_evm::Config,
+	<Runtime as frame_system::Config>::Call: IsSubType<module_honzon::Call<Runtime>>
+		+ IsSubType<module_dex::Call<Runtime>>
+		+ IsSubType<module_evm::Call<Runtime>>,
+	Xt: RuntimeExtrinsic<Call = <Runtime as frame_system::Config>::Call>,
+{
+	fn fmt_block(&self, fmt: &mut fmt::Formatter, block: &TBlock) -> fmt::Result {
+		writeln!(fmt, "Header:")?;
+		writeln!(fmt, "{:?}", block.header())?;
+		writeln!(fmt, "Extrinsics ({})", block.extrinsics().len())?;
+		for (idx, ex) in block.extrinsics().iter().enumerate() {
+			writeln!(fmt, "- {}:", idx)?;
+			<Self as PrettyPrinter<TBlock>>::fmt_extrinsic(self, fmt, ex)?;
+		}
+		Ok(())
+	}
+
+	fn fmt_extrinsic(&self, fmt: &mut fmt::Formatter, extrinsic: &TBlock::Extrinsic) -> fmt::Result {
+		let bytes = extrinsic.encode();
+		match Xt::decode(&mut &*bytes) {
+			Ok(xt) => {
+				match xt.signer() {
+					Some(signer) => writeln!(fmt, " Signer: {}", signer)?,
+					None => writeln!(fmt, " Unsigned")?,
+				}
+				self.fmt_call(fmt, xt.call())?;
+			}
+			Err(e) => writeln!(fmt, " Could not decode extrinsic: {}", e)?,
+		}
+		writeln!(fmt, " Bytes: {:?}", HexDisplay::from(&bytes))
+	}
+
+	fn fmt_events(&self, fmt: &mut fmt::Formatter, events: &[u8]) -> fmt::Result {
+		type EventRecordOf<Runtime> =
+			EventRecord<<Runtime as frame_system::Config>::Event, <Runtime as frame_system::Config>::Hash>;
+
+		match Vec::<EventRecordOf<Runtime>>::decode(&mut &*events) {
+			Ok(records) => {
+				writeln!(fmt, "Events ({})", records.len())?;
+				for (idx, record) in records.iter().enumerate() {
+					writeln!(fmt, "- {}: {:?}", idx, record.phase)?;
+					writeln!(fmt, " {:?}", record.event)?;
+					if !record.topics.is_empty() {
+						writeln!(fmt, " Topics: {:?}", record.topics)?;
+					}
+				}
+				Ok(())
+			}
+			Err(e) => writeln!(fmt, "Could not decode events: {}", e),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn should_look_up_selectors() {
+		assert_eq!(
+			lookup_selector(&[0xa9, 0x05, 0x9c, 0xbb, 0x00]),
+			Some("transfer(address,uint256)")
+		);
+		assert_eq!(
+			lookup_selector(&[0x23, 0xb8, 0x72, 0xdd]),
+			Some("transferFrom(address,address,uint256)")
+		);
+		assert_eq!(
+			lookup_selector(&[0x09, 0x5e, 0xa7, 0xb3]),
+			Some("approve(address,uint256)")
+		);
+		assert_eq!(lookup_selector(&[0x70, 0xa0, 0x82, 0x31]), Some("balanceOf(address)"));
+		assert_eq!(lookup_selector(&[0x00, 0x00, 0x00, 0x00]), None);
+		assert_eq!(lookup_selector(&[0xa9, 0x05, 0x9c]), None);
+	}
+}
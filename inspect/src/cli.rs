@@ -60,4 +60,15 @@ pub enum InspectSubCmd {
 		#[structopt(value_name = "BLOCK:INDEX or BYTES")]
 		input: String,
 	},
+	/// Decode events of a block with native version of runtime and print out
+	/// the details.
+	Events {
+		/// Address of the block whose events to print out.
+		///
+		/// Can be either a block hash (no 0x prefix) or a number to read the
+		/// events from the state of existing block, or a 0x-prefixed bytes hex
+		/// string, representing SCALE encoding of the events.
+		#[structopt(value_name = "HASH or NUMBER or BYTES")]
+		input: String,
+	},
 }
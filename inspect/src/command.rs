@@ -19,25 +19,34 @@
 //! Command ran by the CLI
 
 use crate::cli::{InspectCmd, InspectSubCmd};
-use crate::Inspector;
+use crate::{BlockAddress, BlockAddressFor, Inspector, PrettyPrinter};
 use sc_cli::{CliConfiguration, ImportParams, Result, SharedParams};
-use sc_client_api::BlockBackend;
+use sc_client_api::{Backend, BlockBackend, StorageProvider};
 use sp_blockchain::HeaderBackend;
-use sp_runtime::traits::Block;
+use sp_core::{hashing::twox_128, storage::StorageKey};
+use sp_runtime::{generic::BlockId, traits::Block};
 use std::str::FromStr;
 use std::sync::Arc;
 
 impl InspectCmd {
 	/// Run the inspect command, passing the inspector.
-	pub fn run<B, CL>(&self, client: Arc<CL>) -> Result<()>
+	pub fn run<B, BE, CL, P>(&self, client: Arc<CL>, printer: P) -> Result<()>
 	where
 		B: Block,
 		B::Hash: FromStr,
-		CL: BlockBackend<B> + HeaderBackend<B> + 'static,
+		BE: Backend<B>,
+		CL: BlockBackend<B> + HeaderBackend<B> + StorageProvider<B, BE> + 'static,
+		P: PrettyPrinter<B>,
 	{
+		// the events are read from the state before the client is moved into the inspector
+		let events = match &self.command {
+			InspectSubCmd::Events { input } => Some(read_events(&*client, input.parse()?)?),
+			_ => None,
+		};
+
 		match Arc::try_unwrap(client) {
 			Ok(cli) => {
-				let inspect = Inspector::<B>::new(cli);
+				let inspect = Inspector::<B, P>::with_printer(cli, printer);
 
 				match &self.command {
 					InspectSubCmd::Block { input } => {
@@ -52,6 +61,11 @@ impl InspectCmd {
 						println!("{}", res);
 						Ok(())
 					}
+					InspectSubCmd::Events { .. } => {
+						let events = events.expect("events are read for the events command; qed");
+						println!("{}", inspect.events(&events));
+						Ok(())
+					}
 				}
 			}
 
@@ -60,6 +74,28 @@ impl InspectCmd {
 	}
 }
 
+/// Read the SCALE-encoded events of the block, either from the `System::Events` storage at the
+/// block or the given bytes.
+fn read_events<B, BE, CL>(client: &CL, input: BlockAddressFor<B>) -> Result<Vec<u8>>
+where
+	B: Block,
+	BE: Backend<B>,
+	CL: StorageProvider<B, BE>,
+{
+	let id = match input {
+		BlockAddress::Bytes(bytes) => return Ok(bytes),
+		BlockAddress::Hash(hash) => BlockId::hash(hash),
+		BlockAddress::Number(number) => BlockId::number(number),
+	};
+
+	let key = StorageKey([twox_128(b"System"), twox_128(b"Events")].concat());
+	let events = client
+		.storage(&id, &key)
+		.map_err(|e| format!("{}", e))?
+		.ok_or_else(|| format!("Could not find events of block {:?}", id))?;
+	Ok(events.0)
+}
+
 impl CliConfiguration for InspectCmd {
 	fn shared_params(&self) -> &SharedParams {
 		&self.shared_params
@@ -16,9 +16,12 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+pub mod acala;
 pub mod cli;
 pub mod command;
 
+pub use acala::AcalaPrinter;
+
 use codec::{Decode, Encode};
 use sc_client_api::BlockBackend;
 use sp_blockchain::HeaderBackend;
@@ -38,6 +41,10 @@ pub trait PrettyPrinter<TBlock: Block> {
 	fn fmt_block(&self, fmt: &mut fmt::Formatter, block: &TBlock) -> fmt::Result;
 	/// Nicely format extrinsic.
 	fn fmt_extrinsic(&self, fmt: &mut fmt::Formatter, extrinsic: &TBlock::Extrinsic) -> fmt::Result;
+	/// Nicely format SCALE-encoded events of a block.
+	fn fmt_events(&self, fmt: &mut fmt::Formatter, events: &[u8]) -> fmt::Result {
+		writeln!(fmt, "Events bytes: {:?}", HexDisplay::from(&events))
+	}
 }
 
 /// Default dummy debug printer.
@@ -189,6 +196,18 @@ impl<TBlock: Block, TPrinter: PrettyPrinter<TBlock>> Inspector<TBlock, TPrinter>
 
 		Ok(format!("{}", ExtrinsicPrinter(ext, &self.printer)))
 	}
+
+	/// Get pretty-printed SCALE-encoded events of a block.
+	pub fn events(&self, events: &[u8]) -> String {
+		struct EventsPrinter<'a, A, B>(&'a [u8], &'a B, PhantomData<A>);
+		impl<'a, A: Block, B: PrettyPrinter<A>> fmt::Display for EventsPrinter<'a, A, B> {
+			fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+				self.1.fmt_events(fmt, self.0)
+			}
+		}
+
+		format!("{}", EventsPrinter::<TBlock, _>(events, &self.printer, PhantomData))
+	}
 }
 
 /// A block to retrieve.
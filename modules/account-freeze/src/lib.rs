@@ -0,0 +1,205 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Account Freeze Module
+//!
+//! ## Overview
+//!
+//! The freeze origin can freeze a currency of an account with a reason code and an optional
+//! expiry, e.g. to stop the stolen funds of an exploit from being bridged out. The frozen funds
+//! can't be transferred, withdrawn or reserved through `module-currencies`, while the account can
+//! still receive funds and move its other currencies. The freeze is lifted by the freeze origin,
+//! or when it expires.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{pallet_prelude::*, transactional};
+use frame_system::pallet_prelude::*;
+use primitives::CurrencyId;
+use sp_runtime::RuntimeDebug;
+use support::FreezeChecker;
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+/// The reason of freeze.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum FreezeReason {
+	/// The funds are stolen in an exploit.
+	Exploit,
+	/// The funds are subject to legal or regulatory requirements.
+	Compliance,
+	/// The other reason identified by the code.
+	Other(u32),
+}
+
+/// The freeze of the currency of an account.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct FreezeInfo<BlockNumber> {
+	/// The reason of freeze.
+	pub reason: FreezeReason,
+	/// The block number the freeze expires at, `None` if it never expires.
+	pub expiry: Option<BlockNumber>,
+}
+
+impl<BlockNumber: PartialOrd> FreezeInfo<BlockNumber> {
+	/// Whether the freeze is in effect at `now`.
+	pub fn is_active(&self, now: BlockNumber) -> bool {
+		self.expiry.as_ref().map_or(true, |expiry| now < *expiry)
+	}
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The origin which may freeze and unfreeze the funds of accounts.
+		type FreezeOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The expiry is not in the future
+		InvalidExpiry,
+		/// The currency of the account is not frozen
+		NotFrozen,
+		/// The currency of the account is frozen
+		AccountFrozen,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Froze the currency of account. \[who, currency_id, reason, expiry\]
+		Frozen(T::AccountId, CurrencyId, FreezeReason, Option<T::BlockNumber>),
+		/// Unfroze the currency of account. \[who, currency_id\]
+		Unfrozen(T::AccountId, CurrencyId),
+	}
+
+	/// The freezes of the currencies of accounts.
+	///
+	/// Freezes: double_map AccountId, CurrencyId => Option<FreezeInfo>
+	#[pallet::storage]
+	#[pallet::getter(fn freezes)]
+	pub type Freezes<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		CurrencyId,
+		FreezeInfo<T::BlockNumber>,
+		OptionQuery,
+	>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Freeze `currency_id` of `who`, or update the existing freeze.
+		///
+		/// The dispatch origin of this call must be `FreezeOrigin`.
+		///
+		/// - `who`: the account to freeze.
+		/// - `currency_id`: the currency to freeze.
+		/// - `reason`: the reason of freeze.
+		/// - `expiry`: the block number the freeze expires at, `None` if it never expires.
+		#[pallet::weight(T::WeightInfo::freeze())]
+		#[transactional]
+		pub fn freeze(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			currency_id: CurrencyId,
+			reason: FreezeReason,
+			expiry: Option<T::BlockNumber>,
+		) -> DispatchResultWithPostInfo {
+			T::FreezeOrigin::ensure_origin(origin)?;
+			if let Some(expiry) = expiry {
+				ensure!(
+					expiry > frame_system::Pallet::<T>::block_number(),
+					Error::<T>::InvalidExpiry
+				);
+			}
+
+			Freezes::<T>::insert(&who, currency_id, FreezeInfo { reason, expiry });
+			Self::deposit_event(Event::Frozen(who, currency_id, reason, expiry));
+			Ok(().into())
+		}
+
+		/// Unfreeze `currency_id` of `who`.
+		///
+		/// The dispatch origin of this call must be `FreezeOrigin`.
+		///
+		/// - `who`: the frozen account.
+		/// - `currency_id`: the frozen currency.
+		#[pallet::weight(T::WeightInfo::unfreeze())]
+		#[transactional]
+		pub fn unfreeze(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			currency_id: CurrencyId,
+		) -> DispatchResultWithPostInfo {
+			T::FreezeOrigin::ensure_origin(origin)?;
+			ensure!(Freezes::<T>::contains_key(&who, currency_id), Error::<T>::NotFrozen);
+
+			Freezes::<T>::remove(&who, currency_id);
+			Self::deposit_event(Event::Unfrozen(who, currency_id));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Whether `currency_id` of `who` is frozen now.
+	pub fn is_frozen(currency_id: CurrencyId, who: &T::AccountId) -> bool {
+		Self::freezes(who, currency_id).map_or(false, |freeze| {
+			freeze.is_active(frame_system::Pallet::<T>::block_number())
+		})
+	}
+}
+
+impl<T: Config> FreezeChecker<T::AccountId> for Pallet<T> {
+	fn ensure_not_frozen(currency_id: CurrencyId, who: &T::AccountId) -> DispatchResult {
+		ensure!(!Self::is_frozen(currency_id, who), Error::<T>::AccountFrozen);
+		Ok(())
+	}
+
+	fn ensure_no_frozen_currency(who: &T::AccountId) -> DispatchResult {
+		let now = frame_system::Pallet::<T>::block_number();
+		ensure!(
+			!Freezes::<T>::iter_prefix_values(who).any(|freeze| freeze.is_active(now)),
+			Error::<T>::AccountFrozen
+		);
+		Ok(())
+	}
+}
@@ -0,0 +1,134 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the account freeze module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+use sp_runtime::traits::BadOrigin;
+
+#[test]
+fn freeze_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			AccountFreezeModule::freeze(Origin::signed(BOB), BOB, AUSD, FreezeReason::Exploit, None),
+			BadOrigin
+		);
+		assert_noop!(
+			AccountFreezeModule::freeze(Origin::signed(ALICE), BOB, AUSD, FreezeReason::Exploit, Some(1)),
+			Error::<Runtime>::InvalidExpiry
+		);
+
+		assert_ok!(AccountFreezeModule::freeze(
+			Origin::signed(ALICE),
+			BOB,
+			AUSD,
+			FreezeReason::Exploit,
+			None
+		));
+		System::assert_last_event(Event::AccountFreezeModule(crate::Event::Frozen(
+			BOB,
+			AUSD,
+			FreezeReason::Exploit,
+			None,
+		)));
+		assert_eq!(
+			AccountFreezeModule::freezes(BOB, AUSD),
+			Some(FreezeInfo {
+				reason: FreezeReason::Exploit,
+				expiry: None,
+			})
+		);
+		assert!(AccountFreezeModule::is_frozen(AUSD, &BOB));
+		assert!(!AccountFreezeModule::is_frozen(DOT, &BOB));
+		assert!(!AccountFreezeModule::is_frozen(AUSD, &ALICE));
+
+		// update the existing freeze
+		assert_ok!(AccountFreezeModule::freeze(
+			Origin::signed(ALICE),
+			BOB,
+			AUSD,
+			FreezeReason::Other(7),
+			Some(10)
+		));
+		assert_eq!(
+			AccountFreezeModule::freezes(BOB, AUSD),
+			Some(FreezeInfo {
+				reason: FreezeReason::Other(7),
+				expiry: Some(10),
+			})
+		);
+	});
+}
+
+#[test]
+fn unfreeze_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			AccountFreezeModule::unfreeze(Origin::signed(ALICE), BOB, AUSD),
+			Error::<Runtime>::NotFrozen
+		);
+		assert_ok!(AccountFreezeModule::freeze(
+			Origin::signed(ALICE),
+			BOB,
+			AUSD,
+			FreezeReason::Compliance,
+			None
+		));
+		assert_noop!(AccountFreezeModule::unfreeze(Origin::signed(BOB), BOB, AUSD), BadOrigin);
+
+		assert_ok!(AccountFreezeModule::unfreeze(Origin::signed(ALICE), BOB, AUSD));
+		System::assert_last_event(Event::AccountFreezeModule(crate::Event::Unfrozen(BOB, AUSD)));
+		assert_eq!(AccountFreezeModule::freezes(BOB, AUSD), None);
+		assert!(!AccountFreezeModule::is_frozen(AUSD, &BOB));
+	});
+}
+
+#[test]
+fn freeze_checker_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(AccountFreezeModule::ensure_not_frozen(AUSD, &BOB));
+		assert_ok!(AccountFreezeModule::ensure_no_frozen_currency(&BOB));
+
+		assert_ok!(AccountFreezeModule::freeze(
+			Origin::signed(ALICE),
+			BOB,
+			AUSD,
+			FreezeReason::Exploit,
+			Some(10)
+		));
+		assert_noop!(
+			AccountFreezeModule::ensure_not_frozen(AUSD, &BOB),
+			Error::<Runtime>::AccountFrozen
+		);
+		assert_ok!(AccountFreezeModule::ensure_not_frozen(DOT, &BOB));
+		assert_noop!(
+			AccountFreezeModule::ensure_no_frozen_currency(&BOB),
+			Error::<Runtime>::AccountFrozen
+		);
+		assert_ok!(AccountFreezeModule::ensure_no_frozen_currency(&ALICE));
+
+		// the freeze is lifted once expired
+		System::set_block_number(10);
+		assert_ok!(AccountFreezeModule::ensure_not_frozen(AUSD, &BOB));
+		assert_ok!(AccountFreezeModule::ensure_no_frozen_currency(&BOB));
+	});
+}
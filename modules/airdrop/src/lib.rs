@@ -16,82 +16,350 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+//! # Airdrop Module
+//!
+//! ## Overview
+//!
+//! The airdrops are distributed with merkle proofs instead of credited to each recipient by
+//! governance. The create origin posts the merkle root of the recipients and their amounts, and
+//! the total amount is moved from the treasury to the module account. Each recipient claims the
+//! amount with the merkle proof of its leaf, and the unclaimed amount is swept back to the
+//! treasury after the airdrop expires.
+//!
+//! Each leaf of the merkle tree is the hash of `(index, recipient, amount)`, where `index` is
+//! the position of the leaf in `0..recipients`, and each node is the hash of its children in
+//! ascending order. The claims are recorded in a bitmap by the index of the leaf.
+//!
+//! The airdrop records of the genesis are kept in `AirDrops`.
+
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 
-use frame_support::{decl_event, decl_module, decl_storage, transactional};
-use frame_system::{self as system, ensure_root};
-use primitives::{AirDropCurrencyId, Balance};
-use sp_runtime::traits::StaticLookup;
+use frame_support::{pallet_prelude::*, transactional, PalletId};
+use frame_system::pallet_prelude::*;
+use orml_traits::MultiCurrency;
+use primitives::{AirDropCurrencyId, Balance, CurrencyId};
+use sp_runtime::{
+	traits::{AccountIdConversion, Hash, One, Saturating, Zero},
+	ArithmeticError, RuntimeDebug,
+};
+use sp_std::prelude::*;
 
 mod mock;
 mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+/// The id of airdrop.
+pub type AirdropId = u32;
 
-pub trait Config: system::Config {
-	type Event: From<Event<Self>> + Into<<Self as system::Config>::Event>;
+/// The number of the claims recorded in a word of the claimed bitmap.
+pub const CLAIMED_BITS_PER_WORD: u32 = 128;
+
+/// The airdrop distributed with merkle proofs.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct AirdropInfo<BlockNumber, Hash> {
+	/// The currency of the airdrop.
+	pub currency_id: CurrencyId,
+	/// The merkle root of the recipients and their amounts.
+	pub merkle_root: Hash,
+	/// The number of the leaves of the merkle tree.
+	pub recipients: u32,
+	/// The total amount of the airdrop.
+	pub total: Balance,
+	/// The amount claimed by the recipients.
+	pub claimed: Balance,
+	/// The block number the airdrop expires at, the unclaimed amount can be swept back to the
+	/// treasury since then.
+	pub expiry: BlockNumber,
 }
 
-decl_storage! {
-	trait Store for Module<T: Config> as AirDrop {
-		AirDrops get(fn airdrops): double_map hasher(twox_64_concat) T::AccountId, hasher(twox_64_concat) AirDropCurrencyId => Balance;
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currencies of airdrops.
+		type Currency: MultiCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
+
+		/// The treasury account which funds the airdrops and receives the unclaimed amounts.
+		#[pallet::constant]
+		type TreasuryAccount: Get<Self::AccountId>;
+
+		/// The module id to keep the unclaimed amounts of airdrops.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The origin which may create airdrops.
+		type CreateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The expiry is not in the future
+		InvalidExpiry,
+		/// The total amount or the number of recipients is zero
+		InvalidAmount,
+		/// The airdrop does not exist
+		AirdropNotFound,
+		/// The airdrop has expired
+		AirdropExpired,
+		/// The airdrop has not expired yet
+		AirdropNotExpired,
+		/// The index of the leaf is out of the recipients
+		InvalidIndex,
+		/// The leaf has been claimed
+		AlreadyClaimed,
+		/// The merkle proof of the claim is invalid
+		InvalidProof,
+		/// The claims exceed the total amount of the airdrop
+		ExceedTotal,
 	}
 
-	add_extra_genesis {
-		config(airdrop_accounts): Vec<(T::AccountId, AirDropCurrencyId, Balance)>;
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Created airdrop. \[airdrop_id, currency_id, merkle_root, total, expiry\]
+		AirdropCreated(AirdropId, CurrencyId, T::Hash, Balance, T::BlockNumber),
+		/// Claimed from airdrop. \[airdrop_id, index, recipient, amount\]
+		Claimed(AirdropId, u32, T::AccountId, Balance),
+		/// Swept the unclaimed amount of airdrop to the treasury. \[airdrop_id, amount\]
+		Swept(AirdropId, Balance),
+	}
 
-		build(|config: &GenesisConfig<T>| {
-			config.airdrop_accounts.iter().for_each(|(account_id, airdrop_currency_id, initial_balance)| {
-				<AirDrops<T>>::mutate(account_id, airdrop_currency_id, | amount | *amount += *initial_balance)
-			})
-		})
+	/// The airdrop records of the genesis.
+	///
+	/// AirDrops: double_map AccountId, AirDropCurrencyId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn airdrops)]
+	pub type AirDrops<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, AirDropCurrencyId, Balance, ValueQuery>;
+
+	/// The next airdrop id.
+	///
+	/// NextAirdropId: AirdropId
+	#[pallet::storage]
+	#[pallet::getter(fn next_airdrop_id)]
+	pub type NextAirdropId<T: Config> = StorageValue<_, AirdropId, ValueQuery>;
+
+	/// The airdrops distributed with merkle proofs.
+	///
+	/// MerkleAirdrops: map AirdropId => Option<AirdropInfo>
+	#[pallet::storage]
+	#[pallet::getter(fn merkle_airdrops)]
+	pub type MerkleAirdrops<T: Config> =
+		StorageMap<_, Twox64Concat, AirdropId, AirdropInfo<T::BlockNumber, T::Hash>, OptionQuery>;
+
+	/// The bitmap of the claimed leaves of airdrops, each word records the claims of
+	/// `CLAIMED_BITS_PER_WORD` leaves.
+	///
+	/// ClaimedBitmap: double_map AirdropId, u32 => u128
+	#[pallet::storage]
+	#[pallet::getter(fn claimed_bitmap)]
+	pub type ClaimedBitmap<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, AirdropId, Twox64Concat, u32, u128, ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		pub airdrop_accounts: Vec<(T::AccountId, AirDropCurrencyId, Balance)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			GenesisConfig {
+				airdrop_accounts: vec![],
+			}
+		}
 	}
-}
 
-decl_event!(
-	pub enum Event<T> where
-		<T as system::Config>::AccountId,
-		AirDropCurrencyId = AirDropCurrencyId,
-		Balance = Balance,
-	{
-		/// \[to, currency_id, amount\]
-		Airdrop(AccountId, AirDropCurrencyId, Balance),
-		/// \[to, currency_id, amount\]
-		UpdateAirdrop(AccountId, AirDropCurrencyId, Balance),
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			self.airdrop_accounts
+				.iter()
+				.for_each(|(account_id, airdrop_currency_id, initial_balance)| {
+					AirDrops::<T>::mutate(account_id, airdrop_currency_id, |amount| {
+						*amount = amount.saturating_add(*initial_balance)
+					});
+				});
+		}
 	}
-);
 
-decl_module! {
-	pub struct Module<T: Config> for enum Call where origin: T::Origin {
-		fn deposit_event() = default;
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
 
-		#[weight = 10_000]
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Create an airdrop of `total` of `currency_id` to the recipients of the merkle tree.
+		/// The total amount is moved from the treasury to the module account.
+		///
+		/// The dispatch origin of this call must be `CreateOrigin`.
+		///
+		/// - `currency_id`: the currency of the airdrop.
+		/// - `merkle_root`: the merkle root of the recipients and their amounts.
+		/// - `recipients`: the number of the leaves of the merkle tree.
+		/// - `total`: the total amount of the airdrop.
+		/// - `expiry`: the block number the airdrop expires at.
+		#[pallet::weight(T::WeightInfo::create_airdrop())]
 		#[transactional]
-		pub fn airdrop(
-			origin,
-			to: <T::Lookup as StaticLookup>::Source,
-			currency_id: AirDropCurrencyId,
-			amount: Balance,
-		) {
-			ensure_root(origin)?;
-			let to = T::Lookup::lookup(to)?;
-			<AirDrops<T>>::mutate(&to, currency_id, |balance| *balance += amount);
-			Self::deposit_event(RawEvent::Airdrop(to, currency_id, amount));
+		pub fn create_airdrop(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			merkle_root: T::Hash,
+			recipients: u32,
+			#[pallet::compact] total: Balance,
+			expiry: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			T::CreateOrigin::ensure_origin(origin)?;
+			ensure!(
+				expiry > frame_system::Pallet::<T>::block_number(),
+				Error::<T>::InvalidExpiry
+			);
+			ensure!(!total.is_zero() && !recipients.is_zero(), Error::<T>::InvalidAmount);
+
+			T::Currency::transfer(currency_id, &T::TreasuryAccount::get(), &Self::account_id(), total)?;
+
+			let airdrop_id = NextAirdropId::<T>::try_mutate(|id| -> Result<AirdropId, DispatchError> {
+				let current_id = *id;
+				*id = id.checked_add(One::one()).ok_or(ArithmeticError::Overflow)?;
+				Ok(current_id)
+			})?;
+			MerkleAirdrops::<T>::insert(
+				airdrop_id,
+				AirdropInfo {
+					currency_id,
+					merkle_root,
+					recipients,
+					total,
+					claimed: Zero::zero(),
+					expiry,
+				},
+			);
+
+			Self::deposit_event(Event::AirdropCreated(
+				airdrop_id,
+				currency_id,
+				merkle_root,
+				total,
+				expiry,
+			));
+			Ok(().into())
+		}
+
+		/// Claim the amount of the leaf `(index, recipient, amount)` of the airdrop with its
+		/// merkle proof. Anyone can claim on behalf of the recipient, the amount is always paid
+		/// to the recipient.
+		///
+		/// - `airdrop_id`: the id of the airdrop.
+		/// - `index`: the index of the leaf.
+		/// - `recipient`: the recipient of the leaf.
+		/// - `amount`: the amount of the leaf.
+		/// - `proof`: the merkle proof of the leaf.
+		#[pallet::weight(T::WeightInfo::claim(proof.len() as u32))]
+		#[transactional]
+		pub fn claim(
+			origin: OriginFor<T>,
+			airdrop_id: AirdropId,
+			index: u32,
+			recipient: T::AccountId,
+			#[pallet::compact] amount: Balance,
+			proof: Vec<T::Hash>,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+
+			MerkleAirdrops::<T>::try_mutate(airdrop_id, |maybe_airdrop| -> DispatchResult {
+				let airdrop = maybe_airdrop.as_mut().ok_or(Error::<T>::AirdropNotFound)?;
+				ensure!(
+					frame_system::Pallet::<T>::block_number() < airdrop.expiry,
+					Error::<T>::AirdropExpired
+				);
+				ensure!(index < airdrop.recipients, Error::<T>::InvalidIndex);
+				ensure!(!Self::is_claimed(airdrop_id, index), Error::<T>::AlreadyClaimed);
+
+				let leaf = T::Hashing::hash_of(&(index, &recipient, amount));
+				ensure!(
+					Self::merkle_root(leaf, &proof) == airdrop.merkle_root,
+					Error::<T>::InvalidProof
+				);
+
+				let claimed = airdrop.claimed.saturating_add(amount);
+				ensure!(claimed <= airdrop.total, Error::<T>::ExceedTotal);
+				airdrop.claimed = claimed;
+
+				T::Currency::transfer(airdrop.currency_id, &Self::account_id(), &recipient, amount)?;
+				ClaimedBitmap::<T>::mutate(airdrop_id, index / CLAIMED_BITS_PER_WORD, |word| {
+					*word |= 1u128 << (index % CLAIMED_BITS_PER_WORD)
+				});
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::Claimed(airdrop_id, index, recipient, amount));
+			Ok(().into())
 		}
 
-		#[weight = 10_000]
+		/// Sweep the unclaimed amount of the expired airdrop back to the treasury. The airdrop
+		/// and its claims are removed.
+		///
+		/// - `airdrop_id`: the id of the airdrop.
+		#[pallet::weight(T::WeightInfo::sweep())]
 		#[transactional]
-		pub fn update_airdrop(
-			origin,
-			to: <T::Lookup as StaticLookup>::Source,
-			currency_id: AirDropCurrencyId,
-			amount: Balance,
-		) {
-			ensure_root(origin)?;
-			let to = T::Lookup::lookup(to)?;
-			<AirDrops<T>>::insert(&to, currency_id, amount);
-			Self::deposit_event(RawEvent::UpdateAirdrop(to, currency_id, amount));
+		pub fn sweep(origin: OriginFor<T>, airdrop_id: AirdropId) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let airdrop = Self::merkle_airdrops(airdrop_id).ok_or(Error::<T>::AirdropNotFound)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() >= airdrop.expiry,
+				Error::<T>::AirdropNotExpired
+			);
+
+			let unclaimed = airdrop.total.saturating_sub(airdrop.claimed);
+			T::Currency::transfer(
+				airdrop.currency_id,
+				&Self::account_id(),
+				&T::TreasuryAccount::get(),
+				unclaimed,
+			)?;
+			MerkleAirdrops::<T>::remove(airdrop_id);
+			ClaimedBitmap::<T>::remove_prefix(airdrop_id);
+
+			Self::deposit_event(Event::Swept(airdrop_id, unclaimed));
+			Ok(().into())
 		}
 	}
 }
 
-impl<T: Config> Pallet<T> {}
+impl<T: Config> Pallet<T> {
+	/// The account to keep the unclaimed amounts of airdrops.
+	pub fn account_id() -> T::AccountId {
+		T::PalletId::get().into_account()
+	}
+
+	/// Whether the leaf of `index` of the airdrop has been claimed.
+	pub fn is_claimed(airdrop_id: AirdropId, index: u32) -> bool {
+		Self::claimed_bitmap(airdrop_id, index / CLAIMED_BITS_PER_WORD) & (1u128 << (index % CLAIMED_BITS_PER_WORD))
+			!= 0
+	}
+
+	/// The merkle root computed from `leaf` and its `proof`, each node is the hash of its
+	/// children in ascending order.
+	pub fn merkle_root(leaf: T::Hash, proof: &[T::Hash]) -> T::Hash {
+		proof.iter().fold(leaf, |node, sibling| {
+			if node <= *sibling {
+				T::Hashing::hash_of(&(node, sibling))
+			} else {
+				T::Hashing::hash_of(&(sibling, node))
+			}
+		})
+	}
+}
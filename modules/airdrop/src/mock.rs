@@ -22,6 +22,9 @@
 
 use super::*;
 use frame_support::{construct_runtime, parameter_types};
+use frame_system::EnsureRoot;
+use orml_traits::parameter_type_with_key;
+use primitives::{Amount, TokenSymbol};
 use sp_core::H256;
 use sp_runtime::{testing::Header, traits::IdentityLookup};
 
@@ -31,6 +34,8 @@ pub type BlockNumber = u64;
 pub const ALICE: AccountId = 0;
 pub const BOB: AccountId = 1;
 pub const CHARLIE: AccountId = 2;
+pub const TREASURY: AccountId = 10;
+pub const AUSD: CurrencyId = CurrencyId::Token(TokenSymbol::AUSD);
 pub const ACA: AirDropCurrencyId = AirDropCurrencyId::ACA;
 pub const KAR: AirDropCurrencyId = AirDropCurrencyId::KAR;
 
@@ -68,8 +73,35 @@ impl frame_system::Config for Runtime {
 	type OnSetCode = ();
 }
 
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: CurrencyId| -> Balance {
+		Default::default()
+	};
+}
+
+impl orml_tokens::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type Amount = Amount;
+	type CurrencyId = CurrencyId;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type OnDust = ();
+	type MaxLocks = ();
+}
+
+parameter_types! {
+	pub const TreasuryAccount: AccountId = TREASURY;
+	pub const AirdropPalletId: PalletId = PalletId(*b"aca/aird");
+}
+
 impl Config for Runtime {
 	type Event = Event;
+	type Currency = Tokens;
+	type TreasuryAccount = TreasuryAccount;
+	type PalletId = AirdropPalletId;
+	type CreateOrigin = EnsureRoot<AccountId>;
+	type WeightInfo = ();
 }
 
 pub type Block = sp_runtime::generic::Block<Header, UncheckedExtrinsic>;
@@ -82,6 +114,7 @@ construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic
 	{
 		System: frame_system::{Pallet, Call, Storage, Config, Event<T>},
+		Tokens: orml_tokens::{Pallet, Storage, Event<T>, Config<T>},
 		AirDrop: airdrop::{Pallet, Call, Storage, Event<T>, Config<T>},
 	}
 );
@@ -102,11 +135,20 @@ impl ExtBuilder {
 			.build_storage::<Runtime>()
 			.unwrap();
 
+		orml_tokens::GenesisConfig::<Runtime> {
+			balances: vec![(TREASURY, AUSD, 1000)],
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
 		airdrop::GenesisConfig::<Runtime> {
 			airdrop_accounts: vec![(CHARLIE, KAR, 100), (CHARLIE, KAR, 50), (CHARLIE, ACA, 80)],
 		}
 		.assimilate_storage(&mut t)
 		.unwrap();
-		t.into()
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
 	}
 }
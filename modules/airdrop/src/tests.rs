@@ -22,31 +22,180 @@
 
 use super::*;
 use frame_support::{assert_noop, assert_ok};
-use mock::{Airdrop, Event, ExtBuilder, Origin, System, ACA, ALICE, BOB, CHARLIE, KAR};
-use sp_runtime::traits::BadOrigin;
+use mock::{
+	Airdrop, Event, ExtBuilder, Origin, Runtime, System, Tokens, ACA, ALICE, AUSD, BOB, CHARLIE, KAR, TREASURY,
+};
+use orml_traits::MultiCurrency;
+use sp_core::H256;
+use sp_runtime::traits::{BadOrigin, BlakeTwo256};
+
+fn leaf(index: u32, recipient: u128, amount: Balance) -> H256 {
+	BlakeTwo256::hash_of(&(index, &recipient, amount))
+}
+
+fn node(a: H256, b: H256) -> H256 {
+	if a <= b {
+		BlakeTwo256::hash_of(&(a, b))
+	} else {
+		BlakeTwo256::hash_of(&(b, a))
+	}
+}
+
+// the merkle tree of (0, ALICE, 100), (1, BOB, 200) and (2, CHARLIE, 300), returns the root and
+// the proofs of the leaves
+fn merkle_tree() -> (H256, Vec<Vec<H256>>) {
+	let leaves = [leaf(0, ALICE, 100), leaf(1, BOB, 200), leaf(2, CHARLIE, 300)];
+	let root = node(node(leaves[0], leaves[1]), leaves[2]);
+	let proofs = vec![
+		vec![leaves[1], leaves[2]],
+		vec![leaves[0], leaves[2]],
+		vec![node(leaves[0], leaves[1])],
+	];
+	(root, proofs)
+}
+
+fn create_airdrop() {
+	let (root, _) = merkle_tree();
+	assert_ok!(Airdrop::create_airdrop(Origin::root(), AUSD, root, 3, 600, 100));
+}
 
 #[test]
-fn airdrop_work() {
+fn create_airdrop_works() {
 	ExtBuilder::default().build().execute_with(|| {
-		System::set_block_number(1);
-		assert_noop!(Airdrop::airdrop(Origin::signed(BOB), ALICE, KAR, 10000), BadOrigin,);
-		assert_ok!(Airdrop::airdrop(Origin::root(), ALICE, KAR, 10000));
-		System::assert_last_event(Event::AirDrop(RawEvent::Airdrop(ALICE, KAR, 10000)));
-		assert_eq!(Airdrop::airdrops(ALICE, KAR), 10000);
+		let (root, _) = merkle_tree();
+		assert_noop!(
+			Airdrop::create_airdrop(Origin::signed(ALICE), AUSD, root, 3, 600, 100),
+			BadOrigin
+		);
+		assert_noop!(
+			Airdrop::create_airdrop(Origin::root(), AUSD, root, 3, 600, 1),
+			Error::<Runtime>::InvalidExpiry
+		);
+		assert_noop!(
+			Airdrop::create_airdrop(Origin::root(), AUSD, root, 0, 600, 100),
+			Error::<Runtime>::InvalidAmount
+		);
+		assert_noop!(
+			Airdrop::create_airdrop(Origin::root(), AUSD, root, 3, 0, 100),
+			Error::<Runtime>::InvalidAmount
+		);
+
+		assert_ok!(Airdrop::create_airdrop(Origin::root(), AUSD, root, 3, 600, 100));
+		System::assert_last_event(Event::AirDrop(crate::Event::AirdropCreated(0, AUSD, root, 600, 100)));
+		assert_eq!(
+			Airdrop::merkle_airdrops(0),
+			Some(AirdropInfo {
+				currency_id: AUSD,
+				merkle_root: root,
+				recipients: 3,
+				total: 600,
+				claimed: 0,
+				expiry: 100,
+			})
+		);
+		assert_eq!(Airdrop::next_airdrop_id(), 1);
+		assert_eq!(Tokens::free_balance(AUSD, &TREASURY), 400);
+		assert_eq!(Tokens::free_balance(AUSD, &Airdrop::account_id()), 600);
 	});
 }
 
 #[test]
-fn update_airdrop_work() {
+fn claim_works() {
 	ExtBuilder::default().build().execute_with(|| {
-		System::set_block_number(1);
-		assert_ok!(Airdrop::airdrop(Origin::root(), ALICE, ACA, 10000));
-		assert_ok!(Airdrop::airdrop(Origin::root(), ALICE, ACA, 10000));
-		assert_eq!(Airdrop::airdrops(ALICE, ACA), 20000);
-		assert_noop!(Airdrop::update_airdrop(Origin::signed(BOB), ALICE, ACA, 0), BadOrigin,);
-		assert_ok!(Airdrop::update_airdrop(Origin::root(), ALICE, ACA, 0));
-		System::assert_last_event(Event::AirDrop(RawEvent::UpdateAirdrop(ALICE, ACA, 0)));
-		assert_eq!(Airdrop::airdrops(ALICE, ACA), 0);
+		create_airdrop();
+		let (_, proofs) = merkle_tree();
+
+		// anyone can claim on behalf of the recipient
+		assert_ok!(Airdrop::claim(Origin::signed(BOB), 0, 0, ALICE, 100, proofs[0].clone()));
+		System::assert_last_event(Event::AirDrop(crate::Event::Claimed(0, 0, ALICE, 100)));
+		assert_eq!(Tokens::free_balance(AUSD, &ALICE), 100);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 0);
+		assert!(Airdrop::is_claimed(0, 0));
+		assert!(!Airdrop::is_claimed(0, 1));
+		assert_noop!(
+			Airdrop::claim(Origin::signed(ALICE), 0, 0, ALICE, 100, proofs[0].clone()),
+			Error::<Runtime>::AlreadyClaimed
+		);
+
+		assert_ok!(Airdrop::claim(
+			Origin::signed(CHARLIE),
+			0,
+			2,
+			CHARLIE,
+			300,
+			proofs[2].clone()
+		));
+		assert_eq!(Tokens::free_balance(AUSD, &CHARLIE), 300);
+		assert_eq!(Airdrop::merkle_airdrops(0).unwrap().claimed, 400);
+		assert_eq!(Tokens::free_balance(AUSD, &Airdrop::account_id()), 200);
+	});
+}
+
+#[test]
+fn claim_fails() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (_, proofs) = merkle_tree();
+		assert_noop!(
+			Airdrop::claim(Origin::signed(BOB), 0, 1, BOB, 200, proofs[1].clone()),
+			Error::<Runtime>::AirdropNotFound
+		);
+
+		create_airdrop();
+		assert_noop!(
+			Airdrop::claim(Origin::signed(BOB), 0, 1, BOB, 201, proofs[1].clone()),
+			Error::<Runtime>::InvalidProof
+		);
+		assert_noop!(
+			Airdrop::claim(Origin::signed(BOB), 0, 1, ALICE, 200, proofs[1].clone()),
+			Error::<Runtime>::InvalidProof
+		);
+		assert_noop!(
+			Airdrop::claim(Origin::signed(BOB), 0, 0, BOB, 200, proofs[1].clone()),
+			Error::<Runtime>::InvalidProof
+		);
+		assert_noop!(
+			Airdrop::claim(Origin::signed(BOB), 0, 3, BOB, 200, proofs[1].clone()),
+			Error::<Runtime>::InvalidIndex
+		);
+
+		System::set_block_number(100);
+		assert_noop!(
+			Airdrop::claim(Origin::signed(BOB), 0, 1, BOB, 200, proofs[1].clone()),
+			Error::<Runtime>::AirdropExpired
+		);
+	});
+}
+
+#[test]
+fn sweep_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			Airdrop::sweep(Origin::signed(BOB), 0),
+			Error::<Runtime>::AirdropNotFound
+		);
+
+		create_airdrop();
+		let (_, proofs) = merkle_tree();
+		assert_ok!(Airdrop::claim(
+			Origin::signed(ALICE),
+			0,
+			0,
+			ALICE,
+			100,
+			proofs[0].clone()
+		));
+		assert_noop!(
+			Airdrop::sweep(Origin::signed(BOB), 0),
+			Error::<Runtime>::AirdropNotExpired
+		);
+
+		System::set_block_number(100);
+		assert_ok!(Airdrop::sweep(Origin::signed(BOB), 0));
+		System::assert_last_event(Event::AirDrop(crate::Event::Swept(0, 500)));
+		assert_eq!(Tokens::free_balance(AUSD, &TREASURY), 900);
+		assert_eq!(Tokens::free_balance(AUSD, &Airdrop::account_id()), 0);
+		assert_eq!(Airdrop::merkle_airdrops(0), None);
+		assert!(!Airdrop::is_claimed(0, 0));
 	});
 }
 
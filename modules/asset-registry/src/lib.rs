@@ -0,0 +1,308 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Asset Registry Module
+//!
+//! ## Overview
+//!
+//! The asset registry stores the metadata (name, symbol, decimals and minimal balance) of the
+//! assets on chain, and the mappings between the XCM locations and the ids of foreign assets.
+//! Foreign assets are registered by governance with their locations and get a new
+//! `CurrencyId::ForeignAsset` id. Bridged Erc20 contracts are registered with their minimal
+//! balance, the rest of the metadata is read from the contract. The registered metadata takes
+//! precedence over the metadata hard-coded in `primitives`, which can be overridden for tokens.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{pallet_prelude::*, transactional};
+use frame_system::pallet_prelude::*;
+use primitives::{evm::EvmAddress, Balance, CurrencyId, ForeignAssetId};
+use sp_runtime::traits::One;
+use sp_std::{boxed::Box, vec::Vec};
+use support::{AssetMetadata, AssetMetadataProvider, CurrencyIdMapping};
+use xcm::v0::MultiLocation;
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Mapping between CurrencyId and Erc20 address, which provides the metadata of Erc20
+		/// contracts.
+		type CurrencyIdMapping: CurrencyIdMapping;
+
+		/// The origin which may register and update assets.
+		type RegisterOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The location has been registered.
+		LocationExisted,
+		/// The asset id is not registered.
+		AssetIdNotExists,
+		/// The asset id has been registered.
+		AssetIdExisted,
+		/// There is no available foreign asset id.
+		NoAvailableForeignAssetId,
+		/// The metadata of the currency id can't be registered.
+		InvalidCurrencyId,
+		/// The metadata of the Erc20 contract can't be read.
+		InvalidErc20Contract,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Registered foreign asset. \[foreign_asset_id, location, metadata\]
+		ForeignAssetRegistered(ForeignAssetId, MultiLocation, AssetMetadata<Balance>),
+		/// Updated foreign asset. \[foreign_asset_id, location, metadata\]
+		ForeignAssetUpdated(ForeignAssetId, MultiLocation, AssetMetadata<Balance>),
+		/// Registered asset. \[currency_id, metadata\]
+		AssetRegistered(CurrencyId, AssetMetadata<Balance>),
+		/// Updated the metadata of asset. \[currency_id, metadata\]
+		AssetUpdated(CurrencyId, AssetMetadata<Balance>),
+	}
+
+	/// The id of the next foreign asset to be registered.
+	///
+	/// NextForeignAssetId: ForeignAssetId
+	#[pallet::storage]
+	#[pallet::getter(fn next_foreign_asset_id)]
+	pub type NextForeignAssetId<T: Config> = StorageValue<_, ForeignAssetId, ValueQuery>;
+
+	/// The XCM locations of foreign assets.
+	///
+	/// ForeignAssetLocations: map ForeignAssetId => Option<MultiLocation>
+	#[pallet::storage]
+	#[pallet::getter(fn foreign_asset_locations)]
+	pub type ForeignAssetLocations<T: Config> = StorageMap<_, Twox64Concat, ForeignAssetId, MultiLocation, OptionQuery>;
+
+	/// The currency ids of the XCM locations.
+	///
+	/// LocationToCurrencyIds: map MultiLocation => Option<CurrencyId>
+	#[pallet::storage]
+	#[pallet::getter(fn location_to_currency_ids)]
+	pub type LocationToCurrencyIds<T: Config> = StorageMap<_, Twox64Concat, MultiLocation, CurrencyId, OptionQuery>;
+
+	/// The metadata of registered assets.
+	///
+	/// AssetMetadatas: map CurrencyId => Option<AssetMetadata>
+	#[pallet::storage]
+	#[pallet::getter(fn asset_metadatas)]
+	pub type AssetMetadatas<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, AssetMetadata<Balance>, OptionQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Register a foreign asset with its XCM location and metadata, it's assigned the next
+		/// foreign asset id.
+		///
+		/// The dispatch origin of this call must be `RegisterOrigin`.
+		///
+		/// - `location`: the XCM location of the asset.
+		/// - `metadata`: the metadata of the asset.
+		#[pallet::weight(T::WeightInfo::register_foreign_asset())]
+		#[transactional]
+		pub fn register_foreign_asset(
+			origin: OriginFor<T>,
+			location: Box<MultiLocation>,
+			metadata: Box<AssetMetadata<Balance>>,
+		) -> DispatchResultWithPostInfo {
+			T::RegisterOrigin::ensure_origin(origin)?;
+
+			let foreign_asset_id = Self::do_register_foreign_asset(&location, &metadata)?;
+
+			Self::deposit_event(Event::ForeignAssetRegistered(foreign_asset_id, *location, *metadata));
+			Ok(().into())
+		}
+
+		/// Update the XCM location and metadata of a registered foreign asset.
+		///
+		/// The dispatch origin of this call must be `RegisterOrigin`.
+		///
+		/// - `foreign_asset_id`: the id of the foreign asset.
+		/// - `location`: the XCM location of the asset.
+		/// - `metadata`: the metadata of the asset.
+		#[pallet::weight(T::WeightInfo::update_foreign_asset())]
+		#[transactional]
+		pub fn update_foreign_asset(
+			origin: OriginFor<T>,
+			foreign_asset_id: ForeignAssetId,
+			location: Box<MultiLocation>,
+			metadata: Box<AssetMetadata<Balance>>,
+		) -> DispatchResultWithPostInfo {
+			T::RegisterOrigin::ensure_origin(origin)?;
+
+			Self::do_update_foreign_asset(foreign_asset_id, &location, &metadata)?;
+
+			Self::deposit_event(Event::ForeignAssetUpdated(foreign_asset_id, *location, *metadata));
+			Ok(().into())
+		}
+
+		/// Register a bridged Erc20 contract as asset, the name, symbol and decimals are read from
+		/// the contract.
+		///
+		/// The dispatch origin of this call must be `RegisterOrigin`.
+		///
+		/// - `contract`: the Erc20 contract address.
+		/// - `minimal_balance`: the minimal balance of the asset.
+		#[pallet::weight(T::WeightInfo::register_erc20_asset())]
+		#[transactional]
+		pub fn register_erc20_asset(
+			origin: OriginFor<T>,
+			contract: EvmAddress,
+			minimal_balance: Balance,
+		) -> DispatchResultWithPostInfo {
+			T::RegisterOrigin::ensure_origin(origin)?;
+
+			let currency_id = CurrencyId::Erc20(contract);
+			ensure!(
+				!AssetMetadatas::<T>::contains_key(currency_id),
+				Error::<T>::AssetIdExisted
+			);
+
+			T::CurrencyIdMapping::set_erc20_mapping(contract)?;
+			let metadata = AssetMetadata {
+				name: T::CurrencyIdMapping::name(currency_id).ok_or(Error::<T>::InvalidErc20Contract)?,
+				symbol: T::CurrencyIdMapping::symbol(currency_id).ok_or(Error::<T>::InvalidErc20Contract)?,
+				decimals: T::CurrencyIdMapping::decimals(currency_id).ok_or(Error::<T>::InvalidErc20Contract)?,
+				minimal_balance,
+			};
+			AssetMetadatas::<T>::insert(currency_id, &metadata);
+
+			Self::deposit_event(Event::AssetRegistered(currency_id, metadata));
+			Ok(().into())
+		}
+
+		/// Set the metadata of a token, which overrides the metadata hard-coded in `primitives`,
+		/// or update the metadata of a registered Erc20 or foreign asset.
+		///
+		/// The dispatch origin of this call must be `RegisterOrigin`.
+		///
+		/// - `currency_id`: the currency id of the asset.
+		/// - `metadata`: the metadata of the asset.
+		#[pallet::weight(T::WeightInfo::update_asset_metadata())]
+		#[transactional]
+		pub fn update_asset_metadata(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			metadata: Box<AssetMetadata<Balance>>,
+		) -> DispatchResultWithPostInfo {
+			T::RegisterOrigin::ensure_origin(origin)?;
+
+			match currency_id {
+				CurrencyId::Token(_) => {}
+				CurrencyId::Erc20(_) | CurrencyId::ForeignAsset(_) => ensure!(
+					AssetMetadatas::<T>::contains_key(currency_id),
+					Error::<T>::AssetIdNotExists
+				),
+				_ => return Err(Error::<T>::InvalidCurrencyId.into()),
+			}
+			AssetMetadatas::<T>::insert(currency_id, &*metadata);
+
+			Self::deposit_event(Event::AssetUpdated(currency_id, *metadata));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	fn do_register_foreign_asset(
+		location: &MultiLocation,
+		metadata: &AssetMetadata<Balance>,
+	) -> Result<ForeignAssetId, DispatchError> {
+		let foreign_asset_id =
+			NextForeignAssetId::<T>::try_mutate(|current| -> Result<ForeignAssetId, DispatchError> {
+				let id = *current;
+				*current = current
+					.checked_add(One::one())
+					.ok_or(Error::<T>::NoAvailableForeignAssetId)?;
+				Ok(id)
+			})?;
+		let currency_id = CurrencyId::ForeignAsset(foreign_asset_id);
+
+		LocationToCurrencyIds::<T>::try_mutate(location, |maybe_currency_id| -> DispatchResult {
+			ensure!(maybe_currency_id.is_none(), Error::<T>::LocationExisted);
+			*maybe_currency_id = Some(currency_id);
+			Ok(())
+		})?;
+		ForeignAssetLocations::<T>::insert(foreign_asset_id, location);
+		AssetMetadatas::<T>::insert(currency_id, metadata);
+
+		Ok(foreign_asset_id)
+	}
+
+	fn do_update_foreign_asset(
+		foreign_asset_id: ForeignAssetId,
+		location: &MultiLocation,
+		metadata: &AssetMetadata<Balance>,
+	) -> DispatchResult {
+		let currency_id = CurrencyId::ForeignAsset(foreign_asset_id);
+
+		ForeignAssetLocations::<T>::try_mutate(foreign_asset_id, |maybe_location| -> DispatchResult {
+			let old_location = maybe_location.as_mut().ok_or(Error::<T>::AssetIdNotExists)?;
+
+			if old_location != location {
+				LocationToCurrencyIds::<T>::try_mutate(location, |maybe_currency_id| -> DispatchResult {
+					ensure!(maybe_currency_id.is_none(), Error::<T>::LocationExisted);
+					*maybe_currency_id = Some(currency_id);
+					Ok(())
+				})?;
+				LocationToCurrencyIds::<T>::remove(&*old_location);
+				*old_location = location.clone();
+			}
+			Ok(())
+		})?;
+		AssetMetadatas::<T>::insert(currency_id, metadata);
+
+		Ok(())
+	}
+
+	/// The currency ids of the foreign assets registered with their locations.
+	pub fn foreign_assets() -> Vec<(CurrencyId, MultiLocation)> {
+		ForeignAssetLocations::<T>::iter()
+			.map(|(foreign_asset_id, location)| (CurrencyId::ForeignAsset(foreign_asset_id), location))
+			.collect()
+	}
+}
+
+impl<T: Config> AssetMetadataProvider<Balance> for Pallet<T> {
+	fn get_asset_metadata(currency_id: CurrencyId) -> Option<AssetMetadata<Balance>> {
+		Self::asset_metadatas(currency_id)
+	}
+}
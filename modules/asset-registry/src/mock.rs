@@ -0,0 +1,159 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Mocks for asset registry module.
+
+#![cfg(test)]
+
+use super::*;
+
+use crate as asset_registry;
+use frame_support::{construct_runtime, ord_parameter_types, parameter_types};
+use frame_system::EnsureSignedBy;
+use primitives::TokenSymbol;
+use sp_core::{H160, H256};
+use sp_runtime::{testing::Header, traits::IdentityLookup, DispatchResult};
+use sp_std::cell::RefCell;
+use std::collections::HashSet;
+
+pub type AccountId = u128;
+pub type BlockNumber = u64;
+
+pub const ALICE: AccountId = 0;
+pub const DOT: CurrencyId = CurrencyId::Token(TokenSymbol::DOT);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Runtime {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+thread_local! {
+	static ERC20_MAPPINGS: RefCell<HashSet<EvmAddress>> = RefCell::new(HashSet::new());
+}
+
+pub fn erc20_address() -> EvmAddress {
+	H160::from_low_u64_be(0x2000000)
+}
+
+pub fn mock_erc20_contract() -> CurrencyId {
+	CurrencyId::Erc20(erc20_address())
+}
+
+// Only `erc20_address()` is a valid Erc20 contract.
+pub struct MockCurrencyIdMapping;
+impl CurrencyIdMapping for MockCurrencyIdMapping {
+	fn set_erc20_mapping(address: EvmAddress) -> DispatchResult {
+		ERC20_MAPPINGS.with(|v| v.borrow_mut().insert(address));
+		Ok(())
+	}
+
+	fn get_evm_address(_currency_id: u32) -> Option<EvmAddress> {
+		None
+	}
+
+	fn name(currency_id: CurrencyId) -> Option<Vec<u8>> {
+		(currency_id == mock_erc20_contract()).then(|| b"Long Name".to_vec())
+	}
+
+	fn symbol(currency_id: CurrencyId) -> Option<Vec<u8>> {
+		(currency_id == mock_erc20_contract()).then(|| b"TestToken".to_vec())
+	}
+
+	fn decimals(currency_id: CurrencyId) -> Option<u8> {
+		(currency_id == mock_erc20_contract()).then(|| 17)
+	}
+
+	fn encode_evm_address(_v: CurrencyId) -> Option<EvmAddress> {
+		None
+	}
+
+	fn decode_evm_address(_v: EvmAddress) -> Option<CurrencyId> {
+		None
+	}
+}
+
+pub fn erc20_mapped(address: EvmAddress) -> bool {
+	ERC20_MAPPINGS.with(|v| v.borrow().contains(&address))
+}
+
+ord_parameter_types! {
+	pub const CouncilAccount: AccountId = 1;
+}
+
+impl Config for Runtime {
+	type Event = Event;
+	type CurrencyIdMapping = MockCurrencyIdMapping;
+	type RegisterOrigin = EnsureSignedBy<CouncilAccount, AccountId>;
+	type WeightInfo = ();
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		AssetRegistryModule: asset_registry::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+#[derive(Default)]
+pub struct ExtBuilder;
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		ERC20_MAPPINGS.with(|v| v.borrow_mut().clear());
+
+		let t = frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap();
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
@@ -0,0 +1,295 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for asset registry module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+use primitives::TokenSymbol;
+use sp_core::H160;
+use sp_runtime::traits::BadOrigin;
+use xcm::v0::Junction;
+
+fn dot_location() -> MultiLocation {
+	MultiLocation::X1(Junction::Parent)
+}
+
+fn statemine_location() -> MultiLocation {
+	MultiLocation::X3(
+		Junction::Parent,
+		Junction::Parachain(1000),
+		Junction::GeneralIndex { id: 1 },
+	)
+}
+
+fn metadata(decimals: u8, minimal_balance: Balance) -> AssetMetadata<Balance> {
+	AssetMetadata {
+		name: b"Token Name".to_vec(),
+		symbol: b"TN".to_vec(),
+		decimals,
+		minimal_balance,
+	}
+}
+
+#[test]
+fn register_foreign_asset_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			AssetRegistryModule::register_foreign_asset(
+				Origin::signed(ALICE),
+				Box::new(dot_location()),
+				Box::new(metadata(12, 1))
+			),
+			BadOrigin
+		);
+
+		assert_ok!(AssetRegistryModule::register_foreign_asset(
+			Origin::signed(CouncilAccount::get()),
+			Box::new(dot_location()),
+			Box::new(metadata(12, 1))
+		));
+		System::assert_last_event(Event::AssetRegistryModule(crate::Event::ForeignAssetRegistered(
+			0,
+			dot_location(),
+			metadata(12, 1),
+		)));
+		assert_eq!(AssetRegistryModule::next_foreign_asset_id(), 1);
+		assert_eq!(AssetRegistryModule::foreign_asset_locations(0), Some(dot_location()));
+		assert_eq!(
+			AssetRegistryModule::location_to_currency_ids(dot_location()),
+			Some(CurrencyId::ForeignAsset(0))
+		);
+		assert_eq!(
+			AssetRegistryModule::get_asset_metadata(CurrencyId::ForeignAsset(0)),
+			Some(metadata(12, 1))
+		);
+
+		assert_noop!(
+			AssetRegistryModule::register_foreign_asset(
+				Origin::signed(CouncilAccount::get()),
+				Box::new(dot_location()),
+				Box::new(metadata(10, 1))
+			),
+			Error::<Runtime>::LocationExisted
+		);
+
+		NextForeignAssetId::<Runtime>::put(ForeignAssetId::MAX);
+		assert_noop!(
+			AssetRegistryModule::register_foreign_asset(
+				Origin::signed(CouncilAccount::get()),
+				Box::new(statemine_location()),
+				Box::new(metadata(10, 1))
+			),
+			Error::<Runtime>::NoAvailableForeignAssetId
+		);
+	});
+}
+
+#[test]
+fn update_foreign_asset_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			AssetRegistryModule::update_foreign_asset(
+				Origin::signed(CouncilAccount::get()),
+				0,
+				Box::new(dot_location()),
+				Box::new(metadata(12, 1))
+			),
+			Error::<Runtime>::AssetIdNotExists
+		);
+
+		assert_ok!(AssetRegistryModule::register_foreign_asset(
+			Origin::signed(CouncilAccount::get()),
+			Box::new(dot_location()),
+			Box::new(metadata(12, 1))
+		));
+		assert_ok!(AssetRegistryModule::register_foreign_asset(
+			Origin::signed(CouncilAccount::get()),
+			Box::new(statemine_location()),
+			Box::new(metadata(10, 1))
+		));
+
+		assert_noop!(
+			AssetRegistryModule::update_foreign_asset(
+				Origin::signed(ALICE),
+				0,
+				Box::new(dot_location()),
+				Box::new(metadata(12, 2))
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			AssetRegistryModule::update_foreign_asset(
+				Origin::signed(CouncilAccount::get()),
+				0,
+				Box::new(statemine_location()),
+				Box::new(metadata(12, 2))
+			),
+			Error::<Runtime>::LocationExisted
+		);
+
+		// update metadata only
+		assert_ok!(AssetRegistryModule::update_foreign_asset(
+			Origin::signed(CouncilAccount::get()),
+			0,
+			Box::new(dot_location()),
+			Box::new(metadata(12, 2))
+		));
+		System::assert_last_event(Event::AssetRegistryModule(crate::Event::ForeignAssetUpdated(
+			0,
+			dot_location(),
+			metadata(12, 2),
+		)));
+		assert_eq!(
+			AssetRegistryModule::asset_metadatas(CurrencyId::ForeignAsset(0)),
+			Some(metadata(12, 2))
+		);
+
+		// update location
+		let new_location = MultiLocation::X2(Junction::Parent, Junction::Parachain(2000));
+		assert_ok!(AssetRegistryModule::update_foreign_asset(
+			Origin::signed(CouncilAccount::get()),
+			1,
+			Box::new(new_location.clone()),
+			Box::new(metadata(10, 1))
+		));
+		assert_eq!(
+			AssetRegistryModule::foreign_asset_locations(1),
+			Some(new_location.clone())
+		);
+		assert_eq!(
+			AssetRegistryModule::location_to_currency_ids(new_location),
+			Some(CurrencyId::ForeignAsset(1))
+		);
+		assert_eq!(
+			AssetRegistryModule::location_to_currency_ids(statemine_location()),
+			None
+		);
+	});
+}
+
+#[test]
+fn register_erc20_asset_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			AssetRegistryModule::register_erc20_asset(Origin::signed(ALICE), erc20_address(), 1),
+			BadOrigin
+		);
+		assert_noop!(
+			AssetRegistryModule::register_erc20_asset(
+				Origin::signed(CouncilAccount::get()),
+				H160::from_low_u64_be(1),
+				1
+			),
+			Error::<Runtime>::InvalidErc20Contract
+		);
+
+		assert_ok!(AssetRegistryModule::register_erc20_asset(
+			Origin::signed(CouncilAccount::get()),
+			erc20_address(),
+			1
+		));
+		let expected_metadata = AssetMetadata {
+			name: b"Long Name".to_vec(),
+			symbol: b"TestToken".to_vec(),
+			decimals: 17,
+			minimal_balance: 1,
+		};
+		System::assert_last_event(Event::AssetRegistryModule(crate::Event::AssetRegistered(
+			mock_erc20_contract(),
+			expected_metadata.clone(),
+		)));
+		assert!(erc20_mapped(erc20_address()));
+		assert_eq!(
+			AssetRegistryModule::asset_metadatas(mock_erc20_contract()),
+			Some(expected_metadata)
+		);
+
+		assert_noop!(
+			AssetRegistryModule::register_erc20_asset(Origin::signed(CouncilAccount::get()), erc20_address(), 1),
+			Error::<Runtime>::AssetIdExisted
+		);
+	});
+}
+
+#[test]
+fn update_asset_metadata_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			AssetRegistryModule::update_asset_metadata(Origin::signed(ALICE), DOT, Box::new(metadata(10, 1))),
+			BadOrigin
+		);
+
+		assert_ok!(AssetRegistryModule::update_asset_metadata(
+			Origin::signed(CouncilAccount::get()),
+			DOT,
+			Box::new(metadata(10, 1))
+		));
+		System::assert_last_event(Event::AssetRegistryModule(crate::Event::AssetUpdated(
+			DOT,
+			metadata(10, 1),
+		)));
+		assert_eq!(AssetRegistryModule::get_asset_metadata(DOT), Some(metadata(10, 1)));
+
+		assert_noop!(
+			AssetRegistryModule::update_asset_metadata(
+				Origin::signed(CouncilAccount::get()),
+				mock_erc20_contract(),
+				Box::new(metadata(10, 1))
+			),
+			Error::<Runtime>::AssetIdNotExists
+		);
+		assert_noop!(
+			AssetRegistryModule::update_asset_metadata(
+				Origin::signed(CouncilAccount::get()),
+				CurrencyId::ForeignAsset(0),
+				Box::new(metadata(10, 1))
+			),
+			Error::<Runtime>::AssetIdNotExists
+		);
+		assert_noop!(
+			AssetRegistryModule::update_asset_metadata(
+				Origin::signed(CouncilAccount::get()),
+				CurrencyId::DexShare(
+					primitives::DexShare::Token(TokenSymbol::ACA),
+					primitives::DexShare::Token(TokenSymbol::AUSD)
+				),
+				Box::new(metadata(10, 1))
+			),
+			Error::<Runtime>::InvalidCurrencyId
+		);
+
+		assert_ok!(AssetRegistryModule::register_erc20_asset(
+			Origin::signed(CouncilAccount::get()),
+			erc20_address(),
+			1
+		));
+		assert_ok!(AssetRegistryModule::update_asset_metadata(
+			Origin::signed(CouncilAccount::get()),
+			mock_erc20_contract(),
+			Box::new(metadata(10, 2))
+		));
+		assert_eq!(
+			AssetRegistryModule::get_asset_metadata(mock_erc20_contract()),
+			Some(metadata(10, 2))
+		);
+	});
+}
@@ -24,6 +24,10 @@
 //! business. Auction types include:
 //!   - `collateral auction`: sell collateral assets for getting stable currency to eliminate the
 //!     system's bad debit by auction
+//!
+//! After emergency shutdown, the active auctions which aren't in reverse stage are cancelled in
+//! the background by the idle scheduler, with the unsigned transactions of the offchain worker as
+//! the fallback.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
@@ -43,14 +47,17 @@ use sp_runtime::{
 		storage_lock::{StorageLock, Time},
 		Duration,
 	},
-	traits::{CheckedDiv, Saturating, Zero},
+	traits::{CheckedDiv, Saturating, UniqueSaturatedInto, Zero},
 	transaction_validity::{
 		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity, ValidTransaction,
 	},
 	DispatchError, DispatchResult, FixedPointNumber, RuntimeDebug,
 };
-use sp_std::prelude::*;
-use support::{AuctionManager, CDPTreasury, CDPTreasuryExtended, DEXManager, EmergencyShutdown, PriceProvider, Rate};
+use sp_std::{marker::PhantomData, prelude::*};
+use support::{
+	event_topic, event_topics::kind, AuctionManager, CDPTreasury, CDPTreasuryExtended, DEXManager, DispatchableTask,
+	EmergencyShutdown, EventTopicsRecorder, IdleScheduler, OnEmergencyShutdown, PriceProvider, Rate, TaskResult,
+};
 
 mod mock;
 mod tests;
@@ -123,6 +130,67 @@ impl<AccountId, BlockNumber> CollateralAuctionItem<AccountId, BlockNumber> {
 	}
 }
 
+/// The background tasks of the module.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum AuctionManagerTask<T: Config> {
+	/// Cancel the active collateral auctions after emergency shutdown, starting from the raw
+	/// storage key of `CollateralAuctions` after the last cancelled one.
+	CancelAuctions {
+		start_key: Option<Vec<u8>>,
+		#[codec(skip)]
+		_marker: PhantomData<T>,
+	},
+}
+
+impl<T: Config> AuctionManagerTask<T> {
+	/// The task to cancel all the active collateral auctions.
+	pub fn cancel_auctions() -> Self {
+		AuctionManagerTask::CancelAuctions {
+			start_key: None,
+			_marker: PhantomData,
+		}
+	}
+}
+
+impl<T: Config> DispatchableTask for AuctionManagerTask<T> {
+	fn dispatch(&mut self, weight: Weight) -> TaskResult {
+		match self {
+			AuctionManagerTask::CancelAuctions { start_key, .. } => {
+				let cancel_weight = T::WeightInfo::cancel_collateral_auction();
+				let limit = weight.checked_div(cancel_weight).unwrap_or(Weight::max_value());
+				let mut iterator = <CollateralAuctions<T> as IterableStorageMapExtended<_, _>>::iter(
+					Some(limit.unique_saturated_into()),
+					start_key.take(),
+				);
+
+				let mut count: Weight = 0;
+				#[allow(clippy::while_let_on_iterator)]
+				while let Some((collateral_auction_id, _)) = iterator.next() {
+					count += 1;
+					// the auctions in reverse stage are left to be dealt
+					if let Err(e) = Pallet::<T>::do_cancel(collateral_auction_id) {
+						log::debug!(
+							target: "auction-manager",
+							"cancel auctions: skip the collateral auction {:?}: {:?}",
+							collateral_auction_id, e,
+						);
+					}
+				}
+
+				if !iterator.finished {
+					*start_key = Some(iterator.storage_map_iterator.previous_key);
+				}
+
+				TaskResult {
+					result: Ok(()),
+					used_weight: cancel_weight.saturating_mul(count),
+					finished: iterator.finished,
+				}
+			}
+		}
+	}
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -173,6 +241,13 @@ pub mod module {
 		/// Emergency shutdown.
 		type EmergencyShutdown: EmergencyShutdown;
 
+		/// Recorder of the indexing topics of the bids.
+		type EventTopics: EventTopicsRecorder;
+
+		/// Schedules the background tasks, e.g. the cancellation of the active auctions after
+		/// emergency shutdown.
+		type IdleScheduler: IdleScheduler<AuctionManagerTask<Self>>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -268,8 +343,7 @@ pub mod module {
 		pub fn cancel(origin: OriginFor<T>, id: AuctionId) -> DispatchResultWithPostInfo {
 			ensure_none(origin)?;
 			ensure!(T::EmergencyShutdown::is_shutdown(), Error::<T>::MustAfterShutdown);
-			<Self as AuctionManager<T::AccountId>>::cancel_auction(id)?;
-			Self::deposit_event(Event::CancelAuction(id));
+			Self::do_cancel(id)?;
 			Ok(().into())
 		}
 	}
@@ -308,6 +382,13 @@ pub mod module {
 }
 
 impl<T: Config> Pallet<T> {
+	#[transactional]
+	fn do_cancel(id: AuctionId) -> DispatchResult {
+		<Self as AuctionManager<T::AccountId>>::cancel_auction(id)?;
+		Self::deposit_event(Event::CancelAuction(id));
+		Ok(())
+	}
+
 	fn get_last_bid(auction_id: AuctionId) -> Option<(T::AccountId, Balance)> {
 		T::Auction::auction_info(auction_id).and_then(|auction_info| auction_info.bid)
 	}
@@ -696,13 +777,17 @@ impl<T: Config> AuctionHandler<T::AccountId, Balance, T::BlockNumber, AuctionId>
 		new_bid: (T::AccountId, Balance),
 		last_bid: Option<(T::AccountId, Balance)>,
 	) -> OnNewBidResult<T::BlockNumber> {
+		let bidder = new_bid.0.clone();
 		let bid_result = Self::collateral_auction_bid_handler(now, id, new_bid, last_bid);
 
 		match bid_result {
-			Ok(new_auction_end_time) => OnNewBidResult {
-				accept_bid: true,
-				auction_end_change: Change::NewValue(Some(new_auction_end_time)),
-			},
+			Ok(new_auction_end_time) => {
+				T::EventTopics::record(&[event_topic(kind::AUCTION_BID, &id), event_topic(kind::ACCOUNT, &bidder)]);
+				OnNewBidResult {
+					accept_bid: true,
+					auction_end_change: Change::NewValue(Some(new_auction_end_time)),
+				}
+			}
 			Err(_) => OnNewBidResult {
 				accept_bid: false,
 				auction_end_change: Change::NoChange,
@@ -796,3 +881,16 @@ impl<T: Config> AuctionManager<T::AccountId> for Pallet<T> {
 		Self::total_target_in_auction()
 	}
 }
+
+impl<T: Config> OnEmergencyShutdown for Pallet<T> {
+	fn on_emergency_shutdown() {
+		if let Err(e) = T::IdleScheduler::schedule(AuctionManagerTask::cancel_auctions()) {
+			log::warn!(
+				target: "auction-manager",
+				"on_emergency_shutdown: failed to schedule the cancellation of the auctions: {:?}. \
+				The auctions are left to the offchain worker",
+				e
+			);
+		}
+	}
+}
@@ -31,7 +31,7 @@ use sp_runtime::{
 	traits::{AccountIdConversion, IdentityLookup, One as OneT},
 };
 use sp_std::cell::RefCell;
-pub use support::Price;
+pub use support::{Price, Ratio};
 
 pub type AccountId = u128;
 pub type BlockNumber = u64;
@@ -112,6 +112,9 @@ parameter_types! {
 	pub const MaxAuctionsCount: u32 = 10_000;
 	pub const CDPTreasuryPalletId: PalletId = PalletId(*b"aca/cdpt");
 	pub TreasuryAccount: AccountId = PalletId(*b"aca/hztr").into_account();
+	pub const GetNativeCurrencyId: CurrencyId = CurrencyId::Token(TokenSymbol::ACA);
+	pub const SurplusAllocationPeriod: BlockNumber = 10;
+	pub MaxSlippageSwapWithDEX: Ratio = Ratio::saturating_from_rational(50, 100);
 }
 
 impl cdp_treasury::Config for Runtime {
@@ -124,6 +127,11 @@ impl cdp_treasury::Config for Runtime {
 	type MaxAuctionsCount = MaxAuctionsCount;
 	type PalletId = CDPTreasuryPalletId;
 	type TreasuryAccount = TreasuryAccount;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type SavingRateAccount = TreasuryAccount;
+	type StabilityBufferAccount = TreasuryAccount;
+	type SurplusAllocationPeriod = SurplusAllocationPeriod;
+	type MaxSlippageSwapWithDEX = MaxSlippageSwapWithDEX;
 	type WeightInfo = ();
 }
 
@@ -168,6 +176,11 @@ impl module_dex::Config for Runtime {
 	type DEXIncentives = ();
 	type WeightInfo = ();
 	type ListingOrigin = EnsureSignedBy<One, AccountId>;
+	type OnSwap = ();
+	type EventTopics = ();
+	type SwapFeeShare = ();
+	type SwapFeeCurrencyId = GetNativeCurrencyId;
+	type SwapFeeReceiver = ();
 }
 
 thread_local! {
@@ -205,6 +218,8 @@ impl Config for Runtime {
 	type PriceSource = MockPriceSource;
 	type UnsignedPriority = UnsignedPriority;
 	type EmergencyShutdown = MockEmergencyShutdown;
+	type EventTopics = ();
+	type IdleScheduler = ();
 	type WeightInfo = ();
 }
 
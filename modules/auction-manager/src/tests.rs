@@ -417,3 +417,62 @@ fn cancel_collateral_auction_work() {
 		assert_eq!(bob_ref_count_1, bob_ref_count_0 - 1);
 	});
 }
+
+#[test]
+fn cancel_auctions_task_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, BTC, 20));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 10, 100));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 10, 100));
+		assert_ok!(AuctionModule::bid(Origin::signed(BOB), 1, 100));
+		mock_shutdown();
+
+		// at most one auction is cancelled in the weight
+		let cancel_weight = <() as WeightInfo>::cancel_collateral_auction();
+		let mut task = AuctionManagerTask::<Runtime>::cancel_auctions();
+		assert_eq!(
+			task.dispatch(cancel_weight),
+			TaskResult {
+				result: Ok(()),
+				used_weight: cancel_weight,
+				finished: false,
+			}
+		);
+		assert_eq!(
+			task.dispatch(Weight::max_value()),
+			TaskResult {
+				result: Ok(()),
+				used_weight: cancel_weight,
+				finished: true,
+			}
+		);
+
+		// the auction in reverse stage is skipped
+		assert_eq!(AuctionManagerModule::collateral_auctions(0).is_some(), false);
+		assert_eq!(AuctionModule::auction_info(0).is_some(), false);
+		assert_eq!(AuctionManagerModule::collateral_auctions(1).is_some(), true);
+		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 10);
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == Event::AuctionManagerModule(crate::Event::CancelAuction(0))));
+	});
+}
+
+#[test]
+fn on_emergency_shutdown_cancels_auctions() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, BTC, 10));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 10, 100));
+		assert_ok!(AuctionModule::bid(Origin::signed(BOB), 0, 80));
+		mock_shutdown();
+
+		// the task is dispatched right away without idle scheduler
+		AuctionManagerModule::on_emergency_shutdown();
+		System::assert_last_event(Event::AuctionManagerModule(crate::Event::CancelAuction(0)));
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 1000);
+		assert_eq!(AuctionManagerModule::collateral_auctions(0).is_some(), false);
+		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 0);
+	});
+}
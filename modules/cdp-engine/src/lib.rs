@@ -51,11 +51,12 @@ use sp_runtime::{
 	transaction_validity::{
 		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity, ValidTransaction,
 	},
-	DispatchError, DispatchResult, FixedPointNumber, RuntimeDebug,
+	DispatchError, DispatchResult, FixedPointNumber, PerThing, Permill, RuntimeDebug,
 };
 use sp_std::prelude::*;
 use support::{
-	CDPTreasury, CDPTreasuryExtended, EmergencyShutdown, ExchangeRate, Price, PriceProvider, Rate, Ratio, RiskManager,
+	CDPTreasury, CDPTreasuryExtended, EmergencyShutdown, ExchangeRate, HonzonInfo, Price, PriceProvider, Rate, Ratio,
+	RiskManager,
 };
 
 mod debit_exchange_rate_convertor;
@@ -102,6 +103,24 @@ pub struct RiskManagementParams {
 	pub required_collateral_ratio: Option<Ratio>,
 }
 
+/// The liquidation of the positions of a collateral simulated under a price shock.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct LiquidationSimulation<AccountId> {
+	/// The positions that are unsafe at the shocked price.
+	pub unsafe_positions: Vec<(AccountId, Position)>,
+
+	/// The total collateral of the unsafe positions, which is put in
+	/// collateral auctions if it can't be swapped with DEX.
+	pub total_collateral: Balance,
+
+	/// The total debit value of the unsafe positions.
+	pub total_debit_value: Balance,
+
+	/// The debit value of the unsafe positions that can't be covered by
+	/// their collateral at the shocked price.
+	pub bad_debt_value: Balance,
+}
+
 // typedef to help polkadot.js disambiguate Change with different generic
 // parameters
 type ChangeOptionRate = Change<Option<Rate>>;
@@ -699,6 +718,44 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Simulate the liquidation of the positions with `currency_id` as
+	/// collateral when its price drops by `price_drop`, without changing any
+	/// state. Returns `None` if the price of the collateral is unavailable.
+	pub fn simulate_liquidation(
+		currency_id: CurrencyId,
+		price_drop: Permill,
+	) -> Option<LiquidationSimulation<T::AccountId>> {
+		let feed_price = T::PriceSource::get_relative_price(currency_id, T::GetStableCurrencyId::get())?;
+		let shocked_price = feed_price.saturating_mul(price_drop.left_from_one().into());
+		let liquidation_ratio = Self::get_liquidation_ratio(currency_id);
+
+		let mut simulation = LiquidationSimulation {
+			unsafe_positions: Vec::new(),
+			total_collateral: Zero::zero(),
+			total_debit_value: Zero::zero(),
+			bad_debt_value: Zero::zero(),
+		};
+		for (who, position) in loans::Positions::<T>::iter_prefix(currency_id) {
+			let Position { collateral, debit } = position;
+			if debit.is_zero()
+				|| Self::calculate_collateral_ratio(currency_id, collateral, debit, shocked_price) >= liquidation_ratio
+			{
+				continue;
+			}
+
+			let debit_value = Self::get_debit_value(currency_id, debit);
+			let collateral_value = shocked_price.saturating_mul_int(collateral);
+			simulation.total_collateral = simulation.total_collateral.saturating_add(collateral);
+			simulation.total_debit_value = simulation.total_debit_value.saturating_add(debit_value);
+			simulation.bad_debt_value = simulation
+				.bad_debt_value
+				.saturating_add(debit_value.saturating_sub(collateral_value));
+			simulation.unsafe_positions.push((who, position));
+		}
+
+		Some(simulation)
+	}
+
 	pub fn maximum_total_debit_value(currency_id: CurrencyId) -> Balance {
 		Self::collateral_params(currency_id).maximum_total_debit_value
 	}
@@ -942,6 +999,32 @@ impl<T: Config> RiskManager<T::AccountId, CurrencyId, Balance, Balance> for Pall
 	}
 }
 
+impl<T: Config> HonzonInfo<CurrencyId, Balance> for Pallet<T> {
+	fn liquidation_ratio(currency_id: CurrencyId) -> Ratio {
+		Self::get_liquidation_ratio(currency_id)
+	}
+
+	fn interest_rate_per_sec(currency_id: CurrencyId) -> Rate {
+		Self::get_interest_rate_per_sec(currency_id)
+	}
+
+	fn maximum_total_debit_value(currency_id: CurrencyId) -> Balance {
+		Self::maximum_total_debit_value(currency_id)
+	}
+
+	fn total_debit_value(currency_id: CurrencyId) -> Balance {
+		Self::get_debit_value(currency_id, <LoansOf<T>>::total_positions(currency_id).debit)
+	}
+
+	fn collateral_currency_ids() -> Vec<CurrencyId> {
+		T::CollateralCurrencyIds::get()
+	}
+
+	fn is_shutdown() -> bool {
+		T::EmergencyShutdown::is_shutdown()
+	}
+}
+
 /// Pick a new PRN, in the range [0, `max`) (exclusive).
 fn pick_u32<R: RngCore>(rng: &mut R, max: u32) -> u32 {
 	rng.next_u32() % max
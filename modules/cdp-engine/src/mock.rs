@@ -138,6 +138,7 @@ impl loans::Config for Runtime {
 	type CDPTreasury = CDPTreasuryModule;
 	type PalletId = LoansPalletId;
 	type OnUpdateLoan = ();
+	type EventTopics = ();
 }
 
 thread_local! {
@@ -201,6 +202,7 @@ parameter_types! {
 	pub const MaxAuctionsCount: u32 = 10_000;
 	pub const CDPTreasuryPalletId: PalletId = PalletId(*b"aca/cdpt");
 	pub TreasuryAccount: AccountId = PalletId(*b"aca/hztr").into_account();
+	pub const SurplusAllocationPeriod: BlockNumber = 10;
 }
 
 impl cdp_treasury::Config for Runtime {
@@ -213,6 +215,11 @@ impl cdp_treasury::Config for Runtime {
 	type MaxAuctionsCount = MaxAuctionsCount;
 	type PalletId = CDPTreasuryPalletId;
 	type TreasuryAccount = TreasuryAccount;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type SavingRateAccount = TreasuryAccount;
+	type StabilityBufferAccount = TreasuryAccount;
+	type SurplusAllocationPeriod = SurplusAllocationPeriod;
+	type MaxSlippageSwapWithDEX = MaxSlippageSwapWithDEX;
 	type WeightInfo = ();
 }
 
@@ -233,6 +240,11 @@ impl module_dex::Config for Runtime {
 	type DEXIncentives = ();
 	type WeightInfo = ();
 	type ListingOrigin = EnsureSignedBy<One, AccountId>;
+	type OnSwap = ();
+	type EventTopics = ();
+	type SwapFeeShare = ();
+	type SwapFeeCurrencyId = GetNativeCurrencyId;
+	type SwapFeeReceiver = ();
 }
 
 parameter_types! {
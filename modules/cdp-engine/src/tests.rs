@@ -381,6 +381,69 @@ fn adjust_position_work() {
 	});
 }
 
+#[test]
+fn honzon_info_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPEngineModule::set_global_params(
+			Origin::signed(1),
+			Rate::saturating_from_rational(1, 10000),
+		));
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			DOT,
+			Change::NewValue(None),
+			Change::NewValue(None),
+			Change::NewValue(None),
+			Change::NewValue(None),
+			Change::NewValue(10000),
+		));
+		assert_eq!(
+			<CDPEngineModule as HonzonInfo<CurrencyId, Balance>>::liquidation_ratio(BTC),
+			Ratio::saturating_from_rational(3, 2)
+		);
+		assert_eq!(
+			<CDPEngineModule as HonzonInfo<CurrencyId, Balance>>::liquidation_ratio(DOT),
+			DefaultLiquidationRatio::get()
+		);
+		assert_eq!(
+			<CDPEngineModule as HonzonInfo<CurrencyId, Balance>>::interest_rate_per_sec(BTC),
+			Rate::saturating_from_rational(11, 100000)
+		);
+		assert_eq!(
+			<CDPEngineModule as HonzonInfo<CurrencyId, Balance>>::maximum_total_debit_value(BTC),
+			10000
+		);
+
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 50));
+		loans::TotalPositions::<Runtime>::mutate(DOT, |total_positions| total_positions.debit = 30);
+		assert_eq!(
+			<CDPEngineModule as HonzonInfo<CurrencyId, Balance>>::total_debit_value(BTC),
+			50
+		);
+		assert_eq!(
+			<CDPEngineModule as HonzonInfo<CurrencyId, Balance>>::total_debit_value(DOT),
+			30
+		);
+		assert_eq!(
+			<CDPEngineModule as HonzonInfo<CurrencyId, Balance>>::collateral_currency_ids(),
+			vec![BTC, DOT]
+		);
+
+		assert!(!<CDPEngineModule as HonzonInfo<CurrencyId, Balance>>::is_shutdown());
+		mock_shutdown();
+		assert!(<CDPEngineModule as HonzonInfo<CurrencyId, Balance>>::is_shutdown());
+	});
+}
+
 #[test]
 fn remain_debit_value_too_small_check() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -452,6 +515,87 @@ fn liquidate_unsafe_cdp_by_collateral_auction() {
 	});
 }
 
+#[test]
+fn simulate_liquidation_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(None),
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 60));
+		assert_ok!(CDPEngineModule::adjust_position(&BOB, BTC, 100, 40));
+
+		let simulate = |price_drop: Permill| {
+			let mut simulation = CDPEngineModule::simulate_liquidation(BTC, price_drop).unwrap();
+			simulation.unsafe_positions.sort_by_key(|(who, _)| *who);
+			simulation
+		};
+		assert_eq!(
+			simulate(Permill::zero()),
+			LiquidationSimulation {
+				unsafe_positions: vec![],
+				total_collateral: 0,
+				total_debit_value: 0,
+				bad_debt_value: 0,
+			}
+		);
+		assert_eq!(
+			simulate(Permill::from_percent(20)),
+			LiquidationSimulation {
+				unsafe_positions: vec![(
+					ALICE,
+					Position {
+						collateral: 100,
+						debit: 60
+					}
+				)],
+				total_collateral: 100,
+				total_debit_value: 60,
+				bad_debt_value: 0,
+			}
+		);
+		assert_eq!(
+			simulate(Permill::from_percent(50)),
+			LiquidationSimulation {
+				unsafe_positions: vec![
+					(
+						ALICE,
+						Position {
+							collateral: 100,
+							debit: 60
+						}
+					),
+					(
+						BOB,
+						Position {
+							collateral: 100,
+							debit: 40
+						}
+					),
+				],
+				total_collateral: 200,
+				total_debit_value: 100,
+				bad_debt_value: 10,
+			}
+		);
+
+		// the simulation doesn't change the positions
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 60);
+		assert_eq!(LoansModule::positions(BTC, BOB).debit, 40);
+
+		MockPriceSource::set_relative_price(None);
+		assert_eq!(
+			CDPEngineModule::simulate_liquidation(BTC, Permill::from_percent(20)),
+			None
+		);
+	});
+}
+
 #[test]
 fn get_interest_rate_per_sec_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -24,17 +24,23 @@
 //! CDPs, and handle excessive surplus or debits timely in order to keep the
 //! system healthy with low risk. It's the only entry for issuing/burning stable
 //! coin for whole system.
+//!
+//! The surplus that isn't needed to offset the debit pool is allocated each
+//! period to the strategies configured by governance: the savings rate pot,
+//! the buyback and burn of native currency, and the stability buffer of the
+//! selected currencies swapped with DEX. Each strategy takes a proportion of
+//! the surplus up to its cap, and the proportions never sum to more than 100%.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 
-use frame_support::{log, pallet_prelude::*, transactional, PalletId};
+use frame_support::{log, pallet_prelude::*, storage::with_transaction, transactional, PalletId};
 use frame_system::pallet_prelude::*;
 use orml_traits::{MultiCurrency, MultiCurrencyExtended};
 use primitives::{Balance, CurrencyId};
 use sp_runtime::{
-	traits::{AccountIdConversion, One, Zero},
-	ArithmeticError, DispatchError, DispatchResult, FixedPointNumber,
+	traits::{AccountIdConversion, CheckedAdd, One, Zero},
+	ArithmeticError, DispatchError, DispatchResult, FixedPointNumber, Permill, RuntimeDebug, TransactionOutcome,
 };
 use support::{AuctionManager, CDPTreasury, CDPTreasuryExtended, DEXManager, Ratio};
 
@@ -45,6 +51,27 @@ pub mod weights;
 pub use module::*;
 pub use weights::WeightInfo;
 
+/// The strategy to handle the surplus.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum SurplusStrategy {
+	/// Transfer the surplus to the savings rate pot.
+	SavingRate,
+	/// Swap the surplus to native currency with DEX and burn it.
+	BuybackAndBurn,
+	/// Swap the surplus to the currency with DEX and hold it in the stability
+	/// buffer.
+	StabilityBuffer(CurrencyId),
+}
+
+/// The allocation of the surplus to a strategy.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct SurplusAllocation {
+	/// The proportion of the surplus allocated each period.
+	pub proportion: Permill,
+	/// The maximum amount of the surplus allocated each period.
+	pub cap: Balance,
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -80,6 +107,26 @@ pub mod module {
 		#[pallet::constant]
 		type TreasuryAccount: Get<Self::AccountId>;
 
+		/// The native currency to buyback and burn with the surplus.
+		#[pallet::constant]
+		type GetNativeCurrencyId: Get<CurrencyId>;
+
+		/// The account of the savings rate pot to receive the surplus.
+		#[pallet::constant]
+		type SavingRateAccount: Get<Self::AccountId>;
+
+		/// The account to hold the stability buffer swapped from the surplus.
+		#[pallet::constant]
+		type StabilityBufferAccount: Get<Self::AccountId>;
+
+		/// The period to allocate the surplus to the strategies.
+		#[pallet::constant]
+		type SurplusAllocationPeriod: Get<Self::BlockNumber>;
+
+		/// The price impact limit when swap the surplus with DEX.
+		#[pallet::constant]
+		type MaxSlippageSwapWithDEX: Get<Ratio>;
+
 		/// The CDP treasury's module id, keep surplus and collateral assets
 		/// from liquidation.
 		#[pallet::constant]
@@ -99,6 +146,10 @@ pub mod module {
 		DebitPoolNotEnough,
 		/// The swap path is invalid
 		InvalidSwapPath,
+		/// The sum of the proportions of the surplus allocations exceeds 100%
+		InvalidSurplusAllocations,
+		/// The stable currency can't be the stability buffer
+		InvalidStabilityBuffer,
 	}
 
 	#[pallet::event]
@@ -107,6 +158,12 @@ pub mod module {
 		/// The expected amount size for per lot collateral auction of specific
 		/// collateral type updated. \[collateral_type, new_size\]
 		ExpectedCollateralAuctionSizeUpdated(CurrencyId, Balance),
+		/// The allocation of the surplus to the strategy updated. \[strategy,
+		/// allocation\]
+		SurplusAllocationUpdated(SurplusStrategy, Option<SurplusAllocation>),
+		/// The surplus allocated to the strategy. \[strategy, surplus_amount,
+		/// received_amount\]
+		SurplusAllocated(SurplusStrategy, Balance, Balance),
 	}
 
 	/// The expected amount size for per lot collateral auction of specific
@@ -125,6 +182,14 @@ pub mod module {
 	#[pallet::getter(fn debit_pool)]
 	pub type DebitPool<T: Config> = StorageValue<_, Balance, ValueQuery>;
 
+	/// The allocations of the surplus to the strategies.
+	///
+	/// SurplusAllocations: map SurplusStrategy => Option<SurplusAllocation>
+	#[pallet::storage]
+	#[pallet::getter(fn surplus_allocations)]
+	pub type SurplusAllocations<T: Config> =
+		StorageMap<_, Twox64Concat, SurplusStrategy, SurplusAllocation, OptionQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig {
 		pub expected_collateral_auction_size: Vec<(CurrencyId, Balance)>,
@@ -155,6 +220,16 @@ pub mod module {
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		/// Allocate the surplus to the strategies at the start of each period
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			if now % T::SurplusAllocationPeriod::get() == Zero::zero() {
+				let count = Self::allocate_surplus();
+				T::WeightInfo::on_initialize(count)
+			} else {
+				0
+			}
+		}
+
 		/// Handle excessive surplus or debits of system when block end
 		fn on_finalize(_now: T::BlockNumber) {
 			// offset the same amount between debit pool and surplus pool
@@ -216,6 +291,39 @@ pub mod module {
 			Self::deposit_event(Event::ExpectedCollateralAuctionSizeUpdated(currency_id, size));
 			Ok(().into())
 		}
+
+		/// Update the allocations of the surplus to the strategies, the sum of
+		/// the proportions of all the allocations must not exceed 100%.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `updates`: the strategies and their allocations, `None` to remove
+		///   the allocation of the strategy
+		#[pallet::weight((T::WeightInfo::set_surplus_allocations(updates.len() as u32), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_surplus_allocations(
+			origin: OriginFor<T>,
+			updates: Vec<(SurplusStrategy, Option<SurplusAllocation>)>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			for (strategy, allocation) in updates {
+				if let SurplusStrategy::StabilityBuffer(currency_id) = strategy {
+					ensure!(
+						currency_id != T::GetStableCurrencyId::get(),
+						Error::<T>::InvalidStabilityBuffer
+					);
+				}
+				SurplusAllocations::<T>::mutate_exists(strategy, |maybe_allocation| *maybe_allocation = allocation);
+				Self::deposit_event(Event::SurplusAllocationUpdated(strategy, allocation));
+			}
+
+			SurplusAllocations::<T>::iter_values()
+				.try_fold(Permill::zero(), |total, allocation| {
+					total.checked_add(&allocation.proportion)
+				})
+				.ok_or(Error::<T>::InvalidSurplusAllocations)?;
+			Ok(().into())
+		}
 	}
 }
 
@@ -241,6 +349,89 @@ impl<T: Config> Pallet<T> {
 			.saturating_sub(T::AuctionManagerHandler::get_total_collateral_in_auction(currency_id))
 	}
 
+	/// Allocate the surplus that isn't needed to offset the debit pool to the
+	/// strategies by their proportions and caps, returns the number of the
+	/// strategies. The allocation to a strategy that fails is skipped.
+	fn allocate_surplus() -> u32 {
+		let surplus = Self::surplus_pool().saturating_sub(Self::debit_pool());
+		let mut count: u32 = 0;
+
+		for (strategy, allocation) in SurplusAllocations::<T>::iter() {
+			count = count.saturating_add(1);
+			let amount = allocation.proportion.mul_floor(surplus).min(allocation.cap);
+			if amount.is_zero() {
+				continue;
+			}
+
+			let res = with_transaction(|| match Self::do_allocate_surplus(strategy, amount) {
+				Ok(received_amount) => TransactionOutcome::Commit(Ok(received_amount)),
+				Err(e) => TransactionOutcome::Rollback(Err(e)),
+			});
+			match res {
+				Ok(received_amount) => {
+					Self::deposit_event(Event::SurplusAllocated(strategy, amount, received_amount));
+				}
+				Err(e) => {
+					log::warn!(
+						target: "cdp-treasury",
+						"allocate_surplus: Attempt to allocate surplus {:?} to {:?} failed: {:?}, this is unexpected but should be safe",
+						amount, strategy, e
+					);
+				}
+			}
+		}
+
+		count
+	}
+
+	/// Allocate `amount` of the surplus to the strategy, returns the amount
+	/// received by the strategy.
+	fn do_allocate_surplus(
+		strategy: SurplusStrategy,
+		amount: Balance,
+	) -> sp_std::result::Result<Balance, DispatchError> {
+		let stable_currency_id = T::GetStableCurrencyId::get();
+		match strategy {
+			SurplusStrategy::SavingRate => {
+				T::Currency::transfer(
+					stable_currency_id,
+					&Self::account_id(),
+					&T::SavingRateAccount::get(),
+					amount,
+				)?;
+				Ok(amount)
+			}
+			SurplusStrategy::BuybackAndBurn => {
+				let native_currency_id = T::GetNativeCurrencyId::get();
+				let native_amount = T::DEX::swap_with_exact_supply(
+					&Self::account_id(),
+					&[stable_currency_id, native_currency_id],
+					amount,
+					Zero::zero(),
+					Some(T::MaxSlippageSwapWithDEX::get()),
+				)?;
+				T::Currency::withdraw(native_currency_id, &Self::account_id(), native_amount)?;
+				Ok(native_amount)
+			}
+			SurplusStrategy::StabilityBuffer(currency_id) => {
+				let buffer_amount = T::DEX::swap_with_exact_supply(
+					&Self::account_id(),
+					&[stable_currency_id, currency_id],
+					amount,
+					Zero::zero(),
+					Some(T::MaxSlippageSwapWithDEX::get()),
+				)?;
+				T::Currency::transfer(
+					currency_id,
+					&Self::account_id(),
+					&T::StabilityBufferAccount::get(),
+					buffer_amount,
+				)?;
+				Ok(buffer_amount)
+			}
+		}
+	}
+
 	fn offset_surplus_and_debit() {
 		let offset_amount = sp_std::cmp::min(Self::debit_pool(), Self::surplus_pool());
 
@@ -125,7 +125,7 @@ parameter_types! {
 	pub const GetStableCurrencyId: CurrencyId = AUSD;
 	pub const GetExchangeFee: (u32, u32) = (0, 100);
 	pub const TradingPathLimit: u32 = 3;
-	pub EnabledTradingPairs : Vec<TradingPair> = vec![TradingPair::new(AUSD, BTC), TradingPair::new(AUSD, DOT), TradingPair::new(BTC, DOT)];
+	pub EnabledTradingPairs : Vec<TradingPair> = vec![TradingPair::new(AUSD, BTC), TradingPair::new(AUSD, DOT), TradingPair::new(BTC, DOT), TradingPair::new(AUSD, ACA)];
 	pub const DEXPalletId: PalletId = PalletId(*b"aca/dexm");
 }
 
@@ -139,6 +139,11 @@ impl module_dex::Config for Runtime {
 	type DEXIncentives = ();
 	type WeightInfo = ();
 	type ListingOrigin = EnsureSignedBy<One, AccountId>;
+	type OnSwap = ();
+	type EventTopics = ();
+	type SwapFeeShare = ();
+	type SwapFeeCurrencyId = GetNativeCurrencyId;
+	type SwapFeeReceiver = ();
 }
 
 thread_local! {
@@ -184,6 +189,10 @@ ord_parameter_types! {
 parameter_types! {
 	pub const CDPTreasuryPalletId: PalletId = PalletId(*b"aca/cdpt");
 	pub const TreasuryAccount: AccountId = 10;
+	pub const SavingRateAccount: AccountId = 11;
+	pub const StabilityBufferAccount: AccountId = 12;
+	pub const SurplusAllocationPeriod: BlockNumber = 10;
+	pub MaxSlippageSwapWithDEX: Ratio = Ratio::saturating_from_rational(50, 100);
 }
 
 thread_local! {
@@ -200,6 +209,11 @@ impl Config for Runtime {
 	type MaxAuctionsCount = MaxAuctionsCount;
 	type PalletId = CDPTreasuryPalletId;
 	type TreasuryAccount = TreasuryAccount;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type SavingRateAccount = SavingRateAccount;
+	type StabilityBufferAccount = StabilityBufferAccount;
+	type SurplusAllocationPeriod = SurplusAllocationPeriod;
+	type MaxSlippageSwapWithDEX = MaxSlippageSwapWithDEX;
 	type WeightInfo = ();
 }
 
@@ -216,7 +230,7 @@ construct_runtime!(
 		CDPTreasuryModule: cdp_treasury::{Pallet, Storage, Call, Config, Event<T>},
 		Currencies: orml_currencies::{Pallet, Call, Event<T>},
 		Tokens: orml_tokens::{Pallet, Storage, Event<T>, Config<T>},
-		PalletBalances: pallet_balances::{Pallet, Call, Storage, Event<T>},
+		PalletBalances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		DEXModule: module_dex::{Pallet, Storage, Call, Event<T>, Config<T>},
 	}
 );
@@ -252,6 +266,12 @@ impl ExtBuilder {
 		.assimilate_storage(&mut t)
 		.unwrap();
 
+		pallet_balances::GenesisConfig::<Runtime> {
+			balances: vec![(ALICE, 10000), (BOB, 10000)],
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
 		module_dex::GenesisConfig::<Runtime> {
 			initial_listing_trading_pairs: vec![],
 			initial_enabled_trading_pairs: EnabledTradingPairs::get(),
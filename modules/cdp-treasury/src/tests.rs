@@ -423,3 +423,166 @@ fn extract_surplus_to_treasury_work() {
 		assert_eq!(Currencies::free_balance(AUSD, &TreasuryAccount::get()), 200);
 	});
 }
+
+#[test]
+fn set_surplus_allocations_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let allocation = SurplusAllocation {
+			proportion: Permill::from_percent(60),
+			cap: 1000,
+		};
+		assert_noop!(
+			CDPTreasuryModule::set_surplus_allocations(
+				Origin::signed(5),
+				vec![(SurplusStrategy::SavingRate, Some(allocation))]
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			CDPTreasuryModule::set_surplus_allocations(
+				Origin::signed(1),
+				vec![(SurplusStrategy::StabilityBuffer(AUSD), Some(allocation))]
+			),
+			Error::<Runtime>::InvalidStabilityBuffer
+		);
+		assert_noop!(
+			CDPTreasuryModule::set_surplus_allocations(
+				Origin::signed(1),
+				vec![
+					(SurplusStrategy::SavingRate, Some(allocation)),
+					(SurplusStrategy::StabilityBuffer(DOT), Some(allocation))
+				]
+			),
+			Error::<Runtime>::InvalidSurplusAllocations
+		);
+
+		assert_ok!(CDPTreasuryModule::set_surplus_allocations(
+			Origin::signed(1),
+			vec![
+				(SurplusStrategy::SavingRate, Some(allocation)),
+				(SurplusStrategy::StabilityBuffer(DOT), None)
+			]
+		));
+		System::assert_has_event(Event::CDPTreasuryModule(crate::Event::SurplusAllocationUpdated(
+			SurplusStrategy::SavingRate,
+			Some(allocation),
+		)));
+		System::assert_last_event(Event::CDPTreasuryModule(crate::Event::SurplusAllocationUpdated(
+			SurplusStrategy::StabilityBuffer(DOT),
+			None,
+		)));
+		assert_eq!(
+			CDPTreasuryModule::surplus_allocations(SurplusStrategy::SavingRate),
+			Some(allocation)
+		);
+
+		// move the allocation of the savings rate to the stability buffer
+		assert_ok!(CDPTreasuryModule::set_surplus_allocations(
+			Origin::signed(1),
+			vec![
+				(SurplusStrategy::SavingRate, None),
+				(SurplusStrategy::StabilityBuffer(DOT), Some(allocation))
+			]
+		));
+		assert_eq!(
+			CDPTreasuryModule::surplus_allocations(SurplusStrategy::SavingRate),
+			None
+		);
+		assert_eq!(
+			CDPTreasuryModule::surplus_allocations(SurplusStrategy::StabilityBuffer(DOT)),
+			Some(allocation)
+		);
+	});
+}
+
+#[test]
+fn allocate_surplus_on_initialize_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(ALICE),
+			ACA,
+			AUSD,
+			1000,
+			1000,
+			0,
+			false
+		));
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(BOB),
+			DOT,
+			AUSD,
+			1000,
+			1000,
+			0,
+			false
+		));
+		assert_ok!(CDPTreasuryModule::on_system_surplus(1000));
+		assert_ok!(CDPTreasuryModule::on_system_debit(200));
+		assert_ok!(CDPTreasuryModule::set_surplus_allocations(
+			Origin::signed(1),
+			vec![
+				(
+					SurplusStrategy::SavingRate,
+					Some(SurplusAllocation {
+						proportion: Permill::from_percent(10),
+						cap: 1000,
+					})
+				),
+				(
+					SurplusStrategy::BuybackAndBurn,
+					Some(SurplusAllocation {
+						proportion: Permill::from_percent(20),
+						cap: 100,
+					})
+				),
+				(
+					SurplusStrategy::StabilityBuffer(DOT),
+					Some(SurplusAllocation {
+						proportion: Permill::from_percent(30),
+						cap: 1000,
+					})
+				),
+				// there's no liquidity to swap AUSD to BTC
+				(
+					SurplusStrategy::StabilityBuffer(BTC),
+					Some(SurplusAllocation {
+						proportion: Permill::from_percent(10),
+						cap: 1000,
+					})
+				),
+			]
+		));
+		let aca_total_issuance = Currencies::total_issuance(ACA);
+
+		CDPTreasuryModule::on_initialize(9);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 1000);
+
+		// allocate 80% of the surplus that isn't needed to offset the debit pool
+		CDPTreasuryModule::on_initialize(10);
+		System::assert_has_event(Event::CDPTreasuryModule(crate::Event::SurplusAllocated(
+			SurplusStrategy::SavingRate,
+			80,
+			80,
+		)));
+		System::assert_has_event(Event::CDPTreasuryModule(crate::Event::SurplusAllocated(
+			SurplusStrategy::BuybackAndBurn,
+			100,
+			90,
+		)));
+		System::assert_has_event(Event::CDPTreasuryModule(crate::Event::SurplusAllocated(
+			SurplusStrategy::StabilityBuffer(DOT),
+			240,
+			193,
+		)));
+		assert_eq!(Currencies::free_balance(AUSD, &SavingRateAccount::get()), 80);
+		assert_eq!(Currencies::total_issuance(ACA), aca_total_issuance - 90);
+		assert_eq!(Currencies::free_balance(ACA, &CDPTreasuryModule::account_id()), 0);
+		assert_eq!(Currencies::free_balance(DOT, &StabilityBufferAccount::get()), 193);
+		assert_eq!(Currencies::free_balance(DOT, &CDPTreasuryModule::account_id()), 0);
+		assert_eq!(Currencies::free_balance(BTC, &StabilityBufferAccount::get()), 0);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 580);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 200);
+	});
+}
@@ -51,6 +51,8 @@ pub trait WeightInfo {
 	fn extract_surplus_to_treasury() -> Weight;
 	fn auction_collateral() -> Weight;
 	fn set_expected_collateral_auction_size() -> Weight;
+	fn set_surplus_allocations(c: u32, ) -> Weight;
+	fn on_initialize(c: u32, ) -> Weight;
 }
 
 /// Weights for module_cdp_treasury using the Acala node and recommended hardware.
@@ -70,6 +72,21 @@ impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
 		(14_000_000 as Weight)
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	fn set_surplus_allocations(c: u32, ) -> Weight {
+		(18_000_000 as Weight)
+			// Standard Error: 0
+			.saturating_add((4_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
+	fn on_initialize(c: u32, ) -> Weight {
+		(12_000_000 as Weight)
+			// Standard Error: 0
+			.saturating_add((156_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().reads((6 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes((6 as Weight).saturating_mul(c as Weight)))
+	}
 }
 
 // For backwards compatibility and tests
@@ -88,4 +105,19 @@ impl WeightInfo for () {
 		(14_000_000 as Weight)
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
+	fn set_surplus_allocations(c: u32, ) -> Weight {
+		(18_000_000 as Weight)
+			// Standard Error: 0
+			.saturating_add((4_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
+	fn on_initialize(c: u32, ) -> Weight {
+		(12_000_000 as Weight)
+			// Standard Error: 0
+			.saturating_add((156_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((6 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((6 as Weight).saturating_mul(c as Weight)))
+	}
 }
@@ -0,0 +1,405 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Circuit Breaker Module
+//!
+//! ## Overview
+//!
+//! The emergency keys can take the constrained emergency actions, e.g. pause a pallet, lock a
+//! price or halt a bridge, without waiting for the governance. An action is proposed by a key and
+//! taken once it's approved by `KeyThreshold` keys, then it expires after `ActionDuration` blocks.
+//! Every action must be ratified by the council retroactively, the council can also reject it,
+//! which reverts it immediately if it has not expired. The expired actions are kept until they're
+//! ratified or rejected.
+//!
+//! An action already in effect when it's taken, e.g. the pallet is paused by the governance, is
+//! left untouched, and is not reverted when the action expires or is rejected. The actions only
+//! expire by this module, the handler must not set an expiry of its own.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{log, pallet_prelude::*, transactional};
+use frame_system::pallet_prelude::*;
+use primitives::CurrencyId;
+use sp_runtime::{traits::Saturating, DispatchError, RuntimeDebug};
+use sp_std::prelude::*;
+use support::Contains;
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+pub type ActionId = u32;
+
+/// The emergency action.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum EmergencyAction {
+	/// Pause all the calls of the pallet, by the name of the pallet.
+	PausePallet(Vec<u8>),
+	/// Lock the price of the currency.
+	LockPrice(CurrencyId),
+	/// Halt the bridge, by the name of the bridge pallet.
+	HaltBridge(Vec<u8>),
+}
+
+/// Take and revert the emergency actions.
+pub trait EmergencyActionHandler {
+	/// Take the action until it's reverted. Returns `false` if the action is already in effect, in
+	/// which case nothing is changed.
+	fn take(action: &EmergencyAction) -> Result<bool, DispatchError>;
+	/// Revert the action taken, when it expires or is rejected.
+	fn revert(action: &EmergencyAction) -> DispatchResult;
+}
+
+/// The action proposed by the emergency keys.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Proposal<AccountId> {
+	/// The action to take.
+	pub action: EmergencyAction,
+	/// The keys which approved the action, including the proposer.
+	pub approvals: Vec<AccountId>,
+}
+
+/// The action taken by the emergency keys.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct ActionInfo<BlockNumber> {
+	/// The action taken.
+	pub action: EmergencyAction,
+	/// The block number the action expires at.
+	pub expiry: BlockNumber,
+	/// Whether the action is ratified by the council.
+	pub ratified: bool,
+	/// Whether the action was taken by the emergency keys, `false` if it was already in effect.
+	/// Only the actions taken are reverted.
+	pub taken: bool,
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The origin which may set the emergency keys.
+		type KeysOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The origin which may ratify and reject the actions.
+		type RatifyOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The actions the emergency keys may take.
+		type ActionFilter: Contains<EmergencyAction>;
+
+		/// Take and revert the actions.
+		type ActionHandler: EmergencyActionHandler;
+
+		/// The number of approvals of keys to take an action.
+		#[pallet::constant]
+		type KeyThreshold: Get<u32>;
+
+		/// The maximum number of emergency keys.
+		#[pallet::constant]
+		type MaxKeys: Get<u32>;
+
+		/// The number of blocks an action lasts before it expires.
+		#[pallet::constant]
+		type ActionDuration: Get<Self::BlockNumber>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The keys are fewer than the threshold, more than the maximum or duplicated
+		InvalidKeys,
+		/// The caller is not an emergency key
+		NotEmergencyKey,
+		/// The action is not allowed
+		ActionNotAllowed,
+		/// The proposal does not exist
+		ProposalNotFound,
+		/// The key has approved the proposal
+		AlreadyApproved,
+		/// The action does not exist
+		ActionNotFound,
+		/// The action has been ratified
+		AlreadyRatified,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Emergency keys updated. \[keys\]
+		EmergencyKeysUpdated(Vec<T::AccountId>),
+		/// Proposed the action. \[action_id, who, action\]
+		ActionProposed(ActionId, T::AccountId, EmergencyAction),
+		/// Approved the proposed action. \[action_id, who\]
+		ActionApproved(ActionId, T::AccountId),
+		/// Took the action. \[action_id, action, expiry\]
+		ActionTaken(ActionId, EmergencyAction, T::BlockNumber),
+		/// The action expired and was reverted. \[action_id\]
+		ActionExpired(ActionId),
+		/// The council ratified the action. \[action_id\]
+		ActionRatified(ActionId),
+		/// The council rejected the action. \[action_id\]
+		ActionRejected(ActionId),
+	}
+
+	/// The emergency keys.
+	///
+	/// EmergencyKeys: Vec<AccountId>
+	#[pallet::storage]
+	#[pallet::getter(fn emergency_keys)]
+	pub type EmergencyKeys<T: Config> = StorageValue<_, Vec<T::AccountId>, ValueQuery>;
+
+	/// The next action id.
+	///
+	/// NextActionId: ActionId
+	#[pallet::storage]
+	#[pallet::getter(fn next_action_id)]
+	pub type NextActionId<T: Config> = StorageValue<_, ActionId, ValueQuery>;
+
+	/// The proposed actions waiting for approvals.
+	///
+	/// Proposals: map ActionId => Option<Proposal>
+	#[pallet::storage]
+	#[pallet::getter(fn proposals)]
+	pub type Proposals<T: Config> = StorageMap<_, Twox64Concat, ActionId, Proposal<T::AccountId>, OptionQuery>;
+
+	/// The actions taken, which are not ratified or not expired.
+	///
+	/// Actions: map ActionId => Option<ActionInfo>
+	#[pallet::storage]
+	#[pallet::getter(fn actions)]
+	pub type Actions<T: Config> = StorageMap<_, Twox64Concat, ActionId, ActionInfo<T::BlockNumber>, OptionQuery>;
+
+	/// The actions expire at the block number.
+	///
+	/// ActionExpiries: map BlockNumber => Vec<ActionId>
+	#[pallet::storage]
+	#[pallet::getter(fn action_expiries)]
+	pub type ActionExpiries<T: Config> = StorageMap<_, Twox64Concat, T::BlockNumber, Vec<ActionId>, ValueQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		/// Revert the actions expiring at `now`.
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let expired = ActionExpiries::<T>::take(now);
+			for action_id in expired.iter() {
+				Self::expire_action(*action_id);
+			}
+			T::WeightInfo::on_initialize(expired.len() as u32)
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set the emergency keys, the proposals keep only the approvals of the new keys.
+		///
+		/// The dispatch origin of this call must be `KeysOrigin`.
+		///
+		/// - `keys`: the emergency keys.
+		#[pallet::weight(T::WeightInfo::set_emergency_keys())]
+		#[transactional]
+		pub fn set_emergency_keys(origin: OriginFor<T>, mut keys: Vec<T::AccountId>) -> DispatchResultWithPostInfo {
+			T::KeysOrigin::ensure_origin(origin)?;
+			let len = keys.len() as u32;
+			keys.sort();
+			keys.dedup();
+			ensure!(
+				keys.len() as u32 == len && len >= T::KeyThreshold::get() && len <= T::MaxKeys::get(),
+				Error::<T>::InvalidKeys
+			);
+
+			EmergencyKeys::<T>::put(&keys);
+			Self::deposit_event(Event::EmergencyKeysUpdated(keys));
+			Ok(().into())
+		}
+
+		/// Propose the emergency action, which is approved by the caller.
+		///
+		/// The dispatch origin of this call must be an emergency key.
+		///
+		/// - `action`: the action to take.
+		#[pallet::weight(T::WeightInfo::propose())]
+		#[transactional]
+		pub fn propose(origin: OriginFor<T>, action: EmergencyAction) -> DispatchResultWithPostInfo {
+			let who = Self::ensure_emergency_key(origin)?;
+			ensure!(T::ActionFilter::contains(&action), Error::<T>::ActionNotAllowed);
+
+			let action_id = Self::next_action_id();
+			NextActionId::<T>::put(action_id.saturating_add(1));
+			Proposals::<T>::insert(
+				action_id,
+				Proposal {
+					action: action.clone(),
+					approvals: vec![who.clone()],
+				},
+			);
+
+			Self::deposit_event(Event::ActionProposed(action_id, who, action));
+			Self::try_take_action(action_id)?;
+			Ok(().into())
+		}
+
+		/// Approve the proposed action, the action is taken once it's approved by `KeyThreshold`
+		/// keys.
+		///
+		/// The dispatch origin of this call must be an emergency key.
+		///
+		/// - `action_id`: the id of the proposed action.
+		#[pallet::weight(T::WeightInfo::approve())]
+		#[transactional]
+		pub fn approve(origin: OriginFor<T>, action_id: ActionId) -> DispatchResultWithPostInfo {
+			let who = Self::ensure_emergency_key(origin)?;
+			Proposals::<T>::try_mutate(action_id, |maybe_proposal| -> DispatchResult {
+				let proposal = maybe_proposal.as_mut().ok_or(Error::<T>::ProposalNotFound)?;
+				ensure!(!proposal.approvals.contains(&who), Error::<T>::AlreadyApproved);
+				proposal.approvals.push(who.clone());
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::ActionApproved(action_id, who));
+			Self::try_take_action(action_id)?;
+			Ok(().into())
+		}
+
+		/// Ratify the action taken by the emergency keys.
+		///
+		/// The dispatch origin of this call must be `RatifyOrigin`.
+		///
+		/// - `action_id`: the id of the action.
+		#[pallet::weight(T::WeightInfo::ratify())]
+		#[transactional]
+		pub fn ratify(origin: OriginFor<T>, action_id: ActionId) -> DispatchResultWithPostInfo {
+			T::RatifyOrigin::ensure_origin(origin)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			Actions::<T>::try_mutate_exists(action_id, |maybe_info| -> DispatchResult {
+				let info = maybe_info.as_mut().ok_or(Error::<T>::ActionNotFound)?;
+				ensure!(!info.ratified, Error::<T>::AlreadyRatified);
+				if info.expiry <= now {
+					*maybe_info = None;
+				} else {
+					info.ratified = true;
+				}
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::ActionRatified(action_id));
+			Ok(().into())
+		}
+
+		/// Reject the action taken by the emergency keys, or the proposed action. The action is
+		/// reverted if it has not expired.
+		///
+		/// The dispatch origin of this call must be `RatifyOrigin`.
+		///
+		/// - `action_id`: the id of the action.
+		#[pallet::weight(T::WeightInfo::reject())]
+		#[transactional]
+		pub fn reject(origin: OriginFor<T>, action_id: ActionId) -> DispatchResultWithPostInfo {
+			T::RatifyOrigin::ensure_origin(origin)?;
+
+			if Proposals::<T>::take(action_id).is_none() {
+				let info = Actions::<T>::take(action_id).ok_or(Error::<T>::ActionNotFound)?;
+				if info.expiry > frame_system::Pallet::<T>::block_number() {
+					ActionExpiries::<T>::mutate(info.expiry, |action_ids| action_ids.retain(|id| *id != action_id));
+					if info.taken {
+						T::ActionHandler::revert(&info.action)?;
+					}
+				}
+			}
+
+			Self::deposit_event(Event::ActionRejected(action_id));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	fn ensure_emergency_key(origin: OriginFor<T>) -> Result<T::AccountId, DispatchError> {
+		let who = ensure_signed(origin)?;
+		ensure!(
+			Self::emergency_keys().binary_search(&who).is_ok(),
+			Error::<T>::NotEmergencyKey
+		);
+		Ok(who)
+	}
+
+	/// Take the proposed action if it's approved by enough current keys.
+	fn try_take_action(action_id: ActionId) -> DispatchResult {
+		let keys = Self::emergency_keys();
+		let proposal = match Self::proposals(action_id) {
+			Some(proposal) => proposal,
+			None => return Ok(()),
+		};
+		let approvals = proposal
+			.approvals
+			.iter()
+			.filter(|who| keys.binary_search(who).is_ok())
+			.count() as u32;
+		if approvals < T::KeyThreshold::get() {
+			return Ok(());
+		}
+
+		let expiry = frame_system::Pallet::<T>::block_number().saturating_add(T::ActionDuration::get());
+		let taken = T::ActionHandler::take(&proposal.action)?;
+		Proposals::<T>::remove(action_id);
+		Actions::<T>::insert(
+			action_id,
+			ActionInfo {
+				action: proposal.action.clone(),
+				expiry,
+				ratified: false,
+				taken,
+			},
+		);
+		ActionExpiries::<T>::append(expiry, action_id);
+
+		Self::deposit_event(Event::ActionTaken(action_id, proposal.action, expiry));
+		Ok(())
+	}
+
+	fn expire_action(action_id: ActionId) {
+		if let Some(info) = Self::actions(action_id) {
+			if info.taken {
+				if let Err(e) = T::ActionHandler::revert(&info.action) {
+					log::warn!(
+						target: "circuit-breaker",
+						"revert: failed to revert action {:?}: {:?}. \
+						This is unexpected but should be safe",
+						action_id, e
+					);
+				}
+			}
+			if info.ratified {
+				Actions::<T>::remove(action_id);
+			}
+			Self::deposit_event(Event::ActionExpired(action_id));
+		}
+	}
+}
@@ -0,0 +1,170 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Mocks for the circuit breaker module.
+
+#![cfg(test)]
+
+use super::*;
+
+use crate as circuit_breaker;
+use frame_support::{assert_ok, construct_runtime, ord_parameter_types, parameter_types};
+use frame_system::EnsureSignedBy;
+use primitives::{CurrencyId, TokenSymbol};
+use sp_core::H256;
+use sp_runtime::{testing::Header, traits::IdentityLookup};
+use sp_std::cell::RefCell;
+
+pub type AccountId = u128;
+pub type BlockNumber = u64;
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const CHARLIE: AccountId = 3;
+pub const DAVE: AccountId = 4;
+pub const EVE: AccountId = 5;
+pub const COUNCIL: AccountId = 100;
+pub const DOT: CurrencyId = CurrencyId::Token(TokenSymbol::DOT);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Runtime {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+thread_local! {
+	static ACTIVE_ACTIONS: RefCell<Vec<EmergencyAction>> = RefCell::new(vec![]);
+}
+
+pub fn active_actions() -> Vec<EmergencyAction> {
+	ACTIVE_ACTIONS.with(|v| v.borrow().clone())
+}
+
+/// The action in effect before it's taken by the emergency keys, e.g. by the governance.
+pub fn take_by_governance(action: EmergencyAction) {
+	ACTIVE_ACTIONS.with(|v| v.borrow_mut().push(action));
+}
+
+pub struct MockActionFilter;
+impl Contains<EmergencyAction> for MockActionFilter {
+	fn contains(action: &EmergencyAction) -> bool {
+		!matches!(action, EmergencyAction::PausePallet(pallet_name) if pallet_name == b"CircuitBreaker")
+	}
+}
+
+pub struct MockActionHandler;
+impl EmergencyActionHandler for MockActionHandler {
+	fn take(action: &EmergencyAction) -> Result<bool, DispatchError> {
+		ACTIVE_ACTIONS.with(|v| {
+			let mut actions = v.borrow_mut();
+			if actions.contains(action) {
+				return Ok(false);
+			}
+			actions.push(action.clone());
+			Ok(true)
+		})
+	}
+
+	fn revert(action: &EmergencyAction) -> DispatchResult {
+		ACTIVE_ACTIONS.with(|v| v.borrow_mut().retain(|a| a != action));
+		Ok(())
+	}
+}
+
+ord_parameter_types! {
+	pub const Council: AccountId = COUNCIL;
+}
+
+parameter_types! {
+	pub const KeyThreshold: u32 = 3;
+	pub const MaxKeys: u32 = 5;
+	pub const ActionDuration: BlockNumber = 10;
+}
+
+impl Config for Runtime {
+	type Event = Event;
+	type KeysOrigin = EnsureSignedBy<Council, AccountId>;
+	type RatifyOrigin = EnsureSignedBy<Council, AccountId>;
+	type ActionFilter = MockActionFilter;
+	type ActionHandler = MockActionHandler;
+	type KeyThreshold = KeyThreshold;
+	type MaxKeys = MaxKeys;
+	type ActionDuration = ActionDuration;
+	type WeightInfo = ();
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		CircuitBreaker: circuit_breaker::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+#[derive(Default)]
+pub struct ExtBuilder;
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let t = frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap();
+
+		ACTIVE_ACTIONS.with(|v| v.borrow_mut().clear());
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(CircuitBreaker::set_emergency_keys(
+				Origin::signed(COUNCIL),
+				vec![ALICE, BOB, CHARLIE, DAVE, EVE]
+			));
+		});
+		ext
+	}
+}
@@ -0,0 +1,236 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the circuit breaker module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok, traits::OnInitialize};
+use mock::{Event, *};
+use sp_runtime::traits::BadOrigin;
+
+fn pause_dex() -> EmergencyAction {
+	EmergencyAction::PausePallet(b"Dex".to_vec())
+}
+
+fn take_action(action: EmergencyAction) -> ActionId {
+	let action_id = CircuitBreaker::next_action_id();
+	assert_ok!(CircuitBreaker::propose(Origin::signed(ALICE), action));
+	assert_ok!(CircuitBreaker::approve(Origin::signed(BOB), action_id));
+	assert_ok!(CircuitBreaker::approve(Origin::signed(CHARLIE), action_id));
+	action_id
+}
+
+#[test]
+fn set_emergency_keys_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			CircuitBreaker::set_emergency_keys(Origin::signed(ALICE), vec![ALICE, BOB, CHARLIE]),
+			BadOrigin
+		);
+		assert_noop!(
+			CircuitBreaker::set_emergency_keys(Origin::signed(COUNCIL), vec![ALICE, BOB]),
+			Error::<Runtime>::InvalidKeys
+		);
+		assert_noop!(
+			CircuitBreaker::set_emergency_keys(Origin::signed(COUNCIL), vec![ALICE, BOB, BOB]),
+			Error::<Runtime>::InvalidKeys
+		);
+		assert_noop!(
+			CircuitBreaker::set_emergency_keys(Origin::signed(COUNCIL), vec![1, 2, 3, 4, 5, 6]),
+			Error::<Runtime>::InvalidKeys
+		);
+
+		assert_ok!(CircuitBreaker::set_emergency_keys(
+			Origin::signed(COUNCIL),
+			vec![CHARLIE, BOB, ALICE]
+		));
+		System::assert_last_event(Event::CircuitBreaker(crate::Event::EmergencyKeysUpdated(vec![
+			ALICE, BOB, CHARLIE,
+		])));
+		assert_eq!(CircuitBreaker::emergency_keys(), vec![ALICE, BOB, CHARLIE]);
+	});
+}
+
+#[test]
+fn propose_and_approve_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			CircuitBreaker::propose(Origin::signed(COUNCIL), pause_dex()),
+			Error::<Runtime>::NotEmergencyKey
+		);
+		assert_noop!(
+			CircuitBreaker::propose(
+				Origin::signed(ALICE),
+				EmergencyAction::PausePallet(b"CircuitBreaker".to_vec())
+			),
+			Error::<Runtime>::ActionNotAllowed
+		);
+
+		assert_ok!(CircuitBreaker::propose(Origin::signed(ALICE), pause_dex()));
+		System::assert_last_event(Event::CircuitBreaker(crate::Event::ActionProposed(
+			0,
+			ALICE,
+			pause_dex(),
+		)));
+		assert_ok!(CircuitBreaker::approve(Origin::signed(BOB), 0));
+		assert_noop!(
+			CircuitBreaker::approve(Origin::signed(BOB), 0),
+			Error::<Runtime>::AlreadyApproved
+		);
+		assert_eq!(CircuitBreaker::proposals(0).unwrap().approvals, vec![ALICE, BOB]);
+		assert_eq!(active_actions(), vec![]);
+
+		// the approval of the removed key doesn't count
+		assert_ok!(CircuitBreaker::set_emergency_keys(
+			Origin::signed(COUNCIL),
+			vec![ALICE, CHARLIE, DAVE, EVE]
+		));
+		assert_ok!(CircuitBreaker::approve(Origin::signed(CHARLIE), 0));
+		assert_eq!(active_actions(), vec![]);
+
+		assert_ok!(CircuitBreaker::approve(Origin::signed(DAVE), 0));
+		System::assert_last_event(Event::CircuitBreaker(crate::Event::ActionTaken(0, pause_dex(), 11)));
+		assert_eq!(active_actions(), vec![pause_dex()]);
+		assert_eq!(CircuitBreaker::proposals(0), None);
+		assert_eq!(
+			CircuitBreaker::actions(0),
+			Some(ActionInfo {
+				action: pause_dex(),
+				expiry: 11,
+				ratified: false,
+				taken: true,
+			})
+		);
+		assert_eq!(CircuitBreaker::action_expiries(11), vec![0]);
+
+		assert_noop!(
+			CircuitBreaker::approve(Origin::signed(EVE), 0),
+			Error::<Runtime>::ProposalNotFound
+		);
+	});
+}
+
+#[test]
+fn action_expire_and_ratify_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let lock_price = EmergencyAction::LockPrice(DOT);
+		assert_ok!(CircuitBreaker::propose(Origin::signed(ALICE), pause_dex()));
+		assert_ok!(CircuitBreaker::propose(Origin::signed(ALICE), lock_price.clone()));
+		assert_noop!(
+			CircuitBreaker::ratify(Origin::signed(COUNCIL), 0),
+			Error::<Runtime>::ActionNotFound
+		);
+
+		for action_id in 0..2 {
+			assert_ok!(CircuitBreaker::approve(Origin::signed(BOB), action_id));
+			assert_ok!(CircuitBreaker::approve(Origin::signed(CHARLIE), action_id));
+		}
+		assert_eq!(active_actions(), vec![pause_dex(), lock_price.clone()]);
+
+		assert_noop!(CircuitBreaker::ratify(Origin::signed(ALICE), 0), BadOrigin);
+		assert_ok!(CircuitBreaker::ratify(Origin::signed(COUNCIL), 0));
+		System::assert_last_event(Event::CircuitBreaker(crate::Event::ActionRatified(0)));
+		assert!(CircuitBreaker::actions(0).unwrap().ratified);
+		assert_noop!(
+			CircuitBreaker::ratify(Origin::signed(COUNCIL), 0),
+			Error::<Runtime>::AlreadyRatified
+		);
+
+		System::set_block_number(11);
+		CircuitBreaker::on_initialize(11);
+		System::assert_has_event(Event::CircuitBreaker(crate::Event::ActionExpired(0)));
+		System::assert_last_event(Event::CircuitBreaker(crate::Event::ActionExpired(1)));
+		assert_eq!(active_actions(), vec![]);
+		assert_eq!(CircuitBreaker::action_expiries(11), vec![]);
+
+		// the ratified action is removed, the other waits for ratification
+		assert_eq!(CircuitBreaker::actions(0), None);
+		assert!(CircuitBreaker::actions(1).is_some());
+		assert_ok!(CircuitBreaker::ratify(Origin::signed(COUNCIL), 1));
+		assert_eq!(CircuitBreaker::actions(1), None);
+	});
+}
+
+#[test]
+fn reject_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let action_id = take_action(pause_dex());
+		assert_ok!(CircuitBreaker::propose(
+			Origin::signed(ALICE),
+			EmergencyAction::HaltBridge(b"XTokens".to_vec())
+		));
+
+		assert_noop!(CircuitBreaker::reject(Origin::signed(ALICE), action_id), BadOrigin);
+		assert_noop!(
+			CircuitBreaker::reject(Origin::signed(COUNCIL), 2),
+			Error::<Runtime>::ActionNotFound
+		);
+
+		assert_ok!(CircuitBreaker::reject(Origin::signed(COUNCIL), action_id));
+		System::assert_last_event(Event::CircuitBreaker(crate::Event::ActionRejected(action_id)));
+		assert_eq!(active_actions(), vec![]);
+		assert_eq!(CircuitBreaker::actions(action_id), None);
+		assert_eq!(CircuitBreaker::action_expiries(11), vec![]);
+
+		// reject the proposal
+		assert_ok!(CircuitBreaker::reject(Origin::signed(COUNCIL), 1));
+		assert_eq!(CircuitBreaker::proposals(1), None);
+	});
+}
+
+#[test]
+fn action_in_effect_is_not_reverted() {
+	ExtBuilder::default().build().execute_with(|| {
+		let halt_bridge = EmergencyAction::HaltBridge(b"XTokens".to_vec());
+		take_by_governance(pause_dex());
+		take_by_governance(halt_bridge.clone());
+
+		let expired = take_action(pause_dex());
+		let rejected = take_action(halt_bridge.clone());
+		assert!(!CircuitBreaker::actions(expired).unwrap().taken);
+		assert!(!CircuitBreaker::actions(rejected).unwrap().taken);
+
+		// the pause of the governance is kept when the action is rejected or expires
+		assert_ok!(CircuitBreaker::reject(Origin::signed(COUNCIL), rejected));
+		System::set_block_number(11);
+		CircuitBreaker::on_initialize(11);
+		System::assert_last_event(Event::CircuitBreaker(crate::Event::ActionExpired(expired)));
+		assert_eq!(active_actions(), vec![pause_dex(), halt_bridge]);
+	});
+}
+
+#[test]
+fn only_the_action_taking_effect_is_reverted() {
+	ExtBuilder::default().build().execute_with(|| {
+		let first = take_action(pause_dex());
+		System::set_block_number(5);
+		let second = take_action(pause_dex());
+		assert!(CircuitBreaker::actions(first).unwrap().taken);
+		assert!(!CircuitBreaker::actions(second).unwrap().taken);
+
+		// the second action doesn't revert the pause of the first one
+		assert_ok!(CircuitBreaker::reject(Origin::signed(COUNCIL), second));
+		assert_eq!(active_actions(), vec![pause_dex()]);
+
+		System::set_block_number(11);
+		CircuitBreaker::on_initialize(11);
+		assert_eq!(active_actions(), vec![]);
+	});
+}
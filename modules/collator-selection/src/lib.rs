@@ -30,6 +30,8 @@
 //!
 //! - Collator: A parachain block producer.
 //! - Bond: An amount of `Balance` _reserved_ for candidate registration.
+//! - Unbonding: The bond of a removed candidate is kept reserved for [`Config::UnbondingDelay`]
+//!   sessions before it's released.
 //! - Invulnerable: An account guaranteed to be in the collator set.
 //!
 //! ## Implementation
@@ -44,6 +46,26 @@
 //! The current implementation resolves congestion of [`Candidates`] in a first-come-first-serve
 //! manner.
 //!
+//! ### Kicking
+//!
+//! The candidates earn [`POINT_PER_BLOCK`] points for each block authored in the session. At the
+//! end of the session, the candidates whose points are not above [`Config::CollatorKickThreshold`]
+//! of the average are kicked.
+//!
+//! ### Unbonding
+//!
+//! When a candidate leaves or is kicked, the bond is not released instantly since the candidate
+//! may still be a collator of the next sessions. The bond is queued to be released at the start of
+//! the session [`Config::UnbondingDelay`] sessions later. The candidate which registers again
+//! before that keeps the bond reserved.
+//!
+//! ### Session keys rotation
+//!
+//! The collators can schedule new session keys for a future session, together with the proof of
+//! possession signed by the keys over [`keys_ownership_payload`] of the account. This prevents
+//! setting keys which the node doesn't control. The keys are set when the session is planned, so
+//! they are active from that session.
+//!
 //! ### Rewards
 //!
 //! The Collator Selection pallet maintains an on-chain account (the "Pot"). In each block, the
@@ -84,7 +106,7 @@ pub mod pallet {
 			Currency, EnsureOrigin, ExistenceRequirement::KeepAlive, NamedReservableCurrency, ValidatorRegistration,
 			ValidatorSet,
 		},
-		BoundedVec, PalletId,
+		transactional, BoundedVec, PalletId,
 	};
 	use frame_support::{
 		sp_runtime::{
@@ -94,7 +116,7 @@ pub mod pallet {
 		weights::DispatchClass,
 	};
 	use frame_system::pallet_prelude::*;
-	use frame_system::Config as SystemConfig;
+	use frame_system::{Config as SystemConfig, RawOrigin};
 	use pallet_session::SessionManager;
 	use primitives::ReserveIdentifier;
 	use sp_staking::SessionIndex;
@@ -102,6 +124,18 @@ pub mod pallet {
 
 	pub const RESERVE_ID: ReserveIdentifier = ReserveIdentifier::CollatorSelection;
 	pub const POINT_PER_BLOCK: u32 = 10;
+	pub const KEYS_OWNERSHIP_PREFIX: &[u8] = b"acala/collator-session-keys";
+
+	/// The payload the session keys sign to prove the possession for `who`.
+	pub fn keys_ownership_payload<AccountId: Encode>(who: &AccountId) -> Vec<u8> {
+		(KEYS_OWNERSHIP_PREFIX, who).encode()
+	}
+
+	/// Verify the proof of possession of the session keys.
+	pub trait KeysOwnershipProof<AccountId, Keys> {
+		/// Whether `proof` is signed by `keys` over [`keys_ownership_payload`] of `who`.
+		fn verify(who: &AccountId, keys: &Keys, proof: &[u8]) -> bool;
+	}
 
 	type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as SystemConfig>::AccountId>>::Balance;
 
@@ -116,7 +150,7 @@ pub mod pallet {
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
-	pub trait Config: frame_system::Config {
+	pub trait Config: frame_system::Config + pallet_session::Config {
 		/// Overarching event type.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
@@ -153,6 +187,14 @@ pub mod pallet {
 		#[pallet::constant]
 		type CollatorKickThreshold: Get<Permill>;
 
+		/// The number of sessions the bond of a removed candidate is kept reserved before it's
+		/// released.
+		#[pallet::constant]
+		type UnbondingDelay: Get<SessionIndex>;
+
+		/// The proof of possession of the session keys.
+		type KeysOwnershipProof: KeysOwnershipProof<Self::AccountId, <Self as pallet_session::Config>::Keys>;
+
 		/// The weight information of this pallet.
 		type WeightInfo: WeightInfo;
 	}
@@ -188,6 +230,34 @@ pub mod pallet {
 	#[pallet::getter(fn session_points)]
 	pub type SessionPoints<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
 
+	/// The session index at which the bond of each removed candidate is released.
+	#[pallet::storage]
+	#[pallet::getter(fn unbonding)]
+	pub type Unbonding<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, SessionIndex, OptionQuery>;
+
+	/// The queue of the bonds to release at the start of each session.
+	#[pallet::storage]
+	pub type UnbondingQueue<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, SessionIndex, Twox64Concat, T::AccountId, (), OptionQuery>;
+
+	/// The session index each account has scheduled the session keys for.
+	#[pallet::storage]
+	#[pallet::getter(fn keys_scheduled_at)]
+	pub type KeysScheduledAt<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, SessionIndex, OptionQuery>;
+
+	/// The session keys scheduled to be set at each session.
+	#[pallet::storage]
+	#[pallet::getter(fn scheduled_keys)]
+	pub type ScheduledKeys<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		SessionIndex,
+		Twox64Concat,
+		T::AccountId,
+		<T as pallet_session::Config>::Keys,
+		OptionQuery,
+	>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub invulnerables: Vec<T::AccountId>,
@@ -241,6 +311,12 @@ pub mod pallet {
 		NewCandidacyBond(BalanceOf<T>),
 		CandidateAdded(T::AccountId, BalanceOf<T>),
 		CandidateRemoved(T::AccountId),
+		CandidateKicked(T::AccountId, u32),
+		BondUnbonding(T::AccountId, SessionIndex),
+		BondReleased(T::AccountId, BalanceOf<T>),
+		SessionKeysScheduled(T::AccountId, SessionIndex),
+		SessionKeysRotated(T::AccountId, SessionIndex),
+		SessionKeysRotationFailed(T::AccountId, SessionIndex),
 	}
 
 	// Errors inform users that something went wrong.
@@ -256,6 +332,7 @@ pub mod pallet {
 		AlreadyInvulnerable,
 		InvalidProof,
 		MaxInvulnerablesExceeded,
+		SessionTooEarly,
 	}
 
 	#[pallet::hooks]
@@ -263,7 +340,7 @@ pub mod pallet {
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		#[pallet::weight(T::WeightInfo::set_invulnerables(new.len() as u32))]
+		#[pallet::weight(<T as Config>::WeightInfo::set_invulnerables(new.len() as u32))]
 		pub fn set_invulnerables(origin: OriginFor<T>, new: Vec<T::AccountId>) -> DispatchResultWithPostInfo {
 			T::UpdateOrigin::ensure_origin(origin)?;
 			let bounded_new: BoundedVec<T::AccountId, T::MaxInvulnerables> =
@@ -273,7 +350,7 @@ pub mod pallet {
 			Ok(().into())
 		}
 
-		#[pallet::weight(T::WeightInfo::set_desired_candidates())]
+		#[pallet::weight(<T as Config>::WeightInfo::set_desired_candidates())]
 		pub fn set_desired_candidates(origin: OriginFor<T>, max: u32) -> DispatchResultWithPostInfo {
 			T::UpdateOrigin::ensure_origin(origin)?;
 			if max > T::MaxCandidates::get() {
@@ -284,7 +361,7 @@ pub mod pallet {
 			Ok(().into())
 		}
 
-		#[pallet::weight(T::WeightInfo::set_candidacy_bond())]
+		#[pallet::weight(<T as Config>::WeightInfo::set_candidacy_bond())]
 		pub fn set_candidacy_bond(origin: OriginFor<T>, bond: BalanceOf<T>) -> DispatchResultWithPostInfo {
 			T::UpdateOrigin::ensure_origin(origin)?;
 			<CandidacyBond<T>>::put(&bond);
@@ -292,7 +369,7 @@ pub mod pallet {
 			Ok(().into())
 		}
 
-		#[pallet::weight(T::WeightInfo::register_as_candidate(T::MaxCandidates::get()))]
+		#[pallet::weight(<T as Config>::WeightInfo::register_as_candidate(T::MaxCandidates::get()))]
 		pub fn register_as_candidate(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
 
@@ -300,26 +377,64 @@ pub mod pallet {
 			let bounded_candidates_len = Self::do_register_candidate(&who, deposit)?;
 
 			Self::deposit_event(Event::CandidateAdded(who, deposit));
-			Ok(Some(T::WeightInfo::register_as_candidate(bounded_candidates_len as u32)).into())
+			Ok(Some(<T as Config>::WeightInfo::register_as_candidate(
+				bounded_candidates_len as u32,
+			))
+			.into())
 		}
 
-		#[pallet::weight(T::WeightInfo::register_candidate(T::MaxCandidates::get()))]
+		#[pallet::weight(<T as Config>::WeightInfo::register_candidate(T::MaxCandidates::get()))]
 		pub fn register_candidate(origin: OriginFor<T>, new_candidate: T::AccountId) -> DispatchResultWithPostInfo {
 			T::UpdateOrigin::ensure_origin(origin)?;
 
 			let bounded_candidates_len = Self::do_register_candidate(&new_candidate, Zero::zero())?;
 
 			Self::deposit_event(Event::CandidateAdded(new_candidate, Zero::zero()));
-			Ok(Some(T::WeightInfo::register_candidate(bounded_candidates_len as u32)).into())
+			Ok(Some(<T as Config>::WeightInfo::register_candidate(
+				bounded_candidates_len as u32,
+			))
+			.into())
 		}
 
-		#[pallet::weight(T::WeightInfo::leave_intent(T::MaxCandidates::get()))]
+		#[pallet::weight(<T as Config>::WeightInfo::leave_intent(T::MaxCandidates::get()))]
 		pub fn leave_intent(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
 
 			let current_count = Self::try_remove_candidate(&who)?;
 
-			Ok(Some(T::WeightInfo::leave_intent(current_count as u32)).into())
+			Ok(Some(<T as Config>::WeightInfo::leave_intent(current_count as u32)).into())
+		}
+
+		/// Schedule new session keys to be active from `session`, with the proof of possession
+		/// signed by the keys over [`keys_ownership_payload`] of the caller. Scheduling again
+		/// replaces the keys scheduled before.
+		///
+		/// The keys of the next session are already planned, so `session` must be at least two
+		/// sessions after the current one.
+		#[pallet::weight(<T as Config>::WeightInfo::schedule_session_keys())]
+		pub fn schedule_session_keys(
+			origin: OriginFor<T>,
+			keys: <T as pallet_session::Config>::Keys,
+			proof: Vec<u8>,
+			session: SessionIndex,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				session >= T::ValidatorSet::session_index().saturating_add(2),
+				Error::<T>::SessionTooEarly
+			);
+			ensure!(
+				T::KeysOwnershipProof::verify(&who, &keys, &proof),
+				Error::<T>::InvalidProof
+			);
+
+			if let Some(previous) = <KeysScheduledAt<T>>::mutate(&who, |index| index.replace(session)) {
+				<ScheduledKeys<T>>::remove(previous, &who);
+			}
+			<ScheduledKeys<T>>::insert(session, &who, keys);
+
+			Self::deposit_event(Event::SessionKeysScheduled(who, session));
+			Ok(().into())
 		}
 	}
 
@@ -339,13 +454,72 @@ pub mod pallet {
 				);
 
 				candidates.take(who).ok_or(Error::<T>::NotCandidate)?;
-				T::Currency::unreserve_all_named(&RESERVE_ID, &who);
 				Ok(candidates.len())
 			})?;
 			Self::deposit_event(Event::CandidateRemoved(who.clone()));
+			Self::unbond(who);
 			Ok(current_count)
 		}
 
+		/// Queue the bond of the removed candidate to be released after `UnbondingDelay` sessions,
+		/// or release it now if there's no delay.
+		fn unbond(who: &T::AccountId) {
+			let delay = T::UnbondingDelay::get();
+			if delay.is_zero() {
+				let amount = T::Currency::unreserve_all_named(&RESERVE_ID, who);
+				Self::deposit_event(Event::BondReleased(who.clone(), amount));
+			} else {
+				let release_at = T::ValidatorSet::session_index().saturating_add(delay);
+				if let Some(previous) = <Unbonding<T>>::mutate(who, |index| index.replace(release_at)) {
+					<UnbondingQueue<T>>::remove(previous, who);
+				}
+				<UnbondingQueue<T>>::insert(release_at, who, ());
+				Self::deposit_event(Event::BondUnbonding(who.clone(), release_at));
+			}
+		}
+
+		/// Release the bonds queued to be released at the start of session `index`, returns the
+		/// number of the released bonds.
+		pub fn release_bonds(index: SessionIndex) -> u32 {
+			let mut released: u32 = 0;
+			for (who, _) in <UnbondingQueue<T>>::drain_prefix(index) {
+				<Unbonding<T>>::remove(&who);
+				let amount = T::Currency::unreserve_all_named(&RESERVE_ID, &who);
+				Self::deposit_event(Event::BondReleased(who, amount));
+				released = released.saturating_add(1);
+			}
+			released
+		}
+
+		/// Set the session keys scheduled for session `index`, returns the number of the scheduled
+		/// keys.
+		pub fn rotate_scheduled_keys(index: SessionIndex) -> u32 {
+			let mut rotated: u32 = 0;
+			for (who, keys) in <ScheduledKeys<T>>::drain_prefix(index) {
+				<KeysScheduledAt<T>>::remove(&who);
+				match Self::do_set_keys(&who, keys) {
+					Ok(_) => Self::deposit_event(Event::SessionKeysRotated(who, index)),
+					Err(why) => {
+						log::warn!(
+							target: "collator-selection",
+							"Failed to set the scheduled session keys of {:?}: {:?}",
+							who,
+							why,
+						);
+						Self::deposit_event(Event::SessionKeysRotationFailed(who, index));
+					}
+				}
+				rotated = rotated.saturating_add(1);
+			}
+			rotated
+		}
+
+		/// Ensured atomic.
+		#[transactional]
+		fn do_set_keys(who: &T::AccountId, keys: <T as pallet_session::Config>::Keys) -> DispatchResult {
+			pallet_session::Pallet::<T>::set_keys(RawOrigin::Signed(who.clone()).into(), keys, vec![])
+		}
+
 		/// Assemble the current set of candidates and invulnerables into the next collator set.
 		///
 		/// This is done on the fly, as frequent as we are told to do so, as the session manager.
@@ -372,6 +546,10 @@ pub mod pallet {
 					.try_insert(who.clone())
 					.map_err(|_| Error::<T>::MaxCandidatesExceeded)?;
 				T::Currency::ensure_reserved_named(&RESERVE_ID, &who, deposit)?;
+				// the unbonding bond is kept reserved for the candidate
+				if let Some(release_at) = <Unbonding<T>>::take(who) {
+					<UnbondingQueue<T>>::remove(release_at, who);
+				}
 				Ok(candidates.len())
 			})
 		}
@@ -404,7 +582,7 @@ pub mod pallet {
 			}
 
 			frame_system::Pallet::<T>::register_extra_weight_unchecked(
-				T::WeightInfo::note_author(),
+				<T as Config>::WeightInfo::note_author(),
 				DispatchClass::Mandatory,
 			);
 		}
@@ -415,6 +593,13 @@ pub mod pallet {
 	/// Play the role of the session manager.
 	impl<T: Config> SessionManager<T::AccountId> for Pallet<T> {
 		fn new_session(index: SessionIndex) -> Option<Vec<T::AccountId>> {
+			// the keys set now are queued for session `index`
+			let rotated = Self::rotate_scheduled_keys(index);
+			frame_system::Pallet::<T>::register_extra_weight_unchecked(
+				<T as Config>::WeightInfo::rotate_scheduled_keys(rotated),
+				DispatchClass::Mandatory,
+			);
+
 			let candidates = Self::candidates().into_iter().collect::<Vec<_>>();
 			let result = Self::assemble_collators(candidates);
 
@@ -427,7 +612,7 @@ pub mod pallet {
 			);
 
 			frame_system::Pallet::<T>::register_extra_weight_unchecked(
-				T::WeightInfo::new_session(),
+				<T as Config>::WeightInfo::new_session(),
 				DispatchClass::Mandatory,
 			);
 
@@ -435,6 +620,12 @@ pub mod pallet {
 		}
 
 		fn start_session(index: SessionIndex) {
+			let released = Self::release_bonds(index);
+			frame_system::Pallet::<T>::register_extra_weight_unchecked(
+				<T as Config>::WeightInfo::release_bonds(released),
+				DispatchClass::Mandatory,
+			);
+
 			let validators = T::ValidatorSet::validators();
 			let candidates = Self::candidates();
 			let mut collators = vec![];
@@ -455,7 +646,7 @@ pub mod pallet {
 			);
 
 			frame_system::Pallet::<T>::register_extra_weight_unchecked(
-				T::WeightInfo::start_session(candidates.len() as u32, collators.len() as u32),
+				<T as Config>::WeightInfo::start_session(candidates.len() as u32, collators.len() as u32),
 				DispatchClass::Mandatory,
 			);
 		}
@@ -491,12 +682,14 @@ pub mod pallet {
 							target: "collator-selection",
 							"Failed to remove candidate {:?}", why);
 						debug_assert!(false, "failed to remove candidate {:?}", why);
+					} else {
+						Self::deposit_event(Event::CandidateKicked(who, point));
 					}
 				}
 			}
 
 			frame_system::Pallet::<T>::register_extra_weight_unchecked(
-				T::WeightInfo::end_session(candidates_len, removed_len as u32),
+				<T as Config>::WeightInfo::end_session(candidates_len, removed_len as u32),
 				DispatchClass::Mandatory,
 			);
 		}
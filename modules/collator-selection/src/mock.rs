@@ -18,6 +18,7 @@
 
 use super::*;
 use crate as collator_selection;
+use codec::Decode;
 use frame_support::{
 	ord_parameter_types, parameter_types,
 	traits::{FindAuthor, GenesisBuild},
@@ -146,6 +147,15 @@ impl From<UintAuthorityId> for MockSessionKeys {
 	}
 }
 
+pub struct MockKeysOwnershipProof;
+impl KeysOwnershipProof<u64, MockSessionKeys> for MockKeysOwnershipProof {
+	fn verify(who: &u64, keys: &MockSessionKeys, proof: &[u8]) -> bool {
+		let payload = keys_ownership_payload(who);
+		<UintAuthorityId as RuntimeAppPublic>::Signature::decode(&mut &proof[..])
+			.map_or(false, |signature| keys.aura.verify(&payload, &signature))
+	}
+}
+
 parameter_types! {
 	pub static SessionHandlerCollators: Vec<u64> = vec![];
 	pub static SessionChangeBlock: u64 = 0;
@@ -195,6 +205,7 @@ parameter_types! {
 	pub const MaxCandidates: u32 = 4;
 	pub const MaxInvulnerables: u32 = 4;
 	pub const CollatorKickThreshold: Permill = Permill::from_percent(100);
+	pub const UnbondingDelay: u32 = 2;
 }
 
 impl Config for Test {
@@ -207,6 +218,8 @@ impl Config for Test {
 	type MaxCandidates = MaxCandidates;
 	type MaxInvulnerables = MaxInvulnerables;
 	type CollatorKickThreshold = CollatorKickThreshold;
+	type UnbondingDelay = UnbondingDelay;
+	type KeysOwnershipProof = MockKeysOwnershipProof;
 	type WeightInfo = ();
 }
 
@@ -17,14 +17,18 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate as collator_selection;
-use crate::{mock::*, Error, RESERVE_ID};
+use crate::{keys_ownership_payload, mock::*, Error, RESERVE_ID};
+use codec::Encode;
 use frame_support::{
 	assert_noop, assert_ok,
 	storage::bounded_btree_set::BoundedBTreeSet,
 	traits::{Currency, GenesisBuild, NamedReservableCurrency, OnInitialize},
 };
 use pallet_balances::Error as BalancesError;
-use sp_runtime::{testing::UintAuthorityId, traits::BadOrigin};
+use sp_runtime::{
+	testing::{TestSignature, UintAuthorityId},
+	traits::BadOrigin,
+};
 
 #[test]
 fn basic_setup_works() {
@@ -294,14 +298,62 @@ fn leave_intent() {
 			Error::<Test>::NotCandidate
 		);
 
-		// bond is returned
+		// bond is unbonding
 		assert_ok!(CollatorSelection::leave_intent(Origin::signed(3)));
-		assert_eq!(Balances::free_balance(3), 100);
+		assert_eq!(Balances::free_balance(3), 90);
+		assert_eq!(Balances::reserved_balance_named(&RESERVE_ID, &3), 10);
+		assert_eq!(CollatorSelection::unbonding(3), Some(2));
 
 		assert_noop!(
 			CollatorSelection::leave_intent(Origin::signed(4)),
 			Error::<Test>::BelowCandidatesMin
 		);
+
+		// bond is returned after the unbonding delay
+		initialize_to_block(19);
+		assert_eq!(Balances::free_balance(3), 90);
+		initialize_to_block(20);
+		assert_eq!(Balances::free_balance(3), 100);
+		assert_eq!(Balances::reserved_balance_named(&RESERVE_ID, &3), 0);
+		assert_eq!(CollatorSelection::unbonding(3), None);
+		System::assert_has_event(Event::CollatorSelection(crate::Event::BondReleased(3, 10)));
+	});
+}
+
+#[test]
+fn register_again_keeps_unbonding_bond() {
+	new_test_ext().execute_with(|| {
+		initialize_to_block(1);
+		assert_ok!(Session::set_keys(
+			Origin::signed(3),
+			MockSessionKeys {
+				aura: UintAuthorityId(3)
+			},
+			vec![]
+		));
+		assert_ok!(Session::set_keys(
+			Origin::signed(4),
+			MockSessionKeys {
+				aura: UintAuthorityId(4)
+			},
+			vec![]
+		));
+		assert_ok!(CollatorSelection::register_as_candidate(Origin::signed(3)));
+		assert_ok!(CollatorSelection::register_as_candidate(Origin::signed(4)));
+
+		assert_ok!(CollatorSelection::leave_intent(Origin::signed(3)));
+		System::assert_last_event(Event::CollatorSelection(crate::Event::BondUnbonding(3, 2)));
+		assert!(crate::UnbondingQueue::<Test>::contains_key(2, 3));
+
+		// the unbonding is cancelled
+		assert_ok!(CollatorSelection::register_as_candidate(Origin::signed(3)));
+		assert_eq!(Balances::free_balance(3), 90);
+		assert_eq!(Balances::reserved_balance_named(&RESERVE_ID, &3), 10);
+		assert_eq!(CollatorSelection::unbonding(3), None);
+		assert!(!crate::UnbondingQueue::<Test>::contains_key(2, 3));
+
+		assert_eq!(CollatorSelection::release_bonds(2), 0);
+		assert_eq!(Balances::reserved_balance_named(&RESERVE_ID, &3), 10);
 	});
 }
 
@@ -423,11 +475,129 @@ fn kick_mechanism() {
 		let mut collators = BoundedBTreeSet::new();
 		assert_ok!(collators.try_insert(4));
 		assert_eq!(CollatorSelection::candidates(), collators);
-		// kicked collator gets funds back
+		System::assert_has_event(Event::CollatorSelection(crate::Event::CandidateKicked(3, 0)));
+		// kicked collator gets funds back after the unbonding delay
+		assert_eq!(Balances::free_balance(3), 90);
+		assert_eq!(CollatorSelection::unbonding(3), Some(4));
+		assert_eq!(CollatorSelection::release_bonds(4), 1);
 		assert_eq!(Balances::free_balance(3), 100);
 	});
 }
 
+fn ownership_proof(who: u64, key: u64) -> Vec<u8> {
+	TestSignature(key, keys_ownership_payload(&who)).encode()
+}
+
+#[test]
+fn schedule_session_keys_works() {
+	new_test_ext().execute_with(|| {
+		initialize_to_block(1);
+		let keys = MockSessionKeys {
+			aura: UintAuthorityId(33),
+		};
+
+		assert_noop!(
+			CollatorSelection::schedule_session_keys(Origin::signed(3), keys.clone(), ownership_proof(3, 33), 1),
+			Error::<Test>::SessionTooEarly
+		);
+		// signed by other keys
+		assert_noop!(
+			CollatorSelection::schedule_session_keys(Origin::signed(3), keys.clone(), ownership_proof(3, 3), 2),
+			Error::<Test>::InvalidProof
+		);
+		// signed for other account
+		assert_noop!(
+			CollatorSelection::schedule_session_keys(Origin::signed(3), keys.clone(), ownership_proof(4, 33), 2),
+			Error::<Test>::InvalidProof
+		);
+		assert_noop!(
+			CollatorSelection::schedule_session_keys(Origin::signed(3), keys.clone(), vec![1, 2, 3], 2),
+			Error::<Test>::InvalidProof
+		);
+
+		assert_ok!(CollatorSelection::schedule_session_keys(
+			Origin::signed(3),
+			keys.clone(),
+			ownership_proof(3, 33),
+			2
+		));
+		System::assert_last_event(Event::CollatorSelection(crate::Event::SessionKeysScheduled(3, 2)));
+		assert_eq!(CollatorSelection::keys_scheduled_at(3), Some(2));
+		assert_eq!(CollatorSelection::scheduled_keys(2, 3), Some(keys.clone()));
+
+		// schedule again replaces the keys scheduled before
+		assert_ok!(CollatorSelection::schedule_session_keys(
+			Origin::signed(3),
+			keys.clone(),
+			ownership_proof(3, 33),
+			3
+		));
+		assert_eq!(CollatorSelection::keys_scheduled_at(3), Some(3));
+		assert_eq!(CollatorSelection::scheduled_keys(2, 3), None);
+		assert_eq!(CollatorSelection::scheduled_keys(3, 3), Some(keys));
+	});
+}
+
+#[test]
+fn scheduled_session_keys_are_rotated() {
+	new_test_ext().execute_with(|| {
+		initialize_to_block(1);
+		assert_ok!(Session::set_keys(
+			Origin::signed(3),
+			MockSessionKeys {
+				aura: UintAuthorityId(3)
+			},
+			vec![]
+		));
+		assert_ok!(Session::set_keys(
+			Origin::signed(4),
+			MockSessionKeys {
+				aura: UintAuthorityId(4)
+			},
+			vec![]
+		));
+		assert_ok!(CollatorSelection::register_as_candidate(Origin::signed(3)));
+
+		let new_keys = MockSessionKeys {
+			aura: UintAuthorityId(33),
+		};
+		assert_ok!(CollatorSelection::schedule_session_keys(
+			Origin::signed(3),
+			new_keys.clone(),
+			ownership_proof(3, 33),
+			3
+		));
+		// the keys of 4 are owned by 4
+		assert_ok!(CollatorSelection::schedule_session_keys(
+			Origin::signed(5),
+			MockSessionKeys {
+				aura: UintAuthorityId(4)
+			},
+			ownership_proof(5, 4),
+			3
+		));
+
+		initialize_to_block(10);
+		assert_eq!(
+			Session::queued_keys().last(),
+			Some(&(
+				3,
+				MockSessionKeys {
+					aura: UintAuthorityId(3)
+				}
+			))
+		);
+
+		// session 3 is planned
+		initialize_to_block(20);
+		System::assert_has_event(Event::CollatorSelection(crate::Event::SessionKeysRotated(3, 3)));
+		System::assert_has_event(Event::CollatorSelection(crate::Event::SessionKeysRotationFailed(5, 3)));
+		assert_eq!(Session::queued_keys().last(), Some(&(3, new_keys)));
+		assert_eq!(CollatorSelection::keys_scheduled_at(3), None);
+		assert_eq!(CollatorSelection::scheduled_keys(3, 3), None);
+	});
+}
+
 #[test]
 fn exceeding_max_invulnerables_should_fail() {
 	new_test_ext().execute_with(|| {
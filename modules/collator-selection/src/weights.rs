@@ -53,9 +53,33 @@ pub trait WeightInfo {
 	fn register_as_candidate(c: u32, ) -> Weight;
 	fn register_candidate(c: u32, ) -> Weight;
 	fn leave_intent(c: u32, ) -> Weight;
+	fn schedule_session_keys() -> Weight;
+	fn rotate_scheduled_keys(k: u32, ) -> Weight;
+	fn schedule_session_keys() -> Weight {
+		(41_732_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn rotate_scheduled_keys(k: u32, ) -> Weight {
+		(1_986_000 as Weight)
+			// Standard Error: 11_000
+			.saturating_add((31_208_000 as Weight).saturating_mul(k as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((4 as Weight).saturating_mul(k as Weight)))
+			.saturating_add(T::DbWeight::get().writes((5 as Weight).saturating_mul(k as Weight)))
+	}
 	fn note_author() -> Weight;
 	fn new_session() -> Weight;
 	fn start_session(r: u32, c: u32, ) -> Weight;
+	fn release_bonds(u: u32, ) -> Weight;
+	fn release_bonds(u: u32, ) -> Weight {
+		(2_104_000 as Weight)
+			// Standard Error: 9_000
+			.saturating_add((24_371_000 as Weight).saturating_mul(u as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(u as Weight)))
+	}
 	fn end_session(r: u32, c: u32, ) -> Weight;
 }
 
@@ -162,6 +186,19 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
+	fn schedule_session_keys() -> Weight {
+		(41_732_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn rotate_scheduled_keys(k: u32, ) -> Weight {
+		(1_986_000 as Weight)
+			// Standard Error: 11_000
+			.saturating_add((31_208_000 as Weight).saturating_mul(k as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((4 as Weight).saturating_mul(k as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((5 as Weight).saturating_mul(k as Weight)))
+	}
 	fn note_author() -> Weight {
 		(7_070_000 as Weight)
 			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
@@ -179,6 +216,14 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
+	fn release_bonds(u: u32, ) -> Weight {
+		(2_104_000 as Weight)
+			// Standard Error: 9_000
+			.saturating_add((24_371_000 as Weight).saturating_mul(u as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(u as Weight)))
+	}
 	fn end_session(_r: u32, c: u32, ) -> Weight {
 		(6_384_553_000 as Weight)
 			// Standard Error: 125_000
@@ -29,14 +29,14 @@ use frame_support::{
 		Currency as PalletCurrency, ExistenceRequirement, Get, LockableCurrency as PalletLockableCurrency,
 		ReservableCurrency as PalletReservableCurrency, WithdrawReasons,
 	},
-	transactional,
+	transactional, BoundedVec, CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
 };
 use frame_system::pallet_prelude::*;
 use orml_traits::{
 	arithmetic::{Signed, SimpleArithmetic},
 	currency::TransferAll,
 	BalanceStatus, BasicCurrency, BasicCurrencyExtended, BasicLockableCurrency, BasicReservableCurrency,
-	LockIdentifier, MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency, MultiReservableCurrency,
+	LockIdentifier, MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency, MultiReservableCurrency, OnDust,
 };
 use primitives::{evm::EvmAddress, CurrencyId};
 use sp_io::hashing::blake2_256;
@@ -48,8 +48,12 @@ use sp_std::{
 	convert::{TryFrom, TryInto},
 	fmt::Debug,
 	marker, result,
+	vec::Vec,
+};
+use support::{
+	AddressMapping, DEXManager, DispatchableTask, EVMBridge, FreezeChecker, IdleScheduler, InvokeContext,
+	MultiCurrencyAllowance, TaskResult,
 };
-use support::{AddressMapping, EVMBridge, InvokeContext};
 
 mod mock;
 mod tests;
@@ -90,6 +94,20 @@ pub mod module {
 		/// Mapping from address to account id.
 		type AddressMapping: AddressMapping<Self::AccountId>;
 		type EVMBridge: EVMBridge<Self::AccountId, BalanceOf<Self>>;
+
+		/// The origin which may opt currencies out of the dust conversion.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The maximum length of the memo of transfer.
+		#[pallet::constant]
+		type MaxMemoLength: Get<u32>;
+
+		/// The maximum number of transfers in a batch.
+		#[pallet::constant]
+		type MaxBatchTransfers: Get<u32>;
+
+		/// Checks the frozen funds, which can't be transferred, withdrawn or reserved.
+		type FreezeChecker: FreezeChecker<Self::AccountId>;
 	}
 
 	#[pallet::error]
@@ -102,6 +120,10 @@ pub mod module {
 		Erc20InvalidOperation,
 		/// EVM account not found
 		EvmAccountNotFound,
+		/// The spender's allowance is too low.
+		AllowanceTooLow,
+		/// The number of transfers exceeds `MaxBatchTransfers`.
+		TooManyTransfers,
 	}
 
 	#[pallet::event]
@@ -115,7 +137,49 @@ pub mod module {
 		Deposited(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
 		/// Withdraw success. [currency_id, who, amount]
 		Withdrawn(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
-	}
+		/// Allowance updated. [currency_id, owner, spender, amount]
+		Approved(CurrencyIdOf<T>, T::AccountId, T::AccountId, BalanceOf<T>),
+		/// Dust was converted to native currency via DEX. [currency_id, who, dust_amount,
+		/// native_amount]
+		DustConverted(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>, BalanceOf<T>),
+		/// Dust was swept to the treasury. [currency_id, who, amount]
+		DustSwept(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
+		/// The dust conversion of currency was updated. [currency_id, disabled]
+		DustConversionUpdated(CurrencyIdOf<T>, bool),
+		/// Currency transfer with memo success. [currency_id, from, to, amount, memo]
+		TransferredWithMemo(
+			CurrencyIdOf<T>,
+			T::AccountId,
+			T::AccountId,
+			BalanceOf<T>,
+			BoundedVec<u8, T::MaxMemoLength>,
+		),
+		/// Batch transfer completed. [from, succeeded, failed_indexes]
+		BatchTransferred(T::AccountId, u32, Vec<u32>),
+	}
+
+	/// The amount a spender is allowed to transfer on behalf of an owner.
+	///
+	/// Allowances: double_map (CurrencyId, Owner), Spender => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn allowances)]
+	pub type Allowances<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		(CurrencyIdOf<T>, T::AccountId),
+		Twox64Concat,
+		T::AccountId,
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	/// The currencies whose dust is swept to the treasury directly instead of being converted
+	/// to native currency.
+	///
+	/// DustConversionDisabled: map CurrencyId => bool
+	#[pallet::storage]
+	#[pallet::getter(fn dust_conversion_disabled)]
+	pub type DustConversionDisabled<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, bool, ValueQuery>;
 
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
@@ -160,6 +224,65 @@ pub mod module {
 			Ok(().into())
 		}
 
+		/// Transfer some balance to another account under `currency_id`, with a `memo` that
+		/// identifies the payment, e.g. the reference of a deposit to exchange.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::transfer_with_memo())]
+		pub fn transfer_with_memo(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+			memo: BoundedVec<u8, T::MaxMemoLength>,
+		) -> DispatchResultWithPostInfo {
+			let from = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(dest)?;
+			<Self as MultiCurrency<T::AccountId>>::transfer(currency_id, &from, &to, amount)?;
+
+			Self::deposit_event(Event::TransferredWithMemo(currency_id, from, to, amount, memo));
+			Ok(().into())
+		}
+
+		/// Transfer multiple balances under any currencies in one call, e.g. the payouts of
+		/// treasuries and multisigs.
+		///
+		/// If `all_or_nothing`, the batch fails when any transfer fails. Otherwise the failed
+		/// transfers are skipped and their indexes are reported in the event.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::batch_transfer(transfers.len() as u32))]
+		#[transactional]
+		pub fn batch_transfer(
+			origin: OriginFor<T>,
+			transfers: Vec<(CurrencyIdOf<T>, <T::Lookup as StaticLookup>::Source, BalanceOf<T>)>,
+			all_or_nothing: bool,
+		) -> DispatchResultWithPostInfo {
+			let from = ensure_signed(origin)?;
+			ensure!(
+				transfers.len() as u32 <= T::MaxBatchTransfers::get(),
+				Error::<T>::TooManyTransfers
+			);
+
+			let mut succeeded: u32 = 0;
+			let mut failed_indexes: Vec<u32> = Vec::new();
+			for (index, (currency_id, dest, amount)) in transfers.into_iter().enumerate() {
+				let result = T::Lookup::lookup(dest)
+					.map_err(DispatchError::from)
+					.and_then(|to| Self::do_batch_transfer_item(currency_id, &from, &to, amount));
+				match result {
+					Ok(()) => succeeded += 1,
+					Err(e) if all_or_nothing => return Err(e.into()),
+					Err(_) => failed_indexes.push(index as u32),
+				}
+			}
+
+			Self::deposit_event(Event::BatchTransferred(from, succeeded, failed_indexes));
+			Ok(().into())
+		}
+
 		/// update amount of account `who` under `currency_id`.
 		///
 		/// The dispatch origin of this call must be _Root_.
@@ -175,6 +298,84 @@ pub mod module {
 			<Self as MultiCurrencyExtended<T::AccountId>>::update_balance(currency_id, &dest, amount)?;
 			Ok(().into())
 		}
+
+		/// Enable or disable the conversion of the dust of `currency_id` to native currency.
+		/// When disabled, the dust is swept to the treasury directly.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight(T::WeightInfo::set_dust_conversion())]
+		#[transactional]
+		pub fn set_dust_conversion(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			disabled: bool,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			DustConversionDisabled::<T>::mutate_exists(currency_id, |maybe_disabled| {
+				*maybe_disabled = if disabled { Some(true) } else { None };
+			});
+			Self::deposit_event(Event::DustConversionUpdated(currency_id, disabled));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Ensured atomic, so the failed transfer in best-effort batch leaves no changes.
+	#[transactional]
+	fn do_batch_transfer_item(
+		currency_id: CurrencyIdOf<T>,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		<Self as MultiCurrency<T::AccountId>>::transfer(currency_id, from, to, amount)
+	}
+
+	/// The runtime can't mint or burn Erc20 tokens, so a deposit releases tokens held by the bridge
+	/// to `who`.
+	#[transactional]
+	fn deposit_erc20(contract: EvmAddress, who: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+		let holding_address = erc20_holding_address();
+		let held = T::EVMBridge::balance_of(
+			InvokeContext {
+				contract,
+				sender: Default::default(),
+				origin: Default::default(),
+			},
+			holding_address,
+		)
+		.unwrap_or_default();
+		ensure!(held >= amount, Error::<T>::BalanceTooLow);
+
+		let address = T::AddressMapping::get_or_create_evm_address(&who);
+		T::EVMBridge::transfer(
+			InvokeContext {
+				contract,
+				sender: holding_address,
+				origin: address,
+			},
+			address,
+			amount,
+		)
+	}
+
+	/// The runtime can't mint or burn Erc20 tokens, so a withdrawal moves the tokens of `who` to be
+	/// held by the bridge.
+	#[transactional]
+	fn withdraw_erc20(contract: EvmAddress, who: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+		Self::ensure_can_withdraw(CurrencyId::Erc20(contract), who, amount)?;
+
+		let address = T::AddressMapping::get_evm_address(&who).ok_or(Error::<T>::EvmAccountNotFound)?;
+		T::EVMBridge::transfer(
+			InvokeContext {
+				contract,
+				sender: address,
+				origin: address,
+			},
+			erc20_holding_address(),
+			amount,
+		)
 	}
 }
 
@@ -192,12 +393,17 @@ impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 
 	fn total_issuance(currency_id: Self::CurrencyId) -> Self::Balance {
 		match currency_id {
-			CurrencyId::Erc20(contract) => T::EVMBridge::total_supply(InvokeContext {
-				contract,
-				sender: Default::default(),
-				origin: Default::default(),
-			})
-			.unwrap_or_default(),
+			CurrencyId::Erc20(contract) => {
+				let context = InvokeContext {
+					contract,
+					sender: Default::default(),
+					origin: Default::default(),
+				};
+				// the tokens held by the bridge are withdrawn, exclude them from the issuance.
+				T::EVMBridge::total_supply(context)
+					.unwrap_or_default()
+					.saturating_sub(T::EVMBridge::balance_of(context, erc20_holding_address()).unwrap_or_default())
+			}
 			id if id == T::GetNativeCurrencyId::get() => T::NativeCurrency::total_issuance(),
 			_ => T::MultiCurrency::total_issuance(currency_id),
 		}
@@ -240,6 +446,7 @@ impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 	}
 
 	fn ensure_can_withdraw(currency_id: Self::CurrencyId, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+		T::FreezeChecker::ensure_not_frozen(currency_id, who)?;
 		match currency_id {
 			CurrencyId::Erc20(contract) => {
 				let address = T::AddressMapping::get_evm_address(&who).ok_or(Error::<T>::EvmAccountNotFound)?;
@@ -269,6 +476,7 @@ impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 		if amount.is_zero() || from == to {
 			return Ok(());
 		}
+		T::FreezeChecker::ensure_not_frozen(currency_id, from)?;
 
 		match currency_id {
 			CurrencyId::Erc20(contract) => {
@@ -299,7 +507,7 @@ impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 			return Ok(());
 		}
 		match currency_id {
-			CurrencyId::Erc20(_) => return Err(Error::<T>::Erc20InvalidOperation.into()),
+			CurrencyId::Erc20(contract) => Self::deposit_erc20(contract, who, amount)?,
 			id if id == T::GetNativeCurrencyId::get() => T::NativeCurrency::deposit(who, amount)?,
 			_ => T::MultiCurrency::deposit(currency_id, who, amount)?,
 		}
@@ -311,8 +519,9 @@ impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 		if amount.is_zero() {
 			return Ok(());
 		}
+		T::FreezeChecker::ensure_not_frozen(currency_id, who)?;
 		match currency_id {
-			CurrencyId::Erc20(_) => return Err(Error::<T>::Erc20InvalidOperation.into()),
+			CurrencyId::Erc20(contract) => Self::withdraw_erc20(contract, who, amount)?,
 			id if id == T::GetNativeCurrencyId::get() => T::NativeCurrency::withdraw(who, amount)?,
 			_ => T::MultiCurrency::withdraw(currency_id, who, amount)?,
 		}
@@ -341,8 +550,21 @@ impl<T: Config> MultiCurrencyExtended<T::AccountId> for Pallet<T> {
 	type Amount = AmountOf<T>;
 
 	fn update_balance(currency_id: Self::CurrencyId, who: &T::AccountId, by_amount: Self::Amount) -> DispatchResult {
+		if by_amount.is_negative() {
+			T::FreezeChecker::ensure_not_frozen(currency_id, who)?;
+		}
 		match currency_id {
-			CurrencyId::Erc20(_) => return Err(Error::<T>::Erc20InvalidOperation.into()),
+			CurrencyId::Erc20(contract) => {
+				let by_balance = by_amount
+					.abs()
+					.try_into()
+					.map_err(|_| Error::<T>::AmountIntoBalanceFailed)?;
+				if by_amount.is_positive() {
+					Self::deposit_erc20(contract, who, by_balance)?
+				} else {
+					Self::withdraw_erc20(contract, who, by_balance)?
+				}
+			}
 			id if id == T::GetNativeCurrencyId::get() => T::NativeCurrency::update_balance(who, by_amount)?,
 			_ => T::MultiCurrency::update_balance(currency_id, who, by_amount)?,
 		}
@@ -391,6 +613,9 @@ impl<T: Config> MultiLockableCurrency<T::AccountId> for Pallet<T> {
 
 impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 	fn can_reserve(currency_id: Self::CurrencyId, who: &T::AccountId, value: Self::Balance) -> bool {
+		if T::FreezeChecker::ensure_not_frozen(currency_id, who).is_err() {
+			return false;
+		}
 		match currency_id {
 			CurrencyId::Erc20(_) => Self::ensure_can_withdraw(currency_id, who, value).is_ok(),
 			id if id == T::GetNativeCurrencyId::get() => T::NativeCurrency::can_reserve(who, value),
@@ -428,6 +653,7 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 	}
 
 	fn reserve(currency_id: Self::CurrencyId, who: &T::AccountId, value: Self::Balance) -> DispatchResult {
+		T::FreezeChecker::ensure_not_frozen(currency_id, who)?;
 		match currency_id {
 			CurrencyId::Erc20(contract) => {
 				if value.is_zero() {
@@ -494,6 +720,7 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 		value: Self::Balance,
 		status: BalanceStatus,
 	) -> result::Result<Self::Balance, DispatchError> {
+		T::FreezeChecker::ensure_not_frozen(currency_id, slashed)?;
 		match currency_id {
 			CurrencyId::Erc20(contract) => {
 				if value.is_zero() {
@@ -833,9 +1060,54 @@ where
 	}
 }
 
+impl<T: Config> MultiCurrencyAllowance<T::AccountId> for Pallet<T> {
+	type CurrencyId = CurrencyIdOf<T>;
+	type Balance = BalanceOf<T>;
+
+	fn allowance(currency_id: Self::CurrencyId, owner: &T::AccountId, spender: &T::AccountId) -> Self::Balance {
+		Self::allowances((currency_id, owner), spender)
+	}
+
+	fn approve(
+		currency_id: Self::CurrencyId,
+		owner: &T::AccountId,
+		spender: &T::AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult {
+		if amount.is_zero() {
+			Allowances::<T>::remove((currency_id, owner), spender);
+		} else {
+			Allowances::<T>::insert((currency_id, owner), spender, amount);
+		}
+
+		Self::deposit_event(Event::Approved(currency_id, owner.clone(), spender.clone(), amount));
+		Ok(())
+	}
+
+	#[transactional]
+	fn transfer_from(
+		currency_id: Self::CurrencyId,
+		spender: &T::AccountId,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult {
+		if spender != from {
+			let remaining = Self::allowance(currency_id, from, spender)
+				.checked_sub(&amount)
+				.ok_or(Error::<T>::AllowanceTooLow)?;
+			Self::approve(currency_id, from, spender, remaining)?;
+		}
+
+		<Self as MultiCurrency<T::AccountId>>::transfer(currency_id, from, to, amount)
+	}
+}
+
 impl<T: Config> TransferAll<T::AccountId> for Pallet<T> {
 	#[transactional]
 	fn transfer_all(source: &T::AccountId, dest: &T::AccountId) -> DispatchResult {
+		T::FreezeChecker::ensure_no_frozen_currency(source)?;
+
 		// transfer non-native free to dest
 		T::MultiCurrency::transfer_all(source, dest)?;
 
@@ -844,7 +1116,138 @@ impl<T: Config> TransferAll<T::AccountId> for Pallet<T> {
 	}
 }
 
+/// Handles the dust of `MultiCurrency` by converting it to native currency via `DEX`, and sweeping
+/// it to the `GetTreasuryAccount` if the conversion is disabled for the currency or fails, e.g.
+/// there is no liquidity or the converted amount is below the existential deposit. The conversion
+/// is scheduled as a `DustTask` on `Scheduler`, so the swap isn't done in the transfer that leaves
+/// the dust, and is swept right away if it can't be scheduled.
+pub struct SweepDust<T, DEX, GetTreasuryAccount, Scheduler = ()>(
+	marker::PhantomData<(T, DEX, GetTreasuryAccount, Scheduler)>,
+);
+
+impl<T, DEX, GetTreasuryAccount, Scheduler> OnDust<T::AccountId, CurrencyId, BalanceOf<T>>
+	for SweepDust<T, DEX, GetTreasuryAccount, Scheduler>
+where
+	T: Config,
+	DEX: DEXManager<T::AccountId, CurrencyId, BalanceOf<T>>,
+	GetTreasuryAccount: Get<T::AccountId>,
+	Scheduler: IdleScheduler<DustTask<T, DEX, GetTreasuryAccount>>,
+{
+	fn on_dust(who: &T::AccountId, currency_id: CurrencyId, amount: BalanceOf<T>) {
+		if currency_id != T::GetNativeCurrencyId::get()
+			&& !Pallet::<T>::dust_conversion_disabled(currency_id)
+			&& Scheduler::schedule(DustTask::convert_dust(who.clone(), currency_id)).is_ok()
+		{
+			return;
+		}
+
+		sweep_dust::<T, GetTreasuryAccount>(who, currency_id, amount);
+	}
+}
+
+/// The background tasks of the dust handling.
+#[derive(Encode, Decode, CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound)]
+pub enum DustTask<T: Config, DEX, GetTreasuryAccount> {
+	/// Convert the dust of `currency_id` of `who` to native currency, or sweep it to treasury if
+	/// the conversion fails. It's skipped if the account isn't left with dust any more.
+	ConvertDust {
+		who: T::AccountId,
+		currency_id: CurrencyId,
+		#[codec(skip)]
+		_marker: marker::PhantomData<(DEX, GetTreasuryAccount)>,
+	},
+}
+
+impl<T: Config, DEX, GetTreasuryAccount> DustTask<T, DEX, GetTreasuryAccount> {
+	/// The task to convert the dust of `currency_id` of `who`.
+	pub fn convert_dust(who: T::AccountId, currency_id: CurrencyId) -> Self {
+		DustTask::ConvertDust {
+			who,
+			currency_id,
+			_marker: marker::PhantomData,
+		}
+	}
+}
+
+impl<T, DEX, GetTreasuryAccount> DispatchableTask for DustTask<T, DEX, GetTreasuryAccount>
+where
+	T: Config,
+	DEX: DEXManager<T::AccountId, CurrencyId, BalanceOf<T>>,
+	GetTreasuryAccount: Get<T::AccountId>,
+{
+	fn dispatch(&mut self, weight: Weight) -> TaskResult {
+		let convert_weight = T::WeightInfo::convert_dust();
+		if weight < convert_weight {
+			return TaskResult {
+				result: Ok(()),
+				used_weight: 0,
+				finished: false,
+			};
+		}
+
+		match self {
+			DustTask::ConvertDust { who, currency_id, .. } => {
+				// the account may be topped up or emptied since the dust was left
+				let amount = T::MultiCurrency::free_balance(*currency_id, who);
+				let is_dust = T::MultiCurrency::total_balance(*currency_id, who)
+					< T::MultiCurrency::minimum_balance(*currency_id);
+				if is_dust && !amount.is_zero() {
+					convert_dust::<T, DEX, GetTreasuryAccount>(who, *currency_id, amount);
+				}
+			}
+		}
+
+		TaskResult {
+			result: Ok(()),
+			used_weight: convert_weight,
+			finished: true,
+		}
+	}
+}
+
+/// Convert the dust to native currency via `DEX`, or sweep it if the conversion is disabled or
+/// fails.
+fn convert_dust<T, DEX, GetTreasuryAccount>(who: &T::AccountId, currency_id: CurrencyId, amount: BalanceOf<T>)
+where
+	T: Config,
+	DEX: DEXManager<T::AccountId, CurrencyId, BalanceOf<T>>,
+	GetTreasuryAccount: Get<T::AccountId>,
+{
+	let native_currency_id = T::GetNativeCurrencyId::get();
+	if currency_id != native_currency_id && !Pallet::<T>::dust_conversion_disabled(currency_id) {
+		// the swap is atomic, the dust is left untouched if it fails
+		let converted =
+			DEX::swap_with_exact_supply(who, &[currency_id, native_currency_id], amount, Zero::zero(), None);
+		if let Ok(native_amount) = converted {
+			if !native_amount.is_zero() {
+				Pallet::<T>::deposit_event(Event::DustConverted(currency_id, who.clone(), amount, native_amount));
+				return;
+			}
+		}
+	}
+
+	sweep_dust::<T, GetTreasuryAccount>(who, currency_id, amount);
+}
+
+/// Sweep the dust to treasury account.
+fn sweep_dust<T, GetTreasuryAccount>(who: &T::AccountId, currency_id: CurrencyId, amount: BalanceOf<T>)
+where
+	T: Config,
+	GetTreasuryAccount: Get<T::AccountId>,
+{
+	// ignore the result, if failed will leave some dust which still could be recycled.
+	if T::MultiCurrency::transfer(currency_id, who, &GetTreasuryAccount::get(), amount).is_ok() {
+		Pallet::<T>::deposit_event(Event::DustSwept(currency_id, who.clone(), amount));
+	}
+}
+
 fn reserve_address(address: EvmAddress) -> EvmAddress {
 	let payload = (b"erc20:", address);
 	EvmAddress::from_slice(&payload.using_encoded(blake2_256)[0..20])
 }
+
+/// The address holding the withdrawn Erc20 tokens.
+fn erc20_holding_address() -> EvmAddress {
+	let payload = b"erc20:holding";
+	EvmAddress::from_slice(&payload.using_encoded(blake2_256)[0..20])
+}
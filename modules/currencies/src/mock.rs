@@ -29,12 +29,13 @@ use sp_runtime::{
 	traits::{AccountIdConversion, IdentityLookup},
 	AccountId32, Perbill,
 };
-use support::{mocks::MockAddressMapping, AddressMapping};
+use support::{mocks::MockAddressMapping, AddressMapping, Ratio};
 
 use super::*;
 use frame_system::EnsureSignedBy;
 use sp_core::{bytes::from_hex, H160};
-use sp_std::str::FromStr;
+use sp_std::{cell::RefCell, str::FromStr};
+use std::collections::BTreeSet;
 
 pub use crate as currencies;
 
@@ -75,8 +76,11 @@ impl frame_system::Config for Runtime {
 type Balance = u128;
 
 parameter_type_with_key! {
-	pub ExistentialDeposits: |_currency_id: CurrencyId| -> Balance {
-		Default::default()
+	pub ExistentialDeposits: |currency_id: CurrencyId| -> Balance {
+		match currency_id {
+			CurrencyId::Token(TokenSymbol::DOT) | CurrencyId::Token(TokenSymbol::LDOT) => 2,
+			_ => Default::default(),
+		}
 	};
 }
 
@@ -91,13 +95,83 @@ impl tokens::Config for Runtime {
 	type Amount = i64;
 	type CurrencyId = CurrencyId;
 	type ExistentialDeposits = ExistentialDeposits;
-	type OnDust = tokens::TransferDust<Runtime, DustAccount>;
+	type OnDust = SweepDust<Runtime, MockDEX, DustAccount>;
 	type WeightInfo = ();
 	type MaxLocks = MaxLocks;
 }
 
 pub const NATIVE_CURRENCY_ID: CurrencyId = CurrencyId::Token(TokenSymbol::ACA);
 pub const X_TOKEN_ID: CurrencyId = CurrencyId::Token(TokenSymbol::AUSD);
+pub const Y_TOKEN_ID: CurrencyId = CurrencyId::Token(TokenSymbol::DOT);
+pub const Z_TOKEN_ID: CurrencyId = CurrencyId::Token(TokenSymbol::LDOT);
+
+pub struct MockDEX;
+impl DEXManager<AccountId, CurrencyId, Balance> for MockDEX {
+	fn get_liquidity_pool(_currency_id_a: CurrencyId, _currency_id_b: CurrencyId) -> (Balance, Balance) {
+		unimplemented!()
+	}
+
+	fn get_liquidity_token_address(_currency_id_a: CurrencyId, _currency_id_b: CurrencyId) -> Option<H160> {
+		unimplemented!()
+	}
+
+	fn get_swap_target_amount(_: &[CurrencyId], _: Balance, _: Option<Ratio>) -> Option<Balance> {
+		unimplemented!()
+	}
+
+	fn get_swap_supply_amount(_: &[CurrencyId], _: Balance, _: Option<Ratio>) -> Option<Balance> {
+		unimplemented!()
+	}
+
+	// only `Y_TOKEN_ID` to native currency has liquidity, swap at 1:10
+	fn swap_with_exact_supply(
+		who: &AccountId,
+		path: &[CurrencyId],
+		supply_amount: Balance,
+		_: Balance,
+		_: Option<Ratio>,
+	) -> sp_std::result::Result<Balance, DispatchError> {
+		ensure!(path == [Y_TOKEN_ID, NATIVE_CURRENCY_ID], "no liquidity");
+		let target_amount = supply_amount * 10;
+		Tokens::withdraw(Y_TOKEN_ID, who, supply_amount)?;
+		NativeCurrency::deposit(who, target_amount)?;
+		Ok(target_amount)
+	}
+
+	fn swap_with_exact_target(
+		_: &AccountId,
+		_: &[CurrencyId],
+		_: Balance,
+		_: Balance,
+		_: Option<Ratio>,
+	) -> sp_std::result::Result<Balance, DispatchError> {
+		unimplemented!()
+	}
+
+	fn add_liquidity(
+		_: &AccountId,
+		_: CurrencyId,
+		_: CurrencyId,
+		_: Balance,
+		_: Balance,
+		_: Balance,
+		_: bool,
+	) -> DispatchResult {
+		unimplemented!()
+	}
+
+	fn remove_liquidity(
+		_: &AccountId,
+		_: CurrencyId,
+		_: CurrencyId,
+		_: Balance,
+		_: Balance,
+		_: Balance,
+		_: bool,
+	) -> DispatchResult {
+		unimplemented!()
+	}
+}
 
 parameter_types! {
 	pub const GetNativeCurrencyId: CurrencyId = NATIVE_CURRENCY_ID;
@@ -145,6 +219,7 @@ ord_parameter_types! {
 	pub const MaxCodeSize: u32 = 60 * 1024;
 	pub const DeveloperDeposit: u64 = 1000;
 	pub const DeploymentFee: u64 = 200;
+	pub const EvictionNoticePeriod: u64 = 10;
 }
 
 impl module_evm::Config for Runtime {
@@ -159,6 +234,8 @@ impl module_evm::Config for Runtime {
 	type Precompiles = ();
 	type ChainId = ();
 	type GasToWeight = ();
+	type BlockGasTarget = ();
+	type AccessListGasMetering = ();
 	type ChargeTransactionPayment = ();
 	type NetworkContractOrigin = EnsureSignedBy<NetworkContractAccount, AccountId>;
 	type NetworkContractSource = NetworkContractSource;
@@ -167,6 +244,9 @@ impl module_evm::Config for Runtime {
 	type DeploymentFee = DeploymentFee;
 	type TreasuryAccount = TreasuryAccount;
 	type FreeDeploymentOrigin = EnsureSignedBy<CouncilAccount, AccountId32>;
+	type IdleScheduler = ();
+	type ContractFilter = ();
+	type EvictionNoticePeriod = EvictionNoticePeriod;
 
 	type WeightInfo = ();
 }
@@ -175,6 +255,35 @@ impl module_evm_bridge::Config for Runtime {
 	type EVM = EVM;
 }
 
+thread_local! {
+	static FROZEN: RefCell<BTreeSet<(CurrencyId, AccountId)>> = RefCell::new(BTreeSet::new());
+}
+
+pub fn mock_freeze(currency_id: CurrencyId, who: AccountId) {
+	FROZEN.with(|v| v.borrow_mut().insert((currency_id, who)));
+}
+
+pub struct MockFreezeChecker;
+impl FreezeChecker<AccountId> for MockFreezeChecker {
+	fn ensure_not_frozen(currency_id: CurrencyId, who: &AccountId) -> DispatchResult {
+		ensure!(
+			!FROZEN.with(|v| v.borrow().contains(&(currency_id, who.clone()))),
+			"frozen"
+		);
+		Ok(())
+	}
+
+	fn ensure_no_frozen_currency(who: &AccountId) -> DispatchResult {
+		ensure!(!FROZEN.with(|v| v.borrow().iter().any(|(_, v)| v == who)), "frozen");
+		Ok(())
+	}
+}
+
+parameter_types! {
+	pub const MaxMemoLength: u32 = 32;
+	pub const MaxBatchTransfers: u32 = 4;
+}
+
 impl Config for Runtime {
 	type Event = Event;
 	type MultiCurrency = Tokens;
@@ -183,6 +292,10 @@ impl Config for Runtime {
 	type WeightInfo = ();
 	type AddressMapping = MockAddressMapping;
 	type EVMBridge = EVMBridge;
+	type UpdateOrigin = EnsureSignedBy<CouncilAccount, AccountId>;
+	type MaxMemoLength = MaxMemoLength;
+	type MaxBatchTransfers = MaxBatchTransfers;
+	type FreezeChecker = MockFreezeChecker;
 }
 
 pub type NativeCurrency = Currency<Runtime, GetNativeCurrencyId>;
@@ -202,7 +315,7 @@ frame_support::construct_runtime!(
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		Tokens: tokens::{Pallet, Storage, Event<T>, Config<T>},
-		Currencies: currencies::{Pallet, Call, Event<T>},
+		Currencies: currencies::{Pallet, Call, Storage, Event<T>},
 		EVM: module_evm::{Pallet, Config<T>, Call, Storage, Event<T>},
 		EVMBridge: module_evm_bridge::{Pallet},
 	}
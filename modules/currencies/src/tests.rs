@@ -23,8 +23,9 @@
 use super::*;
 use frame_support::{assert_noop, assert_ok};
 use mock::{
-	alice, bob, deploy_contracts, erc20_address, eva, AccountId, AdaptedBasicCurrency, Currencies, Event, ExtBuilder,
-	NativeCurrency, Origin, PalletBalances, Runtime, System, Tokens, EVM, ID_1, NATIVE_CURRENCY_ID, X_TOKEN_ID,
+	alice, bob, deploy_contracts, erc20_address, eva, AccountId, AdaptedBasicCurrency, CouncilAccount, Currencies,
+	DustAccount, Event, ExtBuilder, MaxMemoLength, MockDEX, NativeCurrency, Origin, PalletBalances, Runtime, System,
+	Tokens, EVM, ID_1, NATIVE_CURRENCY_ID, X_TOKEN_ID, Y_TOKEN_ID, Z_TOKEN_ID,
 };
 use sp_core::H160;
 use sp_runtime::traits::BadOrigin;
@@ -318,6 +319,296 @@ fn call_event_should_work() {
 		});
 }
 
+#[test]
+fn transfer_with_memo_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			let memo: BoundedVec<u8, MaxMemoLength> = BoundedVec::try_from(b"order-42".to_vec()).unwrap();
+			assert_ok!(Currencies::transfer_with_memo(
+				Some(alice()).into(),
+				bob(),
+				X_TOKEN_ID,
+				50,
+				memo.clone()
+			));
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &alice()), 50);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &bob()), 150);
+			System::assert_last_event(Event::Currencies(crate::Event::TransferredWithMemo(
+				X_TOKEN_ID,
+				alice(),
+				bob(),
+				50,
+				memo.clone(),
+			)));
+
+			assert_noop!(
+				Currencies::transfer_with_memo(Some(alice()).into(), bob(), X_TOKEN_ID, 100, memo),
+				tokens::Error::<Runtime>::BalanceTooLow
+			);
+
+			let too_long_memo = vec![0u8; MaxMemoLength::get() as usize + 1];
+			assert!(BoundedVec::<u8, MaxMemoLength>::try_from(too_long_memo).is_err());
+		});
+}
+
+#[test]
+fn batch_transfer_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Currencies::batch_transfer(
+				Some(alice()).into(),
+				vec![
+					(X_TOKEN_ID, bob(), 10),
+					(NATIVE_CURRENCY_ID, eva(), 20),
+					(X_TOKEN_ID, eva(), 30)
+				],
+				true
+			));
+			System::assert_last_event(Event::Currencies(crate::Event::BatchTransferred(alice(), 3, vec![])));
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &alice()), 60);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &bob()), 110);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &eva()), 30);
+			assert_eq!(Currencies::free_balance(NATIVE_CURRENCY_ID, &alice()), 80);
+			assert_eq!(Currencies::free_balance(NATIVE_CURRENCY_ID, &eva()), 20);
+
+			// all or nothing
+			assert_noop!(
+				Currencies::batch_transfer(
+					Some(alice()).into(),
+					vec![(X_TOKEN_ID, bob(), 10), (X_TOKEN_ID, eva(), 100)],
+					true
+				),
+				tokens::Error::<Runtime>::BalanceTooLow
+			);
+
+			// best effort
+			assert_ok!(Currencies::batch_transfer(
+				Some(alice()).into(),
+				vec![
+					(X_TOKEN_ID, bob(), 10),
+					(X_TOKEN_ID, eva(), 100),
+					(X_TOKEN_ID, eva(), 20)
+				],
+				false
+			));
+			System::assert_last_event(Event::Currencies(crate::Event::BatchTransferred(alice(), 2, vec![1])));
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &alice()), 30);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &bob()), 120);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &eva()), 50);
+
+			assert_noop!(
+				Currencies::batch_transfer(Some(alice()).into(), vec![(X_TOKEN_ID, bob(), 1); 5], false),
+				Error::<Runtime>::TooManyTransfers
+			);
+		});
+}
+
+#[test]
+fn frozen_currency_cannot_be_moved_out() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			mock_freeze(X_TOKEN_ID, alice());
+
+			assert_noop!(
+				Currencies::transfer(Some(alice()).into(), bob(), X_TOKEN_ID, 10),
+				DispatchError::Other("frozen")
+			);
+			assert_noop!(
+				Currencies::withdraw(X_TOKEN_ID, &alice(), 10),
+				DispatchError::Other("frozen")
+			);
+			assert_noop!(
+				Currencies::update_balance(X_TOKEN_ID, &alice(), -10),
+				DispatchError::Other("frozen")
+			);
+			assert_noop!(
+				Currencies::reserve(X_TOKEN_ID, &alice(), 10),
+				DispatchError::Other("frozen")
+			);
+			assert!(!Currencies::can_reserve(X_TOKEN_ID, &alice(), 10));
+			assert_noop!(
+				Currencies::transfer_all(&alice(), &eva()),
+				DispatchError::Other("frozen")
+			);
+
+			// the frozen account can still receive funds and move other currencies
+			assert_ok!(Currencies::transfer(Some(bob()).into(), alice(), X_TOKEN_ID, 10));
+			assert_ok!(Currencies::deposit(X_TOKEN_ID, &alice(), 10));
+			assert_ok!(Currencies::update_balance(X_TOKEN_ID, &alice(), 10));
+			assert_ok!(Currencies::transfer(
+				Some(alice()).into(),
+				bob(),
+				NATIVE_CURRENCY_ID,
+				10
+			));
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &alice()), 130);
+		});
+}
+
+#[test]
+fn dust_should_be_converted_to_native_currency() {
+	ExtBuilder::default()
+		.balances(vec![
+			(alice(), NATIVE_CURRENCY_ID, 100),
+			(alice(), Y_TOKEN_ID, 100),
+			(DustAccount::get(), Y_TOKEN_ID, 10),
+		])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Currencies::transfer(Some(alice()).into(), bob(), Y_TOKEN_ID, 99));
+			assert_eq!(Currencies::free_balance(Y_TOKEN_ID, &alice()), 0);
+			assert_eq!(Currencies::free_balance(Y_TOKEN_ID, &bob()), 99);
+			assert_eq!(Currencies::free_balance(Y_TOKEN_ID, &DustAccount::get()), 10);
+			assert_eq!(Currencies::free_balance(NATIVE_CURRENCY_ID, &alice()), 110);
+
+			let converted_event = Event::Currencies(crate::Event::DustConverted(Y_TOKEN_ID, alice(), 1, 10));
+			assert!(System::events().iter().any(|record| record.event == converted_event));
+		});
+}
+
+#[test]
+fn dust_should_be_swept_to_treasury() {
+	ExtBuilder::default()
+		.balances(vec![
+			(alice(), Y_TOKEN_ID, 100),
+			(alice(), Z_TOKEN_ID, 100),
+			(DustAccount::get(), Y_TOKEN_ID, 10),
+			(DustAccount::get(), Z_TOKEN_ID, 10),
+		])
+		.build()
+		.execute_with(|| {
+			// no liquidity
+			assert_ok!(Currencies::transfer(Some(alice()).into(), bob(), Z_TOKEN_ID, 99));
+			assert_eq!(Currencies::free_balance(Z_TOKEN_ID, &alice()), 0);
+			assert_eq!(Currencies::free_balance(Z_TOKEN_ID, &DustAccount::get()), 11);
+			let swept_event = Event::Currencies(crate::Event::DustSwept(Z_TOKEN_ID, alice(), 1));
+			assert!(System::events().iter().any(|record| record.event == swept_event));
+
+			// conversion disabled
+			assert_ok!(Currencies::set_dust_conversion(
+				Origin::signed(CouncilAccount::get()),
+				Y_TOKEN_ID,
+				true
+			));
+			System::assert_last_event(Event::Currencies(crate::Event::DustConversionUpdated(Y_TOKEN_ID, true)));
+			assert!(Currencies::dust_conversion_disabled(Y_TOKEN_ID));
+
+			assert_ok!(Currencies::transfer(Some(alice()).into(), bob(), Y_TOKEN_ID, 99));
+			assert_eq!(Currencies::free_balance(Y_TOKEN_ID, &alice()), 0);
+			assert_eq!(Currencies::free_balance(Y_TOKEN_ID, &DustAccount::get()), 11);
+			assert_eq!(Currencies::free_balance(NATIVE_CURRENCY_ID, &alice()), 0);
+			let swept_event = Event::Currencies(crate::Event::DustSwept(Y_TOKEN_ID, alice(), 1));
+			assert!(System::events().iter().any(|record| record.event == swept_event));
+		});
+}
+
+#[test]
+fn dust_task_should_skip_the_account_without_dust() {
+	ExtBuilder::default()
+		.balances(vec![(alice(), Y_TOKEN_ID, 100), (DustAccount::get(), Y_TOKEN_ID, 10)])
+		.build()
+		.execute_with(|| {
+			let mut task = DustTask::<Runtime, MockDEX, DustAccount>::convert_dust(alice(), Y_TOKEN_ID);
+
+			// not enough weight to convert the dust
+			assert_eq!(
+				task.dispatch(<() as WeightInfo>::convert_dust() - 1),
+				TaskResult {
+					result: Ok(()),
+					used_weight: 0,
+					finished: false,
+				}
+			);
+
+			// the account was topped up after the dust was left
+			assert_eq!(
+				task.dispatch(<() as WeightInfo>::convert_dust()),
+				TaskResult {
+					result: Ok(()),
+					used_weight: <() as WeightInfo>::convert_dust(),
+					finished: true,
+				}
+			);
+			assert_eq!(Currencies::free_balance(Y_TOKEN_ID, &alice()), 100);
+			assert_eq!(Currencies::free_balance(Y_TOKEN_ID, &DustAccount::get()), 10);
+			assert_eq!(Currencies::free_balance(NATIVE_CURRENCY_ID, &alice()), 0);
+		});
+}
+
+#[test]
+fn set_dust_conversion_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			Currencies::set_dust_conversion(Origin::signed(alice()), Y_TOKEN_ID, true),
+			BadOrigin
+		);
+
+		assert_ok!(Currencies::set_dust_conversion(
+			Origin::signed(CouncilAccount::get()),
+			Y_TOKEN_ID,
+			true
+		));
+		assert!(Currencies::dust_conversion_disabled(Y_TOKEN_ID));
+
+		assert_ok!(Currencies::set_dust_conversion(
+			Origin::signed(CouncilAccount::get()),
+			Y_TOKEN_ID,
+			false
+		));
+		System::assert_last_event(Event::Currencies(crate::Event::DustConversionUpdated(
+			Y_TOKEN_ID, false,
+		)));
+		assert!(!Currencies::dust_conversion_disabled(Y_TOKEN_ID));
+		assert!(!DustConversionDisabled::<Runtime>::contains_key(Y_TOKEN_ID));
+	});
+}
+
+#[test]
+fn allowance_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(Currencies::approve(X_TOKEN_ID, &alice(), &bob(), 50));
+			assert_eq!(Currencies::allowance(X_TOKEN_ID, &alice(), &bob()), 50);
+			System::assert_last_event(Event::Currencies(crate::Event::Approved(
+				X_TOKEN_ID,
+				alice(),
+				bob(),
+				50,
+			)));
+
+			assert_noop!(
+				Currencies::transfer_from(X_TOKEN_ID, &bob(), &alice(), &eva(), 51),
+				Error::<Runtime>::AllowanceTooLow
+			);
+			assert_noop!(
+				Currencies::transfer_from(X_TOKEN_ID, &eva(), &alice(), &eva(), 1),
+				Error::<Runtime>::AllowanceTooLow
+			);
+
+			assert_ok!(Currencies::transfer_from(X_TOKEN_ID, &bob(), &alice(), &eva(), 30));
+			assert_eq!(Currencies::allowance(X_TOKEN_ID, &alice(), &bob()), 20);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &alice()), 70);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &eva()), 30);
+
+			// the owner doesn't need an allowance
+			assert_ok!(Currencies::transfer_from(X_TOKEN_ID, &alice(), &alice(), &eva(), 10));
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &eva()), 40);
+
+			assert_ok!(Currencies::approve(X_TOKEN_ID, &alice(), &bob(), 0));
+			assert!(!Allowances::<Runtime>::contains_key((X_TOKEN_ID, alice()), bob()));
+		});
+}
+
 #[test]
 fn erc20_total_issuance_should_work() {
 	ExtBuilder::default()
@@ -793,23 +1084,36 @@ fn erc20_repatriate_reserved_should_work() {
 }
 
 #[test]
-fn erc20_invalid_operation() {
+fn erc20_deposit_and_withdraw_should_work() {
 	ExtBuilder::default()
 		.balances(vec![(alice(), NATIVE_CURRENCY_ID, 100000)])
 		.build()
 		.execute_with(|| {
 			deploy_contracts();
+			let erc20 = CurrencyId::Erc20(erc20_address());
+
+			// nothing held by the bridge yet
+			assert_noop!(Currencies::deposit(erc20, &alice(), 1), Error::<Runtime>::BalanceTooLow);
+			assert_noop!(Currencies::withdraw(erc20, &bob(), 1), Error::<Runtime>::BalanceTooLow);
+
+			assert_ok!(Currencies::withdraw(erc20, &alice(), 100));
+			assert_eq!(Currencies::free_balance(erc20, &alice()), 9900);
+			assert_eq!(Currencies::total_issuance(erc20), 9900);
+
+			assert_ok!(Currencies::deposit(erc20, &alice(), 30));
+			assert_eq!(Currencies::free_balance(erc20, &alice()), 9930);
+			assert_eq!(Currencies::total_issuance(erc20), 9930);
 			assert_noop!(
-				Currencies::deposit(CurrencyId::Erc20(erc20_address()), &alice(), 1),
-				Error::<Runtime>::Erc20InvalidOperation
-			);
-			assert_noop!(
-				Currencies::withdraw(CurrencyId::Erc20(erc20_address()), &alice(), 1),
-				Error::<Runtime>::Erc20InvalidOperation
-			);
-			assert_noop!(
-				Currencies::update_balance(Origin::root(), alice(), CurrencyId::Erc20(erc20_address()), 1),
-				Error::<Runtime>::Erc20InvalidOperation,
+				Currencies::deposit(erc20, &alice(), 71),
+				Error::<Runtime>::BalanceTooLow
 			);
+
+			assert_ok!(Currencies::update_balance(Origin::root(), alice(), erc20, -70));
+			assert_eq!(Currencies::free_balance(erc20, &alice()), 9860);
+			assert_eq!(Currencies::total_issuance(erc20), 9860);
+
+			assert_ok!(Currencies::update_balance(Origin::root(), alice(), erc20, 140));
+			assert_eq!(Currencies::free_balance(erc20, &alice()), 10000);
+			assert_eq!(Currencies::total_issuance(erc20), 10000);
 		});
 }
@@ -53,6 +53,10 @@ pub trait WeightInfo {
 	fn update_balance_non_native_currency() -> Weight;
 	fn update_balance_native_currency_creating() -> Weight;
 	fn update_balance_native_currency_killing() -> Weight;
+	fn set_dust_conversion() -> Weight;
+	fn convert_dust() -> Weight;
+	fn transfer_with_memo() -> Weight;
+	fn batch_transfer(c: u32, ) -> Weight;
 }
 
 /// Weights for module_currencies using the Acala node and recommended hardware.
@@ -77,6 +81,26 @@ impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
 	fn update_balance_native_currency_killing() -> Weight {
 		(13_000_000 as Weight)
 	}
+	fn set_dust_conversion() -> Weight {
+		(14_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn convert_dust() -> Weight {
+		(98_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+	fn transfer_with_memo() -> Weight {
+		(66_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	fn batch_transfer(c: u32, ) -> Weight {
+		(4_000_000 as Weight)
+			.saturating_add((62_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads((6 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes((6 as Weight).saturating_mul(c as Weight)))
+	}
 }
 
 // For backwards compatibility and tests
@@ -100,4 +124,24 @@ impl WeightInfo for () {
 	fn update_balance_native_currency_killing() -> Weight {
 		(13_000_000 as Weight)
 	}
+	fn set_dust_conversion() -> Weight {
+		(14_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn convert_dust() -> Weight {
+		(98_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+	}
+	fn transfer_with_memo() -> Weight {
+		(66_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	fn batch_transfer(c: u32, ) -> Weight {
+		(4_000_000 as Weight)
+			.saturating_add((62_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads((6 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((6 as Weight).saturating_mul(c as Weight)))
+	}
 }
@@ -0,0 +1,114 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! RPC interface for the dex module.
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use module_dex_rpc_runtime_api::LiquidityPool;
+use sp_api::{ApiExt, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, MaybeDisplay, MaybeFromStr},
+};
+use std::sync::Arc;
+
+pub use self::gen_client::Client as DexClient;
+pub use module_dex_rpc_runtime_api::DexApi as DexRuntimeApi;
+
+#[rpc]
+pub trait DexApi<BlockHash, CurrencyId, Balance> {
+	#[rpc(name = "dex_getLiquidityPools")]
+	fn get_liquidity_pools(&self, at: Option<BlockHash>) -> Result<Vec<LiquidityPool<CurrencyId, Balance>>>;
+}
+
+/// A struct that implements the [`DexApi`].
+pub struct Dex<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Dex<C, B> {
+	/// Create new `Dex` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Dex {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+pub enum Error {
+	RuntimeError,
+	ApiNotAvailable,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::ApiNotAvailable => 2,
+		}
+	}
+}
+
+/// The runtime API is not available at the blocks before the runtime upgrade introducing it.
+fn ensure_api_available(has_api: std::result::Result<bool, sp_api::ApiError>) -> Result<()> {
+	match has_api {
+		Ok(true) => Ok(()),
+		Ok(false) => Err(RpcError {
+			code: ErrorCode::ServerError(Error::ApiNotAvailable.into()),
+			message: "The runtime API is not available at the block.".into(),
+			data: None,
+		}),
+		Err(e) => Err(RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get the runtime API version.".into(),
+			data: Some(format!("{:?}", e).into()),
+		}),
+	}
+}
+
+impl<C, Block, CurrencyId, Balance> DexApi<<Block as BlockT>::Hash, CurrencyId, Balance> for Dex<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: DexRuntimeApi<Block, CurrencyId, Balance>,
+	CurrencyId: Codec,
+	Balance: Codec + MaybeDisplay + MaybeFromStr,
+{
+	fn get_liquidity_pools(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<LiquidityPool<CurrencyId, Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		));
+		ensure_api_available(api.has_api::<dyn DexRuntimeApi<Block, CurrencyId, Balance>>(&at))?;
+
+		api.get_liquidity_pools(&at).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get liquidity pools.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+}
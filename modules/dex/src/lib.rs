@@ -26,6 +26,14 @@
 //! liquidation by auction when the liquidity is sufficient. And providing
 //! market making liquidity for DEX will also receive stable currency as
 //! additional reward for its participation in the CDP liquidation.
+//!
+//! The swap extrinsics can be paused when the emergency shutdown occurs, if the
+//! runtime registers the module with the emergency shutdown. The swaps of the
+//! other modules through `DEXManager` are not paused.
+//!
+//! The `SwapFeeShare` of the swap fees paid in `SwapFeeCurrencyId` is routed
+//! to `SwapFeeReceiver` instead of the liquidity pools, e.g. to fund the fee
+//! rebates.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::too_many_arguments)]
@@ -37,13 +45,16 @@ use frame_support::{log, pallet_prelude::*, traits::MaxEncodedLen, transactional
 use frame_system::pallet_prelude::*;
 use orml_traits::{MultiCurrency, MultiCurrencyExtended};
 use primitives::{Balance, CurrencyId, TradingPair};
-use sp_core::{H160, U256};
+use sp_core::{H160, H256, U256};
 use sp_runtime::{
 	traits::{AccountIdConversion, UniqueSaturatedInto, Zero},
-	DispatchError, DispatchResult, FixedPointNumber, RuntimeDebug, SaturatedConversion,
+	DispatchError, DispatchResult, FixedPointNumber, Permill, RuntimeDebug, SaturatedConversion,
 };
 use sp_std::{convert::TryInto, prelude::*, vec};
-use support::{CurrencyIdMapping, DEXIncentives, DEXManager, Price, Ratio};
+use support::{
+	event_topic, event_topics::kind, CurrencyIdMapping, DEXIncentives, DEXManager, DEXTradingPairManager,
+	EventTopicsRecorder, OnEmergencyShutdown, OnSwap, Price, Ratio,
+};
 
 mod mock;
 mod tests;
@@ -124,6 +135,26 @@ pub mod module {
 
 		/// The origin which may list, enable or disable trading pairs.
 		type ListingOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Handler for the swaps.
+		type OnSwap: OnSwap<Self::AccountId, CurrencyId, Balance>;
+
+		/// Recorder of the indexing topics of the swaps.
+		type EventTopics: EventTopicsRecorder;
+
+		/// The share of the swap fees routed to `SwapFeeReceiver`, the rest is
+		/// kept by the liquidity providers.
+		#[pallet::constant]
+		type SwapFeeShare: Get<Permill>;
+
+		/// The currency of the swap fees routed to `SwapFeeReceiver`, the fees
+		/// paid in the other currencies are all kept by the liquidity
+		/// providers.
+		#[pallet::constant]
+		type SwapFeeCurrencyId: Get<CurrencyId>;
+
+		/// The receiver of the routed swap fees.
+		type SwapFeeReceiver: Get<Self::AccountId>;
 	}
 
 	#[pallet::error]
@@ -164,6 +195,8 @@ pub mod module {
 		UnacceptableLiquidityWithdrawn,
 		/// The swap dosen't meet the invariant check
 		InvariantCheckFailed,
+		/// The swaps are paused by the emergency shutdown
+		SwapPaused,
 	}
 
 	#[pallet::event]
@@ -217,6 +250,13 @@ pub mod module {
 	pub type ProvisioningPool<T: Config> =
 		StorageDoubleMap<_, Twox64Concat, TradingPair, Twox64Concat, T::AccountId, (Balance, Balance), ValueQuery>;
 
+	/// Whether the swap extrinsics are paused by the emergency shutdown.
+	///
+	/// IsSwapPaused: bool
+	#[pallet::storage]
+	#[pallet::getter(fn is_swap_paused)]
+	pub type IsSwapPaused<T: Config> = StorageValue<_, bool, ValueQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub initial_listing_trading_pairs: Vec<(TradingPair, (Balance, Balance), (Balance, Balance), T::BlockNumber)>,
@@ -316,6 +356,7 @@ pub mod module {
 			#[pallet::compact] min_target_amount: Balance,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
+			ensure!(!Self::is_swap_paused(), Error::<T>::SwapPaused);
 			let _ = Self::do_swap_with_exact_supply(&who, &path, supply_amount, min_target_amount, None)?;
 			Ok(().into())
 		}
@@ -334,6 +375,7 @@ pub mod module {
 			#[pallet::compact] max_supply_amount: Balance,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
+			ensure!(!Self::is_swap_paused(), Error::<T>::SwapPaused);
 			Self::do_swap_with_exact_target(&who, &path, target_amount, max_supply_amount, None)?;
 			Ok(().into())
 		}
@@ -504,19 +546,7 @@ pub mod module {
 			currency_id_b: CurrencyId,
 		) -> DispatchResultWithPostInfo {
 			T::ListingOrigin::ensure_origin(origin)?;
-
-			let trading_pair = TradingPair::from_token_currency_ids(currency_id_a, currency_id_b)
-				.ok_or(Error::<T>::InvalidCurrencyId)?;
-			ensure!(
-				matches!(
-					Self::trading_pair_statuses(trading_pair),
-					TradingPairStatus::<_, _>::NotEnabled
-				),
-				Error::<T>::MustBeNotEnabled
-			);
-
-			TradingPairStatuses::<T>::insert(trading_pair, TradingPairStatus::Enabled);
-			Self::deposit_event(Event::EnableTradingPair(trading_pair));
+			Self::do_enable_trading_pair(currency_id_a, currency_id_b)?;
 			Ok(().into())
 		}
 
@@ -567,6 +597,22 @@ impl<T: Config> Pallet<T> {
 		T::PalletId::get().into_account()
 	}
 
+	fn do_enable_trading_pair(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> DispatchResult {
+		let trading_pair =
+			TradingPair::from_token_currency_ids(currency_id_a, currency_id_b).ok_or(Error::<T>::InvalidCurrencyId)?;
+		ensure!(
+			matches!(
+				Self::trading_pair_statuses(trading_pair),
+				TradingPairStatus::<_, _>::NotEnabled
+			),
+			Error::<T>::MustBeNotEnabled
+		);
+
+		TradingPairStatuses::<T>::insert(trading_pair, TradingPairStatus::Enabled);
+		Self::deposit_event(Event::EnableTradingPair(trading_pair));
+		Ok(())
+	}
+
 	/// Access status of specific trading_pair,
 	/// if status is Provisioning and able to be `Enabled`, update it and
 	/// return `Enabled`
@@ -1052,19 +1098,63 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
-	fn _swap_by_path(path: &[CurrencyId], amounts: &[Balance]) {
+	/// The share of the swap fee of `supply_amount` routed to `SwapFeeReceiver`.
+	fn routed_swap_fee(supply_currency_id: CurrencyId, supply_amount: Balance) -> Balance {
+		if supply_currency_id != T::SwapFeeCurrencyId::get() {
+			return Zero::zero();
+		}
+
+		let (fee_numerator, fee_denominator) = T::GetExchangeFee::get();
+		let fee: Balance = U256::from(supply_amount)
+			.saturating_mul(U256::from(fee_numerator))
+			.checked_div(U256::from(fee_denominator))
+			.and_then(|n| TryInto::<Balance>::try_into(n).ok())
+			.unwrap_or_else(Zero::zero);
+		T::SwapFeeShare::get() * fee
+	}
+
+	/// Swap by the path, returns the routed swap fees, which are not added to
+	/// the liquidity pools.
+	fn _swap_by_path(path: &[CurrencyId], amounts: &[Balance]) -> Balance {
+		let mut routed_fee: Balance = Zero::zero();
 		let mut i: usize = 0;
 		while i + 1 < path.len() {
 			let (supply_currency_id, target_currency_id) = (path[i], path[i + 1]);
-			let (supply_increment, target_decrement) = (amounts[i], amounts[i + 1]);
+			let fee = Self::routed_swap_fee(supply_currency_id, amounts[i]);
+			let (supply_increment, target_decrement) = (amounts[i].saturating_sub(fee), amounts[i + 1]);
 			Self::_swap(
 				supply_currency_id,
 				target_currency_id,
 				supply_increment,
 				target_decrement,
 			);
+			routed_fee = routed_fee.saturating_add(fee);
 			i += 1;
 		}
+		routed_fee
+	}
+
+	/// Transfer the routed swap fees to `SwapFeeReceiver`.
+	fn route_swap_fee(routed_fee: Balance) -> DispatchResult {
+		if routed_fee.is_zero() {
+			return Ok(());
+		}
+		T::Currency::transfer(
+			T::SwapFeeCurrencyId::get(),
+			&Self::account_id(),
+			&T::SwapFeeReceiver::get(),
+			routed_fee,
+		)
+	}
+
+	/// Record the indexing topics of the swap: the trading pairs of the path and the account.
+	fn record_swap_topics(who: &T::AccountId, path: &[CurrencyId]) {
+		let mut topics: Vec<H256> = path
+			.windows(2)
+			.map(|pair| event_topic(kind::DEX_SWAP, &TradingPair::new(pair[0], pair[1])))
+			.collect();
+		topics.push(event_topic(kind::ACCOUNT, who));
+		T::EventTopics::record(&topics);
 	}
 
 	/// Ensured atomic.
@@ -1085,9 +1175,12 @@ impl<T: Config> Pallet<T> {
 		let actual_target_amount = amounts[amounts.len() - 1];
 
 		T::Currency::transfer(path[0], who, &module_account_id, supply_amount)?;
-		Self::_swap_by_path(&path, &amounts);
+		let routed_fee = Self::_swap_by_path(&path, &amounts);
 		T::Currency::transfer(path[path.len() - 1], &module_account_id, who, actual_target_amount)?;
+		Self::route_swap_fee(routed_fee)?;
 
+		T::OnSwap::on_swap(who, path, supply_amount, actual_target_amount);
+		Self::record_swap_topics(who, path);
 		Self::deposit_event(Event::Swap(
 			who.clone(),
 			path.to_vec(),
@@ -1112,9 +1205,12 @@ impl<T: Config> Pallet<T> {
 		let actual_supply_amount = amounts[0];
 
 		T::Currency::transfer(path[0], who, &module_account_id, actual_supply_amount)?;
-		Self::_swap_by_path(&path, &amounts);
+		let routed_fee = Self::_swap_by_path(&path, &amounts);
 		T::Currency::transfer(path[path.len() - 1], &module_account_id, who, target_amount)?;
+		Self::route_swap_fee(routed_fee)?;
 
+		T::OnSwap::on_swap(who, path, actual_supply_amount, target_amount);
+		Self::record_swap_topics(who, path);
 		Self::deposit_event(Event::Swap(
 			who.clone(),
 			path.to_vec(),
@@ -1125,6 +1221,18 @@ impl<T: Config> Pallet<T> {
 	}
 }
 
+impl<T: Config> OnEmergencyShutdown for Pallet<T> {
+	fn on_emergency_shutdown() {
+		IsSwapPaused::<T>::put(true);
+	}
+}
+
+impl<T: Config> DEXTradingPairManager<CurrencyId> for Pallet<T> {
+	fn enable_trading_pair(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> DispatchResult {
+		Self::do_enable_trading_pair(currency_id_a, currency_id_b)
+	}
+}
+
 impl<T: Config> DEXManager<T::AccountId, CurrencyId, Balance> for Pallet<T> {
 	fn get_liquidity_pool(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> (Balance, Balance) {
 		Self::get_liquidity(currency_id_a, currency_id_b)
@@ -27,12 +27,14 @@ use orml_traits::{parameter_type_with_key, MultiReservableCurrency};
 use primitives::{Amount, TokenSymbol};
 use sp_core::H256;
 use sp_runtime::{testing::Header, traits::IdentityLookup};
+use sp_std::cell::RefCell;
 
 pub type BlockNumber = u64;
 pub type AccountId = u128;
 
 pub const ALICE: AccountId = 1;
 pub const BOB: AccountId = 2;
+pub const FEE_RECEIVER: AccountId = 4;
 pub const AUSD: CurrencyId = CurrencyId::Token(TokenSymbol::AUSD);
 pub const RENBTC: CurrencyId = CurrencyId::Token(TokenSymbol::RENBTC);
 pub const DOT: CurrencyId = CurrencyId::Token(TokenSymbol::DOT);
@@ -104,6 +106,21 @@ impl DEXIncentives<AccountId, CurrencyId, Balance> for MockDEXIncentives {
 	}
 }
 
+thread_local! {
+	static LAST_SWAP: RefCell<Option<(AccountId, Vec<CurrencyId>, Balance, Balance)>> = RefCell::new(None);
+}
+
+pub struct MockOnSwap;
+impl OnSwap<AccountId, CurrencyId, Balance> for MockOnSwap {
+	fn on_swap(who: &AccountId, path: &[CurrencyId], supply_amount: Balance, target_amount: Balance) {
+		LAST_SWAP.with(|v| *v.borrow_mut() = Some((*who, path.to_vec(), supply_amount, target_amount)));
+	}
+}
+
+pub fn last_swap() -> Option<(AccountId, Vec<CurrencyId>, Balance, Balance)> {
+	LAST_SWAP.with(|v| v.borrow().clone())
+}
+
 ord_parameter_types! {
 	pub const ListingOrigin: AccountId = 3;
 }
@@ -112,6 +129,23 @@ parameter_types! {
 	pub const GetExchangeFee: (u32, u32) = (1, 100);
 	pub const TradingPathLimit: u32 = 3;
 	pub const DEXPalletId: PalletId = PalletId(*b"aca/dexm");
+	pub const SwapFeeCurrencyId: CurrencyId = AUSD;
+	pub const SwapFeeReceiver: AccountId = FEE_RECEIVER;
+}
+
+thread_local! {
+	static SWAP_FEE_SHARE: RefCell<Permill> = RefCell::new(Permill::zero());
+}
+
+pub struct SwapFeeShare;
+impl Get<Permill> for SwapFeeShare {
+	fn get() -> Permill {
+		SWAP_FEE_SHARE.with(|v| *v.borrow())
+	}
+}
+
+pub fn set_swap_fee_share(share: Permill) {
+	SWAP_FEE_SHARE.with(|v| *v.borrow_mut() = share);
 }
 
 impl Config for Runtime {
@@ -124,6 +158,11 @@ impl Config for Runtime {
 	type WeightInfo = ();
 	type DEXIncentives = MockDEXIncentives;
 	type ListingOrigin = EnsureSignedBy<ListingOrigin, AccountId>;
+	type OnSwap = MockOnSwap;
+	type EventTopics = ();
+	type SwapFeeShare = SwapFeeShare;
+	type SwapFeeCurrencyId = SwapFeeCurrencyId;
+	type SwapFeeReceiver = SwapFeeReceiver;
 }
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
@@ -209,6 +248,8 @@ impl ExtBuilder {
 	}
 
 	pub fn build(self) -> sp_io::TestExternalities {
+		set_swap_fee_share(Permill::zero());
+
 		let mut t = frame_system::GenesisConfig::default()
 			.build_storage::<Runtime>()
 			.unwrap();
@@ -23,8 +23,8 @@
 use super::*;
 use frame_support::{assert_noop, assert_ok};
 use mock::{
-	DexModule, Event, ExtBuilder, ListingOrigin, Origin, Runtime, System, Tokens, ACA, ALICE, AUSD, AUSD_DOT_PAIR,
-	AUSD_XBTC_PAIR, BOB, DOT, RENBTC,
+	last_swap, set_swap_fee_share, DexModule, Event, ExtBuilder, ListingOrigin, Origin, Runtime, System, Tokens, ACA,
+	ALICE, AUSD, AUSD_DOT_PAIR, AUSD_XBTC_PAIR, BOB, DOT, FEE_RECEIVER, RENBTC,
 };
 use orml_traits::MultiReservableCurrency;
 use sp_runtime::traits::BadOrigin;
@@ -1001,6 +1001,10 @@ fn do_swap_with_exact_supply_work() {
 				100_000_000_000_000,
 				248_743_718_592_964,
 			)));
+			assert_eq!(
+				last_swap(),
+				Some((BOB, vec![DOT, AUSD], 100_000_000_000_000, 248_743_718_592_964))
+			);
 			assert_eq!(
 				DexModule::get_liquidity(AUSD, DOT),
 				(251_256_281_407_036, 200_000_000_000_000)
@@ -1145,6 +1149,10 @@ fn do_swap_with_exact_target_work() {
 				101_010_101_010_102,
 				250_000_000_000_000,
 			)));
+			assert_eq!(
+				last_swap(),
+				Some((BOB, vec![DOT, AUSD], 101_010_101_010_102, 250_000_000_000_000))
+			);
 			assert_eq!(
 				DexModule::get_liquidity(AUSD, DOT),
 				(250_000_000_000_000, 201_010_101_010_102)
@@ -1196,6 +1204,104 @@ fn do_swap_with_exact_target_work() {
 		});
 }
 
+#[test]
+fn swap_fee_share_is_routed_to_receiver() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			set_swap_fee_share(Permill::from_percent(50));
+
+			assert_ok!(DexModule::add_liquidity(
+				Origin::signed(ALICE),
+				AUSD,
+				DOT,
+				500_000_000_000_000,
+				100_000_000_000_000,
+				0,
+				false,
+			));
+
+			// the fee paid in the swap fee currency is routed
+			let target_amount =
+				DexModule::do_swap_with_exact_supply(&BOB, &[AUSD, DOT], 100_000_000_000_000, 0, None).unwrap();
+			assert_eq!(
+				target_amount,
+				DexModule::get_target_amount(500_000_000_000_000, 100_000_000_000_000, 100_000_000_000_000)
+			);
+			assert_eq!(Tokens::free_balance(AUSD, &FEE_RECEIVER), 500_000_000_000);
+			assert_eq!(
+				DexModule::get_liquidity(AUSD, DOT),
+				(599_500_000_000_000, 100_000_000_000_000 - target_amount)
+			);
+			assert_eq!(
+				Tokens::free_balance(AUSD, &DexModule::account_id()),
+				599_500_000_000_000
+			);
+
+			// the fee paid in the other currencies is kept by the pool
+			let supply_amount =
+				DexModule::do_swap_with_exact_target(&BOB, &[DOT, AUSD], 100_000_000_000_000, u128::MAX, None).unwrap();
+			assert_eq!(Tokens::free_balance(AUSD, &FEE_RECEIVER), 500_000_000_000);
+			assert_eq!(
+				DexModule::get_liquidity(AUSD, DOT),
+				(499_500_000_000_000, 100_000_000_000_000 - target_amount + supply_amount)
+			);
+		});
+}
+
+#[test]
+fn swap_paused_on_emergency_shutdown() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(DexModule::add_liquidity(
+				Origin::signed(ALICE),
+				AUSD,
+				DOT,
+				500_000_000_000_000,
+				100_000_000_000_000,
+				0,
+				false,
+			));
+			assert_ok!(DexModule::swap_with_exact_supply(
+				Origin::signed(BOB),
+				vec![DOT, AUSD],
+				100_000_000_000,
+				0,
+			));
+
+			<DexModule as OnEmergencyShutdown>::on_emergency_shutdown();
+			assert_eq!(DexModule::is_swap_paused(), true);
+			assert_noop!(
+				DexModule::swap_with_exact_supply(Origin::signed(BOB), vec![DOT, AUSD], 100_000_000_000, 0),
+				Error::<Runtime>::SwapPaused
+			);
+			assert_noop!(
+				DexModule::swap_with_exact_target(
+					Origin::signed(BOB),
+					vec![DOT, AUSD],
+					100_000_000_000,
+					1_000_000_000_000
+				),
+				Error::<Runtime>::SwapPaused
+			);
+
+			// the swaps of the other modules are not paused
+			assert_ok!(DexModule::do_swap_with_exact_supply(
+				&BOB,
+				&[DOT, AUSD],
+				100_000_000_000,
+				0,
+				None
+			));
+		});
+}
+
 #[test]
 fn initialize_added_liquidity_pools_genesis_work() {
 	ExtBuilder::default()
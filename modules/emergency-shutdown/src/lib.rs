@@ -28,6 +28,10 @@
 //! CDPs has debit, cancel all active auctions module, when debits and gaps are
 //! settled, the stable currency holder are allowed to refund a basket of
 //! remaining collateral assets.
+//!
+//! The other modules, e.g. Homa, DEX, incentives and bridges, register their
+//! `OnEmergencyShutdown` hooks with this module to halt when the emergency
+//! shutdown occurs.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
@@ -37,7 +41,7 @@ use frame_system::{ensure_signed, pallet_prelude::*};
 use primitives::{Balance, CurrencyId};
 use sp_runtime::{traits::Zero, FixedPointNumber};
 use sp_std::prelude::*;
-use support::{AuctionManager, CDPTreasury, EmergencyShutdown, PriceProvider, Ratio};
+use support::{AuctionManager, CDPTreasury, EmergencyShutdown, OnEmergencyShutdown, PriceProvider, Ratio};
 
 mod mock;
 mod tests;
@@ -72,6 +76,10 @@ pub mod module {
 		/// this.
 		type ShutdownOrigin: EnsureOrigin<Self::Origin>;
 
+		/// The hooks of the modules to halt when the emergency shutdown
+		/// occurs.
+		type OnShutdown: OnEmergencyShutdown;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -141,6 +149,7 @@ pub mod module {
 			}
 
 			IsShutdown::<T>::put(true);
+			T::OnShutdown::on_emergency_shutdown();
 			Self::deposit_event(Event::Shutdown(<frame_system::Pallet<T>>::block_number()));
 			Ok(().into())
 		}
@@ -31,7 +31,8 @@ use sp_runtime::{
 	traits::{AccountIdConversion, Convert, IdentityLookup, One as OneT},
 	DispatchResult,
 };
-use support::{AuctionManager, Price, PriceProvider};
+use sp_std::cell::RefCell;
+use support::{AuctionManager, Price, PriceProvider, Ratio};
 
 pub type AccountId = u128;
 pub type AuctionId = u32;
@@ -144,6 +145,7 @@ impl loans::Config for Runtime {
 	type CDPTreasury = CDPTreasuryModule;
 	type PalletId = LoansPalletId;
 	type OnUpdateLoan = ();
+	type EventTopics = ();
 }
 
 pub struct MockPriceSource;
@@ -198,6 +200,8 @@ parameter_types! {
 	pub const MaxAuctionsCount: u32 = 10_000;
 	pub const CDPTreasuryPalletId: PalletId = PalletId(*b"aca/cdpt");
 	pub TreasuryAccount: AccountId = PalletId(*b"aca/hztr").into_account();
+	pub const SurplusAllocationPeriod: BlockNumber = 10;
+	pub MaxSlippageSwapWithDEX: Ratio = Ratio::saturating_from_rational(50, 100);
 }
 
 impl cdp_treasury::Config for Runtime {
@@ -210,9 +214,30 @@ impl cdp_treasury::Config for Runtime {
 	type MaxAuctionsCount = MaxAuctionsCount;
 	type PalletId = CDPTreasuryPalletId;
 	type TreasuryAccount = TreasuryAccount;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type SavingRateAccount = TreasuryAccount;
+	type StabilityBufferAccount = TreasuryAccount;
+	type SurplusAllocationPeriod = SurplusAllocationPeriod;
+	type MaxSlippageSwapWithDEX = MaxSlippageSwapWithDEX;
 	type WeightInfo = ();
 }
 
+thread_local! {
+	static HALTED: RefCell<bool> = RefCell::new(false);
+}
+
+pub struct MockOnShutdown;
+impl MockOnShutdown {
+	pub fn is_halted() -> bool {
+		HALTED.with(|v| *v.borrow())
+	}
+}
+impl OnEmergencyShutdown for MockOnShutdown {
+	fn on_emergency_shutdown() {
+		HALTED.with(|v| *v.borrow_mut() = true);
+	}
+}
+
 ord_parameter_types! {
 	pub const CollateralCurrencyIds: Vec<CurrencyId> = vec![BTC, DOT];
 }
@@ -224,6 +249,7 @@ impl Config for Runtime {
 	type CDPTreasury = CDPTreasuryModule;
 	type AuctionManagerHandler = MockAuctionManager;
 	type ShutdownOrigin = EnsureSignedBy<One, AccountId>;
+	type OnShutdown = MockOnShutdown;
 	type WeightInfo = ();
 }
 
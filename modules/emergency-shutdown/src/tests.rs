@@ -34,9 +34,11 @@ fn emergency_shutdown_work() {
 			EmergencyShutdownModule::emergency_shutdown(Origin::signed(5)),
 			BadOrigin,
 		);
+		assert_eq!(MockOnShutdown::is_halted(), false);
 		assert_ok!(EmergencyShutdownModule::emergency_shutdown(Origin::signed(1)));
 		System::assert_last_event(Event::EmergencyShutdownModule(crate::Event::Shutdown(1)));
 		assert_eq!(EmergencyShutdownModule::is_shutdown(), true);
+		assert_eq!(MockOnShutdown::is_halted(), true);
 		assert_noop!(
 			EmergencyShutdownModule::emergency_shutdown(Origin::signed(1)),
 			Error::<Runtime>::AlreadyShutdown,
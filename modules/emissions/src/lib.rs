@@ -0,0 +1,267 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Emissions Module
+//!
+//! ## Overview
+//!
+//! The emissions module owns the total emission budget of native currency per period, and
+//! distributes it from the unreleased native currency across the incentives, the savings rate and
+//! the collator rewards by the proportions set by governance. The proportions never sum to more
+//! than 100%, and the emission of each target is capped by the remaining budget of the period, so
+//! the total emissions never exceed the budget. `TargetEmissionBudget` provides the budget of a
+//! target to its consumer, e.g. the gauges allocating the incentive rewards and the incentives
+//! checking the reward amounts of the pools against it.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{log, pallet_prelude::*, transactional};
+use frame_system::pallet_prelude::*;
+use orml_traits::MultiCurrency;
+use primitives::{Balance, CurrencyId};
+use sp_runtime::{
+	helpers_128bit::multiply_by_rational,
+	traits::{CheckedAdd, UniqueSaturatedInto, Zero},
+	Permill, RuntimeDebug,
+};
+use sp_std::{marker::PhantomData, prelude::*};
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+/// The target to distribute the emission to.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, RuntimeDebug)]
+pub enum EmissionTarget {
+	/// The rewards of the incentive pools.
+	Incentives,
+	/// The savings rate.
+	SavingRate,
+	/// The rewards of collators.
+	CollatorRewards,
+}
+
+impl EmissionTarget {
+	/// All the emission targets.
+	pub const ALL: [EmissionTarget; 3] = [
+		EmissionTarget::Incentives,
+		EmissionTarget::SavingRate,
+		EmissionTarget::CollatorRewards,
+	];
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Currency for transfer the emission.
+		type Currency: MultiCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
+
+		/// The native currency to emit.
+		#[pallet::constant]
+		type NativeCurrencyId: Get<CurrencyId>;
+
+		/// The account holds the unreleased native currency to emit.
+		#[pallet::constant]
+		type EmissionSource: Get<Self::AccountId>;
+
+		/// The account to receive the emission of the incentives.
+		#[pallet::constant]
+		type IncentivesAccount: Get<Self::AccountId>;
+
+		/// The account to receive the emission of the savings rate.
+		#[pallet::constant]
+		type SavingRateAccount: Get<Self::AccountId>;
+
+		/// The account to receive the emission of the collator rewards.
+		#[pallet::constant]
+		type CollatorRewardsAccount: Get<Self::AccountId>;
+
+		/// The period to distribute the emission budget.
+		#[pallet::constant]
+		type EmissionPeriod: Get<Self::BlockNumber>;
+
+		/// The origin which may update the emission budget and proportions.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The sum of the proportions exceeds 100%
+		InvalidProportions,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Emission budget per period updated. \[budget\]
+		EmissionBudgetUpdated(Balance),
+		/// Emission proportion of the target updated. \[target, proportion\]
+		EmissionProportionUpdated(EmissionTarget, Permill),
+		/// Emission distributed to the target. \[target, amount\]
+		Emitted(EmissionTarget, Balance),
+	}
+
+	/// The emission budget of native currency per period.
+	///
+	/// EmissionBudget: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn emission_budget)]
+	pub type EmissionBudget<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+	/// The proportions of the emission budget of targets.
+	///
+	/// EmissionProportions: map EmissionTarget => Permill
+	#[pallet::storage]
+	#[pallet::getter(fn emission_proportions)]
+	pub type EmissionProportions<T: Config> = StorageMap<_, Twox64Concat, EmissionTarget, Permill, ValueQuery>;
+
+	/// The total emissions distributed.
+	///
+	/// TotalEmitted: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn total_emitted)]
+	pub type TotalEmitted<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			if now % T::EmissionPeriod::get() == Zero::zero() {
+				Self::distribute_emission();
+				T::WeightInfo::on_initialize()
+			} else {
+				0
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set the emission budget of native currency per period.
+		#[pallet::weight(T::WeightInfo::set_emission_budget())]
+		#[transactional]
+		pub fn set_emission_budget(
+			origin: OriginFor<T>,
+			#[pallet::compact] budget: Balance,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			EmissionBudget::<T>::put(budget);
+			Self::deposit_event(Event::EmissionBudgetUpdated(budget));
+			Ok(().into())
+		}
+
+		/// Update the proportions of the emission budget of targets, the sum
+		/// of the proportions of all the targets must not exceed 100%.
+		#[pallet::weight(T::WeightInfo::set_emission_proportions(updates.len() as u32))]
+		#[transactional]
+		pub fn set_emission_proportions(
+			origin: OriginFor<T>,
+			updates: Vec<(EmissionTarget, Permill)>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			for (target, proportion) in updates {
+				EmissionProportions::<T>::insert(target, proportion);
+				Self::deposit_event(Event::EmissionProportionUpdated(target, proportion));
+			}
+
+			EmissionTarget::ALL
+				.iter()
+				.try_fold(Permill::zero(), |total, target| {
+					total.checked_add(&Self::emission_proportions(target))
+				})
+				.ok_or(Error::<T>::InvalidProportions)?;
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The account to receive the emission of the target.
+	pub fn target_account(target: EmissionTarget) -> T::AccountId {
+		match target {
+			EmissionTarget::Incentives => T::IncentivesAccount::get(),
+			EmissionTarget::SavingRate => T::SavingRateAccount::get(),
+			EmissionTarget::CollatorRewards => T::CollatorRewardsAccount::get(),
+		}
+	}
+
+	/// The emission budget of the target per `EmissionPeriod`.
+	pub fn target_budget(target: EmissionTarget) -> Balance {
+		Self::emission_proportions(target).mul_floor(Self::emission_budget())
+	}
+
+	/// Distribute the emission budget of the period to the targets by their
+	/// proportions. The emission of each target is capped by the remaining
+	/// budget.
+	pub fn distribute_emission() {
+		let native_currency_id = T::NativeCurrencyId::get();
+		let budget = Self::emission_budget();
+		let mut remaining = budget;
+
+		for target in EmissionTarget::ALL.iter() {
+			let amount = Self::emission_proportions(target).mul_floor(budget).min(remaining);
+			if amount.is_zero() {
+				continue;
+			}
+
+			let account = Self::target_account(*target);
+			match T::Currency::transfer(native_currency_id, &T::EmissionSource::get(), &account, amount) {
+				Ok(_) => {
+					remaining = remaining.saturating_sub(amount);
+					TotalEmitted::<T>::mutate(|total| *total = total.saturating_add(amount));
+					Self::deposit_event(Event::Emitted(*target, amount));
+				}
+				Err(e) => {
+					log::warn!(
+						target: "emissions",
+						"transfer: failed to transfer {:?} {:?} from {:?} to {:?}: {:?}. \
+						This is unexpected but should be safe",
+						amount, native_currency_id, T::EmissionSource::get(), account, e
+					);
+				}
+			}
+		}
+	}
+}
+
+/// The emission budget of `Target` per `Period`, prorated from its budget per
+/// `EmissionPeriod`.
+pub struct TargetEmissionBudget<T, Target, Period>(PhantomData<(T, Target, Period)>);
+impl<T: Config, Target: Get<EmissionTarget>, Period: Get<T::BlockNumber>> Get<Balance>
+	for TargetEmissionBudget<T, Target, Period>
+{
+	fn get() -> Balance {
+		let period: u128 = Period::get().unique_saturated_into();
+		let emission_period: u128 = T::EmissionPeriod::get().unique_saturated_into();
+		multiply_by_rational(Pallet::<T>::target_budget(Target::get()), period, emission_period).unwrap_or_default()
+	}
+}
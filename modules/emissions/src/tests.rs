@@ -0,0 +1,180 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for emissions module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+use orml_traits::MultiCurrency;
+use sp_runtime::traits::BadOrigin;
+
+#[test]
+fn set_emission_budget_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			EmissionsModule::set_emission_budget(Origin::signed(ALICE), 1000),
+			BadOrigin
+		);
+
+		assert_ok!(EmissionsModule::set_emission_budget(Origin::signed(1), 1000));
+		System::assert_last_event(Event::EmissionsModule(crate::Event::EmissionBudgetUpdated(1000)));
+		assert_eq!(EmissionsModule::emission_budget(), 1000);
+	});
+}
+
+#[test]
+fn set_emission_proportions_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			EmissionsModule::set_emission_proportions(Origin::signed(ALICE), vec![]),
+			BadOrigin
+		);
+
+		assert_ok!(EmissionsModule::set_emission_proportions(
+			Origin::signed(1),
+			vec![
+				(EmissionTarget::Incentives, Permill::from_percent(60)),
+				(EmissionTarget::CollatorRewards, Permill::from_percent(30)),
+			]
+		));
+		System::assert_last_event(Event::EmissionsModule(crate::Event::EmissionProportionUpdated(
+			EmissionTarget::CollatorRewards,
+			Permill::from_percent(30),
+		)));
+		assert_eq!(
+			EmissionsModule::emission_proportions(EmissionTarget::Incentives),
+			Permill::from_percent(60)
+		);
+		assert_eq!(
+			EmissionsModule::emission_proportions(EmissionTarget::CollatorRewards),
+			Permill::from_percent(30)
+		);
+
+		// the sum of the proportions of all the targets exceeds 100%
+		assert_noop!(
+			EmissionsModule::set_emission_proportions(
+				Origin::signed(1),
+				vec![(EmissionTarget::CollatorRewards, Permill::from_percent(50))]
+			),
+			Error::<Runtime>::InvalidProportions
+		);
+
+		assert_ok!(EmissionsModule::set_emission_proportions(
+			Origin::signed(1),
+			vec![
+				(EmissionTarget::Incentives, Permill::from_percent(50)),
+				(EmissionTarget::CollatorRewards, Permill::from_percent(50)),
+			]
+		));
+		assert_eq!(
+			EmissionsModule::emission_proportions(EmissionTarget::CollatorRewards),
+			Permill::from_percent(50)
+		);
+	});
+}
+
+#[test]
+fn target_emission_budget_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(EmissionsModule::set_emission_budget(Origin::signed(1), 1000));
+		assert_ok!(EmissionsModule::set_emission_proportions(
+			Origin::signed(1),
+			vec![(EmissionTarget::Incentives, Permill::from_percent(50))]
+		));
+		assert_eq!(EmissionsModule::target_budget(EmissionTarget::Incentives), 500);
+		assert_eq!(EmissionsModule::target_budget(EmissionTarget::CollatorRewards), 0);
+
+		// prorated from the budget per `EmissionPeriod`
+		assert_eq!(
+			TargetEmissionBudget::<Runtime, IncentivesTarget, HalfEmissionPeriod>::get(),
+			250
+		);
+	});
+}
+
+#[test]
+fn on_initialize_distribute_emission_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(EmissionsModule::set_emission_budget(Origin::signed(1), 1000));
+		assert_ok!(EmissionsModule::set_emission_proportions(
+			Origin::signed(1),
+			vec![
+				(EmissionTarget::Incentives, Permill::from_percent(50)),
+				(EmissionTarget::SavingRate, Permill::from_percent(15)),
+				(EmissionTarget::CollatorRewards, Permill::from_percent(30)),
+			]
+		));
+
+		// not the beginning of the period
+		EmissionsModule::on_initialize(9);
+		assert_eq!(TokensModule::free_balance(ACA, &UNRELEASED), 10_000);
+
+		EmissionsModule::on_initialize(10);
+		System::assert_has_event(Event::EmissionsModule(crate::Event::Emitted(
+			EmissionTarget::Incentives,
+			500,
+		)));
+		System::assert_has_event(Event::EmissionsModule(crate::Event::Emitted(
+			EmissionTarget::SavingRate,
+			150,
+		)));
+		System::assert_has_event(Event::EmissionsModule(crate::Event::Emitted(
+			EmissionTarget::CollatorRewards,
+			300,
+		)));
+		assert_eq!(TokensModule::free_balance(ACA, &UNRELEASED), 9050);
+		assert_eq!(TokensModule::free_balance(ACA, &INCENTIVES), 500);
+		assert_eq!(TokensModule::free_balance(ACA, &SAVING_RATE), 150);
+		assert_eq!(TokensModule::free_balance(ACA, &COLLATORS), 300);
+		assert_eq!(EmissionsModule::total_emitted(), 950);
+	});
+}
+
+#[test]
+fn emission_never_exceeds_budget_and_source() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(EmissionsModule::set_emission_budget(Origin::signed(1), 6000));
+		assert_ok!(EmissionsModule::set_emission_proportions(
+			Origin::signed(1),
+			vec![
+				(EmissionTarget::Incentives, Permill::from_rational(1u32, 3u32)),
+				(EmissionTarget::CollatorRewards, Permill::from_percent(60)),
+			]
+		));
+
+		EmissionsModule::on_initialize(10);
+		assert_eq!(TokensModule::free_balance(ACA, &INCENTIVES), 1999);
+		assert_eq!(TokensModule::free_balance(ACA, &COLLATORS), 3600);
+		assert_eq!(EmissionsModule::total_emitted(), 5599);
+		assert!(EmissionsModule::total_emitted() <= EmissionsModule::emission_budget());
+
+		// the unreleased native currency is not enough for collator rewards
+		EmissionsModule::on_initialize(20);
+		assert_eq!(TokensModule::free_balance(ACA, &INCENTIVES), 3998);
+		assert_eq!(TokensModule::free_balance(ACA, &COLLATORS), 3600);
+		assert_eq!(TokensModule::free_balance(ACA, &UNRELEASED), 2402);
+		assert_eq!(EmissionsModule::total_emitted(), 7598);
+	});
+}
@@ -0,0 +1,90 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Event Topics Module
+//!
+//! ## Overview
+//!
+//! The modules record the indexing topics of their high-volume events, e.g. the swaps of
+//! `module-dex`, the loan adjustments of `module-loans` and the auction bids of
+//! `module-auction-manager`, to this module. The topics of the block are accrued into a bloom
+//! filter, which is deposited in the block digest at the end of the block, so light clients and
+//! indexers can find the blocks with the relevant events from the headers only. See
+//! `module_support::event_topics` for the topics and the digest item.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::pallet_prelude::*;
+use frame_system::pallet_prelude::*;
+use sp_core::H256;
+use support::{event_topics_digest_item, EventTopicsBloom, EventTopicsRecorder};
+
+mod mock;
+mod tests;
+
+pub use module::*;
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {}
+
+	/// The bloom of the event topics recorded in the current block, removed at the end of the
+	/// block.
+	///
+	/// BlockTopicsBloom: EventTopicsBloom
+	#[pallet::storage]
+	#[pallet::getter(fn block_topics_bloom)]
+	pub type BlockTopicsBloom<T: Config> = StorageValue<_, EventTopicsBloom, OptionQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_initialize(_now: T::BlockNumber) -> Weight {
+			// the weight of `on_finalize`
+			T::DbWeight::get().reads_writes(1, 1)
+		}
+
+		fn on_finalize(_now: T::BlockNumber) {
+			if let Some(bloom) = BlockTopicsBloom::<T>::take() {
+				frame_system::Pallet::<T>::deposit_log(event_topics_digest_item(&bloom));
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {}
+}
+
+impl<T: Config> EventTopicsRecorder for Pallet<T> {
+	fn record(topics: &[H256]) {
+		if topics.is_empty() {
+			return;
+		}
+
+		BlockTopicsBloom::<T>::mutate(|maybe_bloom| {
+			let bloom = maybe_bloom.get_or_insert_with(Default::default);
+			topics.iter().for_each(|topic| bloom.accrue(topic));
+		});
+	}
+}
@@ -0,0 +1,87 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the event topics module.
+
+#![cfg(test)]
+
+use super::*;
+use mock::{EventTopicsModule, ExtBuilder, System};
+use support::{event_topic, event_topics::kind, find_event_topics_bloom};
+
+#[test]
+fn record_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let swap = event_topic(kind::DEX_SWAP, &(1u8, 2u8));
+		let account = event_topic(kind::ACCOUNT, &1u128);
+		let bid = event_topic(kind::AUCTION_BID, &0u32);
+		assert_eq!(EventTopicsModule::block_topics_bloom(), None);
+
+		EventTopicsModule::record(&[]);
+		assert_eq!(EventTopicsModule::block_topics_bloom(), None);
+
+		EventTopicsModule::record(&[swap, account]);
+		let bloom = EventTopicsModule::block_topics_bloom().unwrap();
+		assert!(bloom.contains(&swap));
+		assert!(bloom.contains(&account));
+		assert!(!bloom.contains(&bid));
+
+		EventTopicsModule::record(&[bid]);
+		let bloom = EventTopicsModule::block_topics_bloom().unwrap();
+		assert!(bloom.contains(&swap));
+		assert!(bloom.contains(&bid));
+	});
+}
+
+#[test]
+fn deposit_bloom_in_digest_on_finalize() {
+	ExtBuilder::default().build().execute_with(|| {
+		let swap = event_topic(kind::DEX_SWAP, &(1u8, 2u8));
+		let loan = event_topic(kind::LOAN_ADJUSTMENT, &1u8);
+
+		EventTopicsModule::record(&[swap]);
+		EventTopicsModule::on_finalize(1);
+		assert_eq!(EventTopicsModule::block_topics_bloom(), None);
+
+		let bloom = find_event_topics_bloom(&System::digest()).unwrap();
+		assert!(bloom.contains(&swap));
+		assert!(!bloom.contains(&loan));
+	});
+}
+
+#[test]
+fn no_digest_without_topics() {
+	ExtBuilder::default().build().execute_with(|| {
+		EventTopicsModule::on_finalize(1);
+		assert_eq!(find_event_topics_bloom(&System::digest()), None);
+		assert!(System::digest().logs().is_empty());
+	});
+}
+
+#[test]
+fn topics_are_rolled_back_with_storage() {
+	ExtBuilder::default().build().execute_with(|| {
+		let swap = event_topic(kind::DEX_SWAP, &(1u8, 2u8));
+
+		let _ = frame_support::storage::with_transaction(|| {
+			EventTopicsModule::record(&[swap]);
+			frame_support::storage::TransactionOutcome::Rollback(())
+		});
+		assert_eq!(EventTopicsModule::block_topics_bloom(), None);
+	});
+}
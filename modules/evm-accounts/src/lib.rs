@@ -22,6 +22,9 @@
 //!
 //! Evm Accounts module provide a two way mapping between Substrate accounts and
 //! EVM accounts so user only have deal with one account / private key.
+//!
+//! A claimed mapping can be removed by requesting an unbind and executing it
+//! once `UnbindDelay` has passed, after which the account can claim again.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
@@ -43,7 +46,7 @@ use sp_io::{
 	hashing::{blake2_256, keccak_256},
 };
 use sp_runtime::{
-	traits::{LookupError, StaticLookup},
+	traits::{LookupError, Saturating, StaticLookup},
 	MultiAddress,
 };
 use sp_std::{marker::PhantomData, vec::Vec};
@@ -74,6 +77,10 @@ pub mod module {
 		/// Merge free balance from source to dest.
 		type TransferAll: TransferAll<Self::AccountId>;
 
+		/// The delay between requesting an unbind and being able to execute it.
+		#[pallet::constant]
+		type UnbindDelay: Get<Self::BlockNumber>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -84,6 +91,14 @@ pub mod module {
 		/// Mapping between Substrate accounts and EVM accounts
 		/// claim account. \[account_id, evm_address\]
 		ClaimAccount(T::AccountId, EvmAddress),
+		/// Unbind of a claimed account requested. \[account_id, evm_address,
+		/// unbind_at\]
+		UnbindRequested(T::AccountId, EvmAddress, T::BlockNumber),
+		/// Unbind request cancelled. \[account_id, evm_address\]
+		UnbindCancelled(T::AccountId, EvmAddress),
+		/// Mapping between Substrate accounts and EVM accounts removed.
+		/// \[account_id, evm_address\]
+		UnbindAccount(T::AccountId, EvmAddress),
 	}
 
 	/// Error for evm accounts module.
@@ -99,6 +114,14 @@ pub mod module {
 		InvalidSignature,
 		/// Account ref count is not zero
 		NonZeroRefCount,
+		/// AccountId has not mapped
+		AccountIdNotMapped,
+		/// Unbind has already been requested
+		UnbindAlreadyRequested,
+		/// Unbind has not been requested
+		UnbindNotRequested,
+		/// The unbind delay has not passed yet
+		UnbindDelayNotPassed,
 	}
 
 	/// The Substrate Account for EvmAddresses
@@ -115,6 +138,13 @@ pub mod module {
 	#[pallet::getter(fn evm_addresses)]
 	pub type EvmAddresses<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, EvmAddress, OptionQuery>;
 
+	/// The block number from which a requested unbind can be executed
+	///
+	/// PendingUnbinds: map AccountId => Option<BlockNumber>
+	#[pallet::storage]
+	#[pallet::getter(fn pending_unbinds)]
+	pub type PendingUnbinds<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, T::BlockNumber, OptionQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
@@ -180,6 +210,62 @@ pub mod module {
 
 			Ok(().into())
 		}
+
+		/// Request to remove the mapping claimed by the caller. The mapping
+		/// stays active until `unbind_account` is called after `UnbindDelay`.
+		#[pallet::weight(T::WeightInfo::request_unbind())]
+		pub fn request_unbind(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let eth_address = Self::evm_addresses(&who).ok_or(Error::<T>::AccountIdNotMapped)?;
+			ensure!(
+				!PendingUnbinds::<T>::contains_key(&who),
+				Error::<T>::UnbindAlreadyRequested
+			);
+
+			let unbind_at = frame_system::Pallet::<T>::block_number().saturating_add(T::UnbindDelay::get());
+			PendingUnbinds::<T>::insert(&who, unbind_at);
+
+			Self::deposit_event(Event::UnbindRequested(who, eth_address, unbind_at));
+
+			Ok(().into())
+		}
+
+		/// Cancel a pending unbind request of the caller.
+		#[pallet::weight(T::WeightInfo::cancel_unbind())]
+		pub fn cancel_unbind(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let eth_address = Self::evm_addresses(&who).ok_or(Error::<T>::AccountIdNotMapped)?;
+			PendingUnbinds::<T>::take(&who).ok_or(Error::<T>::UnbindNotRequested)?;
+
+			Self::deposit_event(Event::UnbindCancelled(who, eth_address));
+
+			Ok(().into())
+		}
+
+		/// Remove the mapping claimed by the caller once the unbind delay has
+		/// passed. The caller can then claim a new mapping.
+		#[pallet::weight(T::WeightInfo::unbind_account())]
+		#[transactional]
+		pub fn unbind_account(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let eth_address = Self::evm_addresses(&who).ok_or(Error::<T>::AccountIdNotMapped)?;
+			let unbind_at = Self::pending_unbinds(&who).ok_or(Error::<T>::UnbindNotRequested)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() >= unbind_at,
+				Error::<T>::UnbindDelayNotPassed
+			);
+
+			PendingUnbinds::<T>::remove(&who);
+			Accounts::<T>::remove(eth_address);
+			EvmAddresses::<T>::remove(&who);
+
+			Self::deposit_event(Event::UnbindAccount(who, eth_address));
+
+			Ok(().into())
+		}
 	}
 }
 
@@ -314,6 +400,8 @@ impl<T: Config> OnKilledAccount<T::AccountId> for CallKillAccount<T> {
 			Accounts::<T>::remove(evm_addr);
 			EvmAddresses::<T>::remove(who);
 		}
+
+		PendingUnbinds::<T>::remove(who);
 	}
 }
 
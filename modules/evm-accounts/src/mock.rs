@@ -113,11 +113,16 @@ impl orml_currencies::Config for Runtime {
 }
 pub type AdaptedBasicCurrency = orml_currencies::BasicCurrencyAdapter<Runtime, Balances, Amount, BlockNumber>;
 
+parameter_types! {
+	pub const UnbindDelay: u64 = 10;
+}
+
 impl Config for Runtime {
 	type Event = Event;
 	type Currency = Balances;
 	type AddressMapping = EvmAddressMapping<Runtime>;
 	type TransferAll = Currencies;
+	type UnbindDelay = UnbindDelay;
 	type WeightInfo = ();
 }
 
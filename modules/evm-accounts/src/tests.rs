@@ -191,3 +191,83 @@ fn account_to_evm_with_create_default() {
 		);
 	});
 }
+
+#[test]
+fn unbind_account_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let alice_evm_account = EvmAccountsModule::eth_address(&alice());
+		let bob_evm_account = EvmAccountsModule::eth_address(&bob());
+
+		assert_noop!(
+			EvmAccountsModule::request_unbind(Origin::signed(ALICE)),
+			Error::<Runtime>::AccountIdNotMapped
+		);
+
+		assert_ok!(EvmAccountsModule::claim_account(
+			Origin::signed(ALICE),
+			alice_evm_account,
+			EvmAccountsModule::eth_sign(&alice(), &ALICE.encode(), &[][..])
+		));
+
+		assert_noop!(
+			EvmAccountsModule::unbind_account(Origin::signed(ALICE)),
+			Error::<Runtime>::UnbindNotRequested
+		);
+		assert_noop!(
+			EvmAccountsModule::cancel_unbind(Origin::signed(ALICE)),
+			Error::<Runtime>::UnbindNotRequested
+		);
+
+		assert_ok!(EvmAccountsModule::request_unbind(Origin::signed(ALICE)));
+		System::assert_last_event(Event::EvmAccountsModule(crate::Event::UnbindRequested(
+			ALICE,
+			alice_evm_account,
+			11,
+		)));
+		assert_eq!(EvmAccountsModule::pending_unbinds(ALICE), Some(11));
+		assert_noop!(
+			EvmAccountsModule::request_unbind(Origin::signed(ALICE)),
+			Error::<Runtime>::UnbindAlreadyRequested
+		);
+
+		assert_ok!(EvmAccountsModule::cancel_unbind(Origin::signed(ALICE)));
+		System::assert_last_event(Event::EvmAccountsModule(crate::Event::UnbindCancelled(
+			ALICE,
+			alice_evm_account,
+		)));
+		assert_eq!(EvmAccountsModule::pending_unbinds(ALICE), None);
+
+		assert_ok!(EvmAccountsModule::request_unbind(Origin::signed(ALICE)));
+
+		// the mapping stays active until the delay has passed
+		System::set_block_number(10);
+		assert_noop!(
+			EvmAccountsModule::unbind_account(Origin::signed(ALICE)),
+			Error::<Runtime>::UnbindDelayNotPassed
+		);
+		assert_eq!(EvmAddressMapping::<Runtime>::get_account_id(&alice_evm_account), ALICE);
+
+		System::set_block_number(11);
+		assert_ok!(EvmAccountsModule::unbind_account(Origin::signed(ALICE)));
+		System::assert_last_event(Event::EvmAccountsModule(crate::Event::UnbindAccount(
+			ALICE,
+			alice_evm_account,
+		)));
+		assert!(!Accounts::<Runtime>::contains_key(alice_evm_account));
+		assert!(!EvmAddresses::<Runtime>::contains_key(ALICE));
+		assert!(!PendingUnbinds::<Runtime>::contains_key(ALICE));
+		assert_eq!(EvmAddressMapping::<Runtime>::get_evm_address(&ALICE), None);
+
+		// rebind to another address
+		assert_ok!(EvmAccountsModule::claim_account(
+			Origin::signed(ALICE),
+			bob_evm_account,
+			EvmAccountsModule::eth_sign(&bob(), &ALICE.encode(), &[][..])
+		));
+		assert_eq!(EvmAddressMapping::<Runtime>::get_account_id(&bob_evm_account), ALICE);
+		assert_eq!(
+			EvmAddressMapping::<Runtime>::get_evm_address(&ALICE),
+			Some(bob_evm_account)
+		);
+	});
+}
@@ -109,6 +109,7 @@ ord_parameter_types! {
 	pub const MaxCodeSize: u32 = 60 * 1024;
 	pub const DeveloperDeposit: u64 = 1000;
 	pub const DeploymentFee: u64 = 200;
+	pub const EvictionNoticePeriod: u64 = 10;
 }
 
 impl module_evm::Config for Runtime {
@@ -123,6 +124,8 @@ impl module_evm::Config for Runtime {
 	type Precompiles = ();
 	type ChainId = ();
 	type GasToWeight = ();
+	type BlockGasTarget = ();
+	type AccessListGasMetering = ();
 	type ChargeTransactionPayment = ();
 	type NetworkContractOrigin = EnsureSignedBy<NetworkContractAccount, AccountId32>;
 	type NetworkContractSource = NetworkContractSource;
@@ -131,6 +134,9 @@ impl module_evm::Config for Runtime {
 	type DeploymentFee = DeploymentFee;
 	type TreasuryAccount = TreasuryAccount;
 	type FreeDeploymentOrigin = EnsureSignedBy<CouncilAccount, AccountId32>;
+	type IdleScheduler = ();
+	type ContractFilter = ();
+	type EvictionNoticePeriod = EvictionNoticePeriod;
 
 	type WeightInfo = ();
 }
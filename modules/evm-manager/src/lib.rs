@@ -22,12 +22,16 @@
 //!
 //! Evm manager module provides common support features for Evm, including:
 //! - A two way mapping between `u32` and `Erc20 address` so user can use Erc20 address as LP token.
+//! - Registration of Erc20 contracts by governance, so they can be used as `CurrencyId::Erc20`.
+//! - The metadata of currencies, the ones registered in the asset registry take precedence over
+//!   the hard-coded token metadata and the Erc20 contract metadata.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 
-use frame_support::{ensure, pallet_prelude::*, require_transactional, traits::Currency};
-use module_support::{CurrencyIdMapping, EVMBridge, InvokeContext};
+use frame_support::{ensure, pallet_prelude::*, require_transactional, traits::Currency, transactional};
+use frame_system::pallet_prelude::*;
+use module_support::{AssetMetadataProvider, CurrencyIdMapping, EVMBridge, InvokeContext};
 use primitives::{
 	currency::TokenInfo,
 	evm::{Erc20Info, EvmAddress},
@@ -51,8 +55,15 @@ pub mod module {
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 		type Currency: Currency<Self::AccountId>;
 		type EVMBridge: EVMBridge<Self::AccountId, BalanceOf<Self>>;
+
+		/// The origin which can register Erc20 contracts.
+		type RegisterOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The metadata of the assets registered on chain.
+		type AssetRegistry: AssetMetadataProvider<Balance>;
 	}
 
 	/// Error for evm accounts module.
@@ -60,6 +71,15 @@ pub mod module {
 	pub enum Error<T> {
 		/// CurrencyId existed
 		CurrencyIdExisted,
+		/// Erc20 contract already registered
+		Erc20AlreadyRegistered,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Registered Erc20 contract. \[contract\]
+		Erc20Registered(EvmAddress),
 	}
 
 	/// Mapping between u32 and Erc20 address.
@@ -78,10 +98,32 @@ pub mod module {
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
 
 	#[pallet::call]
-	impl<T: Config> Pallet<T> {}
+	impl<T: Config> Pallet<T> {
+		/// Register an Erc20 contract, so it can be used as `CurrencyId::Erc20` in transfers,
+		/// DEX and incentives. The name, symbol and decimals are read from the contract.
+		///
+		/// The dispatch origin of this call must be `RegisterOrigin`.
+		///
+		/// - `contract`: the Erc20 contract address.
+		#[pallet::weight(10_000)]
+		#[transactional]
+		pub fn register_erc20(origin: OriginFor<T>, contract: EvmAddress) -> DispatchResultWithPostInfo {
+			T::RegisterOrigin::ensure_origin(origin)?;
+			ensure!(Self::erc20_info(contract).is_none(), Error::<T>::Erc20AlreadyRegistered);
+			EvmCurrencyIdMapping::<T>::set_erc20_mapping(contract)?;
+
+			Self::deposit_event(Event::Erc20Registered(contract));
+			Ok(().into())
+		}
+	}
 }
 
-impl<T: Config> Pallet<T> {}
+impl<T: Config> Pallet<T> {
+	/// The info of a registered Erc20 contract.
+	pub fn erc20_info(contract: EvmAddress) -> Option<Erc20Info> {
+		CurrencyIdMap::<T>::get(Into::<u32>::into(DexShare::Erc20(contract))).filter(|v| v.address == contract)
+	}
+}
 
 pub struct EvmCurrencyIdMapping<T>(sp_std::marker::PhantomData<T>);
 
@@ -125,6 +167,10 @@ impl<T: Config> CurrencyIdMapping for EvmCurrencyIdMapping<T> {
 	// If CurrencyId is CurrencyId::DexShare and contain DexShare::Erc20,
 	// the EvmAddress must have been mapped.
 	fn name(currency_id: CurrencyId) -> Option<Vec<u8>> {
+		if let Some(metadata) = T::AssetRegistry::get_asset_metadata(currency_id) {
+			return Some(truncate_to_32_bytes(metadata.name));
+		}
+
 		let name = match currency_id {
 			CurrencyId::Token(_) => currency_id.name().map(|v| v.as_bytes().to_vec()),
 			CurrencyId::DexShare(symbol_0, symbol_1) => {
@@ -133,12 +179,14 @@ impl<T: Config> CurrencyIdMapping for EvmCurrencyIdMapping<T> {
 					DexShare::Erc20(address) => CurrencyIdMap::<T>::get(Into::<u32>::into(symbol_0))
 						.filter(|v| v.address == address)
 						.map(|v| v.name),
+					DexShare::LaunchpadToken(_) => None,
 				}?;
 				let name_1 = match symbol_1 {
 					DexShare::Token(symbol) => CurrencyId::Token(symbol).name().map(|v| v.as_bytes().to_vec()),
 					DexShare::Erc20(address) => CurrencyIdMap::<T>::get(Into::<u32>::into(symbol_1))
 						.filter(|v| v.address == address)
 						.map(|v| v.name),
+					DexShare::LaunchpadToken(_) => None,
 				}?;
 
 				let mut vec = Vec::new();
@@ -151,21 +199,23 @@ impl<T: Config> CurrencyIdMapping for EvmCurrencyIdMapping<T> {
 			CurrencyId::Erc20(address) => CurrencyIdMap::<T>::get(Into::<u32>::into(DexShare::Erc20(address)))
 				.filter(|v| v.address == address)
 				.map(|v| v.name),
-			CurrencyId::ChainSafe(_) => None,
+			CurrencyId::ChainSafe(_)
+			| CurrencyId::ForeignAsset(_)
+			| CurrencyId::LaunchpadToken(_)
+			| CurrencyId::LiquidCrowdloan(_) => None,
 		}?;
 
-		// More than 32 bytes will be truncated.
-		if name.len() > 32 {
-			Some(name[..32].to_vec())
-		} else {
-			Some(name)
-		}
+		Some(truncate_to_32_bytes(name))
 	}
 
 	// Returns the symbol associated with a given CurrencyId.
 	// If CurrencyId is CurrencyId::DexShare and contain DexShare::Erc20,
 	// the EvmAddress must have been mapped.
 	fn symbol(currency_id: CurrencyId) -> Option<Vec<u8>> {
+		if let Some(metadata) = T::AssetRegistry::get_asset_metadata(currency_id) {
+			return Some(truncate_to_32_bytes(metadata.symbol));
+		}
+
 		let symbol = match currency_id {
 			CurrencyId::Token(_) => currency_id.symbol().map(|v| v.as_bytes().to_vec()),
 			CurrencyId::DexShare(symbol_0, symbol_1) => {
@@ -174,12 +224,14 @@ impl<T: Config> CurrencyIdMapping for EvmCurrencyIdMapping<T> {
 					DexShare::Erc20(address) => CurrencyIdMap::<T>::get(Into::<u32>::into(symbol_0))
 						.filter(|v| v.address == address)
 						.map(|v| v.symbol),
+					DexShare::LaunchpadToken(_) => None,
 				}?;
 				let token_symbol_1 = match symbol_1 {
 					DexShare::Token(symbol) => CurrencyId::Token(symbol).symbol().map(|v| v.as_bytes().to_vec()),
 					DexShare::Erc20(address) => CurrencyIdMap::<T>::get(Into::<u32>::into(symbol_1))
 						.filter(|v| v.address == address)
 						.map(|v| v.symbol),
+					DexShare::LaunchpadToken(_) => None,
 				}?;
 
 				let mut vec = Vec::new();
@@ -192,21 +244,23 @@ impl<T: Config> CurrencyIdMapping for EvmCurrencyIdMapping<T> {
 			CurrencyId::Erc20(address) => CurrencyIdMap::<T>::get(Into::<u32>::into(DexShare::Erc20(address)))
 				.filter(|v| v.address == address)
 				.map(|v| v.symbol),
-			CurrencyId::ChainSafe(_) => None,
+			CurrencyId::ChainSafe(_)
+			| CurrencyId::ForeignAsset(_)
+			| CurrencyId::LaunchpadToken(_)
+			| CurrencyId::LiquidCrowdloan(_) => None,
 		}?;
 
-		// More than 32 bytes will be truncated.
-		if symbol.len() > 32 {
-			Some(symbol[..32].to_vec())
-		} else {
-			Some(symbol)
-		}
+		Some(truncate_to_32_bytes(symbol))
 	}
 
 	// Returns the decimals associated with a given CurrencyId.
 	// If CurrencyId is CurrencyId::DexShare and contain DexShare::Erc20,
 	// the EvmAddress must have been mapped.
 	fn decimals(currency_id: CurrencyId) -> Option<u8> {
+		if let Some(metadata) = T::AssetRegistry::get_asset_metadata(currency_id) {
+			return Some(metadata.decimals);
+		}
+
 		match currency_id {
 			CurrencyId::Token(_) => currency_id.decimals(),
 			CurrencyId::DexShare(symbol_0, symbol_1) => {
@@ -215,12 +269,14 @@ impl<T: Config> CurrencyIdMapping for EvmCurrencyIdMapping<T> {
 					DexShare::Erc20(address) => CurrencyIdMap::<T>::get(Into::<u32>::into(symbol_0))
 						.filter(|v| v.address == address)
 						.map(|v| v.decimals),
+					DexShare::LaunchpadToken(_) => None,
 				}?;
 				let decimals_1 = match symbol_1 {
 					DexShare::Token(symbol) => CurrencyId::Token(symbol).decimals(),
 					DexShare::Erc20(address) => CurrencyIdMap::<T>::get(Into::<u32>::into(symbol_1))
 						.filter(|v| v.address == address)
 						.map(|v| v.decimals),
+					DexShare::LaunchpadToken(_) => None,
 				}?;
 
 				Some(sp_std::cmp::max(decimals_0, decimals_1))
@@ -228,7 +284,10 @@ impl<T: Config> CurrencyIdMapping for EvmCurrencyIdMapping<T> {
 			CurrencyId::Erc20(address) => CurrencyIdMap::<T>::get(Into::<u32>::into(DexShare::Erc20(address)))
 				.filter(|v| v.address == address)
 				.map(|v| v.decimals),
-			CurrencyId::ChainSafe(_) => None,
+			CurrencyId::ChainSafe(_)
+			| CurrencyId::ForeignAsset(_)
+			| CurrencyId::LaunchpadToken(_)
+			| CurrencyId::LiquidCrowdloan(_) => None,
 		}
 	}
 
@@ -244,6 +303,7 @@ impl<T: Config> CurrencyIdMapping for EvmCurrencyIdMapping<T> {
 						let id: u32 = left.into();
 						CurrencyIdMap::<T>::get(id).filter(|v| v.address == address).map(|_| id)
 					}
+					DexShare::LaunchpadToken(_) => None,
 				}?;
 				let symbol_1 = match right {
 					DexShare::Token(_) => Some(right.into()),
@@ -251,6 +311,7 @@ impl<T: Config> CurrencyIdMapping for EvmCurrencyIdMapping<T> {
 						let id: u32 = right.into();
 						CurrencyIdMap::<T>::get(id).filter(|v| v.address == address).map(|_| id)
 					}
+					DexShare::LaunchpadToken(_) => None,
 				}?;
 
 				let mut prefix = EvmAddress::default();
@@ -258,7 +319,10 @@ impl<T: Config> CurrencyIdMapping for EvmCurrencyIdMapping<T> {
 				Some(prefix | EvmAddress::from_low_u64_be(u64::from(symbol_0) << 32 | u64::from(symbol_1)))
 			}
 
-			// Token or Erc20 or ChainSafe
+			// Only the registered foreign assets are mapped.
+			CurrencyId::ForeignAsset(_) => T::AssetRegistry::get_asset_metadata(v).and(EvmAddress::try_from(v).ok()),
+
+			// Token or Erc20 or ChainSafe or LaunchpadToken or LiquidCrowdloan
 			_ => EvmAddress::try_from(v).ok(),
 		}
 	}
@@ -274,6 +338,13 @@ impl<T: Config> CurrencyIdMapping for EvmCurrencyIdMapping<T> {
 			return address[H160_POSITION_TOKEN].try_into().map(CurrencyId::Token).ok();
 		}
 
+		// ForeignAsset
+		if address.starts_with(&H160_PREFIX_FOREIGN_ASSET) {
+			let foreign_asset_id = u16::from_be_bytes(address[H160_POSITION_FOREIGN_ASSET].try_into().ok()?);
+			let currency_id = CurrencyId::ForeignAsset(foreign_asset_id);
+			return T::AssetRegistry::get_asset_metadata(currency_id).map(|_| currency_id);
+		}
+
 		// DexShare
 		if address.starts_with(&H160_PREFIX_DEXSHARE) {
 			let left = {
@@ -311,3 +382,9 @@ impl<T: Config> CurrencyIdMapping for EvmCurrencyIdMapping<T> {
 		CurrencyIdMap::<T>::get(id).map(|v| CurrencyId::Erc20(v.address))
 	}
 }
+
+// More than 32 bytes will be truncated.
+fn truncate_to_32_bytes(mut bytes: Vec<u8>) -> Vec<u8> {
+	bytes.truncate(32);
+	bytes
+}
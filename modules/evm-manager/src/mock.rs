@@ -23,7 +23,7 @@
 use super::*;
 use frame_support::{assert_ok, construct_runtime, ord_parameter_types, parameter_types};
 use frame_system::EnsureSignedBy;
-use module_support::{mocks::MockAddressMapping, AddressMapping};
+use module_support::{mocks::MockAddressMapping, AddressMapping, AssetMetadata};
 use orml_traits::parameter_type_with_key;
 use primitives::{Amount, Balance, CurrencyId, ReserveIdentifier, TokenSymbol};
 use sp_core::{bytes::from_hex, crypto::AccountId32, H256};
@@ -136,6 +136,7 @@ ord_parameter_types! {
 	pub const MaxCodeSize: u32 = 60 * 1024;
 	pub const DeveloperDeposit: u64 = 1000;
 	pub const DeploymentFee: u64 = 200;
+	pub const EvictionNoticePeriod: u64 = 10;
 }
 
 impl module_evm::Config for Runtime {
@@ -150,6 +151,8 @@ impl module_evm::Config for Runtime {
 	type Precompiles = ();
 	type ChainId = ();
 	type GasToWeight = ();
+	type BlockGasTarget = ();
+	type AccessListGasMetering = ();
 	type ChargeTransactionPayment = ();
 	type NetworkContractOrigin = EnsureSignedBy<NetworkContractAccount, AccountId>;
 	type NetworkContractSource = NetworkContractSource;
@@ -158,6 +161,9 @@ impl module_evm::Config for Runtime {
 	type DeploymentFee = DeploymentFee;
 	type TreasuryAccount = TreasuryAccount;
 	type FreeDeploymentOrigin = EnsureSignedBy<CouncilAccount, AccountId32>;
+	type IdleScheduler = ();
+	type ContractFilter = ();
+	type EvictionNoticePeriod = EvictionNoticePeriod;
 
 	type WeightInfo = ();
 }
@@ -166,9 +172,29 @@ impl module_evm_bridge::Config for Runtime {
 	type EVM = EVM;
 }
 
+pub const FOREIGN_ASSET: CurrencyId = CurrencyId::ForeignAsset(0);
+
+pub struct MockAssetRegistry;
+impl AssetMetadataProvider<Balance> for MockAssetRegistry {
+	fn get_asset_metadata(currency_id: CurrencyId) -> Option<AssetMetadata<Balance>> {
+		match currency_id {
+			FOREIGN_ASSET => Some(AssetMetadata {
+				name: b"Foreign Token".to_vec(),
+				symbol: b"FT".to_vec(),
+				decimals: 12,
+				minimal_balance: 1,
+			}),
+			_ => None,
+		}
+	}
+}
+
 impl Config for Runtime {
+	type Event = Event;
 	type Currency = Balances;
 	type EVMBridge = EVMBridge;
+	type RegisterOrigin = EnsureSignedBy<CouncilAccount, AccountId>;
+	type AssetRegistry = MockAssetRegistry;
 }
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
@@ -198,7 +224,7 @@ construct_runtime!(
 	{
 		System: frame_system::{Pallet, Call, Storage, Config, Event<T>},
 		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
-		EvmManager: evm_manager::{Pallet, Storage},
+		EvmManager: evm_manager::{Pallet, Call, Storage, Event<T>},
 		Tokens: orml_tokens::{Pallet, Storage, Event<T>, Config<T>},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		Currencies: orml_currencies::{Pallet, Call, Event<T>},
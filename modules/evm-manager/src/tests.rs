@@ -22,10 +22,14 @@
 
 use super::*;
 use frame_support::{assert_noop, assert_ok};
-use mock::{alice, deploy_contracts, erc20_address, erc20_address_not_exists, ExtBuilder, Runtime};
+use mock::{
+	alice, deploy_contracts, erc20_address, erc20_address_not_exists, CouncilAccount, Event, EvmManager, ExtBuilder,
+	Origin, Runtime, System, FOREIGN_ASSET,
+};
 use orml_utilities::with_transaction_result;
 use primitives::TokenSymbol;
 use sp_core::H160;
+use sp_runtime::traits::BadOrigin;
 use std::str::FromStr;
 
 #[test]
@@ -70,6 +74,45 @@ fn set_erc20_mapping_works() {
 		});
 }
 
+#[test]
+fn register_erc20_works() {
+	ExtBuilder::default()
+		.balances(vec![(alice(), 1_000_000_000_000)])
+		.build()
+		.execute_with(|| {
+			deploy_contracts();
+			assert_noop!(
+				EvmManager::register_erc20(Origin::signed(alice()), erc20_address()),
+				BadOrigin
+			);
+			assert_noop!(
+				EvmManager::register_erc20(Origin::signed(CouncilAccount::get()), erc20_address_not_exists()),
+				module_evm_bridge::Error::<Runtime>::InvalidReturnValue,
+			);
+
+			assert_eq!(EvmManager::erc20_info(erc20_address()), None);
+			assert_ok!(EvmManager::register_erc20(
+				Origin::signed(CouncilAccount::get()),
+				erc20_address()
+			));
+			let event = Event::EvmManager(crate::Event::Erc20Registered(erc20_address()));
+			assert!(System::events().iter().any(|record| record.event == event));
+			assert_eq!(
+				EvmManager::erc20_info(erc20_address()).map(|info| info.decimals),
+				Some(17)
+			);
+			assert_eq!(
+				EvmCurrencyIdMapping::<Runtime>::decode_evm_address(erc20_address()),
+				Some(CurrencyId::Erc20(erc20_address()))
+			);
+
+			assert_noop!(
+				EvmManager::register_erc20(Origin::signed(CouncilAccount::get()), erc20_address()),
+				Error::<Runtime>::Erc20AlreadyRegistered
+			);
+		});
+}
+
 #[test]
 fn get_evm_address_works() {
 	ExtBuilder::default()
@@ -456,3 +499,40 @@ fn decode_evm_address_works() {
 			);
 		});
 }
+
+#[test]
+fn foreign_asset_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(
+			EvmCurrencyIdMapping::<Runtime>::name(FOREIGN_ASSET),
+			Some(b"Foreign Token".to_vec())
+		);
+		assert_eq!(
+			EvmCurrencyIdMapping::<Runtime>::symbol(FOREIGN_ASSET),
+			Some(b"FT".to_vec())
+		);
+		assert_eq!(EvmCurrencyIdMapping::<Runtime>::decimals(FOREIGN_ASSET), Some(12));
+
+		let address = H160::from_str("0x0000000000000000000000000000010000000000").unwrap();
+		assert_eq!(
+			EvmCurrencyIdMapping::<Runtime>::encode_evm_address(FOREIGN_ASSET),
+			Some(address)
+		);
+		assert_eq!(
+			EvmCurrencyIdMapping::<Runtime>::decode_evm_address(address),
+			Some(FOREIGN_ASSET)
+		);
+
+		// not registered
+		let unregistered = CurrencyId::ForeignAsset(1);
+		assert_eq!(EvmCurrencyIdMapping::<Runtime>::name(unregistered), None);
+		assert_eq!(EvmCurrencyIdMapping::<Runtime>::decimals(unregistered), None);
+		assert_eq!(EvmCurrencyIdMapping::<Runtime>::encode_evm_address(unregistered), None);
+		assert_eq!(
+			EvmCurrencyIdMapping::<Runtime>::decode_evm_address(
+				H160::from_str("0x0000000000000000000000000000010000000001").unwrap()
+			),
+			None
+		);
+	});
+}
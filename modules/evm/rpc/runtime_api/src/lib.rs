@@ -19,11 +19,17 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::all)]
 
-use ethereum_types::H160;
-use primitives::evm::{CallInfo, CreateInfo, EstimateResourcesRequest};
+use ethereum_types::{H160, H256};
+use primitives::{
+	evm::{
+		BlockFeeInfo, CallInfo, ContractMetadata, ContractStatus, CreateInfo, DryRunInfo, EstimateFeeInfo,
+		EstimateResourcesRequest, EvmTransaction, LogsBloom, Receipt, TraceInfo, TracerConfig,
+	},
+	CurrencyId, Nonce,
+};
 use sp_runtime::{
 	codec::Codec,
-	traits::{MaybeDisplay, MaybeFromStr},
+	traits::{Block as BlockT, MaybeDisplay, MaybeFromStr},
 };
 use sp_std::vec::Vec;
 
@@ -53,5 +59,67 @@ sp_api::decl_runtime_apis! {
 		) -> Result<CreateInfo, sp_runtime::DispatchError>;
 
 		fn get_estimate_resources_request(data: Vec<u8>) -> Result<EstimateResourcesRequest, sp_runtime::DispatchError>;
+
+		/// Simulate the execution, and estimate the used gas and storage and the fee including the
+		/// storage deposit, in `fee_currency_id` if set.
+		#[skip_initialize_block]
+		fn estimate_fee(
+			from: H160,
+			to: Option<H160>,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			storage_limit: u32,
+			fee_currency_id: Option<CurrencyId>,
+		) -> Result<EstimateFeeInfo<Balance>, sp_runtime::DispatchError>;
+
+		/// The fee information of the block.
+		#[skip_initialize_block]
+		fn block_fee_info() -> BlockFeeInfo;
+
+		/// The publication status of the contract at `address`, `None` if it's not a contract.
+		fn contract_status(address: H160) -> Option<ContractStatus>;
+
+		/// The verification metadata of the contract code with `code_hash`, `None` if not submitted.
+		fn contract_metadata(code_hash: H256) -> Option<ContractMetadata>;
+
+		/// The bloom of the logs emitted in the block, `None` if no log was emitted.
+		#[skip_initialize_block]
+		fn block_logs_bloom() -> Option<LogsBloom>;
+
+		/// The receipts of the EVM executions in the block.
+		#[skip_initialize_block]
+		fn block_receipts() -> Vec<Receipt>;
+	}
+
+	pub trait EVMTraceRuntimeApi {
+		/// Initialize the block with `header`, apply the `extrinsics` before `index`, and trace
+		/// the EVM execution of the extrinsic at `index`.
+		#[skip_initialize_block]
+		fn trace_extrinsic(
+			header: &<Block as BlockT>::Header,
+			extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+			index: u32,
+			config: TracerConfig,
+		) -> Result<TraceInfo, sp_runtime::DispatchError>;
+	}
+
+	pub trait DryRunRuntimeApi<Balance> where
+		Balance: Codec + MaybeDisplay + MaybeFromStr,
+	{
+		/// Apply `extrinsic` on top of the initialized block, and return the result, the events,
+		/// the storage usage changes of the contracts and the fee. The changes are discarded.
+		fn dry_run_extrinsic(extrinsic: <Block as BlockT>::Extrinsic) -> DryRunInfo<Balance>;
+	}
+
+	pub trait EVMTransactionPoolApi<AccountId, Balance> where
+		AccountId: Codec,
+		Balance: Codec + MaybeDisplay + MaybeFromStr,
+	{
+		/// The account of the EVM `address` and its next nonce.
+		fn evm_account_nonce(address: H160) -> (AccountId, Nonce);
+
+		/// The EVM execution of the signed `extrinsic`, `None` if it doesn't execute the EVM.
+		fn evm_transaction(extrinsic: <Block as BlockT>::Extrinsic) -> Option<EvmTransaction<Balance>>;
 	}
 }
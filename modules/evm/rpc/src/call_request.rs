@@ -17,6 +17,7 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use ethereum_types::{H160, U256};
+use primitives::CurrencyId;
 use serde::{Deserialize, Serialize};
 use sp_core::Bytes;
 use sp_rpc::number::NumberOrHex;
@@ -38,6 +39,10 @@ pub struct CallRequest {
 	pub value: Option<NumberOrHex>,
 	/// Data
 	pub data: Option<Bytes>,
+	/// Max fee per gas, of EIP-1559 transactions
+	pub max_fee_per_gas: Option<U256>,
+	/// Max priority fee per gas, of EIP-1559 transactions
+	pub max_priority_fee_per_gas: Option<U256>,
 }
 
 /// EstimateResources response
@@ -51,3 +56,22 @@ pub struct EstimateResourcesResponse {
 	/// Adjusted weight fee
 	pub weight_fee: U256,
 }
+
+/// EstimateFee response
+#[derive(Debug, Eq, PartialEq, Default, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EstimateFeeResponse {
+	/// Used gas
+	pub gas: U256,
+	/// Used storage
+	pub storage: i32,
+	/// Fee of the used gas, in native currency
+	pub gas_fee: U256,
+	/// Deposit reserved for the used storage, in native currency
+	pub storage_deposit: U256,
+	/// Sum of the gas fee and the storage deposit, in `fee_currency_id`. `None` if it can't be
+	/// swapped into that currency.
+	pub total_fee: Option<U256>,
+	/// Currency the total fee is charged in, native currency if `None`
+	pub fee_currency_id: Option<CurrencyId>,
+}
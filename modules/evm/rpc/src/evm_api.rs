@@ -18,14 +18,19 @@
 
 //! EVM rpc interface.
 
-use ethereum_types::H160;
+use ethereum_types::{H160, U256};
 use jsonrpc_core::Result;
 use jsonrpc_derive::rpc;
+use primitives::CurrencyId;
 use sp_core::Bytes;
 
 pub use rpc_impl_EVMApi::gen_server::EVMApi as EVMApiServer;
 
-use crate::call_request::{CallRequest, EstimateResourcesResponse};
+use crate::call_request::{CallRequest, EstimateFeeResponse, EstimateResourcesResponse};
+use crate::fee_history::FeeHistory;
+use crate::logs::{LogFilter, LogResponse};
+use crate::pending::{BlockTag, PendingTransactionResponse};
+use crate::trace::{TraceParams, TraceResponse};
 
 /// EVM rpc interface.
 #[rpc(server)]
@@ -42,4 +47,50 @@ pub trait EVMApi<BlockHash> {
 		unsigned_extrinsic: Bytes,
 		at: Option<BlockHash>,
 	) -> Result<EstimateResourcesResponse>;
+
+	/// Estimate the used gas and storage of the call, and the fee including the storage deposit,
+	/// in `fee_currency_id` if set.
+	#[rpc(name = "evm_estimateFee")]
+	fn estimate_fee(
+		&self,
+		request: CallRequest,
+		fee_currency_id: Option<CurrencyId>,
+		at: Option<BlockHash>,
+	) -> Result<EstimateFeeResponse>;
+
+	/// Returns the base fee per gas and the gas used ratio of `block_count` blocks up to
+	/// `newest_block`, in the same shape as `eth_feeHistory`.
+	#[rpc(name = "evm_feeHistory")]
+	fn fee_history(
+		&self,
+		block_count: U256,
+		newest_block: Option<BlockHash>,
+		reward_percentiles: Option<Vec<f64>>,
+	) -> Result<FeeHistory>;
+
+	/// Replay the extrinsic in the block, returning the trace of its EVM execution.
+	#[rpc(name = "evm_traceTransaction")]
+	fn trace_transaction(
+		&self,
+		block_hash: BlockHash,
+		extrinsic_hash: BlockHash,
+		params: Option<TraceParams>,
+	) -> Result<TraceResponse>;
+
+	/// Returns the logs matching `filter` from the indexed receipts, in the same shape as
+	/// `eth_getLogs`.
+	#[rpc(name = "evm_getLogs")]
+	fn get_logs(&self, filter: LogFilter<BlockHash>) -> Result<Vec<LogResponse<BlockHash>>>;
+
+	/// Returns the next nonce of the EVM address at the block, the best block if not set. With
+	/// the `pending` tag, the ready transactions of the address in the transaction pool are
+	/// counted, up to the first nonce gap. Same as `eth_getTransactionCount`.
+	#[rpc(name = "evm_getTransactionCount")]
+	fn get_transaction_count(&self, address: H160, block: Option<BlockTag<BlockHash>>) -> Result<U256>;
+
+	/// Returns the EVM executions of the ready transactions in the transaction pool, in the same
+	/// shape as `eth_pendingTransactions`. The transactions with future nonces are queued in the
+	/// pool until the nonce gap is filled, and are not returned until then.
+	#[rpc(name = "evm_pendingTransactions")]
+	fn pending_transactions(&self) -> Result<Vec<PendingTransactionResponse<BlockHash>>>;
 }
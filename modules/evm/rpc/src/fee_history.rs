@@ -0,0 +1,35 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use ethereum_types::U256;
+use serde::Serialize;
+
+/// FeeHistory response, with the shape of `eth_feeHistory` response
+#[derive(Debug, Default, PartialEq, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeHistory {
+	/// Number of the oldest returned block
+	pub oldest_block: U256,
+	/// Base fee per gas of the returned blocks, and of the block after the newest
+	pub base_fee_per_gas: Vec<U256>,
+	/// Ratio of gas used to gas limit of the returned blocks
+	pub gas_used_ratio: Vec<f64>,
+	/// Priority fee per gas at the requested percentiles of the returned blocks
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub reward: Option<Vec<Vec<U256>>>,
+}
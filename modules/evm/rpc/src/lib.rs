@@ -22,7 +22,9 @@ use ethereum_types::{H160, U256};
 use frame_support::log;
 use jsonrpc_core::{Error, ErrorCode, Result, Value};
 use pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi;
+use primitives::CurrencyId;
 use rustc_hex::ToHex;
+use sc_client_api::BlockBackend;
 use sc_rpc_api::DenyUnsafe;
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
@@ -31,20 +33,31 @@ use sp_rpc::number::NumberOrHex;
 use sp_runtime::{
 	codec::Codec,
 	generic::BlockId,
-	traits::{self, Block as BlockT, MaybeDisplay, MaybeFromStr},
+	traits::{
+		self, Block as BlockT, Hash as HashT, Header as HeaderT, MaybeDisplay, MaybeFromStr, UniqueSaturatedInto, Zero,
+	},
 	SaturatedConversion,
 };
 use std::convert::{TryFrom, TryInto};
 use std::{marker::PhantomData, sync::Arc};
 
-use call_request::{CallRequest, EstimateResourcesResponse};
+use call_request::{CallRequest, EstimateFeeResponse, EstimateResourcesResponse};
+use fee_history::FeeHistory;
+use logs::{LogFilter, LogResponse};
 pub use module_evm::{ExitError, ExitReason};
-pub use module_evm_rpc_runtime_api::EVMRuntimeRPCApi;
+pub use module_evm_rpc_runtime_api::{EVMRuntimeRPCApi, EVMTraceRuntimeApi, EVMTransactionPoolApi};
+use pending::{pending_nonce, BlockTag, PendingTransactionResponse, Tag};
+use sp_transaction_pool::{InPoolTransaction, TransactionPool};
+use trace::{TraceParams, TraceResponse};
 
 pub use crate::evm_api::{EVMApi as EVMApiT, EVMApiServer};
 
 mod call_request;
 mod evm_api;
+mod fee_history;
+mod logs;
+mod pending;
+mod trace;
 
 fn internal_err<T: ToString>(message: T) -> Error {
 	Error {
@@ -106,16 +119,48 @@ fn decode_revert_message(data: &[u8]) -> Option<String> {
 	None
 }
 
-pub struct EVMApi<B, C, Balance> {
+/// The max number of blocks `fee_history` returns.
+const MAX_FEE_HISTORY_BLOCK_COUNT: u64 = 1024;
+
+/// The max number of blocks `get_logs` searches.
+const MAX_GET_LOGS_BLOCK_RANGE: u64 = 1024;
+
+fn ensure_fee_per_gas(
+	base_fee_per_gas: U256,
+	max_fee_per_gas: Option<U256>,
+	max_priority_fee_per_gas: Option<U256>,
+) -> Result<()> {
+	let invalid_params = |message: &str| Error {
+		code: ErrorCode::InvalidParams,
+		message: message.into(),
+		data: None,
+	};
+
+	if let (Some(max_fee), Some(max_priority_fee)) = (max_fee_per_gas, max_priority_fee_per_gas) {
+		if max_priority_fee > max_fee {
+			return Err(invalid_params("max priority fee per gas higher than max fee per gas"));
+		}
+	}
+	if let Some(max_fee) = max_fee_per_gas {
+		if max_fee < base_fee_per_gas {
+			return Err(invalid_params("max fee per gas less than block base fee"));
+		}
+	}
+	Ok(())
+}
+
+pub struct EVMApi<B, C, P, AccountId, Balance> {
 	client: Arc<C>,
+	pool: Arc<P>,
 	deny_unsafe: DenyUnsafe,
-	_marker: PhantomData<(B, Balance)>,
+	_marker: PhantomData<(B, AccountId, Balance)>,
 }
 
-impl<B, C, Balance> EVMApi<B, C, Balance> {
-	pub fn new(client: Arc<C>, deny_unsafe: DenyUnsafe) -> Self {
+impl<B, C, P, AccountId, Balance> EVMApi<B, C, P, AccountId, Balance> {
+	pub fn new(client: Arc<C>, pool: Arc<P>, deny_unsafe: DenyUnsafe) -> Self {
 		Self {
 			client,
+			pool,
 			deny_unsafe,
 			_marker: Default::default(),
 		}
@@ -126,12 +171,16 @@ fn to_u128(val: NumberOrHex) -> std::result::Result<u128, ()> {
 	val.into_u256().try_into().map_err(|_| ())
 }
 
-impl<B, C, Balance> EVMApiT<<B as BlockT>::Hash> for EVMApi<B, C, Balance>
+impl<B, C, P, AccountId, Balance> EVMApiT<<B as BlockT>::Hash> for EVMApi<B, C, P, AccountId, Balance>
 where
 	B: BlockT,
-	C: ProvideRuntimeApi<B> + HeaderBackend<B> + Send + Sync + 'static,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B> + BlockBackend<B> + Send + Sync + 'static,
 	C::Api: EVMRuntimeRPCApi<B, Balance>,
+	C::Api: EVMTraceRuntimeApi<B>,
+	C::Api: EVMTransactionPoolApi<B, AccountId, Balance>,
 	C::Api: TransactionPaymentApi<B, Balance>,
+	P: TransactionPool<Block = B> + 'static,
+	AccountId: Codec + Send + Sync + 'static,
 	Balance: Codec + MaybeDisplay + MaybeFromStr + Default + Send + Sync + 'static + TryFrom<u128> + Into<U256>,
 {
 	fn call(&self, request: CallRequest, at: Option<<B as BlockT>::Hash>) -> Result<Bytes> {
@@ -146,6 +195,8 @@ where
 			storage_limit,
 			value,
 			data,
+			max_fee_per_gas,
+			max_priority_fee_per_gas,
 		} = request;
 
 		let gas_limit = gas_limit.unwrap_or_else(u64::max_value); // TODO: set a limit
@@ -154,6 +205,11 @@ where
 
 		let api = self.client.runtime_api();
 
+		let fee_info = api
+			.block_fee_info(&BlockId::Hash(hash))
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?;
+		ensure_fee_per_gas(fee_info.base_fee_per_gas, max_fee_per_gas, max_priority_fee_per_gas)?;
+
 		let balance_value = if let Some(value) = value {
 			to_u128(value).and_then(|v| TryInto::<Balance>::try_into(v).map_err(|_| ()))
 		} else {
@@ -230,6 +286,8 @@ where
 			storage_limit: request.storage_limit,
 			value: request.value.map(|v| NumberOrHex::Hex(U256::from(v))),
 			data: request.data.map(Bytes),
+			max_fee_per_gas: None,
+			max_priority_fee_per_gas: None,
 		};
 
 		let calculate_gas_used = |request| -> Result<(U256, i32)> {
@@ -242,6 +300,7 @@ where
 				storage_limit,
 				value,
 				data,
+				..
 			} = request;
 
 			let gas_limit = gas_limit.unwrap_or_else(u64::max_value); // TODO: set a limit
@@ -424,6 +483,346 @@ where
 			})
 		}
 	}
+
+	fn estimate_fee(
+		&self,
+		request: CallRequest,
+		fee_currency_id: Option<CurrencyId>,
+		at: Option<<B as BlockT>::Hash>,
+	) -> Result<EstimateFeeResponse> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let CallRequest {
+			from,
+			to,
+			gas_limit,
+			storage_limit,
+			value,
+			data,
+			..
+		} = request;
+
+		let gas_limit = gas_limit.unwrap_or_else(u64::max_value); // TODO: set a limit
+		let storage_limit = storage_limit.unwrap_or_else(u32::max_value); // TODO: set a limit
+		let data = data.map(|d| d.0).unwrap_or_default();
+
+		let balance_value = if let Some(value) = value {
+			to_u128(value).and_then(|v| TryInto::<Balance>::try_into(v).map_err(|_| ()))
+		} else {
+			Ok(Default::default())
+		};
+
+		let balance_value = balance_value.map_err(|_| Error {
+			code: ErrorCode::InvalidParams,
+			message: format!("Invalid parameter value: {:?}", value),
+			data: None,
+		})?;
+
+		let info = self
+			.client
+			.runtime_api()
+			.estimate_fee(
+				&BlockId::Hash(hash),
+				from.unwrap_or_default(),
+				to,
+				data,
+				balance_value,
+				gas_limit,
+				storage_limit,
+				fee_currency_id,
+			)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+			.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))?;
+
+		error_on_execution_failure(&info.exit_reason, &info.output)?;
+
+		Ok(EstimateFeeResponse {
+			gas: info.used_gas,
+			storage: info.used_storage,
+			gas_fee: info.gas_fee.into(),
+			storage_deposit: info.storage_deposit.into(),
+			total_fee: info.total_fee.map(Into::into),
+			fee_currency_id: info.fee_currency_id,
+		})
+	}
+
+	fn fee_history(
+		&self,
+		block_count: U256,
+		newest_block: Option<<B as BlockT>::Hash>,
+		reward_percentiles: Option<Vec<f64>>,
+	) -> Result<FeeHistory> {
+		let block_count = block_count.min(MAX_FEE_HISTORY_BLOCK_COUNT.into()).as_u64();
+		if block_count.is_zero() {
+			return Ok(Default::default());
+		}
+
+		if let Some(percentiles) = &reward_percentiles {
+			let mut last = 0.0;
+			for percentile in percentiles {
+				if *percentile < last || *percentile > 100.0 {
+					return Err(Error {
+						code: ErrorCode::InvalidParams,
+						message: format!("invalid reward percentile: {}", percentile),
+						data: None,
+					});
+				}
+				last = *percentile;
+			}
+		}
+
+		let mut hash = newest_block.unwrap_or_else(|| self.client.info().best_hash);
+		let mut oldest_block = U256::zero();
+		let mut next_base_fee_per_gas = None;
+		let mut base_fee_per_gas = Vec::new();
+		let mut gas_used_ratio = Vec::new();
+
+		for _ in 0..block_count {
+			let header = self
+				.client
+				.header(BlockId::Hash(hash))
+				.map_err(|err| internal_err(format!("blockchain error: {:?}", err)))?
+				.ok_or_else(|| internal_err(format!("block not found: {:?}", hash)))?;
+			let info = self
+				.client
+				.runtime_api()
+				.block_fee_info(&BlockId::Hash(hash))
+				.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?;
+
+			if next_base_fee_per_gas.is_none() {
+				next_base_fee_per_gas = Some(info.next_base_fee_per_gas);
+			}
+			base_fee_per_gas.push(info.base_fee_per_gas);
+			gas_used_ratio.push(if info.gas_limit.is_zero() {
+				0.0
+			} else {
+				info.gas_used as f64 / info.gas_limit as f64
+			});
+
+			let number: u64 = (*header.number()).unique_saturated_into();
+			oldest_block = number.into();
+			if number.is_zero() {
+				break;
+			}
+			hash = *header.parent_hash();
+		}
+
+		base_fee_per_gas.reverse();
+		base_fee_per_gas.extend(next_base_fee_per_gas);
+		gas_used_ratio.reverse();
+
+		// EVM transactions don't pay priority fee per gas, the rewards are always zero.
+		let reward =
+			reward_percentiles.map(|percentiles| vec![vec![U256::zero(); percentiles.len()]; gas_used_ratio.len()]);
+
+		Ok(FeeHistory {
+			oldest_block,
+			base_fee_per_gas,
+			gas_used_ratio,
+			reward,
+		})
+	}
+
+	fn trace_transaction(
+		&self,
+		block_hash: <B as BlockT>::Hash,
+		extrinsic_hash: <B as BlockT>::Hash,
+		params: Option<TraceParams>,
+	) -> Result<TraceResponse> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let config = params.unwrap_or_default().tracer_config()?;
+
+		let id = BlockId::Hash(block_hash);
+		let header = self
+			.client
+			.header(id)
+			.map_err(|err| internal_err(format!("blockchain error: {:?}", err)))?
+			.ok_or_else(|| internal_err(format!("block not found: {:?}", block_hash)))?;
+		let extrinsics = self
+			.client
+			.block_body(&id)
+			.map_err(|err| internal_err(format!("blockchain error: {:?}", err)))?
+			.ok_or_else(|| internal_err(format!("block body not found: {:?}", block_hash)))?;
+
+		let index = extrinsics
+			.iter()
+			.position(|xt| <B::Header as HeaderT>::Hashing::hash_of(xt) == extrinsic_hash)
+			.ok_or_else(|| Error {
+				code: ErrorCode::InvalidParams,
+				message: format!("extrinsic {:?} not found in block {:?}", extrinsic_hash, block_hash),
+				data: None,
+			})?;
+
+		let info = self
+			.client
+			.runtime_api()
+			.trace_extrinsic(
+				&BlockId::Hash(*header.parent_hash()),
+				&header,
+				extrinsics,
+				index as u32,
+				config,
+			)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+			.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))?;
+
+		Ok(TraceResponse::new(info, config))
+	}
+
+	fn get_logs(&self, filter: LogFilter<<B as BlockT>::Hash>) -> Result<Vec<LogResponse<<B as BlockT>::Hash>>> {
+		let hashes = if let Some(hash) = filter.block_hash {
+			vec![hash]
+		} else {
+			let best_number: u64 = self.client.info().best_number.unique_saturated_into();
+			let to_block = filter
+				.to_block
+				.map_or(best_number, |number| number.min(best_number.into()).as_u64());
+			let from_block = filter
+				.from_block
+				.map_or(to_block, |number| number.min(to_block.into()).as_u64());
+			if to_block - from_block >= MAX_GET_LOGS_BLOCK_RANGE {
+				return Err(Error {
+					code: ErrorCode::InvalidParams,
+					message: format!("block range is larger than {}", MAX_GET_LOGS_BLOCK_RANGE),
+					data: None,
+				});
+			}
+
+			(from_block..=to_block)
+				.map(|number| {
+					self.client
+						.hash(number.unique_saturated_into())
+						.map_err(|err| internal_err(format!("blockchain error: {:?}", err)))?
+						.ok_or_else(|| internal_err(format!("block not found: {:?}", number)))
+				})
+				.collect::<Result<Vec<_>>>()?
+		};
+
+		let mut logs = Vec::new();
+		for hash in hashes {
+			let id = BlockId::Hash(hash);
+			let api = self.client.runtime_api();
+
+			// only read the receipts of the blocks that may contain matching logs
+			let bloom = api
+				.block_logs_bloom(&id)
+				.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?;
+			if !bloom.map_or(false, |bloom| filter.matches_bloom(&bloom.into())) {
+				continue;
+			}
+
+			let receipts = api
+				.block_receipts(&id)
+				.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?;
+			let header = self
+				.client
+				.header(id)
+				.map_err(|err| internal_err(format!("blockchain error: {:?}", err)))?
+				.ok_or_else(|| internal_err(format!("block not found: {:?}", hash)))?;
+			let extrinsics = self
+				.client
+				.block_body(&id)
+				.map_err(|err| internal_err(format!("blockchain error: {:?}", err)))?
+				.ok_or_else(|| internal_err(format!("block body not found: {:?}", hash)))?;
+			let block_number: u64 = (*header.number()).unique_saturated_into();
+
+			let mut log_index = 0u64;
+			for receipt in receipts {
+				let transaction_hash = receipt.extrinsic_index.and_then(|index| {
+					extrinsics
+						.get(index as usize)
+						.map(<B::Header as HeaderT>::Hashing::hash_of)
+				});
+
+				for (transaction_log_index, log) in receipt.logs.into_iter().enumerate() {
+					if filter.matches_log(&log) {
+						logs.push(LogResponse {
+							address: log.address,
+							topics: log.topics,
+							data: log.data.into(),
+							block_hash: hash,
+							block_number: block_number.into(),
+							transaction_hash,
+							transaction_index: receipt.extrinsic_index.map(Into::into),
+							log_index: log_index.into(),
+							transaction_log_index: transaction_log_index.into(),
+							removed: false,
+						});
+					}
+					log_index += 1;
+				}
+			}
+		}
+
+		Ok(logs)
+	}
+
+	fn get_transaction_count(&self, address: H160, block: Option<BlockTag<<B as BlockT>::Hash>>) -> Result<U256> {
+		let best_hash = self.client.info().best_hash;
+		let (hash, pending) = match block {
+			None | Some(BlockTag::Tag(Tag::Latest)) => (best_hash, false),
+			Some(BlockTag::Tag(Tag::Pending)) => (best_hash, true),
+			Some(BlockTag::Hash(hash)) => (hash, false),
+		};
+
+		let (account, nonce) = self
+			.client
+			.runtime_api()
+			.evm_account_nonce(&BlockId::Hash(hash), address)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?;
+
+		let nonce = if pending {
+			pending_nonce(&account, nonce, self.pool.ready().flat_map(|tx| tx.provides().to_vec()))
+		} else {
+			nonce
+		};
+
+		Ok(nonce.into())
+	}
+
+	fn pending_transactions(&self) -> Result<Vec<PendingTransactionResponse<<B as BlockT>::Hash>>> {
+		let id = BlockId::Hash(self.client.info().best_hash);
+		let api = self.client.runtime_api();
+
+		let mut transactions = Vec::new();
+		for tx in self.pool.ready() {
+			let extrinsic = tx.data();
+			let transaction = api
+				.evm_transaction(&id, extrinsic.clone())
+				.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?;
+
+			if let Some(transaction) = transaction {
+				transactions.push(PendingTransactionResponse {
+					hash: <B::Header as HeaderT>::Hashing::hash_of(extrinsic),
+					nonce: transaction.nonce.into(),
+					block_hash: None,
+					block_number: None,
+					transaction_index: None,
+					from: transaction.from,
+					to: transaction.to,
+					value: transaction.value.into(),
+					gas: transaction.gas_limit.into(),
+					storage_limit: transaction.storage_limit.into(),
+					input: transaction.input.into(),
+				});
+			}
+		}
+
+		Ok(transactions)
+	}
+}
+
+#[test]
+fn ensure_fee_per_gas_should_work() {
+	assert!(ensure_fee_per_gas(10.into(), None, None).is_ok());
+	assert!(ensure_fee_per_gas(10.into(), Some(10.into()), None).is_ok());
+	assert!(ensure_fee_per_gas(10.into(), Some(20.into()), Some(20.into())).is_ok());
+	assert!(ensure_fee_per_gas(10.into(), None, Some(20.into())).is_ok());
+	assert!(ensure_fee_per_gas(10.into(), Some(9.into()), None).is_err());
+	assert!(ensure_fee_per_gas(10.into(), Some(20.into()), Some(21.into())).is_err());
 }
 
 #[test]
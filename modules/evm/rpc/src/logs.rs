@@ -0,0 +1,170 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use ethereum_types::{Bloom, BloomInput, H160, H256, U256};
+use primitives::evm::Log;
+use serde::{Deserialize, Serialize};
+use sp_core::Bytes;
+
+/// A single value or a list of values, any of which matches.
+#[derive(Debug, PartialEq, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum VariadicValue<T> {
+	Single(T),
+	Multiple(Vec<T>),
+}
+
+impl<T: PartialEq> VariadicValue<T> {
+	fn to_vec(&self) -> Vec<&T> {
+		match self {
+			VariadicValue::Single(value) => vec![value],
+			VariadicValue::Multiple(values) => values.iter().collect(),
+		}
+	}
+
+	fn matches(&self, value: &T) -> bool {
+		self.to_vec().into_iter().any(|v| v == value)
+	}
+}
+
+/// Log filter, with the shape of `eth_getLogs` filter.
+#[derive(Debug, PartialEq, Deserialize, Clone)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct LogFilter<BlockHash> {
+	/// First block of the range, the newest block if not set
+	pub from_block: Option<U256>,
+	/// Last block of the range, the newest block if not set
+	pub to_block: Option<U256>,
+	/// Only include the logs of this block, other than the block range
+	pub block_hash: Option<BlockHash>,
+	/// Contract addresses the logs are emitted from
+	pub address: Option<VariadicValue<H160>>,
+	/// Topics of the logs by position, `None` matches any topic
+	pub topics: Option<Vec<Option<VariadicValue<H256>>>>,
+}
+
+impl<BlockHash> LogFilter<BlockHash> {
+	/// Whether the logs of a block with `bloom` could match the filter.
+	pub fn matches_bloom(&self, bloom: &Bloom) -> bool {
+		let address_matches = self.address.as_ref().map_or(true, |address| {
+			address
+				.to_vec()
+				.into_iter()
+				.any(|address| bloom.contains_input(BloomInput::Raw(address.as_bytes())))
+		});
+
+		address_matches
+			&& self.topics.iter().flatten().flatten().all(|topic| {
+				topic
+					.to_vec()
+					.into_iter()
+					.any(|topic| bloom.contains_input(BloomInput::Raw(topic.as_bytes())))
+			})
+	}
+
+	/// Whether the `log` matches the filter.
+	pub fn matches_log(&self, log: &Log) -> bool {
+		let address_matches = self
+			.address
+			.as_ref()
+			.map_or(true, |address| address.matches(&log.address));
+
+		address_matches
+			&& self.topics.iter().flatten().enumerate().all(|(i, topic)| match topic {
+				None => true,
+				Some(topic) => log.topics.get(i).map_or(false, |log_topic| topic.matches(log_topic)),
+			})
+	}
+}
+
+/// Log response, with the shape of `eth_getLogs` response.
+#[derive(Debug, PartialEq, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LogResponse<BlockHash> {
+	/// Contract address the log is emitted from
+	pub address: H160,
+	/// Topics of the log
+	pub topics: Vec<H256>,
+	/// Data of the log
+	pub data: Bytes,
+	/// Hash of the block
+	pub block_hash: BlockHash,
+	/// Number of the block
+	pub block_number: U256,
+	/// Hash of the extrinsic, `None` if not emitted by an extrinsic
+	pub transaction_hash: Option<BlockHash>,
+	/// Index of the extrinsic in the block, `None` if not emitted by an extrinsic
+	pub transaction_index: Option<U256>,
+	/// Index of the log in the block
+	pub log_index: U256,
+	/// Index of the log in the extrinsic
+	pub transaction_log_index: U256,
+	/// Always false, logs of finalized and best blocks are both returned
+	pub removed: bool,
+}
+
+#[test]
+fn log_filter_should_work() {
+	use module_evm::accrue_bloom;
+	use primitives::evm::LOGS_BLOOM_SIZE;
+
+	let log = Log {
+		address: H160::repeat_byte(1),
+		topics: vec![H256::repeat_byte(2), H256::repeat_byte(3)],
+		data: vec![],
+	};
+	let mut bloom = [0u8; LOGS_BLOOM_SIZE];
+	accrue_bloom(&mut bloom, log.address.as_bytes());
+	for topic in &log.topics {
+		accrue_bloom(&mut bloom, topic.as_bytes());
+	}
+	let bloom = Bloom::from(bloom);
+
+	let filter: LogFilter<H256> = serde_json::from_str("{}").unwrap();
+	assert!(filter.matches_bloom(&bloom));
+	assert!(filter.matches_log(&log));
+
+	let filter: LogFilter<H256> = serde_json::from_str(
+		r#"{
+			"address": ["0x0101010101010101010101010101010101010101", "0x0404040404040404040404040404040404040404"],
+			"topics": [null, "0x0303030303030303030303030303030303030303030303030303030303030303"]
+		}"#,
+	)
+	.unwrap();
+	assert!(filter.matches_bloom(&bloom));
+	assert!(filter.matches_log(&log));
+
+	let filter: LogFilter<H256> = serde_json::from_str(
+		r#"{
+			"address": "0x0404040404040404040404040404040404040404"
+		}"#,
+	)
+	.unwrap();
+	assert!(!filter.matches_bloom(&bloom));
+	assert!(!filter.matches_log(&log));
+
+	// the topics are matched by position
+	let filter: LogFilter<H256> = serde_json::from_str(
+		r#"{
+			"topics": ["0x0303030303030303030303030303030303030303030303030303030303030303"]
+		}"#,
+	)
+	.unwrap();
+	assert!(filter.matches_bloom(&bloom));
+	assert!(!filter.matches_log(&log));
+}
@@ -0,0 +1,116 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use ethereum_types::{H160, U256};
+use primitives::Nonce;
+use serde::{Deserialize, Serialize};
+use sp_core::{Bytes, Encode};
+use std::collections::HashSet;
+
+/// Block tag, with the shape of the block parameter of `eth_getTransactionCount`.
+#[derive(Debug, PartialEq, Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum Tag {
+	/// The best block
+	Latest,
+	/// The best block, with the ready transactions of the transaction pool
+	Pending,
+}
+
+/// A block tag or a block hash.
+#[derive(Debug, PartialEq, Deserialize, Clone, Copy)]
+#[serde(untagged)]
+pub enum BlockTag<BlockHash> {
+	Tag(Tag),
+	Hash(BlockHash),
+}
+
+/// Pending transaction response, with the shape of `eth_pendingTransactions` response.
+#[derive(Debug, PartialEq, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingTransactionResponse<BlockHash> {
+	/// Hash of the extrinsic
+	pub hash: BlockHash,
+	/// Nonce of the extrinsic
+	pub nonce: U256,
+	/// Always `None`, the extrinsic is not included in a block
+	pub block_hash: Option<BlockHash>,
+	/// Always `None`, the extrinsic is not included in a block
+	pub block_number: Option<U256>,
+	/// Always `None`, the extrinsic is not included in a block
+	pub transaction_index: Option<U256>,
+	/// EVM address of the signer
+	pub from: H160,
+	/// Contract address to call, `None` if it creates a contract
+	pub to: Option<H160>,
+	/// Amount sent with the execution
+	pub value: U256,
+	/// Gas limit
+	pub gas: U256,
+	/// Storage limit
+	pub storage_limit: U256,
+	/// Input of the call or init code of the contract
+	pub input: Bytes,
+}
+
+/// The next nonce of `account` after its transactions in the transaction pool, given its `nonce`
+/// in the state and the tags `provides` of the ready transactions.
+///
+/// The transaction of `account` with `nonce` provides the tag `(account, nonce)`, and only counts
+/// if the transactions with all the nonces before it are ready too, so that the transactions
+/// queued after a nonce gap are not counted and the missing nonce is returned.
+pub fn pending_nonce<AccountId: Encode>(
+	account: &AccountId,
+	nonce: Nonce,
+	provides: impl IntoIterator<Item = Vec<u8>>,
+) -> Nonce {
+	let provides = provides.into_iter().collect::<HashSet<_>>();
+	let mut nonce = nonce;
+	while provides.contains(&(account, nonce).encode()) {
+		nonce = nonce.saturating_add(1);
+	}
+	nonce
+}
+
+#[test]
+fn block_tag_should_work() {
+	use ethereum_types::H256;
+
+	let tag: BlockTag<H256> = serde_json::from_str(r#""latest""#).unwrap();
+	assert_eq!(tag, BlockTag::Tag(Tag::Latest));
+	let tag: BlockTag<H256> = serde_json::from_str(r#""pending""#).unwrap();
+	assert_eq!(tag, BlockTag::Tag(Tag::Pending));
+	let tag: BlockTag<H256> =
+		serde_json::from_str(r#""0x0101010101010101010101010101010101010101010101010101010101010101""#).unwrap();
+	assert_eq!(tag, BlockTag::Hash(H256::repeat_byte(1)));
+}
+
+#[test]
+fn pending_nonce_should_work() {
+	let alice = 1u64;
+	let bob = 2u64;
+	let provides = vec![(alice, 5u32).encode(), (alice, 6u32).encode(), (bob, 7u32).encode()];
+
+	assert_eq!(pending_nonce(&alice, 5, provides.clone()), 7);
+	assert_eq!(pending_nonce(&alice, 4, provides.clone()), 4);
+	assert_eq!(pending_nonce(&bob, 7, provides.clone()), 8);
+
+	// the transaction after the nonce gap is not counted
+	let provides = vec![(alice, 5u32).encode(), (alice, 7u32).encode()];
+	assert_eq!(pending_nonce(&alice, 5, provides), 6);
+}
@@ -0,0 +1,335 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use ethereum_types::{H160, U256};
+use jsonrpc_core::{Error, ErrorCode, Result};
+use module_evm::{ExitError, ExitReason};
+use primitives::evm::{CallTrace, CallType, StructLog, TraceInfo, TracerConfig};
+use rustc_hex::ToHex;
+use serde::{Deserialize, Serialize};
+use sp_core::Bytes;
+
+/// Trace params, compatible with the options of Geth's `debug_traceTransaction`.
+#[derive(Debug, Default, PartialEq, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceParams {
+	/// Tracer, only `callTracer` is supported. Trace the executed opcodes if not set.
+	pub tracer: Option<String>,
+	/// Disable the stack of the executed opcodes
+	pub disable_stack: Option<bool>,
+	/// Disable the memory of the executed opcodes
+	pub disable_memory: Option<bool>,
+}
+
+impl TraceParams {
+	pub fn tracer_config(&self) -> Result<TracerConfig> {
+		match self.tracer.as_deref() {
+			None => Ok(TracerConfig::OpcodeTracer {
+				disable_stack: self.disable_stack.unwrap_or_default(),
+				disable_memory: self.disable_memory.unwrap_or_default(),
+			}),
+			Some("callTracer") => Ok(TracerConfig::CallTracer),
+			Some(tracer) => Err(Error {
+				code: ErrorCode::InvalidParams,
+				message: format!("Unsupported tracer: {}", tracer),
+				data: None,
+			}),
+		}
+	}
+}
+
+/// Trace response, with the shape of Geth's `debug_traceTransaction` response.
+#[derive(Debug, PartialEq, Serialize, Clone)]
+#[serde(untagged)]
+pub enum TraceResponse {
+	/// Response of `callTracer`
+	CallTrace(CallFrame),
+	/// Response of the default struct logger
+	StructLogs(StructLogsResponse),
+}
+
+impl TraceResponse {
+	pub fn new(info: TraceInfo, config: TracerConfig) -> Self {
+		match config {
+			TracerConfig::CallTracer => TraceResponse::CallTrace(info.call.into()),
+			TracerConfig::OpcodeTracer {
+				disable_stack,
+				disable_memory,
+			} => TraceResponse::StructLogs(StructLogsResponse {
+				gas: info.call.gas_used,
+				failed: !matches!(info.call.exit_reason, Some(ExitReason::Succeed(_))),
+				return_value: info.call.output.to_hex(),
+				struct_logs: info
+					.struct_logs
+					.into_iter()
+					.map(|struct_log| StructLogResponse::new(struct_log, disable_stack, disable_memory))
+					.collect(),
+			}),
+		}
+	}
+}
+
+/// Call frame
+#[derive(Debug, PartialEq, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CallFrame {
+	/// Call type, e.g. `CALL`, `CREATE`
+	#[serde(rename = "type")]
+	pub call_type: String,
+	/// From
+	pub from: H160,
+	/// To
+	pub to: H160,
+	/// Value
+	pub value: U256,
+	/// Gas limit
+	pub gas: U256,
+	/// Used gas
+	pub gas_used: U256,
+	/// Input
+	pub input: Bytes,
+	/// Output
+	pub output: Bytes,
+	/// Error, if the call failed
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+	/// Sub calls
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	pub calls: Vec<CallFrame>,
+}
+
+impl From<CallTrace> for CallFrame {
+	fn from(trace: CallTrace) -> Self {
+		let call_type = match trace.call_type {
+			CallType::Call => "CALL",
+			CallType::CallCode => "CALLCODE",
+			CallType::DelegateCall => "DELEGATECALL",
+			CallType::StaticCall => "STATICCALL",
+			CallType::Create => "CREATE",
+		};
+
+		CallFrame {
+			call_type: call_type.into(),
+			from: trace.from,
+			to: trace.to,
+			value: trace.value,
+			gas: trace.gas.into(),
+			gas_used: trace.gas_used.into(),
+			input: Bytes(trace.input),
+			output: Bytes(trace.output),
+			error: trace.exit_reason.as_ref().and_then(error_message),
+			calls: trace.calls.into_iter().map(Into::into).collect(),
+		}
+	}
+}
+
+/// Struct logs response
+#[derive(Debug, PartialEq, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLogsResponse {
+	/// Used gas
+	pub gas: u64,
+	/// Whether the execution failed
+	pub failed: bool,
+	/// Return value in hex, without `0x` prefix
+	pub return_value: String,
+	/// The executed opcodes
+	pub struct_logs: Vec<StructLogResponse>,
+}
+
+/// Struct log of an executed opcode
+#[derive(Debug, PartialEq, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLogResponse {
+	/// Program counter
+	pub pc: u64,
+	/// Opcode name
+	pub op: String,
+	/// Gas left before executing the opcode
+	pub gas: u64,
+	/// Gas cost of the opcode
+	pub gas_cost: u64,
+	/// Call depth
+	pub depth: u32,
+	/// Stack, from bottom to top
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub stack: Option<Vec<U256>>,
+	/// Memory, in 32 bytes words in hex, without `0x` prefix
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub memory: Option<Vec<String>>,
+}
+
+impl StructLogResponse {
+	fn new(struct_log: StructLog, disable_stack: bool, disable_memory: bool) -> Self {
+		StructLogResponse {
+			pc: struct_log.pc,
+			op: opcode_name(struct_log.op),
+			gas: struct_log.gas,
+			gas_cost: struct_log.gas_cost,
+			depth: struct_log.depth,
+			stack: if disable_stack {
+				None
+			} else {
+				Some(
+					struct_log
+						.stack
+						.iter()
+						.map(|value| U256::from_big_endian(value.as_bytes()))
+						.collect(),
+				)
+			},
+			memory: if disable_memory {
+				None
+			} else {
+				Some(struct_log.memory.chunks(32).map(|word| word.to_hex()).collect())
+			},
+		}
+	}
+}
+
+fn error_message(reason: &ExitReason) -> Option<String> {
+	match reason {
+		ExitReason::Succeed(_) => None,
+		ExitReason::Revert(_) => Some("execution reverted".into()),
+		ExitReason::Error(ExitError::OutOfGas) => Some("out of gas".into()),
+		ExitReason::Error(e) => Some(format!("execution error: {:?}", e)),
+		ExitReason::Fatal(e) => Some(format!("execution fatal: {:?}", e)),
+	}
+}
+
+fn opcode_name(opcode: u8) -> String {
+	let name = match opcode {
+		0x00 => "STOP",
+		0x01 => "ADD",
+		0x02 => "MUL",
+		0x03 => "SUB",
+		0x04 => "DIV",
+		0x05 => "SDIV",
+		0x06 => "MOD",
+		0x07 => "SMOD",
+		0x08 => "ADDMOD",
+		0x09 => "MULMOD",
+		0x0a => "EXP",
+		0x0b => "SIGNEXTEND",
+		0x10 => "LT",
+		0x11 => "GT",
+		0x12 => "SLT",
+		0x13 => "SGT",
+		0x14 => "EQ",
+		0x15 => "ISZERO",
+		0x16 => "AND",
+		0x17 => "OR",
+		0x18 => "XOR",
+		0x19 => "NOT",
+		0x1a => "BYTE",
+		0x1b => "SHL",
+		0x1c => "SHR",
+		0x1d => "SAR",
+		0x20 => "SHA3",
+		0x30 => "ADDRESS",
+		0x31 => "BALANCE",
+		0x32 => "ORIGIN",
+		0x33 => "CALLER",
+		0x34 => "CALLVALUE",
+		0x35 => "CALLDATALOAD",
+		0x36 => "CALLDATASIZE",
+		0x37 => "CALLDATACOPY",
+		0x38 => "CODESIZE",
+		0x39 => "CODECOPY",
+		0x3a => "GASPRICE",
+		0x3b => "EXTCODESIZE",
+		0x3c => "EXTCODECOPY",
+		0x3d => "RETURNDATASIZE",
+		0x3e => "RETURNDATACOPY",
+		0x3f => "EXTCODEHASH",
+		0x40 => "BLOCKHASH",
+		0x41 => "COINBASE",
+		0x42 => "TIMESTAMP",
+		0x43 => "NUMBER",
+		0x44 => "DIFFICULTY",
+		0x45 => "GASLIMIT",
+		0x46 => "CHAINID",
+		0x47 => "SELFBALANCE",
+		0x50 => "POP",
+		0x51 => "MLOAD",
+		0x52 => "MSTORE",
+		0x53 => "MSTORE8",
+		0x54 => "SLOAD",
+		0x55 => "SSTORE",
+		0x56 => "JUMP",
+		0x57 => "JUMPI",
+		0x58 => "PC",
+		0x59 => "MSIZE",
+		0x5a => "GAS",
+		0x5b => "JUMPDEST",
+		0x60..=0x7f => return format!("PUSH{}", opcode - 0x5f),
+		0x80..=0x8f => return format!("DUP{}", opcode - 0x7f),
+		0x90..=0x9f => return format!("SWAP{}", opcode - 0x8f),
+		0xa0..=0xa4 => return format!("LOG{}", opcode - 0xa0),
+		0xf0 => "CREATE",
+		0xf1 => "CALL",
+		0xf2 => "CALLCODE",
+		0xf3 => "RETURN",
+		0xf4 => "DELEGATECALL",
+		0xf5 => "CREATE2",
+		0xfa => "STATICCALL",
+		0xfd => "REVERT",
+		0xfe => "INVALID",
+		0xff => "SELFDESTRUCT",
+		_ => return format!("opcode 0x{:x} not defined", opcode),
+	};
+	name.into()
+}
+
+#[test]
+fn opcode_name_should_work() {
+	assert_eq!(opcode_name(0x00), "STOP");
+	assert_eq!(opcode_name(0x60), "PUSH1");
+	assert_eq!(opcode_name(0x7f), "PUSH32");
+	assert_eq!(opcode_name(0x80), "DUP1");
+	assert_eq!(opcode_name(0x9f), "SWAP16");
+	assert_eq!(opcode_name(0xa4), "LOG4");
+	assert_eq!(opcode_name(0xfd), "REVERT");
+	assert_eq!(opcode_name(0x0c), "opcode 0xc not defined");
+}
+
+#[test]
+fn tracer_config_should_work() {
+	assert_eq!(
+		TraceParams::default().tracer_config().unwrap(),
+		TracerConfig::OpcodeTracer {
+			disable_stack: false,
+			disable_memory: false,
+		}
+	);
+	assert_eq!(
+		TraceParams {
+			tracer: Some("callTracer".into()),
+			..Default::default()
+		}
+		.tracer_config()
+		.unwrap(),
+		TracerConfig::CallTracer
+	);
+	assert!(TraceParams {
+		tracer: Some("prestateTracer".into()),
+		..Default::default()
+	}
+	.tracer_config()
+	.is_err());
+}
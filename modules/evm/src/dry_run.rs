@@ -0,0 +1,35 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Record the contracts whose storage usage changes during a dry run. The recorder is set with
+//! `using`.
+
+use primitives::evm::EvmAddress;
+use sp_std::collections::btree_set::BTreeSet;
+
+environmental::environmental!(recorder: BTreeSet<EvmAddress>);
+
+/// Execute `f` and record the contracts whose storage usage changes in it into `contracts`.
+pub fn using<R, F: FnOnce() -> R>(contracts: &mut BTreeSet<EvmAddress>, f: F) -> R {
+	recorder::using(contracts, f)
+}
+
+/// Record `contract` if in a dry run.
+pub(crate) fn record_storage_change(contract: EvmAddress) {
+	recorder::with(|contracts| contracts.insert(contract));
+}
@@ -23,7 +23,7 @@
 #![allow(clippy::upper_case_acronyms)]
 
 use crate::runner::{
-	handler::{Handler, StorageMeterHandlerImpl},
+	handler::{self, Handler, StorageMeterHandlerImpl},
 	storage_meter::{StorageMeter, StorageMeterHandler},
 };
 use codec::{Decode, Encode};
@@ -34,8 +34,8 @@ use frame_support::{
 	error::BadOrigin,
 	pallet_prelude::*,
 	traits::{
-		BalanceStatus, Currency, EnsureOrigin, ExistenceRequirement, Get, MaxEncodedLen, NamedReservableCurrency,
-		OnKilledAccount,
+		BalanceStatus, Currency, EnsureOrigin, ExistenceRequirement, Filter, Get, MaxEncodedLen,
+		NamedReservableCurrency, OnKilledAccount,
 	},
 	transactional,
 	weights::{Pays, PostDispatchInfo, Weight},
@@ -43,6 +43,7 @@ use frame_support::{
 };
 use frame_system::{ensure_root, ensure_signed, pallet_prelude::*, EnsureOneOf, EnsureRoot, EnsureSigned};
 use primitive_types::{H256, U256};
+use primitives::CurrencyId;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use sha3::{Digest, Keccak256};
@@ -53,10 +54,12 @@ use sp_runtime::{
 	transaction_validity::TransactionValidityError,
 	Either, TransactionOutcome,
 };
-use sp_std::{convert::TryInto, marker::PhantomData, prelude::*};
+use sp_std::{collections::btree_set::BTreeSet, convert::TryInto, marker::PhantomData, prelude::*};
+
+use support::{DispatchableTask, IdleScheduler, TaskResult};
 
 pub use support::{
-	AddressMapping, EVMStateRentTrait, ExecutionMode, InvokeContext, TransactionPayment, EVM as EVMTrait,
+	AddressMapping, EVMCall, EVMStateRentTrait, ExecutionMode, InvokeContext, TransactionPayment, EVM as EVMTrait,
 };
 
 pub use crate::precompiles::{Precompile, Precompiles};
@@ -64,10 +67,14 @@ pub use crate::runner::Runner;
 pub use evm::{Context, ExitError, ExitFatal, ExitReason, ExitRevert, ExitSucceed};
 pub use orml_traits::currency::TransferAll;
 pub use primitives::{
-	evm::{Account, CallInfo, CreateInfo, EvmAddress, Log, Vicinity},
-	ReserveIdentifier, MIRRORED_NFT_ADDRESS_START,
+	evm::{
+		AccessListItem, Account, BlockFeeInfo, CallInfo, ContractMetadata, ContractStatus, CreateInfo, EstimateFeeInfo,
+		EvmAddress, EvmTransaction, Log, LogsBloom, Receipt, Vicinity, LOGS_BLOOM_SIZE,
+	},
+	Nonce, ReserveIdentifier, MIRRORED_NFT_ADDRESS_START,
 };
 
+pub mod dry_run;
 pub mod precompiles;
 pub mod runner;
 
@@ -84,6 +91,18 @@ pub type NegativeImbalanceOf<T> =
 	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::NegativeImbalance;
 pub const RESERVE_ID_STORAGE_DEPOSIT: ReserveIdentifier = ReserveIdentifier::EvmStorageDeposit;
 pub const RESERVE_ID_DEVELOPER_DEPOSIT: ReserveIdentifier = ReserveIdentifier::EvmDeveloperDeposit;
+/// The gas limit of a block is `BlockGasTarget` times this multiplier.
+pub const ELASTICITY_MULTIPLIER: u64 = 2;
+/// The amount of gas the base fee per gas is averaged over, so the rounding of the weight fee
+/// doesn't skew the fee of a single gas.
+pub const BASE_FEE_GAS_UNIT: u64 = 1_000_000;
+/// The max length of the IPFS CID of the contract metadata.
+pub const MAX_SOURCE_CID_LENGTH: usize = 128;
+/// The max length of the compiler version of the contract metadata.
+pub const MAX_COMPILER_VERSION_LENGTH: usize = 64;
+/// The gas of the `LOG` opcodes before the topics and data, bounds the number of logs of an
+/// execution.
+pub const LOG_GAS: u64 = 375;
 
 // Initially based on Istanbul hard fork configuration.
 static ACALA_CONFIG: EvmConfig = EvmConfig {
@@ -124,6 +143,47 @@ static ACALA_CONFIG: EvmConfig = EvmConfig {
 	estimate: false,
 };
 
+/// The background tasks of the module.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum EvmTask<T: Config> {
+	/// Remove the storage of the removed contract.
+	RemoveStorage {
+		contract: EvmAddress,
+		#[codec(skip)]
+		_marker: PhantomData<T>,
+	},
+}
+
+impl<T: Config> EvmTask<T> {
+	/// The task to remove the storage of the removed `contract`.
+	pub fn remove_storage(contract: EvmAddress) -> Self {
+		EvmTask::RemoveStorage {
+			contract,
+			_marker: PhantomData,
+		}
+	}
+}
+
+impl<T: Config> DispatchableTask for EvmTask<T> {
+	fn dispatch(&mut self, weight: Weight) -> TaskResult {
+		match self {
+			EvmTask::RemoveStorage { contract, .. } => {
+				let entry_weight = T::DbWeight::get().reads_writes(1, 1);
+				let limit = weight.checked_div(entry_weight).unwrap_or(Weight::max_value());
+				let removed = AccountStorages::<T>::drain_prefix(*contract)
+					.take(limit.unique_saturated_into())
+					.count() as Weight;
+
+				TaskResult {
+					result: Ok(()),
+					used_weight: entry_weight.saturating_mul(removed),
+					finished: removed < limit,
+				}
+			}
+		}
+	}
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use crate::runner::handler;
@@ -169,6 +229,15 @@ pub mod module {
 		/// Convert gas to weight.
 		type GasToWeight: Convert<u64, Weight>;
 
+		/// The gas used per block targeted, the gas limit reported of a block is twice of it.
+		#[pallet::constant]
+		type BlockGasTarget: Get<u64>;
+
+		/// Surcharge the cold state accesses as EIP-2929, which can be prepaid by the EIP-2930
+		/// access lists, so the gas costs of the state accesses match Ethereum.
+		#[pallet::constant]
+		type AccessListGasMetering: Get<bool>;
+
 		/// ChargeTransactionPayment convert weight to fee.
 		type ChargeTransactionPayment: TransactionPayment<Self::AccountId, BalanceOf<Self>, NegativeImbalanceOf<Self>>;
 
@@ -197,6 +266,20 @@ pub mod module {
 
 		type FreeDeploymentOrigin: EnsureOrigin<Self::Origin>;
 
+		/// Schedules the background tasks, e.g. the removal of the storage of the removed
+		/// contracts, which is unbounded.
+		type IdleScheduler: IdleScheduler<EvmTask<Self>>;
+
+		/// The filter of the contracts that can be called. It's checked on every call frame, so the
+		/// filtered contracts can't be reached by the sub calls, e.g. of the precompiles.
+		type ContractFilter: Filter<EvmAddress>;
+
+		/// The number of blocks from the eviction notice of a contract until it can be evicted, so
+		/// the maintainer can top up the storage deposit. The notice lapses after as many blocks
+		/// again.
+		#[pallet::constant]
+		type EvictionNoticePeriod: Get<Self::BlockNumber>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -226,6 +309,17 @@ pub mod module {
 		pub ref_count: u32,
 	}
 
+	#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug, Encode, Decode, MaxEncodedLen)]
+	pub struct EvictedContractInfo {
+		/// The hash of the removed storage entries which are not resurrected yet, chained in the
+		/// order of removal.
+		pub storage_hash: H256,
+		/// The number of the removed storage entries which are not resurrected yet.
+		pub storage_count: u32,
+		/// Whether all the storage entries are removed.
+		pub completed: bool,
+	}
+
 	#[cfg(feature = "std")]
 	#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, Serialize, Deserialize)]
 	/// Account definition used for genesis block construction.
@@ -279,6 +373,28 @@ pub mod module {
 	#[pallet::getter(fn code_infos)]
 	pub type CodeInfos<T: Config> = StorageMap<_, Identity, H256, CodeInfo, OptionQuery>;
 
+	/// The contracts evicted for insufficient storage deposit, whose storage entries are removed
+	/// until they are resurrected.
+	///
+	/// EvictedContracts: map EvmAddress => Option<EvictedContractInfo>
+	#[pallet::storage]
+	#[pallet::getter(fn evicted_contracts)]
+	pub type EvictedContracts<T: Config> = StorageMap<_, Twox64Concat, EvmAddress, EvictedContractInfo, OptionQuery>;
+
+	/// The block number from which the underfunded contracts can be evicted.
+	///
+	/// EvictionNotices: map EvmAddress => Option<BlockNumber>
+	#[pallet::storage]
+	#[pallet::getter(fn eviction_notices)]
+	pub type EvictionNotices<T: Config> = StorageMap<_, Twox64Concat, EvmAddress, T::BlockNumber, OptionQuery>;
+
+	/// The verification metadata of contract codes, the key is the code hash.
+	///
+	/// ContractMetadatas: map H256 => Option<ContractMetadata>
+	#[pallet::storage]
+	#[pallet::getter(fn contract_metadatas)]
+	pub type ContractMetadatas<T: Config> = StorageMap<_, Identity, H256, ContractMetadata, OptionQuery>;
+
 	/// Next available system contract address.
 	///
 	/// NetworkContractIndex: u64
@@ -293,6 +409,41 @@ pub mod module {
 	#[pallet::getter(fn extrinsic_origin)]
 	pub type ExtrinsicOrigin<T: Config> = StorageValue<_, T::AccountId, OptionQuery>;
 
+	/// The gas used by the EVM executions in the current block.
+	///
+	/// BlockGasUsed: u64
+	#[pallet::storage]
+	#[pallet::getter(fn block_gas_used)]
+	pub type BlockGasUsed<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+	/// The bloom of the log addresses and topics emitted in the current block.
+	///
+	/// BlockLogsBloom: Option<LogsBloom>
+	#[pallet::storage]
+	#[pallet::getter(fn block_logs_bloom)]
+	pub type BlockLogsBloom<T: Config> = StorageValue<_, LogsBloom, OptionQuery>;
+
+	/// The receipts of the EVM executions in the current block, by the index of the receipt.
+	///
+	/// BlockReceipts: map u32 => Option<Receipt>
+	#[pallet::storage]
+	pub type BlockReceipts<T: Config> = StorageMap<_, Twox64Concat, u32, Receipt, OptionQuery>;
+
+	/// The number of the receipts in the current block.
+	///
+	/// BlockReceiptCount: u32
+	#[pallet::storage]
+	#[pallet::getter(fn block_receipt_count)]
+	pub type BlockReceiptCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// The logs of the EVM executions in the current block which are not yet recorded in the
+	/// receipts, by the extrinsic index. They are appended without decoding, and recorded once
+	/// per extrinsic.
+	///
+	/// PendingLogs: map Option<u32> => Vec<Log>
+	#[pallet::storage]
+	pub type PendingLogs<T: Config> = StorageMap<_, Twox64Concat, Option<u32>, Vec<Log>, ValueQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub accounts: std::collections::BTreeMap<EvmAddress, GenesisAccount<BalanceOf<T>, T::Index>>,
@@ -417,6 +568,19 @@ pub mod module {
 		ContractSetCode(EvmAddress),
 		/// Selfdestructed contract code. \[contract\]
 		ContractSelfdestructed(EvmAddress),
+		/// Notified the eviction of contract for insufficient storage deposit. \[contract,
+		/// evictable_at\]
+		EvictionNotified(EvmAddress, T::BlockNumber),
+		/// Topped up the storage deposit of contract. \[contract, who, amount\]
+		StorageDepositToppedUp(EvmAddress, T::AccountId, BalanceOf<T>),
+		/// Evicted storage entries of contract for insufficient storage deposit. \[contract,
+		/// removed_count, completed\]
+		ContractEvicted(EvmAddress, u32, bool),
+		/// Resurrected storage entries of evicted contract. \[contract, who, restored_count,
+		/// completed\]
+		ContractResurrected(EvmAddress, T::AccountId, u32, bool),
+		/// Submitted the verification metadata of contract code. \[contract, code_hash\]
+		ContractMetadataSubmitted(EvmAddress, H256),
 	}
 
 	#[pallet::error]
@@ -447,13 +611,62 @@ pub mod module {
 		CannotKillContract,
 		/// Contract address conflicts with the system contract
 		ConflictContractAddress,
+		/// Contract is evicted
+		ContractEvicted,
+		/// Contract is not evicted
+		ContractNotEvicted,
+		/// Contract storage deposit is sufficient
+		StorageDepositSufficient,
+		/// Storage entries do not match the evicted storage
+		InvalidEvictedStorage,
+		/// Execution failed
+		ExecutionFail,
+		/// Execution reverted
+		ExecutionRevert,
+		/// Execution fatal
+		ExecutionFatal,
+		/// Execution error
+		ExecutionError,
+		/// The metadata of the contract code is already submitted
+		ContractMetadataAlreadySubmitted,
+		/// The contract metadata is too long
+		InvalidContractMetadata,
+		/// Contract eviction is not completed
+		EvictionNotCompleted,
+		/// Contract eviction is not notified, or the notice is not in effect
+		EvictionNotNotified,
+		/// Contract eviction is already notified
+		EvictionAlreadyNotified,
+		/// The calls to the contract are filtered
+		ContractFiltered,
 	}
 
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_initialize(_now: T::BlockNumber) -> Weight {
+			BlockGasUsed::<T>::kill();
+			BlockLogsBloom::<T>::kill();
+			let receipt_count = BlockReceiptCount::<T>::take();
+			for index in 0..receipt_count {
+				BlockReceipts::<T>::remove(index);
+			}
+			// including the read of the pending logs in `on_finalize`
+			T::DbWeight::get().reads_writes(2, 3 + receipt_count as Weight)
+		}
+
+		/// Record the logs of the executions which are not recorded by their extrinsics, e.g. the
+		/// executions of the other modules.
+		fn on_finalize(_now: T::BlockNumber) {
+			let mut pending_logs: Vec<(Option<u32>, Vec<Log>)> = PendingLogs::<T>::drain().collect();
+			pending_logs.sort_by_key(|(extrinsic_index, _)| *extrinsic_index);
+			for (extrinsic_index, logs) in pending_logs {
+				Self::record_receipt(extrinsic_index, 0, logs);
+			}
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -465,7 +678,7 @@ pub mod module {
 		/// - `value`: the amount sent for payable calls
 		/// - `gas_limit`: the maximum gas the call can use
 		/// - `storage_limit`: the total bytes the contract's storage can increase by
-		#[pallet::weight(T::GasToWeight::convert(*gas_limit))]
+		#[pallet::weight(Pallet::<T>::max_execution_weight(*gas_limit))]
 		pub fn call(
 			origin: OriginFor<T>,
 			target: EvmAddress,
@@ -495,9 +708,10 @@ pub mod module {
 			}
 
 			let used_gas: u64 = info.used_gas.unique_saturated_into();
+			let logs = Self::record_execution(used_gas);
 
 			Ok(PostDispatchInfo {
-				actual_weight: Some(T::GasToWeight::convert(used_gas)),
+				actual_weight: Some(Self::execution_weight(used_gas, logs)),
 				pays_fee: Pays::Yes,
 			})
 		}
@@ -511,7 +725,7 @@ pub mod module {
 		/// - `value`: the amount sent for payable calls
 		/// - `gas_limit`: the maximum gas the call can use
 		/// - `storage_limit`: the total bytes the contract's storage can increase by
-		#[pallet::weight(T::GasToWeight::convert(*gas_limit))]
+		#[pallet::weight(Pallet::<T>::max_execution_weight(*gas_limit))]
 		#[transactional]
 		pub fn scheduled_call(
 			origin: OriginFor<T>,
@@ -544,6 +758,7 @@ pub mod module {
 			}
 
 			let used_gas: u64 = info.used_gas.unique_saturated_into();
+			let logs = Self::record_execution(used_gas);
 
 			#[cfg(not(feature = "with-ethereum-compatibility"))]
 			{
@@ -562,7 +777,7 @@ pub mod module {
 			}
 
 			Ok(PostDispatchInfo {
-				actual_weight: Some(T::GasToWeight::convert(used_gas)),
+				actual_weight: Some(Self::execution_weight(used_gas, logs)),
 				pays_fee: Pays::Yes,
 			})
 		}
@@ -574,7 +789,7 @@ pub mod module {
 		/// - `value`: the amount sent to the contract upon creation
 		/// - `gas_limit`: the maximum gas the call can use
 		/// - `storage_limit`: the total bytes the contract's storage can increase by
-		#[pallet::weight(T::GasToWeight::convert(*gas_limit))]
+		#[pallet::weight(Pallet::<T>::max_execution_weight(*gas_limit))]
 		pub fn create(
 			origin: OriginFor<T>,
 			init: Vec<u8>,
@@ -594,9 +809,10 @@ pub mod module {
 			}
 
 			let used_gas: u64 = info.used_gas.unique_saturated_into();
+			let logs = Self::record_execution(used_gas);
 
 			Ok(PostDispatchInfo {
-				actual_weight: Some(T::GasToWeight::convert(used_gas)),
+				actual_weight: Some(Self::execution_weight(used_gas, logs)),
 				pays_fee: Pays::Yes,
 			})
 		}
@@ -609,7 +825,7 @@ pub mod module {
 		/// - `value`: the amount sent for payable calls
 		/// - `gas_limit`: the maximum gas the call can use
 		/// - `storage_limit`: the total bytes the contract's storage can increase by
-		#[pallet::weight(T::GasToWeight::convert(*gas_limit))]
+		#[pallet::weight(Pallet::<T>::max_execution_weight(*gas_limit))]
 		pub fn create2(
 			origin: OriginFor<T>,
 			init: Vec<u8>,
@@ -630,9 +846,10 @@ pub mod module {
 			}
 
 			let used_gas: u64 = info.used_gas.unique_saturated_into();
+			let logs = Self::record_execution(used_gas);
 
 			Ok(PostDispatchInfo {
-				actual_weight: Some(T::GasToWeight::convert(used_gas)),
+				actual_weight: Some(Self::execution_weight(used_gas, logs)),
 				pays_fee: Pays::Yes,
 			})
 		}
@@ -644,7 +861,7 @@ pub mod module {
 		/// - `value`: the amount sent for payable calls
 		/// - `gas_limit`: the maximum gas the call can use
 		/// - `storage_limit`: the total bytes the contract's storage can increase by
-		#[pallet::weight(T::GasToWeight::convert(*gas_limit))]
+		#[pallet::weight(Pallet::<T>::max_execution_weight(*gas_limit))]
 		pub fn create_network_contract(
 			origin: OriginFor<T>,
 			init: Vec<u8>,
@@ -668,9 +885,137 @@ pub mod module {
 			}
 
 			let used_gas: u64 = info.used_gas.unique_saturated_into();
+			let logs = Self::record_execution(used_gas);
+
+			Ok(PostDispatchInfo {
+				actual_weight: Some(Self::execution_weight(used_gas, logs)),
+				pays_fee: Pays::Yes,
+			})
+		}
+
+		/// Issue an EVM call operation like `call`, but the extrinsic fails and its changes are
+		/// reverted if the execution doesn't succeed. Native modules can make the same call with
+		/// `EVMCall`.
+		///
+		/// - `target`: the contract address to call
+		/// - `input`: the data supplied for the call
+		/// - `value`: the amount sent for payable calls
+		/// - `gas_limit`: the maximum gas the call can use
+		/// - `storage_limit`: the total bytes the contract's storage can increase by
+		#[pallet::weight(Pallet::<T>::max_execution_weight(*gas_limit))]
+		pub fn call_evm(
+			origin: OriginFor<T>,
+			target: EvmAddress,
+			input: Vec<u8>,
+			value: BalanceOf<T>,
+			gas_limit: u64,
+			storage_limit: u32,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let info = Self::do_call_evm(&who, target, input, value, gas_limit, storage_limit)?;
+
+			Pallet::<T>::deposit_event(Event::<T>::Executed(target));
+
+			let used_gas: u64 = info.used_gas.unique_saturated_into();
+			let logs = Self::record_execution(used_gas);
+
+			Ok(PostDispatchInfo {
+				actual_weight: Some(Self::execution_weight(used_gas, logs)),
+				pays_fee: Pays::Yes,
+			})
+		}
+
+		/// Issue an EVM call operation with an EIP-2930 access list.
+		///
+		/// - `target`: the contract address to call
+		/// - `input`: the data supplied for the call
+		/// - `value`: the amount sent for payable calls
+		/// - `gas_limit`: the maximum gas the call can use
+		/// - `storage_limit`: the total bytes the contract's storage can increase by
+		/// - `access_list`: the addresses and storage keys which are warm from the start, ignored
+		///   if `AccessListGasMetering` is disabled
+		#[pallet::weight(Pallet::<T>::max_execution_weight(*gas_limit))]
+		pub fn call_with_access_list(
+			origin: OriginFor<T>,
+			target: EvmAddress,
+			input: Vec<u8>,
+			value: BalanceOf<T>,
+			gas_limit: u64,
+			storage_limit: u32,
+			access_list: Vec<AccessListItem>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let source = T::AddressMapping::get_or_create_evm_address(&who);
+
+			let info = Runner::<T>::call_with_access_list(
+				source,
+				source,
+				target,
+				input,
+				value,
+				gas_limit,
+				storage_limit,
+				access_list,
+				T::config(),
+			)?;
+
+			if info.exit_reason.is_succeed() {
+				Pallet::<T>::deposit_event(Event::<T>::Executed(target));
+			} else {
+				Pallet::<T>::deposit_event(Event::<T>::ExecutedFailed(target, info.exit_reason, info.output));
+			}
+
+			let used_gas: u64 = info.used_gas.unique_saturated_into();
+			let logs = Self::record_execution(used_gas);
+
+			Ok(PostDispatchInfo {
+				actual_weight: Some(Self::execution_weight(used_gas, logs)),
+				pays_fee: Pays::Yes,
+			})
+		}
+
+		/// Issue an EVM create operation with an EIP-2930 access list.
+		///
+		/// - `init`: the data supplied for the contract's constructor
+		/// - `value`: the amount sent to the contract upon creation
+		/// - `gas_limit`: the maximum gas the call can use
+		/// - `storage_limit`: the total bytes the contract's storage can increase by
+		/// - `access_list`: the addresses and storage keys which are warm from the start, ignored
+		///   if `AccessListGasMetering` is disabled
+		#[pallet::weight(Pallet::<T>::max_execution_weight(*gas_limit))]
+		pub fn create_with_access_list(
+			origin: OriginFor<T>,
+			init: Vec<u8>,
+			value: BalanceOf<T>,
+			gas_limit: u64,
+			storage_limit: u32,
+			access_list: Vec<AccessListItem>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let source = T::AddressMapping::get_or_create_evm_address(&who);
+
+			let info = Runner::<T>::create_with_access_list(
+				source,
+				init,
+				value,
+				gas_limit,
+				storage_limit,
+				access_list,
+				T::config(),
+			)?;
+
+			if info.exit_reason.is_succeed() {
+				Pallet::<T>::deposit_event(Event::<T>::Created(info.address));
+			} else {
+				Pallet::<T>::deposit_event(Event::<T>::CreatedFailed(info.address, info.exit_reason, info.output));
+			}
+
+			let used_gas: u64 = info.used_gas.unique_saturated_into();
+			let logs = Self::record_execution(used_gas);
 
 			Ok(PostDispatchInfo {
-				actual_weight: Some(T::GasToWeight::convert(used_gas)),
+				actual_weight: Some(Self::execution_weight(used_gas, logs)),
 				pays_fee: Pays::Yes,
 			})
 		}
@@ -787,10 +1132,248 @@ pub mod module {
 
 			Ok(().into())
 		}
+
+		/// Notify the eviction of a contract whose reserved storage deposit doesn't cover its
+		/// storage usage anymore. The contract can be evicted after `EvictionNoticePeriod` blocks,
+		/// unless its storage deposit is topped up, and until the notice lapses.
+		///
+		/// - `contract`: The contract to evict
+		#[pallet::weight(<T as Config>::WeightInfo::notify_eviction())]
+		#[transactional]
+		pub fn notify_eviction(origin: OriginFor<T>, contract: EvmAddress) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let evictable_at = Self::do_notify_eviction(contract)?;
+
+			Pallet::<T>::deposit_event(Event::<T>::EvictionNotified(contract, evictable_at));
+
+			Ok(().into())
+		}
+
+		/// Top up the storage deposit of a contract to cover its storage usage.
+		///
+		/// - `contract`: The contract, the caller pays the storage deposit it lacks
+		#[pallet::weight(<T as Config>::WeightInfo::top_up_storage_deposit())]
+		#[transactional]
+		pub fn top_up_storage_deposit(origin: OriginFor<T>, contract: EvmAddress) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::accounts(contract)
+				.and_then(|account_info| account_info.contract_info)
+				.ok_or(Error::<T>::ContractNotFound)?;
+			ensure!(
+				!EvictedContracts::<T>::contains_key(contract),
+				Error::<T>::ContractEvicted
+			);
+			let amount = Self::do_top_up_storage_deposit(&who, contract)?;
+
+			Pallet::<T>::deposit_event(Event::<T>::StorageDepositToppedUp(contract, who, amount));
+
+			Ok(().into())
+		}
+
+		/// Evict a contract whose reserved storage deposit doesn't cover its storage usage anymore,
+		/// once its eviction notice is in effect. The contract can't be called from the first
+		/// eviction call until resurrected, its storage entries are removed by up to `limit` per
+		/// call until none is left.
+		///
+		/// - `contract`: The contract to evict
+		/// - `limit`: The max number of storage entries to remove in this call
+		#[pallet::weight(<T as Config>::WeightInfo::evict_contract(*limit))]
+		#[transactional]
+		pub fn evict_contract(origin: OriginFor<T>, contract: EvmAddress, limit: u32) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let (removed_count, completed) = Self::do_evict_contract(contract, limit)?;
+
+			Pallet::<T>::deposit_event(Event::<T>::ContractEvicted(contract, removed_count, completed));
+
+			Ok(PostDispatchInfo {
+				actual_weight: Some(<T as Config>::WeightInfo::evict_contract(removed_count)),
+				pays_fee: Pays::Yes,
+			})
+		}
+
+		/// Resurrect an evicted contract by restoring its storage entries and topping up its
+		/// storage deposit. The entries are restored from the last removed ones by batches, the
+		/// contract can be called again once all of them are restored.
+		///
+		/// - `contract`: The evicted contract
+		/// - `storages`: The last removed storage entries not restored yet, in the order of
+		///   removal, the caller pays the storage deposit the contract lacks with them
+		/// - `prefix_hash`: The hash of the storage entries removed before `storages`
+		#[pallet::weight(<T as Config>::WeightInfo::resurrect_contract(storages.len() as u32))]
+		#[transactional]
+		pub fn resurrect_contract(
+			origin: OriginFor<T>,
+			contract: EvmAddress,
+			storages: Vec<(H256, H256)>,
+			prefix_hash: H256,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let restored_count = storages.len() as u32;
+			let completed = Self::do_resurrect_contract(&who, contract, storages, prefix_hash)?;
+
+			Pallet::<T>::deposit_event(Event::<T>::ContractResurrected(
+				contract,
+				who,
+				restored_count,
+				completed,
+			));
+
+			Ok(().into())
+		}
+
+		/// Submit the verification metadata of the code of a contract, so the source of the code
+		/// can be verified against the metadata. The metadata of a code can only be submitted once.
+		///
+		/// - `contract`: The contract, the caller must be the contract's maintainer
+		/// - `source_cid`: The IPFS CID of the source code and the compiler metadata
+		/// - `compiler_version`: The compiler version
+		/// - `compiler_settings_hash`: The hash of the compiler settings
+		#[pallet::weight(<T as Config>::WeightInfo::submit_contract_metadata())]
+		#[transactional]
+		pub fn submit_contract_metadata(
+			origin: OriginFor<T>,
+			contract: EvmAddress,
+			source_cid: Vec<u8>,
+			compiler_version: Vec<u8>,
+			compiler_settings_hash: H256,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let maintainer = T::AddressMapping::get_evm_address(&who).ok_or(Error::<T>::AddressNotMapped)?;
+			let code_hash = Self::do_submit_contract_metadata(
+				maintainer,
+				contract,
+				source_cid,
+				compiler_version,
+				compiler_settings_hash,
+			)?;
+
+			Pallet::<T>::deposit_event(Event::<T>::ContractMetadataSubmitted(contract, code_hash));
+
+			Ok(().into())
+		}
 	}
 }
 
 impl<T: Config> Pallet<T> {
+	/// The base fee per gas, derived from the weight fee charged for the gas used by an EVM
+	/// execution and rounded up. The fee of the gas doesn't depend on the gas used of the block,
+	/// so the base fee of the next block is the same.
+	pub fn base_fee_per_gas() -> U256 {
+		let fee = T::ChargeTransactionPayment::weight_to_fee(T::GasToWeight::convert(BASE_FEE_GAS_UNIT));
+		let fee = U256::from(UniqueSaturatedInto::<u128>::unique_saturated_into(fee));
+		let unit = U256::from(BASE_FEE_GAS_UNIT);
+		fee.saturating_add(unit - 1) / unit
+	}
+
+	/// The fee information of the current block.
+	pub fn block_fee_info() -> BlockFeeInfo {
+		let base_fee_per_gas = Self::base_fee_per_gas();
+		BlockFeeInfo {
+			base_fee_per_gas,
+			next_base_fee_per_gas: base_fee_per_gas,
+			gas_used: Self::block_gas_used(),
+			gas_limit: T::BlockGasTarget::get().saturating_mul(ELASTICITY_MULTIPLIER),
+		}
+	}
+
+	/// The receipts of the EVM executions in the current block, in the order of the extrinsics.
+	pub fn block_receipts() -> Vec<Receipt> {
+		let mut receipts: Vec<Receipt> = (0..Self::block_receipt_count())
+			.filter_map(BlockReceipts::<T>::get)
+			.collect();
+		// the receipts recorded in `on_finalize` are after the ones of the later extrinsics
+		receipts.sort_by_key(|receipt| receipt.extrinsic_index);
+		receipts
+	}
+
+	/// The max number of logs an execution with `gas_limit` can emit.
+	pub fn max_logs(gas_limit: u64) -> u32 {
+		(gas_limit / LOG_GAS).unique_saturated_into()
+	}
+
+	/// The weight of an execution which uses `used_gas` and emits `logs` logs, including
+	/// recording it in the block gas used and the receipts.
+	pub fn execution_weight(used_gas: u64, logs: u32) -> Weight {
+		T::GasToWeight::convert(used_gas).saturating_add(T::WeightInfo::record_execution(logs))
+	}
+
+	/// The max weight of an execution with `gas_limit`.
+	pub fn max_execution_weight(gas_limit: u64) -> Weight {
+		Self::execution_weight(gas_limit, Self::max_logs(gas_limit))
+	}
+
+	/// Add the gas used by an execution to the block, and record it with the pending logs of the
+	/// current extrinsic in its receipt. Returns the number of the logs recorded.
+	fn record_execution(used_gas: u64) -> u32 {
+		BlockGasUsed::<T>::mutate(|gas| *gas = gas.saturating_add(used_gas));
+
+		let extrinsic_index = frame_system::Pallet::<T>::extrinsic_index();
+		let logs = PendingLogs::<T>::take(extrinsic_index);
+		let count = logs.len() as u32;
+		Self::record_receipt(extrinsic_index, used_gas, logs);
+		count
+	}
+
+	/// Deposit the EVM log of `address`, e.g. a precompile mirroring its effects as logs. It's
+	/// recorded in the receipt of the current extrinsic like the logs of contracts.
+	pub fn deposit_log(address: EvmAddress, topics: Vec<H256>, data: Vec<u8>) {
+		let log = Log { address, topics, data };
+		Self::record_log(log.clone());
+		Self::deposit_event(Event::<T>::Log(log));
+	}
+
+	/// Add the log to the pending logs of the current extrinsic, which are recorded in its receipt
+	/// once the execution is recorded.
+	pub(crate) fn record_log(log: Log) {
+		PendingLogs::<T>::append(frame_system::Pallet::<T>::extrinsic_index(), log);
+	}
+
+	/// Accrue the logs into the block logs bloom, and add them with the gas used to the receipt of
+	/// the extrinsic.
+	fn record_receipt(extrinsic_index: Option<u32>, used_gas: u64, logs: Vec<Log>) {
+		if !logs.is_empty() {
+			BlockLogsBloom::<T>::mutate(|bloom| {
+				let bloom = bloom.get_or_insert([0u8; LOGS_BLOOM_SIZE]);
+				for log in &logs {
+					accrue_bloom(bloom, log.address.as_bytes());
+					for topic in &log.topics {
+						accrue_bloom(bloom, topic.as_bytes());
+					}
+				}
+			});
+		}
+
+		Self::mutate_receipt(extrinsic_index, |receipt| {
+			receipt.used_gas = receipt.used_gas.saturating_add(used_gas);
+			receipt.logs.extend(logs);
+		});
+	}
+
+	/// Only the last receipt is read and written if it's of the extrinsic, otherwise a new
+	/// receipt is added. The other receipts are left untouched.
+	fn mutate_receipt(extrinsic_index: Option<u32>, f: impl FnOnce(&mut Receipt)) {
+		let count = Self::block_receipt_count();
+		let last = count
+			.checked_sub(1)
+			.and_then(|index| BlockReceipts::<T>::get(index).map(|receipt| (index, receipt)));
+		let (index, mut receipt) = match last {
+			Some((index, receipt)) if receipt.extrinsic_index == extrinsic_index => (index, receipt),
+			_ => {
+				BlockReceiptCount::<T>::put(count.saturating_add(1));
+				(
+					count,
+					Receipt {
+						extrinsic_index,
+						used_gas: 0,
+						logs: Vec::new(),
+					},
+				)
+			}
+		};
+		f(&mut receipt);
+		BlockReceipts::<T>::insert(index, receipt);
+	}
+
 	#[transactional]
 	pub fn remove_contract(address: &EvmAddress, dest: &EvmAddress) -> Result<u32, DispatchError> {
 		let address_account = T::AddressMapping::get_account_id(&address);
@@ -815,7 +1398,9 @@ impl<T: Config> Pallet<T> {
 				}
 			});
 
-			AccountStorages::<T>::remove_prefix(address);
+			// the storage is removed in the background, the address can't be used until it's removed
+			T::IdleScheduler::schedule(EvmTask::remove_storage(*address))?;
+			EvictedContracts::<T>::remove(address);
 
 			let size = ContractStorageSizes::<T>::take(address);
 
@@ -863,6 +1448,32 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// The EVM execution of `call` signed by `who` with `nonce`, `None` if `call` doesn't execute
+	/// the EVM with the signed origin.
+	pub fn evm_transaction(who: &T::AccountId, nonce: Nonce, call: &Call<T>) -> Option<EvmTransaction<BalanceOf<T>>> {
+		let (to, input, value, gas_limit, storage_limit) = match call {
+			Call::call(target, input, value, gas_limit, storage_limit)
+			| Call::call_evm(target, input, value, gas_limit, storage_limit)
+			| Call::call_with_access_list(target, input, value, gas_limit, storage_limit, _) => {
+				(Some(*target), input, value, gas_limit, storage_limit)
+			}
+			Call::create(init, value, gas_limit, storage_limit)
+			| Call::create2(init, _, value, gas_limit, storage_limit) => (None, init, value, gas_limit, storage_limit),
+			_ => return None,
+		};
+
+		Some(EvmTransaction {
+			from: T::AddressMapping::get_evm_address(who)
+				.unwrap_or_else(|| T::AddressMapping::get_default_evm_address(who)),
+			to,
+			nonce,
+			value: *value,
+			gas_limit: *gas_limit,
+			storage_limit: *storage_limit,
+			input: input.clone(),
+		})
+	}
+
 	/// Get the account basic in EVM format.
 	pub fn account_basic(address: &EvmAddress) -> Account {
 		let account_id = T::AddressMapping::get_account_id(address);
@@ -876,13 +1487,16 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
-	/// Get code hash at given address.
+	/// Get code hash at given address. Evicted contracts have no code until resurrected.
 	pub fn code_hash_at_address(address: &EvmAddress) -> H256 {
 		if let Some(AccountInfo {
 			contract_info: Some(contract_info),
 			..
 		}) = Self::accounts(address)
 		{
+			if EvictedContracts::<T>::contains_key(address) {
+				return code_hash(&[]);
+			}
 			contract_info.code_hash
 		} else {
 			code_hash(&[])
@@ -898,6 +1512,7 @@ impl<T: Config> Pallet<T> {
 		if change == 0 {
 			return;
 		}
+		dry_run::record_storage_change(*address);
 		ContractStorageSizes::<T>::mutate(address, |val| {
 			if change > 0 {
 				*val = val.saturating_add(change as u32);
@@ -988,6 +1603,92 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// The publication status of a contract, `None` if the address is not a contract.
+	pub fn contract_status(address: &EvmAddress) -> Option<ContractStatus> {
+		Accounts::<T>::get(address)
+			.and_then(|account_info| account_info.contract_info)
+			.map(|contract_info| ContractStatus {
+				maintainer: contract_info.maintainer,
+				deployed: contract_info.deployed,
+			})
+	}
+
+	/// Execute `f` with its changes discarded, returns its result and the storage usage changes
+	/// of the contracts in bytes.
+	pub fn dry_run<R, F: FnOnce() -> R>(f: F) -> (R, Vec<(EvmAddress, i32)>) {
+		let mut contracts = BTreeSet::new();
+		let (result, new_sizes) = frame_support::storage::with_transaction(|| {
+			let result = dry_run::using(&mut contracts, f);
+			let new_sizes = contracts
+				.iter()
+				.map(|contract| (*contract, Self::contract_storage_sizes(contract)))
+				.collect::<Vec<_>>();
+			TransactionOutcome::Rollback((result, new_sizes))
+		});
+
+		let storage_changes = new_sizes
+			.into_iter()
+			.map(|(contract, new_size)| {
+				let change = (new_size as i64).saturating_sub(Self::contract_storage_sizes(contract) as i64);
+				(contract, change as i32)
+			})
+			.filter(|(_, change)| *change != 0)
+			.collect();
+
+		(result, storage_changes)
+	}
+
+	/// The verification metadata of the code with `code_hash`, `None` if not submitted.
+	pub fn contract_metadata(code_hash: &H256) -> Option<ContractMetadata> {
+		Self::contract_metadatas(code_hash)
+	}
+
+	/// Simulate the execution, and estimate the used gas and storage and the
+	/// fee, including the storage deposit that isn't covered by the gas.
+	pub fn estimate_fee(
+		from: EvmAddress,
+		to: Option<EvmAddress>,
+		data: Vec<u8>,
+		value: BalanceOf<T>,
+		gas_limit: u64,
+		storage_limit: u32,
+		fee_currency_id: Option<CurrencyId>,
+	) -> Result<EstimateFeeInfo<BalanceOf<T>>, DispatchError> {
+		let mut config = T::config().clone();
+		config.estimate = true;
+
+		let (exit_reason, output, used_gas, used_storage) = match to {
+			Some(to) => {
+				let info = Runner::<T>::call(from, from, to, data, value, gas_limit, storage_limit, &config)?;
+				(info.exit_reason, info.output, info.used_gas, info.used_storage)
+			}
+			None => {
+				let info = Runner::<T>::create(from, data, value, gas_limit, storage_limit, &config)?;
+				(info.exit_reason, info.output, info.used_gas, info.used_storage)
+			}
+		};
+
+		let gas_fee =
+			T::ChargeTransactionPayment::weight_to_fee(T::GasToWeight::convert(used_gas.unique_saturated_into()));
+		let storage_deposit = T::StorageDepositPerByte::get().saturating_mul((used_storage.max(0) as u32).into());
+		let fee = gas_fee.saturating_add(storage_deposit);
+		let total_fee = match fee_currency_id {
+			Some(currency_id) => T::ChargeTransactionPayment::fee_in_currency(currency_id, fee),
+			None => Some(fee),
+		};
+
+		Ok(EstimateFeeInfo {
+			exit_reason,
+			output,
+			used_gas,
+			used_storage,
+			gas_fee,
+			storage_deposit,
+			total_fee,
+			fee_currency_id,
+		})
+	}
+
 	/// Mark contract as deployed
 	///
 	/// If maintainer is provider then it will check maintainer
@@ -1084,12 +1785,240 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Bind the verification metadata to the code of `contract`. Returns the code hash.
+	fn do_submit_contract_metadata(
+		maintainer: EvmAddress,
+		contract: EvmAddress,
+		source_cid: Vec<u8>,
+		compiler_version: Vec<u8>,
+		compiler_settings_hash: H256,
+	) -> Result<H256, DispatchError> {
+		let contract_info = Self::accounts(contract)
+			.and_then(|account_info| account_info.contract_info)
+			.ok_or(Error::<T>::ContractNotFound)?;
+		ensure!(contract_info.maintainer == maintainer, Error::<T>::NoPermission);
+		ensure!(
+			source_cid.len() <= MAX_SOURCE_CID_LENGTH && compiler_version.len() <= MAX_COMPILER_VERSION_LENGTH,
+			Error::<T>::InvalidContractMetadata
+		);
+
+		let code_hash = contract_info.code_hash;
+		ContractMetadatas::<T>::try_mutate(code_hash, |maybe_metadata| -> DispatchResult {
+			ensure!(maybe_metadata.is_none(), Error::<T>::ContractMetadataAlreadySubmitted);
+			*maybe_metadata = Some(ContractMetadata {
+				submitter: maintainer,
+				source_cid,
+				compiler_version,
+				compiler_settings_hash,
+			});
+			Ok(())
+		})?;
+
+		Ok(code_hash)
+	}
+
+	/// Whether the reserved storage deposit of a contract is less than required by its storage
+	/// usage.
+	fn is_underfunded(contract: &EvmAddress) -> bool {
+		let contract_account = T::AddressMapping::get_account_id(contract);
+		let required = T::StorageDepositPerByte::get().saturating_mul(Self::contract_storage_sizes(contract).into());
+		T::Currency::reserved_balance_named(&RESERVE_ID_STORAGE_DEPOSIT, &contract_account) < required
+	}
+
+	/// Notify the eviction of an underfunded contract, unless a notice is in effect. Returns the
+	/// block number from which the contract can be evicted.
+	fn do_notify_eviction(contract: EvmAddress) -> Result<T::BlockNumber, DispatchError> {
+		Self::accounts(contract)
+			.and_then(|account_info| account_info.contract_info)
+			.ok_or(Error::<T>::ContractNotFound)?;
+		ensure!(
+			!EvictedContracts::<T>::contains_key(contract),
+			Error::<T>::ContractEvicted
+		);
+		ensure!(Self::is_underfunded(&contract), Error::<T>::StorageDepositSufficient);
+
+		let now = frame_system::Pallet::<T>::block_number();
+		let period = T::EvictionNoticePeriod::get();
+		if let Some(evictable_at) = Self::eviction_notices(contract) {
+			ensure!(
+				now >= evictable_at.saturating_add(period),
+				Error::<T>::EvictionAlreadyNotified
+			);
+		}
+
+		let evictable_at = now.saturating_add(period);
+		EvictionNotices::<T>::insert(contract, evictable_at);
+		Ok(evictable_at)
+	}
+
+	/// Transfer the storage deposit a contract lacks from `who` and reserve it. Returns the
+	/// amount topped up.
+	fn do_top_up_storage_deposit(who: &T::AccountId, contract: EvmAddress) -> Result<BalanceOf<T>, DispatchError> {
+		let contract_account = T::AddressMapping::get_account_id(&contract);
+		let required = T::StorageDepositPerByte::get().saturating_mul(Self::contract_storage_sizes(contract).into());
+		let reserved = T::Currency::reserved_balance_named(&RESERVE_ID_STORAGE_DEPOSIT, &contract_account);
+		let shortfall = required.saturating_sub(reserved);
+		if !shortfall.is_zero() {
+			T::Currency::transfer(who, &contract_account, shortfall, ExistenceRequirement::AllowDeath)?;
+			T::Currency::reserve_named(&RESERVE_ID_STORAGE_DEPOSIT, &contract_account, shortfall)?;
+		}
+		Ok(shortfall)
+	}
+
+	/// Evict a contract whose reserved storage deposit is less than required by its storage
+	/// usage. Up to `limit` storage entries are removed and a commitment to them is kept, the
+	/// remaining deposit stays reserved for the resurrection. The eviction notice and the deposit
+	/// are only checked on the first call, the following calls continue the eviction. Returns the
+	/// number of removed entries and whether the eviction is completed.
+	fn do_evict_contract(contract: EvmAddress, limit: u32) -> Result<(u32, bool), DispatchError> {
+		Self::accounts(contract)
+			.and_then(|account_info| account_info.contract_info)
+			.ok_or(Error::<T>::ContractNotFound)?;
+
+		let mut evicted_info = match Self::evicted_contracts(contract) {
+			Some(evicted_info) => {
+				ensure!(!evicted_info.completed, Error::<T>::ContractEvicted);
+				evicted_info
+			}
+			None => {
+				let now = frame_system::Pallet::<T>::block_number();
+				let evictable_at = EvictionNotices::<T>::take(contract).ok_or(Error::<T>::EvictionNotNotified)?;
+				ensure!(
+					now >= evictable_at && now < evictable_at.saturating_add(T::EvictionNoticePeriod::get()),
+					Error::<T>::EvictionNotNotified
+				);
+				ensure!(Self::is_underfunded(&contract), Error::<T>::StorageDepositSufficient);
+				EvictedContractInfo {
+					storage_hash: H256::zero(),
+					storage_count: 0,
+					completed: false,
+				}
+			}
+		};
+
+		let storages = AccountStorages::<T>::iter_prefix(contract)
+			.take(limit as usize)
+			.collect::<Vec<_>>();
+		let removed_count = storages.len() as u32;
+		for (index, value) in storages {
+			AccountStorages::<T>::remove(contract, index);
+			evicted_info.storage_hash = chain_storage_hash(evicted_info.storage_hash, index, value);
+		}
+		Self::update_contract_storage_size(&contract, -(removed_count.saturating_mul(handler::STORAGE_SIZE) as i32));
+
+		evicted_info.storage_count = evicted_info.storage_count.saturating_add(removed_count);
+		evicted_info.completed = AccountStorages::<T>::iter_prefix(contract).next().is_none();
+		EvictedContracts::<T>::insert(contract, evicted_info);
+
+		Ok((removed_count, evicted_info.completed))
+	}
+
+	/// Resurrect the last removed `storages` of an evicted contract, which are chained to
+	/// `prefix_hash` to verify them against the commitment, `who` tops up the storage deposit to
+	/// cover the storage usage of the contract. Returns whether the resurrection is completed.
+	fn do_resurrect_contract(
+		who: &T::AccountId,
+		contract: EvmAddress,
+		storages: Vec<(H256, H256)>,
+		prefix_hash: H256,
+	) -> Result<bool, DispatchError> {
+		let mut evicted_info = Self::evicted_contracts(contract).ok_or(Error::<T>::ContractNotEvicted)?;
+		ensure!(evicted_info.completed, Error::<T>::EvictionNotCompleted);
+
+		let restored_count = storages.len() as u32;
+		let storage_count = evicted_info
+			.storage_count
+			.checked_sub(restored_count)
+			.ok_or(Error::<T>::InvalidEvictedStorage)?;
+		let storage_hash = storages.iter().fold(prefix_hash, |hash, (index, value)| {
+			chain_storage_hash(hash, *index, *value)
+		});
+		// the hash of no entries is zero, so the first removed entries are restored last
+		ensure!(
+			storage_hash == evicted_info.storage_hash && (storage_count != 0 || prefix_hash.is_zero()),
+			Error::<T>::InvalidEvictedStorage
+		);
+
+		for (index, value) in storages {
+			AccountStorages::<T>::insert(contract, index, value);
+		}
+		Self::update_contract_storage_size(&contract, restored_count.saturating_mul(handler::STORAGE_SIZE) as i32);
+
+		let completed = storage_count == 0;
+		if completed {
+			EvictedContracts::<T>::remove(contract);
+		} else {
+			evicted_info.storage_hash = prefix_hash;
+			evicted_info.storage_count = storage_count;
+			EvictedContracts::<T>::insert(contract, evicted_info);
+		}
+
+		Self::do_top_up_storage_deposit(who, contract)?;
+
+		Ok(completed)
+	}
+
+	/// Call `contract` from `who`, the changes are reverted unless the execution succeeds.
+	fn do_call_evm(
+		who: &T::AccountId,
+		contract: EvmAddress,
+		input: Vec<u8>,
+		value: BalanceOf<T>,
+		gas_limit: u64,
+		storage_limit: u32,
+	) -> Result<CallInfo, DispatchError> {
+		let source = T::AddressMapping::get_or_create_evm_address(who);
+
+		frame_support::storage::with_transaction(|| {
+			let result = Runner::<T>::call(
+				source,
+				source,
+				contract,
+				input,
+				value,
+				gas_limit,
+				storage_limit,
+				T::config(),
+			)
+			.and_then(|info| Self::handle_exit_reason(&info.exit_reason).map(|_| info));
+
+			match result {
+				Ok(info) => TransactionOutcome::Commit(Ok(info)),
+				Err(e) => TransactionOutcome::Rollback(Err(e)),
+			}
+		})
+	}
+
+	fn handle_exit_reason(exit_reason: &ExitReason) -> DispatchResult {
+		match exit_reason {
+			ExitReason::Succeed(ExitSucceed::Returned) => Ok(()),
+			ExitReason::Succeed(ExitSucceed::Stopped) => Ok(()),
+			ExitReason::Succeed(_) => Err(Error::<T>::ExecutionFail.into()),
+			ExitReason::Revert(_) => Err(Error::<T>::ExecutionRevert.into()),
+			ExitReason::Fatal(_) => Err(Error::<T>::ExecutionFatal.into()),
+			ExitReason::Error(_) => Err(Error::<T>::ExecutionError.into()),
+		}
+	}
+
 	fn ensure_root_or_signed(o: T::Origin) -> Result<Either<(), T::AccountId>, BadOrigin> {
 		EnsureOneOf::<T::AccountId, EnsureRoot<T::AccountId>, EnsureSigned<T::AccountId>>::try_origin(o)
 			.map_or(Err(BadOrigin), Ok)
 	}
 }
 
+impl<T: Config> EVMCall<T::AccountId, BalanceOf<T>> for Pallet<T> {
+	fn call_evm(
+		who: &T::AccountId,
+		contract: EvmAddress,
+		input: Vec<u8>,
+		value: BalanceOf<T>,
+		gas_limit: u64,
+		storage_limit: u32,
+	) -> Result<Vec<u8>, DispatchError> {
+		Self::do_call_evm(who, contract, input, value, gas_limit, storage_limit).map(|info| info.output)
+	}
+}
+
 impl<T: Config> EVMTrait<T::AccountId> for Pallet<T> {
 	type Balance = BalanceOf<T>;
 	fn execute(
@@ -1197,6 +2126,22 @@ pub fn code_hash(code: &[u8]) -> H256 {
 	H256::from_slice(Keccak256::digest(code).as_slice())
 }
 
+/// Chain the storage entry at `index` to the commitment `hash` to the storage entries removed
+/// before it from an evicted contract.
+fn chain_storage_hash(hash: H256, index: H256, value: H256) -> H256 {
+	H256::from_slice(Keccak256::digest(&(hash, index, value).encode()).as_slice())
+}
+
+/// Set the 3 bits of `input` in the 2048 bits `bloom`, as specified by the
+/// Ethereum Yellow Paper.
+pub fn accrue_bloom(bloom: &mut LogsBloom, input: &[u8]) {
+	let hash = Keccak256::digest(input);
+	for i in [0usize, 2, 4].iter() {
+		let bit = (((hash[*i] as usize) << 8) | hash[*i + 1] as usize) & (LOGS_BLOOM_SIZE * 8 - 1);
+		bloom[LOGS_BLOOM_SIZE - 1 - bit / 8] |= 1 << (bit % 8);
+	}
+}
+
 #[derive(Encode, Decode, Clone, Eq, PartialEq)]
 pub struct SetEvmOrigin<T: Config + Send + Sync>(PhantomData<T>);
 
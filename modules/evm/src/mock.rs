@@ -30,7 +30,7 @@ use sp_runtime::{
 	traits::{BlakeTwo256, IdentityLookup},
 	AccountId32,
 };
-use std::{collections::BTreeMap, str::FromStr};
+use std::{cell::RefCell, collections::BTreeMap, str::FromStr};
 use support::mocks::MockAddressMapping;
 
 mod evm_mod {
@@ -133,6 +133,7 @@ impl Convert<u64, u64> for GasToWeight {
 
 parameter_types! {
 	pub NetworkContractSource: H160 = alice();
+	pub static AccessListGasMetering: bool = false;
 }
 
 ord_parameter_types! {
@@ -143,8 +144,25 @@ ord_parameter_types! {
 	pub const StorageDepositPerByte: u64 = 10;
 	pub const DeveloperDeposit: u64 = 1000;
 	pub const DeploymentFee: u64 = 200;
+	pub const EvictionNoticePeriod: u64 = 10;
 	pub const MaxCodeSize: u32 = 1000;
 	pub const ChainId: u64 = 1;
+	pub const BlockGasTarget: u64 = 1_000_000;
+}
+
+thread_local! {
+	static FILTERED_CONTRACTS: RefCell<Vec<H160>> = RefCell::new(Vec::new());
+}
+
+pub fn mock_filtered_contract(address: H160) {
+	FILTERED_CONTRACTS.with(|v| v.borrow_mut().push(address));
+}
+
+pub struct MockContractFilter;
+impl Filter<H160> for MockContractFilter {
+	fn filter(address: &H160) -> bool {
+		FILTERED_CONTRACTS.with(|v| !v.borrow().contains(address))
+	}
 }
 
 impl Config for Test {
@@ -159,6 +177,8 @@ impl Config for Test {
 	type Precompiles = ();
 	type ChainId = ChainId;
 	type GasToWeight = GasToWeight;
+	type BlockGasTarget = BlockGasTarget;
+	type AccessListGasMetering = AccessListGasMetering;
 	type ChargeTransactionPayment = ();
 
 	type NetworkContractOrigin = EnsureSignedBy<NetworkContractAccount, AccountId32>;
@@ -167,6 +187,9 @@ impl Config for Test {
 	type DeploymentFee = DeploymentFee;
 	type TreasuryAccount = TreasuryAccount;
 	type FreeDeploymentOrigin = EnsureSignedBy<CouncilAccount, AccountId32>;
+	type IdleScheduler = ();
+	type ContractFilter = MockContractFilter;
+	type EvictionNoticePeriod = EvictionNoticePeriod;
 
 	type WeightInfo = ();
 }
@@ -211,6 +234,8 @@ pub fn charlie() -> H160 {
 }
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
+	FILTERED_CONTRACTS.with(|v| v.borrow_mut().clear());
+
 	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
 
 	let mut accounts = BTreeMap::new();
@@ -0,0 +1,140 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Warm/cold state access gas accounting of EIP-2929, with the access lists of EIP-2930.
+
+use evm::{Config as EvmConfig, ExitError, Opcode, Stack};
+use primitive_types::{H160, H256};
+use primitives::{evm::AccessListItem, SYSTEM_CONTRACT_ADDRESS_PREFIX};
+use sp_std::{collections::btree_set::BTreeSet, rc::Rc};
+
+pub const WARM_STORAGE_READ_COST: u64 = 100;
+pub const COLD_SLOAD_COST: u64 = 2100;
+pub const COLD_ACCOUNT_ACCESS_COST: u64 = 2600;
+pub const ACCESS_LIST_ADDRESS_COST: u64 = 2400;
+pub const ACCESS_LIST_STORAGE_KEY_COST: u64 = 1900;
+
+/// The addresses and storage slots accessed by the transaction, which are warm for further
+/// accesses. It's copied into sub calls and only merged back if the sub call succeeds.
+#[derive(Clone)]
+pub struct AccessedState {
+	/// The EVM config with the warm access costs. The surcharges of cold accesses are charged by
+	/// `cold_access_cost`.
+	config: Rc<EvmConfig>,
+	addresses: BTreeSet<H160>,
+	storages: BTreeSet<(H160, H256)>,
+}
+
+impl AccessedState {
+	/// The state at the start of a transaction, where the `origin`, the `target` and the
+	/// entries of `access_list` are warm.
+	pub fn new(config: &EvmConfig, origin: H160, target: H160, access_list: &[AccessListItem]) -> Self {
+		let mut config = config.clone();
+		config.gas_sload = WARM_STORAGE_READ_COST;
+		config.gas_sstore_reset = config.gas_sstore_reset.saturating_sub(COLD_SLOAD_COST);
+		config.gas_balance = WARM_STORAGE_READ_COST;
+		config.gas_ext_code = WARM_STORAGE_READ_COST;
+		config.gas_ext_code_hash = WARM_STORAGE_READ_COST;
+		config.gas_call = WARM_STORAGE_READ_COST;
+
+		let mut state = Self {
+			config: Rc::new(config),
+			addresses: BTreeSet::new(),
+			storages: BTreeSet::new(),
+		};
+		state.access_address(origin);
+		state.access_address(target);
+		for item in access_list {
+			state.access_address(item.address);
+			for key in &item.storage_keys {
+				state.access_storage(item.address, *key);
+			}
+		}
+		state
+	}
+
+	/// The intrinsic gas of `access_list`.
+	pub fn access_list_cost(access_list: &[AccessListItem]) -> u64 {
+		access_list.iter().fold(0u64, |cost, item| {
+			cost.saturating_add(ACCESS_LIST_ADDRESS_COST)
+				.saturating_add(ACCESS_LIST_STORAGE_KEY_COST.saturating_mul(item.storage_keys.len() as u64))
+		})
+	}
+
+	/// The EVM config with the warm access costs.
+	pub fn config(&self) -> &EvmConfig {
+		&self.config
+	}
+
+	/// Mark `address` as warm, return true if it was cold. The system contracts, including the
+	/// precompiles, are always warm.
+	pub fn access_address(&mut self, address: H160) -> bool {
+		if address.as_bytes().starts_with(&SYSTEM_CONTRACT_ADDRESS_PREFIX) {
+			return false;
+		}
+		self.addresses.insert(address)
+	}
+
+	/// Mark the storage slot `index` of `address` as warm, return true if it was cold.
+	pub fn access_storage(&mut self, address: H160, index: H256) -> bool {
+		self.storages.insert((address, index))
+	}
+
+	/// The surcharge of `opcode` executed in the context of `address`, if it accesses cold state.
+	pub fn cold_access_cost(&mut self, address: H160, opcode: Opcode, stack: &Stack) -> Result<u64, ExitError> {
+		let cost = match opcode {
+			Opcode::SLOAD => {
+				if self.access_storage(address, stack.peek(0)?) {
+					COLD_SLOAD_COST - WARM_STORAGE_READ_COST
+				} else {
+					0
+				}
+			}
+			Opcode::SSTORE => {
+				if self.access_storage(address, stack.peek(0)?) {
+					COLD_SLOAD_COST
+				} else {
+					0
+				}
+			}
+			Opcode::BALANCE | Opcode::EXTCODESIZE | Opcode::EXTCODECOPY | Opcode::EXTCODEHASH => {
+				if self.access_address(stack.peek(0)?.into()) {
+					COLD_ACCOUNT_ACCESS_COST - WARM_STORAGE_READ_COST
+				} else {
+					0
+				}
+			}
+			Opcode::CALL | Opcode::CALLCODE | Opcode::DELEGATECALL | Opcode::STATICCALL => {
+				if self.access_address(stack.peek(1)?.into()) {
+					COLD_ACCOUNT_ACCESS_COST - WARM_STORAGE_READ_COST
+				} else {
+					0
+				}
+			}
+			Opcode::SUICIDE => {
+				if self.access_address(stack.peek(0)?.into()) {
+					COLD_ACCOUNT_ACCESS_COST
+				} else {
+					0
+				}
+			}
+			_ => 0,
+		};
+		Ok(cost)
+	}
+}
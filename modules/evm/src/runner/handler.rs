@@ -20,19 +20,25 @@
 
 use crate::{
 	precompiles::Precompiles,
-	runner::storage_meter::{StorageMeter, StorageMeterHandler},
-	AccountInfo, AccountStorages, Accounts, AddressMapping, Codes, Config, ContractInfo, Error, Event, Log, Pallet,
-	Vicinity, RESERVE_ID_DEVELOPER_DEPOSIT, RESERVE_ID_STORAGE_DEPOSIT,
+	runner::{
+		access_list::AccessedState,
+		storage_meter::{StorageMeter, StorageMeterHandler},
+		tracing,
+	},
+	AccountInfo, AccountStorages, Accounts, AddressMapping, Codes, Config, ContractInfo, Error, EvictedContracts,
+	Pallet, Vicinity, RESERVE_ID_DEVELOPER_DEPOSIT, RESERVE_ID_STORAGE_DEPOSIT,
 };
 use evm::{Capture, Context, CreateScheme, ExitError, ExitReason, Opcode, Runtime, Stack, Transfer};
 use evm_gasometer::{self as gasometer, Gasometer};
 use evm_runtime::{Config as EvmRuntimeConfig, Handler as HandlerT};
 use frame_support::{
 	log,
-	traits::{BalanceStatus, Currency, ExistenceRequirement, Get, NamedReservableCurrency},
+	traits::{BalanceStatus, Currency, ExistenceRequirement, Filter, Get, NamedReservableCurrency},
 };
 use primitive_types::{H160, H256, U256};
-use primitives::{H160_PREFIX_DEXSHARE, H160_PREFIX_TOKEN, PREDEPLOY_ADDRESS_START, SYSTEM_CONTRACT_ADDRESS_PREFIX};
+use primitives::{
+	evm::CallType, H160_PREFIX_DEXSHARE, H160_PREFIX_TOKEN, PREDEPLOY_ADDRESS_START, SYSTEM_CONTRACT_ADDRESS_PREFIX,
+};
 use sha3::{Digest, Keccak256};
 use sp_runtime::{
 	traits::{One, Saturating, UniqueSaturatedInto, Zero},
@@ -49,6 +55,8 @@ pub struct Handler<'vicinity, 'config, 'meter, T: Config> {
 	pub gasometer: Gasometer<'config>,
 	pub storage_meter: StorageMeter<'meter>,
 	pub is_static: bool,
+	/// The warm state of the transaction, `None` if the cold state accesses are not surcharged.
+	pub accessed: Option<AccessedState>,
 	_marker: PhantomData<T>,
 }
 
@@ -63,6 +71,7 @@ impl<'vicinity, 'config, 'meter, T: Config> Handler<'vicinity, 'config, 'meter,
 		storage_meter: StorageMeter<'meter>,
 		is_static: bool,
 		config: &'config EvmRuntimeConfig,
+		accessed: Option<AccessedState>,
 	) -> Self {
 		Handler::<'vicinity, 'config, '_, T> {
 			vicinity,
@@ -70,6 +79,7 @@ impl<'vicinity, 'config, 'meter, T: Config> Handler<'vicinity, 'config, 'meter,
 			is_static,
 			gasometer: Gasometer::new(gas_limit, config),
 			storage_meter,
+			accessed,
 			_marker: PhantomData,
 		}
 	}
@@ -81,6 +91,7 @@ impl<'vicinity, 'config, 'meter, T: Config> Handler<'vicinity, 'config, 'meter,
 		contract: H160,
 		is_static: bool,
 		config: &'config EvmRuntimeConfig,
+		accessed: Option<AccessedState>,
 		f: F,
 	) -> Result<R, DispatchError> {
 		frame_support::storage::with_transaction(|| {
@@ -90,7 +101,7 @@ impl<'vicinity, 'config, 'meter, T: Config> Handler<'vicinity, 'config, 'meter,
 				Err(e) => return TransactionOutcome::Rollback(Err(e)),
 			};
 
-			let mut substate = Handler::new(vicinity, gas_limit, storage_meter, is_static, config);
+			let mut substate = Handler::new(vicinity, gas_limit, storage_meter, is_static, config, accessed);
 
 			match f(&mut substate) {
 				TransactionOutcome::Commit(r) => match substate.storage_meter.finish() {
@@ -121,11 +132,22 @@ impl<'vicinity, 'config, 'meter, T: Config> Handler<'vicinity, 'config, 'meter,
 				Err(e) => return TransactionOutcome::Rollback(Err(e)),
 			};
 
-			let mut substate = Handler::new(vicinity, gas_limit, storage_meter, is_static, config);
+			let mut substate = Handler::new(
+				vicinity,
+				gas_limit,
+				storage_meter,
+				is_static,
+				config,
+				self.accessed.clone(),
+			);
 
 			match f(&mut substate, &mut self.gasometer) {
 				TransactionOutcome::Commit(r) => match substate.storage_meter.finish() {
-					Ok(_) => TransactionOutcome::Commit(Ok(r)),
+					Ok(_) => {
+						// the state accessed by the sub call stays warm
+						self.accessed = substate.accessed;
+						TransactionOutcome::Commit(Ok(r))
+					}
 					Err(e) => TransactionOutcome::Rollback(Err(e)),
 				},
 				TransactionOutcome::Rollback(e) => TransactionOutcome::Rollback(Ok(e)),
@@ -162,9 +184,13 @@ impl<'vicinity, 'config, 'meter, T: Config> Handler<'vicinity, 'config, 'meter,
 
 		let mut runtime = Runtime::new(Rc::new(code), Rc::new(input), context, self.config);
 
-		let reason = match runtime.run(self) {
-			Capture::Exit(s) => s,
-			Capture::Trap(_) => unreachable!("Trap is Infallible"),
+		let reason = if tracing::is_tracing_opcodes() {
+			self.run_with_opcode_tracing(&mut runtime)
+		} else {
+			match runtime.run(self) {
+				Capture::Exit(s) => s,
+				Capture::Trap(_) => unreachable!("Trap is Infallible"),
+			}
 		};
 
 		match reason {
@@ -178,6 +204,34 @@ impl<'vicinity, 'config, 'meter, T: Config> Handler<'vicinity, 'config, 'meter,
 		}
 	}
 
+	/// Execute `runtime` step by step and record every executed opcode to the tracer.
+	fn run_with_opcode_tracing(&mut self, runtime: &mut Runtime) -> ExitReason {
+		loop {
+			let gas = self.gasometer.gas();
+			let machine = runtime.machine();
+			let index = match (machine.position(), machine.inspect()) {
+				(Ok(pc), Some((opcode, stack))) => {
+					tracing::with(|tracer| tracer.step(*pc, opcode.0, gas, stack.data(), machine.memory().data()))
+						.flatten()
+				}
+				_ => None,
+			};
+
+			let result = runtime.step(self);
+
+			if let Some(index) = index {
+				let gas_cost = gas.saturating_sub(self.gasometer.gas());
+				tracing::with(|tracer| tracer.set_gas_cost(index, gas_cost));
+			}
+
+			match result {
+				Ok(()) => {}
+				Err(Capture::Exit(s)) => return s,
+				Err(Capture::Trap(_)) => unreachable!("Trap is Infallible"),
+			}
+		}
+	}
+
 	fn transfer(transfer: Transfer) -> Result<(), ExitError> {
 		let source = T::AddressMapping::get_account_id(&transfer.source);
 		let target = T::AddressMapping::get_account_id(&transfer.target);
@@ -232,7 +286,11 @@ impl<'vicinity, 'config, 'meter, T: Config> Handler<'vicinity, 'config, 'meter,
 			CreateScheme::Fixed(naddress) => naddress,
 		};
 
-		if address.as_bytes().starts_with(&SYSTEM_CONTRACT_ADDRESS_PREFIX) {
+		// reserved for system contracts, or a removed contract whose storage is still being removed
+		// in the background
+		if address.as_bytes().starts_with(&SYSTEM_CONTRACT_ADDRESS_PREFIX)
+			|| AccountStorages::<T>::iter_prefix(address).next().is_some()
+		{
 			Err(ExitError::Other(
 				Into::<&str>::into(Error::<T>::ConflictContractAddress).into(),
 			))
@@ -249,7 +307,9 @@ impl<'vicinity, 'config, 'meter, T: Config> Handler<'vicinity, 'config, 'meter,
 			..
 		}) = Accounts::<T>::get(address)
 		{
-			deployed || maintainer == *caller || Self::is_developer_or_contract(caller)
+			// evicted contracts can't be called until resurrected
+			!EvictedContracts::<T>::contains_key(address)
+				&& (deployed || maintainer == *caller || Self::is_developer_or_contract(caller))
 		} else {
 			// contract non exist, we don't override defualt evm behaviour
 			true
@@ -457,7 +517,7 @@ impl<'vicinity, 'config, 'meter, T: Config> HandlerT for Handler<'vicinity, 'con
 	}
 
 	fn log(&mut self, address: H160, topics: Vec<H256>, data: Vec<u8>) -> Result<(), ExitError> {
-		Pallet::<T>::deposit_event(Event::<T>::Log(Log { address, topics, data }));
+		Pallet::<T>::deposit_log(address, topics, data);
 
 		Ok(())
 	}
@@ -512,10 +572,16 @@ impl<'vicinity, 'config, 'meter, T: Config> HandlerT for Handler<'vicinity, 'con
 			maybe_address.unwrap()
 		};
 		Self::inc_nonce(caller);
+		if let Some(accessed) = self.accessed.as_mut() {
+			accessed.access_address(address);
+		}
 
 		let origin = &self.vicinity.origin;
 
-		self.run_sub_transaction(
+		let gas_before = self.gasometer.gas();
+		tracing::with(|tracer| tracer.enter(CallType::Create, caller, address, value, target_gas, init_code.clone()));
+
+		let result = self.run_sub_transaction(
 			self.vicinity,
 			target_gas,
 			address,
@@ -558,14 +624,21 @@ impl<'vicinity, 'config, 'meter, T: Config> HandlerT for Handler<'vicinity, 'con
 					}
 				}
 			},
-		)
-		.unwrap_or_else(|x| {
+		);
+		let result = result.unwrap_or_else(|x| {
 			Capture::Exit((
 				ExitReason::Error(ExitError::Other(Into::<&'static str>::into(x).into())),
 				None,
 				Vec::new(),
 			))
-		})
+		});
+
+		if let Capture::Exit((reason, _, out)) = &result {
+			let gas_used = gas_before.saturating_sub(self.gasometer.gas());
+			tracing::with(|tracer| tracer.exit(reason.clone(), gas_used, out.clone()));
+		}
+
+		result
 	}
 
 	fn call(
@@ -593,6 +666,14 @@ impl<'vicinity, 'config, 'meter, T: Config> HandlerT for Handler<'vicinity, 'con
 			return Capture::Exit((ExitError::OutOfGas.into(), Vec::new()));
 		}
 
+		// the filtered contracts can't run in any call frame, neither their code nor in their context
+		if !T::ContractFilter::filter(&code_address) || !T::ContractFilter::filter(&context.address) {
+			return Capture::Exit((
+				ExitError::Other(Into::<&str>::into(Error::<T>::ContractFiltered).into()).into(),
+				Vec::new(),
+			));
+		}
+
 		let mut after_gas = self.gasometer.gas();
 		if self.config.call_l64_after_gas {
 			after_gas = l64(after_gas);
@@ -608,7 +689,31 @@ impl<'vicinity, 'config, 'meter, T: Config> HandlerT for Handler<'vicinity, 'con
 
 		let code = self.code(code_address);
 
-		self.run_sub_transaction(
+		let gas_before = self.gasometer.gas();
+		tracing::with(|tracer| {
+			let call_type = if is_static {
+				CallType::StaticCall
+			} else if context.address == code_address {
+				CallType::Call
+			} else if transfer.is_some() {
+				CallType::CallCode
+			} else {
+				CallType::DelegateCall
+			};
+			let value = transfer
+				.as_ref()
+				.map_or(context.apparent_value, |transfer| transfer.value);
+			tracer.enter(
+				call_type,
+				context.caller,
+				code_address,
+				value,
+				target_gas,
+				input.clone(),
+			)
+		});
+
+		let result = self.run_sub_transaction(
 			self.vicinity,
 			target_gas,
 			context.address,
@@ -670,23 +775,36 @@ impl<'vicinity, 'config, 'meter, T: Config> HandlerT for Handler<'vicinity, 'con
 					}
 				}
 			},
-		)
-		.unwrap_or_else(|x| {
+		);
+		let result = result.unwrap_or_else(|x| {
 			Capture::Exit((
 				ExitReason::Error(ExitError::Other(Into::<&'static str>::into(x).into())),
 				Vec::new(),
 			))
-		})
+		});
+
+		if let Capture::Exit((reason, out)) = &result {
+			let gas_used = gas_before.saturating_sub(self.gasometer.gas());
+			tracing::with(|tracer| tracer.exit(reason.clone(), gas_used, out.clone()));
+		}
+
+		result
 	}
 
 	fn pre_validate(&mut self, context: &Context, opcode: Opcode, stack: &Stack) -> Result<(), ExitError> {
 		if let Some(cost) = gasometer::static_opcode_cost(opcode) {
 			self.gasometer.record_cost(cost)?;
 		} else {
+			let config = self.accessed.as_ref().map_or(self.config, |accessed| accessed.config());
 			let (gas_cost, memory_cost) =
-				gasometer::dynamic_opcode_cost(context.address, opcode, stack, self.is_static, &self.config, self)?;
+				gasometer::dynamic_opcode_cost(context.address, opcode, stack, self.is_static, config, self)?;
 
 			self.gasometer.record_dynamic_cost(gas_cost, memory_cost)?;
+
+			if let Some(accessed) = self.accessed.as_mut() {
+				let cold_access_cost = accessed.cold_access_cost(context.address, opcode, stack)?;
+				self.gasometer.record_cost(cold_access_cost)?;
+			}
 		}
 		Ok(())
 	}
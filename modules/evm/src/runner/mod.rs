@@ -16,19 +16,23 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+pub mod access_list;
 pub mod handler;
 pub mod storage_meter;
+pub mod tracing;
 
 use crate::{AddressMapping, BalanceOf, CallInfo, Config, CreateInfo, Error, Pallet, Vicinity};
+use access_list::AccessedState;
 use evm::{CreateScheme, ExitError, ExitReason};
 use evm_gasometer::{self as gasometer};
 use evm_runtime::Handler as HandlerT;
 use frame_support::{
 	log,
-	traits::{Currency, ExistenceRequirement, Get},
+	traits::{Currency, ExistenceRequirement, Filter, Get},
 };
 use handler::Handler;
 use primitive_types::{H160, H256, U256};
+use primitives::evm::{AccessListItem, CallType};
 use sha3::{Digest, Keccak256};
 use sp_runtime::{traits::Zero, DispatchError, DispatchResult, SaturatedConversion, TransactionOutcome};
 use sp_std::{marker::PhantomData, vec::Vec};
@@ -47,6 +51,7 @@ impl<T: Config> Runner<T> {
 		storage_limit: u32,
 		assigned_address: Option<H160>,
 		salt: Option<H256>,
+		access_list: Vec<AccessListItem>,
 		tag: &'static str,
 		config: &evm::Config,
 	) -> Result<CreateInfo, DispatchError> {
@@ -82,6 +87,11 @@ impl<T: Config> Runner<T> {
 
 		Handler::<T>::inc_nonce(source);
 
+		let accessed = Self::accessed_state(source, address, &access_list, config);
+		let access_list_cost = accessed
+			.as_ref()
+			.map_or(0, |_| AccessedState::access_list_cost(&access_list));
+
 		Handler::<T>::run_transaction(
 			&vicinity,
 			gas_limit,
@@ -89,23 +99,25 @@ impl<T: Config> Runner<T> {
 			address,
 			false,
 			config,
+			accessed,
 			|substate| {
 				if let Err(e) = Self::transfer(source, address, value) {
 					return TransactionOutcome::Rollback(Err(e));
 				}
 
 				let transaction_cost = gasometer::call_transaction_cost(&init);
-				if substate.gasometer.record_transaction(transaction_cost).is_err() {
+				if substate.gasometer.record_transaction(transaction_cost).is_err()
+					|| substate.gasometer.record_cost(access_list_cost).is_err()
+				{
 					return TransactionOutcome::Rollback(Err(DispatchError::Other("OutOfGas")));
 				}
 
-				let (reason, out) = substate.execute(
-					source,
-					address,
-					U256::from(value.saturated_into::<u128>()),
-					init,
-					Vec::new(),
-				);
+				let value = U256::from(value.saturated_into::<u128>());
+				tracing::with(|tracer| tracer.enter(CallType::Create, source, address, value, gas_limit, init.clone()));
+
+				let (reason, out) = substate.execute(source, address, value, init, Vec::new());
+
+				tracing::with(|tracer| tracer.exit(reason.clone(), substate.used_gas(), out.clone()));
 
 				let mut create_info = CreateInfo {
 					exit_reason: reason.clone(),
@@ -157,6 +169,20 @@ impl<T: Config> Runner<T> {
 		)?
 	}
 
+	/// The initial warm state of the transaction, if the cold state accesses are surcharged.
+	fn accessed_state(
+		origin: H160,
+		target: H160,
+		access_list: &[AccessListItem],
+		config: &evm::Config,
+	) -> Option<AccessedState> {
+		if T::AccessListGasMetering::get() {
+			Some(AccessedState::new(config, origin, target, access_list))
+		} else {
+			None
+		}
+	}
+
 	fn transfer(source: H160, target: H160, value: BalanceOf<T>) -> DispatchResult {
 		if value.is_zero() {
 			return Ok(());
@@ -178,6 +204,31 @@ impl<T: Config> Runner<T> {
 		gas_limit: u64,
 		storage_limit: u32,
 		config: &evm::Config,
+	) -> Result<CallInfo, DispatchError> {
+		Self::call_with_access_list(
+			sender,
+			origin,
+			target,
+			input,
+			value,
+			gas_limit,
+			storage_limit,
+			Vec::new(),
+			config,
+		)
+	}
+
+	/// Call `target` with the EIP-2930 `access_list`, whose entries are warm from the start.
+	pub fn call_with_access_list(
+		sender: H160,
+		origin: H160,
+		target: H160,
+		input: Vec<u8>,
+		value: BalanceOf<T>,
+		gas_limit: u64,
+		storage_limit: u32,
+		access_list: Vec<AccessListItem>,
+		config: &evm::Config,
 	) -> Result<CallInfo, DispatchError> {
 		log::debug!(
 			target: "evm",
@@ -200,42 +251,67 @@ impl<T: Config> Runner<T> {
 		if !Handler::<T>::can_call_contract(&target, &sender) {
 			return Err(Error::<T>::NoPermission.into());
 		}
+		// the sub calls are filtered by the handler
+		if !T::ContractFilter::filter(&target) {
+			return Err(Error::<T>::ContractFiltered.into());
+		}
 
 		Handler::<T>::inc_nonce(sender);
 
-		Handler::<T>::run_transaction(&vicinity, gas_limit, storage_limit, target, false, config, |substate| {
-			if let Err(e) = Self::transfer(sender, target, value) {
-				return TransactionOutcome::Rollback(Err(e));
-			}
-
-			let code = substate.code(target);
-			let transaction_cost = gasometer::call_transaction_cost(&code);
-			if substate.gasometer.record_transaction(transaction_cost).is_err() {
-				return TransactionOutcome::Rollback(Err(DispatchError::Other("OutOfGas")));
-			}
-
-			let (reason, out) =
-				substate.execute(sender, target, U256::from(value.saturated_into::<u128>()), code, input);
-
-			let call_info = CallInfo {
-				exit_reason: reason.clone(),
-				output: out,
-				used_gas: U256::from(substate.used_gas()),
-				used_storage: substate.used_storage(),
-			};
+		let mut accessed = Self::accessed_state(origin, target, &access_list, config);
+		let access_list_cost = accessed.as_mut().map_or(0, |accessed| {
+			accessed.access_address(sender);
+			AccessedState::access_list_cost(&access_list)
+		});
+
+		Handler::<T>::run_transaction(
+			&vicinity,
+			gas_limit,
+			storage_limit,
+			target,
+			false,
+			config,
+			accessed,
+			|substate| {
+				if let Err(e) = Self::transfer(sender, target, value) {
+					return TransactionOutcome::Rollback(Err(e));
+				}
 
-			log::debug!(
-				target: "evm",
-				"call-result: call_info {:?}",
-				call_info
-			);
+				let code = substate.code(target);
+				let transaction_cost = gasometer::call_transaction_cost(&code);
+				if substate.gasometer.record_transaction(transaction_cost).is_err()
+					|| substate.gasometer.record_cost(access_list_cost).is_err()
+				{
+					return TransactionOutcome::Rollback(Err(DispatchError::Other("OutOfGas")));
+				}
+
+				let value = U256::from(value.saturated_into::<u128>());
+				tracing::with(|tracer| tracer.enter(CallType::Call, sender, target, value, gas_limit, input.clone()));
+
+				let (reason, out) = substate.execute(sender, target, value, code, input);
+
+				tracing::with(|tracer| tracer.exit(reason.clone(), substate.used_gas(), out.clone()));
 
-			if !reason.is_succeed() {
-				return TransactionOutcome::Rollback(Ok(call_info));
-			}
+				let call_info = CallInfo {
+					exit_reason: reason.clone(),
+					output: out,
+					used_gas: U256::from(substate.used_gas()),
+					used_storage: substate.used_storage(),
+				};
 
-			TransactionOutcome::Commit(Ok(call_info))
-		})?
+				log::debug!(
+					target: "evm",
+					"call-result: call_info {:?}",
+					call_info
+				);
+
+				if !reason.is_succeed() {
+					return TransactionOutcome::Rollback(Ok(call_info));
+				}
+
+				TransactionOutcome::Commit(Ok(call_info))
+			},
+		)?
 	}
 
 	pub fn create(
@@ -245,6 +321,19 @@ impl<T: Config> Runner<T> {
 		gas_limit: u64,
 		storage_limit: u32,
 		config: &evm::Config,
+	) -> Result<CreateInfo, DispatchError> {
+		Self::create_with_access_list(source, init, value, gas_limit, storage_limit, Vec::new(), config)
+	}
+
+	/// Create a contract with the EIP-2930 `access_list`, whose entries are warm from the start.
+	pub fn create_with_access_list(
+		source: H160,
+		init: Vec<u8>,
+		value: BalanceOf<T>,
+		gas_limit: u64,
+		storage_limit: u32,
+		access_list: Vec<AccessListItem>,
+		config: &evm::Config,
 	) -> Result<CreateInfo, DispatchError> {
 		Self::inner_create(
 			source,
@@ -254,6 +343,7 @@ impl<T: Config> Runner<T> {
 			storage_limit,
 			None,
 			None,
+			access_list,
 			"create",
 			config,
 		)
@@ -276,6 +366,7 @@ impl<T: Config> Runner<T> {
 			storage_limit,
 			None,
 			Some(salt),
+			Vec::new(),
 			"create2",
 			config,
 		)
@@ -298,6 +389,7 @@ impl<T: Config> Runner<T> {
 			storage_limit,
 			Some(assigned_address),
 			None,
+			Vec::new(),
 			"create-system-contract",
 			config,
 		)
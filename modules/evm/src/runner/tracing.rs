@@ -0,0 +1,132 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Tracing of the EVM execution, collects the call frames and the executed opcodes while a
+//! `Tracer` is set with `using`.
+
+use primitive_types::{H160, H256, U256};
+use primitives::evm::{CallTrace, CallType, StructLog, TraceInfo, TracerConfig};
+use sp_std::prelude::*;
+
+use evm::ExitReason;
+
+environmental::environmental!(tracer: Tracer);
+
+pub struct Tracer {
+	config: TracerConfig,
+	/// The call frames in progress, the last one is the current call frame.
+	calls: Vec<CallTrace>,
+	/// The first finished outermost call frame.
+	root: Option<CallTrace>,
+	struct_logs: Vec<StructLog>,
+}
+
+impl Tracer {
+	pub fn new(config: TracerConfig) -> Self {
+		Self {
+			config,
+			calls: Vec::new(),
+			root: None,
+			struct_logs: Vec::new(),
+		}
+	}
+
+	/// Returns the traces, or `None` if there were no EVM executions.
+	pub fn finalize(self) -> Option<TraceInfo> {
+		let struct_logs = self.struct_logs;
+		self.root.map(|call| TraceInfo { call, struct_logs })
+	}
+
+	pub fn enter(&mut self, call_type: CallType, from: H160, to: H160, value: U256, gas: u64, input: Vec<u8>) {
+		self.calls.push(CallTrace {
+			call_type,
+			from,
+			to,
+			value,
+			gas,
+			gas_used: 0,
+			input,
+			output: Vec::new(),
+			exit_reason: None,
+			calls: Vec::new(),
+		});
+	}
+
+	pub fn exit(&mut self, exit_reason: ExitReason, gas_used: u64, output: Vec<u8>) {
+		if let Some(mut call) = self.calls.pop() {
+			call.exit_reason = Some(exit_reason);
+			call.gas_used = gas_used;
+			call.output = output;
+
+			match self.calls.last_mut() {
+				Some(parent) => parent.calls.push(call),
+				None => {
+					if self.root.is_none() {
+						self.root = Some(call);
+					}
+				}
+			}
+		}
+	}
+
+	fn trace_opcodes(&self) -> bool {
+		matches!(self.config, TracerConfig::OpcodeTracer { .. })
+	}
+
+	/// Record the opcode about to be executed, returns the index of the struct log.
+	pub fn step(&mut self, pc: usize, op: u8, gas: u64, stack: &[H256], memory: &[u8]) -> Option<usize> {
+		if let TracerConfig::OpcodeTracer {
+			disable_stack,
+			disable_memory,
+		} = self.config
+		{
+			self.struct_logs.push(StructLog {
+				pc: pc as u64,
+				op,
+				gas,
+				gas_cost: 0,
+				depth: self.calls.len() as u32,
+				stack: if disable_stack { Vec::new() } else { stack.to_vec() },
+				memory: if disable_memory { Vec::new() } else { memory.to_vec() },
+			});
+			Some(self.struct_logs.len() - 1)
+		} else {
+			None
+		}
+	}
+
+	pub fn set_gas_cost(&mut self, index: usize, gas_cost: u64) {
+		if let Some(struct_log) = self.struct_logs.get_mut(index) {
+			struct_log.gas_cost = gas_cost;
+		}
+	}
+}
+
+/// Execute `f` and trace the EVM executions in it with `tracer`.
+pub fn using<R, F: FnOnce() -> R>(tracer: &mut Tracer, f: F) -> R {
+	tracer::using(tracer, f)
+}
+
+/// Execute `f` with the current tracer, returns `None` if not tracing.
+pub(crate) fn with<R, F: FnOnce(&mut Tracer) -> R>(f: F) -> Option<R> {
+	tracer::with(f)
+}
+
+pub(crate) fn is_tracing_opcodes() -> bool {
+	with(|tracer| tracer.trace_opcodes()).unwrap_or(false)
+}
@@ -21,14 +21,19 @@
 use super::*;
 use mock::{Event, *};
 
-use crate::runner::handler::{Handler, STORAGE_SIZE};
+use crate::runner::{
+	handler::{Handler, STORAGE_SIZE},
+	tracing::{self, Tracer},
+};
 use frame_support::{assert_err, assert_noop, assert_ok};
+use primitives::evm::{CallType, TracerConfig};
 use sp_core::{
 	bytes::{from_hex, to_hex},
 	H160,
 };
 use sp_runtime::{traits::BadOrigin, AccountId32};
 use std::str::FromStr;
+use support::{EvmInput, EvmOutput};
 
 #[test]
 fn fail_call_return_ok() {
@@ -43,6 +48,180 @@ fn fail_call_return_ok() {
 	});
 }
 
+#[test]
+fn should_record_block_gas_used() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(EVM::block_gas_used(), 0);
+
+		let mut data = [0u8; 32];
+		data[0..4].copy_from_slice(b"evm:");
+		let signer: AccountId32 = AccountId32::from(data).into();
+
+		assert_ok!(EVM::call(
+			Origin::signed(signer),
+			contract_a(),
+			Vec::new(),
+			0,
+			1000000,
+			0
+		));
+		let gas_used = EVM::block_gas_used();
+		assert!(gas_used > 0);
+		assert_eq!(EVM::block_fee_info().gas_used, gas_used);
+
+		EVM::on_initialize(2);
+		assert_eq!(EVM::block_gas_used(), 0);
+	});
+}
+
+#[test]
+fn accrue_bloom_should_work() {
+	let mut bloom = [0u8; LOGS_BLOOM_SIZE];
+	accrue_bloom(&mut bloom, &[1u8; 32]);
+
+	let mut expected = [0u8; LOGS_BLOOM_SIZE];
+	expected[38] = 8;
+	expected[40] = 16;
+	expected[239] = 4;
+	assert_eq!(bloom, expected);
+}
+
+#[test]
+fn should_record_logs_bloom_and_receipts() {
+	// PUSH32 0x01..01 PUSH1 0 PUSH1 0 LOG1 STOP
+	let contract =
+		from_hex("0x7f010101010101010101010101010101010101010101010101010101010101010160006000a100").unwrap();
+
+	new_test_ext().execute_with(|| {
+		assert_eq!(EVM::block_logs_bloom(), None);
+		assert_eq!(EVM::block_receipts(), vec![]);
+
+		System::set_extrinsic_index(1);
+		assert_ok!(EVM::create_network_contract(
+			Origin::signed(NetworkContractAccount::get()),
+			contract,
+			0,
+			1000000,
+			1000000,
+		));
+
+		let log = Log {
+			address: H160::from_low_u64_be(MIRRORED_NFT_ADDRESS_START),
+			topics: vec![H256::repeat_byte(1)],
+			data: vec![],
+		};
+		let mut bloom = [0u8; LOGS_BLOOM_SIZE];
+		accrue_bloom(&mut bloom, log.address.as_bytes());
+		accrue_bloom(&mut bloom, &[1u8; 32]);
+		assert_eq!(EVM::block_logs_bloom(), Some(bloom));
+
+		assert_eq!(EVM::block_receipt_count(), 1);
+		let receipts = EVM::block_receipts();
+		assert_eq!(receipts.len(), 1);
+		assert_eq!(receipts[0].extrinsic_index, Some(1));
+		assert_eq!(receipts[0].used_gas, EVM::block_gas_used());
+		assert_eq!(receipts[0].logs, vec![log]);
+
+		EVM::on_initialize(2);
+		assert_eq!(EVM::block_logs_bloom(), None);
+		assert_eq!(EVM::block_receipt_count(), 0);
+		assert_eq!(BlockReceipts::<Test>::get(0), None);
+		assert_eq!(EVM::block_receipts(), vec![]);
+	});
+}
+
+#[test]
+fn should_record_pending_logs_once() {
+	let log = |topic: u8| Log {
+		address: H160::from_low_u64_be(1),
+		topics: vec![H256::repeat_byte(topic)],
+		data: vec![],
+	};
+
+	new_test_ext().execute_with(|| {
+		// the logs of an execution outside of the EVM extrinsics
+		System::set_extrinsic_index(2);
+		EVM::record_log(log(1));
+		EVM::record_log(log(2));
+		assert_eq!(PendingLogs::<Test>::get(Some(2)), vec![log(1), log(2)]);
+		assert_eq!(EVM::block_receipts(), vec![]);
+		assert_eq!(EVM::block_logs_bloom(), None);
+
+		// the logs of the extrinsic are recorded with its execution
+		System::set_extrinsic_index(3);
+		EVM::record_log(log(3));
+		assert_eq!(EVM::record_execution(10), 1);
+		assert_eq!(PendingLogs::<Test>::get(Some(3)), vec![]);
+		assert_eq!(
+			EVM::block_receipts(),
+			vec![Receipt {
+				extrinsic_index: Some(3),
+				used_gas: 10,
+				logs: vec![log(3)],
+			}]
+		);
+
+		// the remaining logs are recorded at the end of the block
+		EVM::on_finalize(1);
+		assert_eq!(PendingLogs::<Test>::iter().count(), 0);
+		assert_eq!(
+			EVM::block_receipts(),
+			vec![
+				Receipt {
+					extrinsic_index: Some(2),
+					used_gas: 0,
+					logs: vec![log(1), log(2)],
+				},
+				Receipt {
+					extrinsic_index: Some(3),
+					used_gas: 10,
+					logs: vec![log(3)],
+				}
+			]
+		);
+
+		let mut bloom = [0u8; LOGS_BLOOM_SIZE];
+		for topic in 1..=3 {
+			accrue_bloom(&mut bloom, H160::from_low_u64_be(1).as_bytes());
+			accrue_bloom(&mut bloom, &[topic; 32]);
+		}
+		assert_eq!(EVM::block_logs_bloom(), Some(bloom));
+	});
+}
+
+#[test]
+fn should_derive_base_fee_per_gas_from_weight_fee() {
+	new_test_ext().execute_with(|| {
+		// the mock charges no weight fee
+		assert_eq!(EVM::base_fee_per_gas(), U256::zero());
+		assert_eq!(
+			EVM::block_fee_info(),
+			BlockFeeInfo {
+				base_fee_per_gas: U256::zero(),
+				next_base_fee_per_gas: U256::zero(),
+				gas_used: 0,
+				gas_limit: 2 * BlockGasTarget::get(),
+			}
+		);
+
+		// the gas used of the block doesn't change the base fee
+		BlockGasUsed::<Test>::put(2 * BlockGasTarget::get());
+		assert_eq!(
+			EVM::block_fee_info(),
+			BlockFeeInfo {
+				base_fee_per_gas: U256::zero(),
+				next_base_fee_per_gas: U256::zero(),
+				gas_used: 2 * BlockGasTarget::get(),
+				gas_limit: 2 * BlockGasTarget::get(),
+			}
+		);
+
+		EVM::on_initialize(2);
+		assert_eq!(EVM::block_gas_used(), 0);
+		assert_eq!(EVM::base_fee_per_gas(), U256::zero());
+	});
+}
+
 #[test]
 fn should_calculate_contract_address() {
 	new_test_ext().execute_with(|| {
@@ -146,6 +325,107 @@ fn should_create_and_call_contract() {
 	});
 }
 
+#[test]
+fn should_estimate_fee() {
+	// pragma solidity ^0.5.0;
+	//
+	// contract Test {
+	//	 function multiply(uint a, uint b) public pure returns(uint) {
+	// 	 	return a * b;
+	// 	 }
+	// }
+	let contract = from_hex(
+		"0x608060405234801561001057600080fd5b5060b88061001f6000396000f3fe6080604052348015600f57600080fd5b506004361060285760003560e01c8063165c4a1614602d575b600080fd5b606060048036036040811015604157600080fd5b8101908080359060200190929190803590602001909291905050506076565b6040518082815260200191505060405180910390f35b600081830290509291505056fea265627a7a723158201f3db7301354b88b310868daf4395a6ab6cd42d16b1d8e68cdf4fdd9d34fffbf64736f6c63430005110032"
+	).unwrap();
+
+	new_test_ext().execute_with(|| {
+		let info = EVM::estimate_fee(alice(), None, contract, 0, 1000000, 1000000, None).unwrap();
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+		assert!(!info.used_gas.is_zero());
+		assert_eq!(info.used_storage, 184 + NewContractExtraBytes::get() as i32);
+		assert_eq!(
+			info.storage_deposit,
+			info.used_storage as u64 * StorageDepositPerByte::get()
+		);
+		assert_eq!(info.total_fee, Some(info.gas_fee + info.storage_deposit));
+		assert_eq!(info.fee_currency_id, None);
+	});
+}
+
+#[test]
+fn should_trace_contract_call() {
+	// the `multiply` contract of `should_create_and_call_contract`
+	let contract = from_hex(
+		"0x608060405234801561001057600080fd5b5060b88061001f6000396000f3fe6080604052348015600f57600080fd5b506004361060285760003560e01c8063165c4a1614602d575b600080fd5b606060048036036040811015604157600080fd5b8101908080359060200190929190803590602001909291905050506076565b6040518082815260200191505060405180910390f35b600081830290509291505056fea265627a7a723158201f3db7301354b88b310868daf4395a6ab6cd42d16b1d8e68cdf4fdd9d34fffbf64736f6c63430005110032"
+	).unwrap();
+	// multiply(2, 3)
+	let multiply = from_hex(
+		"0x165c4a1600000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000003"
+	).unwrap();
+
+	new_test_ext().execute_with(|| {
+		let result =
+			Runner::<Test>::create(alice(), contract, 0, 1000000, 1000000, <Test as Config>::config()).unwrap();
+		assert_eq!(result.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+		let contract_address = result.address;
+
+		#[cfg(not(feature = "with-ethereum-compatibility"))]
+		deploy_free(contract_address);
+
+		let call = || {
+			Runner::<Test>::call(
+				alice(),
+				alice(),
+				contract_address,
+				multiply.clone(),
+				0,
+				1000000,
+				1000000,
+				<Test as Config>::config(),
+			)
+			.unwrap()
+		};
+
+		// call tracer
+		let mut tracer = Tracer::new(TracerConfig::CallTracer);
+		let result = tracing::using(&mut tracer, call);
+		let trace = tracer.finalize().unwrap();
+		assert_eq!(trace.call.call_type, CallType::Call);
+		assert_eq!(trace.call.from, alice());
+		assert_eq!(trace.call.to, contract_address);
+		assert_eq!(trace.call.input, multiply);
+		assert_eq!(trace.call.output, result.output);
+		assert_eq!(trace.call.gas_used, result.used_gas.as_u64());
+		assert_eq!(trace.call.exit_reason, Some(result.exit_reason));
+		assert!(trace.call.calls.is_empty());
+		assert!(trace.struct_logs.is_empty());
+
+		// opcode tracer
+		let mut tracer = Tracer::new(TracerConfig::OpcodeTracer {
+			disable_stack: false,
+			disable_memory: true,
+		});
+		tracing::using(&mut tracer, call);
+		let trace = tracer.finalize().unwrap();
+		assert!(!trace.struct_logs.is_empty());
+		// PUSH1 0x80
+		assert_eq!(trace.struct_logs[0].pc, 0);
+		assert_eq!(trace.struct_logs[0].op, 0x60);
+		assert_eq!(trace.struct_logs[0].depth, 1);
+		assert_eq!(trace.struct_logs[0].gas_cost, 3);
+		assert!(trace.struct_logs[0].stack.is_empty());
+		// PUSH1 0x40
+		assert_eq!(trace.struct_logs[1].pc, 2);
+		assert_eq!(trace.struct_logs[1].stack, vec![H256::from_low_u64_be(0x80)]);
+		assert!(trace.struct_logs.iter().all(|struct_log| struct_log.memory.is_empty()));
+
+		// not tracing
+		let mut tracer = Tracer::new(TracerConfig::CallTracer);
+		call();
+		assert_eq!(tracer.finalize(), None);
+	});
+}
+
 #[test]
 fn create_reverts_with_message() {
 	// pragma solidity ^0.5.0;
@@ -403,6 +683,65 @@ fn should_transfer_from_contract() {
 	})
 }
 
+#[test]
+fn filtered_contracts_cannot_be_called() {
+	// pragma solidity ^0.5.16;
+	//
+	// contract SendEther {
+	//     ...
+	//     function sendViaCall(address payable _to) public payable {
+	//         (bool sent, bytes memory data) = _to.call.value(msg.value)("");
+	//         require(sent, "Failed to send Ether");
+	//     }
+	// }
+	let contract = from_hex(
+		"0x608060405234801561001057600080fd5b50610318806100206000396000f3fe6080604052600436106100345760003560e01c8063636e082b1461003957806374be48061461007d578063830c29ae146100c1575b600080fd5b61007b6004803603602081101561004f57600080fd5b81019080803573ffffffffffffffffffffffffffffffffffffffff169060200190929190505050610105565b005b6100bf6004803603602081101561009357600080fd5b81019080803573ffffffffffffffffffffffffffffffffffffffff16906020019092919050505061014f565b005b610103600480360360208110156100d757600080fd5b81019080803573ffffffffffffffffffffffffffffffffffffffff1690602001909291905050506101ff565b005b8073ffffffffffffffffffffffffffffffffffffffff166108fc349081150290604051600060405180830381858888f1935050505015801561014b573d6000803e3d6000fd5b5050565b60008173ffffffffffffffffffffffffffffffffffffffff166108fc349081150290604051600060405180830381858888f193505050509050806101fb576040517f08c379a00000000000000000000000000000000000000000000000000000000081526004018080602001828103825260148152602001807f4661696c656420746f2073656e6420457468657200000000000000000000000081525060200191505060405180910390fd5b5050565b600060608273ffffffffffffffffffffffffffffffffffffffff163460405180600001905060006040518083038185875af1925050503d8060008114610261576040519150601f19603f3d011682016040523d82523d6000602084013e610266565b606091505b5091509150816102de576040517f08c379a00000000000000000000000000000000000000000000000000000000081526004018080602001828103825260148152602001807f4661696c656420746f2073656e6420457468657200000000000000000000000081525060200191505060405180910390fd5b50505056fea265627a7a723158201b401be037c87d59ec386e75b0166702abb5a64f93ea20080904b6791bd88d1564736f6c63430005110032"
+	).unwrap();
+	new_test_ext().execute_with(|| {
+		let result = Runner::<Test>::create(alice(), contract, 0, 10000000, 10000000, <Test as Config>::config())
+			.expect("create shouldn't fail");
+		let contract_address = result.address;
+
+		#[cfg(not(feature = "with-ethereum-compatibility"))]
+		deploy_free(contract_address);
+
+		let mut via_call = from_hex("0x830c29ae").unwrap();
+		via_call.append(&mut Vec::from(H256::from(charlie()).as_bytes()));
+
+		// the sub call to the filtered contract fails
+		mock_filtered_contract(charlie());
+		let result = Runner::<Test>::call(
+			alice(),
+			alice(),
+			contract_address,
+			via_call.clone(),
+			1000,
+			1000000,
+			1000000,
+			<Test as Config>::config(),
+		)
+		.unwrap();
+		assert!(matches!(result.exit_reason, ExitReason::Revert(_)));
+		assert_eq!(balance(charlie()), 0);
+
+		// the call to the filtered contract fails
+		mock_filtered_contract(contract_address);
+		assert_noop!(
+			Runner::<Test>::call(
+				alice(),
+				alice(),
+				contract_address,
+				via_call,
+				1000,
+				1000000,
+				1000000,
+				<Test as Config>::config(),
+			),
+			Error::<Test>::ContractFiltered
+		);
+	})
+}
+
 #[test]
 fn contract_should_deploy_contracts() {
 	// pragma solidity ^0.5.0;
@@ -707,6 +1046,7 @@ fn should_deploy() {
 
 		// contract not created yet
 		assert_noop!(EVM::deploy(Origin::signed(alice_account_id.clone()), H160::default()), Error::<Test>::ContractNotFound);
+		assert_eq!(EVM::contract_status(&H160::default()), None);
 
 		// if the contract not exists, evm will return ExitSucceed::Stopped.
 		let result = Runner::<Test>::call(
@@ -725,6 +1065,7 @@ fn should_deploy() {
 		// create contract
 		let result = Runner::<Test>::create(alice(), contract, 0, 21_000_000, 21_000_000, <Test as Config>::config()).unwrap();
 		let contract_address = result.address;
+		assert_eq!(EVM::contract_status(&contract_address), Some(ContractStatus { maintainer: alice(), deployed: false }));
 
 		assert_eq!(result.used_storage, 284);
 		let alice_balance = INITIAL_BALANCE - 284 * <Test as Config>::StorageDepositPerByte::get();
@@ -777,6 +1118,7 @@ fn should_deploy() {
 		assert_noop!(EVM::deploy(Origin::signed(bob_account_id), contract_address), Error::<Test>::NoPermission);
 
 		assert_ok!(EVM::deploy(Origin::signed(alice_account_id.clone()), contract_address));
+		assert_eq!(EVM::contract_status(&contract_address), Some(ContractStatus { maintainer: alice(), deployed: true }));
 		let code_size = Accounts::<Test>::get(contract_address).map_or(0, |account_info| -> u32 {
 			account_info.contract_info.map_or(0, |contract_info| CodeInfos::<Test>::get(contract_info.code_hash).map_or(0, |code_info| code_info.code_size))
 		});
@@ -1045,6 +1387,603 @@ fn should_selfdestruct() {
 	});
 }
 
+#[test]
+fn should_notify_eviction() {
+	// pragma solidity ^0.5.0;
+	//
+	// contract Test {
+	// 	 uint value;
+	// 	 constructor(uint a) public payable {
+	// 		value = a;
+	// 	 }
+	//
+	//   function getValue() public payable returns (uint) {
+	// 	     return value;
+	// 	 }
+	// }
+	let mut contract = from_hex(
+		"0x60806040526040516100c73803806100c783398181016040526020811015602557600080fd5b81019080805190602001909291905050508060008190555050607b8061004c6000396000f3fe608060405260043610601c5760003560e01c806320965255146021575b600080fd5b6027603d565b6040518082815260200191505060405180910390f35b6000805490509056fea265627a7a72315820b832564a9db725638dcef03d07bfbdd2dc818020ea359630317e2126e95c314964736f6c63430005110032"
+	).unwrap();
+
+	new_test_ext().execute_with(|| {
+		let alice_account_id = <Test as Config>::AddressMapping::get_account_id(&alice());
+		let bob_account_id = <Test as Config>::AddressMapping::get_account_id(&bob());
+
+		let stored_value: Vec<u8> =
+			from_hex("0x000000000000000000000000000000000000000000000000000000000000007b").unwrap();
+		contract.append(&mut stored_value.clone());
+
+		// create contract
+		let result = Runner::<Test>::create(alice(), contract, 0, 100000, 100000, <Test as Config>::config()).unwrap();
+
+		let contract_address = result.address;
+		let contract_account_id = <Test as Config>::AddressMapping::get_account_id(&contract_address);
+		assert_eq!(result.used_storage, 287);
+
+		assert_noop!(
+			EVM::notify_eviction(Origin::signed(bob_account_id.clone()), contract_address),
+			Error::<Test>::StorageDepositSufficient
+		);
+
+		// the storage deposit no longer covers the storage usage
+		let _ = Balances::slash_reserved_named(&RESERVE_ID_STORAGE_DEPOSIT, &contract_account_id, 10);
+
+		assert_noop!(
+			EVM::evict_contract(Origin::signed(bob_account_id.clone()), contract_address, 1),
+			Error::<Test>::EvictionNotNotified
+		);
+		assert_ok!(EVM::notify_eviction(
+			Origin::signed(bob_account_id.clone()),
+			contract_address
+		));
+		System::assert_last_event(Event::EVM(crate::Event::EvictionNotified(contract_address, 11)));
+		assert_eq!(EVM::eviction_notices(contract_address), Some(11));
+		assert_noop!(
+			EVM::notify_eviction(Origin::signed(bob_account_id.clone()), contract_address),
+			Error::<Test>::EvictionAlreadyNotified
+		);
+		assert_noop!(
+			EVM::evict_contract(Origin::signed(bob_account_id.clone()), contract_address, 1),
+			Error::<Test>::EvictionNotNotified
+		);
+
+		// the maintainer tops up the storage deposit in the notice period
+		let alice_balance = balance(alice());
+		assert_ok!(EVM::top_up_storage_deposit(
+			Origin::signed(alice_account_id),
+			contract_address
+		));
+		System::assert_last_event(Event::EVM(crate::Event::StorageDepositToppedUp(
+			contract_address,
+			<Test as Config>::AddressMapping::get_account_id(&alice()),
+			10,
+		)));
+		assert_eq!(balance(alice()), alice_balance - 10);
+		assert_eq!(
+			reserved_balance(contract_address),
+			287 * <Test as Config>::StorageDepositPerByte::get()
+		);
+
+		System::set_block_number(11);
+		assert_noop!(
+			EVM::evict_contract(Origin::signed(bob_account_id.clone()), contract_address, 1),
+			Error::<Test>::StorageDepositSufficient
+		);
+
+		// the notice lapses after the notice period
+		let _ = Balances::slash_reserved_named(&RESERVE_ID_STORAGE_DEPOSIT, &contract_account_id, 10);
+		System::set_block_number(21);
+		assert_noop!(
+			EVM::evict_contract(Origin::signed(bob_account_id.clone()), contract_address, 1),
+			Error::<Test>::EvictionNotNotified
+		);
+		assert_ok!(EVM::notify_eviction(
+			Origin::signed(bob_account_id.clone()),
+			contract_address
+		));
+		assert_eq!(EVM::eviction_notices(contract_address), Some(31));
+
+		System::set_block_number(31);
+		assert_ok!(EVM::evict_contract(
+			Origin::signed(bob_account_id.clone()),
+			contract_address,
+			1
+		));
+		assert_eq!(EVM::eviction_notices(contract_address), None);
+		assert_noop!(
+			EVM::notify_eviction(Origin::signed(bob_account_id.clone()), contract_address),
+			Error::<Test>::ContractEvicted
+		);
+		assert_noop!(
+			EVM::top_up_storage_deposit(Origin::signed(bob_account_id), contract_address),
+			Error::<Test>::ContractEvicted
+		);
+	});
+}
+
+#[test]
+fn should_evict_and_resurrect_contract() {
+	// pragma solidity ^0.5.0;
+	//
+	// contract Test {
+	// 	 uint value;
+	// 	 constructor(uint a) public payable {
+	// 		value = a;
+	// 	 }
+	//
+	//   function getValue() public payable returns (uint) {
+	// 	     return value;
+	// 	 }
+	// }
+	let mut contract = from_hex(
+		"0x60806040526040516100c73803806100c783398181016040526020811015602557600080fd5b81019080805190602001909291905050508060008190555050607b8061004c6000396000f3fe608060405260043610601c5760003560e01c806320965255146021575b600080fd5b6027603d565b6040518082815260200191505060405180910390f35b6000805490509056fea265627a7a72315820b832564a9db725638dcef03d07bfbdd2dc818020ea359630317e2126e95c314964736f6c63430005110032"
+	).unwrap();
+
+	new_test_ext().execute_with(|| {
+		let bob_account_id = <Test as Config>::AddressMapping::get_account_id(&bob());
+
+		let stored_value: Vec<u8> =
+			from_hex("0x000000000000000000000000000000000000000000000000000000000000007b").unwrap();
+		contract.append(&mut stored_value.clone());
+
+		// create contract
+		let result = Runner::<Test>::create(alice(), contract, 0, 100000, 100000, <Test as Config>::config()).unwrap();
+
+		let contract_address = result.address;
+		let contract_account_id = <Test as Config>::AddressMapping::get_account_id(&contract_address);
+		assert_eq!(result.used_storage, 287);
+
+		// another storage entry, which isn't paid for
+		AccountStorages::<Test>::insert(&contract_address, H256::from_low_u64_be(1), H256::from_low_u64_be(1));
+		EVM::update_contract_storage_size(&contract_address, STORAGE_SIZE as i32);
+		let storages = AccountStorages::<Test>::iter_prefix(&contract_address).collect::<Vec<_>>();
+		let deposit = STORAGE_SIZE as u64 * <Test as Config>::StorageDepositPerByte::get();
+
+		// the storage deposit no longer covers the storage usage
+		let _ = Balances::slash_reserved_named(&RESERVE_ID_STORAGE_DEPOSIT, &contract_account_id, 10);
+		assert_ok!(EVM::notify_eviction(
+			Origin::signed(bob_account_id.clone()),
+			contract_address
+		));
+		System::set_block_number(11);
+
+		// the contract is evicted from the first call, before its storage entries are removed
+		assert_ok!(EVM::evict_contract(
+			Origin::signed(bob_account_id.clone()),
+			contract_address,
+			0
+		));
+		System::assert_last_event(Event::EVM(crate::Event::ContractEvicted(contract_address, 0, false)));
+		assert_eq!(AccountStorages::<Test>::iter_prefix(&contract_address).count(), 2);
+		assert_eq!(EVM::code_at_address(&contract_address).len(), 0);
+		assert_noop!(
+			EVM::resurrect_contract(
+				Origin::signed(bob_account_id.clone()),
+				contract_address,
+				vec![],
+				H256::zero()
+			),
+			Error::<Test>::EvictionNotCompleted
+		);
+
+		// the storage entries are removed in the order of iteration
+		for (i, storage) in storages.iter().enumerate() {
+			assert_ok!(EVM::evict_contract(
+				Origin::signed(bob_account_id.clone()),
+				contract_address,
+				1
+			));
+			System::assert_last_event(Event::EVM(crate::Event::ContractEvicted(contract_address, 1, i == 1)));
+			assert!(!AccountStorages::<Test>::contains_key(&contract_address, storage.0));
+		}
+		let first_hash = chain_storage_hash(H256::zero(), storages[0].0, storages[0].1);
+		let storage_hash = chain_storage_hash(first_hash, storages[1].0, storages[1].1);
+		assert_eq!(
+			EVM::evicted_contracts(contract_address),
+			Some(EvictedContractInfo {
+				storage_hash,
+				storage_count: 2,
+				completed: true,
+			})
+		);
+		assert_noop!(
+			EVM::evict_contract(Origin::signed(bob_account_id.clone()), contract_address, 1),
+			Error::<Test>::ContractEvicted
+		);
+
+		assert_eq!(AccountStorages::<Test>::iter_prefix(&contract_address).count(), 0);
+		assert_eq!(ContractStorageSizes::<Test>::get(&contract_address), 287 - STORAGE_SIZE);
+		assert_eq!(EVM::code_at_address(&contract_address).len(), 0);
+
+		// call method `getValue()`
+		assert_noop!(
+			Runner::<Test>::call(
+				alice(),
+				alice(),
+				contract_address,
+				from_hex("0x20965255").unwrap(),
+				0,
+				1000000,
+				0,
+				<Test as Config>::config(),
+			),
+			Error::<Test>::NoPermission
+		);
+
+		assert_noop!(
+			EVM::resurrect_contract(
+				Origin::signed(bob_account_id.clone()),
+				contract_address,
+				vec![(storages[1].0, H256::from_low_u64_be(2))],
+				first_hash
+			),
+			Error::<Test>::InvalidEvictedStorage
+		);
+		// the first removed entries are restored last
+		assert_noop!(
+			EVM::resurrect_contract(
+				Origin::signed(bob_account_id.clone()),
+				contract_address,
+				vec![storages[0]],
+				H256::zero()
+			),
+			Error::<Test>::InvalidEvictedStorage
+		);
+		assert_noop!(
+			EVM::resurrect_contract(
+				Origin::signed(bob_account_id.clone()),
+				contract_address,
+				vec![],
+				storage_hash
+			),
+			Error::<Test>::InvalidEvictedStorage
+		);
+
+		// the last removed entry is restored, the contract is still evicted
+		let bob_balance = balance(bob());
+		assert_ok!(EVM::resurrect_contract(
+			Origin::signed(bob_account_id.clone()),
+			contract_address,
+			vec![storages[1]],
+			first_hash
+		));
+		System::assert_last_event(Event::EVM(crate::Event::ContractResurrected(
+			contract_address,
+			bob_account_id.clone(),
+			1,
+			false,
+		)));
+		assert_eq!(
+			EVM::evicted_contracts(contract_address),
+			Some(EvictedContractInfo {
+				storage_hash: first_hash,
+				storage_count: 1,
+				completed: true,
+			})
+		);
+		assert_eq!(ContractStorageSizes::<Test>::get(&contract_address), 287);
+		assert_eq!(EVM::code_at_address(&contract_address).len(), 0);
+
+		assert_ok!(EVM::resurrect_contract(
+			Origin::signed(bob_account_id.clone()),
+			contract_address,
+			vec![storages[0]],
+			H256::zero()
+		));
+		System::assert_last_event(Event::EVM(crate::Event::ContractResurrected(
+			contract_address,
+			bob_account_id.clone(),
+			1,
+			true,
+		)));
+		assert_noop!(
+			EVM::resurrect_contract(Origin::signed(bob_account_id), contract_address, vec![], H256::zero()),
+			Error::<Test>::ContractNotEvicted
+		);
+
+		assert_eq!(
+			AccountStorages::<Test>::get(&contract_address, H256::zero()),
+			H256::from_low_u64_be(0x7b)
+		);
+		assert_eq!(ContractStorageSizes::<Test>::get(&contract_address), 287 + STORAGE_SIZE);
+		// bob tops up the slashed and the unpaid storage deposit
+		assert_eq!(balance(bob()), bob_balance - 10 - deposit);
+		assert_eq!(
+			reserved_balance(contract_address),
+			287 * <Test as Config>::StorageDepositPerByte::get() + deposit
+		);
+
+		// call method `getValue()`
+		let result = Runner::<Test>::call(
+			alice(),
+			alice(),
+			contract_address,
+			from_hex("0x20965255").unwrap(),
+			0,
+			1000000,
+			0,
+			<Test as Config>::config(),
+		)
+		.unwrap();
+		assert_eq!(result.output, stored_value);
+	});
+}
+
+#[test]
+fn should_call_evm() {
+	// pragma solidity ^0.5.0;
+	//
+	// contract Test {
+	// 	 uint value;
+	// 	 constructor(uint a) public payable {
+	// 		value = a;
+	// 	 }
+	//
+	//   function getValue() public payable returns (uint) {
+	// 	     return value;
+	// 	 }
+	// }
+	let mut contract = from_hex(
+		"0x60806040526040516100c73803806100c783398181016040526020811015602557600080fd5b81019080805190602001909291905050508060008190555050607b8061004c6000396000f3fe608060405260043610601c5760003560e01c806320965255146021575b600080fd5b6027603d565b6040518082815260200191505060405180910390f35b6000805490509056fea265627a7a72315820b832564a9db725638dcef03d07bfbdd2dc818020ea359630317e2126e95c314964736f6c63430005110032"
+	).unwrap();
+
+	new_test_ext().execute_with(|| {
+		let alice_account_id = <Test as Config>::AddressMapping::get_account_id(&alice());
+
+		let mut stored_value: Vec<u8> =
+			from_hex("0x000000000000000000000000000000000000000000000000000000000000007b").unwrap();
+		contract.append(&mut stored_value);
+
+		// create contract
+		let result = Runner::<Test>::create(alice(), contract, 0, 100000, 100000, <Test as Config>::config()).unwrap();
+		let contract_address = result.address;
+
+		let input = EvmInput::with_signature(b"getValue()");
+		assert_eq!(input, EvmInput::new(0x20965255));
+
+		let output = <EVM as EVMCall<_, _>>::call_evm(
+			&alice_account_id,
+			contract_address,
+			input.clone().into(),
+			100,
+			100000,
+			0,
+		)
+		.unwrap();
+		assert_eq!(EvmOutput::new(&output).u256_at(0), Ok(0x7b.into()));
+		assert_eq!(EvmOutput::new(&output).balance_at(0), Ok(0x7b));
+		assert!(EvmOutput::new(&output).u256_at(1).is_err());
+		assert_eq!(balance(contract_address), 100);
+
+		assert_ok!(EVM::call_evm(
+			Origin::signed(alice_account_id.clone()),
+			contract_address,
+			input.into(),
+			0,
+			100000,
+			0
+		));
+		System::assert_last_event(Event::EVM(crate::Event::Executed(contract_address)));
+
+		// the call reverts without the fallback function
+		assert_noop!(
+			EVM::call_evm(
+				Origin::signed(alice_account_id.clone()),
+				contract_address,
+				EvmInput::with_signature(b"setValue(uint256)").u256(1.into()).into(),
+				0,
+				100000,
+				0
+			),
+			Error::<Test>::ExecutionRevert
+		);
+		assert_noop!(
+			<EVM as EVMCall<_, _>>::call_evm(
+				&alice_account_id,
+				contract_address,
+				EvmInput::with_signature(b"setValue(uint256)").u256(1.into()).into(),
+				100,
+				100000,
+				0,
+			),
+			Error::<Test>::ExecutionRevert
+		);
+	});
+}
+
+#[test]
+fn should_charge_cold_state_access() {
+	// pragma solidity ^0.5.0;
+	//
+	// contract Test {
+	// 	 uint value;
+	// 	 constructor(uint a) public payable {
+	// 		value = a;
+	// 	 }
+	//
+	//   function getValue() public payable returns (uint) {
+	// 	     return value;
+	// 	 }
+	// }
+	let mut contract = from_hex(
+		"0x60806040526040516100c73803806100c783398181016040526020811015602557600080fd5b81019080805190602001909291905050508060008190555050607b8061004c6000396000f3fe608060405260043610601c5760003560e01c806320965255146021575b600080fd5b6027603d565b6040518082815260200191505060405180910390f35b6000805490509056fea265627a7a72315820b832564a9db725638dcef03d07bfbdd2dc818020ea359630317e2126e95c314964736f6c63430005110032"
+	).unwrap();
+
+	new_test_ext().execute_with(|| {
+		let mut stored_value: Vec<u8> =
+			from_hex("0x000000000000000000000000000000000000000000000000000000000000007b").unwrap();
+		contract.append(&mut stored_value);
+
+		// create contract
+		let result = Runner::<Test>::create(alice(), contract, 0, 100000, 100000, <Test as Config>::config()).unwrap();
+		let contract_address = result.address;
+
+		let get_value = |access_list: Vec<AccessListItem>| {
+			// call method `getValue()`
+			let result = Runner::<Test>::call_with_access_list(
+				alice(),
+				alice(),
+				contract_address,
+				from_hex("0x20965255").unwrap(),
+				0,
+				100000,
+				0,
+				access_list,
+				<Test as Config>::config(),
+			)
+			.unwrap();
+			assert!(result.exit_reason.is_succeed());
+			result.used_gas.as_u64()
+		};
+		let access_list = vec![AccessListItem {
+			address: contract_address,
+			storage_keys: vec![H256::zero()],
+		}];
+
+		let used_gas = get_value(vec![]);
+		// the access list is ignored without the access list gas metering
+		assert_eq!(get_value(access_list.clone()), used_gas);
+
+		AccessListGasMetering::set(true);
+
+		// the cold `SLOAD` costs 2100 instead of 800
+		assert_eq!(get_value(vec![]), used_gas + 1300);
+		// the access list costs 2400 for the address and 1900 for the storage key, then the warm
+		// `SLOAD` costs 100
+		assert_eq!(get_value(access_list), used_gas + 4300 - 700);
+	});
+}
+
+#[test]
+fn should_submit_contract_metadata() {
+	new_test_ext().execute_with(|| {
+		let alice_account_id = <Test as Config>::AddressMapping::get_account_id(&alice());
+		let bob_account_id = <Test as Config>::AddressMapping::get_account_id(&bob());
+
+		// contract with code `0x01`
+		let result = Runner::<Test>::create(
+			alice(),
+			vec![0x60, 0x01, 0x60, 0x00, 0x53, 0x60, 0x01, 0x60, 0x00, 0xf3],
+			0,
+			1000000,
+			1000000,
+			<Test as Config>::config(),
+		)
+		.unwrap();
+		let contract_address = result.address;
+		let code_hash = EVM::accounts(&contract_address)
+			.unwrap()
+			.contract_info
+			.unwrap()
+			.code_hash;
+
+		assert_noop!(
+			EVM::submit_contract_metadata(
+				Origin::signed(bob_account_id),
+				contract_address,
+				b"cid".to_vec(),
+				b"v0.8.4".to_vec(),
+				H256::repeat_byte(1)
+			),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			EVM::submit_contract_metadata(
+				Origin::signed(alice_account_id.clone()),
+				contract_address,
+				vec![0u8; MAX_SOURCE_CID_LENGTH + 1],
+				b"v0.8.4".to_vec(),
+				H256::repeat_byte(1)
+			),
+			Error::<Test>::InvalidContractMetadata
+		);
+
+		assert_ok!(EVM::submit_contract_metadata(
+			Origin::signed(alice_account_id.clone()),
+			contract_address,
+			b"cid".to_vec(),
+			b"v0.8.4".to_vec(),
+			H256::repeat_byte(1)
+		));
+		System::assert_last_event(Event::EVM(crate::Event::ContractMetadataSubmitted(
+			contract_address,
+			code_hash,
+		)));
+		assert_eq!(
+			EVM::contract_metadata(&code_hash),
+			Some(ContractMetadata {
+				submitter: alice(),
+				source_cid: b"cid".to_vec(),
+				compiler_version: b"v0.8.4".to_vec(),
+				compiler_settings_hash: H256::repeat_byte(1),
+			})
+		);
+
+		assert_noop!(
+			EVM::submit_contract_metadata(
+				Origin::signed(alice_account_id),
+				contract_address,
+				b"cid".to_vec(),
+				b"v0.8.4".to_vec(),
+				H256::repeat_byte(1)
+			),
+			Error::<Test>::ContractMetadataAlreadySubmitted
+		);
+	});
+}
+
+#[test]
+fn should_dry_run() {
+	// pragma solidity ^0.5.0;
+	//
+	// contract Test {
+	//     mapping(address => uint256) public values;
+	//
+	//     constructor() public {
+	//         values[msg.sender] = 42;
+	//     }
+	//
+	//     function set(uint val) public {
+	//      values[msg.sender] = val;
+	//     }
+	// }
+	let contract = from_hex(
+		"0x608060405234801561001057600080fd5b50602a6000803373ffffffffffffffffffffffffffffffffffffffff1673ffffffffffffffffffffffffffffffffffffffff16815260200190815260200160002081905550610154806100646000396000f3fe608060405234801561001057600080fd5b50600436106100365760003560e01c806354fe9fd71461003b57806360fe47b114610093575b600080fd5b61007d6004803603602081101561005157600080fd5b81019080803573ffffffffffffffffffffffffffffffffffffffff1690602001909291905050506100c1565b6040518082815260200191505060405180910390f35b6100bf600480360360208110156100a957600080fd5b81019080803590602001909291905050506100d9565b005b60006020528060005260406000206000915090505481565b806000803373ffffffffffffffffffffffffffffffffffffffff1673ffffffffffffffffffffffffffffffffffffffff168152602001908152602001600020819055505056fea265627a7a723158207ab6991e97c9c12f57d81df0c7f955435418354adeb26116b581d7f2f035ca8f64736f6c63430005110032"
+	).unwrap();
+
+	new_test_ext().execute_with(|| {
+		// create contract
+		let result = Runner::<Test>::create(alice(), contract, 0, 500000, 100000, <Test as Config>::config()).unwrap();
+		let contract_address = result.address;
+		let used_storage = ContractStorageSizes::<Test>::get(&contract_address);
+
+		#[cfg(not(feature = "with-ethereum-compatibility"))]
+		deploy_free(contract_address);
+
+		let bob_balance = balance(bob());
+
+		// call method `set(123)`
+		let (result, storage_changes) = EVM::dry_run(|| {
+			Runner::<Test>::call(
+				bob(),
+				bob(),
+				contract_address,
+				from_hex("0x60fe47b1000000000000000000000000000000000000000000000000000000000000007b").unwrap(),
+				0,
+				1000000,
+				STORAGE_SIZE,
+				<Test as Config>::config(),
+			)
+		});
+		assert_eq!(result.unwrap().used_storage, STORAGE_SIZE as i32);
+		assert_eq!(storage_changes, vec![(contract_address, STORAGE_SIZE as i32)]);
+
+		// the changes are discarded
+		assert_eq!(ContractStorageSizes::<Test>::get(&contract_address), used_storage);
+		assert_eq!(balance(bob()), bob_balance);
+	});
+}
+
 #[test]
 fn storage_limit_should_work() {
 	// pragma solidity ^0.5.0;
@@ -0,0 +1,694 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Federated Bridge Module
+//!
+//! ## Overview
+//!
+//! A generic bridge to mint the wrapped assets of an external chain. The lock events on the
+//! external chain are attested by a federation of registered signers, and the wrapped asset is
+//! minted to the recipient once the lock event is attested by `Threshold` signers. Each signer
+//! attests one lock event for a nonce, the attestations are kept by the hash of the lock event, so
+//! a conflicting lock event attested by a malicious signer can't block the honest one, and the
+//! first lock event of the nonce reaching `Threshold` is minted. The wrapped assets are burnt to be
+//! released on the external chain by the federation.
+//!
+//! The signers must bond `SignerBond` before they're added to the signer set, which is rotated by
+//! the update origin. The bond can be withdrawn `BondingDuration` after unbonding, unless a fraud
+//! report against the account is pending. Anyone can report a signer for attesting a lock event
+//! that doesn't exist on the external chain by reserving `ReportDeposit`, the bond of the signer
+//! is slashed if the fraud origin upholds the report, and `ReporterReward` of it goes to the
+//! reporter. The slashed signer is removed from the signer set, and the threshold is lowered to
+//! the number of the remaining signers if it exceeds it.
+//!
+//! Only the registered assets can be bridged, and the outstanding amount of each asset is capped.
+//! The assets are minted and burnt through the `BridgeAsset` trait, so other modules can provide
+//! their own wrapped assets.
+//!
+//! The attestations and burns are halted when the emergency shutdown occurs.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{
+	pallet_prelude::*,
+	traits::{BalanceStatus, NamedReservableCurrency},
+	transactional,
+};
+use frame_system::pallet_prelude::*;
+use orml_traits::MultiCurrency;
+use primitives::{Balance, CurrencyId, ReserveIdentifier};
+use sp_runtime::{
+	traits::{Hash, Saturating, Zero},
+	Permill, RuntimeDebug,
+};
+use sp_std::{marker::PhantomData, prelude::*};
+use support::{BridgeAsset, MintGuard, OnEmergencyShutdown};
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+pub type LockNonce = u64;
+pub type BurnId = u64;
+pub type ReportId = u32;
+
+/// The lock event on the external chain.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct LockEvent<AccountId> {
+	/// The nonce of the lock event on the external chain.
+	pub nonce: LockNonce,
+	/// The wrapped currency to mint.
+	pub currency_id: CurrencyId,
+	/// The recipient of the wrapped currency.
+	pub recipient: AccountId,
+	/// The amount locked.
+	pub amount: Balance,
+}
+
+/// The lock event attested by the signers, waiting for more attestations.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Attestation<AccountId> {
+	/// The lock event.
+	pub event: LockEvent<AccountId>,
+	/// The signers which attested the lock event.
+	pub approvals: Vec<AccountId>,
+}
+
+/// The report of a fraudulent attestation.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct FraudReport<AccountId> {
+	/// The reporter, who reserved the report deposit.
+	pub reporter: AccountId,
+	/// The reported signer.
+	pub signer: AccountId,
+	/// The nonce of the lock event which the signer attested but doesn't exist.
+	pub nonce: LockNonce,
+	/// The report deposit.
+	pub deposit: Balance,
+}
+
+/// Mint and burn the wrapped assets in `Currency`.
+pub struct MultiCurrencyBridgeAsset<Currency>(PhantomData<Currency>);
+impl<AccountId, Currency> BridgeAsset<AccountId, Balance> for MultiCurrencyBridgeAsset<Currency>
+where
+	Currency: MultiCurrency<AccountId, CurrencyId = CurrencyId, Balance = Balance>,
+{
+	fn mint(currency_id: CurrencyId, who: &AccountId, amount: Balance) -> DispatchResult {
+		Currency::deposit(currency_id, who, amount)
+	}
+
+	fn burn(currency_id: CurrencyId, who: &AccountId, amount: Balance) -> DispatchResult {
+		Currency::withdraw(currency_id, who, amount)
+	}
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	pub const RESERVE_ID: ReserveIdentifier = ReserveIdentifier::FederatedBridge;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency for the signer bonds and the report deposits.
+		type Currency: NamedReservableCurrency<
+			Self::AccountId,
+			Balance = Balance,
+			ReserveIdentifier = ReserveIdentifier,
+		>;
+
+		/// Mint and burn the wrapped assets.
+		type BridgeAsset: BridgeAsset<Self::AccountId, Balance>;
+
+		/// The guard rejecting the mints while they are paused, e.g. the reserve of the asset
+		/// falls short.
+		type MintGuard: MintGuard;
+
+		/// The origin which may set the signers and the asset caps.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The origin which may resolve the fraud reports.
+		type FraudOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The bond reserved from a signer.
+		#[pallet::constant]
+		type SignerBond: Get<Balance>;
+
+		/// The number of blocks the bond stays reserved after unbonding, so the fraud of the
+		/// signer can still be reported.
+		#[pallet::constant]
+		type BondingDuration: Get<Self::BlockNumber>;
+
+		/// The deposit reserved from a reporter, which is slashed if the report is rejected.
+		#[pallet::constant]
+		type ReportDeposit: Get<Balance>;
+
+		/// The share of the slashed bond paid to the reporter, the rest goes to the treasury.
+		#[pallet::constant]
+		type ReporterReward: Get<Permill>;
+
+		/// The treasury account.
+		#[pallet::constant]
+		type TreasuryAccount: Get<Self::AccountId>;
+
+		/// The maximum number of signers.
+		#[pallet::constant]
+		type MaxSigners: Get<u32>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The signers are fewer than the threshold, more than the maximum, duplicated, not
+		/// bonded or unbonding, or the threshold is zero
+		InvalidSigners,
+		/// The account has bonded
+		AlreadyBonded,
+		/// The account has not bonded
+		NotBonded,
+		/// The account is in the signer set and can't unbond
+		SignerInSet,
+		/// The caller is not a signer
+		NotSigner,
+		/// The asset is not registered
+		AssetNotRegistered,
+		/// The outstanding amount of the asset exceeds the cap
+		ExceedAssetCap,
+		/// The lock event has been minted
+		AlreadyMinted,
+		/// The signer has attested a lock event of the nonce
+		AlreadyAttested,
+		/// The fraud report does not exist
+		ReportNotFound,
+		/// The bridge is halted by the emergency shutdown
+		BridgeHalted,
+		/// The account is unbonding already
+		UnbondingExists,
+		/// The account is not unbonding
+		NotUnbonding,
+		/// The bonding duration has not passed since unbonding
+		BondingDurationNotPassed,
+		/// The account has pending fraud reports and can't withdraw the bond
+		FraudReportPending,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Bonded to be a signer. \[who, bond\]
+		Bonded(T::AccountId, Balance),
+		/// Withdrew the unbonded bond. \[who, bond\]
+		Unbonded(T::AccountId, Balance),
+		/// The signer set is rotated. \[signers, threshold\]
+		SignersUpdated(Vec<T::AccountId>, u32),
+		/// The cap of the asset is updated, `None` if the asset is unregistered. \[currency_id,
+		/// cap\]
+		AssetCapUpdated(CurrencyId, Option<Balance>),
+		/// The signer attested the lock event. \[signer, nonce, event_hash\]
+		Attested(T::AccountId, LockNonce, T::Hash),
+		/// Minted the wrapped asset of the lock event. \[nonce, recipient, currency_id, amount\]
+		Minted(LockNonce, T::AccountId, CurrencyId, Balance),
+		/// Burnt the wrapped asset to release it on the external chain. \[burn_id, who,
+		/// currency_id, amount, dest\]
+		Burnt(BurnId, T::AccountId, CurrencyId, Balance, Vec<u8>),
+		/// Reported the fraud of the signer. \[report_id, reporter, signer, nonce\]
+		FraudReported(ReportId, T::AccountId, T::AccountId, LockNonce),
+		/// The signer is slashed for the fraud. \[report_id, signer, slashed\]
+		SignerSlashed(ReportId, T::AccountId, Balance),
+		/// The fraud report is rejected. \[report_id\]
+		FraudReportRejected(ReportId),
+		/// Unbonding, the bond can be withdrawn at the block. \[who, bond, unlock_at\]
+		Unbonding(T::AccountId, Balance, T::BlockNumber),
+	}
+
+	/// The signer set, sorted.
+	///
+	/// Signers: Vec<AccountId>
+	#[pallet::storage]
+	#[pallet::getter(fn signers)]
+	pub type Signers<T: Config> = StorageValue<_, Vec<T::AccountId>, ValueQuery>;
+
+	/// The number of attestations of signers to mint a lock event.
+	///
+	/// Threshold: u32
+	#[pallet::storage]
+	#[pallet::getter(fn threshold)]
+	pub type Threshold<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// The bonds of the accounts.
+	///
+	/// Bonds: map AccountId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn bonds)]
+	pub type Bonds<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, Balance, ValueQuery>;
+
+	/// The block numbers the bonds of the unbonding accounts can be withdrawn at.
+	///
+	/// UnbondingBonds: map AccountId => Option<BlockNumber>
+	#[pallet::storage]
+	#[pallet::getter(fn unbonding_bonds)]
+	pub type UnbondingBonds<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, T::BlockNumber, OptionQuery>;
+
+	/// The number of the pending fraud reports against the accounts.
+	///
+	/// PendingReportCounts: map AccountId => u32
+	#[pallet::storage]
+	#[pallet::getter(fn pending_report_counts)]
+	pub type PendingReportCounts<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
+	/// The cap of the outstanding amount of the registered assets.
+	///
+	/// AssetCaps: map CurrencyId => Option<Balance>
+	#[pallet::storage]
+	#[pallet::getter(fn asset_caps)]
+	pub type AssetCaps<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Balance, OptionQuery>;
+
+	/// The outstanding amount of the wrapped assets minted by the bridge.
+	///
+	/// Outstanding: map CurrencyId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn outstanding)]
+	pub type Outstanding<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Balance, ValueQuery>;
+
+	/// The lock events waiting for more attestations, by the nonce and the hash of the lock event.
+	///
+	/// Attestations: double_map LockNonce, Hash => Option<Attestation>
+	#[pallet::storage]
+	#[pallet::getter(fn attestations)]
+	pub type Attestations<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, LockNonce, Identity, T::Hash, Attestation<T::AccountId>, OptionQuery>;
+
+	/// The hash of the lock event each signer attested for the nonce.
+	///
+	/// AttestedEvents: double_map LockNonce, AccountId => Option<Hash>
+	#[pallet::storage]
+	#[pallet::getter(fn attested_events)]
+	pub type AttestedEvents<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, LockNonce, Twox64Concat, T::AccountId, T::Hash, OptionQuery>;
+
+	/// The lock events which have been minted.
+	///
+	/// MintedLocks: map LockNonce => Option<()>
+	#[pallet::storage]
+	#[pallet::getter(fn minted_locks)]
+	pub type MintedLocks<T: Config> = StorageMap<_, Twox64Concat, LockNonce, (), OptionQuery>;
+
+	/// The next burn id.
+	///
+	/// NextBurnId: BurnId
+	#[pallet::storage]
+	#[pallet::getter(fn next_burn_id)]
+	pub type NextBurnId<T: Config> = StorageValue<_, BurnId, ValueQuery>;
+
+	/// The next fraud report id.
+	///
+	/// NextReportId: ReportId
+	#[pallet::storage]
+	#[pallet::getter(fn next_report_id)]
+	pub type NextReportId<T: Config> = StorageValue<_, ReportId, ValueQuery>;
+
+	/// The fraud reports waiting to be resolved.
+	///
+	/// FraudReports: map ReportId => Option<FraudReport>
+	#[pallet::storage]
+	#[pallet::getter(fn fraud_reports)]
+	pub type FraudReports<T: Config> = StorageMap<_, Twox64Concat, ReportId, FraudReport<T::AccountId>, OptionQuery>;
+
+	/// Whether the bridge is halted by the emergency shutdown.
+	///
+	/// IsHalted: bool
+	#[pallet::storage]
+	#[pallet::getter(fn is_halted)]
+	pub type IsHalted<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Bond `SignerBond` to be added to the signer set.
+		#[pallet::weight(T::WeightInfo::bond())]
+		#[transactional]
+		pub fn bond(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::bonds(&who).is_zero(), Error::<T>::AlreadyBonded);
+
+			let bond = T::SignerBond::get();
+			T::Currency::reserve_named(&RESERVE_ID, &who, bond)?;
+			Bonds::<T>::insert(&who, bond);
+
+			Self::deposit_event(Event::Bonded(who, bond));
+			Ok(().into())
+		}
+
+		/// Unbond, the caller must not be in the signer set. The bond can be withdrawn
+		/// `BondingDuration` later, and the caller can't be added to the signer set meanwhile.
+		#[pallet::weight(T::WeightInfo::unbond())]
+		#[transactional]
+		pub fn unbond(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let bond = Self::bonds(&who);
+			ensure!(!bond.is_zero(), Error::<T>::NotBonded);
+			ensure!(!Self::is_signer(&who), Error::<T>::SignerInSet);
+			ensure!(!UnbondingBonds::<T>::contains_key(&who), Error::<T>::UnbondingExists);
+
+			let unlock_at = frame_system::Pallet::<T>::block_number().saturating_add(T::BondingDuration::get());
+			UnbondingBonds::<T>::insert(&who, unlock_at);
+
+			Self::deposit_event(Event::Unbonding(who, bond, unlock_at));
+			Ok(().into())
+		}
+
+		/// Rotate the signer set, the pending attestations keep only the approvals of the new
+		/// signers.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `signers`: the new signers, all of them must have bonded.
+		/// - `threshold`: the number of attestations to mint a lock event.
+		#[pallet::weight(T::WeightInfo::set_signers(signers.len() as u32))]
+		#[transactional]
+		pub fn set_signers(
+			origin: OriginFor<T>,
+			mut signers: Vec<T::AccountId>,
+			threshold: u32,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let len = signers.len() as u32;
+			signers.sort();
+			signers.dedup();
+			ensure!(
+				signers.len() as u32 == len
+					&& !threshold.is_zero()
+					&& threshold <= len
+					&& len <= T::MaxSigners::get()
+					&& signers
+						.iter()
+						.all(|who| !Self::bonds(who).is_zero() && !UnbondingBonds::<T>::contains_key(who)),
+				Error::<T>::InvalidSigners
+			);
+
+			Signers::<T>::put(&signers);
+			Threshold::<T>::put(threshold);
+			Self::deposit_event(Event::SignersUpdated(signers, threshold));
+			Ok(().into())
+		}
+
+		/// Register the asset with the cap of its outstanding amount, or unregister it.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: the wrapped currency.
+		/// - `cap`: the cap of the outstanding amount, `None` to unregister the asset.
+		#[pallet::weight(T::WeightInfo::set_asset_cap())]
+		#[transactional]
+		pub fn set_asset_cap(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			cap: Option<Balance>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			AssetCaps::<T>::mutate_exists(currency_id, |maybe_cap| *maybe_cap = cap);
+			Self::deposit_event(Event::AssetCapUpdated(currency_id, cap));
+			Ok(().into())
+		}
+
+		/// Attest the lock event on the external chain, the wrapped asset is minted once the lock
+		/// event is attested by `Threshold` signers. A signer can attest only one lock event for
+		/// each nonce.
+		///
+		/// The dispatch origin of this call must be a signer.
+		///
+		/// - `event`: the lock event.
+		#[pallet::weight(T::WeightInfo::attest(T::MaxSigners::get()))]
+		#[transactional]
+		pub fn attest(origin: OriginFor<T>, event: LockEvent<T::AccountId>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!Self::is_halted(), Error::<T>::BridgeHalted);
+			ensure!(Self::is_signer(&who), Error::<T>::NotSigner);
+			ensure!(!MintedLocks::<T>::contains_key(event.nonce), Error::<T>::AlreadyMinted);
+			ensure!(
+				AssetCaps::<T>::contains_key(event.currency_id),
+				Error::<T>::AssetNotRegistered
+			);
+
+			let nonce = event.nonce;
+			ensure!(
+				!AttestedEvents::<T>::contains_key(nonce, &who),
+				Error::<T>::AlreadyAttested
+			);
+
+			let event_hash = T::Hashing::hash_of(&event);
+			AttestedEvents::<T>::insert(nonce, &who, event_hash);
+			let attestation = Attestations::<T>::mutate(nonce, event_hash, |maybe_attestation| {
+				let attestation = maybe_attestation.get_or_insert_with(|| Attestation {
+					event,
+					approvals: vec![],
+				});
+				attestation.approvals.push(who.clone());
+				attestation.clone()
+			});
+			Self::deposit_event(Event::Attested(who, nonce, event_hash));
+
+			let signers = Self::signers();
+			let approvals = attestation
+				.approvals
+				.iter()
+				.filter(|who| signers.binary_search(who).is_ok())
+				.count() as u32;
+			if approvals >= Self::threshold() {
+				Self::do_mint(attestation.event)?;
+			}
+			Ok(().into())
+		}
+
+		/// Burn the wrapped asset to release it on the external chain.
+		///
+		/// - `currency_id`: the wrapped currency.
+		/// - `amount`: the amount to burn.
+		/// - `dest`: the recipient on the external chain.
+		#[pallet::weight(T::WeightInfo::burn())]
+		#[transactional]
+		pub fn burn(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			#[pallet::compact] amount: Balance,
+			dest: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!Self::is_halted(), Error::<T>::BridgeHalted);
+			ensure!(
+				AssetCaps::<T>::contains_key(currency_id),
+				Error::<T>::AssetNotRegistered
+			);
+
+			T::BridgeAsset::burn(currency_id, &who, amount)?;
+			Outstanding::<T>::mutate(currency_id, |outstanding| {
+				*outstanding = outstanding.saturating_sub(amount)
+			});
+
+			let burn_id = Self::next_burn_id();
+			NextBurnId::<T>::put(burn_id.saturating_add(1));
+			Self::deposit_event(Event::Burnt(burn_id, who, currency_id, amount, dest));
+			Ok(().into())
+		}
+
+		/// Report the signer which attested the lock event that doesn't exist on the external
+		/// chain, `ReportDeposit` is reserved from the caller.
+		///
+		/// - `signer`: the reported signer.
+		/// - `nonce`: the nonce of the fraudulent lock event.
+		#[pallet::weight(T::WeightInfo::report_fraud())]
+		#[transactional]
+		pub fn report_fraud(
+			origin: OriginFor<T>,
+			signer: T::AccountId,
+			nonce: LockNonce,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!Self::bonds(&signer).is_zero(), Error::<T>::NotBonded);
+
+			let deposit = T::ReportDeposit::get();
+			T::Currency::reserve_named(&RESERVE_ID, &who, deposit)?;
+
+			let report_id = Self::next_report_id();
+			NextReportId::<T>::put(report_id.saturating_add(1));
+			PendingReportCounts::<T>::mutate(&signer, |count| *count = count.saturating_add(1));
+			FraudReports::<T>::insert(
+				report_id,
+				FraudReport {
+					reporter: who.clone(),
+					signer: signer.clone(),
+					nonce,
+					deposit,
+				},
+			);
+
+			Self::deposit_event(Event::FraudReported(report_id, who, signer, nonce));
+			Ok(().into())
+		}
+
+		/// Resolve the fraud report. If the fraud is upheld, the bond of the signer is slashed and
+		/// the signer is removed from the signer set, otherwise the report deposit is slashed. The
+		/// slashed funds go to the treasury, except `ReporterReward` of the slashed bond which goes
+		/// to the reporter.
+		///
+		/// The dispatch origin of this call must be `FraudOrigin`.
+		///
+		/// - `report_id`: the id of the fraud report.
+		/// - `is_fraud`: whether the fraud is upheld.
+		#[pallet::weight(T::WeightInfo::resolve_fraud_report())]
+		#[transactional]
+		pub fn resolve_fraud_report(
+			origin: OriginFor<T>,
+			report_id: ReportId,
+			is_fraud: bool,
+		) -> DispatchResultWithPostInfo {
+			T::FraudOrigin::ensure_origin(origin)?;
+			let report = FraudReports::<T>::take(report_id).ok_or(Error::<T>::ReportNotFound)?;
+			PendingReportCounts::<T>::mutate_exists(&report.signer, |maybe_count| {
+				*maybe_count = maybe_count
+					.map(|count| count.saturating_sub(1))
+					.filter(|count| !count.is_zero())
+			});
+			let treasury = T::TreasuryAccount::get();
+
+			if is_fraud {
+				T::Currency::unreserve_named(&RESERVE_ID, &report.reporter, report.deposit);
+
+				let bond = Bonds::<T>::take(&report.signer);
+				let reward = T::ReporterReward::get() * bond;
+				T::Currency::repatriate_reserved_named(
+					&RESERVE_ID,
+					&report.signer,
+					&report.reporter,
+					reward,
+					BalanceStatus::Free,
+				)?;
+				T::Currency::repatriate_reserved_named(
+					&RESERVE_ID,
+					&report.signer,
+					&treasury,
+					bond.saturating_sub(reward),
+					BalanceStatus::Free,
+				)?;
+				UnbondingBonds::<T>::remove(&report.signer);
+				Self::remove_signer(&report.signer);
+
+				Self::deposit_event(Event::SignerSlashed(report_id, report.signer, bond));
+			} else {
+				T::Currency::repatriate_reserved_named(
+					&RESERVE_ID,
+					&report.reporter,
+					&treasury,
+					report.deposit,
+					BalanceStatus::Free,
+				)?;
+
+				Self::deposit_event(Event::FraudReportRejected(report_id));
+			}
+			Ok(().into())
+		}
+
+		/// Withdraw the bond `BondingDuration` after unbonding, there must be no pending fraud
+		/// report against the caller.
+		#[pallet::weight(T::WeightInfo::withdraw_unbonded())]
+		#[transactional]
+		pub fn withdraw_unbonded(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let unlock_at = Self::unbonding_bonds(&who).ok_or(Error::<T>::NotUnbonding)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() >= unlock_at,
+				Error::<T>::BondingDurationNotPassed
+			);
+			ensure!(
+				Self::pending_report_counts(&who).is_zero(),
+				Error::<T>::FraudReportPending
+			);
+
+			let bond = Bonds::<T>::take(&who);
+			T::Currency::unreserve_named(&RESERVE_ID, &who, bond);
+			UnbondingBonds::<T>::remove(&who);
+
+			Self::deposit_event(Event::Unbonded(who, bond));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Whether `who` is in the signer set.
+	pub fn is_signer(who: &T::AccountId) -> bool {
+		Self::signers().binary_search(who).is_ok()
+	}
+
+	/// Remove `who` from the signer set, and lower the threshold to the number of the remaining
+	/// signers if it exceeds it, so the lock events can still be minted.
+	fn remove_signer(who: &T::AccountId) {
+		let mut signers = Self::signers();
+		if let Ok(index) = signers.binary_search(who) {
+			signers.remove(index);
+			let threshold = Self::threshold().min(signers.len() as u32);
+			Signers::<T>::put(&signers);
+			Threshold::<T>::put(threshold);
+			Self::deposit_event(Event::SignersUpdated(signers, threshold));
+		}
+	}
+
+	fn do_mint(event: LockEvent<T::AccountId>) -> DispatchResult {
+		let cap = Self::asset_caps(event.currency_id).ok_or(Error::<T>::AssetNotRegistered)?;
+		Outstanding::<T>::try_mutate(event.currency_id, |outstanding| -> DispatchResult {
+			*outstanding = outstanding.saturating_add(event.amount);
+			ensure!(*outstanding <= cap, Error::<T>::ExceedAssetCap);
+			Ok(())
+		})?;
+
+		T::MintGuard::ensure_can_mint(event.currency_id)?;
+		T::BridgeAsset::mint(event.currency_id, &event.recipient, event.amount)?;
+		// each signer attests one lock event for the nonce, so these are at most the attested
+		// signers.
+		Attestations::<T>::remove_prefix(event.nonce);
+		AttestedEvents::<T>::remove_prefix(event.nonce);
+		MintedLocks::<T>::insert(event.nonce, ());
+
+		Self::deposit_event(Event::Minted(
+			event.nonce,
+			event.recipient,
+			event.currency_id,
+			event.amount,
+		));
+		Ok(())
+	}
+}
+
+impl<T: Config> OnEmergencyShutdown for Pallet<T> {
+	fn on_emergency_shutdown() {
+		IsHalted::<T>::put(true);
+	}
+}
@@ -0,0 +1,198 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Mocks for the federated bridge module.
+
+#![cfg(test)]
+
+use super::*;
+
+use crate as federated_bridge;
+use frame_support::{construct_runtime, ord_parameter_types, parameter_types};
+use frame_system::EnsureSignedBy;
+use orml_traits::parameter_type_with_key;
+use primitives::{Amount, TokenSymbol};
+use sp_core::H256;
+use sp_runtime::{testing::Header, traits::IdentityLookup};
+
+pub type AccountId = u128;
+pub type BlockNumber = u64;
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const CHARLIE: AccountId = 3;
+pub const DAVE: AccountId = 4;
+pub const COUNCIL: AccountId = 100;
+pub const TREASURY: AccountId = 101;
+pub const REPORTER: AccountId = 102;
+pub const RENBTC: CurrencyId = CurrencyId::Token(TokenSymbol::RENBTC);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Runtime {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: Balance = 1;
+	pub const MaxReserves: u32 = 50;
+}
+
+impl pallet_balances::Config for Runtime {
+	type Balance = Balance;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = frame_system::Pallet<Runtime>;
+	type MaxLocks = ();
+	type MaxReserves = MaxReserves;
+	type ReserveIdentifier = ReserveIdentifier;
+	type WeightInfo = ();
+}
+
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: CurrencyId| -> Balance {
+		Default::default()
+	};
+}
+
+impl orml_tokens::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type Amount = Amount;
+	type CurrencyId = CurrencyId;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type OnDust = ();
+	type MaxLocks = ();
+}
+
+ord_parameter_types! {
+	pub const Council: AccountId = COUNCIL;
+	pub const Reporter: AccountId = REPORTER;
+}
+
+parameter_types! {
+	pub const CheckPeriod: BlockNumber = 10;
+	pub const MaxReserveAge: BlockNumber = 100;
+	pub const MaxMonitoredAssets: u32 = 2;
+}
+
+impl module_proof_of_reserve::Config for Runtime {
+	type Event = Event;
+	type Currency = Tokens;
+	type ReporterOrigin = EnsureSignedBy<Reporter, AccountId>;
+	type UpdateOrigin = EnsureSignedBy<Council, AccountId>;
+	type CheckPeriod = CheckPeriod;
+	type MaxReserveAge = MaxReserveAge;
+	type MaxMonitoredAssets = MaxMonitoredAssets;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const SignerBond: Balance = 1000;
+	pub const BondingDuration: u64 = 10;
+	pub const ReportDeposit: Balance = 100;
+	pub const ReporterReward: Permill = Permill::from_percent(10);
+	pub const TreasuryAccount: AccountId = TREASURY;
+	pub const MaxSigners: u32 = 3;
+}
+
+impl Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type BridgeAsset = MultiCurrencyBridgeAsset<Tokens>;
+	type MintGuard = ProofOfReserve;
+	type UpdateOrigin = EnsureSignedBy<Council, AccountId>;
+	type FraudOrigin = EnsureSignedBy<Council, AccountId>;
+	type SignerBond = SignerBond;
+	type BondingDuration = BondingDuration;
+	type ReportDeposit = ReportDeposit;
+	type ReporterReward = ReporterReward;
+	type TreasuryAccount = TreasuryAccount;
+	type MaxSigners = MaxSigners;
+	type WeightInfo = ();
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Tokens: orml_tokens::{Pallet, Storage, Event<T>, Config<T>},
+		FederatedBridge: federated_bridge::{Pallet, Call, Storage, Event<T>},
+		ProofOfReserve: module_proof_of_reserve::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+#[derive(Default)]
+pub struct ExtBuilder;
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap();
+
+		pallet_balances::GenesisConfig::<Runtime> {
+			balances: vec![
+				(ALICE, 10000),
+				(BOB, 10000),
+				(CHARLIE, 10000),
+				(DAVE, 10000),
+				(TREASURY, 1),
+			],
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
@@ -0,0 +1,501 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the federated bridge module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+use orml_traits::MultiCurrency;
+use primitives::TokenSymbol;
+use sp_runtime::traits::{BadOrigin, BlakeTwo256, Hash};
+
+fn lock_event(nonce: LockNonce, amount: Balance) -> LockEvent<AccountId> {
+	LockEvent {
+		nonce,
+		currency_id: RENBTC,
+		recipient: DAVE,
+		amount,
+	}
+}
+
+fn setup_signers() {
+	for who in [ALICE, BOB, CHARLIE].iter() {
+		assert_ok!(FederatedBridge::bond(Origin::signed(*who)));
+	}
+	assert_ok!(FederatedBridge::set_signers(
+		Origin::signed(COUNCIL),
+		vec![CHARLIE, ALICE, BOB],
+		2
+	));
+	assert_ok!(FederatedBridge::set_asset_cap(
+		Origin::signed(COUNCIL),
+		RENBTC,
+		Some(1000)
+	));
+}
+
+#[test]
+fn bond_and_unbond_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(FederatedBridge::bond(Origin::signed(ALICE)));
+		System::assert_last_event(Event::FederatedBridge(crate::Event::Bonded(ALICE, 1000)));
+		assert_eq!(FederatedBridge::bonds(ALICE), 1000);
+		assert_eq!(Balances::reserved_balance(ALICE), 1000);
+		assert_noop!(
+			FederatedBridge::bond(Origin::signed(ALICE)),
+			Error::<Runtime>::AlreadyBonded
+		);
+
+		assert_ok!(FederatedBridge::set_signers(Origin::signed(COUNCIL), vec![ALICE], 1));
+		assert_noop!(
+			FederatedBridge::unbond(Origin::signed(ALICE)),
+			Error::<Runtime>::SignerInSet
+		);
+		assert_noop!(
+			FederatedBridge::unbond(Origin::signed(BOB)),
+			Error::<Runtime>::NotBonded
+		);
+
+		assert_ok!(FederatedBridge::bond(Origin::signed(BOB)));
+		assert_ok!(FederatedBridge::set_signers(Origin::signed(COUNCIL), vec![BOB], 1));
+		assert_noop!(
+			FederatedBridge::withdraw_unbonded(Origin::signed(ALICE)),
+			Error::<Runtime>::NotUnbonding
+		);
+		assert_ok!(FederatedBridge::unbond(Origin::signed(ALICE)));
+		System::assert_last_event(Event::FederatedBridge(crate::Event::Unbonding(ALICE, 1000, 11)));
+		assert_eq!(FederatedBridge::unbonding_bonds(ALICE), Some(11));
+		assert_eq!(Balances::reserved_balance(ALICE), 1000);
+		assert_noop!(
+			FederatedBridge::unbond(Origin::signed(ALICE)),
+			Error::<Runtime>::UnbondingExists
+		);
+		// the unbonding account can't be a signer
+		assert_noop!(
+			FederatedBridge::set_signers(Origin::signed(COUNCIL), vec![ALICE], 1),
+			Error::<Runtime>::InvalidSigners
+		);
+
+		System::set_block_number(10);
+		assert_noop!(
+			FederatedBridge::withdraw_unbonded(Origin::signed(ALICE)),
+			Error::<Runtime>::BondingDurationNotPassed
+		);
+
+		System::set_block_number(11);
+		assert_ok!(FederatedBridge::withdraw_unbonded(Origin::signed(ALICE)));
+		System::assert_last_event(Event::FederatedBridge(crate::Event::Unbonded(ALICE, 1000)));
+		assert_eq!(FederatedBridge::bonds(ALICE), 0);
+		assert_eq!(FederatedBridge::unbonding_bonds(ALICE), None);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_noop!(
+			FederatedBridge::withdraw_unbonded(Origin::signed(ALICE)),
+			Error::<Runtime>::NotUnbonding
+		);
+	});
+}
+
+#[test]
+fn set_signers_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		for who in [ALICE, BOB, CHARLIE].iter() {
+			assert_ok!(FederatedBridge::bond(Origin::signed(*who)));
+		}
+
+		assert_noop!(
+			FederatedBridge::set_signers(Origin::signed(ALICE), vec![ALICE, BOB], 2),
+			BadOrigin
+		);
+		// not bonded
+		assert_noop!(
+			FederatedBridge::set_signers(Origin::signed(COUNCIL), vec![ALICE, DAVE], 2),
+			Error::<Runtime>::InvalidSigners
+		);
+		// duplicated
+		assert_noop!(
+			FederatedBridge::set_signers(Origin::signed(COUNCIL), vec![ALICE, ALICE], 2),
+			Error::<Runtime>::InvalidSigners
+		);
+		// zero threshold
+		assert_noop!(
+			FederatedBridge::set_signers(Origin::signed(COUNCIL), vec![ALICE, BOB], 0),
+			Error::<Runtime>::InvalidSigners
+		);
+		// threshold exceeds the signers
+		assert_noop!(
+			FederatedBridge::set_signers(Origin::signed(COUNCIL), vec![ALICE, BOB], 3),
+			Error::<Runtime>::InvalidSigners
+		);
+		// exceeds MaxSigners
+		assert_ok!(FederatedBridge::bond(Origin::signed(DAVE)));
+		assert_noop!(
+			FederatedBridge::set_signers(Origin::signed(COUNCIL), vec![ALICE, BOB, CHARLIE, DAVE], 2),
+			Error::<Runtime>::InvalidSigners
+		);
+
+		assert_ok!(FederatedBridge::set_signers(
+			Origin::signed(COUNCIL),
+			vec![CHARLIE, ALICE, BOB],
+			2
+		));
+		System::assert_last_event(Event::FederatedBridge(crate::Event::SignersUpdated(
+			vec![ALICE, BOB, CHARLIE],
+			2,
+		)));
+		assert_eq!(FederatedBridge::signers(), vec![ALICE, BOB, CHARLIE]);
+		assert_eq!(FederatedBridge::threshold(), 2);
+	});
+}
+
+#[test]
+fn attest_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup_signers();
+
+		assert_noop!(
+			FederatedBridge::attest(Origin::signed(DAVE), lock_event(0, 100)),
+			Error::<Runtime>::NotSigner
+		);
+		assert_noop!(
+			FederatedBridge::attest(
+				Origin::signed(ALICE),
+				LockEvent {
+					currency_id: CurrencyId::Token(TokenSymbol::DOT),
+					..lock_event(0, 100)
+				}
+			),
+			Error::<Runtime>::AssetNotRegistered
+		);
+
+		let event_hash = BlakeTwo256::hash_of(&lock_event(0, 100));
+		assert_ok!(FederatedBridge::attest(Origin::signed(ALICE), lock_event(0, 100)));
+		System::assert_last_event(Event::FederatedBridge(crate::Event::Attested(ALICE, 0, event_hash)));
+		assert_eq!(
+			FederatedBridge::attestations(0, event_hash),
+			Some(Attestation {
+				event: lock_event(0, 100),
+				approvals: vec![ALICE],
+			})
+		);
+		assert_eq!(FederatedBridge::attested_events(0, ALICE), Some(event_hash));
+		assert_eq!(Tokens::free_balance(RENBTC, &DAVE), 0);
+
+		assert_noop!(
+			FederatedBridge::attest(Origin::signed(ALICE), lock_event(0, 100)),
+			Error::<Runtime>::AlreadyAttested
+		);
+		assert_noop!(
+			FederatedBridge::attest(Origin::signed(ALICE), lock_event(0, 200)),
+			Error::<Runtime>::AlreadyAttested
+		);
+
+		assert_ok!(FederatedBridge::attest(Origin::signed(BOB), lock_event(0, 100)));
+		System::assert_last_event(Event::FederatedBridge(crate::Event::Minted(0, DAVE, RENBTC, 100)));
+		assert_eq!(Tokens::free_balance(RENBTC, &DAVE), 100);
+		assert_eq!(FederatedBridge::outstanding(RENBTC), 100);
+		assert_eq!(FederatedBridge::attestations(0, event_hash), None);
+		assert_eq!(FederatedBridge::attested_events(0, ALICE), None);
+		assert_eq!(FederatedBridge::minted_locks(0), Some(()));
+
+		assert_noop!(
+			FederatedBridge::attest(Origin::signed(CHARLIE), lock_event(0, 100)),
+			Error::<Runtime>::AlreadyMinted
+		);
+	});
+}
+
+#[test]
+fn conflicting_attestation_does_not_block_lock_event() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup_signers();
+
+		// a malicious signer front-runs the lock event with a fake one
+		let fake_event = LockEvent {
+			recipient: CHARLIE,
+			..lock_event(0, 1000)
+		};
+		let fake_hash = BlakeTwo256::hash_of(&fake_event);
+		assert_ok!(FederatedBridge::attest(Origin::signed(CHARLIE), fake_event));
+
+		assert_ok!(FederatedBridge::attest(Origin::signed(ALICE), lock_event(0, 100)));
+		assert_eq!(Tokens::free_balance(RENBTC, &DAVE), 0);
+		assert_ok!(FederatedBridge::attest(Origin::signed(BOB), lock_event(0, 100)));
+		System::assert_last_event(Event::FederatedBridge(crate::Event::Minted(0, DAVE, RENBTC, 100)));
+		assert_eq!(Tokens::free_balance(RENBTC, &DAVE), 100);
+		assert_eq!(Tokens::free_balance(RENBTC, &CHARLIE), 0);
+
+		// all the attestations of the nonce are cleared
+		assert_eq!(FederatedBridge::attestations(0, fake_hash), None);
+		assert_eq!(FederatedBridge::attested_events(0, CHARLIE), None);
+		assert_noop!(
+			FederatedBridge::attest(Origin::signed(CHARLIE), lock_event(0, 100)),
+			Error::<Runtime>::AlreadyMinted
+		);
+	});
+}
+
+#[test]
+fn asset_cap_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup_signers();
+
+		assert_ok!(FederatedBridge::attest(Origin::signed(ALICE), lock_event(0, 800)));
+		assert_ok!(FederatedBridge::attest(Origin::signed(BOB), lock_event(0, 800)));
+		assert_eq!(FederatedBridge::outstanding(RENBTC), 800);
+
+		assert_ok!(FederatedBridge::attest(Origin::signed(ALICE), lock_event(1, 300)));
+		assert_noop!(
+			FederatedBridge::attest(Origin::signed(BOB), lock_event(1, 300)),
+			Error::<Runtime>::ExceedAssetCap
+		);
+
+		// burning frees the cap
+		assert_ok!(FederatedBridge::burn(Origin::signed(DAVE), RENBTC, 100, vec![1, 2, 3]));
+		assert_ok!(FederatedBridge::attest(Origin::signed(BOB), lock_event(1, 300)));
+		assert_eq!(FederatedBridge::outstanding(RENBTC), 1000);
+	});
+}
+
+#[test]
+fn burn_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup_signers();
+		assert_ok!(FederatedBridge::attest(Origin::signed(ALICE), lock_event(0, 100)));
+		assert_ok!(FederatedBridge::attest(Origin::signed(BOB), lock_event(0, 100)));
+
+		assert_noop!(
+			FederatedBridge::burn(
+				Origin::signed(DAVE),
+				CurrencyId::Token(TokenSymbol::DOT),
+				10,
+				vec![1, 2, 3]
+			),
+			Error::<Runtime>::AssetNotRegistered
+		);
+		assert_noop!(
+			FederatedBridge::burn(Origin::signed(DAVE), RENBTC, 101, vec![1, 2, 3]),
+			orml_tokens::Error::<Runtime>::BalanceTooLow
+		);
+
+		assert_ok!(FederatedBridge::burn(Origin::signed(DAVE), RENBTC, 40, vec![1, 2, 3]));
+		System::assert_last_event(Event::FederatedBridge(crate::Event::Burnt(
+			0,
+			DAVE,
+			RENBTC,
+			40,
+			vec![1, 2, 3],
+		)));
+		assert_eq!(Tokens::free_balance(RENBTC, &DAVE), 60);
+		assert_eq!(FederatedBridge::outstanding(RENBTC), 60);
+		assert_eq!(FederatedBridge::next_burn_id(), 1);
+	});
+}
+
+#[test]
+fn mints_rejected_on_reserve_shortfall() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup_signers();
+		assert_ok!(ProofOfReserve::monitor_asset(Origin::signed(COUNCIL), RENBTC, 100));
+		assert_ok!(FederatedBridge::attest(Origin::signed(ALICE), lock_event(0, 100)));
+		assert_ok!(FederatedBridge::attest(Origin::signed(BOB), lock_event(0, 100)));
+
+		// the locked balance falls short of the minted amount
+		assert_ok!(ProofOfReserve::report_reserve(Origin::signed(REPORTER), RENBTC, 50));
+		assert!(ProofOfReserve::paused_mints(RENBTC).is_some());
+
+		assert_ok!(FederatedBridge::attest(Origin::signed(ALICE), lock_event(1, 100)));
+		assert_noop!(
+			FederatedBridge::attest(Origin::signed(BOB), lock_event(1, 100)),
+			module_proof_of_reserve::Error::<Runtime>::MintsPaused
+		);
+		assert_eq!(Tokens::free_balance(RENBTC, &DAVE), 100);
+		assert_eq!(FederatedBridge::outstanding(RENBTC), 100);
+
+		assert_ok!(ProofOfReserve::report_reserve(Origin::signed(REPORTER), RENBTC, 200));
+		assert_ok!(ProofOfReserve::resume_mints(Origin::signed(COUNCIL), RENBTC));
+		assert_ok!(FederatedBridge::attest(Origin::signed(BOB), lock_event(1, 100)));
+		assert_eq!(Tokens::free_balance(RENBTC, &DAVE), 200);
+	});
+}
+
+#[test]
+fn halted_on_emergency_shutdown() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup_signers();
+		assert_ok!(FederatedBridge::attest(Origin::signed(ALICE), lock_event(0, 100)));
+		assert_ok!(FederatedBridge::attest(Origin::signed(BOB), lock_event(0, 100)));
+
+		<FederatedBridge as OnEmergencyShutdown>::on_emergency_shutdown();
+		assert_eq!(FederatedBridge::is_halted(), true);
+		assert_noop!(
+			FederatedBridge::attest(Origin::signed(ALICE), lock_event(1, 100)),
+			Error::<Runtime>::BridgeHalted
+		);
+		assert_noop!(
+			FederatedBridge::burn(Origin::signed(DAVE), RENBTC, 40, vec![1, 2, 3]),
+			Error::<Runtime>::BridgeHalted
+		);
+	});
+}
+
+#[test]
+fn rotated_out_signers_do_not_count() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup_signers();
+		assert_ok!(FederatedBridge::attest(Origin::signed(ALICE), lock_event(0, 100)));
+
+		assert_ok!(FederatedBridge::bond(Origin::signed(DAVE)));
+		assert_ok!(FederatedBridge::set_signers(
+			Origin::signed(COUNCIL),
+			vec![BOB, CHARLIE, DAVE],
+			2
+		));
+		assert_noop!(
+			FederatedBridge::attest(Origin::signed(ALICE), lock_event(1, 100)),
+			Error::<Runtime>::NotSigner
+		);
+
+		assert_ok!(FederatedBridge::attest(Origin::signed(BOB), lock_event(0, 100)));
+		assert_eq!(Tokens::free_balance(RENBTC, &DAVE), 0);
+
+		assert_ok!(FederatedBridge::attest(Origin::signed(CHARLIE), lock_event(0, 100)));
+		assert_eq!(Tokens::free_balance(RENBTC, &DAVE), 100);
+	});
+}
+
+#[test]
+fn fraud_report_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup_signers();
+
+		assert_noop!(
+			FederatedBridge::report_fraud(Origin::signed(DAVE), TREASURY, 0),
+			Error::<Runtime>::NotBonded
+		);
+
+		assert_ok!(FederatedBridge::report_fraud(Origin::signed(DAVE), ALICE, 5));
+		System::assert_last_event(Event::FederatedBridge(crate::Event::FraudReported(0, DAVE, ALICE, 5)));
+		assert_eq!(Balances::reserved_balance(DAVE), 100);
+		assert_ok!(FederatedBridge::report_fraud(Origin::signed(DAVE), BOB, 6));
+		assert_eq!(Balances::reserved_balance(DAVE), 200);
+
+		assert_noop!(
+			FederatedBridge::resolve_fraud_report(Origin::signed(DAVE), 0, true),
+			BadOrigin
+		);
+		assert_noop!(
+			FederatedBridge::resolve_fraud_report(Origin::signed(COUNCIL), 2, true),
+			Error::<Runtime>::ReportNotFound
+		);
+
+		// the fraud is upheld
+		assert_ok!(FederatedBridge::resolve_fraud_report(Origin::signed(COUNCIL), 0, true));
+		System::assert_last_event(Event::FederatedBridge(crate::Event::SignerSlashed(0, ALICE, 1000)));
+		assert_eq!(FederatedBridge::bonds(ALICE), 0);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(Balances::free_balance(ALICE), 9000);
+		assert_eq!(Balances::free_balance(DAVE), 10000);
+		assert_eq!(Balances::reserved_balance(DAVE), 100);
+		assert_eq!(Balances::free_balance(TREASURY), 901);
+		assert_eq!(FederatedBridge::signers(), vec![BOB, CHARLIE]);
+		assert_eq!(FederatedBridge::fraud_reports(0), None);
+
+		// the fraud is rejected
+		assert_ok!(FederatedBridge::resolve_fraud_report(Origin::signed(COUNCIL), 1, false));
+		System::assert_last_event(Event::FederatedBridge(crate::Event::FraudReportRejected(1)));
+		assert_eq!(FederatedBridge::bonds(BOB), 1000);
+		assert_eq!(Balances::free_balance(DAVE), 10000);
+		assert_eq!(Balances::reserved_balance(DAVE), 0);
+		assert_eq!(Balances::free_balance(TREASURY), 1001);
+		assert_eq!(FederatedBridge::signers(), vec![BOB, CHARLIE]);
+	});
+}
+
+#[test]
+fn pending_fraud_report_blocks_withdrawal() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(FederatedBridge::bond(Origin::signed(ALICE)));
+		assert_ok!(FederatedBridge::bond(Origin::signed(BOB)));
+		assert_ok!(FederatedBridge::unbond(Origin::signed(ALICE)));
+		assert_ok!(FederatedBridge::unbond(Origin::signed(BOB)));
+
+		// the unbonding accounts can still be reported
+		assert_ok!(FederatedBridge::report_fraud(Origin::signed(DAVE), ALICE, 5));
+		assert_ok!(FederatedBridge::report_fraud(Origin::signed(DAVE), ALICE, 6));
+		assert_ok!(FederatedBridge::report_fraud(Origin::signed(DAVE), BOB, 7));
+		assert_eq!(FederatedBridge::pending_report_counts(ALICE), 2);
+
+		System::set_block_number(11);
+		assert_noop!(
+			FederatedBridge::withdraw_unbonded(Origin::signed(ALICE)),
+			Error::<Runtime>::FraudReportPending
+		);
+
+		assert_ok!(FederatedBridge::resolve_fraud_report(Origin::signed(COUNCIL), 0, false));
+		assert_eq!(FederatedBridge::pending_report_counts(ALICE), 1);
+		assert_noop!(
+			FederatedBridge::withdraw_unbonded(Origin::signed(ALICE)),
+			Error::<Runtime>::FraudReportPending
+		);
+		assert_ok!(FederatedBridge::resolve_fraud_report(Origin::signed(COUNCIL), 1, false));
+		assert_eq!(FederatedBridge::pending_report_counts(ALICE), 0);
+		assert_ok!(FederatedBridge::withdraw_unbonded(Origin::signed(ALICE)));
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+
+		// the slashed account is not unbonding anymore
+		assert_ok!(FederatedBridge::resolve_fraud_report(Origin::signed(COUNCIL), 2, true));
+		assert_eq!(FederatedBridge::bonds(BOB), 0);
+		assert_eq!(FederatedBridge::unbonding_bonds(BOB), None);
+		assert_eq!(FederatedBridge::pending_report_counts(BOB), 0);
+		assert_noop!(
+			FederatedBridge::withdraw_unbonded(Origin::signed(BOB)),
+			Error::<Runtime>::NotUnbonding
+		);
+	});
+}
+
+#[test]
+fn slashing_signer_lowers_threshold() {
+	ExtBuilder::default().build().execute_with(|| {
+		setup_signers();
+		assert_ok!(FederatedBridge::set_signers(
+			Origin::signed(COUNCIL),
+			vec![ALICE, BOB],
+			2
+		));
+
+		assert_ok!(FederatedBridge::report_fraud(Origin::signed(DAVE), ALICE, 5));
+		assert_ok!(FederatedBridge::resolve_fraud_report(Origin::signed(COUNCIL), 0, true));
+		System::assert_has_event(Event::FederatedBridge(crate::Event::SignersUpdated(vec![BOB], 1)));
+		assert_eq!(FederatedBridge::signers(), vec![BOB]);
+		assert_eq!(FederatedBridge::threshold(), 1);
+
+		// the remaining signer can still mint
+		assert_ok!(FederatedBridge::attest(Origin::signed(BOB), lock_event(0, 100)));
+		assert_eq!(Tokens::free_balance(RENBTC, &DAVE), 100);
+
+		// the slashed account out of the signer set doesn't change the signer set
+		assert_ok!(FederatedBridge::report_fraud(Origin::signed(DAVE), CHARLIE, 6));
+		assert_ok!(FederatedBridge::resolve_fraud_report(Origin::signed(COUNCIL), 1, true));
+		assert_eq!(FederatedBridge::signers(), vec![BOB]);
+		assert_eq!(FederatedBridge::threshold(), 1);
+	});
+}
@@ -0,0 +1,314 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Fee Rebates Module
+//!
+//! ## Overview
+//!
+//! High-volume traders get a part of their transaction fees rebated. During each period the
+//! module tracks, for each account, the volume of its DEX swaps valued by the price source and the
+//! transaction fees it paid. The swaps made to pay the transaction fees are not counted as volume.
+//! When the period ends, the accounts whose volume reaches the threshold of a rebate tier get the
+//! rebate rate of the highest such tier of their fees back from the rebate pot. The rebate pot is
+//! the module account, which the runtime funds by routing a share of the DEX swap fees to it. The
+//! rebates are paid in the following blocks, limited per block. The rebate tiers are configured by
+//! governance.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{
+	pallet_prelude::*,
+	traits::{Currency, ExistenceRequirement},
+	transactional, PalletId,
+};
+use frame_system::pallet_prelude::*;
+use primitives::{Balance, CurrencyId};
+use sp_runtime::{
+	traits::{AccountIdConversion, Zero},
+	FixedPointNumber, Permill, RuntimeDebug,
+};
+use sp_std::prelude::*;
+use support::{OnSwap, OnTransactionFeePaid, PriceProvider};
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+/// The index of rebate period.
+pub type PeriodIndex = u32;
+
+/// The rebate tier of the accounts whose trading volume reaches the threshold.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct RebateTier {
+	/// The min trading volume in a period to reach the tier.
+	pub volume_threshold: Balance,
+	/// The rate of the transaction fees rebated.
+	pub rebate_rate: Permill,
+}
+
+/// The trading stats of an account in a period.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct TradingStats {
+	/// The trading volume of DEX swaps.
+	pub volume: Balance,
+	/// The transaction fees paid.
+	pub fees: Balance,
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The native currency to rebate the transaction fees.
+		type Currency: Currency<Self::AccountId, Balance = Balance>;
+
+		/// The price source to value the trading volume.
+		type PriceSource: PriceProvider<CurrencyId>;
+
+		/// The length of rebate period in blocks.
+		#[pallet::constant]
+		type RebatePeriod: Get<Self::BlockNumber>;
+
+		/// The max number of rebate tiers.
+		#[pallet::constant]
+		type MaxRebateTiers: Get<u32>;
+
+		/// The max number of rebates paid in a block.
+		#[pallet::constant]
+		type MaxRebatesPerBlock: Get<u32>;
+
+		/// The fee rebates module id, keeps the rebate pot.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The origin which may update the rebate tiers.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Exceed the max number of rebate tiers
+		TooManyTiers,
+		/// The volume thresholds of the tiers are not ascending
+		InvalidTiers,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Updated the rebate tiers. \[tiers\]
+		RebateTiersUpdated(Vec<RebateTier>),
+		/// The rebate period ended. \[period\]
+		PeriodEnded(PeriodIndex),
+		/// Rebated the transaction fees of account. \[who, period, amount\]
+		Rebated(T::AccountId, PeriodIndex, Balance),
+		/// Failed to rebate the transaction fees of account as the pot is
+		/// insufficient. \[who, period, amount\]
+		RebateFailed(T::AccountId, PeriodIndex, Balance),
+	}
+
+	/// The rebate tiers, in ascending order of volume threshold.
+	///
+	/// RebateTiers: Vec<RebateTier>
+	#[pallet::storage]
+	#[pallet::getter(fn rebate_tiers)]
+	pub type RebateTiers<T: Config> = StorageValue<_, Vec<RebateTier>, ValueQuery>;
+
+	/// The current rebate period.
+	///
+	/// CurrentPeriod: PeriodIndex
+	#[pallet::storage]
+	#[pallet::getter(fn current_period)]
+	pub type CurrentPeriod<T: Config> = StorageValue<_, PeriodIndex, ValueQuery>;
+
+	/// The earliest ended period whose rebates are not all paid.
+	///
+	/// NextRebatePeriod: PeriodIndex
+	#[pallet::storage]
+	#[pallet::getter(fn next_rebate_period)]
+	pub type NextRebatePeriod<T: Config> = StorageValue<_, PeriodIndex, ValueQuery>;
+
+	/// The trading stats of accounts in the periods.
+	///
+	/// Stats: double_map PeriodIndex, AccountId => TradingStats
+	#[pallet::storage]
+	#[pallet::getter(fn stats)]
+	pub type Stats<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, PeriodIndex, Twox64Concat, T::AccountId, TradingStats, ValueQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		/// Start a new period every `RebatePeriod` blocks, then pay the
+		/// rebates of the ended periods.
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			if (now % T::RebatePeriod::get()).is_zero() {
+				let period = Self::current_period();
+				CurrentPeriod::<T>::put(period.saturating_add(1));
+				Self::deposit_event(Event::PeriodEnded(period));
+			}
+
+			T::WeightInfo::on_initialize(Self::pay_rebates(T::MaxRebatesPerBlock::get()))
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Update the rebate tiers, which take effect on the periods whose
+		/// rebates are not paid.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `tiers`: the rebate tiers, in ascending order of volume threshold.
+		#[pallet::weight(T::WeightInfo::set_rebate_tiers())]
+		#[transactional]
+		pub fn set_rebate_tiers(origin: OriginFor<T>, tiers: Vec<RebateTier>) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(tiers.len() as u32 <= T::MaxRebateTiers::get(), Error::<T>::TooManyTiers);
+			ensure!(
+				tiers
+					.windows(2)
+					.all(|pair| pair[0].volume_threshold < pair[1].volume_threshold),
+				Error::<T>::InvalidTiers
+			);
+
+			RebateTiers::<T>::put(&tiers);
+			Self::deposit_event(Event::RebateTiersUpdated(tiers));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The account of the rebate pot.
+	pub fn account_id() -> T::AccountId {
+		T::PalletId::get().into_account()
+	}
+
+	/// The trading volume of the swap, valued by the price of the supply
+	/// currency, or of the target currency if it's unavailable.
+	pub fn swap_volume(path: &[CurrencyId], supply_amount: Balance, target_amount: Balance) -> Balance {
+		let value = |currency_id: Option<&CurrencyId>, amount: Balance| {
+			currency_id
+				.and_then(|currency_id| T::PriceSource::get_price(*currency_id))
+				.map(|price| price.saturating_mul_int(amount))
+		};
+		value(path.first(), supply_amount)
+			.or_else(|| value(path.last(), target_amount))
+			.unwrap_or_default()
+	}
+
+	/// The rebate of the trading stats, by the highest tier reached.
+	pub fn rebate_of(tiers: &[RebateTier], stats: &TradingStats) -> Balance {
+		tiers
+			.iter()
+			.rev()
+			.find(|tier| stats.volume >= tier.volume_threshold)
+			.map_or(Zero::zero(), |tier| tier.rebate_rate * stats.fees)
+	}
+
+	/// Pay at most `limit` rebates of the ended periods, returns the number of
+	/// accounts processed.
+	fn pay_rebates(limit: u32) -> u32 {
+		let current_period = Self::current_period();
+		let pot = Self::account_id();
+		let mut tiers = None;
+		let mut count: u32 = 0;
+
+		while count < limit {
+			let period = Self::next_rebate_period();
+			if period >= current_period {
+				break;
+			}
+
+			let tiers = tiers.get_or_insert_with(Self::rebate_tiers);
+			let mut drained: u32 = 0;
+			for (who, stats) in Stats::<T>::drain_prefix(period).take((limit - count) as usize) {
+				drained += 1;
+				let amount = Self::rebate_of(tiers, &stats);
+				if amount.is_zero() {
+					continue;
+				}
+
+				if T::Currency::transfer(&pot, &who, amount, ExistenceRequirement::KeepAlive).is_ok() {
+					Self::deposit_event(Event::Rebated(who, period, amount));
+				} else {
+					Self::deposit_event(Event::RebateFailed(who, period, amount));
+				}
+			}
+
+			count += drained;
+			if count < limit {
+				// all the rebates of the period are paid
+				NextRebatePeriod::<T>::put(period.saturating_add(1));
+			}
+		}
+
+		count
+	}
+}
+
+impl<T: Config> OnSwap<T::AccountId, CurrencyId, Balance> for Pallet<T> {
+	fn on_swap(who: &T::AccountId, path: &[CurrencyId], supply_amount: Balance, target_amount: Balance) {
+		let volume = Self::swap_volume(path, supply_amount, target_amount);
+		if !volume.is_zero() {
+			Stats::<T>::mutate(Self::current_period(), who, |stats| {
+				stats.volume = stats.volume.saturating_add(volume)
+			});
+		}
+	}
+}
+
+impl<T: Config> OnTransactionFeePaid<T::AccountId, Balance> for Pallet<T> {
+	fn on_fee_paid(who: &T::AccountId, fee: Balance) {
+		if !fee.is_zero() {
+			Stats::<T>::mutate(Self::current_period(), who, |stats| {
+				stats.fees = stats.fees.saturating_add(fee)
+			});
+		}
+	}
+
+	/// The swaps to pay the fees are not trading volume, remove the volume
+	/// counted by `on_swap`.
+	fn on_fee_swapped(who: &T::AccountId, path: &[CurrencyId], supply_amount: Balance, target_amount: Balance) {
+		let volume = Self::swap_volume(path, supply_amount, target_amount);
+		if !volume.is_zero() {
+			Stats::<T>::mutate_exists(Self::current_period(), who, |maybe_stats| {
+				if let Some(stats) = maybe_stats {
+					stats.volume = stats.volume.saturating_sub(volume);
+					if *stats == TradingStats::default() {
+						*maybe_stats = None;
+					}
+				}
+			});
+		}
+	}
+}
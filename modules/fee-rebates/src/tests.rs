@@ -0,0 +1,160 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the fee rebates module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{
+	Balances, Event, ExtBuilder, FeeRebatesModule, Origin, Runtime, System, ALICE, AUSD, BOB, CHARLIE, DOT, RENBTC,
+};
+use sp_runtime::traits::BadOrigin;
+
+fn tiers() -> Vec<RebateTier> {
+	vec![
+		RebateTier {
+			volume_threshold: 100,
+			rebate_rate: Permill::from_percent(10),
+		},
+		RebateTier {
+			volume_threshold: 1000,
+			rebate_rate: Permill::from_percent(50),
+		},
+	]
+}
+
+#[test]
+fn set_rebate_tiers_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			FeeRebatesModule::set_rebate_tiers(Origin::signed(BOB), tiers()),
+			BadOrigin
+		);
+		assert_noop!(
+			FeeRebatesModule::set_rebate_tiers(Origin::signed(ALICE), [tiers(), tiers()].concat()),
+			Error::<Runtime>::TooManyTiers
+		);
+		assert_noop!(
+			FeeRebatesModule::set_rebate_tiers(Origin::signed(ALICE), tiers().into_iter().rev().collect()),
+			Error::<Runtime>::InvalidTiers
+		);
+
+		assert_ok!(FeeRebatesModule::set_rebate_tiers(Origin::signed(ALICE), tiers()));
+		System::assert_last_event(Event::FeeRebatesModule(crate::Event::RebateTiersUpdated(tiers())));
+		assert_eq!(FeeRebatesModule::rebate_tiers(), tiers());
+	});
+}
+
+#[test]
+fn track_volume_and_fees_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		// valued by the supply currency
+		FeeRebatesModule::on_swap(&ALICE, &[DOT, AUSD], 10, 95);
+		// valued by the target currency
+		FeeRebatesModule::on_swap(&ALICE, &[RENBTC, DOT], 1, 2);
+		// no price
+		FeeRebatesModule::on_swap(&ALICE, &[RENBTC], 1, 1);
+		FeeRebatesModule::on_fee_paid(&ALICE, 30);
+		FeeRebatesModule::on_fee_paid(&BOB, 20);
+
+		assert_eq!(
+			FeeRebatesModule::stats(0, ALICE),
+			TradingStats { volume: 120, fees: 30 }
+		);
+		assert_eq!(FeeRebatesModule::stats(0, BOB), TradingStats { volume: 0, fees: 20 });
+	});
+}
+
+#[test]
+fn fee_swaps_are_not_volume() {
+	ExtBuilder::default().build().execute_with(|| {
+		FeeRebatesModule::on_swap(&ALICE, &[DOT, AUSD], 10, 95);
+		FeeRebatesModule::on_swap(&ALICE, &[AUSD, DOT], 20, 2);
+		FeeRebatesModule::on_fee_swapped(&ALICE, &[AUSD, DOT], 20, 2);
+		FeeRebatesModule::on_fee_paid(&ALICE, 2);
+		assert_eq!(FeeRebatesModule::stats(0, ALICE), TradingStats { volume: 100, fees: 2 });
+
+		FeeRebatesModule::on_swap(&BOB, &[AUSD, DOT], 20, 2);
+		FeeRebatesModule::on_fee_swapped(&BOB, &[AUSD, DOT], 20, 2);
+		assert!(!Stats::<Runtime>::contains_key(0, BOB));
+	});
+}
+
+#[test]
+fn rebate_of_work() {
+	let stats = |volume| TradingStats { volume, fees: 1000 };
+	assert_eq!(FeeRebatesModule::rebate_of(&tiers(), &stats(99)), 0);
+	assert_eq!(FeeRebatesModule::rebate_of(&tiers(), &stats(100)), 100);
+	assert_eq!(FeeRebatesModule::rebate_of(&tiers(), &stats(999)), 100);
+	assert_eq!(FeeRebatesModule::rebate_of(&tiers(), &stats(1000)), 500);
+	assert_eq!(FeeRebatesModule::rebate_of(&[], &stats(1000)), 0);
+}
+
+#[test]
+fn pay_rebates_at_period_end() {
+	ExtBuilder::default().pot(1000).build().execute_with(|| {
+		assert_ok!(FeeRebatesModule::set_rebate_tiers(Origin::signed(ALICE), tiers()));
+		FeeRebatesModule::on_swap(&ALICE, &[AUSD, DOT], 150, 15);
+		FeeRebatesModule::on_fee_paid(&ALICE, 100);
+		FeeRebatesModule::on_swap(&BOB, &[AUSD, DOT], 2000, 200);
+		FeeRebatesModule::on_fee_paid(&BOB, 100);
+		FeeRebatesModule::on_swap(&CHARLIE, &[AUSD, DOT], 50, 5);
+		FeeRebatesModule::on_fee_paid(&CHARLIE, 100);
+
+		FeeRebatesModule::on_initialize(9);
+		assert_eq!(FeeRebatesModule::current_period(), 0);
+
+		// the period ends, at most 2 rebates are paid in a block
+		FeeRebatesModule::on_initialize(10);
+		System::assert_has_event(Event::FeeRebatesModule(crate::Event::PeriodEnded(0)));
+		assert_eq!(FeeRebatesModule::current_period(), 1);
+		assert_eq!(FeeRebatesModule::next_rebate_period(), 0);
+		assert_eq!(Stats::<Runtime>::iter_prefix(0).count(), 1);
+
+		// the stats in the new period
+		FeeRebatesModule::on_fee_paid(&ALICE, 100);
+
+		FeeRebatesModule::on_initialize(11);
+		assert_eq!(FeeRebatesModule::next_rebate_period(), 1);
+		assert_eq!(Stats::<Runtime>::iter_prefix(0).count(), 0);
+		assert_eq!(Balances::free_balance(ALICE), 1000 + 10);
+		assert_eq!(Balances::free_balance(BOB), 1000 + 50);
+		assert_eq!(Balances::free_balance(CHARLIE), 1000);
+		assert_eq!(Balances::free_balance(FeeRebatesModule::account_id()), 1000 - 60);
+		System::assert_has_event(Event::FeeRebatesModule(crate::Event::Rebated(ALICE, 0, 10)));
+		System::assert_has_event(Event::FeeRebatesModule(crate::Event::Rebated(BOB, 0, 50)));
+		assert_eq!(FeeRebatesModule::stats(1, ALICE), TradingStats { volume: 0, fees: 100 });
+	});
+}
+
+#[test]
+fn rebate_failed_when_pot_is_insufficient() {
+	ExtBuilder::default().pot(10).build().execute_with(|| {
+		assert_ok!(FeeRebatesModule::set_rebate_tiers(Origin::signed(ALICE), tiers()));
+		FeeRebatesModule::on_swap(&BOB, &[AUSD, DOT], 2000, 200);
+		FeeRebatesModule::on_fee_paid(&BOB, 100);
+
+		FeeRebatesModule::on_initialize(10);
+		System::assert_last_event(Event::FeeRebatesModule(crate::Event::RebateFailed(BOB, 0, 50)));
+		assert_eq!(Balances::free_balance(BOB), 1000);
+		assert_eq!(Balances::free_balance(FeeRebatesModule::account_id()), 10);
+		assert_eq!(FeeRebatesModule::next_rebate_period(), 1);
+	});
+}
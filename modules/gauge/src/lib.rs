@@ -0,0 +1,341 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Gauge Module
+//!
+//! ## Overview
+//!
+//! The emission budget of native currency per period from `EmissionBudget` is allocated across
+//! the incentive pools (gauges) by weight voting. Voters allocate their voting power from the `VotingPowerProvider`
+//! across gauges by percentages, the vote weight of a gauge is the sum of the voting power at the
+//! time of voting multiplied by the percentages. At the beginning of each `VotingPeriod`, the
+//! emission budget is allocated to the gauges in proportion to their vote weights and applied to
+//! the incentive reward amounts of the pools, then the vote weights are reset. The votes only count
+//! for the period in which they are cast, voters need to vote again every period with their
+//! current voting power. Every gauge begins a new epoch when it's added and when the emission is
+//! allocated, the votes of the previous epochs don't count and are cleared lazily when the voter
+//! votes again, so that neither the allocation nor the removal of a gauge iterates the votes. The balances counted in the voting power are locked under
+//! `GAUGE_VOTE_LOCK_ID` when voting, so they can't be moved to vote again from another account,
+//! the lock is removed when the voter revokes the votes.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{log, pallet_prelude::*, traits::LockIdentifier, transactional};
+use frame_system::pallet_prelude::*;
+use primitives::Balance;
+use sp_runtime::{
+	helpers_128bit::multiply_by_rational,
+	traits::{CheckedAdd, MaybeSerializeDeserialize, Zero},
+	Permill,
+};
+use sp_std::{fmt::Debug, prelude::*};
+use support::{IncentivesManager, VotingPowerProvider};
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+pub const GAUGE_VOTE_LOCK_ID: LockIdentifier = *b"aca/gvte";
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The incentive pool type of gauges.
+		type PoolId: Parameter + Member + MaybeSerializeDeserialize + Debug + Ord;
+
+		/// The provider of the voting power.
+		type VotingPower: VotingPowerProvider<Self::AccountId, Balance>;
+
+		/// The incentives to apply the allocated emission.
+		type Incentives: IncentivesManager<Self::PoolId, Balance>;
+
+		/// The emission budget of native currency per period of the
+		/// incentive rewards.
+		type EmissionBudget: Get<Balance>;
+
+		/// The origin which may update the gauges.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The period to allocate the emission budget.
+		#[pallet::constant]
+		type VotingPeriod: Get<Self::BlockNumber>;
+
+		/// The maximum number of gauges.
+		#[pallet::constant]
+		type MaxGauges: Get<u32>;
+
+		/// The maximum number of gauges a voter can vote for.
+		#[pallet::constant]
+		type MaxVotes: Get<u32>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The gauge exists already
+		GaugeExists,
+		/// The gauge does not exist
+		GaugeNotExists,
+		/// Exceed the maximum number of gauges
+		TooManyGauges,
+		/// Exceed the maximum number of votes
+		TooManyVotes,
+		/// The sum of the percentages exceeds 100%
+		InvalidPercentages,
+		/// The voter has no voting power
+		NoVotingPower,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Gauge added. \[pool_id\]
+		GaugeAdded(T::PoolId),
+		/// Gauge removed. \[pool_id\]
+		GaugeRemoved(T::PoolId),
+		/// Voted for gauges. \[who, voting_power, votes\]
+		Voted(T::AccountId, Balance, Vec<(T::PoolId, Permill)>),
+		/// Emission allocated to the gauge. \[pool_id, reward_amount_per_period\]
+		EmissionAllocated(T::PoolId, Balance),
+	}
+
+	/// The vote weights of gauges.
+	///
+	/// Gauges: map PoolId => Option<Balance>
+	#[pallet::storage]
+	#[pallet::getter(fn gauges)]
+	pub type Gauges<T: Config> = StorageMap<_, Twox64Concat, T::PoolId, Balance, OptionQuery>;
+
+	/// The number of gauges.
+	///
+	/// GaugeCount: u32
+	#[pallet::storage]
+	#[pallet::getter(fn gauge_count)]
+	pub type GaugeCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// The current epochs of gauges.
+	///
+	/// GaugeEpochs: map PoolId => u32
+	#[pallet::storage]
+	#[pallet::getter(fn gauge_epochs)]
+	pub type GaugeEpochs<T: Config> = StorageMap<_, Twox64Concat, T::PoolId, u32, ValueQuery>;
+
+	/// The number of epochs begun.
+	///
+	/// EpochCount: u32
+	#[pallet::storage]
+	#[pallet::getter(fn epoch_count)]
+	pub type EpochCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// The epochs and vote weights of voters in gauges, only the votes of
+	/// the current epoch of the gauge count.
+	///
+	/// GaugeVotes: double_map PoolId, AccountId => (u32, Balance)
+	#[pallet::storage]
+	#[pallet::getter(fn gauge_votes)]
+	pub type GaugeVotes<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::PoolId, Twox64Concat, T::AccountId, (u32, Balance), ValueQuery>;
+
+	/// The voting power and votes of voter.
+	///
+	/// Votes: map AccountId => Option<(Balance, Vec<(PoolId, Permill)>)>
+	#[pallet::storage]
+	#[pallet::getter(fn votes)]
+	pub type Votes<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, (Balance, Vec<(T::PoolId, Permill)>), OptionQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			if now % T::VotingPeriod::get() == Zero::zero() {
+				let count = Self::allocate_emission();
+				T::WeightInfo::on_initialize(count)
+			} else {
+				0
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Add the incentive pool as gauge.
+		#[pallet::weight(T::WeightInfo::add_gauge())]
+		#[transactional]
+		pub fn add_gauge(origin: OriginFor<T>, pool_id: T::PoolId) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(Self::gauges(&pool_id).is_none(), Error::<T>::GaugeExists);
+			ensure!(Self::gauge_count() < T::MaxGauges::get(), Error::<T>::TooManyGauges);
+
+			Gauges::<T>::insert(&pool_id, Balance::zero());
+			GaugeEpochs::<T>::insert(&pool_id, Self::begin_epoch());
+			GaugeCount::<T>::mutate(|count| *count += 1);
+			Self::deposit_event(Event::GaugeAdded(pool_id));
+			Ok(().into())
+		}
+
+		/// Remove the gauge, and stop the incentive rewards of the pool. The
+		/// votes for it don't count any more, and are cleared lazily.
+		#[pallet::weight(T::WeightInfo::remove_gauge())]
+		#[transactional]
+		pub fn remove_gauge(origin: OriginFor<T>, pool_id: T::PoolId) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(Self::gauges(&pool_id).is_some(), Error::<T>::GaugeNotExists);
+
+			Gauges::<T>::remove(&pool_id);
+			GaugeEpochs::<T>::remove(&pool_id);
+			GaugeCount::<T>::mutate(|count| *count = count.saturating_sub(1));
+			T::Incentives::set_incentive_reward_amount(&pool_id, Zero::zero())?;
+			Self::deposit_event(Event::GaugeRemoved(pool_id));
+			Ok(().into())
+		}
+
+		/// Allocate the current voting power across gauges by percentages,
+		/// replace the previous votes of the current voting period. The
+		/// counted balances are locked until revoked, vote with empty `votes`
+		/// to revoke and unlock them.
+		#[pallet::weight(T::WeightInfo::vote(votes.len() as u32))]
+		#[transactional]
+		pub fn vote(origin: OriginFor<T>, votes: Vec<(T::PoolId, Permill)>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(votes.len() as u32 <= T::MaxVotes::get(), Error::<T>::TooManyVotes);
+			votes
+				.iter()
+				.try_fold(Permill::zero(), |total, (_, percentage)| total.checked_add(percentage))
+				.ok_or(Error::<T>::InvalidPercentages)?;
+
+			// revoke the previous votes
+			if let Some((_, previous_votes)) = Votes::<T>::take(&who) {
+				for (pool_id, _) in previous_votes {
+					let (epoch, vote_weight) = GaugeVotes::<T>::take(&pool_id, &who);
+					if epoch != Self::gauge_epochs(&pool_id) {
+						continue;
+					}
+					Gauges::<T>::mutate_exists(&pool_id, |maybe_weight| {
+						if let Some(weight) = maybe_weight {
+							*weight = weight.saturating_sub(vote_weight);
+						}
+					});
+				}
+			}
+
+			if votes.is_empty() {
+				T::VotingPower::unlock_voting_power(GAUGE_VOTE_LOCK_ID, &who)?;
+				Self::deposit_event(Event::Voted(who, Zero::zero(), votes));
+				return Ok(().into());
+			}
+
+			let voting_power = T::VotingPower::voting_power(&who);
+			ensure!(!voting_power.is_zero(), Error::<T>::NoVotingPower);
+			T::VotingPower::lock_voting_power(GAUGE_VOTE_LOCK_ID, &who)?;
+			for (pool_id, percentage) in votes.iter() {
+				let vote_weight = percentage.mul_floor(voting_power);
+				Gauges::<T>::try_mutate_exists(pool_id, |maybe_weight| -> DispatchResult {
+					let weight = maybe_weight.as_mut().ok_or(Error::<T>::GaugeNotExists)?;
+					*weight = weight.saturating_add(vote_weight);
+					Ok(())
+				})?;
+				let epoch = Self::gauge_epochs(pool_id);
+				GaugeVotes::<T>::mutate(pool_id, &who, |(vote_epoch, weight)| {
+					if *vote_epoch != epoch {
+						*vote_epoch = epoch;
+						*weight = Zero::zero();
+					}
+					*weight = weight.saturating_add(vote_weight);
+				});
+			}
+
+			Votes::<T>::insert(&who, (voting_power, votes.clone()));
+			Self::deposit_event(Event::Voted(who, voting_power, votes));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The vote weight of `who` in the current epoch of the gauge.
+	pub fn vote_weight(pool_id: &T::PoolId, who: &T::AccountId) -> Balance {
+		let (epoch, weight) = Self::gauge_votes(pool_id, who);
+		if Self::gauges(pool_id).is_some() && epoch == Self::gauge_epochs(pool_id) {
+			weight
+		} else {
+			Zero::zero()
+		}
+	}
+
+	fn begin_epoch() -> u32 {
+		EpochCount::<T>::mutate(|count| {
+			*count = count.saturating_add(1);
+			*count
+		})
+	}
+
+	/// Allocate the emission budget to the gauges in proportion to their
+	/// vote weights and reset the votes by beginning a new epoch, return the
+	/// number of gauges. The reward amounts are not changed if there's no
+	/// vote.
+	fn allocate_emission() -> u32 {
+		let gauges = Gauges::<T>::iter().collect::<Vec<_>>();
+		let total_weight = gauges
+			.iter()
+			.fold(Balance::zero(), |total, (_, weight)| total.saturating_add(*weight));
+		if total_weight.is_zero() {
+			return gauges.len() as u32;
+		}
+
+		// release the previous allocation first, so that the new one is checked
+		// against the budget as a whole rather than on top of it.
+		for (pool_id, _) in gauges.iter() {
+			let _ = T::Incentives::set_incentive_reward_amount(pool_id, Zero::zero());
+		}
+
+		let budget = T::EmissionBudget::get();
+		let epoch = Self::begin_epoch();
+		for (pool_id, weight) in gauges.iter() {
+			let amount = multiply_by_rational(budget, *weight, total_weight).unwrap_or_default();
+			match T::Incentives::set_incentive_reward_amount(pool_id, amount) {
+				Ok(_) => Self::deposit_event(Event::EmissionAllocated(pool_id.clone(), amount)),
+				Err(e) => {
+					log::warn!(
+						target: "gauge",
+						"set_incentive_reward_amount: failed to set {:?} for {:?}: {:?}. \
+						This is unexpected but should be safe",
+						amount, pool_id, e
+					);
+				}
+			}
+
+			Gauges::<T>::insert(pool_id, Balance::zero());
+			GaugeEpochs::<T>::insert(pool_id, epoch);
+		}
+		gauges.len() as u32
+	}
+}
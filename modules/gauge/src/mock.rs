@@ -0,0 +1,183 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Mocks for gauge module.
+
+#![cfg(test)]
+
+use super::*;
+
+use crate as gauge;
+use frame_support::{construct_runtime, ord_parameter_types, parameter_types};
+use frame_system::EnsureSignedBy;
+use sp_core::H256;
+use sp_runtime::{testing::Header, traits::IdentityLookup, DispatchResult};
+use sp_std::cell::RefCell;
+use std::collections::HashMap;
+
+pub type AccountId = u128;
+pub type BlockNumber = u64;
+pub type PoolId = u32;
+
+pub const ALICE: AccountId = 0;
+pub const BOB: AccountId = 1;
+pub const CHARLIE: AccountId = 2;
+pub const POOL_A: PoolId = 0;
+pub const POOL_B: PoolId = 1;
+pub const POOL_C: PoolId = 2;
+pub const POOL_D: PoolId = 3;
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Runtime {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+thread_local! {
+	static VOTING_POWER: RefCell<HashMap<AccountId, Balance>> = RefCell::new(HashMap::new());
+	static REWARD_AMOUNTS: RefCell<HashMap<PoolId, Balance>> = RefCell::new(HashMap::new());
+	static EMISSION_BUDGET: RefCell<Balance> = RefCell::new(0);
+	static VOTING_POWER_LOCKS: RefCell<HashMap<AccountId, LockIdentifier>> = RefCell::new(HashMap::new());
+}
+
+pub fn mock_emission_budget(budget: Balance) {
+	EMISSION_BUDGET.with(|v| *v.borrow_mut() = budget);
+}
+
+pub fn mock_voting_power(who: AccountId, power: Balance) {
+	VOTING_POWER.with(|v| v.borrow_mut().insert(who, power));
+}
+
+pub fn voting_power_lock(who: AccountId) -> Option<LockIdentifier> {
+	VOTING_POWER_LOCKS.with(|v| v.borrow().get(&who).copied())
+}
+
+pub fn reward_amount(pool_id: PoolId) -> Option<Balance> {
+	REWARD_AMOUNTS.with(|v| v.borrow().get(&pool_id).copied())
+}
+
+pub struct MockVotingPower;
+impl VotingPowerProvider<AccountId, Balance> for MockVotingPower {
+	fn voting_power(who: &AccountId) -> Balance {
+		VOTING_POWER.with(|v| v.borrow().get(who).copied().unwrap_or_default())
+	}
+
+	fn lock_voting_power(lock_id: LockIdentifier, who: &AccountId) -> DispatchResult {
+		VOTING_POWER_LOCKS.with(|v| v.borrow_mut().insert(*who, lock_id));
+		Ok(())
+	}
+
+	fn unlock_voting_power(_: LockIdentifier, who: &AccountId) -> DispatchResult {
+		VOTING_POWER_LOCKS.with(|v| v.borrow_mut().remove(who));
+		Ok(())
+	}
+}
+
+pub struct MockEmissionBudget;
+impl Get<Balance> for MockEmissionBudget {
+	fn get() -> Balance {
+		EMISSION_BUDGET.with(|v| *v.borrow())
+	}
+}
+
+pub struct MockIncentives;
+impl IncentivesManager<PoolId, Balance> for MockIncentives {
+	fn set_incentive_reward_amount(pool_id: &PoolId, amount: Balance) -> DispatchResult {
+		REWARD_AMOUNTS.with(|v| v.borrow_mut().insert(*pool_id, amount));
+		Ok(())
+	}
+}
+
+ord_parameter_types! {
+	pub const One: AccountId = 1;
+}
+
+parameter_types! {
+	pub const VotingPeriod: BlockNumber = 10;
+	pub const MaxGauges: u32 = 3;
+	pub const MaxVotes: u32 = 2;
+}
+
+impl Config for Runtime {
+	type Event = Event;
+	type PoolId = PoolId;
+	type VotingPower = MockVotingPower;
+	type Incentives = MockIncentives;
+	type EmissionBudget = MockEmissionBudget;
+	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
+	type VotingPeriod = VotingPeriod;
+	type MaxGauges = MaxGauges;
+	type MaxVotes = MaxVotes;
+	type WeightInfo = ();
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		GaugeModule: gauge::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+#[derive(Default)]
+pub struct ExtBuilder;
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		VOTING_POWER.with(|v| v.borrow_mut().clear());
+		REWARD_AMOUNTS.with(|v| v.borrow_mut().clear());
+		EMISSION_BUDGET.with(|v| *v.borrow_mut() = 0);
+		VOTING_POWER_LOCKS.with(|v| v.borrow_mut().clear());
+
+		let t = frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap();
+
+		t.into()
+	}
+}
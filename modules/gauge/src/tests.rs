@@ -0,0 +1,264 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for gauge module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+use sp_runtime::traits::BadOrigin;
+
+#[test]
+fn add_and_remove_gauge_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(GaugeModule::add_gauge(Origin::signed(ALICE), POOL_A), BadOrigin);
+
+		assert_ok!(GaugeModule::add_gauge(Origin::signed(BOB), POOL_A));
+		System::assert_last_event(Event::GaugeModule(crate::Event::GaugeAdded(POOL_A)));
+		assert_eq!(GaugeModule::gauges(POOL_A), Some(0));
+		assert_eq!(GaugeModule::gauge_count(), 1);
+		assert_noop!(
+			GaugeModule::add_gauge(Origin::signed(BOB), POOL_A),
+			Error::<Runtime>::GaugeExists
+		);
+
+		assert_ok!(GaugeModule::add_gauge(Origin::signed(BOB), POOL_B));
+		assert_ok!(GaugeModule::add_gauge(Origin::signed(BOB), POOL_C));
+		assert_noop!(
+			GaugeModule::add_gauge(Origin::signed(BOB), POOL_D),
+			Error::<Runtime>::TooManyGauges
+		);
+
+		assert_noop!(GaugeModule::remove_gauge(Origin::signed(ALICE), POOL_A), BadOrigin);
+		assert_noop!(
+			GaugeModule::remove_gauge(Origin::signed(BOB), POOL_D),
+			Error::<Runtime>::GaugeNotExists
+		);
+		assert_ok!(GaugeModule::remove_gauge(Origin::signed(BOB), POOL_A));
+		System::assert_last_event(Event::GaugeModule(crate::Event::GaugeRemoved(POOL_A)));
+		assert_eq!(GaugeModule::gauges(POOL_A), None);
+		assert_eq!(GaugeModule::gauge_count(), 2);
+		assert_eq!(reward_amount(POOL_A), Some(0));
+
+		assert_ok!(GaugeModule::add_gauge(Origin::signed(BOB), POOL_D));
+		assert_eq!(GaugeModule::gauge_count(), 3);
+	});
+}
+
+#[test]
+fn vote_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(GaugeModule::add_gauge(Origin::signed(BOB), POOL_A));
+		assert_ok!(GaugeModule::add_gauge(Origin::signed(BOB), POOL_B));
+		assert_ok!(GaugeModule::add_gauge(Origin::signed(BOB), POOL_C));
+
+		assert_noop!(
+			GaugeModule::vote(Origin::signed(ALICE), vec![(POOL_A, Permill::from_percent(50))]),
+			Error::<Runtime>::NoVotingPower
+		);
+
+		mock_voting_power(ALICE, 100);
+		assert_noop!(
+			GaugeModule::vote(
+				Origin::signed(ALICE),
+				vec![
+					(POOL_A, Permill::from_percent(20)),
+					(POOL_B, Permill::from_percent(20)),
+					(POOL_C, Permill::from_percent(20))
+				]
+			),
+			Error::<Runtime>::TooManyVotes
+		);
+		assert_noop!(
+			GaugeModule::vote(
+				Origin::signed(ALICE),
+				vec![(POOL_A, Permill::from_percent(60)), (POOL_B, Permill::from_percent(50))]
+			),
+			Error::<Runtime>::InvalidPercentages
+		);
+		assert_noop!(
+			GaugeModule::vote(Origin::signed(ALICE), vec![(POOL_D, Permill::from_percent(50))]),
+			Error::<Runtime>::GaugeNotExists
+		);
+
+		let votes = vec![(POOL_A, Permill::from_percent(60)), (POOL_B, Permill::from_percent(40))];
+		assert_ok!(GaugeModule::vote(Origin::signed(ALICE), votes.clone()));
+		System::assert_last_event(Event::GaugeModule(crate::Event::Voted(ALICE, 100, votes.clone())));
+		assert_eq!(GaugeModule::votes(ALICE), Some((100, votes)));
+		assert_eq!(voting_power_lock(ALICE), Some(GAUGE_VOTE_LOCK_ID));
+		assert_eq!(GaugeModule::gauges(POOL_A), Some(60));
+		assert_eq!(GaugeModule::gauges(POOL_B), Some(40));
+		assert_eq!(GaugeModule::vote_weight(&POOL_A, &ALICE), 60);
+		assert_eq!(GaugeModule::vote_weight(&POOL_B, &ALICE), 40);
+
+		mock_voting_power(BOB, 50);
+		assert_ok!(GaugeModule::vote(
+			Origin::signed(BOB),
+			vec![(POOL_B, Permill::from_percent(100))]
+		));
+		assert_eq!(GaugeModule::gauges(POOL_B), Some(90));
+
+		// vote again with the decayed voting power
+		mock_voting_power(ALICE, 80);
+		let votes = vec![(POOL_C, Permill::from_percent(50))];
+		assert_ok!(GaugeModule::vote(Origin::signed(ALICE), votes.clone()));
+		assert_eq!(GaugeModule::votes(ALICE), Some((80, votes)));
+		assert_eq!(GaugeModule::gauges(POOL_A), Some(0));
+		assert_eq!(GaugeModule::gauges(POOL_B), Some(50));
+		assert_eq!(GaugeModule::gauges(POOL_C), Some(40));
+
+		// the votes for removed gauge are cleared
+		assert_ok!(GaugeModule::remove_gauge(Origin::signed(BOB), POOL_C));
+		assert_eq!(GaugeModule::vote_weight(&POOL_C, &ALICE), 0);
+		assert_ok!(GaugeModule::add_gauge(Origin::signed(BOB), POOL_C));
+		mock_voting_power(CHARLIE, 30);
+		assert_ok!(GaugeModule::vote(
+			Origin::signed(CHARLIE),
+			vec![(POOL_C, Permill::from_percent(100))]
+		));
+		assert_ok!(GaugeModule::vote(Origin::signed(ALICE), vec![]));
+		System::assert_last_event(Event::GaugeModule(crate::Event::Voted(ALICE, 0, vec![])));
+		assert_eq!(GaugeModule::votes(ALICE), None);
+		assert_eq!(voting_power_lock(ALICE), None);
+		assert_eq!(GaugeModule::gauges(POOL_C), Some(30));
+
+		// revoke the votes without voting power
+		mock_voting_power(BOB, 0);
+		assert_ok!(GaugeModule::vote(Origin::signed(BOB), vec![]));
+		assert_eq!(GaugeModule::votes(BOB), None);
+		assert_eq!(GaugeModule::gauges(POOL_B), Some(0));
+	});
+}
+
+#[test]
+fn on_initialize_allocate_emission_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		mock_emission_budget(1000);
+		assert_ok!(GaugeModule::add_gauge(Origin::signed(BOB), POOL_A));
+		assert_ok!(GaugeModule::add_gauge(Origin::signed(BOB), POOL_B));
+
+		// no votes, the reward amounts are not changed
+		GaugeModule::on_initialize(10);
+		assert_eq!(reward_amount(POOL_A), None);
+		assert_eq!(reward_amount(POOL_B), None);
+
+		mock_voting_power(ALICE, 100);
+		mock_voting_power(CHARLIE, 50);
+		assert_ok!(GaugeModule::vote(
+			Origin::signed(ALICE),
+			vec![(POOL_A, Permill::from_percent(60)), (POOL_B, Permill::from_percent(40))]
+		));
+		assert_ok!(GaugeModule::vote(
+			Origin::signed(CHARLIE),
+			vec![(POOL_B, Permill::from_percent(100))]
+		));
+
+		// not the beginning of the voting period
+		GaugeModule::on_initialize(11);
+		assert_eq!(reward_amount(POOL_A), None);
+		assert_eq!(reward_amount(POOL_B), None);
+
+		GaugeModule::on_initialize(20);
+		System::assert_has_event(Event::GaugeModule(crate::Event::EmissionAllocated(POOL_A, 400)));
+		System::assert_has_event(Event::GaugeModule(crate::Event::EmissionAllocated(POOL_B, 600)));
+		assert_eq!(reward_amount(POOL_A), Some(400));
+		assert_eq!(reward_amount(POOL_B), Some(600));
+
+		// the votes are reset, voters need to vote again with the current voting power
+		assert_eq!(GaugeModule::gauges(POOL_A), Some(0));
+		assert_eq!(GaugeModule::gauges(POOL_B), Some(0));
+		assert_eq!(GaugeModule::vote_weight(&POOL_A, &ALICE), 0);
+		assert_eq!(GaugeModule::vote_weight(&POOL_B, &CHARLIE), 0);
+
+		mock_voting_power(ALICE, 50);
+		assert_ok!(GaugeModule::vote(
+			Origin::signed(ALICE),
+			vec![(POOL_A, Permill::from_percent(100))]
+		));
+		assert_eq!(GaugeModule::gauges(POOL_A), Some(50));
+		assert_eq!(GaugeModule::gauges(POOL_B), Some(0));
+
+		GaugeModule::on_initialize(30);
+		assert_eq!(reward_amount(POOL_A), Some(1000));
+		assert_eq!(reward_amount(POOL_B), Some(0));
+	});
+}
+
+#[test]
+fn votes_of_previous_epochs_are_cleared_lazily() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		mock_emission_budget(1000);
+		assert_ok!(GaugeModule::add_gauge(Origin::signed(BOB), POOL_A));
+		assert_ok!(GaugeModule::add_gauge(Origin::signed(BOB), POOL_B));
+		assert_eq!(GaugeModule::gauge_epochs(POOL_A), 1);
+		assert_eq!(GaugeModule::gauge_epochs(POOL_B), 2);
+
+		mock_voting_power(ALICE, 100);
+		mock_voting_power(CHARLIE, 50);
+		assert_ok!(GaugeModule::vote(
+			Origin::signed(ALICE),
+			vec![(POOL_A, Permill::from_percent(60)), (POOL_B, Permill::from_percent(40))]
+		));
+		assert_ok!(GaugeModule::vote(
+			Origin::signed(CHARLIE),
+			vec![(POOL_B, Permill::from_percent(100))]
+		));
+		assert_eq!(GaugeModule::gauge_votes(POOL_B, CHARLIE), (2, 50));
+
+		// a new epoch begins without clearing the votes
+		GaugeModule::on_initialize(10);
+		assert_eq!(GaugeModule::gauge_epochs(POOL_A), 3);
+		assert_eq!(GaugeModule::gauge_epochs(POOL_B), 3);
+		assert_eq!(GaugeModule::gauge_votes(POOL_B, CHARLIE), (2, 50));
+		assert_eq!(GaugeModule::vote_weight(&POOL_B, &CHARLIE), 0);
+
+		// the votes of the previous epoch are cleared when voting again
+		assert_ok!(GaugeModule::vote(
+			Origin::signed(ALICE),
+			vec![(POOL_A, Permill::from_percent(100))]
+		));
+		assert_eq!(GaugeModule::gauge_votes(POOL_A, ALICE), (3, 100));
+		assert!(!GaugeVotes::<Runtime>::contains_key(POOL_B, ALICE));
+		assert_eq!(GaugeModule::gauges(POOL_A), Some(100));
+
+		// revoking the votes of the previous epoch doesn't change the vote weights
+		mock_voting_power(BOB, 20);
+		assert_ok!(GaugeModule::vote(
+			Origin::signed(BOB),
+			vec![(POOL_B, Permill::from_percent(100))]
+		));
+		assert_ok!(GaugeModule::vote(Origin::signed(CHARLIE), vec![]));
+		assert!(!GaugeVotes::<Runtime>::contains_key(POOL_B, CHARLIE));
+		assert_eq!(GaugeModule::gauges(POOL_B), Some(20));
+
+		// the votes for the removed gauge don't count for the gauge added again
+		assert_ok!(GaugeModule::remove_gauge(Origin::signed(BOB), POOL_B));
+		assert_eq!(GaugeModule::vote_weight(&POOL_B, &BOB), 0);
+		assert_ok!(GaugeModule::add_gauge(Origin::signed(BOB), POOL_B));
+		assert_eq!(GaugeModule::gauge_epochs(POOL_B), 4);
+		assert_eq!(GaugeModule::vote_weight(&POOL_B, &BOB), 0);
+		assert_ok!(GaugeModule::vote(Origin::signed(BOB), vec![]));
+		assert_eq!(GaugeModule::gauges(POOL_B), Some(0));
+	});
+}
@@ -0,0 +1,365 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Grants Module
+//!
+//! ## Overview
+//!
+//! The long-running grants of the treasury are streamed to their beneficiaries per period instead
+//! of paid in a lump sum. When a grant is approved, its total amount is moved from the treasury to
+//! the module account, and vests by `per_period` at the end of each period. The stream stops at
+//! each milestone of the grant until the milestone is confirmed by the council.
+//!
+//! The beneficiary can claim the vested amount at any time. The approve origin can claw back the
+//! unvested amount of a grant to the treasury, the vested amount is still paid to the
+//! beneficiary.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+#![allow(clippy::too_many_arguments)]
+
+use frame_support::{pallet_prelude::*, transactional, PalletId};
+use frame_system::pallet_prelude::*;
+use orml_traits::MultiCurrency;
+use primitives::{Balance, CurrencyId};
+use sp_runtime::{
+	traits::{AccountIdConversion, One, Saturating, StaticLookup, UniqueSaturatedInto, Zero},
+	ArithmeticError, RuntimeDebug,
+};
+use sp_std::prelude::*;
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+/// The id of grant.
+pub type GrantId = u32;
+
+/// The grant streaming to the beneficiary per period.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Grant<AccountId, BlockNumber> {
+	/// The beneficiary of the grant.
+	pub beneficiary: AccountId,
+	/// The currency of the grant.
+	pub currency_id: CurrencyId,
+	/// The block number the first period starts at.
+	pub start: BlockNumber,
+	/// The length of a period in blocks.
+	pub period: BlockNumber,
+	/// The number of periods.
+	pub period_count: u32,
+	/// The amount vested at the end of each period.
+	pub per_period: Balance,
+	/// The numbers of the vested periods at which the stream stops until the milestone is
+	/// confirmed, in ascending order.
+	pub milestones: Vec<u32>,
+	/// The number of the confirmed milestones.
+	pub confirmed_milestones: u32,
+	/// The amount claimed by the beneficiary.
+	pub claimed: Balance,
+}
+
+impl<AccountId, BlockNumber: UniqueSaturatedInto<u32> + Copy + PartialOrd + Saturating + Zero>
+	Grant<AccountId, BlockNumber>
+{
+	/// The total amount of the grant.
+	pub fn total(&self) -> Balance {
+		self.per_period.saturating_mul(self.period_count.into())
+	}
+
+	/// The number of the vested periods at `now`.
+	pub fn vested_periods(&self, now: BlockNumber) -> u32 {
+		if now <= self.start || self.period.is_zero() {
+			return Zero::zero();
+		}
+		let elapsed: u32 = now.saturating_sub(self.start).unique_saturated_into();
+		let period: u32 = self.period.unique_saturated_into();
+		let unlocked = self
+			.milestones
+			.get(self.confirmed_milestones as usize)
+			.copied()
+			.unwrap_or(self.period_count);
+		(elapsed / period).min(unlocked).min(self.period_count)
+	}
+
+	/// The amount vested at `now`, including the claimed amount.
+	pub fn vested(&self, now: BlockNumber) -> Balance {
+		self.per_period.saturating_mul(self.vested_periods(now).into())
+	}
+
+	/// The amount the beneficiary can claim at `now`.
+	pub fn claimable(&self, now: BlockNumber) -> Balance {
+		self.vested(now).saturating_sub(self.claimed)
+	}
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currencies of grants.
+		type Currency: MultiCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
+
+		/// The treasury account which funds the grants.
+		#[pallet::constant]
+		type TreasuryAccount: Get<Self::AccountId>;
+
+		/// The module id to keep the unclaimed amounts of grants.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The max number of milestones of a grant.
+		#[pallet::constant]
+		type MaxMilestones: Get<u32>;
+
+		/// The origin which may approve grants and claw them back.
+		type ApproveOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The origin which may confirm the milestones of grants.
+		type MilestoneOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The start block number is in the past, or the period is zero
+		InvalidPeriod,
+		/// The amount per period or the number of periods is zero
+		InvalidAmount,
+		/// The milestones are not ascending within the periods
+		InvalidMilestones,
+		/// The number of milestones exceeds `MaxMilestones`
+		TooManyMilestones,
+		/// The grant does not exist
+		GrantNotFound,
+		/// The caller is not the beneficiary of the grant
+		NoPermission,
+		/// All the milestones of the grant are confirmed
+		NoPendingMilestone,
+		/// The grant has no vested amount to claim
+		NothingToClaim,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Approved grant. \[grant_id, beneficiary, currency_id, total\]
+		GrantCreated(GrantId, T::AccountId, CurrencyId, Balance),
+		/// Confirmed the milestone of grant. \[grant_id, milestone_index\]
+		MilestoneConfirmed(GrantId, u32),
+		/// Claimed from grant. \[grant_id, beneficiary, amount\]
+		Claimed(GrantId, T::AccountId, Balance),
+		/// Clawed back the unvested amount of grant. \[grant_id, paid, refunded\]
+		ClawedBack(GrantId, Balance, Balance),
+	}
+
+	/// The next grant id.
+	///
+	/// NextGrantId: GrantId
+	#[pallet::storage]
+	#[pallet::getter(fn next_grant_id)]
+	pub type NextGrantId<T: Config> = StorageValue<_, GrantId, ValueQuery>;
+
+	/// The grants.
+	///
+	/// Grants: map GrantId => Option<Grant>
+	#[pallet::storage]
+	#[pallet::getter(fn grants)]
+	pub type Grants<T: Config> = StorageMap<_, Twox64Concat, GrantId, Grant<T::AccountId, T::BlockNumber>, OptionQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Approve a grant of `per_period` of `currency_id` for `period_count` periods to
+		/// `beneficiary`. The total amount is moved from the treasury to the module account.
+		///
+		/// The dispatch origin of this call must be `ApproveOrigin`.
+		///
+		/// - `beneficiary`: the beneficiary of the grant.
+		/// - `currency_id`: the currency of the grant.
+		/// - `per_period`: the amount vested at the end of each period.
+		/// - `start`: the block number the first period starts at, not in the past.
+		/// - `period`: the length of a period in blocks.
+		/// - `period_count`: the number of periods.
+		/// - `milestones`: the numbers of the vested periods at which the stream stops until the
+		///   milestone is confirmed, in ascending order.
+		#[pallet::weight(T::WeightInfo::create_grant())]
+		#[transactional]
+		pub fn create_grant(
+			origin: OriginFor<T>,
+			beneficiary: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyId,
+			#[pallet::compact] per_period: Balance,
+			start: T::BlockNumber,
+			period: T::BlockNumber,
+			period_count: u32,
+			milestones: Vec<u32>,
+		) -> DispatchResultWithPostInfo {
+			T::ApproveOrigin::ensure_origin(origin)?;
+			let beneficiary = T::Lookup::lookup(beneficiary)?;
+			ensure!(
+				start >= frame_system::Pallet::<T>::block_number() && !period.is_zero(),
+				Error::<T>::InvalidPeriod
+			);
+			ensure!(
+				!per_period.is_zero() && !period_count.is_zero(),
+				Error::<T>::InvalidAmount
+			);
+			ensure!(
+				milestones.len() as u32 <= T::MaxMilestones::get(),
+				Error::<T>::TooManyMilestones
+			);
+			ensure!(
+				milestones.first().map_or(true, |first| !first.is_zero())
+					&& milestones.windows(2).all(|w| w[0] < w[1])
+					&& milestones.last().map_or(true, |last| *last < period_count),
+				Error::<T>::InvalidMilestones
+			);
+
+			let grant = Grant {
+				beneficiary: beneficiary.clone(),
+				currency_id,
+				start,
+				period,
+				period_count,
+				per_period,
+				milestones,
+				confirmed_milestones: Zero::zero(),
+				claimed: Zero::zero(),
+			};
+			let total = grant.total();
+			T::Currency::transfer(currency_id, &T::TreasuryAccount::get(), &Self::account_id(), total)?;
+
+			let grant_id = NextGrantId::<T>::try_mutate(|id| -> Result<GrantId, DispatchError> {
+				let current_id = *id;
+				*id = id.checked_add(One::one()).ok_or(ArithmeticError::Overflow)?;
+				Ok(current_id)
+			})?;
+			Grants::<T>::insert(grant_id, grant);
+
+			Self::deposit_event(Event::GrantCreated(grant_id, beneficiary, currency_id, total));
+			Ok(().into())
+		}
+
+		/// Confirm the next milestone of the grant, the stream continues to the milestone after.
+		///
+		/// The dispatch origin of this call must be `MilestoneOrigin`.
+		///
+		/// - `grant_id`: the id of the grant.
+		#[pallet::weight(T::WeightInfo::confirm_milestone())]
+		#[transactional]
+		pub fn confirm_milestone(origin: OriginFor<T>, grant_id: GrantId) -> DispatchResultWithPostInfo {
+			T::MilestoneOrigin::ensure_origin(origin)?;
+
+			let milestone_index = Grants::<T>::try_mutate(grant_id, |maybe_grant| -> Result<u32, DispatchError> {
+				let grant = maybe_grant.as_mut().ok_or(Error::<T>::GrantNotFound)?;
+				ensure!(
+					(grant.confirmed_milestones as usize) < grant.milestones.len(),
+					Error::<T>::NoPendingMilestone
+				);
+				let milestone_index = grant.confirmed_milestones;
+				grant.confirmed_milestones = grant.confirmed_milestones.saturating_add(1);
+				Ok(milestone_index)
+			})?;
+
+			Self::deposit_event(Event::MilestoneConfirmed(grant_id, milestone_index));
+			Ok(().into())
+		}
+
+		/// Claim the vested amount of the grant of the caller. The grant is removed when it's
+		/// fully claimed.
+		///
+		/// - `grant_id`: the id of the grant.
+		#[pallet::weight(T::WeightInfo::claim())]
+		#[transactional]
+		pub fn claim(origin: OriginFor<T>, grant_id: GrantId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let now = frame_system::Pallet::<T>::block_number();
+
+			let amount = Grants::<T>::try_mutate_exists(grant_id, |maybe_grant| -> Result<Balance, DispatchError> {
+				let grant = maybe_grant.as_mut().ok_or(Error::<T>::GrantNotFound)?;
+				ensure!(grant.beneficiary == who, Error::<T>::NoPermission);
+				let amount = grant.claimable(now);
+				ensure!(!amount.is_zero(), Error::<T>::NothingToClaim);
+
+				T::Currency::transfer(grant.currency_id, &Self::account_id(), &who, amount)?;
+				grant.claimed = grant.claimed.saturating_add(amount);
+				if grant.claimed >= grant.total() {
+					*maybe_grant = None;
+				}
+				Ok(amount)
+			})?;
+
+			Self::deposit_event(Event::Claimed(grant_id, who, amount));
+			Ok(().into())
+		}
+
+		/// Claw back the unvested amount of the grant to the treasury, and pay the vested amount
+		/// which is not claimed yet to the beneficiary. The grant is removed.
+		///
+		/// The dispatch origin of this call must be `ApproveOrigin`.
+		///
+		/// - `grant_id`: the id of the grant.
+		#[pallet::weight(T::WeightInfo::clawback())]
+		#[transactional]
+		pub fn clawback(origin: OriginFor<T>, grant_id: GrantId) -> DispatchResultWithPostInfo {
+			T::ApproveOrigin::ensure_origin(origin)?;
+			let grant = Grants::<T>::take(grant_id).ok_or(Error::<T>::GrantNotFound)?;
+
+			let now = frame_system::Pallet::<T>::block_number();
+			let paid = grant.claimable(now);
+			let refunded = grant.total().saturating_sub(grant.vested(now));
+			T::Currency::transfer(grant.currency_id, &Self::account_id(), &grant.beneficiary, paid)?;
+			T::Currency::transfer(
+				grant.currency_id,
+				&Self::account_id(),
+				&T::TreasuryAccount::get(),
+				refunded,
+			)?;
+
+			Self::deposit_event(Event::ClawedBack(grant_id, paid, refunded));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The account to keep the unclaimed amounts of grants.
+	pub fn account_id() -> T::AccountId {
+		T::PalletId::get().into_account()
+	}
+
+	/// The amount the beneficiary of the grant can claim now.
+	pub fn claimable_balance(grant_id: GrantId) -> Option<Balance> {
+		Self::grants(grant_id).map(|grant| grant.claimable(frame_system::Pallet::<T>::block_number()))
+	}
+}
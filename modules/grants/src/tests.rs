@@ -0,0 +1,154 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the grants module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+use sp_runtime::traits::BadOrigin;
+
+fn create_grant(milestones: Vec<u32>) -> DispatchResultWithPostInfo {
+	GrantsModule::create_grant(Origin::signed(ALICE), CHARLIE, AUSD, 10, 1, 10, 10, milestones)
+}
+
+#[test]
+fn create_grant_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			GrantsModule::create_grant(Origin::signed(BOB), CHARLIE, AUSD, 10, 1, 10, 10, vec![]),
+			BadOrigin
+		);
+		assert_noop!(
+			GrantsModule::create_grant(Origin::signed(ALICE), CHARLIE, AUSD, 10, 0, 10, 10, vec![]),
+			Error::<Runtime>::InvalidPeriod
+		);
+		assert_noop!(
+			GrantsModule::create_grant(Origin::signed(ALICE), CHARLIE, AUSD, 10, 1, 0, 10, vec![]),
+			Error::<Runtime>::InvalidPeriod
+		);
+		assert_noop!(
+			GrantsModule::create_grant(Origin::signed(ALICE), CHARLIE, AUSD, 0, 1, 10, 10, vec![]),
+			Error::<Runtime>::InvalidAmount
+		);
+		assert_noop!(
+			GrantsModule::create_grant(Origin::signed(ALICE), CHARLIE, AUSD, 10, 1, 10, 0, vec![]),
+			Error::<Runtime>::InvalidAmount
+		);
+		assert_noop!(create_grant(vec![1, 2, 3, 4]), Error::<Runtime>::TooManyMilestones);
+		assert_noop!(create_grant(vec![0]), Error::<Runtime>::InvalidMilestones);
+		assert_noop!(create_grant(vec![3, 3]), Error::<Runtime>::InvalidMilestones);
+		assert_noop!(create_grant(vec![10]), Error::<Runtime>::InvalidMilestones);
+		assert_noop!(
+			GrantsModule::create_grant(Origin::signed(ALICE), CHARLIE, AUSD, 200, 1, 10, 10, vec![]),
+			orml_tokens::Error::<Runtime>::BalanceTooLow
+		);
+
+		assert_ok!(create_grant(vec![3, 6]));
+		System::assert_last_event(Event::GrantsModule(crate::Event::GrantCreated(0, CHARLIE, AUSD, 100)));
+		assert_eq!(GrantsModule::next_grant_id(), 1);
+		assert_eq!(
+			GrantsModule::grants(0),
+			Some(Grant {
+				beneficiary: CHARLIE,
+				currency_id: AUSD,
+				start: 1,
+				period: 10,
+				period_count: 10,
+				per_period: 10,
+				milestones: vec![3, 6],
+				confirmed_milestones: 0,
+				claimed: 0,
+			})
+		);
+		assert_eq!(Tokens::free_balance(AUSD, &TREASURY), 900);
+		assert_eq!(Tokens::free_balance(AUSD, &GrantsModule::account_id()), 100);
+	});
+}
+
+#[test]
+fn stream_with_milestones_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(create_grant(vec![3, 6]));
+		assert_noop!(
+			GrantsModule::claim(Origin::signed(CHARLIE), 0),
+			Error::<Runtime>::NothingToClaim
+		);
+
+		System::set_block_number(21);
+		assert_eq!(GrantsModule::claimable_balance(0), Some(20));
+		assert_noop!(
+			GrantsModule::claim(Origin::signed(BOB), 0),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(GrantsModule::claim(Origin::signed(CHARLIE), 0));
+		System::assert_last_event(Event::GrantsModule(crate::Event::Claimed(0, CHARLIE, 20)));
+		assert_eq!(Tokens::free_balance(AUSD, &CHARLIE), 20);
+
+		// the stream stops at the first milestone until it's confirmed
+		System::set_block_number(51);
+		assert_eq!(GrantsModule::claimable_balance(0), Some(10));
+		assert_noop!(GrantsModule::confirm_milestone(Origin::signed(ALICE), 0), BadOrigin);
+		assert_ok!(GrantsModule::confirm_milestone(Origin::signed(BOB), 0));
+		System::assert_last_event(Event::GrantsModule(crate::Event::MilestoneConfirmed(0, 0)));
+		assert_eq!(GrantsModule::claimable_balance(0), Some(30));
+
+		assert_ok!(GrantsModule::confirm_milestone(Origin::signed(BOB), 0));
+		System::assert_last_event(Event::GrantsModule(crate::Event::MilestoneConfirmed(0, 1)));
+		assert_noop!(
+			GrantsModule::confirm_milestone(Origin::signed(BOB), 0),
+			Error::<Runtime>::NoPendingMilestone
+		);
+
+		// the grant is removed when it's fully claimed
+		System::set_block_number(200);
+		assert_eq!(GrantsModule::claimable_balance(0), Some(80));
+		assert_ok!(GrantsModule::claim(Origin::signed(CHARLIE), 0));
+		assert_eq!(Tokens::free_balance(AUSD, &CHARLIE), 100);
+		assert_eq!(Tokens::free_balance(AUSD, &GrantsModule::account_id()), 0);
+		assert_eq!(GrantsModule::grants(0), None);
+		assert_noop!(
+			GrantsModule::confirm_milestone(Origin::signed(BOB), 0),
+			Error::<Runtime>::GrantNotFound
+		);
+	});
+}
+
+#[test]
+fn clawback_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(create_grant(vec![3, 6]));
+		System::set_block_number(21);
+		assert_ok!(GrantsModule::claim(Origin::signed(CHARLIE), 0));
+
+		System::set_block_number(41);
+		assert_noop!(GrantsModule::clawback(Origin::signed(BOB), 0), BadOrigin);
+		assert_ok!(GrantsModule::clawback(Origin::signed(ALICE), 0));
+		System::assert_last_event(Event::GrantsModule(crate::Event::ClawedBack(0, 10, 70)));
+		assert_eq!(Tokens::free_balance(AUSD, &CHARLIE), 30);
+		assert_eq!(Tokens::free_balance(AUSD, &TREASURY), 970);
+		assert_eq!(Tokens::free_balance(AUSD, &GrantsModule::account_id()), 0);
+		assert_eq!(GrantsModule::grants(0), None);
+		assert_noop!(
+			GrantsModule::clawback(Origin::signed(ALICE), 0),
+			Error::<Runtime>::GrantNotFound
+		);
+	});
+}
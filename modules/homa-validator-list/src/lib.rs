@@ -23,6 +23,11 @@
 //! This will require validators to lock some Liquid Token into insurance fund
 //! and if slash happened, HomaCouncil can burn those Liquid Token to compensate
 //! Liquid Token holders.
+//!
+//! Guarantors bond Liquid Token behind specific validators, and their
+//! guarantees are the first to be burned when the validators are slashed. In
+//! return, guarantors share the rewards deposited for the validators in
+//! proportion to their guarantees, and can claim them at any time.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
@@ -31,7 +36,7 @@
 use frame_support::{
 	pallet_prelude::*,
 	traits::{Contains, MaxEncodedLen},
-	transactional,
+	transactional, PalletId,
 };
 use frame_system::pallet_prelude::*;
 use orml_traits::{BasicCurrency, BasicLockableCurrency, Happened, LockIdentifier};
@@ -40,11 +45,11 @@ use primitives::Balance;
 use serde::{Deserialize, Serialize};
 use sp_runtime::{
 	offchain::storage_lock::BlockNumberProvider,
-	traits::{MaybeDisplay, MaybeSerializeDeserialize, Member, Zero},
+	traits::{AccountIdConversion, MaybeDisplay, MaybeSerializeDeserialize, Member, Saturating, Zero},
 	DispatchResult, FixedPointNumber, RuntimeDebug,
 };
 use sp_std::{fmt::Debug, vec::Vec};
-use support::{ExchangeRateProvider, Ratio};
+use support::{ExchangeRateProvider, Ratio, ValidatorRewardsDistributor};
 
 mod mock;
 mod tests;
@@ -61,6 +66,7 @@ pub trait WeightInfo {
 	fn freeze(u: u32) -> Weight;
 	fn thaw() -> Weight;
 	fn slash() -> Weight;
+	fn claim_rewards() -> Weight;
 }
 
 // TODO: do benchmarking test.
@@ -86,6 +92,9 @@ impl WeightInfo for () {
 	fn slash() -> Weight {
 		10_000
 	}
+	fn claim_rewards() -> Weight {
+		10_000
+	}
 }
 
 #[derive(Encode, Decode, Clone, Copy, RuntimeDebug, Default, PartialEq, MaxEncodedLen)]
@@ -148,6 +157,16 @@ pub struct SlashInfo<Balance, RelaychainAccountId> {
 	relaychain_token_amount: Balance,
 }
 
+/// The reward record of a guarantor for a validator.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, Default, PartialEq, MaxEncodedLen)]
+pub struct GuarantorReward {
+	/// The accumulated reward per guarantee of the validator when the rewards
+	/// were settled last time.
+	last_reward_per_share: Ratio,
+	/// The settled rewards which have not been claimed.
+	unclaimed: Balance,
+}
+
 #[derive(Encode, Decode, Clone, Copy, RuntimeDebug, Default, MaxEncodedLen)]
 pub struct ValidatorBacking {
 	total_insurance: Balance,
@@ -171,7 +190,10 @@ pub mod module {
 		type ValidatorInsuranceThreshold: Get<Balance>;
 		type FreezeOrigin: EnsureOrigin<Self::Origin>;
 		type SlashOrigin: EnsureOrigin<Self::Origin>;
-		type OnSlash: Happened<Balance>;
+		/// Callback when slash reported, the params are the total slashed
+		/// relaychain token amount and the relaychain token amount which has
+		/// been compensated by burning guarantees.
+		type OnSlash: Happened<(Balance, Balance)>;
 		type LiquidStakingExchangeRateProvider: ExchangeRateProvider;
 		type WeightInfo: WeightInfo;
 		type OnIncreaseGuarantee: Happened<(Self::AccountId, Self::RelaychainAccountId, Balance)>;
@@ -179,6 +201,10 @@ pub mod module {
 
 		// The block number provider
 		type BlockNumberProvider: BlockNumberProvider<BlockNumber = Self::BlockNumber>;
+
+		/// The homa validator list module id, keep the rewards of guarantors.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
 	}
 
 	#[pallet::error]
@@ -186,6 +212,7 @@ pub mod module {
 		BelowMinBondAmount,
 		UnbondingExists,
 		FrozenValidator,
+		NoGuarantee,
 	}
 
 	#[pallet::event]
@@ -197,6 +224,11 @@ pub mod module {
 		UnbondGuarantee(T::AccountId, T::RelaychainAccountId, Balance),
 		WithdrawnGuarantee(T::AccountId, T::RelaychainAccountId, Balance),
 		SlashGuarantee(T::AccountId, T::RelaychainAccountId, Balance),
+		/// Deposit rewards for the guarantors of validator. \[who,
+		/// validator, amount\]
+		DepositReward(T::AccountId, T::RelaychainAccountId, Balance),
+		/// Guarantor claimed the rewards. \[guarantor, validator, amount\]
+		ClaimRewards(T::AccountId, T::RelaychainAccountId, Balance),
 	}
 
 	/// The slash guarantee deposits for relaychain validators.
@@ -229,6 +261,30 @@ pub mod module {
 	pub type ValidatorBackings<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::RelaychainAccountId, ValidatorBacking, OptionQuery>;
 
+	/// The accumulated rewards per guarantee for validators.
+	///
+	/// ValidatorRewardPerShare: map RelaychainAccountId => Ratio
+	#[pallet::storage]
+	#[pallet::getter(fn validator_reward_per_share)]
+	pub type ValidatorRewardPerShare<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::RelaychainAccountId, Ratio, ValueQuery>;
+
+	/// The reward records of guarantors.
+	///
+	/// GuarantorRewards: double_map RelaychainAccountId, AccountId =>
+	/// Option<GuarantorReward>
+	#[pallet::storage]
+	#[pallet::getter(fn guarantor_rewards)]
+	pub type GuarantorRewards<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::RelaychainAccountId,
+		Twox64Concat,
+		T::AccountId,
+		GuarantorReward,
+		OptionQuery,
+	>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
@@ -387,12 +443,14 @@ pub mod module {
 			let liquid_staking_exchange_rate = T::LiquidStakingExchangeRateProvider::get_exchange_rate();
 			let staking_liquid_exchange_rate = liquid_staking_exchange_rate.reciprocal().unwrap_or_default();
 			let mut actual_total_slashing: Balance = Zero::zero();
+			let mut relaychain_total_slashing: Balance = Zero::zero();
 
 			for SlashInfo {
 				validator,
 				relaychain_token_amount,
 			} in slashes
 			{
+				relaychain_total_slashing = relaychain_total_slashing.saturating_add(relaychain_token_amount);
 				let ValidatorBacking { total_insurance, .. } = Self::validator_backings(&validator).unwrap_or_default();
 				let insurance_loss = staking_liquid_exchange_rate
 					.saturating_mul_int(relaychain_token_amount)
@@ -419,18 +477,96 @@ pub mod module {
 				}
 			}
 
-			T::OnSlash::happened(&actual_total_slashing);
+			let compensated = liquid_staking_exchange_rate
+				.saturating_mul_int(actual_total_slashing)
+				.min(relaychain_total_slashing);
+			T::OnSlash::happened(&(relaychain_total_slashing, compensated));
+			Ok(().into())
+		}
+
+		/// Claim all the rewards of guarantee for validator.
+		///
+		/// - `validator`: the validator which is guaranteed.
+		#[pallet::weight(T::WeightInfo::claim_rewards())]
+		#[transactional]
+		pub fn claim_rewards(origin: OriginFor<T>, validator: T::RelaychainAccountId) -> DispatchResultWithPostInfo {
+			let guarantor = ensure_signed(origin)?;
+			Self::settle_rewards(&guarantor, &validator);
+
+			let unclaimed = GuarantorRewards::<T>::mutate_exists(&validator, &guarantor, |maybe_reward| {
+				let mut reward = maybe_reward.take().unwrap_or_default();
+				let unclaimed = sp_std::mem::take(&mut reward.unclaimed);
+				if Self::guarantees(&validator, &guarantor).is_some() {
+					*maybe_reward = Some(reward);
+				}
+				unclaimed
+			});
+
+			if !unclaimed.is_zero() {
+				T::LiquidTokenCurrency::transfer(&Self::account_id(), &guarantor, unclaimed)?;
+				Self::deposit_event(Event::ClaimRewards(guarantor, validator, unclaimed));
+			}
 			Ok(().into())
 		}
 	}
 }
 
 impl<T: Config> Pallet<T> {
+	/// The account of homa validator list module, keep the rewards of
+	/// guarantors.
+	pub fn account_id() -> T::AccountId {
+		T::PalletId::get().into_account()
+	}
+
+	/// Transfer `amount` of liquid token from `who` as the rewards for the
+	/// guarantors of `validator`, which will be shared in proportion to their
+	/// guarantees.
+	pub fn distribute_reward(
+		who: &T::AccountId,
+		validator: &T::RelaychainAccountId,
+		amount: Balance,
+	) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(());
+		}
+
+		let total_insurance = Self::validator_backings(validator).unwrap_or_default().total_insurance;
+		ensure!(!total_insurance.is_zero(), Error::<T>::NoGuarantee);
+
+		T::LiquidTokenCurrency::transfer(who, &Self::account_id(), amount)?;
+		ValidatorRewardPerShare::<T>::mutate(validator, |reward_per_share| {
+			*reward_per_share = reward_per_share
+				.saturating_add(Ratio::checked_from_rational(amount, total_insurance).unwrap_or_default());
+		});
+
+		Self::deposit_event(Event::DepositReward(who.clone(), validator.clone(), amount));
+		Ok(())
+	}
+
+	/// Settle the rewards of guarantor for validator according to the current
+	/// guarantee, must be called before the guarantee changes.
+	fn settle_rewards(guarantor: &T::AccountId, validator: &T::RelaychainAccountId) {
+		let reward_per_share = Self::validator_reward_per_share(validator);
+		let guarantee_total = Self::guarantees(validator, guarantor).unwrap_or_default().total;
+
+		GuarantorRewards::<T>::mutate_exists(validator, guarantor, |maybe_reward| {
+			let mut reward = maybe_reward.take().unwrap_or_default();
+			let pending = reward_per_share
+				.saturating_sub(reward.last_reward_per_share)
+				.saturating_mul_int(guarantee_total);
+			reward.unclaimed = reward.unclaimed.saturating_add(pending);
+			reward.last_reward_per_share = reward_per_share;
+			*maybe_reward = Some(reward);
+		});
+	}
+
 	fn update_guarantee(
 		guarantor: &T::AccountId,
 		validator: &T::RelaychainAccountId,
 		f: impl FnOnce(&mut Guarantee<T::BlockNumber>) -> DispatchResult,
 	) -> DispatchResult {
+		Self::settle_rewards(guarantor, validator);
+
 		Guarantees::<T>::try_mutate_exists(validator, guarantor, |maybe_guarantee| -> DispatchResult {
 			let mut guarantee = maybe_guarantee.take().unwrap_or_default();
 			let old_total = guarantee.total;
@@ -439,6 +575,11 @@ impl<T: Config> Pallet<T> {
 				let new_total = guarantee.total;
 				if guarantee.total.is_zero() {
 					*maybe_guarantee = None;
+					GuarantorRewards::<T>::mutate_exists(validator, guarantor, |maybe_reward| {
+						if maybe_reward.map_or(false, |reward| reward.unclaimed.is_zero()) {
+							*maybe_reward = None;
+						}
+					});
 				} else {
 					*maybe_guarantee = Some(guarantee);
 				}
@@ -497,3 +638,9 @@ impl<T: Config> Contains<T::RelaychainAccountId> for Pallet<T> {
 			>= T::ValidatorInsuranceThreshold::get()
 	}
 }
+
+impl<T: Config> ValidatorRewardsDistributor<T::AccountId, T::RelaychainAccountId, Balance> for Pallet<T> {
+	fn distribute_reward(payer: &T::AccountId, validator: &T::RelaychainAccountId, amount: Balance) -> DispatchResult {
+		Self::distribute_reward(payer, validator, amount)
+	}
+}
@@ -131,13 +131,18 @@ impl orml_currencies::Config for Runtime {
 
 thread_local! {
 	pub static SHARES: RefCell<HashMap<(AccountId, AccountId), Balance>> = RefCell::new(HashMap::new());
-	pub static ACCUMULATED_SLASH: RefCell<Balance> = RefCell::new(0);
+	pub static ACCUMULATED_SLASH: RefCell<(Balance, Balance)> = RefCell::new((0, 0));
 }
 
 pub struct MockOnSlash;
-impl Happened<Balance> for MockOnSlash {
-	fn happened(amount: &Balance) {
-		ACCUMULATED_SLASH.with(|v| *v.borrow_mut() += amount);
+impl Happened<(Balance, Balance)> for MockOnSlash {
+	fn happened(info: &(Balance, Balance)) {
+		let (slashed, compensated) = info;
+		ACCUMULATED_SLASH.with(|v| {
+			let mut accumulated = v.borrow_mut();
+			accumulated.0 += slashed;
+			accumulated.1 += compensated;
+		});
 	}
 }
 
@@ -187,6 +192,7 @@ parameter_types! {
 	pub const BondingDuration: BlockNumber = 100;
 	pub const ValidatorInsuranceThreshold: Balance = 200;
 	pub static MockBlockNumberProvider: u64 = 0;
+	pub const HomaValidatorListPalletId: PalletId = PalletId(*b"aca/hmvl");
 }
 
 impl BlockNumberProvider for MockBlockNumberProvider {
@@ -216,6 +222,7 @@ impl Config for Runtime {
 	type OnIncreaseGuarantee = MockOnIncreaseGuarantee;
 	type OnDecreaseGuarantee = MockOnDecreaseGuarantee;
 	type BlockNumberProvider = MockBlockNumberProvider;
+	type PalletId = HomaValidatorListPalletId;
 }
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
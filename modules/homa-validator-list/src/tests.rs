@@ -713,6 +713,7 @@ fn slash_work() {
 			VALIDATOR_2,
 			100,
 		)));
+		assert_eq!(ACCUMULATED_SLASH.with(|v| *v.borrow()), (140, 139));
 		assert_eq!(
 			HomaValidatorListModule::validator_backings(VALIDATOR_1)
 				.unwrap_or_default()
@@ -796,3 +797,97 @@ fn contains_work() {
 		assert_eq!(HomaValidatorListModule::contains(&VALIDATOR_1), true);
 	});
 }
+
+#[test]
+fn distribute_reward_and_claim_rewards_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_noop!(
+			HomaValidatorListModule::distribute_reward(&BOB, &VALIDATOR_1, 400),
+			Error::<Runtime>::NoGuarantee
+		);
+
+		assert_ok!(HomaValidatorListModule::bond(Origin::signed(ALICE), VALIDATOR_1, 100));
+		assert_ok!(HomaValidatorListModule::bond(Origin::signed(BOB), VALIDATOR_1, 300));
+		assert_ok!(HomaValidatorListModule::distribute_reward(&BOB, &VALIDATOR_1, 400));
+		System::assert_last_event(mock::Event::HomaValidatorListModule(crate::Event::DepositReward(
+			BOB,
+			VALIDATOR_1,
+			400,
+		)));
+		assert_eq!(
+			OrmlTokens::accounts(HomaValidatorListModule::account_id(), LDOT).free,
+			400
+		);
+		assert_eq!(OrmlTokens::accounts(BOB, LDOT).free, 600);
+		assert_eq!(
+			HomaValidatorListModule::validator_reward_per_share(VALIDATOR_1),
+			Ratio::saturating_from_integer(1)
+		);
+
+		// new guarantee doesn't share the rewards deposited before
+		assert_ok!(HomaValidatorListModule::bond(Origin::signed(ALICE), VALIDATOR_1, 100));
+		assert_eq!(
+			HomaValidatorListModule::guarantor_rewards(VALIDATOR_1, ALICE),
+			Some(GuarantorReward {
+				last_reward_per_share: Ratio::saturating_from_integer(1),
+				unclaimed: 100,
+			})
+		);
+
+		assert_ok!(HomaValidatorListModule::claim_rewards(
+			Origin::signed(ALICE),
+			VALIDATOR_1
+		));
+		System::assert_last_event(mock::Event::HomaValidatorListModule(crate::Event::ClaimRewards(
+			ALICE,
+			VALIDATOR_1,
+			100,
+		)));
+		assert_eq!(OrmlTokens::accounts(ALICE, LDOT).free, 1100);
+		assert_eq!(
+			HomaValidatorListModule::guarantor_rewards(VALIDATOR_1, ALICE),
+			Some(GuarantorReward {
+				last_reward_per_share: Ratio::saturating_from_integer(1),
+				unclaimed: 0,
+			})
+		);
+
+		// unbonding guarantee still shares the rewards since it can be slashed
+		assert_ok!(HomaValidatorListModule::unbond(Origin::signed(ALICE), VALIDATOR_1, 200));
+		assert_ok!(HomaValidatorListModule::distribute_reward(&BOB, &VALIDATOR_1, 250));
+		MockBlockNumberProvider::set(100);
+		assert_ok!(HomaValidatorListModule::withdraw_unbonded(
+			Origin::signed(ALICE),
+			VALIDATOR_1
+		));
+		assert_eq!(HomaValidatorListModule::guarantees(VALIDATOR_1, ALICE), None);
+		assert_eq!(
+			HomaValidatorListModule::guarantor_rewards(VALIDATOR_1, ALICE),
+			Some(GuarantorReward {
+				last_reward_per_share: Ratio::saturating_from_rational(3, 2),
+				unclaimed: 100,
+			})
+		);
+
+		assert_ok!(HomaValidatorListModule::claim_rewards(
+			Origin::signed(ALICE),
+			VALIDATOR_1
+		));
+		assert_eq!(OrmlTokens::accounts(ALICE, LDOT).free, 1200);
+		assert_eq!(HomaValidatorListModule::guarantor_rewards(VALIDATOR_1, ALICE), None);
+
+		assert_ok!(HomaValidatorListModule::claim_rewards(Origin::signed(BOB), VALIDATOR_1));
+		System::assert_last_event(mock::Event::HomaValidatorListModule(crate::Event::ClaimRewards(
+			BOB,
+			VALIDATOR_1,
+			450,
+		)));
+		assert_eq!(OrmlTokens::accounts(BOB, LDOT).free, 800);
+		assert_eq!(
+			OrmlTokens::accounts(HomaValidatorListModule::account_id(), LDOT).free,
+			0
+		);
+	});
+}
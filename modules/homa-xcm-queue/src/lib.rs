@@ -0,0 +1,467 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Homa XCM Queue Module
+//!
+//! ## Overview
+//!
+//! Serializes the XCM messages of the Homa operations on relaychain, i.e. bond, unbond, rebond,
+//! transfer to the sub accounts and withdraw unbonded, so that they are executed on relaychain
+//! in the order they are issued. The operations are queued with increasing ids and sent one at a
+//! time by `Transactor`, the next operation is only sent after the response of the previous one
+//! is received, using the id of the operation as the query id of the response.
+//!
+//! An operation is resent with exponential backoff if it can't be routed or exceeds the weight
+//! limit. After `MaxRetries` retries the operation fails permanently and holds up the queue, until
+//! the update origin retries or cancels it. If the response of a sent operation times out, the
+//! operation may still have been executed on relaychain, so only the idempotent withdraw unbonded
+//! is resent, the others fail at once for the update origin to check relaychain before retrying or
+//! cancelling them. A late response still completes the failed operation.
+//!
+//! `QueuedPolkadotBridge` wraps the bridge of the staking pool, to queue the XCM messages of the
+//! bridge calls of Homa.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{pallet_prelude::*, transactional};
+use frame_system::pallet_prelude::*;
+use primitives::{Balance, EraIndex};
+use sp_runtime::{traits::Saturating, RuntimeDebug};
+use sp_std::{marker::PhantomData, prelude::*};
+use support::{PolkadotBridge, PolkadotBridgeCall, PolkadotBridgeState, PolkadotBridgeType, PolkadotStakingLedger};
+use xcm::v0::{Error as XcmError, MultiLocation, Response};
+use xcm_executor::traits::OnResponse;
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+/// The id of the queued operation, also used as the query id of its response.
+pub type OperationId = u64;
+
+/// The Homa operation on relaychain.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum HomaXcmOperation {
+	/// Bond extra `amount` of the sub account.
+	BondExtra { account_index: u32, amount: Balance },
+	/// Unbond `amount` of the sub account.
+	Unbond { account_index: u32, amount: Balance },
+	/// Rebond `amount` of the unlocking funds of the sub account.
+	Rebond { account_index: u32, amount: Balance },
+	/// Transfer `amount` from the parachain sovereign account to the sub account.
+	TransferToSubAccount { account_index: u32, amount: Balance },
+	/// Withdraw the unbonded funds of the sub account.
+	WithdrawUnbonded { account_index: u32 },
+}
+
+impl HomaXcmOperation {
+	/// Whether executing the operation again on relaychain has no further effect, so it's safe to
+	/// resend it when it may have been executed already.
+	pub fn is_idempotent(&self) -> bool {
+		matches!(self, HomaXcmOperation::WithdrawUnbonded { .. })
+	}
+}
+
+/// The reason of the failed attempt of an operation.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum FailureReason {
+	/// Failed to send the XCM message.
+	Xcm(XcmError),
+	/// The response is not received in time.
+	Timeout,
+}
+
+impl FailureReason {
+	/// Whether `operation` may be resent. `CannotReachDestination` and `SendFailed` are the
+	/// unroutable errors of XCM v0, `WeightLimitReached` and `TooMuchWeightRequired` the
+	/// overweight errors, the message isn't executed on any of them. A timed out operation may
+	/// have been executed, so it's only resent if it's idempotent.
+	pub fn is_retryable(&self, operation: &HomaXcmOperation) -> bool {
+		match self {
+			FailureReason::Timeout => operation.is_idempotent(),
+			FailureReason::Xcm(error) => matches!(
+				error,
+				XcmError::CannotReachDestination(..)
+					| XcmError::SendFailed(_)
+					| XcmError::WeightLimitReached(_)
+					| XcmError::TooMuchWeightRequired
+			),
+		}
+	}
+}
+
+/// The status of the queued operation.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum OperationStatus<BlockNumber> {
+	/// Waiting to be sent at `next_attempt`.
+	Pending { next_attempt: BlockNumber },
+	/// Sent and waiting for the response until `timeout`.
+	Sent { timeout: BlockNumber },
+	/// Failed permanently, waiting to be retried or cancelled by the update origin.
+	Failed(FailureReason),
+}
+
+/// The queued operation.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct QueuedOperation<BlockNumber> {
+	/// The operation on relaychain.
+	pub operation: HomaXcmOperation,
+	/// The status of the operation.
+	pub status: OperationStatus<BlockNumber>,
+	/// The number of retries of the operation.
+	pub retries: u32,
+}
+
+/// Send the XCM messages of the Homa operations to relaychain.
+pub trait HomaXcmTransactor {
+	/// Send the XCM message of `operation`, which reports back with `query_id` when executed.
+	fn send(operation: HomaXcmOperation, query_id: u64) -> Result<(), XcmError>;
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Send the XCM messages of the operations.
+		type Transactor: HomaXcmTransactor;
+
+		/// The location the responses of the operations come from.
+		type ResponseOrigin: Get<MultiLocation>;
+
+		/// The number of blocks to wait for the response of the sent operation.
+		#[pallet::constant]
+		type ResponseTimeout: Get<Self::BlockNumber>;
+
+		/// The number of blocks to wait before the first retry, doubled for each of the later
+		/// retries.
+		#[pallet::constant]
+		type RetryBackoff: Get<Self::BlockNumber>;
+
+		/// The maximum number of retries before the operation fails permanently.
+		#[pallet::constant]
+		type MaxRetries: Get<u32>;
+
+		/// The origin which may retry and cancel the failed operations.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The operation is not found
+		OperationNotFound,
+		/// The operation has not failed
+		OperationNotFailed,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Queued the operation. \[operation_id, operation\]
+		OperationQueued(OperationId, HomaXcmOperation),
+		/// Sent the XCM message of the operation. \[operation_id\]
+		OperationSent(OperationId),
+		/// The operation is executed on relaychain. \[operation_id\]
+		OperationCompleted(OperationId),
+		/// Scheduled the retry of the operation. \[operation_id, retries, next_attempt, reason\]
+		OperationRetryScheduled(OperationId, u32, T::BlockNumber, Option<FailureReason>),
+		/// The operation failed permanently. \[operation_id, reason\]
+		OperationFailed(OperationId, FailureReason),
+		/// Cancelled the failed operation. \[operation_id\]
+		OperationCancelled(OperationId),
+	}
+
+	/// The id of the next queued operation.
+	///
+	/// NextOperationId: OperationId
+	#[pallet::storage]
+	#[pallet::getter(fn next_operation_id)]
+	pub type NextOperationId<T: Config> = StorageValue<_, OperationId, ValueQuery>;
+
+	/// The id of the operation at the head of the queue, the only one which may be sent.
+	///
+	/// QueueHead: OperationId
+	#[pallet::storage]
+	#[pallet::getter(fn queue_head)]
+	pub type QueueHead<T: Config> = StorageValue<_, OperationId, ValueQuery>;
+
+	/// The queued operations.
+	///
+	/// Operations: map OperationId => Option<QueuedOperation>
+	#[pallet::storage]
+	#[pallet::getter(fn operations)]
+	pub type Operations<T: Config> =
+		StorageMap<_, Twox64Concat, OperationId, QueuedOperation<T::BlockNumber>, OptionQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			Self::process_queue_head(now);
+			T::WeightInfo::on_initialize()
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Retry the failed operation from now, with the retries reset. An operation failed by
+		/// `Timeout` must only be retried after checking it wasn't executed on relaychain.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `operation_id`: the id of the failed operation.
+		#[pallet::weight(T::WeightInfo::retry_operation())]
+		#[transactional]
+		pub fn retry_operation(origin: OriginFor<T>, operation_id: OperationId) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			Operations::<T>::try_mutate(operation_id, |maybe_queued| -> DispatchResult {
+				let queued = maybe_queued.as_mut().ok_or(Error::<T>::OperationNotFound)?;
+				ensure!(
+					matches!(queued.status, OperationStatus::Failed(_)),
+					Error::<T>::OperationNotFailed
+				);
+
+				let now = frame_system::Pallet::<T>::block_number();
+				queued.status = OperationStatus::Pending { next_attempt: now };
+				queued.retries = 0;
+				Self::deposit_event(Event::OperationRetryScheduled(operation_id, 0, now, None));
+				Ok(())
+			})?;
+			Ok(().into())
+		}
+
+		/// Cancel the failed operation, the queue moves on to the next operation.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `operation_id`: the id of the failed operation.
+		#[pallet::weight(T::WeightInfo::cancel_operation())]
+		#[transactional]
+		pub fn cancel_operation(origin: OriginFor<T>, operation_id: OperationId) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let queued = Self::operations(operation_id).ok_or(Error::<T>::OperationNotFound)?;
+			ensure!(
+				matches!(queued.status, OperationStatus::Failed(_)),
+				Error::<T>::OperationNotFailed
+			);
+
+			Self::pop_queue_head(operation_id);
+			Self::deposit_event(Event::OperationCancelled(operation_id));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Queue `operation`, return the id of the operation.
+	pub fn enqueue(operation: HomaXcmOperation) -> OperationId {
+		let operation_id = NextOperationId::<T>::mutate(|id| {
+			let current = *id;
+			*id = id.saturating_add(1);
+			current
+		});
+		Operations::<T>::insert(
+			operation_id,
+			QueuedOperation {
+				operation,
+				status: OperationStatus::Pending {
+					next_attempt: frame_system::Pallet::<T>::block_number(),
+				},
+				retries: 0,
+			},
+		);
+		Self::deposit_event(Event::OperationQueued(operation_id, operation));
+		operation_id
+	}
+
+	/// Send the operation at the head of the queue if it's due, or handle the timeout of its
+	/// response.
+	fn process_queue_head(now: T::BlockNumber) {
+		let operation_id = Self::queue_head();
+		let mut queued = match Self::operations(operation_id) {
+			Some(queued) => queued,
+			None => return,
+		};
+
+		match queued.status {
+			OperationStatus::Pending { next_attempt } if now >= next_attempt => {
+				match T::Transactor::send(queued.operation, operation_id) {
+					Ok(()) => {
+						queued.status = OperationStatus::Sent {
+							timeout: now.saturating_add(T::ResponseTimeout::get()),
+						};
+						Self::deposit_event(Event::OperationSent(operation_id));
+					}
+					Err(error) => Self::retry_or_fail(operation_id, &mut queued, now, FailureReason::Xcm(error)),
+				}
+			}
+			OperationStatus::Sent { timeout } if now >= timeout => {
+				Self::retry_or_fail(operation_id, &mut queued, now, FailureReason::Timeout)
+			}
+			_ => return,
+		}
+		Operations::<T>::insert(operation_id, queued);
+	}
+
+	/// Schedule the retry of the operation with exponential backoff if `reason` is retryable and
+	/// the retries are not used up, otherwise fail it permanently.
+	fn retry_or_fail(
+		operation_id: OperationId,
+		queued: &mut QueuedOperation<T::BlockNumber>,
+		now: T::BlockNumber,
+		reason: FailureReason,
+	) {
+		if reason.is_retryable(&queued.operation) && queued.retries < T::MaxRetries::get() {
+			let backoff = T::RetryBackoff::get().saturating_mul(2u32.saturating_pow(queued.retries).into());
+			let next_attempt = now.saturating_add(backoff);
+			queued.retries = queued.retries.saturating_add(1);
+			queued.status = OperationStatus::Pending { next_attempt };
+			Self::deposit_event(Event::OperationRetryScheduled(
+				operation_id,
+				queued.retries,
+				next_attempt,
+				Some(reason),
+			));
+		} else {
+			queued.status = OperationStatus::Failed(reason.clone());
+			Self::deposit_event(Event::OperationFailed(operation_id, reason));
+		}
+	}
+
+	/// Remove the operation at the head of the queue.
+	fn pop_queue_head(operation_id: OperationId) {
+		Operations::<T>::remove(operation_id);
+		QueueHead::<T>::put(operation_id.saturating_add(1));
+	}
+}
+
+impl<T: Config> OnResponse for Pallet<T> {
+	/// Only expect the response of the operation at the head of the queue. The late responses of
+	/// the timed out operations are accepted too, as they are executed anyway, which completes
+	/// the operations failed by the timeout.
+	fn expecting_response(origin: &MultiLocation, query_id: u64) -> bool {
+		*origin == T::ResponseOrigin::get() && query_id == Self::queue_head() && Operations::<T>::contains_key(query_id)
+	}
+
+	fn on_response(origin: MultiLocation, query_id: u64, _response: Response) -> Weight {
+		if Self::expecting_response(&origin, query_id) {
+			Self::pop_queue_head(query_id);
+			Self::deposit_event(Event::OperationCompleted(query_id));
+		}
+		T::WeightInfo::on_response()
+	}
+}
+
+/// Wraps the bridge of the staking pool, to queue the XCM messages of the bond, unbond, rebond,
+/// transfer and withdraw unbonded calls after they are applied to `Bridge`.
+pub struct QueuedPolkadotBridge<T, Bridge>(PhantomData<(T, Bridge)>);
+
+impl<T, Bridge> PolkadotBridgeType<T::BlockNumber, EraIndex> for QueuedPolkadotBridge<T, Bridge>
+where
+	T: Config,
+	Bridge: PolkadotBridgeType<T::BlockNumber, EraIndex>,
+{
+	type BondingDuration = Bridge::BondingDuration;
+	type EraLength = Bridge::EraLength;
+	type PolkadotAccountId = Bridge::PolkadotAccountId;
+}
+
+impl<T, Bridge> PolkadotBridgeCall<T::AccountId, T::BlockNumber, Balance, EraIndex> for QueuedPolkadotBridge<T, Bridge>
+where
+	T: Config,
+	Bridge: PolkadotBridgeCall<T::AccountId, T::BlockNumber, Balance, EraIndex>,
+{
+	fn bond_extra(account_index: u32, amount: Balance) -> DispatchResult {
+		Bridge::bond_extra(account_index, amount)?;
+		Pallet::<T>::enqueue(HomaXcmOperation::BondExtra { account_index, amount });
+		Ok(())
+	}
+
+	fn unbond(account_index: u32, amount: Balance) -> DispatchResult {
+		Bridge::unbond(account_index, amount)?;
+		Pallet::<T>::enqueue(HomaXcmOperation::Unbond { account_index, amount });
+		Ok(())
+	}
+
+	fn rebond(account_index: u32, amount: Balance) -> DispatchResult {
+		Bridge::rebond(account_index, amount)?;
+		Pallet::<T>::enqueue(HomaXcmOperation::Rebond { account_index, amount });
+		Ok(())
+	}
+
+	fn withdraw_unbonded(account_index: u32) {
+		Bridge::withdraw_unbonded(account_index);
+		Pallet::<T>::enqueue(HomaXcmOperation::WithdrawUnbonded { account_index });
+	}
+
+	fn nominate(account_index: u32, targets: Vec<Self::PolkadotAccountId>) {
+		Bridge::nominate(account_index, targets)
+	}
+
+	fn transfer_to_bridge(account_index: u32, from: &T::AccountId, amount: Balance) -> DispatchResult {
+		Bridge::transfer_to_bridge(account_index, from, amount)?;
+		Pallet::<T>::enqueue(HomaXcmOperation::TransferToSubAccount { account_index, amount });
+		Ok(())
+	}
+
+	fn receive_from_bridge(account_index: u32, to: &T::AccountId, amount: Balance) -> DispatchResult {
+		Bridge::receive_from_bridge(account_index, to, amount)
+	}
+
+	fn payout_stakers(account_index: u32, era: EraIndex) -> Balance {
+		Bridge::payout_stakers(account_index, era)
+	}
+}
+
+impl<T, Bridge> PolkadotBridgeState<Balance, EraIndex> for QueuedPolkadotBridge<T, Bridge>
+where
+	T: Config,
+	Bridge: PolkadotBridgeState<Balance, EraIndex>,
+{
+	fn staking_ledger(account_index: u32) -> PolkadotStakingLedger<Balance, EraIndex> {
+		Bridge::staking_ledger(account_index)
+	}
+
+	fn free_balance(account_index: u32) -> Balance {
+		Bridge::free_balance(account_index)
+	}
+
+	fn current_era() -> EraIndex {
+		Bridge::current_era()
+	}
+}
+
+impl<T, Bridge> PolkadotBridge<T::AccountId, T::BlockNumber, Balance, EraIndex> for QueuedPolkadotBridge<T, Bridge>
+where
+	T: Config,
+	Bridge: PolkadotBridge<T::AccountId, T::BlockNumber, Balance, EraIndex>,
+{
+}
@@ -0,0 +1,145 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Mocks for the homa xcm queue module.
+
+#![cfg(test)]
+
+use super::*;
+
+use crate as homa_xcm_queue;
+use frame_support::{construct_runtime, ord_parameter_types, parameter_types};
+use frame_system::EnsureSignedBy;
+use sp_core::H256;
+use sp_runtime::{testing::Header, traits::IdentityLookup};
+use sp_std::cell::RefCell;
+use xcm::v0::Junction;
+
+pub type AccountId = u128;
+pub type BlockNumber = u64;
+
+pub const ALICE: AccountId = 1;
+pub const COUNCIL: AccountId = 100;
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Runtime {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+thread_local! {
+	pub static SEND_ERROR: RefCell<Option<XcmError>> = RefCell::new(None);
+	pub static SENT: RefCell<Vec<(HomaXcmOperation, u64)>> = RefCell::new(vec![]);
+}
+
+pub fn set_send_error(error: Option<XcmError>) {
+	SEND_ERROR.with(|v| *v.borrow_mut() = error);
+}
+
+pub fn sent() -> Vec<(HomaXcmOperation, u64)> {
+	SENT.with(|v| v.borrow().clone())
+}
+
+pub struct MockTransactor;
+impl HomaXcmTransactor for MockTransactor {
+	fn send(operation: HomaXcmOperation, query_id: u64) -> Result<(), XcmError> {
+		if let Some(error) = SEND_ERROR.with(|v| v.borrow().clone()) {
+			return Err(error);
+		}
+		SENT.with(|v| v.borrow_mut().push((operation, query_id)));
+		Ok(())
+	}
+}
+
+ord_parameter_types! {
+	pub const Council: AccountId = COUNCIL;
+}
+
+parameter_types! {
+	pub const RelaychainLocation: MultiLocation = MultiLocation::X1(Junction::Parent);
+	pub const ResponseTimeout: BlockNumber = 10;
+	pub const RetryBackoff: BlockNumber = 2;
+	pub const MaxRetries: u32 = 2;
+}
+
+impl Config for Runtime {
+	type Event = Event;
+	type Transactor = MockTransactor;
+	type ResponseOrigin = RelaychainLocation;
+	type ResponseTimeout = ResponseTimeout;
+	type RetryBackoff = RetryBackoff;
+	type MaxRetries = MaxRetries;
+	type UpdateOrigin = EnsureSignedBy<Council, AccountId>;
+	type WeightInfo = ();
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		HomaXcmQueueModule: homa_xcm_queue::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+#[derive(Default)]
+pub struct ExtBuilder;
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		set_send_error(None);
+		SENT.with(|v| v.borrow_mut().clear());
+
+		let t = frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap();
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
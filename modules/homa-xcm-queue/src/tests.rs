@@ -0,0 +1,269 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the homa xcm queue module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+use sp_runtime::traits::BadOrigin;
+use xcm::v0::Junction;
+
+const BOND: HomaXcmOperation = HomaXcmOperation::BondExtra {
+	account_index: 0,
+	amount: 100,
+};
+const UNBOND: HomaXcmOperation = HomaXcmOperation::Unbond {
+	account_index: 0,
+	amount: 50,
+};
+
+fn relaychain() -> MultiLocation {
+	MultiLocation::X1(Junction::Parent)
+}
+
+fn respond(query_id: u64) {
+	HomaXcmQueueModule::on_response(relaychain(), query_id, Response::Assets(vec![]));
+}
+
+#[test]
+fn operations_are_sent_in_order() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(HomaXcmQueueModule::enqueue(BOND), 0);
+		System::assert_last_event(Event::HomaXcmQueueModule(crate::Event::OperationQueued(0, BOND)));
+		assert_eq!(HomaXcmQueueModule::enqueue(UNBOND), 1);
+		assert_eq!(HomaXcmQueueModule::next_operation_id(), 2);
+
+		HomaXcmQueueModule::on_initialize(1);
+		System::assert_last_event(Event::HomaXcmQueueModule(crate::Event::OperationSent(0)));
+		assert_eq!(sent(), vec![(BOND, 0)]);
+		assert_eq!(
+			HomaXcmQueueModule::operations(0).map(|queued| queued.status),
+			Some(OperationStatus::Sent { timeout: 11 })
+		);
+
+		// the next operation waits for the response of the head
+		HomaXcmQueueModule::on_initialize(2);
+		assert_eq!(sent(), vec![(BOND, 0)]);
+		assert!(!HomaXcmQueueModule::expecting_response(&relaychain(), 1));
+
+		respond(0);
+		System::assert_last_event(Event::HomaXcmQueueModule(crate::Event::OperationCompleted(0)));
+		assert_eq!(HomaXcmQueueModule::operations(0), None);
+		assert_eq!(HomaXcmQueueModule::queue_head(), 1);
+
+		HomaXcmQueueModule::on_initialize(3);
+		assert_eq!(sent(), vec![(BOND, 0), (UNBOND, 1)]);
+		respond(1);
+		assert_eq!(HomaXcmQueueModule::queue_head(), 2);
+	});
+}
+
+#[test]
+fn only_expect_response_of_queue_head_from_response_origin() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert!(!HomaXcmQueueModule::expecting_response(&relaychain(), 0));
+
+		HomaXcmQueueModule::enqueue(BOND);
+		HomaXcmQueueModule::on_initialize(1);
+		assert!(HomaXcmQueueModule::expecting_response(&relaychain(), 0));
+		assert!(!HomaXcmQueueModule::expecting_response(
+			&MultiLocation::X2(Junction::Parent, Junction::Parachain(2000)),
+			0
+		));
+
+		HomaXcmQueueModule::on_response(
+			MultiLocation::X2(Junction::Parent, Junction::Parachain(2000)),
+			0,
+			Response::Assets(vec![]),
+		);
+		assert_eq!(HomaXcmQueueModule::queue_head(), 0);
+		assert!(HomaXcmQueueModule::operations(0).is_some());
+	});
+}
+
+#[test]
+fn retry_with_backoff_until_failed() {
+	ExtBuilder::default().build().execute_with(|| {
+		HomaXcmQueueModule::enqueue(BOND);
+		HomaXcmQueueModule::enqueue(UNBOND);
+		set_send_error(Some(XcmError::SendFailed("unroutable")));
+		let reason = FailureReason::Xcm(XcmError::SendFailed("unroutable"));
+
+		HomaXcmQueueModule::on_initialize(1);
+		System::assert_last_event(Event::HomaXcmQueueModule(crate::Event::OperationRetryScheduled(
+			0,
+			1,
+			3,
+			Some(reason.clone()),
+		)));
+
+		// not due yet
+		HomaXcmQueueModule::on_initialize(2);
+		assert_eq!(HomaXcmQueueModule::operations(0).map(|queued| queued.retries), Some(1));
+
+		HomaXcmQueueModule::on_initialize(3);
+		System::assert_last_event(Event::HomaXcmQueueModule(crate::Event::OperationRetryScheduled(
+			0,
+			2,
+			7,
+			Some(reason.clone()),
+		)));
+
+		HomaXcmQueueModule::on_initialize(7);
+		System::assert_last_event(Event::HomaXcmQueueModule(crate::Event::OperationFailed(
+			0,
+			reason.clone(),
+		)));
+		assert_eq!(
+			HomaXcmQueueModule::operations(0).map(|queued| queued.status),
+			Some(OperationStatus::Failed(reason))
+		);
+
+		// the failed operation holds up the queue
+		set_send_error(None);
+		HomaXcmQueueModule::on_initialize(8);
+		assert_eq!(sent(), vec![]);
+	});
+}
+
+#[test]
+fn non_retryable_error_fails_immediately() {
+	ExtBuilder::default().build().execute_with(|| {
+		HomaXcmQueueModule::enqueue(BOND);
+		set_send_error(Some(XcmError::Unimplemented));
+
+		HomaXcmQueueModule::on_initialize(1);
+		System::assert_last_event(Event::HomaXcmQueueModule(crate::Event::OperationFailed(
+			0,
+			FailureReason::Xcm(XcmError::Unimplemented),
+		)));
+	});
+}
+
+#[test]
+fn response_timeout_fails_non_idempotent_operation() {
+	ExtBuilder::default().build().execute_with(|| {
+		HomaXcmQueueModule::enqueue(BOND);
+		HomaXcmQueueModule::enqueue(UNBOND);
+		HomaXcmQueueModule::on_initialize(1);
+
+		HomaXcmQueueModule::on_initialize(10);
+		assert_eq!(
+			HomaXcmQueueModule::operations(0).map(|queued| queued.status),
+			Some(OperationStatus::Sent { timeout: 11 })
+		);
+
+		// the bond may have been executed, it's not resent
+		HomaXcmQueueModule::on_initialize(11);
+		System::assert_last_event(Event::HomaXcmQueueModule(crate::Event::OperationFailed(
+			0,
+			FailureReason::Timeout,
+		)));
+		HomaXcmQueueModule::on_initialize(20);
+		assert_eq!(sent(), vec![(BOND, 0)]);
+
+		// the late response completes the failed operation
+		assert!(HomaXcmQueueModule::expecting_response(&relaychain(), 0));
+		respond(0);
+		System::assert_last_event(Event::HomaXcmQueueModule(crate::Event::OperationCompleted(0)));
+		assert_eq!(HomaXcmQueueModule::operations(0), None);
+		HomaXcmQueueModule::on_initialize(21);
+		assert_eq!(sent(), vec![(BOND, 0), (UNBOND, 1)]);
+	});
+}
+
+#[test]
+fn response_timeout_retries_idempotent_operation() {
+	ExtBuilder::default().build().execute_with(|| {
+		let withdraw = HomaXcmOperation::WithdrawUnbonded { account_index: 0 };
+		HomaXcmQueueModule::enqueue(withdraw);
+		HomaXcmQueueModule::on_initialize(1);
+
+		HomaXcmQueueModule::on_initialize(11);
+		System::assert_last_event(Event::HomaXcmQueueModule(crate::Event::OperationRetryScheduled(
+			0,
+			1,
+			13,
+			Some(FailureReason::Timeout),
+		)));
+
+		HomaXcmQueueModule::on_initialize(13);
+		assert_eq!(sent(), vec![(withdraw, 0), (withdraw, 0)]);
+		respond(0);
+		assert_eq!(HomaXcmQueueModule::operations(0), None);
+	});
+}
+
+#[test]
+fn retry_operation_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		HomaXcmQueueModule::enqueue(BOND);
+		assert_noop!(HomaXcmQueueModule::retry_operation(Origin::signed(ALICE), 0), BadOrigin);
+		assert_noop!(
+			HomaXcmQueueModule::retry_operation(Origin::signed(COUNCIL), 1),
+			Error::<Runtime>::OperationNotFound
+		);
+		assert_noop!(
+			HomaXcmQueueModule::retry_operation(Origin::signed(COUNCIL), 0),
+			Error::<Runtime>::OperationNotFailed
+		);
+
+		set_send_error(Some(XcmError::Unimplemented));
+		HomaXcmQueueModule::on_initialize(1);
+		set_send_error(None);
+
+		System::set_block_number(5);
+		assert_ok!(HomaXcmQueueModule::retry_operation(Origin::signed(COUNCIL), 0));
+		System::assert_last_event(Event::HomaXcmQueueModule(crate::Event::OperationRetryScheduled(
+			0, 0, 5, None,
+		)));
+		HomaXcmQueueModule::on_initialize(5);
+		assert_eq!(sent(), vec![(BOND, 0)]);
+	});
+}
+
+#[test]
+fn cancel_operation_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		HomaXcmQueueModule::enqueue(BOND);
+		HomaXcmQueueModule::enqueue(UNBOND);
+		assert_noop!(
+			HomaXcmQueueModule::cancel_operation(Origin::signed(ALICE), 0),
+			BadOrigin
+		);
+		assert_noop!(
+			HomaXcmQueueModule::cancel_operation(Origin::signed(COUNCIL), 0),
+			Error::<Runtime>::OperationNotFailed
+		);
+
+		set_send_error(Some(XcmError::Unimplemented));
+		HomaXcmQueueModule::on_initialize(1);
+		set_send_error(None);
+
+		assert_ok!(HomaXcmQueueModule::cancel_operation(Origin::signed(COUNCIL), 0));
+		System::assert_last_event(Event::HomaXcmQueueModule(crate::Event::OperationCancelled(0)));
+		assert_eq!(HomaXcmQueueModule::operations(0), None);
+		assert_eq!(HomaXcmQueueModule::queue_head(), 1);
+
+		HomaXcmQueueModule::on_initialize(2);
+		assert_eq!(sent(), vec![(UNBOND, 1)]);
+	});
+}
@@ -24,16 +24,29 @@
 //! pool and get LDOT, which is the redemption voucher for DOT owned by the
 //! staking pool. The staking pool will staking these DOT to get staking
 //! rewards. Holders of LDOT can choose different ways to redeem DOT.
+//!
+//! The pending unbonding of redemption can be exchanged for a transferable
+//! NFT receipt carrying the unlock era and amount, and the owner of the
+//! receipt can exchange it back for the pending unbonding at any time.
+//!
+//! The mints are halted when the emergency shutdown occurs.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 
-use frame_support::{pallet_prelude::*, traits::MaxEncodedLen, transactional};
+use frame_support::{pallet_prelude::*, traits::MaxEncodedLen, transactional, PalletId};
 use frame_system::pallet_prelude::*;
+use module_nft::{ClassData, ClassIdOf, ClassProperty, Properties, TokenData, TokenIdOf};
 use primitives::{Balance, EraIndex};
-use sp_runtime::RuntimeDebug;
-use support::HomaProtocol;
+use sp_runtime::{
+	traits::{AccountIdConversion, Zero},
+	DispatchError, RuntimeDebug,
+};
+use sp_std::prelude::*;
+use support::{HomaProtocol, OnEmergencyShutdown};
 
+mod mock;
+mod tests;
 pub mod weights;
 
 pub use module::*;
@@ -59,14 +72,65 @@ pub mod module {
 	use super::*;
 
 	#[pallet::config]
-	pub trait Config: frame_system::Config {
+	pub trait Config: frame_system::Config + module_nft::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
 		/// The core of Homa protocol.
 		type Homa: HomaProtocol<Self::AccountId, Balance, EraIndex>;
 
+		/// The Homa module id, the owner of the NFT class of unbonding
+		/// receipts.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
 
+	#[pallet::error]
+	pub enum Error<T> {
+		/// There's no pending unbonding at the target era.
+		NoUnbonding,
+		/// The unbonding receipt does not exist.
+		ReceiptNotFound,
+		/// The caller is not the owner of the unbonding receipt.
+		NotReceiptOwner,
+		/// The mints are halted by the emergency shutdown.
+		MintHalted,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Exchange the pending unbonding for an unbonding receipt. \[who,
+		/// token_id, target_era, amount\]
+		UnbondingReceiptIssued(T::AccountId, TokenIdOf<T>, EraIndex, Balance),
+		/// Exchange the unbonding receipt back for the pending unbonding.
+		/// \[who, token_id, target_era, amount\]
+		UnbondingReceiptClaimed(T::AccountId, TokenIdOf<T>, EraIndex, Balance),
+	}
+
+	/// The NFT class of unbonding receipts, created at the first issuance.
+	///
+	/// ReceiptClassId: ClassId
+	#[pallet::storage]
+	#[pallet::getter(fn receipt_class_id)]
+	pub type ReceiptClassId<T: Config> = StorageValue<_, ClassIdOf<T>, OptionQuery>;
+
+	/// The pending unbonding carried by unbonding receipt.
+	///
+	/// UnbondingReceipts: map TokenId => Option<(EraIndex, Balance)>
+	#[pallet::storage]
+	#[pallet::getter(fn unbonding_receipts)]
+	pub type UnbondingReceipts<T: Config> = StorageMap<_, Twox64Concat, TokenIdOf<T>, (EraIndex, Balance), OptionQuery>;
+
+	/// Whether the mints are halted by the emergency shutdown.
+	///
+	/// IsMintHalted: bool
+	#[pallet::storage]
+	#[pallet::getter(fn is_mint_halted)]
+	pub type IsMintHalted<T: Config> = StorageValue<_, bool, ValueQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
@@ -83,10 +147,28 @@ pub mod module {
 		#[transactional]
 		pub fn mint(origin: OriginFor<T>, #[pallet::compact] amount: Balance) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
+			ensure!(!Self::is_mint_halted(), Error::<T>::MintHalted);
 			T::Homa::mint(&who, amount)?;
 			Ok(().into())
 		}
 
+		/// Inject DOT to staking pool, which will be bonded in batch at the
+		/// beginning of next era, and mint LDOT at that time with a reduced
+		/// fee.
+		///
+		/// - `amount`: the DOT amount to inject into staking pool.
+		#[pallet::weight(<T as Config>::WeightInfo::mint_at_next_era())]
+		#[transactional]
+		pub fn mint_at_next_era(
+			origin: OriginFor<T>,
+			#[pallet::compact] amount: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!Self::is_mint_halted(), Error::<T>::MintHalted);
+			T::Homa::mint_at_next_era(&who, amount)?;
+			Ok(().into())
+		}
+
 		/// Burn LDOT and redeem DOT from staking pool.
 		///
 		/// - `amount`: the LDOT amount to redeem.
@@ -125,5 +207,115 @@ pub mod module {
 			T::Homa::withdraw_redemption(&who)?;
 			Ok(().into())
 		}
+
+		/// Exchange the pending unbonding of specific era for a transferable
+		/// unbonding receipt.
+		///
+		/// - `target_era`: the era at which the unbonding will be unlocked.
+		#[pallet::weight(<T as Config>::WeightInfo::issue_unbonding_receipt())]
+		#[transactional]
+		pub fn issue_unbonding_receipt(origin: OriginFor<T>, target_era: EraIndex) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let amount = T::Homa::take_unbonding(&who, target_era);
+			ensure!(!amount.is_zero(), Error::<T>::NoUnbonding);
+
+			let class_id = Self::get_or_create_receipt_class()?;
+			let token_id = orml_nft::Pallet::<T>::mint(
+				&who,
+				class_id,
+				(target_era, amount).encode(),
+				TokenData { deposit: Zero::zero() },
+			)?;
+			UnbondingReceipts::<T>::insert(token_id, (target_era, amount));
+
+			Self::deposit_event(Event::UnbondingReceiptIssued(who, token_id, target_era, amount));
+			Ok(().into())
+		}
+
+		/// Burn the unbonding receipt and get back the pending unbonding
+		/// carried by it.
+		///
+		/// - `token_id`: the token id of the unbonding receipt.
+		#[pallet::weight(<T as Config>::WeightInfo::claim_unbonding_receipt())]
+		#[transactional]
+		pub fn claim_unbonding_receipt(origin: OriginFor<T>, token_id: TokenIdOf<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let class_id = Self::receipt_class_id().ok_or(Error::<T>::ReceiptNotFound)?;
+			let token_info = orml_nft::Pallet::<T>::tokens(class_id, token_id).ok_or(Error::<T>::ReceiptNotFound)?;
+			ensure!(token_info.owner == who, Error::<T>::NotReceiptOwner);
+			let (target_era, amount) = Self::unbonding_receipts(token_id).ok_or(Error::<T>::ReceiptNotFound)?;
+
+			orml_nft::Pallet::<T>::burn(&who, (class_id, token_id))?;
+			UnbondingReceipts::<T>::remove(token_id);
+			T::Homa::add_unbonding(&who, target_era, amount);
+
+			Self::deposit_event(Event::UnbondingReceiptClaimed(who, token_id, target_era, amount));
+			Ok(().into())
+		}
+
+		/// Update the amount of the request queued by volume limits. Reducing
+		/// keeps the position in queue and returns the difference, zero
+		/// amount cancels the request, and topping up re-queues the request
+		/// at the tail.
+		///
+		/// - `index`: the index of the queued request.
+		/// - `new_amount`: the new amount of the request.
+		#[pallet::weight(<T as Config>::WeightInfo::update_queued_request())]
+		#[transactional]
+		pub fn update_queued_request(
+			origin: OriginFor<T>,
+			index: u32,
+			#[pallet::compact] new_amount: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			T::Homa::update_queued_request(&who, index, new_amount)?;
+			Ok(().into())
+		}
+
+		/// Cancel the redemption by unbond which hasn't been unbonded at the
+		/// beginning of next era, and get back LDOT.
+		///
+		/// - `amount`: the DOT amount of redemption to cancel.
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_redeem_by_unbond())]
+		#[transactional]
+		pub fn cancel_redeem_by_unbond(
+			origin: OriginFor<T>,
+			#[pallet::compact] amount: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			T::Homa::cancel_redeem_by_unbond(&who, amount)?;
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	pub fn account_id() -> T::AccountId {
+		<T as Config>::PalletId::get().into_account()
+	}
+
+	/// The unbonding receipts are transferable but not burnable by the
+	/// owner, they can only be burned when claimed.
+	fn get_or_create_receipt_class() -> sp_std::result::Result<ClassIdOf<T>, DispatchError> {
+		if let Some(class_id) = Self::receipt_class_id() {
+			return Ok(class_id);
+		}
+
+		let class_id = orml_nft::Pallet::<T>::create_class(
+			&Self::account_id(),
+			b"Homa Unbonding Receipt".to_vec(),
+			ClassData {
+				deposit: Zero::zero(),
+				properties: Properties(ClassProperty::Transferable.into()),
+			},
+		)?;
+		ReceiptClassId::<T>::put(class_id);
+		Ok(class_id)
+	}
+}
+
+impl<T: Config> OnEmergencyShutdown for Pallet<T> {
+	fn on_emergency_shutdown() {
+		IsMintHalted::<T>::put(true);
 	}
 }
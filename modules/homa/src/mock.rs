@@ -0,0 +1,288 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Mocks for the homa module.
+
+#![cfg(test)]
+
+use super::*;
+
+use crate as homa;
+use frame_support::{construct_runtime, parameter_types};
+use primitives::ReserveIdentifier;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+	DispatchResult,
+};
+use sp_std::cell::RefCell;
+use std::collections::HashMap;
+
+pub type AccountId = u128;
+pub type BlockNumber = u64;
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Runtime {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: Balance = 1;
+	pub const MaxReserves: u32 = 50;
+}
+
+impl pallet_balances::Config for Runtime {
+	type Balance = Balance;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = frame_system::Pallet<Runtime>;
+	type MaxLocks = ();
+	type MaxReserves = MaxReserves;
+	type ReserveIdentifier = ReserveIdentifier;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const ProxyDepositBase: Balance = 1;
+	pub const ProxyDepositFactor: Balance = 1;
+	pub const MaxProxies: u16 = 4;
+	pub const MaxPending: u32 = 2;
+	pub const AnnouncementDepositBase: Balance = 1;
+	pub const AnnouncementDepositFactor: Balance = 1;
+}
+
+impl pallet_proxy::Config for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type Currency = Balances;
+	type ProxyType = ();
+	type ProxyDepositBase = ProxyDepositBase;
+	type ProxyDepositFactor = ProxyDepositFactor;
+	type MaxProxies = MaxProxies;
+	type WeightInfo = ();
+	type CallHasher = BlakeTwo256;
+	type MaxPending = MaxPending;
+	type AnnouncementDepositBase = AnnouncementDepositBase;
+	type AnnouncementDepositFactor = AnnouncementDepositFactor;
+}
+
+parameter_types! {
+	pub const MinimumPeriod: u64 = 1000;
+}
+
+impl pallet_timestamp::Config for Runtime {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = MinimumPeriod;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const CreateClassDeposit: Balance = 200;
+	pub const CreateTokenDeposit: Balance = 100;
+	pub const DataDepositPerByte: Balance = 10;
+	pub const NftPalletId: PalletId = PalletId(*b"aca/aNFT");
+	pub const MaxAttributeKeyLength: u32 = 64;
+	pub const MaxAttributeValueLength: u32 = 1024;
+}
+
+impl module_nft::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type CreateClassDeposit = CreateClassDeposit;
+	type CreateTokenDeposit = CreateTokenDeposit;
+	type DataDepositPerByte = DataDepositPerByte;
+	type PalletId = NftPalletId;
+	type MaxAttributeKeyLength = MaxAttributeKeyLength;
+	type MaxAttributeValueLength = MaxAttributeValueLength;
+	type UnixTime = Timestamp;
+	type OnNftTransfer = ();
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const MaxClassMetadata: u32 = 1024;
+	pub const MaxTokenMetadata: u32 = 1024;
+}
+
+impl orml_nft::Config for Runtime {
+	type ClassId = u32;
+	type TokenId = u64;
+	type ClassData = ClassData<Balance>;
+	type TokenData = TokenData<Balance>;
+	type MaxClassMetadata = MaxClassMetadata;
+	type MaxTokenMetadata = MaxTokenMetadata;
+}
+
+thread_local! {
+	static CURRENT_ERA: RefCell<EraIndex> = RefCell::new(0);
+	static UNBONDINGS: RefCell<HashMap<(AccountId, EraIndex), Balance>> = RefCell::new(HashMap::new());
+	static WITHDRAWN: RefCell<HashMap<AccountId, Balance>> = RefCell::new(HashMap::new());
+}
+
+pub fn set_current_era(era: EraIndex) {
+	CURRENT_ERA.with(|v| *v.borrow_mut() = era);
+}
+
+pub fn unbonding(who: AccountId, target_era: EraIndex) -> Balance {
+	UNBONDINGS.with(|v| v.borrow().get(&(who, target_era)).copied().unwrap_or_default())
+}
+
+pub fn withdrawn(who: AccountId) -> Balance {
+	WITHDRAWN.with(|v| v.borrow().get(&who).copied().unwrap_or_default())
+}
+
+/// The mocked Homa protocol, only tracks the pending unbondings, which can be
+/// withdrawn once the current era reaches their target era.
+pub struct MockHoma;
+impl HomaProtocol<AccountId, Balance, EraIndex> for MockHoma {
+	type Balance = Balance;
+
+	fn mint(_who: &AccountId, _amount: Balance) -> sp_std::result::Result<Balance, DispatchError> {
+		unimplemented!()
+	}
+	fn mint_at_next_era(_who: &AccountId, _amount: Balance) -> DispatchResult {
+		unimplemented!()
+	}
+	fn redeem_by_unbond(_who: &AccountId, _amount: Balance) -> DispatchResult {
+		unimplemented!()
+	}
+	fn redeem_by_free_unbonded(_who: &AccountId, _amount: Balance) -> DispatchResult {
+		unimplemented!()
+	}
+	fn redeem_by_claim_unbonding(_who: &AccountId, _amount: Balance, _target_era: EraIndex) -> DispatchResult {
+		unimplemented!()
+	}
+	fn withdraw_redemption(who: &AccountId) -> sp_std::result::Result<Balance, DispatchError> {
+		let current_era = CURRENT_ERA.with(|v| *v.borrow());
+		let amount = UNBONDINGS.with(|v| {
+			let mut unbondings = v.borrow_mut();
+			let unlocked = unbondings
+				.iter()
+				.filter(|((account, era), _)| account == who && *era <= current_era)
+				.map(|(key, _)| *key)
+				.collect::<Vec<_>>();
+			unlocked
+				.into_iter()
+				.filter_map(|key| unbondings.remove(&key))
+				.sum::<Balance>()
+		});
+		WITHDRAWN.with(|v| *v.borrow_mut().entry(*who).or_default() += amount);
+		Ok(amount)
+	}
+	fn take_unbonding(who: &AccountId, target_era: EraIndex) -> Balance {
+		UNBONDINGS.with(|v| v.borrow_mut().remove(&(*who, target_era)).unwrap_or_default())
+	}
+	fn add_unbonding(who: &AccountId, target_era: EraIndex, amount: Balance) {
+		if !amount.is_zero() {
+			UNBONDINGS.with(|v| *v.borrow_mut().entry((*who, target_era)).or_default() += amount);
+		}
+	}
+	fn update_queued_request(_who: &AccountId, _index: u32, _new_amount: Balance) -> DispatchResult {
+		unimplemented!()
+	}
+	fn cancel_redeem_by_unbond(_who: &AccountId, _amount: Balance) -> DispatchResult {
+		unimplemented!()
+	}
+}
+
+parameter_types! {
+	pub const HomaPalletId: PalletId = PalletId(*b"aca/homa");
+}
+
+impl Config for Runtime {
+	type Event = Event;
+	type Homa = MockHoma;
+	type PalletId = HomaPalletId;
+	type WeightInfo = ();
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
+		Proxy: pallet_proxy::{Pallet, Call, Storage, Event<T>},
+		OrmlNFT: orml_nft::{Pallet, Storage, Config<T>},
+		NFTModule: module_nft::{Pallet, Call, Event<T>},
+		HomaModule: homa::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+#[derive(Default)]
+pub struct ExtBuilder {
+	unbondings: Vec<(AccountId, EraIndex, Balance)>,
+}
+
+impl ExtBuilder {
+	pub fn unbondings(mut self, unbondings: Vec<(AccountId, EraIndex, Balance)>) -> Self {
+		self.unbondings = unbondings;
+		self
+	}
+
+	pub fn build(self) -> sp_io::TestExternalities {
+		let t = frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap();
+
+		for (who, target_era, amount) in self.unbondings {
+			MockHoma::add_unbonding(&who, target_era, amount);
+		}
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
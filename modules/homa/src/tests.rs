@@ -0,0 +1,142 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the homa module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+
+#[test]
+fn issue_unbonding_receipt_works() {
+	ExtBuilder::default()
+		.unbondings(vec![(ALICE, 10, 1000)])
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				HomaModule::issue_unbonding_receipt(Origin::signed(ALICE), 11),
+				Error::<Runtime>::NoUnbonding
+			);
+
+			assert_ok!(HomaModule::issue_unbonding_receipt(Origin::signed(ALICE), 10));
+			System::assert_last_event(Event::HomaModule(crate::Event::UnbondingReceiptIssued(
+				ALICE, 0, 10, 1000,
+			)));
+			assert_eq!(unbonding(ALICE, 10), 0);
+			assert_eq!(HomaModule::unbonding_receipts(0), Some((10, 1000)));
+
+			let class_id = HomaModule::receipt_class_id().unwrap();
+			assert_eq!(OrmlNFT::classes(class_id).unwrap().owner, HomaModule::account_id());
+			assert_eq!(OrmlNFT::tokens(class_id, 0).unwrap().owner, ALICE);
+
+			// the pending unbonding is taken by the receipt
+			assert_noop!(
+				HomaModule::issue_unbonding_receipt(Origin::signed(ALICE), 10),
+				Error::<Runtime>::NoUnbonding
+			);
+		});
+}
+
+#[test]
+fn claim_unbonding_receipt_works() {
+	ExtBuilder::default()
+		.unbondings(vec![(ALICE, 10, 1000)])
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				HomaModule::claim_unbonding_receipt(Origin::signed(ALICE), 0),
+				Error::<Runtime>::ReceiptNotFound
+			);
+			assert_ok!(HomaModule::issue_unbonding_receipt(Origin::signed(ALICE), 10));
+			let class_id = HomaModule::receipt_class_id().unwrap();
+
+			assert_ok!(NFTModule::transfer(Origin::signed(ALICE), BOB, (class_id, 0)));
+			assert_noop!(
+				HomaModule::claim_unbonding_receipt(Origin::signed(ALICE), 0),
+				Error::<Runtime>::NotReceiptOwner
+			);
+
+			assert_ok!(HomaModule::claim_unbonding_receipt(Origin::signed(BOB), 0));
+			System::assert_last_event(Event::HomaModule(crate::Event::UnbondingReceiptClaimed(
+				BOB, 0, 10, 1000,
+			)));
+			assert_eq!(unbonding(ALICE, 10), 0);
+			assert_eq!(unbonding(BOB, 10), 1000);
+			assert_eq!(HomaModule::unbonding_receipts(0), None);
+			assert!(OrmlNFT::tokens(class_id, 0).is_none());
+		});
+}
+
+#[test]
+fn claim_unbonding_receipt_twice_fails() {
+	ExtBuilder::default()
+		.unbondings(vec![(ALICE, 10, 1000)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(HomaModule::issue_unbonding_receipt(Origin::signed(ALICE), 10));
+			assert_ok!(HomaModule::claim_unbonding_receipt(Origin::signed(ALICE), 0));
+			assert_eq!(unbonding(ALICE, 10), 1000);
+
+			assert_noop!(
+				HomaModule::claim_unbonding_receipt(Origin::signed(ALICE), 0),
+				Error::<Runtime>::ReceiptNotFound
+			);
+			assert_eq!(unbonding(ALICE, 10), 1000);
+		});
+}
+
+#[test]
+fn claim_unbonding_receipt_before_unlock() {
+	ExtBuilder::default()
+		.unbondings(vec![(ALICE, 10, 1000)])
+		.build()
+		.execute_with(|| {
+			set_current_era(5);
+			assert_ok!(HomaModule::issue_unbonding_receipt(Origin::signed(ALICE), 10));
+			assert_ok!(HomaModule::claim_unbonding_receipt(Origin::signed(ALICE), 0));
+
+			// claiming before the unlock returns the pending unbonding, not the DOT
+			assert_eq!(unbonding(ALICE, 10), 1000);
+			assert_ok!(HomaModule::withdraw_redemption(Origin::signed(ALICE)));
+			assert_eq!(withdrawn(ALICE), 0);
+			assert_eq!(unbonding(ALICE, 10), 1000);
+
+			set_current_era(10);
+			assert_ok!(HomaModule::withdraw_redemption(Origin::signed(ALICE)));
+			assert_eq!(withdrawn(ALICE), 1000);
+			assert_eq!(unbonding(ALICE, 10), 0);
+		});
+}
+
+#[test]
+fn receipt_is_not_burnable_by_owner() {
+	ExtBuilder::default()
+		.unbondings(vec![(ALICE, 10, 1000)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(HomaModule::issue_unbonding_receipt(Origin::signed(ALICE), 10));
+			let class_id = HomaModule::receipt_class_id().unwrap();
+
+			assert_noop!(
+				NFTModule::burn(Origin::signed(ALICE), (class_id, 0)),
+				module_nft::Error::<Runtime>::NonBurnable
+			);
+		});
+}
@@ -0,0 +1,172 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! RPC interface for the honzon module.
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use module_honzon_rpc_runtime_api::{CdpPosition, CollateralState, LiquidationSimulation};
+use sp_api::{ApiExt, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, MaybeDisplay, MaybeFromStr},
+	Permill,
+};
+use std::sync::Arc;
+
+pub use self::gen_client::Client as HonzonClient;
+pub use module_honzon_rpc_runtime_api::HonzonApi as HonzonRuntimeApi;
+
+#[rpc]
+pub trait HonzonApi<BlockHash, AccountId, CurrencyId, Balance> {
+	#[rpc(name = "honzon_getPositions")]
+	fn get_positions(
+		&self,
+		currency_id: CurrencyId,
+		at: Option<BlockHash>,
+	) -> Result<Vec<CdpPosition<AccountId, Balance>>>;
+
+	#[rpc(name = "honzon_getCollateralStates")]
+	fn get_collateral_states(&self, at: Option<BlockHash>) -> Result<Vec<CollateralState<CurrencyId, Balance>>>;
+
+	#[rpc(name = "honzon_simulateLiquidation")]
+	fn simulate_liquidation(
+		&self,
+		currency_id: CurrencyId,
+		price_drop: Permill,
+		at: Option<BlockHash>,
+	) -> Result<Option<LiquidationSimulation<AccountId, Balance>>>;
+}
+
+/// A struct that implements the [`HonzonApi`].
+pub struct Honzon<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Honzon<C, B> {
+	/// Create new `Honzon` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Honzon {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+pub enum Error {
+	RuntimeError,
+	ApiNotAvailable,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::ApiNotAvailable => 2,
+		}
+	}
+}
+
+/// The runtime API is not available at the blocks before the runtime upgrade introducing it.
+fn ensure_api_available(has_api: std::result::Result<bool, sp_api::ApiError>) -> Result<()> {
+	match has_api {
+		Ok(true) => Ok(()),
+		Ok(false) => Err(RpcError {
+			code: ErrorCode::ServerError(Error::ApiNotAvailable.into()),
+			message: "The runtime API is not available at the block.".into(),
+			data: None,
+		}),
+		Err(e) => Err(RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get the runtime API version.".into(),
+			data: Some(format!("{:?}", e).into()),
+		}),
+	}
+}
+
+impl<C, Block, AccountId, CurrencyId, Balance> HonzonApi<<Block as BlockT>::Hash, AccountId, CurrencyId, Balance>
+	for Honzon<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: HonzonRuntimeApi<Block, AccountId, CurrencyId, Balance>,
+	AccountId: Codec,
+	CurrencyId: Codec,
+	Balance: Codec + MaybeDisplay + MaybeFromStr,
+{
+	fn get_positions(
+		&self,
+		currency_id: CurrencyId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<CdpPosition<AccountId, Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		));
+		ensure_api_available(api.has_api::<dyn HonzonRuntimeApi<Block, AccountId, CurrencyId, Balance>>(&at))?;
+
+		api.get_positions(&at, currency_id).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get positions.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn get_collateral_states(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<CollateralState<CurrencyId, Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		));
+		ensure_api_available(api.has_api::<dyn HonzonRuntimeApi<Block, AccountId, CurrencyId, Balance>>(&at))?;
+
+		api.get_collateral_states(&at).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get collateral states.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn simulate_liquidation(
+		&self,
+		currency_id: CurrencyId,
+		price_drop: Permill,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<LiquidationSimulation<AccountId, Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		));
+		ensure_api_available(api.has_api::<dyn HonzonRuntimeApi<Block, AccountId, CurrencyId, Balance>>(&at))?;
+
+		api.simulate_liquidation(&at, currency_id, price_drop)
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError.into()),
+				message: "Unable to simulate liquidation.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})
+	}
+}
@@ -139,6 +139,7 @@ impl loans::Config for Runtime {
 	type CDPTreasury = CDPTreasuryModule;
 	type PalletId = LoansPalletId;
 	type OnUpdateLoan = ();
+	type EventTopics = ();
 }
 
 pub struct MockPriceSource;
@@ -208,6 +209,7 @@ parameter_types! {
 	pub const MaxAuctionsCount: u32 = 10_000;
 	pub const CDPTreasuryPalletId: PalletId = PalletId(*b"aca/cdpt");
 	pub TreasuryAccount: AccountId = PalletId(*b"aca/hztr").into_account();
+	pub const SurplusAllocationPeriod: BlockNumber = 10;
 }
 
 impl cdp_treasury::Config for Runtime {
@@ -220,6 +222,11 @@ impl cdp_treasury::Config for Runtime {
 	type MaxAuctionsCount = MaxAuctionsCount;
 	type PalletId = CDPTreasuryPalletId;
 	type TreasuryAccount = TreasuryAccount;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type SavingRateAccount = TreasuryAccount;
+	type StabilityBufferAccount = TreasuryAccount;
+	type SurplusAllocationPeriod = SurplusAllocationPeriod;
+	type MaxSlippageSwapWithDEX = MaxSlippageSwapWithDEX;
 	type WeightInfo = ();
 }
 
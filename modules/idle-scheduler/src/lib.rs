@@ -0,0 +1,273 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Idle Scheduler Module
+//!
+//! ## Overview
+//!
+//! The shared queue of background tasks, e.g. the cleanup of unbounded storage, which the modules
+//! push work into instead of doing it in `on_initialize` or never doing it. The tasks are
+//! dispatched in `on_idle` within the weight left in the block beyond
+//! `MinimumWeightRemainInBlock`. The queued tasks are dispatched in round-robin, each at most once
+//! in a block, and an unfinished task is moved to the back of the queue, so a long task doesn't
+//! starve the others. The queue is kept in a map between the head and tail indexes, so a dispatch
+//! only touches the entries of the dispatched tasks. The number of dispatches and failures and the
+//! used weight of each task are recorded, and reported when the task is finished. The changes and
+//! the progress of a failed dispatch are reverted, and the task is moved to the back of the queue
+//! to be retried, so the work it's responsible for is never dropped.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{pallet_prelude::*, storage::with_transaction, transactional};
+use frame_system::pallet_prelude::*;
+use sp_runtime::{traits::One, ArithmeticError, RuntimeDebug, TransactionOutcome};
+use support::{DispatchableTask, IdleScheduler, TaskResult};
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+/// The id of scheduled task.
+pub type TaskId = u32;
+
+/// The index of the entries of the task queue.
+pub type QueueIndex = u32;
+
+/// The scheduled task and its dispatch metrics.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct ScheduledTask<Task> {
+	/// The task.
+	pub task: Task,
+	/// The number of dispatches of the task.
+	pub dispatches: u32,
+	/// The number of failed dispatches of the task.
+	pub failures: u32,
+	/// The total weight used by the dispatches of the task.
+	pub used_weight: Weight,
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The background task, which is the combination of the tasks of the modules in runtime.
+		type Task: DispatchableTask + Parameter;
+
+		/// The weight kept unused in blocks, the tasks are dispatched in the weight left beyond it.
+		#[pallet::constant]
+		type MinimumWeightRemainInBlock: Get<Weight>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Scheduled the task. \[task_id, task\]
+		TaskScheduled(TaskId, T::Task),
+		/// The task is finished. \[task_id, dispatches, used_weight\]
+		TaskFinished(TaskId, u32, Weight),
+		/// The task failed and is retried later. \[task_id, error\]
+		TaskFailed(TaskId, DispatchError),
+	}
+
+	/// The id of the next scheduled task.
+	///
+	/// NextTaskId: TaskId
+	#[pallet::storage]
+	#[pallet::getter(fn next_task_id)]
+	pub type NextTaskId<T: Config> = StorageValue<_, TaskId, ValueQuery>;
+
+	/// The scheduled tasks.
+	///
+	/// Tasks: map TaskId => Option<ScheduledTask>
+	#[pallet::storage]
+	#[pallet::getter(fn tasks)]
+	pub type Tasks<T: Config> = StorageMap<_, Twox64Concat, TaskId, ScheduledTask<T::Task>, OptionQuery>;
+
+	/// The ids of the scheduled tasks, dispatched in the order of the index from `QueueHead` to
+	/// `QueueTail`.
+	///
+	/// TaskQueue: map QueueIndex => Option<TaskId>
+	#[pallet::storage]
+	#[pallet::getter(fn task_queue)]
+	pub type TaskQueue<T: Config> = StorageMap<_, Twox64Concat, QueueIndex, TaskId, OptionQuery>;
+
+	/// The index of the first entry of the task queue.
+	///
+	/// QueueHead: QueueIndex
+	#[pallet::storage]
+	#[pallet::getter(fn queue_head)]
+	pub type QueueHead<T: Config> = StorageValue<_, QueueIndex, ValueQuery>;
+
+	/// The index after the last entry of the task queue.
+	///
+	/// QueueTail: QueueIndex
+	#[pallet::storage]
+	#[pallet::getter(fn queue_tail)]
+	pub type QueueTail<T: Config> = StorageValue<_, QueueIndex, ValueQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_idle(_now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			Self::dispatch_tasks(remaining_weight.saturating_sub(T::MinimumWeightRemainInBlock::get()))
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Schedule a background task.
+		///
+		/// The dispatch origin of this call must be _Root_.
+		///
+		/// - `task`: the task.
+		#[pallet::weight(T::WeightInfo::schedule_task())]
+		#[transactional]
+		pub fn schedule_task(origin: OriginFor<T>, task: T::Task) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			Self::do_schedule_task(task)?;
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	fn do_schedule_task(task: T::Task) -> DispatchResult {
+		let task_id = NextTaskId::<T>::try_mutate(|id| -> Result<TaskId, DispatchError> {
+			let current_id = *id;
+			*id = id.checked_add(One::one()).ok_or(ArithmeticError::Overflow)?;
+			Ok(current_id)
+		})?;
+		Tasks::<T>::insert(
+			task_id,
+			ScheduledTask {
+				task: task.clone(),
+				dispatches: 0,
+				failures: 0,
+				used_weight: 0,
+			},
+		);
+		Self::push_task(task_id);
+
+		Self::deposit_event(Event::TaskScheduled(task_id, task));
+		Ok(())
+	}
+
+	/// Push `task_id` to the back of the queue.
+	fn push_task(task_id: TaskId) {
+		QueueTail::<T>::mutate(|tail| {
+			TaskQueue::<T>::insert(*tail, task_id);
+			*tail = tail.wrapping_add(1);
+		});
+	}
+
+	/// Dispatch the queued tasks in round-robin within `weight`, each at most once. Returns the
+	/// used weight.
+	pub fn dispatch_tasks(weight: Weight) -> Weight {
+		let mut used_weight = T::WeightInfo::on_idle();
+		if weight <= used_weight {
+			return 0;
+		}
+
+		// the tasks pushed back or scheduled during the dispatches are queued after `end`
+		let mut head = Self::queue_head();
+		let end = Self::queue_tail();
+		while head != end {
+			let remaining_weight = weight
+				.saturating_sub(used_weight)
+				.saturating_sub(T::WeightInfo::dispatch_task());
+			if remaining_weight == 0 {
+				break;
+			}
+
+			let queued = TaskQueue::<T>::take(head)
+				.and_then(|task_id| Self::tasks(task_id).map(|scheduled| (task_id, scheduled)));
+			head = head.wrapping_add(1);
+			used_weight = used_weight.saturating_add(T::WeightInfo::dispatch_task());
+
+			let (task_id, mut scheduled) = match queued {
+				Some(queued) => queued,
+				None => continue,
+			};
+			let mut task = scheduled.task.clone();
+			let TaskResult {
+				result,
+				used_weight: task_weight,
+				finished,
+			} = with_transaction(|| {
+				let task_result = task.dispatch(remaining_weight);
+				if task_result.result.is_ok() {
+					TransactionOutcome::Commit(task_result)
+				} else {
+					TransactionOutcome::Rollback(task_result)
+				}
+			});
+			let task_weight = task_weight.min(remaining_weight);
+			used_weight = used_weight.saturating_add(task_weight);
+			scheduled.dispatches = scheduled.dispatches.saturating_add(1);
+			scheduled.used_weight = scheduled.used_weight.saturating_add(task_weight);
+
+			match result {
+				Err(e) => {
+					// the progress is discarded with the reverted changes, the retry continues from
+					// the last successful dispatch
+					scheduled.failures = scheduled.failures.saturating_add(1);
+					Tasks::<T>::insert(task_id, scheduled);
+					Self::push_task(task_id);
+					Self::deposit_event(Event::TaskFailed(task_id, e));
+				}
+				Ok(()) if finished => {
+					Tasks::<T>::remove(task_id);
+					Self::deposit_event(Event::TaskFinished(
+						task_id,
+						scheduled.dispatches,
+						scheduled.used_weight,
+					));
+				}
+				Ok(()) => {
+					scheduled.task = task;
+					Tasks::<T>::insert(task_id, scheduled);
+					Self::push_task(task_id);
+				}
+			}
+		}
+
+		QueueHead::<T>::put(head);
+
+		used_weight
+	}
+}
+
+impl<T: Config, Task: Into<T::Task>> IdleScheduler<Task> for Pallet<T> {
+	fn schedule(task: Task) -> Result<Weight, DispatchError> {
+		Self::do_schedule_task(task.into())?;
+		Ok(T::WeightInfo::schedule_task())
+	}
+}
@@ -0,0 +1,156 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Mocks for idle scheduler module.
+
+#![cfg(test)]
+
+use super::*;
+
+use crate as idle_scheduler;
+use frame_support::{construct_runtime, parameter_types};
+use sp_core::H256;
+use sp_runtime::{testing::Header, traits::IdentityLookup};
+use std::cell::RefCell;
+
+pub type AccountId = u128;
+pub type BlockNumber = u64;
+
+pub const ALICE: AccountId = 1;
+pub const ITEM_WEIGHT: Weight = 1_000_000_000;
+pub const FAILED_TASK_KEY: &[u8] = b"failed_task";
+
+thread_local! {
+	static COUNTDOWN_FAILS: RefCell<bool> = RefCell::new(false);
+}
+
+/// Makes the dispatches of `MockTask::Countdown` fail after processing the items.
+pub fn set_countdown_fails(fails: bool) {
+	COUNTDOWN_FAILS.with(|v| *v.borrow_mut() = fails);
+}
+
+/// The mock task.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum MockTask {
+	/// Processes the remaining items, each takes `ITEM_WEIGHT`. Fails after processing the items
+	/// if set by `set_countdown_fails`.
+	Countdown(u32),
+	/// Writes `FAILED_TASK_KEY` and fails.
+	Fail,
+}
+
+impl DispatchableTask for MockTask {
+	fn dispatch(&mut self, weight: Weight) -> TaskResult {
+		match self {
+			MockTask::Countdown(remaining) => {
+				let count = (*remaining as Weight).min(weight / ITEM_WEIGHT) as u32;
+				*remaining -= count;
+				let result = if COUNTDOWN_FAILS.with(|v| *v.borrow()) {
+					Err(DispatchError::Other("countdown failed"))
+				} else {
+					Ok(())
+				};
+				TaskResult {
+					result,
+					used_weight: (count as Weight).saturating_mul(ITEM_WEIGHT),
+					finished: *remaining == 0,
+				}
+			}
+			MockTask::Fail => {
+				sp_io::storage::set(FAILED_TASK_KEY, &[1]);
+				TaskResult {
+					result: Err(DispatchError::Other("failed")),
+					used_weight: ITEM_WEIGHT,
+					finished: false,
+				}
+			}
+		}
+	}
+}
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Runtime {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+parameter_types! {
+	pub const MinimumWeightRemainInBlock: Weight = 10_000_000_000;
+}
+
+impl Config for Runtime {
+	type Event = Event;
+	type Task = MockTask;
+	type MinimumWeightRemainInBlock = MinimumWeightRemainInBlock;
+	type WeightInfo = ();
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		IdleSchedulerModule: idle_scheduler::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+#[derive(Default)]
+pub struct ExtBuilder;
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let t = frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap();
+
+		set_countdown_fails(false);
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
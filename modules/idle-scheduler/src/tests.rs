@@ -0,0 +1,290 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for idle scheduler module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+use sp_runtime::traits::BadOrigin;
+
+/// The weight to dispatch the tasks which process `items` respectively.
+fn dispatch_weight(items: &[u32]) -> Weight {
+	items.iter().fold(<() as WeightInfo>::on_idle(), |weight, count| {
+		weight
+			.saturating_add(<() as WeightInfo>::dispatch_task())
+			.saturating_add((*count as Weight).saturating_mul(ITEM_WEIGHT))
+	})
+}
+
+/// The ids of the queued tasks, from the head to the tail of the queue.
+fn queued_tasks() -> Vec<TaskId> {
+	(IdleSchedulerModule::queue_head()..IdleSchedulerModule::queue_tail())
+		.filter_map(IdleSchedulerModule::task_queue)
+		.collect()
+}
+
+#[test]
+fn schedule_task_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			IdleSchedulerModule::schedule_task(Origin::signed(ALICE), MockTask::Countdown(1)),
+			BadOrigin
+		);
+
+		assert_ok!(IdleSchedulerModule::schedule_task(
+			Origin::root(),
+			MockTask::Countdown(1)
+		));
+		System::assert_last_event(Event::IdleSchedulerModule(crate::Event::TaskScheduled(
+			0,
+			MockTask::Countdown(1),
+		)));
+		assert_ok!(<IdleSchedulerModule as IdleScheduler<MockTask>>::schedule(
+			MockTask::Fail
+		));
+		System::assert_last_event(Event::IdleSchedulerModule(crate::Event::TaskScheduled(
+			1,
+			MockTask::Fail,
+		)));
+
+		assert_eq!(
+			IdleSchedulerModule::tasks(0),
+			Some(ScheduledTask {
+				task: MockTask::Countdown(1),
+				dispatches: 0,
+				failures: 0,
+				used_weight: 0,
+			})
+		);
+		assert_eq!(queued_tasks(), vec![0, 1]);
+		assert_eq!(IdleSchedulerModule::queue_head(), 0);
+		assert_eq!(IdleSchedulerModule::queue_tail(), 2);
+		assert_eq!(IdleSchedulerModule::next_task_id(), 2);
+	});
+}
+
+#[test]
+fn dispatch_tasks_in_round_robin() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(IdleSchedulerModule::schedule_task(
+			Origin::root(),
+			MockTask::Countdown(5)
+		));
+		assert_ok!(IdleSchedulerModule::schedule_task(
+			Origin::root(),
+			MockTask::Countdown(1)
+		));
+		assert_ok!(IdleSchedulerModule::schedule_task(
+			Origin::root(),
+			MockTask::Countdown(3)
+		));
+
+		// not enough weight to dispatch any task
+		assert_eq!(IdleSchedulerModule::dispatch_tasks(<() as WeightInfo>::on_idle()), 0);
+		assert_eq!(queued_tasks(), vec![0, 1, 2]);
+
+		// the first task is dispatched and moved to the back of the queue
+		assert_eq!(
+			IdleSchedulerModule::dispatch_tasks(dispatch_weight(&[2])),
+			dispatch_weight(&[2])
+		);
+		assert_eq!(
+			IdleSchedulerModule::tasks(0),
+			Some(ScheduledTask {
+				task: MockTask::Countdown(3),
+				dispatches: 1,
+				failures: 0,
+				used_weight: 2 * ITEM_WEIGHT,
+			})
+		);
+		assert_eq!(queued_tasks(), vec![1, 2, 0]);
+
+		// the finished task is removed
+		assert_eq!(
+			IdleSchedulerModule::dispatch_tasks(dispatch_weight(&[1, 2])),
+			dispatch_weight(&[1, 2])
+		);
+		assert_eq!(
+			System::events()
+				.iter()
+				.filter(
+					|record| record.event == Event::IdleSchedulerModule(crate::Event::TaskFinished(1, 1, ITEM_WEIGHT))
+				)
+				.count(),
+			1
+		);
+		assert_eq!(IdleSchedulerModule::tasks(1), None);
+		assert_eq!(
+			IdleSchedulerModule::tasks(2),
+			Some(ScheduledTask {
+				task: MockTask::Countdown(1),
+				dispatches: 1,
+				failures: 0,
+				used_weight: 2 * ITEM_WEIGHT,
+			})
+		);
+		assert_eq!(queued_tasks(), vec![0, 2]);
+
+		// each task is dispatched at most once in a call
+		assert_eq!(
+			IdleSchedulerModule::dispatch_tasks(dispatch_weight(&[10, 10])),
+			dispatch_weight(&[3, 1])
+		);
+		assert_eq!(
+			System::events()
+				.iter()
+				.filter(|record| record.event
+					== Event::IdleSchedulerModule(crate::Event::TaskFinished(0, 2, 5 * ITEM_WEIGHT)))
+				.count(),
+			1
+		);
+		System::assert_last_event(Event::IdleSchedulerModule(crate::Event::TaskFinished(
+			2,
+			2,
+			3 * ITEM_WEIGHT,
+		)));
+		assert_eq!(IdleSchedulerModule::tasks(0), None);
+		assert_eq!(IdleSchedulerModule::tasks(2), None);
+		assert_eq!(queued_tasks(), Vec::<TaskId>::new());
+		assert_eq!(IdleSchedulerModule::task_queue(4), None);
+		assert_eq!(IdleSchedulerModule::queue_head(), 5);
+		assert_eq!(IdleSchedulerModule::queue_tail(), 5);
+	});
+}
+
+#[test]
+fn failed_task_is_retried() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(IdleSchedulerModule::schedule_task(Origin::root(), MockTask::Fail));
+		assert_ok!(IdleSchedulerModule::schedule_task(
+			Origin::root(),
+			MockTask::Countdown(1)
+		));
+
+		assert_eq!(
+			IdleSchedulerModule::dispatch_tasks(dispatch_weight(&[1, 1])),
+			dispatch_weight(&[1, 1])
+		);
+		assert_eq!(
+			System::events()
+				.iter()
+				.filter(|record| record.event
+					== Event::IdleSchedulerModule(crate::Event::TaskFailed(0, DispatchError::Other("failed"))))
+				.count(),
+			1
+		);
+		assert_eq!(
+			IdleSchedulerModule::tasks(0),
+			Some(ScheduledTask {
+				task: MockTask::Fail,
+				dispatches: 1,
+				failures: 1,
+				used_weight: ITEM_WEIGHT,
+			})
+		);
+		assert_eq!(IdleSchedulerModule::tasks(1), None);
+		assert_eq!(queued_tasks(), vec![0]);
+
+		// the changes of the failed dispatch are reverted
+		assert_eq!(sp_io::storage::get(FAILED_TASK_KEY), None);
+
+		// the failed task is dispatched again
+		assert_eq!(
+			IdleSchedulerModule::dispatch_tasks(dispatch_weight(&[1])),
+			dispatch_weight(&[1])
+		);
+		assert_eq!(
+			IdleSchedulerModule::tasks(0).map(|scheduled| scheduled.failures),
+			Some(2)
+		);
+		assert_eq!(queued_tasks(), vec![0]);
+	});
+}
+
+#[test]
+fn failed_dispatch_keeps_the_progress_of_the_last_successful_one() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(IdleSchedulerModule::schedule_task(
+			Origin::root(),
+			MockTask::Countdown(5)
+		));
+
+		set_countdown_fails(true);
+		assert_eq!(
+			IdleSchedulerModule::dispatch_tasks(dispatch_weight(&[2])),
+			dispatch_weight(&[2])
+		);
+		System::assert_last_event(Event::IdleSchedulerModule(crate::Event::TaskFailed(
+			0,
+			DispatchError::Other("countdown failed"),
+		)));
+		assert_eq!(
+			IdleSchedulerModule::tasks(0),
+			Some(ScheduledTask {
+				task: MockTask::Countdown(5),
+				dispatches: 1,
+				failures: 1,
+				used_weight: 2 * ITEM_WEIGHT,
+			})
+		);
+		assert_eq!(queued_tasks(), vec![0]);
+
+		set_countdown_fails(false);
+		assert_eq!(
+			IdleSchedulerModule::dispatch_tasks(dispatch_weight(&[5])),
+			dispatch_weight(&[5])
+		);
+		System::assert_last_event(Event::IdleSchedulerModule(crate::Event::TaskFinished(
+			0,
+			2,
+			7 * ITEM_WEIGHT,
+		)));
+		assert_eq!(IdleSchedulerModule::tasks(0), None);
+		assert_eq!(queued_tasks(), Vec::<TaskId>::new());
+		assert_eq!(IdleSchedulerModule::queue_head(), 2);
+		assert_eq!(IdleSchedulerModule::queue_tail(), 2);
+	});
+}
+
+#[test]
+fn on_idle_keeps_minimum_weight_remain_in_block() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(IdleSchedulerModule::schedule_task(
+			Origin::root(),
+			MockTask::Countdown(5)
+		));
+
+		assert_eq!(IdleSchedulerModule::on_idle(1, MinimumWeightRemainInBlock::get()), 0);
+		assert_eq!(
+			IdleSchedulerModule::tasks(0).map(|scheduled| scheduled.task),
+			Some(MockTask::Countdown(5))
+		);
+
+		assert_eq!(
+			IdleSchedulerModule::on_idle(1, MinimumWeightRemainInBlock::get() + dispatch_weight(&[2])),
+			dispatch_weight(&[2])
+		);
+		assert_eq!(
+			IdleSchedulerModule::tasks(0).map(|scheduled| scheduled.task),
+			Some(MockTask::Countdown(3))
+		);
+	});
+}
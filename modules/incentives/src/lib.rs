@@ -45,9 +45,50 @@
 //! 3. Liquid currency(LDOT/LKSM): reward comes from the transfer of other accounts(Usually are the
 //! validators on the relay chain).
 //!
+//! Besides the reward currency type of the pool, LoansIncentive/DexIncentive/HomaIncentive can
+//! distribute several extra reward currencies(e.g. KAR or project tokens) simultaneously, each one
+//! with its own reward amount per period and claim accounting. Rewards of extra reward currencies
+//! come from the transfer of ExtraRewardsSource.
+//!
+//! The rewards of a pool can be escrowed instead of paid out immediately, they vest linearly over
+//! the vesting period of the pool since claimed. Each claim vests in its own entry, at most
+//! `MaxEscrowedRewards` entries per user, pool and currency, the new rewards vest with the latest
+//! entry once the entries are full. Users can claim the vested rewards at any time, or
+//! exit early to claim all the escrowed rewards at the cost of forfeiting a portion of the unvested
+//! rewards back to the pool.
+//!
+//! The shares of LoansIncentive/DexIncentive are boosted by the voting power of the user in the
+//! voting escrow. The working share of the user in the pool is
+//! `min(40% * share + 60% * total_shares * voting_power / total_voting_power, share)`, so the
+//! account with enough voting power earns up to 2.5x rewards of the one without. The working share
+//! is updated when the share changes, when the user claims the rewards of the pool, or by
+//! `update_boost` which anyone can call to kick the boost of the user once the voting power decays.
+//! The boost is disabled if there is no voting power at all.
+//!
+//! The incentive reward amount per period of LoansIncentive pools is scaled by the risk weight of
+//! the collateral type, which is set by governance to incentivize borrowing against some collateral
+//! types more than the others. It's unscaled if the risk weight is not set. The risk weight never
+//! exceeds 100%, so the scaled amount is capped at the allocation of the pool, and at most
+//! `MaxLoansRiskWeights` collateral types have the risk weight.
+//!
+//! The total incentive reward amount per period of all the pools, by the reward amounts, the
+//! emission schedules and the risk weights, can't be raised above `IncentiveRewardsBudget`.
+//!
+//! Any account can top up the rewards of LoansIncentive/DexIncentive/HomaIncentive pools to
+//! co-incentivize them without governance, by an amount per period for a number of periods. The
+//! full amount is escrowed in the vault up front and accumulated to the pool period by period,
+//! the top-up is paused while the pool has no shares.
+//!
+//! The payout deduction can be exempted for specific accounts(e.g. protocol owned liquidity) of
+//! the pool. The pool can also have loyalty tiers, the deduction rate of the user decreases with the
+//! duration staking in the pool, which is reset when the share of the user decreases, and weighted
+//! by the amount when the share increases, so the added share starts staking from now.
+//!
 //! Reward accumulation:
 //! 1. LoansIncentive/DexIncentive/HomaIncentive/DexSaving: the fixed blocks is
-//! period(AccumulatePeriod), and on the beginning of each period will accumulate reward.
+//! period(AccumulatePeriod), and on the beginning of each period will accumulate reward. The
+//! accumulation of the pools is scheduled as a background task of the idle scheduler, so the
+//! number of pools doesn't weigh on `on_initialize`.
 //! 2. HomaValidatorAllowance: transfer rewards into the vault account.
 
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -58,12 +99,18 @@ use frame_support::{log, pallet_prelude::*, transactional, PalletId};
 use frame_system::pallet_prelude::*;
 use orml_traits::{Happened, MultiCurrency, RewardHandler};
 use primitives::{Amount, Balance, CurrencyId};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 use sp_runtime::{
-	traits::{AccountIdConversion, MaybeDisplay, One, UniqueSaturatedInto, Zero},
-	DispatchResult, FixedPointNumber, RuntimeDebug,
+	helpers_128bit::multiply_by_rational,
+	traits::{AccountIdConversion, AtLeast32BitUnsigned, MaybeDisplay, One, Saturating, UniqueSaturatedInto, Zero},
+	ArithmeticError, DispatchResult, FixedPointNumber, RuntimeDebug,
+};
+use sp_std::{collections::btree_map::BTreeMap, fmt::Debug, prelude::*};
+use support::{
+	CDPTreasury, DEXIncentives, DEXManager, DispatchableTask, IdleScheduler, IncentivesManager, IncentivesRewards,
+	OnEmergencyShutdown, Price, Rate, TaskResult, VotingEscrow,
 };
-use sp_std::{fmt::Debug, vec::Vec};
-use support::{CDPTreasury, DEXIncentives, DEXManager, EmergencyShutdown, Rate};
 
 mod mock;
 mod tests;
@@ -74,6 +121,7 @@ pub use weights::WeightInfo;
 
 /// PoolId for various rewards pools
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum PoolId<AccountId> {
 	/// Rewards pool(NativeCurrencyId) for users who open CDP
 	LoansIncentive(CurrencyId),
@@ -94,6 +142,208 @@ pub enum PoolId<AccountId> {
 	HomaValidatorAllowance(AccountId),
 }
 
+/// The background tasks of the module.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum IncentivesTask<T: Config> {
+	/// Accumulate the rewards of the period at `now` to the remaining `pools`.
+	AccumulateRewards {
+		now: T::BlockNumber,
+		pools: Vec<PoolId<T::RelaychainAccountId>>,
+	},
+}
+
+impl<T: Config> DispatchableTask for IncentivesTask<T> {
+	fn dispatch(&mut self, weight: Weight) -> TaskResult {
+		match self {
+			IncentivesTask::AccumulateRewards { now, pools } => {
+				// the accumulation is stopped by emergency shutdown
+				if Pallet::<T>::is_accumulation_stopped() {
+					pools.clear();
+				}
+
+				// the weight to accumulate a pool or a top-up of it
+				let item_weight = T::WeightInfo::on_initialize(1).saturating_sub(T::WeightInfo::on_initialize(0));
+				let max_pool_weight = item_weight.saturating_mul(T::MaxTopUps::get().saturating_add(1) as Weight);
+				let mut used_weight: Weight = 0;
+				let mut accumulated: usize = 0;
+				for pool_id in pools.iter() {
+					if used_weight.saturating_add(max_pool_weight) > weight {
+						break;
+					}
+					let count = Pallet::<T>::accumulate_pool_rewards(pool_id, *now);
+					used_weight = used_weight.saturating_add(item_weight.saturating_mul(count as Weight));
+					accumulated += 1;
+				}
+				pools.drain(..accumulated);
+
+				TaskResult {
+					result: Ok(()),
+					used_weight,
+					finished: pools.is_empty(),
+				}
+			}
+		}
+	}
+}
+
+/// The rewards accounting of an extra reward currency of a pool.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, Default)]
+pub struct ExtraRewardInfo {
+	/// Total rewards accumulated, include the virtual rewards for shares
+	/// added later.
+	pub total_rewards: Balance,
+	/// Total rewards withdrawn, include the virtual rewards for shares added
+	/// later.
+	pub total_withdrawn_rewards: Balance,
+}
+
+/// The vesting params of the rewards of a pool.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, Default)]
+pub struct RewardsVestingParams<BlockNumber> {
+	/// The period for the escrowed rewards to vest linearly.
+	pub period: BlockNumber,
+	/// The rate of the unvested rewards to forfeit back to the pool when exit
+	/// early.
+	pub early_exit_penalty_rate: Rate,
+}
+
+/// An entry of the escrowed rewards of a user, which vest linearly from
+/// `start` over `period`.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, Default)]
+pub struct EscrowedReward<BlockNumber> {
+	/// The block number the vesting starts.
+	pub start: BlockNumber,
+	/// The period to vest all the escrowed rewards.
+	pub period: BlockNumber,
+	/// Total escrowed rewards.
+	pub total: Balance,
+	/// The vested rewards already claimed.
+	pub claimed: Balance,
+}
+
+impl<BlockNumber: AtLeast32BitUnsigned + Copy> EscrowedReward<BlockNumber> {
+	/// The vested rewards at `now`, include the claimed.
+	fn vested(&self, now: BlockNumber) -> Balance {
+		let elapsed = now.saturating_sub(self.start);
+		if elapsed >= self.period {
+			self.total
+		} else {
+			multiply_by_rational(
+				self.total,
+				elapsed.unique_saturated_into(),
+				self.period.unique_saturated_into(),
+			)
+			.unwrap_or_default()
+		}
+	}
+}
+
+/// The deposit limits of a pool.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, Default)]
+pub struct DepositLimits {
+	/// The maximum total shares of the pool, unlimited if none.
+	pub max_total_shares: Option<Balance>,
+	/// The maximum shares of an account in the pool, unlimited if none.
+	pub max_account_shares: Option<Balance>,
+	/// Whether only the whitelisted accounts can deposit.
+	pub whitelist_only: bool,
+}
+
+/// The loyalty tier of the payout deduction of a pool.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, Default)]
+pub struct LoyaltyTier<BlockNumber> {
+	/// The minimum duration staking in the pool to reach the tier.
+	pub min_duration: BlockNumber,
+	/// The payout deduction rate of the tier.
+	pub deduction_rate: Rate,
+}
+
+/// The third party top-up of the rewards of a pool.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct RewardTopUp<AccountId> {
+	/// The account funds the top-up.
+	pub funder: AccountId,
+	/// The reward currency.
+	pub currency_id: CurrencyId,
+	/// The reward amount to accumulate per period.
+	pub amount_per_period: Balance,
+	/// The number of periods left to accumulate.
+	pub remaining_periods: u32,
+}
+
+/// The decay curve of the emission schedule.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum DecayCurve<BlockNumber> {
+	/// The reward amount per period keeps constant.
+	Constant,
+	/// The reward amount per period decays linearly to zero at the end.
+	Linear,
+	/// The reward amount per period decays by `rate` every `period`.
+	Exponential { period: BlockNumber, rate: Rate },
+}
+
+/// The emission schedule of the incentive rewards of a pool.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct EmissionSchedule<BlockNumber> {
+	/// The block number the emission starts.
+	pub start: BlockNumber,
+	/// The block number the emission ends.
+	pub end: BlockNumber,
+	/// The reward amount per period at the start.
+	pub initial_reward_amount: Balance,
+	/// The decay curve of the reward amount per period.
+	pub curve: DecayCurve<BlockNumber>,
+}
+
+impl<BlockNumber: AtLeast32BitUnsigned + Copy> EmissionSchedule<BlockNumber> {
+	/// Whether the schedule is valid.
+	fn is_valid(&self) -> bool {
+		self.start < self.end
+			&& match self.curve {
+				DecayCurve::Constant | DecayCurve::Linear => true,
+				DecayCurve::Exponential { period, rate } => !period.is_zero() && rate <= Rate::one(),
+			}
+	}
+
+	/// The reward amount per period at `now`.
+	fn reward_amount(&self, now: BlockNumber) -> Balance {
+		if now < self.start || now >= self.end {
+			return Zero::zero();
+		}
+
+		match self.curve {
+			DecayCurve::Constant => self.initial_reward_amount,
+			DecayCurve::Linear => multiply_by_rational(
+				self.initial_reward_amount,
+				self.end.saturating_sub(now).unique_saturated_into(),
+				self.end.saturating_sub(self.start).unique_saturated_into(),
+			)
+			.unwrap_or_default(),
+			DecayCurve::Exponential { period, rate } => {
+				let decayed_periods: u32 = (now.saturating_sub(self.start) / period).unique_saturated_into();
+				Rate::one()
+					.saturating_sub(rate)
+					.saturating_pow(decayed_periods as usize)
+					.saturating_mul_int(self.initial_reward_amount)
+			}
+		}
+	}
+}
+
+/// The storage versions of the module.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum Releases {
+	V1_0_0,
+	/// `TotalRawShares` seeded for the existing boosted pools.
+	V2_0_0,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V1_0_0
+	}
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -101,7 +351,7 @@ pub mod module {
 	#[pallet::config]
 	pub trait Config:
 		frame_system::Config
-		+ orml_rewards::Config<Share = Balance, Balance = Balance, PoolId = PoolId<Self::RelaychainAccountId>>
+		+ module_rewards::Config<Share = Balance, Balance = Balance, PoolId = PoolId<Self::RelaychainAccountId>>
 	{
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
@@ -128,6 +378,10 @@ pub mod module {
 		#[pallet::constant]
 		type NativeRewardsSource: Get<Self::AccountId>;
 
+		/// The source account for rewards of extra reward currencies.
+		#[pallet::constant]
+		type ExtraRewardsSource: Get<Self::AccountId>;
+
 		/// The vault account to keep rewards.
 		#[pallet::constant]
 		type RewardsVaultAccountId: Get<Self::AccountId>;
@@ -144,13 +398,42 @@ pub mod module {
 		/// DEX to supply liquidity info
 		type DEX: DEXManager<Self::AccountId, CurrencyId, Balance>;
 
-		/// Emergency shutdown.
-		type EmergencyShutdown: EmergencyShutdown;
+		/// The voting escrow to boost the shares of incentive pools.
+		type VotingEscrow: VotingEscrow<Self::AccountId, Balance>;
+
+		/// The budget of the incentive rewards of all the pools per
+		/// `AccumulatePeriod`.
+		type IncentiveRewardsBudget: Get<Balance>;
+
+		/// The maximum number of pools to claim rewards in one
+		/// `claim_all_rewards`.
+		#[pallet::constant]
+		type MaxClaimAllPools: Get<u32>;
+
+		/// The maximum number of loyalty tiers of a pool.
+		#[pallet::constant]
+		type MaxLoyaltyTiers: Get<u32>;
+
+		/// The maximum number of ongoing top-ups of a pool.
+		#[pallet::constant]
+		type MaxTopUps: Get<u32>;
+
+		/// The maximum number of the escrowed rewards entries of a user in a
+		/// pool for a reward currency.
+		#[pallet::constant]
+		type MaxEscrowedRewards: Get<u32>;
+
+		/// The maximum number of collateral types with the risk weight.
+		#[pallet::constant]
+		type MaxLoansRiskWeights: Get<u32>;
 
 		/// The module id, keep DexShare LP.
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
 
+		/// Schedules the background tasks, e.g. the accumulation of the rewards of the pools.
+		type IdleScheduler: IdleScheduler<IncentivesTask<Self>>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -165,6 +448,38 @@ pub mod module {
 		InvalidPoolId,
 		/// Invalid rate
 		InvalidRate,
+		/// Invalid vesting period
+		InvalidPeriod,
+		/// No escrowed rewards
+		NoEscrowedRewards,
+		/// Invalid emission schedule
+		InvalidSchedule,
+		/// The total shares of the pool exceed the cap
+		ExceedPoolCap,
+		/// The shares of the account in the pool exceed the cap
+		ExceedAccountCap,
+		/// The account is not in the whitelist of the pool
+		NotWhitelisted,
+		/// No rewards to compound
+		NoRewardsToCompound,
+		/// Invalid loyalty tiers
+		InvalidLoyaltyTiers,
+		/// Invalid top-up amount or periods
+		InvalidTopUp,
+		/// Exceed the maximum number of ongoing top-ups of the pool
+		TooManyTopUps,
+		/// Exceed the maximum number of pools to claim at once
+		TooManyPools,
+		/// The currency is not allowed to top up rewards
+		TopUpCurrencyNotAllowed,
+		/// The top-up amount per period is below the minimum
+		BelowMinTopUpAmount,
+		/// The top-up does not exist
+		TopUpNotFound,
+		/// The total incentive reward amount exceeds the budget
+		ExceedIncentiveRewardsBudget,
+		/// Exceed the maximum number of collateral types with the risk weight
+		TooManyRiskWeights,
 	}
 
 	#[pallet::event]
@@ -188,6 +503,61 @@ pub mod module {
 		SavingRewardRateUpdated(PoolId<T::RelaychainAccountId>, Rate),
 		/// Payout deduction rate updated. \[pool_id, deduction_rate\]
 		PayoutDeductionRateUpdated(PoolId<T::RelaychainAccountId>, Rate),
+		/// Extra incentive reward amount updated. \[pool_id,
+		/// reward_currency_type, reward_amount_per_period\]
+		ExtraIncentiveRewardAmountUpdated(PoolId<T::RelaychainAccountId>, CurrencyId, Balance),
+		/// Rewards vesting params updated. \[pool_id, vesting_params\]
+		RewardsVestingUpdated(
+			PoolId<T::RelaychainAccountId>,
+			Option<RewardsVestingParams<T::BlockNumber>>,
+		),
+		/// Rewards escrowed. \[who, pool_id, reward_currency_type, amount\]
+		RewardsEscrowed(T::AccountId, PoolId<T::RelaychainAccountId>, CurrencyId, Balance),
+		/// Escrowed rewards claimed. \[who, pool_id, reward_currency_type,
+		/// claimed_amount, forfeited_amount\]
+		EscrowedRewardsClaimed(
+			T::AccountId,
+			PoolId<T::RelaychainAccountId>,
+			CurrencyId,
+			Balance,
+			Balance,
+		),
+		/// Working share updated by the boost. \[who, pool_id, share,
+		/// working_share\]
+		WorkingShareUpdated(T::AccountId, PoolId<T::RelaychainAccountId>, Balance, Balance),
+		/// Emission schedule updated. \[pool_id, emission_schedule\]
+		EmissionScheduleUpdated(PoolId<T::RelaychainAccountId>, Option<EmissionSchedule<T::BlockNumber>>),
+		/// Deposit limits updated. \[pool_id, deposit_limits\]
+		DepositLimitsUpdated(PoolId<T::RelaychainAccountId>, Option<DepositLimits>),
+		/// Deposit whitelist updated. \[pool_id, who, is_whitelisted\]
+		DepositWhitelistUpdated(PoolId<T::RelaychainAccountId>, T::AccountId, bool),
+		/// Rewards compounded. \[who, pool_id, reward_currency_type,
+		/// compounded_amount\]
+		RewardsCompounded(T::AccountId, PoolId<T::RelaychainAccountId>, CurrencyId, Balance),
+		/// Payout deduction exemption updated. \[pool_id, who, is_exempted\]
+		DeductionExemptionUpdated(PoolId<T::RelaychainAccountId>, T::AccountId, bool),
+		/// Loyalty tiers updated. \[pool_id, loyalty_tiers\]
+		LoyaltyTiersUpdated(PoolId<T::RelaychainAccountId>, Vec<LoyaltyTier<T::BlockNumber>>),
+		/// Rewards topped up. \[funder, pool_id, top_up_id, reward_currency_type,
+		/// amount_per_period, periods\]
+		RewardsToppedUp(
+			T::AccountId,
+			PoolId<T::RelaychainAccountId>,
+			u32,
+			CurrencyId,
+			Balance,
+			u32,
+		),
+		/// Risk weight of the loans incentive updated. \[collateral_type,
+		/// risk_weight\]
+		LoansRiskWeightUpdated(CurrencyId, Option<Rate>),
+		/// Minimum top-up amount of the reward currency updated, the
+		/// currency is not allowed to top up if none. \[reward_currency_type,
+		/// min_amount_per_period\]
+		TopUpCurrencyUpdated(CurrencyId, Option<Balance>),
+		/// Top-up cancelled and the remaining rewards refunded to the funder.
+		/// \[pool_id, top_up_id, refund_amount\]
+		TopUpCancelled(PoolId<T::RelaychainAccountId>, u32, Balance),
 	}
 
 	/// Mapping from pool to its fixed reward amount per period.
@@ -214,104 +584,290 @@ pub mod module {
 	pub type PayoutDeductionRates<T: Config> =
 		StorageMap<_, Twox64Concat, PoolId<T::RelaychainAccountId>, Rate, ValueQuery>;
 
+	/// Mapping from pool to its fixed reward amount per period of the extra
+	/// reward currency.
+	///
+	/// ExtraIncentiveRewardAmount: double_map PoolId, CurrencyId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn extra_incentive_reward_amount)]
+	pub type ExtraIncentiveRewardAmount<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		PoolId<T::RelaychainAccountId>,
+		Twox64Concat,
+		CurrencyId,
+		Balance,
+		ValueQuery,
+	>;
+
+	/// Mapping from pool to the rewards accounting of its extra reward
+	/// currency.
+	///
+	/// ExtraRewardPools: double_map PoolId, CurrencyId => ExtraRewardInfo
+	#[pallet::storage]
+	#[pallet::getter(fn extra_reward_pools)]
+	pub type ExtraRewardPools<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		PoolId<T::RelaychainAccountId>,
+		Twox64Concat,
+		CurrencyId,
+		ExtraRewardInfo,
+		ValueQuery,
+	>;
+
+	/// The withdrawn rewards of extra reward currencies of user in pool,
+	/// include the virtual rewards for shares added.
+	///
+	/// ExtraWithdrawnRewards: double_map PoolId, AccountId => BTreeMap<CurrencyId, Balance>
+	#[pallet::storage]
+	#[pallet::getter(fn extra_withdrawn_rewards)]
+	pub type ExtraWithdrawnRewards<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		PoolId<T::RelaychainAccountId>,
+		Twox64Concat,
+		T::AccountId,
+		BTreeMap<CurrencyId, Balance>,
+		ValueQuery,
+	>;
+
+	/// Mapping from pool to its rewards vesting params, rewards are paid out
+	/// immediately if it's none.
+	///
+	/// RewardsVesting: map PoolId => Option<RewardsVestingParams>
+	#[pallet::storage]
+	#[pallet::getter(fn rewards_vesting)]
+	pub type RewardsVesting<T: Config> =
+		StorageMap<_, Twox64Concat, PoolId<T::RelaychainAccountId>, RewardsVestingParams<T::BlockNumber>, OptionQuery>;
+
+	/// The escrowed rewards entries of user, ordered by the start.
+	///
+	/// EscrowedRewards: double_map AccountId, (PoolId, CurrencyId) =>
+	/// Vec<EscrowedReward>
+	#[pallet::storage]
+	#[pallet::getter(fn escrowed_rewards)]
+	pub type EscrowedRewards<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		(PoolId<T::RelaychainAccountId>, CurrencyId),
+		Vec<EscrowedReward<T::BlockNumber>>,
+		ValueQuery,
+	>;
+
+	/// Mapping from pool to its emission schedule of incentive rewards, which
+	/// overrides the fixed reward amount per period.
+	///
+	/// EmissionSchedules: map PoolId => Option<EmissionSchedule>
+	#[pallet::storage]
+	#[pallet::getter(fn emission_schedules)]
+	pub type EmissionSchedules<T: Config> =
+		StorageMap<_, Twox64Concat, PoolId<T::RelaychainAccountId>, EmissionSchedule<T::BlockNumber>, OptionQuery>;
+
+	/// Mapping from pool to its deposit limits, deposit is unlimited if none.
+	///
+	/// PoolDepositLimits: map PoolId => Option<DepositLimits>
+	#[pallet::storage]
+	#[pallet::getter(fn pool_deposit_limits)]
+	pub type PoolDepositLimits<T: Config> =
+		StorageMap<_, Twox64Concat, PoolId<T::RelaychainAccountId>, DepositLimits, OptionQuery>;
+
+	/// The accounts allowed to deposit to the pool which is whitelist only.
+	///
+	/// DepositWhitelist: double_map PoolId, AccountId => bool
+	#[pallet::storage]
+	#[pallet::getter(fn deposit_whitelist)]
+	pub type DepositWhitelist<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, PoolId<T::RelaychainAccountId>, Twox64Concat, T::AccountId, bool, ValueQuery>;
+
+	/// The share of user in the boosted pool before boost. It's the share of
+	/// orml rewards if none, as the share of the user is unboosted until it's
+	/// updated.
+	///
+	/// RawShares: double_map PoolId, AccountId => Option<Balance>
+	#[pallet::storage]
+	pub type RawShares<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		PoolId<T::RelaychainAccountId>,
+		Twox64Concat,
+		T::AccountId,
+		Balance,
+		OptionQuery,
+	>;
+
+	/// The total shares of the boosted pool before boost. It's seeded by the
+	/// total shares of orml rewards for the existing pools in the migration.
+	///
+	/// TotalRawShares: map PoolId => Balance
+	#[pallet::storage]
+	pub type TotalRawShares<T: Config> =
+		StorageMap<_, Twox64Concat, PoolId<T::RelaychainAccountId>, Balance, ValueQuery>;
+
+	/// The accounts exempted from the payout deduction of the pool.
+	///
+	/// DeductionExemptions: double_map PoolId, AccountId => bool
+	#[pallet::storage]
+	#[pallet::getter(fn deduction_exemptions)]
+	pub type DeductionExemptions<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, PoolId<T::RelaychainAccountId>, Twox64Concat, T::AccountId, bool, ValueQuery>;
+
+	/// Mapping from pool to its loyalty tiers of the payout deduction, sorted
+	/// by the minimum duration.
+	///
+	/// LoyaltyTiers: map PoolId => Vec<LoyaltyTier>
+	#[pallet::storage]
+	#[pallet::getter(fn loyalty_tiers)]
+	pub type LoyaltyTiers<T: Config> =
+		StorageMap<_, Twox64Concat, PoolId<T::RelaychainAccountId>, Vec<LoyaltyTier<T::BlockNumber>>, ValueQuery>;
+
+	/// The block number since which user stakes in the pool, it's reset when
+	/// the share decreases, and weighted by the amount when the share
+	/// increases.
+	///
+	/// StakeSince: double_map PoolId, AccountId => Option<BlockNumber>
+	#[pallet::storage]
+	#[pallet::getter(fn stake_since)]
+	pub type StakeSince<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		PoolId<T::RelaychainAccountId>,
+		Twox64Concat,
+		T::AccountId,
+		T::BlockNumber,
+		OptionQuery,
+	>;
+
+	/// The ongoing third party top-ups of the rewards of the pool.
+	///
+	/// RewardTopUps: double_map PoolId, TopUpId => Option<RewardTopUp>
+	#[pallet::storage]
+	#[pallet::getter(fn reward_top_ups)]
+	pub type RewardTopUps<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		PoolId<T::RelaychainAccountId>,
+		Twox64Concat,
+		u32,
+		RewardTopUp<T::AccountId>,
+		OptionQuery,
+	>;
+
+	/// The id of the next top-up.
+	///
+	/// NextTopUpId: u32
+	#[pallet::storage]
+	#[pallet::getter(fn next_top_up_id)]
+	pub type NextTopUpId<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// Mapping from reward currency allowed to top up to its minimum top-up
+	/// amount per period.
+	///
+	/// TopUpMinAmounts: map CurrencyId => Option<Balance>
+	#[pallet::storage]
+	#[pallet::getter(fn top_up_min_amounts)]
+	pub type TopUpMinAmounts<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Balance, OptionQuery>;
+
+	/// Mapping from collateral type to its risk weight to scale the
+	/// incentive reward amount of the loans incentive pool, unscaled if none.
+	/// The risk weight never exceeds 100%.
+	///
+	/// LoansRiskWeights: map CurrencyId => Option<Rate>
+	#[pallet::storage]
+	#[pallet::getter(fn loans_risk_weights)]
+	pub type LoansRiskWeights<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Rate, OptionQuery>;
+
+	/// Whether the accumulation of rewards is stopped by the emergency
+	/// shutdown.
+	///
+	/// IsAccumulationStopped: bool
+	#[pallet::storage]
+	#[pallet::getter(fn is_accumulation_stopped)]
+	pub type IsAccumulationStopped<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// StorageVersion: Releases
+	#[pallet::storage]
+	#[pallet::getter(fn storage_version)]
+	pub(crate) type StorageVersion<T: Config> = StorageValue<_, Releases, ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		pub incentive_reward_amounts: Vec<(PoolId<T::RelaychainAccountId>, Balance)>,
+		pub dex_saving_reward_rates: Vec<(PoolId<T::RelaychainAccountId>, Rate)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			GenesisConfig {
+				incentive_reward_amounts: vec![],
+				dex_saving_reward_rates: vec![],
+			}
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			self.incentive_reward_amounts.iter().for_each(|(pool_id, amount)| {
+				Pallet::<T>::do_set_incentive_reward_amount(pool_id, *amount)
+					.expect("the pool of incentive reward amount is invalid");
+			});
+			self.dex_saving_reward_rates.iter().for_each(|(pool_id, rate)| {
+				assert!(
+					matches!(pool_id, PoolId::DexSaving(currency_id) if currency_id.is_dex_share_currency_id()),
+					"the pool of dex saving reward rate is invalid"
+				);
+				DexSavingRewardRate::<T>::insert(pool_id, rate);
+			});
+			StorageVersion::<T>::put(Releases::V2_0_0);
+		}
+	}
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Self::storage_version() == Releases::V1_0_0 {
+				Self::migrate_to_v2()
+			} else {
+				0
+			}
+		}
+
 		fn on_initialize(now: T::BlockNumber) -> Weight {
 			// accumulate reward periodically
-			if !T::EmergencyShutdown::is_shutdown() && now % T::AccumulatePeriod::get() == Zero::zero() {
+			if !Self::is_accumulation_stopped() && now % T::AccumulatePeriod::get() == Zero::zero() {
 				let mut count: u32 = 0;
-				let native_currency_id = T::NativeCurrencyId::get();
-				let stable_currency_id = T::StableCurrencyId::get();
-
-				for (pool_id, pool_info) in orml_rewards::Pools::<T>::iter() {
-					if !pool_info.total_shares.is_zero() {
-						match pool_id {
-							PoolId::LoansIncentive(_) | PoolId::DexIncentive(_) | PoolId::HomaIncentive => {
-								count += 1;
-								let incentive_reward_amount = Self::incentive_reward_amount(pool_id.clone());
-
-								if !incentive_reward_amount.is_zero() {
-									let res = T::Currency::transfer(
-										native_currency_id,
-										&T::NativeRewardsSource::get(),
-										&T::RewardsVaultAccountId::get(),
-										incentive_reward_amount,
-									);
-									match res {
-										Ok(_) => {
-											<orml_rewards::Pallet<T>>::accumulate_reward(
-												&pool_id,
-												incentive_reward_amount,
-											);
-										}
-										Err(e) => {
-											log::warn!(
-												target: "incentives",
-												"transfer: failed to transfer {:?} {:?} from {:?} to {:?}: {:?}. \
-												This is unexpected but should be safe",
-												incentive_reward_amount, native_currency_id, T::NativeRewardsSource::get(), T::RewardsVaultAccountId::get(), e
-											);
-										}
-									}
-								}
-							}
-
-							PoolId::DexSaving(lp_currency_id) => {
-								count += 1;
-								let dex_saving_reward_rate = Self::dex_saving_reward_rate(pool_id.clone());
-
-								if !dex_saving_reward_rate.is_zero() {
-									if let Some((currency_id_a, currency_id_b)) =
-										lp_currency_id.split_dex_share_currency_id()
-									{
-										// accumulate saving reward only for liquidity pool of stable currency id
-										let dex_saving_reward_base = if currency_id_a == stable_currency_id {
-											T::DEX::get_liquidity_pool(stable_currency_id, currency_id_b).0
-										} else if currency_id_b == stable_currency_id {
-											T::DEX::get_liquidity_pool(stable_currency_id, currency_id_a).0
-										} else {
-											Zero::zero()
-										};
-										let dex_saving_reward_amount =
-											dex_saving_reward_rate.saturating_mul_int(dex_saving_reward_base);
-
-										// issue stable coin without backing.
-										if !dex_saving_reward_amount.is_zero() {
-											let res = T::CDPTreasury::issue_debit(
-												&T::RewardsVaultAccountId::get(),
-												dex_saving_reward_amount,
-												false,
-											);
-											match res {
-												Ok(_) => {
-													<orml_rewards::Pallet<T>>::accumulate_reward(
-														&pool_id,
-														dex_saving_reward_amount,
-													);
-												}
-												Err(e) => {
-													log::warn!(
-														target: "incentives",
-														"issue_debit: failed to issue {:?} unbacked stable to {:?}: {:?}. \
-														This is unexpected but should be safe",
-														dex_saving_reward_amount, T::RewardsVaultAccountId::get(), e
-													);
-												}
-											}
-										}
-									}
-								}
-							}
-
-							_ => {}
+				let mut pools: Vec<PoolId<T::RelaychainAccountId>> = Vec::new();
+				for (pool_id, pool_info) in module_rewards::Pools::<T>::iter() {
+					count += 1;
+					if !pool_info.total_shares.is_zero() && Self::is_accumulated_periodically(&pool_id) {
+						pools.push(pool_id);
+					}
+				}
+
+				let mut weight =
+					T::WeightInfo::on_initialize(0).saturating_add(T::DbWeight::get().reads(count as Weight));
+				if !pools.is_empty() {
+					match T::IdleScheduler::schedule(IncentivesTask::AccumulateRewards { now, pools }) {
+						Ok(schedule_weight) => weight = weight.saturating_add(schedule_weight),
+						Err(e) => {
+							log::warn!(
+								target: "incentives",
+								"schedule: failed to schedule the accumulation of rewards at {:?}: {:?}. \
+								This is unexpected but should be safe",
+								now, e
+							);
 						}
 					}
 				}
 
-				T::WeightInfo::on_initialize(count)
+				weight
 			} else {
 				0
 			}
@@ -344,14 +900,131 @@ pub mod module {
 			Ok(().into())
 		}
 
-		#[pallet::weight(<T as Config>::WeightInfo::claim_rewards())]
+		/// Claim the rewards of the caller in the pool, the working share of
+		/// the caller in the boosted pool is updated by the current voting
+		/// power after the claim.
+		#[pallet::weight(<T as Config>::WeightInfo::claim_rewards()
+			.saturating_add(<T as Config>::WeightInfo::update_boost()))]
 		#[transactional]
 		pub fn claim_rewards(
 			origin: OriginFor<T>,
 			pool_id: PoolId<T::RelaychainAccountId>,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
-			<orml_rewards::Pallet<T>>::claim_rewards(&who, &pool_id);
+			<Self as IncentivesRewards<_, _, _, _>>::claim_rewards(&who, &pool_id);
+			Ok(().into())
+		}
+
+		/// Claim the rewards of the caller in the given pools, at most
+		/// `MaxClaimAllPools` pools. The pools without pending rewards are
+		/// skipped.
+		#[pallet::weight(<T as Config>::WeightInfo::claim_all_rewards(pool_ids.len() as u32)
+			.saturating_add(<T as Config>::WeightInfo::update_boost().saturating_mul(pool_ids.len() as Weight)))]
+		#[transactional]
+		pub fn claim_all_rewards(
+			origin: OriginFor<T>,
+			pool_ids: Vec<PoolId<T::RelaychainAccountId>>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				pool_ids.len() as u32 <= T::MaxClaimAllPools::get(),
+				Error::<T>::TooManyPools
+			);
+
+			let mut count: u32 = 0;
+			for pool_id in pool_ids {
+				if !Self::has_pending_rewards(&pool_id, &who) {
+					continue;
+				}
+
+				<Self as IncentivesRewards<_, _, _, _>>::claim_rewards(&who, &pool_id);
+				count += 1;
+			}
+			Ok(Some(
+				<T as Config>::WeightInfo::claim_all_rewards(count)
+					.saturating_add(<T as Config>::WeightInfo::update_boost().saturating_mul(count as Weight)),
+			)
+			.into())
+		}
+
+		/// Claim the rewards of the dex pool and add them as liquidity of
+		/// the pool, the increased dex share is deposited to incentives. The
+		/// extra reward currencies are claimed but not compounded.
+		///
+		/// - `min_target_amount`: the minimum amount received from each swap
+		///   of the rewards.
+		/// - `min_share_increment`: the minimum dex share added.
+		#[pallet::weight(<T as Config>::WeightInfo::compound_rewards())]
+		#[transactional]
+		pub fn compound_rewards(
+			origin: OriginFor<T>,
+			pool_id: PoolId<T::RelaychainAccountId>,
+			#[pallet::compact] min_target_amount: Balance,
+			#[pallet::compact] min_share_increment: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::do_compound_rewards(&who, &pool_id, min_target_amount, min_share_increment)?;
+			Ok(().into())
+		}
+
+		/// Fund the rewards of the pool by `amount_per_period` for `periods`
+		/// periods, the full amount is escrowed up front.
+		#[pallet::weight(<T as Config>::WeightInfo::top_up_rewards())]
+		#[transactional]
+		pub fn top_up_rewards(
+			origin: OriginFor<T>,
+			pool_id: PoolId<T::RelaychainAccountId>,
+			currency_id: CurrencyId,
+			#[pallet::compact] amount_per_period: Balance,
+			periods: u32,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				matches!(
+					pool_id,
+					PoolId::LoansIncentive(_) | PoolId::DexIncentive(_) | PoolId::HomaIncentive
+				) && module_rewards::Pools::<T>::contains_key(&pool_id),
+				Error::<T>::InvalidPoolId
+			);
+			ensure!(
+				!amount_per_period.is_zero() && !periods.is_zero(),
+				Error::<T>::InvalidTopUp
+			);
+			let min_amount = Self::top_up_min_amounts(currency_id).ok_or(Error::<T>::TopUpCurrencyNotAllowed)?;
+			ensure!(amount_per_period >= min_amount, Error::<T>::BelowMinTopUpAmount);
+			ensure!(
+				(RewardTopUps::<T>::iter_prefix(&pool_id).count() as u32) < T::MaxTopUps::get(),
+				Error::<T>::TooManyTopUps
+			);
+
+			let total_amount = amount_per_period
+				.checked_mul(periods.into())
+				.ok_or(ArithmeticError::Overflow)?;
+			T::Currency::transfer(currency_id, &who, &T::RewardsVaultAccountId::get(), total_amount)?;
+
+			let top_up_id = NextTopUpId::<T>::try_mutate(|id| -> Result<u32, DispatchError> {
+				let current_id = *id;
+				*id = id.checked_add(1).ok_or(ArithmeticError::Overflow)?;
+				Ok(current_id)
+			})?;
+			RewardTopUps::<T>::insert(
+				&pool_id,
+				top_up_id,
+				RewardTopUp {
+					funder: who.clone(),
+					currency_id,
+					amount_per_period,
+					remaining_periods: periods,
+				},
+			);
+			Self::deposit_event(Event::RewardsToppedUp(
+				who,
+				pool_id,
+				top_up_id,
+				currency_id,
+				amount_per_period,
+				periods,
+			));
 			Ok(().into())
 		}
 
@@ -362,18 +1035,40 @@ pub mod module {
 			updates: Vec<(PoolId<T::RelaychainAccountId>, Balance)>,
 		) -> DispatchResultWithPostInfo {
 			T::UpdateOrigin::ensure_origin(origin)?;
+			let total_reward_amount = Self::total_incentive_reward_amount();
 			for (pool_id, amount) in updates {
+				Self::do_set_incentive_reward_amount(&pool_id, amount)?;
+			}
+			Self::ensure_within_budget(total_reward_amount)?;
+			Ok(().into())
+		}
+
+		#[pallet::weight(<T as Config>::WeightInfo::update_extra_incentive_rewards(updates.len() as u32))]
+		#[transactional]
+		pub fn update_extra_incentive_rewards(
+			origin: OriginFor<T>,
+			updates: Vec<(PoolId<T::RelaychainAccountId>, CurrencyId, Balance)>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			for (pool_id, currency_id, amount) in updates {
 				match pool_id {
-					PoolId::DexIncentive(currency_id) => {
-						ensure!(currency_id.is_dex_share_currency_id(), Error::<T>::InvalidCurrencyId);
+					PoolId::DexIncentive(lp_currency_id) => {
+						ensure!(lp_currency_id.is_dex_share_currency_id(), Error::<T>::InvalidCurrencyId);
 					}
 					PoolId::LoansIncentive(_) | PoolId::HomaIncentive => {}
 					_ => {
 						return Err(Error::<T>::InvalidPoolId.into());
 					}
 				}
-				IncentiveRewardAmount::<T>::insert(&pool_id, amount);
-				Self::deposit_event(Event::IncentiveRewardAmountUpdated(pool_id, amount));
+				// the reward of native currency is set by `update_incentive_rewards`
+				ensure!(currency_id != T::NativeCurrencyId::get(), Error::<T>::InvalidCurrencyId);
+
+				if amount.is_zero() {
+					ExtraIncentiveRewardAmount::<T>::remove(&pool_id, currency_id);
+				} else {
+					ExtraIncentiveRewardAmount::<T>::insert(&pool_id, currency_id, amount);
+				}
+				Self::deposit_event(Event::ExtraIncentiveRewardAmountUpdated(pool_id, currency_id, amount));
 			}
 			Ok(().into())
 		}
@@ -421,52 +1116,1189 @@ pub mod module {
 			Ok(().into())
 		}
 
-		#[pallet::weight(<T as Config>::WeightInfo::add_allowance())]
+		#[pallet::weight(<T as Config>::WeightInfo::update_rewards_vesting(updates.len() as u32))]
 		#[transactional]
-		pub fn add_allowance(
+		pub fn update_rewards_vesting(
 			origin: OriginFor<T>,
-			pool_id: PoolId<T::RelaychainAccountId>,
-			amount: Balance,
+			updates: Vec<(
+				PoolId<T::RelaychainAccountId>,
+				Option<RewardsVestingParams<T::BlockNumber>>,
+			)>,
 		) -> DispatchResultWithPostInfo {
-			let who = ensure_signed(origin)?;
+			T::UpdateOrigin::ensure_origin(origin)?;
+			for (pool_id, params) in updates {
+				match params {
+					Some(params) => {
+						ensure!(!params.period.is_zero(), Error::<T>::InvalidPeriod);
+						ensure!(params.early_exit_penalty_rate <= Rate::one(), Error::<T>::InvalidRate);
+						RewardsVesting::<T>::insert(&pool_id, params);
+					}
+					None => {
+						RewardsVesting::<T>::remove(&pool_id);
+					}
+				}
+				Self::deposit_event(Event::RewardsVestingUpdated(pool_id, params));
+			}
+			Ok(().into())
+		}
+
+		/// Update the emission schedules of incentive rewards, the schedule
+		/// overrides the fixed reward amount per period of the pool.
+		#[pallet::weight(<T as Config>::WeightInfo::update_emission_schedules(updates.len() as u32))]
+		#[transactional]
+		pub fn update_emission_schedules(
+			origin: OriginFor<T>,
+			updates: Vec<(PoolId<T::RelaychainAccountId>, Option<EmissionSchedule<T::BlockNumber>>)>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let total_reward_amount = Self::total_incentive_reward_amount();
+			for (pool_id, schedule) in updates {
+				match pool_id {
+					PoolId::DexIncentive(currency_id) => {
+						ensure!(currency_id.is_dex_share_currency_id(), Error::<T>::InvalidCurrencyId);
+					}
+					PoolId::LoansIncentive(_) | PoolId::HomaIncentive => {}
+					_ => {
+						return Err(Error::<T>::InvalidPoolId.into());
+					}
+				}
+				match schedule {
+					Some(schedule) => {
+						ensure!(schedule.is_valid(), Error::<T>::InvalidSchedule);
+						EmissionSchedules::<T>::insert(&pool_id, schedule);
+					}
+					None => {
+						EmissionSchedules::<T>::remove(&pool_id);
+					}
+				}
+				Self::deposit_event(Event::EmissionScheduleUpdated(pool_id, schedule));
+			}
+			Self::ensure_within_budget(total_reward_amount)?;
+			Ok(().into())
+		}
 
+		/// Set the deposit limits of the dex incentive pool, remove the limits
+		/// if none.
+		#[pallet::weight(<T as Config>::WeightInfo::set_deposit_limits())]
+		#[transactional]
+		pub fn set_deposit_limits(
+			origin: OriginFor<T>,
+			pool_id: PoolId<T::RelaychainAccountId>,
+			limits: Option<DepositLimits>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
 			match pool_id {
-				PoolId::HomaValidatorAllowance(_) => {
-					T::Currency::transfer(
-						T::LiquidCurrencyId::get(),
-						&who,
-						&T::RewardsVaultAccountId::get(),
-						amount,
-					)?;
-					<orml_rewards::Pallet<T>>::accumulate_reward(&pool_id, amount);
+				PoolId::DexIncentive(currency_id) => {
+					ensure!(currency_id.is_dex_share_currency_id(), Error::<T>::InvalidCurrencyId);
 				}
 				_ => {
 					return Err(Error::<T>::InvalidPoolId.into());
 				}
 			}
+			match limits {
+				Some(limits) => PoolDepositLimits::<T>::insert(&pool_id, limits),
+				None => PoolDepositLimits::<T>::remove(&pool_id),
+			}
+			Self::deposit_event(Event::DepositLimitsUpdated(pool_id, limits));
+			Ok(().into())
+		}
 
+		/// Add accounts to or remove accounts from the deposit whitelist of
+		/// the pool.
+		#[pallet::weight(<T as Config>::WeightInfo::update_deposit_whitelist(updates.len() as u32))]
+		#[transactional]
+		pub fn update_deposit_whitelist(
+			origin: OriginFor<T>,
+			pool_id: PoolId<T::RelaychainAccountId>,
+			updates: Vec<(T::AccountId, bool)>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			for (who, is_whitelisted) in updates {
+				if is_whitelisted {
+					DepositWhitelist::<T>::insert(&pool_id, &who, true);
+				} else {
+					DepositWhitelist::<T>::remove(&pool_id, &who);
+				}
+				Self::deposit_event(Event::DepositWhitelistUpdated(pool_id.clone(), who, is_whitelisted));
+			}
 			Ok(().into())
 		}
-	}
-}
 
-impl<T: Config> Pallet<T> {
+		/// Exempt accounts from or subject accounts to the payout deduction
+		/// of the pool.
+		#[pallet::weight(<T as Config>::WeightInfo::update_deduction_exemptions(updates.len() as u32))]
+		#[transactional]
+		pub fn update_deduction_exemptions(
+			origin: OriginFor<T>,
+			pool_id: PoolId<T::RelaychainAccountId>,
+			updates: Vec<(T::AccountId, bool)>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			for (who, is_exempted) in updates {
+				if is_exempted {
+					DeductionExemptions::<T>::insert(&pool_id, &who, true);
+				} else {
+					DeductionExemptions::<T>::remove(&pool_id, &who);
+				}
+				Self::deposit_event(Event::DeductionExemptionUpdated(pool_id.clone(), who, is_exempted));
+			}
+			Ok(().into())
+		}
+
+		/// Update the loyalty tiers of the payout deduction of pools. The
+		/// tiers must be sorted by the minimum duration, with decreasing
+		/// deduction rates. Empty tiers to remove.
+		#[pallet::weight(<T as Config>::WeightInfo::update_loyalty_tiers(updates.len() as u32))]
+		#[transactional]
+		pub fn update_loyalty_tiers(
+			origin: OriginFor<T>,
+			updates: Vec<(PoolId<T::RelaychainAccountId>, Vec<LoyaltyTier<T::BlockNumber>>)>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			for (pool_id, tiers) in updates {
+				ensure!(
+					tiers.len() as u32 <= T::MaxLoyaltyTiers::get(),
+					Error::<T>::InvalidLoyaltyTiers
+				);
+				ensure!(
+					tiers.iter().all(|tier| tier.deduction_rate <= Rate::one()),
+					Error::<T>::InvalidRate
+				);
+				ensure!(
+					tiers.windows(2).all(|pair| pair[0].min_duration < pair[1].min_duration
+						&& pair[0].deduction_rate >= pair[1].deduction_rate),
+					Error::<T>::InvalidLoyaltyTiers
+				);
+
+				if tiers.is_empty() {
+					LoyaltyTiers::<T>::remove(&pool_id);
+				} else {
+					LoyaltyTiers::<T>::insert(&pool_id, &tiers);
+				}
+				Self::deposit_event(Event::LoyaltyTiersUpdated(pool_id, tiers));
+			}
+			Ok(().into())
+		}
+
+		/// Update the risk weights of collateral types to scale the incentive
+		/// reward amounts of the loans incentive pools, remove the risk weight
+		/// if none. The risk weight must not exceed 100%, and at most
+		/// `MaxLoansRiskWeights` collateral types can have the risk weight.
+		#[pallet::weight(<T as Config>::WeightInfo::update_loans_risk_weights(updates.len() as u32))]
+		#[transactional]
+		pub fn update_loans_risk_weights(
+			origin: OriginFor<T>,
+			updates: Vec<(CurrencyId, Option<Rate>)>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let total_reward_amount = Self::total_incentive_reward_amount();
+			for (currency_id, risk_weight) in updates {
+				match risk_weight {
+					Some(risk_weight) => {
+						ensure!(risk_weight <= Rate::one(), Error::<T>::InvalidRate);
+						LoansRiskWeights::<T>::insert(currency_id, risk_weight);
+					}
+					None => LoansRiskWeights::<T>::remove(currency_id),
+				}
+				Self::deposit_event(Event::LoansRiskWeightUpdated(currency_id, risk_weight));
+			}
+			ensure!(
+				LoansRiskWeights::<T>::iter().count() as u32 <= T::MaxLoansRiskWeights::get(),
+				Error::<T>::TooManyRiskWeights
+			);
+			Self::ensure_within_budget(total_reward_amount)?;
+			Ok(().into())
+		}
+
+		/// Claim the vested escrowed rewards, or all the escrowed rewards if
+		/// `early_exit`, the penalty of the pool for the unvested rewards
+		/// will be forfeited back to the pool.
+		#[pallet::weight(<T as Config>::WeightInfo::claim_escrowed_rewards())]
+		#[transactional]
+		pub fn claim_escrowed_rewards(
+			origin: OriginFor<T>,
+			pool_id: PoolId<T::RelaychainAccountId>,
+			currency_id: CurrencyId,
+			early_exit: bool,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let key = (pool_id.clone(), currency_id);
+			let mut escrows = Self::escrowed_rewards(&who, &key);
+			ensure!(!escrows.is_empty(), Error::<T>::NoEscrowedRewards);
+			let now = <frame_system::Pallet<T>>::block_number();
+
+			let (claim_amount, forfeited_amount) = if early_exit {
+				let (remaining, unvested) =
+					escrows
+						.iter()
+						.fold((Balance::zero(), Balance::zero()), |(remaining, unvested), escrow| {
+							(
+								remaining.saturating_add(escrow.total.saturating_sub(escrow.claimed)),
+								unvested.saturating_add(escrow.total.saturating_sub(escrow.vested(now))),
+							)
+						});
+				let penalty_rate = Self::rewards_vesting(&pool_id)
+					.map(|params| params.early_exit_penalty_rate)
+					.unwrap_or_default();
+				let forfeited_amount = penalty_rate.saturating_mul_int(unvested).min(unvested);
+				EscrowedRewards::<T>::remove(&who, &key);
+				(remaining.saturating_sub(forfeited_amount), forfeited_amount)
+			} else {
+				let claim_amount = Self::claim_vested(&mut escrows, now);
+				if escrows.is_empty() {
+					EscrowedRewards::<T>::remove(&who, &key);
+				} else {
+					EscrowedRewards::<T>::insert(&who, &key, escrows);
+				}
+				(claim_amount, Zero::zero())
+			};
+
+			T::Currency::transfer(currency_id, &T::RewardsVaultAccountId::get(), &who, claim_amount)?;
+			if !forfeited_amount.is_zero() {
+				Self::reaccumulate_reward(&pool_id, currency_id, forfeited_amount);
+			}
+
+			Self::deposit_event(Event::EscrowedRewardsClaimed(
+				who,
+				pool_id,
+				currency_id,
+				claim_amount,
+				forfeited_amount,
+			));
+			Ok(().into())
+		}
+
+		/// Update the working share of `who` in the boosted pool by the
+		/// current voting power.
+		#[pallet::weight(<T as Config>::WeightInfo::update_boost())]
+		#[transactional]
+		pub fn update_boost(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			pool_id: PoolId<T::RelaychainAccountId>,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			ensure!(Self::is_boosted_pool(&pool_id), Error::<T>::InvalidPoolId);
+			Self::set_raw_share(&who, &pool_id, Self::raw_share(&pool_id, &who));
+			Ok(().into())
+		}
+
+		#[pallet::weight(<T as Config>::WeightInfo::add_allowance())]
+		#[transactional]
+		pub fn add_allowance(
+			origin: OriginFor<T>,
+			pool_id: PoolId<T::RelaychainAccountId>,
+			amount: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			match pool_id {
+				PoolId::HomaValidatorAllowance(_) => {
+					T::Currency::transfer(
+						T::LiquidCurrencyId::get(),
+						&who,
+						&T::RewardsVaultAccountId::get(),
+						amount,
+					)?;
+					<module_rewards::Pallet<T>>::accumulate_reward(&pool_id, amount);
+				}
+				_ => {
+					return Err(Error::<T>::InvalidPoolId.into());
+				}
+			}
+
+			Ok(().into())
+		}
+
+		/// Allow the reward currencies to top up with the minimum amount per
+		/// period, disallow if none. The ongoing top-ups are not affected.
+		#[pallet::weight(<T as Config>::WeightInfo::update_top_up_currencies(updates.len() as u32))]
+		#[transactional]
+		pub fn update_top_up_currencies(
+			origin: OriginFor<T>,
+			updates: Vec<(CurrencyId, Option<Balance>)>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			for (currency_id, min_amount) in updates {
+				match min_amount {
+					Some(min_amount) => TopUpMinAmounts::<T>::insert(currency_id, min_amount),
+					None => TopUpMinAmounts::<T>::remove(currency_id),
+				}
+				Self::deposit_event(Event::TopUpCurrencyUpdated(currency_id, min_amount));
+			}
+			Ok(().into())
+		}
+
+		/// Cancel the top-up, the rewards of the remaining periods are
+		/// refunded to the funder.
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_top_up())]
+		#[transactional]
+		pub fn cancel_top_up(
+			origin: OriginFor<T>,
+			pool_id: PoolId<T::RelaychainAccountId>,
+			top_up_id: u32,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let top_up = RewardTopUps::<T>::take(&pool_id, top_up_id).ok_or(Error::<T>::TopUpNotFound)?;
+
+			let refund_amount = top_up.amount_per_period.saturating_mul(top_up.remaining_periods.into());
+			T::Currency::transfer(
+				top_up.currency_id,
+				&T::RewardsVaultAccountId::get(),
+				&top_up.funder,
+				refund_amount,
+			)?;
+			Self::deposit_event(Event::TopUpCancelled(pool_id, top_up_id, refund_amount));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
 	pub fn account_id() -> T::AccountId {
 		T::PalletId::get().into_account()
 	}
+
+	/// The reward currency type of the pool, exclude the extra reward
+	/// currencies.
+	fn reward_currency_id(pool_id: &PoolId<T::RelaychainAccountId>) -> CurrencyId {
+		match pool_id {
+			PoolId::LoansIncentive(_) | PoolId::DexIncentive(_) | PoolId::HomaIncentive => T::NativeCurrencyId::get(),
+			PoolId::DexSaving(_) => T::StableCurrencyId::get(),
+			PoolId::HomaValidatorAllowance(_) => T::LiquidCurrencyId::get(),
+		}
+	}
+
+	/// Whether `who` has rewards to claim in the pool, the extra reward
+	/// currencies are always considered pending.
+	fn has_pending_rewards(pool_id: &PoolId<T::RelaychainAccountId>, who: &T::AccountId) -> bool {
+		let share = <module_rewards::Pallet<T>>::share_and_withdrawn_reward(pool_id, who).0;
+		if share.is_zero() {
+			return false;
+		}
+
+		!<module_rewards::Pallet<T>>::pending_reward(pool_id, who).is_zero()
+			|| ExtraRewardPools::<T>::iter_prefix(pool_id).next().is_some()
+	}
+
+	fn do_compound_rewards(
+		who: &T::AccountId,
+		pool_id: &PoolId<T::RelaychainAccountId>,
+		min_target_amount: Balance,
+		min_share_increment: Balance,
+	) -> DispatchResult {
+		let lp_currency_id = match pool_id {
+			PoolId::DexIncentive(lp_currency_id) | PoolId::DexSaving(lp_currency_id) => *lp_currency_id,
+			_ => return Err(Error::<T>::InvalidPoolId.into()),
+		};
+		let (currency_id_a, currency_id_b) = lp_currency_id
+			.split_dex_share_currency_id()
+			.ok_or(Error::<T>::InvalidCurrencyId)?;
+		let reward_currency_id = Self::reward_currency_id(pool_id);
+
+		let balance_before = T::Currency::free_balance(reward_currency_id, who);
+		<module_rewards::Pallet<T>>::claim_rewards(who, pool_id);
+		Self::claim_extra_rewards(who, pool_id);
+		let claimed_amount = T::Currency::free_balance(reward_currency_id, who).saturating_sub(balance_before);
+		ensure!(!claimed_amount.is_zero(), Error::<T>::NoRewardsToCompound);
+
+		// swap the rewards to both sides of the liquidity pool
+		let half_amount = claimed_amount / 2;
+		let remain_amount = claimed_amount.saturating_sub(half_amount);
+		let (amount_a, amount_b) = if reward_currency_id == currency_id_a {
+			let amount_b = T::DEX::swap_with_exact_supply(
+				who,
+				&[reward_currency_id, currency_id_b],
+				half_amount,
+				min_target_amount,
+				None,
+			)?;
+			(remain_amount, amount_b)
+		} else if reward_currency_id == currency_id_b {
+			let amount_a = T::DEX::swap_with_exact_supply(
+				who,
+				&[reward_currency_id, currency_id_a],
+				half_amount,
+				min_target_amount,
+				None,
+			)?;
+			(amount_a, remain_amount)
+		} else {
+			let amount_a = T::DEX::swap_with_exact_supply(
+				who,
+				&[reward_currency_id, currency_id_a],
+				half_amount,
+				min_target_amount,
+				None,
+			)?;
+			let amount_b = T::DEX::swap_with_exact_supply(
+				who,
+				&[reward_currency_id, currency_id_b],
+				remain_amount,
+				min_target_amount,
+				None,
+			)?;
+			(amount_a, amount_b)
+		};
+
+		T::DEX::add_liquidity(
+			who,
+			currency_id_a,
+			currency_id_b,
+			amount_a,
+			amount_b,
+			min_share_increment,
+			true,
+		)?;
+
+		Self::deposit_event(Event::RewardsCompounded(
+			who.clone(),
+			pool_id.clone(),
+			reward_currency_id,
+			claimed_amount,
+		));
+		Ok(())
+	}
+
+	/// Re-accumulate the rewards which are already in the vault to the pool.
+	fn reaccumulate_reward(pool_id: &PoolId<T::RelaychainAccountId>, currency_id: CurrencyId, amount: Balance) {
+		if currency_id == Self::reward_currency_id(pool_id) {
+			<module_rewards::Pallet<T>>::accumulate_reward(pool_id, amount);
+		} else {
+			ExtraRewardPools::<T>::mutate(pool_id, currency_id, |info| {
+				info.total_rewards = info.total_rewards.saturating_add(amount);
+			});
+		}
+	}
+
+	/// Mark the vested rewards of the escrowed rewards entries as claimed and
+	/// remove the fully claimed entries, return the claimed amount.
+	fn claim_vested(escrows: &mut Vec<EscrowedReward<T::BlockNumber>>, now: T::BlockNumber) -> Balance {
+		let mut claim_amount: Balance = Zero::zero();
+		for escrow in escrows.iter_mut() {
+			let vested = escrow.vested(now);
+			claim_amount = claim_amount.saturating_add(vested.saturating_sub(escrow.claimed));
+			escrow.claimed = vested;
+		}
+		escrows.retain(|escrow| escrow.claimed < escrow.total);
+		claim_amount
+	}
+
+	/// Escrow the rewards to vest over the vesting period of the pool. The
+	/// vested part of the previous escrowed rewards will be paid out, and the
+	/// new rewards vest in a new entry from now, the previous entries keep
+	/// vesting as they are. Once the entries are full, the new rewards vest
+	/// with the unvested part of the latest entry from now.
+	fn escrow_reward(
+		who: &T::AccountId,
+		pool_id: &PoolId<T::RelaychainAccountId>,
+		currency_id: CurrencyId,
+		amount: Balance,
+		period: T::BlockNumber,
+	) {
+		let now = <frame_system::Pallet<T>>::block_number();
+		let key = (pool_id.clone(), currency_id);
+		let mut escrows = Self::escrowed_rewards(who, &key);
+		let previous_escrows = escrows.clone();
+
+		let vested_amount = Self::claim_vested(&mut escrows, now);
+		if !vested_amount.is_zero() {
+			let res = T::Currency::transfer(currency_id, &T::RewardsVaultAccountId::get(), &who, vested_amount);
+			match res {
+				Ok(_) => {
+					Self::deposit_event(Event::EscrowedRewardsClaimed(
+						who.clone(),
+						pool_id.clone(),
+						currency_id,
+						vested_amount,
+						Zero::zero(),
+					));
+				}
+				Err(e) => {
+					log::warn!(
+						target: "incentives",
+						"transfer: failed to transfer {:?} {:?} from {:?} to {:?}: {:?}. \
+						This is unexpected but should be safe",
+						vested_amount, currency_id, T::RewardsVaultAccountId::get(), who, e
+					);
+					// keep the vested amount escrowed
+					escrows = previous_escrows;
+				}
+			}
+		}
+
+		if escrows.is_empty() || (escrows.len() as u32) < T::MaxEscrowedRewards::get() {
+			escrows.push(EscrowedReward {
+				start: now,
+				period,
+				total: amount,
+				claimed: Zero::zero(),
+			});
+		} else if let Some(latest) = escrows.last_mut() {
+			*latest = EscrowedReward {
+				start: now,
+				period,
+				total: latest.total.saturating_sub(latest.claimed).saturating_add(amount),
+				claimed: Zero::zero(),
+			};
+		}
+		EscrowedRewards::<T>::insert(who, &key, escrows);
+		Self::deposit_event(Event::RewardsEscrowed(
+			who.clone(),
+			pool_id.clone(),
+			currency_id,
+			amount,
+		));
+	}
+
+	/// Accumulate the fixed reward amounts per period of the extra reward
+	/// currencies to the pool.
+	/// Whether the rewards of the pool are accumulated every `AccumulatePeriod`.
+	fn is_accumulated_periodically(pool_id: &PoolId<T::RelaychainAccountId>) -> bool {
+		matches!(
+			pool_id,
+			PoolId::LoansIncentive(_) | PoolId::DexIncentive(_) | PoolId::HomaIncentive | PoolId::DexSaving(_)
+		)
+	}
+
+	/// Accumulate the rewards of the period at `now` to the pool, returns the number of the
+	/// accumulated rewards and top-ups.
+	fn accumulate_pool_rewards(pool_id: &PoolId<T::RelaychainAccountId>, now: T::BlockNumber) -> u32 {
+		let mut count: u32 = 0;
+		match pool_id {
+			PoolId::LoansIncentive(_) | PoolId::DexIncentive(_) | PoolId::HomaIncentive => {
+				count += 1;
+				let native_currency_id = T::NativeCurrencyId::get();
+				let incentive_reward_amount = Self::period_incentive_reward_amount(pool_id, now);
+
+				if !incentive_reward_amount.is_zero() {
+					let res = T::Currency::transfer(
+						native_currency_id,
+						&T::NativeRewardsSource::get(),
+						&T::RewardsVaultAccountId::get(),
+						incentive_reward_amount,
+					);
+					match res {
+						Ok(_) => {
+							<module_rewards::Pallet<T>>::accumulate_reward(pool_id, incentive_reward_amount);
+						}
+						Err(e) => {
+							log::warn!(
+								target: "incentives",
+								"transfer: failed to transfer {:?} {:?} from {:?} to {:?}: {:?}. \
+								This is unexpected but should be safe",
+								incentive_reward_amount, native_currency_id, T::NativeRewardsSource::get(), T::RewardsVaultAccountId::get(), e
+							);
+						}
+					}
+				}
+
+				Self::accumulate_extra_rewards(pool_id);
+				count += Self::accumulate_top_ups(pool_id);
+			}
+
+			PoolId::DexSaving(lp_currency_id) => {
+				count += 1;
+				let dex_saving_reward_amount = Self::dex_saving_reward_amount(pool_id, *lp_currency_id);
+
+				// issue stable coin without backing.
+				if !dex_saving_reward_amount.is_zero() {
+					let res =
+						T::CDPTreasury::issue_debit(&T::RewardsVaultAccountId::get(), dex_saving_reward_amount, false);
+					match res {
+						Ok(_) => {
+							<module_rewards::Pallet<T>>::accumulate_reward(pool_id, dex_saving_reward_amount);
+						}
+						Err(e) => {
+							log::warn!(
+								target: "incentives",
+								"issue_debit: failed to issue {:?} unbacked stable to {:?}: {:?}. \
+								This is unexpected but should be safe",
+								dex_saving_reward_amount, T::RewardsVaultAccountId::get(), e
+							);
+						}
+					}
+				}
+			}
+
+			_ => {}
+		}
+		count
+	}
+
+	fn accumulate_extra_rewards(pool_id: &PoolId<T::RelaychainAccountId>) {
+		for (currency_id, amount) in ExtraIncentiveRewardAmount::<T>::iter_prefix(pool_id) {
+			let res = T::Currency::transfer(
+				currency_id,
+				&T::ExtraRewardsSource::get(),
+				&T::RewardsVaultAccountId::get(),
+				amount,
+			);
+			match res {
+				Ok(_) => {
+					ExtraRewardPools::<T>::mutate(pool_id, currency_id, |info| {
+						info.total_rewards = info.total_rewards.saturating_add(amount);
+					});
+				}
+				Err(e) => {
+					log::warn!(
+						target: "incentives",
+						"transfer: failed to transfer {:?} {:?} from {:?} to {:?}: {:?}. \
+						This is unexpected but should be safe",
+						amount, currency_id, T::ExtraRewardsSource::get(), T::RewardsVaultAccountId::get(), e
+					);
+				}
+			}
+		}
+	}
+
+	/// Accumulate the reward amounts per period of the ongoing top-ups to the
+	/// pool, return the number of top-ups. The escrowed rewards are in the
+	/// vault already.
+	fn accumulate_top_ups(pool_id: &PoolId<T::RelaychainAccountId>) -> u32 {
+		let top_ups = RewardTopUps::<T>::iter_prefix(pool_id).collect::<Vec<_>>();
+		for (top_up_id, mut top_up) in top_ups.iter().cloned() {
+			if top_up.currency_id == Self::reward_currency_id(pool_id) {
+				<module_rewards::Pallet<T>>::accumulate_reward(pool_id, top_up.amount_per_period);
+			} else {
+				ExtraRewardPools::<T>::mutate(pool_id, top_up.currency_id, |info| {
+					info.total_rewards = info.total_rewards.saturating_add(top_up.amount_per_period);
+				});
+			}
+
+			top_up.remaining_periods = top_up.remaining_periods.saturating_sub(1);
+			if top_up.remaining_periods.is_zero() {
+				RewardTopUps::<T>::remove(pool_id, top_up_id);
+			} else {
+				RewardTopUps::<T>::insert(pool_id, top_up_id, top_up);
+			}
+		}
+		top_ups.len() as u32
+	}
+
+	/// Payout all the pending rewards of extra reward currencies of the user
+	/// in the pool.
+	fn claim_extra_rewards(who: &T::AccountId, pool_id: &PoolId<T::RelaychainAccountId>) {
+		let share = <module_rewards::Pallet<T>>::share_and_withdrawn_reward(pool_id, who).0;
+		if share.is_zero() {
+			return;
+		}
+		let total_shares = module_rewards::Pools::<T>::get(pool_id).total_shares;
+
+		let mut withdrawn_rewards = Self::extra_withdrawn_rewards(pool_id, who);
+		let mut claimed = false;
+		for (currency_id, mut info) in ExtraRewardPools::<T>::iter_prefix(pool_id) {
+			let withdrawn_reward = withdrawn_rewards.get(&currency_id).copied().unwrap_or_default();
+			let reward = multiply_by_rational(share, info.total_rewards, total_shares)
+				.unwrap_or_default()
+				.saturating_sub(withdrawn_reward);
+			if reward.is_zero() {
+				continue;
+			}
+
+			withdrawn_rewards.insert(currency_id, withdrawn_reward.saturating_add(reward));
+			info.total_withdrawn_rewards = info.total_withdrawn_rewards.saturating_add(reward);
+			// re-accumulate deduction to rewards pool
+			let deduction_amount = Self::transfer_reward(who, pool_id, currency_id, reward);
+			info.total_rewards = info.total_rewards.saturating_add(deduction_amount);
+			ExtraRewardPools::<T>::insert(pool_id, currency_id, info);
+			claimed = true;
+		}
+
+		if claimed {
+			ExtraWithdrawnRewards::<T>::insert(pool_id, who, withdrawn_rewards);
+		}
+	}
+
+	/// Add share to the pool, the virtual rewards of extra reward currencies
+	/// for the added share are recorded as withdrawn to keep the rewards of
+	/// other users.
+	fn add_share(who: &T::AccountId, pool_id: &PoolId<T::RelaychainAccountId>, add_amount: Balance) {
+		if add_amount.is_zero() {
+			return;
+		}
+
+		let share = <module_rewards::Pallet<T>>::share_and_withdrawn_reward(pool_id, who).0;
+		let total_shares = module_rewards::Pools::<T>::get(pool_id).total_shares;
+		if !total_shares.is_zero() {
+			let mut withdrawn_rewards = Self::extra_withdrawn_rewards(pool_id, who);
+			let mut inflated = false;
+			for (currency_id, mut info) in ExtraRewardPools::<T>::iter_prefix(pool_id) {
+				let reward_inflation =
+					multiply_by_rational(add_amount, info.total_rewards, total_shares).unwrap_or_default();
+				if reward_inflation.is_zero() {
+					continue;
+				}
+
+				info.total_rewards = info.total_rewards.saturating_add(reward_inflation);
+				info.total_withdrawn_rewards = info.total_withdrawn_rewards.saturating_add(reward_inflation);
+				ExtraRewardPools::<T>::insert(pool_id, currency_id, info);
+				let withdrawn_reward = withdrawn_rewards.entry(currency_id).or_default();
+				*withdrawn_reward = withdrawn_reward.saturating_add(reward_inflation);
+				inflated = true;
+			}
+
+			if inflated {
+				ExtraWithdrawnRewards::<T>::insert(pool_id, who, withdrawn_rewards);
+			}
+		}
+
+		<module_rewards::Pallet<T>>::add_share(who, pool_id, add_amount);
+		if !Self::is_boosted_pool(pool_id) {
+			Self::update_stake_since(who, pool_id, share, share.saturating_add(add_amount));
+		}
+	}
+
+	/// Remove share from the pool, the pending rewards of extra reward
+	/// currencies will be claimed first.
+	fn remove_share(who: &T::AccountId, pool_id: &PoolId<T::RelaychainAccountId>, remove_amount: Balance) {
+		if remove_amount.is_zero() {
+			return;
+		}
+
+		Self::claim_extra_rewards(who, pool_id);
+
+		let share = <module_rewards::Pallet<T>>::share_and_withdrawn_reward(pool_id, who).0;
+		if !share.is_zero() {
+			let remove_amount = remove_amount.min(share);
+			ExtraWithdrawnRewards::<T>::mutate_exists(pool_id, who, |maybe_withdrawn_rewards| {
+				if let Some(withdrawn_rewards) = maybe_withdrawn_rewards {
+					for (currency_id, withdrawn_reward) in withdrawn_rewards.iter_mut() {
+						let withdrawn_to_remove =
+							multiply_by_rational(remove_amount, *withdrawn_reward, share).unwrap_or_default();
+						ExtraRewardPools::<T>::mutate(pool_id, currency_id, |info| {
+							info.total_rewards = info.total_rewards.saturating_sub(withdrawn_to_remove);
+							info.total_withdrawn_rewards =
+								info.total_withdrawn_rewards.saturating_sub(withdrawn_to_remove);
+						});
+						*withdrawn_reward = withdrawn_reward.saturating_sub(withdrawn_to_remove);
+					}
+					withdrawn_rewards.retain(|_, withdrawn_reward| !withdrawn_reward.is_zero());
+					if withdrawn_rewards.is_empty() {
+						*maybe_withdrawn_rewards = None;
+					}
+				}
+			});
+		}
+
+		<module_rewards::Pallet<T>>::remove_share(who, pool_id, remove_amount);
+		if !Self::is_boosted_pool(pool_id) {
+			Self::update_stake_since(who, pool_id, share, share.saturating_sub(remove_amount));
+		}
+	}
+
+	/// Update the block number since which `who` stakes in the pool by the
+	/// share change, after the rewards are claimed by the previous duration.
+	/// When the share increases, it's the average of the previous block
+	/// number and now weighted by the previous and the added share.
+	fn update_stake_since(
+		who: &T::AccountId,
+		pool_id: &PoolId<T::RelaychainAccountId>,
+		share: Balance,
+		new_share: Balance,
+	) {
+		let now = <frame_system::Pallet<T>>::block_number();
+		if new_share.is_zero() {
+			StakeSince::<T>::remove(pool_id, who);
+		} else if new_share > share {
+			let since = Self::stake_since(pool_id, who)
+				.filter(|_| !share.is_zero())
+				.map_or(now, |since| {
+					let elapsed: Balance = now.saturating_sub(since).unique_saturated_into();
+					let weighted_elapsed =
+						multiply_by_rational(elapsed, new_share.saturating_sub(share), new_share).unwrap_or(elapsed);
+					since.saturating_add(weighted_elapsed.unique_saturated_into())
+				});
+			StakeSince::<T>::insert(pool_id, who, since);
+		} else if new_share < share || !StakeSince::<T>::contains_key(pool_id, who) {
+			StakeSince::<T>::insert(pool_id, who, now);
+		}
+	}
+
+	/// The payout deduction rate of `who` in the pool. It's zero if `who` is
+	/// exempted, or the rate of the highest loyalty tier `who` reaches by the
+	/// duration staking in the pool, which never exceeds the deduction rate
+	/// of the pool.
+	pub fn deduction_rate(pool_id: &PoolId<T::RelaychainAccountId>, who: &T::AccountId) -> Rate {
+		if Self::deduction_exemptions(pool_id, who) {
+			return Rate::zero();
+		}
+
+		let deduction_rate = Self::payout_deduction_rates(pool_id);
+		Self::stake_since(pool_id, who)
+			.and_then(|since| {
+				let duration = <frame_system::Pallet<T>>::block_number().saturating_sub(since);
+				Self::loyalty_tiers(pool_id)
+					.into_iter()
+					.rev()
+					.find(|tier| tier.min_duration <= duration)
+			})
+			.map_or(deduction_rate, |tier| tier.deduction_rate.min(deduction_rate))
+	}
+
+	/// The incentive reward amount of the pool for the period at `now`, by
+	/// the emission schedule if it's set, and scaled by the risk weight of
+	/// the loans incentive pool.
+	pub fn period_incentive_reward_amount(pool_id: &PoolId<T::RelaychainAccountId>, now: T::BlockNumber) -> Balance {
+		let reward_amount = match Self::emission_schedules(pool_id) {
+			Some(schedule) => schedule.reward_amount(now),
+			None => Self::incentive_reward_amount(pool_id),
+		};
+		Self::scale_by_risk_weight(pool_id, reward_amount)
+	}
+
+	/// Scale the incentive reward amount of the loans incentive pool by the
+	/// risk weight of the collateral type, capped at the reward amount.
+	fn scale_by_risk_weight(pool_id: &PoolId<T::RelaychainAccountId>, reward_amount: Balance) -> Balance {
+		match pool_id {
+			PoolId::LoansIncentive(currency_id) => Self::loans_risk_weights(currency_id)
+				.map_or(reward_amount, |risk_weight| {
+					risk_weight.saturating_mul_int(reward_amount).min(reward_amount)
+				}),
+			_ => reward_amount,
+		}
+	}
+
+	/// The maximum total incentive reward amount per period of all the pools
+	/// from now on. It's the initial reward amount for the pool with the
+	/// emission schedule until the end, as the reward amount of the schedule
+	/// never increases.
+	pub fn total_incentive_reward_amount() -> Balance {
+		let now = <frame_system::Pallet<T>>::block_number();
+		let scheduled_reward_amount =
+			EmissionSchedules::<T>::iter().fold(Balance::zero(), |total, (pool_id, schedule)| {
+				let reward_amount = if now < schedule.end {
+					schedule.initial_reward_amount
+				} else {
+					Zero::zero()
+				};
+				total.saturating_add(Self::scale_by_risk_weight(&pool_id, reward_amount))
+			});
+		IncentiveRewardAmount::<T>::iter()
+			.filter(|(pool_id, _)| !EmissionSchedules::<T>::contains_key(pool_id))
+			.fold(scheduled_reward_amount, |total, (pool_id, reward_amount)| {
+				total.saturating_add(Self::scale_by_risk_weight(&pool_id, reward_amount))
+			})
+	}
+
+	/// Ensure the total incentive reward amount is within the budget, unless
+	/// it's not raised from `previous_total`.
+	fn ensure_within_budget(previous_total: Balance) -> DispatchResult {
+		let total = Self::total_incentive_reward_amount();
+		ensure!(
+			total <= previous_total || total <= T::IncentiveRewardsBudget::get(),
+			Error::<T>::ExceedIncentiveRewardsBudget
+		);
+		Ok(())
+	}
+
+	/// Set the incentive reward amount per period of the pool, without the
+	/// budget check.
+	fn do_set_incentive_reward_amount(pool_id: &PoolId<T::RelaychainAccountId>, amount: Balance) -> DispatchResult {
+		match pool_id {
+			PoolId::DexIncentive(currency_id) => {
+				ensure!(currency_id.is_dex_share_currency_id(), Error::<T>::InvalidCurrencyId);
+			}
+			PoolId::LoansIncentive(_) | PoolId::HomaIncentive => {}
+			_ => {
+				return Err(Error::<T>::InvalidPoolId.into());
+			}
+		}
+		IncentiveRewardAmount::<T>::insert(pool_id, amount);
+		Self::deposit_event(Event::IncentiveRewardAmountUpdated(pool_id.clone(), amount));
+		Ok(())
+	}
+
+	/// The dex saving reward amount of the pool for the period.
+	fn dex_saving_reward_amount(pool_id: &PoolId<T::RelaychainAccountId>, lp_currency_id: CurrencyId) -> Balance {
+		let dex_saving_reward_rate = Self::dex_saving_reward_rate(pool_id);
+		if dex_saving_reward_rate.is_zero() {
+			return Zero::zero();
+		}
+
+		let stable_currency_id = T::StableCurrencyId::get();
+		if let Some((currency_id_a, currency_id_b)) = lp_currency_id.split_dex_share_currency_id() {
+			// accumulate saving reward only for liquidity pool of stable currency id
+			let dex_saving_reward_base = if currency_id_a == stable_currency_id {
+				T::DEX::get_liquidity_pool(stable_currency_id, currency_id_b).0
+			} else if currency_id_b == stable_currency_id {
+				T::DEX::get_liquidity_pool(stable_currency_id, currency_id_a).0
+			} else {
+				Zero::zero()
+			};
+			dex_saving_reward_rate.saturating_mul_int(dex_saving_reward_base)
+		} else {
+			Zero::zero()
+		}
+	}
+
+	/// The reward amounts of all the reward currencies of the pool for the
+	/// period at `now`.
+	pub fn period_reward_amounts(
+		pool_id: &PoolId<T::RelaychainAccountId>,
+		now: T::BlockNumber,
+	) -> Vec<(CurrencyId, Balance)> {
+		match pool_id {
+			PoolId::LoansIncentive(_) | PoolId::DexIncentive(_) | PoolId::HomaIncentive => {
+				let mut amounts = vec![(
+					T::NativeCurrencyId::get(),
+					Self::period_incentive_reward_amount(pool_id, now),
+				)];
+				amounts.extend(ExtraIncentiveRewardAmount::<T>::iter_prefix(pool_id));
+				amounts.extend(
+					RewardTopUps::<T>::iter_prefix_values(pool_id)
+						.map(|top_up| (top_up.currency_id, top_up.amount_per_period)),
+				);
+				amounts
+			}
+			PoolId::DexSaving(lp_currency_id) => vec![(
+				T::StableCurrencyId::get(),
+				Self::dex_saving_reward_amount(pool_id, *lp_currency_id),
+			)],
+			PoolId::HomaValidatorAllowance(_) => vec![],
+		}
+	}
+
+	/// The pending rewards of `who` in all the pools, after the payout
+	/// deduction. The rewards are escrowed when claimed if the pool has
+	/// rewards vesting.
+	pub fn pending_rewards(who: &T::AccountId) -> Vec<(PoolId<T::RelaychainAccountId>, CurrencyId, Balance)> {
+		let mut pending_rewards = vec![];
+		for (pool_id, pool_info) in module_rewards::Pools::<T>::iter() {
+			for (currency_id, reward) in Self::pool_pending_rewards(&pool_id, &pool_info, who) {
+				pending_rewards.push((pool_id.clone(), currency_id, reward));
+			}
+		}
+		pending_rewards
+	}
+
+	fn pool_pending_rewards(
+		pool_id: &PoolId<T::RelaychainAccountId>,
+		pool_info: &module_rewards::PoolInfo<Balance, Balance>,
+		who: &T::AccountId,
+	) -> Vec<(CurrencyId, Balance)> {
+		let share = <module_rewards::Pallet<T>>::share_and_withdrawn_reward(pool_id, who).0;
+		if share.is_zero() {
+			return vec![];
+		}
+
+		let deduction_rate = Self::deduction_rate(pool_id, who);
+		let mut rewards = vec![(
+			Self::reward_currency_id(pool_id),
+			<module_rewards::Pallet<T>>::pending_reward(pool_id, who),
+		)];
+		let extra_withdrawn_rewards = Self::extra_withdrawn_rewards(pool_id, who);
+		for (currency_id, info) in ExtraRewardPools::<T>::iter_prefix(pool_id) {
+			rewards.push((
+				currency_id,
+				multiply_by_rational(share, info.total_rewards, pool_info.total_shares)
+					.unwrap_or_default()
+					.saturating_sub(extra_withdrawn_rewards.get(&currency_id).copied().unwrap_or_default()),
+			));
+		}
+
+		rewards
+			.into_iter()
+			.map(|(currency_id, reward)| {
+				(
+					currency_id,
+					reward.saturating_sub(deduction_rate.saturating_mul_int(reward)),
+				)
+			})
+			.filter(|(_, reward)| !reward.is_zero())
+			.collect()
+	}
+
+	/// The APR estimates of all the pools, by the rewards of the period at
+	/// `now`. `reward_price` is the price of the reward currency and
+	/// `share_price` is the price of one share of the pool.
+	pub fn apr_estimates(
+		now: T::BlockNumber,
+		periods_per_year: u32,
+		reward_price: impl Fn(CurrencyId) -> Option<Price>,
+		share_price: impl Fn(&PoolId<T::RelaychainAccountId>) -> Option<Price>,
+	) -> Vec<(PoolId<T::RelaychainAccountId>, Rate)> {
+		module_rewards::Pools::<T>::iter_keys()
+			.filter_map(|pool_id| {
+				let staked_value = share_price(&pool_id)?.saturating_mul_int(Self::total_raw_shares(&pool_id));
+				let reward_value = Self::period_reward_amounts(&pool_id, now)
+					.into_iter()
+					.filter_map(|(currency_id, amount)| Some(reward_price(currency_id)?.saturating_mul_int(amount)))
+					.fold(Balance::zero(), |total, value| total.saturating_add(value));
+				let apr =
+					Rate::checked_from_rational(reward_value.saturating_mul(periods_per_year.into()), staked_value)?;
+				Some((pool_id, apr))
+			})
+			.collect()
+	}
+
+	/// Seed the total raw shares of the existing boosted pools by the total
+	/// shares of orml rewards. The pools without the total raw shares have
+	/// not been boosted yet, so their shares are all raw shares.
+	fn migrate_to_v2() -> Weight {
+		let mut count: u32 = 0;
+
+		for (pool_id, pool_info) in module_rewards::Pools::<T>::iter() {
+			count += 1;
+			if Self::is_boosted_pool(&pool_id) && !TotalRawShares::<T>::contains_key(&pool_id) {
+				TotalRawShares::<T>::insert(&pool_id, pool_info.total_shares);
+			}
+		}
+
+		StorageVersion::<T>::put(Releases::V2_0_0);
+
+		T::DbWeight::get().reads_writes(
+			(count as Weight).saturating_mul(2).saturating_add(1),
+			(count as Weight).saturating_add(1),
+		)
+	}
+
+	/// Whether the shares of the pool are boosted by the voting power.
+	fn is_boosted_pool(pool_id: &PoolId<T::RelaychainAccountId>) -> bool {
+		matches!(pool_id, PoolId::LoansIncentive(_) | PoolId::DexIncentive(_))
+	}
+
+	/// The share of user in the boosted pool before boost.
+	pub fn raw_share(pool_id: &PoolId<T::RelaychainAccountId>, who: &T::AccountId) -> Balance {
+		RawShares::<T>::get(pool_id, who)
+			.unwrap_or_else(|| <module_rewards::Pallet<T>>::share_and_withdrawn_reward(pool_id, who).0)
+	}
+
+	/// The total shares of the boosted pool before boost.
+	pub fn total_raw_shares(pool_id: &PoolId<T::RelaychainAccountId>) -> Balance {
+		TotalRawShares::<T>::get(pool_id)
+	}
+
+	/// The working share of user with `share` in the boosted pool with
+	/// `total_shares`.
+	fn working_share(who: &T::AccountId, share: Balance, total_shares: Balance) -> Balance {
+		let total_voting_power = T::VotingEscrow::total_supply();
+		if total_voting_power.is_zero() {
+			return share;
+		}
+
+		let boosted_share = multiply_by_rational(total_shares, T::VotingEscrow::balance_of(who), total_voting_power)
+			.unwrap_or_default();
+		Rate::saturating_from_rational(40, 100)
+			.saturating_mul_int(share)
+			.saturating_add(Rate::saturating_from_rational(60, 100).saturating_mul_int(boosted_share))
+			.min(share)
+	}
+
+	/// Set share of user in the boosted pool before boost, and update the
+	/// working share by the boost.
+	fn set_raw_share(who: &T::AccountId, pool_id: &PoolId<T::RelaychainAccountId>, new_share: Balance) {
+		let share = Self::raw_share(pool_id, who);
+		let total_shares = Self::total_raw_shares(pool_id)
+			.saturating_sub(share)
+			.saturating_add(new_share);
+		TotalRawShares::<T>::insert(pool_id, total_shares);
+		if new_share.is_zero() {
+			RawShares::<T>::remove(pool_id, who);
+		} else {
+			RawShares::<T>::insert(pool_id, who, new_share);
+		}
+
+		let working_share = Self::working_share(who, new_share, total_shares);
+		Self::set_share(who, pool_id, working_share);
+		Self::update_stake_since(who, pool_id, share, new_share);
+		Self::deposit_event(Event::WorkingShareUpdated(
+			who.clone(),
+			pool_id.clone(),
+			new_share,
+			working_share,
+		));
+	}
+
+	/// Set share of the pool.
+	fn set_share(who: &T::AccountId, pool_id: &PoolId<T::RelaychainAccountId>, new_share: Balance) {
+		let share = <module_rewards::Pallet<T>>::share_and_withdrawn_reward(pool_id, who).0;
+		if new_share > share {
+			Self::add_share(who, pool_id, new_share.saturating_sub(share));
+		} else {
+			Self::remove_share(who, pool_id, share.saturating_sub(new_share));
+		}
+	}
+
+	/// Transfer the reward(exclude deduction) to user from the vault, return
+	/// the deduction amount which should be re-accumulated to the pool.
+	fn transfer_reward(
+		who: &T::AccountId,
+		pool_id: &PoolId<T::RelaychainAccountId>,
+		currency_id: CurrencyId,
+		payout_amount: Balance,
+	) -> Balance {
+		// calculate actual payout and deduction amount
+		let deduction_amount = Self::deduction_rate(pool_id, who)
+			.saturating_mul_int(payout_amount)
+			.min(payout_amount);
+		let actual_payout = payout_amount.saturating_sub(deduction_amount);
+
+		if let Some(params) = Self::rewards_vesting(pool_id) {
+			// keep the reward(exclude deduction) in the vault to vest.
+			if !actual_payout.is_zero() {
+				Self::escrow_reward(who, pool_id, currency_id, actual_payout, params.period);
+			}
+		} else {
+			// payout the reward(exclude deduction) to user from the pool. it should not affect the
+			// process, ignore the result to continue. if it fails, just the user will not
+			// be rewarded, there will not increase user balance.
+			let res = T::Currency::transfer(currency_id, &T::RewardsVaultAccountId::get(), &who, actual_payout);
+			if let Err(e) = res {
+				log::warn!(
+					target: "incentives",
+					"transfer: failed to transfer {:?} {:?} from {:?} to {:?}: {:?}. \
+					This is unexpected but should be safe",
+					actual_payout, currency_id, T::RewardsVaultAccountId::get(), who, e
+				);
+				debug_assert!(false);
+			}
+		}
+
+		Self::deposit_event(Event::PayoutRewards(
+			who.clone(),
+			pool_id.clone(),
+			currency_id,
+			actual_payout,
+			deduction_amount,
+		));
+
+		deduction_amount
+	}
+}
+
+impl<T: Config> OnEmergencyShutdown for Pallet<T> {
+	fn on_emergency_shutdown() {
+		IsAccumulationStopped::<T>::put(true);
+	}
 }
 
 impl<T: Config> DEXIncentives<T::AccountId, CurrencyId, Balance> for Pallet<T> {
 	fn do_deposit_dex_share(who: &T::AccountId, lp_currency_id: CurrencyId, amount: Balance) -> DispatchResult {
 		ensure!(lp_currency_id.is_dex_share_currency_id(), Error::<T>::InvalidCurrencyId);
 
+		let dex_incentive_pool_id = PoolId::DexIncentive(lp_currency_id);
+		let new_share = Self::raw_share(&dex_incentive_pool_id, who).saturating_add(amount);
+		if let Some(limits) = Self::pool_deposit_limits(&dex_incentive_pool_id) {
+			ensure!(
+				!limits.whitelist_only || Self::deposit_whitelist(&dex_incentive_pool_id, who),
+				Error::<T>::NotWhitelisted
+			);
+			ensure!(
+				limits.max_account_shares.map_or(true, |cap| new_share <= cap),
+				Error::<T>::ExceedAccountCap
+			);
+			ensure!(
+				limits.max_total_shares.map_or(true, |cap| {
+					Self::total_raw_shares(&dex_incentive_pool_id).saturating_add(amount) <= cap
+				}),
+				Error::<T>::ExceedPoolCap
+			);
+		}
+
 		T::Currency::transfer(lp_currency_id, who, &Self::account_id(), amount)?;
-		<orml_rewards::Pallet<T>>::add_share(
-			who,
-			&PoolId::DexIncentive(lp_currency_id),
-			amount.unique_saturated_into(),
-		);
-		<orml_rewards::Pallet<T>>::add_share(who, &PoolId::DexSaving(lp_currency_id), amount);
+		Self::set_raw_share(who, &dex_incentive_pool_id, new_share);
+		Self::add_share(who, &PoolId::DexSaving(lp_currency_id), amount);
 
 		Self::deposit_event(Event::DepositDexShare(who.clone(), lp_currency_id, amount));
 		Ok(())
@@ -474,29 +2306,51 @@ impl<T: Config> DEXIncentives<T::AccountId, CurrencyId, Balance> for Pallet<T> {
 
 	fn do_withdraw_dex_share(who: &T::AccountId, lp_currency_id: CurrencyId, amount: Balance) -> DispatchResult {
 		ensure!(lp_currency_id.is_dex_share_currency_id(), Error::<T>::InvalidCurrencyId);
+		let dex_incentive_pool_id = PoolId::DexIncentive(lp_currency_id);
+		let dex_incentive_share = Self::raw_share(&dex_incentive_pool_id, who);
 		ensure!(
-			<orml_rewards::Pallet<T>>::share_and_withdrawn_reward(&PoolId::DexIncentive(lp_currency_id), &who).0
-				>= amount && <orml_rewards::Pallet<T>>::share_and_withdrawn_reward(
-				&PoolId::DexSaving(lp_currency_id),
-				&who
-			)
-			.0 >= amount,
+			dex_incentive_share >= amount
+				&& <module_rewards::Pallet<T>>::share_and_withdrawn_reward(&PoolId::DexSaving(lp_currency_id), &who).0
+					>= amount,
 			Error::<T>::NotEnough,
 		);
 
 		T::Currency::transfer(lp_currency_id, &Self::account_id(), &who, amount)?;
-		<orml_rewards::Pallet<T>>::remove_share(
-			who,
-			&PoolId::DexIncentive(lp_currency_id),
-			amount.unique_saturated_into(),
-		);
-		<orml_rewards::Pallet<T>>::remove_share(who, &PoolId::DexSaving(lp_currency_id), amount);
+		Self::set_raw_share(who, &dex_incentive_pool_id, dex_incentive_share.saturating_sub(amount));
+		Self::remove_share(who, &PoolId::DexSaving(lp_currency_id), amount);
 
 		Self::deposit_event(Event::WithdrawDexShare(who.clone(), lp_currency_id, amount));
 		Ok(())
 	}
 }
 
+impl<T: Config> IncentivesManager<PoolId<T::RelaychainAccountId>, Balance> for Pallet<T> {
+	#[transactional]
+	fn set_incentive_reward_amount(pool_id: &PoolId<T::RelaychainAccountId>, amount: Balance) -> DispatchResult {
+		let total_reward_amount = Self::total_incentive_reward_amount();
+		Self::do_set_incentive_reward_amount(pool_id, amount)?;
+		Self::ensure_within_budget(total_reward_amount)
+	}
+}
+
+impl<T: Config> IncentivesRewards<T::AccountId, PoolId<T::RelaychainAccountId>, CurrencyId, Balance> for Pallet<T> {
+	fn claim_rewards(who: &T::AccountId, pool_id: &PoolId<T::RelaychainAccountId>) {
+		<module_rewards::Pallet<T>>::claim_rewards(who, pool_id);
+		Self::claim_extra_rewards(who, pool_id);
+
+		// the rewards until now are earned by the previous working share, the boost
+		// follows the decaying voting power from now on.
+		let share = Self::raw_share(pool_id, who);
+		if Self::is_boosted_pool(pool_id) && !share.is_zero() {
+			Self::set_raw_share(who, pool_id, share);
+		}
+	}
+
+	fn pending_rewards(who: &T::AccountId, pool_id: &PoolId<T::RelaychainAccountId>) -> Vec<(CurrencyId, Balance)> {
+		Self::pool_pending_rewards(pool_id, &module_rewards::Pools::<T>::get(pool_id), who)
+	}
+}
+
 pub struct OnUpdateLoan<T>(sp_std::marker::PhantomData<T>);
 impl<T: Config> Happened<(T::AccountId, CurrencyId, Amount, Balance)> for OnUpdateLoan<T> {
 	fn happened(info: &(T::AccountId, CurrencyId, Amount, Balance)) {
@@ -511,7 +2365,7 @@ impl<T: Config> Happened<(T::AccountId, CurrencyId, Amount, Balance)> for OnUpda
 				previous_amount.saturating_sub(adjustment_abs)
 			};
 
-			<orml_rewards::Pallet<T>>::set_share(who, &PoolId::LoansIncentive(*currency_id), new_share_amount);
+			Pallet::<T>::set_raw_share(who, &PoolId::LoansIncentive(*currency_id), new_share_amount);
 		}
 	}
 }
@@ -520,7 +2374,7 @@ pub struct OnIncreaseGuarantee<T>(sp_std::marker::PhantomData<T>);
 impl<T: Config> Happened<(T::AccountId, T::RelaychainAccountId, Balance)> for OnIncreaseGuarantee<T> {
 	fn happened(info: &(T::AccountId, T::RelaychainAccountId, Balance)) {
 		let (who, validator, increment) = info;
-		<orml_rewards::Pallet<T>>::add_share(who, &PoolId::HomaValidatorAllowance(validator.clone()), *increment);
+		Pallet::<T>::add_share(who, &PoolId::HomaValidatorAllowance(validator.clone()), *increment);
 	}
 }
 
@@ -528,7 +2382,7 @@ pub struct OnDecreaseGuarantee<T>(sp_std::marker::PhantomData<T>);
 impl<T: Config> Happened<(T::AccountId, T::RelaychainAccountId, Balance)> for OnDecreaseGuarantee<T> {
 	fn happened(info: &(T::AccountId, T::RelaychainAccountId, Balance)) {
 		let (who, validator, decrement) = info;
-		<orml_rewards::Pallet<T>>::remove_share(who, &PoolId::HomaValidatorAllowance(validator.clone()), *decrement);
+		Pallet::<T>::remove_share(who, &PoolId::HomaValidatorAllowance(validator.clone()), *decrement);
 	}
 }
 
@@ -541,44 +2395,11 @@ impl<T: Config> RewardHandler<T::AccountId> for Pallet<T> {
 			return;
 		}
 
-		let currency_id = match pool_id {
-			PoolId::LoansIncentive(_) | PoolId::DexIncentive(_) | PoolId::HomaIncentive => T::NativeCurrencyId::get(),
-			PoolId::DexSaving(_) => T::StableCurrencyId::get(),
-			PoolId::HomaValidatorAllowance(_) => T::LiquidCurrencyId::get(),
-		};
-
-		// calculate actual payout and deduction amount
-		let (actual_payout, deduction_amount) = {
-			let deduction_amount = Self::payout_deduction_rates(pool_id)
-				.saturating_mul_int(payout_amount)
-				.min(payout_amount);
-			if !deduction_amount.is_zero() {
-				// re-accumulate deduction to rewards pool if deduction amount is not zero
-				<orml_rewards::Pallet<T>>::accumulate_reward(pool_id, deduction_amount);
-			}
-			(payout_amount.saturating_sub(deduction_amount), deduction_amount)
-		};
-
-		// payout the reward(exclude deduction) to user from the pool. it should not affect the
-		// process, ignore the result to continue. if it fails, just the user will not
-		// be rewarded, there will not increase user balance.
-		let res = T::Currency::transfer(currency_id, &T::RewardsVaultAccountId::get(), &who, actual_payout);
-		if let Err(e) = res {
-			log::warn!(
-				target: "incentives",
-				"transfer: failed to transfer {:?} {:?} from {:?} to {:?}: {:?}. \
-				This is unexpected but should be safe",
-				actual_payout, currency_id, T::RewardsVaultAccountId::get(), who, e
-			);
-			debug_assert!(false);
+		let currency_id = Self::reward_currency_id(pool_id);
+		let deduction_amount = Self::transfer_reward(who, pool_id, currency_id, payout_amount);
+		if !deduction_amount.is_zero() {
+			// re-accumulate deduction to rewards pool if deduction amount is not zero
+			<module_rewards::Pallet<T>>::accumulate_reward(pool_id, deduction_amount);
 		}
-
-		Self::deposit_event(Event::PayoutRewards(
-			who.clone(),
-			pool_id.clone(),
-			currency_id,
-			actual_payout,
-			deduction_amount,
-		));
 	}
 }
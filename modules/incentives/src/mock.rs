@@ -24,15 +24,16 @@ use super::*;
 use frame_support::{
 	construct_runtime,
 	dispatch::{DispatchError, DispatchResult},
-	ord_parameter_types, parameter_types,
+	ensure, ord_parameter_types, parameter_types,
 };
 use frame_system::EnsureSignedBy;
-use orml_traits::parameter_type_with_key;
+use orml_traits::{parameter_type_with_key, MultiCurrency};
 use primitives::{DexShare, TokenSymbol};
 use sp_core::{H160, H256};
 use sp_runtime::{testing::Header, traits::IdentityLookup};
 use sp_std::cell::RefCell;
-pub use support::{CDPTreasury, DEXManager, Price, Ratio};
+use std::collections::HashMap;
+pub use support::{CDPTreasury, DEXManager, Price, Ratio, VotingEscrow};
 
 pub type AccountId = u128;
 pub type BlockNumber = u64;
@@ -41,6 +42,7 @@ pub const ALICE: AccountId = 1;
 pub const BOB: AccountId = 2;
 pub const VAULT: AccountId = 10;
 pub const UNRELEASED: AccountId = 11;
+pub const TREASURY: AccountId = 12;
 pub const VALIDATOR: AccountId = 3;
 pub const ACA: CurrencyId = CurrencyId::Token(TokenSymbol::ACA);
 pub const AUSD: CurrencyId = CurrencyId::Token(TokenSymbol::AUSD);
@@ -177,14 +179,21 @@ impl DEXManager<AccountId, CurrencyId, Balance> for MockDEX {
 		unimplemented!()
 	}
 
+	// swap at 1:1
 	fn swap_with_exact_supply(
-		_: &AccountId,
-		_: &[CurrencyId],
-		_: Balance,
-		_: Balance,
+		who: &AccountId,
+		path: &[CurrencyId],
+		supply_amount: Balance,
+		min_target_amount: Balance,
 		_: Option<Ratio>,
 	) -> sp_std::result::Result<Balance, DispatchError> {
-		unimplemented!()
+		ensure!(
+			supply_amount >= min_target_amount,
+			DispatchError::Other("SlippageExceeded")
+		);
+		TokensModule::withdraw(path[0], who, supply_amount)?;
+		TokensModule::deposit(path[path.len() - 1], who, supply_amount)?;
+		Ok(supply_amount)
 	}
 
 	fn swap_with_exact_target(
@@ -197,16 +206,29 @@ impl DEXManager<AccountId, CurrencyId, Balance> for MockDEX {
 		unimplemented!()
 	}
 
+	// issue the sum of the amounts as dex share
 	fn add_liquidity(
-		_: &AccountId,
-		_: CurrencyId,
-		_: CurrencyId,
-		_: Balance,
-		_: Balance,
-		_: Balance,
-		_: bool,
+		who: &AccountId,
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		max_amount_a: Balance,
+		max_amount_b: Balance,
+		min_share_increment: Balance,
+		deposit_increment_share: bool,
 	) -> DispatchResult {
-		unimplemented!()
+		let lp_currency_id = CurrencyId::join_dex_share_currency_id(currency_id_a, currency_id_b).unwrap();
+		let share_increment = max_amount_a + max_amount_b;
+		ensure!(
+			share_increment >= min_share_increment,
+			DispatchError::Other("SlippageExceeded")
+		);
+		TokensModule::withdraw(currency_id_a, who, max_amount_a)?;
+		TokensModule::withdraw(currency_id_b, who, max_amount_b)?;
+		TokensModule::deposit(lp_currency_id, who, share_increment)?;
+		if deposit_increment_share {
+			IncentivesModule::do_deposit_dex_share(who, lp_currency_id, share_increment)?;
+		}
+		Ok(())
 	}
 
 	fn remove_liquidity(
@@ -223,21 +245,40 @@ impl DEXManager<AccountId, CurrencyId, Balance> for MockDEX {
 }
 
 thread_local! {
-	static IS_SHUTDOWN: RefCell<bool> = RefCell::new(false);
+	static VOTING_POWER: RefCell<HashMap<AccountId, Balance>> = RefCell::new(HashMap::new());
+}
+
+pub fn mock_voting_power(who: AccountId, voting_power: Balance) {
+	VOTING_POWER.with(|v| v.borrow_mut().insert(who, voting_power));
+}
+
+pub struct MockVotingEscrow;
+impl VotingEscrow<AccountId, Balance> for MockVotingEscrow {
+	fn balance_of(who: &AccountId) -> Balance {
+		VOTING_POWER.with(|v| v.borrow().get(who).copied().unwrap_or_default())
+	}
+
+	fn total_supply() -> Balance {
+		VOTING_POWER.with(|v| v.borrow().values().sum())
+	}
+}
+
+thread_local! {
+	static INCENTIVE_REWARDS_BUDGET: RefCell<Balance> = RefCell::new(Balance::max_value());
 }
 
-pub fn mock_shutdown() {
-	IS_SHUTDOWN.with(|v| *v.borrow_mut() = true)
+pub fn mock_incentive_rewards_budget(budget: Balance) {
+	INCENTIVE_REWARDS_BUDGET.with(|v| *v.borrow_mut() = budget);
 }
 
-pub struct MockEmergencyShutdown;
-impl EmergencyShutdown for MockEmergencyShutdown {
-	fn is_shutdown() -> bool {
-		IS_SHUTDOWN.with(|v| *v.borrow_mut())
+pub struct MockIncentiveRewardsBudget;
+impl Get<Balance> for MockIncentiveRewardsBudget {
+	fn get() -> Balance {
+		INCENTIVE_REWARDS_BUDGET.with(|v| *v.borrow())
 	}
 }
 
-impl orml_rewards::Config for Runtime {
+impl module_rewards::Config for Runtime {
 	type Share = Balance;
 	type Balance = Balance;
 	type PoolId = PoolId<AccountId>;
@@ -247,11 +288,17 @@ impl orml_rewards::Config for Runtime {
 parameter_types! {
 	pub const RewardsVaultAccountId: AccountId = VAULT;
 	pub const NativeRewardsSource: AccountId = UNRELEASED;
+	pub const ExtraRewardsSource: AccountId = TREASURY;
 	pub const AccumulatePeriod: BlockNumber = 10;
 	pub const NativeCurrencyId: CurrencyId = ACA;
 	pub const StableCurrencyId: CurrencyId = AUSD;
 	pub const LiquidCurrencyId: CurrencyId = LDOT;
 	pub const IncentivesPalletId: PalletId = PalletId(*b"aca/inct");
+	pub const MaxClaimAllPools: u32 = 3;
+	pub const MaxLoyaltyTiers: u32 = 2;
+	pub const MaxTopUps: u32 = 2;
+	pub const MaxEscrowedRewards: u32 = 2;
+	pub const MaxLoansRiskWeights: u32 = 2;
 }
 
 ord_parameter_types! {
@@ -263,6 +310,7 @@ impl Config for Runtime {
 	type RelaychainAccountId = AccountId;
 	type RewardsVaultAccountId = RewardsVaultAccountId;
 	type NativeRewardsSource = NativeRewardsSource;
+	type ExtraRewardsSource = ExtraRewardsSource;
 	type AccumulatePeriod = AccumulatePeriod;
 	type NativeCurrencyId = NativeCurrencyId;
 	type StableCurrencyId = StableCurrencyId;
@@ -271,8 +319,15 @@ impl Config for Runtime {
 	type CDPTreasury = MockCDPTreasury;
 	type Currency = TokensModule;
 	type DEX = MockDEX;
-	type EmergencyShutdown = MockEmergencyShutdown;
+	type VotingEscrow = MockVotingEscrow;
+	type IncentiveRewardsBudget = MockIncentiveRewardsBudget;
+	type MaxClaimAllPools = MaxClaimAllPools;
+	type MaxLoyaltyTiers = MaxLoyaltyTiers;
+	type MaxTopUps = MaxTopUps;
+	type MaxEscrowedRewards = MaxEscrowedRewards;
+	type MaxLoansRiskWeights = MaxLoansRiskWeights;
 	type PalletId = IncentivesPalletId;
+	type IdleScheduler = ();
 	type WeightInfo = ();
 }
 
@@ -286,9 +341,9 @@ construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Pallet, Call, Storage, Config, Event<T>},
-		IncentivesModule: incentives::{Pallet, Storage, Call, Event<T>},
+		IncentivesModule: incentives::{Pallet, Storage, Call, Config<T>, Event<T>},
 		TokensModule: orml_tokens::{Pallet, Storage, Event<T>, Config<T>},
-		RewardsModule: orml_rewards::{Pallet, Storage, Call},
+		RewardsModule: module_rewards::{Pallet, Storage, Call},
 	}
 );
 
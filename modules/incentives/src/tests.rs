@@ -23,9 +23,9 @@
 use super::*;
 use frame_support::{assert_noop, assert_ok};
 use mock::{Event, *};
-use orml_rewards::PoolInfo;
+use module_rewards::PoolInfo;
 use orml_traits::MultiCurrency;
-use sp_runtime::{traits::BadOrigin, FixedPointNumber};
+use sp_runtime::{traits::BadOrigin, DispatchError, FixedPointNumber};
 
 #[test]
 fn deposit_dex_share_works() {
@@ -256,6 +256,82 @@ fn update_incentive_rewards_works() {
 	});
 }
 
+#[test]
+fn update_extra_incentive_rewards_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			IncentivesModule::update_extra_incentive_rewards(Origin::signed(ALICE), vec![]),
+			BadOrigin
+		);
+
+		assert_eq!(
+			IncentivesModule::extra_incentive_reward_amount(PoolId::LoansIncentive(DOT), AUSD),
+			0
+		);
+		assert_ok!(IncentivesModule::update_extra_incentive_rewards(
+			Origin::signed(4),
+			vec![
+				(PoolId::LoansIncentive(DOT), AUSD, 500),
+				(PoolId::LoansIncentive(DOT), LDOT, 100),
+				(PoolId::DexIncentive(DOT_AUSD_LP), LDOT, 200),
+			],
+		));
+		System::assert_has_event(Event::IncentivesModule(
+			crate::Event::ExtraIncentiveRewardAmountUpdated(PoolId::LoansIncentive(DOT), AUSD, 500),
+		));
+		System::assert_has_event(Event::IncentivesModule(
+			crate::Event::ExtraIncentiveRewardAmountUpdated(PoolId::LoansIncentive(DOT), LDOT, 100),
+		));
+		System::assert_has_event(Event::IncentivesModule(
+			crate::Event::ExtraIncentiveRewardAmountUpdated(PoolId::DexIncentive(DOT_AUSD_LP), LDOT, 200),
+		));
+		assert_eq!(
+			IncentivesModule::extra_incentive_reward_amount(PoolId::LoansIncentive(DOT), AUSD),
+			500
+		);
+		assert_eq!(
+			IncentivesModule::extra_incentive_reward_amount(PoolId::LoansIncentive(DOT), LDOT),
+			100
+		);
+		assert_eq!(
+			IncentivesModule::extra_incentive_reward_amount(PoolId::DexIncentive(DOT_AUSD_LP), LDOT),
+			200
+		);
+
+		assert_ok!(IncentivesModule::update_extra_incentive_rewards(
+			Origin::signed(4),
+			vec![(PoolId::LoansIncentive(DOT), AUSD, 0)],
+		));
+		assert_eq!(
+			ExtraIncentiveRewardAmount::<Runtime>::contains_key(PoolId::LoansIncentive(DOT), AUSD),
+			false
+		);
+
+		assert_noop!(
+			IncentivesModule::update_extra_incentive_rewards(
+				Origin::signed(4),
+				vec![(PoolId::LoansIncentive(DOT), ACA, 100)],
+			),
+			Error::<Runtime>::InvalidCurrencyId
+		);
+		assert_noop!(
+			IncentivesModule::update_extra_incentive_rewards(
+				Origin::signed(4),
+				vec![(PoolId::DexIncentive(DOT), AUSD, 100)],
+			),
+			Error::<Runtime>::InvalidCurrencyId
+		);
+		assert_noop!(
+			IncentivesModule::update_extra_incentive_rewards(
+				Origin::signed(4),
+				vec![(PoolId::DexSaving(DOT_AUSD_LP), LDOT, 100)],
+			),
+			Error::<Runtime>::InvalidPoolId
+		);
+	});
+}
+
 #[test]
 fn update_dex_saving_rewards_works() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -583,6 +659,70 @@ fn payout_works() {
 	});
 }
 
+#[test]
+fn accumulate_rewards_task_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(IncentivesModule::update_incentive_rewards(
+			Origin::signed(4),
+			vec![
+				(PoolId::LoansIncentive(BTC), 1000),
+				(PoolId::DexIncentive(BTC_AUSD_LP), 100)
+			],
+		));
+		RewardsModule::add_share(&ALICE, &PoolId::LoansIncentive(BTC), 1);
+		RewardsModule::add_share(&ALICE, &PoolId::DexIncentive(BTC_AUSD_LP), 1);
+
+		let item_weight = <() as WeightInfo>::on_initialize(1) - <() as WeightInfo>::on_initialize(0);
+		let max_pool_weight = item_weight * (MaxTopUps::get() as Weight + 1);
+		let mut task = IncentivesTask::<Runtime>::AccumulateRewards {
+			now: 10,
+			pools: vec![PoolId::LoansIncentive(BTC), PoolId::DexIncentive(BTC_AUSD_LP)],
+		};
+
+		// not enough weight for a pool
+		assert_eq!(
+			task.dispatch(max_pool_weight - 1),
+			TaskResult {
+				result: Ok(()),
+				used_weight: 0,
+				finished: false,
+			}
+		);
+		assert_eq!(RewardsModule::pools(PoolId::LoansIncentive(BTC)).total_rewards, 0);
+
+		// the pools are accumulated in order
+		assert_eq!(
+			task.dispatch(max_pool_weight),
+			TaskResult {
+				result: Ok(()),
+				used_weight: item_weight,
+				finished: false,
+			}
+		);
+		assert_eq!(RewardsModule::pools(PoolId::LoansIncentive(BTC)).total_rewards, 1000);
+		assert_eq!(RewardsModule::pools(PoolId::DexIncentive(BTC_AUSD_LP)).total_rewards, 0);
+		assert_eq!(
+			task,
+			IncentivesTask::<Runtime>::AccumulateRewards {
+				now: 10,
+				pools: vec![PoolId::DexIncentive(BTC_AUSD_LP)],
+			}
+		);
+
+		// the accumulation is stopped by emergency shutdown
+		IncentivesModule::on_emergency_shutdown();
+		assert_eq!(
+			task.dispatch(Weight::max_value()),
+			TaskResult {
+				result: Ok(()),
+				used_weight: 0,
+				finished: true,
+			}
+		);
+		assert_eq!(RewardsModule::pools(PoolId::DexIncentive(BTC_AUSD_LP)).total_rewards, 0);
+	});
+}
+
 #[test]
 fn on_initialize_should_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -666,7 +806,8 @@ fn on_initialize_should_work() {
 		assert_eq!(RewardsModule::pools(PoolId::DexSaving(BTC_AUSD_LP)).total_rewards, 10);
 		assert_eq!(RewardsModule::pools(PoolId::DexSaving(DOT_AUSD_LP)).total_rewards, 8);
 
-		mock_shutdown();
+		<IncentivesModule as OnEmergencyShutdown>::on_emergency_shutdown();
+		assert_eq!(IncentivesModule::is_accumulation_stopped(), true);
 		IncentivesModule::on_initialize(30);
 		assert_eq!(TokensModule::free_balance(ACA, &VAULT), 4630);
 		assert_eq!(TokensModule::free_balance(AUSD, &VAULT), 18);
@@ -685,3 +826,1644 @@ fn on_initialize_should_work() {
 		assert_eq!(RewardsModule::pools(PoolId::DexSaving(DOT_AUSD_LP)).total_rewards, 8);
 	});
 }
+
+#[test]
+fn extra_rewards_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TokensModule::deposit(AUSD, &TREASURY, 10000));
+		assert_ok!(TokensModule::deposit(LDOT, &TREASURY, 10000));
+		assert_ok!(IncentivesModule::update_extra_incentive_rewards(
+			Origin::signed(4),
+			vec![
+				(PoolId::LoansIncentive(BTC), AUSD, 100),
+				(PoolId::LoansIncentive(BTC), LDOT, 50),
+			],
+		));
+
+		OnUpdateLoan::<Runtime>::happened(&(ALICE, BTC, 100, 0));
+		IncentivesModule::on_initialize(10);
+		assert_eq!(TokensModule::free_balance(AUSD, &TREASURY), 9900);
+		assert_eq!(TokensModule::free_balance(LDOT, &TREASURY), 9950);
+		assert_eq!(
+			IncentivesModule::extra_reward_pools(PoolId::LoansIncentive(BTC), AUSD),
+			ExtraRewardInfo {
+				total_rewards: 100,
+				total_withdrawn_rewards: 0,
+			}
+		);
+		assert_eq!(
+			IncentivesModule::extra_reward_pools(PoolId::LoansIncentive(BTC), LDOT),
+			ExtraRewardInfo {
+				total_rewards: 50,
+				total_withdrawn_rewards: 0,
+			}
+		);
+
+		// the rewards accumulated before are kept for ALICE
+		OnUpdateLoan::<Runtime>::happened(&(BOB, BTC, 300, 0));
+		assert_eq!(
+			IncentivesModule::extra_reward_pools(PoolId::LoansIncentive(BTC), AUSD),
+			ExtraRewardInfo {
+				total_rewards: 400,
+				total_withdrawn_rewards: 300,
+			}
+		);
+		assert_eq!(
+			IncentivesModule::extra_withdrawn_rewards(PoolId::LoansIncentive(BTC), BOB),
+			vec![(AUSD, 300), (LDOT, 150)].into_iter().collect()
+		);
+
+		IncentivesModule::on_initialize(20);
+		assert_ok!(IncentivesModule::claim_rewards(
+			Origin::signed(ALICE),
+			PoolId::LoansIncentive(BTC)
+		));
+		System::assert_has_event(Event::IncentivesModule(crate::Event::PayoutRewards(
+			ALICE,
+			PoolId::LoansIncentive(BTC),
+			AUSD,
+			125,
+			0,
+		)));
+		System::assert_has_event(Event::IncentivesModule(crate::Event::PayoutRewards(
+			ALICE,
+			PoolId::LoansIncentive(BTC),
+			LDOT,
+			62,
+			0,
+		)));
+		assert_eq!(TokensModule::free_balance(AUSD, &ALICE), 125);
+		assert_eq!(TokensModule::free_balance(LDOT, &ALICE), 62);
+
+		assert_ok!(IncentivesModule::claim_rewards(
+			Origin::signed(BOB),
+			PoolId::LoansIncentive(BTC)
+		));
+		assert_eq!(TokensModule::free_balance(AUSD, &BOB), 75);
+		assert_eq!(TokensModule::free_balance(LDOT, &BOB), 37);
+		assert_eq!(TokensModule::free_balance(AUSD, &VAULT), 0);
+		assert_eq!(TokensModule::free_balance(LDOT, &VAULT), 1);
+
+		// remove all share of BOB
+		OnUpdateLoan::<Runtime>::happened(&(BOB, BTC, -300, 300));
+		assert_eq!(
+			IncentivesModule::extra_reward_pools(PoolId::LoansIncentive(BTC), AUSD),
+			ExtraRewardInfo {
+				total_rewards: 125,
+				total_withdrawn_rewards: 125,
+			}
+		);
+		assert_eq!(
+			ExtraWithdrawnRewards::<Runtime>::contains_key(PoolId::LoansIncentive(BTC), BOB),
+			false
+		);
+	});
+}
+
+#[test]
+fn update_rewards_vesting_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let params = RewardsVestingParams {
+			period: 100,
+			early_exit_penalty_rate: Rate::saturating_from_rational(50, 100),
+		};
+		assert_noop!(
+			IncentivesModule::update_rewards_vesting(Origin::signed(ALICE), vec![]),
+			BadOrigin
+		);
+		assert_noop!(
+			IncentivesModule::update_rewards_vesting(
+				Origin::signed(4),
+				vec![(
+					PoolId::HomaIncentive,
+					Some(RewardsVestingParams { period: 0, ..params })
+				)],
+			),
+			Error::<Runtime>::InvalidPeriod
+		);
+		assert_noop!(
+			IncentivesModule::update_rewards_vesting(
+				Origin::signed(4),
+				vec![(
+					PoolId::HomaIncentive,
+					Some(RewardsVestingParams {
+						early_exit_penalty_rate: Rate::saturating_from_rational(101, 100),
+						..params
+					})
+				)],
+			),
+			Error::<Runtime>::InvalidRate
+		);
+
+		assert_eq!(IncentivesModule::rewards_vesting(PoolId::HomaIncentive), None);
+		assert_ok!(IncentivesModule::update_rewards_vesting(
+			Origin::signed(4),
+			vec![(PoolId::HomaIncentive, Some(params))],
+		));
+		System::assert_last_event(Event::IncentivesModule(crate::Event::RewardsVestingUpdated(
+			PoolId::HomaIncentive,
+			Some(params),
+		)));
+		assert_eq!(IncentivesModule::rewards_vesting(PoolId::HomaIncentive), Some(params));
+
+		assert_ok!(IncentivesModule::update_rewards_vesting(
+			Origin::signed(4),
+			vec![(PoolId::HomaIncentive, None)],
+		));
+		System::assert_last_event(Event::IncentivesModule(crate::Event::RewardsVestingUpdated(
+			PoolId::HomaIncentive,
+			None,
+		)));
+		assert_eq!(IncentivesModule::rewards_vesting(PoolId::HomaIncentive), None);
+	});
+}
+
+#[test]
+fn claim_escrowed_rewards_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TokensModule::deposit(ACA, &VAULT, 10000));
+		assert_ok!(IncentivesModule::update_rewards_vesting(
+			Origin::signed(4),
+			vec![(
+				PoolId::LoansIncentive(BTC),
+				Some(RewardsVestingParams {
+					period: 100,
+					early_exit_penalty_rate: Rate::saturating_from_rational(50, 100),
+				})
+			)],
+		));
+		assert_noop!(
+			IncentivesModule::claim_escrowed_rewards(Origin::signed(ALICE), PoolId::LoansIncentive(BTC), ACA, false),
+			Error::<Runtime>::NoEscrowedRewards
+		);
+
+		IncentivesModule::payout(&ALICE, &PoolId::LoansIncentive(BTC), 1000);
+		System::assert_has_event(Event::IncentivesModule(crate::Event::RewardsEscrowed(
+			ALICE,
+			PoolId::LoansIncentive(BTC),
+			ACA,
+			1000,
+		)));
+		assert_eq!(TokensModule::free_balance(ACA, &VAULT), 10000);
+		assert_eq!(TokensModule::free_balance(ACA, &ALICE), 0);
+		assert_eq!(
+			IncentivesModule::escrowed_rewards(ALICE, (PoolId::LoansIncentive(BTC), ACA)),
+			vec![EscrowedReward {
+				start: 1,
+				period: 100,
+				total: 1000,
+				claimed: 0,
+			}]
+		);
+
+		// claim the vested half
+		System::set_block_number(51);
+		assert_ok!(IncentivesModule::claim_escrowed_rewards(
+			Origin::signed(ALICE),
+			PoolId::LoansIncentive(BTC),
+			ACA,
+			false
+		));
+		System::assert_last_event(Event::IncentivesModule(crate::Event::EscrowedRewardsClaimed(
+			ALICE,
+			PoolId::LoansIncentive(BTC),
+			ACA,
+			500,
+			0,
+		)));
+		assert_eq!(TokensModule::free_balance(ACA, &ALICE), 500);
+		assert_eq!(
+			IncentivesModule::escrowed_rewards(ALICE, (PoolId::LoansIncentive(BTC), ACA)),
+			vec![EscrowedReward {
+				start: 1,
+				period: 100,
+				total: 1000,
+				claimed: 500,
+			}]
+		);
+
+		// the new escrowed rewards vest in a new entry from now, the previous entry
+		// keeps vesting
+		IncentivesModule::payout(&ALICE, &PoolId::LoansIncentive(BTC), 1000);
+		assert_eq!(
+			IncentivesModule::escrowed_rewards(ALICE, (PoolId::LoansIncentive(BTC), ACA)),
+			vec![
+				EscrowedReward {
+					start: 1,
+					period: 100,
+					total: 1000,
+					claimed: 500,
+				},
+				EscrowedReward {
+					start: 51,
+					period: 100,
+					total: 1000,
+					claimed: 0,
+				}
+			]
+		);
+
+		// exit early, half of the unvested is forfeited back to the pool
+		System::set_block_number(61);
+		assert_ok!(IncentivesModule::claim_escrowed_rewards(
+			Origin::signed(ALICE),
+			PoolId::LoansIncentive(BTC),
+			ACA,
+			true
+		));
+		System::assert_last_event(Event::IncentivesModule(crate::Event::EscrowedRewardsClaimed(
+			ALICE,
+			PoolId::LoansIncentive(BTC),
+			ACA,
+			850,
+			650,
+		)));
+		assert_eq!(TokensModule::free_balance(ACA, &ALICE), 1350);
+		assert_eq!(TokensModule::free_balance(ACA, &VAULT), 8650);
+		assert_eq!(
+			IncentivesModule::escrowed_rewards(ALICE, (PoolId::LoansIncentive(BTC), ACA)),
+			vec![]
+		);
+		assert_eq!(
+			RewardsModule::pools(PoolId::LoansIncentive(BTC)),
+			PoolInfo {
+				total_shares: 0,
+				total_rewards: 650,
+				total_withdrawn_rewards: 0
+			}
+		);
+	});
+}
+
+#[test]
+fn escrowed_rewards_vest_per_claim() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TokensModule::deposit(ACA, &VAULT, 10000));
+		assert_ok!(IncentivesModule::update_rewards_vesting(
+			Origin::signed(4),
+			vec![(
+				PoolId::LoansIncentive(BTC),
+				Some(RewardsVestingParams {
+					period: 100,
+					early_exit_penalty_rate: Rate::saturating_from_rational(50, 100),
+				})
+			)],
+		));
+
+		IncentivesModule::payout(&ALICE, &PoolId::LoansIncentive(BTC), 1000);
+		System::set_block_number(51);
+		IncentivesModule::payout(&ALICE, &PoolId::LoansIncentive(BTC), 1000);
+		assert_eq!(TokensModule::free_balance(ACA, &ALICE), 500);
+
+		// the entries are full, the new rewards vest with the latest entry
+		System::set_block_number(61);
+		IncentivesModule::payout(&ALICE, &PoolId::LoansIncentive(BTC), 100);
+		assert_eq!(TokensModule::free_balance(ACA, &ALICE), 700);
+		assert_eq!(
+			IncentivesModule::escrowed_rewards(ALICE, (PoolId::LoansIncentive(BTC), ACA)),
+			vec![
+				EscrowedReward {
+					start: 1,
+					period: 100,
+					total: 1000,
+					claimed: 600,
+				},
+				EscrowedReward {
+					start: 61,
+					period: 100,
+					total: 1000,
+					claimed: 0,
+				}
+			]
+		);
+
+		// the first claim is fully vested regardless of the later claims
+		System::set_block_number(101);
+		assert_ok!(IncentivesModule::claim_escrowed_rewards(
+			Origin::signed(ALICE),
+			PoolId::LoansIncentive(BTC),
+			ACA,
+			false
+		));
+		assert_eq!(TokensModule::free_balance(ACA, &ALICE), 1500);
+		assert_eq!(
+			IncentivesModule::escrowed_rewards(ALICE, (PoolId::LoansIncentive(BTC), ACA)),
+			vec![EscrowedReward {
+				start: 61,
+				period: 100,
+				total: 1000,
+				claimed: 400,
+			}]
+		);
+	});
+}
+
+#[test]
+fn update_boost_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		mock_voting_power(ALICE, 100);
+
+		OnUpdateLoan::<Runtime>::happened(&(ALICE, BTC, 100, 0));
+		assert_eq!(IncentivesModule::raw_share(&PoolId::LoansIncentive(BTC), &ALICE), 100);
+		assert_eq!(
+			RewardsModule::share_and_withdrawn_reward(PoolId::LoansIncentive(BTC), ALICE).0,
+			100
+		);
+
+		OnUpdateLoan::<Runtime>::happened(&(BOB, BTC, 100, 0));
+		assert_eq!(IncentivesModule::raw_share(&PoolId::LoansIncentive(BTC), &BOB), 100);
+		assert_eq!(IncentivesModule::total_raw_shares(&PoolId::LoansIncentive(BTC)), 200);
+		assert_eq!(
+			RewardsModule::share_and_withdrawn_reward(PoolId::LoansIncentive(BTC), BOB).0,
+			40
+		);
+		System::assert_last_event(Event::IncentivesModule(crate::Event::WorkingShareUpdated(
+			BOB,
+			PoolId::LoansIncentive(BTC),
+			100,
+			40,
+		)));
+
+		assert_noop!(
+			IncentivesModule::update_boost(Origin::signed(ALICE), BOB, PoolId::DexSaving(BTC_AUSD_LP)),
+			Error::<Runtime>::InvalidPoolId
+		);
+
+		mock_voting_power(BOB, 100);
+		assert_ok!(IncentivesModule::update_boost(
+			Origin::signed(ALICE),
+			BOB,
+			PoolId::LoansIncentive(BTC)
+		));
+		assert_eq!(
+			RewardsModule::share_and_withdrawn_reward(PoolId::LoansIncentive(BTC), BOB).0,
+			100
+		);
+		assert_eq!(IncentivesModule::raw_share(&PoolId::LoansIncentive(BTC), &BOB), 100);
+
+		OnUpdateLoan::<Runtime>::happened(&(BOB, BTC, -100, 100));
+		assert_eq!(RawShares::<Runtime>::get(PoolId::LoansIncentive(BTC), BOB), None);
+		assert_eq!(
+			RewardsModule::share_and_withdrawn_reward(PoolId::LoansIncentive(BTC), BOB).0,
+			0
+		);
+		assert_eq!(IncentivesModule::total_raw_shares(&PoolId::LoansIncentive(BTC)), 100);
+	});
+}
+
+#[test]
+fn claim_rewards_updates_boost() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		mock_voting_power(ALICE, 100);
+
+		OnUpdateLoan::<Runtime>::happened(&(ALICE, BTC, 100, 0));
+		OnUpdateLoan::<Runtime>::happened(&(BOB, BTC, 100, 0));
+		assert_eq!(
+			RewardsModule::share_and_withdrawn_reward(PoolId::LoansIncentive(BTC), ALICE).0,
+			100
+		);
+
+		// the voting power of ALICE decays, the boost follows at the claim
+		mock_voting_power(ALICE, 0);
+		mock_voting_power(BOB, 100);
+		assert_ok!(IncentivesModule::claim_rewards(
+			Origin::signed(ALICE),
+			PoolId::LoansIncentive(BTC)
+		));
+		System::assert_last_event(Event::IncentivesModule(crate::Event::WorkingShareUpdated(
+			ALICE,
+			PoolId::LoansIncentive(BTC),
+			100,
+			40,
+		)));
+		assert_eq!(
+			RewardsModule::share_and_withdrawn_reward(PoolId::LoansIncentive(BTC), ALICE).0,
+			40
+		);
+		assert_eq!(IncentivesModule::raw_share(&PoolId::LoansIncentive(BTC), &ALICE), 100);
+		assert_eq!(IncentivesModule::total_raw_shares(&PoolId::LoansIncentive(BTC)), 200);
+	});
+}
+
+#[test]
+fn migrate_to_v2_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		// the shares before boost
+		RewardsModule::add_share(&ALICE, &PoolId::LoansIncentive(BTC), 100);
+		RewardsModule::add_share(&BOB, &PoolId::LoansIncentive(BTC), 50);
+		RewardsModule::add_share(&ALICE, &PoolId::DexSaving(BTC_AUSD_LP), 100);
+		assert_eq!(IncentivesModule::total_raw_shares(&PoolId::LoansIncentive(BTC)), 0);
+		assert_eq!(IncentivesModule::storage_version(), Releases::V1_0_0);
+
+		IncentivesModule::on_runtime_upgrade();
+		assert_eq!(IncentivesModule::storage_version(), Releases::V2_0_0);
+		assert_eq!(IncentivesModule::total_raw_shares(&PoolId::LoansIncentive(BTC)), 150);
+		assert_eq!(
+			TotalRawShares::<Runtime>::contains_key(PoolId::DexSaving(BTC_AUSD_LP)),
+			false
+		);
+	});
+}
+
+#[test]
+fn update_emission_schedules_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let schedule = EmissionSchedule {
+			start: 10,
+			end: 110,
+			initial_reward_amount: 1000,
+			curve: DecayCurve::Linear,
+		};
+		assert_noop!(
+			IncentivesModule::update_emission_schedules(Origin::signed(ALICE), vec![]),
+			BadOrigin
+		);
+		assert_noop!(
+			IncentivesModule::update_emission_schedules(
+				Origin::signed(4),
+				vec![(PoolId::DexSaving(BTC_AUSD_LP), Some(schedule))],
+			),
+			Error::<Runtime>::InvalidPoolId
+		);
+		assert_noop!(
+			IncentivesModule::update_emission_schedules(
+				Origin::signed(4),
+				vec![(PoolId::DexIncentive(BTC), Some(schedule))],
+			),
+			Error::<Runtime>::InvalidCurrencyId
+		);
+		assert_noop!(
+			IncentivesModule::update_emission_schedules(
+				Origin::signed(4),
+				vec![(PoolId::HomaIncentive, Some(EmissionSchedule { end: 10, ..schedule }))],
+			),
+			Error::<Runtime>::InvalidSchedule
+		);
+		assert_noop!(
+			IncentivesModule::update_emission_schedules(
+				Origin::signed(4),
+				vec![(
+					PoolId::HomaIncentive,
+					Some(EmissionSchedule {
+						curve: DecayCurve::Exponential {
+							period: 0,
+							rate: Rate::saturating_from_rational(10, 100)
+						},
+						..schedule
+					})
+				)],
+			),
+			Error::<Runtime>::InvalidSchedule
+		);
+
+		assert_eq!(IncentivesModule::emission_schedules(PoolId::HomaIncentive), None);
+		assert_ok!(IncentivesModule::update_emission_schedules(
+			Origin::signed(4),
+			vec![(PoolId::HomaIncentive, Some(schedule))],
+		));
+		System::assert_last_event(Event::IncentivesModule(crate::Event::EmissionScheduleUpdated(
+			PoolId::HomaIncentive,
+			Some(schedule),
+		)));
+		assert_eq!(
+			IncentivesModule::emission_schedules(PoolId::HomaIncentive),
+			Some(schedule)
+		);
+
+		assert_ok!(IncentivesModule::update_emission_schedules(
+			Origin::signed(4),
+			vec![(PoolId::HomaIncentive, None)],
+		));
+		System::assert_last_event(Event::IncentivesModule(crate::Event::EmissionScheduleUpdated(
+			PoolId::HomaIncentive,
+			None,
+		)));
+		assert_eq!(IncentivesModule::emission_schedules(PoolId::HomaIncentive), None);
+	});
+}
+
+#[test]
+fn emission_schedule_decay_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(IncentivesModule::update_incentive_rewards(
+			Origin::signed(4),
+			vec![(PoolId::LoansIncentive(BTC), 100), (PoolId::LoansIncentive(DOT), 100),],
+		));
+		assert_ok!(IncentivesModule::update_emission_schedules(
+			Origin::signed(4),
+			vec![
+				(
+					PoolId::LoansIncentive(BTC),
+					Some(EmissionSchedule {
+						start: 10,
+						end: 110,
+						initial_reward_amount: 1000,
+						curve: DecayCurve::Linear,
+					})
+				),
+				(
+					PoolId::HomaIncentive,
+					Some(EmissionSchedule {
+						start: 10,
+						end: 110,
+						initial_reward_amount: 1000,
+						curve: DecayCurve::Exponential {
+							period: 20,
+							rate: Rate::saturating_from_rational(50, 100)
+						},
+					})
+				),
+			],
+		));
+
+		// the fixed reward amount is used if no emission schedule
+		assert_eq!(
+			IncentivesModule::period_incentive_reward_amount(&PoolId::LoansIncentive(DOT), 10),
+			100
+		);
+
+		assert_eq!(
+			IncentivesModule::period_incentive_reward_amount(&PoolId::LoansIncentive(BTC), 0),
+			0
+		);
+		assert_eq!(
+			IncentivesModule::period_incentive_reward_amount(&PoolId::LoansIncentive(BTC), 10),
+			1000
+		);
+		assert_eq!(
+			IncentivesModule::period_incentive_reward_amount(&PoolId::LoansIncentive(BTC), 60),
+			500
+		);
+		assert_eq!(
+			IncentivesModule::period_incentive_reward_amount(&PoolId::LoansIncentive(BTC), 110),
+			0
+		);
+
+		assert_eq!(
+			IncentivesModule::period_incentive_reward_amount(&PoolId::HomaIncentive, 20),
+			1000
+		);
+		assert_eq!(
+			IncentivesModule::period_incentive_reward_amount(&PoolId::HomaIncentive, 30),
+			500
+		);
+		assert_eq!(
+			IncentivesModule::period_incentive_reward_amount(&PoolId::HomaIncentive, 70),
+			125
+		);
+		assert_eq!(
+			IncentivesModule::period_incentive_reward_amount(&PoolId::HomaIncentive, 120),
+			0
+		);
+
+		RewardsModule::add_share(&ALICE, &PoolId::LoansIncentive(BTC), 1);
+		IncentivesModule::on_initialize(60);
+		assert_eq!(TokensModule::free_balance(ACA, &VAULT), 500);
+		assert_eq!(RewardsModule::pools(PoolId::LoansIncentive(BTC)).total_rewards, 500);
+	});
+}
+
+#[test]
+fn set_deposit_limits_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let limits = DepositLimits {
+			max_total_shares: Some(1000),
+			max_account_shares: Some(600),
+			whitelist_only: true,
+		};
+		assert_noop!(
+			IncentivesModule::set_deposit_limits(
+				Origin::signed(ALICE),
+				PoolId::DexIncentive(BTC_AUSD_LP),
+				Some(limits)
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			IncentivesModule::set_deposit_limits(Origin::signed(4), PoolId::HomaIncentive, Some(limits)),
+			Error::<Runtime>::InvalidPoolId
+		);
+		assert_noop!(
+			IncentivesModule::set_deposit_limits(Origin::signed(4), PoolId::DexIncentive(BTC), Some(limits)),
+			Error::<Runtime>::InvalidCurrencyId
+		);
+
+		assert_ok!(IncentivesModule::set_deposit_limits(
+			Origin::signed(4),
+			PoolId::DexIncentive(BTC_AUSD_LP),
+			Some(limits)
+		));
+		System::assert_last_event(Event::IncentivesModule(crate::Event::DepositLimitsUpdated(
+			PoolId::DexIncentive(BTC_AUSD_LP),
+			Some(limits),
+		)));
+		assert_eq!(
+			IncentivesModule::pool_deposit_limits(PoolId::DexIncentive(BTC_AUSD_LP)),
+			Some(limits)
+		);
+
+		assert_ok!(IncentivesModule::set_deposit_limits(
+			Origin::signed(4),
+			PoolId::DexIncentive(BTC_AUSD_LP),
+			None
+		));
+		System::assert_last_event(Event::IncentivesModule(crate::Event::DepositLimitsUpdated(
+			PoolId::DexIncentive(BTC_AUSD_LP),
+			None,
+		)));
+		assert_eq!(
+			IncentivesModule::pool_deposit_limits(PoolId::DexIncentive(BTC_AUSD_LP)),
+			None
+		);
+	});
+}
+
+#[test]
+fn deposit_dex_share_with_limits_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TokensModule::deposit(BTC_AUSD_LP, &ALICE, 10000));
+		assert_ok!(TokensModule::deposit(BTC_AUSD_LP, &BOB, 10000));
+		assert_ok!(IncentivesModule::set_deposit_limits(
+			Origin::signed(4),
+			PoolId::DexIncentive(BTC_AUSD_LP),
+			Some(DepositLimits {
+				max_total_shares: Some(1000),
+				max_account_shares: Some(600),
+				whitelist_only: true,
+			})
+		));
+
+		assert_noop!(
+			IncentivesModule::deposit_dex_share(Origin::signed(ALICE), BTC_AUSD_LP, 100),
+			Error::<Runtime>::NotWhitelisted
+		);
+
+		assert_noop!(
+			IncentivesModule::update_deposit_whitelist(
+				Origin::signed(ALICE),
+				PoolId::DexIncentive(BTC_AUSD_LP),
+				vec![]
+			),
+			BadOrigin
+		);
+		assert_ok!(IncentivesModule::update_deposit_whitelist(
+			Origin::signed(4),
+			PoolId::DexIncentive(BTC_AUSD_LP),
+			vec![(ALICE, true), (BOB, true)]
+		));
+		System::assert_last_event(Event::IncentivesModule(crate::Event::DepositWhitelistUpdated(
+			PoolId::DexIncentive(BTC_AUSD_LP),
+			BOB,
+			true,
+		)));
+		assert!(IncentivesModule::deposit_whitelist(
+			PoolId::DexIncentive(BTC_AUSD_LP),
+			ALICE
+		));
+
+		assert_noop!(
+			IncentivesModule::deposit_dex_share(Origin::signed(ALICE), BTC_AUSD_LP, 601),
+			Error::<Runtime>::ExceedAccountCap
+		);
+		assert_ok!(IncentivesModule::deposit_dex_share(
+			Origin::signed(ALICE),
+			BTC_AUSD_LP,
+			600
+		));
+		assert_noop!(
+			IncentivesModule::deposit_dex_share(Origin::signed(BOB), BTC_AUSD_LP, 401),
+			Error::<Runtime>::ExceedPoolCap
+		);
+		assert_ok!(IncentivesModule::deposit_dex_share(
+			Origin::signed(BOB),
+			BTC_AUSD_LP,
+			400
+		));
+
+		assert_ok!(IncentivesModule::withdraw_dex_share(
+			Origin::signed(ALICE),
+			BTC_AUSD_LP,
+			100
+		));
+		assert_ok!(IncentivesModule::update_deposit_whitelist(
+			Origin::signed(4),
+			PoolId::DexIncentive(BTC_AUSD_LP),
+			vec![(ALICE, false)]
+		));
+		assert!(!IncentivesModule::deposit_whitelist(
+			PoolId::DexIncentive(BTC_AUSD_LP),
+			ALICE
+		));
+		assert_noop!(
+			IncentivesModule::deposit_dex_share(Origin::signed(ALICE), BTC_AUSD_LP, 100),
+			Error::<Runtime>::NotWhitelisted
+		);
+		assert_ok!(IncentivesModule::deposit_dex_share(
+			Origin::signed(BOB),
+			BTC_AUSD_LP,
+			100
+		));
+		assert_eq!(
+			RewardsModule::pools(PoolId::DexIncentive(BTC_AUSD_LP)).total_shares,
+			1000
+		);
+	});
+}
+
+#[test]
+fn claim_all_rewards_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TokensModule::deposit(ACA, &VAULT, 10000));
+		assert_ok!(IncentivesModule::update_rewards_vesting(
+			Origin::signed(4),
+			vec![(
+				PoolId::LoansIncentive(DOT),
+				Some(RewardsVestingParams {
+					period: 100,
+					early_exit_penalty_rate: Rate::zero(),
+				})
+			)],
+		));
+
+		for pool_id in vec![
+			PoolId::LoansIncentive(BTC),
+			PoolId::LoansIncentive(DOT),
+			PoolId::DexIncentive(BTC_AUSD_LP),
+			PoolId::DexIncentive(DOT_AUSD_LP),
+			PoolId::HomaIncentive,
+		] {
+			RewardsModule::add_share(&ALICE, &pool_id, 1);
+			RewardsModule::accumulate_reward(&pool_id, 100);
+		}
+		assert_eq!(TokensModule::free_balance(ACA, &ALICE), 0);
+
+		// claim in at most MaxClaimAllPools pools
+		assert_noop!(
+			IncentivesModule::claim_all_rewards(
+				Origin::signed(ALICE),
+				vec![
+					PoolId::LoansIncentive(BTC),
+					PoolId::DexIncentive(BTC_AUSD_LP),
+					PoolId::DexIncentive(DOT_AUSD_LP),
+					PoolId::HomaIncentive,
+				]
+			),
+			Error::<Runtime>::TooManyPools
+		);
+
+		// only claims for the caller
+		assert_ok!(IncentivesModule::claim_all_rewards(
+			Origin::signed(BOB),
+			vec![PoolId::LoansIncentive(BTC), PoolId::HomaIncentive]
+		));
+		assert_eq!(TokensModule::free_balance(ACA, &ALICE), 0);
+
+		assert_ok!(IncentivesModule::claim_all_rewards(
+			Origin::signed(ALICE),
+			vec![
+				PoolId::LoansIncentive(BTC),
+				PoolId::DexIncentive(BTC_AUSD_LP),
+				PoolId::HomaIncentive,
+			]
+		));
+		assert_eq!(TokensModule::free_balance(ACA, &ALICE), 300);
+
+		// the pools without pending rewards are skipped
+		assert_ok!(IncentivesModule::claim_all_rewards(
+			Origin::signed(ALICE),
+			vec![PoolId::LoansIncentive(BTC), PoolId::LoansIncentive(DOT)]
+		));
+		assert_eq!(TokensModule::free_balance(ACA, &ALICE), 300);
+		assert_eq!(
+			RewardsModule::pools(PoolId::LoansIncentive(DOT)).total_withdrawn_rewards,
+			100
+		);
+		assert_eq!(
+			IncentivesModule::escrowed_rewards(ALICE, (PoolId::LoansIncentive(DOT), ACA)).map(|escrow| escrow.total),
+			Some(100)
+		);
+	});
+}
+
+#[test]
+fn compound_rewards_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TokensModule::deposit(ACA, &VAULT, 10000));
+		assert_ok!(TokensModule::deposit(AUSD, &VAULT, 10000));
+		assert_ok!(TokensModule::deposit(BTC_AUSD_LP, &ALICE, 100));
+		assert_ok!(IncentivesModule::deposit_dex_share(
+			Origin::signed(ALICE),
+			BTC_AUSD_LP,
+			100
+		));
+
+		assert_noop!(
+			IncentivesModule::compound_rewards(Origin::signed(ALICE), PoolId::LoansIncentive(BTC), 0, 0),
+			Error::<Runtime>::InvalidPoolId
+		);
+		assert_noop!(
+			IncentivesModule::compound_rewards(Origin::signed(ALICE), PoolId::DexIncentive(BTC_AUSD_LP), 0, 0),
+			Error::<Runtime>::NoRewardsToCompound
+		);
+
+		// the reward currency is not in the liquidity pool
+		RewardsModule::accumulate_reward(&PoolId::DexIncentive(BTC_AUSD_LP), 100);
+		assert_noop!(
+			IncentivesModule::compound_rewards(Origin::signed(ALICE), PoolId::DexIncentive(BTC_AUSD_LP), 51, 0),
+			DispatchError::Other("SlippageExceeded")
+		);
+		assert_noop!(
+			IncentivesModule::compound_rewards(Origin::signed(ALICE), PoolId::DexIncentive(BTC_AUSD_LP), 0, 101),
+			DispatchError::Other("SlippageExceeded")
+		);
+		assert_ok!(IncentivesModule::compound_rewards(
+			Origin::signed(ALICE),
+			PoolId::DexIncentive(BTC_AUSD_LP),
+			50,
+			100
+		));
+		System::assert_last_event(Event::IncentivesModule(crate::Event::RewardsCompounded(
+			ALICE,
+			PoolId::DexIncentive(BTC_AUSD_LP),
+			ACA,
+			100,
+		)));
+		assert_eq!(TokensModule::free_balance(ACA, &ALICE), 0);
+		assert_eq!(
+			IncentivesModule::raw_share(&PoolId::DexIncentive(BTC_AUSD_LP), &ALICE),
+			200
+		);
+		assert_eq!(
+			RewardsModule::share_and_withdrawn_reward(PoolId::DexSaving(BTC_AUSD_LP), ALICE).0,
+			200
+		);
+
+		// the reward currency is in the liquidity pool
+		RewardsModule::accumulate_reward(&PoolId::DexSaving(BTC_AUSD_LP), 100);
+		assert_ok!(IncentivesModule::compound_rewards(
+			Origin::signed(ALICE),
+			PoolId::DexSaving(BTC_AUSD_LP),
+			0,
+			0
+		));
+		System::assert_last_event(Event::IncentivesModule(crate::Event::RewardsCompounded(
+			ALICE,
+			PoolId::DexSaving(BTC_AUSD_LP),
+			AUSD,
+			100,
+		)));
+		assert_eq!(TokensModule::free_balance(AUSD, &ALICE), 0);
+		assert_eq!(TokensModule::free_balance(BTC, &ALICE), 0);
+		assert_eq!(
+			IncentivesModule::raw_share(&PoolId::DexIncentive(BTC_AUSD_LP), &ALICE),
+			300
+		);
+		assert_eq!(
+			TokensModule::free_balance(BTC_AUSD_LP, &IncentivesModule::account_id()),
+			300
+		);
+	});
+}
+
+#[test]
+fn pending_rewards_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(IncentivesModule::update_payout_deduction_rates(
+			Origin::signed(4),
+			vec![(PoolId::LoansIncentive(BTC), Rate::saturating_from_rational(10, 100))],
+		));
+		RewardsModule::add_share(&ALICE, &PoolId::LoansIncentive(BTC), 1);
+		RewardsModule::add_share(&BOB, &PoolId::LoansIncentive(BTC), 1);
+		RewardsModule::add_share(&ALICE, &PoolId::DexSaving(BTC_AUSD_LP), 1);
+		RewardsModule::accumulate_reward(&PoolId::LoansIncentive(BTC), 1000);
+		RewardsModule::accumulate_reward(&PoolId::DexSaving(BTC_AUSD_LP), 200);
+		ExtraRewardPools::<Runtime>::insert(
+			PoolId::LoansIncentive(BTC),
+			LDOT,
+			ExtraRewardInfo {
+				total_rewards: 100,
+				total_withdrawn_rewards: 0,
+			},
+		);
+
+		let mut pending_rewards = IncentivesModule::pending_rewards(&ALICE);
+		pending_rewards.sort_by_key(|(_, currency_id, _)| *currency_id);
+		assert_eq!(
+			pending_rewards,
+			vec![
+				(PoolId::LoansIncentive(BTC), ACA, 450),
+				(PoolId::DexSaving(BTC_AUSD_LP), AUSD, 200),
+				(PoolId::LoansIncentive(BTC), LDOT, 45),
+			]
+		);
+		assert_eq!(IncentivesModule::pending_rewards(&VALIDATOR), vec![]);
+	});
+}
+
+#[test]
+fn incentives_rewards_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(TokensModule::deposit(ACA, &VAULT, 1000));
+		assert_ok!(TokensModule::deposit(LDOT, &VAULT, 100));
+		RewardsModule::add_share(&ALICE, &PoolId::LoansIncentive(BTC), 1);
+		RewardsModule::add_share(&BOB, &PoolId::LoansIncentive(BTC), 1);
+		RewardsModule::accumulate_reward(&PoolId::LoansIncentive(BTC), 1000);
+		ExtraRewardPools::<Runtime>::insert(
+			PoolId::LoansIncentive(BTC),
+			LDOT,
+			ExtraRewardInfo {
+				total_rewards: 100,
+				total_withdrawn_rewards: 0,
+			},
+		);
+
+		assert_eq!(
+			<IncentivesModule as IncentivesRewards<_, _, _, _>>::pending_rewards(&ALICE, &PoolId::LoansIncentive(BTC)),
+			vec![(ACA, 500), (LDOT, 50)]
+		);
+		assert_eq!(
+			<IncentivesModule as IncentivesRewards<_, _, _, _>>::pending_rewards(&ALICE, &PoolId::HomaIncentive),
+			vec![]
+		);
+
+		<IncentivesModule as IncentivesRewards<_, _, _, _>>::claim_rewards(&ALICE, &PoolId::LoansIncentive(BTC));
+		assert_eq!(TokensModule::free_balance(ACA, &ALICE), 500);
+		assert_eq!(TokensModule::free_balance(LDOT, &ALICE), 50);
+		assert_eq!(
+			<IncentivesModule as IncentivesRewards<_, _, _, _>>::pending_rewards(&ALICE, &PoolId::LoansIncentive(BTC)),
+			vec![]
+		);
+		assert_eq!(
+			<IncentivesModule as IncentivesRewards<_, _, _, _>>::pending_rewards(&BOB, &PoolId::LoansIncentive(BTC)),
+			vec![(ACA, 500), (LDOT, 50)]
+		);
+	});
+}
+
+#[test]
+fn apr_estimates_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(IncentivesModule::update_incentive_rewards(
+			Origin::signed(4),
+			vec![(PoolId::LoansIncentive(BTC), 100), (PoolId::HomaIncentive, 100)],
+		));
+		assert_ok!(IncentivesModule::update_extra_incentive_rewards(
+			Origin::signed(4),
+			vec![(PoolId::LoansIncentive(BTC), LDOT, 50)],
+		));
+		RewardsModule::add_share(&ALICE, &PoolId::LoansIncentive(BTC), 10000);
+		RewardsModule::add_share(&ALICE, &PoolId::HomaIncentive, 10000);
+
+		assert_eq!(
+			IncentivesModule::period_reward_amounts(&PoolId::LoansIncentive(BTC), 10),
+			vec![(ACA, 100), (LDOT, 50)]
+		);
+
+		let reward_price = |currency_id| match currency_id {
+			ACA => Some(Price::saturating_from_integer(2)),
+			LDOT => Some(Price::saturating_from_integer(4)),
+			_ => None,
+		};
+		let share_price = |pool_id: &PoolId<AccountId>| match pool_id {
+			PoolId::LoansIncentive(_) => Some(Price::saturating_from_integer(1)),
+			_ => None,
+		};
+		// (100 * 2 + 50 * 4) * 10 / (10000 * 1)
+		assert_eq!(
+			IncentivesModule::apr_estimates(10, 10, reward_price, share_price),
+			vec![(PoolId::LoansIncentive(BTC), Rate::saturating_from_rational(40, 100))]
+		);
+	});
+}
+
+#[test]
+fn update_deduction_exemptions_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			IncentivesModule::update_deduction_exemptions(
+				Origin::signed(ALICE),
+				PoolId::LoansIncentive(BTC),
+				vec![(ALICE, true)]
+			),
+			BadOrigin
+		);
+
+		assert_ok!(IncentivesModule::update_deduction_exemptions(
+			Origin::signed(4),
+			PoolId::LoansIncentive(BTC),
+			vec![(ALICE, true), (BOB, true)]
+		));
+		System::assert_last_event(Event::IncentivesModule(crate::Event::DeductionExemptionUpdated(
+			PoolId::LoansIncentive(BTC),
+			BOB,
+			true,
+		)));
+		assert!(IncentivesModule::deduction_exemptions(
+			PoolId::LoansIncentive(BTC),
+			ALICE
+		));
+		assert!(IncentivesModule::deduction_exemptions(PoolId::LoansIncentive(BTC), BOB));
+
+		assert_ok!(IncentivesModule::update_deduction_exemptions(
+			Origin::signed(4),
+			PoolId::LoansIncentive(BTC),
+			vec![(BOB, false)]
+		));
+		System::assert_last_event(Event::IncentivesModule(crate::Event::DeductionExemptionUpdated(
+			PoolId::LoansIncentive(BTC),
+			BOB,
+			false,
+		)));
+		assert!(IncentivesModule::deduction_exemptions(
+			PoolId::LoansIncentive(BTC),
+			ALICE
+		));
+		assert!(!IncentivesModule::deduction_exemptions(
+			PoolId::LoansIncentive(BTC),
+			BOB
+		));
+	});
+}
+
+#[test]
+fn update_loyalty_tiers_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let tier = |min_duration: BlockNumber, rate: u32| LoyaltyTier {
+			min_duration,
+			deduction_rate: Rate::saturating_from_rational(rate, 100),
+		};
+
+		assert_noop!(
+			IncentivesModule::update_loyalty_tiers(Origin::signed(ALICE), vec![]),
+			BadOrigin
+		);
+		assert_noop!(
+			IncentivesModule::update_loyalty_tiers(
+				Origin::signed(4),
+				vec![(
+					PoolId::LoansIncentive(BTC),
+					vec![tier(10, 30), tier(20, 20), tier(30, 10)]
+				)]
+			),
+			Error::<Runtime>::InvalidLoyaltyTiers
+		);
+		assert_noop!(
+			IncentivesModule::update_loyalty_tiers(
+				Origin::signed(4),
+				vec![(PoolId::LoansIncentive(BTC), vec![tier(10, 101)])]
+			),
+			Error::<Runtime>::InvalidRate
+		);
+		assert_noop!(
+			IncentivesModule::update_loyalty_tiers(
+				Origin::signed(4),
+				vec![(PoolId::LoansIncentive(BTC), vec![tier(20, 30), tier(10, 20)])]
+			),
+			Error::<Runtime>::InvalidLoyaltyTiers
+		);
+		assert_noop!(
+			IncentivesModule::update_loyalty_tiers(
+				Origin::signed(4),
+				vec![(PoolId::LoansIncentive(BTC), vec![tier(10, 20), tier(20, 30)])]
+			),
+			Error::<Runtime>::InvalidLoyaltyTiers
+		);
+
+		assert_ok!(IncentivesModule::update_loyalty_tiers(
+			Origin::signed(4),
+			vec![(PoolId::LoansIncentive(BTC), vec![tier(10, 30), tier(20, 20)])]
+		));
+		System::assert_last_event(Event::IncentivesModule(crate::Event::LoyaltyTiersUpdated(
+			PoolId::LoansIncentive(BTC),
+			vec![tier(10, 30), tier(20, 20)],
+		)));
+		assert_eq!(
+			IncentivesModule::loyalty_tiers(PoolId::LoansIncentive(BTC)),
+			vec![tier(10, 30), tier(20, 20)]
+		);
+
+		assert_ok!(IncentivesModule::update_loyalty_tiers(
+			Origin::signed(4),
+			vec![(PoolId::LoansIncentive(BTC), vec![])]
+		));
+		assert_eq!(IncentivesModule::loyalty_tiers(PoolId::LoansIncentive(BTC)), vec![]);
+		assert!(!LoyaltyTiers::<Runtime>::contains_key(PoolId::LoansIncentive(BTC)));
+	});
+}
+
+#[test]
+fn deduction_rate_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let pool_id = PoolId::HomaValidatorAllowance(VALIDATOR);
+		assert_ok!(TokensModule::deposit(LDOT, &VAULT, 10000));
+		assert_ok!(IncentivesModule::update_payout_deduction_rates(
+			Origin::signed(4),
+			vec![(pool_id.clone(), Rate::saturating_from_rational(50, 100))]
+		));
+		assert_ok!(IncentivesModule::update_loyalty_tiers(
+			Origin::signed(4),
+			vec![(
+				pool_id.clone(),
+				vec![
+					LoyaltyTier {
+						min_duration: 10,
+						deduction_rate: Rate::saturating_from_rational(20, 100),
+					},
+					LoyaltyTier {
+						min_duration: 20,
+						deduction_rate: Rate::zero(),
+					},
+				]
+			)]
+		));
+
+		OnIncreaseGuarantee::<Runtime>::happened(&(ALICE, VALIDATOR, 100));
+		assert_eq!(IncentivesModule::stake_since(&pool_id, ALICE), Some(1));
+		assert_eq!(
+			IncentivesModule::deduction_rate(&pool_id, &ALICE),
+			Rate::saturating_from_rational(50, 100)
+		);
+		assert_eq!(
+			IncentivesModule::deduction_rate(&pool_id, &BOB),
+			Rate::saturating_from_rational(50, 100)
+		);
+
+		System::set_block_number(11);
+		assert_eq!(
+			IncentivesModule::deduction_rate(&pool_id, &ALICE),
+			Rate::saturating_from_rational(20, 100)
+		);
+		IncentivesModule::payout(&ALICE, &pool_id, 1000);
+		System::assert_last_event(Event::IncentivesModule(crate::Event::PayoutRewards(
+			ALICE,
+			pool_id.clone(),
+			LDOT,
+			800,
+			200,
+		)));
+
+		// increasing the share weights the staking duration by the amount
+		OnIncreaseGuarantee::<Runtime>::happened(&(ALICE, VALIDATOR, 100));
+		assert_eq!(IncentivesModule::stake_since(&pool_id, ALICE), Some(6));
+		assert_eq!(
+			IncentivesModule::deduction_rate(&pool_id, &ALICE),
+			Rate::saturating_from_rational(50, 100)
+		);
+
+		System::set_block_number(21);
+		assert_eq!(
+			IncentivesModule::deduction_rate(&pool_id, &ALICE),
+			Rate::saturating_from_rational(20, 100)
+		);
+		System::set_block_number(26);
+		assert_eq!(IncentivesModule::deduction_rate(&pool_id, &ALICE), Rate::zero());
+
+		// decreasing the share resets the staking duration
+		OnDecreaseGuarantee::<Runtime>::happened(&(ALICE, VALIDATOR, 10));
+		assert_eq!(IncentivesModule::stake_since(&pool_id, ALICE), Some(26));
+		assert_eq!(
+			IncentivesModule::deduction_rate(&pool_id, &ALICE),
+			Rate::saturating_from_rational(50, 100)
+		);
+
+		// the loyalty tier never exceeds the deduction rate of the pool
+		assert_ok!(IncentivesModule::update_payout_deduction_rates(
+			Origin::signed(4),
+			vec![(pool_id.clone(), Rate::saturating_from_rational(10, 100))]
+		));
+		System::set_block_number(36);
+		assert_eq!(
+			IncentivesModule::deduction_rate(&pool_id, &ALICE),
+			Rate::saturating_from_rational(10, 100)
+		);
+
+		assert_ok!(IncentivesModule::update_deduction_exemptions(
+			Origin::signed(4),
+			pool_id.clone(),
+			vec![(ALICE, true)]
+		));
+		assert_eq!(IncentivesModule::deduction_rate(&pool_id, &ALICE), Rate::zero());
+		IncentivesModule::payout(&ALICE, &pool_id, 1000);
+		System::assert_last_event(Event::IncentivesModule(crate::Event::PayoutRewards(
+			ALICE,
+			pool_id.clone(),
+			LDOT,
+			1000,
+			0,
+		)));
+
+		OnDecreaseGuarantee::<Runtime>::happened(&(ALICE, VALIDATOR, 190));
+		assert_eq!(IncentivesModule::stake_since(&pool_id, ALICE), None);
+	});
+}
+
+#[test]
+fn boosted_pool_stake_since_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let pool_id = PoolId::LoansIncentive(BTC);
+		mock_voting_power(BOB, 100);
+
+		OnUpdateLoan::<Runtime>::happened(&(ALICE, BTC, 100, 0));
+		assert_eq!(IncentivesModule::stake_since(&pool_id, ALICE), Some(1));
+
+		// the working share changed by the boost does not reset the staking duration
+		System::set_block_number(11);
+		mock_voting_power(ALICE, 100);
+		assert_ok!(IncentivesModule::update_boost(
+			Origin::signed(BOB),
+			ALICE,
+			pool_id.clone()
+		));
+		assert_eq!(IncentivesModule::stake_since(&pool_id, ALICE), Some(1));
+
+		OnUpdateLoan::<Runtime>::happened(&(ALICE, BTC, 100, 100));
+		assert_eq!(IncentivesModule::stake_since(&pool_id, ALICE), Some(6));
+
+		OnUpdateLoan::<Runtime>::happened(&(ALICE, BTC, -50, 200));
+		assert_eq!(IncentivesModule::stake_since(&pool_id, ALICE), Some(11));
+
+		OnUpdateLoan::<Runtime>::happened(&(ALICE, BTC, -150, 150));
+		assert_eq!(IncentivesModule::stake_since(&pool_id, ALICE), None);
+	});
+}
+
+#[test]
+fn top_up_rewards_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let pool_id = PoolId::LoansIncentive(BTC);
+		assert_ok!(TokensModule::deposit(ACA, &BOB, 10000));
+		assert_ok!(TokensModule::deposit(LDOT, &BOB, 10000));
+
+		assert_noop!(
+			IncentivesModule::top_up_rewards(Origin::signed(BOB), PoolId::DexSaving(BTC_AUSD_LP), AUSD, 100, 2),
+			Error::<Runtime>::InvalidPoolId
+		);
+		assert_noop!(
+			IncentivesModule::top_up_rewards(Origin::signed(BOB), pool_id.clone(), ACA, 100, 2),
+			Error::<Runtime>::InvalidPoolId
+		);
+
+		RewardsModule::add_share(&ALICE, &pool_id, 1);
+		assert_noop!(
+			IncentivesModule::top_up_rewards(Origin::signed(BOB), pool_id.clone(), ACA, 0, 2),
+			Error::<Runtime>::InvalidTopUp
+		);
+		assert_noop!(
+			IncentivesModule::top_up_rewards(Origin::signed(BOB), pool_id.clone(), ACA, 100, 0),
+			Error::<Runtime>::InvalidTopUp
+		);
+		assert_noop!(
+			IncentivesModule::top_up_rewards(Origin::signed(BOB), pool_id.clone(), ACA, 100, 2),
+			Error::<Runtime>::TopUpCurrencyNotAllowed
+		);
+
+		assert_noop!(
+			IncentivesModule::update_top_up_currencies(Origin::signed(BOB), vec![(ACA, Some(10))]),
+			BadOrigin
+		);
+		assert_ok!(IncentivesModule::update_top_up_currencies(
+			Origin::signed(4),
+			vec![(ACA, Some(10)), (LDOT, Some(50)), (AUSD, None)]
+		));
+		System::assert_has_event(Event::IncentivesModule(crate::Event::TopUpCurrencyUpdated(
+			ACA,
+			Some(10),
+		)));
+		assert_eq!(IncentivesModule::top_up_min_amounts(ACA), Some(10));
+		assert_eq!(IncentivesModule::top_up_min_amounts(AUSD), None);
+		assert_noop!(
+			IncentivesModule::top_up_rewards(Origin::signed(BOB), pool_id.clone(), LDOT, 49, 2),
+			Error::<Runtime>::BelowMinTopUpAmount
+		);
+		assert_noop!(
+			IncentivesModule::top_up_rewards(Origin::signed(BOB), pool_id.clone(), ACA, Balance::max_value(), 2),
+			ArithmeticError::Overflow
+		);
+		assert_noop!(
+			IncentivesModule::top_up_rewards(Origin::signed(BOB), pool_id.clone(), ACA, 10000, 2),
+			orml_tokens::Error::<Runtime>::BalanceTooLow
+		);
+
+		assert_ok!(IncentivesModule::top_up_rewards(
+			Origin::signed(BOB),
+			pool_id.clone(),
+			ACA,
+			100,
+			2
+		));
+		System::assert_last_event(Event::IncentivesModule(crate::Event::RewardsToppedUp(
+			BOB,
+			pool_id.clone(),
+			0,
+			ACA,
+			100,
+			2,
+		)));
+		assert_eq!(
+			IncentivesModule::reward_top_ups(&pool_id, 0),
+			Some(RewardTopUp {
+				funder: BOB,
+				currency_id: ACA,
+				amount_per_period: 100,
+				remaining_periods: 2,
+			})
+		);
+		assert_eq!(TokensModule::free_balance(ACA, &BOB), 9800);
+		assert_eq!(TokensModule::free_balance(ACA, &VAULT), 200);
+
+		assert_ok!(IncentivesModule::top_up_rewards(
+			Origin::signed(BOB),
+			pool_id.clone(),
+			LDOT,
+			50,
+			3
+		));
+		assert_eq!(TokensModule::free_balance(LDOT, &BOB), 9850);
+		assert_eq!(TokensModule::free_balance(LDOT, &VAULT), 150);
+		assert_eq!(IncentivesModule::next_top_up_id(), 2);
+		assert_noop!(
+			IncentivesModule::top_up_rewards(Origin::signed(BOB), pool_id.clone(), ACA, 100, 2),
+			Error::<Runtime>::TooManyTopUps
+		);
+
+		let mut period_reward_amounts = IncentivesModule::period_reward_amounts(&pool_id, 10);
+		period_reward_amounts.sort();
+		assert_eq!(period_reward_amounts, vec![(ACA, 0), (ACA, 100), (LDOT, 50)]);
+
+		IncentivesModule::on_initialize(10);
+		assert_eq!(RewardsModule::pools(&pool_id).total_rewards, 100);
+		assert_eq!(IncentivesModule::extra_reward_pools(&pool_id, LDOT).total_rewards, 50);
+		assert_eq!(
+			IncentivesModule::reward_top_ups(&pool_id, 0).unwrap().remaining_periods,
+			1
+		);
+		assert_eq!(
+			IncentivesModule::reward_top_ups(&pool_id, 1).unwrap().remaining_periods,
+			2
+		);
+
+		IncentivesModule::on_initialize(20);
+		assert_eq!(RewardsModule::pools(&pool_id).total_rewards, 200);
+		assert_eq!(IncentivesModule::extra_reward_pools(&pool_id, LDOT).total_rewards, 100);
+		assert_eq!(IncentivesModule::reward_top_ups(&pool_id, 0), None);
+
+		IncentivesModule::on_initialize(30);
+		IncentivesModule::on_initialize(40);
+		assert_eq!(RewardsModule::pools(&pool_id).total_rewards, 200);
+		assert_eq!(IncentivesModule::extra_reward_pools(&pool_id, LDOT).total_rewards, 150);
+		assert_eq!(IncentivesModule::reward_top_ups(&pool_id, 1), None);
+		assert_eq!(TokensModule::free_balance(ACA, &VAULT), 200);
+		assert_eq!(TokensModule::free_balance(LDOT, &VAULT), 150);
+	});
+}
+
+#[test]
+fn cancel_top_up_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let pool_id = PoolId::LoansIncentive(BTC);
+		assert_ok!(TokensModule::deposit(LDOT, &BOB, 10000));
+		RewardsModule::add_share(&ALICE, &pool_id, 1);
+		assert_ok!(IncentivesModule::update_top_up_currencies(
+			Origin::signed(4),
+			vec![(LDOT, Some(10))]
+		));
+		assert_ok!(IncentivesModule::top_up_rewards(
+			Origin::signed(BOB),
+			pool_id.clone(),
+			LDOT,
+			50,
+			3
+		));
+		IncentivesModule::on_initialize(10);
+		assert_eq!(IncentivesModule::extra_reward_pools(&pool_id, LDOT).total_rewards, 50);
+
+		assert_noop!(
+			IncentivesModule::cancel_top_up(Origin::signed(BOB), pool_id.clone(), 0),
+			BadOrigin
+		);
+		assert_noop!(
+			IncentivesModule::cancel_top_up(Origin::signed(4), pool_id.clone(), 1),
+			Error::<Runtime>::TopUpNotFound
+		);
+
+		assert_ok!(IncentivesModule::cancel_top_up(Origin::signed(4), pool_id.clone(), 0));
+		System::assert_last_event(Event::IncentivesModule(crate::Event::TopUpCancelled(
+			pool_id.clone(),
+			0,
+			100,
+		)));
+		assert_eq!(IncentivesModule::reward_top_ups(&pool_id, 0), None);
+		assert_eq!(TokensModule::free_balance(LDOT, &BOB), 9950);
+		assert_eq!(TokensModule::free_balance(LDOT, &VAULT), 50);
+
+		IncentivesModule::on_initialize(20);
+		assert_eq!(IncentivesModule::extra_reward_pools(&pool_id, LDOT).total_rewards, 50);
+	});
+}
+
+#[test]
+fn update_loans_risk_weights_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			IncentivesModule::update_loans_risk_weights(Origin::signed(ALICE), vec![]),
+			BadOrigin
+		);
+
+		assert_noop!(
+			IncentivesModule::update_loans_risk_weights(
+				Origin::signed(4),
+				vec![(BTC, Some(Rate::saturating_from_rational(150, 100)))]
+			),
+			Error::<Runtime>::InvalidRate
+		);
+
+		assert_ok!(IncentivesModule::update_loans_risk_weights(
+			Origin::signed(4),
+			vec![
+				(BTC, Some(Rate::saturating_from_rational(50, 100))),
+				(DOT, Some(Rate::zero()))
+			]
+		));
+		System::assert_last_event(Event::IncentivesModule(crate::Event::LoansRiskWeightUpdated(
+			DOT,
+			Some(Rate::zero()),
+		)));
+		assert_eq!(
+			IncentivesModule::loans_risk_weights(BTC),
+			Some(Rate::saturating_from_rational(50, 100))
+		);
+		assert_eq!(IncentivesModule::loans_risk_weights(DOT), Some(Rate::zero()));
+
+		// at most MaxLoansRiskWeights collateral types
+		assert_noop!(
+			IncentivesModule::update_loans_risk_weights(Origin::signed(4), vec![(LDOT, Some(Rate::one()))]),
+			Error::<Runtime>::TooManyRiskWeights
+		);
+
+		assert_ok!(IncentivesModule::update_loans_risk_weights(
+			Origin::signed(4),
+			vec![(DOT, None)]
+		));
+		System::assert_last_event(Event::IncentivesModule(crate::Event::LoansRiskWeightUpdated(DOT, None)));
+		assert_eq!(IncentivesModule::loans_risk_weights(DOT), None);
+	});
+}
+
+#[test]
+fn loans_risk_weights_scale_accumulation() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(IncentivesModule::update_incentive_rewards(
+			Origin::signed(4),
+			vec![
+				(PoolId::LoansIncentive(BTC), 1000),
+				(PoolId::LoansIncentive(DOT), 1000),
+				(PoolId::DexIncentive(BTC_AUSD_LP), 100),
+			],
+		));
+		assert_ok!(IncentivesModule::update_loans_risk_weights(
+			Origin::signed(4),
+			vec![
+				(BTC, Some(Rate::saturating_from_rational(80, 100))),
+				(DOT, Some(Rate::saturating_from_rational(50, 100)))
+			]
+		));
+		RewardsModule::add_share(&ALICE, &PoolId::LoansIncentive(BTC), 1);
+		RewardsModule::add_share(&ALICE, &PoolId::LoansIncentive(DOT), 1);
+		RewardsModule::add_share(&ALICE, &PoolId::DexIncentive(BTC_AUSD_LP), 1);
+		assert_eq!(
+			IncentivesModule::period_incentive_reward_amount(&PoolId::LoansIncentive(BTC), 10),
+			800
+		);
+
+		IncentivesModule::on_initialize(10);
+		assert_eq!(TokensModule::free_balance(ACA, &VAULT), 1400);
+		assert_eq!(RewardsModule::pools(PoolId::LoansIncentive(BTC)).total_rewards, 800);
+		assert_eq!(RewardsModule::pools(PoolId::LoansIncentive(DOT)).total_rewards, 500);
+		assert_eq!(
+			RewardsModule::pools(PoolId::DexIncentive(BTC_AUSD_LP)).total_rewards,
+			100
+		);
+
+		assert_ok!(IncentivesModule::update_loans_risk_weights(
+			Origin::signed(4),
+			vec![(BTC, None)]
+		));
+		IncentivesModule::on_initialize(20);
+		assert_eq!(RewardsModule::pools(PoolId::LoansIncentive(BTC)).total_rewards, 1800);
+		assert_eq!(RewardsModule::pools(PoolId::LoansIncentive(DOT)).total_rewards, 1000);
+	});
+}
+
+#[test]
+fn incentive_rewards_budget_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		mock_incentive_rewards_budget(1000);
+		assert_ok!(IncentivesModule::update_incentive_rewards(
+			Origin::signed(4),
+			vec![
+				(PoolId::LoansIncentive(BTC), 600),
+				(PoolId::DexIncentive(BTC_AUSD_LP), 300),
+			],
+		));
+		assert_eq!(IncentivesModule::total_incentive_reward_amount(), 900);
+
+		assert_noop!(
+			IncentivesModule::update_incentive_rewards(Origin::signed(4), vec![(PoolId::HomaIncentive, 200)]),
+			Error::<Runtime>::ExceedIncentiveRewardsBudget
+		);
+		assert_noop!(
+			<IncentivesModule as IncentivesManager<_, _>>::set_incentive_reward_amount(&PoolId::HomaIncentive, 200),
+			Error::<Runtime>::ExceedIncentiveRewardsBudget
+		);
+
+		// the schedule counts by the initial reward amount before it starts
+		assert_noop!(
+			IncentivesModule::update_emission_schedules(
+				Origin::signed(4),
+				vec![(
+					PoolId::HomaIncentive,
+					Some(EmissionSchedule {
+						start: 100,
+						end: 200,
+						initial_reward_amount: 200,
+						curve: DecayCurve::Linear,
+					})
+				)]
+			),
+			Error::<Runtime>::ExceedIncentiveRewardsBudget
+		);
+
+		// the risk weight counts
+		assert_ok!(IncentivesModule::update_loans_risk_weights(
+			Origin::signed(4),
+			vec![(BTC, Some(Rate::saturating_from_rational(50, 100)))]
+		));
+		assert_ok!(IncentivesModule::update_incentive_rewards(
+			Origin::signed(4),
+			vec![(PoolId::HomaIncentive, 200)],
+		));
+		assert_eq!(IncentivesModule::total_incentive_reward_amount(), 800);
+		assert_noop!(
+			IncentivesModule::update_loans_risk_weights(Origin::signed(4), vec![(BTC, None)]),
+			Error::<Runtime>::ExceedIncentiveRewardsBudget
+		);
+
+		// the reward amounts can be lowered above the budget
+		mock_incentive_rewards_budget(500);
+		assert_ok!(IncentivesModule::update_incentive_rewards(
+			Origin::signed(4),
+			vec![(PoolId::DexIncentive(BTC_AUSD_LP), 100)],
+		));
+		assert_eq!(IncentivesModule::total_incentive_reward_amount(), 600);
+	});
+}
+
+#[test]
+fn genesis_config_works() {
+	let mut t = frame_system::GenesisConfig::default()
+		.build_storage::<Runtime>()
+		.unwrap();
+	crate::GenesisConfig::<Runtime> {
+		incentive_reward_amounts: vec![
+			(PoolId::LoansIncentive(DOT), 1000),
+			(PoolId::DexIncentive(DOT_AUSD_LP), 200),
+		],
+		dex_saving_reward_rates: vec![(PoolId::DexSaving(DOT_AUSD_LP), Rate::saturating_from_rational(1, 100))],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	sp_io::TestExternalities::from(t).execute_with(|| {
+		assert_eq!(
+			IncentivesModule::incentive_reward_amount(PoolId::LoansIncentive(DOT)),
+			1000
+		);
+		assert_eq!(
+			IncentivesModule::incentive_reward_amount(PoolId::DexIncentive(DOT_AUSD_LP)),
+			200
+		);
+		assert_eq!(
+			IncentivesModule::dex_saving_reward_rate(PoolId::DexSaving(DOT_AUSD_LP)),
+			Rate::saturating_from_rational(1, 100)
+		);
+	});
+}
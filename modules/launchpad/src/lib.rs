@@ -0,0 +1,497 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Launchpad Module
+//!
+//! ## Overview
+//!
+//! Anyone can launch a new `CurrencyId::LaunchpadToken` by reserving the creation deposit. The
+//! tokens are minted and burned along a bonding curve priced in the stable currency: buyers pay
+//! the integral of the curve over the purchased supply into the reserve of the launch, and
+//! sellers are paid from it. The amount that can be bought from a launch in each block is
+//! limited to keep bots from sweeping the curve.
+//!
+//! Once the supply reaches the target supply, anyone can finalize the launch. The whole reserve
+//! and the tokens matching it at the final price of the curve are added as the initial liquidity
+//! of the DEX trading pair with the stable currency. The liquidity is locked in the module
+//! account, the creation deposit is returned and the curve is closed.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{pallet_prelude::*, transactional, BoundedVec, PalletId};
+use frame_system::pallet_prelude::*;
+use orml_traits::{MultiCurrency, MultiCurrencyExtended, MultiReservableCurrency};
+use primitives::{Balance, CurrencyId, LaunchpadTokenId};
+use sp_runtime::{
+	traits::{AccountIdConversion, CheckedAdd, CheckedDiv, CheckedMul, One, Saturating, Zero},
+	ArithmeticError, FixedPointNumber, RuntimeDebug,
+};
+use sp_std::prelude::*;
+use support::{DEXManager, DEXTradingPairManager, Price, Rate};
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+/// The bonding curve of launchpad token, the price is the amount of the stable currency for a
+/// unit of the token.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum BondingCurve {
+	/// The price starts from `initial_price` and increases by `slope` for each unit of supply.
+	Linear { initial_price: Price, slope: Price },
+	/// The price starts from `initial_price` and increases by `growth` rate for each `step` of
+	/// supply.
+	Exponential {
+		initial_price: Price,
+		growth: Rate,
+		step: Balance,
+	},
+}
+
+impl BondingCurve {
+	fn is_valid(&self) -> bool {
+		match self {
+			BondingCurve::Linear { initial_price, .. } => !initial_price.is_zero(),
+			BondingCurve::Exponential {
+				initial_price, step, ..
+			} => !initial_price.is_zero() && !step.is_zero(),
+		}
+	}
+
+	/// The price of the next unit when the supply is `supply`.
+	pub fn price(&self, supply: Balance) -> Option<Price> {
+		match *self {
+			BondingCurve::Linear { initial_price, slope } => {
+				initial_price.checked_add(&Price::from_inner(slope.into_inner().checked_mul(supply)?))
+			}
+			BondingCurve::Exponential {
+				initial_price,
+				growth,
+				step,
+			} => initial_price.checked_mul(&Self::growth_factor(growth, supply / step)),
+		}
+	}
+
+	/// The total cost to mint the supply from zero to `supply`, the integral of the curve.
+	pub fn integral(&self, supply: Balance) -> Option<Balance> {
+		match *self {
+			// supply * (initial_price + slope * supply / 2)
+			BondingCurve::Linear { initial_price, slope } => initial_price
+				.checked_add(&Price::from_inner(slope.into_inner().checked_mul(supply)? / 2))?
+				.checked_mul_int(supply),
+			// the sum of the full steps, initial_price * step * ((1 + growth)^steps - 1) / growth,
+			// plus the rest of supply priced at the current step.
+			BondingCurve::Exponential {
+				initial_price,
+				growth,
+				step,
+			} => {
+				let steps = supply / step;
+				let rest = supply % step;
+				let factor = Self::growth_factor(growth, steps);
+				let full_steps_cost = if growth.is_zero() {
+					initial_price.checked_mul_int(step)?.checked_mul(steps)?
+				} else {
+					factor
+						.saturating_sub(Rate::one())
+						.checked_div(&growth)?
+						.checked_mul_int(initial_price.checked_mul_int(step)?)?
+				};
+				let rest_cost = initial_price.checked_mul(&factor)?.checked_mul_int(rest)?;
+				full_steps_cost.checked_add(rest_cost)
+			}
+		}
+	}
+
+	/// The cost to mint the supply from `from` to `to`.
+	pub fn cost(&self, from: Balance, to: Balance) -> Option<Balance> {
+		self.integral(to)?.checked_sub(self.integral(from)?)
+	}
+
+	fn growth_factor(growth: Rate, steps: Balance) -> Rate {
+		Rate::one()
+			.saturating_add(growth)
+			.saturating_pow(steps.min(usize::MAX as Balance) as usize)
+	}
+}
+
+/// The status of launch.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum LaunchStatus {
+	/// The token is traded on the bonding curve.
+	Active,
+	/// The reserve has been migrated to DEX.
+	Finalized,
+}
+
+/// The launch of launchpad token.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct LaunchInfo<AccountId, BlockNumber> {
+	/// The creator who reserved the creation deposit.
+	pub creator: AccountId,
+	/// The name of the token.
+	pub name: Vec<u8>,
+	/// The symbol of the token.
+	pub symbol: Vec<u8>,
+	/// The bonding curve.
+	pub curve: BondingCurve,
+	/// The supply to finalize the launch at.
+	pub target_supply: Balance,
+	/// The supply minted by the bonding curve.
+	pub supply: Balance,
+	/// The amount of stable currency paid into the bonding curve.
+	pub reserve: Balance,
+	/// The max amount of the token that can be bought in a block.
+	pub max_purchase_per_block: Balance,
+	/// The block number of the last purchase and the amount bought in it.
+	pub block_purchases: (BlockNumber, Balance),
+	/// The reserved creation deposit.
+	pub deposit: Balance,
+	/// The status.
+	pub status: LaunchStatus,
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currencies to mint launchpad tokens, pay the bonding curves and reserve the
+		/// creation deposits.
+		type Currency: MultiCurrencyExtended<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>
+			+ MultiReservableCurrency<Self::AccountId>;
+
+		/// DEX to migrate the finalized launches to.
+		type DEX: DEXManager<Self::AccountId, CurrencyId, Balance> + DEXTradingPairManager<CurrencyId>;
+
+		/// The stable currency id the bonding curves are priced in.
+		#[pallet::constant]
+		type GetStableCurrencyId: Get<CurrencyId>;
+
+		/// The native currency id of the creation deposit.
+		#[pallet::constant]
+		type GetNativeCurrencyId: Get<CurrencyId>;
+
+		/// The deposit reserved from the creator until the launch is finalized.
+		#[pallet::constant]
+		type CreationDeposit: Get<Balance>;
+
+		/// The module id to keep the reserves of bonding curves and the locked liquidity.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The max length of the name and symbol of token.
+		#[pallet::constant]
+		type MaxNameLength: Get<u32>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The initial price or the step of the bonding curve is zero
+		InvalidBondingCurve,
+		/// The target supply is zero
+		InvalidTargetSupply,
+		/// The max purchase per block is zero
+		InvalidPurchaseLimit,
+		/// The amount is zero
+		InvalidAmount,
+		/// The launch does not exist
+		LaunchNotFound,
+		/// The launch has been finalized
+		AlreadyFinalized,
+		/// The supply would exceed the target supply
+		ExceedTargetSupply,
+		/// The amount bought in this block would exceed the max purchase per block
+		ExceedMaxPurchasePerBlock,
+		/// The supply hasn't reached the target supply
+		TargetSupplyNotReached,
+		/// The cost is more than the max cost
+		ExceedMaxCost,
+		/// The proceeds are less than the min proceeds
+		BelowMinProceeds,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Launched new token. \[currency_id, creator, target_supply\]
+		TokenLaunched(CurrencyId, T::AccountId, Balance),
+		/// Bought token from the bonding curve. \[currency_id, who, amount, cost\]
+		Bought(CurrencyId, T::AccountId, Balance, Balance),
+		/// Sold token to the bonding curve. \[currency_id, who, amount, proceeds\]
+		Sold(CurrencyId, T::AccountId, Balance, Balance),
+		/// Finalized launch and added the liquidity to DEX. \[currency_id, token_amount,
+		/// stable_amount\]
+		Finalized(CurrencyId, Balance, Balance),
+	}
+
+	/// The id of the next launchpad token.
+	///
+	/// NextTokenId: LaunchpadTokenId
+	#[pallet::storage]
+	#[pallet::getter(fn next_token_id)]
+	pub type NextTokenId<T: Config> = StorageValue<_, LaunchpadTokenId, ValueQuery>;
+
+	/// The launches of launchpad tokens.
+	///
+	/// Launches: map LaunchpadTokenId => Option<LaunchInfo>
+	#[pallet::storage]
+	#[pallet::getter(fn launches)]
+	pub type Launches<T: Config> =
+		StorageMap<_, Twox64Concat, LaunchpadTokenId, LaunchInfo<T::AccountId, T::BlockNumber>, OptionQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Launch a new token on the bonding curve, the creation deposit is reserved until the
+		/// launch is finalized.
+		///
+		/// - `name`: the name of the token.
+		/// - `symbol`: the symbol of the token.
+		/// - `curve`: the bonding curve priced in the stable currency.
+		/// - `target_supply`: the supply to finalize the launch at.
+		/// - `max_purchase_per_block`: the max amount of the token that can be bought in a block.
+		#[pallet::weight(T::WeightInfo::create_launch())]
+		#[transactional]
+		pub fn create_launch(
+			origin: OriginFor<T>,
+			name: BoundedVec<u8, T::MaxNameLength>,
+			symbol: BoundedVec<u8, T::MaxNameLength>,
+			curve: BondingCurve,
+			#[pallet::compact] target_supply: Balance,
+			#[pallet::compact] max_purchase_per_block: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(curve.is_valid(), Error::<T>::InvalidBondingCurve);
+			ensure!(!target_supply.is_zero(), Error::<T>::InvalidTargetSupply);
+			ensure!(!max_purchase_per_block.is_zero(), Error::<T>::InvalidPurchaseLimit);
+			// the whole curve must be priced without overflow
+			curve.integral(target_supply).ok_or(ArithmeticError::Overflow)?;
+
+			let deposit = T::CreationDeposit::get();
+			T::Currency::reserve(T::GetNativeCurrencyId::get(), &who, deposit)?;
+
+			let token_id = NextTokenId::<T>::try_mutate(|id| -> Result<LaunchpadTokenId, DispatchError> {
+				let current_id = *id;
+				*id = id.checked_add(One::one()).ok_or(ArithmeticError::Overflow)?;
+				Ok(current_id)
+			})?;
+			Launches::<T>::insert(
+				token_id,
+				LaunchInfo {
+					creator: who.clone(),
+					name: name.into_inner(),
+					symbol: symbol.into_inner(),
+					curve,
+					target_supply,
+					supply: Zero::zero(),
+					reserve: Zero::zero(),
+					max_purchase_per_block,
+					block_purchases: (Zero::zero(), Zero::zero()),
+					deposit,
+					status: LaunchStatus::Active,
+				},
+			);
+
+			Self::deposit_event(Event::TokenLaunched(
+				CurrencyId::LaunchpadToken(token_id),
+				who,
+				target_supply,
+			));
+			Ok(().into())
+		}
+
+		/// Buy `amount` of the token from the bonding curve.
+		///
+		/// - `token_id`: the id of launchpad token.
+		/// - `amount`: the amount of the token to buy.
+		/// - `max_cost`: the max amount of the stable currency to pay.
+		#[pallet::weight(T::WeightInfo::buy())]
+		#[transactional]
+		pub fn buy(
+			origin: OriginFor<T>,
+			token_id: LaunchpadTokenId,
+			#[pallet::compact] amount: Balance,
+			#[pallet::compact] max_cost: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), Error::<T>::InvalidAmount);
+			let now = frame_system::Pallet::<T>::block_number();
+
+			let cost = Launches::<T>::try_mutate(token_id, |maybe_launch| -> Result<Balance, DispatchError> {
+				let launch = maybe_launch.as_mut().ok_or(Error::<T>::LaunchNotFound)?;
+				ensure!(launch.status == LaunchStatus::Active, Error::<T>::AlreadyFinalized);
+
+				let new_supply = launch.supply.checked_add(amount).ok_or(ArithmeticError::Overflow)?;
+				ensure!(new_supply <= launch.target_supply, Error::<T>::ExceedTargetSupply);
+
+				let (last_block, bought) = launch.block_purchases;
+				let bought = if last_block == now { bought } else { Zero::zero() };
+				let bought = bought.saturating_add(amount);
+				ensure!(
+					bought <= launch.max_purchase_per_block,
+					Error::<T>::ExceedMaxPurchasePerBlock
+				);
+
+				let cost = launch
+					.curve
+					.cost(launch.supply, new_supply)
+					.ok_or(ArithmeticError::Overflow)?;
+				ensure!(cost <= max_cost, Error::<T>::ExceedMaxCost);
+
+				T::Currency::transfer(T::GetStableCurrencyId::get(), &who, &Self::account_id(), cost)?;
+				T::Currency::deposit(CurrencyId::LaunchpadToken(token_id), &who, amount)?;
+
+				launch.supply = new_supply;
+				launch.reserve = launch.reserve.saturating_add(cost);
+				launch.block_purchases = (now, bought);
+				Ok(cost)
+			})?;
+
+			Self::deposit_event(Event::Bought(CurrencyId::LaunchpadToken(token_id), who, amount, cost));
+			Ok(().into())
+		}
+
+		/// Sell `amount` of the token to the bonding curve.
+		///
+		/// - `token_id`: the id of launchpad token.
+		/// - `amount`: the amount of the token to sell.
+		/// - `min_proceeds`: the min amount of the stable currency to receive.
+		#[pallet::weight(T::WeightInfo::sell())]
+		#[transactional]
+		pub fn sell(
+			origin: OriginFor<T>,
+			token_id: LaunchpadTokenId,
+			#[pallet::compact] amount: Balance,
+			#[pallet::compact] min_proceeds: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), Error::<T>::InvalidAmount);
+
+			let proceeds = Launches::<T>::try_mutate(token_id, |maybe_launch| -> Result<Balance, DispatchError> {
+				let launch = maybe_launch.as_mut().ok_or(Error::<T>::LaunchNotFound)?;
+				ensure!(launch.status == LaunchStatus::Active, Error::<T>::AlreadyFinalized);
+
+				T::Currency::withdraw(CurrencyId::LaunchpadToken(token_id), &who, amount)?;
+
+				let new_supply = launch.supply.saturating_sub(amount);
+				let proceeds = launch
+					.curve
+					.cost(new_supply, launch.supply)
+					.ok_or(ArithmeticError::Overflow)?
+					.min(launch.reserve);
+				ensure!(proceeds >= min_proceeds, Error::<T>::BelowMinProceeds);
+
+				T::Currency::transfer(T::GetStableCurrencyId::get(), &Self::account_id(), &who, proceeds)?;
+
+				launch.supply = new_supply;
+				launch.reserve = launch.reserve.saturating_sub(proceeds);
+				Ok(proceeds)
+			})?;
+
+			Self::deposit_event(Event::Sold(CurrencyId::LaunchpadToken(token_id), who, amount, proceeds));
+			Ok(().into())
+		}
+
+		/// Finalize the launch whose supply reached the target supply. The reserve and the
+		/// tokens matching it at the final price are added as the liquidity of the DEX trading
+		/// pair with the stable currency, which is locked in the module account.
+		///
+		/// The dispatch origin of this call must be signed by any account.
+		///
+		/// - `token_id`: the id of launchpad token.
+		#[pallet::weight(T::WeightInfo::finalize())]
+		#[transactional]
+		pub fn finalize(origin: OriginFor<T>, token_id: LaunchpadTokenId) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+
+			let (token_amount, stable_amount) =
+				Launches::<T>::try_mutate(token_id, |maybe_launch| -> Result<(Balance, Balance), DispatchError> {
+					let launch = maybe_launch.as_mut().ok_or(Error::<T>::LaunchNotFound)?;
+					ensure!(launch.status == LaunchStatus::Active, Error::<T>::AlreadyFinalized);
+					ensure!(
+						launch.supply >= launch.target_supply,
+						Error::<T>::TargetSupplyNotReached
+					);
+
+					let currency_id = CurrencyId::LaunchpadToken(token_id);
+					let stable_currency_id = T::GetStableCurrencyId::get();
+					let stable_amount = launch.reserve;
+					let token_amount = launch
+						.curve
+						.price(launch.supply)
+						.and_then(|price| price.reciprocal())
+						.and_then(|price| price.checked_mul_int(stable_amount))
+						.ok_or(ArithmeticError::Overflow)?;
+
+					let module_account = Self::account_id();
+					T::Currency::deposit(currency_id, &module_account, token_amount)?;
+					T::DEX::enable_trading_pair(currency_id, stable_currency_id)?;
+					T::DEX::add_liquidity(
+						&module_account,
+						currency_id,
+						stable_currency_id,
+						token_amount,
+						stable_amount,
+						Zero::zero(),
+						false,
+					)?;
+
+					T::Currency::unreserve(T::GetNativeCurrencyId::get(), &launch.creator, launch.deposit);
+					launch.reserve = Zero::zero();
+					launch.status = LaunchStatus::Finalized;
+					Ok((token_amount, stable_amount))
+				})?;
+
+			Self::deposit_event(Event::Finalized(
+				CurrencyId::LaunchpadToken(token_id),
+				token_amount,
+				stable_amount,
+			));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The account to keep the reserves of bonding curves and the locked liquidity.
+	pub fn account_id() -> T::AccountId {
+		T::PalletId::get().into_account()
+	}
+
+	/// The cost to buy `amount` of the token from the bonding curve now.
+	pub fn buy_cost(token_id: LaunchpadTokenId, amount: Balance) -> Option<Balance> {
+		let launch = Self::launches(token_id)?;
+		launch.curve.cost(launch.supply, launch.supply.checked_add(amount)?)
+	}
+}
@@ -0,0 +1,309 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the launchpad module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+
+// the price increases from 1 by 0.01 for each unit
+fn linear_curve() -> BondingCurve {
+	BondingCurve::Linear {
+		initial_price: Price::one(),
+		slope: Price::saturating_from_rational(1, 100),
+	}
+}
+
+fn create_launch() {
+	assert_ok!(LaunchpadModule::create_launch(
+		Origin::signed(ALICE),
+		bounded_name(b"Token"),
+		bounded_name(b"TKN"),
+		linear_curve(),
+		100,
+		60
+	));
+}
+
+#[test]
+fn bonding_curve_works() {
+	let curve = linear_curve();
+	assert_eq!(curve.price(0), Some(Price::one()));
+	assert_eq!(curve.price(100), Some(Price::saturating_from_integer(2)));
+	assert_eq!(curve.integral(0), Some(0));
+	assert_eq!(curve.integral(50), Some(62));
+	assert_eq!(curve.integral(100), Some(150));
+	assert_eq!(curve.cost(50, 100), Some(88));
+
+	// the price doubles for each 10 units
+	let curve = BondingCurve::Exponential {
+		initial_price: Price::one(),
+		growth: Rate::one(),
+		step: 10,
+	};
+	assert_eq!(curve.price(9), Some(Price::one()));
+	assert_eq!(curve.price(25), Some(Price::saturating_from_integer(4)));
+	assert_eq!(curve.integral(25), Some(50));
+	assert_eq!(curve.cost(10, 25), Some(40));
+
+	let curve = BondingCurve::Exponential {
+		initial_price: Price::one(),
+		growth: Rate::zero(),
+		step: 10,
+	};
+	assert_eq!(curve.price(25), Some(Price::one()));
+	assert_eq!(curve.integral(25), Some(25));
+}
+
+#[test]
+fn create_launch_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			LaunchpadModule::create_launch(
+				Origin::signed(ALICE),
+				bounded_name(b"Token"),
+				bounded_name(b"TKN"),
+				BondingCurve::Linear {
+					initial_price: Price::zero(),
+					slope: Price::one(),
+				},
+				100,
+				60
+			),
+			Error::<Runtime>::InvalidBondingCurve
+		);
+		assert_noop!(
+			LaunchpadModule::create_launch(
+				Origin::signed(ALICE),
+				bounded_name(b"Token"),
+				bounded_name(b"TKN"),
+				BondingCurve::Exponential {
+					initial_price: Price::one(),
+					growth: Rate::one(),
+					step: 0,
+				},
+				100,
+				60
+			),
+			Error::<Runtime>::InvalidBondingCurve
+		);
+		assert_noop!(
+			LaunchpadModule::create_launch(
+				Origin::signed(ALICE),
+				bounded_name(b"Token"),
+				bounded_name(b"TKN"),
+				linear_curve(),
+				0,
+				60
+			),
+			Error::<Runtime>::InvalidTargetSupply
+		);
+		assert_noop!(
+			LaunchpadModule::create_launch(
+				Origin::signed(ALICE),
+				bounded_name(b"Token"),
+				bounded_name(b"TKN"),
+				linear_curve(),
+				100,
+				0
+			),
+			Error::<Runtime>::InvalidPurchaseLimit
+		);
+		assert_noop!(
+			LaunchpadModule::create_launch(
+				Origin::signed(ALICE),
+				bounded_name(b"Token"),
+				bounded_name(b"TKN"),
+				BondingCurve::Linear {
+					initial_price: Price::one(),
+					slope: Price::from_inner(u128::MAX),
+				},
+				100,
+				60
+			),
+			ArithmeticError::Overflow
+		);
+		assert_noop!(
+			LaunchpadModule::create_launch(
+				Origin::signed(BOB),
+				bounded_name(b"Token"),
+				bounded_name(b"TKN"),
+				linear_curve(),
+				100,
+				60
+			),
+			orml_tokens::Error::<Runtime>::BalanceTooLow
+		);
+
+		create_launch();
+		System::assert_last_event(Event::LaunchpadModule(crate::Event::TokenLaunched(TOKEN, ALICE, 100)));
+		assert_eq!(LaunchpadModule::next_token_id(), 1);
+		assert_eq!(
+			LaunchpadModule::launches(0),
+			Some(LaunchInfo {
+				creator: ALICE,
+				name: b"Token".to_vec(),
+				symbol: b"TKN".to_vec(),
+				curve: linear_curve(),
+				target_supply: 100,
+				supply: 0,
+				reserve: 0,
+				max_purchase_per_block: 60,
+				block_purchases: (0, 0),
+				deposit: 100,
+				status: LaunchStatus::Active,
+			})
+		);
+		assert_eq!(Tokens::reserved_balance(ACA, &ALICE), 100);
+	});
+}
+
+#[test]
+fn buy_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			LaunchpadModule::buy(Origin::signed(BOB), 0, 50, 100),
+			Error::<Runtime>::LaunchNotFound
+		);
+		create_launch();
+		assert_noop!(
+			LaunchpadModule::buy(Origin::signed(BOB), 0, 0, 100),
+			Error::<Runtime>::InvalidAmount
+		);
+		assert_noop!(
+			LaunchpadModule::buy(Origin::signed(BOB), 0, 101, 1000),
+			Error::<Runtime>::ExceedTargetSupply
+		);
+		assert_noop!(
+			LaunchpadModule::buy(Origin::signed(BOB), 0, 61, 1000),
+			Error::<Runtime>::ExceedMaxPurchasePerBlock
+		);
+		assert_noop!(
+			LaunchpadModule::buy(Origin::signed(BOB), 0, 50, 61),
+			Error::<Runtime>::ExceedMaxCost
+		);
+
+		assert_ok!(LaunchpadModule::buy(Origin::signed(BOB), 0, 50, 62));
+		System::assert_last_event(Event::LaunchpadModule(crate::Event::Bought(TOKEN, BOB, 50, 62)));
+		assert_eq!(Tokens::free_balance(TOKEN, &BOB), 50);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 938);
+		assert_eq!(Tokens::free_balance(AUSD, &LaunchpadModule::account_id()), 62);
+		assert_eq!(LaunchpadModule::buy_cost(0, 50), Some(88));
+
+		// the purchases of all accounts in the block are limited
+		assert_noop!(
+			LaunchpadModule::buy(Origin::signed(ALICE), 0, 20, 1000),
+			Error::<Runtime>::ExceedMaxPurchasePerBlock
+		);
+		assert_ok!(LaunchpadModule::buy(Origin::signed(ALICE), 0, 10, 1000));
+
+		System::set_block_number(2);
+		assert_ok!(LaunchpadModule::buy(Origin::signed(ALICE), 0, 40, 1000));
+		System::assert_last_event(Event::LaunchpadModule(crate::Event::Bought(TOKEN, ALICE, 40, 72)));
+		let launch = LaunchpadModule::launches(0).unwrap();
+		assert_eq!(launch.supply, 100);
+		assert_eq!(launch.reserve, 150);
+		assert_eq!(launch.block_purchases, (2, 40));
+	});
+}
+
+#[test]
+fn sell_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			LaunchpadModule::sell(Origin::signed(BOB), 0, 10, 0),
+			Error::<Runtime>::LaunchNotFound
+		);
+		create_launch();
+		assert_ok!(LaunchpadModule::buy(Origin::signed(BOB), 0, 50, 62));
+		assert_noop!(
+			LaunchpadModule::sell(Origin::signed(BOB), 0, 0, 0),
+			Error::<Runtime>::InvalidAmount
+		);
+		assert_noop!(
+			LaunchpadModule::sell(Origin::signed(BOB), 0, 51, 0),
+			orml_tokens::Error::<Runtime>::BalanceTooLow
+		);
+		assert_noop!(
+			LaunchpadModule::sell(Origin::signed(BOB), 0, 10, 15),
+			Error::<Runtime>::BelowMinProceeds
+		);
+
+		assert_ok!(LaunchpadModule::sell(Origin::signed(BOB), 0, 10, 14));
+		System::assert_last_event(Event::LaunchpadModule(crate::Event::Sold(TOKEN, BOB, 10, 14)));
+		assert_eq!(Tokens::free_balance(TOKEN, &BOB), 40);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 952);
+		let launch = LaunchpadModule::launches(0).unwrap();
+		assert_eq!(launch.supply, 40);
+		assert_eq!(launch.reserve, 48);
+
+		// sell all back to the curve
+		assert_ok!(LaunchpadModule::sell(Origin::signed(BOB), 0, 40, 48));
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 1000);
+		assert_eq!(Tokens::free_balance(AUSD, &LaunchpadModule::account_id()), 0);
+	});
+}
+
+#[test]
+fn finalize_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			LaunchpadModule::finalize(Origin::signed(BOB), 0),
+			Error::<Runtime>::LaunchNotFound
+		);
+		create_launch();
+		assert_ok!(LaunchpadModule::buy(Origin::signed(BOB), 0, 50, 62));
+		assert_noop!(
+			LaunchpadModule::finalize(Origin::signed(BOB), 0),
+			Error::<Runtime>::TargetSupplyNotReached
+		);
+		System::set_block_number(2);
+		assert_ok!(LaunchpadModule::buy(Origin::signed(BOB), 0, 50, 88));
+
+		// the reserve of 150 AUSD is paired with 75 tokens at the final price 2
+		assert_ok!(LaunchpadModule::finalize(Origin::signed(BOB), 0));
+		System::assert_last_event(Event::LaunchpadModule(crate::Event::Finalized(TOKEN, 75, 150)));
+		assert!(is_trading_pair_enabled(TOKEN, AUSD));
+		let lp_currency_id = CurrencyId::join_dex_share_currency_id(TOKEN, AUSD).unwrap();
+		assert_eq!(
+			Tokens::free_balance(lp_currency_id, &LaunchpadModule::account_id()),
+			225
+		);
+		assert_eq!(Tokens::free_balance(AUSD, &LaunchpadModule::account_id()), 0);
+		assert_eq!(Tokens::reserved_balance(ACA, &ALICE), 0);
+		let launch = LaunchpadModule::launches(0).unwrap();
+		assert_eq!(launch.reserve, 0);
+		assert_eq!(launch.status, LaunchStatus::Finalized);
+
+		assert_noop!(
+			LaunchpadModule::finalize(Origin::signed(BOB), 0),
+			Error::<Runtime>::AlreadyFinalized
+		);
+		assert_noop!(
+			LaunchpadModule::buy(Origin::signed(BOB), 0, 1, 10),
+			Error::<Runtime>::AlreadyFinalized
+		);
+		assert_noop!(
+			LaunchpadModule::sell(Origin::signed(BOB), 0, 1, 0),
+			Error::<Runtime>::AlreadyFinalized
+		);
+	});
+}
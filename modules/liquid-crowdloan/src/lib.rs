@@ -0,0 +1,375 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Liquid Crowdloan Module
+//!
+//! ## Overview
+//!
+//! Accepts relaychain currency(DOT) contributions which are routed to the
+//! crowdloans on relaychain opened by governance through the
+//! `CrowdloanBridge`, and mints the transferable liquid crowdloan
+//! currency(lcDOT) 1:1 to the contributors, the XCM fee is deducted from the
+//! contribution so the parachain sovereign account keeps backing lcDOT 1:1.
+//! Each crowdloan has its own lcDOT currency
+//! `CurrencyId::LiquidCrowdloan(para_id)`. Once the lease ended, or the
+//! crowdloan failed and the contributions are refunded to the parachain
+//! sovereign account, the refunded DOT join the redeemable pool of that
+//! crowdloan, which only the lcDOT of the same crowdloan can be redeemed 1:1
+//! for.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{pallet_prelude::*, transactional};
+use frame_system::pallet_prelude::*;
+use orml_traits::MultiCurrencyExtended;
+use primitives::{Balance, CurrencyId};
+use sp_runtime::{traits::Zero, ArithmeticError, DispatchResult, FixedPointNumber, RuntimeDebug};
+use support::{ExchangeRate, ExchangeRateProvider};
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+/// The id of the parachain which the crowdloan is for.
+pub type ParaId = primitives::CrowdloanParaId;
+
+/// The status of the crowdloan.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+pub enum CrowdloanStatus {
+	/// Crowdloan is accepting contributions.
+	Contributing,
+	/// Crowdloan won the auction, contributions are locked until lease end.
+	Won,
+	/// Crowdloan failed and contributions are refunded, lcDOT is redeemable.
+	Failed,
+	/// Lease ended and contributions are returned, lcDOT is redeemable.
+	LeaseEnded,
+}
+
+impl Default for CrowdloanStatus {
+	fn default() -> Self {
+		Self::Contributing
+	}
+}
+
+impl CrowdloanStatus {
+	/// Whether the status can be changed to `next`.
+	fn can_transfer_to(&self, next: CrowdloanStatus) -> bool {
+		matches!(
+			(self, next),
+			(CrowdloanStatus::Contributing, CrowdloanStatus::Won)
+				| (CrowdloanStatus::Contributing, CrowdloanStatus::Failed)
+				| (CrowdloanStatus::Won, CrowdloanStatus::LeaseEnded)
+		)
+	}
+
+	/// Whether the liquid crowdloan currency is redeemable.
+	fn is_redeemable(&self) -> bool {
+		matches!(self, CrowdloanStatus::Failed | CrowdloanStatus::LeaseEnded)
+	}
+}
+
+/// The bridge to the crowdloans on relaychain.
+pub trait CrowdloanBridge<Balance> {
+	/// Contribute `amount` held by the parachain sovereign account on
+	/// relaychain to the crowdloan of `para_id`.
+	fn contribute(para_id: ParaId, amount: Balance) -> DispatchResult;
+	/// Withdraw the contributions from the crowdloan of `para_id` back to the
+	/// parachain sovereign account on relaychain.
+	fn withdraw(para_id: ParaId) -> DispatchResult;
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency for relaychain currency and liquid crowdloan currency.
+		type Currency: MultiCurrencyExtended<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
+
+		/// The relaychain currency id, it should be DOT in Acala.
+		#[pallet::constant]
+		type RelaychainCurrencyId: Get<CurrencyId>;
+
+		/// The minimum amount of a contribution, which should not be less than
+		/// the minimum contribution of crowdloan on relaychain.
+		#[pallet::constant]
+		type MinContribution: Get<Balance>;
+
+		/// The bridge to contribute to and withdraw from the crowdloan on
+		/// relaychain.
+		type Bridge: CrowdloanBridge<Balance>;
+
+		/// The XCM fee paid by the parachain sovereign account on relaychain
+		/// for each bridge operation.
+		#[pallet::constant]
+		type XcmFee: Get<Balance>;
+
+		/// The account which funds the XCM fee of withdrawing the
+		/// contributions.
+		#[pallet::constant]
+		type TreasuryAccount: Get<Self::AccountId>;
+
+		/// The origin which may update the crowdloan status and withdraw the
+		/// contributions. Root can always do this.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The contribution is below the minimum contribution.
+		BelowMinContribution,
+		/// The crowdloan is not accepting contributions.
+		NotContributing,
+		/// The status transition is not allowed.
+		InvalidStatusTransition,
+		/// The crowdloan has not been opened.
+		CrowdloanNotFound,
+		/// The crowdloan has already been opened.
+		CrowdloanAlreadyOpened,
+		/// The redeemable pool is not enough for the redemption.
+		InsufficientRedeemablePool,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Open the crowdloan to accept contributions. \[para_id\]
+		CrowdloanOpened(ParaId),
+		/// Contribute to the crowdloan and mint liquid crowdloan currency.
+		/// \[para_id, who, amount\]
+		Contributed(ParaId, T::AccountId, Balance),
+		/// Burn liquid crowdloan currency and redeem relaychain currency.
+		/// \[para_id, who, amount\]
+		Redeemed(ParaId, T::AccountId, Balance),
+		/// The crowdloan status updated. \[para_id, status\]
+		CrowdloanStatusUpdated(ParaId, CrowdloanStatus),
+		/// Request to withdraw the contributions from the crowdloan.
+		/// \[para_id\]
+		WithdrawRequested(ParaId),
+	}
+
+	/// The contributions to the crowdloans by AccountId.
+	///
+	/// Contributions: double_map ParaId, AccountId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn contributions)]
+	pub type Contributions<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, ParaId, Twox64Concat, T::AccountId, Balance, ValueQuery>;
+
+	/// The total contributions to the crowdloans.
+	///
+	/// TotalContribution: map ParaId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn total_contribution)]
+	pub type TotalContribution<T: Config> = StorageMap<_, Twox64Concat, ParaId, Balance, ValueQuery>;
+
+	/// The status of the opened crowdloans.
+	///
+	/// Status: map ParaId => Option<CrowdloanStatus>
+	#[pallet::storage]
+	#[pallet::getter(fn status)]
+	pub type Status<T: Config> = StorageMap<_, Twox64Concat, ParaId, CrowdloanStatus, OptionQuery>;
+
+	/// The relaychain currency refunded from the crowdloans, which the lcDOT
+	/// of the same crowdloan can be redeemed for.
+	///
+	/// RedeemablePool: map ParaId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn redeemable_pool)]
+	pub type RedeemablePool<T: Config> = StorageMap<_, Twox64Concat, ParaId, Balance, ValueQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Open the crowdloan of `para_id` on relaychain to accept
+		/// contributions.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `para_id`: the parachain id of the crowdloan.
+		#[pallet::weight((T::WeightInfo::open_crowdloan(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn open_crowdloan(origin: OriginFor<T>, #[pallet::compact] para_id: ParaId) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(!Status::<T>::contains_key(para_id), Error::<T>::CrowdloanAlreadyOpened);
+
+			Status::<T>::insert(para_id, CrowdloanStatus::Contributing);
+			Self::deposit_event(Event::CrowdloanOpened(para_id));
+			Ok(().into())
+		}
+
+		/// Contribute DOT to the crowdloan and mint lcDOT 1:1 for the
+		/// contribution after the XCM fee deducted.
+		///
+		/// - `para_id`: the parachain id of the crowdloan.
+		/// - `amount`: the DOT amount to contribute, including the XCM fee.
+		#[pallet::weight(T::WeightInfo::contribute())]
+		#[transactional]
+		pub fn contribute(
+			origin: OriginFor<T>,
+			#[pallet::compact] para_id: ParaId,
+			#[pallet::compact] amount: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				Self::status(para_id).ok_or(Error::<T>::CrowdloanNotFound)? == CrowdloanStatus::Contributing,
+				Error::<T>::NotContributing
+			);
+			let contribution = amount
+				.checked_sub(T::XcmFee::get())
+				.filter(|contribution| *contribution >= T::MinContribution::get())
+				.ok_or(Error::<T>::BelowMinContribution)?;
+
+			// the DOT on parachain is backed by the sovereign account on relaychain,
+			// burn it here as the backing will pay the XCM fee and be contributed to
+			// the crowdloan.
+			T::Currency::withdraw(T::RelaychainCurrencyId::get(), &who, amount)?;
+			T::Bridge::contribute(para_id, contribution)?;
+			T::Currency::deposit(Self::liquid_currency_id(para_id), &who, contribution)?;
+
+			Contributions::<T>::mutate(para_id, &who, |total| {
+				*total = total.saturating_add(contribution);
+			});
+			TotalContribution::<T>::mutate(para_id, |total| *total = total.saturating_add(contribution));
+
+			Self::deposit_event(Event::Contributed(para_id, who, contribution));
+			Ok(().into())
+		}
+
+		/// Burn the lcDOT of the crowdloan and redeem DOT 1:1 from its
+		/// redeemable pool, which is available after the lease ended or the
+		/// crowdloan failed.
+		///
+		/// - `para_id`: the parachain id of the crowdloan.
+		/// - `amount`: the lcDOT amount to redeem.
+		#[pallet::weight(T::WeightInfo::redeem())]
+		#[transactional]
+		pub fn redeem(
+			origin: OriginFor<T>,
+			#[pallet::compact] para_id: ParaId,
+			#[pallet::compact] amount: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			if !amount.is_zero() {
+				RedeemablePool::<T>::try_mutate(para_id, |pool| -> DispatchResult {
+					*pool = pool.checked_sub(amount).ok_or(Error::<T>::InsufficientRedeemablePool)?;
+					Ok(())
+				})?;
+				T::Currency::withdraw(Self::liquid_currency_id(para_id), &who, amount)?;
+				T::Currency::deposit(T::RelaychainCurrencyId::get(), &who, amount)?;
+				Self::deposit_event(Event::Redeemed(para_id, who, amount));
+			}
+			Ok(().into())
+		}
+
+		/// Request to withdraw the contributions from the crowdloan on
+		/// relaychain back to the parachain sovereign account, the XCM fee is
+		/// funded by `TreasuryAccount`.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `para_id`: the parachain id of the crowdloan.
+		#[pallet::weight((T::WeightInfo::withdraw_contributions(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn withdraw_contributions(
+			origin: OriginFor<T>,
+			#[pallet::compact] para_id: ParaId,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(Status::<T>::contains_key(para_id), Error::<T>::CrowdloanNotFound);
+
+			// burn the DOT of treasury on parachain for the backing to pay the XCM fee.
+			T::Currency::withdraw(
+				T::RelaychainCurrencyId::get(),
+				&T::TreasuryAccount::get(),
+				T::XcmFee::get(),
+			)?;
+			T::Bridge::withdraw(para_id)?;
+			Self::deposit_event(Event::WithdrawRequested(para_id));
+			Ok(().into())
+		}
+
+		/// Update the crowdloan status. The status can only be moved to
+		/// `Failed` or `LeaseEnded` after the contributions have been returned
+		/// to the parachain sovereign account on relaychain, and then the
+		/// contributions join the redeemable pool of the crowdloan.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `para_id`: the parachain id of the crowdloan.
+		/// - `status`: the new crowdloan status.
+		#[pallet::weight((T::WeightInfo::set_status(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_status(
+			origin: OriginFor<T>,
+			#[pallet::compact] para_id: ParaId,
+			status: CrowdloanStatus,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				Self::status(para_id)
+					.ok_or(Error::<T>::CrowdloanNotFound)?
+					.can_transfer_to(status),
+				Error::<T>::InvalidStatusTransition
+			);
+
+			if status.is_redeemable() {
+				RedeemablePool::<T>::try_mutate(para_id, |pool| -> DispatchResult {
+					*pool = pool
+						.checked_add(Self::total_contribution(para_id))
+						.ok_or(ArithmeticError::Overflow)?;
+					Ok(())
+				})?;
+			}
+			Status::<T>::insert(para_id, status);
+			Self::deposit_event(Event::CrowdloanStatusUpdated(para_id, status));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The liquid crowdloan currency(lcDOT) of the crowdloan of `para_id`.
+	pub fn liquid_currency_id(para_id: ParaId) -> CurrencyId {
+		CurrencyId::LiquidCrowdloan(para_id)
+	}
+}
+
+impl<T: Config> ExchangeRateProvider for Pallet<T> {
+	/// lcDOT is always redeemed 1:1 for DOT.
+	fn get_exchange_rate() -> ExchangeRate {
+		ExchangeRate::one()
+	}
+}
@@ -0,0 +1,190 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Mocks for liquid crowdloan module.
+
+#![cfg(test)]
+
+use super::*;
+
+use crate as liquid_crowdloan;
+use frame_support::{construct_runtime, ord_parameter_types, parameter_types};
+use frame_system::EnsureSignedBy;
+use orml_traits::parameter_type_with_key;
+use primitives::{Amount, TokenSymbol};
+use sp_core::H256;
+use sp_runtime::{testing::Header, traits::IdentityLookup, DispatchError};
+use std::cell::RefCell;
+
+pub type AccountId = u128;
+pub type BlockNumber = u64;
+
+pub const ALICE: AccountId = 0;
+pub const BOB: AccountId = 1;
+pub const TREASURY: AccountId = 2;
+pub const PARA_ID: ParaId = 2000;
+pub const DOT: CurrencyId = CurrencyId::Token(TokenSymbol::DOT);
+pub const LCDOT: CurrencyId = CurrencyId::LiquidCrowdloan(PARA_ID);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Runtime {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: CurrencyId| -> Balance {
+		Default::default()
+	};
+}
+
+parameter_types! {
+	pub const MaxLocks: u32 = 100;
+}
+
+impl orml_tokens::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type Amount = Amount;
+	type CurrencyId = CurrencyId;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type OnDust = ();
+	type MaxLocks = MaxLocks;
+}
+
+thread_local! {
+	pub static CONTRIBUTED: RefCell<Vec<(ParaId, Balance)>> = RefCell::new(vec![]);
+	pub static WITHDRAWN: RefCell<Vec<ParaId>> = RefCell::new(vec![]);
+	pub static BRIDGE_AVAILABLE: RefCell<bool> = RefCell::new(true);
+}
+
+pub struct MockBridge;
+impl CrowdloanBridge<Balance> for MockBridge {
+	fn contribute(para_id: ParaId, amount: Balance) -> DispatchResult {
+		if !BRIDGE_AVAILABLE.with(|v| *v.borrow()) {
+			return Err(DispatchError::Other("bridge unavailable"));
+		}
+		CONTRIBUTED.with(|v| v.borrow_mut().push((para_id, amount)));
+		Ok(())
+	}
+
+	fn withdraw(para_id: ParaId) -> DispatchResult {
+		if !BRIDGE_AVAILABLE.with(|v| *v.borrow()) {
+			return Err(DispatchError::Other("bridge unavailable"));
+		}
+		WITHDRAWN.with(|v| v.borrow_mut().push(para_id));
+		Ok(())
+	}
+}
+
+parameter_types! {
+	pub const RelaychainCurrencyId: CurrencyId = DOT;
+	pub const MinContribution: Balance = 5;
+	pub const XcmFee: Balance = 2;
+	pub const TreasuryAccount: AccountId = TREASURY;
+}
+
+ord_parameter_types! {
+	pub const Admin: AccountId = 10;
+}
+
+impl Config for Runtime {
+	type Event = Event;
+	type Currency = Tokens;
+	type RelaychainCurrencyId = RelaychainCurrencyId;
+	type MinContribution = MinContribution;
+	type Bridge = MockBridge;
+	type XcmFee = XcmFee;
+	type TreasuryAccount = TreasuryAccount;
+	type UpdateOrigin = EnsureSignedBy<Admin, AccountId>;
+	type WeightInfo = ();
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		LiquidCrowdloanModule: liquid_crowdloan::{Pallet, Call, Storage, Event<T>},
+		Tokens: orml_tokens::{Pallet, Storage, Event<T>, Config<T>},
+	}
+);
+
+pub struct ExtBuilder {
+	balances: Vec<(AccountId, CurrencyId, Balance)>,
+}
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		Self {
+			balances: vec![(ALICE, DOT, 1000), (BOB, DOT, 1000), (TREASURY, DOT, 10)],
+		}
+	}
+}
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap();
+
+		orml_tokens::GenesisConfig::<Runtime> {
+			balances: self.balances,
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		CONTRIBUTED.with(|v| v.borrow_mut().clear());
+		WITHDRAWN.with(|v| v.borrow_mut().clear());
+		BRIDGE_AVAILABLE.with(|v| *v.borrow_mut() = true);
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
@@ -0,0 +1,301 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for liquid crowdloan module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+use orml_traits::MultiCurrency;
+use sp_runtime::{traits::BadOrigin, DispatchError};
+
+#[test]
+fn open_crowdloan_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			LiquidCrowdloanModule::open_crowdloan(Origin::signed(ALICE), PARA_ID),
+			BadOrigin
+		);
+
+		assert_ok!(LiquidCrowdloanModule::open_crowdloan(
+			Origin::signed(Admin::get()),
+			PARA_ID
+		));
+		System::assert_last_event(Event::LiquidCrowdloanModule(crate::Event::CrowdloanOpened(PARA_ID)));
+		assert_eq!(
+			LiquidCrowdloanModule::status(PARA_ID),
+			Some(CrowdloanStatus::Contributing)
+		);
+		assert_noop!(
+			LiquidCrowdloanModule::open_crowdloan(Origin::signed(Admin::get()), PARA_ID),
+			Error::<Runtime>::CrowdloanAlreadyOpened
+		);
+	});
+}
+
+#[test]
+fn contribute_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			LiquidCrowdloanModule::contribute(Origin::signed(ALICE), PARA_ID, 100),
+			Error::<Runtime>::CrowdloanNotFound
+		);
+		assert_ok!(LiquidCrowdloanModule::open_crowdloan(
+			Origin::signed(Admin::get()),
+			PARA_ID
+		));
+		assert_noop!(
+			LiquidCrowdloanModule::contribute(Origin::signed(ALICE), PARA_ID, 6),
+			Error::<Runtime>::BelowMinContribution
+		);
+
+		assert_ok!(LiquidCrowdloanModule::contribute(Origin::signed(ALICE), PARA_ID, 102));
+		System::assert_last_event(Event::LiquidCrowdloanModule(crate::Event::Contributed(
+			PARA_ID, ALICE, 100,
+		)));
+		assert_ok!(LiquidCrowdloanModule::contribute(Origin::signed(ALICE), PARA_ID, 52));
+		assert_ok!(LiquidCrowdloanModule::contribute(Origin::signed(BOB), PARA_ID, 202));
+
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 846);
+		assert_eq!(Tokens::free_balance(LCDOT, &ALICE), 150);
+		assert_eq!(Tokens::free_balance(LCDOT, &BOB), 200);
+		assert_eq!(Tokens::total_issuance(DOT), 1654);
+		assert_eq!(LiquidCrowdloanModule::contributions(PARA_ID, ALICE), 150);
+		assert_eq!(LiquidCrowdloanModule::contributions(PARA_ID, BOB), 200);
+		assert_eq!(LiquidCrowdloanModule::total_contribution(PARA_ID), 350);
+		assert_eq!(
+			CONTRIBUTED.with(|v| v.borrow().clone()),
+			vec![(PARA_ID, 100), (PARA_ID, 50), (PARA_ID, 200)]
+		);
+
+		BRIDGE_AVAILABLE.with(|v| *v.borrow_mut() = false);
+		assert_noop!(
+			LiquidCrowdloanModule::contribute(Origin::signed(ALICE), PARA_ID, 100),
+			DispatchError::Other("bridge unavailable")
+		);
+		BRIDGE_AVAILABLE.with(|v| *v.borrow_mut() = true);
+
+		assert_noop!(
+			LiquidCrowdloanModule::contribute(Origin::signed(ALICE), PARA_ID, 2000),
+			orml_tokens::Error::<Runtime>::BalanceTooLow
+		);
+
+		assert_ok!(LiquidCrowdloanModule::set_status(
+			Origin::signed(Admin::get()),
+			PARA_ID,
+			CrowdloanStatus::Won
+		));
+		assert_noop!(
+			LiquidCrowdloanModule::contribute(Origin::signed(ALICE), PARA_ID, 100),
+			Error::<Runtime>::NotContributing
+		);
+	});
+}
+
+#[test]
+fn set_status_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			LiquidCrowdloanModule::set_status(Origin::signed(ALICE), PARA_ID, CrowdloanStatus::Won),
+			BadOrigin
+		);
+		assert_noop!(
+			LiquidCrowdloanModule::set_status(Origin::signed(Admin::get()), PARA_ID, CrowdloanStatus::Won),
+			Error::<Runtime>::CrowdloanNotFound
+		);
+		assert_ok!(LiquidCrowdloanModule::open_crowdloan(
+			Origin::signed(Admin::get()),
+			PARA_ID
+		));
+		assert_noop!(
+			LiquidCrowdloanModule::set_status(Origin::signed(Admin::get()), PARA_ID, CrowdloanStatus::LeaseEnded),
+			Error::<Runtime>::InvalidStatusTransition
+		);
+
+		assert_ok!(LiquidCrowdloanModule::set_status(
+			Origin::signed(Admin::get()),
+			PARA_ID,
+			CrowdloanStatus::Won
+		));
+		System::assert_last_event(Event::LiquidCrowdloanModule(crate::Event::CrowdloanStatusUpdated(
+			PARA_ID,
+			CrowdloanStatus::Won,
+		)));
+		assert_eq!(LiquidCrowdloanModule::status(PARA_ID), Some(CrowdloanStatus::Won));
+		assert_noop!(
+			LiquidCrowdloanModule::set_status(Origin::signed(Admin::get()), PARA_ID, CrowdloanStatus::Failed),
+			Error::<Runtime>::InvalidStatusTransition
+		);
+
+		assert_ok!(LiquidCrowdloanModule::set_status(
+			Origin::signed(Admin::get()),
+			PARA_ID,
+			CrowdloanStatus::LeaseEnded
+		));
+		assert_eq!(
+			LiquidCrowdloanModule::status(PARA_ID),
+			Some(CrowdloanStatus::LeaseEnded)
+		);
+	});
+}
+
+#[test]
+fn withdraw_contributions_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			LiquidCrowdloanModule::withdraw_contributions(Origin::signed(ALICE), PARA_ID),
+			BadOrigin
+		);
+		assert_noop!(
+			LiquidCrowdloanModule::withdraw_contributions(Origin::signed(Admin::get()), PARA_ID),
+			Error::<Runtime>::CrowdloanNotFound
+		);
+		assert_ok!(LiquidCrowdloanModule::open_crowdloan(
+			Origin::signed(Admin::get()),
+			PARA_ID
+		));
+
+		assert_ok!(LiquidCrowdloanModule::withdraw_contributions(
+			Origin::signed(Admin::get()),
+			PARA_ID
+		));
+		System::assert_last_event(Event::LiquidCrowdloanModule(crate::Event::WithdrawRequested(PARA_ID)));
+		assert_eq!(WITHDRAWN.with(|v| v.borrow().clone()), vec![PARA_ID]);
+		// the XCM fee is funded by treasury
+		assert_eq!(Tokens::free_balance(DOT, &TREASURY), 8);
+	});
+}
+
+#[test]
+fn redeem_at_lease_end_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(LiquidCrowdloanModule::open_crowdloan(
+			Origin::signed(Admin::get()),
+			PARA_ID
+		));
+		assert_ok!(LiquidCrowdloanModule::contribute(Origin::signed(ALICE), PARA_ID, 102));
+		assert_ok!(<Tokens as MultiCurrency<_>>::transfer(LCDOT, &ALICE, &BOB, 40));
+		assert_noop!(
+			LiquidCrowdloanModule::redeem(Origin::signed(ALICE), PARA_ID, 60),
+			Error::<Runtime>::InsufficientRedeemablePool
+		);
+
+		assert_ok!(LiquidCrowdloanModule::set_status(
+			Origin::signed(Admin::get()),
+			PARA_ID,
+			CrowdloanStatus::Won
+		));
+		assert_noop!(
+			LiquidCrowdloanModule::redeem(Origin::signed(ALICE), PARA_ID, 60),
+			Error::<Runtime>::InsufficientRedeemablePool
+		);
+
+		assert_ok!(LiquidCrowdloanModule::set_status(
+			Origin::signed(Admin::get()),
+			PARA_ID,
+			CrowdloanStatus::LeaseEnded
+		));
+		assert_eq!(LiquidCrowdloanModule::redeemable_pool(PARA_ID), 100);
+		assert_ok!(LiquidCrowdloanModule::redeem(Origin::signed(ALICE), PARA_ID, 60));
+		System::assert_last_event(Event::LiquidCrowdloanModule(crate::Event::Redeemed(PARA_ID, ALICE, 60)));
+		assert_ok!(LiquidCrowdloanModule::redeem(Origin::signed(BOB), PARA_ID, 40));
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 958);
+		assert_eq!(Tokens::free_balance(DOT, &BOB), 1040);
+		assert_eq!(Tokens::total_issuance(LCDOT), 0);
+		assert_eq!(LiquidCrowdloanModule::redeemable_pool(PARA_ID), 0);
+
+		assert_noop!(
+			LiquidCrowdloanModule::redeem(Origin::signed(ALICE), PARA_ID, 1),
+			Error::<Runtime>::InsufficientRedeemablePool
+		);
+	});
+}
+
+#[test]
+fn refund_failed_crowdloan_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(LiquidCrowdloanModule::open_crowdloan(
+			Origin::signed(Admin::get()),
+			PARA_ID
+		));
+		assert_ok!(LiquidCrowdloanModule::contribute(Origin::signed(ALICE), PARA_ID, 102));
+		assert_ok!(LiquidCrowdloanModule::set_status(
+			Origin::signed(Admin::get()),
+			PARA_ID,
+			CrowdloanStatus::Failed
+		));
+		assert_ok!(LiquidCrowdloanModule::redeem(Origin::signed(ALICE), PARA_ID, 100));
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 998);
+		assert_eq!(Tokens::free_balance(LCDOT, &ALICE), 0);
+		assert_eq!(LiquidCrowdloanModule::contributions(PARA_ID, ALICE), 100);
+	});
+}
+
+#[test]
+fn multiple_crowdloans_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(LiquidCrowdloanModule::open_crowdloan(
+			Origin::signed(Admin::get()),
+			PARA_ID
+		));
+		assert_ok!(LiquidCrowdloanModule::contribute(Origin::signed(ALICE), PARA_ID, 102));
+		assert_ok!(LiquidCrowdloanModule::set_status(
+			Origin::signed(Admin::get()),
+			PARA_ID,
+			CrowdloanStatus::Won
+		));
+
+		// the second crowdloan accepts contributions while the first one is locked.
+		assert_ok!(LiquidCrowdloanModule::open_crowdloan(
+			Origin::signed(Admin::get()),
+			PARA_ID + 1
+		));
+		assert_ok!(LiquidCrowdloanModule::contribute(Origin::signed(BOB), PARA_ID + 1, 52));
+		assert_eq!(LiquidCrowdloanModule::total_contribution(PARA_ID), 100);
+		assert_eq!(LiquidCrowdloanModule::total_contribution(PARA_ID + 1), 50);
+
+		// only the refunded contributions are redeemable.
+		assert_ok!(LiquidCrowdloanModule::set_status(
+			Origin::signed(Admin::get()),
+			PARA_ID + 1,
+			CrowdloanStatus::Failed
+		));
+		assert_eq!(LiquidCrowdloanModule::redeemable_pool(PARA_ID), 0);
+		assert_eq!(LiquidCrowdloanModule::redeemable_pool(PARA_ID + 1), 50);
+		assert_noop!(
+			LiquidCrowdloanModule::redeem(Origin::signed(ALICE), PARA_ID, 100),
+			Error::<Runtime>::InsufficientRedeemablePool
+		);
+
+		// the lcDOT of the won crowdloan can't redeem the refunds of the failed one.
+		assert_eq!(Tokens::free_balance(LCDOT, &ALICE), 100);
+		assert_noop!(
+			LiquidCrowdloanModule::redeem(Origin::signed(ALICE), PARA_ID + 1, 50),
+			orml_tokens::Error::<Runtime>::BalanceTooLow
+		);
+
+		assert_ok!(LiquidCrowdloanModule::redeem(Origin::signed(BOB), PARA_ID + 1, 50));
+		assert_eq!(
+			Tokens::free_balance(LiquidCrowdloanModule::liquid_currency_id(PARA_ID + 1), &BOB),
+			0
+		);
+		assert_eq!(LiquidCrowdloanModule::redeemable_pool(PARA_ID + 1), 0);
+	});
+}
@@ -35,7 +35,7 @@ use sp_runtime::{
 	ArithmeticError, DispatchResult, RuntimeDebug,
 };
 use sp_std::{convert::TryInto, result};
-use support::{CDPTreasury, RiskManager};
+use support::{event_topic, event_topics::kind, CDPTreasury, EventTopicsRecorder, RiskManager};
 
 mod mock;
 mod tests;
@@ -85,6 +85,9 @@ pub mod module {
 
 		/// Event handler which calls when update loan.
 		type OnUpdateLoan: Happened<(Self::AccountId, CurrencyId, Amount, Balance)>;
+
+		/// Recorder of the indexing topics of the position adjustments.
+		type EventTopics: EventTopicsRecorder;
 	}
 
 	#[pallet::error]
@@ -215,6 +218,10 @@ impl<T: Config> Pallet<T> {
 		let Position { collateral, debit } = Self::positions(currency_id, who);
 		T::RiskManager::check_position_valid(currency_id, collateral, debit)?;
 
+		T::EventTopics::record(&[
+			event_topic(kind::LOAN_ADJUSTMENT, &currency_id),
+			event_topic(kind::ACCOUNT, who),
+		]);
 		Self::deposit_event(Event::PositionUpdated(
 			who.clone(),
 			currency_id,
@@ -0,0 +1,542 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # NFT Marketplace Module
+//!
+//! ## Overview
+//!
+//! The marketplace of the tokens of `module_nft`, supports fixed-price listings, English auctions
+//! and offers. The tokens stay with their owners while listed or in auction, and are locked by
+//! the `OnNftTransfer` hook of `module_nft` until sold or cancelled. The bids and offers are
+//! reserved from the bidders until they are outbid, withdrawn or accepted. On every sale, the
+//! royalty of the class is paid to the royalty beneficiary, the `MarketplaceFee` is paid to the
+//! treasury, and the rest is paid to the seller. Only the tokens of transferable classes can be
+//! sold. If the token of an ended auction can't be delivered anymore, e.g. it's burned, the best
+//! bid is refunded on settlement.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+#![allow(clippy::type_complexity)]
+
+use frame_support::{
+	pallet_prelude::*,
+	traits::{Currency, ExistenceRequirement::KeepAlive, NamedReservableCurrency},
+	transactional,
+};
+use frame_system::pallet_prelude::*;
+use module_nft::{BalanceOf, ClassIdOf, OnNftTransfer, TokenIdOf};
+use orml_traits::NFT;
+use primitives::ReserveIdentifier;
+use sp_runtime::{
+	traits::{Saturating, Zero},
+	DispatchError, DispatchResult, Permill, RuntimeDebug,
+};
+use sp_std::prelude::*;
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+pub type TokenOf<T> = (ClassIdOf<T>, TokenIdOf<T>);
+type CurrencyOf<T> = <T as module_nft::Config>::Currency;
+
+/// The fixed-price listing of a token.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct Listing<AccountId, Balance> {
+	/// The owner of the token
+	pub seller: AccountId,
+	/// The price to buy the token
+	pub price: Balance,
+}
+
+/// The English auction of a token.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct AuctionInfo<AccountId, Balance, BlockNumber> {
+	/// The owner of the token
+	pub seller: AccountId,
+	/// The minimum amount of the first bid
+	pub min_bid: Balance,
+	/// The block number the auction ends at
+	pub end: BlockNumber,
+	/// The best bidder and bid amount
+	pub best_bid: Option<(AccountId, Balance)>,
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	pub const RESERVE_ID: ReserveIdentifier = ReserveIdentifier::NftMarketplace;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + module_nft::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The fee rate of the sale price paid to the treasury.
+		#[pallet::constant]
+		type MarketplaceFee: Get<Permill>;
+
+		/// The account to receive the marketplace fee.
+		#[pallet::constant]
+		type TreasuryAccount: Get<Self::AccountId>;
+
+		/// The minimum increment of a bid over the best bid.
+		#[pallet::constant]
+		type MinBidIncrement: Get<Permill>;
+
+		/// The minimum duration of auctions.
+		#[pallet::constant]
+		type MinAuctionDuration: Get<Self::BlockNumber>;
+
+		/// The maximum duration of auctions.
+		#[pallet::constant]
+		type MaxAuctionDuration: Get<Self::BlockNumber>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The operator is not the owner of the token
+		NoPermission,
+		/// The token is listed or in auction already
+		TokenInMarket,
+		/// The token is not listed
+		NotListed,
+		/// The token is not in auction
+		NotInAuction,
+		/// The price or amount is zero
+		InvalidAmount,
+		/// The price exceeds the maximum price of the buyer
+		PriceTooHigh,
+		/// The auction duration is out of bounds
+		InvalidDuration,
+		/// The auction has ended
+		AuctionEnded,
+		/// The auction has not ended
+		AuctionNotEnded,
+		/// The auction has bids and can not be cancelled
+		AuctionHasBid,
+		/// The bid is lower than the minimum bid
+		BidTooLow,
+		/// The offer does not exist
+		OfferNotFound,
+		/// The seller can not buy the own token
+		CannotBuyOwnToken,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Listed the token at fixed price. \[seller, class_id, token_id, price\]
+		Listed(T::AccountId, ClassIdOf<T>, TokenIdOf<T>, BalanceOf<T>),
+		/// Cancelled the listing of the token. \[seller, class_id, token_id\]
+		ListingCancelled(T::AccountId, ClassIdOf<T>, TokenIdOf<T>),
+		/// Sold the listed token. \[seller, buyer, class_id, token_id, price, royalty, fee\]
+		Sold(
+			T::AccountId,
+			T::AccountId,
+			ClassIdOf<T>,
+			TokenIdOf<T>,
+			BalanceOf<T>,
+			BalanceOf<T>,
+			BalanceOf<T>,
+		),
+		/// Created the auction of the token. \[seller, class_id, token_id, min_bid, end\]
+		AuctionCreated(T::AccountId, ClassIdOf<T>, TokenIdOf<T>, BalanceOf<T>, T::BlockNumber),
+		/// Placed a bid in the auction. \[bidder, class_id, token_id, amount\]
+		BidPlaced(T::AccountId, ClassIdOf<T>, TokenIdOf<T>, BalanceOf<T>),
+		/// Cancelled the auction without bids. \[seller, class_id, token_id\]
+		AuctionCancelled(T::AccountId, ClassIdOf<T>, TokenIdOf<T>),
+		/// Settled the auction to the best bidder. \[seller, winner, class_id, token_id, price,
+		/// royalty, fee\]
+		AuctionSettled(
+			T::AccountId,
+			T::AccountId,
+			ClassIdOf<T>,
+			TokenIdOf<T>,
+			BalanceOf<T>,
+			BalanceOf<T>,
+			BalanceOf<T>,
+		),
+		/// The auction ended without bids. \[seller, class_id, token_id\]
+		AuctionExpired(T::AccountId, ClassIdOf<T>, TokenIdOf<T>),
+		/// Made an offer for the token. \[who, class_id, token_id, amount\]
+		OfferMade(T::AccountId, ClassIdOf<T>, TokenIdOf<T>, BalanceOf<T>),
+		/// Withdrew the offer for the token. \[who, class_id, token_id, amount\]
+		OfferWithdrawn(T::AccountId, ClassIdOf<T>, TokenIdOf<T>, BalanceOf<T>),
+		/// Accepted the offer for the token. \[seller, buyer, class_id, token_id, price, royalty,
+		/// fee\]
+		OfferAccepted(
+			T::AccountId,
+			T::AccountId,
+			ClassIdOf<T>,
+			TokenIdOf<T>,
+			BalanceOf<T>,
+			BalanceOf<T>,
+			BalanceOf<T>,
+		),
+		/// The ended auction could not be settled and the best bid was refunded. \[seller,
+		/// bidder, class_id, token_id, amount\]
+		AuctionRefunded(T::AccountId, T::AccountId, ClassIdOf<T>, TokenIdOf<T>, BalanceOf<T>),
+	}
+
+	/// The fixed-price listings of tokens.
+	///
+	/// Listings: map (ClassId, TokenId) => Option<Listing>
+	#[pallet::storage]
+	#[pallet::getter(fn listings)]
+	pub type Listings<T: Config> =
+		StorageMap<_, Twox64Concat, TokenOf<T>, Listing<T::AccountId, BalanceOf<T>>, OptionQuery>;
+
+	/// The auctions of tokens.
+	///
+	/// Auctions: map (ClassId, TokenId) => Option<AuctionInfo>
+	#[pallet::storage]
+	#[pallet::getter(fn auctions)]
+	pub type Auctions<T: Config> =
+		StorageMap<_, Twox64Concat, TokenOf<T>, AuctionInfo<T::AccountId, BalanceOf<T>, T::BlockNumber>, OptionQuery>;
+
+	/// The reserved offers for tokens.
+	///
+	/// Offers: double_map (ClassId, TokenId), AccountId => Option<Balance>
+	#[pallet::storage]
+	#[pallet::getter(fn offers)]
+	pub type Offers<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, TokenOf<T>, Twox64Concat, T::AccountId, BalanceOf<T>, OptionQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// List the token at fixed price.
+		///
+		/// - `token`: (class_id, token_id)
+		/// - `price`: the price to buy the token
+		#[pallet::weight(<T as Config>::WeightInfo::list())]
+		#[transactional]
+		pub fn list(origin: OriginFor<T>, token: TokenOf<T>, price: BalanceOf<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!price.is_zero(), Error::<T>::InvalidAmount);
+			Self::ensure_can_sell(&who, token)?;
+
+			Listings::<T>::insert(
+				token,
+				Listing {
+					seller: who.clone(),
+					price,
+				},
+			);
+			Self::deposit_event(Event::Listed(who, token.0, token.1, price));
+			Ok(().into())
+		}
+
+		/// Cancel the listing of the token.
+		///
+		/// - `token`: (class_id, token_id)
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_listing())]
+		#[transactional]
+		pub fn cancel_listing(origin: OriginFor<T>, token: TokenOf<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let listing = Self::listings(token).ok_or(Error::<T>::NotListed)?;
+			ensure!(who == listing.seller, Error::<T>::NoPermission);
+
+			Listings::<T>::remove(token);
+			Self::deposit_event(Event::ListingCancelled(who, token.0, token.1));
+			Ok(().into())
+		}
+
+		/// Buy the listed token.
+		///
+		/// - `token`: (class_id, token_id)
+		/// - `max_price`: the maximum price to pay, protects the buyer against price changes
+		#[pallet::weight(<T as Config>::WeightInfo::buy())]
+		#[transactional]
+		pub fn buy(origin: OriginFor<T>, token: TokenOf<T>, max_price: BalanceOf<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let listing = Self::listings(token).ok_or(Error::<T>::NotListed)?;
+			ensure!(who != listing.seller, Error::<T>::CannotBuyOwnToken);
+			ensure!(listing.price <= max_price, Error::<T>::PriceTooHigh);
+
+			Listings::<T>::remove(token);
+			let (royalty, fee) = Self::do_sell(&listing.seller, &who, token, listing.price)?;
+
+			Self::deposit_event(Event::Sold(
+				listing.seller,
+				who,
+				token.0,
+				token.1,
+				listing.price,
+				royalty,
+				fee,
+			));
+			Ok(().into())
+		}
+
+		/// Create the English auction of the token.
+		///
+		/// - `token`: (class_id, token_id)
+		/// - `min_bid`: the minimum amount of the first bid
+		/// - `duration`: the number of blocks the auction lasts
+		#[pallet::weight(<T as Config>::WeightInfo::create_auction())]
+		#[transactional]
+		pub fn create_auction(
+			origin: OriginFor<T>,
+			token: TokenOf<T>,
+			min_bid: BalanceOf<T>,
+			duration: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!min_bid.is_zero(), Error::<T>::InvalidAmount);
+			ensure!(
+				duration >= T::MinAuctionDuration::get() && duration <= T::MaxAuctionDuration::get(),
+				Error::<T>::InvalidDuration
+			);
+			Self::ensure_can_sell(&who, token)?;
+
+			let end = frame_system::Pallet::<T>::block_number().saturating_add(duration);
+			Auctions::<T>::insert(
+				token,
+				AuctionInfo {
+					seller: who.clone(),
+					min_bid,
+					end,
+					best_bid: None,
+				},
+			);
+			Self::deposit_event(Event::AuctionCreated(who, token.0, token.1, min_bid, end));
+			Ok(().into())
+		}
+
+		/// Bid in the auction of the token, the bid is reserved until outbid or the auction is
+		/// settled.
+		///
+		/// - `token`: (class_id, token_id)
+		/// - `amount`: the bid amount
+		#[pallet::weight(<T as Config>::WeightInfo::bid())]
+		#[transactional]
+		pub fn bid(origin: OriginFor<T>, token: TokenOf<T>, amount: BalanceOf<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			Auctions::<T>::try_mutate(token, |maybe_auction| -> DispatchResult {
+				let auction = maybe_auction.as_mut().ok_or(Error::<T>::NotInAuction)?;
+				ensure!(
+					frame_system::Pallet::<T>::block_number() < auction.end,
+					Error::<T>::AuctionEnded
+				);
+				ensure!(who != auction.seller, Error::<T>::CannotBuyOwnToken);
+
+				if let Some((best_bidder, best_amount)) = auction.best_bid.take() {
+					let min_amount = best_amount.saturating_add(T::MinBidIncrement::get() * best_amount);
+					ensure!(amount > best_amount && amount >= min_amount, Error::<T>::BidTooLow);
+					CurrencyOf::<T>::unreserve_named(&RESERVE_ID, &best_bidder, best_amount);
+				} else {
+					ensure!(amount >= auction.min_bid, Error::<T>::BidTooLow);
+				}
+
+				CurrencyOf::<T>::reserve_named(&RESERVE_ID, &who, amount)?;
+				auction.best_bid = Some((who.clone(), amount));
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::BidPlaced(who, token.0, token.1, amount));
+			Ok(().into())
+		}
+
+		/// Cancel the auction of the token without bids.
+		///
+		/// - `token`: (class_id, token_id)
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_auction())]
+		#[transactional]
+		pub fn cancel_auction(origin: OriginFor<T>, token: TokenOf<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let auction = Self::auctions(token).ok_or(Error::<T>::NotInAuction)?;
+			ensure!(who == auction.seller, Error::<T>::NoPermission);
+			ensure!(auction.best_bid.is_none(), Error::<T>::AuctionHasBid);
+
+			Auctions::<T>::remove(token);
+			Self::deposit_event(Event::AuctionCancelled(who, token.0, token.1));
+			Ok(().into())
+		}
+
+		/// Settle the ended auction of the token, the token is sold to the best bidder. The best
+		/// bid is refunded if the sale fails. Can be called by anyone.
+		///
+		/// - `token`: (class_id, token_id)
+		#[pallet::weight(<T as Config>::WeightInfo::settle_auction())]
+		#[transactional]
+		pub fn settle_auction(origin: OriginFor<T>, token: TokenOf<T>) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let auction = Self::auctions(token).ok_or(Error::<T>::NotInAuction)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() >= auction.end,
+				Error::<T>::AuctionNotEnded
+			);
+
+			Auctions::<T>::remove(token);
+			if let Some((winner, amount)) = auction.best_bid {
+				CurrencyOf::<T>::unreserve_named(&RESERVE_ID, &winner, amount);
+				// the sale is reverted on failure, so the unreserved bid is refunded to the winner
+				match Self::do_sell(&auction.seller, &winner, token, amount) {
+					Ok((royalty, fee)) => Self::deposit_event(Event::AuctionSettled(
+						auction.seller,
+						winner,
+						token.0,
+						token.1,
+						amount,
+						royalty,
+						fee,
+					)),
+					Err(_) => {
+						Self::deposit_event(Event::AuctionRefunded(auction.seller, winner, token.0, token.1, amount))
+					}
+				}
+			} else {
+				Self::deposit_event(Event::AuctionExpired(auction.seller, token.0, token.1));
+			}
+			Ok(().into())
+		}
+
+		/// Make an offer for the token, the offer is reserved until withdrawn or accepted. Replaces
+		/// the previous offer of the caller.
+		///
+		/// - `token`: (class_id, token_id)
+		/// - `amount`: the offered price
+		#[pallet::weight(<T as Config>::WeightInfo::make_offer())]
+		#[transactional]
+		pub fn make_offer(origin: OriginFor<T>, token: TokenOf<T>, amount: BalanceOf<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), Error::<T>::InvalidAmount);
+			let owner = <module_nft::Pallet<T> as NFT<T::AccountId>>::owner(token)
+				.ok_or(module_nft::Error::<T>::TokenIdNotFound)?;
+			ensure!(who != owner, Error::<T>::CannotBuyOwnToken);
+
+			if let Some(previous) = Offers::<T>::take(token, &who) {
+				CurrencyOf::<T>::unreserve_named(&RESERVE_ID, &who, previous);
+			}
+			CurrencyOf::<T>::reserve_named(&RESERVE_ID, &who, amount)?;
+			Offers::<T>::insert(token, &who, amount);
+
+			Self::deposit_event(Event::OfferMade(who, token.0, token.1, amount));
+			Ok(().into())
+		}
+
+		/// Withdraw the offer for the token.
+		///
+		/// - `token`: (class_id, token_id)
+		#[pallet::weight(<T as Config>::WeightInfo::withdraw_offer())]
+		#[transactional]
+		pub fn withdraw_offer(origin: OriginFor<T>, token: TokenOf<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let amount = Offers::<T>::take(token, &who).ok_or(Error::<T>::OfferNotFound)?;
+			CurrencyOf::<T>::unreserve_named(&RESERVE_ID, &who, amount);
+
+			Self::deposit_event(Event::OfferWithdrawn(who, token.0, token.1, amount));
+			Ok(().into())
+		}
+
+		/// Accept the offer for the token, the listing of the token is cancelled. The token can
+		/// not be in auction.
+		///
+		/// - `token`: (class_id, token_id)
+		/// - `buyer`: the account who made the offer
+		#[pallet::weight(<T as Config>::WeightInfo::accept_offer())]
+		#[transactional]
+		pub fn accept_offer(
+			origin: OriginFor<T>,
+			token: TokenOf<T>,
+			buyer: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let owner = <module_nft::Pallet<T> as NFT<T::AccountId>>::owner(token)
+				.ok_or(module_nft::Error::<T>::TokenIdNotFound)?;
+			ensure!(who == owner, Error::<T>::NoPermission);
+			ensure!(!Auctions::<T>::contains_key(token), Error::<T>::TokenInMarket);
+			let amount = Offers::<T>::take(token, &buyer).ok_or(Error::<T>::OfferNotFound)?;
+
+			if Listings::<T>::take(token).is_some() {
+				Self::deposit_event(Event::ListingCancelled(who.clone(), token.0, token.1));
+			}
+
+			CurrencyOf::<T>::unreserve_named(&RESERVE_ID, &buyer, amount);
+			let (royalty, fee) = Self::do_sell(&who, &buyer, token, amount)?;
+
+			Self::deposit_event(Event::OfferAccepted(who, buyer, token.0, token.1, amount, royalty, fee));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	fn ensure_can_sell(who: &T::AccountId, token: TokenOf<T>) -> DispatchResult {
+		let owner = <module_nft::Pallet<T> as NFT<T::AccountId>>::owner(token)
+			.ok_or(module_nft::Error::<T>::TokenIdNotFound)?;
+		ensure!(*who == owner, Error::<T>::NoPermission);
+		ensure!(!Self::is_in_market(token), Error::<T>::TokenInMarket);
+		module_nft::Pallet::<T>::ensure_transferable(token.0)?;
+		Ok(())
+	}
+
+	/// Whether the token is listed or in auction.
+	pub fn is_in_market(token: TokenOf<T>) -> bool {
+		Listings::<T>::contains_key(token) || Auctions::<T>::contains_key(token)
+	}
+
+	/// Transfer `token` from `seller` to `buyer`, and pay `price` from `buyer`: the royalty to the
+	/// royalty beneficiary, the fee to the treasury and the rest to `seller`. Returns the royalty
+	/// and the fee. The token must be removed from the market before.
+	///
+	/// Ensured atomic.
+	#[transactional]
+	fn do_sell(
+		seller: &T::AccountId,
+		buyer: &T::AccountId,
+		token: TokenOf<T>,
+		price: BalanceOf<T>,
+	) -> Result<(BalanceOf<T>, BalanceOf<T>), DispatchError> {
+		let royalty = module_nft::Pallet::<T>::pay_royalty(buyer, token, price)?;
+		let fee = (T::MarketplaceFee::get() * price).min(price.saturating_sub(royalty));
+		if !fee.is_zero() {
+			CurrencyOf::<T>::transfer(buyer, &T::TreasuryAccount::get(), fee, KeepAlive)?;
+		}
+		CurrencyOf::<T>::transfer(
+			buyer,
+			seller,
+			price.saturating_sub(royalty).saturating_sub(fee),
+			KeepAlive,
+		)?;
+
+		<module_nft::Pallet<T> as NFT<T::AccountId>>::transfer(seller, buyer, token)?;
+		Ok((royalty, fee))
+	}
+}
+
+impl<T: Config> OnNftTransfer<T::AccountId, ClassIdOf<T>, TokenIdOf<T>> for Pallet<T> {
+	fn on_nft_transfer(_from: &T::AccountId, _to: &T::AccountId, token: TokenOf<T>) -> DispatchResult {
+		ensure!(!Self::is_in_market(token), Error::<T>::TokenInMarket);
+		Ok(())
+	}
+}
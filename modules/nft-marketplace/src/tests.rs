@@ -0,0 +1,372 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for nft marketplace module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+use primitives::Balance;
+use sp_runtime::traits::AccountIdConversion;
+
+fn free_balance(who: &AccountId) -> Balance {
+	Balances::free_balance(who)
+}
+
+fn reserved_bid(who: &AccountId) -> Balance {
+	Balances::reserved_balance_named(&RESERVE_ID, who)
+}
+
+fn owner() -> Option<AccountId> {
+	<NFTModule as NFT<AccountId>>::owner(TOKEN)
+}
+
+#[test]
+fn list_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			NFTMarketplace::list(Origin::signed(BOB), TOKEN, 1000),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			NFTMarketplace::list(Origin::signed(ALICE), TOKEN, 0),
+			Error::<Runtime>::InvalidAmount
+		);
+		assert_noop!(
+			NFTMarketplace::list(Origin::signed(ALICE), (CLASS_ID, 1), 1000),
+			module_nft::Error::<Runtime>::TokenIdNotFound
+		);
+
+		assert_ok!(NFTMarketplace::list(Origin::signed(ALICE), TOKEN, 1000));
+		System::assert_last_event(Event::NFTMarketplace(crate::Event::Listed(
+			ALICE, CLASS_ID, TOKEN_ID, 1000,
+		)));
+		assert_eq!(
+			NFTMarketplace::listings(TOKEN),
+			Some(Listing {
+				seller: ALICE,
+				price: 1000
+			})
+		);
+
+		assert_noop!(
+			NFTMarketplace::list(Origin::signed(ALICE), TOKEN, 1000),
+			Error::<Runtime>::TokenInMarket
+		);
+		assert_noop!(
+			NFTMarketplace::create_auction(Origin::signed(ALICE), TOKEN, 1000, 10),
+			Error::<Runtime>::TokenInMarket
+		);
+		// the listed token is locked
+		assert_noop!(
+			NFTModule::transfer(Origin::signed(ALICE), BOB, TOKEN),
+			Error::<Runtime>::TokenInMarket
+		);
+
+		assert_noop!(
+			NFTMarketplace::cancel_listing(Origin::signed(BOB), TOKEN),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(NFTMarketplace::cancel_listing(Origin::signed(ALICE), TOKEN));
+		System::assert_last_event(Event::NFTMarketplace(crate::Event::ListingCancelled(
+			ALICE, CLASS_ID, TOKEN_ID,
+		)));
+		assert_eq!(NFTMarketplace::listings(TOKEN), None);
+		assert_noop!(
+			NFTMarketplace::cancel_listing(Origin::signed(ALICE), TOKEN),
+			Error::<Runtime>::NotListed
+		);
+		assert_ok!(NFTModule::transfer(Origin::signed(ALICE), BOB, TOKEN));
+	});
+}
+
+#[test]
+fn buy_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			NFTMarketplace::buy(Origin::signed(BOB), TOKEN, 1000),
+			Error::<Runtime>::NotListed
+		);
+		assert_ok!(NFTMarketplace::list(Origin::signed(ALICE), TOKEN, 1000));
+
+		assert_noop!(
+			NFTMarketplace::buy(Origin::signed(ALICE), TOKEN, 1000),
+			Error::<Runtime>::CannotBuyOwnToken
+		);
+		assert_noop!(
+			NFTMarketplace::buy(Origin::signed(BOB), TOKEN, 999),
+			Error::<Runtime>::PriceTooHigh
+		);
+
+		let alice_balance = free_balance(&ALICE);
+		let bob_balance = free_balance(&BOB);
+		assert_ok!(NFTMarketplace::buy(Origin::signed(BOB), TOKEN, 1000));
+		System::assert_last_event(Event::NFTMarketplace(crate::Event::Sold(
+			ALICE, BOB, CLASS_ID, TOKEN_ID, 1000, 50, 20,
+		)));
+		assert_eq!(NFTMarketplace::listings(TOKEN), None);
+		assert_eq!(owner(), Some(BOB));
+		assert_eq!(free_balance(&BOB), bob_balance - 1000);
+		assert_eq!(free_balance(&ALICE), alice_balance + 930);
+		assert_eq!(free_balance(&ROYALTY_BENEFICIARY), 50);
+		assert_eq!(free_balance(&TREASURY), 1 + 20);
+	});
+}
+
+#[test]
+fn auction_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			NFTMarketplace::create_auction(Origin::signed(ALICE), TOKEN, 1000, 9),
+			Error::<Runtime>::InvalidDuration
+		);
+		assert_noop!(
+			NFTMarketplace::create_auction(Origin::signed(ALICE), TOKEN, 1000, 101),
+			Error::<Runtime>::InvalidDuration
+		);
+		assert_noop!(
+			NFTMarketplace::create_auction(Origin::signed(BOB), TOKEN, 1000, 20),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(NFTMarketplace::create_auction(Origin::signed(ALICE), TOKEN, 1000, 20));
+		System::assert_last_event(Event::NFTMarketplace(crate::Event::AuctionCreated(
+			ALICE, CLASS_ID, TOKEN_ID, 1000, 21,
+		)));
+		assert_noop!(
+			NFTModule::transfer(Origin::signed(ALICE), BOB, TOKEN),
+			Error::<Runtime>::TokenInMarket
+		);
+
+		assert_noop!(
+			NFTMarketplace::bid(Origin::signed(ALICE), TOKEN, 1000),
+			Error::<Runtime>::CannotBuyOwnToken
+		);
+		assert_noop!(
+			NFTMarketplace::bid(Origin::signed(BOB), TOKEN, 999),
+			Error::<Runtime>::BidTooLow
+		);
+		assert_ok!(NFTMarketplace::bid(Origin::signed(BOB), TOKEN, 1000));
+		System::assert_last_event(Event::NFTMarketplace(crate::Event::BidPlaced(
+			BOB, CLASS_ID, TOKEN_ID, 1000,
+		)));
+		assert_eq!(reserved_bid(&BOB), 1000);
+
+		// the bid must exceed the best bid by `MinBidIncrement`
+		assert_noop!(
+			NFTMarketplace::bid(Origin::signed(CHARLIE), TOKEN, 1099),
+			Error::<Runtime>::BidTooLow
+		);
+		assert_ok!(NFTMarketplace::bid(Origin::signed(CHARLIE), TOKEN, 1100));
+		assert_eq!(reserved_bid(&BOB), 0);
+		assert_eq!(reserved_bid(&CHARLIE), 1100);
+		assert_eq!(NFTMarketplace::auctions(TOKEN).unwrap().best_bid, Some((CHARLIE, 1100)));
+
+		assert_noop!(
+			NFTMarketplace::cancel_auction(Origin::signed(ALICE), TOKEN),
+			Error::<Runtime>::AuctionHasBid
+		);
+		assert_noop!(
+			NFTMarketplace::settle_auction(Origin::signed(BOB), TOKEN),
+			Error::<Runtime>::AuctionNotEnded
+		);
+
+		System::set_block_number(21);
+		assert_noop!(
+			NFTMarketplace::bid(Origin::signed(BOB), TOKEN, 2000),
+			Error::<Runtime>::AuctionEnded
+		);
+
+		let alice_balance = free_balance(&ALICE);
+		let charlie_balance = free_balance(&CHARLIE);
+		assert_ok!(NFTMarketplace::settle_auction(Origin::signed(BOB), TOKEN));
+		System::assert_last_event(Event::NFTMarketplace(crate::Event::AuctionSettled(
+			ALICE, CHARLIE, CLASS_ID, TOKEN_ID, 1100, 55, 22,
+		)));
+		assert_eq!(NFTMarketplace::auctions(TOKEN), None);
+		assert_eq!(owner(), Some(CHARLIE));
+		assert_eq!(reserved_bid(&CHARLIE), 0);
+		assert_eq!(free_balance(&CHARLIE), charlie_balance);
+		assert_eq!(free_balance(&ALICE), alice_balance + 1023);
+		assert_eq!(free_balance(&ROYALTY_BENEFICIARY), 55);
+		assert_eq!(free_balance(&TREASURY), 1 + 22);
+	});
+}
+
+#[test]
+fn auction_without_bids_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTMarketplace::create_auction(Origin::signed(ALICE), TOKEN, 1000, 20));
+		assert_noop!(
+			NFTMarketplace::cancel_auction(Origin::signed(BOB), TOKEN),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(NFTMarketplace::cancel_auction(Origin::signed(ALICE), TOKEN));
+		System::assert_last_event(Event::NFTMarketplace(crate::Event::AuctionCancelled(
+			ALICE, CLASS_ID, TOKEN_ID,
+		)));
+		assert_eq!(NFTMarketplace::auctions(TOKEN), None);
+
+		assert_ok!(NFTMarketplace::create_auction(Origin::signed(ALICE), TOKEN, 1000, 20));
+		System::set_block_number(21);
+		assert_ok!(NFTMarketplace::settle_auction(Origin::signed(BOB), TOKEN));
+		System::assert_last_event(Event::NFTMarketplace(crate::Event::AuctionExpired(
+			ALICE, CLASS_ID, TOKEN_ID,
+		)));
+		assert_eq!(NFTMarketplace::auctions(TOKEN), None);
+		assert_eq!(owner(), Some(ALICE));
+		assert_noop!(
+			NFTMarketplace::settle_auction(Origin::signed(BOB), TOKEN),
+			Error::<Runtime>::NotInAuction
+		);
+	});
+}
+
+#[test]
+fn offer_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			NFTMarketplace::make_offer(Origin::signed(ALICE), TOKEN, 1000),
+			Error::<Runtime>::CannotBuyOwnToken
+		);
+		assert_noop!(
+			NFTMarketplace::make_offer(Origin::signed(BOB), TOKEN, 0),
+			Error::<Runtime>::InvalidAmount
+		);
+
+		assert_ok!(NFTMarketplace::make_offer(Origin::signed(BOB), TOKEN, 1000));
+		System::assert_last_event(Event::NFTMarketplace(crate::Event::OfferMade(
+			BOB, CLASS_ID, TOKEN_ID, 1000,
+		)));
+		assert_eq!(reserved_bid(&BOB), 1000);
+
+		// replace the previous offer
+		assert_ok!(NFTMarketplace::make_offer(Origin::signed(BOB), TOKEN, 800));
+		assert_eq!(reserved_bid(&BOB), 800);
+		assert_eq!(NFTMarketplace::offers(TOKEN, BOB), Some(800));
+
+		assert_ok!(NFTMarketplace::make_offer(Origin::signed(CHARLIE), TOKEN, 1200));
+		assert_ok!(NFTMarketplace::withdraw_offer(Origin::signed(CHARLIE), TOKEN));
+		System::assert_last_event(Event::NFTMarketplace(crate::Event::OfferWithdrawn(
+			CHARLIE, CLASS_ID, TOKEN_ID, 1200,
+		)));
+		assert_eq!(reserved_bid(&CHARLIE), 0);
+		assert_noop!(
+			NFTMarketplace::withdraw_offer(Origin::signed(CHARLIE), TOKEN),
+			Error::<Runtime>::OfferNotFound
+		);
+
+		assert_noop!(
+			NFTMarketplace::accept_offer(Origin::signed(BOB), TOKEN, BOB),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			NFTMarketplace::accept_offer(Origin::signed(ALICE), TOKEN, CHARLIE),
+			Error::<Runtime>::OfferNotFound
+		);
+
+		// the listing is cancelled by accepting the offer
+		assert_ok!(NFTMarketplace::list(Origin::signed(ALICE), TOKEN, 2000));
+		let alice_balance = free_balance(&ALICE);
+		let bob_balance = free_balance(&BOB);
+		assert_ok!(NFTMarketplace::accept_offer(Origin::signed(ALICE), TOKEN, BOB));
+		System::assert_last_event(Event::NFTMarketplace(crate::Event::OfferAccepted(
+			ALICE, BOB, CLASS_ID, TOKEN_ID, 800, 40, 16,
+		)));
+		assert_eq!(NFTMarketplace::listings(TOKEN), None);
+		assert_eq!(NFTMarketplace::offers(TOKEN, BOB), None);
+		assert_eq!(owner(), Some(BOB));
+		assert_eq!(reserved_bid(&BOB), 0);
+		assert_eq!(free_balance(&BOB), bob_balance);
+		assert_eq!(free_balance(&ALICE), alice_balance + 744);
+		assert_eq!(free_balance(&ROYALTY_BENEFICIARY), 40);
+		assert_eq!(free_balance(&TREASURY), 1 + 16);
+	});
+}
+
+#[test]
+fn accept_offer_should_fail_in_auction() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTMarketplace::make_offer(Origin::signed(BOB), TOKEN, 1000));
+		assert_ok!(NFTMarketplace::create_auction(Origin::signed(ALICE), TOKEN, 1000, 20));
+		assert_noop!(
+			NFTMarketplace::accept_offer(Origin::signed(ALICE), TOKEN, BOB),
+			Error::<Runtime>::TokenInMarket
+		);
+	});
+}
+
+#[test]
+fn should_not_sell_non_transferable_token() {
+	ExtBuilder::default().build().execute_with(|| {
+		let class_id = CLASS_ID + 1;
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			module_nft::Properties(module_nft::ClassProperty::Burnable.into())
+		));
+		let class_account: AccountId = NftPalletId::get().into_sub_account(class_id);
+		assert_ok!(Balances::transfer(
+			Origin::signed(ALICE),
+			class_account.clone(),
+			CreateTokenDeposit::get()
+		));
+		assert_ok!(NFTModule::mint(
+			Origin::signed(class_account),
+			ALICE,
+			class_id,
+			vec![1],
+			1
+		));
+
+		assert_noop!(
+			NFTMarketplace::list(Origin::signed(ALICE), (class_id, 0), 1000),
+			module_nft::Error::<Runtime>::NonTransferable
+		);
+		assert_noop!(
+			NFTMarketplace::create_auction(Origin::signed(ALICE), (class_id, 0), 1000, 20),
+			module_nft::Error::<Runtime>::NonTransferable
+		);
+	});
+}
+
+#[test]
+fn settle_auction_should_refund_undeliverable_token() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTMarketplace::create_auction(Origin::signed(ALICE), TOKEN, 1000, 20));
+		assert_ok!(NFTMarketplace::bid(Origin::signed(BOB), TOKEN, 1000));
+		assert_eq!(reserved_bid(&BOB), 1000);
+
+		// the token can't be delivered anymore
+		assert_ok!(NFTModule::burn(Origin::signed(ALICE), TOKEN));
+
+		System::set_block_number(21);
+		let alice_balance = free_balance(&ALICE);
+		let bob_balance = free_balance(&BOB);
+		assert_ok!(NFTMarketplace::settle_auction(Origin::signed(CHARLIE), TOKEN));
+		System::assert_last_event(Event::NFTMarketplace(crate::Event::AuctionRefunded(
+			ALICE, BOB, CLASS_ID, TOKEN_ID, 1000,
+		)));
+		assert_eq!(NFTMarketplace::auctions(TOKEN), None);
+		assert_eq!(reserved_bid(&BOB), 0);
+		assert_eq!(free_balance(&BOB), bob_balance + 1000);
+		assert_eq!(free_balance(&ALICE), alice_balance);
+		assert_eq!(free_balance(&ROYALTY_BENEFICIARY), 0);
+	});
+}
@@ -125,6 +125,115 @@ benchmarks! {
 		let module_account: T::AccountId = T::PalletId::get().into_sub_account(orml_nft::Pallet::<T>::next_class_id());
 		crate::Pallet::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable))?;
 	}: _(RawOrigin::Signed(module_account), 0u32.into(), caller_lookup)
+
+	// set the royalty of NFT class
+	set_class_royalty {
+		let caller: T::AccountId = account("caller", 0, SEED);
+
+		let base_currency_amount = dollar(1000);
+
+		<T as module::Config>::Currency::make_free_balance_be(&caller, base_currency_amount.unique_saturated_into());
+
+		let module_account: T::AccountId = T::PalletId::get().into_sub_account(orml_nft::Pallet::<T>::next_class_id());
+		crate::Pallet::<T>::create_class(RawOrigin::Signed(caller.clone()).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable))?;
+	}: _(RawOrigin::Signed(module_account), 0u32.into(), Some(RoyaltyInfo { beneficiary: caller, basis_points: 500 }))
+
+	// set the attribute updater of NFT class
+	set_attribute_updater {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let updater: T::AccountId = account("updater", 0, SEED);
+
+		let base_currency_amount = dollar(1000);
+
+		<T as module::Config>::Currency::make_free_balance_be(&caller, base_currency_amount.unique_saturated_into());
+
+		let module_account: T::AccountId = T::PalletId::get().into_sub_account(orml_nft::Pallet::<T>::next_class_id());
+		crate::Pallet::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mutable))?;
+	}: _(RawOrigin::Signed(module_account), 0u32.into(), Some(updater))
+
+	// set the attribute of NFT token, replaces the previous value
+	set_token_attribute {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let to: T::AccountId = account("to", 0, SEED);
+		let to_lookup = T::Lookup::unlookup(to);
+
+		let base_currency_amount = dollar(1000);
+		<T as module::Config>::Currency::make_free_balance_be(&caller, base_currency_amount.unique_saturated_into());
+
+		let module_account: T::AccountId = T::PalletId::get().into_sub_account(orml_nft::Pallet::<T>::next_class_id());
+		crate::Pallet::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mutable))?;
+		<T as module::Config>::Currency::make_free_balance_be(&module_account, base_currency_amount.unique_saturated_into());
+		crate::Pallet::<T>::mint(RawOrigin::Signed(module_account.clone()).into(), to_lookup, 0u32.into(), vec![1], 1)?;
+
+		let key = vec![1; T::MaxAttributeKeyLength::get() as usize];
+		let value = vec![1; T::MaxAttributeValueLength::get() as usize];
+		crate::Pallet::<T>::set_token_attribute(RawOrigin::Signed(module_account.clone()).into(), (0u32.into(), 0u32.into()), key.clone(), Some(value.clone()))?;
+	}: _(RawOrigin::Signed(module_account), (0u32.into(), 0u32.into()), key, Some(value))
+
+	// set the lock of NFT class
+	set_class_lock {
+		let caller: T::AccountId = account("caller", 0, SEED);
+
+		let base_currency_amount = dollar(1000);
+
+		<T as module::Config>::Currency::make_free_balance_be(&caller, base_currency_amount.unique_saturated_into());
+
+		let module_account: T::AccountId = T::PalletId::get().into_sub_account(orml_nft::Pallet::<T>::next_class_id());
+		crate::Pallet::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable))?;
+	}: _(RawOrigin::Signed(module_account), 0u32.into(), Some(LockedUntil::BlockNumber(100u32.into())))
+
+	// mint NFT tokens to multiple recipients
+	batch_mint {
+		let i in 1 .. 1000;
+
+		let caller: T::AccountId = account("caller", 0, SEED);
+
+		let base_currency_amount = dollar(1000);
+		<T as module::Config>::Currency::make_free_balance_be(&caller, base_currency_amount.unique_saturated_into());
+
+		let module_account: T::AccountId = T::PalletId::get().into_sub_account(orml_nft::Pallet::<T>::next_class_id());
+		crate::Pallet::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable))?;
+		<T as module::Config>::Currency::make_free_balance_be(&module_account, base_currency_amount.unique_saturated_into());
+
+		let mut tokens = vec![];
+		for j in 0..i {
+			let to: T::AccountId = account("to", j, SEED);
+			<T as module::Config>::Currency::make_free_balance_be(&to, base_currency_amount.unique_saturated_into());
+			tokens.push((T::Lookup::unlookup(to), vec![1]));
+		}
+	}: _(RawOrigin::Signed(module_account), 0u32.into(), tokens)
+
+	// set the merkle root of the claims of NFT class
+	set_claim_root {
+		let caller: T::AccountId = account("caller", 0, SEED);
+
+		let base_currency_amount = dollar(1000);
+		<T as module::Config>::Currency::make_free_balance_be(&caller, base_currency_amount.unique_saturated_into());
+
+		let module_account: T::AccountId = T::PalletId::get().into_sub_account(orml_nft::Pallet::<T>::next_class_id());
+		crate::Pallet::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable))?;
+	}: _(RawOrigin::Signed(module_account), 0u32.into(), Some(Default::default()))
+
+	// claim NFT token with the merkle proof
+	claim {
+		let p in 0 .. 32;
+
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let to: T::AccountId = account("to", 0, SEED);
+
+		let base_currency_amount = dollar(1000);
+		<T as module::Config>::Currency::make_free_balance_be(&caller, base_currency_amount.unique_saturated_into());
+		<T as module::Config>::Currency::make_free_balance_be(&to, base_currency_amount.unique_saturated_into());
+
+		let module_account: T::AccountId = T::PalletId::get().into_sub_account(orml_nft::Pallet::<T>::next_class_id());
+		crate::Pallet::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable))?;
+		<T as module::Config>::Currency::make_free_balance_be(&module_account, base_currency_amount.unique_saturated_into());
+
+		let proof: Vec<T::Hash> = (0..p).map(|j| T::Hashing::hash_of(&j)).collect();
+		let leaf = T::Hashing::hash_of(&(&to, &vec![1u8]));
+		let root = crate::Pallet::<T>::merkle_root(leaf, &proof);
+		crate::Pallet::<T>::set_claim_root(RawOrigin::Signed(module_account).into(), 0u32.into(), Some(root))?;
+	}: _(RawOrigin::Signed(to), 0u32.into(), vec![1], proof)
 }
 
 #[cfg(test)]
@@ -257,11 +366,24 @@ mod mock {
 		type AnnouncementDepositFactor = AnnouncementDepositFactor;
 	}
 
+	parameter_types! {
+		pub const MinimumPeriod: u64 = 1000;
+	}
+
+	impl pallet_timestamp::Config for Runtime {
+		type Moment = u64;
+		type OnTimestampSet = ();
+		type MinimumPeriod = MinimumPeriod;
+		type WeightInfo = ();
+	}
+
 	parameter_types! {
 		pub const CreateClassDeposit: Balance = 200;
 		pub const CreateTokenDeposit: Balance = 100;
 		pub const DataDepositPerByte: Balance = 10;
 		pub const NftPalletId: PalletId = PalletId(*b"aca/aNFT");
+		pub const MaxAttributeKeyLength: u32 = 64;
+		pub const MaxAttributeValueLength: u32 = 1024;
 	}
 	impl crate::Config for Runtime {
 		type Event = ();
@@ -270,6 +392,10 @@ mod mock {
 		type CreateTokenDeposit = CreateTokenDeposit;
 		type DataDepositPerByte = DataDepositPerByte;
 		type PalletId = NftPalletId;
+		type MaxAttributeKeyLength = MaxAttributeKeyLength;
+		type MaxAttributeValueLength = MaxAttributeValueLength;
+		type UnixTime = Timestamp;
+		type OnNftTransfer = ();
 		type WeightInfo = ();
 	}
 
@@ -300,6 +426,7 @@ mod mock {
 			Utility: pallet_utility::{Pallet, Call, Event},
 			Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 			Proxy: pallet_proxy::{Pallet, Call, Storage, Event<T>},
+			Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
 			OrmlNFT: orml_nft::{Pallet, Storage, Config<T>},
 			NFT: nft::{Pallet, Call, Event<T>},
 		}
@@ -365,4 +492,53 @@ mod tests {
 			assert_ok!(test_benchmark_destroy_class::<Runtime>());
 		});
 	}
+
+	#[test]
+	fn test_set_class_royalty() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_class_royalty::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn test_set_attribute_updater() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_attribute_updater::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn test_set_token_attribute() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_token_attribute::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn test_set_class_lock() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_class_lock::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn test_batch_mint() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_batch_mint::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn test_set_claim_root() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_claim_root::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn test_claim() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_claim::<Runtime>());
+		});
+	}
 }
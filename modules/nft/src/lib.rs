@@ -27,7 +27,7 @@ use frame_support::{
 	traits::{
 		Currency,
 		ExistenceRequirement::{AllowDeath, KeepAlive},
-		NamedReservableCurrency,
+		NamedReservableCurrency, UnixTime,
 	},
 	transactional, PalletId,
 };
@@ -37,8 +37,8 @@ use primitives::{NFTBalance, ReserveIdentifier};
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use sp_runtime::{
-	traits::{AccountIdConversion, Hash, Saturating, StaticLookup, Zero},
-	DispatchResult, RuntimeDebug,
+	traits::{AccountIdConversion, AtLeast32BitUnsigned, Hash, Saturating, StaticLookup, Zero},
+	DispatchError, DispatchResult, Permill, RuntimeDebug,
 };
 use sp_std::vec::Vec;
 
@@ -59,6 +59,10 @@ pub enum ClassProperty {
 	Transferable = 0b00000001,
 	/// Token can be burned
 	Burnable = 0b00000010,
+	/// Token attributes can be updated after minted
+	Mutable = 0b00000100,
+	/// Token is bound to its owner and can't be transferred, even if `Transferable`
+	Soulbound = 0b00001000,
 }
 
 #[derive(Clone, Copy, PartialEq, Default, RuntimeDebug)]
@@ -96,6 +100,58 @@ pub struct TokenData<Balance> {
 	pub deposit: Balance,
 }
 
+/// The attribute value of a token, with the deposit reserved from `depositor` for its storage.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct AttributeInfo<AccountId, Balance> {
+	/// The attribute value
+	pub value: Vec<u8>,
+	/// The account who reserved the deposit
+	pub depositor: AccountId,
+	/// The reserved deposit
+	pub deposit: Balance,
+}
+
+/// The royalty paid to `beneficiary` on the sales of the tokens of a class.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct RoyaltyInfo<AccountId> {
+	/// The receiver of the royalty
+	pub beneficiary: AccountId,
+	/// The royalty rate of the sale price, in basis points
+	pub basis_points: u16,
+}
+
+impl<AccountId> RoyaltyInfo<AccountId> {
+	/// The royalty of a sale at `price`.
+	pub fn royalty<Balance: AtLeast32BitUnsigned>(&self, price: Balance) -> Balance {
+		Permill::from_parts(u32::from(self.basis_points).saturating_mul(100)) * price
+	}
+}
+
+/// The time until which the tokens of a class can't be transferred or burned.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum LockedUntil<BlockNumber> {
+	/// Locked until the block number
+	BlockNumber(BlockNumber),
+	/// Locked until the unix timestamp, in seconds
+	Timestamp(u64),
+}
+
+/// Handler for the transfers of NFT tokens, e.g. a marketplace can reject the transfers of
+/// the tokens that are listed on it.
+pub trait OnNftTransfer<AccountId, ClassId, TokenId> {
+	/// Called before `token` is transferred from `from` to `to`, the transfer fails if an
+	/// error is returned.
+	fn on_nft_transfer(from: &AccountId, to: &AccountId, token: (ClassId, TokenId)) -> DispatchResult;
+}
+
+impl<AccountId, ClassId, TokenId> OnNftTransfer<AccountId, ClassId, TokenId> for () {
+	fn on_nft_transfer(_from: &AccountId, _to: &AccountId, _token: (ClassId, TokenId)) -> DispatchResult {
+		Ok(())
+	}
+}
+
 pub type TokenIdOf<T> = <T as orml_nft::Config>::TokenId;
 pub type ClassIdOf<T> = <T as orml_nft::Config>::ClassId;
 pub type BalanceOf<T> =
@@ -106,6 +162,9 @@ pub mod module {
 	use super::*;
 
 	pub const RESERVE_ID: ReserveIdentifier = ReserveIdentifier::Nft;
+	pub const MAX_ROYALTY_BASIS_POINTS: u16 = 10_000;
+	/// The attribute key of the token URI, used by the ERC-721 mirror of the class.
+	pub const TOKEN_URI_ATTRIBUTE_KEY: &[u8] = b"tokenURI";
 
 	#[pallet::config]
 	pub trait Config:
@@ -138,6 +197,20 @@ pub mod module {
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
 
+		/// The maximum length of token attribute keys
+		#[pallet::constant]
+		type MaxAttributeKeyLength: Get<u32>;
+
+		/// The maximum length of token attribute values
+		#[pallet::constant]
+		type MaxAttributeValueLength: Get<u32>;
+
+		/// Time used for the timestamp locks of classes
+		type UnixTime: UnixTime;
+
+		/// The handler for the transfers of tokens
+		type OnNftTransfer: OnNftTransfer<Self::AccountId, ClassIdOf<Self>, TokenIdOf<Self>>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -159,6 +232,26 @@ pub mod module {
 		/// Can not destroy class
 		/// Total issuance is not 0
 		CannotDestroyClass,
+		/// The royalty rate exceeds 100%
+		InvalidRoyaltyRate,
+		/// Property of class don't support attribute updates
+		NonMutable,
+		/// The attribute key is too long
+		AttributeKeyTooLong,
+		/// The attribute value is too long
+		AttributeValueTooLong,
+		/// Property of class is soulbound, tokens can't be transferred
+		Soulbound,
+		/// The tokens of class are locked
+		ClassLocked,
+		/// The lock of class can only be set before any token is minted
+		CannotLockIssuedClass,
+		/// The class has no claim root
+		ClaimRootNotFound,
+		/// The merkle proof of the claim is invalid
+		InvalidClaimProof,
+		/// The token has been claimed
+		AlreadyClaimed,
 	}
 
 	#[pallet::event]
@@ -176,8 +269,86 @@ pub mod module {
 		BurnedTokenWithRemark(T::AccountId, ClassIdOf<T>, TokenIdOf<T>, T::Hash),
 		/// Destroyed NFT class. \[owner, class_id\]
 		DestroyedClass(T::AccountId, ClassIdOf<T>),
+		/// Updated the royalty of NFT class. \[class_id, royalty\]
+		ClassRoyaltyUpdated(ClassIdOf<T>, Option<RoyaltyInfo<T::AccountId>>),
+		/// Paid the royalty of the sale of NFT token. \[class_id, token_id, payer, beneficiary,
+		/// price, royalty_amount\]
+		RoyaltyPaid(
+			ClassIdOf<T>,
+			TokenIdOf<T>,
+			T::AccountId,
+			T::AccountId,
+			BalanceOf<T>,
+			BalanceOf<T>,
+		),
+		/// Set the attribute updater of NFT class. \[class_id, updater\]
+		AttributeUpdaterSet(ClassIdOf<T>, Option<T::AccountId>),
+		/// Updated the attribute of NFT token. \[who, class_id, token_id, key, value\]
+		TokenAttributeUpdated(T::AccountId, ClassIdOf<T>, TokenIdOf<T>, Vec<u8>, Option<Vec<u8>>),
+		/// Updated the lock of NFT class. \[class_id, locked_until\]
+		ClassLockUpdated(ClassIdOf<T>, Option<LockedUntil<T::BlockNumber>>),
+		/// Minted NFT tokens to multiple recipients. \[owner, class_id, quantity,
+		/// failed_indexes\]
+		BatchMinted(T::AccountId, ClassIdOf<T>, u32, Vec<u32>),
+		/// Updated the merkle root of the claims of NFT class. \[class_id, root\]
+		ClaimRootUpdated(ClassIdOf<T>, Option<T::Hash>),
+		/// Claimed NFT token. \[who, class_id, leaf\]
+		ClaimedToken(T::AccountId, ClassIdOf<T>, T::Hash),
 	}
 
+	/// The royalty of the sales of the tokens of the class.
+	///
+	/// ClassRoyalties: map ClassId => Option<RoyaltyInfo>
+	#[pallet::storage]
+	#[pallet::getter(fn class_royalties)]
+	pub type ClassRoyalties<T: Config> =
+		StorageMap<_, Twox64Concat, ClassIdOf<T>, RoyaltyInfo<T::AccountId>, OptionQuery>;
+
+	/// The account allowed to update the token attributes of the class, besides the class owner.
+	///
+	/// AttributeUpdaters: map ClassId => Option<AccountId>
+	#[pallet::storage]
+	#[pallet::getter(fn attribute_updaters)]
+	pub type AttributeUpdaters<T: Config> = StorageMap<_, Twox64Concat, ClassIdOf<T>, T::AccountId, OptionQuery>;
+
+	/// The attributes of tokens.
+	///
+	/// TokenAttributes: double_map (ClassId, TokenId), Key => Option<AttributeInfo>
+	#[pallet::storage]
+	#[pallet::getter(fn token_attributes)]
+	pub type TokenAttributes<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		(ClassIdOf<T>, TokenIdOf<T>),
+		Blake2_128Concat,
+		Vec<u8>,
+		AttributeInfo<T::AccountId, BalanceOf<T>>,
+		OptionQuery,
+	>;
+
+	/// The time until which the tokens of the class are locked.
+	///
+	/// ClassLocks: map ClassId => Option<LockedUntil>
+	#[pallet::storage]
+	#[pallet::getter(fn class_locks)]
+	pub type ClassLocks<T: Config> =
+		StorageMap<_, Twox64Concat, ClassIdOf<T>, LockedUntil<T::BlockNumber>, OptionQuery>;
+
+	/// The merkle root of the tokens of the class that can be claimed, each leaf is the hash of
+	/// `(recipient, metadata)`.
+	///
+	/// ClaimRoots: map ClassId => Option<Hash>
+	#[pallet::storage]
+	#[pallet::getter(fn claim_roots)]
+	pub type ClaimRoots<T: Config> = StorageMap<_, Twox64Concat, ClassIdOf<T>, T::Hash, OptionQuery>;
+
+	/// The claimed leaves of the class.
+	///
+	/// Claimed: double_map ClassId, Hash => Option<()>
+	#[pallet::storage]
+	#[pallet::getter(fn claimed)]
+	pub type Claimed<T: Config> = StorageDoubleMap<_, Twox64Concat, ClassIdOf<T>, Identity, T::Hash, (), OptionQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
@@ -238,20 +409,95 @@ pub mod module {
 			ensure!(quantity >= 1, Error::<T>::InvalidQuantity);
 			let class_info = orml_nft::Pallet::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
 			ensure!(who == class_info.owner, Error::<T>::NoPermission);
-			let deposit = T::CreateTokenDeposit::get();
-			let total_deposit = deposit.saturating_mul(quantity.into());
 
-			// `repatriate_reserved` will check `to` account exist and may return
-			// `DeadAccount`.
-			<T as module::Config>::Currency::transfer(&who, &to, total_deposit, KeepAlive)?;
-			<T as module::Config>::Currency::reserve_named(&RESERVE_ID, &to, total_deposit)?;
+			Self::do_mint(&who, &to, class_id, metadata, quantity)?;
+			Ok(().into())
+		}
 
-			let data = TokenData { deposit };
-			for _ in 0..quantity {
-				orml_nft::Pallet::<T>::mint(&to, class_id, metadata.clone(), data.clone())?;
+		/// Mint NFT tokens to multiple recipients, one token for each. The mints that fail, e.g.
+		/// the class owner doesn't have enough balance for the deposit, are skipped and their
+		/// indexes are reported in the event.
+		///
+		/// - `class_id`: token belong to the class id
+		/// - `tokens`: the recipients and the external metadata of the tokens
+		#[pallet::weight(<T as Config>::WeightInfo::batch_mint(tokens.len() as u32))]
+		#[transactional]
+		pub fn batch_mint(
+			origin: OriginFor<T>,
+			class_id: ClassIdOf<T>,
+			tokens: Vec<(<T::Lookup as StaticLookup>::Source, CID)>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!tokens.is_empty(), Error::<T>::InvalidQuantity);
+			let class_info = orml_nft::Pallet::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+			ensure!(who == class_info.owner, Error::<T>::NoPermission);
+
+			let mut quantity: u32 = 0;
+			let mut failed_indexes: Vec<u32> = Vec::new();
+			for (index, (to, metadata)) in tokens.into_iter().enumerate() {
+				let result = T::Lookup::lookup(to)
+					.map_err(DispatchError::from)
+					.and_then(|to| Self::do_mint(&who, &to, class_id, metadata, 1));
+				if result.is_ok() {
+					quantity = quantity.saturating_add(1);
+				} else {
+					failed_indexes.push(index as u32);
+				}
 			}
 
-			Self::deposit_event(Event::MintedToken(who, to, class_id, quantity));
+			Self::deposit_event(Event::BatchMinted(who, class_id, quantity, failed_indexes));
+			Ok(().into())
+		}
+
+		/// Set or remove the merkle root of the tokens of NFT class that can be claimed by their
+		/// recipients. Each leaf of the merkle tree is the hash of `(recipient, metadata)`, and
+		/// each node is the hash of its children in ascending order.
+		///
+		/// - `class_id`: The class ID
+		/// - `root`: the merkle root, `None` to remove it
+		#[pallet::weight(<T as Config>::WeightInfo::set_claim_root())]
+		#[transactional]
+		pub fn set_claim_root(
+			origin: OriginFor<T>,
+			class_id: ClassIdOf<T>,
+			root: Option<T::Hash>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let class_info = orml_nft::Pallet::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+			ensure!(who == class_info.owner, Error::<T>::NoPermission);
+
+			ClaimRoots::<T>::mutate_exists(class_id, |maybe_root| *maybe_root = root);
+
+			Self::deposit_event(Event::ClaimRootUpdated(class_id, root));
+			Ok(().into())
+		}
+
+		/// Claim the NFT token of the caller with the merkle proof, the deposit of the token is
+		/// paid by the class owner.
+		///
+		/// - `class_id`: The class ID
+		/// - `metadata`: external metadata of the token
+		/// - `proof`: the merkle proof of the leaf `(caller, metadata)`
+		#[pallet::weight(<T as Config>::WeightInfo::claim(proof.len() as u32))]
+		#[transactional]
+		pub fn claim(
+			origin: OriginFor<T>,
+			class_id: ClassIdOf<T>,
+			metadata: CID,
+			proof: Vec<T::Hash>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let class_info = orml_nft::Pallet::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+			let root = Self::claim_roots(class_id).ok_or(Error::<T>::ClaimRootNotFound)?;
+
+			let leaf = T::Hashing::hash_of(&(&who, &metadata));
+			ensure!(!Claimed::<T>::contains_key(class_id, leaf), Error::<T>::AlreadyClaimed);
+			ensure!(Self::merkle_root(leaf, &proof) == root, Error::<T>::InvalidClaimProof);
+
+			Self::do_mint(&class_info.owner, &who, class_id, metadata, 1)?;
+			Claimed::<T>::insert(class_id, leaf, ());
+
+			Self::deposit_event(Event::ClaimedToken(who, class_id, leaf));
 			Ok(().into())
 		}
 
@@ -328,6 +574,11 @@ pub mod module {
 			<T as module::Config>::Currency::unreserve_named(&RESERVE_ID, &who, data.deposit);
 
 			orml_nft::Pallet::<T>::destroy_class(&who, class_id)?;
+			ClassRoyalties::<T>::remove(class_id);
+			AttributeUpdaters::<T>::remove(class_id);
+			ClassLocks::<T>::remove(class_id);
+			ClaimRoots::<T>::remove(class_id);
+			Claimed::<T>::remove_prefix(class_id);
 
 			// this should unresere proxy deposit
 			pallet_proxy::Pallet::<T>::remove_proxy_delegate(&who, dest.clone(), Default::default(), Zero::zero())?;
@@ -342,22 +593,185 @@ pub mod module {
 			Self::deposit_event(Event::DestroyedClass(who, class_id));
 			Ok(().into())
 		}
+
+		/// Set or remove the royalty of NFT class
+		///
+		/// - `class_id`: The class ID
+		/// - `royalty`: the royalty beneficiary and rate in basis points, `None` to remove it
+		#[pallet::weight(<T as Config>::WeightInfo::set_class_royalty())]
+		#[transactional]
+		pub fn set_class_royalty(
+			origin: OriginFor<T>,
+			class_id: ClassIdOf<T>,
+			royalty: Option<RoyaltyInfo<T::AccountId>>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let class_info = orml_nft::Pallet::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+			ensure!(who == class_info.owner, Error::<T>::NoPermission);
+
+			if let Some(royalty) = &royalty {
+				ensure!(
+					royalty.basis_points <= MAX_ROYALTY_BASIS_POINTS,
+					Error::<T>::InvalidRoyaltyRate
+				);
+				ClassRoyalties::<T>::insert(class_id, royalty);
+			} else {
+				ClassRoyalties::<T>::remove(class_id);
+			}
+
+			Self::deposit_event(Event::ClassRoyaltyUpdated(class_id, royalty));
+			Ok(().into())
+		}
+
+		/// Set or remove the account allowed to update the token attributes of NFT class
+		///
+		/// - `class_id`: The class ID
+		/// - `updater`: the attribute updater, `None` to remove it
+		#[pallet::weight(<T as Config>::WeightInfo::set_attribute_updater())]
+		#[transactional]
+		pub fn set_attribute_updater(
+			origin: OriginFor<T>,
+			class_id: ClassIdOf<T>,
+			updater: Option<T::AccountId>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let class_info = orml_nft::Pallet::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+			ensure!(who == class_info.owner, Error::<T>::NoPermission);
+
+			AttributeUpdaters::<T>::mutate_exists(class_id, |maybe_updater| *maybe_updater = updater.clone());
+
+			Self::deposit_event(Event::AttributeUpdaterSet(class_id, updater));
+			Ok(().into())
+		}
+
+		/// Set or remove the lock of NFT class, the tokens of the class can't be transferred or
+		/// burned until the lock expires. The lock can only be set before any token is minted, but
+		/// can be removed at any time.
+		///
+		/// - `class_id`: The class ID
+		/// - `locked_until`: the block number or timestamp of the lock expiry, `None` to remove it
+		#[pallet::weight(<T as Config>::WeightInfo::set_class_lock())]
+		#[transactional]
+		pub fn set_class_lock(
+			origin: OriginFor<T>,
+			class_id: ClassIdOf<T>,
+			locked_until: Option<LockedUntil<T::BlockNumber>>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let class_info = orml_nft::Pallet::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+			ensure!(who == class_info.owner, Error::<T>::NoPermission);
+
+			if let Some(locked_until) = locked_until {
+				ensure!(
+					class_info.total_issuance == Zero::zero(),
+					Error::<T>::CannotLockIssuedClass
+				);
+				ClassLocks::<T>::insert(class_id, locked_until);
+			} else {
+				ClassLocks::<T>::remove(class_id);
+			}
+
+			Self::deposit_event(Event::ClassLockUpdated(class_id, locked_until));
+			Ok(().into())
+		}
+
+		/// Set or remove the attribute of NFT token, the class must be `Mutable`. The caller
+		/// reserves the deposit for the storage of the attribute, and the deposit of the
+		/// previous value is returned to its depositor.
+		///
+		/// - `token`: (class_id, token_id)
+		/// - `key`: the attribute key
+		/// - `value`: the attribute value, `None` to remove it
+		#[pallet::weight(<T as Config>::WeightInfo::set_token_attribute())]
+		#[transactional]
+		pub fn set_token_attribute(
+			origin: OriginFor<T>,
+			token: (ClassIdOf<T>, TokenIdOf<T>),
+			key: Vec<u8>,
+			value: Option<Vec<u8>>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let class_info = orml_nft::Pallet::<T>::classes(token.0).ok_or(Error::<T>::ClassIdNotFound)?;
+			ensure!(
+				who == class_info.owner || Self::attribute_updaters(token.0).as_ref() == Some(&who),
+				Error::<T>::NoPermission
+			);
+			ensure!(
+				class_info.data.properties.0.contains(ClassProperty::Mutable),
+				Error::<T>::NonMutable
+			);
+			ensure!(
+				orml_nft::Pallet::<T>::tokens(token.0, token.1).is_some(),
+				Error::<T>::TokenIdNotFound
+			);
+			ensure!(
+				key.len() <= T::MaxAttributeKeyLength::get() as usize,
+				Error::<T>::AttributeKeyTooLong
+			);
+
+			if let Some(previous) = TokenAttributes::<T>::take(token, &key) {
+				<T as module::Config>::Currency::unreserve_named(&RESERVE_ID, &previous.depositor, previous.deposit);
+			}
+
+			if let Some(value) = &value {
+				ensure!(
+					value.len() <= T::MaxAttributeValueLength::get() as usize,
+					Error::<T>::AttributeValueTooLong
+				);
+				let deposit = T::DataDepositPerByte::get().saturating_mul(((key.len() + value.len()) as u32).into());
+				<T as module::Config>::Currency::reserve_named(&RESERVE_ID, &who, deposit)?;
+				TokenAttributes::<T>::insert(
+					token,
+					&key,
+					AttributeInfo {
+						value: value.clone(),
+						depositor: who.clone(),
+						deposit,
+					},
+				);
+			}
+
+			Self::deposit_event(Event::TokenAttributeUpdated(who, token.0, token.1, key, value));
+			Ok(().into())
+		}
 	}
 }
 
 impl<T: Config> Pallet<T> {
+	/// Ensured atomic.
+	#[transactional]
+	fn do_mint(
+		who: &T::AccountId,
+		to: &T::AccountId,
+		class_id: ClassIdOf<T>,
+		metadata: CID,
+		quantity: u32,
+	) -> DispatchResult {
+		let deposit = T::CreateTokenDeposit::get();
+		let total_deposit = deposit.saturating_mul(quantity.into());
+
+		// `repatriate_reserved` will check `to` account exist and may return
+		// `DeadAccount`.
+		<T as module::Config>::Currency::transfer(&who, &to, total_deposit, KeepAlive)?;
+		<T as module::Config>::Currency::reserve_named(&RESERVE_ID, &to, total_deposit)?;
+
+		let data = TokenData { deposit };
+		for _ in 0..quantity {
+			orml_nft::Pallet::<T>::mint(&to, class_id, metadata.clone(), data.clone())?;
+		}
+
+		Self::deposit_event(Event::MintedToken(who.clone(), to.clone(), class_id, quantity));
+		Ok(())
+	}
+
 	/// Ensured atomic.
 	#[transactional]
 	fn do_transfer(from: &T::AccountId, to: &T::AccountId, token: (ClassIdOf<T>, TokenIdOf<T>)) -> DispatchResult {
-		let class_info = orml_nft::Pallet::<T>::classes(token.0).ok_or(Error::<T>::ClassIdNotFound)?;
-		let data = class_info.data;
-		ensure!(
-			data.properties.0.contains(ClassProperty::Transferable),
-			Error::<T>::NonTransferable
-		);
+		Self::ensure_transferable(token.0)?;
 
 		let token_info = orml_nft::Pallet::<T>::tokens(token.0, token.1).ok_or(Error::<T>::TokenIdNotFound)?;
 
+		T::OnNftTransfer::on_nft_transfer(from, to, token)?;
 		orml_nft::Pallet::<T>::transfer(from, to, token)?;
 
 		<T as module::Config>::Currency::unreserve_named(&RESERVE_ID, &from, token_info.data.deposit);
@@ -377,6 +791,7 @@ impl<T: Config> Pallet<T> {
 			data.properties.0.contains(ClassProperty::Burnable),
 			Error::<T>::NonBurnable
 		);
+		ensure!(!Self::is_class_locked(token.0), Error::<T>::ClassLocked);
 
 		let token_info = orml_nft::Pallet::<T>::tokens(token.0, token.1).ok_or(Error::<T>::TokenIdNotFound)?;
 		ensure!(*who == token_info.owner, Error::<T>::NoPermission);
@@ -384,8 +799,103 @@ impl<T: Config> Pallet<T> {
 		orml_nft::Pallet::<T>::burn(&who, token)?;
 
 		<T as module::Config>::Currency::unreserve_named(&RESERVE_ID, &who, token_info.data.deposit);
+		for (_, attribute) in TokenAttributes::<T>::drain_prefix(token) {
+			<T as module::Config>::Currency::unreserve_named(&RESERVE_ID, &attribute.depositor, attribute.deposit);
+		}
+		Ok(())
+	}
+
+	/// The merkle root computed from `leaf` and its `proof`, each node is the hash of its
+	/// children in ascending order.
+	pub fn merkle_root(leaf: T::Hash, proof: &[T::Hash]) -> T::Hash {
+		proof.iter().fold(leaf, |node, sibling| {
+			if node <= *sibling {
+				T::Hashing::hash_of(&(node, sibling))
+			} else {
+				T::Hashing::hash_of(&(sibling, node))
+			}
+		})
+	}
+
+	/// Ensure the tokens of `class_id` can be transferred now, used by marketplaces to reject
+	/// the sales of the tokens which can't be delivered.
+	pub fn ensure_transferable(class_id: ClassIdOf<T>) -> DispatchResult {
+		let class_info = orml_nft::Pallet::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+		let properties = class_info.data.properties.0;
+		ensure!(
+			properties.contains(ClassProperty::Transferable),
+			Error::<T>::NonTransferable
+		);
+		ensure!(!properties.contains(ClassProperty::Soulbound), Error::<T>::Soulbound);
+		ensure!(!Self::is_class_locked(class_id), Error::<T>::ClassLocked);
 		Ok(())
 	}
+
+	/// Whether the tokens of `class_id` are locked now.
+	pub fn is_class_locked(class_id: ClassIdOf<T>) -> bool {
+		match Self::class_locks(class_id) {
+			Some(LockedUntil::BlockNumber(block_number)) => frame_system::Pallet::<T>::block_number() < block_number,
+			Some(LockedUntil::Timestamp(timestamp)) => T::UnixTime::now().as_secs() < timestamp,
+			None => false,
+		}
+	}
+
+	/// The number of the tokens of `class_id` owned by `who`.
+	pub fn class_balance(who: &T::AccountId, class_id: ClassIdOf<T>) -> NFTBalance {
+		orml_nft::TokensByOwner::<T>::iter_prefix(who)
+			.filter(|((c, _), _)| *c == class_id)
+			.count() as u128
+	}
+
+	/// The URI of `token`, the `TOKEN_URI_ATTRIBUTE_KEY` attribute if set, otherwise the token
+	/// metadata. `None` if the token doesn't exist.
+	pub fn token_uri(token: (ClassIdOf<T>, TokenIdOf<T>)) -> Option<Vec<u8>> {
+		let token_info = orml_nft::Pallet::<T>::tokens(token.0, token.1)?;
+		Some(
+			Self::token_attributes(token, TOKEN_URI_ATTRIBUTE_KEY.to_vec())
+				.map(|attribute| attribute.value)
+				.unwrap_or_else(|| token_info.metadata.to_vec()),
+		)
+	}
+
+	/// The royalty beneficiary and amount of the sale of the tokens of `class_id` at `price`,
+	/// `None` if the class has no royalty.
+	pub fn royalty_of(class_id: ClassIdOf<T>, price: BalanceOf<T>) -> Option<(T::AccountId, BalanceOf<T>)> {
+		Self::class_royalties(class_id).map(|royalty| {
+			let amount = royalty.royalty(price);
+			(royalty.beneficiary, amount)
+		})
+	}
+
+	/// Pay the royalty of the sale of `token` at `price` from `payer` to the royalty
+	/// beneficiary, returns the paid amount. Used by marketplaces to enforce the royalty.
+	#[transactional]
+	pub fn pay_royalty(
+		payer: &T::AccountId,
+		token: (ClassIdOf<T>, TokenIdOf<T>),
+		price: BalanceOf<T>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		ensure!(
+			orml_nft::Pallet::<T>::tokens(token.0, token.1).is_some(),
+			Error::<T>::TokenIdNotFound
+		);
+
+		match Self::royalty_of(token.0, price) {
+			Some((beneficiary, amount)) if !amount.is_zero() => {
+				<T as module::Config>::Currency::transfer(payer, &beneficiary, amount, KeepAlive)?;
+				Self::deposit_event(Event::RoyaltyPaid(
+					token.0,
+					token.1,
+					payer.clone(),
+					beneficiary,
+					price,
+					amount,
+				));
+				Ok(amount)
+			}
+			_ => Ok(Zero::zero()),
+		}
+	}
 }
 
 impl<T: Config> NFT<T::AccountId> for Pallet<T> {
@@ -34,6 +34,7 @@ use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
 };
+use sp_std::cell::RefCell;
 use support::mocks::MockAddressMapping;
 
 parameter_types! {
@@ -169,6 +170,11 @@ parameter_types! {
 	pub const GetNativeCurrencyId: CurrencyId = NATIVE_CURRENCY_ID;
 }
 
+parameter_types! {
+	pub const MaxMemoLength: u32 = 32;
+	pub const MaxBatchTransfers: u32 = 4;
+}
+
 impl module_currencies::Config for Runtime {
 	type Event = Event;
 	type MultiCurrency = Tokens;
@@ -177,6 +183,36 @@ impl module_currencies::Config for Runtime {
 	type WeightInfo = ();
 	type AddressMapping = MockAddressMapping;
 	type EVMBridge = ();
+	type UpdateOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxMemoLength = MaxMemoLength;
+	type MaxBatchTransfers = MaxBatchTransfers;
+	type FreezeChecker = ();
+}
+
+thread_local! {
+	pub static LOCKED_TOKENS: RefCell<Vec<(u32, u64)>> = RefCell::new(vec![]);
+}
+
+pub struct MockOnNftTransfer;
+impl OnNftTransfer<AccountId, u32, u64> for MockOnNftTransfer {
+	fn on_nft_transfer(_from: &AccountId, _to: &AccountId, token: (u32, u64)) -> DispatchResult {
+		if LOCKED_TOKENS.with(|v| v.borrow().contains(&token)) {
+			Err(DispatchError::Other("token is locked"))
+		} else {
+			Ok(())
+		}
+	}
+}
+
+parameter_types! {
+	pub const MinimumPeriod: u64 = 1000;
+}
+
+impl pallet_timestamp::Config for Runtime {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = MinimumPeriod;
+	type WeightInfo = ();
 }
 
 parameter_types! {
@@ -184,6 +220,8 @@ parameter_types! {
 	pub const CreateTokenDeposit: Balance = 100;
 	pub const DataDepositPerByte: Balance = 10;
 	pub const NftPalletId: PalletId = PalletId(*b"aca/aNFT");
+	pub const MaxAttributeKeyLength: u32 = 64;
+	pub const MaxAttributeValueLength: u32 = 1024;
 }
 impl Config for Runtime {
 	type Event = Event;
@@ -192,6 +230,10 @@ impl Config for Runtime {
 	type CreateTokenDeposit = CreateTokenDeposit;
 	type DataDepositPerByte = DataDepositPerByte;
 	type PalletId = NftPalletId;
+	type MaxAttributeKeyLength = MaxAttributeKeyLength;
+	type MaxAttributeValueLength = MaxAttributeValueLength;
+	type UnixTime = Timestamp;
+	type OnNftTransfer = MockOnNftTransfer;
 	type WeightInfo = ();
 }
 
@@ -222,12 +264,13 @@ construct_runtime!(
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		NFTModule: nft::{Pallet, Call, Event<T>},
+		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
 		OrmlNFT: orml_nft::{Pallet, Storage, Config<T>},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		Proxy: pallet_proxy::{Pallet, Call, Storage, Event<T>},
 		Utility: pallet_utility::{Pallet, Call, Event},
 		Tokens: orml_tokens::{Pallet, Storage, Event<T>, Config<T>},
-		Currency: module_currencies::{Pallet, Call, Event<T>},
+		Currency: module_currencies::{Pallet, Call, Storage, Event<T>},
 	}
 );
 
@@ -537,3 +537,641 @@ fn destroy_class_should_fail() {
 		));
 	});
 }
+
+#[test]
+fn set_class_royalty_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Default::default()
+		));
+		let royalty = RoyaltyInfo {
+			beneficiary: ALICE,
+			basis_points: 250,
+		};
+
+		assert_noop!(
+			NFTModule::set_class_royalty(Origin::signed(class_id_account()), CLASS_ID_NOT_EXIST, None),
+			Error::<Runtime>::ClassIdNotFound
+		);
+		assert_noop!(
+			NFTModule::set_class_royalty(Origin::signed(BOB), CLASS_ID, Some(royalty.clone())),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			NFTModule::set_class_royalty(
+				Origin::signed(class_id_account()),
+				CLASS_ID,
+				Some(RoyaltyInfo {
+					beneficiary: ALICE,
+					basis_points: 10_001,
+				})
+			),
+			Error::<Runtime>::InvalidRoyaltyRate
+		);
+
+		assert_ok!(NFTModule::set_class_royalty(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			Some(royalty.clone())
+		));
+		System::assert_last_event(Event::NFTModule(crate::Event::ClassRoyaltyUpdated(
+			CLASS_ID,
+			Some(royalty.clone()),
+		)));
+		assert_eq!(NFTModule::class_royalties(CLASS_ID), Some(royalty));
+		assert_eq!(NFTModule::royalty_of(CLASS_ID, 1000), Some((ALICE, 25)));
+
+		assert_ok!(NFTModule::set_class_royalty(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			None
+		));
+		System::assert_last_event(Event::NFTModule(crate::Event::ClassRoyaltyUpdated(CLASS_ID, None)));
+		assert_eq!(NFTModule::class_royalties(CLASS_ID), None);
+		assert_eq!(NFTModule::royalty_of(CLASS_ID, 1000), None);
+	});
+}
+
+#[test]
+fn pay_royalty_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+		));
+		assert_ok!(Balances::deposit_into_existing(
+			&class_id_account(),
+			<Runtime as Config>::CreateTokenDeposit::get()
+		));
+		assert_ok!(NFTModule::mint(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![1],
+			1
+		));
+
+		assert_noop!(
+			NFTModule::pay_royalty(&ALICE, (CLASS_ID, TOKEN_ID_NOT_EXIST), 1000),
+			Error::<Runtime>::TokenIdNotFound
+		);
+
+		// no royalty
+		assert_eq!(NFTModule::pay_royalty(&ALICE, (CLASS_ID, TOKEN_ID), 1000), Ok(0));
+
+		assert_ok!(NFTModule::set_class_royalty(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			Some(RoyaltyInfo {
+				beneficiary: BOB,
+				basis_points: 500,
+			})
+		));
+		let alice_balance = free_balance(&ALICE);
+		let bob_balance = free_balance(&BOB);
+		assert_eq!(NFTModule::pay_royalty(&ALICE, (CLASS_ID, TOKEN_ID), 1000), Ok(50));
+		System::assert_last_event(Event::NFTModule(crate::Event::RoyaltyPaid(
+			CLASS_ID, TOKEN_ID, ALICE, BOB, 1000, 50,
+		)));
+		assert_eq!(free_balance(&ALICE), alice_balance - 50);
+		assert_eq!(free_balance(&BOB), bob_balance + 50);
+	});
+}
+
+#[test]
+fn on_nft_transfer_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+		));
+		assert_ok!(Balances::deposit_into_existing(
+			&class_id_account(),
+			<Runtime as Config>::CreateTokenDeposit::get()
+		));
+		assert_ok!(NFTModule::mint(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![1],
+			1
+		));
+
+		LOCKED_TOKENS.with(|v| v.borrow_mut().push((CLASS_ID, TOKEN_ID)));
+		assert_noop!(
+			NFTModule::transfer(Origin::signed(BOB), ALICE, (CLASS_ID, TOKEN_ID)),
+			DispatchError::Other("token is locked")
+		);
+
+		LOCKED_TOKENS.with(|v| v.borrow_mut().clear());
+		assert_ok!(NFTModule::transfer(Origin::signed(BOB), ALICE, (CLASS_ID, TOKEN_ID)));
+	});
+}
+
+#[test]
+fn set_attribute_updater_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Mutable.into())
+		));
+
+		assert_noop!(
+			NFTModule::set_attribute_updater(Origin::signed(class_id_account()), CLASS_ID_NOT_EXIST, Some(BOB)),
+			Error::<Runtime>::ClassIdNotFound
+		);
+		assert_noop!(
+			NFTModule::set_attribute_updater(Origin::signed(BOB), CLASS_ID, Some(BOB)),
+			Error::<Runtime>::NoPermission
+		);
+
+		assert_ok!(NFTModule::set_attribute_updater(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			Some(BOB)
+		));
+		System::assert_last_event(Event::NFTModule(crate::Event::AttributeUpdaterSet(CLASS_ID, Some(BOB))));
+		assert_eq!(NFTModule::attribute_updaters(CLASS_ID), Some(BOB));
+
+		assert_ok!(NFTModule::set_attribute_updater(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			None
+		));
+		System::assert_last_event(Event::NFTModule(crate::Event::AttributeUpdaterSet(CLASS_ID, None)));
+		assert_eq!(NFTModule::attribute_updaters(CLASS_ID), None);
+	});
+}
+
+#[test]
+fn set_token_attribute_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Burnable | ClassProperty::Mutable)
+		));
+		assert_ok!(Balances::deposit_into_existing(
+			&class_id_account(),
+			<Runtime as Config>::CreateTokenDeposit::get()
+		));
+		assert_ok!(NFTModule::mint(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![1],
+			1
+		));
+		assert_ok!(Balances::deposit_into_existing(&class_id_account(), 1000));
+
+		assert_noop!(
+			NFTModule::set_token_attribute(
+				Origin::signed(BOB),
+				(CLASS_ID, TOKEN_ID),
+				b"level".to_vec(),
+				Some(vec![1])
+			),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			NFTModule::set_token_attribute(
+				Origin::signed(class_id_account()),
+				(CLASS_ID, TOKEN_ID_NOT_EXIST),
+				b"level".to_vec(),
+				Some(vec![1])
+			),
+			Error::<Runtime>::TokenIdNotFound
+		);
+		assert_noop!(
+			NFTModule::set_token_attribute(
+				Origin::signed(class_id_account()),
+				(CLASS_ID, TOKEN_ID),
+				vec![1; 65],
+				Some(vec![1])
+			),
+			Error::<Runtime>::AttributeKeyTooLong
+		);
+		assert_noop!(
+			NFTModule::set_token_attribute(
+				Origin::signed(class_id_account()),
+				(CLASS_ID, TOKEN_ID),
+				b"level".to_vec(),
+				Some(vec![1; 1025])
+			),
+			Error::<Runtime>::AttributeValueTooLong
+		);
+
+		let reserved = reserved_balance(&class_id_account());
+		assert_ok!(NFTModule::set_token_attribute(
+			Origin::signed(class_id_account()),
+			(CLASS_ID, TOKEN_ID),
+			b"level".to_vec(),
+			Some(vec![1])
+		));
+		System::assert_last_event(Event::NFTModule(crate::Event::TokenAttributeUpdated(
+			class_id_account(),
+			CLASS_ID,
+			TOKEN_ID,
+			b"level".to_vec(),
+			Some(vec![1]),
+		)));
+		let deposit = 6 * <Runtime as Config>::DataDepositPerByte::get();
+		assert_eq!(
+			NFTModule::token_attributes((CLASS_ID, TOKEN_ID), b"level".to_vec()),
+			Some(AttributeInfo {
+				value: vec![1],
+				depositor: class_id_account(),
+				deposit,
+			})
+		);
+		assert_eq!(reserved_balance(&class_id_account()), reserved + deposit);
+
+		// the attribute updater replaces the value, the previous deposit is returned
+		assert_ok!(NFTModule::set_attribute_updater(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			Some(ALICE)
+		));
+		let alice_reserved = reserved_balance(&ALICE);
+		assert_ok!(NFTModule::set_token_attribute(
+			Origin::signed(ALICE),
+			(CLASS_ID, TOKEN_ID),
+			b"level".to_vec(),
+			Some(vec![2, 2])
+		));
+		assert_eq!(reserved_balance(&class_id_account()), reserved);
+		assert_eq!(
+			reserved_balance(&ALICE),
+			alice_reserved + 7 * <Runtime as Config>::DataDepositPerByte::get()
+		);
+
+		// burn removes the attributes and returns the deposits
+		assert_ok!(NFTModule::burn(Origin::signed(BOB), (CLASS_ID, TOKEN_ID)));
+		assert_eq!(
+			NFTModule::token_attributes((CLASS_ID, TOKEN_ID), b"level".to_vec()),
+			None
+		);
+		assert_eq!(reserved_balance(&ALICE), alice_reserved);
+	});
+}
+
+#[test]
+fn set_token_attribute_should_fail_for_immutable_class() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+		));
+		assert_ok!(Balances::deposit_into_existing(
+			&class_id_account(),
+			<Runtime as Config>::CreateTokenDeposit::get()
+		));
+		assert_ok!(NFTModule::mint(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![1],
+			1
+		));
+		assert_noop!(
+			NFTModule::set_token_attribute(
+				Origin::signed(class_id_account()),
+				(CLASS_ID, TOKEN_ID),
+				b"level".to_vec(),
+				Some(vec![1])
+			),
+			Error::<Runtime>::NonMutable
+		);
+	});
+}
+
+#[test]
+fn class_balance_and_token_uri_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Mutable)
+		));
+		assert_ok!(Balances::deposit_into_existing(
+			&class_id_account(),
+			2 * <Runtime as Config>::CreateTokenDeposit::get() + 1000
+		));
+		assert_ok!(NFTModule::mint(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![1],
+			2
+		));
+		assert_eq!(NFTModule::class_balance(&BOB, CLASS_ID), 2);
+		assert_eq!(NFTModule::class_balance(&BOB, CLASS_ID_NOT_EXIST), 0);
+		assert_eq!(NFTModule::class_balance(&ALICE, CLASS_ID), 0);
+
+		assert_eq!(NFTModule::token_uri((CLASS_ID, 2)), None);
+		assert_eq!(NFTModule::token_uri((CLASS_ID, TOKEN_ID)), Some(vec![1]));
+
+		assert_ok!(NFTModule::set_token_attribute(
+			Origin::signed(class_id_account()),
+			(CLASS_ID, TOKEN_ID),
+			TOKEN_URI_ATTRIBUTE_KEY.to_vec(),
+			Some(b"ipfs://token".to_vec())
+		));
+		assert_eq!(
+			NFTModule::token_uri((CLASS_ID, TOKEN_ID)),
+			Some(b"ipfs://token".to_vec())
+		);
+	});
+}
+
+#[test]
+fn soulbound_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable | ClassProperty::Soulbound)
+		));
+		assert_ok!(Balances::deposit_into_existing(
+			&class_id_account(),
+			<Runtime as Config>::CreateTokenDeposit::get()
+		));
+		assert_ok!(NFTModule::mint(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![1],
+			1
+		));
+
+		assert_noop!(
+			NFTModule::transfer(Origin::signed(BOB), ALICE, (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::Soulbound
+		);
+		assert_ok!(NFTModule::burn(Origin::signed(BOB), (CLASS_ID, TOKEN_ID)));
+	});
+}
+
+#[test]
+fn set_class_lock_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+		));
+
+		assert_noop!(
+			NFTModule::set_class_lock(Origin::signed(ALICE), CLASS_ID, Some(LockedUntil::BlockNumber(10))),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			NFTModule::set_class_lock(
+				Origin::signed(class_id_account()),
+				CLASS_ID_NOT_EXIST,
+				Some(LockedUntil::BlockNumber(10))
+			),
+			Error::<Runtime>::ClassIdNotFound
+		);
+
+		assert_ok!(NFTModule::set_class_lock(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			Some(LockedUntil::BlockNumber(10))
+		));
+		System::assert_last_event(Event::NFTModule(crate::Event::ClassLockUpdated(
+			CLASS_ID,
+			Some(LockedUntil::BlockNumber(10)),
+		)));
+		assert_eq!(NFTModule::class_locks(CLASS_ID), Some(LockedUntil::BlockNumber(10)));
+
+		assert_ok!(Balances::deposit_into_existing(
+			&class_id_account(),
+			2 * <Runtime as Config>::CreateTokenDeposit::get()
+		));
+		assert_ok!(NFTModule::mint(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![1],
+			2
+		));
+
+		// the lock can't be changed after minted
+		assert_noop!(
+			NFTModule::set_class_lock(
+				Origin::signed(class_id_account()),
+				CLASS_ID,
+				Some(LockedUntil::BlockNumber(20))
+			),
+			Error::<Runtime>::CannotLockIssuedClass
+		);
+
+		assert_noop!(
+			NFTModule::transfer(Origin::signed(BOB), ALICE, (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::ClassLocked
+		);
+		assert_noop!(
+			NFTModule::burn(Origin::signed(BOB), (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::ClassLocked
+		);
+
+		System::set_block_number(10);
+		assert_ok!(NFTModule::transfer(Origin::signed(BOB), ALICE, (CLASS_ID, TOKEN_ID)));
+		assert_ok!(NFTModule::burn(Origin::signed(ALICE), (CLASS_ID, TOKEN_ID)));
+
+		// but it can be removed
+		assert_ok!(NFTModule::set_class_lock(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			None
+		));
+		System::assert_last_event(Event::NFTModule(crate::Event::ClassLockUpdated(CLASS_ID, None)));
+		assert_eq!(NFTModule::class_locks(CLASS_ID), None);
+	});
+}
+
+#[test]
+fn class_timestamp_lock_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+		));
+		assert_ok!(NFTModule::set_class_lock(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			Some(LockedUntil::Timestamp(100))
+		));
+		assert_ok!(Balances::deposit_into_existing(
+			&class_id_account(),
+			<Runtime as Config>::CreateTokenDeposit::get()
+		));
+		assert_ok!(NFTModule::mint(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![1],
+			1
+		));
+
+		Timestamp::set_timestamp(99_999);
+		assert!(NFTModule::is_class_locked(CLASS_ID));
+		assert_noop!(
+			NFTModule::transfer(Origin::signed(BOB), ALICE, (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::ClassLocked
+		);
+
+		Timestamp::set_timestamp(100_000);
+		assert!(!NFTModule::is_class_locked(CLASS_ID));
+		assert_ok!(NFTModule::transfer(Origin::signed(BOB), ALICE, (CLASS_ID, TOKEN_ID)));
+	});
+}
+
+#[test]
+fn batch_mint_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+		));
+
+		assert_noop!(
+			NFTModule::batch_mint(Origin::signed(class_id_account()), CLASS_ID, vec![]),
+			Error::<Runtime>::InvalidQuantity
+		);
+		assert_noop!(
+			NFTModule::batch_mint(Origin::signed(ALICE), CLASS_ID, vec![(BOB, vec![1])]),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			NFTModule::batch_mint(
+				Origin::signed(class_id_account()),
+				CLASS_ID_NOT_EXIST,
+				vec![(BOB, vec![1])]
+			),
+			Error::<Runtime>::ClassIdNotFound
+		);
+
+		// only enough for the deposit of 2 tokens
+		assert_ok!(Balances::deposit_into_existing(
+			&class_id_account(),
+			2 * <Runtime as Config>::CreateTokenDeposit::get()
+		));
+		assert_ok!(NFTModule::batch_mint(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			vec![(BOB, vec![1]), (ALICE, vec![2]), (BOB, vec![3])]
+		));
+		System::assert_last_event(Event::NFTModule(crate::Event::BatchMinted(
+			class_id_account(),
+			CLASS_ID,
+			2,
+			vec![2],
+		)));
+
+		assert_eq!(
+			orml_nft::Pallet::<Runtime>::tokens(CLASS_ID, 0).map(|t| (t.owner, t.metadata.to_vec())),
+			Some((BOB, vec![1]))
+		);
+		assert_eq!(
+			orml_nft::Pallet::<Runtime>::tokens(CLASS_ID, 1).map(|t| (t.owner, t.metadata.to_vec())),
+			Some((ALICE, vec![2]))
+		);
+		assert!(orml_nft::Pallet::<Runtime>::tokens(CLASS_ID, 2).is_none());
+		assert_eq!(reserved_balance(&BOB), <Runtime as Config>::CreateTokenDeposit::get());
+	});
+}
+
+#[test]
+fn claim_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+		));
+		assert_ok!(Balances::deposit_into_existing(
+			&class_id_account(),
+			2 * <Runtime as Config>::CreateTokenDeposit::get()
+		));
+
+		let alice_leaf = BlakeTwo256::hash_of(&(&ALICE, &vec![1u8]));
+		let bob_leaf = BlakeTwo256::hash_of(&(&BOB, &vec![2u8]));
+		let root = if alice_leaf <= bob_leaf {
+			BlakeTwo256::hash_of(&(alice_leaf, bob_leaf))
+		} else {
+			BlakeTwo256::hash_of(&(bob_leaf, alice_leaf))
+		};
+
+		assert_noop!(
+			NFTModule::claim(Origin::signed(BOB), CLASS_ID, vec![2], vec![alice_leaf]),
+			Error::<Runtime>::ClaimRootNotFound
+		);
+		assert_noop!(
+			NFTModule::set_claim_root(Origin::signed(ALICE), CLASS_ID, Some(root)),
+			Error::<Runtime>::NoPermission
+		);
+
+		assert_ok!(NFTModule::set_claim_root(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			Some(root)
+		));
+		System::assert_last_event(Event::NFTModule(crate::Event::ClaimRootUpdated(CLASS_ID, Some(root))));
+		assert_eq!(NFTModule::claim_roots(CLASS_ID), Some(root));
+
+		// wrong metadata
+		assert_noop!(
+			NFTModule::claim(Origin::signed(BOB), CLASS_ID, vec![1], vec![alice_leaf]),
+			Error::<Runtime>::InvalidClaimProof
+		);
+		// wrong proof
+		assert_noop!(
+			NFTModule::claim(Origin::signed(BOB), CLASS_ID, vec![2], vec![bob_leaf]),
+			Error::<Runtime>::InvalidClaimProof
+		);
+
+		assert_ok!(NFTModule::claim(
+			Origin::signed(BOB),
+			CLASS_ID,
+			vec![2],
+			vec![alice_leaf]
+		));
+		System::assert_last_event(Event::NFTModule(crate::Event::ClaimedToken(BOB, CLASS_ID, bob_leaf)));
+		assert_eq!(
+			orml_nft::Pallet::<Runtime>::tokens(CLASS_ID, TOKEN_ID).map(|t| (t.owner, t.metadata.to_vec())),
+			Some((BOB, vec![2]))
+		);
+		assert_eq!(reserved_balance(&BOB), <Runtime as Config>::CreateTokenDeposit::get());
+		assert!(NFTModule::claimed(CLASS_ID, bob_leaf).is_some());
+
+		assert_noop!(
+			NFTModule::claim(Origin::signed(BOB), CLASS_ID, vec![2], vec![alice_leaf]),
+			Error::<Runtime>::AlreadyClaimed
+		);
+
+		assert_ok!(NFTModule::claim(
+			Origin::signed(ALICE),
+			CLASS_ID,
+			vec![1],
+			vec![bob_leaf]
+		));
+
+		assert_ok!(NFTModule::set_claim_root(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			None
+		));
+		System::assert_last_event(Event::NFTModule(crate::Event::ClaimRootUpdated(CLASS_ID, None)));
+		assert_eq!(NFTModule::claim_roots(CLASS_ID), None);
+	});
+}
@@ -23,18 +23,18 @@ use codec::Encode;
 use frame_support::{
 	log,
 	pallet_prelude::*,
-	traits::{Contains, Get, LockIdentifier, MaxEncodedLen},
+	traits::{ChangeMembers, Contains, Get, LockIdentifier, MaxEncodedLen, NamedReservableCurrency},
 	transactional, BoundedVec,
 };
 use frame_system::pallet_prelude::*;
-use orml_traits::{BasicCurrency, BasicLockableCurrency};
-use primitives::{Balance, EraIndex};
+use orml_traits::{BasicCurrency, BasicLockableCurrency, Happened};
+use primitives::{Balance, EraIndex, ReserveIdentifier};
 use sp_runtime::{
 	traits::{MaybeDisplay, MaybeSerializeDeserialize, Member, Zero},
-	RuntimeDebug, SaturatedConversion,
+	FixedPointNumber, RuntimeDebug, SaturatedConversion,
 };
-use sp_std::{convert::TryInto, fmt::Debug, prelude::*};
-use support::{NomineesProvider, OnNewEra};
+use sp_std::{collections::btree_map::BTreeMap, convert::TryInto, fmt::Debug, prelude::*};
+use support::{NomineesProvider, OnNewEra, Ratio};
 
 mod mock;
 mod tests;
@@ -131,14 +131,37 @@ where
 	}
 }
 
+/// The storage versions of the module.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum Releases {
+	V1_0_0,
+	/// `LastConfirmedEra` seeded for the existing nominators, `NominatorsCount`
+	/// and `CouncilVotersCount` introduced.
+	V2_0_0,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V1_0_0
+	}
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
 
+	pub const RESERVE_ID: ReserveIdentifier = ReserveIdentifier::NomineesElection;
+
 	#[pallet::config]
 	pub trait Config<I: 'static = ()>: frame_system::Config {
 		type Event: From<Event<Self, I>> + IsType<<Self as frame_system::Config>::Event>;
 		type Currency: BasicLockableCurrency<Self::AccountId, Moment = Self::BlockNumber, Balance = Balance>;
+		/// The currency for the deposits of nominators and council voters.
+		type DepositCurrency: NamedReservableCurrency<
+			Self::AccountId,
+			Balance = Balance,
+			ReserveIdentifier = ReserveIdentifier,
+		>;
 		type NomineeId: Parameter + Member + MaybeSerializeDeserialize + Debug + MaybeDisplay + Ord + Default;
 		#[pallet::constant]
 		type PalletId: Get<LockIdentifier>;
@@ -151,6 +174,40 @@ pub mod module {
 		#[pallet::constant]
 		type MaxUnlockingChunks: Get<u32>;
 		type NomineeFilter: Contains<Self::NomineeId>;
+		/// The number of eras after which the votes of a nomination decay
+		/// linearly to zero unless re-confirmed. Zero means no decay.
+		#[pallet::constant]
+		type VoteDecayPeriod: Get<EraIndex>;
+		/// The max number of members of the nominators' council, which is
+		/// elected by the bonded nominators and can adjust the nominees
+		/// between elections.
+		#[pallet::constant]
+		type CouncilSize: Get<u32>;
+		/// The max number of nominators, which bounds the iteration of
+		/// nominations when rebalancing at the beginning of every era.
+		#[pallet::constant]
+		type MaxNominators: Get<u32>;
+		/// The max number of voters for the candidates of nominators' council,
+		/// which bounds the iteration of votes when electing the council.
+		#[pallet::constant]
+		type MaxCouncilVoters: Get<u32>;
+		/// The deposit reserved from a nominator while it takes one of the
+		/// `MaxNominators` slots, so the slots cannot be filled for free.
+		#[pallet::constant]
+		type NominatorDeposit: Get<Balance>;
+		/// The deposit reserved from a council voter while it takes one of the
+		/// `MaxCouncilVoters` slots, so the slots cannot be filled for free.
+		#[pallet::constant]
+		type CouncilVoterDeposit: Get<Balance>;
+		/// Callback to sync the members of the collective of nominators'
+		/// council when it's elected.
+		type CouncilMembersChanged: ChangeMembers<Self::AccountId>;
+		/// The origin which may adjust the nominees between elections, it
+		/// should be the collective of nominators' council.
+		type AdjustOrigin: EnsureOrigin<Self::Origin>;
+		/// Callback when the nominees have been adjusted by the member of
+		/// nominators' council.
+		type OnNomineesAdjusted: Happened<Vec<Self::NomineeId>>;
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -164,6 +221,9 @@ pub mod module {
 		NoUnlockChunk,
 		InvalidNominee,
 		NominateesCountExceeded,
+		NoNominations,
+		TooManyNominators,
+		TooManyCouncilVoters,
 	}
 
 	#[pallet::event]
@@ -171,6 +231,19 @@ pub mod module {
 	pub enum Event<T: Config<I>, I: 'static = ()> {
 		/// rebond. [who, amount]
 		Rebond(T::AccountId, Balance),
+		/// re-confirm the nominations. [who, era]
+		RefreshVote(T::AccountId, EraIndex),
+		/// vote for the candidate of nominators' council. [who, candidate]
+		VoteCouncil(T::AccountId, T::AccountId),
+		/// revoke the vote for the candidate of nominators' council. [who]
+		RevokeCouncilVote(T::AccountId),
+		/// the nominators' council has been elected. [members]
+		CouncilElected(Vec<T::AccountId>),
+		/// the nominees have been adjusted by nominators' council. [nominees]
+		NomineesAdjusted(Vec<<T as Config<I>>::NomineeId>),
+		/// the nominations have been removed by the migration as the
+		/// nominators exceed `MaxNominators`. [who]
+		NominationsRemoved(T::AccountId),
 	}
 
 	/// The nominations for nominators.
@@ -210,6 +283,30 @@ pub mod module {
 	pub type Nominees<T: Config<I>, I: 'static = ()> =
 		StorageValue<_, BoundedVec<<T as Config<I>>::NomineeId, T::NominateesCount>, ValueQuery>;
 
+	/// The era at which the nominations of nominator were confirmed last time.
+	///
+	/// LastConfirmedEra: map AccountId => EraIndex
+	#[pallet::storage]
+	#[pallet::getter(fn last_confirmed_era)]
+	pub type LastConfirmedEra<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, T::AccountId, EraIndex, ValueQuery>;
+
+	/// The candidate of nominators' council voted by nominators.
+	///
+	/// CouncilVotes: map AccountId => Option<AccountId>
+	#[pallet::storage]
+	#[pallet::getter(fn council_votes)]
+	pub type CouncilVotes<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, T::AccountId, T::AccountId, OptionQuery>;
+
+	/// The members of nominators' council.
+	///
+	/// Council: Vec<AccountId>
+	#[pallet::storage]
+	#[pallet::getter(fn council)]
+	pub type Council<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BoundedVec<T::AccountId, T::CouncilSize>, ValueQuery>;
+
 	/// Current era index.
 	///
 	/// CurrentEra: EraIndex
@@ -217,11 +314,56 @@ pub mod module {
 	#[pallet::getter(fn current_era)]
 	pub type CurrentEra<T: Config<I>, I: 'static = ()> = StorageValue<_, EraIndex, ValueQuery>;
 
+	/// The number of nominators.
+	///
+	/// NominatorsCount: u32
+	#[pallet::storage]
+	#[pallet::getter(fn nominators_count)]
+	pub type NominatorsCount<T: Config<I>, I: 'static = ()> = StorageValue<_, u32, ValueQuery>;
+
+	/// The number of voters for the candidates of nominators' council.
+	///
+	/// CouncilVotersCount: u32
+	#[pallet::storage]
+	#[pallet::getter(fn council_voters_count)]
+	pub type CouncilVotersCount<T: Config<I>, I: 'static = ()> = StorageValue<_, u32, ValueQuery>;
+
+	/// The deposit reserved from the nominator.
+	///
+	/// NominatorDeposits: map AccountId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn nominator_deposits)]
+	pub type NominatorDeposits<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, T::AccountId, Balance, ValueQuery>;
+
+	/// The deposit reserved from the council voter.
+	///
+	/// CouncilVoterDeposits: map AccountId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn council_voter_deposits)]
+	pub type CouncilVoterDeposits<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, T::AccountId, Balance, ValueQuery>;
+
+	/// Storage version of the module.
+	///
+	/// StorageVersion: Releases
+	#[pallet::storage]
+	#[pallet::getter(fn storage_version)]
+	pub(crate) type StorageVersion<T: Config<I>, I: 'static = ()> = StorageValue<_, Releases, ValueQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
 
 	#[pallet::hooks]
-	impl<T: Config<I>, I: 'static> Hooks<T::BlockNumber> for Pallet<T, I> {}
+	impl<T: Config<I>, I: 'static> Hooks<T::BlockNumber> for Pallet<T, I> {
+		fn on_runtime_upgrade() -> Weight {
+			if Self::storage_version() == Releases::V1_0_0 {
+				Self::migrate_to_v2()
+			} else {
+				0
+			}
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config<I>, I: 'static> Pallet<T, I> {
@@ -345,11 +487,23 @@ pub mod module {
 				ensure!(T::NomineeFilter::contains(&validator), Error::<T, I>::InvalidNominee);
 			}
 
+			if !Nominations::<T, I>::contains_key(&who) {
+				NominatorsCount::<T, I>::try_mutate(|count| -> DispatchResult {
+					ensure!(*count < T::MaxNominators::get(), Error::<T, I>::TooManyNominators);
+					*count += 1;
+					Ok(())
+				})?;
+				let deposit = T::NominatorDeposit::get();
+				T::DepositCurrency::reserve_named(&RESERVE_ID, &who, deposit)?;
+				NominatorDeposits::<T, I>::insert(&who, deposit);
+			}
+
 			let old_nominations = Self::nominations(&who);
 			let old_active = Self::ledger(&who).active;
 
 			Self::update_votes(old_active, &old_nominations, old_active, &bounded_targets);
 			Nominations::<T, I>::insert(&who, &bounded_targets);
+			LastConfirmedEra::<T, I>::insert(&who, Self::current_era());
 			Ok(().into())
 		}
 
@@ -362,9 +516,94 @@ pub mod module {
 			let old_active = Self::ledger(&who).active;
 
 			Self::update_votes(old_active, &old_nominations, Zero::zero(), &[]);
-			Nominations::<T, I>::remove(&who);
+			Self::remove_nominations(&who);
 			Ok(Some(T::WeightInfo::chill(old_nominations.len() as u32)).into())
 		}
+
+		/// Re-confirm the current nominations to restore the full weight of
+		/// votes.
+		#[pallet::weight(T::WeightInfo::refresh_vote())]
+		#[transactional]
+		pub fn refresh_vote(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!Self::nominations(&who).is_empty(), Error::<T, I>::NoNominations);
+
+			let current_era = Self::current_era();
+			LastConfirmedEra::<T, I>::insert(&who, current_era);
+			Self::deposit_event(Event::RefreshVote(who, current_era));
+			Ok(().into())
+		}
+
+		/// Vote for the candidate of nominators' council with the active
+		/// bonded, the council is elected at the beginning of every era.
+		///
+		/// - `candidate`: the candidate of nominators' council.
+		#[pallet::weight(T::WeightInfo::vote_council())]
+		#[transactional]
+		pub fn vote_council(origin: OriginFor<T>, candidate: T::AccountId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!Self::ledger(&who).total.is_zero(), Error::<T, I>::NoBonded);
+
+			if !CouncilVotes::<T, I>::contains_key(&who) {
+				CouncilVotersCount::<T, I>::try_mutate(|count| -> DispatchResult {
+					ensure!(*count < T::MaxCouncilVoters::get(), Error::<T, I>::TooManyCouncilVoters);
+					*count += 1;
+					Ok(())
+				})?;
+				let deposit = T::CouncilVoterDeposit::get();
+				T::DepositCurrency::reserve_named(&RESERVE_ID, &who, deposit)?;
+				CouncilVoterDeposits::<T, I>::insert(&who, deposit);
+			}
+
+			CouncilVotes::<T, I>::insert(&who, &candidate);
+			Self::deposit_event(Event::VoteCouncil(who, candidate));
+			Ok(().into())
+		}
+
+		/// Revoke the vote for the candidate of nominators' council.
+		#[pallet::weight(T::WeightInfo::revoke_council_vote())]
+		#[transactional]
+		pub fn revoke_council_vote(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			if Self::remove_council_vote(&who) {
+				Self::deposit_event(Event::RevokeCouncilVote(who));
+			}
+			Ok(().into())
+		}
+
+		/// Adjust the elected nominees before the next election.
+		///
+		/// The dispatch origin of this call must be `AdjustOrigin`.
+		///
+		/// - `removes`: the nominees to remove.
+		/// - `adds`: the nominees to add, must be contained by `NomineeFilter`.
+		#[pallet::weight(T::WeightInfo::adjust_nominees((removes.len() + adds.len()) as u32))]
+		#[transactional]
+		pub fn adjust_nominees(
+			origin: OriginFor<T>,
+			removes: Vec<T::NomineeId>,
+			adds: Vec<T::NomineeId>,
+		) -> DispatchResultWithPostInfo {
+			T::AdjustOrigin::ensure_origin(origin)?;
+
+			let mut nominees = Self::nominees().into_inner();
+			nominees.retain(|nominee| !removes.contains(nominee));
+			for nominee in adds {
+				ensure!(T::NomineeFilter::contains(&nominee), Error::<T, I>::InvalidNominee);
+				if !nominees.contains(&nominee) {
+					nominees.push(nominee);
+				}
+			}
+
+			let bounded_nominees: BoundedVec<<T as Config<I>>::NomineeId, <T as Config<I>>::NominateesCount> = nominees
+				.clone()
+				.try_into()
+				.map_err(|_| Error::<T, I>::NominateesCountExceeded)?;
+			Nominees::<T, I>::put(bounded_nominees);
+			T::OnNomineesAdjusted::happened(&nominees);
+			Self::deposit_event(Event::NomineesAdjusted(nominees));
+			Ok(().into())
+		}
 	}
 }
 
@@ -397,7 +636,77 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		}
 
 		Ledger::<T, I>::remove(who);
+		Self::remove_nominations(who);
+		Self::remove_council_vote(who);
+	}
+
+	/// Remove the vote for the candidate of nominators' council and return
+	/// its deposit, return whether there was a vote.
+	fn remove_council_vote(who: &T::AccountId) -> bool {
+		let voted = CouncilVotes::<T, I>::take(who).is_some();
+		if voted {
+			CouncilVotersCount::<T, I>::mutate(|count| *count = count.saturating_sub(1));
+			T::DepositCurrency::unreserve_named(&RESERVE_ID, who, CouncilVoterDeposits::<T, I>::take(who));
+		}
+		voted
+	}
+
+	fn remove_nominations(who: &T::AccountId) {
+		if Nominations::<T, I>::contains_key(who) {
+			NominatorsCount::<T, I>::mutate(|count| *count = count.saturating_sub(1));
+			T::DepositCurrency::unreserve_named(&RESERVE_ID, who, NominatorDeposits::<T, I>::take(who));
+		}
 		Nominations::<T, I>::remove(who);
+		LastConfirmedEra::<T, I>::remove(who);
+	}
+
+	/// Seed `LastConfirmedEra` with the current era for the nominators that
+	/// nominated before the vote decay was introduced, otherwise their votes
+	/// would decay entirely at the first rebalance after the upgrade.
+	///
+	/// The nominators beyond `MaxNominators` are trimmed by the least active
+	/// bonded, then by the account id, so that the rebalance counts all the
+	/// nominators left.
+	fn migrate_to_v2() -> Weight {
+		let current_era = Self::current_era();
+		let mut nominators: Vec<(T::AccountId, Balance)> = Vec::new();
+		let mut seeded: u32 = 0;
+		for nominator in Nominations::<T, I>::iter_keys() {
+			if !LastConfirmedEra::<T, I>::contains_key(&nominator) {
+				LastConfirmedEra::<T, I>::insert(&nominator, current_era);
+				seeded = seeded.saturating_add(1);
+			}
+			let active = Self::ledger(&nominator).active;
+			nominators.push((nominator, active));
+		}
+		let nominators_count = nominators.len() as u32;
+		let council_voters = CouncilVotes::<T, I>::iter_keys().count() as u32;
+		NominatorsCount::<T, I>::put(nominators_count);
+		CouncilVotersCount::<T, I>::put(council_voters);
+
+		let max_nominators: usize = T::MaxNominators::get().saturated_into();
+		let mut trimmed: u32 = 0;
+		if nominators.len() > max_nominators {
+			nominators.sort_by(|(a, a_active), (b, b_active)| b_active.cmp(a_active).then_with(|| a.cmp(b)));
+			for (nominator, active) in nominators.into_iter().skip(max_nominators) {
+				Self::update_votes(active, &Self::nominations(&nominator), Zero::zero(), &[]);
+				Self::remove_nominations(&nominator);
+				Self::deposit_event(Event::NominationsRemoved(nominator));
+				trimmed = trimmed.saturating_add(1);
+			}
+		}
+		StorageVersion::<T, I>::put(Releases::V2_0_0);
+
+		let trimmed_weight = T::WeightInfo::chill(T::NominateesCount::get()).saturating_mul(trimmed as Weight);
+		T::DbWeight::get()
+			.reads_writes(
+				(nominators_count as Weight)
+					.saturating_mul(3)
+					.saturating_add(council_voters as Weight)
+					.saturating_add(2),
+				(seeded as Weight).saturating_add(3),
+			)
+			.saturating_add(trimmed_weight)
 	}
 
 	fn update_votes(
@@ -419,13 +728,46 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		}
 	}
 
+	/// The weight of votes after decay, which decrease linearly from `active`
+	/// to zero in `VoteDecayPeriod` eras since the last confirmation.
+	fn decayed_vote_weight(active: Balance, confirmed_era: EraIndex, current_era: EraIndex) -> Balance {
+		let decay_period = T::VoteDecayPeriod::get();
+		if decay_period.is_zero() {
+			return active;
+		}
+
+		let elapsed = current_era.saturating_sub(confirmed_era).min(decay_period);
+		Ratio::checked_from_rational(decay_period - elapsed, decay_period)
+			.unwrap_or_default()
+			.saturating_mul_int(active)
+	}
+
 	fn rebalance() {
-		let mut voters = Votes::<T, I>::iter().collect::<Vec<(T::NomineeId, Balance)>>();
+		let current_era = Self::current_era();
+		let mut decayed_votes: BTreeMap<T::NomineeId, Balance> = BTreeMap::new();
+		for (nominator, nominations) in Nominations::<T, I>::iter().take(T::MaxNominators::get().saturated_into()) {
+			let weight = Self::decayed_vote_weight(
+				Self::ledger(&nominator).active,
+				Self::last_confirmed_era(&nominator),
+				current_era,
+			);
+			if weight.is_zero() {
+				continue;
+			}
+
+			for nominee in nominations {
+				let votes = decayed_votes.entry(nominee).or_default();
+				*votes = votes.saturating_add(weight);
+			}
+		}
+
+		let mut voters = decayed_votes.into_iter().collect::<Vec<(T::NomineeId, Balance)>>();
 
 		voters.sort_by(|a, b| b.1.cmp(&a.1));
 
 		let new_nominees: BoundedVec<<T as Config<I>>::NomineeId, <T as Config<I>>::NominateesCount> = voters
 			.into_iter()
+			.filter(|(nominee, _)| T::NomineeFilter::contains(nominee))
 			.take(T::NominateesCount::get().saturated_into())
 			.map(|(nominee, _)| nominee)
 			.collect::<Vec<_>>()
@@ -434,6 +776,40 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 
 		Nominees::<T, I>::put(new_nominees);
 	}
+
+	fn elect_council() {
+		let mut council_votes: BTreeMap<T::AccountId, Balance> = BTreeMap::new();
+		for (voter, candidate) in CouncilVotes::<T, I>::iter().take(T::MaxCouncilVoters::get().saturated_into()) {
+			let active = Self::ledger(&voter).active;
+			if active.is_zero() {
+				continue;
+			}
+
+			let votes = council_votes.entry(candidate).or_default();
+			*votes = votes.saturating_add(active);
+		}
+
+		let mut candidates = council_votes.into_iter().collect::<Vec<(T::AccountId, Balance)>>();
+
+		candidates.sort_by(|a, b| b.1.cmp(&a.1));
+
+		let members: BoundedVec<T::AccountId, T::CouncilSize> = candidates
+			.into_iter()
+			.take(T::CouncilSize::get().saturated_into())
+			.map(|(candidate, _)| candidate)
+			.collect::<Vec<_>>()
+			.try_into()
+			.expect("Only took from candidates");
+
+		let mut old_members = Self::council().into_inner();
+		let mut new_members = members.clone().into_inner();
+		old_members.sort();
+		new_members.sort();
+		T::CouncilMembersChanged::set_members_sorted(&new_members, &old_members);
+
+		Council::<T, I>::put(members.clone());
+		Self::deposit_event(Event::CouncilElected(members.into_inner()));
+	}
 }
 
 impl<T: Config<I>, I: 'static> NomineesProvider<T::NomineeId> for Pallet<T, I> {
@@ -446,5 +822,6 @@ impl<T: Config<I>, I: 'static> OnNewEra<EraIndex> for Pallet<T, I> {
 	fn on_new_era(era: EraIndex) {
 		CurrentEra::<T, I>::put(era);
 		Self::rebalance();
+		Self::elect_council();
 	}
 }
@@ -23,17 +23,20 @@
 use super::*;
 
 use crate as nominees_election;
-use frame_support::{construct_runtime, parameter_types};
+use frame_support::{construct_runtime, ord_parameter_types, parameter_types};
+use frame_system::EnsureSignedBy;
 use orml_traits::parameter_type_with_key;
-use primitives::{Amount, CurrencyId, TokenSymbol};
+use primitives::{Amount, CurrencyId, ReserveIdentifier, TokenSymbol};
 use sp_core::H256;
 use sp_runtime::{testing::Header, traits::IdentityLookup};
+use sp_std::cell::RefCell;
 
 pub type AccountId = u128;
 pub type BlockNumber = u64;
 
 pub const ALICE: AccountId = 0;
 pub const BOB: AccountId = 1;
+pub const CHARLIE: AccountId = 2;
 pub const ACA: CurrencyId = CurrencyId::Token(TokenSymbol::ACA);
 pub const LDOT: CurrencyId = CurrencyId::Token(TokenSymbol::LDOT);
 
@@ -90,6 +93,7 @@ impl orml_tokens::Config for Runtime {
 
 parameter_types! {
 	pub const ExistentialDeposit: Balance = 1;
+	pub const MaxReserves: u32 = 50;
 }
 
 impl pallet_balances::Config for Runtime {
@@ -99,8 +103,8 @@ impl pallet_balances::Config for Runtime {
 	type ExistentialDeposit = ExistentialDeposit;
 	type AccountStore = System;
 	type MaxLocks = ();
-	type MaxReserves = ();
-	type ReserveIdentifier = [u8; 8];
+	type MaxReserves = MaxReserves;
+	type ReserveIdentifier = ReserveIdentifier;
 	type WeightInfo = ();
 }
 
@@ -125,9 +129,38 @@ parameter_types! {
 	pub const BondingDuration: EraIndex = 4;
 	pub const NominateesCount: u32 = 5;
 	pub const MaxUnlockingChunks: u32 = 3;
+	pub const VoteDecayPeriod: EraIndex = 4;
+	pub const CouncilSize: u32 = 2;
+	pub const MaxNominators: u32 = 2;
+	pub const MaxCouncilVoters: u32 = 2;
+	pub const NominatorDeposit: Balance = 10;
+	pub const CouncilVoterDeposit: Balance = 20;
 	pub const PalletId: LockIdentifier = *b"1       ";
 }
 
+ord_parameter_types! {
+	pub const CouncilCollective: AccountId = 100;
+}
+
+thread_local! {
+	pub static ADJUSTED_NOMINEES: RefCell<Vec<AccountId>> = RefCell::new(vec![]);
+	pub static COUNCIL_MEMBERS: RefCell<Vec<AccountId>> = RefCell::new(vec![]);
+}
+
+pub struct MockCouncilMembersChanged;
+impl ChangeMembers<AccountId> for MockCouncilMembersChanged {
+	fn change_members_sorted(_incoming: &[AccountId], _outgoing: &[AccountId], sorted_new: &[AccountId]) {
+		COUNCIL_MEMBERS.with(|v| *v.borrow_mut() = sorted_new.to_vec());
+	}
+}
+
+pub struct MockOnNomineesAdjusted;
+impl Happened<Vec<AccountId>> for MockOnNomineesAdjusted {
+	fn happened(nominees: &Vec<AccountId>) {
+		ADJUSTED_NOMINEES.with(|v| *v.borrow_mut() = nominees.clone());
+	}
+}
+
 pub struct MockNomineeFilter;
 impl Contains<AccountId> for MockNomineeFilter {
 	fn contains(a: &AccountId) -> bool {
@@ -141,6 +174,7 @@ impl Contains<AccountId> for MockNomineeFilter {
 impl Config for Runtime {
 	type Event = Event;
 	type Currency = LDOTCurrency;
+	type DepositCurrency = PalletBalances;
 	type NomineeId = AccountId;
 	type PalletId = PalletId;
 	type MinBondThreshold = MinBondThreshold;
@@ -148,6 +182,15 @@ impl Config for Runtime {
 	type NominateesCount = NominateesCount;
 	type MaxUnlockingChunks = MaxUnlockingChunks;
 	type NomineeFilter = MockNomineeFilter;
+	type VoteDecayPeriod = VoteDecayPeriod;
+	type CouncilSize = CouncilSize;
+	type MaxNominators = MaxNominators;
+	type MaxCouncilVoters = MaxCouncilVoters;
+	type NominatorDeposit = NominatorDeposit;
+	type CouncilVoterDeposit = CouncilVoterDeposit;
+	type CouncilMembersChanged = MockCouncilMembersChanged;
+	type AdjustOrigin = EnsureSignedBy<CouncilCollective, AccountId>;
+	type OnNomineesAdjusted = MockOnNomineesAdjusted;
 	type WeightInfo = ();
 }
 
@@ -163,7 +206,7 @@ construct_runtime!(
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		NomineesElectionModule: nominees_election::{Pallet, Call, Storage, Event<T>},
 		TokensModule: orml_tokens::{Pallet, Storage, Event<T>, Config<T>},
-		PalletBalances: pallet_balances::{Pallet, Call, Storage, Event<T>},
+		PalletBalances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		OrmlCurrencies: orml_currencies::{Pallet, Call, Event<T>},
 	}
 );
@@ -175,12 +218,17 @@ pub struct ExtBuilder {
 impl Default for ExtBuilder {
 	fn default() -> Self {
 		Self {
-			balances: vec![(ALICE, LDOT, 1000), (BOB, LDOT, 1000)],
+			balances: vec![(ALICE, LDOT, 1000), (BOB, LDOT, 1000), (CHARLIE, LDOT, 1000)],
 		}
 	}
 }
 
 impl ExtBuilder {
+	pub fn balances(mut self, balances: Vec<(AccountId, CurrencyId, Balance)>) -> Self {
+		self.balances = balances;
+		self
+	}
+
 	pub fn build(self) -> sp_io::TestExternalities {
 		let mut t = frame_system::GenesisConfig::default()
 			.build_storage::<Runtime>()
@@ -192,6 +240,12 @@ impl ExtBuilder {
 		.assimilate_storage(&mut t)
 		.unwrap();
 
+		pallet_balances::GenesisConfig::<Runtime> {
+			balances: vec![(ALICE, 100), (BOB, 100), (CHARLIE, 100)],
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
 		t.into()
 	}
 }
@@ -23,6 +23,7 @@
 use super::*;
 use frame_support::{assert_noop, assert_ok};
 use mock::*;
+use sp_runtime::traits::BadOrigin;
 
 #[test]
 fn bond_below_min_bond_threshold() {
@@ -237,6 +238,73 @@ fn rebalance_work() {
 	});
 }
 
+#[test]
+fn refresh_vote_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			NomineesElectionModule::refresh_vote(Origin::signed(ALICE)),
+			Error::<Runtime>::NoNominations,
+		);
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(ALICE), 500));
+		assert_ok!(NomineesElectionModule::nominate(
+			Origin::signed(ALICE),
+			vec![1, 2, 3, 4, 5]
+		));
+		assert_eq!(NomineesElectionModule::last_confirmed_era(&ALICE), 0);
+
+		NomineesElectionModule::on_new_era(3);
+		assert_ok!(NomineesElectionModule::refresh_vote(Origin::signed(ALICE)));
+		System::assert_last_event(Event::NomineesElectionModule(crate::Event::RefreshVote(ALICE, 3)));
+		assert_eq!(NomineesElectionModule::last_confirmed_era(&ALICE), 3);
+
+		assert_ok!(NomineesElectionModule::chill(Origin::signed(ALICE)));
+		assert_eq!(NomineesElectionModule::last_confirmed_era(&ALICE), 0);
+	});
+}
+
+#[test]
+fn decayed_vote_weight_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(NomineesElectionModule::decayed_vote_weight(1000, 0, 0), 1000);
+		assert_eq!(NomineesElectionModule::decayed_vote_weight(1000, 0, 1), 750);
+		assert_eq!(NomineesElectionModule::decayed_vote_weight(1000, 2, 5), 250);
+		assert_eq!(NomineesElectionModule::decayed_vote_weight(1000, 2, 6), 0);
+		assert_eq!(NomineesElectionModule::decayed_vote_weight(1000, 2, 100), 0);
+	});
+}
+
+#[test]
+fn rebalance_with_vote_decay_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(ALICE), 500));
+		assert_ok!(NomineesElectionModule::nominate(
+			Origin::signed(ALICE),
+			vec![1, 2, 3, 4, 5]
+		));
+
+		NomineesElectionModule::on_new_era(2);
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(BOB), 100));
+		assert_ok!(NomineesElectionModule::nominate(Origin::signed(BOB), vec![6]));
+
+		// ALICE's votes have decayed to 125, but still greater than BOB's 75
+		NomineesElectionModule::on_new_era(3);
+		assert_eq!(NomineesElectionModule::nominees().len(), 5);
+		assert_eq!(NomineesElectionModule::nominees().contains(&6), false);
+
+		// ALICE's votes have decayed to 0
+		NomineesElectionModule::on_new_era(4);
+		assert_eq!(NomineesElectionModule::nominees(), vec![6]);
+		assert_eq!(NomineesElectionModule::votes(1), 500);
+
+		// re-confirm to restore the full weight
+		assert_ok!(NomineesElectionModule::refresh_vote(Origin::signed(ALICE)));
+		NomineesElectionModule::on_new_era(5);
+		assert_eq!(NomineesElectionModule::nominees().len(), 5);
+		assert_eq!(NomineesElectionModule::nominees().contains(&6), false);
+	});
+}
+
 #[test]
 fn update_votes_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -257,3 +325,221 @@ fn update_votes_work() {
 		assert_eq!(NomineesElectionModule::votes(4), 10);
 	});
 }
+
+#[test]
+fn nominators_council_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			NomineesElectionModule::vote_council(Origin::signed(ALICE), 10),
+			Error::<Runtime>::NoBonded,
+		);
+
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(ALICE), 500));
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(BOB), 100));
+		assert_ok!(NomineesElectionModule::nominate(
+			Origin::signed(ALICE),
+			vec![1, 2, 3, 4, 5]
+		));
+		assert_ok!(NomineesElectionModule::vote_council(Origin::signed(ALICE), 10));
+		System::assert_last_event(Event::NomineesElectionModule(crate::Event::VoteCouncil(ALICE, 10)));
+		assert_ok!(NomineesElectionModule::vote_council(Origin::signed(BOB), 11));
+		assert_eq!(NomineesElectionModule::council_votes(&BOB), Some(11));
+
+		NomineesElectionModule::on_new_era(1);
+		assert_eq!(NomineesElectionModule::council(), vec![10, 11]);
+		assert_eq!(COUNCIL_MEMBERS.with(|v| v.borrow().clone()), vec![10, 11]);
+		System::assert_last_event(Event::NomineesElectionModule(crate::Event::CouncilElected(vec![
+			10, 11,
+		])));
+		assert_eq!(NomineesElectionModule::nominees(), vec![1, 2, 3, 4, 5]);
+
+		assert_noop!(
+			NomineesElectionModule::adjust_nominees(Origin::signed(10), vec![1], vec![6]),
+			BadOrigin,
+		);
+		assert_noop!(
+			NomineesElectionModule::adjust_nominees(Origin::signed(CouncilCollective::get()), vec![1], vec![7]),
+			Error::<Runtime>::InvalidNominee,
+		);
+		assert_noop!(
+			NomineesElectionModule::adjust_nominees(Origin::signed(CouncilCollective::get()), vec![], vec![6]),
+			Error::<Runtime>::NominateesCountExceeded,
+		);
+
+		assert_ok!(NomineesElectionModule::adjust_nominees(
+			Origin::signed(CouncilCollective::get()),
+			vec![1],
+			vec![6, 2]
+		));
+		assert_eq!(NomineesElectionModule::nominees(), vec![2, 3, 4, 5, 6]);
+		assert_eq!(ADJUSTED_NOMINEES.with(|v| v.borrow().clone()), vec![2, 3, 4, 5, 6]);
+		System::assert_last_event(Event::NomineesElectionModule(crate::Event::NomineesAdjusted(vec![
+			2, 3, 4, 5, 6,
+		])));
+
+		assert_eq!(NomineesElectionModule::council_voters_count(), 2);
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(CHARLIE), 100));
+		assert_noop!(
+			NomineesElectionModule::vote_council(Origin::signed(CHARLIE), 12),
+			Error::<Runtime>::TooManyCouncilVoters,
+		);
+		// change the candidate does not count as a new voter
+		assert_ok!(NomineesElectionModule::vote_council(Origin::signed(BOB), 12));
+
+		assert_ok!(NomineesElectionModule::revoke_council_vote(Origin::signed(BOB)));
+		System::assert_last_event(Event::NomineesElectionModule(crate::Event::RevokeCouncilVote(BOB)));
+		assert_eq!(NomineesElectionModule::council_votes(&BOB), None);
+		assert_eq!(NomineesElectionModule::council_voters_count(), 1);
+		NomineesElectionModule::on_new_era(2);
+		assert_eq!(NomineesElectionModule::council(), vec![10]);
+		assert_eq!(COUNCIL_MEMBERS.with(|v| v.borrow().clone()), vec![10]);
+	});
+}
+
+#[test]
+fn nominate_exceed_max_nominators() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(ALICE), 500));
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(BOB), 500));
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(CHARLIE), 500));
+		assert_ok!(NomineesElectionModule::nominate(Origin::signed(ALICE), vec![1]));
+		assert_ok!(NomineesElectionModule::nominate(Origin::signed(BOB), vec![2]));
+		assert_eq!(NomineesElectionModule::nominators_count(), 2);
+		assert_noop!(
+			NomineesElectionModule::nominate(Origin::signed(CHARLIE), vec![3]),
+			Error::<Runtime>::TooManyNominators,
+		);
+
+		// re-nominate does not count as a new nominator
+		assert_ok!(NomineesElectionModule::nominate(Origin::signed(ALICE), vec![3]));
+		assert_eq!(NomineesElectionModule::nominators_count(), 2);
+
+		assert_ok!(NomineesElectionModule::chill(Origin::signed(ALICE)));
+		assert_eq!(NomineesElectionModule::nominators_count(), 1);
+		assert_ok!(NomineesElectionModule::nominate(Origin::signed(CHARLIE), vec![3]));
+		assert_eq!(NomineesElectionModule::nominators_count(), 2);
+	});
+}
+
+#[test]
+fn migrate_to_v2_seeds_last_confirmed_era() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(ALICE), 500));
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(BOB), 500));
+		assert_ok!(NomineesElectionModule::nominate(Origin::signed(ALICE), vec![1]));
+		assert_ok!(NomineesElectionModule::nominate(Origin::signed(BOB), vec![2]));
+		// the state before `LastConfirmedEra` and `NominatorsCount` were introduced
+		LastConfirmedEra::<Runtime>::remove(&ALICE);
+		LastConfirmedEra::<Runtime>::insert(&BOB, 1);
+		NominatorsCount::<Runtime>::kill();
+		CurrentEra::<Runtime>::put(3);
+		assert_eq!(NomineesElectionModule::storage_version(), Releases::V1_0_0);
+
+		NomineesElectionModule::on_runtime_upgrade();
+		assert_eq!(NomineesElectionModule::storage_version(), Releases::V2_0_0);
+		assert_eq!(NomineesElectionModule::last_confirmed_era(&ALICE), 3);
+		assert_eq!(NomineesElectionModule::last_confirmed_era(&BOB), 1);
+		assert_eq!(NomineesElectionModule::nominators_count(), 2);
+		assert_eq!(NomineesElectionModule::council_voters_count(), 0);
+
+		// runs only once
+		LastConfirmedEra::<Runtime>::remove(&ALICE);
+		assert_eq!(NomineesElectionModule::on_runtime_upgrade(), 0);
+		assert_eq!(NomineesElectionModule::last_confirmed_era(&ALICE), 0);
+	});
+}
+
+#[test]
+fn migrate_to_v2_trims_nominators_above_max() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(ALICE), 500));
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(BOB), 300));
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(CHARLIE), 300));
+		assert_ok!(NomineesElectionModule::nominate(Origin::signed(ALICE), vec![1]));
+		assert_ok!(NomineesElectionModule::nominate(Origin::signed(BOB), vec![2]));
+		// the state before `NominatorsCount` was introduced, with more nominators
+		// than `MaxNominators`
+		NominatorsCount::<Runtime>::kill();
+		assert_ok!(NomineesElectionModule::nominate(Origin::signed(CHARLIE), vec![2]));
+		NominatorsCount::<Runtime>::kill();
+		assert_eq!(NomineesElectionModule::votes(2), 600);
+
+		NomineesElectionModule::on_runtime_upgrade();
+		assert_eq!(NomineesElectionModule::nominators_count(), 2);
+		// the least active bonded is trimmed, the tie is broken by the account id
+		System::assert_last_event(Event::NomineesElectionModule(crate::Event::NominationsRemoved(CHARLIE)));
+		assert_eq!(NomineesElectionModule::nominations(&CHARLIE), vec![]);
+		assert_eq!(NomineesElectionModule::nominations(&BOB), vec![2]);
+		assert_eq!(NomineesElectionModule::nominations(&ALICE), vec![1]);
+		assert_eq!(NomineesElectionModule::votes(2), 300);
+		assert_eq!(NomineesElectionModule::ledger(&CHARLIE).active, 300);
+	});
+}
+
+#[test]
+fn nominator_and_council_voter_deposits_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(ALICE), 500));
+		assert_ok!(NomineesElectionModule::nominate(Origin::signed(ALICE), vec![1]));
+		assert_eq!(PalletBalances::reserved_balance(ALICE), 10);
+		assert_eq!(NomineesElectionModule::nominator_deposits(ALICE), 10);
+
+		// re-nominate does not reserve again
+		assert_ok!(NomineesElectionModule::nominate(Origin::signed(ALICE), vec![2]));
+		assert_eq!(PalletBalances::reserved_balance(ALICE), 10);
+
+		assert_ok!(NomineesElectionModule::vote_council(Origin::signed(ALICE), 10));
+		assert_ok!(NomineesElectionModule::vote_council(Origin::signed(ALICE), 11));
+		assert_eq!(PalletBalances::reserved_balance(ALICE), 30);
+		assert_eq!(NomineesElectionModule::council_voter_deposits(ALICE), 20);
+
+		assert_ok!(NomineesElectionModule::chill(Origin::signed(ALICE)));
+		assert_eq!(PalletBalances::reserved_balance(ALICE), 20);
+		assert_eq!(NomineesElectionModule::nominator_deposits(ALICE), 0);
+
+		assert_ok!(NomineesElectionModule::revoke_council_vote(Origin::signed(ALICE)));
+		assert_eq!(PalletBalances::reserved_balance(ALICE), 0);
+		assert_eq!(NomineesElectionModule::council_voter_deposits(ALICE), 0);
+		assert_eq!(PalletBalances::free_balance(ALICE), 100);
+	});
+}
+
+#[test]
+fn deposits_are_returned_when_ledger_removed() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(ALICE), 500));
+		assert_ok!(NomineesElectionModule::nominate(Origin::signed(ALICE), vec![1]));
+		assert_ok!(NomineesElectionModule::vote_council(Origin::signed(ALICE), 10));
+		assert_eq!(PalletBalances::reserved_balance(ALICE), 30);
+
+		assert_ok!(NomineesElectionModule::unbond(Origin::signed(ALICE), 500));
+		NomineesElectionModule::on_new_era(BondingDuration::get());
+		assert_ok!(NomineesElectionModule::withdraw_unbonded(Origin::signed(ALICE)));
+		assert_eq!(NomineesElectionModule::nominations(&ALICE), vec![]);
+		assert_eq!(NomineesElectionModule::council_votes(&ALICE), None);
+		assert_eq!(PalletBalances::reserved_balance(ALICE), 0);
+		assert_eq!(PalletBalances::free_balance(ALICE), 100);
+	});
+}
+
+#[test]
+fn nominate_and_vote_council_require_deposit() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, LDOT, 1000), (3, LDOT, 1000)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(NomineesElectionModule::bond(Origin::signed(3), 500));
+			assert_noop!(
+				NomineesElectionModule::nominate(Origin::signed(3), vec![1]),
+				pallet_balances::Error::<Runtime>::InsufficientBalance,
+			);
+			assert_noop!(
+				NomineesElectionModule::vote_council(Origin::signed(3), 10),
+				pallet_balances::Error::<Runtime>::InsufficientBalance,
+			);
+			assert_eq!(NomineesElectionModule::nominators_count(), 0);
+			assert_eq!(NomineesElectionModule::council_voters_count(), 0);
+		});
+}
@@ -0,0 +1,206 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Parameters Module
+//!
+//! ## Overview
+//!
+//! The frequently tuned risk parameters, e.g. the stability fees and the liquidation ratios of the
+//! collaterals, can be adjusted by a lower privileged origin than the one of their modules, within
+//! the bounds set by the bounds origin. A parameter can only be updated after its bounds are set,
+//! to a value between the min and the max, and its total change in a period is limited by the
+//! max change.
+//!
+//! The values of the parameters are kept in their modules, `ParameterHandler` reads them and
+//! applies the updates.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{pallet_prelude::*, transactional};
+use frame_system::pallet_prelude::*;
+use primitives::CurrencyId;
+use sp_runtime::{
+	traits::{Saturating, Zero},
+	FixedU128, RuntimeDebug,
+};
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+/// The parameter which can be adjusted by this module.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum ParameterKey {
+	/// The stability fee, i.e. the extra interest rate per sec, of the collateral.
+	StabilityFee(CurrencyId),
+	/// The liquidation ratio of the collateral.
+	LiquidationRatio(CurrencyId),
+	/// The dex saving reward rate of the liquidity pool of the dex share.
+	DexSavingRewardRate(CurrencyId),
+}
+
+/// The bounds of the updates of a parameter.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct ParameterBounds<BlockNumber> {
+	/// The min value of the parameter.
+	pub min: FixedU128,
+	/// The max value of the parameter.
+	pub max: FixedU128,
+	/// The max total change of the parameter in a period.
+	pub max_change: FixedU128,
+	/// The length of the period in blocks.
+	pub period: BlockNumber,
+}
+
+/// The modules which own the values of the parameters.
+pub trait ParameterHandler {
+	/// The current value of the parameter, `None` if it is not set.
+	fn get(key: &ParameterKey) -> Option<FixedU128>;
+	/// Update the parameter to `value`.
+	fn set(key: &ParameterKey, value: FixedU128) -> DispatchResult;
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The origin which may set the bounds of the parameters.
+		type BoundsOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The origin which may update the parameters within their bounds.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The modules which own the values of the parameters.
+		type ParameterHandler: ParameterHandler;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The min is greater than the max, or the period is zero
+		InvalidBounds,
+		/// The bounds of the parameter are not set
+		ParameterNotBounded,
+		/// The value is out of the bounds
+		OutOfBounds,
+		/// The total change of the parameter in the period exceeds the max change
+		ExceedMaxChange,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Updated the bounds of the parameter. \[key, bounds\]
+		BoundsUpdated(ParameterKey, Option<ParameterBounds<T::BlockNumber>>),
+		/// Updated the parameter. \[key, value\]
+		ParameterUpdated(ParameterKey, FixedU128),
+	}
+
+	/// The bounds of the updates of the parameters.
+	///
+	/// Bounds: map ParameterKey => Option<ParameterBounds>
+	#[pallet::storage]
+	#[pallet::getter(fn bounds)]
+	pub type Bounds<T: Config> =
+		StorageMap<_, Twox64Concat, ParameterKey, ParameterBounds<T::BlockNumber>, OptionQuery>;
+
+	/// The start block of the current period of the parameter, and its value at the start.
+	///
+	/// PeriodStarts: map ParameterKey => Option<(BlockNumber, FixedU128)>
+	#[pallet::storage]
+	#[pallet::getter(fn period_starts)]
+	pub type PeriodStarts<T: Config> =
+		StorageMap<_, Twox64Concat, ParameterKey, (T::BlockNumber, FixedU128), OptionQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set or remove the bounds of the parameter.
+		///
+		/// The dispatch origin of this call must be `BoundsOrigin`.
+		///
+		/// - `key`: the parameter.
+		/// - `bounds`: the bounds, `None` to remove them and stop the updates of the parameter.
+		#[pallet::weight(T::WeightInfo::set_bounds())]
+		#[transactional]
+		pub fn set_bounds(
+			origin: OriginFor<T>,
+			key: ParameterKey,
+			bounds: Option<ParameterBounds<T::BlockNumber>>,
+		) -> DispatchResultWithPostInfo {
+			T::BoundsOrigin::ensure_origin(origin)?;
+			if let Some(bounds) = bounds {
+				ensure!(
+					bounds.min <= bounds.max && !bounds.period.is_zero(),
+					Error::<T>::InvalidBounds
+				);
+			}
+
+			Bounds::<T>::mutate_exists(key, |maybe_bounds| *maybe_bounds = bounds);
+			if bounds.is_none() {
+				PeriodStarts::<T>::remove(key);
+			}
+			Self::deposit_event(Event::BoundsUpdated(key, bounds));
+			Ok(().into())
+		}
+
+		/// Update the parameter within its bounds.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `key`: the parameter.
+		/// - `value`: the new value, the change from the value at the start of the current period
+		///   must not exceed the max change. If the parameter is not set yet, the first value is
+		///   only limited by the min and the max.
+		#[pallet::weight(T::WeightInfo::set_parameter())]
+		#[transactional]
+		pub fn set_parameter(origin: OriginFor<T>, key: ParameterKey, value: FixedU128) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let bounds = Self::bounds(key).ok_or(Error::<T>::ParameterNotBounded)?;
+			ensure!(bounds.min <= value && value <= bounds.max, Error::<T>::OutOfBounds);
+
+			let now = frame_system::Pallet::<T>::block_number();
+			let (start, start_value) = match Self::period_starts(key) {
+				Some((start, start_value)) if now < start.saturating_add(bounds.period) => (start, start_value),
+				_ => (now, T::ParameterHandler::get(&key).unwrap_or(value)),
+			};
+			let change = value.saturating_sub(start_value).max(start_value.saturating_sub(value));
+			ensure!(change <= bounds.max_change, Error::<T>::ExceedMaxChange);
+
+			T::ParameterHandler::set(&key, value)?;
+			PeriodStarts::<T>::insert(key, (start, start_value));
+			Self::deposit_event(Event::ParameterUpdated(key, value));
+			Ok(().into())
+		}
+	}
+}
@@ -0,0 +1,139 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Mocks for the parameters module.
+
+#![cfg(test)]
+
+use super::*;
+
+use crate as parameters;
+use frame_support::{construct_runtime, ord_parameter_types, parameter_types};
+use frame_system::EnsureSignedBy;
+use primitives::TokenSymbol;
+use sp_core::H256;
+use sp_runtime::{testing::Header, traits::IdentityLookup};
+use sp_std::cell::RefCell;
+
+pub type AccountId = u128;
+pub type BlockNumber = u64;
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const DOT: CurrencyId = CurrencyId::Token(TokenSymbol::DOT);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Runtime {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+thread_local! {
+	static PARAMETERS: RefCell<Vec<(ParameterKey, FixedU128)>> = RefCell::new(vec![]);
+}
+
+pub struct MockParameterHandler;
+impl ParameterHandler for MockParameterHandler {
+	fn get(key: &ParameterKey) -> Option<FixedU128> {
+		PARAMETERS.with(|v| v.borrow().iter().find(|(k, _)| k == key).map(|(_, value)| *value))
+	}
+
+	fn set(key: &ParameterKey, value: FixedU128) -> DispatchResult {
+		PARAMETERS.with(|v| {
+			let mut parameters = v.borrow_mut();
+			parameters.retain(|(k, _)| k != key);
+			parameters.push((*key, value));
+		});
+		Ok(())
+	}
+}
+
+ord_parameter_types! {
+	pub const One: AccountId = 1;
+	pub const Two: AccountId = 2;
+}
+
+impl Config for Runtime {
+	type Event = Event;
+	type BoundsOrigin = EnsureSignedBy<One, AccountId>;
+	type UpdateOrigin = EnsureSignedBy<Two, AccountId>;
+	type ParameterHandler = MockParameterHandler;
+	type WeightInfo = ();
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		ParametersModule: parameters::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+#[derive(Default)]
+pub struct ExtBuilder {
+	parameters: Vec<(ParameterKey, FixedU128)>,
+}
+
+impl ExtBuilder {
+	pub fn parameters(mut self, parameters: Vec<(ParameterKey, FixedU128)>) -> Self {
+		self.parameters = parameters;
+		self
+	}
+
+	pub fn build(self) -> sp_io::TestExternalities {
+		let t = frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap();
+
+		PARAMETERS.with(|v| *v.borrow_mut() = self.parameters);
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
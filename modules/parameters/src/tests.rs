@@ -0,0 +1,167 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the parameters module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+use sp_runtime::{traits::BadOrigin, FixedPointNumber};
+
+const KEY: ParameterKey = ParameterKey::StabilityFee(DOT);
+
+fn percent(n: u128) -> FixedU128 {
+	FixedU128::saturating_from_rational(n, 100)
+}
+
+fn bounds(min: u128, max: u128, max_change: u128, period: BlockNumber) -> ParameterBounds<BlockNumber> {
+	ParameterBounds {
+		min: percent(min),
+		max: percent(max),
+		max_change: percent(max_change),
+		period,
+	}
+}
+
+#[test]
+fn set_bounds_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			ParametersModule::set_bounds(Origin::signed(BOB), KEY, Some(bounds(0, 10, 1, 10))),
+			BadOrigin
+		);
+		assert_noop!(
+			ParametersModule::set_bounds(Origin::signed(ALICE), KEY, Some(bounds(10, 0, 1, 10))),
+			Error::<Runtime>::InvalidBounds
+		);
+		assert_noop!(
+			ParametersModule::set_bounds(Origin::signed(ALICE), KEY, Some(bounds(0, 10, 1, 0))),
+			Error::<Runtime>::InvalidBounds
+		);
+
+		assert_ok!(ParametersModule::set_bounds(
+			Origin::signed(ALICE),
+			KEY,
+			Some(bounds(0, 10, 1, 10))
+		));
+		System::assert_last_event(Event::ParametersModule(crate::Event::BoundsUpdated(
+			KEY,
+			Some(bounds(0, 10, 1, 10)),
+		)));
+		assert_eq!(ParametersModule::bounds(KEY), Some(bounds(0, 10, 1, 10)));
+
+		assert_ok!(ParametersModule::set_parameter(Origin::signed(BOB), KEY, percent(1)));
+		assert_eq!(ParametersModule::period_starts(KEY), Some((1, percent(1))));
+
+		assert_ok!(ParametersModule::set_bounds(Origin::signed(ALICE), KEY, None));
+		System::assert_last_event(Event::ParametersModule(crate::Event::BoundsUpdated(KEY, None)));
+		assert_eq!(ParametersModule::bounds(KEY), None);
+		assert_eq!(ParametersModule::period_starts(KEY), None);
+	});
+}
+
+#[test]
+fn set_parameter_work() {
+	ExtBuilder::default()
+		.parameters(vec![(KEY, percent(5))])
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				ParametersModule::set_parameter(Origin::signed(ALICE), KEY, percent(6)),
+				BadOrigin
+			);
+			assert_noop!(
+				ParametersModule::set_parameter(Origin::signed(BOB), KEY, percent(6)),
+				Error::<Runtime>::ParameterNotBounded
+			);
+
+			assert_ok!(ParametersModule::set_bounds(
+				Origin::signed(ALICE),
+				KEY,
+				Some(bounds(2, 10, 5, 10))
+			));
+			assert_noop!(
+				ParametersModule::set_parameter(Origin::signed(BOB), KEY, percent(1)),
+				Error::<Runtime>::OutOfBounds
+			);
+			assert_noop!(
+				ParametersModule::set_parameter(Origin::signed(BOB), KEY, percent(11)),
+				Error::<Runtime>::OutOfBounds
+			);
+
+			assert_ok!(ParametersModule::set_parameter(Origin::signed(BOB), KEY, percent(6)));
+			System::assert_last_event(Event::ParametersModule(crate::Event::ParameterUpdated(KEY, percent(6))));
+			assert_eq!(MockParameterHandler::get(&KEY), Some(percent(6)));
+			assert_eq!(MockParameterHandler::get(&ParameterKey::LiquidationRatio(DOT)), None);
+		});
+}
+
+#[test]
+fn max_change_per_period_work() {
+	ExtBuilder::default()
+		.parameters(vec![(KEY, percent(5))])
+		.build()
+		.execute_with(|| {
+			assert_ok!(ParametersModule::set_bounds(
+				Origin::signed(ALICE),
+				KEY,
+				Some(bounds(0, 100, 2, 10))
+			));
+
+			// the changes are accumulated in the period
+			assert_ok!(ParametersModule::set_parameter(Origin::signed(BOB), KEY, percent(6)));
+			assert_ok!(ParametersModule::set_parameter(Origin::signed(BOB), KEY, percent(7)));
+			assert_noop!(
+				ParametersModule::set_parameter(Origin::signed(BOB), KEY, percent(8)),
+				Error::<Runtime>::ExceedMaxChange
+			);
+			assert_ok!(ParametersModule::set_parameter(Origin::signed(BOB), KEY, percent(3)));
+			assert_noop!(
+				ParametersModule::set_parameter(Origin::signed(BOB), KEY, percent(2)),
+				Error::<Runtime>::ExceedMaxChange
+			);
+			assert_eq!(ParametersModule::period_starts(KEY), Some((1, percent(5))));
+
+			// the next period starts from the current value
+			System::set_block_number(11);
+			assert_noop!(
+				ParametersModule::set_parameter(Origin::signed(BOB), KEY, percent(6)),
+				Error::<Runtime>::ExceedMaxChange
+			);
+			assert_ok!(ParametersModule::set_parameter(Origin::signed(BOB), KEY, percent(5)));
+			assert_eq!(ParametersModule::period_starts(KEY), Some((11, percent(3))));
+		});
+}
+
+#[test]
+fn first_value_is_not_limited_by_max_change() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(ParametersModule::set_bounds(
+			Origin::signed(ALICE),
+			KEY,
+			Some(bounds(0, 100, 2, 10))
+		));
+		assert_ok!(ParametersModule::set_parameter(Origin::signed(BOB), KEY, percent(50)));
+		assert_noop!(
+			ParametersModule::set_parameter(Origin::signed(BOB), KEY, percent(53)),
+			Error::<Runtime>::ExceedMaxChange
+		);
+	});
+}
@@ -22,18 +22,23 @@
 use frame_support::{log, pallet_prelude::*, traits::Get, transactional, BoundedVec};
 use frame_system::pallet_prelude::*;
 use orml_traits::BasicCurrency;
-use primitives::{Balance, EraIndex};
+use primitives::{Balance, BlockNumber as RelaychainBlockNumber, EraIndex};
 use sp_runtime::{
-	traits::{CheckedSub, MaybeDisplay, MaybeSerializeDeserialize, Member, StaticLookup, Zero},
-	ArithmeticError, DispatchResult, FixedPointNumber, RuntimeDebug,
+	traits::{CheckedSub, Convert, MaybeDisplay, MaybeSerializeDeserialize, Member, StaticLookup, Zero},
+	ArithmeticError, DispatchError, DispatchResult, FixedPointNumber, RuntimeDebug,
 };
 use sp_std::{convert::TryInto, fmt::Debug, prelude::*};
 use support::{
 	OnNewEra, PolkadotBridge, PolkadotBridgeCall, PolkadotBridgeState, PolkadotBridgeType, PolkadotStakingLedger,
-	PolkadotUnlockChunk, Rate,
+	PolkadotUnlockChunk, Rate, RelaychainStateVerifier,
 };
 
+mod mock;
+mod tests;
+pub mod weights;
+
 pub use module::*;
+pub use weights::WeightInfo;
 
 /// The params related to rebalance per era
 #[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, Default)]
@@ -47,12 +52,49 @@ pub struct SubAccountStatus<Unbonding> {
 	pub mock_reward_rate: Rate,
 }
 
+/// The `ActiveEra` of staking module on relaychain.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct RelaychainActiveEraInfo {
+	/// Index of era.
+	pub index: EraIndex,
+	/// Moment of start expressed as millisecond from `$UNIX_EPOCH`.
+	pub start: Option<u64>,
+}
+
+/// The `UnlockChunk` of staking module on relaychain.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct RelaychainUnlockChunk {
+	/// Amount of funds to be unlocked.
+	#[codec(compact)]
+	pub value: Balance,
+	/// Era number at which point it'll be unlocked.
+	#[codec(compact)]
+	pub era: EraIndex,
+}
+
+/// The `StakingLedger` of staking module on relaychain, the trailing claimed rewards are not
+/// decoded.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct RelaychainStakingLedger<AccountId> {
+	/// The stash account whose balance is actually locked and at stake.
+	pub stash: AccountId,
+	/// The total amount of the stash's balance that we are currently accounting for.
+	#[codec(compact)]
+	pub total: Balance,
+	/// The total amount of the stash's balance that will be at stake in any forthcoming rounds.
+	#[codec(compact)]
+	pub active: Balance,
+	/// Any balance that is becoming free.
+	pub unlocking: Vec<RelaychainUnlockChunk>,
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 		type DOTCurrency: BasicCurrency<Self::AccountId, Balance = Balance>;
 		type OnNewEra: OnNewEra<EraIndex>;
 		#[pallet::constant]
@@ -62,12 +104,41 @@ pub mod module {
 		type PolkadotAccountId: Parameter + Member + MaybeSerializeDeserialize + Debug + MaybeDisplay + Ord + Default;
 		#[pallet::constant]
 		type MaxUnbonding: Get<u32>;
+		/// Verify the relaychain storage proofs. If relaychain is not followed,
+		/// the era will be simulated by `EraLength`.
+		type RelaychainState: RelaychainStateVerifier<RelaychainBlockNumber>;
+		/// The account on relaychain of the sub account.
+		type RelaychainSubAccount: Convert<u32, Self::PolkadotAccountId>;
+		/// The maximum size in bytes of the relaychain storage proof.
+		#[pallet::constant]
+		type MaxProofSize: Get<u32>;
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::error]
 	pub enum Error<T> {
 		NotEnough,
 		MaxUnbondingExceeded,
+		/// The relaychain storage proof is invalid.
+		InvalidProof,
+		/// The relaychain era is not greater than current era.
+		EraNotAdvanced,
+		/// The relaychain block is not later than the last synced one.
+		RelaychainBlockNotAdvanced,
+		/// The relaychain storage proof exceeds `MaxProofSize`.
+		ProofTooLarge,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Current era bumped by the relaychain storage proof.
+		/// \[who, relaychain_block_number, new_era\]
+		EraBumped(T::AccountId, RelaychainBlockNumber, EraIndex),
+		/// The ledger of sub account synced by the relaychain storage proof.
+		/// \[who, relaychain_block_number, account_index, bonded\]
+		SubAccountLedgerSynced(T::AccountId, RelaychainBlockNumber, u32, Balance),
 	}
 
 	type Unbonding<T> = BoundedVec<(EraIndex, Balance), <T as Config>::MaxUnbonding>;
@@ -80,31 +151,32 @@ pub mod module {
 	#[pallet::getter(fn era_start_block_number)]
 	pub type EraStartBlockNumber<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
 
-	#[pallet::storage]
-	#[pallet::getter(fn forced_era)]
-	pub type ForcedEra<T: Config> = StorageValue<_, T::BlockNumber, OptionQuery>;
-
 	#[pallet::storage]
 	#[pallet::getter(fn sub_accounts)]
 	pub type SubAccounts<T: Config> = StorageMap<_, Twox64Concat, u32, SubAccountStatus<Unbonding<T>>, ValueQuery>;
 
+	/// The relaychain block number the ledger of sub account is last synced
+	/// at.
+	///
+	/// SubAccountLedgerSyncedAt: map u32 => Option<RelaychainBlockNumber>
+	#[pallet::storage]
+	#[pallet::getter(fn sub_account_ledger_synced_at)]
+	pub type SubAccountLedgerSyncedAt<T: Config> = StorageMap<_, Twox64Concat, u32, RelaychainBlockNumber, OptionQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
 		fn on_finalize(now: T::BlockNumber) {
-			let force_era = Self::forced_era().map_or(false, |block| {
-				if block == now {
-					<ForcedEra<T>>::kill();
-					true
-				} else {
-					false
-				}
-			});
-			let len = now.checked_sub(&Self::era_start_block_number()).unwrap_or_default();
+			// the era is bumped by the proofs if relaychain is followed.
+			if T::RelaychainState::latest_relaychain_block_number().is_some() {
+				return;
+			}
 
-			if len >= T::EraLength::get() || force_era {
+			// simulate the era by `EraLength` if not running on relaychain.
+			let len = now.checked_sub(&Self::era_start_block_number()).unwrap_or_default();
+			if len >= T::EraLength::get() {
 				Self::new_era(now);
 			}
 		}
@@ -223,28 +295,152 @@ pub mod module {
 			Ok(().into())
 		}
 
-		#[pallet::weight(10_000)]
+		/// Bump current era to the active era on relaychain, which is proved
+		/// by the relaychain storage proof at a recent relaychain block.
+		///
+		/// - `relaychain_block_number`: the relaychain block the proof is
+		///   generated at.
+		/// - `proof`: the storage proof of `Staking::ActiveEra` on relaychain.
+		#[pallet::weight(T::WeightInfo::bump_era(proof_size(proof)))]
 		#[transactional]
-		pub fn force_era(origin: OriginFor<T>, at: T::BlockNumber) -> DispatchResultWithPostInfo {
-			ensure_root(origin)?;
-			if at > <frame_system::Pallet<T>>::block_number() {
-				ForcedEra::<T>::put(at);
-			}
+		pub fn bump_era(
+			origin: OriginFor<T>,
+			relaychain_block_number: RelaychainBlockNumber,
+			proof: Vec<Vec<u8>>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let active_era = Self::verify_active_era(relaychain_block_number, proof)?;
+			ensure!(active_era > Self::current_era(), Error::<T>::EraNotAdvanced);
+
+			Self::bump_era_to(active_era, <frame_system::Pallet<T>>::block_number());
+			Self::deposit_event(Event::EraBumped(who, relaychain_block_number, active_era));
+			Ok(().into())
+		}
+
+		/// Sync the bonded and unbonding amounts of sub account to its staking
+		/// ledger on relaychain, which is proved by the relaychain storage
+		/// proof at a recent relaychain block.
+		///
+		/// - `account_index`: the index of sub account.
+		/// - `relaychain_block_number`: the relaychain block the proof is
+		///   generated at, must be later than the last synced one.
+		/// - `proof`: the storage proof of `Staking::Ledger` of sub account on
+		///   relaychain.
+		#[pallet::weight(T::WeightInfo::sync_sub_account_ledger(proof_size(proof)))]
+		#[transactional]
+		pub fn sync_sub_account_ledger(
+			origin: OriginFor<T>,
+			account_index: u32,
+			relaychain_block_number: RelaychainBlockNumber,
+			proof: Vec<Vec<u8>>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				Self::sub_account_ledger_synced_at(account_index)
+					.map_or(true, |number| number < relaychain_block_number),
+				Error::<T>::RelaychainBlockNotAdvanced
+			);
+			let ledger = Self::verify_sub_account_ledger(account_index, relaychain_block_number, proof)?;
+
+			SubAccounts::<T>::try_mutate(account_index, |status| -> DispatchResult {
+				status.bonded = ledger.active;
+				status.unbonding = ledger
+					.unlocking
+					.into_iter()
+					.map(|chunk| (chunk.era, chunk.value))
+					.collect::<Vec<_>>()
+					.try_into()
+					.map_err(|_| Error::<T>::MaxUnbondingExceeded)?;
+				Ok(())
+			})?;
+			SubAccountLedgerSyncedAt::<T>::insert(account_index, relaychain_block_number);
+
+			Self::deposit_event(Event::SubAccountLedgerSynced(
+				who,
+				relaychain_block_number,
+				account_index,
+				ledger.active,
+			));
 			Ok(().into())
 		}
 	}
 }
 
+/// The total size in bytes of the trie nodes in the storage proof.
+pub fn proof_size(proof: &[Vec<u8>]) -> u32 {
+	proof
+		.iter()
+		.fold(0u32, |size, node| size.saturating_add(node.len() as u32))
+}
+
 impl<T: Config> Pallet<T> {
 	pub fn new_era(now: T::BlockNumber) {
-		let new_era = CurrentEra::<T>::mutate(|era| {
-			*era += 1;
-			*era
-		});
+		Self::bump_era_to(Self::current_era() + 1, now);
+	}
+
+	fn bump_era_to(new_era: EraIndex, now: T::BlockNumber) {
+		CurrentEra::<T>::put(new_era);
 		EraStartBlockNumber::<T>::put(now);
 		T::OnNewEra::on_new_era(new_era);
 	}
 
+	/// The storage key of `Staking::ActiveEra` on relaychain.
+	fn active_era_storage_key() -> Vec<u8> {
+		[
+			sp_io::hashing::twox_128(b"Staking"),
+			sp_io::hashing::twox_128(b"ActiveEra"),
+		]
+		.concat()
+	}
+
+	/// The storage key of `Staking::Ledger` of sub account on relaychain.
+	fn sub_account_ledger_storage_key(account_index: u32) -> Vec<u8> {
+		let account = T::RelaychainSubAccount::convert(account_index).encode();
+		[
+			&sp_io::hashing::twox_128(b"Staking")[..],
+			&sp_io::hashing::twox_128(b"Ledger")[..],
+			&sp_io::hashing::blake2_128(&account)[..],
+			&account[..],
+		]
+		.concat()
+	}
+
+	/// Ensure the size of the storage proof doesn't exceed `MaxProofSize`.
+	fn ensure_proof_size(proof: &[Vec<u8>]) -> DispatchResult {
+		ensure!(proof_size(proof) <= T::MaxProofSize::get(), Error::<T>::ProofTooLarge);
+		Ok(())
+	}
+
+	/// Read the index of the active era on relaychain from the storage proof.
+	fn verify_active_era(
+		relaychain_block_number: RelaychainBlockNumber,
+		proof: Vec<Vec<u8>>,
+	) -> sp_std::result::Result<EraIndex, DispatchError> {
+		Self::ensure_proof_size(&proof)?;
+		let raw =
+			T::RelaychainState::verify_relay_state(relaychain_block_number, &Self::active_era_storage_key(), proof)?
+				.ok_or(Error::<T>::InvalidProof)?;
+		let active_era = RelaychainActiveEraInfo::decode(&mut &raw[..]).map_err(|_| Error::<T>::InvalidProof)?;
+		Ok(active_era.index)
+	}
+
+	/// Read the staking ledger of sub account on relaychain from the storage
+	/// proof.
+	fn verify_sub_account_ledger(
+		account_index: u32,
+		relaychain_block_number: RelaychainBlockNumber,
+		proof: Vec<Vec<u8>>,
+	) -> sp_std::result::Result<RelaychainStakingLedger<T::PolkadotAccountId>, DispatchError> {
+		Self::ensure_proof_size(&proof)?;
+		let raw = T::RelaychainState::verify_relay_state(
+			relaychain_block_number,
+			&Self::sub_account_ledger_storage_key(account_index),
+			proof,
+		)?
+		.ok_or(Error::<T>::InvalidProof)?;
+		RelaychainStakingLedger::decode(&mut &raw[..]).map_err(|_| Error::<T>::InvalidProof.into())
+	}
+
 	/// simulate bond extra by sub account
 	fn sub_account_bond_extra(account_index: u32, amount: Balance) -> DispatchResult {
 		if !amount.is_zero() {
@@ -346,18 +542,21 @@ impl<T: Config> Pallet<T> {
 		});
 	}
 
-	/// simulate receive staking reward by sub account
-	fn sub_account_payout_stakers(account_index: u32, _era: EraIndex) {
+	/// simulate receive staking reward by sub account, the reward is paid to
+	/// the free balance.
+	fn sub_account_payout_stakers(account_index: u32, _era: EraIndex) -> Balance {
 		SubAccounts::<T>::mutate(account_index, |status| {
 			let reward = status.mock_reward_rate.saturating_mul_int(status.bonded);
-			status.bonded = status.bonded.saturating_add(reward);
+			status.available = status.available.saturating_add(reward);
 
 			log::debug!(
 				target: "polkadot bridge simulator",
 				"sub account {:?} get reward: {:?}",
 				account_index, reward,
 			);
-		});
+
+			reward
+		})
 	}
 
 	/// simulate nominate by sub account
@@ -405,7 +604,7 @@ impl<T: Config> PolkadotBridgeCall<T::AccountId, T::BlockNumber, Balance, EraInd
 		Self::sub_account_withdraw_unbonded(account_index)
 	}
 
-	fn payout_stakers(account_index: u32, era: EraIndex) {
+	fn payout_stakers(account_index: u32, era: EraIndex) -> Balance {
 		Self::sub_account_payout_stakers(account_index, era)
 	}
 
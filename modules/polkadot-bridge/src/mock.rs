@@ -0,0 +1,220 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Mocks for the polkadot bridge module.
+
+#![cfg(test)]
+
+use super::*;
+
+use crate as polkadot_bridge;
+use frame_support::{construct_runtime, parameter_types};
+use orml_traits::parameter_type_with_key;
+use primitives::{Amount, CurrencyId, TokenSymbol};
+use sp_core::H256;
+use sp_runtime::{testing::Header, traits::IdentityLookup};
+use sp_std::cell::RefCell;
+use std::collections::HashMap;
+
+pub type AccountId = u128;
+pub type BlockNumber = u64;
+
+pub const ALICE: AccountId = 1;
+pub const ACA: CurrencyId = CurrencyId::Token(TokenSymbol::ACA);
+pub const DOT: CurrencyId = CurrencyId::Token(TokenSymbol::DOT);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Runtime {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: CurrencyId| -> Balance {
+		Default::default()
+	};
+}
+
+impl orml_tokens::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type Amount = Amount;
+	type CurrencyId = CurrencyId;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type OnDust = ();
+	type MaxLocks = ();
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: Balance = 1;
+}
+
+impl pallet_balances::Config for Runtime {
+	type Balance = Balance;
+	type DustRemoval = ();
+	type Event = Event;
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const GetNativeCurrencyId: CurrencyId = ACA;
+	pub const GetStakingCurrencyId: CurrencyId = DOT;
+}
+
+pub type NativeCurrency = orml_currencies::BasicCurrencyAdapter<Runtime, Balances, Amount, BlockNumber>;
+pub type DOTCurrency = orml_currencies::Currency<Runtime, GetStakingCurrencyId>;
+
+impl orml_currencies::Config for Runtime {
+	type Event = Event;
+	type MultiCurrency = Tokens;
+	type NativeCurrency = NativeCurrency;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type WeightInfo = ();
+}
+
+thread_local! {
+	static RELAYCHAIN_STATE: RefCell<HashMap<Vec<u8>, Vec<u8>>> = RefCell::new(HashMap::new());
+	static NEW_ERA: RefCell<Option<EraIndex>> = RefCell::new(None);
+}
+
+/// Set the value of `key` in the mocked relaychain state.
+pub fn mock_relaychain_state(key: Vec<u8>, value: Vec<u8>) {
+	RELAYCHAIN_STATE.with(|v| v.borrow_mut().insert(key, value));
+}
+
+/// The era of the last `OnNewEra` call.
+pub fn last_new_era() -> Option<EraIndex> {
+	NEW_ERA.with(|v| *v.borrow())
+}
+
+/// The mocked relaychain state, an empty proof is invalid and any other proof
+/// proves the value stored by `mock_relaychain_state`.
+pub struct MockRelaychainState;
+impl RelaychainStateVerifier<RelaychainBlockNumber> for MockRelaychainState {
+	fn latest_relaychain_block_number() -> Option<RelaychainBlockNumber> {
+		Some(100)
+	}
+
+	fn verify_relay_state(
+		_relaychain_block_number: RelaychainBlockNumber,
+		key: &[u8],
+		proof: Vec<Vec<u8>>,
+	) -> sp_std::result::Result<Option<Vec<u8>>, DispatchError> {
+		if proof.is_empty() {
+			return Err(DispatchError::Other("invalid proof"));
+		}
+		Ok(RELAYCHAIN_STATE.with(|v| v.borrow().get(key).cloned()))
+	}
+}
+
+pub struct MockOnNewEra;
+impl OnNewEra<EraIndex> for MockOnNewEra {
+	fn on_new_era(era: EraIndex) {
+		NEW_ERA.with(|v| *v.borrow_mut() = Some(era));
+	}
+}
+
+pub struct MockRelaychainSubAccount;
+impl Convert<u32, AccountId> for MockRelaychainSubAccount {
+	fn convert(account_index: u32) -> AccountId {
+		1000 + account_index as AccountId
+	}
+}
+
+parameter_types! {
+	pub const BondingDuration: EraIndex = 7;
+	pub const EraLength: BlockNumber = 10;
+	pub const MaxUnbonding: u32 = 2;
+	pub const MaxProofSize: u32 = 100;
+}
+
+impl Config for Runtime {
+	type Event = Event;
+	type DOTCurrency = DOTCurrency;
+	type OnNewEra = MockOnNewEra;
+	type BondingDuration = BondingDuration;
+	type EraLength = EraLength;
+	type PolkadotAccountId = AccountId;
+	type MaxUnbonding = MaxUnbonding;
+	type RelaychainState = MockRelaychainState;
+	type RelaychainSubAccount = MockRelaychainSubAccount;
+	type MaxProofSize = MaxProofSize;
+	type WeightInfo = ();
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Tokens: orml_tokens::{Pallet, Storage, Event<T>, Config<T>},
+		Currencies: orml_currencies::{Pallet, Call, Event<T>},
+		PolkadotBridge: polkadot_bridge::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+#[derive(Default)]
+pub struct ExtBuilder;
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let t = frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap();
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
@@ -0,0 +1,182 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the polkadot bridge module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok, weights::GetDispatchInfo};
+use mock::{Event, *};
+
+fn mock_active_era(index: EraIndex) {
+	mock_relaychain_state(
+		PolkadotBridge::active_era_storage_key(),
+		RelaychainActiveEraInfo { index, start: None }.encode(),
+	);
+}
+
+fn mock_ledger(account_index: u32, active: Balance, unlocking: Vec<RelaychainUnlockChunk>) {
+	mock_relaychain_state(
+		PolkadotBridge::sub_account_ledger_storage_key(account_index),
+		RelaychainStakingLedger {
+			stash: MockRelaychainSubAccount::convert(account_index),
+			total: active + unlocking.iter().map(|chunk| chunk.value).sum::<Balance>(),
+			active,
+			unlocking,
+		}
+		.encode(),
+	);
+}
+
+#[test]
+fn bump_era_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		mock_active_era(3);
+		System::set_block_number(5);
+
+		assert_ok!(PolkadotBridge::bump_era(Origin::signed(ALICE), 50, vec![vec![0; 10]]));
+		System::assert_last_event(Event::PolkadotBridge(crate::Event::EraBumped(ALICE, 50, 3)));
+		assert_eq!(PolkadotBridge::current_era(), 3);
+		assert_eq!(PolkadotBridge::era_start_block_number(), 5);
+		assert_eq!(last_new_era(), Some(3));
+
+		assert_noop!(
+			PolkadotBridge::bump_era(Origin::signed(ALICE), 60, vec![vec![0; 10]]),
+			Error::<Runtime>::EraNotAdvanced
+		);
+
+		mock_active_era(4);
+		assert_ok!(PolkadotBridge::bump_era(Origin::signed(ALICE), 60, vec![vec![0; 10]]));
+		assert_eq!(PolkadotBridge::current_era(), 4);
+		assert_eq!(last_new_era(), Some(4));
+	});
+}
+
+#[test]
+fn bump_era_fails_with_invalid_proof() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			PolkadotBridge::bump_era(Origin::signed(ALICE), 50, vec![vec![0; 10]]),
+			Error::<Runtime>::InvalidProof
+		);
+
+		mock_relaychain_state(PolkadotBridge::active_era_storage_key(), vec![1]);
+		assert_noop!(
+			PolkadotBridge::bump_era(Origin::signed(ALICE), 50, vec![vec![0; 10]]),
+			Error::<Runtime>::InvalidProof
+		);
+
+		mock_active_era(3);
+		assert_noop!(
+			PolkadotBridge::bump_era(Origin::signed(ALICE), 50, vec![]),
+			DispatchError::Other("invalid proof")
+		);
+		assert_eq!(last_new_era(), None);
+	});
+}
+
+#[test]
+fn bump_era_fails_with_too_large_proof() {
+	ExtBuilder::default().build().execute_with(|| {
+		mock_active_era(3);
+
+		assert_noop!(
+			PolkadotBridge::bump_era(Origin::signed(ALICE), 50, vec![vec![0; 60], vec![0; 41]]),
+			Error::<Runtime>::ProofTooLarge
+		);
+		assert_ok!(PolkadotBridge::bump_era(
+			Origin::signed(ALICE),
+			50,
+			vec![vec![0; 60], vec![0; 40]]
+		));
+		assert_eq!(PolkadotBridge::current_era(), 3);
+	});
+}
+
+#[test]
+fn proof_weight_grows_with_proof_size() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(proof_size(&[]), 0);
+		assert_eq!(proof_size(&[vec![0; 60], vec![0; 40]]), 100);
+
+		let small = crate::Call::<Runtime>::bump_era(50, vec![vec![0; 10]]).get_dispatch_info();
+		let large = crate::Call::<Runtime>::bump_era(50, vec![vec![0; 100]]).get_dispatch_info();
+		assert!(large.weight > small.weight);
+
+		let small = crate::Call::<Runtime>::sync_sub_account_ledger(0, 50, vec![vec![0; 10]]).get_dispatch_info();
+		let large = crate::Call::<Runtime>::sync_sub_account_ledger(0, 50, vec![vec![0; 100]]).get_dispatch_info();
+		assert!(large.weight > small.weight);
+	});
+}
+
+#[test]
+fn sync_sub_account_ledger_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		mock_ledger(0, 800, vec![RelaychainUnlockChunk { value: 200, era: 10 }]);
+
+		assert_ok!(PolkadotBridge::sync_sub_account_ledger(
+			Origin::signed(ALICE),
+			0,
+			50,
+			vec![vec![0; 10]]
+		));
+		System::assert_last_event(Event::PolkadotBridge(crate::Event::SubAccountLedgerSynced(
+			ALICE, 50, 0, 800,
+		)));
+		let status = PolkadotBridge::sub_accounts(0);
+		assert_eq!(status.bonded, 800);
+		assert_eq!(status.unbonding, vec![(10, 200)]);
+		assert_eq!(PolkadotBridge::sub_account_ledger_synced_at(0), Some(50));
+
+		assert_noop!(
+			PolkadotBridge::sync_sub_account_ledger(Origin::signed(ALICE), 0, 50, vec![vec![0; 10]]),
+			Error::<Runtime>::RelaychainBlockNotAdvanced
+		);
+		assert_noop!(
+			PolkadotBridge::sync_sub_account_ledger(Origin::signed(ALICE), 1, 50, vec![vec![0; 10]]),
+			Error::<Runtime>::InvalidProof
+		);
+	});
+}
+
+#[test]
+fn sync_sub_account_ledger_fails() {
+	ExtBuilder::default().build().execute_with(|| {
+		mock_ledger(
+			0,
+			800,
+			vec![
+				RelaychainUnlockChunk { value: 100, era: 10 },
+				RelaychainUnlockChunk { value: 100, era: 11 },
+				RelaychainUnlockChunk { value: 100, era: 12 },
+			],
+		);
+		assert_noop!(
+			PolkadotBridge::sync_sub_account_ledger(Origin::signed(ALICE), 0, 50, vec![vec![0; 10]]),
+			Error::<Runtime>::MaxUnbondingExceeded
+		);
+
+		mock_ledger(0, 800, vec![]);
+		assert_noop!(
+			PolkadotBridge::sync_sub_account_ledger(Origin::signed(ALICE), 0, 50, vec![vec![0; 101]]),
+			Error::<Runtime>::ProofTooLarge
+		);
+		assert_eq!(PolkadotBridge::sub_account_ledger_synced_at(0), None);
+	});
+}
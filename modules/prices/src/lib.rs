@@ -175,10 +175,12 @@ impl<T: Config> PriceProvider<CurrencyId> for Pallet<T> {
 			let token_0 = match symbol_0 {
 				DexShare::Token(token) => CurrencyId::Token(token),
 				DexShare::Erc20(address) => CurrencyId::Erc20(address),
+				DexShare::LaunchpadToken(token_id) => CurrencyId::LaunchpadToken(token_id),
 			};
 			let token_1 = match symbol_1 {
 				DexShare::Token(token) => CurrencyId::Token(token),
 				DexShare::Erc20(address) => CurrencyId::Erc20(address),
+				DexShare::LaunchpadToken(token_id) => CurrencyId::LaunchpadToken(token_id),
 			};
 
 			return {
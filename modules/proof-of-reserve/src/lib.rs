@@ -0,0 +1,314 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Proof of Reserve Module
+//!
+//! ## Overview
+//!
+//! Verifies that the balance locked on the external chain backing each bridged asset, e.g. renBTC
+//! or the bridged ERC-20s, is at least the local total issuance of the asset. The locked balance is
+//! reported by the reporter origin, e.g. the oracle feeders or a light client module verifying the
+//! proofs, and is checked whenever it's reported and every check period. The mints of the asset
+//! are paused and an alert is emitted if the locked balance falls short of the total issuance, or
+//! it hasn't been reported for too long. The paused mints are resumed by the update origin once
+//! the reserve is healthy again.
+//!
+//! The bridges check the pause through the `MintGuard` implemented by this module before minting.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{pallet_prelude::*, transactional};
+use frame_system::pallet_prelude::*;
+use orml_traits::MultiCurrency;
+use primitives::{Balance, CurrencyId};
+use sp_runtime::{
+	traits::{Saturating, Zero},
+	RuntimeDebug,
+};
+use sp_std::prelude::*;
+use support::MintGuard;
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+/// The reserve backing a bridged asset.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct ReserveInfo<BlockNumber> {
+	/// The balance locked on the external chain.
+	pub locked: Balance,
+	/// The block number the locked balance was reported at.
+	pub updated_at: BlockNumber,
+}
+
+/// The reason the mints of a bridged asset are paused.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum PauseReason<BlockNumber> {
+	/// The locked balance is less than the total issuance.
+	Shortfall { locked: Balance, total_issuance: Balance },
+	/// The locked balance hasn't been reported since `updated_at` for too long.
+	StaleReserve { updated_at: BlockNumber },
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency of the bridged assets.
+		type Currency: MultiCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
+
+		/// The origin which may report the locked balances.
+		type ReporterOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The origin which may monitor the assets and resume the paused mints.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The period to check the reserves of all the monitored assets.
+		#[pallet::constant]
+		type CheckPeriod: Get<Self::BlockNumber>;
+
+		/// The maximum age of the reported locked balance before the reserve is stale.
+		#[pallet::constant]
+		type MaxReserveAge: Get<Self::BlockNumber>;
+
+		/// The maximum number of the monitored assets.
+		#[pallet::constant]
+		type MaxMonitoredAssets: Get<u32>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The asset is not monitored
+		AssetNotMonitored,
+		/// The asset is monitored already
+		AssetAlreadyMonitored,
+		/// Exceed the maximum number of the monitored assets
+		TooManyMonitoredAssets,
+		/// The mints of the asset are paused
+		MintsPaused,
+		/// The mints of the asset are not paused
+		MintsNotPaused,
+		/// The reserve of the asset is not healthy
+		ReserveNotHealthy,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Started monitoring the reserve of the asset. \[currency_id, locked\]
+		AssetMonitored(CurrencyId, Balance),
+		/// Stopped monitoring the reserve of the asset. \[currency_id\]
+		AssetUnmonitored(CurrencyId),
+		/// Reported the locked balance of the asset. \[currency_id, locked\]
+		ReserveReported(CurrencyId, Balance),
+		/// Paused the mints of the asset as the reserve failed the check. \[currency_id, reason\]
+		MintsPaused(CurrencyId, PauseReason<T::BlockNumber>),
+		/// Resumed the mints of the asset. \[currency_id\]
+		MintsResumed(CurrencyId),
+	}
+
+	/// The reserves of the monitored assets.
+	///
+	/// Reserves: map CurrencyId => Option<ReserveInfo>
+	#[pallet::storage]
+	#[pallet::getter(fn reserves)]
+	pub type Reserves<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, ReserveInfo<T::BlockNumber>, OptionQuery>;
+
+	/// The assets of which the mints are paused.
+	///
+	/// PausedMints: map CurrencyId => Option<PauseReason>
+	#[pallet::storage]
+	#[pallet::getter(fn paused_mints)]
+	pub type PausedMints<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, PauseReason<T::BlockNumber>, OptionQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			if now % T::CheckPeriod::get() == Zero::zero() {
+				let mut count: u32 = 0;
+				for (currency_id, reserve) in Reserves::<T>::iter() {
+					if let Some(reason) = Self::check_reserve(currency_id, &reserve, now) {
+						Self::pause_mints(currency_id, reason);
+					}
+					count = count.saturating_add(1);
+				}
+				T::WeightInfo::on_initialize(count)
+			} else {
+				0
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Start monitoring the reserve of the bridged asset.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: the bridged asset.
+		/// - `locked`: the balance locked on the external chain.
+		#[pallet::weight(T::WeightInfo::monitor_asset())]
+		#[transactional]
+		pub fn monitor_asset(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			#[pallet::compact] locked: Balance,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				!Reserves::<T>::contains_key(currency_id),
+				Error::<T>::AssetAlreadyMonitored
+			);
+			ensure!(
+				Reserves::<T>::iter().count() < T::MaxMonitoredAssets::get() as usize,
+				Error::<T>::TooManyMonitoredAssets
+			);
+
+			Self::do_report_reserve(currency_id, locked);
+			Self::deposit_event(Event::AssetMonitored(currency_id, locked));
+			Ok(().into())
+		}
+
+		/// Stop monitoring the reserve of the bridged asset, and resume its mints if they are
+		/// paused.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: the bridged asset.
+		#[pallet::weight(T::WeightInfo::unmonitor_asset())]
+		#[transactional]
+		pub fn unmonitor_asset(origin: OriginFor<T>, currency_id: CurrencyId) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(Reserves::<T>::contains_key(currency_id), Error::<T>::AssetNotMonitored);
+
+			Reserves::<T>::remove(currency_id);
+			PausedMints::<T>::remove(currency_id);
+			Self::deposit_event(Event::AssetUnmonitored(currency_id));
+			Ok(().into())
+		}
+
+		/// Report the balance locked on the external chain backing the bridged asset, the mints
+		/// are paused if it falls short of the total issuance.
+		///
+		/// The dispatch origin of this call must be `ReporterOrigin`.
+		///
+		/// - `currency_id`: the bridged asset.
+		/// - `locked`: the balance locked on the external chain.
+		#[pallet::weight(T::WeightInfo::report_reserve())]
+		#[transactional]
+		pub fn report_reserve(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			#[pallet::compact] locked: Balance,
+		) -> DispatchResultWithPostInfo {
+			T::ReporterOrigin::ensure_origin(origin)?;
+			ensure!(Reserves::<T>::contains_key(currency_id), Error::<T>::AssetNotMonitored);
+
+			Self::do_report_reserve(currency_id, locked);
+			Self::deposit_event(Event::ReserveReported(currency_id, locked));
+			Ok(().into())
+		}
+
+		/// Resume the paused mints of the bridged asset, the reserve must be healthy.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: the bridged asset.
+		#[pallet::weight(T::WeightInfo::resume_mints())]
+		#[transactional]
+		pub fn resume_mints(origin: OriginFor<T>, currency_id: CurrencyId) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(PausedMints::<T>::contains_key(currency_id), Error::<T>::MintsNotPaused);
+			let reserve = Self::reserves(currency_id).ok_or(Error::<T>::AssetNotMonitored)?;
+			ensure!(
+				Self::check_reserve(currency_id, &reserve, frame_system::Pallet::<T>::block_number()).is_none(),
+				Error::<T>::ReserveNotHealthy
+			);
+
+			PausedMints::<T>::remove(currency_id);
+			Self::deposit_event(Event::MintsResumed(currency_id));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	fn do_report_reserve(currency_id: CurrencyId, locked: Balance) {
+		let now = frame_system::Pallet::<T>::block_number();
+		let reserve = ReserveInfo {
+			locked,
+			updated_at: now,
+		};
+		Reserves::<T>::insert(currency_id, reserve);
+
+		if let Some(reason) = Self::check_reserve(currency_id, &reserve, now) {
+			Self::pause_mints(currency_id, reason);
+		}
+	}
+
+	/// Returns the reason to pause the mints if the reserve fails the check.
+	fn check_reserve(
+		currency_id: CurrencyId,
+		reserve: &ReserveInfo<T::BlockNumber>,
+		now: T::BlockNumber,
+	) -> Option<PauseReason<T::BlockNumber>> {
+		if now.saturating_sub(reserve.updated_at) > T::MaxReserveAge::get() {
+			return Some(PauseReason::StaleReserve {
+				updated_at: reserve.updated_at,
+			});
+		}
+
+		let total_issuance = T::Currency::total_issuance(currency_id);
+		if reserve.locked < total_issuance {
+			return Some(PauseReason::Shortfall {
+				locked: reserve.locked,
+				total_issuance,
+			});
+		}
+
+		None
+	}
+
+	fn pause_mints(currency_id: CurrencyId, reason: PauseReason<T::BlockNumber>) {
+		if !PausedMints::<T>::contains_key(currency_id) {
+			PausedMints::<T>::insert(currency_id, reason);
+			Self::deposit_event(Event::MintsPaused(currency_id, reason));
+		}
+	}
+}
+
+impl<T: Config> MintGuard for Pallet<T> {
+	fn ensure_can_mint(currency_id: CurrencyId) -> DispatchResult {
+		ensure!(!PausedMints::<T>::contains_key(currency_id), Error::<T>::MintsPaused);
+		Ok(())
+	}
+}
@@ -0,0 +1,193 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the proof of reserve module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+use sp_runtime::traits::BadOrigin;
+
+#[test]
+fn monitor_asset_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			ProofOfReserveModule::monitor_asset(Origin::signed(ALICE), RENBTC, 1_000),
+			BadOrigin
+		);
+
+		assert_ok!(ProofOfReserveModule::monitor_asset(
+			Origin::signed(COUNCIL),
+			RENBTC,
+			1_000
+		));
+		System::assert_last_event(Event::ProofOfReserveModule(crate::Event::AssetMonitored(RENBTC, 1_000)));
+		assert_eq!(
+			ProofOfReserveModule::reserves(RENBTC),
+			Some(ReserveInfo {
+				locked: 1_000,
+				updated_at: 1
+			})
+		);
+		assert_eq!(ProofOfReserveModule::paused_mints(RENBTC), None);
+
+		assert_noop!(
+			ProofOfReserveModule::monitor_asset(Origin::signed(COUNCIL), RENBTC, 1_000),
+			Error::<Runtime>::AssetAlreadyMonitored
+		);
+
+		assert_ok!(ProofOfReserveModule::monitor_asset(Origin::signed(COUNCIL), DOT, 2_000));
+		assert_noop!(
+			ProofOfReserveModule::monitor_asset(Origin::signed(COUNCIL), AUSD, 0),
+			Error::<Runtime>::TooManyMonitoredAssets
+		);
+	});
+}
+
+#[test]
+fn report_reserve_pauses_mints_on_shortfall() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			ProofOfReserveModule::report_reserve(Origin::signed(REPORTER), RENBTC, 1_000),
+			Error::<Runtime>::AssetNotMonitored
+		);
+		assert_ok!(ProofOfReserveModule::monitor_asset(
+			Origin::signed(COUNCIL),
+			RENBTC,
+			1_000
+		));
+
+		assert_noop!(
+			ProofOfReserveModule::report_reserve(Origin::signed(ALICE), RENBTC, 900),
+			BadOrigin
+		);
+		assert_ok!(ProofOfReserveModule::report_reserve(
+			Origin::signed(REPORTER),
+			RENBTC,
+			900
+		));
+		let reason = PauseReason::Shortfall {
+			locked: 900,
+			total_issuance: 1_000,
+		};
+		System::assert_has_event(Event::ProofOfReserveModule(crate::Event::MintsPaused(RENBTC, reason)));
+		assert_eq!(ProofOfReserveModule::paused_mints(RENBTC), Some(reason));
+		assert_noop!(
+			ProofOfReserveModule::ensure_can_mint(RENBTC),
+			Error::<Runtime>::MintsPaused
+		);
+		assert_ok!(ProofOfReserveModule::ensure_can_mint(DOT));
+
+		assert_ok!(ProofOfReserveModule::report_reserve(
+			Origin::signed(REPORTER),
+			RENBTC,
+			800
+		));
+		assert_noop!(
+			ProofOfReserveModule::resume_mints(Origin::signed(COUNCIL), RENBTC),
+			Error::<Runtime>::ReserveNotHealthy
+		);
+
+		assert_ok!(ProofOfReserveModule::report_reserve(
+			Origin::signed(REPORTER),
+			RENBTC,
+			900
+		));
+		assert_noop!(
+			ProofOfReserveModule::resume_mints(Origin::signed(ALICE), RENBTC),
+			BadOrigin
+		);
+		assert_ok!(ProofOfReserveModule::resume_mints(Origin::signed(COUNCIL), RENBTC));
+		System::assert_last_event(Event::ProofOfReserveModule(crate::Event::MintsResumed(RENBTC)));
+		assert_eq!(ProofOfReserveModule::paused_mints(RENBTC), None);
+		assert_ok!(ProofOfReserveModule::ensure_can_mint(RENBTC));
+		assert_noop!(
+			ProofOfReserveModule::resume_mints(Origin::signed(COUNCIL), RENBTC),
+			Error::<Runtime>::MintsNotPaused
+		);
+	});
+}
+
+#[test]
+fn on_initialize_checks_reserves() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(ProofOfReserveModule::monitor_asset(
+			Origin::signed(COUNCIL),
+			RENBTC,
+			1_000
+		));
+		assert_ok!(ProofOfReserveModule::monitor_asset(Origin::signed(COUNCIL), DOT, 1_000));
+
+		// the total issuance grows without the reserve
+		assert_ok!(TokensModule::deposit(DOT, &ALICE, 100));
+		ProofOfReserveModule::on_initialize(9);
+		assert_eq!(ProofOfReserveModule::paused_mints(DOT), None);
+
+		ProofOfReserveModule::on_initialize(10);
+		assert_eq!(
+			ProofOfReserveModule::paused_mints(DOT),
+			Some(PauseReason::Shortfall {
+				locked: 1_000,
+				total_issuance: 1_100,
+			})
+		);
+		assert_eq!(ProofOfReserveModule::paused_mints(RENBTC), None);
+
+		// the reserve isn't reported for too long
+		System::set_block_number(30);
+		ProofOfReserveModule::on_initialize(30);
+		let reason = PauseReason::StaleReserve { updated_at: 1 };
+		System::assert_last_event(Event::ProofOfReserveModule(crate::Event::MintsPaused(RENBTC, reason)));
+		assert_eq!(ProofOfReserveModule::paused_mints(RENBTC), Some(reason));
+		assert_noop!(
+			ProofOfReserveModule::resume_mints(Origin::signed(COUNCIL), RENBTC),
+			Error::<Runtime>::ReserveNotHealthy
+		);
+
+		assert_ok!(ProofOfReserveModule::report_reserve(
+			Origin::signed(REPORTER),
+			RENBTC,
+			1_000
+		));
+		assert_ok!(ProofOfReserveModule::resume_mints(Origin::signed(COUNCIL), RENBTC));
+	});
+}
+
+#[test]
+fn unmonitor_asset_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			ProofOfReserveModule::unmonitor_asset(Origin::signed(COUNCIL), RENBTC),
+			Error::<Runtime>::AssetNotMonitored
+		);
+		assert_ok!(ProofOfReserveModule::monitor_asset(Origin::signed(COUNCIL), RENBTC, 0));
+		assert!(ProofOfReserveModule::paused_mints(RENBTC).is_some());
+
+		assert_noop!(
+			ProofOfReserveModule::unmonitor_asset(Origin::signed(ALICE), RENBTC),
+			BadOrigin
+		);
+		assert_ok!(ProofOfReserveModule::unmonitor_asset(Origin::signed(COUNCIL), RENBTC));
+		System::assert_last_event(Event::ProofOfReserveModule(crate::Event::AssetUnmonitored(RENBTC)));
+		assert_eq!(ProofOfReserveModule::reserves(RENBTC), None);
+		assert_eq!(ProofOfReserveModule::paused_mints(RENBTC), None);
+		assert_ok!(ProofOfReserveModule::ensure_can_mint(RENBTC));
+	});
+}
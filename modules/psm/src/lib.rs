@@ -0,0 +1,318 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # PSM Module
+//!
+//! ## Overview
+//!
+//! The peg stability module mints stable currency 1:1 against the external stablecoins approved
+//! by the update origin, and redeems the stablecoins with stable currency 1:1, both with a small
+//! fee which goes to the surplus pool of the cdp-treasury. The stablecoins are held by the
+//! cdp-treasury, and the stable currency minted against each stablecoin is limited by its debt
+//! ceiling. It anchors the peg during demand shocks, as the arbitrage through the module is
+//! instant and doesn't depend on the collateral auctions or the DEX.
+//!
+//! The pause origin can pause the swaps of a stablecoin, e.g. when it depegs, and the module is
+//! halted by the emergency shutdown.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{pallet_prelude::*, transactional};
+use frame_system::pallet_prelude::*;
+use primitives::{Balance, CurrencyId};
+use sp_runtime::{traits::Zero, ArithmeticError, Permill, RuntimeDebug};
+use support::{CDPTreasury, CurrencyIdMapping, OnEmergencyShutdown};
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+/// The parameters of the stablecoin approved by the PSM.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct AssetParams {
+	/// The fee rate of the mints and redemptions, charged in stable currency.
+	pub fee: Permill,
+	/// The maximum amount of stable currency minted against the stablecoin.
+	pub debt_ceiling: Balance,
+	/// Whether the mints and redemptions of the stablecoin are paused.
+	pub paused: bool,
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// CDP treasury to hold the stablecoins and to issue and burn stable currency.
+		type CDPTreasury: CDPTreasury<Self::AccountId, Balance = Balance, CurrencyId = CurrencyId>;
+
+		/// Mapping to read the decimals of the currencies.
+		type CurrencyIdMapping: CurrencyIdMapping;
+
+		/// The stable currency id, it should be AUSD in Acala.
+		#[pallet::constant]
+		type GetStableCurrencyId: Get<CurrencyId>;
+
+		/// The origin which may approve and remove stablecoins and update their parameters.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The origin which may pause and unpause the swaps of stablecoins.
+		type PauseOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The currency can't be approved, e.g. it's the stable currency, or its decimals are
+		/// unknown or more than the decimals of stable currency
+		InvalidAsset,
+		/// The stablecoin is not approved
+		AssetNotApproved,
+		/// The swaps of the stablecoin are paused
+		AssetPaused,
+		/// The PSM is halted by the emergency shutdown
+		PsmHalted,
+		/// The amount is zero
+		ZeroAmount,
+		/// The stable currency minted against the stablecoin exceeds its debt ceiling
+		ExceedDebtCeiling,
+		/// The redemption exceeds the stable currency minted against the stablecoin
+		ExceedDebt,
+		/// The stable currency minted against the stablecoin is not redeemed yet
+		OutstandingDebt,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Approved the stablecoin or updated its parameters. \[currency_id, fee, debt_ceiling\]
+		AssetApproved(CurrencyId, Permill, Balance),
+		/// Removed the stablecoin. \[currency_id\]
+		AssetRemoved(CurrencyId),
+		/// Paused the swaps of the stablecoin. \[currency_id\]
+		AssetPaused(CurrencyId),
+		/// Unpaused the swaps of the stablecoin. \[currency_id\]
+		AssetUnpaused(CurrencyId),
+		/// Minted stable currency with the stablecoin. \[who, currency_id, amount,
+		/// received_amount, fee\]
+		Minted(T::AccountId, CurrencyId, Balance, Balance, Balance),
+		/// Redeemed the stablecoin with stable currency. \[who, currency_id, amount,
+		/// paid_amount, fee\]
+		Redeemed(T::AccountId, CurrencyId, Balance, Balance, Balance),
+	}
+
+	/// The parameters of the approved stablecoins.
+	///
+	/// Assets: map CurrencyId => Option<AssetParams>
+	#[pallet::storage]
+	#[pallet::getter(fn assets)]
+	pub type Assets<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, AssetParams, OptionQuery>;
+
+	/// The amount of stable currency minted against the stablecoins.
+	///
+	/// Debts: map CurrencyId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn debts)]
+	pub type Debts<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Balance, ValueQuery>;
+
+	/// Whether the PSM is halted by the emergency shutdown.
+	///
+	/// IsHalted: bool
+	#[pallet::storage]
+	#[pallet::getter(fn is_halted)]
+	pub type IsHalted<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Approve the stablecoin, or update the parameters of the approved stablecoin.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: the stablecoin.
+		/// - `fee`: the fee rate of the mints and redemptions.
+		/// - `debt_ceiling`: the maximum amount of stable currency minted against the stablecoin.
+		#[pallet::weight(T::WeightInfo::approve_asset())]
+		#[transactional]
+		pub fn approve_asset(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			fee: Permill,
+			debt_ceiling: Balance,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(currency_id != T::GetStableCurrencyId::get(), Error::<T>::InvalidAsset);
+			Self::conversion_factor(currency_id)?;
+
+			Assets::<T>::mutate(currency_id, |maybe_params| {
+				let paused = maybe_params.map_or(false, |params| params.paused);
+				*maybe_params = Some(AssetParams {
+					fee,
+					debt_ceiling,
+					paused,
+				});
+			});
+			Self::deposit_event(Event::AssetApproved(currency_id, fee, debt_ceiling));
+			Ok(().into())
+		}
+
+		/// Remove the approved stablecoin, all the stable currency minted against it must have
+		/// been redeemed.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: the stablecoin.
+		#[pallet::weight(T::WeightInfo::remove_asset())]
+		#[transactional]
+		pub fn remove_asset(origin: OriginFor<T>, currency_id: CurrencyId) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(Assets::<T>::contains_key(currency_id), Error::<T>::AssetNotApproved);
+			ensure!(Self::debts(currency_id).is_zero(), Error::<T>::OutstandingDebt);
+
+			Assets::<T>::remove(currency_id);
+			Self::deposit_event(Event::AssetRemoved(currency_id));
+			Ok(().into())
+		}
+
+		/// Pause or unpause the mints and redemptions of the approved stablecoin.
+		///
+		/// The dispatch origin of this call must be `PauseOrigin`.
+		///
+		/// - `currency_id`: the stablecoin.
+		/// - `paused`: whether to pause the swaps.
+		#[pallet::weight(T::WeightInfo::set_paused())]
+		#[transactional]
+		pub fn set_paused(origin: OriginFor<T>, currency_id: CurrencyId, paused: bool) -> DispatchResultWithPostInfo {
+			T::PauseOrigin::ensure_origin(origin)?;
+			Assets::<T>::try_mutate(currency_id, |maybe_params| -> DispatchResult {
+				let params = maybe_params.as_mut().ok_or(Error::<T>::AssetNotApproved)?;
+				params.paused = paused;
+				Ok(())
+			})?;
+
+			if paused {
+				Self::deposit_event(Event::AssetPaused(currency_id));
+			} else {
+				Self::deposit_event(Event::AssetUnpaused(currency_id));
+			}
+			Ok(().into())
+		}
+
+		/// Mint stable currency with the stablecoin 1:1, minus the fee.
+		///
+		/// - `currency_id`: the stablecoin.
+		/// - `amount`: the amount of stablecoin to deposit.
+		#[pallet::weight(T::WeightInfo::mint())]
+		#[transactional]
+		pub fn mint(origin: OriginFor<T>, currency_id: CurrencyId, amount: Balance) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let params = Self::ensure_swappable(currency_id, amount)?;
+
+			let stable_amount = Self::to_stable_amount(currency_id, amount)?;
+			let debt = Self::debts(currency_id)
+				.checked_add(stable_amount)
+				.ok_or(ArithmeticError::Overflow)?;
+			ensure!(debt <= params.debt_ceiling, Error::<T>::ExceedDebtCeiling);
+
+			let fee = params.fee.mul_ceil(stable_amount);
+			let received_amount = stable_amount.saturating_sub(fee);
+			T::CDPTreasury::deposit_collateral(&who, currency_id, amount)?;
+			T::CDPTreasury::issue_debit(&who, received_amount, true)?;
+			T::CDPTreasury::on_system_surplus(fee)?;
+			Debts::<T>::insert(currency_id, debt);
+
+			Self::deposit_event(Event::Minted(who, currency_id, amount, received_amount, fee));
+			Ok(().into())
+		}
+
+		/// Redeem the stablecoin with stable currency 1:1, plus the fee.
+		///
+		/// - `currency_id`: the stablecoin.
+		/// - `amount`: the amount of stablecoin to receive.
+		#[pallet::weight(T::WeightInfo::redeem())]
+		#[transactional]
+		pub fn redeem(origin: OriginFor<T>, currency_id: CurrencyId, amount: Balance) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let params = Self::ensure_swappable(currency_id, amount)?;
+
+			let stable_amount = Self::to_stable_amount(currency_id, amount)?;
+			let debt = Self::debts(currency_id)
+				.checked_sub(stable_amount)
+				.ok_or(Error::<T>::ExceedDebt)?;
+
+			let fee = params.fee.mul_ceil(stable_amount);
+			let paid_amount = stable_amount.checked_add(fee).ok_or(ArithmeticError::Overflow)?;
+			T::CDPTreasury::burn_debit(&who, stable_amount)?;
+			T::CDPTreasury::deposit_surplus(&who, fee)?;
+			T::CDPTreasury::withdraw_collateral(&who, currency_id, amount)?;
+			Debts::<T>::insert(currency_id, debt);
+
+			Self::deposit_event(Event::Redeemed(who, currency_id, amount, paid_amount, fee));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	fn ensure_swappable(currency_id: CurrencyId, amount: Balance) -> Result<AssetParams, DispatchError> {
+		ensure!(!Self::is_halted(), Error::<T>::PsmHalted);
+		ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+		let params = Self::assets(currency_id).ok_or(Error::<T>::AssetNotApproved)?;
+		ensure!(!params.paused, Error::<T>::AssetPaused);
+		Ok(params)
+	}
+
+	/// The factor to convert the amount of stablecoin to the amount of stable currency by their
+	/// decimals.
+	fn conversion_factor(currency_id: CurrencyId) -> Result<Balance, DispatchError> {
+		let decimals = T::CurrencyIdMapping::decimals(currency_id).ok_or(Error::<T>::InvalidAsset)?;
+		let stable_decimals =
+			T::CurrencyIdMapping::decimals(T::GetStableCurrencyId::get()).ok_or(Error::<T>::InvalidAsset)?;
+		let exponent = stable_decimals.checked_sub(decimals).ok_or(Error::<T>::InvalidAsset)?;
+		10u128
+			.checked_pow(exponent.into())
+			.ok_or_else(|| Error::<T>::InvalidAsset.into())
+	}
+
+	/// The amount of stable currency worth the `amount` of the stablecoin.
+	pub fn to_stable_amount(currency_id: CurrencyId, amount: Balance) -> Result<Balance, DispatchError> {
+		Self::conversion_factor(currency_id)?
+			.checked_mul(amount)
+			.ok_or_else(|| ArithmeticError::Overflow.into())
+	}
+}
+
+impl<T: Config> OnEmergencyShutdown for Pallet<T> {
+	fn on_emergency_shutdown() {
+		IsHalted::<T>::put(true);
+	}
+}
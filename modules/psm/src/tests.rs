@@ -0,0 +1,279 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the psm module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+use orml_traits::MultiCurrency;
+use sp_runtime::traits::BadOrigin;
+
+#[test]
+fn approve_asset_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			PsmModule::approve_asset(Origin::signed(BOB), USDC, Permill::from_percent(1), 1_000_000),
+			BadOrigin
+		);
+		assert_noop!(
+			PsmModule::approve_asset(Origin::signed(ALICE), AUSD, Permill::from_percent(1), 1_000_000),
+			Error::<Runtime>::InvalidAsset
+		);
+		assert_noop!(
+			PsmModule::approve_asset(Origin::signed(ALICE), DAI, Permill::from_percent(1), 1_000_000),
+			Error::<Runtime>::InvalidAsset
+		);
+		assert_noop!(
+			PsmModule::approve_asset(
+				Origin::signed(ALICE),
+				CurrencyId::ForeignAsset(2),
+				Permill::from_percent(1),
+				1_000_000
+			),
+			Error::<Runtime>::InvalidAsset
+		);
+
+		assert_ok!(PsmModule::approve_asset(
+			Origin::signed(ALICE),
+			USDC,
+			Permill::from_percent(1),
+			1_000_000
+		));
+		System::assert_last_event(Event::PsmModule(crate::Event::AssetApproved(
+			USDC,
+			Permill::from_percent(1),
+			1_000_000,
+		)));
+		assert_eq!(
+			PsmModule::assets(USDC),
+			Some(AssetParams {
+				fee: Permill::from_percent(1),
+				debt_ceiling: 1_000_000,
+				paused: false,
+			})
+		);
+
+		// the update keeps the pause
+		assert_ok!(PsmModule::set_paused(Origin::signed(BOB), USDC, true));
+		assert_ok!(PsmModule::approve_asset(
+			Origin::signed(ALICE),
+			USDC,
+			Permill::from_percent(2),
+			2_000_000
+		));
+		assert_eq!(
+			PsmModule::assets(USDC),
+			Some(AssetParams {
+				fee: Permill::from_percent(2),
+				debt_ceiling: 2_000_000,
+				paused: true,
+			})
+		);
+	});
+}
+
+#[test]
+fn remove_asset_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			PsmModule::remove_asset(Origin::signed(ALICE), USDC),
+			Error::<Runtime>::AssetNotApproved
+		);
+		assert_ok!(PsmModule::approve_asset(
+			Origin::signed(ALICE),
+			USDC,
+			Permill::zero(),
+			1_000_000_000_000
+		));
+		assert_ok!(PsmModule::mint(Origin::signed(ALICE), USDC, 1_000));
+
+		assert_noop!(PsmModule::remove_asset(Origin::signed(BOB), USDC), BadOrigin);
+		assert_noop!(
+			PsmModule::remove_asset(Origin::signed(ALICE), USDC),
+			Error::<Runtime>::OutstandingDebt
+		);
+
+		assert_ok!(PsmModule::redeem(Origin::signed(ALICE), USDC, 1_000));
+		assert_ok!(PsmModule::remove_asset(Origin::signed(ALICE), USDC));
+		System::assert_last_event(Event::PsmModule(crate::Event::AssetRemoved(USDC)));
+		assert_eq!(PsmModule::assets(USDC), None);
+	});
+}
+
+#[test]
+fn set_paused_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			PsmModule::set_paused(Origin::signed(BOB), USDC, true),
+			Error::<Runtime>::AssetNotApproved
+		);
+		assert_ok!(PsmModule::approve_asset(
+			Origin::signed(ALICE),
+			USDC,
+			Permill::zero(),
+			1_000_000_000_000
+		));
+		assert_ok!(PsmModule::mint(Origin::signed(ALICE), USDC, 1_000));
+
+		assert_noop!(PsmModule::set_paused(Origin::signed(ALICE), USDC, true), BadOrigin);
+		assert_ok!(PsmModule::set_paused(Origin::signed(BOB), USDC, true));
+		System::assert_last_event(Event::PsmModule(crate::Event::AssetPaused(USDC)));
+		assert!(PsmModule::assets(USDC).unwrap().paused);
+		assert_noop!(
+			PsmModule::mint(Origin::signed(ALICE), USDC, 1_000),
+			Error::<Runtime>::AssetPaused
+		);
+		assert_noop!(
+			PsmModule::redeem(Origin::signed(ALICE), USDC, 1_000),
+			Error::<Runtime>::AssetPaused
+		);
+
+		assert_ok!(PsmModule::set_paused(Origin::signed(BOB), USDC, false));
+		System::assert_last_event(Event::PsmModule(crate::Event::AssetUnpaused(USDC)));
+		assert_ok!(PsmModule::redeem(Origin::signed(ALICE), USDC, 1_000));
+	});
+}
+
+#[test]
+fn mint_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			PsmModule::mint(Origin::signed(ALICE), USDC, 1_000_000),
+			Error::<Runtime>::AssetNotApproved
+		);
+		assert_ok!(PsmModule::approve_asset(
+			Origin::signed(ALICE),
+			USDC,
+			Permill::from_percent(1),
+			2_000_000_000_000
+		));
+		assert_noop!(
+			PsmModule::mint(Origin::signed(ALICE), USDC, 0),
+			Error::<Runtime>::ZeroAmount
+		);
+
+		assert_ok!(PsmModule::mint(Origin::signed(ALICE), USDC, 1_000_000));
+		System::assert_last_event(Event::PsmModule(crate::Event::Minted(
+			ALICE,
+			USDC,
+			1_000_000,
+			990_000_000_000,
+			10_000_000_000,
+		)));
+		assert_eq!(Currencies::free_balance(USDC, &ALICE), 999_000_000);
+		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 990_000_000_000);
+		assert_eq!(CDPTreasuryModule::total_collaterals(USDC), 1_000_000);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 10_000_000_000);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
+		assert_eq!(PsmModule::debts(USDC), 1_000_000_000_000);
+
+		// exceeds the debt ceiling
+		assert_noop!(
+			PsmModule::mint(Origin::signed(BOB), USDC, 1_000_001),
+			Error::<Runtime>::ExceedDebtCeiling
+		);
+		assert_ok!(PsmModule::mint(Origin::signed(BOB), USDC, 1_000_000));
+		assert_eq!(PsmModule::debts(USDC), 2_000_000_000_000);
+	});
+}
+
+#[test]
+fn redeem_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			PsmModule::redeem(Origin::signed(ALICE), USDC, 1_000_000),
+			Error::<Runtime>::AssetNotApproved
+		);
+		assert_ok!(PsmModule::approve_asset(
+			Origin::signed(ALICE),
+			USDC,
+			Permill::from_percent(1),
+			2_000_000_000_000
+		));
+		assert_ok!(PsmModule::mint(Origin::signed(ALICE), USDC, 1_000_000));
+		assert_noop!(
+			PsmModule::redeem(Origin::signed(ALICE), USDC, 0),
+			Error::<Runtime>::ZeroAmount
+		);
+		assert_noop!(
+			PsmModule::redeem(Origin::signed(ALICE), USDC, 1_000_001),
+			Error::<Runtime>::ExceedDebt
+		);
+		assert_noop!(
+			PsmModule::redeem(Origin::signed(BOB), USDC, 500_000),
+			orml_tokens::Error::<Runtime>::BalanceTooLow
+		);
+
+		assert_ok!(PsmModule::redeem(Origin::signed(ALICE), USDC, 500_000));
+		System::assert_last_event(Event::PsmModule(crate::Event::Redeemed(
+			ALICE,
+			USDC,
+			500_000,
+			505_000_000_000,
+			5_000_000_000,
+		)));
+		assert_eq!(Currencies::free_balance(USDC, &ALICE), 999_500_000);
+		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 485_000_000_000);
+		assert_eq!(CDPTreasuryModule::total_collaterals(USDC), 500_000);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 15_000_000_000);
+		assert_eq!(PsmModule::debts(USDC), 500_000_000_000);
+	});
+}
+
+#[test]
+fn to_stable_amount_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(PsmModule::to_stable_amount(USDC, 1_000_000), Ok(1_000_000_000_000));
+		assert_eq!(PsmModule::to_stable_amount(CASH, 100_000_000), Ok(1_000_000_000_000));
+		assert_eq!(
+			PsmModule::to_stable_amount(DAI, 1_000_000),
+			Err(Error::<Runtime>::InvalidAsset.into())
+		);
+		assert_eq!(
+			PsmModule::to_stable_amount(USDC, Balance::max_value()),
+			Err(ArithmeticError::Overflow.into())
+		);
+	});
+}
+
+#[test]
+fn emergency_shutdown_halts_psm() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(PsmModule::approve_asset(
+			Origin::signed(ALICE),
+			USDC,
+			Permill::zero(),
+			1_000_000_000_000
+		));
+		assert_ok!(PsmModule::mint(Origin::signed(ALICE), USDC, 1_000));
+
+		PsmModule::on_emergency_shutdown();
+		assert!(PsmModule::is_halted());
+		assert_noop!(
+			PsmModule::mint(Origin::signed(ALICE), USDC, 1_000),
+			Error::<Runtime>::PsmHalted
+		);
+		assert_noop!(
+			PsmModule::redeem(Origin::signed(ALICE), USDC, 1_000),
+			Error::<Runtime>::PsmHalted
+		);
+	});
+}
@@ -0,0 +1,387 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Rate Limit Module
+//!
+//! ## Overview
+//!
+//! The update origin can limit the rate of the sensitive calls, e.g. the bridge withdrawals or the
+//! redeems of Homa, as a defense in depth. A call can be limited per account and globally, to a max
+//! number of calls in a sliding window of blocks.
+//!
+//! The limits are enforced by the `CheckRateLimit` signed extension, which rejects the transactions
+//! exceeding the limits of their calls. The calls nested in the outer call, e.g. the calls of a
+//! batch or a proxy, are visited by the runtime's `NestedCalls` and counted against the signer as
+//! well, so they can't bypass the limits. The sliding window is approximated by the number of
+//! calls in the current fixed window, plus the number of calls in the previous one weighted by its
+//! part still in the sliding window.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{pallet_prelude::*, traits::GetCallMetadata, transactional};
+use frame_system::pallet_prelude::*;
+use sp_runtime::{
+	traits::{AtLeast32BitUnsigned, DispatchInfoOf, Saturating, SignedExtension, Zero},
+	transaction_validity::{InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction},
+	Perbill, RuntimeDebug,
+};
+use sp_std::{collections::btree_map::BTreeMap, marker::PhantomData, prelude::*};
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+/// The custom code of the invalid transaction exceeding the rate limit.
+pub const RATE_LIMIT_EXCEEDED: u8 = 1;
+
+/// The name of the pallet and the name of the call.
+pub type CallName = (Vec<u8>, Vec<u8>);
+
+/// Visits the calls nested in a call, e.g. the calls of a batch, which are dispatched with it.
+pub trait NestedCalls<Call> {
+	/// Call `f` with each of the calls nested directly in `call`.
+	fn visit_nested_calls(call: &Call, f: &mut dyn FnMut(&Call));
+}
+
+impl<Call> NestedCalls<Call> for () {
+	fn visit_nested_calls(_call: &Call, _f: &mut dyn FnMut(&Call)) {}
+}
+
+/// The rate limit of a call.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct RateLimit<BlockNumber> {
+	/// The length of the sliding window in blocks.
+	pub window: BlockNumber,
+	/// The max number of calls of an account in the window, `None` if unlimited.
+	pub per_account: Option<u32>,
+	/// The max number of calls of all the accounts in the window, `None` if unlimited.
+	pub global: Option<u32>,
+}
+
+/// The number of calls in the current fixed window and the previous one.
+#[derive(Encode, Decode, Clone, Copy, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct WindowUsage<BlockNumber> {
+	/// The first block of the current window.
+	pub window_start: BlockNumber,
+	/// The number of calls in the previous window.
+	pub previous: u32,
+	/// The number of calls in the current window.
+	pub current: u32,
+}
+
+impl<BlockNumber: AtLeast32BitUnsigned + Copy> WindowUsage<BlockNumber> {
+	/// The usage with the current window moved to the one that contains `now`.
+	pub fn roll(self, now: BlockNumber, window: BlockNumber) -> Self {
+		let elapsed = now.saturating_sub(self.window_start);
+		if elapsed < window {
+			self
+		} else if elapsed < window.saturating_add(window) {
+			Self {
+				window_start: self.window_start.saturating_add(window),
+				previous: self.current,
+				current: 0,
+			}
+		} else {
+			Self {
+				window_start: now.saturating_sub(elapsed % window),
+				previous: 0,
+				current: 0,
+			}
+		}
+	}
+
+	/// The approximate number of calls in the sliding window that ends at `now`, which should be in
+	/// the current window.
+	pub fn count(&self, now: BlockNumber, window: BlockNumber) -> u32 {
+		let elapsed = now.saturating_sub(self.window_start).min(window);
+		Perbill::from_rational(window.saturating_sub(elapsed), window)
+			.mul_floor(self.previous)
+			.saturating_add(self.current)
+	}
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The origin which may set the rate limits.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Visits the calls nested in the calls of the runtime.
+		type NestedCalls: NestedCalls<<Self as frame_system::Config>::Call>;
+
+		/// The maximum number of the calls nested in a transaction, the transactions with more are
+		/// rejected so the check is bounded.
+		#[pallet::constant]
+		type MaxNestedCalls: Get<u32>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The window of the rate limit is zero
+		InvalidRateLimit,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Updated the rate limit of the call. \[pallet_name, function_name, limit\]
+		RateLimitUpdated(Vec<u8>, Vec<u8>, Option<RateLimit<T::BlockNumber>>),
+	}
+
+	/// The rate limits of the calls.
+	///
+	/// RateLimits: map CallName => Option<RateLimit>
+	#[pallet::storage]
+	#[pallet::getter(fn rate_limits)]
+	pub type RateLimits<T: Config> = StorageMap<_, Twox64Concat, CallName, RateLimit<T::BlockNumber>, OptionQuery>;
+
+	/// The usages of the calls of all the accounts.
+	///
+	/// GlobalUsages: map CallName => WindowUsage
+	#[pallet::storage]
+	#[pallet::getter(fn global_usages)]
+	pub type GlobalUsages<T: Config> = StorageMap<_, Twox64Concat, CallName, WindowUsage<T::BlockNumber>, ValueQuery>;
+
+	/// The usages of the calls of the accounts.
+	///
+	/// AccountUsages: double_map CallName, AccountId => WindowUsage
+	#[pallet::storage]
+	#[pallet::getter(fn account_usages)]
+	pub type AccountUsages<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		CallName,
+		Twox64Concat,
+		T::AccountId,
+		WindowUsage<T::BlockNumber>,
+		ValueQuery,
+	>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set or remove the rate limit of the call.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `pallet_name`: the name of the pallet in the runtime.
+		/// - `function_name`: the name of the call.
+		/// - `limit`: the rate limit, `None` to remove it.
+		#[pallet::weight(T::WeightInfo::set_rate_limit())]
+		#[transactional]
+		pub fn set_rate_limit(
+			origin: OriginFor<T>,
+			pallet_name: Vec<u8>,
+			function_name: Vec<u8>,
+			limit: Option<RateLimit<T::BlockNumber>>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if let Some(limit) = limit {
+				ensure!(!limit.window.is_zero(), Error::<T>::InvalidRateLimit);
+			}
+
+			let call_name = (pallet_name, function_name);
+			if limit.is_none() {
+				GlobalUsages::<T>::remove(&call_name);
+			}
+			RateLimits::<T>::mutate_exists(&call_name, |maybe_limit| *maybe_limit = limit);
+
+			let (pallet_name, function_name) = call_name;
+			Self::deposit_event(Event::RateLimitUpdated(pallet_name, function_name, limit));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Check the rate limit of `calls` calls of `call_name` of `who`, and count the calls if
+	/// `record` is true.
+	pub fn check_rate_limit(
+		who: &T::AccountId,
+		call_name: &CallName,
+		calls: u32,
+		record: bool,
+	) -> Result<(), TransactionValidityError> {
+		let limit = match Self::rate_limits(call_name) {
+			Some(limit) => limit,
+			None => return Ok(()),
+		};
+
+		let now = frame_system::Pallet::<T>::block_number();
+		let mut global_usage = Self::global_usages(call_name).roll(now, limit.window);
+		let mut account_usage = Self::account_usages(call_name, who).roll(now, limit.window);
+		if let Some(max) = limit.global {
+			ensure!(
+				global_usage.count(now, limit.window).saturating_add(calls) <= max,
+				InvalidTransaction::Custom(RATE_LIMIT_EXCEEDED)
+			);
+		}
+		if let Some(max) = limit.per_account {
+			ensure!(
+				account_usage.count(now, limit.window).saturating_add(calls) <= max,
+				InvalidTransaction::Custom(RATE_LIMIT_EXCEEDED)
+			);
+		}
+
+		if record {
+			global_usage.current = global_usage.current.saturating_add(calls);
+			account_usage.current = account_usage.current.saturating_add(calls);
+			GlobalUsages::<T>::insert(call_name, global_usage);
+			AccountUsages::<T>::insert(call_name, who, account_usage);
+		}
+		Ok(())
+	}
+
+	/// Check the rate limits of `call` and the calls nested in it of `who`, and count the calls if
+	/// `record` is true.
+	pub fn check_rate_limits(
+		who: &T::AccountId,
+		call: &<T as frame_system::Config>::Call,
+		record: bool,
+	) -> Result<(), TransactionValidityError>
+	where
+		<T as frame_system::Config>::Call: GetCallMetadata,
+	{
+		let mut call_counts: BTreeMap<CallName, u32> = BTreeMap::new();
+		let mut visited: u32 = 0;
+		count_calls::<T>(call, &mut call_counts, &mut visited);
+		ensure!(
+			visited <= T::MaxNestedCalls::get().saturating_add(1),
+			InvalidTransaction::ExhaustsResources
+		);
+
+		// check all the limits before counting any of the calls
+		for (call_name, calls) in call_counts.iter() {
+			Self::check_rate_limit(who, call_name, *calls, false)?;
+		}
+		if record {
+			for (call_name, calls) in call_counts.iter() {
+				Self::check_rate_limit(who, call_name, *calls, true)?;
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Count the calls by name in `call` and the calls nested in it, the walk stops once more than
+/// `MaxNestedCalls` nested calls are visited.
+fn count_calls<T: Config>(
+	call: &<T as frame_system::Config>::Call,
+	call_counts: &mut BTreeMap<CallName, u32>,
+	visited: &mut u32,
+) where
+	<T as frame_system::Config>::Call: GetCallMetadata,
+{
+	*visited = visited.saturating_add(1);
+	if *visited > T::MaxNestedCalls::get().saturating_add(1) {
+		return;
+	}
+
+	let count = call_counts.entry(call_name_of(call)).or_insert(0);
+	*count = count.saturating_add(1);
+	T::NestedCalls::visit_nested_calls(call, &mut |nested| count_calls::<T>(nested, call_counts, visited));
+}
+
+/// Reject the transactions exceeding the rate limits of their calls.
+#[derive(Encode, Decode, Clone, Eq, PartialEq)]
+pub struct CheckRateLimit<T: Config + Send + Sync>(PhantomData<T>);
+
+impl<T: Config + Send + Sync> sp_std::fmt::Debug for CheckRateLimit<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "CheckRateLimit")
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T: Config + Send + Sync> CheckRateLimit<T> {
+	pub fn new() -> Self {
+		Self(sp_std::marker::PhantomData)
+	}
+}
+
+impl<T: Config + Send + Sync> Default for CheckRateLimit<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T: Config + Send + Sync> SignedExtension for CheckRateLimit<T>
+where
+	<T as frame_system::Config>::Call: GetCallMetadata,
+{
+	const IDENTIFIER: &'static str = "CheckRateLimit";
+	type AccountId = T::AccountId;
+	type Call = <T as frame_system::Config>::Call;
+	type AdditionalSigned = ();
+	type Pre = ();
+
+	fn additional_signed(&self) -> sp_std::result::Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> TransactionValidity {
+		Pallet::<T>::check_rate_limits(who, call, false)?;
+		Ok(ValidTransaction::default())
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> Result<(), TransactionValidityError> {
+		Pallet::<T>::check_rate_limits(who, call, true)
+	}
+}
+
+fn call_name_of<Call: GetCallMetadata>(call: &Call) -> CallName {
+	let metadata = call.get_call_metadata();
+	(
+		metadata.pallet_name.as_bytes().to_vec(),
+		metadata.function_name.as_bytes().to_vec(),
+	)
+}
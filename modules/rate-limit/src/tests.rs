@@ -0,0 +1,240 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the rate limit module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok, weights::DispatchInfo};
+use mock::{Call, Event, *};
+use sp_runtime::traits::BadOrigin;
+
+fn remark_call() -> Call {
+	Call::System(frame_system::Call::remark(vec![]))
+}
+
+fn batch_call(calls: Vec<Call>) -> Call {
+	Call::Utility(pallet_utility::Call::batch(calls))
+}
+
+fn remark_call_name() -> CallName {
+	(b"System".to_vec(), b"remark".to_vec())
+}
+
+fn limit(window: BlockNumber, per_account: Option<u32>, global: Option<u32>) -> RateLimit<BlockNumber> {
+	RateLimit {
+		window,
+		per_account,
+		global,
+	}
+}
+
+fn set_remark_limit(limit: RateLimit<BlockNumber>) {
+	assert_ok!(RateLimitModule::set_rate_limit(
+		Origin::signed(ALICE),
+		b"System".to_vec(),
+		b"remark".to_vec(),
+		Some(limit)
+	));
+}
+
+fn validate(who: AccountId, call: &Call) -> TransactionValidity {
+	CheckRateLimit::<Runtime>::new().validate(&who, call, &DispatchInfo::default(), 0)
+}
+
+fn pre_dispatch(who: AccountId, call: &Call) -> Result<(), TransactionValidityError> {
+	CheckRateLimit::<Runtime>::new().pre_dispatch(&who, call, &DispatchInfo::default(), 0)
+}
+
+const EXCEEDED: TransactionValidityError =
+	TransactionValidityError::Invalid(InvalidTransaction::Custom(RATE_LIMIT_EXCEEDED));
+
+#[test]
+fn set_rate_limit_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			RateLimitModule::set_rate_limit(
+				Origin::signed(BOB),
+				b"System".to_vec(),
+				b"remark".to_vec(),
+				Some(limit(10, Some(1), None))
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			RateLimitModule::set_rate_limit(
+				Origin::signed(ALICE),
+				b"System".to_vec(),
+				b"remark".to_vec(),
+				Some(limit(0, Some(1), None))
+			),
+			Error::<Runtime>::InvalidRateLimit
+		);
+
+		set_remark_limit(limit(10, Some(1), None));
+		System::assert_last_event(Event::RateLimitModule(crate::Event::RateLimitUpdated(
+			b"System".to_vec(),
+			b"remark".to_vec(),
+			Some(limit(10, Some(1), None)),
+		)));
+		assert_eq!(
+			RateLimitModule::rate_limits(remark_call_name()),
+			Some(limit(10, Some(1), None))
+		);
+
+		assert_ok!(pre_dispatch(BOB, &remark_call()));
+		assert_eq!(RateLimitModule::global_usages(remark_call_name()).current, 1);
+
+		assert_ok!(RateLimitModule::set_rate_limit(
+			Origin::signed(ALICE),
+			b"System".to_vec(),
+			b"remark".to_vec(),
+			None
+		));
+		System::assert_last_event(Event::RateLimitModule(crate::Event::RateLimitUpdated(
+			b"System".to_vec(),
+			b"remark".to_vec(),
+			None,
+		)));
+		assert_eq!(RateLimitModule::rate_limits(remark_call_name()), None);
+		assert_eq!(RateLimitModule::global_usages(remark_call_name()), Default::default());
+		assert_ok!(pre_dispatch(BOB, &remark_call()));
+	});
+}
+
+#[test]
+fn per_account_rate_limit_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		set_remark_limit(limit(10, Some(2), None));
+
+		assert_ok!(pre_dispatch(BOB, &remark_call()));
+		assert_ok!(validate(BOB, &remark_call()));
+		assert_ok!(pre_dispatch(BOB, &remark_call()));
+		assert_eq!(validate(BOB, &remark_call()), Err(EXCEEDED));
+		assert_eq!(pre_dispatch(BOB, &remark_call()), Err(EXCEEDED));
+		assert_eq!(RateLimitModule::account_usages(remark_call_name(), BOB).current, 2);
+
+		// the other accounts and the calls without limit are not affected
+		assert_ok!(pre_dispatch(CHARLIE, &remark_call()));
+		assert_ok!(pre_dispatch(
+			BOB,
+			&Call::System(frame_system::Call::remark_with_event(vec![]))
+		));
+	});
+}
+
+#[test]
+fn global_rate_limit_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		set_remark_limit(limit(10, None, Some(3)));
+
+		assert_ok!(pre_dispatch(ALICE, &remark_call()));
+		assert_ok!(pre_dispatch(ALICE, &remark_call()));
+		assert_ok!(pre_dispatch(BOB, &remark_call()));
+		assert_eq!(validate(CHARLIE, &remark_call()), Err(EXCEEDED));
+		assert_eq!(pre_dispatch(CHARLIE, &remark_call()), Err(EXCEEDED));
+	});
+}
+
+#[test]
+fn sliding_window_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		set_remark_limit(limit(10, Some(2), None));
+
+		assert_ok!(pre_dispatch(BOB, &remark_call()));
+		assert_ok!(pre_dispatch(BOB, &remark_call()));
+
+		// the calls of the previous window are all in the sliding window
+		System::set_block_number(10);
+		assert_eq!(pre_dispatch(BOB, &remark_call()), Err(EXCEEDED));
+
+		// half of the previous window is in the sliding window
+		System::set_block_number(15);
+		assert_ok!(pre_dispatch(BOB, &remark_call()));
+		assert_eq!(pre_dispatch(BOB, &remark_call()), Err(EXCEEDED));
+		assert_eq!(
+			RateLimitModule::account_usages(remark_call_name(), BOB),
+			WindowUsage {
+				window_start: 10,
+				previous: 2,
+				current: 1,
+			}
+		);
+
+		System::set_block_number(19);
+		assert_ok!(pre_dispatch(BOB, &remark_call()));
+
+		// the windows without calls are skipped
+		System::set_block_number(35);
+		assert_ok!(pre_dispatch(BOB, &remark_call()));
+		assert_eq!(
+			RateLimitModule::account_usages(remark_call_name(), BOB),
+			WindowUsage {
+				window_start: 30,
+				previous: 0,
+				current: 1,
+			}
+		);
+	});
+}
+
+#[test]
+fn nested_calls_are_rate_limited() {
+	ExtBuilder::default().build().execute_with(|| {
+		set_remark_limit(limit(10, Some(2), None));
+
+		assert_eq!(
+			validate(BOB, &batch_call(vec![remark_call(), remark_call(), remark_call()])),
+			Err(EXCEEDED)
+		);
+		assert_eq!(
+			pre_dispatch(
+				BOB,
+				&Call::Utility(pallet_utility::Call::as_derivative(
+					0,
+					Box::new(batch_call(vec![remark_call(), remark_call(), remark_call()]))
+				))
+			),
+			Err(EXCEEDED)
+		);
+
+		assert_ok!(pre_dispatch(BOB, &batch_call(vec![remark_call(), remark_call()])));
+		assert_eq!(
+			RateLimitModule::account_usages(remark_call_name(), BOB),
+			WindowUsage {
+				window_start: 0,
+				previous: 0,
+				current: 2,
+			}
+		);
+		assert_eq!(pre_dispatch(BOB, &batch_call(vec![remark_call()])), Err(EXCEEDED));
+		assert_eq!(pre_dispatch(BOB, &remark_call()), Err(EXCEEDED));
+	});
+}
+
+#[test]
+fn too_many_nested_calls_are_rejected() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(validate(BOB, &batch_call(vec![remark_call(); 4])));
+		assert_eq!(
+			validate(BOB, &batch_call(vec![remark_call(); 5])),
+			Err(TransactionValidityError::Invalid(InvalidTransaction::ExhaustsResources))
+		);
+	});
+}
@@ -0,0 +1,139 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Relaychain Light Client Module
+//!
+//! ## Overview
+//!
+//! Follows the relaychain through the validation data of the parachain blocks, and keeps the
+//! storage roots of the recent relay parents. The relaychain state is read trustlessly by the
+//! storage proofs verified against the kept storage roots, e.g. the era and the bonded amounts of
+//! Homa, without relying on the oracles.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{pallet_prelude::*, BoundedVec};
+use primitives::BlockNumber as RelaychainBlockNumber;
+use sp_core::H256;
+use sp_runtime::traits::BlakeTwo256;
+use sp_state_machine::{Backend, TrieBackend};
+use sp_std::{convert::TryInto, prelude::*};
+use sp_trie::{HashDBT, StorageProof, EMPTY_PREFIX};
+use support::RelaychainStateVerifier;
+
+mod mock;
+mod tests;
+
+pub use module::*;
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The relaychain block number and storage root of the relay parent of current block, from
+		/// the validation data.
+		type RelaychainStateRootProvider: Get<Option<(RelaychainBlockNumber, H256)>>;
+
+		/// The number of recent relaychain storage roots kept to verify the proofs.
+		#[pallet::constant]
+		type MaxRelaychainStateRoots: Get<u32>;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The relaychain storage root of the block is not kept
+		RelaychainStateRootNotFound,
+		/// The relaychain storage proof is invalid
+		InvalidProof,
+	}
+
+	/// The recent relaychain storage roots, by relaychain block number.
+	///
+	/// RelaychainStateRoots: Vec<(RelaychainBlockNumber, H256)>
+	#[pallet::storage]
+	#[pallet::getter(fn relaychain_state_roots)]
+	pub type RelaychainStateRoots<T: Config> =
+		StorageValue<_, BoundedVec<(RelaychainBlockNumber, H256), T::MaxRelaychainStateRoots>, ValueQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_initialize(_now: T::BlockNumber) -> Weight {
+			// `on_finalize`
+			T::DbWeight::get().reads_writes(1, 1)
+		}
+
+		fn on_finalize(_now: T::BlockNumber) {
+			// the validation data is set by the inherent of the block.
+			if let Some(state_root) = T::RelaychainStateRootProvider::get() {
+				RelaychainStateRoots::<T>::mutate(|roots| {
+					if roots.last().map_or(true, |(number, _)| *number < state_root.0) {
+						let mut recent = roots.clone().into_inner();
+						recent.push(state_root);
+						let expired = recent.len().saturating_sub(T::MaxRelaychainStateRoots::get() as usize);
+						recent.drain(..expired);
+						// the length is bounded by `MaxRelaychainStateRoots` after drain.
+						*roots = recent.try_into().unwrap_or_default();
+					}
+				});
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The kept relaychain storage root at `relaychain_block_number`.
+	pub fn state_root(relaychain_block_number: RelaychainBlockNumber) -> Option<H256> {
+		Self::relaychain_state_roots()
+			.into_iter()
+			.find(|(number, _)| *number == relaychain_block_number)
+			.map(|(_, root)| root)
+	}
+
+	/// Read the value of `key` from the storage proof of the relaychain state with `state_root`.
+	pub fn read_proof(state_root: H256, key: &[u8], proof: Vec<Vec<u8>>) -> Result<Option<Vec<u8>>, DispatchError> {
+		let db = StorageProof::new(proof).into_memory_db::<BlakeTwo256>();
+		ensure!(db.contains(&state_root, EMPTY_PREFIX), Error::<T>::InvalidProof);
+		let backend = TrieBackend::new(db, state_root);
+
+		backend.storage(key).map_err(|_| Error::<T>::InvalidProof.into())
+	}
+}
+
+impl<T: Config> RelaychainStateVerifier<RelaychainBlockNumber> for Pallet<T> {
+	fn latest_relaychain_block_number() -> Option<RelaychainBlockNumber> {
+		Self::relaychain_state_roots().last().map(|(number, _)| *number)
+	}
+
+	fn verify_relay_state(
+		relaychain_block_number: RelaychainBlockNumber,
+		key: &[u8],
+		proof: Vec<Vec<u8>>,
+	) -> Result<Option<Vec<u8>>, DispatchError> {
+		let state_root = Self::state_root(relaychain_block_number).ok_or(Error::<T>::RelaychainStateRootNotFound)?;
+		Self::read_proof(state_root, key, proof)
+	}
+}
@@ -0,0 +1,114 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Mocks for the relaychain light client module.
+
+#![cfg(test)]
+
+use super::*;
+
+use crate as relaychain_light_client;
+use frame_support::{construct_runtime, parameter_types};
+use sp_runtime::{testing::Header, traits::IdentityLookup};
+use sp_std::cell::RefCell;
+
+pub type AccountId = u128;
+pub type BlockNumber = u64;
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Runtime {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+thread_local! {
+	static STATE_ROOT: RefCell<Option<(RelaychainBlockNumber, H256)>> = RefCell::new(None);
+}
+
+pub fn set_state_root(state_root: Option<(RelaychainBlockNumber, H256)>) {
+	STATE_ROOT.with(|v| *v.borrow_mut() = state_root);
+}
+
+pub struct MockStateRootProvider;
+impl Get<Option<(RelaychainBlockNumber, H256)>> for MockStateRootProvider {
+	fn get() -> Option<(RelaychainBlockNumber, H256)> {
+		STATE_ROOT.with(|v| *v.borrow())
+	}
+}
+
+parameter_types! {
+	pub const MaxRelaychainStateRoots: u32 = 2;
+}
+
+impl Config for Runtime {
+	type RelaychainStateRootProvider = MockStateRootProvider;
+	type MaxRelaychainStateRoots = MaxRelaychainStateRoots;
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		RelaychainLightClientModule: relaychain_light_client::{Pallet, Storage},
+	}
+);
+
+#[derive(Default)]
+pub struct ExtBuilder;
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		set_state_root(None);
+
+		let t = frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap();
+
+		sp_io::TestExternalities::new(t)
+	}
+}
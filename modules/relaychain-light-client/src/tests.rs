@@ -0,0 +1,92 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the relaychain light client module.
+
+#![cfg(test)]
+
+use super::*;
+use mock::*;
+use sp_state_machine::{prove_read, InMemoryBackend};
+
+fn relaychain_state(value: &[u8]) -> (H256, Vec<Vec<u8>>) {
+	let backend = InMemoryBackend::<BlakeTwo256>::from(vec![(None, vec![(b"key".to_vec(), Some(value.to_vec()))])]);
+	let state_root = *backend.root();
+	let proof = prove_read(backend, &[&b"key"[..], &b"missing"[..]]).unwrap();
+	(state_root, proof.iter_nodes().collect())
+}
+
+#[test]
+fn follows_relaychain_state_roots() {
+	ExtBuilder::default().build().execute_with(|| {
+		RelaychainLightClientModule::on_finalize(1);
+		assert_eq!(
+			RelaychainLightClientModule::relaychain_state_roots().into_inner(),
+			vec![]
+		);
+		assert_eq!(RelaychainLightClientModule::latest_relaychain_block_number(), None);
+
+		set_state_root(Some((10, H256::repeat_byte(10))));
+		RelaychainLightClientModule::on_finalize(2);
+		// the relay parent is not advanced
+		RelaychainLightClientModule::on_finalize(3);
+		assert_eq!(
+			RelaychainLightClientModule::relaychain_state_roots().into_inner(),
+			vec![(10, H256::repeat_byte(10))]
+		);
+
+		set_state_root(Some((11, H256::repeat_byte(11))));
+		RelaychainLightClientModule::on_finalize(4);
+		set_state_root(Some((12, H256::repeat_byte(12))));
+		RelaychainLightClientModule::on_finalize(5);
+		assert_eq!(
+			RelaychainLightClientModule::relaychain_state_roots().into_inner(),
+			vec![(11, H256::repeat_byte(11)), (12, H256::repeat_byte(12))]
+		);
+		assert_eq!(RelaychainLightClientModule::latest_relaychain_block_number(), Some(12));
+		assert_eq!(RelaychainLightClientModule::state_root(10), None);
+		assert_eq!(RelaychainLightClientModule::state_root(11), Some(H256::repeat_byte(11)));
+	});
+}
+
+#[test]
+fn verify_relay_state_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (state_root, proof) = relaychain_state(b"value");
+		let (_, other_proof) = relaychain_state(b"other value");
+		set_state_root(Some((10, state_root)));
+		RelaychainLightClientModule::on_finalize(1);
+
+		assert_eq!(
+			RelaychainLightClientModule::verify_relay_state(10, b"key", proof.clone()),
+			Ok(Some(b"value".to_vec()))
+		);
+		assert_eq!(
+			RelaychainLightClientModule::verify_relay_state(10, b"missing", proof.clone()),
+			Ok(None)
+		);
+		assert_eq!(
+			RelaychainLightClientModule::verify_relay_state(11, b"key", proof),
+			Err(Error::<Runtime>::RelaychainStateRootNotFound.into())
+		);
+		assert_eq!(
+			RelaychainLightClientModule::verify_relay_state(10, b"key", other_proof),
+			Err(Error::<Runtime>::InvalidProof.into())
+		);
+	});
+}
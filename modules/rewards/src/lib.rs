@@ -0,0 +1,380 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Rewards Module
+//!
+//! ## Overview
+//!
+//! Distributes the rewards accumulated to a pool to the holders of the pool
+//! shares pro rata. Every pool keeps the rewards per share scaled by 2^128 and
+//! the scaled rewards which could not be divided by the total shares yet, so
+//! the rewards are distributed without the rounding of the per share rewards.
+//! The share of each account records the reward debt: the rewards the account
+//! is not entitled to, which is rounded up in favor of the pool whenever the
+//! share changes, so the pool always holds the rewards to be claimed.
+//!
+//! The storage of `PoolInfo` and `ShareAndWithdrawnReward` is compatible with
+//! `orml-rewards`, the rewards per share of the existing pools are seeded by
+//! the migration to `V2_0_0`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use codec::{Decode, Encode, FullCodec, HasCompact};
+use frame_support::pallet_prelude::*;
+use orml_traits::RewardHandler;
+use sp_core::{U256, U512};
+use sp_runtime::{
+	traits::{AtLeast32BitUnsigned, MaybeSerializeDeserialize, Member, Saturating, UniqueSaturatedInto, Zero},
+	FixedPointOperand, RuntimeDebug,
+};
+use sp_std::fmt::Debug;
+
+mod mock;
+mod tests;
+
+pub use module::*;
+
+/// The number of fractional bits of the rewards per share.
+pub const REWARD_PER_SHARE_FRACTIONAL_BITS: usize = 128;
+
+/// The info of a rewards pool.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, Default)]
+pub struct PoolInfo<Share: HasCompact, Balance: HasCompact> {
+	/// Total shares amount
+	#[codec(compact)]
+	pub total_shares: Share,
+	/// Total rewards amount, including the reward debts of the shares
+	#[codec(compact)]
+	pub total_rewards: Balance,
+	/// Total withdrawn rewards amount, including the reward debts of the
+	/// shares
+	#[codec(compact)]
+	pub total_withdrawn_rewards: Balance,
+}
+
+/// The rewards per share of a pool.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, Default)]
+pub struct RewardAccumulator {
+	/// The accumulated rewards per share, scaled by 2^128.
+	pub reward_per_share: U256,
+	/// The scaled rewards not distributed to the shares yet, less than the
+	/// total shares unless the pool has no shares.
+	pub remainder: U256,
+}
+
+/// The storage versions of the module.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum Releases {
+	V1_0_0,
+	/// `RewardAccumulators` seeded for the existing pools.
+	V2_0_0,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V1_0_0
+	}
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The share type of pool.
+		type Share: Parameter
+			+ Member
+			+ AtLeast32BitUnsigned
+			+ Default
+			+ Copy
+			+ MaybeSerializeDeserialize
+			+ Debug
+			+ FixedPointOperand;
+
+		/// The reward balance type.
+		type Balance: Parameter
+			+ Member
+			+ AtLeast32BitUnsigned
+			+ Default
+			+ Copy
+			+ MaybeSerializeDeserialize
+			+ Debug
+			+ FixedPointOperand;
+
+		/// The reward pool ID type.
+		type PoolId: Parameter + Member + Clone + FullCodec;
+
+		/// The `RewardHandler` to pay out the claimed rewards.
+		type Handler: RewardHandler<Self::AccountId, Balance = Self::Balance, PoolId = Self::PoolId>;
+	}
+
+	/// Stores reward pool info.
+	///
+	/// Pools: map PoolId => PoolInfo
+	#[pallet::storage]
+	#[pallet::getter(fn pools)]
+	pub type Pools<T: Config> = StorageMap<_, Twox64Concat, T::PoolId, PoolInfo<T::Share, T::Balance>, ValueQuery>;
+
+	/// The rewards per share of the pools.
+	///
+	/// RewardAccumulators: map PoolId => RewardAccumulator
+	#[pallet::storage]
+	#[pallet::getter(fn reward_accumulators)]
+	pub type RewardAccumulators<T: Config> = StorageMap<_, Twox64Concat, T::PoolId, RewardAccumulator, ValueQuery>;
+
+	/// Record share amount and the reward debt of the share.
+	///
+	/// ShareAndWithdrawnReward: double_map PoolId, AccountId => (Share,
+	/// Balance)
+	#[pallet::storage]
+	#[pallet::getter(fn share_and_withdrawn_reward)]
+	pub type ShareAndWithdrawnReward<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::PoolId, Twox64Concat, T::AccountId, (T::Share, T::Balance), ValueQuery>;
+
+	/// StorageVersion: Releases
+	#[pallet::storage]
+	#[pallet::getter(fn storage_version)]
+	pub(crate) type StorageVersion<T: Config> = StorageValue<_, Releases, ValueQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Self::storage_version() == Releases::V1_0_0 {
+				Self::migrate_to_v2()
+			} else {
+				0
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Accumulate `reward_increment` to the pool, the rewards of a pool
+	/// without shares are distributed once the pool has shares.
+	pub fn accumulate_reward(pool: &T::PoolId, reward_increment: T::Balance) {
+		if reward_increment.is_zero() {
+			return;
+		}
+
+		let total_shares = to_u256(Self::pools(pool).total_shares);
+		Pools::<T>::mutate(pool, |pool_info| {
+			pool_info.total_rewards = pool_info.total_rewards.saturating_add(reward_increment);
+		});
+		RewardAccumulators::<T>::mutate(pool, |accumulator| {
+			let scaled_reward = to_u256(reward_increment) << REWARD_PER_SHARE_FRACTIONAL_BITS;
+			accumulator.remainder = accumulator.remainder.saturating_add(scaled_reward);
+			Self::distribute_remainder(accumulator, total_shares);
+		});
+	}
+
+	/// Add `add_amount` to the share of `who`, the reward debt of the added
+	/// share is rounded up.
+	pub fn add_share(who: &T::AccountId, pool: &T::PoolId, add_amount: T::Share) {
+		if add_amount.is_zero() {
+			return;
+		}
+
+		let debt_increment = Self::reward_of(add_amount, Self::reward_per_share(pool), true);
+
+		let (initial_total_shares, total_shares) = Pools::<T>::mutate(pool, |pool_info| {
+			let initial_total_shares = pool_info.total_shares;
+			pool_info.total_shares = pool_info.total_shares.saturating_add(add_amount);
+			pool_info.total_rewards = pool_info.total_rewards.saturating_add(debt_increment);
+			pool_info.total_withdrawn_rewards = pool_info.total_withdrawn_rewards.saturating_add(debt_increment);
+			(initial_total_shares, pool_info.total_shares)
+		});
+		ShareAndWithdrawnReward::<T>::mutate(pool, who, |(share, withdrawn_rewards)| {
+			*share = share.saturating_add(add_amount);
+			*withdrawn_rewards = withdrawn_rewards.saturating_add(debt_increment);
+		});
+
+		// distribute the rewards accumulated while the pool had no shares
+		if initial_total_shares.is_zero() {
+			RewardAccumulators::<T>::mutate(pool, |accumulator| {
+				Self::distribute_remainder(accumulator, to_u256(total_shares));
+			});
+		}
+	}
+
+	/// Claim the rewards of `who` and remove `remove_amount` from the share,
+	/// the reward debt of the remaining share is rounded up.
+	pub fn remove_share(who: &T::AccountId, pool: &T::PoolId, remove_amount: T::Share) {
+		if remove_amount.is_zero() {
+			return;
+		}
+
+		// claim rewards firstly
+		Self::claim_rewards(who, pool);
+
+		ShareAndWithdrawnReward::<T>::mutate_exists(pool, who, |share_info| {
+			if let Some((share, withdrawn_rewards)) = share_info.take() {
+				let remove_amount = remove_amount.min(share);
+				let share = share.saturating_sub(remove_amount);
+
+				let total_shares = Pools::<T>::mutate(pool, |pool_info| {
+					pool_info.total_shares = pool_info.total_shares.saturating_sub(remove_amount);
+					pool_info.total_shares
+				});
+				// keep the remainder less than the remaining shares
+				RewardAccumulators::<T>::mutate(pool, |accumulator| {
+					Self::distribute_remainder(accumulator, to_u256(total_shares));
+				});
+
+				let new_withdrawn_rewards = Self::reward_of(share, Self::reward_per_share(pool), true);
+				Pools::<T>::mutate(pool, |pool_info| {
+					pool_info.total_rewards = pool_info
+						.total_rewards
+						.saturating_add(new_withdrawn_rewards)
+						.saturating_sub(withdrawn_rewards);
+					pool_info.total_withdrawn_rewards = pool_info
+						.total_withdrawn_rewards
+						.saturating_add(new_withdrawn_rewards)
+						.saturating_sub(withdrawn_rewards);
+				});
+
+				if !share.is_zero() {
+					*share_info = Some((share, new_withdrawn_rewards));
+				}
+			}
+		});
+	}
+
+	/// Set the share of `who` to `new_share`.
+	pub fn set_share(who: &T::AccountId, pool: &T::PoolId, new_share: T::Share) {
+		let (share, _) = Self::share_and_withdrawn_reward(pool, who);
+
+		if new_share > share {
+			Self::add_share(who, pool, new_share.saturating_sub(share));
+		} else {
+			Self::remove_share(who, pool, share.saturating_sub(new_share));
+		}
+	}
+
+	/// Pay out the pending rewards of `who` by `T::Handler`.
+	pub fn claim_rewards(who: &T::AccountId, pool: &T::PoolId) {
+		let reward_per_share = Self::reward_per_share(pool);
+		ShareAndWithdrawnReward::<T>::mutate_exists(pool, who, |share_info| {
+			if let Some((share, withdrawn_rewards)) = share_info {
+				let reward_to_withdraw =
+					Self::reward_of(*share, reward_per_share, false).saturating_sub(*withdrawn_rewards);
+				if reward_to_withdraw.is_zero() {
+					return;
+				}
+
+				Pools::<T>::mutate(pool, |pool_info| {
+					pool_info.total_withdrawn_rewards =
+						pool_info.total_withdrawn_rewards.saturating_add(reward_to_withdraw);
+				});
+				*withdrawn_rewards = withdrawn_rewards.saturating_add(reward_to_withdraw);
+
+				T::Handler::payout(who, pool, reward_to_withdraw);
+			}
+		});
+	}
+
+	/// The rewards of `who` to claim in the pool.
+	pub fn pending_reward(pool: &T::PoolId, who: &T::AccountId) -> T::Balance {
+		let (share, withdrawn_rewards) = Self::share_and_withdrawn_reward(pool, who);
+		Self::reward_of(share, Self::reward_per_share(pool), false).saturating_sub(withdrawn_rewards)
+	}
+
+	/// The scaled rewards per share of the pool, rounded up by the remainder
+	/// which is less than one scaled unit per share.
+	fn reward_per_share(pool: &T::PoolId) -> U256 {
+		let accumulator = Self::reward_accumulators(pool);
+		if accumulator.remainder.is_zero() || Self::pools(pool).total_shares.is_zero() {
+			accumulator.reward_per_share
+		} else {
+			accumulator.reward_per_share.saturating_add(U256::one())
+		}
+	}
+
+	/// The rewards of `share` by the scaled `reward_per_share`.
+	fn reward_of(share: T::Share, reward_per_share: U256, round_up: bool) -> T::Balance {
+		let scaled_reward: U512 = to_u256(share).full_mul(reward_per_share);
+		let mut reward = scaled_reward >> REWARD_PER_SHARE_FRACTIONAL_BITS;
+		if round_up && scaled_reward.low_u128() != 0 {
+			reward = reward.saturating_add(U512::one());
+		}
+
+		if reward > U512::from(u128::max_value()) {
+			u128::max_value().unique_saturated_into()
+		} else {
+			reward.low_u128().unique_saturated_into()
+		}
+	}
+
+	/// Distribute the scaled remainder to `total_shares`, the part which can't
+	/// be divided is kept in the remainder.
+	fn distribute_remainder(accumulator: &mut RewardAccumulator, total_shares: U256) {
+		if total_shares.is_zero() {
+			return;
+		}
+
+		accumulator.reward_per_share = accumulator
+			.reward_per_share
+			.saturating_add(accumulator.remainder / total_shares);
+		accumulator.remainder %= total_shares;
+	}
+
+	/// Seed the rewards per share of the pools by the `orml-rewards` pool info,
+	/// so that the pending rewards of `share * total_rewards / total_shares -
+	/// withdrawn_rewards` are kept. The rewards remained in a pool without
+	/// shares are distributed once the pool has shares.
+	fn migrate_to_v2() -> Weight {
+		let mut count: u32 = 0;
+
+		for (pool, pool_info) in Pools::<T>::iter() {
+			count += 1;
+			let total_shares = to_u256(pool_info.total_shares);
+			let accumulator = if total_shares.is_zero() {
+				RewardAccumulator {
+					reward_per_share: U256::zero(),
+					remainder: to_u256(
+						pool_info
+							.total_rewards
+							.saturating_sub(pool_info.total_withdrawn_rewards),
+					) << REWARD_PER_SHARE_FRACTIONAL_BITS,
+				}
+			} else {
+				let scaled_rewards = to_u256(pool_info.total_rewards) << REWARD_PER_SHARE_FRACTIONAL_BITS;
+				RewardAccumulator {
+					reward_per_share: scaled_rewards / total_shares,
+					remainder: scaled_rewards % total_shares,
+				}
+			};
+			RewardAccumulators::<T>::insert(pool, accumulator);
+		}
+
+		StorageVersion::<T>::put(Releases::V2_0_0);
+
+		T::DbWeight::get().reads_writes((count as Weight).saturating_add(1), (count as Weight).saturating_add(1))
+	}
+}
+
+fn to_u256<B: UniqueSaturatedInto<u128>>(amount: B) -> U256 {
+	U256::from(UniqueSaturatedInto::<u128>::unique_saturated_into(amount))
+}
@@ -0,0 +1,131 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Mocks for rewards module.
+
+#![cfg(test)]
+
+use super::*;
+
+use crate as rewards;
+use frame_support::{construct_runtime, parameter_types};
+use sp_core::H256;
+use sp_runtime::{testing::Header, traits::IdentityLookup};
+use sp_std::{cell::RefCell, collections::btree_map::BTreeMap};
+
+pub type AccountId = u128;
+pub type Balance = u128;
+pub type PoolId = u32;
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const CAROL: AccountId = 3;
+pub const DEX_POOL: PoolId = 1;
+pub const LOANS_POOL: PoolId = 2;
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Runtime {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+thread_local! {
+	pub static RECEIVED_PAYOUT: RefCell<BTreeMap<(PoolId, AccountId), Balance>> = RefCell::new(BTreeMap::new());
+}
+
+/// The rewards paid out to `who` in `pool`.
+pub fn received_payout(pool: PoolId, who: AccountId) -> Balance {
+	RECEIVED_PAYOUT.with(|v| v.borrow().get(&(pool, who)).copied().unwrap_or_default())
+}
+
+pub struct Handler;
+impl RewardHandler<AccountId> for Handler {
+	type Balance = Balance;
+	type PoolId = PoolId;
+
+	fn payout(who: &AccountId, pool: &Self::PoolId, amount: Self::Balance) {
+		RECEIVED_PAYOUT.with(|v| {
+			*v.borrow_mut().entry((*pool, *who)).or_default() += amount;
+		});
+	}
+}
+
+impl Config for Runtime {
+	type Share = Balance;
+	type Balance = Balance;
+	type PoolId = PoolId;
+	type Handler = Handler;
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Storage, Config, Event<T>},
+		RewardsModule: rewards::{Pallet, Storage, Call},
+	}
+);
+
+pub struct ExtBuilder;
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		ExtBuilder
+	}
+}
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		RECEIVED_PAYOUT.with(|v| v.borrow_mut().clear());
+
+		let t = frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap();
+
+		t.into()
+	}
+}
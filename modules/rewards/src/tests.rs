@@ -0,0 +1,349 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for rewards module.
+
+#![cfg(test)]
+
+use super::*;
+use mock::*;
+
+fn scaled(amount: Balance) -> U256 {
+	U256::from(amount) << REWARD_PER_SHARE_FRACTIONAL_BITS
+}
+
+#[test]
+fn accumulate_reward_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		RewardsModule::accumulate_reward(&DEX_POOL, 0);
+		assert_eq!(RewardsModule::pools(DEX_POOL), Default::default());
+
+		// no shares, the rewards are distributed once the pool has shares
+		RewardsModule::accumulate_reward(&DEX_POOL, 100);
+		assert_eq!(
+			RewardsModule::pools(DEX_POOL),
+			PoolInfo {
+				total_shares: 0,
+				total_rewards: 100,
+				total_withdrawn_rewards: 0,
+			}
+		);
+		assert_eq!(
+			RewardsModule::reward_accumulators(DEX_POOL),
+			RewardAccumulator {
+				reward_per_share: U256::zero(),
+				remainder: scaled(100),
+			}
+		);
+
+		RewardsModule::add_share(&ALICE, &DEX_POOL, 10);
+		assert_eq!(
+			RewardsModule::reward_accumulators(DEX_POOL),
+			RewardAccumulator {
+				reward_per_share: scaled(10),
+				remainder: U256::zero(),
+			}
+		);
+		assert_eq!(RewardsModule::pending_reward(&DEX_POOL, &ALICE), 100);
+
+		RewardsModule::accumulate_reward(&DEX_POOL, 50);
+		assert_eq!(
+			RewardsModule::pools(DEX_POOL),
+			PoolInfo {
+				total_shares: 10,
+				total_rewards: 150,
+				total_withdrawn_rewards: 0,
+			}
+		);
+		assert_eq!(
+			RewardsModule::reward_accumulators(DEX_POOL).reward_per_share,
+			scaled(15)
+		);
+		assert_eq!(RewardsModule::pending_reward(&DEX_POOL, &ALICE), 150);
+	});
+}
+
+#[test]
+fn add_share_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		RewardsModule::add_share(&ALICE, &DEX_POOL, 0);
+		assert_eq!(RewardsModule::share_and_withdrawn_reward(DEX_POOL, ALICE), (0, 0));
+
+		RewardsModule::add_share(&ALICE, &DEX_POOL, 100);
+		assert_eq!(
+			RewardsModule::pools(DEX_POOL),
+			PoolInfo {
+				total_shares: 100,
+				total_rewards: 0,
+				total_withdrawn_rewards: 0,
+			}
+		);
+		assert_eq!(RewardsModule::share_and_withdrawn_reward(DEX_POOL, ALICE), (100, 0));
+
+		RewardsModule::accumulate_reward(&DEX_POOL, 500);
+		RewardsModule::add_share(&BOB, &DEX_POOL, 100);
+		assert_eq!(
+			RewardsModule::pools(DEX_POOL),
+			PoolInfo {
+				total_shares: 200,
+				total_rewards: 1000,
+				total_withdrawn_rewards: 500,
+			}
+		);
+		assert_eq!(RewardsModule::share_and_withdrawn_reward(DEX_POOL, BOB), (100, 500));
+		assert_eq!(RewardsModule::pending_reward(&DEX_POOL, &ALICE), 500);
+		assert_eq!(RewardsModule::pending_reward(&DEX_POOL, &BOB), 0);
+	});
+}
+
+#[test]
+fn remove_share_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		RewardsModule::add_share(&ALICE, &DEX_POOL, 100);
+		RewardsModule::accumulate_reward(&DEX_POOL, 500);
+		RewardsModule::add_share(&BOB, &DEX_POOL, 100);
+		RewardsModule::accumulate_reward(&DEX_POOL, 300);
+		assert_eq!(RewardsModule::pending_reward(&DEX_POOL, &ALICE), 650);
+		assert_eq!(RewardsModule::pending_reward(&DEX_POOL, &BOB), 150);
+
+		// the rewards are claimed before removing the share
+		RewardsModule::remove_share(&ALICE, &DEX_POOL, 50);
+		assert_eq!(received_payout(DEX_POOL, ALICE), 650);
+		assert_eq!(
+			RewardsModule::pools(DEX_POOL),
+			PoolInfo {
+				total_shares: 150,
+				total_rewards: 975,
+				total_withdrawn_rewards: 825,
+			}
+		);
+		assert_eq!(RewardsModule::share_and_withdrawn_reward(DEX_POOL, ALICE), (50, 325));
+		assert_eq!(RewardsModule::pending_reward(&DEX_POOL, &ALICE), 0);
+		assert_eq!(RewardsModule::pending_reward(&DEX_POOL, &BOB), 150);
+
+		// remove at most the share
+		RewardsModule::remove_share(&ALICE, &DEX_POOL, 100);
+		assert_eq!(received_payout(DEX_POOL, ALICE), 650);
+		assert_eq!(
+			RewardsModule::pools(DEX_POOL),
+			PoolInfo {
+				total_shares: 100,
+				total_rewards: 650,
+				total_withdrawn_rewards: 500,
+			}
+		);
+		assert!(!ShareAndWithdrawnReward::<Runtime>::contains_key(DEX_POOL, ALICE));
+		assert_eq!(RewardsModule::pending_reward(&DEX_POOL, &BOB), 150);
+	});
+}
+
+#[test]
+fn claim_rewards_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		RewardsModule::claim_rewards(&ALICE, &DEX_POOL);
+		assert_eq!(received_payout(DEX_POOL, ALICE), 0);
+
+		RewardsModule::add_share(&ALICE, &DEX_POOL, 100);
+		RewardsModule::add_share(&BOB, &DEX_POOL, 300);
+		RewardsModule::accumulate_reward(&DEX_POOL, 1000);
+
+		RewardsModule::claim_rewards(&ALICE, &DEX_POOL);
+		assert_eq!(received_payout(DEX_POOL, ALICE), 250);
+		assert_eq!(RewardsModule::share_and_withdrawn_reward(DEX_POOL, ALICE), (100, 250));
+		assert_eq!(RewardsModule::pools(DEX_POOL).total_withdrawn_rewards, 250);
+
+		// claimed once
+		RewardsModule::claim_rewards(&ALICE, &DEX_POOL);
+		assert_eq!(received_payout(DEX_POOL, ALICE), 250);
+
+		RewardsModule::claim_rewards(&BOB, &DEX_POOL);
+		assert_eq!(received_payout(DEX_POOL, BOB), 750);
+		assert_eq!(received_payout(LOANS_POOL, BOB), 0);
+		assert_eq!(RewardsModule::pools(DEX_POOL).total_withdrawn_rewards, 1000);
+	});
+}
+
+#[test]
+fn set_share_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		RewardsModule::set_share(&ALICE, &DEX_POOL, 100);
+		assert_eq!(RewardsModule::share_and_withdrawn_reward(DEX_POOL, ALICE), (100, 0));
+
+		RewardsModule::accumulate_reward(&DEX_POOL, 100);
+		RewardsModule::set_share(&ALICE, &DEX_POOL, 40);
+		assert_eq!(received_payout(DEX_POOL, ALICE), 100);
+		assert_eq!(RewardsModule::share_and_withdrawn_reward(DEX_POOL, ALICE), (40, 40));
+		assert_eq!(RewardsModule::pools(DEX_POOL).total_shares, 40);
+	});
+}
+
+#[test]
+fn remainder_is_carried_to_the_next_accumulation() {
+	ExtBuilder::default().build().execute_with(|| {
+		RewardsModule::add_share(&ALICE, &DEX_POOL, 1);
+		RewardsModule::add_share(&BOB, &DEX_POOL, 2);
+		RewardsModule::add_share(&CAROL, &DEX_POOL, 4);
+
+		for _ in 0..10 {
+			RewardsModule::accumulate_reward(&DEX_POOL, 1);
+		}
+		// the rewards of 10 / 7 per share are rounded down only for each account
+		assert_eq!(RewardsModule::pending_reward(&DEX_POOL, &ALICE), 1);
+		assert_eq!(RewardsModule::pending_reward(&DEX_POOL, &BOB), 2);
+		assert_eq!(RewardsModule::pending_reward(&DEX_POOL, &CAROL), 5);
+		RewardsModule::claim_rewards(&ALICE, &DEX_POOL);
+		RewardsModule::claim_rewards(&BOB, &DEX_POOL);
+		RewardsModule::claim_rewards(&CAROL, &DEX_POOL);
+
+		// no dust is lost once the accumulated rewards can be divided exactly
+		for _ in 0..4 {
+			RewardsModule::accumulate_reward(&DEX_POOL, 1);
+		}
+		assert_eq!(
+			RewardsModule::reward_accumulators(DEX_POOL),
+			RewardAccumulator {
+				reward_per_share: scaled(2),
+				remainder: U256::zero(),
+			}
+		);
+		RewardsModule::claim_rewards(&ALICE, &DEX_POOL);
+		RewardsModule::claim_rewards(&BOB, &DEX_POOL);
+		RewardsModule::claim_rewards(&CAROL, &DEX_POOL);
+		assert_eq!(received_payout(DEX_POOL, ALICE), 2);
+		assert_eq!(received_payout(DEX_POOL, BOB), 4);
+		assert_eq!(received_payout(DEX_POOL, CAROL), 8);
+		assert_eq!(
+			RewardsModule::pools(DEX_POOL).total_rewards,
+			RewardsModule::pools(DEX_POOL).total_withdrawn_rewards
+		);
+	});
+}
+
+#[test]
+fn small_staker_is_not_shortchanged_by_share_changes() {
+	ExtBuilder::default().build().execute_with(|| {
+		RewardsModule::add_share(&ALICE, &DEX_POOL, 3);
+		RewardsModule::accumulate_reward(&DEX_POOL, 10);
+		assert_eq!(RewardsModule::pending_reward(&DEX_POOL, &ALICE), 10);
+
+		// the reward debt of the new share is rounded up, and doesn't dilute the
+		// rewards of the existing shares
+		RewardsModule::add_share(&BOB, &DEX_POOL, 1);
+		assert_eq!(RewardsModule::share_and_withdrawn_reward(DEX_POOL, BOB), (1, 4));
+		assert_eq!(RewardsModule::pending_reward(&DEX_POOL, &ALICE), 10);
+		assert_eq!(RewardsModule::pending_reward(&DEX_POOL, &BOB), 0);
+
+		RewardsModule::accumulate_reward(&DEX_POOL, 40);
+		assert_eq!(RewardsModule::pending_reward(&DEX_POOL, &ALICE), 40);
+		assert_eq!(RewardsModule::pending_reward(&DEX_POOL, &BOB), 9);
+
+		RewardsModule::claim_rewards(&ALICE, &DEX_POOL);
+		RewardsModule::claim_rewards(&BOB, &DEX_POOL);
+		// the dust is less than one for each account
+		assert_eq!(received_payout(DEX_POOL, ALICE) + received_payout(DEX_POOL, BOB), 49);
+	});
+}
+
+#[test]
+fn claims_never_exceed_the_accumulated_rewards() {
+	ExtBuilder::default().build().execute_with(|| {
+		let accounts: Vec<AccountId> = (1..=20).collect();
+		let mut accumulated: Balance = 0;
+
+		for round in 1..=50u128 {
+			let who = accounts[(round % 20) as usize];
+			if round % 3 == 0 {
+				RewardsModule::remove_share(&who, &DEX_POOL, round * 7 % 11);
+			} else {
+				RewardsModule::add_share(&who, &DEX_POOL, round * 13 % 17 + 1);
+			}
+
+			let reward = round * 1_000_003 % 997;
+			RewardsModule::accumulate_reward(&DEX_POOL, reward);
+			accumulated += reward;
+
+			let paid: Balance = accounts.iter().map(|who| received_payout(DEX_POOL, *who)).sum();
+			let pending: Balance = accounts
+				.iter()
+				.map(|who| RewardsModule::pending_reward(&DEX_POOL, who))
+				.sum();
+			assert!(paid + pending <= accumulated);
+		}
+
+		for who in accounts.iter() {
+			RewardsModule::claim_rewards(who, &DEX_POOL);
+		}
+		let paid: Balance = accounts.iter().map(|who| received_payout(DEX_POOL, *who)).sum();
+		// the dust is bounded by the share changes, instead of accumulating with
+		// the rewards
+		assert!(accumulated - paid <= 50 + accounts.len() as Balance);
+	});
+}
+
+#[test]
+fn migrate_to_v2_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		// the state of `orml-rewards`
+		Pools::<Runtime>::insert(
+			DEX_POOL,
+			PoolInfo {
+				total_shares: 3,
+				total_rewards: 31,
+				total_withdrawn_rewards: 15,
+			},
+		);
+		ShareAndWithdrawnReward::<Runtime>::insert(DEX_POOL, ALICE, (2, 5));
+		ShareAndWithdrawnReward::<Runtime>::insert(DEX_POOL, BOB, (1, 10));
+		Pools::<Runtime>::insert(
+			LOANS_POOL,
+			PoolInfo {
+				total_shares: 0,
+				total_rewards: 10,
+				total_withdrawn_rewards: 4,
+			},
+		);
+		assert_eq!(RewardsModule::storage_version(), Releases::V1_0_0);
+
+		RewardsModule::on_runtime_upgrade();
+		assert_eq!(RewardsModule::storage_version(), Releases::V2_0_0);
+		assert_eq!(
+			RewardsModule::reward_accumulators(DEX_POOL),
+			RewardAccumulator {
+				reward_per_share: scaled(31) / 3,
+				remainder: scaled(31) % 3,
+			}
+		);
+		// the pending rewards of `share * total_rewards / total_shares - withdrawn_rewards`
+		assert_eq!(RewardsModule::pending_reward(&DEX_POOL, &ALICE), 15);
+		assert_eq!(RewardsModule::pending_reward(&DEX_POOL, &BOB), 0);
+		assert_eq!(
+			RewardsModule::reward_accumulators(LOANS_POOL),
+			RewardAccumulator {
+				reward_per_share: U256::zero(),
+				remainder: scaled(6),
+			}
+		);
+		RewardsModule::add_share(&CAROL, &LOANS_POOL, 2);
+		assert_eq!(RewardsModule::pending_reward(&LOANS_POOL, &CAROL), 6);
+
+		// runs only once
+		RewardAccumulators::<Runtime>::remove(DEX_POOL);
+		assert_eq!(RewardsModule::on_runtime_upgrade(), 0);
+		assert_eq!(RewardsModule::reward_accumulators(DEX_POOL), Default::default());
+	});
+}
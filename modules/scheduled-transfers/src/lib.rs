@@ -0,0 +1,440 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Scheduled Transfers Module
+//!
+//! ## Overview
+//!
+//! Users schedule one-shot or recurring transfers of any currency to another account, e.g.
+//! payroll. The total amount of all the executions is escrowed in the module account when the
+//! transfer is scheduled, and refunded when it's cancelled or the amount or executions are reduced.
+//! The due transfers are executed in `on_initialize` within `MaxExecutionWeight`, the rest are
+//! kept in the agenda and executed in the next blocks from the block `IncompleteSince`, so the
+//! next execution of a transfer always matches its agenda. A transfer that fails to execute is
+//! cancelled and the remaining escrow is refunded. A deposit of native currency is reserved from
+//! the owner for every scheduled transfer, and unreserved when it's completed or cancelled.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{log, pallet_prelude::*, traits::NamedReservableCurrency, transactional, PalletId};
+use frame_system::pallet_prelude::*;
+use orml_traits::MultiCurrency;
+use primitives::{Balance, CurrencyId, ReserveIdentifier};
+use sp_runtime::{
+	traits::{AccountIdConversion, One, Saturating, StaticLookup, Zero},
+	ArithmeticError, RuntimeDebug,
+};
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+/// The id of scheduled transfer.
+pub type TransferId = u64;
+
+/// The scheduled transfer.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct ScheduledTransfer<AccountId, BlockNumber> {
+	/// The owner who scheduled the transfer and funded the escrow.
+	pub from: AccountId,
+	/// The recipient.
+	pub to: AccountId,
+	/// The currency to transfer.
+	pub currency_id: CurrencyId,
+	/// The amount of each execution.
+	pub amount: Balance,
+	/// The block number of the next execution.
+	pub next_execution: BlockNumber,
+	/// The period between executions, `None` for one-shot transfer.
+	pub period: Option<BlockNumber>,
+	/// The number of remaining executions.
+	pub remaining_executions: u32,
+	/// The deposit reserved from the owner.
+	pub deposit: Balance,
+}
+
+impl<AccountId, BlockNumber> ScheduledTransfer<AccountId, BlockNumber> {
+	/// The escrowed amount of the remaining executions.
+	pub fn escrow(&self) -> Balance {
+		self.amount.saturating_mul(self.remaining_executions.into())
+	}
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	pub const RESERVE_ID: ReserveIdentifier = ReserveIdentifier::ScheduledTransfers;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currencies to transfer.
+		type Currency: MultiCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
+
+		/// The native currency to reserve the deposits.
+		type DepositCurrency: NamedReservableCurrency<
+			Self::AccountId,
+			Balance = Balance,
+			ReserveIdentifier = ReserveIdentifier,
+		>;
+
+		/// The deposit reserved for every scheduled transfer.
+		#[pallet::constant]
+		type TransferDeposit: Get<Balance>;
+
+		/// The module id to keep the escrow.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The maximum number of executions of a transfer.
+		#[pallet::constant]
+		type MaxExecutions: Get<u32>;
+
+		/// The maximum weight of the transfers executed in `on_initialize`, the rest are executed
+		/// in the next blocks.
+		#[pallet::constant]
+		type MaxExecutionWeight: Get<Weight>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The execution block number is not in the future
+		InvalidStart,
+		/// The period is zero, or the executions don't match the period
+		InvalidPeriod,
+		/// The number of executions is zero or exceeds `MaxExecutions`
+		InvalidExecutions,
+		/// The amount is zero
+		InvalidAmount,
+		/// The transfer does not exist
+		TransferNotFound,
+		/// The caller is not the owner of the transfer
+		NoPermission,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Scheduled transfer. \[transfer_id, transfer\]
+		TransferScheduled(TransferId, ScheduledTransfer<T::AccountId, T::BlockNumber>),
+		/// Modified the scheduled transfer. \[transfer_id, transfer\]
+		TransferModified(TransferId, ScheduledTransfer<T::AccountId, T::BlockNumber>),
+		/// Cancelled the scheduled transfer and refunded the escrow. \[transfer_id, refund\]
+		TransferCancelled(TransferId, Balance),
+		/// Executed the scheduled transfer. \[transfer_id, currency_id, from, to, amount\]
+		TransferExecuted(TransferId, CurrencyId, T::AccountId, T::AccountId, Balance),
+		/// Failed to execute the scheduled transfer, it's cancelled and the escrow is refunded.
+		/// \[transfer_id, error\]
+		TransferFailed(TransferId, DispatchError),
+	}
+
+	/// The id of the next scheduled transfer.
+	///
+	/// NextTransferId: TransferId
+	#[pallet::storage]
+	#[pallet::getter(fn next_transfer_id)]
+	pub type NextTransferId<T: Config> = StorageValue<_, TransferId, ValueQuery>;
+
+	/// The scheduled transfers.
+	///
+	/// Transfers: map TransferId => Option<ScheduledTransfer>
+	#[pallet::storage]
+	#[pallet::getter(fn transfers)]
+	pub type Transfers<T: Config> =
+		StorageMap<_, Twox64Concat, TransferId, ScheduledTransfer<T::AccountId, T::BlockNumber>, OptionQuery>;
+
+	/// The transfers due at the block number.
+	///
+	/// Agenda: double_map BlockNumber, TransferId => Option<()>
+	#[pallet::storage]
+	#[pallet::getter(fn agenda)]
+	pub type Agenda<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::BlockNumber, Twox64Concat, TransferId, (), OptionQuery>;
+
+	/// The earliest block number of the agenda with the transfers not executed yet, `None` if all
+	/// the transfers due until the last block were executed.
+	///
+	/// IncompleteSince: Option<BlockNumber>
+	#[pallet::storage]
+	#[pallet::getter(fn incomplete_since)]
+	pub type IncompleteSince<T: Config> = StorageValue<_, T::BlockNumber, OptionQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let weight = |executed: u32, scanned: u32| {
+				T::WeightInfo::on_initialize(executed).saturating_add(T::DbWeight::get().reads(scanned.into()))
+			};
+
+			let mut block = Self::incomplete_since().unwrap_or(now);
+			let mut executed: u32 = 0;
+			let mut scanned: u32 = 0;
+			let completed = loop {
+				if weight(executed.saturating_add(1), scanned.saturating_add(1)) > T::MaxExecutionWeight::get() {
+					break false;
+				}
+
+				scanned += 1;
+				match Agenda::<T>::iter_prefix(block).next() {
+					Some((transfer_id, _)) => {
+						Agenda::<T>::remove(block, transfer_id);
+						Self::execute_transfer(transfer_id, now);
+						executed += 1;
+					}
+					None if block < now => block = block.saturating_add(One::one()),
+					None => break true,
+				}
+			};
+
+			if completed {
+				IncompleteSince::<T>::kill();
+			} else {
+				log::debug!(
+					target: "scheduled-transfers",
+					"on_initialize: the transfers due since {:?} are executed in the next blocks",
+					block
+				);
+				IncompleteSince::<T>::put(block);
+			}
+
+			weight(executed, scanned)
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Schedule a transfer of `amount` of `currency_id` to `to`, executed at `start` and then
+		/// every `period` blocks, `executions` times in total. The total amount of all the
+		/// executions is escrowed.
+		///
+		/// - `to`: the recipient.
+		/// - `currency_id`: the currency to transfer.
+		/// - `amount`: the amount of each execution.
+		/// - `start`: the block number of the first execution.
+		/// - `period`: the period between executions, `None` for one-shot transfer.
+		/// - `executions`: the number of executions, must be 1 for one-shot transfer.
+		#[pallet::weight(T::WeightInfo::schedule_transfer())]
+		#[transactional]
+		pub fn schedule_transfer(
+			origin: OriginFor<T>,
+			to: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyId,
+			#[pallet::compact] amount: Balance,
+			start: T::BlockNumber,
+			period: Option<T::BlockNumber>,
+			executions: u32,
+		) -> DispatchResultWithPostInfo {
+			let from = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(to)?;
+			ensure!(
+				start > frame_system::Pallet::<T>::block_number(),
+				Error::<T>::InvalidStart
+			);
+
+			let transfer = ScheduledTransfer {
+				from,
+				to,
+				currency_id,
+				amount,
+				next_execution: start,
+				period,
+				remaining_executions: executions,
+				deposit: T::TransferDeposit::get(),
+			};
+			let escrow = Self::ensure_valid(&transfer)?;
+			T::DepositCurrency::reserve_named(&RESERVE_ID, &transfer.from, transfer.deposit)?;
+			T::Currency::transfer(currency_id, &transfer.from, &Self::account_id(), escrow)?;
+
+			let transfer_id = NextTransferId::<T>::try_mutate(|id| -> Result<TransferId, DispatchError> {
+				let current_id = *id;
+				*id = id.checked_add(One::one()).ok_or(ArithmeticError::Overflow)?;
+				Ok(current_id)
+			})?;
+			Transfers::<T>::insert(transfer_id, &transfer);
+			Agenda::<T>::insert(start, transfer_id, ());
+
+			Self::deposit_event(Event::TransferScheduled(transfer_id, transfer));
+			Ok(().into())
+		}
+
+		/// Cancel the scheduled transfer of the caller, the remaining escrow is refunded and the
+		/// deposit is unreserved.
+		///
+		/// - `transfer_id`: the id of the transfer.
+		#[pallet::weight(T::WeightInfo::cancel_transfer())]
+		#[transactional]
+		pub fn cancel_transfer(origin: OriginFor<T>, transfer_id: TransferId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let transfer = Self::transfers(transfer_id).ok_or(Error::<T>::TransferNotFound)?;
+			ensure!(who == transfer.from, Error::<T>::NoPermission);
+
+			let refund = transfer.escrow();
+			T::Currency::transfer(transfer.currency_id, &Self::account_id(), &transfer.from, refund)?;
+			T::DepositCurrency::unreserve_named(&RESERVE_ID, &transfer.from, transfer.deposit);
+			Transfers::<T>::remove(transfer_id);
+			Agenda::<T>::remove(transfer.next_execution, transfer_id);
+
+			Self::deposit_event(Event::TransferCancelled(transfer_id, refund));
+			Ok(().into())
+		}
+
+		/// Modify the amount, period and remaining executions of the scheduled transfer of the
+		/// caller, the escrow is topped up or refunded by the difference. The next execution is
+		/// not changed.
+		///
+		/// - `transfer_id`: the id of the transfer.
+		/// - `amount`: the amount of each execution.
+		/// - `period`: the period between executions, `None` for one-shot transfer.
+		/// - `executions`: the number of remaining executions.
+		#[pallet::weight(T::WeightInfo::modify_transfer())]
+		#[transactional]
+		pub fn modify_transfer(
+			origin: OriginFor<T>,
+			transfer_id: TransferId,
+			#[pallet::compact] amount: Balance,
+			period: Option<T::BlockNumber>,
+			executions: u32,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let transfer = Transfers::<T>::try_mutate(
+				transfer_id,
+				|maybe_transfer| -> Result<ScheduledTransfer<T::AccountId, T::BlockNumber>, DispatchError> {
+					let transfer = maybe_transfer.as_mut().ok_or(Error::<T>::TransferNotFound)?;
+					ensure!(who == transfer.from, Error::<T>::NoPermission);
+
+					let old_escrow = transfer.escrow();
+					transfer.amount = amount;
+					transfer.period = period;
+					transfer.remaining_executions = executions;
+					let new_escrow = Self::ensure_valid(transfer)?;
+
+					if new_escrow > old_escrow {
+						T::Currency::transfer(
+							transfer.currency_id,
+							&transfer.from,
+							&Self::account_id(),
+							new_escrow - old_escrow,
+						)?;
+					} else if new_escrow < old_escrow {
+						T::Currency::transfer(
+							transfer.currency_id,
+							&Self::account_id(),
+							&transfer.from,
+							old_escrow - new_escrow,
+						)?;
+					}
+					Ok(transfer.clone())
+				},
+			)?;
+
+			Self::deposit_event(Event::TransferModified(transfer_id, transfer));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The account to keep the escrow.
+	pub fn account_id() -> T::AccountId {
+		T::PalletId::get().into_account()
+	}
+
+	/// Ensure the amount, period and executions are valid, returns the escrow of the transfer.
+	fn ensure_valid(transfer: &ScheduledTransfer<T::AccountId, T::BlockNumber>) -> Result<Balance, DispatchError> {
+		ensure!(!transfer.amount.is_zero(), Error::<T>::InvalidAmount);
+		ensure!(
+			transfer.remaining_executions > 0 && transfer.remaining_executions <= T::MaxExecutions::get(),
+			Error::<T>::InvalidExecutions
+		);
+		match transfer.period {
+			Some(period) => ensure!(!period.is_zero(), Error::<T>::InvalidPeriod),
+			None => ensure!(transfer.remaining_executions == 1, Error::<T>::InvalidPeriod),
+		}
+
+		transfer
+			.amount
+			.checked_mul(transfer.remaining_executions.into())
+			.ok_or_else(|| ArithmeticError::Overflow.into())
+	}
+
+	fn execute_transfer(transfer_id: TransferId, now: T::BlockNumber) {
+		let mut transfer = match Self::transfers(transfer_id) {
+			Some(transfer) => transfer,
+			None => return,
+		};
+
+		match T::Currency::transfer(transfer.currency_id, &Self::account_id(), &transfer.to, transfer.amount) {
+			Ok(()) => {
+				Self::deposit_event(Event::TransferExecuted(
+					transfer_id,
+					transfer.currency_id,
+					transfer.from.clone(),
+					transfer.to.clone(),
+					transfer.amount,
+				));
+
+				transfer.remaining_executions = transfer.remaining_executions.saturating_sub(1);
+				match transfer.period {
+					Some(period) if transfer.remaining_executions > 0 => {
+						// keep the schedule if the execution was postponed, but never schedule in the past
+						transfer.next_execution = transfer
+							.next_execution
+							.saturating_add(period)
+							.max(now.saturating_add(One::one()));
+						Agenda::<T>::insert(transfer.next_execution, transfer_id, ());
+						Transfers::<T>::insert(transfer_id, transfer);
+					}
+					_ => {
+						T::DepositCurrency::unreserve_named(&RESERVE_ID, &transfer.from, transfer.deposit);
+						Transfers::<T>::remove(transfer_id);
+					}
+				}
+			}
+			Err(e) => {
+				let refund = transfer.escrow();
+				if let Err(refund_error) =
+					T::Currency::transfer(transfer.currency_id, &Self::account_id(), &transfer.from, refund)
+				{
+					log::warn!(
+						target: "scheduled-transfers",
+						"execute_transfer: failed to refund {:?} of transfer {:?}: {:?}",
+						refund,
+						transfer_id,
+						refund_error
+					);
+				}
+				T::DepositCurrency::unreserve_named(&RESERVE_ID, &transfer.from, transfer.deposit);
+				Transfers::<T>::remove(transfer_id);
+				Self::deposit_event(Event::TransferFailed(transfer_id, e));
+			}
+		}
+	}
+}
@@ -0,0 +1,365 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for scheduled transfers module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+
+fn escrow_account() -> AccountId {
+	ScheduledTransfersModule::account_id()
+}
+
+#[test]
+fn schedule_transfer_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			ScheduledTransfersModule::schedule_transfer(Origin::signed(ALICE), BOB, AUSD, 100, 1, None, 1),
+			Error::<Runtime>::InvalidStart
+		);
+		assert_noop!(
+			ScheduledTransfersModule::schedule_transfer(Origin::signed(ALICE), BOB, AUSD, 0, 2, None, 1),
+			Error::<Runtime>::InvalidAmount
+		);
+		assert_noop!(
+			ScheduledTransfersModule::schedule_transfer(Origin::signed(ALICE), BOB, AUSD, 100, 2, Some(5), 0),
+			Error::<Runtime>::InvalidExecutions
+		);
+		assert_noop!(
+			ScheduledTransfersModule::schedule_transfer(Origin::signed(ALICE), BOB, AUSD, 10, 2, Some(5), 11),
+			Error::<Runtime>::InvalidExecutions
+		);
+		assert_noop!(
+			ScheduledTransfersModule::schedule_transfer(Origin::signed(ALICE), BOB, AUSD, 100, 2, None, 2),
+			Error::<Runtime>::InvalidPeriod
+		);
+		assert_noop!(
+			ScheduledTransfersModule::schedule_transfer(Origin::signed(ALICE), BOB, AUSD, 100, 2, Some(0), 2),
+			Error::<Runtime>::InvalidPeriod
+		);
+		assert_noop!(
+			ScheduledTransfersModule::schedule_transfer(Origin::signed(ALICE), BOB, AUSD, 500, 2, Some(5), 3),
+			orml_tokens::Error::<Runtime>::BalanceTooLow
+		);
+		assert_noop!(
+			ScheduledTransfersModule::schedule_transfer(Origin::signed(CHARLIE), BOB, AUSD, 100, 2, Some(5), 3),
+			pallet_balances::Error::<Runtime>::InsufficientBalance
+		);
+
+		assert_ok!(ScheduledTransfersModule::schedule_transfer(
+			Origin::signed(ALICE),
+			BOB,
+			AUSD,
+			100,
+			2,
+			Some(5),
+			3
+		));
+		let transfer = ScheduledTransfer {
+			from: ALICE,
+			to: BOB,
+			currency_id: AUSD,
+			amount: 100,
+			next_execution: 2,
+			period: Some(5),
+			remaining_executions: 3,
+			deposit: 10,
+		};
+		System::assert_last_event(Event::ScheduledTransfersModule(crate::Event::TransferScheduled(
+			0,
+			transfer.clone(),
+		)));
+		assert_eq!(ScheduledTransfersModule::next_transfer_id(), 1);
+		assert_eq!(ScheduledTransfersModule::transfers(0), Some(transfer));
+		assert_eq!(ScheduledTransfersModule::agenda(2, 0), Some(()));
+		assert_eq!(Tokens::free_balance(AUSD, &ALICE), 700);
+		assert_eq!(Tokens::free_balance(AUSD, &escrow_account()), 300);
+		assert_eq!(Balances::reserved_balance(ALICE), 10);
+	});
+}
+
+#[test]
+fn execute_one_shot_transfer_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(ScheduledTransfersModule::schedule_transfer(
+			Origin::signed(ALICE),
+			BOB,
+			AUSD,
+			100,
+			3,
+			None,
+			1
+		));
+
+		ScheduledTransfersModule::on_initialize(2);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 1000);
+
+		ScheduledTransfersModule::on_initialize(3);
+		System::assert_last_event(Event::ScheduledTransfersModule(crate::Event::TransferExecuted(
+			0, AUSD, ALICE, BOB, 100,
+		)));
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 1100);
+		assert_eq!(Tokens::free_balance(AUSD, &escrow_account()), 0);
+		assert_eq!(ScheduledTransfersModule::transfers(0), None);
+		assert_eq!(ScheduledTransfersModule::agenda(3, 0), None);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+	});
+}
+
+#[test]
+fn execute_recurring_transfer_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(ScheduledTransfersModule::schedule_transfer(
+			Origin::signed(ALICE),
+			BOB,
+			AUSD,
+			100,
+			2,
+			Some(5),
+			2
+		));
+
+		ScheduledTransfersModule::on_initialize(2);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 1100);
+		assert_eq!(Tokens::free_balance(AUSD, &escrow_account()), 100);
+		assert_eq!(ScheduledTransfersModule::agenda(2, 0), None);
+		assert_eq!(ScheduledTransfersModule::agenda(7, 0), Some(()));
+		assert_eq!(
+			ScheduledTransfersModule::transfers(0).map(|t| (t.next_execution, t.remaining_executions)),
+			Some((7, 1))
+		);
+
+		ScheduledTransfersModule::on_initialize(7);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 1200);
+		assert_eq!(Tokens::free_balance(AUSD, &escrow_account()), 0);
+		assert_eq!(ScheduledTransfersModule::transfers(0), None);
+		assert_eq!(ScheduledTransfersModule::agenda(12, 0), None);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+	});
+}
+
+#[test]
+fn execution_exceeding_weight_is_postponed() {
+	ExtBuilder::default().build().execute_with(|| {
+		for _ in 0..3 {
+			assert_ok!(ScheduledTransfersModule::schedule_transfer(
+				Origin::signed(ALICE),
+				BOB,
+				AUSD,
+				100,
+				2,
+				Some(5),
+				2
+			));
+		}
+
+		assert_eq!(
+			ScheduledTransfersModule::on_initialize(2),
+			<() as WeightInfo>::on_initialize(2)
+		);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 1200);
+		assert_eq!(ScheduledTransfersModule::incomplete_since(), Some(2));
+		// the postponed transfer is kept in the agenda of its next execution
+		let (postponed, _) = Agenda::<Runtime>::iter_prefix(2).next().unwrap();
+		assert_eq!(Agenda::<Runtime>::iter_prefix(2).count(), 1);
+		assert_eq!(
+			ScheduledTransfersModule::transfers(postponed).map(|t| t.next_execution),
+			Some(2)
+		);
+		assert_eq!(Agenda::<Runtime>::iter_prefix(7).count(), 2);
+
+		// the postponed execution keeps the schedule of the next one
+		ScheduledTransfersModule::on_initialize(3);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 1300);
+		assert_eq!(ScheduledTransfersModule::incomplete_since(), None);
+		assert_eq!(Agenda::<Runtime>::iter_prefix(2).count(), 0);
+		assert_eq!(Agenda::<Runtime>::iter_prefix(7).count(), 3);
+		assert_eq!(
+			ScheduledTransfersModule::transfers(postponed).map(|t| t.next_execution),
+			Some(7)
+		);
+	});
+}
+
+#[test]
+fn postponed_transfer_can_be_cancelled() {
+	ExtBuilder::default().build().execute_with(|| {
+		for _ in 0..3 {
+			assert_ok!(ScheduledTransfersModule::schedule_transfer(
+				Origin::signed(ALICE),
+				BOB,
+				AUSD,
+				100,
+				2,
+				None,
+				1
+			));
+		}
+		ScheduledTransfersModule::on_initialize(2);
+		let (postponed, _) = Agenda::<Runtime>::iter_prefix(2).next().unwrap();
+		assert_eq!(Balances::reserved_balance(ALICE), 10);
+
+		assert_ok!(ScheduledTransfersModule::cancel_transfer(
+			Origin::signed(ALICE),
+			postponed
+		));
+		assert_eq!(ScheduledTransfersModule::agenda(2, postponed), None);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+
+		// the agenda since the incomplete block is executed
+		ScheduledTransfersModule::on_initialize(3);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 1200);
+		assert_eq!(Tokens::free_balance(AUSD, &ALICE), 800);
+		assert_eq!(ScheduledTransfersModule::incomplete_since(), None);
+	});
+}
+
+#[test]
+fn failed_execution_refunds_escrow() {
+	ExtBuilder::default().build().execute_with(|| {
+		// the amount is below the existential deposit of DOT for the new account
+		assert_ok!(ScheduledTransfersModule::schedule_transfer(
+			Origin::signed(ALICE),
+			CHARLIE,
+			DOT,
+			5,
+			2,
+			Some(5),
+			4
+		));
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 980);
+
+		ScheduledTransfersModule::on_initialize(2);
+		System::assert_last_event(Event::ScheduledTransfersModule(crate::Event::TransferFailed(
+			0,
+			orml_tokens::Error::<Runtime>::ExistentialDeposit.into(),
+		)));
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 1000);
+		assert_eq!(Tokens::free_balance(DOT, &CHARLIE), 0);
+		assert_eq!(ScheduledTransfersModule::transfers(0), None);
+		assert_eq!(ScheduledTransfersModule::agenda(7, 0), None);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+	});
+}
+
+#[test]
+fn cancel_transfer_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			ScheduledTransfersModule::cancel_transfer(Origin::signed(ALICE), 0),
+			Error::<Runtime>::TransferNotFound
+		);
+
+		assert_ok!(ScheduledTransfersModule::schedule_transfer(
+			Origin::signed(ALICE),
+			BOB,
+			AUSD,
+			100,
+			2,
+			Some(5),
+			3
+		));
+		ScheduledTransfersModule::on_initialize(2);
+		assert_eq!(Tokens::free_balance(AUSD, &ALICE), 700);
+
+		assert_noop!(
+			ScheduledTransfersModule::cancel_transfer(Origin::signed(BOB), 0),
+			Error::<Runtime>::NoPermission
+		);
+
+		assert_ok!(ScheduledTransfersModule::cancel_transfer(Origin::signed(ALICE), 0));
+		System::assert_last_event(Event::ScheduledTransfersModule(crate::Event::TransferCancelled(0, 200)));
+		assert_eq!(Tokens::free_balance(AUSD, &ALICE), 900);
+		assert_eq!(Tokens::free_balance(AUSD, &escrow_account()), 0);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(ScheduledTransfersModule::transfers(0), None);
+		assert_eq!(ScheduledTransfersModule::agenda(7, 0), None);
+	});
+}
+
+#[test]
+fn modify_transfer_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			ScheduledTransfersModule::modify_transfer(Origin::signed(ALICE), 0, 100, Some(5), 1),
+			Error::<Runtime>::TransferNotFound
+		);
+
+		assert_ok!(ScheduledTransfersModule::schedule_transfer(
+			Origin::signed(ALICE),
+			BOB,
+			AUSD,
+			100,
+			2,
+			Some(5),
+			3
+		));
+		assert_noop!(
+			ScheduledTransfersModule::modify_transfer(Origin::signed(BOB), 0, 100, Some(5), 1),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			ScheduledTransfersModule::modify_transfer(Origin::signed(ALICE), 0, 100, None, 2),
+			Error::<Runtime>::InvalidPeriod
+		);
+
+		// top up the escrow
+		assert_ok!(ScheduledTransfersModule::modify_transfer(
+			Origin::signed(ALICE),
+			0,
+			200,
+			Some(10),
+			2
+		));
+		let transfer = ScheduledTransfer {
+			from: ALICE,
+			to: BOB,
+			currency_id: AUSD,
+			amount: 200,
+			next_execution: 2,
+			period: Some(10),
+			remaining_executions: 2,
+			deposit: 10,
+		};
+		System::assert_last_event(Event::ScheduledTransfersModule(crate::Event::TransferModified(
+			0,
+			transfer.clone(),
+		)));
+		assert_eq!(ScheduledTransfersModule::transfers(0), Some(transfer));
+		assert_eq!(Tokens::free_balance(AUSD, &ALICE), 600);
+		assert_eq!(Tokens::free_balance(AUSD, &escrow_account()), 400);
+
+		// refund the escrow
+		assert_ok!(ScheduledTransfersModule::modify_transfer(
+			Origin::signed(ALICE),
+			0,
+			50,
+			None,
+			1
+		));
+		assert_eq!(Tokens::free_balance(AUSD, &ALICE), 950);
+		assert_eq!(Tokens::free_balance(AUSD, &escrow_account()), 50);
+
+		ScheduledTransfersModule::on_initialize(2);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 1050);
+		assert_eq!(ScheduledTransfersModule::transfers(0), None);
+	});
+}
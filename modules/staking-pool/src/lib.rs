@@ -19,20 +19,20 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 
-use frame_support::{pallet_prelude::*, transactional, PalletId};
+use frame_support::{log, pallet_prelude::*, transactional, PalletId};
 use frame_system::pallet_prelude::*;
 use orml_traits::{Change, Happened, MultiCurrency};
 use primitives::{Balance, CurrencyId, EraIndex};
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use sp_runtime::{
-	traits::{AccountIdConversion, CheckedDiv, Saturating, Zero},
+	traits::{AccountIdConversion, CheckedDiv, One, Saturating, Zero},
 	ArithmeticError, DispatchError, DispatchResult, FixedPointNumber, RuntimeDebug,
 };
 use sp_std::prelude::*;
 use support::{
 	ExchangeRate, HomaProtocol, NomineesProvider, OnNewEra, PolkadotBridge, PolkadotBridgeCall, PolkadotBridgeState,
-	PolkadotBridgeType, PolkadotStakingLedger, PolkadotUnlockChunk, Rate, Ratio,
+	PolkadotBridgeType, PolkadotStakingLedger, PolkadotUnlockChunk, Rate, Ratio, ValidatorRewardsDistributor,
 };
 
 mod mock;
@@ -121,6 +121,112 @@ impl Ledger {
 	fn unbonding_to_free_ratio(&self) -> Ratio {
 		Ratio::checked_from_rational(self.unbonding_to_free, self.total_belong_to_liquid_holders()).unwrap_or_default()
 	}
+
+	/// The ratio of cash buffer(`free_pool` and `unbonding_to_free`) in
+	/// `total_belong_to_liquid_holders`.
+	fn cash_buffer_ratio(&self) -> Ratio {
+		Ratio::checked_from_rational(
+			self.free_pool.saturating_add(self.unbonding_to_free),
+			self.total_belong_to_liquid_holders(),
+		)
+		.unwrap_or_default()
+	}
+}
+
+/// The policy to keep a fraction of the staking currency belong to liquid
+/// currency holders unbonded, as the cash buffer for instant redemptions. The
+/// cash buffer includes the free pool and the unbonding to free pool.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, Default)]
+pub struct CashBufferPolicy {
+	/// The target ratio of the cash buffer.
+	pub target_ratio: Ratio,
+	/// Unbond to steer toward the target ratio only if the ratio of cash
+	/// buffer is below the min ratio.
+	pub min_ratio: Ratio,
+	/// Bond to steer toward the target ratio only if the ratio of cash buffer
+	/// is above the max ratio.
+	pub max_ratio: Ratio,
+	/// The max ratio of adjustment per era.
+	pub max_adjustment_per_era: Rate,
+}
+
+impl CashBufferPolicy {
+	fn is_valid(&self) -> bool {
+		self.min_ratio <= self.target_ratio && self.target_ratio <= self.max_ratio && self.max_ratio <= Ratio::one()
+	}
+}
+
+/// The fee rates for minting liquid currency.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, Default)]
+pub struct MintFeeRates {
+	/// The fee rate for minting immediately.
+	pub immediately: Rate,
+	/// The fee rate for minting at next era, which should not be greater
+	/// than `immediately`.
+	pub next_era: Rate,
+}
+
+/// The volume limits of mint and redeem, `None` means unlimited. The mint
+/// volume is in staking currency and the redeem volume is in liquid currency.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, Default)]
+pub struct VolumeLimits {
+	/// The max mint volume per block.
+	pub mint_per_block: Option<Balance>,
+	/// The max mint volume per era.
+	pub mint_per_era: Option<Balance>,
+	/// The max redeem volume per block.
+	pub redeem_per_block: Option<Balance>,
+	/// The max redeem volume per era.
+	pub redeem_per_era: Option<Balance>,
+}
+
+/// The mint and redeem volume already used.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, Default)]
+pub struct Volume {
+	/// The volume of mint, in staking currency.
+	pub mint: Balance,
+	/// The volume of redeem, in liquid currency.
+	pub redeem: Balance,
+}
+
+/// The request of Homa protocol, which will be queued if exceed the volume
+/// limits.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub enum HomaRequest {
+	/// Mint liquid currency by the staking amount.
+	Mint(Balance),
+	/// Redeem by unbond with the liquid amount.
+	RedeemByUnbond(Balance),
+	/// Redeem by free unbonded with the liquid amount.
+	RedeemByFreeUnbonded(Balance),
+	/// Redeem by claim unbonding of the target era with the liquid amount.
+	RedeemByClaimUnbonding(Balance, EraIndex),
+}
+
+impl HomaRequest {
+	fn is_mint(&self) -> bool {
+		matches!(self, HomaRequest::Mint(_))
+	}
+
+	fn amount(&self) -> Balance {
+		match *self {
+			HomaRequest::Mint(amount)
+			| HomaRequest::RedeemByUnbond(amount)
+			| HomaRequest::RedeemByFreeUnbonded(amount)
+			| HomaRequest::RedeemByClaimUnbonding(amount, _) => amount,
+		}
+	}
+
+	fn with_amount(&self, amount: Balance) -> Self {
+		match *self {
+			HomaRequest::Mint(_) => HomaRequest::Mint(amount),
+			HomaRequest::RedeemByUnbond(_) => HomaRequest::RedeemByUnbond(amount),
+			HomaRequest::RedeemByFreeUnbonded(_) => HomaRequest::RedeemByFreeUnbonded(amount),
+			HomaRequest::RedeemByClaimUnbonding(_, target_era) => {
+				HomaRequest::RedeemByClaimUnbonding(amount, target_era)
+			}
+		}
+	}
 }
 
 /// Fee rate calculater.
@@ -141,6 +247,14 @@ type PolkadotAccountIdOf<T> = <<T as Config>::Bridge as PolkadotBridgeType<
 	EraIndex,
 >>::PolkadotAccountId;
 
+/// The estimated weight of executing a queued request, excluding the db
+/// operations.
+const QUEUED_REQUEST_WEIGHT: Weight = 200_000_000;
+
+/// The estimated weight of issuing the liquid currency of a next era mint,
+/// excluding the db operations.
+const NEXT_ERA_MINT_WEIGHT: Weight = 50_000_000;
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -186,6 +300,32 @@ pub mod module {
 
 		/// The currency for managing assets related to Homa protocol.
 		type Currency: MultiCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
+
+		/// The number of eras to keep the exchange rate snapshots.
+		#[pallet::constant]
+		type MaxExchangeRateHistory: Get<EraIndex>;
+
+		/// The max number of queued requests to process in a block.
+		#[pallet::constant]
+		type MaxQueuedRequestsPerBlock: Get<u32>;
+
+		/// The max number of next era mints to issue liquid currency for in a
+		/// block, the rest are issued in the following blocks.
+		#[pallet::constant]
+		type MaxNextEraMintsPerBlock: Get<u32>;
+
+		/// The minimum staking amount to mint at next era.
+		#[pallet::constant]
+		type MinNextEraMintAmount: Get<Balance>;
+
+		/// The ratio of the compounded staking rewards to be shared with the
+		/// guarantors of the nominees in liquid currency.
+		#[pallet::constant]
+		type GuarantorRewardRatio: Get<Ratio>;
+
+		/// Distribute the shared staking rewards to the guarantors of
+		/// validators.
+		type ValidatorRewards: ValidatorRewardsDistributor<Self::AccountId, PolkadotAccountIdOf<Self>, Balance>;
 	}
 
 	#[pallet::error]
@@ -198,6 +338,15 @@ pub mod module {
 		InvalidConfig,
 		/// Rebalance process is unfinished.
 		RebalanceUnfinished,
+		/// The request exceeds the volume limit per block or per era.
+		ExceedVolumeLimit,
+		/// The queued request is not found or not owned by the caller.
+		RequestNotFound,
+		/// The amount is below the minimum amount.
+		BelowMinimumAmount,
+		/// The liquid currency of the bonded next era mints is still being
+		/// issued.
+		NextEraMintsIssuing,
 	}
 
 	#[pallet::event]
@@ -219,6 +368,46 @@ pub mod module {
 		/// the unbonding_to_free of specific era. \[who, target_era,
 		/// fee_in_staking, liquid_amount_burned, staking_amount_redeemed\]
 		RedeemByClaimUnbonding(T::AccountId, EraIndex, Balance, Balance, Balance),
+		/// Deposit staking currency(DOT) to staking pool, and will issue liquid
+		/// currency(LDOT) at next era. \[who, staking_amount_deposited\]
+		MintLiquidAtNextEra(T::AccountId, Balance),
+		/// Deposit staking currency(DOT) to the insurance reserve. \[who,
+		/// amount\]
+		DepositInsuranceReserve(T::AccountId, Balance),
+		/// Slash on relaychain has been compensated by the waterfall of
+		/// guarantee deposits and insurance reserve, the remains has been
+		/// deducted from the exchange rate. \[slashed_staking_amount,
+		/// compensated_by_guarantee, compensated_by_insurance,
+		/// uncompensated_loss\]
+		SlashCompensated(Balance, Balance, Balance, Balance),
+		/// The volume limits updated. \[limits\]
+		VolumeLimitsUpdated(VolumeLimits),
+		/// The request exceeds the current volume limits and has been queued,
+		/// the assets of request are kept by staking pool. \[index, who,
+		/// request\]
+		RequestQueued(u32, T::AccountId, HomaRequest),
+		/// The queued request has been executed, the assets of request are
+		/// returned to the requester if failed. \[who, request, succeeded\]
+		QueuedRequestExecuted(T::AccountId, HomaRequest, bool),
+		/// The staking rewards of sub account on relaychain have been claimed
+		/// and bonded. \[sub_account_index, era, rewards\]
+		RewardsCompounded(u32, EraIndex, Balance),
+		/// The amount of queued request has been reduced and the position in
+		/// queue is kept. \[index, who, request\]
+		QueuedRequestReduced(u32, T::AccountId, HomaRequest),
+		/// The queued request has been cancelled and the assets have been
+		/// returned. \[index, who\]
+		QueuedRequestCancelled(u32, T::AccountId),
+		/// The pending unbond of next era has been cancelled, and the liquid
+		/// currency has been issued back. \[who, staking_amount_cancelled,
+		/// liquid_amount_issued\]
+		RedeemByUnbondCancelled(T::AccountId, Balance, Balance),
+		/// The cash buffer policy updated. \[policy\]
+		CashBufferPolicyUpdated(Option<CashBufferPolicy>),
+		/// Failed to issue liquid currency for the bonded next era mint, the
+		/// staking currency has been refunded from the free pool if it's
+		/// enough. \[who, staking_amount, refunded\]
+		NextEraMintFailed(T::AccountId, Balance, bool),
 	}
 
 	/// Current era index on Relaychain.
@@ -275,6 +464,97 @@ pub mod module {
 	#[pallet::getter(fn staking_pool_params)]
 	pub type StakingPoolParams<T: Config> = StorageValue<_, Params, ValueQuery>;
 
+	/// The fee rates for minting liquid currency.
+	///
+	/// StakingPoolMintFeeRates: MintFeeRates
+	#[pallet::storage]
+	#[pallet::getter(fn mint_fee_rates)]
+	pub type StakingPoolMintFeeRates<T: Config> = StorageValue<_, MintFeeRates, ValueQuery>;
+
+	/// Mint on next era beginning by AccountId.
+	///
+	/// NextEraMints: map AccountId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn next_era_mints)]
+	pub type NextEraMints<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, Balance, ValueQuery>;
+
+	/// The total staking amount to bond and mint on next era beginning.
+	///
+	/// TotalNextEraMint: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn total_next_era_mint)]
+	pub type TotalNextEraMint<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+	/// The exchange rate and the fee rate to issue liquid currency at for the
+	/// bonded `NextEraMints`, which are issued by `MaxNextEraMintsPerBlock`
+	/// per block.
+	///
+	/// NextEraMintsIssuance: Option<(ExchangeRate, Rate)>
+	#[pallet::storage]
+	#[pallet::getter(fn next_era_mints_issuance)]
+	pub type NextEraMintsIssuance<T: Config> = StorageValue<_, (ExchangeRate, Rate), OptionQuery>;
+
+	/// The cash buffer policy, the bond and unbond amounts at rebalance are
+	/// decided by `StakingPoolParams` if it's none.
+	///
+	/// StakingPoolCashBufferPolicy: Option<CashBufferPolicy>
+	#[pallet::storage]
+	#[pallet::getter(fn cash_buffer_policy)]
+	pub type StakingPoolCashBufferPolicy<T: Config> = StorageValue<_, CashBufferPolicy, OptionQuery>;
+
+	/// The volume limits of mint and redeem.
+	///
+	/// HomaVolumeLimits: VolumeLimits
+	#[pallet::storage]
+	#[pallet::getter(fn volume_limits)]
+	pub type HomaVolumeLimits<T: Config> = StorageValue<_, VolumeLimits, ValueQuery>;
+
+	/// The volume used in current block.
+	///
+	/// BlockVolume: Volume
+	#[pallet::storage]
+	#[pallet::getter(fn block_volume)]
+	pub type BlockVolume<T: Config> = StorageValue<_, Volume, ValueQuery>;
+
+	/// The volume used in current era.
+	///
+	/// EraVolume: Volume
+	#[pallet::storage]
+	#[pallet::getter(fn era_volume)]
+	pub type EraVolume<T: Config> = StorageValue<_, Volume, ValueQuery>;
+
+	/// The queued requests which exceed the volume limits, by queue index.
+	///
+	/// RequestQueue: map u32 => Option<(AccountId, HomaRequest)>
+	#[pallet::storage]
+	#[pallet::getter(fn request_queue)]
+	pub type RequestQueue<T: Config> = StorageMap<_, Twox64Concat, u32, (T::AccountId, HomaRequest), OptionQuery>;
+
+	/// The index range of request queue, the head is included and the tail is
+	/// excluded.
+	///
+	/// RequestQueueRange: (u32, u32)
+	#[pallet::storage]
+	#[pallet::getter(fn request_queue_range)]
+	pub type RequestQueueRange<T: Config> = StorageValue<_, (u32, u32), ValueQuery>;
+
+	/// The staking currency(DOT) reserved to compensate slash on relaychain,
+	/// which is kept in the pool account but not belong to liquid currency
+	/// holders.
+	///
+	/// InsuranceReserve: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn insurance_reserve)]
+	pub type InsuranceReserve<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+	/// The snapshots of liquid exchange rate at the end of the rebalance of
+	/// era, only keep the recent `MaxExchangeRateHistory` eras.
+	///
+	/// EraExchangeRates: map EraIndex => Option<ExchangeRate>
+	#[pallet::storage]
+	#[pallet::getter(fn era_exchange_rates)]
+	pub type EraExchangeRates<T: Config> = StorageMap<_, Twox64Concat, EraIndex, ExchangeRate, OptionQuery>;
+
 	#[pallet::genesis_config]
 	#[derive(Default)]
 	pub struct GenesisConfig {
@@ -311,10 +591,11 @@ pub mod module {
 	#[pallet::hooks]
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
 		fn on_initialize(_: T::BlockNumber) -> Weight {
+			BlockVolume::<T>::kill();
 			Self::rebalance();
 
 			// TODO: return different weight according rebalance phase.
-			0
+			Self::issue_next_era_mints().saturating_add(Self::process_request_queue())
 		}
 	}
 
@@ -359,6 +640,86 @@ pub mod module {
 			})?;
 			Ok(().into())
 		}
+
+		/// Update the fee rates for minting liquid currency.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight((10_000, DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_mint_fee_rates(
+			origin: OriginFor<T>,
+			immediately: ChangeRate,
+			next_era: ChangeRate,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			StakingPoolMintFeeRates::<T>::try_mutate(|rates| -> DispatchResult {
+				if let Change::NewValue(update) = immediately {
+					rates.immediately = update;
+				}
+				if let Change::NewValue(update) = next_era {
+					rates.next_era = update;
+				}
+
+				ensure!(
+					rates.immediately <= Rate::one() && rates.next_era <= rates.immediately,
+					Error::<T>::InvalidConfig
+				);
+				Ok(())
+			})?;
+			Ok(().into())
+		}
+
+		/// Update the cash buffer policy, `None` to decide the bond and unbond
+		/// amounts by the staking pool params.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight((10_000, DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_cash_buffer_policy(
+			origin: OriginFor<T>,
+			policy: Option<CashBufferPolicy>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				policy.as_ref().map_or(true, |policy| policy.is_valid()),
+				Error::<T>::InvalidConfig
+			);
+			StakingPoolCashBufferPolicy::<T>::set(policy.clone());
+			Self::deposit_event(Event::CashBufferPolicyUpdated(policy));
+			Ok(().into())
+		}
+
+		/// Update the volume limits of mint and redeem.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight((10_000, DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_volume_limits(origin: OriginFor<T>, limits: VolumeLimits) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			HomaVolumeLimits::<T>::put(limits.clone());
+			Self::deposit_event(Event::VolumeLimitsUpdated(limits));
+			Ok(().into())
+		}
+
+		/// Deposit staking currency to the insurance reserve, which will be
+		/// used to compensate liquid currency holders when slash happened and
+		/// guarantee deposits are not enough.
+		///
+		/// - `amount`: the staking currency amount to deposit.
+		#[pallet::weight(10_000)]
+		#[transactional]
+		pub fn deposit_insurance_reserve(
+			origin: OriginFor<T>,
+			#[pallet::compact] amount: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			if !amount.is_zero() {
+				T::Currency::transfer(T::StakingCurrencyId::get(), &who, &Self::account_id(), amount)?;
+				InsuranceReserve::<T>::mutate(|reserve| *reserve = reserve.saturating_add(amount));
+				Self::deposit_event(Event::DepositInsuranceReserve(who, amount));
+			}
+			Ok(().into())
+		}
 	}
 }
 
@@ -506,11 +867,52 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
-	/// Require bridge to get staking rewards on relaychain.
+	/// Require bridge to get staking rewards on relaychain, and bond the
+	/// claimed rewards to compound.
 	pub fn payout_stakers(era: EraIndex) {
+		let mut total_compounded: Balance = Zero::zero();
 		for sub_account_index in T::PoolAccountIndexes::get() {
-			T::Bridge::payout_stakers(sub_account_index, era);
+			let rewards = T::Bridge::payout_stakers(sub_account_index, era);
+
+			// if failed to bond, the rewards remain free on the sub account and will be
+			// withdrawn to the free pool.
+			if !rewards.is_zero() && T::Bridge::bond_extra(sub_account_index, rewards).is_ok() {
+				total_compounded = total_compounded.saturating_add(rewards);
+				Self::deposit_event(Event::RewardsCompounded(sub_account_index, era, rewards));
+			}
 		}
+
+		Self::share_rewards_with_guarantors(total_compounded);
+	}
+
+	/// Issue liquid currency for `GuarantorRewardRatio` of the compounded
+	/// staking rewards, and share it equally by the guarantors of the nominees.
+	fn share_rewards_with_guarantors(rewards: Balance) {
+		let nominees = T::Nominees::nominees();
+		let share = T::GuarantorRewardRatio::get().saturating_mul_int(rewards);
+		if share.is_zero() || nominees.is_empty() {
+			return;
+		}
+
+		let liquid_share_per_nominee = Self::liquid_exchange_rate()
+			.reciprocal()
+			.unwrap_or_default()
+			.saturating_mul_int(share)
+			/ nominees.len() as Balance;
+		if liquid_share_per_nominee.is_zero() {
+			return;
+		}
+
+		for validator in nominees {
+			// if failed, e.g. the validator has no guarantee, the rewards remain in the pool.
+			let _ = Self::issue_guarantor_rewards(&validator, liquid_share_per_nominee);
+		}
+	}
+
+	#[transactional]
+	fn issue_guarantor_rewards(validator: &PolkadotAccountIdOf<T>, amount: Balance) -> DispatchResult {
+		T::Currency::deposit(T::LiquidCurrencyId::get(), &Self::account_id(), amount)?;
+		T::ValidatorRewards::distribute_reward(&Self::account_id(), validator, amount)
 	}
 
 	/// Require bridge to nominate validators of relaychain.
@@ -597,6 +999,144 @@ impl<T: Config> Pallet<T> {
 			})
 	}
 
+	/// Get the unbondings of `who` which are claimable at the expired era,
+	/// sorted by era in ascending order.
+	pub fn get_unbondings(who: &T::AccountId) -> Vec<(EraIndex, Balance)> {
+		let mut unbondings = Unbondings::<T>::iter_prefix(who).collect::<Vec<_>>();
+		unbondings.sort_by(|a, b| a.0.cmp(&b.0));
+		unbondings
+	}
+
+	/// Get the total unbonding amount of staking pool which will be expired at
+	/// the era, sorted by era in ascending order.
+	pub fn get_pending_unbonding() -> Vec<(EraIndex, Balance)> {
+		let mut pending_unbonding = Unbonding::<T>::iter()
+			.map(|(era_index, (total_unbonding, _, _))| (era_index, total_unbonding))
+			.collect::<Vec<_>>();
+		pending_unbonding.sort_by(|a, b| a.0.cmp(&b.0));
+		pending_unbonding
+	}
+
+	/// Get the recent snapshots of the liquid exchange rate, sorted by era in
+	/// ascending order.
+	pub fn get_exchange_rate_history() -> Vec<(EraIndex, ExchangeRate)> {
+		let mut history = EraExchangeRates::<T>::iter().collect::<Vec<_>>();
+		history.sort_by(|a, b| a.0.cmp(&b.0));
+		history
+	}
+
+	/// Issue liquid currency to `who` for `staking_amount` at `exchange_rate`,
+	/// the `fee_rate` of `staking_amount` will be kept in staking pool as fee.
+	fn issue_liquid(
+		who: &T::AccountId,
+		staking_amount: Balance,
+		fee_rate: Rate,
+		exchange_rate: ExchangeRate,
+	) -> sp_std::result::Result<Balance, DispatchError> {
+		let fee = fee_rate.saturating_mul_int(staking_amount);
+		let liquid_amount_to_issue = exchange_rate
+			.reciprocal()
+			.unwrap_or_default()
+			.checked_mul_int(staking_amount.saturating_sub(fee))
+			.ok_or(ArithmeticError::Overflow)?;
+
+		T::Currency::deposit(T::LiquidCurrencyId::get(), who, liquid_amount_to_issue)?;
+		Self::deposit_event(Event::MintLiquid(who.clone(), staking_amount, liquid_amount_to_issue));
+		Ok(liquid_amount_to_issue)
+	}
+
+	/// Bond all the staking currency requested by `mint_at_next_era` in one
+	/// batch, the liquid currency is issued to the requesters by
+	/// `issue_next_era_mints` from this block.
+	fn process_next_era_mints() {
+		let total_next_era_mint = Self::total_next_era_mint();
+		// the mints of last era are still being issued, bond at next era.
+		if total_next_era_mint.is_zero() || Self::next_era_mints_issuance().is_some() {
+			return;
+		}
+
+		// issue liquid currency at the exchange rate before bonding.
+		let exchange_rate = Self::liquid_exchange_rate();
+
+		// only issue liquid currency after the staking currency has been bonded,
+		// if failed, the requests remain and will try on next era beginning.
+		if Self::transfer_and_bond_extra(total_next_era_mint).is_err() {
+			return;
+		}
+		StakingPoolLedger::<T>::mutate(|ledger| {
+			ledger.bonded = ledger.bonded.saturating_add(total_next_era_mint);
+		});
+
+		NextEraMintsIssuance::<T>::put((exchange_rate, Self::mint_fee_rates().next_era));
+		TotalNextEraMint::<T>::kill();
+	}
+
+	/// Issue liquid currency for at most `MaxNextEraMintsPerBlock` bonded next
+	/// era mints. If the issuance fails, the staking amount is refunded from
+	/// the free pool. Return the weight consumed.
+	fn issue_next_era_mints() -> Weight {
+		let db_weight = T::DbWeight::get();
+		let (exchange_rate, fee_rate) = match Self::next_era_mints_issuance() {
+			Some(issuance) => issuance,
+			None => return db_weight.reads(1),
+		};
+
+		let max = T::MaxNextEraMintsPerBlock::get();
+		let mut count: u32 = 0;
+		for (who, staking_amount) in NextEraMints::<T>::drain().take(max as usize) {
+			count += 1;
+			if let Err(e) = Self::issue_liquid(&who, staking_amount, fee_rate, exchange_rate) {
+				// the staking currency is already bonded, return the same amount from
+				// the free pool.
+				let refunded = Self::refund_from_free_pool(&who, staking_amount).is_ok();
+				log::warn!(
+					target: "staking-pool",
+					"issue_next_era_mints: failed to issue liquid currency to {:?} for {:?}: {:?}, refunded: {:?}",
+					who, staking_amount, e, refunded
+				);
+				Self::deposit_event(Event::NextEraMintFailed(who, staking_amount, refunded));
+			}
+		}
+
+		if NextEraMints::<T>::iter().next().is_none() {
+			NextEraMintsIssuance::<T>::kill();
+		}
+
+		let mint_weight = NEXT_ERA_MINT_WEIGHT.saturating_add(db_weight.reads_writes(3, 3));
+		db_weight
+			.reads_writes(2, 1)
+			.saturating_add(mint_weight.saturating_mul(count as Weight))
+	}
+
+	/// Return `amount` of staking currency from the free pool to `who`.
+	#[transactional]
+	fn refund_from_free_pool(who: &T::AccountId, amount: Balance) -> DispatchResult {
+		StakingPoolLedger::<T>::try_mutate(|ledger| -> DispatchResult {
+			ledger.free_pool = ledger.free_pool.checked_sub(amount).ok_or(ArithmeticError::Underflow)?;
+			T::Currency::transfer(T::StakingCurrencyId::get(), &Self::account_id(), who, amount)
+		})
+	}
+
+	/// Transfer staking currency of the pool to relaychain and bond it, revert
+	/// all if either of them failed.
+	#[transactional]
+	fn transfer_and_bond_extra(amount: Balance) -> DispatchResult {
+		Self::transfer_to_bridge(&Self::account_id(), amount)?;
+		Self::bond_extra(amount)
+	}
+
+	/// Record the liquid exchange rate of current era and remove the expired
+	/// snapshot.
+	fn snapshot_exchange_rate() {
+		let current_era = Self::current_era();
+		EraExchangeRates::<T>::insert(current_era, Self::liquid_exchange_rate());
+
+		let max_history = T::MaxExchangeRateHistory::get();
+		if current_era >= max_history {
+			EraExchangeRates::<T>::remove(current_era - max_history);
+		}
+	}
+
 	pub fn rebalance() {
 		match Self::rebalance_phase() {
 			Phase::Started => {
@@ -649,22 +1189,9 @@ impl<T: Config> Pallet<T> {
 
 			Phase::LedgerUpdated => {
 				StakingPoolLedger::<T>::mutate(|ledger| {
-					let staking_pool_params = Self::staking_pool_params();
 					let (mut total_unbond, claimed_unbond) = ledger.to_unbond_next_era;
 
-					let bond_rate = ledger
-						.free_pool_ratio()
-						.saturating_sub(staking_pool_params.target_max_free_unbonded_ratio);
-					let amount_to_bond = bond_rate
-						.saturating_mul_int(ledger.total_belong_to_liquid_holders())
-						.min(ledger.free_pool);
-					let unbond_to_free_rate = staking_pool_params
-						.target_unbonding_to_free_ratio
-						.saturating_sub(ledger.unbonding_to_free_ratio())
-						.min(staking_pool_params.unbonding_to_free_adjustment);
-					let amount_to_unbond_to_free = unbond_to_free_rate
-						.saturating_mul_int(ledger.total_belong_to_liquid_holders())
-						.min(ledger.bonded_belong_to_liquid_holders());
+					let (amount_to_bond, amount_to_unbond_to_free) = Self::rebalance_amounts(ledger);
 					total_unbond = total_unbond.saturating_add(amount_to_unbond_to_free);
 
 					if !amount_to_bond.is_zero() {
@@ -700,6 +1227,8 @@ impl<T: Config> Pallet<T> {
 					}
 				});
 
+				Self::process_next_era_mints();
+				Self::snapshot_exchange_rate();
 				RebalancePhase::<T>::put(Phase::Finished);
 			}
 
@@ -708,20 +1237,123 @@ impl<T: Config> Pallet<T> {
 	}
 }
 
+impl<T: Config> Pallet<T> {
+	/// The amount to bond from free pool and the amount to unbond to free pool
+	/// at rebalance, decided by the cash buffer policy if it's set, otherwise
+	/// by the staking pool params.
+	fn rebalance_amounts(ledger: &Ledger) -> (Balance, Balance) {
+		let (bond_rate, unbond_to_free_rate) = match Self::cash_buffer_policy() {
+			Some(policy) => {
+				let ratio = ledger.cash_buffer_ratio();
+				if ratio > policy.max_ratio {
+					(
+						ratio
+							.saturating_sub(policy.target_ratio)
+							.min(policy.max_adjustment_per_era),
+						Zero::zero(),
+					)
+				} else if ratio < policy.min_ratio {
+					(
+						Zero::zero(),
+						policy
+							.target_ratio
+							.saturating_sub(ratio)
+							.min(policy.max_adjustment_per_era),
+					)
+				} else {
+					(Zero::zero(), Zero::zero())
+				}
+			}
+			None => {
+				let staking_pool_params = Self::staking_pool_params();
+				(
+					ledger
+						.free_pool_ratio()
+						.saturating_sub(staking_pool_params.target_max_free_unbonded_ratio),
+					staking_pool_params
+						.target_unbonding_to_free_ratio
+						.saturating_sub(ledger.unbonding_to_free_ratio())
+						.min(staking_pool_params.unbonding_to_free_adjustment),
+				)
+			}
+		};
+
+		let total = ledger.total_belong_to_liquid_holders();
+		(
+			bond_rate.saturating_mul_int(total).min(ledger.free_pool),
+			unbond_to_free_rate
+				.saturating_mul_int(total)
+				.min(ledger.bonded_belong_to_liquid_holders()),
+		)
+	}
+}
+
 impl<T: Config> OnNewEra<EraIndex> for Pallet<T> {
 	fn on_new_era(new_era: EraIndex) {
 		CurrentEra::<T>::put(new_era);
 		RebalancePhase::<T>::put(Phase::Started);
+		EraVolume::<T>::kill();
 	}
 }
 
-impl<T: Config> HomaProtocol<T::AccountId, Balance, EraIndex> for Pallet<T> {
-	type Balance = Balance;
+impl<T: Config> Pallet<T> {
+	/// The currency of assets carried by the request.
+	fn request_currency_id(request: &HomaRequest) -> CurrencyId {
+		if request.is_mint() {
+			T::StakingCurrencyId::get()
+		} else {
+			T::LiquidCurrencyId::get()
+		}
+	}
 
-	#[transactional]
-	fn mint(who: &T::AccountId, amount: Self::Balance) -> sp_std::result::Result<Self::Balance, DispatchError> {
+	/// The volume limits per block and per era of the request.
+	fn request_volume_limits(request: &HomaRequest) -> (Option<Balance>, Option<Balance>) {
+		let limits = Self::volume_limits();
+		if request.is_mint() {
+			(limits.mint_per_block, limits.mint_per_era)
+		} else {
+			(limits.redeem_per_block, limits.redeem_per_era)
+		}
+	}
+
+	/// Consume the volume of block and era if the request is within the
+	/// remaining volume limits.
+	fn try_consume_volume(request: &HomaRequest) -> bool {
+		let (per_block, per_era) = Self::request_volume_limits(request);
+		let mut block_volume = Self::block_volume();
+		let mut era_volume = Self::era_volume();
+		let (block_used, era_used) = if request.is_mint() {
+			(&mut block_volume.mint, &mut era_volume.mint)
+		} else {
+			(&mut block_volume.redeem, &mut era_volume.redeem)
+		};
+		let amount = request.amount();
+
+		if per_block.map_or(false, |limit| block_used.saturating_add(amount) > limit)
+			|| per_era.map_or(false, |limit| era_used.saturating_add(amount) > limit)
+		{
+			return false;
+		}
+
+		*block_used = block_used.saturating_add(amount);
+		*era_used = era_used.saturating_add(amount);
+		BlockVolume::<T>::put(block_volume);
+		EraVolume::<T>::put(era_volume);
+		true
+	}
+
+	/// Queue the request and keep its assets in staking pool if it exceeds the
+	/// remaining volume limits, return whether the request was queued.
+	///
+	/// Requests are executed in order, so the request is also queued if there
+	/// are already queued requests ahead.
+	fn queue_if_exceed_volume_limits(
+		who: &T::AccountId,
+		request: HomaRequest,
+	) -> sp_std::result::Result<bool, DispatchError> {
+		let amount = request.amount();
 		if amount.is_zero() {
-			return Ok(Zero::zero());
+			return Ok(false);
 		}
 
 		ensure!(
@@ -729,25 +1361,115 @@ impl<T: Config> HomaProtocol<T::AccountId, Balance, EraIndex> for Pallet<T> {
 			Error::<T>::RebalanceUnfinished
 		);
 
-		StakingPoolLedger::<T>::try_mutate(|ledger| -> sp_std::result::Result<Self::Balance, DispatchError> {
-			let liquid_amount_to_issue = Self::liquid_exchange_rate()
-				.reciprocal()
-				.unwrap_or_default()
-				.checked_mul_int(amount)
-				.ok_or(ArithmeticError::Overflow)?;
+		Self::ensure_within_volume_limits(&request)?;
 
-			T::Currency::transfer(T::StakingCurrencyId::get(), who, &Self::account_id(), amount)?;
-			T::Currency::deposit(T::LiquidCurrencyId::get(), who, liquid_amount_to_issue)?;
+		let (head, tail) = Self::request_queue_range();
+		if head == tail && Self::try_consume_volume(&request) {
+			return Ok(false);
+		}
+
+		T::Currency::transfer(Self::request_currency_id(&request), who, &Self::account_id(), amount)?;
+		Self::push_request(who, request);
+		Ok(true)
+	}
 
+	/// Ensure the request doesn't exceed the volume limits, otherwise it can
+	/// never be executed.
+	fn ensure_within_volume_limits(request: &HomaRequest) -> DispatchResult {
+		let amount = request.amount();
+		let (per_block, per_era) = Self::request_volume_limits(request);
+		ensure!(
+			per_block.map_or(true, |limit| amount <= limit) && per_era.map_or(true, |limit| amount <= limit),
+			Error::<T>::ExceedVolumeLimit
+		);
+		Ok(())
+	}
+
+	/// Push the request whose assets are already kept by staking pool to the
+	/// tail of queue.
+	fn push_request(who: &T::AccountId, request: HomaRequest) {
+		let (head, tail) = Self::request_queue_range();
+		RequestQueue::<T>::insert(tail, (who.clone(), request.clone()));
+		RequestQueueRange::<T>::put((head, tail.saturating_add(1)));
+		Self::deposit_event(Event::RequestQueued(tail, who.clone(), request));
+	}
+
+	/// Execute the queued requests in order within the remaining volume
+	/// limits.
+	/// Execute the queued requests in order until the volume limits are
+	/// reached, at most `MaxQueuedRequestsPerBlock` requests are processed.
+	/// Return the weight consumed.
+	fn process_request_queue() -> Weight {
+		let db_weight = T::DbWeight::get();
+		if Self::rebalance_phase() != Phase::Finished {
+			return db_weight.reads(1);
+		}
+
+		let (mut head, tail) = Self::request_queue_range();
+		let end = tail.min(head.saturating_add(T::MaxQueuedRequestsPerBlock::get()));
+		let mut weight = db_weight.reads_writes(2, 1);
+		while head < end {
+			weight = weight.saturating_add(db_weight.reads(1));
+			if let Some((who, request)) = Self::request_queue(head) {
+				if !Self::try_consume_volume(&request) {
+					break;
+				}
+				weight = weight.saturating_add(QUEUED_REQUEST_WEIGHT.saturating_add(db_weight.reads_writes(10, 10)));
+
+				// return the assets to the requester, and execute the request as it's submitted
+				// by the requester now.
+				let succeeded = T::Currency::transfer(
+					Self::request_currency_id(&request),
+					&Self::account_id(),
+					&who,
+					request.amount(),
+				)
+				.and_then(|_| match request {
+					HomaRequest::Mint(amount) => Self::do_mint(&who, amount).map(|_| ()),
+					HomaRequest::RedeemByUnbond(amount) => Self::do_redeem_by_unbond(&who, amount),
+					HomaRequest::RedeemByFreeUnbonded(amount) => Self::do_redeem_by_free_unbonded(&who, amount),
+					HomaRequest::RedeemByClaimUnbonding(amount, target_era) => {
+						Self::do_redeem_by_claim_unbonding(&who, amount, target_era)
+					}
+				})
+				.is_ok();
+
+				RequestQueue::<T>::remove(head);
+				Self::deposit_event(Event::QueuedRequestExecuted(who, request, succeeded));
+			}
+			head += 1;
+		}
+		RequestQueueRange::<T>::put((head, tail));
+		weight
+	}
+
+	#[transactional]
+	fn do_mint(who: &T::AccountId, amount: Balance) -> sp_std::result::Result<Balance, DispatchError> {
+		if amount.is_zero() {
+			return Ok(Zero::zero());
+		}
+
+		ensure!(
+			Self::rebalance_phase() == Phase::Finished,
+			Error::<T>::RebalanceUnfinished
+		);
+
+		let liquid_amount_to_issue = Self::issue_liquid(
+			who,
+			amount,
+			Self::mint_fee_rates().immediately,
+			Self::liquid_exchange_rate(),
+		)?;
+		T::Currency::transfer(T::StakingCurrencyId::get(), who, &Self::account_id(), amount)?;
+		StakingPoolLedger::<T>::mutate(|ledger| {
 			ledger.free_pool = ledger.free_pool.saturating_add(amount);
+		});
 
-			Self::deposit_event(Event::MintLiquid(who.clone(), amount, liquid_amount_to_issue));
-			Ok(liquid_amount_to_issue)
-		})
+		Ok(liquid_amount_to_issue)
 	}
 
 	#[transactional]
-	fn redeem_by_unbond(who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+	fn do_redeem_by_unbond(who: &T::AccountId, amount: Balance) -> DispatchResult {
 		if amount.is_zero() {
 			return Ok(());
 		}
@@ -800,7 +1522,7 @@ impl<T: Config> HomaProtocol<T::AccountId, Balance, EraIndex> for Pallet<T> {
 	}
 
 	#[transactional]
-	fn redeem_by_free_unbonded(who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+	fn do_redeem_by_free_unbonded(who: &T::AccountId, amount: Balance) -> DispatchResult {
 		if amount.is_zero() {
 			return Ok(());
 		}
@@ -876,7 +1598,7 @@ impl<T: Config> HomaProtocol<T::AccountId, Balance, EraIndex> for Pallet<T> {
 	}
 
 	#[transactional]
-	fn redeem_by_claim_unbonding(who: &T::AccountId, amount: Self::Balance, target_era: EraIndex) -> DispatchResult {
+	fn do_redeem_by_claim_unbonding(who: &T::AccountId, amount: Balance, target_era: EraIndex) -> DispatchResult {
 		if amount.is_zero() {
 			return Ok(());
 		}
@@ -959,6 +1681,72 @@ impl<T: Config> HomaProtocol<T::AccountId, Balance, EraIndex> for Pallet<T> {
 			Ok(())
 		})
 	}
+}
+
+impl<T: Config> HomaProtocol<T::AccountId, Balance, EraIndex> for Pallet<T> {
+	type Balance = Balance;
+
+	#[transactional]
+	fn mint(who: &T::AccountId, amount: Self::Balance) -> sp_std::result::Result<Self::Balance, DispatchError> {
+		if Self::queue_if_exceed_volume_limits(who, HomaRequest::Mint(amount))? {
+			return Ok(Zero::zero());
+		}
+		Self::do_mint(who, amount)
+	}
+
+	#[transactional]
+	fn mint_at_next_era(who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(());
+		}
+
+		ensure!(
+			Self::rebalance_phase() == Phase::Finished,
+			Error::<T>::RebalanceUnfinished
+		);
+		ensure!(
+			Self::next_era_mints_issuance().is_none(),
+			Error::<T>::NextEraMintsIssuing
+		);
+		ensure!(amount >= T::MinNextEraMintAmount::get(), Error::<T>::BelowMinimumAmount);
+
+		// the mint at next era is not queued, it must be within the remaining
+		// volume limits.
+		let request = HomaRequest::Mint(amount);
+		Self::ensure_within_volume_limits(&request)?;
+		ensure!(Self::try_consume_volume(&request), Error::<T>::ExceedVolumeLimit);
+
+		T::Currency::transfer(T::StakingCurrencyId::get(), who, &Self::account_id(), amount)?;
+		NextEraMints::<T>::mutate(who, |mint| *mint = mint.saturating_add(amount));
+		TotalNextEraMint::<T>::mutate(|total| *total = total.saturating_add(amount));
+
+		Self::deposit_event(Event::MintLiquidAtNextEra(who.clone(), amount));
+		Ok(())
+	}
+
+	#[transactional]
+	fn redeem_by_unbond(who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+		if Self::queue_if_exceed_volume_limits(who, HomaRequest::RedeemByUnbond(amount))? {
+			return Ok(());
+		}
+		Self::do_redeem_by_unbond(who, amount)
+	}
+
+	#[transactional]
+	fn redeem_by_free_unbonded(who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+		if Self::queue_if_exceed_volume_limits(who, HomaRequest::RedeemByFreeUnbonded(amount))? {
+			return Ok(());
+		}
+		Self::do_redeem_by_free_unbonded(who, amount)
+	}
+
+	#[transactional]
+	fn redeem_by_claim_unbonding(who: &T::AccountId, amount: Self::Balance, target_era: EraIndex) -> DispatchResult {
+		if Self::queue_if_exceed_volume_limits(who, HomaRequest::RedeemByClaimUnbonding(amount, target_era))? {
+			return Ok(());
+		}
+		Self::do_redeem_by_claim_unbonding(who, amount, target_era)
+	}
 
 	#[transactional]
 	fn withdraw_redemption(who: &T::AccountId) -> sp_std::result::Result<Self::Balance, DispatchError> {
@@ -974,12 +1762,134 @@ impl<T: Config> HomaProtocol<T::AccountId, Balance, EraIndex> for Pallet<T> {
 		T::Currency::transfer(T::StakingCurrencyId::get(), &Self::account_id(), who, withdrawn_amount)?;
 		Ok(withdrawn_amount)
 	}
+
+	fn take_unbonding(who: &T::AccountId, target_era: EraIndex) -> Self::Balance {
+		Unbondings::<T>::take(who, target_era)
+	}
+
+	fn add_unbonding(who: &T::AccountId, target_era: EraIndex, amount: Self::Balance) {
+		if !amount.is_zero() {
+			Unbondings::<T>::mutate(who, target_era, |unbonding| {
+				*unbonding = unbonding.saturating_add(amount);
+			});
+		}
+	}
+
+	/// Reducing or cancelling the queued request keeps its position in queue,
+	/// but topping up moves it to the tail of queue, so that the requests
+	/// ahead are not delayed.
+	#[transactional]
+	fn update_queued_request(who: &T::AccountId, index: u32, new_amount: Self::Balance) -> DispatchResult {
+		let (owner, request) = Self::request_queue(index).ok_or(Error::<T>::RequestNotFound)?;
+		ensure!(owner == *who, Error::<T>::RequestNotFound);
+
+		let amount = request.amount();
+		let currency_id = Self::request_currency_id(&request);
+		let new_request = request.with_amount(new_amount);
+
+		if new_amount < amount {
+			T::Currency::transfer(currency_id, &Self::account_id(), who, amount.saturating_sub(new_amount))?;
+
+			if new_amount.is_zero() {
+				RequestQueue::<T>::remove(index);
+				Self::deposit_event(Event::QueuedRequestCancelled(index, who.clone()));
+			} else {
+				RequestQueue::<T>::insert(index, (who.clone(), new_request.clone()));
+				Self::deposit_event(Event::QueuedRequestReduced(index, who.clone(), new_request));
+			}
+		} else if new_amount > amount {
+			Self::ensure_within_volume_limits(&new_request)?;
+			T::Currency::transfer(currency_id, who, &Self::account_id(), new_amount.saturating_sub(amount))?;
+			RequestQueue::<T>::remove(index);
+			Self::push_request(who, new_request);
+		}
+
+		Ok(())
+	}
+
+	#[transactional]
+	fn cancel_redeem_by_unbond(who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+		ensure!(
+			Self::rebalance_phase() == Phase::Finished,
+			Error::<T>::RebalanceUnfinished
+		);
+
+		let amount = amount.min(Self::next_era_unbonds(who));
+		if amount.is_zero() {
+			return Ok(());
+		}
+
+		// issue liquid currency before updating the ledger, the exchange rate keeps the
+		// same.
+		let liquid_exchange_rate = Self::liquid_exchange_rate();
+		let liquid_amount_to_issue = liquid_exchange_rate
+			.reciprocal()
+			.unwrap_or_default()
+			.checked_mul_int(amount)
+			.ok_or(ArithmeticError::Overflow)?;
+		T::Currency::deposit(T::LiquidCurrencyId::get(), who, liquid_amount_to_issue)?;
+
+		NextEraUnbonds::<T>::mutate(who, |unbond| {
+			*unbond = unbond.saturating_sub(amount);
+		});
+		StakingPoolLedger::<T>::mutate(|ledger| {
+			let (total_unbond, claimed_unbond) = ledger.to_unbond_next_era;
+			ledger.to_unbond_next_era = (
+				total_unbond.saturating_sub(amount),
+				claimed_unbond.saturating_sub(amount),
+			);
+		});
+
+		Self::deposit_event(Event::RedeemByUnbondCancelled(
+			who.clone(),
+			amount,
+			liquid_amount_to_issue,
+		));
+		Ok(())
+	}
 }
 
+/// Nominate the adjusted nominees on relaychain immediately.
+pub struct OnNomineesAdjusted<T>(sp_std::marker::PhantomData<T>);
+impl<T: Config> Happened<Vec<PolkadotAccountIdOf<T>>> for OnNomineesAdjusted<T> {
+	fn happened(nominees: &Vec<PolkadotAccountIdOf<T>>) {
+		Pallet::<T>::nominate(nominees.clone());
+	}
+}
+
+/// Compensate the slash on relaychain by waterfall: the guarantee deposits
+/// (already burned by homa_validator_list) first, then the insurance reserve,
+/// and the remains will reduce the exchange rate of liquid currency.
+///
+/// The slashed amount is deducted from `bonded` of the ledger immediately,
+/// the ledger will be re-synced with relaychain in the next rebalance.
 pub struct OnSlash<T>(sp_std::marker::PhantomData<T>);
-impl<T: Config> Happened<Balance> for OnSlash<T> {
-	fn happened(_amount: &Balance) {
-		// TODO: should reduce debit when homa_validator_list module burn
-		// insurance to compensate LDOT holders.
+impl<T: Config> Happened<(Balance, Balance)> for OnSlash<T> {
+	fn happened(info: &(Balance, Balance)) {
+		let (slashed, compensated_by_guarantee) = *info;
+		if slashed.is_zero() {
+			return;
+		}
+
+		let compensated_by_guarantee = compensated_by_guarantee.min(slashed);
+		let remains = slashed.saturating_sub(compensated_by_guarantee);
+		let compensated_by_insurance = InsuranceReserve::<T>::mutate(|reserve| {
+			let compensation = remains.min(*reserve);
+			*reserve = reserve.saturating_sub(compensation);
+			compensation
+		});
+		let uncompensated_loss = remains.saturating_sub(compensated_by_insurance);
+
+		StakingPoolLedger::<T>::mutate(|ledger| {
+			ledger.bonded = ledger.bonded.saturating_sub(slashed);
+			ledger.free_pool = ledger.free_pool.saturating_add(compensated_by_insurance);
+		});
+
+		Pallet::<T>::deposit_event(Event::SlashCompensated(
+			slashed,
+			compensated_by_guarantee,
+			compensated_by_insurance,
+			uncompensated_loss,
+		));
 	}
 }
@@ -131,6 +131,30 @@ impl NomineesProvider<PolkadotAccountId> for MockNomineesProvider {
 	}
 }
 
+thread_local! {
+	pub static GUARANTOR_REWARD_RATIO: RefCell<Ratio> = RefCell::new(Ratio::zero());
+	pub static VALIDATOR_REWARDS: RefCell<Vec<(PolkadotAccountId, Balance)>> = RefCell::new(vec![]);
+}
+
+pub struct GuarantorRewardRatio;
+impl Get<Ratio> for GuarantorRewardRatio {
+	fn get() -> Ratio {
+		GUARANTOR_REWARD_RATIO.with(|v| *v.borrow())
+	}
+}
+
+/// The validator 3 has no guarantee.
+pub struct MockValidatorRewards;
+impl ValidatorRewardsDistributor<AccountId, PolkadotAccountId, Balance> for MockValidatorRewards {
+	fn distribute_reward(_payer: &AccountId, validator: &PolkadotAccountId, amount: Balance) -> DispatchResult {
+		if *validator == 3 {
+			return Err(DispatchError::Other("no guarantee"));
+		}
+		VALIDATOR_REWARDS.with(|v| v.borrow_mut().push((*validator, amount)));
+		Ok(())
+	}
+}
+
 parameter_types! {
 	pub const BondingDuration: EraIndex = 4;
 	pub const EraLength: BlockNumber = 10;
@@ -226,19 +250,20 @@ impl PolkadotBridgeCall<AccountId, BlockNumber, Balance, EraIndex> for MockBridg
 
 	fn nominate(_account_index: u32, _targets: Vec<Self::PolkadotAccountId>) {}
 
-	fn payout_stakers(account_index: u32, _era: EraIndex) {
+	fn payout_stakers(account_index: u32, _era: EraIndex) -> Balance {
 		BRIDGE_STATUS.with(|v| {
 			let mut old_map = v.borrow().clone();
+			let mut reward = 0;
 			if let Some(status) = old_map.get_mut(&account_index) {
-				status.bonded = status
-					.bonded
-					.saturating_add(Rate::saturating_from_rational(1, 100).saturating_mul_int(status.bonded));
+				reward = Rate::saturating_from_rational(1, 100).saturating_mul_int(status.bonded);
+				status.free = status.free.saturating_add(reward);
 			} else {
 				old_map.insert(account_index, Default::default());
 			}
 
 			*v.borrow_mut() = old_map;
-		});
+			reward
+		})
 	}
 
 	fn transfer_to_bridge(account_index: u32, from: &AccountId, amount: Balance) -> DispatchResult {
@@ -345,6 +370,10 @@ parameter_types! {
 	pub DefaultExchangeRate: ExchangeRate = ExchangeRate::saturating_from_rational(10, 100);	// 1 : 10
 	pub const StakingPoolPalletId: PalletId = PalletId(*b"aca/stkp");
 	pub PoolAccountIndexes: Vec<u32> = vec![1, 2, 3, 4];
+	pub const MaxExchangeRateHistory: EraIndex = 3;
+	pub const MaxQueuedRequestsPerBlock: u32 = 3;
+	pub const MaxNextEraMintsPerBlock: u32 = 2;
+	pub const MinNextEraMintAmount: Balance = 10;
 }
 
 ord_parameter_types! {
@@ -363,6 +392,12 @@ impl Config for Runtime {
 	type Nominees = MockNomineesProvider;
 	type Bridge = MockBridge;
 	type Currency = CurrenciesModule;
+	type MaxExchangeRateHistory = MaxExchangeRateHistory;
+	type MaxQueuedRequestsPerBlock = MaxQueuedRequestsPerBlock;
+	type MaxNextEraMintsPerBlock = MaxNextEraMintsPerBlock;
+	type MinNextEraMintAmount = MinNextEraMintAmount;
+	type GuarantorRewardRatio = GuarantorRewardRatio;
+	type ValidatorRewards = MockValidatorRewards;
 }
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
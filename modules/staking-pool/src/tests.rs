@@ -546,9 +546,46 @@ fn withdraw_unbonded_work() {
 	});
 }
 
+#[test]
+fn share_rewards_with_guarantors_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		BRIDGE_STATUS.with(|v| {
+			let mut old_map = v.borrow().clone();
+			old_map.insert(
+				1,
+				Status {
+					bonded: 30000,
+					free: 0,
+					unlocking: vec![],
+				},
+			);
+			*v.borrow_mut() = old_map;
+		});
+
+		// no rewards are shared by default
+		StakingPoolModule::payout_stakers(0);
+		assert_eq!(VALIDATOR_REWARDS.with(|v| v.borrow().clone()), vec![]);
+
+		// 10% of the compounded 303 DOT rewards, at the default exchange rate 1:10
+		GUARANTOR_REWARD_RATIO.with(|v| *v.borrow_mut() = Ratio::saturating_from_rational(10, 100));
+		StakingPoolModule::payout_stakers(1);
+		System::assert_has_event(Event::StakingPoolModule(crate::Event::RewardsCompounded(1, 1, 303)));
+		assert_eq!(VALIDATOR_REWARDS.with(|v| v.borrow().clone()), vec![(1, 100), (2, 100)]);
+
+		// the rewards of validator without guarantee are not issued
+		assert_eq!(
+			CurrenciesModule::free_balance(LDOT, &StakingPoolModule::account_id()),
+			200
+		);
+		assert_eq!(CurrenciesModule::total_issuance(LDOT), 200);
+	});
+}
+
 #[test]
 fn payout_stakers_work() {
 	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
 		BRIDGE_STATUS.with(|v| {
 			let mut old_map = v.borrow().clone();
 			old_map.insert(
@@ -587,6 +624,9 @@ fn payout_stakers_work() {
 		});
 
 		StakingPoolModule::payout_stakers(0);
+		System::assert_has_event(Event::StakingPoolModule(crate::Event::RewardsCompounded(1, 0, 3)));
+		System::assert_has_event(Event::StakingPoolModule(crate::Event::RewardsCompounded(2, 0, 1)));
+		System::assert_has_event(Event::StakingPoolModule(crate::Event::RewardsCompounded(3, 0, 2)));
 		assert_eq!(
 			*BRIDGE_STATUS
 				.with(|v| v.borrow().clone())
@@ -753,6 +793,78 @@ fn set_staking_pool_params_work() {
 	});
 }
 
+#[test]
+fn deposit_insurance_reserve_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(StakingPoolModule::insurance_reserve(), 0);
+		assert_ok!(StakingPoolModule::deposit_insurance_reserve(Origin::signed(ALICE), 300));
+		System::assert_last_event(Event::StakingPoolModule(crate::Event::DepositInsuranceReserve(
+			ALICE, 300,
+		)));
+		assert_eq!(StakingPoolModule::insurance_reserve(), 300);
+		assert_eq!(CurrenciesModule::free_balance(DOT, &ALICE), 700);
+		assert_eq!(
+			CurrenciesModule::free_balance(DOT, &StakingPoolModule::account_id()),
+			300
+		);
+
+		// insurance reserve is not belong to liquid currency holders
+		assert_eq!(StakingPoolModule::staking_pool_ledger().total(), 0);
+	});
+}
+
+#[test]
+fn on_slash_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(StakingPoolModule::deposit_insurance_reserve(Origin::signed(BOB), 100));
+		assert_ok!(CurrenciesModule::deposit(LDOT, &ALICE, 10000));
+		StakingPoolLedger::<Runtime>::put(Ledger {
+			bonded: 1000,
+			free_pool: 0,
+			unbonding_to_free: 0,
+			to_unbond_next_era: (0, 0),
+		});
+		assert_eq!(
+			StakingPoolModule::liquid_exchange_rate(),
+			ExchangeRate::saturating_from_rational(1000, 10000)
+		);
+
+		// compensated by guarantee and insurance reserve totally
+		OnSlash::<Runtime>::happened(&(150, 60));
+		System::assert_last_event(Event::StakingPoolModule(crate::Event::SlashCompensated(150, 60, 90, 0)));
+		assert_eq!(StakingPoolModule::insurance_reserve(), 10);
+		assert_eq!(
+			StakingPoolModule::staking_pool_ledger(),
+			Ledger {
+				bonded: 850,
+				free_pool: 90,
+				unbonding_to_free: 0,
+				to_unbond_next_era: (0, 0)
+			}
+		);
+
+		// insurance reserve is not enough, the remains reduce the exchange rate
+		OnSlash::<Runtime>::happened(&(50, 0));
+		System::assert_last_event(Event::StakingPoolModule(crate::Event::SlashCompensated(50, 0, 10, 40)));
+		assert_eq!(StakingPoolModule::insurance_reserve(), 0);
+		assert_eq!(
+			StakingPoolModule::staking_pool_ledger(),
+			Ledger {
+				bonded: 800,
+				free_pool: 100,
+				unbonding_to_free: 0,
+				to_unbond_next_era: (0, 0)
+			}
+		);
+		assert_eq!(
+			StakingPoolModule::liquid_exchange_rate(),
+			ExchangeRate::saturating_from_rational(900, 10000)
+		);
+	});
+}
+
 #[test]
 fn mint_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -790,6 +902,214 @@ fn mint_work() {
 	});
 }
 
+#[test]
+fn set_mint_fee_rates_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			StakingPoolModule::set_mint_fee_rates(Origin::signed(5), ChangeRate::NoChange, ChangeRate::NoChange),
+			BadOrigin
+		);
+
+		assert_eq!(StakingPoolModule::mint_fee_rates(), Default::default());
+		assert_ok!(StakingPoolModule::set_mint_fee_rates(
+			Origin::signed(One::get()),
+			ChangeRate::NewValue(Rate::saturating_from_rational(2, 100)),
+			ChangeRate::NewValue(Rate::saturating_from_rational(1, 100))
+		));
+		assert_eq!(
+			StakingPoolModule::mint_fee_rates(),
+			MintFeeRates {
+				immediately: Rate::saturating_from_rational(2, 100),
+				next_era: Rate::saturating_from_rational(1, 100),
+			}
+		);
+
+		assert_noop!(
+			StakingPoolModule::set_mint_fee_rates(
+				Origin::signed(One::get()),
+				ChangeRate::NoChange,
+				ChangeRate::NewValue(Rate::saturating_from_rational(3, 100))
+			),
+			Error::<Runtime>::InvalidConfig
+		);
+		assert_noop!(
+			StakingPoolModule::set_mint_fee_rates(
+				Origin::signed(One::get()),
+				ChangeRate::NewValue(Rate::saturating_from_rational(101, 100)),
+				ChangeRate::NoChange
+			),
+			Error::<Runtime>::InvalidConfig
+		);
+	});
+}
+
+#[test]
+fn mint_with_fee_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		StakingPoolMintFeeRates::<Runtime>::put(MintFeeRates {
+			immediately: Rate::saturating_from_rational(2, 100),
+			next_era: Rate::saturating_from_rational(1, 100),
+		});
+
+		assert_eq!(StakingPoolModule::mint(&ALICE, 500), Ok(4900));
+		assert_eq!(CurrenciesModule::free_balance(DOT, &ALICE), 500);
+		assert_eq!(CurrenciesModule::free_balance(LDOT, &ALICE), 4900);
+		assert_eq!(StakingPoolModule::staking_pool_ledger().free_pool, 500);
+		System::assert_last_event(Event::StakingPoolModule(crate::Event::MintLiquid(ALICE, 500, 4900)));
+	});
+}
+
+#[test]
+fn mint_at_next_era_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		StakingPoolMintFeeRates::<Runtime>::put(MintFeeRates {
+			immediately: Rate::saturating_from_rational(2, 100),
+			next_era: Rate::saturating_from_rational(1, 100),
+		});
+
+		assert_ok!(StakingPoolModule::mint_at_next_era(&ALICE, 0));
+		assert_eq!(StakingPoolModule::total_next_era_mint(), 0);
+
+		assert_ok!(StakingPoolModule::mint_at_next_era(&ALICE, 300));
+		System::assert_last_event(Event::StakingPoolModule(crate::Event::MintLiquidAtNextEra(ALICE, 300)));
+		assert_ok!(StakingPoolModule::mint_at_next_era(&ALICE, 200));
+		assert_ok!(StakingPoolModule::mint_at_next_era(&BOB, 100));
+		assert_eq!(CurrenciesModule::free_balance(DOT, &ALICE), 500);
+		assert_eq!(CurrenciesModule::free_balance(LDOT, &ALICE), 0);
+		assert_eq!(
+			CurrenciesModule::free_balance(DOT, &StakingPoolModule::account_id()),
+			600
+		);
+		assert_eq!(StakingPoolModule::next_era_mints(&ALICE), 500);
+		assert_eq!(StakingPoolModule::next_era_mints(&BOB), 100);
+		assert_eq!(StakingPoolModule::total_next_era_mint(), 600);
+		assert_eq!(StakingPoolModule::staking_pool_ledger(), Default::default());
+
+		mock_rebalance_process(1);
+		assert_eq!(CurrenciesModule::free_balance(LDOT, &ALICE), 4950);
+		assert_eq!(CurrenciesModule::free_balance(LDOT, &BOB), 990);
+		assert_eq!(CurrenciesModule::free_balance(DOT, &StakingPoolModule::account_id()), 0);
+		assert_eq!(StakingPoolModule::next_era_mints(&ALICE), 0);
+		assert_eq!(StakingPoolModule::total_next_era_mint(), 0);
+		assert_eq!(
+			StakingPoolModule::staking_pool_ledger(),
+			Ledger {
+				bonded: 600,
+				free_pool: 0,
+				unbonding_to_free: 0,
+				to_unbond_next_era: (0, 0)
+			}
+		);
+
+		RebalancePhase::<Runtime>::put(Phase::Started);
+		assert_noop!(
+			StakingPoolModule::mint_at_next_era(&ALICE, 500),
+			Error::<Runtime>::RebalanceUnfinished
+		);
+	});
+}
+
+#[test]
+fn mint_at_next_era_checks_minimum_amount_and_volume_limits() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			StakingPoolModule::mint_at_next_era(&ALICE, 9),
+			Error::<Runtime>::BelowMinimumAmount
+		);
+
+		HomaVolumeLimits::<Runtime>::put(VolumeLimits {
+			mint_per_block: Some(300),
+			mint_per_era: Some(450),
+			redeem_per_block: None,
+			redeem_per_era: None,
+		});
+		assert_noop!(
+			StakingPoolModule::mint_at_next_era(&ALICE, 301),
+			Error::<Runtime>::ExceedVolumeLimit
+		);
+
+		// the mint at next era consumes the volume and is not queued
+		assert_ok!(StakingPoolModule::mint_at_next_era(&ALICE, 200));
+		assert_eq!(StakingPoolModule::block_volume().mint, 200);
+		assert_eq!(StakingPoolModule::era_volume().mint, 200);
+		assert_noop!(
+			StakingPoolModule::mint_at_next_era(&ALICE, 200),
+			Error::<Runtime>::ExceedVolumeLimit
+		);
+		assert_eq!(StakingPoolModule::request_queue_range(), (0, 0));
+		assert_eq!(StakingPoolModule::next_era_mints(&ALICE), 200);
+	});
+}
+
+#[test]
+fn next_era_mints_are_issued_by_batches() {
+	ExtBuilder::default().build().execute_with(|| {
+		StakingPoolMintFeeRates::<Runtime>::put(MintFeeRates {
+			immediately: Rate::saturating_from_rational(2, 100),
+			next_era: Rate::saturating_from_rational(1, 100),
+		});
+		for who in 0..4 {
+			assert_ok!(CurrenciesModule::deposit(DOT, &who, 100));
+			assert_ok!(StakingPoolModule::mint_at_next_era(&who, 100));
+		}
+
+		// the mints are bonded together, and issued by `MaxNextEraMintsPerBlock`
+		mock_rebalance_process(1);
+		assert_eq!(StakingPoolModule::staking_pool_ledger().bonded, 400);
+		assert_eq!(StakingPoolModule::total_next_era_mint(), 0);
+		assert_eq!(NextEraMints::<Runtime>::iter().count(), 2);
+		assert_eq!(CurrenciesModule::total_issuance(LDOT), 1980);
+		assert!(StakingPoolModule::next_era_mints_issuance().is_some());
+		assert_noop!(
+			StakingPoolModule::mint_at_next_era(&ALICE, 100),
+			Error::<Runtime>::NextEraMintsIssuing
+		);
+
+		// the rest are issued at the same exchange rate
+		StakingPoolModule::on_initialize(6);
+		assert_eq!(NextEraMints::<Runtime>::iter().count(), 0);
+		for who in 0..4 {
+			assert_eq!(CurrenciesModule::free_balance(LDOT, &who), 990);
+		}
+		assert_eq!(StakingPoolModule::next_era_mints_issuance(), None);
+		assert_ok!(StakingPoolModule::mint_at_next_era(&ALICE, 100));
+	});
+}
+
+#[test]
+fn failed_next_era_mint_is_refunded_from_free_pool() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let issuance = (ExchangeRate::saturating_from_rational(10, 100), Rate::zero());
+		// the issuance of liquid currency overflows
+		assert_ok!(CurrenciesModule::deposit(LDOT, &2, Balance::max_value() - 100));
+		assert_ok!(CurrenciesModule::deposit(DOT, &StakingPoolModule::account_id(), 100));
+		StakingPoolLedger::<Runtime>::mutate(|ledger| ledger.free_pool = 100);
+
+		NextEraMintsIssuance::<Runtime>::put(issuance);
+		NextEraMints::<Runtime>::insert(ALICE, 100);
+		StakingPoolModule::issue_next_era_mints();
+		System::assert_last_event(Event::StakingPoolModule(crate::Event::NextEraMintFailed(
+			ALICE, 100, true,
+		)));
+		assert_eq!(CurrenciesModule::free_balance(DOT, &ALICE), 1100);
+		assert_eq!(StakingPoolModule::staking_pool_ledger().free_pool, 0);
+
+		// the free pool is not enough to refund
+		NextEraMintsIssuance::<Runtime>::put(issuance);
+		NextEraMints::<Runtime>::insert(BOB, 100);
+		StakingPoolModule::issue_next_era_mints();
+		System::assert_last_event(Event::StakingPoolModule(crate::Event::NextEraMintFailed(
+			BOB, 100, false,
+		)));
+		assert_eq!(CurrenciesModule::free_balance(DOT, &BOB), 1000);
+		assert_eq!(NextEraMints::<Runtime>::iter().count(), 0);
+		assert_eq!(StakingPoolModule::next_era_mints_issuance(), None);
+	});
+}
+
 #[test]
 fn withdraw_redemption_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -815,6 +1135,25 @@ fn withdraw_redemption_work() {
 	});
 }
 
+#[test]
+fn take_and_add_unbonding_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		StakingPoolModule::add_unbonding(&ALICE, 5, 0);
+		assert!(!Unbondings::<Runtime>::contains_key(ALICE, 5));
+
+		StakingPoolModule::add_unbonding(&ALICE, 5, 200);
+		StakingPoolModule::add_unbonding(&ALICE, 5, 100);
+		assert_eq!(StakingPoolModule::unbondings(&ALICE, 5), 300);
+
+		assert_eq!(StakingPoolModule::take_unbonding(&ALICE, 5), 300);
+		assert!(!Unbondings::<Runtime>::contains_key(ALICE, 5));
+		assert_eq!(StakingPoolModule::take_unbonding(&ALICE, 5), 0);
+
+		StakingPoolModule::add_unbonding(&BOB, 5, 300);
+		assert_eq!(StakingPoolModule::unbondings(&BOB, 5), 300);
+	});
+}
+
 #[test]
 fn redeem_by_unbond_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -1378,3 +1717,424 @@ fn rebalance_work() {
 		assert_eq!(StakingPoolModule::unbondings(&ALICE, 13), 212);
 	});
 }
+
+#[test]
+fn exchange_rate_history_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CurrenciesModule::deposit(DOT, &ALICE, 100000));
+		assert_eq!(StakingPoolModule::mint(&ALICE, 100000), Ok(1000000));
+		assert_eq!(StakingPoolModule::get_exchange_rate_history(), vec![]);
+
+		mock_rebalance_process(1);
+		assert_eq!(
+			StakingPoolModule::era_exchange_rates(1),
+			Some(StakingPoolModule::liquid_exchange_rate())
+		);
+
+		mock_rebalance_process(2);
+		mock_rebalance_process(3);
+		assert_eq!(
+			StakingPoolModule::get_exchange_rate_history()
+				.into_iter()
+				.map(|(era, _)| era)
+				.collect::<Vec<_>>(),
+			vec![1, 2, 3]
+		);
+
+		// only keep the recent `MaxExchangeRateHistory` eras
+		mock_rebalance_process(4);
+		assert_eq!(StakingPoolModule::era_exchange_rates(1), None);
+		assert_eq!(
+			StakingPoolModule::get_exchange_rate_history()
+				.into_iter()
+				.map(|(era, _)| era)
+				.collect::<Vec<_>>(),
+			vec![2, 3, 4]
+		);
+	});
+}
+
+#[test]
+fn get_pending_unbonding_and_unbondings_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		Unbonding::<Runtime>::insert(5, (300, 100, 100));
+		Unbonding::<Runtime>::insert(2, (200, 0, 0));
+		Unbondings::<Runtime>::insert(ALICE, 5, 100);
+		Unbondings::<Runtime>::insert(ALICE, 3, 50);
+
+		assert_eq!(StakingPoolModule::get_pending_unbonding(), vec![(2, 200), (5, 300)]);
+		assert_eq!(StakingPoolModule::get_unbondings(&ALICE), vec![(3, 50), (5, 100)]);
+		assert_eq!(StakingPoolModule::get_unbondings(&BOB), vec![]);
+	});
+}
+
+#[test]
+fn set_volume_limits_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let limits = VolumeLimits {
+			mint_per_block: Some(100),
+			mint_per_era: Some(500),
+			redeem_per_block: Some(1000),
+			redeem_per_era: None,
+		};
+		assert_noop!(
+			StakingPoolModule::set_volume_limits(Origin::signed(5), limits.clone()),
+			BadOrigin
+		);
+
+		assert_eq!(StakingPoolModule::volume_limits(), Default::default());
+		assert_ok!(StakingPoolModule::set_volume_limits(
+			Origin::signed(One::get()),
+			limits.clone()
+		));
+		assert_eq!(StakingPoolModule::volume_limits(), limits.clone());
+		System::assert_last_event(Event::StakingPoolModule(crate::Event::VolumeLimitsUpdated(limits)));
+	});
+}
+
+#[test]
+fn queue_request_exceed_volume_limits_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		HomaVolumeLimits::<Runtime>::put(VolumeLimits {
+			mint_per_block: Some(300),
+			mint_per_era: Some(450),
+			redeem_per_block: None,
+			redeem_per_era: None,
+		});
+
+		// the request which exceeds the limits can never be executed
+		assert_noop!(
+			StakingPoolModule::mint(&ALICE, 301),
+			Error::<Runtime>::ExceedVolumeLimit
+		);
+
+		assert_eq!(StakingPoolModule::mint(&ALICE, 200), Ok(2000));
+		assert_eq!(StakingPoolModule::block_volume().mint, 200);
+		assert_eq!(StakingPoolModule::era_volume().mint, 200);
+
+		// exceed the remaining block volume, queued
+		assert_eq!(StakingPoolModule::mint(&ALICE, 200), Ok(0));
+		assert_eq!(CurrenciesModule::free_balance(DOT, &ALICE), 600);
+		assert_eq!(CurrenciesModule::free_balance(LDOT, &ALICE), 2000);
+		assert_eq!(StakingPoolModule::request_queue_range(), (0, 1));
+		assert_eq!(
+			StakingPoolModule::request_queue(0),
+			Some((ALICE, HomaRequest::Mint(200)))
+		);
+		System::assert_last_event(Event::StakingPoolModule(crate::Event::RequestQueued(
+			0,
+			ALICE,
+			HomaRequest::Mint(200),
+		)));
+
+		// queued requests ahead, queued even though it's within the remaining volume
+		assert_eq!(StakingPoolModule::mint(&BOB, 100), Ok(0));
+		assert_eq!(CurrenciesModule::free_balance(DOT, &BOB), 900);
+		assert_eq!(StakingPoolModule::request_queue_range(), (0, 2));
+		assert_eq!(StakingPoolModule::block_volume().mint, 200);
+
+		// the next block has new block volume, but the era volume only allows the first request
+		StakingPoolModule::on_initialize(2);
+		assert_eq!(StakingPoolModule::request_queue_range(), (1, 2));
+		assert_eq!(StakingPoolModule::request_queue(0), None);
+		assert_eq!(CurrenciesModule::free_balance(DOT, &ALICE), 600);
+		assert_eq!(CurrenciesModule::free_balance(LDOT, &ALICE), 4000);
+		assert_eq!(StakingPoolModule::block_volume().mint, 200);
+		assert_eq!(StakingPoolModule::era_volume().mint, 400);
+		System::assert_last_event(Event::StakingPoolModule(crate::Event::QueuedRequestExecuted(
+			ALICE,
+			HomaRequest::Mint(200),
+			true,
+		)));
+
+		StakingPoolModule::on_initialize(3);
+		assert_eq!(StakingPoolModule::request_queue_range(), (1, 2));
+		assert_eq!(CurrenciesModule::free_balance(LDOT, &BOB), 0);
+
+		// the queue is processed only after rebalance finished in the new era
+		StakingPoolModule::on_new_era(1);
+		assert_eq!(StakingPoolModule::era_volume(), Default::default());
+		StakingPoolModule::on_initialize(4);
+		assert_eq!(StakingPoolModule::request_queue_range(), (1, 2));
+		StakingPoolModule::on_initialize(5);
+		StakingPoolModule::on_initialize(6);
+		assert_eq!(StakingPoolModule::rebalance_phase(), Phase::Finished);
+		assert_eq!(StakingPoolModule::request_queue_range(), (2, 2));
+		assert_eq!(StakingPoolModule::request_queue(1), None);
+		assert_eq!(CurrenciesModule::free_balance(DOT, &BOB), 900);
+		assert!(CurrenciesModule::free_balance(LDOT, &BOB) > 0);
+	});
+}
+
+#[test]
+fn queued_request_failed_return_assets() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(StakingPoolModule::mint(&ALICE, 500), Ok(5000));
+		HomaVolumeLimits::<Runtime>::put(VolumeLimits {
+			mint_per_block: None,
+			mint_per_era: None,
+			redeem_per_block: Some(1000),
+			redeem_per_era: None,
+		});
+		BlockVolume::<Runtime>::put(Volume { mint: 0, redeem: 1000 });
+
+		assert_ok!(StakingPoolModule::redeem_by_claim_unbonding(&ALICE, 1000, 1));
+		assert_eq!(CurrenciesModule::free_balance(LDOT, &ALICE), 4000);
+		assert_eq!(StakingPoolModule::request_queue_range(), (0, 1));
+
+		// the target era of the queued request is expired when it's executed
+		mock_rebalance_process(1);
+		assert_eq!(StakingPoolModule::request_queue_range(), (1, 1));
+		assert_eq!(CurrenciesModule::free_balance(LDOT, &ALICE), 5000);
+		assert_eq!(StakingPoolModule::unbondings(&ALICE, 1), 0);
+		System::assert_has_event(Event::StakingPoolModule(crate::Event::QueuedRequestExecuted(
+			ALICE,
+			HomaRequest::RedeemByClaimUnbonding(1000, 1),
+			false,
+		)));
+	});
+}
+
+#[test]
+fn update_queued_request_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(StakingPoolModule::mint(&ALICE, 500), Ok(5000));
+		HomaVolumeLimits::<Runtime>::put(VolumeLimits {
+			mint_per_block: None,
+			mint_per_era: None,
+			redeem_per_block: Some(1000),
+			redeem_per_era: None,
+		});
+		BlockVolume::<Runtime>::put(Volume { mint: 0, redeem: 1000 });
+
+		assert_ok!(StakingPoolModule::redeem_by_unbond(&ALICE, 600));
+		assert_ok!(StakingPoolModule::mint(&BOB, 100));
+		assert_ok!(StakingPoolModule::redeem_by_unbond(&BOB, 500));
+		assert_eq!(StakingPoolModule::request_queue_range(), (0, 2));
+		assert_eq!(CurrenciesModule::free_balance(LDOT, &ALICE), 4400);
+
+		assert_noop!(
+			StakingPoolModule::update_queued_request(&BOB, 0, 100),
+			Error::<Runtime>::RequestNotFound
+		);
+		assert_noop!(
+			StakingPoolModule::update_queued_request(&ALICE, 2, 100),
+			Error::<Runtime>::RequestNotFound
+		);
+		assert_noop!(
+			StakingPoolModule::update_queued_request(&ALICE, 0, 1001),
+			Error::<Runtime>::ExceedVolumeLimit
+		);
+
+		// reduce keeps the position
+		assert_ok!(StakingPoolModule::update_queued_request(&ALICE, 0, 400));
+		assert_eq!(CurrenciesModule::free_balance(LDOT, &ALICE), 4600);
+		assert_eq!(
+			StakingPoolModule::request_queue(0),
+			Some((ALICE, HomaRequest::RedeemByUnbond(400)))
+		);
+		System::assert_last_event(Event::StakingPoolModule(crate::Event::QueuedRequestReduced(
+			0,
+			ALICE,
+			HomaRequest::RedeemByUnbond(400),
+		)));
+
+		// top up moves to the tail
+		assert_ok!(StakingPoolModule::update_queued_request(&ALICE, 0, 700));
+		assert_eq!(CurrenciesModule::free_balance(LDOT, &ALICE), 4300);
+		assert_eq!(StakingPoolModule::request_queue(0), None);
+		assert_eq!(
+			StakingPoolModule::request_queue(2),
+			Some((ALICE, HomaRequest::RedeemByUnbond(700)))
+		);
+		assert_eq!(StakingPoolModule::request_queue_range(), (0, 3));
+		System::assert_last_event(Event::StakingPoolModule(crate::Event::RequestQueued(
+			2,
+			ALICE,
+			HomaRequest::RedeemByUnbond(700),
+		)));
+
+		// cancel returns all the assets
+		assert_ok!(StakingPoolModule::update_queued_request(&ALICE, 2, 0));
+		assert_eq!(CurrenciesModule::free_balance(LDOT, &ALICE), 5000);
+		assert_eq!(StakingPoolModule::request_queue(2), None);
+		System::assert_last_event(Event::StakingPoolModule(crate::Event::QueuedRequestCancelled(2, ALICE)));
+
+		// the removed requests are skipped
+		StakingPoolModule::on_initialize(2);
+		assert_eq!(StakingPoolModule::request_queue_range(), (3, 3));
+		assert_eq!(StakingPoolModule::request_queue(1), None);
+	});
+}
+
+#[test]
+fn process_request_queue_bounded_per_block() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		RequestQueueRange::<Runtime>::put((0, 5));
+		assert!(StakingPoolModule::on_initialize(2) > 0);
+		assert_eq!(StakingPoolModule::request_queue_range(), (3, 5));
+		StakingPoolModule::on_initialize(3);
+		assert_eq!(StakingPoolModule::request_queue_range(), (5, 5));
+	});
+}
+
+#[test]
+fn cancel_redeem_by_unbond_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(StakingPoolModule::mint(&ALICE, 500), Ok(5000));
+		StakingPoolLedger::<Runtime>::mutate(|ledger| {
+			ledger.bonded = 500;
+			ledger.free_pool = 0;
+		});
+		assert_ok!(StakingPoolModule::redeem_by_unbond(&ALICE, 1000));
+		assert_eq!(CurrenciesModule::free_balance(LDOT, &ALICE), 4000);
+		assert_eq!(StakingPoolModule::next_era_unbonds(&ALICE), 100);
+		assert_eq!(StakingPoolModule::staking_pool_ledger().to_unbond_next_era, (100, 100));
+		let exchange_rate = StakingPoolModule::liquid_exchange_rate();
+
+		RebalancePhase::<Runtime>::put(Phase::Started);
+		assert_noop!(
+			StakingPoolModule::cancel_redeem_by_unbond(&ALICE, 40),
+			Error::<Runtime>::RebalanceUnfinished
+		);
+		RebalancePhase::<Runtime>::put(Phase::Finished);
+
+		assert_ok!(StakingPoolModule::cancel_redeem_by_unbond(&ALICE, 40));
+		assert_eq!(CurrenciesModule::free_balance(LDOT, &ALICE), 4400);
+		assert_eq!(StakingPoolModule::next_era_unbonds(&ALICE), 60);
+		assert_eq!(StakingPoolModule::staking_pool_ledger().to_unbond_next_era, (60, 60));
+		assert_eq!(StakingPoolModule::liquid_exchange_rate(), exchange_rate);
+		System::assert_last_event(Event::StakingPoolModule(crate::Event::RedeemByUnbondCancelled(
+			ALICE, 40, 400,
+		)));
+
+		// cancel at most the pending unbond
+		assert_ok!(StakingPoolModule::cancel_redeem_by_unbond(&ALICE, 100));
+		assert_eq!(CurrenciesModule::free_balance(LDOT, &ALICE), 5000);
+		assert_eq!(StakingPoolModule::next_era_unbonds(&ALICE), 0);
+		assert_eq!(StakingPoolModule::staking_pool_ledger().to_unbond_next_era, (0, 0));
+	});
+}
+
+#[test]
+fn set_cash_buffer_policy_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let policy = CashBufferPolicy {
+			target_ratio: Ratio::saturating_from_rational(20, 100),
+			min_ratio: Ratio::saturating_from_rational(10, 100),
+			max_ratio: Ratio::saturating_from_rational(30, 100),
+			max_adjustment_per_era: Rate::saturating_from_rational(5, 100),
+		};
+		assert_noop!(
+			StakingPoolModule::set_cash_buffer_policy(Origin::signed(5), Some(policy.clone())),
+			BadOrigin
+		);
+		assert_noop!(
+			StakingPoolModule::set_cash_buffer_policy(
+				Origin::signed(One::get()),
+				Some(CashBufferPolicy {
+					min_ratio: Ratio::saturating_from_rational(25, 100),
+					..policy.clone()
+				})
+			),
+			Error::<Runtime>::InvalidConfig
+		);
+		assert_noop!(
+			StakingPoolModule::set_cash_buffer_policy(
+				Origin::signed(One::get()),
+				Some(CashBufferPolicy {
+					max_ratio: Ratio::saturating_from_rational(101, 100),
+					..policy.clone()
+				})
+			),
+			Error::<Runtime>::InvalidConfig
+		);
+
+		assert_eq!(StakingPoolModule::cash_buffer_policy(), None);
+		assert_ok!(StakingPoolModule::set_cash_buffer_policy(
+			Origin::signed(One::get()),
+			Some(policy.clone())
+		));
+		assert_eq!(StakingPoolModule::cash_buffer_policy(), Some(policy.clone()));
+		System::assert_last_event(Event::StakingPoolModule(crate::Event::CashBufferPolicyUpdated(Some(
+			policy,
+		))));
+
+		assert_ok!(StakingPoolModule::set_cash_buffer_policy(
+			Origin::signed(One::get()),
+			None
+		));
+		assert_eq!(StakingPoolModule::cash_buffer_policy(), None);
+		System::assert_last_event(Event::StakingPoolModule(crate::Event::CashBufferPolicyUpdated(None)));
+	});
+}
+
+#[test]
+fn rebalance_with_cash_buffer_policy_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(StakingPoolModule::set_cash_buffer_policy(
+			Origin::signed(One::get()),
+			Some(CashBufferPolicy {
+				target_ratio: Ratio::saturating_from_rational(20, 100),
+				min_ratio: Ratio::saturating_from_rational(10, 100),
+				max_ratio: Ratio::saturating_from_rational(30, 100),
+				max_adjustment_per_era: Rate::saturating_from_rational(80, 100),
+			})
+		));
+		assert_ok!(CurrenciesModule::deposit(DOT, &ALICE, 100000));
+		assert_eq!(StakingPoolModule::mint(&ALICE, 100000), Ok(1000000));
+
+		// the cash buffer ratio 100% is above the max ratio, bond to the target ratio
+		mock_rebalance_process(1);
+		assert_eq!(
+			StakingPoolModule::staking_pool_ledger(),
+			Ledger {
+				bonded: 80000,
+				unbonding_to_free: 0,
+				free_pool: 20000,
+				to_unbond_next_era: (0, 0)
+			}
+		);
+
+		// the cash buffer ratio is between the min ratio and max ratio, no adjustment
+		mock_rebalance_process(2);
+		assert_eq!(
+			StakingPoolModule::staking_pool_ledger(),
+			Ledger {
+				bonded: 80800,
+				unbonding_to_free: 0,
+				free_pool: 20000,
+				to_unbond_next_era: (0, 0)
+			}
+		);
+
+		// the cash buffer ratio is below the min ratio, unbond toward the target ratio
+		// but limited by the max adjustment
+		assert_ok!(StakingPoolModule::set_cash_buffer_policy(
+			Origin::signed(One::get()),
+			Some(CashBufferPolicy {
+				target_ratio: Ratio::saturating_from_rational(25, 100),
+				min_ratio: Ratio::saturating_from_rational(20, 100),
+				max_ratio: Ratio::saturating_from_rational(30, 100),
+				max_adjustment_per_era: Rate::saturating_from_rational(1, 100),
+			})
+		));
+		mock_rebalance_process(3);
+		assert_eq!(
+			StakingPoolModule::staking_pool_ledger(),
+			Ledger {
+				bonded: 80592,
+				unbonding_to_free: 1016,
+				free_pool: 20000,
+				to_unbond_next_era: (0, 0)
+			}
+		);
+	});
+}
@@ -0,0 +1,334 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Streaming Module
+//!
+//! ## Overview
+//!
+//! The sender escrows the deposit of a stream in the module account, which unlocks to the
+//! recipient linearly per block from `start` to `stop`. The recipient can withdraw the unlocked
+//! balance at any time, and transfer the stream to another recipient. Either party can cancel
+//! the stream, the unlocked balance is paid to the recipient and the rest is refunded to the
+//! sender.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{pallet_prelude::*, transactional, PalletId};
+use frame_system::pallet_prelude::*;
+use orml_traits::MultiCurrency;
+use primitives::{Balance, CurrencyId};
+use sp_runtime::{
+	helpers_128bit::multiply_by_rational,
+	traits::{AccountIdConversion, One, Saturating, StaticLookup, UniqueSaturatedInto, Zero},
+	ArithmeticError, RuntimeDebug,
+};
+use sp_std::prelude::*;
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+/// The id of stream.
+pub type StreamId = u64;
+
+/// The stream of currency unlocking to the recipient linearly.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Stream<AccountId, BlockNumber> {
+	/// The sender who funded the deposit.
+	pub sender: AccountId,
+	/// The recipient.
+	pub recipient: AccountId,
+	/// The currency of the stream.
+	pub currency_id: CurrencyId,
+	/// The total amount to stream.
+	pub deposit: Balance,
+	/// The block number the stream starts to unlock.
+	pub start: BlockNumber,
+	/// The block number the deposit is fully unlocked.
+	pub stop: BlockNumber,
+	/// The amount withdrawn by the recipients.
+	pub withdrawn: Balance,
+}
+
+impl<AccountId, BlockNumber: UniqueSaturatedInto<u128> + Copy + PartialOrd + Saturating>
+	Stream<AccountId, BlockNumber>
+{
+	/// The unlocked amount of the deposit at `now`, including the withdrawn amount.
+	pub fn unlocked(&self, now: BlockNumber) -> Balance {
+		if now <= self.start {
+			Zero::zero()
+		} else if now >= self.stop {
+			self.deposit
+		} else {
+			let elapsed: u128 = now.saturating_sub(self.start).unique_saturated_into();
+			let duration: u128 = self.stop.saturating_sub(self.start).unique_saturated_into();
+			multiply_by_rational(self.deposit, elapsed, duration).unwrap_or(self.deposit)
+		}
+	}
+
+	/// The amount the recipient can withdraw at `now`.
+	pub fn available(&self, now: BlockNumber) -> Balance {
+		self.unlocked(now).saturating_sub(self.withdrawn)
+	}
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currencies to stream.
+		type Currency: MultiCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
+
+		/// The module id to keep the deposits of streams.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The start block number is in the past, or not before the stop block number
+		InvalidPeriod,
+		/// The deposit is zero
+		InvalidDeposit,
+		/// The stream does not exist
+		StreamNotFound,
+		/// The caller is not the sender or recipient of the stream
+		NoPermission,
+		/// The amount exceeds the available balance of the stream
+		InsufficientAvailableBalance,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Created stream. \[stream_id, sender, recipient, currency_id, deposit, start, stop\]
+		StreamCreated(
+			StreamId,
+			T::AccountId,
+			T::AccountId,
+			CurrencyId,
+			Balance,
+			T::BlockNumber,
+			T::BlockNumber,
+		),
+		/// Withdrew from stream. \[stream_id, recipient, amount\]
+		Withdrawn(StreamId, T::AccountId, Balance),
+		/// Cancelled stream. \[stream_id, recipient_amount, sender_refund\]
+		StreamCancelled(StreamId, Balance, Balance),
+		/// Transferred stream to new recipient. \[stream_id, from, to\]
+		StreamTransferred(StreamId, T::AccountId, T::AccountId),
+	}
+
+	/// The id of the next stream.
+	///
+	/// NextStreamId: StreamId
+	#[pallet::storage]
+	#[pallet::getter(fn next_stream_id)]
+	pub type NextStreamId<T: Config> = StorageValue<_, StreamId, ValueQuery>;
+
+	/// The streams.
+	///
+	/// Streams: map StreamId => Option<Stream>
+	#[pallet::storage]
+	#[pallet::getter(fn streams)]
+	pub type Streams<T: Config> =
+		StorageMap<_, Twox64Concat, StreamId, Stream<T::AccountId, T::BlockNumber>, OptionQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Create a stream of `deposit` of `currency_id` to `recipient`, unlocking linearly from
+		/// `start` to `stop`. The deposit is escrowed.
+		///
+		/// - `recipient`: the recipient of the stream.
+		/// - `currency_id`: the currency to stream.
+		/// - `deposit`: the total amount to stream.
+		/// - `start`: the block number the stream starts to unlock, not in the past.
+		/// - `stop`: the block number the deposit is fully unlocked.
+		#[pallet::weight(T::WeightInfo::create_stream())]
+		#[transactional]
+		pub fn create_stream(
+			origin: OriginFor<T>,
+			recipient: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyId,
+			#[pallet::compact] deposit: Balance,
+			start: T::BlockNumber,
+			stop: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			let recipient = T::Lookup::lookup(recipient)?;
+			ensure!(
+				start >= frame_system::Pallet::<T>::block_number() && stop > start,
+				Error::<T>::InvalidPeriod
+			);
+			ensure!(!deposit.is_zero(), Error::<T>::InvalidDeposit);
+
+			T::Currency::transfer(currency_id, &sender, &Self::account_id(), deposit)?;
+
+			let stream_id = NextStreamId::<T>::try_mutate(|id| -> Result<StreamId, DispatchError> {
+				let current_id = *id;
+				*id = id.checked_add(One::one()).ok_or(ArithmeticError::Overflow)?;
+				Ok(current_id)
+			})?;
+			Streams::<T>::insert(
+				stream_id,
+				Stream {
+					sender: sender.clone(),
+					recipient: recipient.clone(),
+					currency_id,
+					deposit,
+					start,
+					stop,
+					withdrawn: Zero::zero(),
+				},
+			);
+
+			Self::deposit_event(Event::StreamCreated(
+				stream_id,
+				sender,
+				recipient,
+				currency_id,
+				deposit,
+				start,
+				stop,
+			));
+			Ok(().into())
+		}
+
+		/// Withdraw `amount` of the unlocked balance from the stream of the caller. The stream is
+		/// removed when it's fully withdrawn.
+		///
+		/// - `stream_id`: the id of the stream.
+		/// - `amount`: the amount to withdraw.
+		#[pallet::weight(T::WeightInfo::withdraw_from_stream())]
+		#[transactional]
+		pub fn withdraw_from_stream(
+			origin: OriginFor<T>,
+			stream_id: StreamId,
+			#[pallet::compact] amount: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let now = frame_system::Pallet::<T>::block_number();
+
+			Streams::<T>::try_mutate_exists(stream_id, |maybe_stream| -> DispatchResult {
+				let stream = maybe_stream.as_mut().ok_or(Error::<T>::StreamNotFound)?;
+				ensure!(who == stream.recipient, Error::<T>::NoPermission);
+				ensure!(
+					amount <= stream.available(now),
+					Error::<T>::InsufficientAvailableBalance
+				);
+
+				T::Currency::transfer(stream.currency_id, &Self::account_id(), &who, amount)?;
+				stream.withdrawn = stream.withdrawn.saturating_add(amount);
+				if stream.withdrawn >= stream.deposit {
+					*maybe_stream = None;
+				}
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::Withdrawn(stream_id, who, amount));
+			Ok(().into())
+		}
+
+		/// Cancel the stream, the available balance is paid to the recipient and the rest is
+		/// refunded to the sender.
+		///
+		/// The dispatch origin of this call must be the sender or the recipient of the stream.
+		///
+		/// - `stream_id`: the id of the stream.
+		#[pallet::weight(T::WeightInfo::cancel_stream())]
+		#[transactional]
+		pub fn cancel_stream(origin: OriginFor<T>, stream_id: StreamId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let stream = Self::streams(stream_id).ok_or(Error::<T>::StreamNotFound)?;
+			ensure!(
+				who == stream.sender || who == stream.recipient,
+				Error::<T>::NoPermission
+			);
+
+			let now = frame_system::Pallet::<T>::block_number();
+			let recipient_amount = stream.available(now);
+			let sender_refund = stream.deposit.saturating_sub(stream.unlocked(now));
+			T::Currency::transfer(
+				stream.currency_id,
+				&Self::account_id(),
+				&stream.recipient,
+				recipient_amount,
+			)?;
+			T::Currency::transfer(stream.currency_id, &Self::account_id(), &stream.sender, sender_refund)?;
+			Streams::<T>::remove(stream_id);
+
+			Self::deposit_event(Event::StreamCancelled(stream_id, recipient_amount, sender_refund));
+			Ok(().into())
+		}
+
+		/// Transfer the stream of the caller to a new recipient, including the available balance
+		/// not withdrawn yet.
+		///
+		/// - `stream_id`: the id of the stream.
+		/// - `to`: the new recipient.
+		#[pallet::weight(T::WeightInfo::transfer_stream())]
+		#[transactional]
+		pub fn transfer_stream(
+			origin: OriginFor<T>,
+			stream_id: StreamId,
+			to: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(to)?;
+
+			Streams::<T>::try_mutate(stream_id, |maybe_stream| -> DispatchResult {
+				let stream = maybe_stream.as_mut().ok_or(Error::<T>::StreamNotFound)?;
+				ensure!(who == stream.recipient, Error::<T>::NoPermission);
+				stream.recipient = to.clone();
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::StreamTransferred(stream_id, who, to));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The account to keep the deposits of streams.
+	pub fn account_id() -> T::AccountId {
+		T::PalletId::get().into_account()
+	}
+
+	/// The balance the recipient of the stream can withdraw now.
+	pub fn available_balance(stream_id: StreamId) -> Option<Balance> {
+		Self::streams(stream_id).map(|stream| stream.available(frame_system::Pallet::<T>::block_number()))
+	}
+}
@@ -0,0 +1,199 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for streaming module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+
+fn create_stream() {
+	// 1000 AUSD from block 10 to 110, 10 AUSD per block
+	assert_ok!(StreamingModule::create_stream(
+		Origin::signed(ALICE),
+		BOB,
+		AUSD,
+		1000,
+		10,
+		110
+	));
+}
+
+#[test]
+fn create_stream_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(5);
+		assert_noop!(
+			StreamingModule::create_stream(Origin::signed(ALICE), BOB, AUSD, 1000, 4, 110),
+			Error::<Runtime>::InvalidPeriod
+		);
+		assert_noop!(
+			StreamingModule::create_stream(Origin::signed(ALICE), BOB, AUSD, 1000, 10, 10),
+			Error::<Runtime>::InvalidPeriod
+		);
+		assert_noop!(
+			StreamingModule::create_stream(Origin::signed(ALICE), BOB, AUSD, 0, 10, 110),
+			Error::<Runtime>::InvalidDeposit
+		);
+		assert_noop!(
+			StreamingModule::create_stream(Origin::signed(ALICE), BOB, AUSD, 1001, 10, 110),
+			orml_tokens::Error::<Runtime>::BalanceTooLow
+		);
+
+		create_stream();
+		System::assert_last_event(Event::StreamingModule(crate::Event::StreamCreated(
+			0, ALICE, BOB, AUSD, 1000, 10, 110,
+		)));
+		assert_eq!(StreamingModule::next_stream_id(), 1);
+		assert_eq!(
+			StreamingModule::streams(0),
+			Some(Stream {
+				sender: ALICE,
+				recipient: BOB,
+				currency_id: AUSD,
+				deposit: 1000,
+				start: 10,
+				stop: 110,
+				withdrawn: 0,
+			})
+		);
+		assert_eq!(Tokens::free_balance(AUSD, &ALICE), 0);
+		assert_eq!(Tokens::free_balance(AUSD, &StreamingModule::account_id()), 1000);
+	});
+}
+
+#[test]
+fn available_balance_unlocks_linearly() {
+	ExtBuilder::default().build().execute_with(|| {
+		create_stream();
+		assert_eq!(StreamingModule::available_balance(0), Some(0));
+
+		System::set_block_number(10);
+		assert_eq!(StreamingModule::available_balance(0), Some(0));
+
+		System::set_block_number(35);
+		assert_eq!(StreamingModule::available_balance(0), Some(250));
+
+		System::set_block_number(200);
+		assert_eq!(StreamingModule::available_balance(0), Some(1000));
+
+		assert_eq!(StreamingModule::available_balance(1), None);
+	});
+}
+
+#[test]
+fn withdraw_from_stream_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			StreamingModule::withdraw_from_stream(Origin::signed(BOB), 0, 100),
+			Error::<Runtime>::StreamNotFound
+		);
+
+		create_stream();
+		System::set_block_number(20);
+		assert_noop!(
+			StreamingModule::withdraw_from_stream(Origin::signed(ALICE), 0, 100),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			StreamingModule::withdraw_from_stream(Origin::signed(BOB), 0, 101),
+			Error::<Runtime>::InsufficientAvailableBalance
+		);
+
+		assert_ok!(StreamingModule::withdraw_from_stream(Origin::signed(BOB), 0, 60));
+		System::assert_last_event(Event::StreamingModule(crate::Event::Withdrawn(0, BOB, 60)));
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 1060);
+		assert_eq!(StreamingModule::available_balance(0), Some(40));
+
+		System::set_block_number(110);
+		assert_ok!(StreamingModule::withdraw_from_stream(Origin::signed(BOB), 0, 940));
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 2000);
+		assert_eq!(StreamingModule::streams(0), None);
+	});
+}
+
+#[test]
+fn cancel_stream_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			StreamingModule::cancel_stream(Origin::signed(ALICE), 0),
+			Error::<Runtime>::StreamNotFound
+		);
+
+		create_stream();
+		System::set_block_number(30);
+		assert_ok!(StreamingModule::withdraw_from_stream(Origin::signed(BOB), 0, 50));
+		assert_noop!(
+			StreamingModule::cancel_stream(Origin::signed(CHARLIE), 0),
+			Error::<Runtime>::NoPermission
+		);
+
+		System::set_block_number(40);
+		assert_ok!(StreamingModule::cancel_stream(Origin::signed(ALICE), 0));
+		System::assert_last_event(Event::StreamingModule(crate::Event::StreamCancelled(0, 250, 700)));
+		assert_eq!(Tokens::free_balance(AUSD, &ALICE), 700);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 1300);
+		assert_eq!(Tokens::free_balance(AUSD, &StreamingModule::account_id()), 0);
+		assert_eq!(StreamingModule::streams(0), None);
+	});
+}
+
+#[test]
+fn cancel_stream_by_recipient_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		create_stream();
+		System::set_block_number(60);
+		assert_ok!(StreamingModule::cancel_stream(Origin::signed(BOB), 0));
+		System::assert_last_event(Event::StreamingModule(crate::Event::StreamCancelled(0, 500, 500)));
+		assert_eq!(Tokens::free_balance(AUSD, &ALICE), 500);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 1500);
+	});
+}
+
+#[test]
+fn transfer_stream_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			StreamingModule::transfer_stream(Origin::signed(BOB), 0, CHARLIE),
+			Error::<Runtime>::StreamNotFound
+		);
+
+		create_stream();
+		System::set_block_number(30);
+		assert_noop!(
+			StreamingModule::transfer_stream(Origin::signed(ALICE), 0, CHARLIE),
+			Error::<Runtime>::NoPermission
+		);
+
+		assert_ok!(StreamingModule::transfer_stream(Origin::signed(BOB), 0, CHARLIE));
+		System::assert_last_event(Event::StreamingModule(crate::Event::StreamTransferred(0, BOB, CHARLIE)));
+		assert_eq!(
+			StreamingModule::streams(0).map(|stream| stream.recipient),
+			Some(CHARLIE)
+		);
+
+		assert_noop!(
+			StreamingModule::withdraw_from_stream(Origin::signed(BOB), 0, 100),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(StreamingModule::withdraw_from_stream(Origin::signed(CHARLIE), 0, 200));
+		assert_eq!(Tokens::free_balance(AUSD, &CHARLIE), 200);
+	});
+}
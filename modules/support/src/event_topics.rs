@@ -0,0 +1,120 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! The indexing topics of the high-volume events.
+//!
+//! Besides depositing the event, the module records its topics, e.g. the trading pairs of a swap
+//! and the swapper. The topics of all the events of the block are accrued into a bloom filter
+//! deposited in the block digest, so light clients and indexers can filter the blocks with the
+//! relevant events by the headers, and only download the events of those blocks.
+
+use super::*;
+use sp_core::H256;
+use sp_io::hashing::{blake2_256, keccak_256};
+use sp_runtime::generic::{Digest, DigestItem};
+
+/// The kinds of the event topics.
+pub mod kind {
+	/// The account involved in the event, e.g. the swapper, the loan owner or the bidder.
+	pub const ACCOUNT: &[u8] = b"account";
+	/// The swaps of a trading pair.
+	pub const DEX_SWAP: &[u8] = b"dex/swap";
+	/// The loan adjustments of a collateral currency.
+	pub const LOAN_ADJUSTMENT: &[u8] = b"loans/adjustment";
+	/// The bids of an auction.
+	pub const AUCTION_BID: &[u8] = b"auction/bid";
+}
+
+/// The ID of the block digest item of the event topics bloom.
+pub const EVENT_TOPICS_DIGEST_ID: [u8; 4] = *b"evtp";
+
+/// The size of the event topics bloom in bytes.
+pub const EVENT_TOPICS_BLOOM_SIZE: usize = 256;
+
+/// The topic of the events of `kind` about `key`.
+pub fn event_topic<K: Encode>(kind: &[u8], key: &K) -> H256 {
+	(kind, key).using_encoded(blake2_256).into()
+}
+
+/// The block digest item of the event topics `bloom`.
+pub fn event_topics_digest_item<Hash>(bloom: &EventTopicsBloom) -> DigestItem<Hash> {
+	DigestItem::Other((EVENT_TOPICS_DIGEST_ID, bloom).encode())
+}
+
+/// Find the event topics bloom in the block `digest`, `None` if no topic is recorded in the block.
+pub fn find_event_topics_bloom<Hash>(digest: &Digest<Hash>) -> Option<EventTopicsBloom> {
+	digest.logs().iter().find_map(|item| match item {
+		DigestItem::Other(data) => match <([u8; 4], EventTopicsBloom)>::decode(&mut &data[..]) {
+			Ok((EVENT_TOPICS_DIGEST_ID, bloom)) => Some(bloom),
+			_ => None,
+		},
+		_ => None,
+	})
+}
+
+/// Records the topics of the events.
+pub trait EventTopicsRecorder {
+	/// Record the topics of an event deposited in the current block.
+	fn record(topics: &[H256]);
+}
+
+impl EventTopicsRecorder for () {
+	fn record(_topics: &[H256]) {}
+}
+
+/// The bloom filter of the event topics of a block, in the same way as the logs bloom of
+/// Ethereum: each topic sets 3 of the 2048 bits.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct EventTopicsBloom(pub [u8; EVENT_TOPICS_BLOOM_SIZE]);
+
+impl Default for EventTopicsBloom {
+	fn default() -> Self {
+		EventTopicsBloom([0u8; EVENT_TOPICS_BLOOM_SIZE])
+	}
+}
+
+impl EventTopicsBloom {
+	/// The indexes of the bytes and the masks of the bits set by `topic`.
+	fn bits(topic: &H256) -> [(usize, u8); 3] {
+		let hash = keccak_256(topic.as_bytes());
+		let mut bits = [(0, 0); 3];
+		for (i, bit) in bits.iter_mut().enumerate() {
+			let index = (((hash[2 * i] as usize) << 8) | hash[2 * i + 1] as usize) % (EVENT_TOPICS_BLOOM_SIZE * 8);
+			*bit = (EVENT_TOPICS_BLOOM_SIZE - 1 - index / 8, 1u8 << (index % 8));
+		}
+		bits
+	}
+
+	/// Add `topic` to the bloom.
+	pub fn accrue(&mut self, topic: &H256) {
+		for (byte, mask) in Self::bits(topic).iter() {
+			self.0[*byte] |= mask;
+		}
+	}
+
+	/// Whether the bloom may contain `topic`. There is no false negative, but may be false
+	/// positive.
+	pub fn contains(&self, topic: &H256) -> bool {
+		Self::bits(topic).iter().all(|(byte, mask)| self.0[*byte] & mask != 0)
+	}
+
+	/// Whether the bloom is empty.
+	pub fn is_empty(&self) -> bool {
+		self.0.iter().all(|byte| *byte == 0)
+	}
+}
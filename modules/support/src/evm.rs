@@ -0,0 +1,131 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Helpers to encode the input and decode the output of EVM contract calls made by native
+//! modules. Only static ABI types are supported, each one takes a 32 bytes word.
+
+use primitives::evm::EvmAddress;
+use sp_core::{H256, U256};
+use sp_runtime::DispatchError;
+use sp_std::{convert::TryInto, prelude::*};
+
+/// The ABI encoded input of an EVM contract call.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct EvmInput(Vec<u8>);
+
+impl EvmInput {
+	/// The input of the function with `selector`.
+	pub fn new(selector: u32) -> Self {
+		Self(selector.to_be_bytes().to_vec())
+	}
+
+	/// The input of the function with `signature`, e.g. `b"transfer(address,uint256)"`.
+	pub fn with_signature(signature: &[u8]) -> Self {
+		let hash = sp_io::hashing::keccak_256(signature);
+		Self(hash[..4].to_vec())
+	}
+
+	/// Append an `address` parameter.
+	pub fn address(self, address: EvmAddress) -> Self {
+		self.bytes32(address.into())
+	}
+
+	/// Append a `uint256` parameter.
+	pub fn u256(mut self, value: U256) -> Self {
+		let mut be_bytes = [0u8; 32];
+		value.to_big_endian(&mut be_bytes);
+		self.0.extend_from_slice(&be_bytes);
+		self
+	}
+
+	/// Append a balance as an `uint256` parameter.
+	pub fn balance(self, value: u128) -> Self {
+		self.u256(value.into())
+	}
+
+	/// Append a `bool` parameter.
+	pub fn bool(self, value: bool) -> Self {
+		self.u256(if value { U256::one() } else { U256::zero() })
+	}
+
+	/// Append a `bytes32` parameter.
+	pub fn bytes32(mut self, value: H256) -> Self {
+		self.0.extend_from_slice(value.as_bytes());
+		self
+	}
+
+	/// The encoded input.
+	pub fn into_inner(self) -> Vec<u8> {
+		self.0
+	}
+}
+
+impl From<EvmInput> for Vec<u8> {
+	fn from(input: EvmInput) -> Self {
+		input.into_inner()
+	}
+}
+
+/// The ABI encoded output of an EVM contract call.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub struct EvmOutput<'a>(&'a [u8]);
+
+impl<'a> EvmOutput<'a> {
+	pub fn new(output: &'a [u8]) -> Self {
+		Self(output)
+	}
+
+	/// The word of the return value at `index`.
+	pub fn bytes32_at(&self, index: usize) -> Result<H256, DispatchError> {
+		let start = index.saturating_mul(32);
+		self.0
+			.get(start..start.saturating_add(32))
+			.map(H256::from_slice)
+			.ok_or(DispatchError::Other("invalid EVM output"))
+	}
+
+	/// The `uint256` return value at `index`.
+	pub fn u256_at(&self, index: usize) -> Result<U256, DispatchError> {
+		self.bytes32_at(index)
+			.map(|word| U256::from_big_endian(word.as_bytes()))
+	}
+
+	/// The `uint256` return value at `index` as a balance.
+	pub fn balance_at(&self, index: usize) -> Result<u128, DispatchError> {
+		self.u256_at(index)?
+			.try_into()
+			.map_err(|_| DispatchError::Other("EVM output balance overflow"))
+	}
+
+	/// The `address` return value at `index`.
+	pub fn address_at(&self, index: usize) -> Result<EvmAddress, DispatchError> {
+		self.bytes32_at(index).map(EvmAddress::from)
+	}
+
+	/// The `bool` return value at `index`.
+	pub fn bool_at(&self, index: usize) -> Result<bool, DispatchError> {
+		let value = self.u256_at(index)?;
+		if value.is_zero() {
+			Ok(false)
+		} else if value == U256::one() {
+			Ok(true)
+		} else {
+			Err(DispatchError::Other("invalid EVM output"))
+		}
+	}
+}
@@ -63,7 +63,9 @@ pub trait PolkadotBridgeCall<AccountId, BlockNumber, Balance, EraIndex>:
 	fn nominate(account_index: u32, targets: Vec<Self::PolkadotAccountId>);
 	fn transfer_to_bridge(account_index: u32, from: &AccountId, amount: Balance) -> DispatchResult;
 	fn receive_from_bridge(account_index: u32, to: &AccountId, amount: Balance) -> DispatchResult;
-	fn payout_stakers(account_index: u32, era: EraIndex);
+	/// Claim the staking rewards of `era` to the free balance of sub account,
+	/// return the claimed amount.
+	fn payout_stakers(account_index: u32, era: EraIndex) -> Balance;
 }
 
 pub trait PolkadotBridgeState<Balance, EraIndex> {
@@ -85,12 +87,66 @@ impl<Balance, CurrencyId> OnCommission<Balance, CurrencyId> for () {
 	fn on_commission(_currency_id: CurrencyId, _amount: Balance) {}
 }
 
+/// Distribute the rewards of liquid currency to the guarantors of validator.
+pub trait ValidatorRewardsDistributor<AccountId, ValidatorId, Balance> {
+	/// Transfer `amount` of liquid currency from `payer` as the rewards for
+	/// the guarantors of `validator`.
+	fn distribute_reward(payer: &AccountId, validator: &ValidatorId, amount: Balance) -> DispatchResult;
+}
+
+impl<AccountId, ValidatorId, Balance> ValidatorRewardsDistributor<AccountId, ValidatorId, Balance> for () {
+	fn distribute_reward(_payer: &AccountId, _validator: &ValidatorId, _amount: Balance) -> DispatchResult {
+		Err(DispatchError::Other("unimplemented validator rewards distributor"))
+	}
+}
+
 pub trait HomaProtocol<AccountId, Balance, EraIndex> {
 	type Balance: Decode + Encode + Debug + Eq + PartialEq + Clone + HasCompact;
 
 	fn mint(who: &AccountId, amount: Balance) -> sp_std::result::Result<Balance, DispatchError>;
+	fn mint_at_next_era(who: &AccountId, amount: Balance) -> DispatchResult;
 	fn redeem_by_unbond(who: &AccountId, amount: Balance) -> DispatchResult;
 	fn redeem_by_free_unbonded(who: &AccountId, amount: Balance) -> DispatchResult;
 	fn redeem_by_claim_unbonding(who: &AccountId, amount: Balance, target_era: EraIndex) -> DispatchResult;
 	fn withdraw_redemption(who: &AccountId) -> sp_std::result::Result<Balance, DispatchError>;
+	fn take_unbonding(who: &AccountId, target_era: EraIndex) -> Balance;
+	fn add_unbonding(who: &AccountId, target_era: EraIndex, amount: Balance);
+	fn update_queued_request(who: &AccountId, index: u32, new_amount: Balance) -> DispatchResult;
+	fn cancel_redeem_by_unbond(who: &AccountId, amount: Balance) -> DispatchResult;
+}
+
+impl<AccountId, Balance, EraIndex> HomaProtocol<AccountId, Balance, EraIndex> for ()
+where
+	Balance: Decode + Encode + Debug + Eq + PartialEq + Clone + HasCompact + Default,
+{
+	type Balance = Balance;
+
+	fn mint(_who: &AccountId, _amount: Balance) -> sp_std::result::Result<Balance, DispatchError> {
+		Err(DispatchError::Other("unimplemented homa protocol"))
+	}
+	fn mint_at_next_era(_who: &AccountId, _amount: Balance) -> DispatchResult {
+		Err(DispatchError::Other("unimplemented homa protocol"))
+	}
+	fn redeem_by_unbond(_who: &AccountId, _amount: Balance) -> DispatchResult {
+		Err(DispatchError::Other("unimplemented homa protocol"))
+	}
+	fn redeem_by_free_unbonded(_who: &AccountId, _amount: Balance) -> DispatchResult {
+		Err(DispatchError::Other("unimplemented homa protocol"))
+	}
+	fn redeem_by_claim_unbonding(_who: &AccountId, _amount: Balance, _target_era: EraIndex) -> DispatchResult {
+		Err(DispatchError::Other("unimplemented homa protocol"))
+	}
+	fn withdraw_redemption(_who: &AccountId) -> sp_std::result::Result<Balance, DispatchError> {
+		Err(DispatchError::Other("unimplemented homa protocol"))
+	}
+	fn take_unbonding(_who: &AccountId, _target_era: EraIndex) -> Balance {
+		Default::default()
+	}
+	fn add_unbonding(_who: &AccountId, _target_era: EraIndex, _amount: Balance) {}
+	fn update_queued_request(_who: &AccountId, _index: u32, _new_amount: Balance) -> DispatchResult {
+		Err(DispatchError::Other("unimplemented homa protocol"))
+	}
+	fn cancel_redeem_by_unbond(_who: &AccountId, _amount: Balance) -> DispatchResult {
+		Err(DispatchError::Other("unimplemented homa protocol"))
+	}
 }
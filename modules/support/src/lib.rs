@@ -20,7 +20,10 @@
 #![allow(clippy::upper_case_acronyms)]
 
 use codec::{Decode, Encode, FullCodec, HasCompact};
-use frame_support::pallet_prelude::{DispatchClass, Pays, Weight};
+use frame_support::{
+	pallet_prelude::{DispatchClass, Pays, Weight},
+	traits::LockIdentifier,
+};
 use primitives::{
 	evm::{CallInfo, EvmAddress},
 	CurrencyId,
@@ -37,11 +40,17 @@ use sp_std::{
 	prelude::*,
 };
 
+pub mod event_topics;
+pub mod evm;
 pub mod homa;
 pub mod mocks;
+pub use event_topics::{
+	event_topic, event_topics_digest_item, find_event_topics_bloom, EventTopicsBloom, EventTopicsRecorder,
+};
+pub use evm::{EvmInput, EvmOutput};
 pub use homa::{
 	HomaProtocol, NomineesProvider, OnCommission, OnNewEra, PolkadotBridge, PolkadotBridgeCall, PolkadotBridgeState,
-	PolkadotBridgeType, PolkadotStakingLedger, PolkadotUnlockChunk,
+	PolkadotBridgeType, PolkadotStakingLedger, PolkadotUnlockChunk, ValidatorRewardsDistributor,
 };
 
 pub type Price = FixedU128;
@@ -224,6 +233,30 @@ where
 	}
 }
 
+/// Manages the trading pairs of DEX.
+pub trait DEXTradingPairManager<CurrencyId> {
+	/// Enable the trading pair of `currency_id_a` and `currency_id_b`, without the provision
+	/// process.
+	fn enable_trading_pair(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> DispatchResult;
+}
+
+impl<CurrencyId> DEXTradingPairManager<CurrencyId> for () {
+	fn enable_trading_pair(_currency_id_a: CurrencyId, _currency_id_b: CurrencyId) -> DispatchResult {
+		Ok(())
+	}
+}
+
+/// Handler for the swaps on DEX.
+pub trait OnSwap<AccountId, CurrencyId, Balance> {
+	/// Called after `who` swapped `supply_amount` of the first currency of `path` to
+	/// `target_amount` of the last currency of `path`.
+	fn on_swap(who: &AccountId, path: &[CurrencyId], supply_amount: Balance, target_amount: Balance);
+}
+
+impl<AccountId, CurrencyId, Balance> OnSwap<AccountId, CurrencyId, Balance> for () {
+	fn on_swap(_who: &AccountId, _path: &[CurrencyId], _supply_amount: Balance, _target_amount: Balance) {}
+}
+
 /// An abstraction of cdp treasury for Honzon Protocol.
 pub trait CDPTreasury<AccountId> {
 	type Balance;
@@ -308,6 +341,28 @@ pub trait EmergencyShutdown {
 	fn is_shutdown() -> bool;
 }
 
+/// The hook of the modules to halt when the emergency shutdown occurs.
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+pub trait OnEmergencyShutdown {
+	fn on_emergency_shutdown();
+}
+
+/// The risk parameters of the collaterals and the system state of Honzon.
+pub trait HonzonInfo<CurrencyId, Balance> {
+	/// The liquidation ratio of the collateral.
+	fn liquidation_ratio(currency_id: CurrencyId) -> Ratio;
+	/// The interest rate per second of the collateral, i.e. the stability fee.
+	fn interest_rate_per_sec(currency_id: CurrencyId) -> Rate;
+	/// The debit value hard cap of the collateral, i.e. the debt ceiling.
+	fn maximum_total_debit_value(currency_id: CurrencyId) -> Balance;
+	/// The total debit value of the collateral.
+	fn total_debit_value(currency_id: CurrencyId) -> Balance;
+	/// The currencies that can be used as collateral.
+	fn collateral_currency_ids() -> Vec<CurrencyId>;
+	/// Whether the system is emergency shutdown.
+	fn is_shutdown() -> bool;
+}
+
 pub trait DEXIncentives<AccountId, CurrencyId, Balance> {
 	fn do_deposit_dex_share(who: &AccountId, lp_currency_id: CurrencyId, amount: Balance) -> DispatchResult;
 	fn do_withdraw_dex_share(who: &AccountId, lp_currency_id: CurrencyId, amount: Balance) -> DispatchResult;
@@ -323,6 +378,80 @@ impl<AccountId, CurrencyId, Balance> DEXIncentives<AccountId, CurrencyId, Balanc
 	}
 }
 
+/// The voting escrow, the voting power of the locked currency decays linearly
+/// to zero at unlock.
+pub trait VotingEscrow<AccountId, Balance> {
+	/// The current voting power of `who`.
+	fn balance_of(who: &AccountId) -> Balance;
+	/// The current total voting power.
+	fn total_supply() -> Balance;
+}
+
+impl<AccountId, Balance: Default> VotingEscrow<AccountId, Balance> for () {
+	fn balance_of(_: &AccountId) -> Balance {
+		Default::default()
+	}
+
+	fn total_supply() -> Balance {
+		Default::default()
+	}
+}
+
+/// Provide the voting power of accounts for governance, e.g. the gauge
+/// weight voting.
+pub trait VotingPowerProvider<AccountId, Balance> {
+	/// The current voting power of `who`.
+	fn voting_power(who: &AccountId) -> Balance;
+	/// Lock the balances counted in the voting power of `who` under
+	/// `lock_id`, so they can't be moved to another account and counted
+	/// again while the vote is active.
+	fn lock_voting_power(lock_id: LockIdentifier, who: &AccountId) -> DispatchResult;
+	/// Remove the lock of the voting power of `who` under `lock_id`.
+	fn unlock_voting_power(lock_id: LockIdentifier, who: &AccountId) -> DispatchResult;
+}
+
+impl<AccountId, Balance: Default> VotingPowerProvider<AccountId, Balance> for () {
+	fn voting_power(_: &AccountId) -> Balance {
+		Default::default()
+	}
+
+	fn lock_voting_power(_: LockIdentifier, _: &AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	fn unlock_voting_power(_: LockIdentifier, _: &AccountId) -> DispatchResult {
+		Ok(())
+	}
+}
+
+/// Manage the incentive reward amounts of the pools.
+pub trait IncentivesManager<PoolId, Balance> {
+	/// Set the fixed incentive reward amount per period of the pool.
+	fn set_incentive_reward_amount(pool_id: &PoolId, amount: Balance) -> DispatchResult;
+}
+
+impl<PoolId, Balance> IncentivesManager<PoolId, Balance> for () {
+	fn set_incentive_reward_amount(_: &PoolId, _: Balance) -> DispatchResult {
+		Ok(())
+	}
+}
+
+pub trait IncentivesRewards<AccountId, PoolId, CurrencyId, Balance> {
+	/// Claim the rewards of `who` in the pool, include the extra reward
+	/// currencies.
+	fn claim_rewards(who: &AccountId, pool_id: &PoolId);
+	/// The pending rewards of `who` in the pool of every reward currency,
+	/// after the payout deduction.
+	fn pending_rewards(who: &AccountId, pool_id: &PoolId) -> Vec<(CurrencyId, Balance)>;
+}
+
+impl<AccountId, PoolId, CurrencyId, Balance> IncentivesRewards<AccountId, PoolId, CurrencyId, Balance> for () {
+	fn claim_rewards(_: &AccountId, _: &PoolId) {}
+	fn pending_rewards(_: &AccountId, _: &PoolId) -> Vec<(CurrencyId, Balance)> {
+		Default::default()
+	}
+}
+
 /// Return true if the call of EVM precompile contract is allowed.
 pub trait PrecompileCallerFilter {
 	fn is_allowed(caller: H160) -> bool;
@@ -365,6 +494,33 @@ pub struct InvokeContext {
 	pub origin: EvmAddress,
 }
 
+/// Call EVM contracts from native modules. The call fails and its changes are reverted if
+/// the execution doesn't succeed.
+pub trait EVMCall<AccountId, Balance> {
+	/// Call `contract` from `who` with the ABI encoded `input`, and return the output.
+	fn call_evm(
+		who: &AccountId,
+		contract: EvmAddress,
+		input: Vec<u8>,
+		value: Balance,
+		gas_limit: u64,
+		storage_limit: u32,
+	) -> Result<Vec<u8>, DispatchError>;
+}
+
+impl<AccountId, Balance> EVMCall<AccountId, Balance> for () {
+	fn call_evm(
+		_who: &AccountId,
+		_contract: EvmAddress,
+		_input: Vec<u8>,
+		_value: Balance,
+		_gas_limit: u64,
+		_storage_limit: u32,
+	) -> Result<Vec<u8>, DispatchError> {
+		Err(DispatchError::Other("unimplemented evm call"))
+	}
+}
+
 /// An abstraction of EVMBridge
 pub trait EVMBridge<AccountId, Balance> {
 	/// Execute ERC20.name() to read token name from ERC20 contract
@@ -386,6 +542,31 @@ pub trait EVMBridge<AccountId, Balance> {
 	fn set_origin(origin: AccountId);
 }
 
+/// ERC20 style allowances for multi currencies, used by EVM token contracts.
+pub trait MultiCurrencyAllowance<AccountId> {
+	type CurrencyId;
+	type Balance;
+
+	/// The amount `spender` is still allowed to transfer on behalf of `owner`.
+	fn allowance(currency_id: Self::CurrencyId, owner: &AccountId, spender: &AccountId) -> Self::Balance;
+	/// Set the amount `spender` is allowed to transfer on behalf of `owner`.
+	fn approve(
+		currency_id: Self::CurrencyId,
+		owner: &AccountId,
+		spender: &AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult;
+	/// Transfer `amount` from `from` to `to` on behalf of `spender`, consuming
+	/// the allowance.
+	fn transfer_from(
+		currency_id: Self::CurrencyId,
+		spender: &AccountId,
+		from: &AccountId,
+		to: &AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult;
+}
+
 #[cfg(feature = "std")]
 impl<AccountId, Balance: Default> EVMBridge<AccountId, Balance> for () {
 	fn name(_context: InvokeContext) -> Result<Vec<u8>, DispatchError> {
@@ -430,6 +611,9 @@ pub trait EVMStateRentTrait<AccountId, Balance> {
 
 pub trait TransactionPayment<AccountId, Balance, NegativeImbalance> {
 	fn reserve_fee(who: &AccountId, weight: Weight) -> Result<Balance, DispatchError>;
+	/// Reserve the fees of `count` dispatches of `weight`, returning the fee
+	/// of a single dispatch.
+	fn reserve_fees(who: &AccountId, weight: Weight, count: u32) -> Result<Balance, DispatchError>;
 	fn unreserve_fee(who: &AccountId, fee: Balance);
 	fn unreserve_and_charge_fee(
 		who: &AccountId,
@@ -444,6 +628,27 @@ pub trait TransactionPayment<AccountId, Balance, NegativeImbalance> {
 		pays_fee: Pays,
 		class: DispatchClass,
 	) -> Result<(), TransactionValidityError>;
+	/// The fee charged for `weight`, in native currency.
+	fn weight_to_fee(weight: Weight) -> Balance;
+	/// The amount of `currency_id` swapped by DEX to pay `fee` of native
+	/// currency, `None` if it can't be swapped.
+	fn fee_in_currency(currency_id: CurrencyId, fee: Balance) -> Option<Balance>;
+}
+
+/// Handler for the transaction fees paid by accounts.
+pub trait OnTransactionFeePaid<AccountId, Balance> {
+	/// Called after `who` paid `fee` of native currency, excluding the tip.
+	fn on_fee_paid(who: &AccountId, fee: Balance);
+
+	/// Called after `who` swapped `supply_amount` of the first currency of `path` to
+	/// `target_amount` of native currency on DEX to pay the transaction fee.
+	fn on_fee_swapped(who: &AccountId, path: &[CurrencyId], supply_amount: Balance, target_amount: Balance);
+}
+
+impl<AccountId, Balance> OnTransactionFeePaid<AccountId, Balance> for () {
+	fn on_fee_paid(_who: &AccountId, _fee: Balance) {}
+
+	fn on_fee_swapped(_who: &AccountId, _path: &[CurrencyId], _supply_amount: Balance, _target_amount: Balance) {}
 }
 
 #[cfg(feature = "std")]
@@ -456,6 +661,10 @@ impl<AccountId, Balance: Default + Copy, NegativeImbalance: Imbalance<Balance>>
 		Ok(Default::default())
 	}
 
+	fn reserve_fees(_who: &AccountId, _weight: Weight, _count: u32) -> Result<Balance, DispatchError> {
+		Ok(Default::default())
+	}
+
 	fn unreserve_fee(_who: &AccountId, _fee: Balance) {}
 
 	fn unreserve_and_charge_fee(
@@ -483,6 +692,14 @@ impl<AccountId, Balance: Default + Copy, NegativeImbalance: Imbalance<Balance>>
 	) -> Result<(), TransactionValidityError> {
 		Ok(())
 	}
+
+	fn weight_to_fee(_weight: Weight) -> Balance {
+		Default::default()
+	}
+
+	fn fee_in_currency(_currency_id: CurrencyId, fee: Balance) -> Option<Balance> {
+		Some(fee)
+	}
 }
 
 pub trait Contains<T> {
@@ -569,6 +786,143 @@ impl CurrencyIdMapping for () {
 	}
 }
 
+/// The metadata of an asset registered on chain.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct AssetMetadata<Balance> {
+	pub name: Vec<u8>,
+	pub symbol: Vec<u8>,
+	pub decimals: u8,
+	pub minimal_balance: Balance,
+}
+
+/// Provides the metadata of the assets registered on chain.
+pub trait AssetMetadataProvider<Balance> {
+	/// Returns the AssetMetadata associated with a given CurrencyId.
+	fn get_asset_metadata(currency_id: CurrencyId) -> Option<AssetMetadata<Balance>>;
+}
+
+impl<Balance> AssetMetadataProvider<Balance> for () {
+	fn get_asset_metadata(_currency_id: CurrencyId) -> Option<AssetMetadata<Balance>> {
+		None
+	}
+}
+
+/// Checks whether the funds of accounts are frozen and can't be moved out.
+pub trait FreezeChecker<AccountId> {
+	/// Returns `Err` if `currency_id` of `who` is frozen.
+	fn ensure_not_frozen(currency_id: CurrencyId, who: &AccountId) -> DispatchResult;
+
+	/// Returns `Err` if any currency of `who` is frozen.
+	fn ensure_no_frozen_currency(who: &AccountId) -> DispatchResult;
+}
+
+impl<AccountId> FreezeChecker<AccountId> for () {
+	fn ensure_not_frozen(_currency_id: CurrencyId, _who: &AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	fn ensure_no_frozen_currency(_who: &AccountId) -> DispatchResult {
+		Ok(())
+	}
+}
+
+/// The assets wrapped by a bridge, which are minted when the assets are locked on the external
+/// chain, and burnt to release them on the external chain.
+pub trait BridgeAsset<AccountId, Balance> {
+	/// Mint `amount` of the wrapped `currency_id` to `who`.
+	fn mint(currency_id: CurrencyId, who: &AccountId, amount: Balance) -> DispatchResult;
+
+	/// Burn `amount` of the wrapped `currency_id` from `who`.
+	fn burn(currency_id: CurrencyId, who: &AccountId, amount: Balance) -> DispatchResult;
+}
+
+/// Checks whether the bridged assets can be minted, the bridges must call it before minting.
+pub trait MintGuard {
+	/// Returns `Err` if the mints of `currency_id` are paused, e.g. its reserve falls short.
+	fn ensure_can_mint(currency_id: CurrencyId) -> DispatchResult;
+}
+
+impl MintGuard for () {
+	fn ensure_can_mint(_currency_id: CurrencyId) -> DispatchResult {
+		Ok(())
+	}
+}
+
+/// Reads the relaychain state, verified by the storage proofs against the relaychain storage roots
+/// followed on chain.
+pub trait RelaychainStateVerifier<BlockNumber> {
+	/// The latest followed relaychain block number, `None` if relaychain is not followed.
+	fn latest_relaychain_block_number() -> Option<BlockNumber>;
+
+	/// Returns the value of `key` in the relaychain state at `relaychain_block_number` proved by
+	/// `proof`, `None` if the key doesn't exist.
+	fn verify_relay_state(
+		relaychain_block_number: BlockNumber,
+		key: &[u8],
+		proof: Vec<Vec<u8>>,
+	) -> Result<Option<Vec<u8>>, DispatchError>;
+}
+
+impl<BlockNumber> RelaychainStateVerifier<BlockNumber> for () {
+	fn latest_relaychain_block_number() -> Option<BlockNumber> {
+		None
+	}
+
+	fn verify_relay_state(
+		_relaychain_block_number: BlockNumber,
+		_key: &[u8],
+		_proof: Vec<Vec<u8>>,
+	) -> Result<Option<Vec<u8>>, DispatchError> {
+		Err(DispatchError::Other("relaychain is not followed"))
+	}
+}
+
+/// The result of dispatching a background task within the given weight.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct TaskResult {
+	/// The result of the dispatch, the changes and the progress of a failed dispatch are reverted
+	/// and the task is dispatched again later.
+	pub result: DispatchResult,
+	/// The weight used by the dispatch.
+	pub used_weight: Weight,
+	/// Whether the task is finished, an unfinished task is dispatched again later.
+	pub finished: bool,
+}
+
+/// A background task, e.g. a cleanup of unbounded storage, which is dispatched in chunks within
+/// the weight left in blocks.
+pub trait DispatchableTask {
+	/// Dispatch the task using at most `weight`, the progress of an unfinished task is kept in
+	/// itself for the next dispatch. The task must be finished when `weight` is unlimited.
+	fn dispatch(&mut self, weight: Weight) -> TaskResult;
+}
+
+/// The queue of the background tasks dispatched in the weight left in blocks.
+pub trait IdleScheduler<Task> {
+	/// Schedule `task` to be dispatched in the following blocks. Returns the used weight, which
+	/// includes the dispatches of the task if it's dispatched right away.
+	fn schedule(task: Task) -> Result<Weight, DispatchError>;
+}
+
+/// Dispatches the task right away until it's finished, for the runtimes without idle scheduler.
+impl<Task: DispatchableTask> IdleScheduler<Task> for () {
+	fn schedule(mut task: Task) -> Result<Weight, DispatchError> {
+		let mut used_weight: Weight = 0;
+		loop {
+			let TaskResult {
+				result,
+				used_weight: dispatch_weight,
+				finished,
+			} = task.dispatch(Weight::max_value());
+			used_weight = used_weight.saturating_add(dispatch_weight);
+			result?;
+			if finished {
+				return Ok(used_weight);
+			}
+		}
+	}
+}
+
 /// Used to interface with the Compound's Cash module
 pub trait CompoundCashTrait<Balance, Moment> {
 	fn set_future_yield(next_cash_yield: Balance, yield_index: u128, timestamp_effective: Moment) -> DispatchResult;
@@ -0,0 +1,273 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Transaction Pause Module
+//!
+//! ## Overview
+//!
+//! The update origin can pause the transactions, e.g. to stop an exploit
+//! while the fix is on the way. It can pause all the calls of a pallet, a
+//! call, the calls whose names start with a prefix, or the EVM calls to a
+//! contract, each with an optional expiry after which the pause lifts
+//! automatically, unless it's renewed by pausing again.
+//!
+//! The paused calls are filtered by `NonPausedTransactionFilter`, which
+//! should be part of the `BaseCallFilter` of the runtime. The paused
+//! contracts are filtered by `NonPausedContractFilter`, which should be the
+//! `ContractFilter` of the EVM module, so they can't be called in any call
+//! frame, e.g. by other contracts or the precompiles.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{
+	pallet_prelude::*,
+	traits::{Filter, GetCallMetadata, PalletInfo},
+	transactional,
+};
+use frame_system::pallet_prelude::*;
+use primitives::evm::EvmAddress;
+use sp_runtime::RuntimeDebug;
+use sp_std::{marker::PhantomData, prelude::*};
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+/// The calls to pause.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum PauseFilter {
+	/// All the calls of the pallet.
+	Pallet(Vec<u8>),
+	/// The call of the pallet, by the names of the pallet and the call.
+	Call(Vec<u8>, Vec<u8>),
+	/// The calls of the pallet whose names start with the prefix.
+	CallPrefix(Vec<u8>, Vec<u8>),
+	/// The EVM calls to the contract.
+	EvmContract(EvmAddress),
+}
+
+/// The pause of the calls.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct PauseInfo<BlockNumber> {
+	/// The block number the pause expires at, `None` if it never expires.
+	pub expiry: Option<BlockNumber>,
+}
+
+impl<BlockNumber: PartialOrd> PauseInfo<BlockNumber> {
+	/// Whether the pause is in effect at `now`.
+	pub fn is_active(&self, now: BlockNumber) -> bool {
+		self.expiry.as_ref().map_or(true, |expiry| now < *expiry)
+	}
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The origin which may pause and unpause the transactions.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The transactions of this module can't be paused
+		CannotPause,
+		/// The expiry is not in the future
+		InvalidExpiry,
+		/// The calls are not paused
+		NotPaused,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Paused the transactions. \[filter, expiry\]
+		TransactionPaused(PauseFilter, Option<T::BlockNumber>),
+		/// Unpaused the transactions. \[filter\]
+		TransactionUnpaused(PauseFilter),
+	}
+
+	/// The paused pallets.
+	///
+	/// PausedPallets: map Vec<u8> => Option<PauseInfo>
+	#[pallet::storage]
+	#[pallet::getter(fn paused_pallets)]
+	pub type PausedPallets<T: Config> = StorageMap<_, Twox64Concat, Vec<u8>, PauseInfo<T::BlockNumber>, OptionQuery>;
+
+	/// The paused calls, by the names of the pallet and the call.
+	///
+	/// PausedTransactions: double_map Vec<u8>, Vec<u8> => Option<PauseInfo>
+	#[pallet::storage]
+	#[pallet::getter(fn paused_transactions)]
+	pub type PausedTransactions<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, Vec<u8>, Twox64Concat, Vec<u8>, PauseInfo<T::BlockNumber>, OptionQuery>;
+
+	/// The paused prefixes of the names of the calls of the pallets.
+	///
+	/// PausedCallPrefixes: double_map Vec<u8>, Vec<u8> => Option<PauseInfo>
+	#[pallet::storage]
+	#[pallet::getter(fn paused_call_prefixes)]
+	pub type PausedCallPrefixes<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, Vec<u8>, Twox64Concat, Vec<u8>, PauseInfo<T::BlockNumber>, OptionQuery>;
+
+	/// The contracts whose EVM calls are paused.
+	///
+	/// PausedContracts: map EvmAddress => Option<PauseInfo>
+	#[pallet::storage]
+	#[pallet::getter(fn paused_contracts)]
+	pub type PausedContracts<T: Config> =
+		StorageMap<_, Twox64Concat, EvmAddress, PauseInfo<T::BlockNumber>, OptionQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Pause the calls of `filter`, or renew the existing pause.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `filter`: the calls to pause.
+		/// - `expiry`: the block number the pause expires at, `None` if it never expires.
+		#[pallet::weight(T::WeightInfo::pause_transaction())]
+		#[transactional]
+		pub fn pause_transaction(
+			origin: OriginFor<T>,
+			filter: PauseFilter,
+			expiry: Option<T::BlockNumber>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if let Some(pallet_name) = Self::pallet_name_of(&filter) {
+				ensure!(!Self::is_self(pallet_name), Error::<T>::CannotPause);
+			}
+			if let Some(expiry) = expiry {
+				ensure!(
+					expiry > frame_system::Pallet::<T>::block_number(),
+					Error::<T>::InvalidExpiry
+				);
+			}
+
+			let info = PauseInfo { expiry };
+			match &filter {
+				PauseFilter::Pallet(pallet_name) => PausedPallets::<T>::insert(pallet_name, info),
+				PauseFilter::Call(pallet_name, function_name) => {
+					PausedTransactions::<T>::insert(pallet_name, function_name, info)
+				}
+				PauseFilter::CallPrefix(pallet_name, prefix) => {
+					PausedCallPrefixes::<T>::insert(pallet_name, prefix, info)
+				}
+				PauseFilter::EvmContract(address) => PausedContracts::<T>::insert(address, info),
+			}
+
+			Self::deposit_event(Event::TransactionPaused(filter, expiry));
+			Ok(().into())
+		}
+
+		/// Unpause the calls of `filter`.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `filter`: the paused calls.
+		#[pallet::weight(T::WeightInfo::unpause_transaction())]
+		#[transactional]
+		pub fn unpause_transaction(origin: OriginFor<T>, filter: PauseFilter) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			let removed = match &filter {
+				PauseFilter::Pallet(pallet_name) => PausedPallets::<T>::take(pallet_name),
+				PauseFilter::Call(pallet_name, function_name) => {
+					PausedTransactions::<T>::take(pallet_name, function_name)
+				}
+				PauseFilter::CallPrefix(pallet_name, prefix) => PausedCallPrefixes::<T>::take(pallet_name, prefix),
+				PauseFilter::EvmContract(address) => PausedContracts::<T>::take(address),
+			};
+			ensure!(removed.is_some(), Error::<T>::NotPaused);
+
+			Self::deposit_event(Event::TransactionUnpaused(filter));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	fn pallet_name_of(filter: &PauseFilter) -> Option<&[u8]> {
+		match filter {
+			PauseFilter::Pallet(pallet_name)
+			| PauseFilter::Call(pallet_name, _)
+			| PauseFilter::CallPrefix(pallet_name, _) => Some(pallet_name),
+			PauseFilter::EvmContract(_) => None,
+		}
+	}
+
+	fn is_self(pallet_name: &[u8]) -> bool {
+		<T as frame_system::Config>::PalletInfo::name::<Self>().map_or(false, |name| name.as_bytes() == pallet_name)
+	}
+
+	/// Whether the call `function_name` of the pallet `pallet_name` is paused
+	/// now.
+	pub fn is_paused(pallet_name: &[u8], function_name: &[u8]) -> bool {
+		let now = frame_system::Pallet::<T>::block_number();
+		Self::paused_pallets(pallet_name).map_or(false, |pause| pause.is_active(now))
+			|| Self::paused_transactions(pallet_name, function_name).map_or(false, |pause| pause.is_active(now))
+			|| PausedCallPrefixes::<T>::iter_prefix(pallet_name)
+				.any(|(prefix, pause)| function_name.starts_with(&prefix) && pause.is_active(now))
+	}
+
+	/// Whether the EVM calls to `address` are paused now.
+	pub fn is_contract_paused(address: &EvmAddress) -> bool {
+		Self::paused_contracts(address).map_or(false, |pause| {
+			pause.is_active(frame_system::Pallet::<T>::block_number())
+		})
+	}
+}
+
+/// The filter of the calls that are not paused.
+pub struct NonPausedTransactionFilter<T>(PhantomData<T>);
+
+impl<T: Config> Filter<<T as frame_system::Config>::Call> for NonPausedTransactionFilter<T>
+where
+	<T as frame_system::Config>::Call: GetCallMetadata,
+{
+	fn filter(call: &<T as frame_system::Config>::Call) -> bool {
+		let metadata = call.get_call_metadata();
+		!Pallet::<T>::is_paused(metadata.pallet_name.as_bytes(), metadata.function_name.as_bytes())
+	}
+}
+
+/// The filter of the contracts that are not paused.
+pub struct NonPausedContractFilter<T>(PhantomData<T>);
+
+impl<T: Config> Filter<EvmAddress> for NonPausedContractFilter<T> {
+	fn filter(address: &EvmAddress) -> bool {
+		!Pallet::<T>::is_contract_paused(address)
+	}
+}
@@ -0,0 +1,207 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the transaction pause module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Call, Event, *};
+use sp_runtime::traits::{BadOrigin, Dispatchable};
+
+fn remark_call() -> Call {
+	Call::System(frame_system::Call::remark(vec![]))
+}
+
+fn remark_with_event_call() -> Call {
+	Call::System(frame_system::Call::remark_with_event(vec![]))
+}
+
+fn set_heap_pages_call() -> Call {
+	Call::System(frame_system::Call::set_heap_pages(1))
+}
+
+fn is_allowed(call: Call) -> bool {
+	NonPausedTransactionFilter::<Runtime>::filter(&call)
+}
+
+#[test]
+fn pause_transaction_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let filter = PauseFilter::Call(b"System".to_vec(), b"remark".to_vec());
+
+		assert_noop!(
+			TransactionPause::pause_transaction(Origin::signed(BOB), filter.clone(), None),
+			BadOrigin
+		);
+		assert_noop!(
+			TransactionPause::pause_transaction(Origin::signed(ALICE), filter.clone(), Some(1)),
+			Error::<Runtime>::InvalidExpiry
+		);
+		assert_noop!(
+			TransactionPause::pause_transaction(
+				Origin::signed(ALICE),
+				PauseFilter::Pallet(b"TransactionPause".to_vec()),
+				None
+			),
+			Error::<Runtime>::CannotPause
+		);
+		assert_noop!(
+			TransactionPause::pause_transaction(
+				Origin::signed(ALICE),
+				PauseFilter::CallPrefix(b"TransactionPause".to_vec(), b"".to_vec()),
+				None
+			),
+			Error::<Runtime>::CannotPause
+		);
+
+		assert!(is_allowed(remark_call()));
+		assert_ok!(TransactionPause::pause_transaction(
+			Origin::signed(ALICE),
+			filter.clone(),
+			None
+		));
+		System::assert_last_event(Event::TransactionPause(crate::Event::TransactionPaused(filter, None)));
+		assert_eq!(
+			TransactionPause::paused_transactions(b"System".to_vec(), b"remark".to_vec()),
+			Some(PauseInfo { expiry: None })
+		);
+		assert!(!is_allowed(remark_call()));
+		assert!(is_allowed(remark_with_event_call()));
+		assert!(is_allowed(set_heap_pages_call()));
+	});
+}
+
+#[test]
+fn pause_pallet_and_call_prefix_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let prefix_filter = PauseFilter::CallPrefix(b"System".to_vec(), b"remark".to_vec());
+		assert_ok!(TransactionPause::pause_transaction(
+			Origin::signed(ALICE),
+			prefix_filter.clone(),
+			None
+		));
+		assert!(!is_allowed(remark_call()));
+		assert!(!is_allowed(remark_with_event_call()));
+		assert!(is_allowed(set_heap_pages_call()));
+
+		let pallet_filter = PauseFilter::Pallet(b"System".to_vec());
+		assert_ok!(TransactionPause::pause_transaction(
+			Origin::signed(ALICE),
+			pallet_filter.clone(),
+			None
+		));
+		assert!(!is_allowed(set_heap_pages_call()));
+
+		assert_ok!(TransactionPause::unpause_transaction(
+			Origin::signed(ALICE),
+			pallet_filter
+		));
+		assert!(is_allowed(set_heap_pages_call()));
+		assert!(!is_allowed(remark_with_event_call()));
+
+		assert_ok!(TransactionPause::unpause_transaction(
+			Origin::signed(ALICE),
+			prefix_filter.clone()
+		));
+		System::assert_last_event(Event::TransactionPause(crate::Event::TransactionUnpaused(
+			prefix_filter.clone(),
+		)));
+		assert!(is_allowed(remark_call()));
+		assert!(is_allowed(remark_with_event_call()));
+
+		assert_noop!(
+			TransactionPause::unpause_transaction(Origin::signed(BOB), prefix_filter.clone()),
+			BadOrigin
+		);
+		assert_noop!(
+			TransactionPause::unpause_transaction(Origin::signed(ALICE), prefix_filter),
+			Error::<Runtime>::NotPaused
+		);
+	});
+}
+
+#[test]
+fn pause_contract_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let contract = EvmAddress::from_low_u64_be(1);
+		assert!(!TransactionPause::is_contract_paused(&contract));
+
+		assert_ok!(TransactionPause::pause_transaction(
+			Origin::signed(ALICE),
+			PauseFilter::EvmContract(contract),
+			None
+		));
+		assert!(TransactionPause::is_contract_paused(&contract));
+		assert!(!TransactionPause::is_contract_paused(&EvmAddress::from_low_u64_be(2)));
+		assert!(!NonPausedContractFilter::<Runtime>::filter(&contract));
+		assert!(NonPausedContractFilter::<Runtime>::filter(
+			&EvmAddress::from_low_u64_be(2)
+		));
+
+		assert_ok!(TransactionPause::unpause_transaction(
+			Origin::signed(ALICE),
+			PauseFilter::EvmContract(contract)
+		));
+		assert!(!TransactionPause::is_contract_paused(&contract));
+	});
+}
+
+#[test]
+fn pause_expires_unless_renewed() {
+	ExtBuilder::default().build().execute_with(|| {
+		let filter = PauseFilter::Pallet(b"System".to_vec());
+		assert_ok!(TransactionPause::pause_transaction(
+			Origin::signed(ALICE),
+			filter.clone(),
+			Some(10)
+		));
+		System::assert_last_event(Event::TransactionPause(crate::Event::TransactionPaused(
+			filter.clone(),
+			Some(10),
+		)));
+
+		System::set_block_number(9);
+		assert!(!is_allowed(remark_call()));
+
+		// renew the pause
+		assert_ok!(TransactionPause::pause_transaction(
+			Origin::signed(ALICE),
+			filter,
+			Some(20)
+		));
+		System::set_block_number(10);
+		assert!(!is_allowed(remark_call()));
+
+		System::set_block_number(20);
+		assert!(is_allowed(remark_call()));
+	});
+}
+
+#[test]
+fn paused_calls_are_filtered() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(TransactionPause::pause_transaction(
+			Origin::signed(ALICE),
+			PauseFilter::Call(b"System".to_vec(), b"remark".to_vec()),
+			None
+		));
+		assert_noop!(remark_call().dispatch(Origin::signed(BOB)), BadOrigin);
+	});
+}
@@ -22,6 +22,27 @@
 //!
 //! Transaction payment module is responsible for charge fee and tip in
 //! different currencies
+//!
+//! A sponsor can pay the fees of another account, e.g. to onboard users
+//! without any ACA. The sponsor signs a permit off-chain which allows the
+//! beneficiary to dispatch a specific call, or the calls of a class, through
+//! `sponsored_call` with the fee charged from the sponsor. The sponsored fees
+//! are limited by the budget set by the sponsor, and each permit can be used
+//! only once as it's bound to the nonce of the sponsor and the beneficiary.
+//!
+//! The update origin can set the fee multipliers of the dispatch classes and
+//! of the pallets, e.g. to make the oracle feeds cheaper or the EVM calls
+//! pricier during congestion. They are applied to the weight fee on top of
+//! `NextFeeMultiplier`, and the multiplier of the pallet is taken from the
+//! outer call of the transaction.
+//!
+//! An account can have a fee tank, the native tokens deposited by itself or
+//! the others, e.g. a dapp operator pre-funding the fees of its users. The
+//! fees are drawn from the fee tank before the free balance, and the refunds
+//! go back to it. The fee tank can't be transferred. The depositors have
+//! shares of the fee tank in proportion to their deposits, the fees are drawn
+//! from all of them pro rata, and each depositor can only withdraw the value
+//! of its own shares, to itself, after `FeeTankWithdrawalDelay`.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
@@ -30,9 +51,11 @@ use frame_support::{
 	dispatch::{DispatchResult, Dispatchable},
 	pallet_prelude::*,
 	traits::{
-		Currency, ExistenceRequirement, Imbalance, NamedReservableCurrency, OnUnbalanced, SameOrOther, WithdrawReasons,
+		Currency, ExistenceRequirement, GetCallMetadata, Imbalance, InstanceFilter, IsSubType, NamedReservableCurrency,
+		OnUnbalanced, SameOrOther, WithdrawReasons,
 	},
-	weights::{DispatchInfo, GetDispatchInfo, Pays, PostDispatchInfo, WeightToFeePolynomial},
+	transactional,
+	weights::{extract_actual_weight, DispatchInfo, GetDispatchInfo, Pays, PostDispatchInfo, WeightToFeePolynomial},
 };
 use frame_system::pallet_prelude::*;
 use orml_traits::MultiCurrency;
@@ -40,17 +63,18 @@ use pallet_transaction_payment_rpc_runtime_api::RuntimeDispatchInfo;
 use pallet_transaction_payment_rpc_runtime_api::{FeeDetails, InclusionFee};
 use primitives::{Balance, CurrencyId, ReserveIdentifier};
 use sp_runtime::{
+	helpers_128bit::multiply_by_rational,
 	traits::{
-		CheckedSub, Convert, DispatchInfoOf, PostDispatchInfoOf, SaturatedConversion, Saturating, SignedExtension,
-		UniqueSaturatedInto, Zero,
+		CheckedMul, CheckedSub, Convert, DispatchInfoOf, Hash, IdentifyAccount, PostDispatchInfoOf,
+		SaturatedConversion, Saturating, SignedExtension, StaticLookup, UniqueSaturatedInto, Verify, Zero,
 	},
 	transaction_validity::{
 		InvalidTransaction, TransactionPriority, TransactionValidity, TransactionValidityError, ValidTransaction,
 	},
-	FixedPointNumber, FixedPointOperand, FixedU128, Perquintill,
+	ArithmeticError, FixedPointNumber, FixedPointOperand, FixedU128, Perquintill, RuntimeDebug,
 };
 use sp_std::{prelude::*, vec};
-use support::{DEXManager, Ratio, TransactionPayment};
+use support::{DEXManager, OnTransactionFeePaid, Ratio, TransactionPayment};
 
 mod mock;
 mod tests;
@@ -65,6 +89,53 @@ pub type Multiplier = FixedU128;
 type PalletBalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 type NegativeImbalanceOf<T> =
 	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::NegativeImbalance;
+pub type SponsorPermitOf<T> = SponsorPermit<
+	<T as frame_system::Config>::AccountId,
+	<T as Config>::SponsorCallClass,
+	<T as frame_system::Config>::Hash,
+	PalletBalanceOf<T>,
+	<T as frame_system::Config>::BlockNumber,
+>;
+
+/// The prefix of the message signed by the sponsor for a permit.
+pub const SPONSOR_PERMIT_PREFIX: &[u8] = b"acala/sponsor-permit";
+
+/// The calls covered by a sponsor permit.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum SponsoredCalls<CallClass, Hash> {
+	/// The call with the hash.
+	Call(Hash),
+	/// The calls of the class.
+	Class(CallClass),
+}
+
+/// The permit signed by the sponsor to pay the fee of the beneficiary.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct SponsorPermit<AccountId, CallClass, Hash, Balance, BlockNumber> {
+	/// The account that pays the fee.
+	pub sponsor: AccountId,
+	/// The account whose fee is paid.
+	pub beneficiary: AccountId,
+	/// The calls covered by the permit.
+	pub calls: SponsoredCalls<CallClass, Hash>,
+	/// The max fee, including tip, the sponsor is willing to pay.
+	pub max_fee: Balance,
+	/// The nonce of the sponsor and the beneficiary, for replay protection.
+	pub nonce: u32,
+	/// The permit can't be used after the block number.
+	pub deadline: BlockNumber,
+}
+
+/// The fee tank of an account, the native tokens reserved by
+/// `FEE_TANK_RESERVE_ID` are owned by the depositors by their shares.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct FeeTank<Balance> {
+	/// The era of the shares, it's bumped by the first deposit after the fee
+	/// tank is drained, which voids the shares of the previous era.
+	pub era: u32,
+	/// The total shares of the depositors in the era.
+	pub total_shares: Balance,
+}
 
 /// A struct to update the weight multiplier per block. It implements
 /// `Convert<Multiplier, Multiplier>`, meaning that it can convert the
@@ -216,9 +287,21 @@ pub mod module {
 	use super::*;
 
 	pub const RESERVE_ID: ReserveIdentifier = ReserveIdentifier::TransactionPayment;
+	pub const FEE_TANK_RESERVE_ID: ReserveIdentifier = ReserveIdentifier::FeeTank;
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The overarching call type.
+		type Call: Parameter
+			+ Dispatchable<Origin = Self::Origin, PostInfo = PostDispatchInfo>
+			+ GetDispatchInfo
+			+ GetCallMetadata
+			+ From<frame_system::Call<Self>>
+			+ IsSubType<Call<Self>>
+			+ IsType<<Self as frame_system::Config>::Call>;
+
 		/// All non-native currency ids in Acala.
 		#[pallet::constant]
 		type AllNonNativeCurrencyIds: Get<Vec<CurrencyId>>;
@@ -265,10 +348,55 @@ pub mod module {
 		#[pallet::constant]
 		type MaxSlippageSwapWithDEX: Get<Ratio>;
 
+		/// Handler for the transaction fees paid by accounts, and the DEX swaps
+		/// made to pay them.
+		type OnFeePaid: OnTransactionFeePaid<Self::AccountId, PalletBalanceOf<Self>>;
+
+		/// The classes of calls that can be sponsored.
+		type SponsorCallClass: Parameter + Member + InstanceFilter<<Self as Config>::Call>;
+
+		/// The public key of the sponsor.
+		type SponsorPublic: IdentifyAccount<AccountId = Self::AccountId>;
+
+		/// The signature of the sponsor for the permit.
+		type SponsorSignature: Parameter + Verify<Signer = Self::SponsorPublic>;
+
+		/// The origin which may update the fee multipliers of the dispatch
+		/// classes and the pallets.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The delay of withdrawing the native tokens from the fee tank.
+		#[pallet::constant]
+		type FeeTankWithdrawalDelay: Get<Self::BlockNumber>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
 
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Updated the budget of the sponsor. \[sponsor, budget\]
+		SponsorBudgetUpdated(T::AccountId, PalletBalanceOf<T>),
+		/// The fee of the beneficiary is paid by the sponsor. \[sponsor, beneficiary, nonce,
+		/// fee\]
+		FeeSponsored(T::AccountId, T::AccountId, u32, PalletBalanceOf<T>),
+		/// Updated the fee multiplier of the dispatch class. \[class,
+		/// multiplier\]
+		ClassFeeMultiplierUpdated(DispatchClass, Option<Multiplier>),
+		/// Updated the fee multiplier of the pallet. \[pallet_name,
+		/// multiplier\]
+		PalletFeeMultiplierUpdated(Vec<u8>, Option<Multiplier>),
+		/// Deposited into the fee tank of account. \[from, who, amount\]
+		FeeTankDeposited(T::AccountId, T::AccountId, PalletBalanceOf<T>),
+		/// Requested the withdrawal of the deposit from the fee tank. \[who,
+		/// depositor, amount, unlock_at\]
+		FeeTankWithdrawalRequested(T::AccountId, T::AccountId, PalletBalanceOf<T>, T::BlockNumber),
+		/// Withdrew the deposit from the fee tank to the depositor. \[who,
+		/// depositor, amount\]
+		FeeTankWithdrawn(T::AccountId, T::AccountId, PalletBalanceOf<T>),
+	}
+
 	#[pallet::type_value]
 	pub fn DefaultFeeMultiplier() -> Multiplier {
 		Multiplier::saturating_from_integer(1)
@@ -288,6 +416,89 @@ pub mod module {
 	#[pallet::getter(fn default_fee_currency_id)]
 	pub type DefaultFeeCurrencyId<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, CurrencyId, OptionQuery>;
 
+	/// The remaining budget of the sponsors for the fees of the others.
+	///
+	/// SponsorBudgets: map AccountId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn sponsor_budgets)]
+	pub type SponsorBudgets<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, PalletBalanceOf<T>, ValueQuery>;
+
+	/// The nonce of the next permit of the sponsor for the beneficiary.
+	///
+	/// SponsorNonces: double_map AccountId, AccountId => u32
+	#[pallet::storage]
+	#[pallet::getter(fn sponsor_nonces)]
+	pub type SponsorNonces<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
+	/// The fee multipliers of the dispatch classes, applied on top of the
+	/// next fee multiplier.
+	///
+	/// ClassFeeMultipliers: map DispatchClass => Option<Multiplier>
+	#[pallet::storage]
+	#[pallet::getter(fn class_fee_multipliers)]
+	pub type ClassFeeMultipliers<T: Config> = StorageMap<_, Twox64Concat, DispatchClass, Multiplier, OptionQuery>;
+
+	/// The fee multipliers of the pallets by their names in the runtime,
+	/// applied on top of the next fee multiplier.
+	///
+	/// PalletFeeMultipliers: map Vec<u8> => Option<Multiplier>
+	#[pallet::storage]
+	#[pallet::getter(fn pallet_fee_multipliers)]
+	pub type PalletFeeMultipliers<T: Config> = StorageMap<_, Twox64Concat, Vec<u8>, Multiplier, OptionQuery>;
+
+	/// The fee tanks, the era and the total shares of the depositors.
+	///
+	/// FeeTanks: map AccountId => FeeTank
+	#[pallet::storage]
+	#[pallet::getter(fn fee_tanks)]
+	pub type FeeTanks<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, FeeTank<PalletBalanceOf<T>>, ValueQuery>;
+
+	/// The shares of the depositors in the fee tanks, and the era of the
+	/// shares.
+	///
+	/// FeeTankShares: double_map AccountId, AccountId => (u32, Balance)
+	#[pallet::storage]
+	#[pallet::getter(fn fee_tank_shares)]
+	pub type FeeTankShares<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		T::AccountId,
+		(u32, PalletBalanceOf<T>),
+		ValueQuery,
+	>;
+
+	/// The pending withdrawals of the depositors from the fee tanks, the
+	/// amount and the block number it's unlocked at.
+	///
+	/// FeeTankWithdrawals: double_map AccountId, AccountId => Option<(Balance,
+	/// BlockNumber)>
+	#[pallet::storage]
+	#[pallet::getter(fn fee_tank_withdrawals)]
+	pub type FeeTankWithdrawals<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		T::AccountId,
+		(PalletBalanceOf<T>, T::BlockNumber),
+		OptionQuery,
+	>;
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The permit is not for the caller or not signed by the sponsor
+		InvalidPermit,
+		/// The deposit in the fee tank is not enough
+		FeeTankNotEnough,
+		/// There's no pending withdrawal from the fee tank
+		FeeTankWithdrawalNotFound,
+		/// The withdrawal from the fee tank is still locked
+		FeeTankWithdrawalLocked,
+	}
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
@@ -364,6 +575,188 @@ pub mod module {
 			}
 			Ok(().into())
 		}
+
+		/// Set the budget of the caller for the fees paid on behalf of the
+		/// others with the permits signed by the caller, zero to stop the
+		/// sponsorship.
+		///
+		/// - `budget`: the max total fees to sponsor.
+		#[pallet::weight(<T as Config>::WeightInfo::set_sponsor_budget())]
+		#[transactional]
+		pub fn set_sponsor_budget(origin: OriginFor<T>, budget: PalletBalanceOf<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			SponsorBudgets::<T>::mutate_exists(&who, |maybe_budget| {
+				*maybe_budget = if budget.is_zero() { None } else { Some(budget) };
+			});
+			Self::deposit_event(Event::SponsorBudgetUpdated(who, budget));
+			Ok(().into())
+		}
+
+		/// Dispatch `call` with the fee paid by the sponsor of `permit`.
+		///
+		/// The permit is checked and consumed by `ChargeTransactionPayment`
+		/// when charging the fee of the transaction, so it only takes effect
+		/// if this is the outer call of the transaction.
+		///
+		/// - `permit`: the permit signed by the sponsor.
+		/// - `signature`: the signature of the sponsor for the permit.
+		/// - `call`: the call to dispatch.
+		#[pallet::weight({
+			let info = call.get_dispatch_info();
+			(
+				<T as Config>::WeightInfo::sponsored_call().saturating_add(info.weight),
+				info.class,
+			)
+		})]
+		pub fn sponsored_call(
+			origin: OriginFor<T>,
+			permit: SponsorPermitOf<T>,
+			signature: T::SponsorSignature,
+			call: Box<<T as Config>::Call>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin.clone())?;
+			ensure!(
+				permit.beneficiary == who && Self::verify_permit(&permit, &signature),
+				Error::<T>::InvalidPermit
+			);
+
+			let info = call.get_dispatch_info();
+			let result = call.dispatch(origin);
+			let weight =
+				<T as Config>::WeightInfo::sponsored_call().saturating_add(extract_actual_weight(&result, &info));
+			result
+				.map_err(|mut err| {
+					err.post_info = Some(weight).into();
+					err
+				})
+				.map(|_| Some(weight).into())
+		}
+
+		/// Set or remove the fee multiplier of the dispatch class.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `class`: the dispatch class.
+		/// - `multiplier`: the fee multiplier, `None` to remove it.
+		#[pallet::weight(<T as Config>::WeightInfo::set_class_fee_multiplier())]
+		#[transactional]
+		pub fn set_class_fee_multiplier(
+			origin: OriginFor<T>,
+			class: DispatchClass,
+			multiplier: Option<Multiplier>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			ClassFeeMultipliers::<T>::mutate_exists(class, |maybe_multiplier| *maybe_multiplier = multiplier);
+			Self::deposit_event(Event::ClassFeeMultiplierUpdated(class, multiplier));
+			Ok(().into())
+		}
+
+		/// Set or remove the fee multiplier of the pallet.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `pallet_name`: the name of the pallet in the runtime, e.g.
+		///   `EVM`.
+		/// - `multiplier`: the fee multiplier, `None` to remove it.
+		#[pallet::weight(<T as Config>::WeightInfo::set_pallet_fee_multiplier())]
+		#[transactional]
+		pub fn set_pallet_fee_multiplier(
+			origin: OriginFor<T>,
+			pallet_name: Vec<u8>,
+			multiplier: Option<Multiplier>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			PalletFeeMultipliers::<T>::mutate_exists(&pallet_name, |maybe_multiplier| *maybe_multiplier = multiplier);
+			Self::deposit_event(Event::PalletFeeMultiplierUpdated(pallet_name, multiplier));
+			Ok(().into())
+		}
+
+		/// Deposit native tokens of the caller into the fee tank of `who`,
+		/// which can only be withdrawn by the caller.
+		///
+		/// - `who`: the owner of the fee tank.
+		/// - `amount`: the amount to deposit.
+		#[pallet::weight(<T as Config>::WeightInfo::deposit_fee_tank())]
+		#[transactional]
+		pub fn deposit_fee_tank(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+			amount: PalletBalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let from = ensure_signed(origin)?;
+			let who = T::Lookup::lookup(who)?;
+
+			if from != who {
+				<T as Config>::Currency::transfer(&from, &who, amount, ExistenceRequirement::KeepAlive)?;
+			}
+			Self::mint_fee_tank_shares(&who, &from, amount);
+			<T as Config>::Currency::reserve_named(&FEE_TANK_RESERVE_ID, &who, amount)?;
+
+			Self::deposit_event(Event::FeeTankDeposited(from, who, amount));
+			Ok(().into())
+		}
+
+		/// Request to withdraw `amount` of the deposit of the caller from the
+		/// fee tank of `who`, which can be withdrawn after
+		/// `FeeTankWithdrawalDelay`. It replaces the pending withdrawal if
+		/// there's one.
+		///
+		/// - `who`: the owner of the fee tank.
+		/// - `amount`: the amount to withdraw.
+		#[pallet::weight(<T as Config>::WeightInfo::request_fee_tank_withdrawal())]
+		#[transactional]
+		pub fn request_fee_tank_withdrawal(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+			amount: PalletBalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let depositor = ensure_signed(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			ensure!(
+				amount <= Self::fee_tank_deposit_of(&who, &depositor),
+				Error::<T>::FeeTankNotEnough
+			);
+
+			let unlock_at = frame_system::Pallet::<T>::block_number().saturating_add(T::FeeTankWithdrawalDelay::get());
+			FeeTankWithdrawals::<T>::insert(&who, &depositor, (amount, unlock_at));
+
+			Self::deposit_event(Event::FeeTankWithdrawalRequested(who, depositor, amount, unlock_at));
+			Ok(().into())
+		}
+
+		/// Withdraw the unlocked withdrawal of the caller from the fee tank of
+		/// `who` to the free balance of the caller, capped by the current value
+		/// of its deposit.
+		///
+		/// - `who`: the owner of the fee tank.
+		#[pallet::weight(<T as Config>::WeightInfo::withdraw_fee_tank())]
+		#[transactional]
+		pub fn withdraw_fee_tank(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			let depositor = ensure_signed(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			let (amount, unlock_at) =
+				Self::fee_tank_withdrawals(&who, &depositor).ok_or(Error::<T>::FeeTankWithdrawalNotFound)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() >= unlock_at,
+				Error::<T>::FeeTankWithdrawalLocked
+			);
+
+			FeeTankWithdrawals::<T>::remove(&who, &depositor);
+			let withdrawn = Self::burn_fee_tank_shares(&who, &depositor, amount);
+			<T as Config>::Currency::unreserve_named(&FEE_TANK_RESERVE_ID, &who, withdrawn);
+			if depositor != who {
+				<T as Config>::Currency::transfer(&who, &depositor, withdrawn, ExistenceRequirement::AllowDeath)?;
+			}
+
+			Self::deposit_event(Event::FeeTankWithdrawn(who, depositor, withdrawn));
+			Ok(().into())
+		}
 	}
 }
 
@@ -413,7 +806,7 @@ where
 		len: u32,
 	) -> FeeDetails<PalletBalanceOf<T>>
 	where
-		T::Call: Dispatchable<Info = DispatchInfo>,
+		<T as frame_system::Config>::Call: Dispatchable<Info = DispatchInfo>,
 	{
 		let dispatch_info = <Extrinsic as GetDispatchInfo>::get_dispatch_info(&unchecked_extrinsic);
 		Self::compute_fee_details(len, &dispatch_info, 0u32.into())
@@ -422,13 +815,20 @@ where
 	/// Compute the fee details for a particular transaction.
 	pub fn compute_fee_details(
 		len: u32,
-		info: &DispatchInfoOf<T::Call>,
+		info: &DispatchInfoOf<<T as frame_system::Config>::Call>,
 		tip: PalletBalanceOf<T>,
 	) -> FeeDetails<PalletBalanceOf<T>>
 	where
-		T::Call: Dispatchable<Info = DispatchInfo>,
+		<T as frame_system::Config>::Call: Dispatchable<Info = DispatchInfo>,
 	{
-		Self::compute_fee_raw(len, info.weight, tip, info.pays_fee, info.class)
+		Self::compute_fee_raw(
+			len,
+			info.weight,
+			tip,
+			info.pays_fee,
+			info.class,
+			Self::fee_multiplier(info.class, None),
+		)
 	}
 
 	/// Compute the final fee value for a particular transaction.
@@ -441,7 +841,7 @@ where
 	///   - `weight_fee`: This amount is computed based on the weight of the transaction. Weight
 	///     accounts for the execution time of a transaction.
 	///   - `targeted_fee_adjustment`: This is a multiplier that can tune the final fee based on the
-	///     congestion of the network.
+	///     congestion of the network, and the fee multiplier of the dispatch class.
 	///   - (Optional) `tip`: If included in the transaction, the tip will be added on top. Only
 	///     signed transactions can have a tip.
 	///
@@ -468,12 +868,12 @@ where
 	/// transaction.
 	pub fn compute_actual_fee_details(
 		len: u32,
-		info: &DispatchInfoOf<T::Call>,
-		post_info: &PostDispatchInfoOf<T::Call>,
+		info: &DispatchInfoOf<<T as frame_system::Config>::Call>,
+		post_info: &PostDispatchInfoOf<<T as frame_system::Config>::Call>,
 		tip: PalletBalanceOf<T>,
 	) -> FeeDetails<PalletBalanceOf<T>>
 	where
-		T::Call: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+		<T as frame_system::Config>::Call: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
 	{
 		Self::compute_fee_raw(
 			len,
@@ -481,6 +881,7 @@ where
 			tip,
 			post_info.pays_fee(info),
 			info.class,
+			Self::fee_multiplier(info.class, None),
 		)
 	}
 
@@ -500,12 +901,35 @@ where
 		Self::compute_actual_fee_details(len, info, post_info, tip).final_fee()
 	}
 
+	/// The multiplier of the weight fee of the calls of `class`, and of the
+	/// pallet `pallet_name` if it's given, on top of the next fee multiplier.
+	pub fn fee_multiplier(class: DispatchClass, pallet_name: Option<&[u8]>) -> Multiplier {
+		let mut multiplier = Self::next_fee_multiplier();
+		if let Some(class_multiplier) = Self::class_fee_multipliers(class) {
+			multiplier = multiplier.saturating_mul(class_multiplier);
+		}
+		if let Some(pallet_multiplier) = pallet_name.and_then(Self::pallet_fee_multipliers) {
+			multiplier = multiplier.saturating_mul(pallet_multiplier);
+		}
+		multiplier
+	}
+
+	/// The multiplier of the weight fee of `call`.
+	pub fn call_fee_multiplier(
+		call: &<T as frame_system::Config>::Call,
+		info: &DispatchInfoOf<<T as frame_system::Config>::Call>,
+	) -> Multiplier {
+		let pallet_name = <T as Config>::Call::from_ref(call).get_call_metadata().pallet_name;
+		Self::fee_multiplier(info.class, Some(pallet_name.as_bytes()))
+	}
+
 	fn compute_fee_raw(
 		len: u32,
 		weight: Weight,
 		tip: PalletBalanceOf<T>,
 		pays_fee: Pays,
 		class: DispatchClass,
+		multiplier: Multiplier,
 	) -> FeeDetails<PalletBalanceOf<T>> {
 		if pays_fee == Pays::Yes {
 			let len = <PalletBalanceOf<T>>::from(len);
@@ -516,7 +940,6 @@ where
 
 			// the adjustable part of the fee.
 			let unadjusted_weight_fee = Self::weight_to_fee(weight);
-			let multiplier = Self::next_fee_multiplier();
 			// final adjusted weight fee.
 			let adjusted_weight_fee = multiplier.saturating_mul_int(unadjusted_weight_fee);
 
@@ -544,9 +967,19 @@ where
 		T::WeightToFee::calc(&capped_weight)
 	}
 
-	pub fn ensure_can_charge_fee(who: &T::AccountId, fee: PalletBalanceOf<T>, reason: WithdrawReasons) {
+	/// The DEX path to swap `currency_id` to native currency for fees.
+	fn fee_trading_path(currency_id: CurrencyId) -> Vec<CurrencyId> {
 		let native_currency_id = T::NativeCurrencyId::get();
 		let stable_currency_id = T::StableCurrencyId::get();
+		if currency_id == stable_currency_id {
+			vec![stable_currency_id, native_currency_id]
+		} else {
+			vec![currency_id, stable_currency_id, native_currency_id]
+		}
+	}
+
+	pub fn ensure_can_charge_fee(who: &T::AccountId, fee: PalletBalanceOf<T>, reason: WithdrawReasons) {
+		let native_currency_id = T::NativeCurrencyId::get();
 		let other_currency_ids = T::AllNonNativeCurrencyIds::get();
 		let mut charge_fee_order: Vec<CurrencyId> =
 			if let Some(default_fee_currency_id) = DefaultFeeCurrencyId::<T>::get(who) {
@@ -574,21 +1007,15 @@ where
 				}
 			} else {
 				// try to use non-native currency to swap native currency by exchange with DEX
-				let trading_path = if currency_id == stable_currency_id {
-					vec![stable_currency_id, native_currency_id]
-				} else {
-					vec![currency_id, stable_currency_id, native_currency_id]
-				};
-
-				if T::DEX::swap_with_exact_target(
+				let path = Self::fee_trading_path(currency_id);
+				if let Ok(supply_amount) = T::DEX::swap_with_exact_target(
 					who,
-					&trading_path,
+					&path,
 					fee.unique_saturated_into(),
 					<T as Config>::MultiCurrency::free_balance(currency_id, who),
 					price_impact_limit,
-				)
-				.is_ok()
-				{
+				) {
+					<T as Config>::OnFeePaid::on_fee_swapped(who, &path, supply_amount.unique_saturated_into(), fee);
 					// successfully swap, break iteration
 					break;
 				}
@@ -597,6 +1024,139 @@ where
 	}
 }
 
+impl<T: Config> Pallet<T> {
+	/// The balance of the fee tank of `who`.
+	pub fn fee_tank_balance(who: &T::AccountId) -> PalletBalanceOf<T> {
+		<T as Config>::Currency::reserved_balance_named(&FEE_TANK_RESERVE_ID, who)
+	}
+
+	/// The value of the deposit of `depositor` in the fee tank of `who`.
+	pub fn fee_tank_deposit_of(who: &T::AccountId, depositor: &T::AccountId) -> PalletBalanceOf<T> {
+		let tank = Self::fee_tanks(who);
+		let (era, shares) = Self::fee_tank_shares(who, depositor);
+		if era != tank.era || tank.total_shares.is_zero() {
+			return Zero::zero();
+		}
+		Self::multiply_balance_by_rational(Self::fee_tank_balance(who), shares, tank.total_shares)
+	}
+
+	/// Add the shares of `amount` to `depositor` in the fee tank of `who`,
+	/// before `amount` is reserved into the fee tank.
+	fn mint_fee_tank_shares(who: &T::AccountId, depositor: &T::AccountId, amount: PalletBalanceOf<T>) {
+		let balance = Self::fee_tank_balance(who);
+		FeeTanks::<T>::mutate(who, |tank| {
+			// the shares of the drained fee tank are worthless, start a new era
+			if balance.is_zero() && !tank.total_shares.is_zero() {
+				tank.era = tank.era.saturating_add(1);
+				tank.total_shares = Zero::zero();
+			}
+
+			let shares = if tank.total_shares.is_zero() {
+				amount
+			} else {
+				Self::multiply_balance_by_rational(amount, tank.total_shares, balance)
+			};
+			tank.total_shares = tank.total_shares.saturating_add(shares);
+			FeeTankShares::<T>::mutate(who, depositor, |(era, depositor_shares)| {
+				if *era != tank.era {
+					*era = tank.era;
+					*depositor_shares = Zero::zero();
+				}
+				*depositor_shares = depositor_shares.saturating_add(shares);
+			});
+		});
+	}
+
+	/// Remove the shares of `amount` from `depositor` in the fee tank of
+	/// `who`, capped by the shares of `depositor`. Returns the value of the
+	/// removed shares to unreserve from the fee tank.
+	fn burn_fee_tank_shares(
+		who: &T::AccountId,
+		depositor: &T::AccountId,
+		amount: PalletBalanceOf<T>,
+	) -> PalletBalanceOf<T> {
+		let balance = Self::fee_tank_balance(who);
+		let mut tank = Self::fee_tanks(who);
+		let (era, shares) = Self::fee_tank_shares(who, depositor);
+		if era != tank.era || tank.total_shares.is_zero() || balance.is_zero() {
+			FeeTankShares::<T>::remove(who, depositor);
+			return Zero::zero();
+		}
+
+		let burned = if amount >= Self::multiply_balance_by_rational(balance, shares, tank.total_shares) {
+			shares
+		} else {
+			Self::multiply_balance_by_rational(amount, tank.total_shares, balance)
+		};
+		let withdrawn = Self::multiply_balance_by_rational(balance, burned, tank.total_shares);
+		tank.total_shares = tank.total_shares.saturating_sub(burned);
+		FeeTanks::<T>::insert(who, tank);
+		if burned == shares {
+			FeeTankShares::<T>::remove(who, depositor);
+		} else {
+			FeeTankShares::<T>::insert(who, depositor, (era, shares.saturating_sub(burned)));
+		}
+		withdrawn
+	}
+
+	fn multiply_balance_by_rational(
+		a: PalletBalanceOf<T>,
+		b: PalletBalanceOf<T>,
+		c: PalletBalanceOf<T>,
+	) -> PalletBalanceOf<T> {
+		multiply_by_rational(
+			a.unique_saturated_into(),
+			b.unique_saturated_into(),
+			c.unique_saturated_into(),
+		)
+		.unwrap_or_default()
+		.unique_saturated_into()
+	}
+
+	/// Whether `signature` is signed by the sponsor of `permit` for this
+	/// chain.
+	pub fn verify_permit(permit: &SponsorPermitOf<T>, signature: &T::SponsorSignature) -> bool {
+		let genesis_hash = frame_system::Pallet::<T>::block_hash(T::BlockNumber::zero());
+		(SPONSOR_PERMIT_PREFIX, genesis_hash, permit)
+			.using_encoded(|message| signature.verify(message, &permit.sponsor))
+	}
+
+	/// The sponsor and the nonce of the permit if `call` of `who` is
+	/// sponsored, and the sponsor can pay the `fee`.
+	fn sponsor_of(
+		who: &T::AccountId,
+		call: &<T as frame_system::Config>::Call,
+		fee: PalletBalanceOf<T>,
+	) -> Result<Option<(T::AccountId, u32)>, TransactionValidityError> {
+		let (permit, signature, call) = match <T as Config>::Call::from_ref(call).is_sub_type() {
+			Some(Call::sponsored_call(permit, signature, call)) => (permit, signature, call),
+			_ => return Ok(None),
+		};
+
+		ensure!(permit.beneficiary == *who, InvalidTransaction::BadSigner);
+		ensure!(
+			frame_system::Pallet::<T>::block_number() <= permit.deadline,
+			InvalidTransaction::Stale
+		);
+		let nonce = Self::sponsor_nonces(&permit.sponsor, who);
+		ensure!(permit.nonce >= nonce, InvalidTransaction::Stale);
+		ensure!(permit.nonce == nonce, InvalidTransaction::Future);
+		ensure!(Self::verify_permit(permit, signature), InvalidTransaction::BadProof);
+
+		let covered = match &permit.calls {
+			SponsoredCalls::Call(hash) => *hash == T::Hashing::hash_of(call),
+			SponsoredCalls::Class(class) => class.filter(call),
+		};
+		ensure!(covered, InvalidTransaction::Call);
+		ensure!(
+			fee <= permit.max_fee && fee <= Self::sponsor_budgets(&permit.sponsor),
+			InvalidTransaction::Payment
+		);
+
+		Ok(Some((permit.sponsor.clone(), nonce)))
+	}
+}
+
 impl<T> Convert<Weight, PalletBalanceOf<T>> for Pallet<T>
 where
 	T: Config,
@@ -639,19 +1199,34 @@ where
 		Self(fee)
 	}
 
+	/// Withdraw the fee from `who`, or from the sponsor if the call is
+	/// sponsored. The fee is drawn from the fee tank of the payer first.
+	/// Returns the fee, the payer, the imbalance of the fee, whether it's
+	/// sponsored and the amount drawn from the fee tank.
 	fn withdraw_fee(
 		&self,
 		who: &T::AccountId,
-		_call: &<T as frame_system::Config>::Call,
+		call: &<T as frame_system::Config>::Call,
 		info: &DispatchInfoOf<<T as frame_system::Config>::Call>,
 		len: usize,
-	) -> Result<(PalletBalanceOf<T>, Option<NegativeImbalanceOf<T>>), TransactionValidityError> {
+		multiplier: Multiplier,
+	) -> Result<
+		(
+			PalletBalanceOf<T>,
+			T::AccountId,
+			Option<NegativeImbalanceOf<T>>,
+			bool,
+			PalletBalanceOf<T>,
+		),
+		TransactionValidityError,
+	> {
 		let tip = self.0;
-		let fee = Pallet::<T>::compute_fee(len as u32, info, tip);
+		let fee = Pallet::<T>::compute_fee_raw(len as u32, info.weight, tip, info.pays_fee, info.class, multiplier)
+			.final_fee();
 
 		// Only mess with balances if fee is not zero.
 		if fee.is_zero() {
-			return Ok((fee, None));
+			return Ok((fee, who.clone(), None, false, Zero::zero()));
 		}
 
 		let reason = if tip.is_zero() {
@@ -660,12 +1235,26 @@ where
 			WithdrawReasons::TRANSACTION_PAYMENT | WithdrawReasons::TIP
 		};
 
-		Pallet::<T>::ensure_can_charge_fee(who, fee, reason);
+		let sponsor = Pallet::<T>::sponsor_of(who, call, fee)?;
+		let payer = sponsor.as_ref().map_or(who, |(sponsor, _)| sponsor);
+
+		// draw the fee from the fee tank first
+		let not_drawn = <T as Config>::Currency::unreserve_named(&FEE_TANK_RESERVE_ID, payer, fee);
+		let from_tank = fee.saturating_sub(not_drawn);
+
+		Pallet::<T>::ensure_can_charge_fee(payer, fee, reason);
 
 		// withdraw native currency as fee
-		match <T as Config>::Currency::withdraw(who, fee, reason, ExistenceRequirement::KeepAlive) {
-			Ok(imbalance) => Ok((fee, Some(imbalance))),
-			Err(_) => Err(InvalidTransaction::Payment.into()),
+		let imbalance = <T as Config>::Currency::withdraw(payer, fee, reason, ExistenceRequirement::KeepAlive)
+			.map_err(|_| InvalidTransaction::Payment)?;
+
+		if let Some((sponsor, nonce)) = sponsor {
+			SponsorBudgets::<T>::mutate(&sponsor, |budget| *budget = budget.saturating_sub(fee));
+			SponsorNonces::<T>::insert(&sponsor, who, nonce.saturating_add(1));
+			Pallet::<T>::deposit_event(Event::FeeSponsored(sponsor.clone(), who.clone(), nonce, fee));
+			Ok((fee, sponsor, Some(imbalance), true, from_tank))
+		} else {
+			Ok((fee, who.clone(), Some(imbalance), false, from_tank))
 		}
 	}
 
@@ -712,6 +1301,9 @@ where
 		Self::AccountId,
 		Option<NegativeImbalanceOf<T>>,
 		PalletBalanceOf<T>,
+		bool,
+		Multiplier,
+		PalletBalanceOf<T>,
 	);
 
 	fn additional_signed(&self) -> sp_std::result::Result<(), TransactionValidityError> {
@@ -725,7 +1317,8 @@ where
 		info: &DispatchInfoOf<Self::Call>,
 		len: usize,
 	) -> TransactionValidity {
-		let (fee, _) = self.withdraw_fee(who, call, info, len)?;
+		let multiplier = Pallet::<T>::call_fee_multiplier(call, info);
+		let (fee, _, _, _, _) = self.withdraw_fee(who, call, info, len, multiplier)?;
 		Ok(ValidTransaction {
 			priority: Self::get_priority(len, info, fee),
 			..Default::default()
@@ -739,8 +1332,9 @@ where
 		info: &DispatchInfoOf<Self::Call>,
 		len: usize,
 	) -> Result<Self::Pre, TransactionValidityError> {
-		let (fee, imbalance) = self.withdraw_fee(who, call, info, len)?;
-		Ok((self.0, who.clone(), imbalance, fee))
+		let multiplier = Pallet::<T>::call_fee_multiplier(call, info);
+		let (fee, payer, imbalance, sponsored, from_tank) = self.withdraw_fee(who, call, info, len, multiplier)?;
+		Ok((self.0, payer, imbalance, fee, sponsored, multiplier, from_tank))
 	}
 
 	fn post_dispatch(
@@ -750,12 +1344,34 @@ where
 		len: usize,
 		_result: &DispatchResult,
 	) -> Result<(), TransactionValidityError> {
-		let (tip, who, imbalance, fee) = pre;
+		let (tip, who, imbalance, fee, sponsored, multiplier, from_tank) = pre;
 		if let Some(payed) = imbalance {
-			let actual_fee = Pallet::<T>::compute_actual_fee(len as u32, info, post_info, tip);
+			let actual_fee = Pallet::<T>::compute_fee_raw(
+				len as u32,
+				post_info.calc_actual_weight(info),
+				tip,
+				post_info.pays_fee(info),
+				info.class,
+				multiplier,
+			)
+			.final_fee();
 			let refund = fee.saturating_sub(actual_fee);
 			let actual_payment = match <T as Config>::Currency::deposit_into_existing(&who, refund) {
 				Ok(refund_imbalance) => {
+					// The refund of the sponsored fee goes back to the budget of the sponsor.
+					if sponsored {
+						SponsorBudgets::<T>::mutate(&who, |budget| {
+							*budget = budget.saturating_add(refund_imbalance.peek())
+						});
+					}
+
+					// The refund of the fee drawn from the fee tank goes back to the fee tank.
+					let _ = <T as Config>::Currency::reserve_named(
+						&FEE_TANK_RESERVE_ID,
+						&who,
+						refund_imbalance.peek().min(from_tank),
+					);
+
 					// The refund cannot be larger than the up front payed max weight.
 					// `PostDispatchInfo::calc_unspent` guards against such a case.
 					match payed.offset(refund_imbalance) {
@@ -769,6 +1385,7 @@ where
 				Err(_) => payed,
 			};
 			let (tip, fee) = actual_payment.split(tip);
+			<T as Config>::OnFeePaid::on_fee_paid(&who, fee.peek());
 
 			// distribute fee
 			<T as Config>::OnTransactionPayment::on_unbalanceds(Some(fee).into_iter().chain(Some(tip)));
@@ -789,6 +1406,14 @@ where
 		Ok(fee)
 	}
 
+	fn reserve_fees(who: &T::AccountId, weight: Weight, count: u32) -> Result<PalletBalanceOf<T>, DispatchError> {
+		let fee = Pallet::<T>::weight_to_fee(weight);
+		let total_fee = fee.checked_mul(&count.into()).ok_or(ArithmeticError::Overflow)?;
+		Pallet::<T>::ensure_can_charge_fee(who, total_fee, WithdrawReasons::TRANSACTION_PAYMENT);
+		<T as Config>::Currency::reserve_named(&RESERVE_ID, &who, total_fee)?;
+		Ok(fee)
+	}
+
 	fn unreserve_fee(who: &T::AccountId, fee: PalletBalanceOf<T>) {
 		<T as Config>::Currency::unreserve_named(&RESERVE_ID, &who, fee);
 	}
@@ -857,9 +1482,27 @@ where
 			ExistenceRequirement::KeepAlive,
 		)
 		.map_err(|_| InvalidTransaction::Payment)?;
+		<T as Config>::OnFeePaid::on_fee_paid(who, fee.saturating_sub(tip));
 
 		// distribute fee
 		<T as Config>::OnTransactionPayment::on_unbalanced(actual_payment);
 		Ok(())
 	}
+
+	fn weight_to_fee(weight: Weight) -> PalletBalanceOf<T> {
+		Pallet::<T>::weight_to_fee(weight)
+	}
+
+	fn fee_in_currency(currency_id: CurrencyId, fee: PalletBalanceOf<T>) -> Option<PalletBalanceOf<T>> {
+		if currency_id == T::NativeCurrencyId::get() {
+			return Some(fee);
+		}
+
+		T::DEX::get_swap_supply_amount(
+			&Pallet::<T>::fee_trading_path(currency_id),
+			fee.unique_saturated_into(),
+			Some(T::MaxSlippageSwapWithDEX::get()),
+		)
+		.map(|amount| amount.unique_saturated_into())
+	}
 }
@@ -32,7 +32,7 @@ use sp_core::{crypto::AccountId32, H256};
 use sp_runtime::{
 	testing::Header,
 	traits::{IdentityLookup, One},
-	Perbill,
+	MultiSignature, Perbill,
 };
 use sp_std::cell::RefCell;
 use support::{mocks::MockAddressMapping, Ratio};
@@ -133,6 +133,11 @@ parameter_types! {
 	pub const GetNativeCurrencyId: CurrencyId = ACA;
 }
 
+parameter_types! {
+	pub const MaxMemoLength: u32 = 32;
+	pub const MaxBatchTransfers: u32 = 4;
+}
+
 impl module_currencies::Config for Runtime {
 	type Event = Event;
 	type MultiCurrency = Tokens;
@@ -141,6 +146,10 @@ impl module_currencies::Config for Runtime {
 	type WeightInfo = ();
 	type AddressMapping = MockAddressMapping;
 	type EVMBridge = ();
+	type UpdateOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxMemoLength = MaxMemoLength;
+	type MaxBatchTransfers = MaxBatchTransfers;
+	type FreezeChecker = ();
 }
 
 thread_local! {
@@ -168,11 +177,17 @@ impl module_dex::Config for Runtime {
 	type DEXIncentives = ();
 	type WeightInfo = ();
 	type ListingOrigin = frame_system::EnsureSignedBy<Zero, AccountId>;
+	type OnSwap = ();
+	type EventTopics = ();
+	type SwapFeeShare = ();
+	type SwapFeeCurrencyId = GetNativeCurrencyId;
+	type SwapFeeReceiver = ();
 }
 
 parameter_types! {
 	pub AllNonNativeCurrencyIds: Vec<CurrencyId> = vec![AUSD, DOT];
 	pub MaxSlippageSwapWithDEX: Ratio = Ratio::one();
+	pub const FeeTankWithdrawalDelay: BlockNumber = 10;
 	pub const StableCurrencyId: CurrencyId = AUSD;
 	pub static TransactionByteFee: u128 = 1;
 }
@@ -194,7 +209,27 @@ impl OnUnbalanced<pallet_balances::NegativeImbalance<Runtime>> for DealWithFees
 	}
 }
 
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+pub enum SponsorCallClass {
+	Any,
+	NativeTransfer,
+}
+
+impl InstanceFilter<Call> for SponsorCallClass {
+	fn filter(&self, c: &Call) -> bool {
+		match self {
+			SponsorCallClass::Any => true,
+			SponsorCallClass::NativeTransfer => matches!(
+				c,
+				Call::Currencies(module_currencies::Call::transfer_native_currency(..))
+			),
+		}
+	}
+}
+
 impl Config for Runtime {
+	type Event = Event;
+	type Call = Call;
 	type AllNonNativeCurrencyIds = AllNonNativeCurrencyIds;
 	type NativeCurrencyId = GetNativeCurrencyId;
 	type StableCurrencyId = StableCurrencyId;
@@ -206,6 +241,12 @@ impl Config for Runtime {
 	type FeeMultiplierUpdate = ();
 	type DEX = DEXModule;
 	type MaxSlippageSwapWithDEX = MaxSlippageSwapWithDEX;
+	type OnFeePaid = ();
+	type SponsorCallClass = SponsorCallClass;
+	type SponsorPublic = <MultiSignature as Verify>::Signer;
+	type SponsorSignature = MultiSignature;
+	type UpdateOrigin = frame_system::EnsureRoot<AccountId>;
+	type FeeTankWithdrawalDelay = FeeTankWithdrawalDelay;
 	type WeightInfo = ();
 }
 
@@ -237,10 +278,10 @@ construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
-		TransactionPayment: transaction_payment::{Pallet, Call, Storage},
+		TransactionPayment: transaction_payment::{Pallet, Call, Storage, Event<T>},
 		PalletBalances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		Tokens: orml_tokens::{Pallet, Storage, Event<T>, Config<T>},
-		Currencies: module_currencies::{Pallet, Call, Event<T>},
+		Currencies: module_currencies::{Pallet, Call, Storage, Event<T>},
 		DEXModule: module_dex::{Pallet, Storage, Call, Event<T>, Config<T>},
 	}
 );
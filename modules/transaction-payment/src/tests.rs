@@ -22,15 +22,20 @@
 
 use super::*;
 use frame_support::{
-	assert_ok,
+	assert_noop, assert_ok,
 	weights::{DispatchClass, DispatchInfo, Pays},
 };
 use mock::{
-	AccountId, BlockWeights, Call, Currencies, DEXModule, ExtBuilder, Origin, Runtime, TransactionPayment, ACA, ALICE,
-	AUSD, BOB, CHARLIE, DOT, FEE_UNBALANCED_AMOUNT, TIP_UNBALANCED_AMOUNT,
+	AccountId, BlockWeights, Call, Currencies, DEXModule, Event, ExtBuilder, Origin, Runtime, SponsorCallClass, System,
+	TransactionPayment, ACA, ALICE, AUSD, BOB, CHARLIE, DOT, FEE_UNBALANCED_AMOUNT, TIP_UNBALANCED_AMOUNT,
 };
 use orml_traits::MultiCurrency;
-use sp_runtime::{testing::TestXt, traits::One};
+use sp_core::{sr25519, Pair};
+use sp_runtime::{
+	testing::TestXt,
+	traits::{BadOrigin, One},
+	MultiSignature, MultiSigner,
+};
 
 const CALL: &<Runtime as frame_system::Config>::Call =
 	&Call::Currencies(module_currencies::Call::transfer(BOB, AUSD, 12));
@@ -242,6 +247,53 @@ fn charge_fee_by_default_fee_token() {
 	});
 }
 
+#[test]
+fn fee_in_currency_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(
+			<ChargeTransactionPayment<Runtime> as TransactionPayment<_, _, _>>::weight_to_fee(1000),
+			1000
+		);
+
+		assert_eq!(
+			<ChargeTransactionPayment<Runtime> as TransactionPayment<_, _, _>>::fee_in_currency(ACA, 2000),
+			Some(2000)
+		);
+		assert_eq!(
+			<ChargeTransactionPayment<Runtime> as TransactionPayment<_, _, _>>::fee_in_currency(AUSD, 2000),
+			None
+		);
+
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(ALICE),
+			ACA,
+			AUSD,
+			10000,
+			1000,
+			0,
+			false
+		));
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(ALICE),
+			DOT,
+			AUSD,
+			100,
+			1000,
+			0,
+			false
+		));
+
+		assert_eq!(
+			<ChargeTransactionPayment<Runtime> as TransactionPayment<_, _, _>>::fee_in_currency(AUSD, 2000),
+			Some(251)
+		);
+		assert_eq!(
+			<ChargeTransactionPayment<Runtime> as TransactionPayment<_, _, _>>::fee_in_currency(DOT, 2000),
+			Some(34)
+		);
+	});
+}
+
 #[test]
 fn query_info_works() {
 	ExtBuilder::default()
@@ -394,3 +446,549 @@ fn compute_fee_does_not_overflow() {
 			);
 		});
 }
+
+#[test]
+fn set_class_fee_multiplier_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let multiplier = Multiplier::saturating_from_rational(1, 2);
+
+		assert_noop!(
+			TransactionPayment::set_class_fee_multiplier(
+				Origin::signed(ALICE),
+				DispatchClass::Operational,
+				Some(multiplier)
+			),
+			BadOrigin
+		);
+
+		assert_ok!(TransactionPayment::set_class_fee_multiplier(
+			Origin::root(),
+			DispatchClass::Operational,
+			Some(multiplier)
+		));
+		assert_eq!(
+			TransactionPayment::class_fee_multipliers(DispatchClass::Operational),
+			Some(multiplier)
+		);
+		System::assert_last_event(Event::TransactionPayment(crate::Event::ClassFeeMultiplierUpdated(
+			DispatchClass::Operational,
+			Some(multiplier),
+		)));
+
+		assert_ok!(TransactionPayment::set_class_fee_multiplier(
+			Origin::root(),
+			DispatchClass::Operational,
+			None
+		));
+		assert!(!ClassFeeMultipliers::<Runtime>::contains_key(
+			DispatchClass::Operational
+		));
+		System::assert_last_event(Event::TransactionPayment(crate::Event::ClassFeeMultiplierUpdated(
+			DispatchClass::Operational,
+			None,
+		)));
+	});
+}
+
+#[test]
+fn set_pallet_fee_multiplier_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let multiplier = Multiplier::saturating_from_integer(3);
+
+		assert_noop!(
+			TransactionPayment::set_pallet_fee_multiplier(
+				Origin::signed(ALICE),
+				b"Currencies".to_vec(),
+				Some(multiplier)
+			),
+			BadOrigin
+		);
+
+		assert_ok!(TransactionPayment::set_pallet_fee_multiplier(
+			Origin::root(),
+			b"Currencies".to_vec(),
+			Some(multiplier)
+		));
+		assert_eq!(
+			TransactionPayment::pallet_fee_multipliers(b"Currencies".to_vec()),
+			Some(multiplier)
+		);
+		System::assert_last_event(Event::TransactionPayment(crate::Event::PalletFeeMultiplierUpdated(
+			b"Currencies".to_vec(),
+			Some(multiplier),
+		)));
+
+		assert_ok!(TransactionPayment::set_pallet_fee_multiplier(
+			Origin::root(),
+			b"Currencies".to_vec(),
+			None
+		));
+		assert!(!PalletFeeMultipliers::<Runtime>::contains_key(b"Currencies".to_vec()));
+		System::assert_last_event(Event::TransactionPayment(crate::Event::PalletFeeMultiplierUpdated(
+			b"Currencies".to_vec(),
+			None,
+		)));
+	});
+}
+
+#[test]
+fn fee_multipliers_of_class_and_pallet_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Fees will be x2, the normal calls x2 more and the calls of `Currencies` x3/8 more.
+		NextFeeMultiplier::<Runtime>::put(Multiplier::saturating_from_integer(2));
+		assert_ok!(TransactionPayment::set_class_fee_multiplier(
+			Origin::root(),
+			DispatchClass::Normal,
+			Some(Multiplier::saturating_from_integer(2))
+		));
+		assert_ok!(TransactionPayment::set_pallet_fee_multiplier(
+			Origin::root(),
+			b"Currencies".to_vec(),
+			Some(Multiplier::saturating_from_rational(3, 8))
+		));
+
+		// the multiplier of the pallet is unknown without the call
+		assert_eq!(Pallet::<Runtime>::compute_fee(23, &INFO, 0), 23 * 2 + 4000);
+		assert_eq!(
+			Pallet::<Runtime>::call_fee_multiplier(CALL, &INFO),
+			Multiplier::saturating_from_rational(3, 2)
+		);
+
+		let fee = 23 * 2 + 1500; // len * byte + weight * multiplier
+		let pre = ChargeTransactionPayment::<Runtime>::from(0)
+			.pre_dispatch(&ALICE, CALL, &INFO, 23)
+			.unwrap();
+		assert_eq!(Currencies::free_balance(ACA, &ALICE), 100000 - fee);
+
+		// the multiplier of charging the fee is used for the refund
+		assert_ok!(TransactionPayment::set_pallet_fee_multiplier(
+			Origin::root(),
+			b"Currencies".to_vec(),
+			None
+		));
+		assert_ok!(ChargeTransactionPayment::<Runtime>::post_dispatch(
+			pre,
+			&INFO,
+			&POST_INFO,
+			23,
+			&Ok(())
+		));
+		let refund = 300; // (1000 - 800) * 3 / 2
+		assert_eq!(Currencies::free_balance(ACA, &ALICE), 100000 - fee + refund);
+		assert_eq!(FEE_UNBALANCED_AMOUNT.with(|a| a.borrow().clone()), fee - refund);
+	});
+}
+
+fn sponsor_pair() -> sr25519::Pair {
+	sr25519::Pair::from_seed(&[7u8; 32])
+}
+
+fn sponsor() -> AccountId {
+	MultiSigner::from(sponsor_pair().public()).into_account()
+}
+
+fn sign_permit(pair: &sr25519::Pair, permit: &SponsorPermitOf<Runtime>) -> MultiSignature {
+	let genesis_hash = System::block_hash(0);
+	MultiSignature::from(pair.sign(&(SPONSOR_PERMIT_PREFIX, genesis_hash, permit).encode()))
+}
+
+fn native_transfer_permit(nonce: u32) -> SponsorPermitOf<Runtime> {
+	SponsorPermit {
+		sponsor: sponsor(),
+		beneficiary: BOB,
+		calls: SponsoredCalls::Class(SponsorCallClass::NativeTransfer),
+		max_fee: 2000,
+		nonce,
+		deadline: 10,
+	}
+}
+
+fn sponsored(permit: SponsorPermitOf<Runtime>, signature: MultiSignature, call: &Call) -> Call {
+	Call::TransactionPayment(crate::Call::sponsored_call(permit, signature, Box::new(call.clone())))
+}
+
+#[test]
+fn set_sponsor_budget_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(TransactionPayment::set_sponsor_budget(Origin::signed(ALICE), 5000));
+		assert_eq!(TransactionPayment::sponsor_budgets(&ALICE), 5000);
+		System::assert_last_event(Event::TransactionPayment(crate::Event::SponsorBudgetUpdated(
+			ALICE, 5000,
+		)));
+
+		assert_ok!(TransactionPayment::set_sponsor_budget(Origin::signed(ALICE), 0));
+		assert!(!SponsorBudgets::<Runtime>::contains_key(&ALICE));
+		System::assert_last_event(Event::TransactionPayment(crate::Event::SponsorBudgetUpdated(ALICE, 0)));
+	});
+}
+
+#[test]
+fn sponsored_fee_charged_from_sponsor() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(<Currencies as MultiCurrency<_>>::transfer(
+			ACA,
+			&ALICE,
+			&sponsor(),
+			10000
+		));
+		assert_ok!(TransactionPayment::set_sponsor_budget(Origin::signed(sponsor()), 5000));
+
+		let permit = native_transfer_permit(0);
+		let signature = sign_permit(&sponsor_pair(), &permit);
+		let call = sponsored(permit.clone(), signature.clone(), CALL2);
+
+		let fee = 23 * 2 + 1000; // len * byte + weight
+		let pre = ChargeTransactionPayment::<Runtime>::from(0)
+			.pre_dispatch(&BOB, &call, &INFO, 23)
+			.unwrap();
+		assert_eq!(Currencies::free_balance(ACA, &BOB), 0);
+		assert_eq!(Currencies::free_balance(ACA, &sponsor()), 10000 - fee);
+		assert_eq!(TransactionPayment::sponsor_budgets(&sponsor()), 5000 - fee);
+		assert_eq!(TransactionPayment::sponsor_nonces(&sponsor(), &BOB), 1);
+		System::assert_last_event(Event::TransactionPayment(crate::Event::FeeSponsored(
+			sponsor(),
+			BOB,
+			0,
+			fee,
+		)));
+
+		assert_ok!(ChargeTransactionPayment::<Runtime>::post_dispatch(
+			pre,
+			&INFO,
+			&POST_INFO,
+			23,
+			&Ok(())
+		));
+		let refund = 200; // 1000 - 800
+		assert_eq!(Currencies::free_balance(ACA, &sponsor()), 10000 - fee + refund);
+		assert_eq!(TransactionPayment::sponsor_budgets(&sponsor()), 5000 - fee + refund);
+		assert_eq!(FEE_UNBALANCED_AMOUNT.with(|a| a.borrow().clone()), fee - refund);
+
+		// the permit can't be replayed
+		assert_eq!(
+			ChargeTransactionPayment::<Runtime>::from(0).validate(&BOB, &call, &INFO, 23),
+			Err(InvalidTransaction::Stale.into())
+		);
+
+		// the permit for the call with the hash
+		let permit = SponsorPermit {
+			calls: SponsoredCalls::Call(<Runtime as frame_system::Config>::Hashing::hash_of(CALL)),
+			..native_transfer_permit(1)
+		};
+		let signature = sign_permit(&sponsor_pair(), &permit);
+		assert_ok!(ChargeTransactionPayment::<Runtime>::from(0).validate(
+			&BOB,
+			&sponsored(permit, signature, CALL),
+			&INFO,
+			23
+		));
+	});
+}
+
+#[test]
+fn sponsored_fee_checks_permit() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(<Currencies as MultiCurrency<_>>::transfer(
+			ACA,
+			&ALICE,
+			&sponsor(),
+			10000
+		));
+		assert_ok!(TransactionPayment::set_sponsor_budget(Origin::signed(sponsor()), 5000));
+
+		let validate = |permit: SponsorPermitOf<Runtime>, signature: MultiSignature, call: &Call| {
+			ChargeTransactionPayment::<Runtime>::from(0).validate(&BOB, &sponsored(permit, signature, call), &INFO, 23)
+		};
+		let permit = native_transfer_permit(0);
+		let signature = sign_permit(&sponsor_pair(), &permit);
+
+		// not the beneficiary
+		assert_eq!(
+			ChargeTransactionPayment::<Runtime>::from(0).validate(
+				&CHARLIE,
+				&sponsored(permit.clone(), signature.clone(), CALL2),
+				&INFO,
+				23
+			),
+			Err(InvalidTransaction::BadSigner.into())
+		);
+
+		// not signed by the sponsor
+		let other_signature = sign_permit(&sr25519::Pair::from_seed(&[8u8; 32]), &permit);
+		assert_eq!(
+			validate(permit.clone(), other_signature, CALL2),
+			Err(InvalidTransaction::BadProof.into())
+		);
+
+		// the call isn't covered
+		assert_eq!(
+			validate(permit.clone(), signature.clone(), CALL),
+			Err(InvalidTransaction::Call.into())
+		);
+
+		// the nonce isn't reached
+		let future_permit = native_transfer_permit(1);
+		let future_signature = sign_permit(&sponsor_pair(), &future_permit);
+		assert_eq!(
+			validate(future_permit, future_signature, CALL2),
+			Err(InvalidTransaction::Future.into())
+		);
+
+		// exceed the max fee
+		let cheap_permit = SponsorPermit {
+			max_fee: 1000,
+			..native_transfer_permit(0)
+		};
+		let cheap_signature = sign_permit(&sponsor_pair(), &cheap_permit);
+		assert_eq!(
+			validate(cheap_permit, cheap_signature, CALL2),
+			Err(InvalidTransaction::Payment.into())
+		);
+
+		// exceed the budget
+		assert_ok!(TransactionPayment::set_sponsor_budget(Origin::signed(sponsor()), 1000));
+		assert_eq!(
+			validate(permit.clone(), signature.clone(), CALL2),
+			Err(InvalidTransaction::Payment.into())
+		);
+		assert_ok!(TransactionPayment::set_sponsor_budget(Origin::signed(sponsor()), 5000));
+		assert_ok!(validate(permit.clone(), signature.clone(), CALL2));
+
+		// the permit expired
+		System::set_block_number(11);
+		assert_eq!(
+			validate(permit, signature, CALL2),
+			Err(InvalidTransaction::Stale.into())
+		);
+	});
+}
+
+#[test]
+fn sponsored_call_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let inner_call = Call::TransactionPayment(crate::Call::set_default_fee_token(Some(AUSD)));
+		let permit = SponsorPermit {
+			calls: SponsoredCalls::Class(SponsorCallClass::Any),
+			..native_transfer_permit(0)
+		};
+		let signature = sign_permit(&sponsor_pair(), &permit);
+
+		assert_noop!(
+			TransactionPayment::sponsored_call(
+				Origin::signed(CHARLIE),
+				permit.clone(),
+				signature.clone(),
+				Box::new(inner_call.clone())
+			),
+			Error::<Runtime>::InvalidPermit
+		);
+		assert_noop!(
+			TransactionPayment::sponsored_call(
+				Origin::signed(BOB),
+				permit.clone(),
+				sign_permit(&sr25519::Pair::from_seed(&[8u8; 32]), &permit),
+				Box::new(inner_call.clone())
+			),
+			Error::<Runtime>::InvalidPermit
+		);
+
+		assert_ok!(TransactionPayment::sponsored_call(
+			Origin::signed(BOB),
+			permit,
+			signature,
+			Box::new(inner_call)
+		));
+		assert_eq!(TransactionPayment::default_fee_currency_id(&BOB), Some(AUSD));
+	});
+}
+
+#[test]
+fn deposit_fee_tank_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_noop!(
+			TransactionPayment::deposit_fee_tank(Origin::signed(BOB), BOB, 1000),
+			pallet_balances::Error::<Runtime>::InsufficientBalance
+		);
+
+		assert_ok!(TransactionPayment::deposit_fee_tank(Origin::signed(ALICE), ALICE, 1000));
+		assert_eq!(TransactionPayment::fee_tank_balance(&ALICE), 1000);
+		assert_eq!(Currencies::free_balance(ACA, &ALICE), 99000);
+		System::assert_last_event(Event::TransactionPayment(crate::Event::FeeTankDeposited(
+			ALICE, ALICE, 1000,
+		)));
+
+		assert_ok!(TransactionPayment::deposit_fee_tank(Origin::signed(ALICE), BOB, 2000));
+		assert_eq!(TransactionPayment::fee_tank_balance(&BOB), 2000);
+		assert_eq!(Currencies::free_balance(ACA, &BOB), 0);
+		assert_eq!(Currencies::free_balance(ACA, &ALICE), 97000);
+		System::assert_last_event(Event::TransactionPayment(crate::Event::FeeTankDeposited(
+			ALICE, BOB, 2000,
+		)));
+
+		// the fee tank can't be transferred
+		assert!(<Currencies as MultiCurrency<_>>::transfer(ACA, &BOB, &CHARLIE, 1).is_err());
+	});
+}
+
+#[test]
+fn fee_drawn_from_fee_tank_first() {
+	ExtBuilder::default().build().execute_with(|| {
+		let fee = 23 * 2 + 1000; // len * byte + weight
+		let refund = 200; // 1000 - 800
+
+		// the fee tank pays the whole fee
+		assert_ok!(TransactionPayment::deposit_fee_tank(Origin::signed(ALICE), BOB, 2000));
+		let pre = ChargeTransactionPayment::<Runtime>::from(0)
+			.pre_dispatch(&BOB, CALL, &INFO, 23)
+			.unwrap();
+		assert_eq!(TransactionPayment::fee_tank_balance(&BOB), 2000 - fee);
+		assert_eq!(Currencies::free_balance(ACA, &BOB), 0);
+		assert_ok!(ChargeTransactionPayment::<Runtime>::post_dispatch(
+			pre,
+			&INFO,
+			&POST_INFO,
+			23,
+			&Ok(())
+		));
+		assert_eq!(TransactionPayment::fee_tank_balance(&BOB), 2000 - fee + refund);
+		assert_eq!(Currencies::free_balance(ACA, &BOB), 0);
+
+		// the fee tank pays a part of the fee
+		assert_ok!(TransactionPayment::deposit_fee_tank(
+			Origin::signed(CHARLIE),
+			CHARLIE,
+			500
+		));
+		let pre = ChargeTransactionPayment::<Runtime>::from(0)
+			.pre_dispatch(&CHARLIE, CALL, &INFO, 23)
+			.unwrap();
+		assert_eq!(TransactionPayment::fee_tank_balance(&CHARLIE), 0);
+		assert_eq!(Currencies::free_balance(ACA, &CHARLIE), 100000 - 500 - (fee - 500));
+		assert_ok!(ChargeTransactionPayment::<Runtime>::post_dispatch(
+			pre,
+			&INFO,
+			&POST_INFO,
+			23,
+			&Ok(())
+		));
+		assert_eq!(TransactionPayment::fee_tank_balance(&CHARLIE), refund);
+		assert_eq!(Currencies::free_balance(ACA, &CHARLIE), 100000 - 500 - (fee - 500));
+	});
+}
+
+#[test]
+fn fee_tank_withdrawal_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TransactionPayment::deposit_fee_tank(Origin::signed(ALICE), ALICE, 1000));
+		assert_eq!(TransactionPayment::fee_tank_deposit_of(&ALICE, &ALICE), 1000);
+
+		assert_noop!(
+			TransactionPayment::request_fee_tank_withdrawal(Origin::signed(ALICE), ALICE, 1001),
+			Error::<Runtime>::FeeTankNotEnough
+		);
+		assert_noop!(
+			TransactionPayment::withdraw_fee_tank(Origin::signed(ALICE), ALICE),
+			Error::<Runtime>::FeeTankWithdrawalNotFound
+		);
+
+		assert_ok!(TransactionPayment::request_fee_tank_withdrawal(
+			Origin::signed(ALICE),
+			ALICE,
+			600
+		));
+		assert_eq!(
+			TransactionPayment::fee_tank_withdrawals(&ALICE, &ALICE),
+			Some((600, 11))
+		);
+		System::assert_last_event(Event::TransactionPayment(crate::Event::FeeTankWithdrawalRequested(
+			ALICE, ALICE, 600, 11,
+		)));
+
+		System::set_block_number(10);
+		assert_noop!(
+			TransactionPayment::withdraw_fee_tank(Origin::signed(ALICE), ALICE),
+			Error::<Runtime>::FeeTankWithdrawalLocked
+		);
+
+		System::set_block_number(11);
+		assert_ok!(TransactionPayment::withdraw_fee_tank(Origin::signed(ALICE), ALICE));
+		assert_eq!(TransactionPayment::fee_tank_withdrawals(&ALICE, &ALICE), None);
+		assert_eq!(TransactionPayment::fee_tank_balance(&ALICE), 400);
+		assert_eq!(TransactionPayment::fee_tank_deposit_of(&ALICE, &ALICE), 400);
+		assert_eq!(Currencies::free_balance(ACA, &ALICE), 99600);
+		System::assert_last_event(Event::TransactionPayment(crate::Event::FeeTankWithdrawn(
+			ALICE, ALICE, 600,
+		)));
+	});
+}
+
+#[test]
+fn fee_tank_deposit_only_withdrawn_by_depositor() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TransactionPayment::deposit_fee_tank(Origin::signed(ALICE), BOB, 3000));
+		assert_ok!(TransactionPayment::deposit_fee_tank(Origin::signed(CHARLIE), BOB, 1000));
+		assert_eq!(TransactionPayment::fee_tank_balance(&BOB), 4000);
+		assert_eq!(TransactionPayment::fee_tank_deposit_of(&BOB, &ALICE), 3000);
+		assert_eq!(TransactionPayment::fee_tank_deposit_of(&BOB, &CHARLIE), 1000);
+
+		// the owner of the fee tank can't withdraw the deposits of the others
+		assert_noop!(
+			TransactionPayment::request_fee_tank_withdrawal(Origin::signed(BOB), BOB, 1),
+			Error::<Runtime>::FeeTankNotEnough
+		);
+
+		// the fees are drawn from the deposits pro rata
+		let pre = ChargeTransactionPayment::<Runtime>::from(0)
+			.pre_dispatch(&BOB, CALL, &INFO, 23)
+			.unwrap();
+		assert_ok!(ChargeTransactionPayment::<Runtime>::post_dispatch(
+			pre,
+			&INFO,
+			&POST_INFO,
+			23,
+			&Ok(())
+		));
+		let actual_fee = 23 * 2 + 800;
+		assert_eq!(TransactionPayment::fee_tank_balance(&BOB), 4000 - actual_fee);
+		assert_eq!(
+			TransactionPayment::fee_tank_deposit_of(&BOB, &ALICE),
+			(4000 - actual_fee) * 3 / 4
+		);
+		assert_eq!(
+			TransactionPayment::fee_tank_deposit_of(&BOB, &CHARLIE),
+			(4000 - actual_fee) / 4
+		);
+
+		// the deposit is withdrawn to the depositor
+		let alice_deposit = TransactionPayment::fee_tank_deposit_of(&BOB, &ALICE);
+		assert_ok!(TransactionPayment::request_fee_tank_withdrawal(
+			Origin::signed(ALICE),
+			BOB,
+			alice_deposit
+		));
+		System::set_block_number(11);
+		assert_ok!(TransactionPayment::withdraw_fee_tank(Origin::signed(ALICE), BOB));
+		System::assert_last_event(Event::TransactionPayment(crate::Event::FeeTankWithdrawn(
+			BOB,
+			ALICE,
+			alice_deposit,
+		)));
+		assert_eq!(Currencies::free_balance(ACA, &ALICE), 100000 - 3000 + alice_deposit);
+		assert_eq!(Currencies::free_balance(ACA, &BOB), 0);
+		assert_eq!(TransactionPayment::fee_tank_deposit_of(&BOB, &ALICE), 0);
+		assert_eq!(
+			TransactionPayment::fee_tank_deposit_of(&BOB, &CHARLIE),
+			4000 - actual_fee - alice_deposit
+		);
+	});
+}
@@ -0,0 +1,344 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Vesting Manager Module
+//!
+//! ## Overview
+//!
+//! Manages the vesting schedules of `orml-vesting` while preserving their locked amounts. The
+//! owner of the schedules can merge the tranches of the same grant into one schedule, or split a
+//! part of a schedule to another beneficiary. The clawback origin can split a part of the schedule
+//! of any account to another beneficiary, e.g. when a contributor departs, or claw back the
+//! unvested funds of a schedule.
+//!
+//! The schedules are cut to their remaining periods at the current vesting block number, and the
+//! vesting locks of the affected accounts are updated to their locked amounts, the same as
+//! claiming the vested funds. The fully vested schedules are removed.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{
+	pallet_prelude::*,
+	traits::{Currency, ExistenceRequirement, LockableCurrency, WithdrawReasons},
+	transactional,
+};
+use frame_system::pallet_prelude::*;
+use orml_vesting::{VestingSchedule, VestingSchedules, VESTING_LOCK_ID};
+use sp_runtime::{
+	offchain::storage_lock::BlockNumberProvider,
+	traits::{CheckedAdd, CheckedDiv, Saturating, StaticLookup, UniqueSaturatedInto, Zero},
+};
+use sp_std::{convert::TryInto, prelude::*};
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+pub type BalanceOf<T> =
+	<<T as orml_vesting::Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+pub type VestingScheduleOf<T> = VestingSchedule<<T as frame_system::Config>::BlockNumber, BalanceOf<T>>;
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + orml_vesting::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The origin which may split the schedules of any account and claw back their unvested
+		/// funds.
+		type ClawbackOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The vesting schedule doesn't exist
+		ScheduleNotFound,
+		/// The vesting schedule is fully vested
+		ScheduleVested,
+		/// At least two vesting schedules are required to merge
+		NotEnoughSchedulesToMerge,
+		/// The remaining periods of the vesting schedules are different
+		IncompatibleSchedules,
+		/// The amount per period to split is zero or exceeds the schedule
+		InvalidSplitAmount,
+		/// The amount split to the beneficiary is below `MinVestedTransfer`
+		AmountLow,
+		/// The beneficiary has too many vesting schedules
+		MaxVestingSchedulesExceeded,
+		/// The amount of the vesting schedule overflows
+		Overflow,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Merged vesting schedules of account. \[who, merged_schedule\]
+		SchedulesMerged(T::AccountId, VestingScheduleOf<T>),
+		/// Split vesting schedule to beneficiary. \[from, to, split_schedule\]
+		ScheduleSplit(T::AccountId, T::AccountId, VestingScheduleOf<T>),
+		/// Clawed back the unvested funds of vesting schedule. \[who, dest, amount\]
+		ClawedBack(T::AccountId, T::AccountId, BalanceOf<T>),
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Merge the vesting schedules of the caller into one schedule. The remaining periods of
+		/// the schedules must be the same, e.g. the tranches of the same grant, so the merged
+		/// schedule unlocks exactly the sum of their amounts at the same blocks.
+		///
+		/// - `indexes`: the indexes of the schedules to merge.
+		#[pallet::weight(<T as Config>::WeightInfo::merge_schedules())]
+		#[transactional]
+		pub fn merge_schedules(origin: OriginFor<T>, indexes: Vec<u32>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let mut indexes = indexes;
+			indexes.sort_unstable();
+			indexes.dedup();
+			ensure!(indexes.len() >= 2, Error::<T>::NotEnoughSchedulesToMerge);
+
+			let now = Self::now();
+			let mut schedules = VestingSchedules::<T>::get(&who).into_inner();
+			let mut merged: Option<VestingScheduleOf<T>> = None;
+			// remove from the last index so the other indexes are not shifted
+			for index in indexes.into_iter().rev() {
+				ensure!((index as usize) < schedules.len(), Error::<T>::ScheduleNotFound);
+				let schedule = Self::remaining_schedule(&schedules.remove(index as usize), now)
+					.ok_or(Error::<T>::ScheduleVested)?;
+
+				merged = Some(match merged {
+					None => schedule,
+					Some(mut merged) => {
+						ensure!(
+							merged.start == schedule.start
+								&& merged.period == schedule.period
+								&& merged.period_count == schedule.period_count,
+							Error::<T>::IncompatibleSchedules
+						);
+						merged.per_period = merged
+							.per_period
+							.checked_add(&schedule.per_period)
+							.ok_or(Error::<T>::Overflow)?;
+						merged
+					}
+				});
+			}
+
+			let merged = merged.expect("at least two schedules are merged; qed");
+			schedules.push(merged.clone());
+			Self::update_schedules(&who, schedules, now)?;
+
+			Self::deposit_event(Event::SchedulesMerged(who, merged));
+			Ok(().into())
+		}
+
+		/// Split `per_period` of the remaining periods of the vesting schedule of the caller to
+		/// `beneficiary`. The locked funds of the split schedule are transferred to `beneficiary`,
+		/// and the schedule is transferred if all of it is split.
+		///
+		/// - `index`: the index of the schedule to split.
+		/// - `beneficiary`: the beneficiary of the split schedule.
+		/// - `per_period`: the amount per period of the split schedule.
+		#[pallet::weight(<T as Config>::WeightInfo::split_schedule())]
+		#[transactional]
+		pub fn split_schedule(
+			origin: OriginFor<T>,
+			index: u32,
+			beneficiary: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] per_period: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let beneficiary = T::Lookup::lookup(beneficiary)?;
+			Self::do_split_schedule(&who, index, &beneficiary, per_period)?;
+			Ok(().into())
+		}
+
+		/// Split `per_period` of the remaining periods of the vesting schedule of `who` to
+		/// `beneficiary`, e.g. to reassign the unvested funds of a departing contributor.
+		///
+		/// The dispatch origin of this call must be `ClawbackOrigin`.
+		///
+		/// - `who`: the owner of the schedule.
+		/// - `index`: the index of the schedule to split.
+		/// - `beneficiary`: the beneficiary of the split schedule.
+		/// - `per_period`: the amount per period of the split schedule.
+		#[pallet::weight(<T as Config>::WeightInfo::force_split_schedule())]
+		#[transactional]
+		pub fn force_split_schedule(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+			index: u32,
+			beneficiary: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] per_period: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			T::ClawbackOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			let beneficiary = T::Lookup::lookup(beneficiary)?;
+			Self::do_split_schedule(&who, index, &beneficiary, per_period)?;
+			Ok(().into())
+		}
+
+		/// Remove the vesting schedule of `who` and transfer its unvested funds to `dest`. The
+		/// vested funds of the schedule are kept by `who`.
+		///
+		/// The dispatch origin of this call must be `ClawbackOrigin`.
+		///
+		/// - `who`: the owner of the schedule.
+		/// - `index`: the index of the schedule to claw back.
+		/// - `dest`: the account receiving the unvested funds.
+		#[pallet::weight(<T as Config>::WeightInfo::clawback())]
+		#[transactional]
+		pub fn clawback(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+			index: u32,
+			dest: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			T::ClawbackOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			let dest = T::Lookup::lookup(dest)?;
+
+			let now = Self::now();
+			let mut schedules = VestingSchedules::<T>::get(&who).into_inner();
+			ensure!((index as usize) < schedules.len(), Error::<T>::ScheduleNotFound);
+			let amount = schedules.remove(index as usize).locked_amount(now);
+			ensure!(!amount.is_zero(), Error::<T>::ScheduleVested);
+
+			Self::update_schedules(&who, schedules, now)?;
+			<T as orml_vesting::Config>::Currency::transfer(&who, &dest, amount, ExistenceRequirement::AllowDeath)?;
+
+			Self::deposit_event(Event::ClawedBack(who, dest, amount));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The vesting block number of `orml-vesting`.
+	fn now() -> T::BlockNumber {
+		<T as orml_vesting::Config>::BlockNumberProvider::current_block_number()
+	}
+
+	/// The remaining periods of `schedule` at `now`, which lock the same amounts at and after
+	/// `now`. `None` if `schedule` is fully vested.
+	pub fn remaining_schedule(schedule: &VestingScheduleOf<T>, now: T::BlockNumber) -> Option<VestingScheduleOf<T>> {
+		let period_count: T::BlockNumber = schedule.period_count.into();
+		let elapsed_periods = now
+			.saturating_sub(schedule.start)
+			.checked_div(&schedule.period)
+			.unwrap_or(period_count)
+			.min(period_count);
+		let remaining_count = schedule
+			.period_count
+			.saturating_sub(elapsed_periods.unique_saturated_into());
+		if remaining_count.is_zero() || schedule.per_period.is_zero() {
+			return None;
+		}
+
+		Some(VestingSchedule {
+			start: schedule
+				.start
+				.saturating_add(elapsed_periods.saturating_mul(schedule.period)),
+			period: schedule.period,
+			period_count: remaining_count,
+			per_period: schedule.per_period,
+		})
+	}
+
+	/// Store the remaining periods of `schedules` of `who` at `now`, and update its vesting lock
+	/// to their locked amount.
+	fn update_schedules(
+		who: &T::AccountId,
+		schedules: Vec<VestingScheduleOf<T>>,
+		now: T::BlockNumber,
+	) -> DispatchResult {
+		let schedules: BoundedVec<VestingScheduleOf<T>, T::MaxVestingSchedules> = schedules
+			.iter()
+			.filter_map(|schedule| Self::remaining_schedule(schedule, now))
+			.collect::<Vec<_>>()
+			.try_into()
+			.map_err(|_| Error::<T>::MaxVestingSchedulesExceeded)?;
+
+		if schedules.is_empty() {
+			<T as orml_vesting::Config>::Currency::remove_lock(VESTING_LOCK_ID, who);
+			VestingSchedules::<T>::remove(who);
+		} else {
+			let locked = schedules.iter().fold(Zero::zero(), |locked: BalanceOf<T>, schedule| {
+				locked.saturating_add(schedule.locked_amount(now))
+			});
+			<T as orml_vesting::Config>::Currency::set_lock(VESTING_LOCK_ID, who, locked, WithdrawReasons::all());
+			VestingSchedules::<T>::insert(who, schedules);
+		}
+		Ok(())
+	}
+
+	fn do_split_schedule(
+		who: &T::AccountId,
+		index: u32,
+		beneficiary: &T::AccountId,
+		per_period: BalanceOf<T>,
+	) -> DispatchResult {
+		let now = Self::now();
+		let mut schedules = VestingSchedules::<T>::get(who).into_inner();
+		let schedule = schedules.get_mut(index as usize).ok_or(Error::<T>::ScheduleNotFound)?;
+		let remaining = Self::remaining_schedule(schedule, now).ok_or(Error::<T>::ScheduleVested)?;
+		ensure!(
+			!per_period.is_zero() && per_period <= remaining.per_period,
+			Error::<T>::InvalidSplitAmount
+		);
+
+		let split = VestingSchedule {
+			per_period,
+			..remaining.clone()
+		};
+		let amount = split.total_amount().ok_or(Error::<T>::Overflow)?;
+		ensure!(
+			amount >= <T as orml_vesting::Config>::MinVestedTransfer::get(),
+			Error::<T>::AmountLow
+		);
+
+		// the schedule is removed by `update_schedules` if all of it is split
+		*schedule = VestingSchedule {
+			per_period: remaining.per_period.saturating_sub(per_period),
+			..remaining
+		};
+		Self::update_schedules(who, schedules, now)?;
+		<T as orml_vesting::Config>::Currency::transfer(who, beneficiary, amount, ExistenceRequirement::AllowDeath)?;
+
+		let mut beneficiary_schedules = VestingSchedules::<T>::get(beneficiary).into_inner();
+		beneficiary_schedules.push(split.clone());
+		Self::update_schedules(beneficiary, beneficiary_schedules, now)?;
+
+		Self::deposit_event(Event::ScheduleSplit(who.clone(), beneficiary.clone(), split));
+		Ok(())
+	}
+}
@@ -0,0 +1,259 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the vesting manager module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+use sp_runtime::traits::BadOrigin;
+
+fn schedule(
+	start: BlockNumber,
+	period: BlockNumber,
+	period_count: u32,
+	per_period: Balance,
+) -> VestingScheduleOf<Runtime> {
+	VestingSchedule {
+		start,
+		period,
+		period_count,
+		per_period,
+	}
+}
+
+fn vested_transfer(to: AccountId, schedule: VestingScheduleOf<Runtime>) {
+	assert_ok!(Vesting::vested_transfer(Origin::signed(TREASURY), to, schedule));
+}
+
+fn schedules(who: AccountId) -> Vec<VestingScheduleOf<Runtime>> {
+	VestingSchedules::<Runtime>::get(who).into_inner()
+}
+
+fn vesting_locked(who: AccountId) -> Balance {
+	PalletBalances::locks(&who)
+		.iter()
+		.find(|lock| lock.id == VESTING_LOCK_ID)
+		.map_or(0, |lock| lock.amount)
+}
+
+#[test]
+fn remaining_schedule_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let s = schedule(10, 10, 10, 5);
+		assert_eq!(VestingManager::remaining_schedule(&s, 0), Some(s.clone()));
+		assert_eq!(VestingManager::remaining_schedule(&s, 19), Some(s.clone()));
+		assert_eq!(VestingManager::remaining_schedule(&s, 25), Some(schedule(20, 10, 9, 5)));
+		assert_eq!(
+			VestingManager::remaining_schedule(&s, 109),
+			Some(schedule(100, 10, 1, 5))
+		);
+		assert_eq!(VestingManager::remaining_schedule(&s, 110), None);
+		assert_eq!(VestingManager::remaining_schedule(&schedule(10, 10, 10, 0), 0), None);
+
+		for now in 0..120 {
+			assert_eq!(
+				VestingManager::remaining_schedule(&s, now).map_or(0, |remaining| remaining.locked_amount(now)),
+				s.locked_amount(now)
+			);
+		}
+	});
+}
+
+#[test]
+fn merge_schedules_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		vested_transfer(ALICE, schedule(0, 10, 10, 10));
+		vested_transfer(ALICE, schedule(10, 10, 9, 20));
+		vested_transfer(ALICE, schedule(0, 10, 5, 10));
+		assert_eq!(vesting_locked(ALICE), 330);
+
+		System::set_block_number(25);
+		assert_noop!(
+			VestingManager::merge_schedules(Origin::signed(ALICE), vec![0]),
+			Error::<Runtime>::NotEnoughSchedulesToMerge
+		);
+		assert_noop!(
+			VestingManager::merge_schedules(Origin::signed(ALICE), vec![1, 1]),
+			Error::<Runtime>::NotEnoughSchedulesToMerge
+		);
+		assert_noop!(
+			VestingManager::merge_schedules(Origin::signed(ALICE), vec![0, 3]),
+			Error::<Runtime>::ScheduleNotFound
+		);
+		assert_noop!(
+			VestingManager::merge_schedules(Origin::signed(ALICE), vec![0, 2]),
+			Error::<Runtime>::IncompatibleSchedules
+		);
+
+		assert_ok!(VestingManager::merge_schedules(Origin::signed(ALICE), vec![1, 0]));
+		let merged = schedule(20, 10, 8, 30);
+		System::assert_last_event(Event::VestingManager(crate::Event::SchedulesMerged(
+			ALICE,
+			merged.clone(),
+		)));
+		assert_eq!(schedules(ALICE), vec![schedule(20, 10, 3, 10), merged]);
+		// the locked amount at block 25 is kept
+		assert_eq!(vesting_locked(ALICE), 30 + 240);
+	});
+}
+
+#[test]
+fn merge_vested_schedule_fails() {
+	ExtBuilder::default().build().execute_with(|| {
+		vested_transfer(ALICE, schedule(0, 10, 10, 10));
+		vested_transfer(ALICE, schedule(0, 10, 5, 10));
+
+		System::set_block_number(50);
+		assert_noop!(
+			VestingManager::merge_schedules(Origin::signed(ALICE), vec![0, 1]),
+			Error::<Runtime>::ScheduleVested
+		);
+	});
+}
+
+#[test]
+fn split_schedule_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		vested_transfer(ALICE, schedule(0, 10, 10, 10));
+
+		System::set_block_number(25);
+		assert_noop!(
+			VestingManager::split_schedule(Origin::signed(ALICE), 1, BOB, 4),
+			Error::<Runtime>::ScheduleNotFound
+		);
+		assert_noop!(
+			VestingManager::split_schedule(Origin::signed(ALICE), 0, BOB, 0),
+			Error::<Runtime>::InvalidSplitAmount
+		);
+		assert_noop!(
+			VestingManager::split_schedule(Origin::signed(ALICE), 0, BOB, 11),
+			Error::<Runtime>::InvalidSplitAmount
+		);
+
+		assert_ok!(VestingManager::split_schedule(Origin::signed(ALICE), 0, BOB, 4));
+		let split = schedule(20, 10, 8, 4);
+		System::assert_last_event(Event::VestingManager(crate::Event::ScheduleSplit(
+			ALICE,
+			BOB,
+			split.clone(),
+		)));
+		assert_eq!(schedules(ALICE), vec![schedule(20, 10, 8, 6)]);
+		assert_eq!(schedules(BOB), vec![split]);
+		// the vested 20 is unlocked for ALICE
+		assert_eq!(PalletBalances::free_balance(ALICE), 1 + 100 - 32);
+		assert_eq!(vesting_locked(ALICE), 48);
+		assert_eq!(PalletBalances::free_balance(BOB), 1 + 32);
+		assert_eq!(vesting_locked(BOB), 32);
+	});
+}
+
+#[test]
+fn split_whole_schedule_transfers_it() {
+	ExtBuilder::default().build().execute_with(|| {
+		vested_transfer(ALICE, schedule(0, 10, 10, 10));
+
+		System::set_block_number(25);
+		assert_ok!(VestingManager::split_schedule(Origin::signed(ALICE), 0, BOB, 10));
+		assert_eq!(schedules(ALICE), vec![]);
+		assert!(!VestingSchedules::<Runtime>::contains_key(ALICE));
+		assert_eq!(vesting_locked(ALICE), 0);
+		assert_eq!(PalletBalances::free_balance(ALICE), 21);
+		assert_eq!(schedules(BOB), vec![schedule(20, 10, 8, 10)]);
+		assert_eq!(vesting_locked(BOB), 80);
+	});
+}
+
+#[test]
+fn split_schedule_fails_with_low_amount_or_too_many_schedules() {
+	ExtBuilder::default().build().execute_with(|| {
+		vested_transfer(ALICE, schedule(0, 10, 10, 10));
+		vested_transfer(BOB, schedule(0, 10, 10, 10));
+		vested_transfer(BOB, schedule(0, 10, 10, 10));
+		vested_transfer(BOB, schedule(0, 10, 10, 10));
+
+		assert_noop!(
+			VestingManager::split_schedule(Origin::signed(ALICE), 0, BOB, 1),
+			Error::<Runtime>::MaxVestingSchedulesExceeded
+		);
+
+		System::set_block_number(95);
+		assert_noop!(
+			VestingManager::split_schedule(Origin::signed(ALICE), 0, CHARLIE, 1),
+			Error::<Runtime>::AmountLow
+		);
+	});
+}
+
+#[test]
+fn force_split_schedule_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		vested_transfer(ALICE, schedule(0, 10, 10, 10));
+
+		System::set_block_number(25);
+		assert_noop!(
+			VestingManager::force_split_schedule(Origin::signed(ALICE), ALICE, 0, BOB, 10),
+			BadOrigin
+		);
+		assert_ok!(VestingManager::force_split_schedule(
+			Origin::signed(TREASURY),
+			ALICE,
+			0,
+			CHARLIE,
+			10
+		));
+		assert_eq!(schedules(ALICE), vec![]);
+		assert_eq!(schedules(CHARLIE), vec![schedule(20, 10, 8, 10)]);
+		assert_eq!(PalletBalances::free_balance(CHARLIE), 80);
+		assert_eq!(vesting_locked(CHARLIE), 80);
+	});
+}
+
+#[test]
+fn clawback_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		vested_transfer(ALICE, schedule(0, 10, 10, 10));
+		vested_transfer(ALICE, schedule(0, 10, 5, 10));
+		let treasury_balance = PalletBalances::free_balance(TREASURY);
+
+		System::set_block_number(25);
+		assert_noop!(
+			VestingManager::clawback(Origin::signed(ALICE), ALICE, 0, ALICE),
+			BadOrigin
+		);
+		assert_noop!(
+			VestingManager::clawback(Origin::signed(TREASURY), ALICE, 2, TREASURY),
+			Error::<Runtime>::ScheduleNotFound
+		);
+
+		assert_ok!(VestingManager::clawback(Origin::signed(TREASURY), ALICE, 0, TREASURY));
+		System::assert_last_event(Event::VestingManager(crate::Event::ClawedBack(ALICE, TREASURY, 80)));
+		assert_eq!(PalletBalances::free_balance(TREASURY), treasury_balance + 80);
+		assert_eq!(schedules(ALICE), vec![schedule(20, 10, 3, 10)]);
+		assert_eq!(PalletBalances::free_balance(ALICE), 1 + 150 - 80);
+		assert_eq!(vesting_locked(ALICE), 30);
+
+		System::set_block_number(50);
+		assert_noop!(
+			VestingManager::clawback(Origin::signed(TREASURY), ALICE, 0, TREASURY),
+			Error::<Runtime>::ScheduleVested
+		);
+	});
+}
@@ -0,0 +1,367 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Vote Escrow Module
+//!
+//! ## Overview
+//!
+//! Users lock native currency for a period up to `MaxLockPeriod` to receive the voting power,
+//! which is `amount * remaining_lock_period / MaxLockPeriod` and decays linearly to zero at
+//! unlock. The unlock block number is rounded down to multiples of `LockRoundingPeriod`, so the
+//! total voting power only needs a checkpoint at the beginning of each rounding period. The locked
+//! currency can be withdrawn after unlock.
+//!
+//! The voting power is provided to other modules(e.g. boost the incentives rewards) by the
+//! `VotingEscrow` trait, and to governance by the `VotingPowerProvider` trait.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{
+	log,
+	pallet_prelude::*,
+	traits::{Get, LockIdentifier},
+	transactional,
+};
+use frame_system::pallet_prelude::*;
+use orml_traits::{BasicCurrency, BasicLockableCurrency};
+use primitives::Balance;
+use sp_runtime::{
+	helpers_128bit::multiply_by_rational,
+	traits::{Saturating, UniqueSaturatedInto, Zero},
+	RuntimeDebug,
+};
+use support::{VotingEscrow, VotingPowerProvider};
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+/// The locked native currency of an account.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, Default)]
+pub struct LockedBalance<BlockNumber> {
+	/// The locked amount.
+	pub amount: Balance,
+	/// The block number at which the lock expires.
+	pub end: BlockNumber,
+}
+
+/// The checkpoint of the total voting power, scaled by `MaxLockPeriod`.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, Default)]
+pub struct SupplyPoint<BlockNumber> {
+	/// The total voting power at `block`, scaled by `MaxLockPeriod`.
+	pub bias: Balance,
+	/// The decrease of `bias` per block, which is the total amount of
+	/// unexpired locks.
+	pub slope: Balance,
+	/// The block number of the checkpoint.
+	pub block: BlockNumber,
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The native currency to lock.
+		type Currency: BasicLockableCurrency<Self::AccountId, Moment = Self::BlockNumber, Balance = Balance>;
+
+		/// The lock identifier of the locked native currency.
+		#[pallet::constant]
+		type PalletId: Get<LockIdentifier>;
+
+		/// The max lock period, the voting power of the amount locked for it
+		/// equals to the amount.
+		#[pallet::constant]
+		type MaxLockPeriod: Get<Self::BlockNumber>;
+
+		/// The unlock block number will be rounded down to multiples of it.
+		#[pallet::constant]
+		type LockRoundingPeriod: Get<Self::BlockNumber>;
+
+		/// The minimum amount to lock.
+		#[pallet::constant]
+		type MinLockAmount: Get<Balance>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The account has locked already
+		LockExists,
+		/// The account has no lock
+		NoLock,
+		/// The lock has expired
+		LockExpired,
+		/// The lock has not expired
+		LockNotExpired,
+		/// The lock period is invalid
+		InvalidLockPeriod,
+		/// The lock amount is below the minimum amount
+		BelowMinLockAmount,
+		/// The free balance is not enough to lock
+		BalanceTooLow,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Lock native currency. \[who, locked_amount, unlock_at\]
+		Locked(T::AccountId, Balance, T::BlockNumber),
+		/// Withdraw the expired lock. \[who, amount\]
+		Withdrawn(T::AccountId, Balance),
+	}
+
+	/// The locked native currency of account.
+	///
+	/// Locks: map AccountId => Option<LockedBalance>
+	#[pallet::storage]
+	#[pallet::getter(fn locks)]
+	pub type Locks<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, LockedBalance<T::BlockNumber>, OptionQuery>;
+
+	/// The last checkpoint of the total voting power.
+	///
+	/// Supply: SupplyPoint
+	#[pallet::storage]
+	#[pallet::getter(fn supply)]
+	pub type Supply<T: Config> = StorageValue<_, SupplyPoint<T::BlockNumber>, ValueQuery>;
+
+	/// The decrease of the slope of total voting power at the block number,
+	/// which is the total amount of locks expire at it.
+	///
+	/// SlopeChanges: map BlockNumber => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn slope_changes)]
+	pub type SlopeChanges<T: Config> = StorageMap<_, Twox64Concat, T::BlockNumber, Balance, ValueQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			// locks only expire at the beginning of rounding periods
+			if now % T::LockRoundingPeriod::get() == Zero::zero() {
+				let mut point = Self::checkpoint(now);
+				point.slope = point.slope.saturating_sub(SlopeChanges::<T>::take(now));
+				Supply::<T>::put(point);
+				T::WeightInfo::on_initialize()
+			} else {
+				0
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Lock `amount` of native currency for `lock_period`, the unlock block
+		/// number will be rounded down to multiples of `LockRoundingPeriod`.
+		#[pallet::weight(T::WeightInfo::create_lock())]
+		#[transactional]
+		pub fn create_lock(
+			origin: OriginFor<T>,
+			#[pallet::compact] amount: Balance,
+			lock_period: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::locks(&who).is_none(), Error::<T>::LockExists);
+			ensure!(amount >= T::MinLockAmount::get(), Error::<T>::BelowMinLockAmount);
+
+			let end = Self::unlock_at(lock_period)?;
+			Self::update_lock(&who, None, Some(LockedBalance { amount, end }))?;
+			Ok(().into())
+		}
+
+		/// Lock extra `amount` of native currency to the unexpired lock.
+		#[pallet::weight(T::WeightInfo::increase_amount())]
+		#[transactional]
+		pub fn increase_amount(origin: OriginFor<T>, #[pallet::compact] amount: Balance) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let lock = Self::locks(&who).ok_or(Error::<T>::NoLock)?;
+			ensure!(
+				lock.end > <frame_system::Pallet<T>>::block_number(),
+				Error::<T>::LockExpired
+			);
+
+			let new_lock = LockedBalance {
+				amount: lock.amount.saturating_add(amount),
+				end: lock.end,
+			};
+			Self::update_lock(&who, Some(lock), Some(new_lock))?;
+			Ok(().into())
+		}
+
+		/// Extend the unexpired lock to unlock after `lock_period` from now.
+		#[pallet::weight(T::WeightInfo::extend_lock())]
+		#[transactional]
+		pub fn extend_lock(origin: OriginFor<T>, lock_period: T::BlockNumber) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let lock = Self::locks(&who).ok_or(Error::<T>::NoLock)?;
+			ensure!(
+				lock.end > <frame_system::Pallet<T>>::block_number(),
+				Error::<T>::LockExpired
+			);
+
+			let end = Self::unlock_at(lock_period)?;
+			ensure!(end > lock.end, Error::<T>::InvalidLockPeriod);
+
+			let new_lock = LockedBalance {
+				amount: lock.amount,
+				end,
+			};
+			Self::update_lock(&who, Some(lock), Some(new_lock))?;
+			Ok(().into())
+		}
+
+		/// Withdraw the native currency of the expired lock.
+		#[pallet::weight(T::WeightInfo::withdraw())]
+		#[transactional]
+		pub fn withdraw(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let lock = Self::locks(&who).ok_or(Error::<T>::NoLock)?;
+			ensure!(
+				lock.end <= <frame_system::Pallet<T>>::block_number(),
+				Error::<T>::LockNotExpired
+			);
+
+			Self::update_lock(&who, Some(lock), None)?;
+			Self::deposit_event(Event::Withdrawn(who, lock.amount));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The unlock block number if lock for `lock_period` from now.
+	fn unlock_at(lock_period: T::BlockNumber) -> Result<T::BlockNumber, DispatchError> {
+		ensure!(lock_period <= T::MaxLockPeriod::get(), Error::<T>::InvalidLockPeriod);
+
+		let now = <frame_system::Pallet<T>>::block_number();
+		let rounding_period = T::LockRoundingPeriod::get();
+		let end = now.saturating_add(lock_period) / rounding_period * rounding_period;
+		ensure!(end > now, Error::<T>::InvalidLockPeriod);
+		Ok(end)
+	}
+
+	/// The total voting power checkpoint at `now`, the slope changes between
+	/// the last checkpoint and `now` must have been applied.
+	fn checkpoint(now: T::BlockNumber) -> SupplyPoint<T::BlockNumber> {
+		let point = Self::supply();
+		let elapsed: Balance = now.saturating_sub(point.block).unique_saturated_into();
+		SupplyPoint {
+			bias: point.bias.saturating_sub(point.slope.saturating_mul(elapsed)),
+			slope: point.slope,
+			block: now,
+		}
+	}
+
+	/// Replace the `old_lock` of `who` with `new_lock`, and update the total
+	/// voting power and the lock of native currency.
+	fn update_lock(
+		who: &T::AccountId,
+		old_lock: Option<LockedBalance<T::BlockNumber>>,
+		new_lock: Option<LockedBalance<T::BlockNumber>>,
+	) -> DispatchResult {
+		let now = <frame_system::Pallet<T>>::block_number();
+		let mut point = Self::checkpoint(now);
+
+		if let Some(lock) = old_lock {
+			if lock.end > now {
+				let remaining: Balance = lock.end.saturating_sub(now).unique_saturated_into();
+				point.bias = point.bias.saturating_sub(lock.amount.saturating_mul(remaining));
+				point.slope = point.slope.saturating_sub(lock.amount);
+				SlopeChanges::<T>::mutate(lock.end, |slope| *slope = slope.saturating_sub(lock.amount));
+			}
+		}
+
+		match new_lock {
+			Some(lock) => {
+				ensure!(T::Currency::free_balance(who) >= lock.amount, Error::<T>::BalanceTooLow);
+
+				let remaining: Balance = lock.end.saturating_sub(now).unique_saturated_into();
+				point.bias = point.bias.saturating_add(lock.amount.saturating_mul(remaining));
+				point.slope = point.slope.saturating_add(lock.amount);
+				SlopeChanges::<T>::mutate(lock.end, |slope| *slope = slope.saturating_add(lock.amount));
+
+				T::Currency::set_lock(T::PalletId::get(), who, lock.amount)?;
+				Locks::<T>::insert(who, lock);
+				Self::deposit_event(Event::Locked(who.clone(), lock.amount, lock.end));
+			}
+			None => {
+				let res = T::Currency::remove_lock(T::PalletId::get(), who);
+				if let Err(e) = res {
+					log::warn!(
+						target: "vote-escrow",
+						"remove_lock: failed to remove lock for {:?}: {:?}. \
+						This is unexpected but should be safe",
+						who.clone(), e
+					);
+					debug_assert!(false);
+				}
+				Locks::<T>::remove(who);
+			}
+		}
+
+		Supply::<T>::put(point);
+		Ok(())
+	}
+}
+
+impl<T: Config> VotingEscrow<T::AccountId, Balance> for Pallet<T> {
+	fn balance_of(who: &T::AccountId) -> Balance {
+		let now = <frame_system::Pallet<T>>::block_number();
+		match Self::locks(who) {
+			Some(lock) if lock.end > now => multiply_by_rational(
+				lock.amount,
+				lock.end.saturating_sub(now).unique_saturated_into(),
+				T::MaxLockPeriod::get().unique_saturated_into(),
+			)
+			.unwrap_or_default(),
+			_ => Zero::zero(),
+		}
+	}
+
+	fn total_supply() -> Balance {
+		let point = Self::checkpoint(<frame_system::Pallet<T>>::block_number());
+		let max_lock_period: Balance = T::MaxLockPeriod::get().unique_saturated_into();
+		point.bias.checked_div(max_lock_period).unwrap_or_default()
+	}
+}
+
+impl<T: Config> VotingPowerProvider<T::AccountId, Balance> for Pallet<T> {
+	fn voting_power(who: &T::AccountId) -> Balance {
+		Self::balance_of(who)
+	}
+
+	// the escrowed balance is locked by the escrow until it expires
+	fn lock_voting_power(_: LockIdentifier, _: &T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	fn unlock_voting_power(_: LockIdentifier, _: &T::AccountId) -> DispatchResult {
+		Ok(())
+	}
+}
@@ -0,0 +1,162 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for vote escrow module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+
+#[test]
+fn create_lock_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			VoteEscrowModule::create_lock(Origin::signed(ALICE), 5, 100),
+			Error::<Runtime>::BelowMinLockAmount
+		);
+		assert_noop!(
+			VoteEscrowModule::create_lock(Origin::signed(ALICE), 100, 101),
+			Error::<Runtime>::InvalidLockPeriod
+		);
+		assert_noop!(
+			VoteEscrowModule::create_lock(Origin::signed(ALICE), 100, 5),
+			Error::<Runtime>::InvalidLockPeriod
+		);
+		assert_noop!(
+			VoteEscrowModule::create_lock(Origin::signed(ALICE), 2000, 100),
+			Error::<Runtime>::BalanceTooLow
+		);
+
+		assert_ok!(VoteEscrowModule::create_lock(Origin::signed(ALICE), 100, 100));
+		System::assert_last_event(Event::VoteEscrowModule(crate::Event::Locked(ALICE, 100, 100)));
+		assert_eq!(
+			VoteEscrowModule::locks(ALICE),
+			Some(LockedBalance { amount: 100, end: 100 })
+		);
+		assert_eq!(PalletBalances::usable_balance(&ALICE), 900);
+		assert_eq!(VoteEscrowModule::balance_of(&ALICE), 99);
+		assert_eq!(VoteEscrowModule::total_supply(), 99);
+		assert_eq!(VoteEscrowModule::slope_changes(100), 100);
+
+		assert_noop!(
+			VoteEscrowModule::create_lock(Origin::signed(ALICE), 100, 100),
+			Error::<Runtime>::LockExists
+		);
+	});
+}
+
+#[test]
+fn increase_amount_and_extend_lock_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			VoteEscrowModule::increase_amount(Origin::signed(ALICE), 100),
+			Error::<Runtime>::NoLock
+		);
+		assert_noop!(
+			VoteEscrowModule::extend_lock(Origin::signed(ALICE), 100),
+			Error::<Runtime>::NoLock
+		);
+		assert_ok!(VoteEscrowModule::create_lock(Origin::signed(ALICE), 100, 50));
+		assert_eq!(
+			VoteEscrowModule::locks(ALICE),
+			Some(LockedBalance { amount: 100, end: 50 })
+		);
+
+		System::set_block_number(11);
+		assert_eq!(VoteEscrowModule::balance_of(&ALICE), 39);
+		assert_ok!(VoteEscrowModule::increase_amount(Origin::signed(ALICE), 100));
+		System::assert_last_event(Event::VoteEscrowModule(crate::Event::Locked(ALICE, 200, 50)));
+		assert_eq!(PalletBalances::usable_balance(&ALICE), 800);
+		assert_eq!(VoteEscrowModule::balance_of(&ALICE), 78);
+		assert_eq!(VoteEscrowModule::total_supply(), 78);
+		assert_noop!(
+			VoteEscrowModule::increase_amount(Origin::signed(ALICE), 1000),
+			Error::<Runtime>::BalanceTooLow
+		);
+
+		assert_noop!(
+			VoteEscrowModule::extend_lock(Origin::signed(ALICE), 30),
+			Error::<Runtime>::InvalidLockPeriod
+		);
+		assert_ok!(VoteEscrowModule::extend_lock(Origin::signed(ALICE), 80));
+		System::assert_last_event(Event::VoteEscrowModule(crate::Event::Locked(ALICE, 200, 90)));
+		assert_eq!(VoteEscrowModule::balance_of(&ALICE), 158);
+		assert_eq!(VoteEscrowModule::total_supply(), 158);
+		assert_eq!(VoteEscrowModule::slope_changes(50), 0);
+		assert_eq!(VoteEscrowModule::slope_changes(90), 200);
+
+		System::set_block_number(90);
+		assert_noop!(
+			VoteEscrowModule::increase_amount(Origin::signed(ALICE), 100),
+			Error::<Runtime>::LockExpired
+		);
+		assert_noop!(
+			VoteEscrowModule::extend_lock(Origin::signed(ALICE), 50),
+			Error::<Runtime>::LockExpired
+		);
+	});
+}
+
+#[test]
+fn voting_power_decay_and_withdraw_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(VoteEscrowModule::create_lock(Origin::signed(ALICE), 100, 20));
+		assert_ok!(VoteEscrowModule::create_lock(Origin::signed(BOB), 100, 50));
+		assert_eq!(VoteEscrowModule::total_supply(), 68);
+
+		System::set_block_number(10);
+		VoteEscrowModule::on_initialize(10);
+		assert_eq!(VoteEscrowModule::balance_of(&ALICE), 10);
+		assert_eq!(VoteEscrowModule::balance_of(&BOB), 40);
+		assert_eq!(VoteEscrowModule::total_supply(), 50);
+
+		System::set_block_number(20);
+		VoteEscrowModule::on_initialize(20);
+		assert_eq!(
+			VoteEscrowModule::supply(),
+			SupplyPoint {
+				bias: 3000,
+				slope: 100,
+				block: 20,
+			}
+		);
+		assert_eq!(VoteEscrowModule::balance_of(&ALICE), 0);
+		assert_eq!(VoteEscrowModule::total_supply(), 30);
+
+		System::set_block_number(25);
+		assert_eq!(VoteEscrowModule::total_supply(), 25);
+		assert_noop!(
+			VoteEscrowModule::withdraw(Origin::signed(BOB)),
+			Error::<Runtime>::LockNotExpired
+		);
+		assert_ok!(VoteEscrowModule::withdraw(Origin::signed(ALICE)));
+		System::assert_last_event(Event::VoteEscrowModule(crate::Event::Withdrawn(ALICE, 100)));
+		assert_eq!(VoteEscrowModule::locks(ALICE), None);
+		assert_eq!(PalletBalances::usable_balance(&ALICE), 1000);
+		assert_eq!(VoteEscrowModule::total_supply(), 25);
+		assert_noop!(
+			VoteEscrowModule::withdraw(Origin::signed(ALICE)),
+			Error::<Runtime>::NoLock
+		);
+	});
+}
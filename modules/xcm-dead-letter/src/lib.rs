@@ -0,0 +1,252 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # XCM Dead Letter Module
+//!
+//! ## Overview
+//!
+//! The inbound XCM asset deposits which fail, e.g. the asset is unknown or the amount is below the
+//! existential deposit, are recorded into a dead letter queue by `DeadLetterAssetTransactor`
+//! instead of being lost. The beneficiary can claim the failed transfer once the deposit can
+//! succeed, e.g. the asset is registered or the account exists, and the recovery origin can
+//! deposit it to any account, e.g. when the beneficiary is not a local account. Each failed
+//! transfer is removed from the queue once it's claimed or recovered, so it can't be claimed twice.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{log, pallet_prelude::*, transactional};
+use frame_system::pallet_prelude::*;
+use sp_runtime::{traits::Convert, RuntimeDebug};
+use sp_std::{marker::PhantomData, prelude::*};
+use xcm::v0::{Error as XcmError, MultiAsset, MultiLocation, Result as XcmResult};
+use xcm_executor::{
+	traits::{Convert as XcmConvert, TransactAsset},
+	Assets,
+};
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+pub type FailedTransferId = u64;
+
+/// The inbound asset deposit which failed.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct FailedTransfer {
+	/// The asset to deposit.
+	pub asset: MultiAsset,
+	/// The beneficiary of the deposit.
+	pub beneficiary: MultiLocation,
+	/// The error of the deposit.
+	pub error: XcmError,
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The asset transactor which deposits the assets.
+		type AssetTransactor: TransactAsset;
+
+		/// Convert the beneficiary location to the local account.
+		type LocationToAccountId: XcmConvert<MultiLocation, Self::AccountId>;
+
+		/// Convert the local account to its location.
+		type AccountIdToMultiLocation: Convert<Self::AccountId, MultiLocation>;
+
+		/// The origin which may recover the failed transfers to any account.
+		type RecoveryOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The maximum number of failed transfers in the queue, the failed deposits are not
+		/// recorded once the queue is full.
+		#[pallet::constant]
+		type MaxFailedTransfers: Get<u32>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The failed transfer does not exist
+		FailedTransferNotFound,
+		/// The caller is not the beneficiary of the failed transfer
+		NotBeneficiary,
+		/// The deposit of the failed transfer failed again
+		DepositFailed,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The inbound deposit failed and was recorded. \[id, asset, beneficiary, error\]
+		TransferFailed(FailedTransferId, MultiAsset, MultiLocation, XcmError),
+		/// The beneficiary claimed the failed transfer. \[id, who\]
+		FailedTransferClaimed(FailedTransferId, T::AccountId),
+		/// The recovery origin recovered the failed transfer. \[id, dest\]
+		FailedTransferRecovered(FailedTransferId, T::AccountId),
+	}
+
+	/// The failed transfers waiting to be claimed.
+	///
+	/// FailedTransfers: map FailedTransferId => Option<FailedTransfer>
+	#[pallet::storage]
+	#[pallet::getter(fn failed_transfers)]
+	pub type FailedTransfers<T: Config> = StorageMap<_, Twox64Concat, FailedTransferId, FailedTransfer, OptionQuery>;
+
+	/// The number of failed transfers in the queue.
+	///
+	/// FailedTransferCount: u32
+	#[pallet::storage]
+	#[pallet::getter(fn failed_transfer_count)]
+	pub type FailedTransferCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// The next failed transfer id.
+	///
+	/// NextFailedTransferId: FailedTransferId
+	#[pallet::storage]
+	#[pallet::getter(fn next_failed_transfer_id)]
+	pub type NextFailedTransferId<T: Config> = StorageValue<_, FailedTransferId, ValueQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Claim the failed transfer, which is deposited to the caller again.
+		///
+		/// The dispatch origin of this call must be the beneficiary of the failed transfer.
+		///
+		/// - `id`: the id of the failed transfer.
+		#[pallet::weight(T::WeightInfo::claim_failed_transfer())]
+		#[transactional]
+		pub fn claim_failed_transfer(origin: OriginFor<T>, id: FailedTransferId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let transfer = Self::failed_transfers(id).ok_or(Error::<T>::FailedTransferNotFound)?;
+			ensure!(
+				T::LocationToAccountId::convert_ref(&transfer.beneficiary) == Ok(who.clone()),
+				Error::<T>::NotBeneficiary
+			);
+
+			Self::do_deposit(id, &transfer.asset, &who)?;
+			Self::deposit_event(Event::FailedTransferClaimed(id, who));
+			Ok(().into())
+		}
+
+		/// Recover the failed transfer to `dest`.
+		///
+		/// The dispatch origin of this call must be `RecoveryOrigin`.
+		///
+		/// - `id`: the id of the failed transfer.
+		/// - `dest`: the account to deposit to.
+		#[pallet::weight(T::WeightInfo::recover_failed_transfer())]
+		#[transactional]
+		pub fn recover_failed_transfer(
+			origin: OriginFor<T>,
+			id: FailedTransferId,
+			dest: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			T::RecoveryOrigin::ensure_origin(origin)?;
+			let transfer = Self::failed_transfers(id).ok_or(Error::<T>::FailedTransferNotFound)?;
+
+			Self::do_deposit(id, &transfer.asset, &dest)?;
+			Self::deposit_event(Event::FailedTransferRecovered(id, dest));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	fn do_deposit(id: FailedTransferId, asset: &MultiAsset, who: &T::AccountId) -> DispatchResult {
+		T::AssetTransactor::deposit_asset(asset, &T::AccountIdToMultiLocation::convert(who.clone()))
+			.map_err(|_| Error::<T>::DepositFailed)?;
+		FailedTransfers::<T>::remove(id);
+		FailedTransferCount::<T>::mutate(|count| *count = count.saturating_sub(1));
+		Ok(())
+	}
+
+	/// Record the failed deposit into the queue, returns `Err` if the queue is full.
+	fn record_failed_deposit(asset: &MultiAsset, beneficiary: &MultiLocation, error: XcmError) -> XcmResult {
+		let count = Self::failed_transfer_count();
+		if count >= T::MaxFailedTransfers::get() {
+			return Err(error);
+		}
+
+		let id = Self::next_failed_transfer_id();
+		NextFailedTransferId::<T>::put(id.saturating_add(1));
+		FailedTransferCount::<T>::put(count.saturating_add(1));
+		FailedTransfers::<T>::insert(
+			id,
+			FailedTransfer {
+				asset: asset.clone(),
+				beneficiary: beneficiary.clone(),
+				error: error.clone(),
+			},
+		);
+
+		Self::deposit_event(Event::TransferFailed(id, asset.clone(), beneficiary.clone(), error));
+		Ok(())
+	}
+}
+
+/// The asset transactor which records the failed deposits of `T::AssetTransactor` into the dead
+/// letter queue, and delegates everything else to it.
+pub struct DeadLetterAssetTransactor<T>(PhantomData<T>);
+impl<T: Config> TransactAsset for DeadLetterAssetTransactor<T> {
+	fn can_check_in(origin: &MultiLocation, what: &MultiAsset) -> XcmResult {
+		T::AssetTransactor::can_check_in(origin, what)
+	}
+
+	fn check_in(origin: &MultiLocation, what: &MultiAsset) {
+		T::AssetTransactor::check_in(origin, what)
+	}
+
+	fn check_out(dest: &MultiLocation, what: &MultiAsset) {
+		T::AssetTransactor::check_out(dest, what)
+	}
+
+	fn deposit_asset(what: &MultiAsset, who: &MultiLocation) -> XcmResult {
+		T::AssetTransactor::deposit_asset(what, who).or_else(|error| {
+			log::debug!(
+				target: "xcm-dead-letter",
+				"deposit_asset: failed to deposit {:?} to {:?}: {:?}",
+				what, who, error
+			);
+			Pallet::<T>::record_failed_deposit(what, who, error)
+		})
+	}
+
+	fn withdraw_asset(what: &MultiAsset, who: &MultiLocation) -> Result<Assets, XcmError> {
+		T::AssetTransactor::withdraw_asset(what, who)
+	}
+
+	fn transfer_asset(asset: &MultiAsset, from: &MultiLocation, to: &MultiLocation) -> Result<Assets, XcmError> {
+		T::AssetTransactor::transfer_asset(asset, from, to)
+	}
+}
@@ -0,0 +1,195 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Mocks for the xcm dead letter module.
+
+#![cfg(test)]
+
+use super::*;
+
+use crate as xcm_dead_letter;
+use frame_support::{construct_runtime, ord_parameter_types, parameter_types};
+use frame_system::EnsureSignedBy;
+use sp_core::H256;
+use sp_runtime::{testing::Header, traits::IdentityLookup};
+use sp_std::{cell::RefCell, collections::btree_map::BTreeMap};
+use xcm::v0::{Junction, NetworkId};
+
+pub type AccountId = u128;
+pub type BlockNumber = u64;
+pub type Balance = u128;
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const COUNCIL: AccountId = 100;
+pub const KNOWN: &[u8] = b"KNOWN";
+pub const UNKNOWN: &[u8] = b"UNKNOWN";
+pub const EXISTENTIAL_DEPOSIT: Balance = 10;
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Runtime {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+pub fn asset(key: &[u8], amount: Balance) -> MultiAsset {
+	MultiAsset::ConcreteFungible {
+		id: MultiLocation::X1(Junction::GeneralKey(key.to_vec())),
+		amount,
+	}
+}
+
+pub fn location(who: AccountId) -> MultiLocation {
+	MultiLocation::X1(Junction::AccountIndex64 {
+		network: NetworkId::Any,
+		index: who as u64,
+	})
+}
+
+thread_local! {
+	static KNOWN_ASSETS: RefCell<Vec<Vec<u8>>> = RefCell::new(vec![KNOWN.to_vec()]);
+	static BALANCES: RefCell<BTreeMap<(Vec<u8>, AccountId), Balance>> = RefCell::new(BTreeMap::new());
+}
+
+pub fn register_asset(key: &[u8]) {
+	KNOWN_ASSETS.with(|v| v.borrow_mut().push(key.to_vec()));
+}
+
+pub fn balance(key: &[u8], who: AccountId) -> Balance {
+	BALANCES.with(|v| v.borrow().get(&(key.to_vec(), who)).copied().unwrap_or_default())
+}
+
+pub struct AccountIdConvert;
+impl XcmConvert<MultiLocation, AccountId> for AccountIdConvert {
+	fn convert(location: MultiLocation) -> Result<AccountId, MultiLocation> {
+		match location {
+			MultiLocation::X1(Junction::AccountIndex64 {
+				network: NetworkId::Any,
+				index,
+			}) => Ok(index.into()),
+			_ => Err(location),
+		}
+	}
+}
+
+pub struct AccountIdToMultiLocation;
+impl Convert<AccountId, MultiLocation> for AccountIdToMultiLocation {
+	fn convert(who: AccountId) -> MultiLocation {
+		location(who)
+	}
+}
+
+/// Deposits the known assets to the local accounts, the deposits which are below the existential
+/// deposit of the accounts without balance fail.
+pub struct MockAssetTransactor;
+impl TransactAsset for MockAssetTransactor {
+	fn deposit_asset(what: &MultiAsset, who: &MultiLocation) -> XcmResult {
+		let (key, amount) = match what {
+			MultiAsset::ConcreteFungible {
+				id: MultiLocation::X1(Junction::GeneralKey(key)),
+				amount,
+			} if KNOWN_ASSETS.with(|v| v.borrow().contains(key)) => (key.clone(), *amount),
+			_ => return Err(XcmError::AssetNotFound),
+		};
+		let who = AccountIdConvert::convert_ref(who)
+			.map_err(|_| XcmError::FailedToTransactAsset("AccountIdConversionFailed"))?;
+
+		BALANCES.with(|v| {
+			let mut balances = v.borrow_mut();
+			let balance = balances.entry((key, who)).or_default();
+			if *balance + amount < EXISTENTIAL_DEPOSIT {
+				return Err(XcmError::FailedToTransactAsset("BelowExistentialDeposit"));
+			}
+			*balance += amount;
+			Ok(())
+		})
+	}
+}
+
+ord_parameter_types! {
+	pub const Council: AccountId = COUNCIL;
+}
+
+parameter_types! {
+	pub const MaxFailedTransfers: u32 = 2;
+}
+
+impl Config for Runtime {
+	type Event = Event;
+	type AssetTransactor = MockAssetTransactor;
+	type LocationToAccountId = AccountIdConvert;
+	type AccountIdToMultiLocation = AccountIdToMultiLocation;
+	type RecoveryOrigin = EnsureSignedBy<Council, AccountId>;
+	type MaxFailedTransfers = MaxFailedTransfers;
+	type WeightInfo = ();
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		XcmDeadLetter: xcm_dead_letter::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+pub type DeadLetter = DeadLetterAssetTransactor<Runtime>;
+
+#[derive(Default)]
+pub struct ExtBuilder;
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let t = frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap();
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
@@ -0,0 +1,135 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the xcm dead letter module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+use sp_runtime::traits::BadOrigin;
+
+#[test]
+fn failed_deposit_is_recorded() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(DeadLetter::deposit_asset(&asset(KNOWN, 100), &location(ALICE)));
+		assert_eq!(balance(KNOWN, ALICE), 100);
+		assert_eq!(XcmDeadLetter::next_failed_transfer_id(), 0);
+
+		assert_ok!(DeadLetter::deposit_asset(&asset(UNKNOWN, 100), &location(ALICE)));
+		System::assert_last_event(Event::XcmDeadLetter(crate::Event::TransferFailed(
+			0,
+			asset(UNKNOWN, 100),
+			location(ALICE),
+			XcmError::AssetNotFound,
+		)));
+		assert_eq!(
+			XcmDeadLetter::failed_transfers(0),
+			Some(FailedTransfer {
+				asset: asset(UNKNOWN, 100),
+				beneficiary: location(ALICE),
+				error: XcmError::AssetNotFound,
+			})
+		);
+
+		// below the existential deposit
+		assert_ok!(DeadLetter::deposit_asset(&asset(KNOWN, 5), &location(BOB)));
+		assert_eq!(balance(KNOWN, BOB), 0);
+		// the reason of `FailedToTransactAsset` is not encoded
+		assert!(matches!(
+			XcmDeadLetter::failed_transfers(1),
+			Some(FailedTransfer {
+				error: XcmError::FailedToTransactAsset(_),
+				..
+			})
+		));
+		assert_eq!(XcmDeadLetter::failed_transfer_count(), 2);
+	});
+}
+
+#[test]
+fn failed_deposit_is_not_recorded_when_queue_is_full() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(DeadLetter::deposit_asset(&asset(UNKNOWN, 100), &location(ALICE)));
+		assert_ok!(DeadLetter::deposit_asset(&asset(UNKNOWN, 200), &location(ALICE)));
+		assert_eq!(
+			DeadLetter::deposit_asset(&asset(UNKNOWN, 300), &location(ALICE)),
+			Err(XcmError::AssetNotFound)
+		);
+		assert_eq!(XcmDeadLetter::failed_transfer_count(), 2);
+		assert_eq!(XcmDeadLetter::failed_transfers(2), None);
+	});
+}
+
+#[test]
+fn claim_failed_transfer_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(DeadLetter::deposit_asset(&asset(UNKNOWN, 100), &location(ALICE)));
+
+		assert_noop!(
+			XcmDeadLetter::claim_failed_transfer(Origin::signed(ALICE), 1),
+			Error::<Runtime>::FailedTransferNotFound
+		);
+		assert_noop!(
+			XcmDeadLetter::claim_failed_transfer(Origin::signed(BOB), 0),
+			Error::<Runtime>::NotBeneficiary
+		);
+		assert_noop!(
+			XcmDeadLetter::claim_failed_transfer(Origin::signed(ALICE), 0),
+			Error::<Runtime>::DepositFailed
+		);
+
+		register_asset(UNKNOWN);
+		assert_ok!(XcmDeadLetter::claim_failed_transfer(Origin::signed(ALICE), 0));
+		System::assert_last_event(Event::XcmDeadLetter(crate::Event::FailedTransferClaimed(0, ALICE)));
+		assert_eq!(balance(UNKNOWN, ALICE), 100);
+		assert_eq!(XcmDeadLetter::failed_transfers(0), None);
+		assert_eq!(XcmDeadLetter::failed_transfer_count(), 0);
+
+		assert_noop!(
+			XcmDeadLetter::claim_failed_transfer(Origin::signed(ALICE), 0),
+			Error::<Runtime>::FailedTransferNotFound
+		);
+	});
+}
+
+#[test]
+fn recover_failed_transfer_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		// the beneficiary is not a local account
+		let beneficiary = MultiLocation::X1(xcm::v0::Junction::Parent);
+		assert_ok!(DeadLetter::deposit_asset(&asset(KNOWN, 100), &beneficiary));
+		assert_eq!(XcmDeadLetter::failed_transfer_count(), 1);
+
+		assert_noop!(
+			XcmDeadLetter::recover_failed_transfer(Origin::signed(ALICE), 0, BOB),
+			BadOrigin
+		);
+		assert_noop!(
+			XcmDeadLetter::recover_failed_transfer(Origin::signed(COUNCIL), 1, BOB),
+			Error::<Runtime>::FailedTransferNotFound
+		);
+
+		assert_ok!(XcmDeadLetter::recover_failed_transfer(Origin::signed(COUNCIL), 0, BOB));
+		System::assert_last_event(Event::XcmDeadLetter(crate::Event::FailedTransferRecovered(0, BOB)));
+		assert_eq!(balance(KNOWN, BOB), 100);
+		assert_eq!(XcmDeadLetter::failed_transfers(0), None);
+		assert_eq!(XcmDeadLetter::failed_transfer_count(), 0);
+	});
+}
@@ -0,0 +1,269 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # XCM EVM Module
+//!
+//! ## Overview
+//!
+//! Executes the EVM calls initiated from the sibling parachains via XCM `Transact` with the native
+//! origin of the sibling parachain. The call is sent on behalf of an account of the sibling
+//! parachain, and executed from the account derived from the parachain id and the account, which
+//! the sibling parachain can't forge for the other parachains. The derived account is also
+//! recognized by `DerivedAccountConvert`, so the assets transferred to the account location on the
+//! sibling parachain are deposited to it, e.g. to pay the value and the storage deposit of the
+//! call.
+//!
+//! The gas of the call is paid as the weight of `Transact`, `FeeRateTrader` buys the weight with
+//! the transferred assets at the fee rates set by the update origin, and uses the fallback trader
+//! for the other assets.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{pallet_prelude::*, transactional, weights::constants::WEIGHT_PER_SECOND};
+use frame_system::pallet_prelude::*;
+use primitives::{evm::EvmAddress, Balance, CurrencyId};
+use sp_io::hashing::blake2_256;
+use sp_runtime::traits::Convert;
+use sp_std::{marker::PhantomData, prelude::*};
+use support::EVMCall;
+use xcm::v0::{Error as XcmError, Junction, MultiAsset, MultiLocation};
+use xcm_builder::TakeRevenue;
+use xcm_executor::{
+	traits::{Convert as XcmConvert, WeightTrader},
+	Assets,
+};
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The EVM to execute the calls.
+		type EVM: EVMCall<Self::AccountId, Balance>;
+
+		/// The origin of the sibling parachains, returns the parachain id.
+		type SiblingOrigin: EnsureOrigin<Self::Origin, Success = u32>;
+
+		/// Convert gas to weight.
+		type GasToWeight: Convert<u64, Weight>;
+
+		/// Convert the location of the asset to the currency id.
+		type CurrencyIdConvert: Convert<MultiLocation, Option<CurrencyId>>;
+
+		/// The origin which may set the fee rates.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Executed the EVM call from the sibling parachain. \[para_id, who, contract\]
+		RemoteCallExecuted(u32, T::AccountId, EvmAddress),
+		/// Updated the fee per second of the currency. \[currency_id, fee_per_second\]
+		FeePerSecondUpdated(CurrencyId, Option<u128>),
+	}
+
+	/// The fee per second of weight of the currencies that can pay for the XCM execution.
+	///
+	/// FeePerSecond: map CurrencyId => Option<u128>
+	#[pallet::storage]
+	#[pallet::getter(fn fee_per_second)]
+	pub type FeePerSecond<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, u128, OptionQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Execute the EVM call on behalf of `account` of the sibling parachain, from the account
+		/// derived from the parachain id and `account`.
+		///
+		/// The dispatch origin of this call must be `SiblingOrigin`.
+		///
+		/// - `account`: the account of the sibling parachain.
+		/// - `target`: the contract address to call.
+		/// - `input`: the data supplied for the call.
+		/// - `value`: the amount sent for payable calls.
+		/// - `gas_limit`: the maximum gas the call can use.
+		/// - `storage_limit`: the total bytes the contract's storage can increase by.
+		#[pallet::weight(T::WeightInfo::call().saturating_add(T::GasToWeight::convert(*gas_limit)))]
+		#[transactional]
+		pub fn call(
+			origin: OriginFor<T>,
+			account: [u8; 32],
+			target: EvmAddress,
+			input: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			storage_limit: u32,
+		) -> DispatchResultWithPostInfo {
+			let para_id = T::SiblingOrigin::ensure_origin(origin)?;
+			let who = Self::derived_account(para_id, &account);
+
+			T::EVM::call_evm(&who, target, input, value, gas_limit, storage_limit)?;
+
+			Self::deposit_event(Event::RemoteCallExecuted(para_id, who, target));
+			Ok(().into())
+		}
+
+		/// Set or remove the fee per second of weight of the currency.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: the currency to pay the fee.
+		/// - `fee_per_second`: the fee per second of weight, `None` to remove it.
+		#[pallet::weight(T::WeightInfo::set_fee_per_second())]
+		#[transactional]
+		pub fn set_fee_per_second(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			fee_per_second: Option<u128>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			FeePerSecond::<T>::mutate_exists(currency_id, |maybe_fee| *maybe_fee = fee_per_second);
+			Self::deposit_event(Event::FeePerSecondUpdated(currency_id, fee_per_second));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The account derived from `account` of the sibling parachain `para_id`.
+	pub fn derived_account(para_id: u32, account: &[u8; 32]) -> T::AccountId {
+		let entropy = (b"xcm/evm", para_id, account).using_encoded(blake2_256);
+		T::AccountId::decode(&mut &entropy[..]).unwrap_or_default()
+	}
+
+	/// The fee per second of weight of the asset at `location`.
+	fn location_fee_per_second(location: &MultiLocation) -> Option<u128> {
+		T::CurrencyIdConvert::convert(location.clone()).and_then(Self::fee_per_second)
+	}
+}
+
+/// Converts the location of an account of a sibling parachain, i.e. `X3(Parent, Parachain(id),
+/// AccountId32)`, to the derived account.
+pub struct DerivedAccountConvert<T>(PhantomData<T>);
+impl<T: Config> XcmConvert<MultiLocation, T::AccountId> for DerivedAccountConvert<T> {
+	fn convert(location: MultiLocation) -> Result<T::AccountId, MultiLocation> {
+		match location {
+			MultiLocation::X3(Junction::Parent, Junction::Parachain(para_id), Junction::AccountId32 { id, .. }) => {
+				Ok(Pallet::<T>::derived_account(para_id, &id))
+			}
+			_ => Err(location),
+		}
+	}
+}
+
+fn fee_for(weight: Weight, fee_per_second: u128) -> u128 {
+	fee_per_second.saturating_mul(weight as u128) / (WEIGHT_PER_SECOND as u128)
+}
+
+/// Buys the weight with the first asset of the payment which has a fee rate, and uses `Fallback`
+/// if there's none. The asset used by the first purchase pays for the later ones, and the fee is
+/// taken by `R` when the trader is dropped.
+pub struct FeeRateTrader<T, Fallback, R: TakeRevenue> {
+	weight: Weight,
+	amount: u128,
+	asset: Option<(MultiLocation, u128)>,
+	fallback: Fallback,
+	_marker: PhantomData<(T, R)>,
+}
+
+impl<T: Config, Fallback: WeightTrader, R: TakeRevenue> WeightTrader for FeeRateTrader<T, Fallback, R> {
+	fn new() -> Self {
+		Self {
+			weight: 0,
+			amount: 0,
+			asset: None,
+			fallback: Fallback::new(),
+			_marker: PhantomData,
+		}
+	}
+
+	fn buy_weight(&mut self, weight: Weight, payment: Assets) -> Result<Assets, XcmError> {
+		let asset = self.asset.clone().or_else(|| {
+			payment.fungible_assets_iter().find_map(|asset| match asset {
+				MultiAsset::ConcreteFungible { id, .. } => {
+					Pallet::<T>::location_fee_per_second(&id).map(|fee_per_second| (id, fee_per_second))
+				}
+				_ => None,
+			})
+		});
+		let (id, fee_per_second) = match asset {
+			Some(asset) => asset,
+			None => return self.fallback.buy_weight(weight, payment),
+		};
+
+		let amount = fee_for(weight, fee_per_second);
+		let required = MultiAsset::ConcreteFungible { id: id.clone(), amount };
+		let unused = payment.less(required).map_err(|_| XcmError::TooExpensive)?;
+
+		self.weight = self.weight.saturating_add(weight);
+		self.amount = self.amount.saturating_add(amount);
+		self.asset = Some((id, fee_per_second));
+		Ok(unused)
+	}
+
+	fn refund_weight(&mut self, weight: Weight) -> MultiAsset {
+		match self.asset.clone() {
+			Some((id, fee_per_second)) => {
+				let weight = weight.min(self.weight);
+				let amount = fee_for(weight, fee_per_second).min(self.amount);
+				self.weight = self.weight.saturating_sub(weight);
+				self.amount = self.amount.saturating_sub(amount);
+				if amount > 0 {
+					MultiAsset::ConcreteFungible { id, amount }
+				} else {
+					MultiAsset::None
+				}
+			}
+			None => self.fallback.refund_weight(weight),
+		}
+	}
+}
+
+impl<T, Fallback, R: TakeRevenue> Drop for FeeRateTrader<T, Fallback, R> {
+	fn drop(&mut self) {
+		if let Some((id, _)) = self.asset.take() {
+			if self.amount > 0 {
+				R::take_revenue(MultiAsset::ConcreteFungible {
+					id,
+					amount: self.amount,
+				});
+			}
+		}
+	}
+}
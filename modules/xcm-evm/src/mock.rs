@@ -0,0 +1,198 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Mocks for the xcm evm module.
+
+#![cfg(test)]
+
+use super::*;
+
+use crate as xcm_evm;
+use frame_support::{construct_runtime, ord_parameter_types, parameter_types};
+use frame_system::{EnsureSignedBy, RawOrigin};
+use primitives::TokenSymbol;
+use sp_core::{H160, H256};
+use sp_runtime::{testing::Header, traits::IdentityLookup, AccountId32, DispatchError};
+use sp_std::cell::RefCell;
+
+pub type AccountId = AccountId32;
+pub type BlockNumber = u64;
+
+pub const ALICE: AccountId = AccountId32::new([1u8; 32]);
+pub const SIBLING: AccountId = AccountId32::new([2u8; 32]);
+pub const COUNCIL: AccountId = AccountId32::new([100u8; 32]);
+pub const SIBLING_PARA_ID: u32 = 2000;
+pub const REMOTE_ACCOUNT: [u8; 32] = [10u8; 32];
+pub const AUSD: CurrencyId = CurrencyId::Token(TokenSymbol::AUSD);
+pub const DOT: CurrencyId = CurrencyId::Token(TokenSymbol::DOT);
+
+pub fn contract() -> EvmAddress {
+	H160::from_low_u64_be(1)
+}
+
+pub fn reverting_contract() -> EvmAddress {
+	H160::from_low_u64_be(2)
+}
+
+pub fn currency_location(currency_id: CurrencyId) -> MultiLocation {
+	MultiLocation::X1(Junction::GeneralKey(currency_id.encode()))
+}
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Runtime {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+thread_local! {
+	static EVM_CALLS: RefCell<Vec<(AccountId, EvmAddress, Vec<u8>)>> = RefCell::new(vec![]);
+	static REVENUE: RefCell<Vec<MultiAsset>> = RefCell::new(vec![]);
+}
+
+pub fn evm_calls() -> Vec<(AccountId, EvmAddress, Vec<u8>)> {
+	EVM_CALLS.with(|v| v.borrow().clone())
+}
+
+pub fn revenue() -> Vec<MultiAsset> {
+	REVENUE.with(|v| v.borrow().clone())
+}
+
+/// Records the calls, and fails the calls to `reverting_contract`.
+pub struct MockEVM;
+impl EVMCall<AccountId, Balance> for MockEVM {
+	fn call_evm(
+		who: &AccountId,
+		contract: EvmAddress,
+		input: Vec<u8>,
+		_value: Balance,
+		_gas_limit: u64,
+		_storage_limit: u32,
+	) -> Result<Vec<u8>, DispatchError> {
+		if contract == reverting_contract() {
+			return Err(DispatchError::Other("reverted"));
+		}
+		EVM_CALLS.with(|v| v.borrow_mut().push((who.clone(), contract, input)));
+		Ok(vec![])
+	}
+}
+
+/// The signed origin of `SIBLING` is the origin of the sibling parachain `SIBLING_PARA_ID`.
+pub struct EnsureSiblingPara;
+impl EnsureOrigin<Origin> for EnsureSiblingPara {
+	type Success = u32;
+
+	fn try_origin(o: Origin) -> Result<u32, Origin> {
+		Into::<Result<RawOrigin<AccountId>, Origin>>::into(o).and_then(|o| match o {
+			RawOrigin::Signed(who) if who == SIBLING => Ok(SIBLING_PARA_ID),
+			r => Err(Origin::from(r)),
+		})
+	}
+}
+
+pub struct GasToWeight;
+impl Convert<u64, Weight> for GasToWeight {
+	fn convert(gas: u64) -> Weight {
+		gas
+	}
+}
+
+pub struct CurrencyIdConvert;
+impl Convert<MultiLocation, Option<CurrencyId>> for CurrencyIdConvert {
+	fn convert(location: MultiLocation) -> Option<CurrencyId> {
+		match location {
+			MultiLocation::X1(Junction::GeneralKey(key)) => CurrencyId::decode(&mut &key[..]).ok(),
+			_ => None,
+		}
+	}
+}
+
+pub struct MockRevenue;
+impl TakeRevenue for MockRevenue {
+	fn take_revenue(revenue: MultiAsset) {
+		REVENUE.with(|v| v.borrow_mut().push(revenue));
+	}
+}
+
+ord_parameter_types! {
+	pub const Council: AccountId = COUNCIL;
+}
+
+impl Config for Runtime {
+	type Event = Event;
+	type EVM = MockEVM;
+	type SiblingOrigin = EnsureSiblingPara;
+	type GasToWeight = GasToWeight;
+	type CurrencyIdConvert = CurrencyIdConvert;
+	type UpdateOrigin = EnsureSignedBy<Council, AccountId>;
+	type WeightInfo = ();
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		XcmEvm: xcm_evm::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+pub type Trader = FeeRateTrader<Runtime, (), MockRevenue>;
+
+#[derive(Default)]
+pub struct ExtBuilder;
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let t = frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap();
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
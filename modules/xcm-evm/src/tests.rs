@@ -0,0 +1,179 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the xcm evm module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+use sp_runtime::{traits::BadOrigin, DispatchError};
+use xcm::v0::NetworkId;
+
+fn concrete(currency_id: CurrencyId, amount: u128) -> MultiAsset {
+	MultiAsset::ConcreteFungible {
+		id: currency_location(currency_id),
+		amount,
+	}
+}
+
+#[test]
+fn call_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let who = XcmEvm::derived_account(SIBLING_PARA_ID, &REMOTE_ACCOUNT);
+
+		assert_ok!(XcmEvm::call(
+			Origin::signed(SIBLING),
+			REMOTE_ACCOUNT,
+			contract(),
+			vec![1, 2, 3],
+			0,
+			100_000,
+			0
+		));
+		System::assert_last_event(Event::XcmEvm(crate::Event::RemoteCallExecuted(
+			SIBLING_PARA_ID,
+			who.clone(),
+			contract(),
+		)));
+		assert_eq!(evm_calls(), vec![(who, contract(), vec![1, 2, 3])]);
+	});
+}
+
+#[test]
+fn call_requires_sibling_origin() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			XcmEvm::call(Origin::signed(ALICE), REMOTE_ACCOUNT, contract(), vec![], 0, 100_000, 0),
+			BadOrigin
+		);
+	});
+}
+
+#[test]
+fn call_fails_if_evm_call_fails() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			XcmEvm::call(
+				Origin::signed(SIBLING),
+				REMOTE_ACCOUNT,
+				reverting_contract(),
+				vec![],
+				0,
+				100_000,
+				0
+			),
+			DispatchError::Other("reverted")
+		);
+		assert_eq!(evm_calls(), vec![]);
+	});
+}
+
+#[test]
+fn derived_account_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let who = XcmEvm::derived_account(SIBLING_PARA_ID, &REMOTE_ACCOUNT);
+		assert_ne!(who, XcmEvm::derived_account(SIBLING_PARA_ID + 1, &REMOTE_ACCOUNT));
+		assert_ne!(who, XcmEvm::derived_account(SIBLING_PARA_ID, &[11u8; 32]));
+
+		let location = MultiLocation::X3(
+			Junction::Parent,
+			Junction::Parachain(SIBLING_PARA_ID),
+			Junction::AccountId32 {
+				network: NetworkId::Any,
+				id: REMOTE_ACCOUNT,
+			},
+		);
+		assert_eq!(DerivedAccountConvert::<Runtime>::convert(location), Ok(who));
+
+		let location = MultiLocation::X1(Junction::AccountId32 {
+			network: NetworkId::Any,
+			id: REMOTE_ACCOUNT,
+		});
+		assert_eq!(
+			DerivedAccountConvert::<Runtime>::convert(location.clone()),
+			Err(location)
+		);
+	});
+}
+
+#[test]
+fn set_fee_per_second_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			XcmEvm::set_fee_per_second(Origin::signed(ALICE), AUSD, Some(1)),
+			BadOrigin
+		);
+
+		assert_ok!(XcmEvm::set_fee_per_second(Origin::signed(COUNCIL), AUSD, Some(1)));
+		System::assert_last_event(Event::XcmEvm(crate::Event::FeePerSecondUpdated(AUSD, Some(1))));
+		assert_eq!(XcmEvm::fee_per_second(AUSD), Some(1));
+
+		assert_ok!(XcmEvm::set_fee_per_second(Origin::signed(COUNCIL), AUSD, None));
+		assert_eq!(XcmEvm::fee_per_second(AUSD), None);
+	});
+}
+
+#[test]
+fn trader_buys_weight_with_fee_rate() {
+	ExtBuilder::default().build().execute_with(|| {
+		let fee_per_second = 2 * WEIGHT_PER_SECOND as u128;
+		assert_ok!(XcmEvm::set_fee_per_second(
+			Origin::signed(COUNCIL),
+			AUSD,
+			Some(fee_per_second)
+		));
+
+		let mut trader = Trader::new();
+		let payment = Assets::from(vec![concrete(DOT, 1_000), concrete(AUSD, 1_000)]);
+		assert_eq!(
+			trader.buy_weight(100, payment),
+			Ok(Assets::from(vec![concrete(DOT, 1_000), concrete(AUSD, 800)]))
+		);
+		assert_eq!(trader.refund_weight(50), concrete(AUSD, 100));
+		assert_eq!(trader.refund_weight(100), concrete(AUSD, 100));
+		assert_eq!(trader.refund_weight(100), MultiAsset::None);
+		assert_eq!(revenue(), vec![]);
+
+		let mut trader = Trader::new();
+		assert_ok!(trader.buy_weight(100, Assets::from(vec![concrete(AUSD, 1_000)])));
+		assert_ok!(trader.buy_weight(100, Assets::from(vec![concrete(AUSD, 200)])));
+		assert_eq!(trader.refund_weight(50), concrete(AUSD, 100));
+		drop(trader);
+		assert_eq!(revenue(), vec![concrete(AUSD, 300)]);
+	});
+}
+
+#[test]
+fn trader_uses_fallback_without_fee_rate() {
+	ExtBuilder::default().build().execute_with(|| {
+		let mut trader = Trader::new();
+		assert_eq!(
+			trader.buy_weight(100, Assets::from(vec![concrete(AUSD, 1_000)])),
+			Err(XcmError::TooExpensive)
+		);
+
+		assert_ok!(XcmEvm::set_fee_per_second(Origin::signed(COUNCIL), AUSD, Some(1)));
+		assert_eq!(
+			trader.buy_weight(WEIGHT_PER_SECOND * 2, Assets::from(vec![concrete(AUSD, 1)])),
+			Err(XcmError::TooExpensive)
+		);
+		assert_eq!(revenue(), vec![]);
+	});
+}
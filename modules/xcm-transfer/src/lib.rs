@@ -0,0 +1,200 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # XCM Transfer Module
+//!
+//! ## Overview
+//!
+//! A user-facing cross-chain transfer to the known destination chains. The update origin registers
+//! the fee asset accepted by each destination chain to buy the XCM execution, the amount of the
+//! fee asset to pay and the weight limit of the execution. The transfers pick the fee asset and the
+//! weight limit from the registry, so users can't pick the wrong fee asset and have the transfer
+//! stuck on the destination chain. If the transferred currency is not the fee asset of the
+//! destination chain, the fee is paid in the fee asset by the sender in the same transfer.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{pallet_prelude::*, transactional};
+use frame_system::pallet_prelude::*;
+use primitives::{Balance, CurrencyId};
+use sp_runtime::RuntimeDebug;
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+/// The destination chain of the transfers.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum DestChain {
+	/// The relay chain.
+	Relay,
+	/// The parachain, by the id of the parachain.
+	Parachain(u32),
+}
+
+/// The XCM fee and weight limit of the transfers to the destination chain.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct DestinationInfo {
+	/// The asset accepted by the destination chain to buy the execution.
+	pub fee_currency_id: CurrencyId,
+	/// The amount of the fee asset to pay for the execution.
+	pub fee_amount: Balance,
+	/// The weight limit of the execution on the destination chain.
+	pub dest_weight: Weight,
+}
+
+/// Transfer the assets to the destination chains by XCM.
+pub trait XcmTransferHandler<AccountId> {
+	/// Transfer `amount` of `currency_id` from `who` to `recipient` on `dest`, the execution on
+	/// `dest` is paid with `fee`, or with the transferred asset if `fee` is `None`.
+	fn transfer(
+		who: &AccountId,
+		currency_id: CurrencyId,
+		amount: Balance,
+		fee: Option<(CurrencyId, Balance)>,
+		dest: DestChain,
+		recipient: [u8; 32],
+		dest_weight: Weight,
+	) -> DispatchResult;
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The origin which may register the destination chains.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Transfer the assets by XCM.
+		type XcmTransfer: XcmTransferHandler<Self::AccountId>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The destination chain is not registered
+		UnknownDestination,
+		/// The amount doesn't cover the fee of the destination chain
+		AmountTooLow,
+		/// The fee can't be paid in the fee asset with the transferred currency
+		FeeNotSupported,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The destination chain is registered, updated or removed. \[dest, info\]
+		DestinationUpdated(DestChain, Option<DestinationInfo>),
+		/// Transferred to the destination chain. \[who, currency_id, amount, dest, recipient,
+		/// fee_currency_id, fee_amount\]
+		Transferred(
+			T::AccountId,
+			CurrencyId,
+			Balance,
+			DestChain,
+			[u8; 32],
+			CurrencyId,
+			Balance,
+		),
+	}
+
+	/// The registered destination chains.
+	///
+	/// Destinations: map DestChain => Option<DestinationInfo>
+	#[pallet::storage]
+	#[pallet::getter(fn destinations)]
+	pub type Destinations<T: Config> = StorageMap<_, Twox64Concat, DestChain, DestinationInfo, OptionQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Register, update or remove the destination chain.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `dest`: the destination chain.
+		/// - `info`: the fee and weight limit of the destination chain, `None` to remove it.
+		#[pallet::weight(T::WeightInfo::set_destination())]
+		#[transactional]
+		pub fn set_destination(
+			origin: OriginFor<T>,
+			dest: DestChain,
+			info: Option<DestinationInfo>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			Destinations::<T>::mutate_exists(dest, |maybe_info| *maybe_info = info);
+			Self::deposit_event(Event::DestinationUpdated(dest, info));
+			Ok(().into())
+		}
+
+		/// Transfer to the registered destination chain, with the fee asset and the weight limit
+		/// of the destination chain. If `currency_id` is the fee asset, the fee is deducted from
+		/// `amount` on the destination chain, otherwise the fee is paid by the caller in addition.
+		///
+		/// - `currency_id`: the currency to transfer.
+		/// - `amount`: the amount to transfer.
+		/// - `dest`: the destination chain.
+		/// - `recipient`: the recipient account on the destination chain.
+		#[pallet::weight(T::WeightInfo::transfer())]
+		#[transactional]
+		pub fn transfer(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			#[pallet::compact] amount: Balance,
+			dest: DestChain,
+			recipient: [u8; 32],
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let info = Self::destinations(dest).ok_or(Error::<T>::UnknownDestination)?;
+
+			let fee = if currency_id == info.fee_currency_id {
+				ensure!(amount > info.fee_amount, Error::<T>::AmountTooLow);
+				None
+			} else {
+				Some((info.fee_currency_id, info.fee_amount))
+			};
+			T::XcmTransfer::transfer(&who, currency_id, amount, fee, dest, recipient, info.dest_weight)?;
+
+			Self::deposit_event(Event::Transferred(
+				who,
+				currency_id,
+				amount,
+				dest,
+				recipient,
+				info.fee_currency_id,
+				info.fee_amount,
+			));
+			Ok(().into())
+		}
+	}
+}
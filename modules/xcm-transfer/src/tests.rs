@@ -0,0 +1,127 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the xcm transfer module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+use sp_runtime::traits::BadOrigin;
+
+const RELAY_INFO: DestinationInfo = DestinationInfo {
+	fee_currency_id: DOT,
+	fee_amount: 10,
+	dest_weight: 1_000,
+};
+
+#[test]
+fn set_destination_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			XcmTransfer::set_destination(Origin::signed(ALICE), DestChain::Relay, Some(RELAY_INFO)),
+			BadOrigin
+		);
+
+		assert_ok!(XcmTransfer::set_destination(
+			Origin::signed(COUNCIL),
+			DestChain::Relay,
+			Some(RELAY_INFO)
+		));
+		System::assert_last_event(Event::XcmTransfer(crate::Event::DestinationUpdated(
+			DestChain::Relay,
+			Some(RELAY_INFO),
+		)));
+		assert_eq!(XcmTransfer::destinations(DestChain::Relay), Some(RELAY_INFO));
+
+		assert_ok!(XcmTransfer::set_destination(
+			Origin::signed(COUNCIL),
+			DestChain::Relay,
+			None
+		));
+		assert_eq!(XcmTransfer::destinations(DestChain::Relay), None);
+	});
+}
+
+#[test]
+fn transfer_in_fee_asset_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			XcmTransfer::transfer(Origin::signed(ALICE), DOT, 100, DestChain::Relay, RECIPIENT),
+			Error::<Runtime>::UnknownDestination
+		);
+
+		assert_ok!(XcmTransfer::set_destination(
+			Origin::signed(COUNCIL),
+			DestChain::Relay,
+			Some(RELAY_INFO)
+		));
+		assert_noop!(
+			XcmTransfer::transfer(Origin::signed(ALICE), DOT, 10, DestChain::Relay, RECIPIENT),
+			Error::<Runtime>::AmountTooLow
+		);
+
+		assert_ok!(XcmTransfer::transfer(
+			Origin::signed(ALICE),
+			DOT,
+			100,
+			DestChain::Relay,
+			RECIPIENT
+		));
+		System::assert_last_event(Event::XcmTransfer(crate::Event::Transferred(
+			ALICE,
+			DOT,
+			100,
+			DestChain::Relay,
+			RECIPIENT,
+			DOT,
+			10,
+		)));
+		assert_eq!(
+			transfers(),
+			vec![(ALICE, DOT, 100, None, DestChain::Relay, RECIPIENT, 1_000)]
+		);
+	});
+}
+
+#[test]
+fn transfer_pays_fee_in_fee_asset() {
+	ExtBuilder::default().build().execute_with(|| {
+		let dest = DestChain::Parachain(2000);
+		assert_ok!(XcmTransfer::set_destination(
+			Origin::signed(COUNCIL),
+			dest,
+			Some(DestinationInfo {
+				fee_currency_id: ACA,
+				fee_amount: 50,
+				dest_weight: 2_000,
+			})
+		));
+
+		// the amount is not required to cover the fee paid in the other asset
+		assert_ok!(XcmTransfer::transfer(Origin::signed(ALICE), AUSD, 10, dest, RECIPIENT));
+		System::assert_last_event(Event::XcmTransfer(crate::Event::Transferred(
+			ALICE, AUSD, 10, dest, RECIPIENT, ACA, 50,
+		)));
+		assert_eq!(
+			transfers(),
+			vec![(ALICE, AUSD, 10, Some((ACA, 50)), dest, RECIPIENT, 2_000)]
+		);
+	});
+}
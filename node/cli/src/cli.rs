@@ -38,7 +38,7 @@ pub enum Subcommand {
 	/// Key management cli utilities
 	Key(KeySubcommand),
 
-	/// The custom inspect subcommmand for decoding blocks and extrinsics.
+	/// The custom inspect subcommmand for decoding blocks, extrinsics and events.
 	#[structopt(
 		name = "inspect",
 		about = "Decode given block or extrinsic using current native runtime."
@@ -67,6 +67,10 @@ pub enum Subcommand {
 	/// Build a chain specification.
 	BuildSpec(sc_cli::BuildSpecCmd),
 
+	/// Build a chain specification of Mandala testnet from the economic genesis params.
+	#[structopt(name = "build-spec-custom")]
+	BuildSpecCustom(BuildSpecCustomCommand),
+
 	/// Validate blocks.
 	CheckBlock(sc_cli::CheckBlockCmd),
 
@@ -76,6 +80,10 @@ pub enum Subcommand {
 	/// Export the state of a given block into a chain spec.
 	ExportState(sc_cli::ExportStateCmd),
 
+	/// Report the number of entries and the size of the storage items that grow with usage.
+	#[structopt(name = "state-report")]
+	StateReport(StateReportCmd),
+
 	/// Import blocks.
 	ImportBlocks(sc_cli::ImportBlocksCmd),
 
@@ -106,6 +114,38 @@ pub struct ExportGenesisStateCommand {
 	pub chain: Option<String>,
 }
 
+/// Command for building the chain spec from the economic genesis params.
+#[derive(Debug, StructOpt)]
+pub struct BuildSpecCustomCommand {
+	/// The JSON file of the genesis params, i.e. `resources/mandala-genesis-params.json`.
+	#[structopt(parse(from_os_str))]
+	pub params: PathBuf,
+
+	/// Output file name or stdout if unspecified.
+	#[structopt(short, long, parse(from_os_str))]
+	pub output: Option<PathBuf>,
+
+	/// Force raw genesis storage output.
+	#[structopt(long)]
+	pub raw: bool,
+}
+
+/// Command for reporting the size of the storage items that grow with usage.
+#[derive(Debug, StructOpt)]
+pub struct StateReportCmd {
+	/// The hash of the block to report the state of, the best block if unspecified.
+	#[structopt(long, value_name = "HASH")]
+	pub at: Option<sp_core::H256>,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub shared_params: sc_cli::SharedParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub import_params: sc_cli::ImportParams,
+}
+
 /// Command for exporting the genesis wasm file.
 #[derive(Debug, StructOpt)]
 pub struct ExportGenesisWasmCommand {
@@ -147,6 +187,14 @@ pub struct Cli {
 	/// Can only be used with `--dev`
 	#[structopt(long = "instant-sealing", requires = "dev")]
 	pub instant_sealing: bool,
+
+	/// Instant block sealing and finality, with on demand sealing and time warp
+	///
+	/// The blocks are sealed and finalized when transactions are imported, or on demand by
+	/// `engine_createBlock`. The timestamp and the relay chain block number of the new blocks can
+	/// be warped forward by `dev_warpTime` and `dev_warpRelayBlocks`. Can only be used with `--dev`
+	#[structopt(long = "dev-instant", requires = "dev", conflicts_with = "instant_sealing")]
+	pub dev_instant: bool,
 }
 
 /// Relay chain CLI.
@@ -215,7 +215,7 @@ macro_rules! with_runtime_or_err {
 		if $chain_spec.is_acala() {
 			#[cfg(feature = "with-acala-runtime")]
 			#[allow(unused_imports)]
-			use service::{acala_runtime::{Block, RuntimeApi}, AcalaExecutor as Executor};
+			use service::{acala_runtime::{Block, Runtime, RuntimeApi, UncheckedExtrinsic}, AcalaExecutor as Executor};
 			#[cfg(feature = "with-acala-runtime")]
 			$( $code )*
 
@@ -224,7 +224,7 @@ macro_rules! with_runtime_or_err {
 		} else if $chain_spec.is_karura() {
 			#[cfg(feature = "with-karura-runtime")]
 			#[allow(unused_imports)]
-			use service::{karura_runtime::{Block, RuntimeApi}, KaruraExecutor as Executor};
+			use service::{karura_runtime::{Block, Runtime, RuntimeApi, UncheckedExtrinsic}, KaruraExecutor as Executor};
 			#[cfg(feature = "with-karura-runtime")]
 			$( $code )*
 
@@ -233,7 +233,7 @@ macro_rules! with_runtime_or_err {
 		} else {
 			#[cfg(feature = "with-mandala-runtime")]
 			#[allow(unused_imports)]
-			use service::{mandala_runtime::{Block, RuntimeApi}, MandalaExecutor as Executor};
+			use service::{mandala_runtime::{Block, Runtime, RuntimeApi, UncheckedExtrinsic}, MandalaExecutor as Executor};
 			#[cfg(feature = "with-mandala-runtime")]
 			$( $code )*
 
@@ -254,9 +254,14 @@ pub fn run() -> sc_cli::Result<()> {
 
 			set_default_ss58_version(chain_spec);
 
-			runner.sync_run(|mut config| {
-				let (client, _, _, _) = service::new_chain_ops(&mut config)?;
-				cmd.run(client)
+			with_runtime_or_err!(chain_spec, {
+				return runner.sync_run(|mut config| {
+					let (client, _, _, _) = service::new_chain_ops(&mut config)?;
+					cmd.run::<_, service::FullBackend, _, _>(
+						client,
+						inspect::AcalaPrinter::<Runtime, UncheckedExtrinsic>::default(),
+					)
+				});
 			})
 		}
 
@@ -281,6 +286,25 @@ pub fn run() -> sc_cli::Result<()> {
 			runner.sync_run(|config| cmd.run(config.chain_spec, config.network))
 		}
 
+		Some(Subcommand::BuildSpecCustom(params)) => {
+			#[cfg(feature = "with-mandala-runtime")]
+			{
+				let params_json = std::fs::read(&params.params)?;
+				let chain_spec = chain_spec::mandala::custom_testnet_config(&params_json)?;
+				let output_buf = chain_spec.as_json(params.raw)?;
+
+				if let Some(output) = &params.output {
+					std::fs::write(output, output_buf)?;
+				} else {
+					std::io::stdout().write_all(output_buf.as_bytes())?;
+				}
+
+				Ok(())
+			}
+			#[cfg(not(feature = "with-mandala-runtime"))]
+			Err(service::MANDALA_RUNTIME_NOT_AVAILABLE.into())
+		}
+
 		Some(Subcommand::CheckBlock(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			let chain_spec = &runner.config().chain_spec;
@@ -317,6 +341,18 @@ pub fn run() -> sc_cli::Result<()> {
 			})
 		}
 
+		Some(Subcommand::StateReport(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			let chain_spec = &runner.config().chain_spec;
+
+			set_default_ss58_version(chain_spec);
+
+			runner.sync_run(|mut config| {
+				let (client, _, _, _) = service::new_chain_ops(&mut config)?;
+				cmd.run(&client)
+			})
+		}
+
 		Some(Subcommand::ImportBlocks(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			let chain_spec = &runner.config().chain_spec;
@@ -437,10 +473,19 @@ pub fn run() -> sc_cli::Result<()> {
 
 				if is_mandala_dev {
 					#[cfg(feature = "with-mandala-runtime")]
-					return service::mandala_dev(config, cli.instant_sealing).map_err(Into::into);
+					{
+						let sealing = if cli.dev_instant {
+							service::DevSealing::DevInstant
+						} else if cli.instant_sealing {
+							service::DevSealing::InstantSealing
+						} else {
+							service::DevSealing::Aura
+						};
+						return service::mandala_dev(config, sealing).map_err(Into::into);
+					}
 					#[cfg(not(feature = "with-mandala-runtime"))]
 					return Err(service::MANDALA_RUNTIME_NOT_AVAILABLE.into());
-				} else if cli.instant_sealing {
+				} else if cli.instant_sealing || cli.dev_instant {
 					return Err("Instant sealing can be turned on only in `--dev` mode".into());
 				}
 
@@ -25,6 +25,8 @@
 mod cli;
 #[cfg(feature = "cli")]
 mod command;
+#[cfg(feature = "cli")]
+mod state_report;
 
 #[cfg(feature = "cli")]
 pub use cli::*;
@@ -0,0 +1,150 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! The `state-report` subcommand.
+
+use crate::cli::StateReportCmd;
+use acala_primitives::Block;
+use runtime_common::{StateReportApi, StorageItemReport};
+use sc_cli::{CliConfiguration, ImportParams, Result, SharedParams};
+use service::{AbstractClient, Client, ClientHandle, ExecuteWithClient};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::H256;
+use sp_runtime::{generic::BlockId, traits::BlakeTwo256};
+use std::sync::Arc;
+
+impl StateReportCmd {
+	/// Query the state report of the block from the runtime and print it as a table, the largest
+	/// storage items first.
+	pub fn run(&self, client: &Client) -> Result<()> {
+		let mut reports = client.execute_with(QueryStateReport { at: self.at })?;
+		reports.sort_by(|a, b| b.size.cmp(&a.size));
+		print!("{}", render_table(&reports));
+		Ok(())
+	}
+}
+
+impl CliConfiguration for StateReportCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn import_params(&self) -> Option<&ImportParams> {
+		Some(&self.import_params)
+	}
+}
+
+/// Query the state report at the block `at`, or the best block.
+struct QueryStateReport {
+	at: Option<H256>,
+}
+
+impl ExecuteWithClient for QueryStateReport {
+	type Output = Result<Vec<StorageItemReport>>;
+
+	fn execute_with_client<Client, Api, Backend>(self, client: Arc<Client>) -> Self::Output
+	where
+		<Api as sp_api::ApiExt<Block>>::StateBackend: sp_api::StateBackend<BlakeTwo256>,
+		Backend: sc_client_api::Backend<Block>,
+		Backend::State: sp_api::StateBackend<BlakeTwo256>,
+		Api: service::RuntimeApiCollection<StateBackend = Backend::State>,
+		Client: AbstractClient<Block, Backend, Api = Api> + 'static,
+	{
+		let at = BlockId::Hash(self.at.unwrap_or_else(|| client.info().best_hash));
+		client
+			.runtime_api()
+			.get_state_report(&at)
+			.map_err(|e| format!("Failed to query the state report: {:?}", e).into())
+	}
+}
+
+/// Render the reports as a table with the total at the bottom.
+fn render_table(reports: &[StorageItemReport]) -> String {
+	let header = ["Pallet", "Storage", "Entries", "Size"];
+	let mut rows: Vec<[String; 4]> = reports
+		.iter()
+		.map(|report| {
+			[
+				String::from_utf8_lossy(&report.pallet).into_owned(),
+				String::from_utf8_lossy(&report.item).into_owned(),
+				report.count.to_string(),
+				format_size(report.size),
+			]
+		})
+		.collect();
+	rows.push([
+		"Total".to_string(),
+		String::new(),
+		reports
+			.iter()
+			.map(|report| report.count as u64)
+			.sum::<u64>()
+			.to_string(),
+		format_size(reports.iter().map(|report| report.size).sum()),
+	]);
+
+	let mut widths = [0; 4];
+	for (i, width) in widths.iter_mut().enumerate() {
+		*width = rows.iter().map(|row| row[i].len()).fold(header[i].len(), usize::max);
+	}
+
+	let render_row = |cells: [&str; 4]| {
+		format!(
+			"{:<w0$}  {:<w1$}  {:>w2$}  {:>w3$}\n",
+			cells[0],
+			cells[1],
+			cells[2],
+			cells[3],
+			w0 = widths[0],
+			w1 = widths[1],
+			w2 = widths[2],
+			w3 = widths[3],
+		)
+	};
+	let separator = format!(
+		"{}\n",
+		"-".repeat(widths.iter().sum::<usize>() + 2 * (widths.len() - 1))
+	);
+
+	let mut table = render_row(header);
+	table.push_str(&separator);
+	for (i, row) in rows.iter().enumerate() {
+		if i == rows.len() - 1 {
+			table.push_str(&separator);
+		}
+		table.push_str(&render_row([&row[0], &row[1], &row[2], &row[3]]));
+	}
+	table
+}
+
+/// Format the size in bytes with the binary unit prefix.
+fn format_size(size: u64) -> String {
+	const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+	if size < 1024 {
+		return format!("{} B", size);
+	}
+	let mut value = size as f64 / 1024.0;
+	let mut unit = 0;
+	while value >= 1024.0 && unit < UNITS.len() - 1 {
+		value /= 1024.0;
+		unit += 1;
+	}
+	format!("{:.1} {}", value, UNITS[unit])
+}
@@ -296,6 +296,7 @@ fn acala_genesis(
 			initial_enabled_trading_pairs: EnabledTradingPairs::get(),
 			initial_added_liquidity_pools: vec![],
 		},
+		incentives: Default::default(),
 		parachain_info: ParachainInfoConfig {
 			parachain_id: PARA_ID.into(),
 		},
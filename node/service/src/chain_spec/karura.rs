@@ -285,6 +285,7 @@ fn karura_genesis(
 			initial_enabled_trading_pairs: vec![],
 			initial_added_liquidity_pools: vec![],
 		},
+		incentives: Default::default(),
 		parachain_info: ParachainInfoConfig {
 			parachain_id: PARA_ID.into(),
 		},
@@ -16,15 +16,18 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use acala_primitives::{AccountId, TokenSymbol};
+use acala_primitives::{AccountId, Balance, CurrencyId, TokenSymbol};
 use hex_literal::hex;
+use module_incentives::PoolId;
 use sc_chain_spec::ChainType;
 use sc_telemetry::TelemetryEndpoints;
+use serde::{Deserialize, Serialize};
 use serde_json::map::Map;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_core::{crypto::UncheckedInto, sr25519};
 use sp_finality_grandpa::AuthorityId as GrandpaId;
 use sp_runtime::{traits::Zero, FixedPointNumber, FixedU128};
+use std::convert::TryFrom;
 
 use crate::chain_spec::{
 	evm_genesis, get_account_id_from_seed, get_authority_keys_from_seed, Extensions, TELEMETRY_URL,
@@ -72,6 +75,10 @@ fn dev_testnet_config_from_chain_id(chain_id: &str) -> Result<ChainSpec, String>
 				vec![
 					get_account_id_from_seed::<sr25519::Public>("Alice"),
 					get_account_id_from_seed::<sr25519::Public>("Bob"),
+					get_account_id_from_seed::<sr25519::Public>("Charlie"),
+					get_account_id_from_seed::<sr25519::Public>("Dave"),
+					get_account_id_from_seed::<sr25519::Public>("Eve"),
+					get_account_id_from_seed::<sr25519::Public>("Ferdie"),
 					get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
 				],
@@ -225,6 +232,221 @@ pub fn mandala_testnet_config() -> Result<ChainSpec, String> {
 	ChainSpec::from_json_bytes(&include_bytes!("../../../../resources/mandala-dist.json")[..])
 }
 
+/// The economic parameters of the genesis of the custom testnet.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct GenesisParams {
+	/// The name of the chain.
+	pub name: String,
+	/// The id of the chain.
+	pub id: String,
+	/// The type of the chain.
+	#[serde(default = "default_chain_type")]
+	pub chain_type: ChainType,
+	/// The relay chain of the Parachain.
+	pub relay_chain: String,
+	/// The id of the Parachain.
+	pub para_id: u32,
+	/// The seeds of the initial authorities, i.e. `Alice`.
+	pub authority_seeds: Vec<String>,
+	/// The sudo account, which is also the member of the councils.
+	pub root_key: AccountId,
+	/// The initial balances of the accounts.
+	#[serde(default)]
+	pub endowments: Vec<(AccountId, CurrencyId, Balance)>,
+	/// The risk management params of the collaterals.
+	#[serde(default)]
+	pub collaterals: Vec<CollateralParams>,
+	/// The global interest rate per sec of all collaterals.
+	#[serde(default)]
+	pub global_interest_rate_per_sec: FixedU128,
+	/// The oracle members of the Acala and Band oracles.
+	#[serde(default)]
+	pub oracle_members: Vec<AccountId>,
+	/// The enabled trading pairs of DEX.
+	#[serde(default)]
+	pub dex_pairs: Vec<DexPairParams>,
+	/// The incentive reward amounts of the pools per period.
+	#[serde(default)]
+	pub incentive_reward_amounts: Vec<(PoolId<AccountId>, Balance)>,
+	/// The DEX saving reward rates of the pools per period.
+	#[serde(default)]
+	pub dex_saving_reward_rates: Vec<(PoolId<AccountId>, FixedU128)>,
+}
+
+/// The risk management params of the collateral.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct CollateralParams {
+	pub currency_id: CurrencyId,
+	pub interest_rate_per_sec: Option<FixedU128>,
+	pub liquidation_ratio: Option<FixedU128>,
+	pub liquidation_penalty: Option<FixedU128>,
+	pub required_collateral_ratio: Option<FixedU128>,
+	pub maximum_total_debit_value: Balance,
+	/// The max size of a collateral auction.
+	pub expected_collateral_auction_size: Balance,
+}
+
+/// The enabled trading pair of DEX.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct DexPairParams {
+	pub currency_id_a: CurrencyId,
+	pub currency_id_b: CurrencyId,
+	/// The provider and the amounts of `currency_id_a` and `currency_id_b`
+	/// of the initial liquidity, which are taken from the endowments of the
+	/// provider.
+	#[serde(default)]
+	pub initial_liquidity: Option<(AccountId, Balance, Balance)>,
+}
+
+fn default_chain_type() -> ChainType {
+	ChainType::Local
+}
+
+/// Custom testnet config from the JSON of the economic genesis params
+pub fn custom_testnet_config(params_json: &[u8]) -> Result<ChainSpec, String> {
+	let params: GenesisParams =
+		serde_json::from_slice(params_json).map_err(|e| format!("Invalid genesis params: {}", e))?;
+
+	let mut properties = Map::new();
+	let mut token_symbol: Vec<String> = vec![];
+	let mut token_decimals: Vec<u32> = vec![];
+	TokenSymbol::get_info().iter().for_each(|(symbol_name, decimals)| {
+		token_symbol.push(symbol_name.to_string());
+		token_decimals.push(*decimals);
+	});
+	properties.insert("tokenSymbol".into(), token_symbol.into());
+	properties.insert("tokenDecimals".into(), token_decimals.into());
+
+	let wasm_binary = mandala_runtime::WASM_BINARY.ok_or("Mandala runtime wasm binary not available")?;
+	let extensions = Extensions {
+		relay_chain: params.relay_chain.clone(),
+		para_id: params.para_id,
+	};
+
+	let name = params.name.clone();
+	let id = params.id.clone();
+	let chain_type = params.chain_type.clone();
+
+	Ok(ChainSpec::from_genesis(
+		&name,
+		&id,
+		chain_type,
+		move || custom_genesis(wasm_binary, params.clone()),
+		vec![],
+		None,
+		None,
+		Some(properties),
+		extensions,
+	))
+}
+
+fn custom_genesis(wasm_binary: &[u8], params: GenesisParams) -> mandala_runtime::GenesisConfig {
+	use mandala_runtime::{
+		CdpEngineConfig, CdpTreasuryConfig, DexConfig, IncentivesConfig, OperatorMembershipAcalaConfig,
+		OperatorMembershipBandConfig, ParachainInfoConfig, TradingPair, ACA,
+	};
+
+	let initial_authorities = params
+		.authority_seeds
+		.iter()
+		.map(|seed| get_authority_keys_from_seed(seed))
+		.collect();
+	let genesis = testnet_genesis(wasm_binary, initial_authorities, params.root_key.clone(), vec![]);
+
+	let mut balances = genesis.balances;
+	let mut tokens = genesis.tokens;
+	for (account_id, currency_id, amount) in params.endowments {
+		if currency_id == ACA {
+			match balances.balances.iter_mut().find(|(who, _)| *who == account_id) {
+				Some((_, balance)) => {
+					*balance = balance
+						.checked_add(amount)
+						.expect("balance cannot overflow when building genesis")
+				}
+				None => balances.balances.push((account_id, amount)),
+			}
+		} else {
+			tokens.balances.push((account_id, currency_id, amount));
+		}
+	}
+
+	let trading_pairs = params
+		.dex_pairs
+		.iter()
+		.map(|pair| {
+			let trading_pair = TradingPair::from_token_currency_ids(pair.currency_id_a, pair.currency_id_b)
+				.expect("the trading pair is invalid");
+			(trading_pair, pair)
+		})
+		.collect::<Vec<_>>();
+
+	mandala_runtime::GenesisConfig {
+		balances,
+		tokens,
+		cdp_treasury: CdpTreasuryConfig {
+			expected_collateral_auction_size: params
+				.collaterals
+				.iter()
+				.map(|collateral| (collateral.currency_id, collateral.expected_collateral_auction_size))
+				.collect(),
+		},
+		cdp_engine: CdpEngineConfig {
+			collaterals_params: params
+				.collaterals
+				.iter()
+				.map(|collateral| {
+					(
+						collateral.currency_id,
+						collateral.interest_rate_per_sec,
+						collateral.liquidation_ratio,
+						collateral.liquidation_penalty,
+						collateral.required_collateral_ratio,
+						collateral.maximum_total_debit_value,
+					)
+				})
+				.collect(),
+			global_interest_rate_per_sec: params.global_interest_rate_per_sec,
+		},
+		operator_membership_acala: OperatorMembershipAcalaConfig {
+			members: params.oracle_members.clone(),
+			phantom: Default::default(),
+		},
+		operator_membership_band: OperatorMembershipBandConfig {
+			members: params.oracle_members,
+			phantom: Default::default(),
+		},
+		dex: DexConfig {
+			initial_listing_trading_pairs: vec![],
+			initial_enabled_trading_pairs: trading_pairs.iter().map(|(trading_pair, _)| *trading_pair).collect(),
+			initial_added_liquidity_pools: trading_pairs
+				.iter()
+				.filter_map(|(trading_pair, pair)| {
+					pair.initial_liquidity.clone().map(|(provider, amount_a, amount_b)| {
+						// the amounts of the pool are in the order of the trading pair
+						let amounts = if trading_pair.0 == pair.currency_id_a {
+							(amount_a, amount_b)
+						} else {
+							(amount_b, amount_a)
+						};
+						(provider, vec![(*trading_pair, amounts)])
+					})
+				})
+				.collect(),
+		},
+		incentives: IncentivesConfig {
+			incentive_reward_amounts: params.incentive_reward_amounts,
+			dex_saving_reward_rates: params.dex_saving_reward_rates,
+		},
+		parachain_info: ParachainInfoConfig {
+			parachain_id: params.para_id.into(),
+		},
+		..genesis
+	}
+}
+
 fn testnet_genesis(
 	wasm_binary: &[u8],
 	initial_authorities: Vec<(AccountId, AccountId, GrandpaId, AuraId)>,
@@ -317,9 +539,16 @@ fn testnet_genesis(
 		democracy: Default::default(),
 		treasury: Default::default(),
 		tokens: TokensConfig {
+			// endow all the tokens except the native one
 			balances: endowed_accounts
 				.iter()
-				.flat_map(|x| vec![(x.clone(), DOT, initial_balance), (x.clone(), AUSD, initial_balance)])
+				.flat_map(|x| {
+					TokenSymbol::get_info()
+						.into_iter()
+						.filter_map(|(symbol, _)| CurrencyId::try_from(symbol.as_bytes().to_vec()).ok())
+						.filter(|currency_id| *currency_id != ACA)
+						.map(move |currency_id| (x.clone(), currency_id, initial_balance))
+				})
 				.collect(),
 		},
 		vesting: VestingConfig { vesting: vec![] },
@@ -388,6 +617,7 @@ fn testnet_genesis(
 				],
 			)],
 		},
+		incentives: Default::default(),
 		parachain_info: ParachainInfoConfig {
 			parachain_id: PARA_ID.into(),
 		},
@@ -596,6 +826,7 @@ fn mandala_genesis(
 			initial_enabled_trading_pairs: EnabledTradingPairs::get(),
 			initial_added_liquidity_pools: vec![],
 		},
+		incentives: Default::default(),
 		parachain_info: ParachainInfoConfig {
 			parachain_id: PARA_ID.into(),
 		},
@@ -98,3 +98,10 @@ fn check_nfts() {
 		Vec<(Vec<u8>, module_nft::TokenData<Balance>, Vec<AccountId>)>,
 	)> = serde_json::from_slice(nfts_json).unwrap();
 }
+
+#[test]
+#[cfg(feature = "with-mandala-runtime")]
+fn check_mandala_genesis_params() {
+	let params_json = &include_bytes!("../../../../resources/mandala-genesis-params.json")[..];
+	let _: super::mandala::GenesisParams = serde_json::from_slice(params_json).unwrap();
+}
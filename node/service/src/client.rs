@@ -19,6 +19,7 @@
 //! Acala Client abstractions.
 
 use acala_primitives::{AccountId, Balance, Block, BlockNumber, CurrencyId, DataProviderId, Hash, Header, Nonce};
+use module_incentives::PoolId;
 use runtime_common::TimeStampedPrice;
 use sc_client_api::{Backend as BackendT, BlockchainEvents, KeyIterator};
 use sp_api::{CallApiAt, NumberFor, ProvideRuntimeApi};
@@ -41,7 +42,14 @@ pub trait RuntimeApiCollection:
 	+ pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance>
 	+ orml_oracle_rpc::OracleRuntimeApi<Block, DataProviderId, CurrencyId, TimeStampedPrice>
 	+ module_staking_pool_rpc::StakingPoolRuntimeApi<Block, AccountId, Balance>
+	+ module_incentives_rpc::IncentivesRuntimeApi<Block, AccountId, PoolId<AccountId>, CurrencyId, Balance>
+	+ module_honzon_rpc::HonzonRuntimeApi<Block, AccountId, CurrencyId, Balance>
+	+ module_dex_rpc::DexRuntimeApi<Block, CurrencyId, Balance>
 	+ module_evm_rpc_runtime_api::EVMRuntimeRPCApi<Block, Balance>
+	+ module_evm_rpc_runtime_api::EVMTraceRuntimeApi<Block>
+	+ module_evm_rpc_runtime_api::DryRunRuntimeApi<Block, Balance>
+	+ module_evm_rpc_runtime_api::EVMTransactionPoolApi<Block, AccountId, Balance>
+	+ runtime_common::StateReportApi<Block>
 	+ sp_api::Metadata<Block>
 	+ sp_offchain::OffchainWorkerApi<Block>
 	+ sp_session::SessionKeys<Block>
@@ -60,7 +68,14 @@ where
 		+ pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance>
 		+ orml_oracle_rpc::OracleRuntimeApi<Block, DataProviderId, CurrencyId, TimeStampedPrice>
 		+ module_staking_pool_rpc::StakingPoolRuntimeApi<Block, AccountId, Balance>
+		+ module_incentives_rpc::IncentivesRuntimeApi<Block, AccountId, PoolId<AccountId>, CurrencyId, Balance>
+		+ module_honzon_rpc::HonzonRuntimeApi<Block, AccountId, CurrencyId, Balance>
+		+ module_dex_rpc::DexRuntimeApi<Block, CurrencyId, Balance>
 		+ module_evm_rpc_runtime_api::EVMRuntimeRPCApi<Block, Balance>
+		+ module_evm_rpc_runtime_api::EVMTraceRuntimeApi<Block>
+		+ module_evm_rpc_runtime_api::DryRunRuntimeApi<Block, Balance>
+		+ module_evm_rpc_runtime_api::EVMTransactionPoolApi<Block, AccountId, Balance>
+		+ runtime_common::StateReportApi<Block>
 		+ sp_api::Metadata<Block>
 		+ sp_offchain::OffchainWorkerApi<Block>
 		+ sp_session::SessionKeys<Block>
@@ -134,7 +134,7 @@ impl IdentifyVariant for Box<dyn ChainSpec> {
 }
 
 /// Acala's full backend.
-type FullBackend = TFullBackend<Block>;
+pub type FullBackend = TFullBackend<Block>;
 
 /// Acala's full client.
 type FullClient<RuntimeApi, Executor> = TFullClient<Block, RuntimeApi, Executor>;
@@ -227,7 +227,7 @@ where
 						slot_duration,
 					);
 
-					Ok((timestamp, slot, MockParachainInherentDataProvider))
+					Ok((timestamp, slot, MockParachainInherentDataProvider::default()))
 				},
 				spawner: &task_manager.spawn_essential_handle(),
 				registry,
@@ -355,6 +355,8 @@ where
 				client: client.clone(),
 				pool: transaction_pool.clone(),
 				deny_unsafe,
+				command_sink: None,
+				time_warp: None,
 			};
 
 			acala_rpc::create_full(deps)
@@ -583,8 +585,20 @@ pub fn new_chain_ops(
 	}
 }
 
+/// The block sealing of Mandala dev node.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DevSealing {
+	/// Author blocks with Aura.
+	Aura,
+	/// Seal and finalize a new block when a transaction is imported.
+	InstantSealing,
+	/// Seal and finalize a new block when a transaction is imported or on demand by the manual
+	/// seal RPCs, with the RPCs to warp the timestamp and relay chain block number forward.
+	DevInstant,
+}
+
 #[cfg(feature = "with-mandala-runtime")]
-fn inner_mandala_dev(config: Configuration, instant_sealing: bool) -> Result<TaskManager, ServiceError> {
+fn inner_mandala_dev(config: Configuration, sealing: DevSealing) -> Result<TaskManager, ServiceError> {
 	let sc_service::PartialComponents {
 		client,
 		backend,
@@ -594,7 +608,7 @@ fn inner_mandala_dev(config: Configuration, instant_sealing: bool) -> Result<Tas
 		select_chain: maybe_select_chain,
 		transaction_pool,
 		other: (mut telemetry, _),
-	} = new_partial::<mandala_runtime::RuntimeApi, MandalaExecutor>(&config, true, instant_sealing)?;
+	} = new_partial::<mandala_runtime::RuntimeApi, MandalaExecutor>(&config, true, sealing != DevSealing::Aura)?;
 
 	let (network, system_rpc_tx, network_starter) = sc_service::build_network(sc_service::BuildNetworkParams {
 		config: &config,
@@ -619,6 +633,14 @@ fn inner_mandala_dev(config: Configuration, instant_sealing: bool) -> Result<Tas
 	let select_chain =
 		maybe_select_chain.expect("In mandala dev mode, `new_partial` will return some `select_chain`; qed");
 
+	let time_warp = Arc::new(acala_rpc::TimeWarp::default());
+	let (command_sink, rpc_commands_stream) = futures::channel::mpsc::channel(1024);
+	let (command_sink, rpc_time_warp) = if sealing == DevSealing::DevInstant {
+		(Some(command_sink), Some(time_warp.clone()))
+	} else {
+		(None, None)
+	};
+
 	if role.is_authority() {
 		let proposer_factory = sc_basic_authorship::ProposerFactory::new(
 			task_manager.spawn_handle(),
@@ -628,9 +650,9 @@ fn inner_mandala_dev(config: Configuration, instant_sealing: bool) -> Result<Tas
 			telemetry.as_ref().map(|x| x.handle()),
 		);
 
-		if instant_sealing {
+		if sealing != DevSealing::Aura {
 			let pool = transaction_pool.pool().clone();
-			let commands_stream = pool.validated_pool().import_notification_stream().map(|_| {
+			let import_commands_stream = pool.validated_pool().import_notification_stream().map(|_| {
 				sc_consensus_manual_seal::rpc::EngineCommand::SealNewBlock {
 					create_empty: false,
 					finalize: true,
@@ -638,6 +660,8 @@ fn inner_mandala_dev(config: Configuration, instant_sealing: bool) -> Result<Tas
 					sender: None,
 				}
 			});
+			// The RPC commands stream ends at once if the command sink is not given to the RPCs.
+			let commands_stream = futures::stream::select(import_commands_stream, rpc_commands_stream);
 
 			let authorship_future =
 				sc_consensus_manual_seal::run_manual_seal(sc_consensus_manual_seal::ManualSealParams {
@@ -648,11 +672,21 @@ fn inner_mandala_dev(config: Configuration, instant_sealing: bool) -> Result<Tas
 					commands_stream,
 					select_chain,
 					consensus_data_provider: None,
-					create_inherent_data_providers: |_, _| async {
-						Ok((
-							sp_timestamp::InherentDataProvider::from_system_time(),
-							MockParachainInherentDataProvider,
-						))
+					create_inherent_data_providers: move |_, _| {
+						let time_warp = time_warp.clone();
+						async move {
+							let timestamp = sp_timestamp::InherentDataProvider::from_system_time();
+							let timestamp = sp_timestamp::InherentDataProvider::new(
+								timestamp.saturating_add(time_warp.timestamp_offset()).into(),
+							);
+
+							Ok((
+								timestamp,
+								MockParachainInherentDataProvider {
+									relay_parent_number: time_warp.relay_block_offset(),
+								},
+							))
+						}
 					},
 				});
 			// we spawn the future on a background thread managed by service.
@@ -677,7 +711,7 @@ fn inner_mandala_dev(config: Configuration, instant_sealing: bool) -> Result<Tas
 						slot_duration,
 					);
 
-					Ok((timestamp, slot, MockParachainInherentDataProvider))
+					Ok((timestamp, slot, MockParachainInherentDataProvider::default()))
 				},
 				force_authoring,
 				backoff_authoring_blocks,
@@ -704,6 +738,8 @@ fn inner_mandala_dev(config: Configuration, instant_sealing: bool) -> Result<Tas
 				client: client.clone(),
 				pool: transaction_pool.clone(),
 				deny_unsafe,
+				command_sink: command_sink.clone(),
+				time_warp: rpc_time_warp.clone(),
 			};
 
 			acala_rpc::create_full(deps)
@@ -731,6 +767,6 @@ fn inner_mandala_dev(config: Configuration, instant_sealing: bool) -> Result<Tas
 }
 
 #[cfg(feature = "with-mandala-runtime")]
-pub fn mandala_dev(config: Configuration, instant_sealing: bool) -> Result<TaskManager, ServiceError> {
-	inner_mandala_dev(config, instant_sealing)
+pub fn mandala_dev(config: Configuration, sealing: DevSealing) -> Result<TaskManager, ServiceError> {
+	inner_mandala_dev(config, sealing)
 }
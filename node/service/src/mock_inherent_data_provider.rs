@@ -16,13 +16,18 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use cumulus_primitives_core::PersistedValidationData;
+use cumulus_primitives_core::{relay_chain::BlockNumber as RelayChainBlockNumber, PersistedValidationData};
 use cumulus_primitives_parachain_inherent::{ParachainInherentData, INHERENT_IDENTIFIER};
 use cumulus_test_relay_sproof_builder::RelayStateSproofBuilder;
 use sp_inherents::{InherentData, InherentDataProvider, InherentIdentifier};
 use sp_timestamp::InherentError;
 
-pub struct MockParachainInherentDataProvider;
+/// Provides the mock parachain inherent of the dev node, with no relay chain to validate against.
+#[derive(Default)]
+pub struct MockParachainInherentDataProvider {
+	/// The relay chain block number the new block is built on.
+	pub relay_parent_number: RelayChainBlockNumber,
+}
 
 #[async_trait::async_trait]
 impl InherentDataProvider for MockParachainInherentDataProvider {
@@ -35,7 +40,7 @@ impl InherentDataProvider for MockParachainInherentDataProvider {
 			validation_data: PersistedValidationData {
 				parent_head: Default::default(),
 				relay_parent_storage_root: relay_storage_root,
-				relay_parent_number: Default::default(),
+				relay_parent_number: self.relay_parent_number,
 				max_pov_size: Default::default(),
 			},
 			downward_messages: Default::default(),
@@ -30,6 +30,12 @@ use sp_std::{
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
+/// The id of foreign assets registered in the asset registry, e.g. the XCM assets of other chains.
+pub type ForeignAssetId = u16;
+pub type LaunchpadTokenId = u32;
+/// The id of the parachain whose crowdloan the liquid crowdloan currency is issued for.
+pub type CrowdloanParaId = u32;
+
 macro_rules! create_currency_id {
     ($(#[$meta:meta])*
 	$vis:vis enum TokenSymbol {
@@ -204,6 +210,7 @@ pub trait TokenInfo {
 pub enum DexShare {
 	Token(TokenSymbol),
 	Erc20(EvmAddress),
+	LaunchpadToken(LaunchpadTokenId),
 }
 
 #[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, PartialOrd, Ord)]
@@ -213,6 +220,9 @@ pub enum CurrencyId {
 	DexShare(DexShare, DexShare),
 	Erc20(EvmAddress),
 	ChainSafe(chainbridge::ResourceId),
+	ForeignAsset(ForeignAssetId),
+	LaunchpadToken(LaunchpadTokenId),
+	LiquidCrowdloan(CrowdloanParaId),
 }
 
 impl CurrencyId {
@@ -228,6 +238,18 @@ impl CurrencyId {
 		matches!(self, CurrencyId::Erc20(_))
 	}
 
+	pub fn is_foreign_asset_currency_id(&self) -> bool {
+		matches!(self, CurrencyId::ForeignAsset(_))
+	}
+
+	pub fn is_launchpad_token_currency_id(&self) -> bool {
+		matches!(self, CurrencyId::LaunchpadToken(_))
+	}
+
+	pub fn is_liquid_crowdloan_currency_id(&self) -> bool {
+		matches!(self, CurrencyId::LiquidCrowdloan(_))
+	}
+
 	pub fn split_dex_share_currency_id(&self) -> Option<(Self, Self)> {
 		match self {
 			CurrencyId::DexShare(token_symbol_0, token_symbol_1) => {
@@ -243,11 +265,13 @@ impl CurrencyId {
 		let token_symbol_0 = match currency_id_0 {
 			CurrencyId::Token(symbol) => DexShare::Token(symbol),
 			CurrencyId::Erc20(address) => DexShare::Erc20(address),
+			CurrencyId::LaunchpadToken(token_id) => DexShare::LaunchpadToken(token_id),
 			_ => return None,
 		};
 		let token_symbol_1 = match currency_id_1 {
 			CurrencyId::Token(symbol) => DexShare::Token(symbol),
 			CurrencyId::Erc20(address) => DexShare::Erc20(address),
+			CurrencyId::LaunchpadToken(token_id) => DexShare::LaunchpadToken(token_id),
 			_ => return None,
 		};
 		Some(CurrencyId::DexShare(token_symbol_0, token_symbol_1))
@@ -267,6 +291,9 @@ impl From<DexShare> for u32 {
 				let index = if leading_zeros > 16 { 16 } else { leading_zeros };
 				bytes[..].copy_from_slice(&address[index..index + 4][..]);
 			}
+			DexShare::LaunchpadToken(token_id) => {
+				bytes = token_id.to_be_bytes();
+			}
 		}
 		u32::from_be_bytes(bytes)
 	}
@@ -284,11 +311,11 @@ impl TryFrom<CurrencyId> for EvmAddress {
 			CurrencyId::DexShare(token_symbol_0, token_symbol_1) => {
 				let symbol_0 = match token_symbol_0 {
 					DexShare::Token(token) => CurrencyId::Token(token).currency_id().ok_or(()),
-					DexShare::Erc20(_) => Err(()),
+					DexShare::Erc20(_) | DexShare::LaunchpadToken(_) => Err(()),
 				}?;
 				let symbol_1 = match token_symbol_1 {
 					DexShare::Token(token) => CurrencyId::Token(token).currency_id().ok_or(()),
-					DexShare::Erc20(_) => Err(()),
+					DexShare::Erc20(_) | DexShare::LaunchpadToken(_) => Err(()),
 				}?;
 
 				let mut prefix = EvmAddress::default();
@@ -296,7 +323,10 @@ impl TryFrom<CurrencyId> for EvmAddress {
 				Ok(prefix | EvmAddress::from_low_u64_be(u64::from(symbol_0) << 32 | u64::from(symbol_1)))
 			}
 			CurrencyId::Erc20(address) => Ok(address),
-			CurrencyId::ChainSafe(_) => Err(()),
+			CurrencyId::ChainSafe(_) | CurrencyId::LaunchpadToken(_) | CurrencyId::LiquidCrowdloan(_) => Err(()),
+			CurrencyId::ForeignAsset(foreign_asset_id) => Ok(EvmAddress::from_low_u64_be(
+				MIRRORED_FOREIGN_ASSETS_ADDRESS_START | u64::from(foreign_asset_id),
+			)),
 		}
 	}
 }
@@ -306,6 +336,7 @@ impl Into<CurrencyId> for DexShare {
 		match self {
 			DexShare::Token(token) => CurrencyId::Token(token),
 			DexShare::Erc20(address) => CurrencyId::Erc20(address),
+			DexShare::LaunchpadToken(token_id) => CurrencyId::LaunchpadToken(token_id),
 		}
 	}
 }
@@ -16,13 +16,13 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::Balance;
+use crate::{Balance, CurrencyId, Nonce};
 use codec::{Decode, Encode};
 use evm::ExitReason;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
-use sp_core::{H160, U256};
-use sp_runtime::RuntimeDebug;
+use sp_core::{H160, H256, U256};
+use sp_runtime::{ApplyExtrinsicResult, RuntimeDebug};
 use sp_std::vec::Vec;
 
 pub use evm::backend::{Basic as Account, Log};
@@ -41,6 +41,16 @@ pub struct Vicinity {
 	pub origin: EvmAddress,
 }
 
+/// An EIP-2930 access list entry, the `address` and its `storage_keys` are warm from the start
+/// of the transaction.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct AccessListItem {
+	pub address: EvmAddress,
+	pub storage_keys: Vec<H256>,
+}
+
 #[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct CreateInfo {
@@ -85,3 +95,175 @@ pub struct EstimateResourcesRequest {
 	/// Data
 	pub data: Option<Vec<u8>>,
 }
+
+/// The publication status of a contract.
+#[derive(Clone, Copy, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ContractStatus {
+	/// The maintainer of the contract
+	pub maintainer: EvmAddress,
+	/// Whether the contract is deployed (published). A contract not deployed can only be called
+	/// by its maintainer, developers and contracts.
+	pub deployed: bool,
+}
+
+/// The verification metadata of a contract code, submitted by the maintainer of a contract
+/// deployed with the code.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ContractMetadata {
+	/// The maintainer who submitted the metadata
+	pub submitter: EvmAddress,
+	/// The IPFS CID of the source code and the compiler metadata
+	pub source_cid: Vec<u8>,
+	/// The compiler version, e.g. `v0.8.4+commit.c7e474f2`
+	pub compiler_version: Vec<u8>,
+	/// The hash of the compiler settings, including the optimizer settings
+	pub compiler_settings_hash: H256,
+}
+
+/// Fee information of a block, in the shape of the Ethereum fee history.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct BlockFeeInfo {
+	/// Base fee per gas of the block
+	pub base_fee_per_gas: U256,
+	/// Base fee per gas of the next block
+	pub next_base_fee_per_gas: U256,
+	/// Gas used by the EVM executions in the block
+	pub gas_used: u64,
+	/// Gas limit of the EVM executions in the block
+	pub gas_limit: u64,
+}
+
+/// Estimated resources and fee of an EVM execution.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct EstimateFeeInfo<Balance> {
+	pub exit_reason: ExitReason,
+	pub output: Vec<u8>,
+	pub used_gas: U256,
+	pub used_storage: i32,
+	/// Fee of the used gas, in native currency
+	pub gas_fee: Balance,
+	/// Deposit reserved for the used storage, in native currency
+	pub storage_deposit: Balance,
+	/// Amount of the fee currency needed to pay the gas fee and the storage
+	/// deposit, `None` if it can't be swapped to native currency
+	pub total_fee: Option<Balance>,
+	/// Currency the total fee is calculated in, native currency if `None`
+	pub fee_currency_id: Option<CurrencyId>,
+}
+
+/// The result of the dry run of an extrinsic, whose changes are discarded.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct DryRunInfo<Balance> {
+	/// The result of applying the extrinsic
+	pub result: ApplyExtrinsicResult,
+	/// The SCALE encoded events emitted by the extrinsic
+	pub events: Vec<Vec<u8>>,
+	/// The changes of the storage usage of the contracts, in bytes
+	pub storage_changes: Vec<(EvmAddress, i32)>,
+	/// The transaction fee excluding the tip, in native currency
+	pub fee: Balance,
+}
+
+/// Size in bytes of a logs bloom.
+pub const LOGS_BLOOM_SIZE: usize = 256;
+
+/// Ethereum style bloom filter of log addresses and topics.
+pub type LogsBloom = [u8; LOGS_BLOOM_SIZE];
+
+/// The receipt of the EVM executions of an extrinsic.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Receipt {
+	/// Index of the extrinsic in the block, `None` if executed outside of an extrinsic
+	pub extrinsic_index: Option<u32>,
+	/// Gas used by the executions
+	pub used_gas: u64,
+	/// Logs emitted by the executions
+	pub logs: Vec<Log>,
+}
+
+/// The EVM execution requested by a signed extrinsic, e.g. one waiting in the transaction pool.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct EvmTransaction<Balance> {
+	/// EVM address of the signer
+	pub from: EvmAddress,
+	/// Contract address to call, `None` if it creates a contract
+	pub to: Option<EvmAddress>,
+	/// Nonce of the extrinsic
+	pub nonce: Nonce,
+	/// Amount sent with the execution
+	pub value: Balance,
+	/// Maximum gas the execution can use
+	pub gas_limit: u64,
+	/// Total bytes the storage of contracts can increase by
+	pub storage_limit: u32,
+	/// Input of the call or init code of the contract
+	pub input: Vec<u8>,
+}
+
+/// The tracer used to trace the EVM execution.
+#[derive(Clone, Copy, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum TracerConfig {
+	/// Trace the call frames only.
+	CallTracer,
+	/// Trace the call frames and every executed opcode.
+	OpcodeTracer { disable_stack: bool, disable_memory: bool },
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum CallType {
+	Call,
+	CallCode,
+	DelegateCall,
+	StaticCall,
+	Create,
+}
+
+/// The trace of a call frame.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct CallTrace {
+	pub call_type: CallType,
+	pub from: EvmAddress,
+	pub to: EvmAddress,
+	pub value: U256,
+	pub gas: u64,
+	pub gas_used: u64,
+	pub input: Vec<u8>,
+	pub output: Vec<u8>,
+	pub exit_reason: Option<ExitReason>,
+	/// The sub calls made in this call frame.
+	pub calls: Vec<CallTrace>,
+}
+
+/// The trace of an executed opcode.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct StructLog {
+	pub pc: u64,
+	pub op: u8,
+	/// The gas left before executing the opcode.
+	pub gas: u64,
+	pub gas_cost: u64,
+	/// The depth of the call frame, starts from 1.
+	pub depth: u32,
+	pub stack: Vec<H256>,
+	pub memory: Vec<u8>,
+}
+
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct TraceInfo {
+	/// The trace of the outermost call frame.
+	pub call: CallTrace,
+	/// The traces of the executed opcodes, empty if traced by `TracerConfig::CallTracer`.
+	pub struct_logs: Vec<StructLog>,
+}
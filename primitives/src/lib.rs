@@ -33,7 +33,7 @@ use sp_runtime::{
 };
 use sp_std::{convert::Into, prelude::*};
 
-pub use currency::{CurrencyId, DexShare, TokenSymbol};
+pub use currency::{CrowdloanParaId, CurrencyId, DexShare, ForeignAssetId, LaunchpadTokenId, TokenSymbol};
 
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
@@ -135,9 +135,12 @@ impl TradingPair {
 	}
 
 	pub fn from_token_currency_ids(currency_id_0: CurrencyId, currency_id_1: CurrencyId) -> Option<Self> {
-		match (currency_id_0.is_token_currency_id() || currency_id_0.is_erc20_currency_id())
-			&& (currency_id_1.is_token_currency_id() || currency_id_1.is_erc20_currency_id())
-		{
+		let is_token = |currency_id: CurrencyId| {
+			currency_id.is_token_currency_id()
+				|| currency_id.is_erc20_currency_id()
+				|| currency_id.is_launchpad_token_currency_id()
+		};
+		match is_token(currency_id_0) && is_token(currency_id_1) {
 			true => Some(TradingPair::new(currency_id_1, currency_id_0)),
 			_ => None,
 		}
@@ -156,6 +159,11 @@ pub enum ReserveIdentifier {
 	Honzon,
 	Nft,
 	TransactionPayment,
+	NftMarketplace,
+	FeeTank,
+	FederatedBridge,
+	ScheduledTransfers,
+	NomineesElection,
 }
 
 /// Ethereum precompiles
@@ -172,6 +180,12 @@ pub const MIRRORED_TOKENS_ADDRESS_START: u64 = 0x1000000;
 /// Mirrored NFT (ensure length <= 4 bytes, encode to u32 will take the first 4 non-zero bytes)
 /// 0x2000000
 pub const MIRRORED_NFT_ADDRESS_START: u64 = 0x2000000;
+/// Mirrored NFT classes as ERC-721 contracts (encode the class id to the last 4 bytes)
+/// 0x100000000
+pub const MIRRORED_NFT_CLASSES_ADDRESS_START: u64 = 0x100000000;
+/// Mirrored foreign assets (encode the foreign asset id to the last 2 bytes)
+/// 0x10000000000
+pub const MIRRORED_FOREIGN_ASSETS_ADDRESS_START: u64 = 0x10000000000;
 /// Mirrored LP Tokens
 /// 0x10000000000000000
 pub const MIRRORED_LP_TOKENS_ADDRESS_START: u128 = 0x10000000000000000;
@@ -191,14 +205,27 @@ pub const SYSTEM_CONTRACT_ADDRESS_PREFIX: [u8; 11] = [0u8; 11];
 ///
 /// Erc20
 /// - v[0..20] = evm address(20 bytes)
+///
+/// ForeignAsset
+/// v[14] = 1 // MIRRORED_FOREIGN_ASSETS_ADDRESS_START
+/// - v[18..20] = foreign asset id(2 bytes)
 pub const H160_TYPE_TOKEN: u8 = 1;
 pub const H160_TYPE_DEXSHARE: u8 = 1;
 pub const H160_POSITION_TOKEN: usize = 19;
 pub const H160_POSITION_DEXSHARE_LEFT: Range<usize> = 12..16;
 pub const H160_POSITION_DEXSHARE_RIGHT: Range<usize> = 16..20;
 pub const H160_POSITION_ERC20: Range<usize> = 0..20;
+pub const H160_POSITION_FOREIGN_ASSET: Range<usize> = 18..20;
 pub const H160_PREFIX_TOKEN: [u8; 19] = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0];
 pub const H160_PREFIX_DEXSHARE: [u8; 12] = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1];
+pub const H160_PREFIX_FOREIGN_ASSET: [u8; 18] = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0];
+
+/// NFT class id to H160([u8; 20]) bit encoding rule.
+///
+/// v[15] = 1 // MIRRORED_NFT_CLASSES_ADDRESS_START
+/// - v[16..20] = class id(4 bytes)
+pub const H160_POSITION_NFT_CLASS: Range<usize> = 16..20;
+pub const H160_PREFIX_NFT_CLASS: [u8; 16] = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1];
 
 pub type NFTBalance = u128;
 
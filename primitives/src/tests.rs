@@ -51,6 +51,9 @@ fn currency_id_into_u32_works() {
 
 	let currency_id = DexShare::Erc20(EvmAddress::from_str("0x0000000000000000000000000000000000000000").unwrap());
 	assert_eq!(Into::<u32>::into(currency_id), 0x00);
+
+	let currency_id = DexShare::LaunchpadToken(0x01020304);
+	assert_eq!(Into::<u32>::into(currency_id), 0x01020304);
 }
 
 #[test]
@@ -78,4 +81,12 @@ fn currency_id_try_into_evm_address_works() {
 
 	let erc20 = EvmAddress::from_str("0x1111111111111111111111111111111111111111").unwrap();
 	assert_eq!(EvmAddress::try_from(CurrencyId::Erc20(erc20)), Ok(erc20));
+
+	assert_eq!(
+		EvmAddress::try_from(CurrencyId::ForeignAsset(258)),
+		Ok(EvmAddress::from_str("0x0000000000000000000000000000010000000102").unwrap())
+	);
+
+	assert_eq!(EvmAddress::try_from(CurrencyId::LaunchpadToken(0)), Err(()));
+	assert_eq!(EvmAddress::try_from(CurrencyId::LiquidCrowdloan(2000)), Err(()));
 }
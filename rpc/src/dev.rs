@@ -0,0 +1,105 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! RPCs of the dev node to warp the time forward.
+
+use jsonrpc_core::Result;
+use jsonrpc_derive::rpc;
+use sc_rpc_api::DenyUnsafe;
+use std::sync::{
+	atomic::{AtomicU32, AtomicU64, Ordering},
+	Arc,
+};
+
+/// The offsets the inherents of the new blocks of the dev node are warped forward by.
+#[derive(Debug, Default)]
+pub struct TimeWarp {
+	timestamp_offset: AtomicU64,
+	relay_block_offset: AtomicU32,
+}
+
+impl TimeWarp {
+	/// The milliseconds added to the timestamp of the new blocks.
+	pub fn timestamp_offset(&self) -> u64 {
+		self.timestamp_offset.load(Ordering::SeqCst)
+	}
+
+	/// The blocks added to the relay chain block number of the new blocks.
+	pub fn relay_block_offset(&self) -> u32 {
+		self.relay_block_offset.load(Ordering::SeqCst)
+	}
+
+	/// Warp the timestamp forward by `millis`, returns the new offset.
+	pub fn warp_time(&self, millis: u64) -> u64 {
+		let previous = self
+			.timestamp_offset
+			.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |offset| {
+				Some(offset.saturating_add(millis))
+			});
+		previous.unwrap_or_else(|offset| offset).saturating_add(millis)
+	}
+
+	/// Warp the relay chain block number forward by `blocks`, returns the new offset.
+	pub fn warp_relay_blocks(&self, blocks: u32) -> u32 {
+		let previous = self
+			.relay_block_offset
+			.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |offset| {
+				Some(offset.saturating_add(blocks))
+			});
+		previous.unwrap_or_else(|offset| offset).saturating_add(blocks)
+	}
+}
+
+/// Dev RPC methods.
+#[rpc]
+pub trait DevApi {
+	/// Warp the timestamp of the new blocks forward by `millis`, returns the total offset in
+	/// milliseconds.
+	#[rpc(name = "dev_warpTime")]
+	fn warp_time(&self, millis: u64) -> Result<u64>;
+
+	/// Warp the relay chain block number of the new blocks forward by `blocks`, returns the
+	/// total offset in blocks.
+	#[rpc(name = "dev_warpRelayBlocks")]
+	fn warp_relay_blocks(&self, blocks: u32) -> Result<u32>;
+}
+
+/// A struct that implements the [`DevApi`].
+pub struct Dev {
+	time_warp: Arc<TimeWarp>,
+	deny_unsafe: DenyUnsafe,
+}
+
+impl Dev {
+	/// Create new `Dev` with the given reference to the time warp.
+	pub fn new(time_warp: Arc<TimeWarp>, deny_unsafe: DenyUnsafe) -> Self {
+		Dev { time_warp, deny_unsafe }
+	}
+}
+
+impl DevApi for Dev {
+	fn warp_time(&self, millis: u64) -> Result<u64> {
+		self.deny_unsafe.check_if_safe()?;
+		Ok(self.time_warp.warp_time(millis))
+	}
+
+	fn warp_relay_blocks(&self, blocks: u32) -> Result<u32> {
+		self.deny_unsafe.check_if_safe()?;
+		Ok(self.time_warp.warp_relay_blocks(blocks))
+	}
+}
@@ -20,8 +20,14 @@
 
 #![warn(missing_docs)]
 
+use futures::channel::mpsc::Sender;
+use module_incentives::PoolId;
 use primitives::{AccountId, Balance, Block, CurrencyId, DataProviderId, Hash, Nonce};
-use sc_client_api::light::{Fetcher, RemoteBlockchain};
+use sc_client_api::{
+	light::{Fetcher, RemoteBlockchain},
+	BlockBackend,
+};
+use sc_consensus_manual_seal::rpc::EngineCommand;
 pub use sc_rpc_api::DenyUnsafe;
 use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder;
@@ -31,7 +37,10 @@ use std::sync::Arc;
 
 pub use sc_rpc::SubscriptionTaskExecutor;
 
-pub use evm_rpc::{EVMApi, EVMApiServer, EVMRuntimeRPCApi};
+pub use dev::TimeWarp;
+pub use evm_rpc::{EVMApi, EVMApiServer, EVMRuntimeRPCApi, EVMTraceRuntimeApi, EVMTransactionPoolApi};
+
+mod dev;
 
 /// A type representing all RPC extensions.
 pub type RpcExtension = jsonrpc_core::IoHandler<sc_rpc::Metadata>;
@@ -56,6 +65,10 @@ pub struct FullDeps<C, P> {
 	pub pool: Arc<P>,
 	/// Whether to deny unsafe calls
 	pub deny_unsafe: DenyUnsafe,
+	/// The sink of the manual seal commands, for the dev node sealing blocks on demand.
+	pub command_sink: Option<Sender<EngineCommand<Hash>>>,
+	/// The time warp of the new blocks, for the dev node.
+	pub time_warp: Option<Arc<TimeWarp>>,
 }
 
 /// Instantiate all Full RPC extensions.
@@ -63,18 +76,29 @@ pub fn create_full<C, P>(deps: FullDeps<C, P>) -> RpcExtension
 where
 	C: ProvideRuntimeApi<Block>,
 	C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError>,
+	C: BlockBackend<Block>,
 	C: Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: orml_oracle_rpc::OracleRuntimeApi<Block, DataProviderId, CurrencyId, runtime_common::TimeStampedPrice>,
 	C::Api: module_staking_pool_rpc::StakingPoolRuntimeApi<Block, AccountId, Balance>,
+	C::Api: module_incentives_rpc::IncentivesRuntimeApi<Block, AccountId, PoolId<AccountId>, CurrencyId, Balance>,
+	C::Api: module_honzon_rpc::HonzonRuntimeApi<Block, AccountId, CurrencyId, Balance>,
+	C::Api: module_dex_rpc::DexRuntimeApi<Block, CurrencyId, Balance>,
 	C::Api: EVMRuntimeRPCApi<Block, Balance>,
+	C::Api: EVMTraceRuntimeApi<Block>,
+	C::Api: EVMTransactionPoolApi<Block, AccountId, Balance>,
 	C::Api: BlockBuilder<Block>,
-	P: TransactionPool + Sync + Send + 'static,
+	P: TransactionPool<Block = Block> + Sync + Send + 'static,
 {
+	use dev::{Dev, DevApi};
+	use module_dex_rpc::{Dex, DexApi};
+	use module_honzon_rpc::{Honzon, HonzonApi};
+	use module_incentives_rpc::{Incentives, IncentivesApi};
 	use module_staking_pool_rpc::{StakingPool, StakingPoolApi};
 	use orml_oracle_rpc::{Oracle, OracleApi};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
+	use sc_consensus_manual_seal::rpc::{ManualSeal, ManualSealApi};
 	use substrate_frame_rpc_system::{FullSystem, SystemApi};
 
 	let mut io = jsonrpc_core::IoHandler::default();
@@ -82,11 +106,13 @@ where
 		client,
 		pool,
 		deny_unsafe,
+		command_sink,
+		time_warp,
 	} = deps;
 
 	io.extend_with(SystemApi::to_delegate(FullSystem::new(
 		client.clone(),
-		pool,
+		pool.clone(),
 		deny_unsafe,
 	)));
 	io.extend_with(TransactionPaymentApi::to_delegate(TransactionPayment::new(
@@ -97,7 +123,18 @@ where
 	// These RPCs should use an asynchronous caller instead.
 	io.extend_with(OracleApi::to_delegate(Oracle::new(client.clone())));
 	io.extend_with(StakingPoolApi::to_delegate(StakingPool::new(client.clone())));
-	io.extend_with(EVMApiServer::to_delegate(EVMApi::new(client, deny_unsafe)));
+	io.extend_with(IncentivesApi::to_delegate(Incentives::new(client.clone())));
+	io.extend_with(HonzonApi::to_delegate(Honzon::new(client.clone())));
+	io.extend_with(DexApi::to_delegate(Dex::new(client.clone())));
+	io.extend_with(EVMApiServer::to_delegate(EVMApi::new(client, pool, deny_unsafe)));
+
+	if let Some(command_sink) = command_sink {
+		// Sealing and finalizing blocks on demand, e.g. `engine_createBlock`.
+		io.extend_with(ManualSealApi::to_delegate(ManualSeal::new(command_sink)));
+	}
+	if let Some(time_warp) = time_warp {
+		io.extend_with(DevApi::to_delegate(Dev::new(time_warp, deny_unsafe)));
+	}
 
 	io
 }
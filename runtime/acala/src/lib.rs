@@ -39,11 +39,13 @@ use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_core::{
 	crypto::KeyTypeId,
 	u32_trait::{_1, _2, _3, _4},
-	OpaqueMetadata, H160,
+	OpaqueMetadata, H160, H256,
 };
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
-	traits::{AccountIdConversion, BadOrigin, BlakeTwo256, Block as BlockT, SaturatedConversion, StaticLookup, Zero},
+	traits::{
+		AccountIdConversion, BadOrigin, BlakeTwo256, Block as BlockT, Convert, SaturatedConversion, StaticLookup, Zero,
+	},
 	transaction_validity::{TransactionSource, TransactionValidity},
 	ApplyExtrinsicResult, DispatchResult, FixedPointNumber,
 };
@@ -99,14 +101,17 @@ pub use sp_runtime::{Perbill, Percent, Permill, Perquintill};
 pub use authority::AuthorityConfigImpl;
 pub use constants::{fee::*, time::*};
 pub use primitives::{
-	evm::EstimateResourcesRequest, AccountId, AccountIndex, Amount, AuctionId, AuthoritysOriginId, Balance,
-	BlockNumber, CurrencyId, DataProviderId, EraIndex, Hash, Moment, Nonce, ReserveIdentifier, Share, Signature,
-	TokenSymbol, TradingPair,
+	evm::{
+		BlockFeeInfo, ContractMetadata, ContractStatus, DryRunInfo, EstimateFeeInfo, EstimateResourcesRequest,
+		EvmTransaction, LogsBloom, Receipt, TraceInfo, TracerConfig,
+	},
+	AccountId, AccountIndex, Amount, AuctionId, AuthoritysOriginId, Balance, BlockNumber, CurrencyId, DataProviderId,
+	EraIndex, Hash, Moment, Nonce, ReserveIdentifier, Share, Signature, TokenSymbol, TradingPair,
 };
 pub use runtime_common::{
-	cent, dollar, microcent, millicent, CurveFeeModel, ExchangeRate, GasToWeight, OffchainSolutionWeightLimit, Price,
-	Rate, Ratio, RelaychainBlockNumberProvider, RuntimeBlockLength, RuntimeBlockWeights, SystemContractsFilter,
-	TimeStampedPrice, ACA, AUSD, DOT, LDOT, RENBTC,
+	cent, dollar, microcent, millicent, BlockGasTarget, CurveFeeModel, ExchangeRate, GasToWeight,
+	OffchainSolutionWeightLimit, Price, Rate, Ratio, RelaychainBlockNumberProvider, RelaychainStateRootProvider,
+	RuntimeBlockLength, RuntimeBlockWeights, SystemContractsFilter, TimeStampedPrice, ACA, AUSD, DOT, LDOT, RENBTC,
 };
 
 mod authority;
@@ -148,7 +153,11 @@ parameter_types! {
 	pub const CDPTreasuryPalletId: PalletId = PalletId(*b"aca/cdpt");
 	pub const StakingPoolPalletId: PalletId = PalletId(*b"aca/stkp");
 	pub const HonzonTreasuryPalletId: PalletId = PalletId(*b"aca/hztr");
+	pub const SavingRatePalletId: PalletId = PalletId(*b"aca/svrt");
+	pub const StabilityBufferPalletId: PalletId = PalletId(*b"aca/stbf");
 	pub const HomaTreasuryPalletId: PalletId = PalletId(*b"aca/hmtr");
+	pub const HomaPalletId: PalletId = PalletId(*b"aca/homa");
+	pub const HomaValidatorListPalletId: PalletId = PalletId(*b"aca/hmvl");
 	pub const IncentivesPalletId: PalletId = PalletId(*b"aca/inct");
 	// Treasury reserve
 	pub const TreasuryReservePalletId: PalletId = PalletId(*b"aca/reve");
@@ -165,7 +174,10 @@ pub fn get_all_module_accounts() -> Vec<AccountId> {
 		DEXPalletId::get().into_account(),
 		CDPTreasuryPalletId::get().into_account(),
 		StakingPoolPalletId::get().into_account(),
+		HomaValidatorListPalletId::get().into_account(),
 		HonzonTreasuryPalletId::get().into_account(),
+		SavingRatePalletId::get().into_account(),
+		StabilityBufferPalletId::get().into_account(),
 		HomaTreasuryPalletId::get().into_account(),
 		IncentivesPalletId::get().into_account(),
 		TreasuryReservePalletId::get().into_account(),
@@ -244,12 +256,24 @@ impl pallet_session::Config for Runtime {
 	type WeightInfo = ();
 }
 
+/// The session keys prove the possession with the signature of the aura key.
+pub struct SessionKeysOwnershipProof;
+impl module_collator_selection::KeysOwnershipProof<AccountId, SessionKeys> for SessionKeysOwnershipProof {
+	fn verify(who: &AccountId, keys: &SessionKeys, proof: &[u8]) -> bool {
+		let payload = module_collator_selection::keys_ownership_payload(who);
+		<AuraId as sp_runtime::RuntimeAppPublic>::Signature::decode(&mut &proof[..]).map_or(false, |signature| {
+			sp_runtime::RuntimeAppPublic::verify(&keys.aura, &payload, &signature)
+		})
+	}
+}
+
 parameter_types! {
 	pub const PotId: PalletId = PalletId(*b"PotStake");
 	pub const MinCandidates: u32 = 5;
 	pub const MaxCandidates: u32 = 200;
 	pub const MaxInvulnerables: u32 = 50;
 	pub const CollatorKickThreshold: Permill = Permill::from_percent(50);
+	pub const CollatorUnbondingDelay: u32 = 7;
 }
 
 impl module_collator_selection::Config for Runtime {
@@ -262,6 +286,8 @@ impl module_collator_selection::Config for Runtime {
 	type MaxCandidates = MaxCandidates;
 	type MaxInvulnerables = MaxInvulnerables;
 	type CollatorKickThreshold = CollatorKickThreshold;
+	type UnbondingDelay = CollatorUnbondingDelay;
+	type KeysOwnershipProof = SessionKeysOwnershipProof;
 	type WeightInfo = weights::module_collator_selection::WeightInfo<Runtime>;
 }
 
@@ -489,6 +515,23 @@ impl pallet_collective::Config<TechnicalCommitteeInstance> for Runtime {
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	pub const NominatorsCouncilMotionDuration: BlockNumber = 7 * DAYS;
+	pub const NominatorsCouncilMaxProposals: u32 = 100;
+}
+
+type NominatorsCouncilInstance = pallet_collective::Instance5;
+impl pallet_collective::Config<NominatorsCouncilInstance> for Runtime {
+	type Origin = Origin;
+	type Proposal = Call;
+	type Event = Event;
+	type MotionDuration = NominatorsCouncilMotionDuration;
+	type MaxProposals = NominatorsCouncilMaxProposals;
+	type MaxMembers = NominatorsCouncilSize;
+	type DefaultVote = pallet_collective::PrimeDefaultVote;
+	type WeightInfo = ();
+}
+
 type TechnicalCommitteeMembershipInstance = pallet_membership::Instance4;
 impl pallet_membership::Config<TechnicalCommitteeMembershipInstance> for Runtime {
 	type Event = Event;
@@ -828,7 +871,7 @@ impl orml_tokens::Config for Runtime {
 	type CurrencyId = CurrencyId;
 	type WeightInfo = weights::orml_tokens::WeightInfo<Runtime>;
 	type ExistentialDeposits = ExistentialDeposits;
-	type OnDust = orml_tokens::TransferDust<Runtime, TreasuryAccount>;
+	type OnDust = module_currencies::SweepDust<Runtime, Dex, TreasuryAccount>;
 	type MaxLocks = MaxLocks;
 }
 
@@ -863,6 +906,11 @@ parameter_types! {
 	pub const GetStableCurrencyId: CurrencyId = AUSD;
 }
 
+parameter_types! {
+	pub const MaxMemoLength: u32 = 128;
+	pub const MaxBatchTransfers: u32 = 100;
+}
+
 impl module_currencies::Config for Runtime {
 	type Event = Event;
 	type MultiCurrency = Tokens;
@@ -871,6 +919,10 @@ impl module_currencies::Config for Runtime {
 	type WeightInfo = weights::module_currencies::WeightInfo<Runtime>;
 	type AddressMapping = EvmAddressMapping<Runtime>;
 	type EVMBridge = EVMBridge;
+	type UpdateOrigin = EnsureRootOrHalfFinancialCouncil;
+	type MaxMemoLength = MaxMemoLength;
+	type MaxBatchTransfers = MaxBatchTransfers;
+	type FreezeChecker = ();
 }
 
 pub struct EnsureRootOrTreasury;
@@ -965,6 +1017,8 @@ impl module_auction_manager::Config for Runtime {
 	type PriceSource = Prices;
 	type UnsignedPriority = runtime_common::AuctionManagerUnsignedPriority;
 	type EmergencyShutdown = EmergencyShutdown;
+	type EventTopics = EventTopics;
+	type IdleScheduler = ();
 	type WeightInfo = weights::module_auction_manager::WeightInfo<Runtime>;
 }
 
@@ -976,6 +1030,7 @@ impl module_loans::Config for Runtime {
 	type CDPTreasury = CdpTreasury;
 	type PalletId = LoansPalletId;
 	type OnUpdateLoan = module_incentives::OnUpdateLoan<Runtime>;
+	type EventTopics = EventTopics;
 }
 
 impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Runtime
@@ -1044,6 +1099,7 @@ parameter_types! {
 	pub DefaultLiquidationPenalty: Rate = Rate::saturating_from_rational(5, 100);
 	pub MinimumDebitValue: Balance = dollar(AUSD);
 	pub MaxSlippageSwapWithDEX: Ratio = Ratio::saturating_from_rational(5, 100);
+	pub const FeeTankWithdrawalDelay: BlockNumber = 7 * DAYS;
 }
 
 impl module_cdp_engine::Config for Runtime {
@@ -1082,6 +1138,8 @@ impl module_emergency_shutdown::Config for Runtime {
 	type CDPTreasury = CdpTreasury;
 	type AuctionManagerHandler = AuctionManager;
 	type ShutdownOrigin = EnsureRootOrHalfGeneralCouncil;
+	// the DEX swaps are kept open in the emergency shutdown
+	type OnShutdown = (Homa, Incentives);
 	type WeightInfo = weights::module_emergency_shutdown::WeightInfo<Runtime>;
 }
 
@@ -1106,11 +1164,21 @@ impl module_dex::Config for Runtime {
 	type DEXIncentives = Incentives;
 	type WeightInfo = weights::module_dex::WeightInfo<Runtime>;
 	type ListingOrigin = EnsureRootOrHalfGeneralCouncil;
+	type OnSwap = ();
+	type EventTopics = EventTopics;
+	type SwapFeeShare = ();
+	type SwapFeeCurrencyId = GetNativeCurrencyId;
+	type SwapFeeReceiver = TreasuryAccount;
 }
 
+impl module_event_topics::Config for Runtime {}
+
 parameter_types! {
 	pub const MaxAuctionsCount: u32 = 100;
 	pub HonzonTreasuryAccount: AccountId = HonzonTreasuryPalletId::get().into_account();
+	pub SavingRateAccount: AccountId = SavingRatePalletId::get().into_account();
+	pub StabilityBufferAccount: AccountId = StabilityBufferPalletId::get().into_account();
+	pub const SurplusAllocationPeriod: BlockNumber = DAYS;
 }
 
 impl module_cdp_treasury::Config for Runtime {
@@ -1123,6 +1191,11 @@ impl module_cdp_treasury::Config for Runtime {
 	type MaxAuctionsCount = MaxAuctionsCount;
 	type PalletId = CDPTreasuryPalletId;
 	type TreasuryAccount = HonzonTreasuryAccount;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type SavingRateAccount = SavingRateAccount;
+	type StabilityBufferAccount = StabilityBufferAccount;
+	type SurplusAllocationPeriod = SurplusAllocationPeriod;
+	type MaxSlippageSwapWithDEX = MaxSlippageSwapWithDEX;
 	type WeightInfo = weights::module_cdp_treasury::WeightInfo<Runtime>;
 }
 
@@ -1146,6 +1219,8 @@ impl OnUnbalanced<NegativeImbalance> for DealWithFees {
 }
 
 impl module_transaction_payment::Config for Runtime {
+	type Event = Event;
+	type Call = Call;
 	type AllNonNativeCurrencyIds = AllNonNativeCurrencyIds;
 	type NativeCurrencyId = GetNativeCurrencyId;
 	type StableCurrencyId = GetStableCurrencyId;
@@ -1157,23 +1232,37 @@ impl module_transaction_payment::Config for Runtime {
 	type FeeMultiplierUpdate = TargetedFeeAdjustment<Self, TargetBlockFullness, AdjustmentVariable, MinimumMultiplier>;
 	type DEX = Dex;
 	type MaxSlippageSwapWithDEX = MaxSlippageSwapWithDEX;
+	type OnFeePaid = ();
+	type SponsorCallClass = ProxyType;
+	type SponsorPublic = <Signature as sp_runtime::traits::Verify>::Signer;
+	type SponsorSignature = Signature;
+	type UpdateOrigin = EnsureRootOrHalfGeneralCouncil;
+	type FeeTankWithdrawalDelay = FeeTankWithdrawalDelay;
 	type WeightInfo = weights::module_transaction_payment::WeightInfo<Runtime>;
 }
 
+parameter_types! {
+	pub const EvmAccountsUnbindDelay: BlockNumber = 7 * DAYS;
+}
+
 impl module_evm_accounts::Config for Runtime {
 	type Event = Event;
 	type Currency = Balances;
 	type AddressMapping = EvmAddressMapping<Runtime>;
 	type TransferAll = Currencies;
+	type UnbindDelay = EvmAccountsUnbindDelay;
 	type WeightInfo = weights::module_evm_accounts::WeightInfo<Runtime>;
 }
 
 impl module_evm_manager::Config for Runtime {
+	type Event = Event;
 	type Currency = Balances;
 	type EVMBridge = EVMBridge;
+	type RegisterOrigin = EnsureRootOrHalfGeneralCouncil;
+	type AssetRegistry = ();
 }
 
-impl orml_rewards::Config for Runtime {
+impl module_rewards::Config for Runtime {
 	type Share = Balance;
 	type Balance = Balance;
 	type PoolId = module_incentives::PoolId<AccountId>;
@@ -1182,12 +1271,19 @@ impl orml_rewards::Config for Runtime {
 
 parameter_types! {
 	pub const AccumulatePeriod: BlockNumber = MINUTES;
+	pub const MaxClaimAllPools: u32 = 50;
+	pub const MaxLoyaltyTiers: u32 = 10;
+	pub const MaxTopUps: u32 = 20;
+	pub const MaxEscrowedRewards: u32 = 20;
+	pub const MaxLoansRiskWeights: u32 = 20;
+	pub const IncentiveRewardsBudget: Balance = Balance::max_value();
 }
 
 impl module_incentives::Config for Runtime {
 	type Event = Event;
 	type RelaychainAccountId = AccountId;
 	type NativeRewardsSource = UnreleasedNativeVaultAccountId;
+	type ExtraRewardsSource = TreasuryAccount;
 	type RewardsVaultAccountId = ZeroAccountId;
 	type NativeCurrencyId = GetNativeCurrencyId;
 	type StableCurrencyId = GetStableCurrencyId;
@@ -1197,8 +1293,15 @@ impl module_incentives::Config for Runtime {
 	type CDPTreasury = CdpTreasury;
 	type Currency = Currencies;
 	type DEX = Dex;
-	type EmergencyShutdown = EmergencyShutdown;
+	type VotingEscrow = ();
+	type IncentiveRewardsBudget = IncentiveRewardsBudget;
+	type MaxClaimAllPools = MaxClaimAllPools;
+	type MaxLoyaltyTiers = MaxLoyaltyTiers;
+	type MaxTopUps = MaxTopUps;
+	type MaxEscrowedRewards = MaxEscrowedRewards;
+	type MaxLoansRiskWeights = MaxLoansRiskWeights;
 	type PalletId = IncentivesPalletId;
+	type IdleScheduler = ();
 	type WeightInfo = weights::module_incentives::WeightInfo<Runtime>;
 }
 
@@ -1206,15 +1309,33 @@ parameter_types! {
 	pub const PolkadotBondingDuration: EraIndex = 7;
 	pub const EraLength: BlockNumber = DAYS;
 	pub const MaxUnbonding: u32 = 1000;
+	pub const MaxRelaychainProofSize: u32 = 16 * 1024;
+}
+
+/// The sub account of the parachain sovereign account on relaychain, derived the same way as
+/// `pallet_utility::as_derivative`.
+pub struct RelaychainSubAccount;
+impl Convert<u32, AccountId> for RelaychainSubAccount {
+	fn convert(account_index: u32) -> AccountId {
+		let sovereign: AccountId = ParachainInfo::parachain_id().into_account();
+		let entropy =
+			(b"modlpy/utilisuba", sovereign, account_index as u16).using_encoded(sp_core::hashing::blake2_256);
+		AccountId::decode(&mut &entropy[..]).unwrap_or_default()
+	}
 }
 
 impl module_polkadot_bridge::Config for Runtime {
+	type Event = Event;
 	type DOTCurrency = Currency<Runtime, GetStakingCurrencyId>;
 	type OnNewEra = (NomineesElection, StakingPool);
 	type BondingDuration = PolkadotBondingDuration;
 	type EraLength = EraLength;
 	type PolkadotAccountId = AccountId;
 	type MaxUnbonding = MaxUnbonding;
+	type RelaychainState = RelaychainLightClient;
+	type RelaychainSubAccount = RelaychainSubAccount;
+	type MaxProofSize = MaxRelaychainProofSize;
+	type WeightInfo = weights::module_polkadot_bridge::WeightInfo<Runtime>;
 }
 
 parameter_types! {
@@ -1222,6 +1343,11 @@ parameter_types! {
 	pub const GetStakingCurrencyId: CurrencyId = DOT;
 	pub DefaultExchangeRate: ExchangeRate = ExchangeRate::saturating_from_rational(10, 100);	// 1 : 10
 	pub PoolAccountIndexes: Vec<u32> = vec![1, 2, 3, 4];
+	pub const MaxExchangeRateHistory: EraIndex = 84;
+	pub const MaxQueuedRequestsPerBlock: u32 = 50;
+	pub const MaxNextEraMintsPerBlock: u32 = 50;
+	pub MinNextEraMintAmount: Balance = dollar(DOT);
+	pub GuarantorRewardRatio: Ratio = Ratio::saturating_from_rational(5, 100);
 }
 
 impl module_staking_pool::Config for Runtime {
@@ -1236,10 +1362,18 @@ impl module_staking_pool::Config for Runtime {
 	type Nominees = NomineesElection;
 	type Bridge = PolkadotBridge;
 	type Currency = Currencies;
+	type MaxExchangeRateHistory = MaxExchangeRateHistory;
+	type MaxQueuedRequestsPerBlock = MaxQueuedRequestsPerBlock;
+	type MaxNextEraMintsPerBlock = MaxNextEraMintsPerBlock;
+	type MinNextEraMintAmount = MinNextEraMintAmount;
+	type GuarantorRewardRatio = GuarantorRewardRatio;
+	type ValidatorRewards = HomaValidatorListModule;
 }
 
 impl module_homa::Config for Runtime {
+	type Event = Event;
 	type Homa = StakingPool;
+	type PalletId = HomaPalletId;
 	type WeightInfo = weights::module_homa::WeightInfo<Runtime>;
 }
 
@@ -1248,18 +1382,34 @@ parameter_types! {
 	pub const NominateesCount: u32 = 7;
 	pub const MaxUnlockingChunks: u32 = 7;
 	pub const NomineesElectionBondingDuration: EraIndex = 7;
+	pub const NomineesElectionVoteDecayPeriod: EraIndex = 28;
+	pub const NominatorsCouncilSize: u32 = 5;
+	pub const NomineesElectionMaxNominators: u32 = 2_000;
+	pub const NomineesElectionMaxCouncilVoters: u32 = 2_000;
+	pub NomineesElectionNominatorDeposit: Balance = 10 * dollar(ACA);
+	pub NomineesElectionCouncilVoterDeposit: Balance = 10 * dollar(ACA);
 }
 
 impl module_nominees_election::Config for Runtime {
 	type Event = Event;
 	type Currency = Currency<Runtime, GetLiquidCurrencyId>;
+	type DepositCurrency = Balances;
 	type NomineeId = AccountId;
 	type PalletId = NomineesElectionId;
 	type MinBondThreshold = MinCouncilBondThreshold;
 	type BondingDuration = NomineesElectionBondingDuration;
 	type NominateesCount = NominateesCount;
 	type MaxUnlockingChunks = MaxUnlockingChunks;
-	type NomineeFilter = runtime_common::DummyNomineeFilter;
+	type NomineeFilter = HomaValidatorListModule;
+	type VoteDecayPeriod = NomineesElectionVoteDecayPeriod;
+	type CouncilSize = NominatorsCouncilSize;
+	type MaxNominators = NomineesElectionMaxNominators;
+	type MaxCouncilVoters = NomineesElectionMaxCouncilVoters;
+	type NominatorDeposit = NomineesElectionNominatorDeposit;
+	type CouncilVoterDeposit = NomineesElectionCouncilVoterDeposit;
+	type CouncilMembersChanged = NominatorsCouncil;
+	type AdjustOrigin = pallet_collective::EnsureProportionMoreThan<_1, _2, AccountId, NominatorsCouncilInstance>;
+	type OnNomineesAdjusted = module_staking_pool::OnNomineesAdjusted<Runtime>;
 	type WeightInfo = weights::module_nominees_election::WeightInfo<Runtime>;
 }
 
@@ -1283,11 +1433,14 @@ impl module_homa_validator_list::Config for Runtime {
 	type OnIncreaseGuarantee = module_incentives::OnIncreaseGuarantee<Runtime>;
 	type OnDecreaseGuarantee = module_incentives::OnDecreaseGuarantee<Runtime>;
 	type BlockNumberProvider = RelaychainBlockNumberProvider<Runtime>;
+	type PalletId = HomaValidatorListPalletId;
 }
 
 parameter_types! {
 	pub CreateClassDeposit: Balance = 500 * millicent(ACA);
 	pub CreateTokenDeposit: Balance = 100 * millicent(ACA);
+	pub MaxAttributeKeyLength: u32 = 64;
+	pub MaxAttributeValueLength: u32 = 1024;
 }
 
 impl module_nft::Config for Runtime {
@@ -1297,6 +1450,10 @@ impl module_nft::Config for Runtime {
 	type CreateTokenDeposit = CreateTokenDeposit;
 	type DataDepositPerByte = DataDepositPerByte;
 	type PalletId = NftPalletId;
+	type MaxAttributeKeyLength = MaxAttributeKeyLength;
+	type MaxAttributeValueLength = MaxAttributeValueLength;
+	type UnixTime = Timestamp;
+	type OnNftTransfer = ();
 	type WeightInfo = weights::module_nft::WeightInfo<Runtime>;
 }
 
@@ -1368,15 +1525,24 @@ impl pallet_proxy::Config for Runtime {
 parameter_types! {
 	pub const RENBTCCurrencyId: CurrencyId = RENBTC;
 	pub const RENBTCIdentifier: [u8; 32] = hex!["f6b5b360905f856404bd4cf39021b82209908faa44159e68ea207ab8a5e13197"];
+	pub const RenVmKeyRotationOverlap: BlockNumber = DAYS;
+	pub const RenVmMintEpochLength: BlockNumber = DAYS;
 }
 
 impl ecosystem_renvm_bridge::Config for Runtime {
 	type Event = Event;
 	type Currency = Balances;
 	type BridgedTokenCurrency = Currency<Runtime, RENBTCCurrencyId>;
+	type BridgedTokenCurrencyId = RENBTCCurrencyId;
+	type MintGuard = ();
 	type CurrencyIdentifier = RENBTCIdentifier;
 	type UnsignedPriority = runtime_common::RenvmBridgeUnsignedPriority;
 	type ChargeTransactionPayment = module_transaction_payment::ChargeTransactionPayment<Runtime>;
+	type UpdateOrigin = EnsureRootOrHalfFinancialCouncil;
+	type TreasuryAccount = TreasuryAccount;
+	type KeyRotationOverlap = RenVmKeyRotationOverlap;
+	type EpochLength = RenVmMintEpochLength;
+	type WeightInfo = weights::ecosystem_renvm_bridge::WeightInfo<Runtime>;
 }
 
 parameter_types! {
@@ -1386,6 +1552,8 @@ parameter_types! {
 	pub StorageDepositPerByte: Balance = microcent(ACA);
 	pub const MaxCodeSize: u32 = 60 * 1024;
 	pub NetworkContractSource: H160 = H160::from_low_u64_be(0);
+	pub const AccessListGasMetering: bool = true;
+	pub const EvictionNoticePeriod: BlockNumber = 7 * DAYS;
 	pub DeveloperDeposit: Balance = dollar(ACA);
 	pub DeploymentFee: Balance = dollar(ACA);
 }
@@ -1401,8 +1569,13 @@ pub type NFTPrecompile =
 	runtime_common::NFTPrecompile<AccountId, EvmAddressMapping<Runtime>, EvmCurrencyIdMapping<Runtime>, NFT>;
 pub type StateRentPrecompile =
 	runtime_common::StateRentPrecompile<AccountId, EvmAddressMapping<Runtime>, EvmCurrencyIdMapping<Runtime>, EVM>;
-pub type OraclePrecompile =
-	runtime_common::OraclePrecompile<AccountId, EvmAddressMapping<Runtime>, EvmCurrencyIdMapping<Runtime>, Prices>;
+pub type OraclePrecompile = runtime_common::OraclePrecompile<
+	AccountId,
+	EvmAddressMapping<Runtime>,
+	EvmCurrencyIdMapping<Runtime>,
+	Prices,
+	AggregatedDataProvider,
+>;
 pub type ScheduleCallPrecompile = runtime_common::ScheduleCallPrecompile<
 	AccountId,
 	EvmAddressMapping<Runtime>,
@@ -1416,7 +1589,35 @@ pub type ScheduleCallPrecompile = runtime_common::ScheduleCallPrecompile<
 >;
 
 pub type DexPrecompile =
-	runtime_common::DexPrecompile<AccountId, EvmAddressMapping<Runtime>, EvmCurrencyIdMapping<Runtime>, Dex>;
+	runtime_common::DexPrecompile<AccountId, EvmAddressMapping<Runtime>, EvmCurrencyIdMapping<Runtime>, Dex, Runtime>;
+pub type HomaPrecompile = runtime_common::HomaPrecompile<
+	AccountId,
+	EvmAddressMapping<Runtime>,
+	EvmCurrencyIdMapping<Runtime>,
+	(),
+	LiquidStakingExchangeRateProvider,
+	(),
+	Runtime,
+>;
+pub type IncentivesPrecompile = runtime_common::IncentivesPrecompile<
+	AccountId,
+	EvmAddressMapping<Runtime>,
+	EvmCurrencyIdMapping<Runtime>,
+	Incentives,
+	weights::module_incentives::WeightInfo<Runtime>,
+	Runtime,
+>;
+pub type HonzonPrecompile = runtime_common::HonzonPrecompile<
+	AccountId,
+	EvmAddressMapping<Runtime>,
+	EvmCurrencyIdMapping<Runtime>,
+	CdpEngine,
+	Runtime,
+>;
+pub type MulticallPrecompile =
+	runtime_common::MulticallPrecompile<AccountId, EvmAddressMapping<Runtime>, EvmCurrencyIdMapping<Runtime>, Runtime>;
+pub type ERC721Precompile =
+	runtime_common::ERC721Precompile<AccountId, EvmAddressMapping<Runtime>, EvmCurrencyIdMapping<Runtime>, Runtime>;
 
 impl module_evm::Config for Runtime {
 	type AddressMapping = EvmAddressMapping<Runtime>;
@@ -1434,9 +1635,16 @@ impl module_evm::Config for Runtime {
 		OraclePrecompile,
 		ScheduleCallPrecompile,
 		DexPrecompile,
+		HomaPrecompile,
+		IncentivesPrecompile,
+		HonzonPrecompile,
+		MulticallPrecompile,
+		ERC721Precompile,
 	>;
 	type ChainId = ChainId;
 	type GasToWeight = GasToWeight;
+	type BlockGasTarget = BlockGasTarget;
+	type AccessListGasMetering = AccessListGasMetering;
 	type ChargeTransactionPayment = module_transaction_payment::ChargeTransactionPayment<Runtime>;
 	type NetworkContractOrigin = EnsureRootOrTwoThirdsTechnicalCommittee;
 	type NetworkContractSource = NetworkContractSource;
@@ -1444,6 +1652,9 @@ impl module_evm::Config for Runtime {
 	type DeploymentFee = DeploymentFee;
 	type TreasuryAccount = TreasuryAccount;
 	type FreeDeploymentOrigin = EnsureRootOrHalfGeneralCouncil;
+	type IdleScheduler = ();
+	type ContractFilter = ();
+	type EvictionNoticePeriod = EvictionNoticePeriod;
 	type WeightInfo = weights::module_evm::WeightInfo<Runtime>;
 }
 
@@ -1468,6 +1679,15 @@ impl cumulus_pallet_parachain_system::Config for Runtime {
 
 impl parachain_info::Config for Runtime {}
 
+parameter_types! {
+	pub const MaxRelaychainStateRoots: u32 = 20;
+}
+
+impl module_relaychain_light_client::Config for Runtime {
+	type RelaychainStateRootProvider = RelaychainStateRootProvider<Runtime>;
+	type MaxRelaychainStateRoots = MaxRelaychainStateRoots;
+}
+
 impl cumulus_pallet_aura_ext::Config for Runtime {}
 
 // parameter_types! {
@@ -1628,9 +1848,9 @@ construct_runtime!(
 		// Tokens & Related
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>} = 10,
 		Tokens: orml_tokens::{Pallet, Storage, Event<T>, Config<T>} = 11,
-		Currencies: module_currencies::{Pallet, Call, Event<T>} = 12,
+		Currencies: module_currencies::{Pallet, Call, Storage, Event<T>} = 12,
 		Vesting: orml_vesting::{Pallet, Storage, Call, Event<T>, Config<T>} = 13,
-		TransactionPayment: module_transaction_payment::{Pallet, Call, Storage} = 14,
+		TransactionPayment: module_transaction_payment::{Pallet, Call, Storage, Event<T>} = 14,
 
 		// Treasury
 		Treasury: pallet_treasury::{Pallet, Call, Storage, Config, Event<T>} = 20,
@@ -1655,6 +1875,7 @@ construct_runtime!(
 		HomaCouncilMembership: pallet_membership::<Instance3>::{Pallet, Call, Storage, Event<T>, Config<T>} = 55,
 		TechnicalCommittee: pallet_collective::<Instance4>::{Pallet, Call, Storage, Origin<T>, Event<T>, Config<T>} = 56,
 		TechnicalCommitteeMembership: pallet_membership::<Instance4>::{Pallet, Call, Storage, Event<T>, Config<T>} = 57,
+		NominatorsCouncil: pallet_collective::<Instance5>::{Pallet, Call, Storage, Origin<T>, Event<T>} = 58,
 
 		Authority: orml_authority::{Pallet, Call, Storage, Event<T>, Origin<T>} = 70,
 		ElectionsPhragmen: pallet_elections_phragmen::{Pallet, Call, Storage, Event<T>} = 71,
@@ -1670,7 +1891,7 @@ construct_runtime!(
 
 		// ORML Core
 		Auction: orml_auction::{Pallet, Storage, Call, Event<T>} = 100,
-		Rewards: orml_rewards::{Pallet, Storage, Call} = 101,
+		Rewards: module_rewards::{Pallet, Storage, Call} = 101,
 		OrmlNFT: orml_nft::{Pallet, Storage, Config<T>} = 102,
 
 		// Acala Core
@@ -1686,15 +1907,16 @@ construct_runtime!(
 		EmergencyShutdown: module_emergency_shutdown::{Pallet, Storage, Call, Event<T>} = 125,
 
 		// Homa
-		Homa: module_homa::{Pallet, Call} = 130,
+		Homa: module_homa::{Pallet, Call, Storage, Event<T>} = 130,
 		NomineesElection: module_nominees_election::{Pallet, Call, Storage, Event<T>} = 131,
 		StakingPool: module_staking_pool::{Pallet, Call, Storage, Event<T>, Config} = 132,
-		PolkadotBridge: module_polkadot_bridge::{Pallet, Call, Storage} = 133,
+		PolkadotBridge: module_polkadot_bridge::{Pallet, Call, Storage, Event<T>} = 133,
 		HomaValidatorListModule: module_homa_validator_list::{Pallet, Call, Storage, Event<T>} = 134,
 
 		// Acala Other
-		Incentives: module_incentives::{Pallet, Storage, Call, Event<T>} = 140,
+		Incentives: module_incentives::{Pallet, Storage, Call, Config<T>, Event<T>} = 140,
 		NFT: module_nft::{Pallet, Call, Event<T>} = 141,
+		EventTopics: module_event_topics::{Pallet, Storage} = 142,
 
 		// Ecosystem modules
 		RenVmBridge: ecosystem_renvm_bridge::{Pallet, Call, Config, Storage, Event<T>, ValidateUnsigned} = 150,
@@ -1702,6 +1924,7 @@ construct_runtime!(
 		// Parachain
 		ParachainSystem: cumulus_pallet_parachain_system::{Pallet, Call, Storage, Inherent, Config, Event<T>} = 161,
 		ParachainInfo: parachain_info::{Pallet, Storage, Config} = 162,
+		RelaychainLightClient: module_relaychain_light_client::{Pallet, Storage} = 163,
 
 		// // XCM
 		// XcmHandler: cumulus_pallet_xcm_handler::{Pallet, Call, Event<T>, Origin} = 170,
@@ -1712,7 +1935,7 @@ construct_runtime!(
 		EVM: module_evm::{Pallet, Config<T>, Call, Storage, Event<T>} = 180,
 		EVMBridge: module_evm_bridge::{Pallet} = 181,
 		EvmAccounts: module_evm_accounts::{Pallet, Call, Storage, Event<T>} = 182,
-		EvmManager: module_evm_manager::{Pallet, Storage} = 183,
+		EvmManager: module_evm_manager::{Pallet, Call, Storage, Event<T>} = 183,
 
 		// Collator support. the order of these 4 are important and shall not change.
 		Authorship: pallet_authorship::{Pallet, Call, Storage} = 190,
@@ -1893,6 +2116,177 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl module_honzon_rpc_runtime_api::HonzonApi<
+		Block,
+		AccountId,
+		CurrencyId,
+		Balance,
+	> for Runtime {
+		fn get_positions(
+			currency_id: CurrencyId,
+		) -> Vec<module_honzon_rpc_runtime_api::CdpPosition<AccountId, Balance>> {
+			module_loans::Positions::<Runtime>::iter_prefix(currency_id)
+				.map(|(owner, position)| module_honzon_rpc_runtime_api::CdpPosition {
+					owner,
+					collateral: position.collateral,
+					debit: position.debit,
+				})
+				.collect()
+		}
+
+		fn get_collateral_states() -> Vec<module_honzon_rpc_runtime_api::CollateralState<CurrencyId, Balance>> {
+			module_cdp_engine::CollateralParams::<Runtime>::iter_keys()
+				.map(|currency_id| {
+					let total_positions = Loans::total_positions(currency_id);
+					module_honzon_rpc_runtime_api::CollateralState {
+						currency_id,
+						total_collateral: total_positions.collateral,
+						total_debit: total_positions.debit,
+						debit_exchange_rate: CdpEngine::get_debit_exchange_rate(currency_id),
+					}
+				})
+				.collect()
+		}
+
+		fn simulate_liquidation(
+			currency_id: CurrencyId,
+			price_drop: Permill,
+		) -> Option<module_honzon_rpc_runtime_api::LiquidationSimulation<AccountId, Balance>> {
+			CdpEngine::simulate_liquidation(currency_id, price_drop).map(|simulation| {
+				module_honzon_rpc_runtime_api::LiquidationSimulation {
+					unsafe_positions: simulation
+						.unsafe_positions
+						.into_iter()
+						.map(|(owner, position)| module_honzon_rpc_runtime_api::CdpPosition {
+							owner,
+							collateral: position.collateral,
+							debit: position.debit,
+						})
+						.collect(),
+					total_collateral: simulation.total_collateral,
+					total_debit_value: simulation.total_debit_value,
+					bad_debt_value: simulation.bad_debt_value,
+				}
+			})
+		}
+	}
+
+	impl module_dex_rpc_runtime_api::DexApi<
+		Block,
+		CurrencyId,
+		Balance,
+	> for Runtime {
+		fn get_liquidity_pools() -> Vec<module_dex_rpc_runtime_api::LiquidityPool<CurrencyId, Balance>> {
+			module_dex::LiquidityPool::<Runtime>::iter()
+				.map(|(trading_pair, (reserve_0, reserve_1))| module_dex_rpc_runtime_api::LiquidityPool {
+					currency_id_0: trading_pair.0,
+					currency_id_1: trading_pair.1,
+					reserve_0,
+					reserve_1,
+					total_shares: trading_pair
+						.get_dex_share_currency_id()
+						.map_or(0, orml_tokens::TotalIssuance::<Runtime>::get),
+				})
+				.collect()
+		}
+	}
+
+	impl module_incentives_rpc_runtime_api::IncentivesApi<
+		Block,
+		AccountId,
+		module_incentives::PoolId<AccountId>,
+		CurrencyId,
+		Balance,
+	> for Runtime {
+		fn get_pending_rewards(
+			account: AccountId,
+		) -> Vec<module_incentives_rpc_runtime_api::PendingReward<module_incentives::PoolId<AccountId>, CurrencyId, Balance>> {
+			Incentives::pending_rewards(&account)
+				.into_iter()
+				.map(|(pool_id, currency_id, amount)| module_incentives_rpc_runtime_api::PendingReward {
+					pool_id,
+					currency_id,
+					amount,
+				})
+				.collect()
+		}
+
+		fn get_apr_estimates() -> Vec<module_incentives_rpc_runtime_api::AprEstimate<module_incentives::PoolId<AccountId>>> {
+			use module_incentives::PoolId;
+			use module_support::PriceProvider;
+			use sp_runtime::traits::Saturating;
+
+			Incentives::apr_estimates(
+				System::block_number(),
+				365 * DAYS / AccumulatePeriod::get(),
+				Prices::get_price,
+				|pool_id| match pool_id {
+					// the share of loans incentive pool is the debit amount
+					PoolId::LoansIncentive(currency_id) => Prices::get_price(GetStableCurrencyId::get())
+						.map(|price| price.saturating_mul(CdpEngine::get_debit_exchange_rate(*currency_id))),
+					PoolId::DexIncentive(currency_id) | PoolId::DexSaving(currency_id) => Prices::get_price(*currency_id),
+					PoolId::HomaValidatorAllowance(_) => Prices::get_price(GetLiquidCurrencyId::get()),
+					PoolId::HomaIncentive => None,
+				},
+			)
+			.into_iter()
+			.map(|(pool_id, apr)| module_incentives_rpc_runtime_api::AprEstimate { pool_id, apr })
+			.collect()
+		}
+	}
+
+	impl module_homa_rpc_runtime_api::HomaApi<
+		Block,
+		AccountId,
+		Balance,
+	> for Runtime {
+		fn get_exchange_rate() -> ExchangeRate {
+			StakingPool::liquid_exchange_rate()
+		}
+
+		fn get_pending_unbonding() -> Vec<(EraIndex, Balance)> {
+			StakingPool::get_pending_unbonding()
+		}
+
+		fn get_claimable(account: AccountId) -> module_homa_rpc_runtime_api::BalanceInfo<Balance> {
+			module_homa_rpc_runtime_api::BalanceInfo {
+				amount: StakingPool::get_available_unbonded(&account)
+			}
+		}
+
+		fn get_unbondings(account: AccountId) -> Vec<(EraIndex, Balance)> {
+			StakingPool::get_unbondings(&account)
+		}
+
+		fn get_exchange_rate_history() -> Vec<(EraIndex, ExchangeRate)> {
+			StakingPool::get_exchange_rate_history()
+		}
+	}
+
+	impl runtime_common::AuthorityApi<Block, BlockNumber, Hash, OriginCaller> for Runtime {
+		fn get_scheduled_calls() -> Vec<runtime_common::ScheduledCall<BlockNumber, Hash, OriginCaller>> {
+			runtime_common::authority_scheduled_calls::<Runtime>()
+		}
+	}
+
+	impl runtime_common::AccountPortfolioApi<Block, AccountId, BlockNumber, module_incentives::PoolId<AccountId>> for Runtime {
+		fn get_account_portfolio(
+			account: AccountId,
+		) -> runtime_common::AccountPortfolio<BlockNumber, module_incentives::PoolId<AccountId>> {
+			let homa_claims = runtime_common::HomaClaims {
+				claimable: StakingPool::get_available_unbonded(&account),
+				unbondings: StakingPool::get_unbondings(&account),
+			};
+			runtime_common::account_portfolio::<Runtime>(&account, homa_claims)
+		}
+	}
+
+	impl runtime_common::StateReportApi<Block> for Runtime {
+		fn get_state_report() -> Vec<runtime_common::StorageItemReport> {
+			runtime_common::state_report(runtime_common::STATE_REPORT_ITEMS)
+		}
+	}
+
 	impl module_evm_rpc_runtime_api::EVMRuntimeRPCApi<Block, Balance> for Runtime {
 		fn call(
 			from: H160,
@@ -1979,6 +2373,110 @@ impl_runtime_apis! {
 
 			request.ok_or(sp_runtime::DispatchError::Other("Invalid parameter extrinsic, not evm Call"))
 		}
+
+		fn estimate_fee(
+			from: H160,
+			to: Option<H160>,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			storage_limit: u32,
+			fee_currency_id: Option<CurrencyId>,
+		) -> Result<EstimateFeeInfo<Balance>, sp_runtime::DispatchError> {
+			EVM::estimate_fee(from, to, data, value, gas_limit, storage_limit, fee_currency_id)
+		}
+
+		fn block_fee_info() -> BlockFeeInfo {
+			EVM::block_fee_info()
+		}
+
+		fn contract_status(address: H160) -> Option<ContractStatus> {
+			EVM::contract_status(&address)
+		}
+
+		fn contract_metadata(code_hash: H256) -> Option<ContractMetadata> {
+			EVM::contract_metadata(&code_hash)
+		}
+
+		fn block_logs_bloom() -> Option<LogsBloom> {
+			EVM::block_logs_bloom()
+		}
+
+		fn block_receipts() -> Vec<Receipt> {
+			EVM::block_receipts()
+		}
+	}
+
+	impl module_evm_rpc_runtime_api::EVMTraceRuntimeApi<Block> for Runtime {
+		fn trace_extrinsic(
+			header: &<Block as BlockT>::Header,
+			extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+			index: u32,
+			config: TracerConfig,
+		) -> Result<TraceInfo, sp_runtime::DispatchError> {
+			let index = index as usize;
+			let extrinsic = extrinsics
+				.get(index)
+				.cloned()
+				.ok_or(sp_runtime::DispatchError::Other("Invalid parameter index, extrinsic not found"))?;
+
+			Executive::initialize_block(header);
+			for extrinsic in extrinsics.into_iter().take(index) {
+				let _ = Executive::apply_extrinsic(extrinsic);
+			}
+
+			let mut tracer = module_evm::runner::tracing::Tracer::new(config);
+			module_evm::runner::tracing::using(&mut tracer, || {
+				let _ = Executive::apply_extrinsic(extrinsic);
+			});
+
+			tracer.finalize().ok_or(sp_runtime::DispatchError::Other("Invalid parameter extrinsic, not evm Call"))
+		}
+	}
+
+	impl module_evm_rpc_runtime_api::DryRunRuntimeApi<Block, Balance> for Runtime {
+		fn dry_run_extrinsic(extrinsic: <Block as BlockT>::Extrinsic) -> DryRunInfo<Balance> {
+			let len = extrinsic.encoded_size() as u32;
+			let fee = TransactionPayment::query_info(extrinsic.clone(), len).partial_fee;
+			let event_count = System::event_count() as usize;
+
+			let ((result, events), storage_changes) = EVM::dry_run(|| {
+				let result = Executive::apply_extrinsic(extrinsic);
+				let events = System::events()
+					.into_iter()
+					.skip(event_count)
+					.map(|record| record.event.encode())
+					.collect();
+				(result, events)
+			});
+
+			DryRunInfo {
+				result,
+				events,
+				storage_changes,
+				fee,
+			}
+		}
+	}
+
+	impl module_evm_rpc_runtime_api::EVMTransactionPoolApi<Block, AccountId, Balance> for Runtime {
+		fn evm_account_nonce(address: H160) -> (AccountId, Nonce) {
+			use module_support::AddressMapping;
+
+			let account = EvmAddressMapping::<Runtime>::get_account_id(&address);
+			let nonce = System::account_nonce(&account);
+			(account, nonce)
+		}
+
+		fn evm_transaction(extrinsic: <Block as BlockT>::Extrinsic) -> Option<EvmTransaction<Balance>> {
+			let (address, _, extra) = extrinsic.signature?;
+			let who = <Runtime as frame_system::Config>::Lookup::lookup(address).ok()?;
+			match extrinsic.function {
+				// the nonce of `frame_system::CheckNonce` in `SignedExtra`
+				Call::EVM(call) => EVM::evm_transaction(&who, (extra.4).0, &call),
+				_ => None,
+			}
+		}
 	}
 
 	impl cumulus_primitives_core::CollectCollationInfo<Block> for Runtime {
@@ -33,6 +33,7 @@ pub mod module_honzon;
 pub mod module_incentives;
 pub mod module_nft;
 pub mod module_nominees_election;
+pub mod module_polkadot_bridge;
 pub mod module_prices;
 pub mod module_transaction_payment;
 
@@ -42,3 +43,5 @@ pub mod orml_gradually_update;
 pub mod orml_oracle;
 pub mod orml_tokens;
 pub mod orml_vesting;
+
+pub mod ecosystem_renvm_bridge;
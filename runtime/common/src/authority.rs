@@ -0,0 +1,113 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! The public review of the dispatches scheduled by `orml_authority`.
+
+// The `unnecessary_mut_passed` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::{Codec, Decode, Encode};
+use frame_support::{
+	storage::migration::storage_key_iter,
+	traits::{
+		schedule::{Period, Priority},
+		PalletInfo,
+	},
+	Twox64Concat,
+};
+use orml_authority::ScheduleTaskIndex;
+use sp_runtime::{traits::Hash, RuntimeDebug};
+use sp_std::prelude::*;
+
+/// The pending dispatch scheduled by `orml_authority`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct ScheduledCall<BlockNumber, Hash, PalletsOrigin> {
+	/// The origin the call is dispatched with, which identifies the schedule together with
+	/// `task_id`.
+	pub origin: PalletsOrigin,
+	/// The index of the schedule.
+	pub task_id: ScheduleTaskIndex,
+	/// The block number the call is dispatched at.
+	pub when: BlockNumber,
+	/// The hash of the call.
+	pub call_hash: Hash,
+}
+
+/// The item of the agenda of `pallet_scheduler`, with the same encoding as
+/// `pallet_scheduler::ScheduledV2` whose fields are private.
+#[derive(Decode)]
+struct Scheduled<Call, BlockNumber, PalletsOrigin> {
+	maybe_id: Option<Vec<u8>>,
+	_priority: Priority,
+	call: Call,
+	_maybe_periodic: Option<Period<BlockNumber>>,
+	origin: PalletsOrigin,
+}
+
+type AgendaOf<Runtime> = Vec<
+	Option<
+		Scheduled<
+			<Runtime as pallet_scheduler::Config>::Call,
+			<Runtime as frame_system::Config>::BlockNumber,
+			<Runtime as pallet_scheduler::Config>::PalletsOrigin,
+		>,
+	>,
+>;
+
+/// The pending dispatches scheduled by `orml_authority`, ordered by dispatch time.
+///
+/// The schedules of `orml_authority` are named by the encoded `(origin, task_id)`, so that they
+/// can be told apart from the other named schedules of `pallet_scheduler`.
+pub fn authority_scheduled_calls<Runtime: pallet_scheduler::Config>(
+) -> Vec<ScheduledCall<Runtime::BlockNumber, Runtime::Hash, Runtime::PalletsOrigin>> {
+	let pallet_name = <Runtime as frame_system::Config>::PalletInfo::name::<pallet_scheduler::Pallet<Runtime>>()
+		.unwrap_or("Scheduler");
+
+	let mut calls =
+		storage_key_iter::<Runtime::BlockNumber, AgendaOf<Runtime>, Twox64Concat>(pallet_name.as_bytes(), b"Agenda")
+			.flat_map(|(when, agenda)| {
+				agenda.into_iter().flatten().filter_map(move |scheduled| {
+					let id = scheduled.maybe_id?;
+					let (origin, task_id) = <(Runtime::PalletsOrigin, ScheduleTaskIndex)>::decode(&mut &id[..]).ok()?;
+					if origin != scheduled.origin || (&origin, task_id).encode() != id {
+						return None;
+					}
+
+					Some(ScheduledCall {
+						origin,
+						task_id,
+						when,
+						call_hash: Runtime::Hashing::hash_of(&scheduled.call),
+					})
+				})
+			})
+			.collect::<Vec<_>>();
+	calls.sort_by_key(|call| call.when);
+	calls
+}
+
+sp_api::decl_runtime_apis! {
+	pub trait AuthorityApi<BlockNumber, Hash, PalletsOrigin> where
+		BlockNumber: Codec,
+		Hash: Codec,
+		PalletsOrigin: Codec,
+	{
+		/// The pending dispatches scheduled by the authority module, ordered by dispatch time.
+		fn get_scheduled_calls() -> Vec<ScheduledCall<BlockNumber, Hash, PalletsOrigin>>;
+	}
+}
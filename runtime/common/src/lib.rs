@@ -22,7 +22,7 @@
 
 use frame_support::{
 	parameter_types,
-	traits::Contains,
+	traits::{Contains, Get},
 	weights::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, WEIGHT_PER_MILLIS},
 		DispatchClass, Weight,
@@ -31,9 +31,11 @@ use frame_support::{
 use frame_system::limits;
 pub use module_support::{ExchangeRate, PrecompileCallerFilter, Price, Rate, Ratio};
 use primitives::{
-	Balance, BlockNumber, CurrencyId, PRECOMPILE_ADDRESS_START, PREDEPLOY_ADDRESS_START, SYSTEM_CONTRACT_ADDRESS_PREFIX,
+	Balance, BlockNumber, CurrencyId, H160_POSITION_NFT_CLASS, H160_PREFIX_NFT_CLASS,
+	MIRRORED_NFT_CLASSES_ADDRESS_START, PRECOMPILE_ADDRESS_START, PREDEPLOY_ADDRESS_START,
+	SYSTEM_CONTRACT_ADDRESS_PREFIX,
 };
-use sp_core::H160;
+use sp_core::{H160, H256};
 use sp_runtime::{
 	// TODO: move after https://github.com/paritytech/substrate/pull/9209
 	offchain::storage_lock::BlockNumberProvider,
@@ -43,12 +45,27 @@ use sp_runtime::{
 };
 use static_assertions::const_assert;
 
+pub mod authority;
+pub use authority::{authority_scheduled_calls, AuthorityApi, ScheduledCall};
+
 mod homa;
 pub use homa::*;
 
+mod governance;
+pub use governance::VotingPowerCurrency;
+
+pub mod portfolio;
+pub use portfolio::{account_portfolio, AccountPortfolio, AccountPortfolioApi, HomaClaims};
+
+pub mod state_report;
+pub use state_report::{state_report, StateReportApi, StorageItemReport, STATE_REPORT_ITEMS};
+
+pub mod tasks;
+
 pub mod precompile;
 pub use precompile::{
-	AllPrecompiles, DexPrecompile, MultiCurrencyPrecompile, NFTPrecompile, OraclePrecompile, ScheduleCallPrecompile,
+	AllPrecompiles, DexPrecompile, ERC721Precompile, HomaPrecompile, HonzonPrecompile, IncentivesPrecompile,
+	MultiCurrencyPrecompile, MulticallPrecompile, NFTPrecompile, OraclePrecompile, ScheduleCallPrecompile,
 	StateRentPrecompile,
 };
 pub use primitives::currency::{TokenInfo, ACA, AUSD, DOT, KAR, KSM, KUSD, LDOT, LKSM, RENBTC};
@@ -75,6 +92,23 @@ pub fn is_acala_precompile(address: H160) -> bool {
 		&& address < H160::from_low_u64_be(PREDEPLOY_ADDRESS_START)
 }
 
+/// The address of the ERC-721 contract mirroring the NFT class `class_id`.
+pub fn nft_class_address(class_id: u32) -> H160 {
+	H160::from_low_u64_be(MIRRORED_NFT_CLASSES_ADDRESS_START + class_id as u64)
+}
+
+/// The NFT class id mirrored at `address`, `None` if it's not a mirrored NFT class address.
+pub fn nft_class_id_of(address: H160) -> Option<u32> {
+	let bytes = address.as_bytes();
+	if bytes.starts_with(&H160_PREFIX_NFT_CLASS) {
+		let mut class_id = [0u8; 4];
+		class_id.copy_from_slice(&bytes[H160_POSITION_NFT_CLASS]);
+		Some(u32::from_be_bytes(class_id))
+	} else {
+		None
+	}
+}
+
 /// The call is allowed only if caller is a system contract.
 pub struct SystemContractsFilter;
 impl PrecompileCallerFilter for SystemContractsFilter {
@@ -92,6 +126,14 @@ impl Convert<u64, Weight> for GasToWeight {
 	}
 }
 
+/// Convert weight to gas, the inverse of `GasToWeight`
+pub struct WeightToGas;
+impl Convert<Weight, u64> for WeightToGas {
+	fn convert(a: Weight) -> u64 {
+		a as u64
+	}
+}
+
 // TODO: somehow estimate this value. Start from a conservative value.
 pub const AVERAGE_ON_INITIALIZE_RATIO: Perbill = Perbill::from_percent(10);
 /// The ratio that `Normal` extrinsics should occupy. Start from a conservative value.
@@ -138,6 +180,11 @@ parameter_types! {
 		.saturating_sub(BlockExecutionWeight::get());
 }
 
+parameter_types! {
+	/// The EVM gas used per block targeted by the base fee, half of the `Normal` dispatch weight.
+	pub BlockGasTarget: u64 = WeightToGas::convert(NORMAL_DISPATCH_RATIO * MAXIMUM_BLOCK_WEIGHT) / 2;
+}
+
 pub struct DummyNomineeFilter;
 impl<AccountId> Contains<AccountId> for DummyNomineeFilter {
 	fn contains(_: &AccountId) -> bool {
@@ -174,6 +221,15 @@ impl<T: cumulus_pallet_parachain_system::Config> BlockNumberProvider for Relaych
 	}
 }
 
+pub struct RelaychainStateRootProvider<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: cumulus_pallet_parachain_system::Config> Get<Option<(BlockNumber, H256)>> for RelaychainStateRootProvider<T> {
+	fn get() -> Option<(BlockNumber, H256)> {
+		cumulus_pallet_parachain_system::Pallet::<T>::validation_data()
+			.map(|d| (d.relay_parent_number, d.relay_parent_storage_root))
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -218,4 +274,28 @@ mod tests {
 		assert!(!is_acala_precompile(H160::from_low_u64_be(PREDEPLOY_ADDRESS_START)));
 		assert!(!is_acala_precompile([1u8; 20].into()));
 	}
+
+	#[test]
+	fn nft_class_address_works() {
+		assert_eq!(
+			nft_class_address(0),
+			H160::from_low_u64_be(MIRRORED_NFT_CLASSES_ADDRESS_START)
+		);
+		assert_eq!(nft_class_id_of(nft_class_address(0)), Some(0));
+		assert_eq!(
+			nft_class_id_of(nft_class_address(u32::max_value())),
+			Some(u32::max_value())
+		);
+		assert!(is_system_contract(nft_class_address(u32::max_value())));
+
+		assert_eq!(
+			nft_class_id_of(H160::from_low_u64_be(MIRRORED_NFT_CLASSES_ADDRESS_START - 1)),
+			None
+		);
+		assert_eq!(
+			nft_class_id_of(H160::from_low_u64_be(MIRRORED_NFT_CLASSES_ADDRESS_START << 1)),
+			None
+		);
+		assert_eq!(nft_class_id_of(H160::from_low_u64_be(PRECOMPILE_ADDRESS_START)), None);
+	}
 }
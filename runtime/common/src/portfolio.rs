@@ -0,0 +1,248 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! The consolidated view of the assets and positions of an account.
+
+// The `unnecessary_mut_passed` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::{Codec, Decode, Encode};
+use frame_support::traits::Get;
+use module_incentives::PoolId;
+use module_support::{PriceProvider, Ratio};
+use orml_vesting::VestingSchedule;
+use primitives::{Balance, CurrencyId, EraIndex};
+use sp_runtime::{helpers_128bit::multiply_by_rational, traits::UniqueSaturatedInto, RuntimeDebug};
+use sp_std::prelude::*;
+
+/// The balance of a currency held by the account.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct TokenBalance {
+	/// The currency.
+	pub currency_id: CurrencyId,
+	/// The free balance.
+	pub free: Balance,
+	/// The reserved balance.
+	pub reserved: Balance,
+	/// The part of the free balance that can't be transferred.
+	pub frozen: Balance,
+}
+
+/// The liquidity of a DEX pool provided by the account.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct LpPosition {
+	/// The DEX share currency of the pool.
+	pub currency_id: CurrencyId,
+	/// The DEX share held by the account.
+	pub share: Balance,
+	/// The DEX share deposited to the incentives module by the account.
+	pub deposited_share: Balance,
+	/// The amounts of the two currencies of the pool underlying all the DEX share of the account,
+	/// in the order of `currency_id`.
+	pub underlying_amounts: (Balance, Balance),
+}
+
+/// The collateralized debit position of the account and its health.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct CdpPosition {
+	/// The collateral currency.
+	pub currency_id: CurrencyId,
+	/// The amount of collateral.
+	pub collateral: Balance,
+	/// The amount of debit.
+	pub debit: Balance,
+	/// The value of debit in stable currency.
+	pub debit_value: Balance,
+	/// The collateral ratio at the current price, `None` if the price is not available.
+	pub collateral_ratio: Option<Ratio>,
+	/// The collateral ratio below which the position can be liquidated.
+	pub liquidation_ratio: Ratio,
+	/// Whether the position can be liquidated now.
+	pub is_unsafe: bool,
+}
+
+/// The unbonded staking currency of the account in Homa.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct HomaClaims {
+	/// The amount that can be claimed now.
+	pub claimable: Balance,
+	/// The amounts that can be claimed after each era, sorted by era in ascending order.
+	pub unbondings: Vec<(EraIndex, Balance)>,
+}
+
+/// The rewards of an incentive pool that the account can claim.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct PendingIncentive<PoolId> {
+	/// The incentive pool.
+	pub pool_id: PoolId,
+	/// The reward currency.
+	pub currency_id: CurrencyId,
+	/// The amount of reward.
+	pub amount: Balance,
+}
+
+/// The assets and positions of an account.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct AccountPortfolio<BlockNumber, PoolId> {
+	/// The non-zero balances of the native currency and the tokens.
+	pub token_balances: Vec<TokenBalance>,
+	/// The liquidity provided to DEX.
+	pub lp_positions: Vec<LpPosition>,
+	/// The collateralized debit positions.
+	pub cdp_positions: Vec<CdpPosition>,
+	/// The unbonded staking currency in Homa.
+	pub homa_claims: HomaClaims,
+	/// The vesting schedules.
+	pub vesting_schedules: Vec<VestingSchedule<BlockNumber, Balance>>,
+	/// The pending rewards of the incentive pools.
+	pub pending_incentives: Vec<PendingIncentive<PoolId>>,
+}
+
+/// The assets and positions of `who`, the Homa claims of which are `homa_claims` so that the
+/// runtimes without Homa can leave them empty.
+pub fn account_portfolio<Runtime>(
+	who: &Runtime::AccountId,
+	homa_claims: HomaClaims,
+) -> AccountPortfolio<Runtime::BlockNumber, PoolId<Runtime::RelaychainAccountId>>
+where
+	Runtime: frame_system::Config<AccountData = pallet_balances::AccountData<Balance>>
+		+ orml_tokens::Config<CurrencyId = CurrencyId, Balance = Balance>
+		+ module_currencies::Config
+		+ module_dex::Config
+		+ module_cdp_engine::Config
+		+ module_incentives::Config
+		+ orml_vesting::Config,
+{
+	let native_account = frame_system::Pallet::<Runtime>::account(who).data;
+	let mut token_balances = vec![TokenBalance {
+		currency_id: <Runtime as module_currencies::Config>::GetNativeCurrencyId::get(),
+		free: native_account.free,
+		reserved: native_account.reserved,
+		frozen: native_account.misc_frozen.max(native_account.fee_frozen),
+	}];
+	token_balances.extend(
+		orml_tokens::Accounts::<Runtime>::iter_prefix(who).map(|(currency_id, account)| TokenBalance {
+			currency_id,
+			free: account.free,
+			reserved: account.reserved,
+			frozen: account.frozen,
+		}),
+	);
+	token_balances.retain(|balance| balance.free != 0 || balance.reserved != 0);
+	token_balances.sort_by_key(|balance| balance.currency_id);
+
+	let lp_positions = module_dex::LiquidityPool::<Runtime>::iter()
+		.filter_map(|(trading_pair, (pool_0, pool_1))| {
+			let currency_id = trading_pair.get_dex_share_currency_id()?;
+			let account = orml_tokens::Accounts::<Runtime>::get(who, currency_id);
+			let share = account.free.saturating_add(account.reserved);
+			let deposited_share =
+				module_incentives::Pallet::<Runtime>::raw_share(&PoolId::DexIncentive(currency_id), who);
+			let total_share = share.saturating_add(deposited_share);
+			if total_share == 0 {
+				return None;
+			}
+
+			let total_issuance = orml_tokens::TotalIssuance::<Runtime>::get(currency_id);
+			let underlying_amount =
+				|pool: Balance| multiply_by_rational(total_share, pool, total_issuance).unwrap_or_default();
+			Some(LpPosition {
+				currency_id,
+				share,
+				deposited_share,
+				underlying_amounts: (underlying_amount(pool_0), underlying_amount(pool_1)),
+			})
+		})
+		.collect();
+
+	let stable_currency_id = <Runtime as module_cdp_engine::Config>::GetStableCurrencyId::get();
+	let cdp_positions = module_cdp_engine::CollateralParams::<Runtime>::iter_keys()
+		.filter_map(|currency_id| {
+			let position = module_loans::Pallet::<Runtime>::positions(currency_id, who);
+			if position.collateral == 0 && position.debit == 0 {
+				return None;
+			}
+
+			let collateral_ratio = <Runtime as module_cdp_engine::Config>::PriceSource::get_relative_price(
+				currency_id,
+				stable_currency_id,
+			)
+			.map(|price| {
+				module_cdp_engine::Pallet::<Runtime>::calculate_collateral_ratio(
+					currency_id,
+					position.collateral,
+					position.debit,
+					price,
+				)
+			});
+			Some(CdpPosition {
+				currency_id,
+				collateral: position.collateral,
+				debit: position.debit,
+				debit_value: module_cdp_engine::Pallet::<Runtime>::get_debit_value(currency_id, position.debit),
+				collateral_ratio,
+				liquidation_ratio: module_cdp_engine::Pallet::<Runtime>::get_liquidation_ratio(currency_id),
+				is_unsafe: module_cdp_engine::Pallet::<Runtime>::is_cdp_unsafe(
+					currency_id,
+					position.collateral,
+					position.debit,
+				),
+			})
+		})
+		.collect();
+
+	let vesting_schedules = orml_vesting::VestingSchedules::<Runtime>::get(who)
+		.iter()
+		.map(|schedule| VestingSchedule {
+			start: schedule.start,
+			period: schedule.period,
+			period_count: schedule.period_count,
+			per_period: schedule.per_period.unique_saturated_into(),
+		})
+		.collect();
+
+	let pending_incentives = module_incentives::Pallet::<Runtime>::pending_rewards(who)
+		.into_iter()
+		.map(|(pool_id, currency_id, amount)| PendingIncentive {
+			pool_id,
+			currency_id,
+			amount,
+		})
+		.collect();
+
+	AccountPortfolio {
+		token_balances,
+		lp_positions,
+		cdp_positions,
+		homa_claims,
+		vesting_schedules,
+		pending_incentives,
+	}
+}
+
+sp_api::decl_runtime_apis! {
+	pub trait AccountPortfolioApi<AccountId, BlockNumber, PoolId> where
+		AccountId: Codec,
+		BlockNumber: Codec,
+		PoolId: Codec,
+	{
+		/// The token balances, DEX liquidity, CDPs with their health, Homa claims, vesting
+		/// schedules and pending incentive rewards of `account`.
+		fn get_account_portfolio(account: AccountId) -> AccountPortfolio<BlockNumber, PoolId>;
+	}
+}
@@ -17,12 +17,15 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use super::input::{Input, InputT};
-use frame_support::log;
+use crate::WeightToGas;
+use frame_support::{log, traits::Get};
+use module_dex::WeightInfo;
 use module_evm::{Context, ExitError, ExitSucceed, Precompile};
 use module_support::{AddressMapping as AddressMappingT, CurrencyIdMapping as CurrencyIdMappingT, DEXManager};
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use primitives::{Balance, CurrencyId};
 use sp_core::U256;
+use sp_runtime::traits::Convert;
 use sp_std::{fmt::Debug, marker::PhantomData, prelude::*, result};
 
 /// The `DEX` impl precompile.
@@ -32,10 +35,21 @@ use sp_std::{fmt::Debug, marker::PhantomData, prelude::*, result};
 ///
 /// Actions:
 /// - Get liquidity. Rest `input` bytes: `currency_id_a`, `currency_id_b`.
-/// - Swap with exact supply. Rest `input` bytes: `who`, `currency_id_a`, `currency_id_b`,
-///   `supply_amount`, `min_target_amount`.
-pub struct DexPrecompile<AccountId, AddressMapping, CurrencyIdMapping, Dex>(
-	PhantomData<(AccountId, AddressMapping, CurrencyIdMapping, Dex)>,
+/// - Get liquidity token address. Rest `input` bytes: `currency_id_a`, `currency_id_b`.
+/// - Get swap target amount. Rest `input` bytes: `path`, `supply_amount`.
+/// - Get swap supply amount. Rest `input` bytes: `path`, `target_amount`.
+/// - Swap with exact supply. Rest `input` bytes: `who`, `path`, `supply_amount`,
+///   `min_target_amount`.
+/// - Swap with exact target. Rest `input` bytes: `who`, `path`, `target_amount`,
+///   `max_supply_amount`.
+/// - Add liquidity. Rest `input` bytes: `who`, `currency_id_a`, `currency_id_b`, `max_amount_a`,
+///   `max_amount_b`, `min_share_increment`.
+/// - Remove liquidity. Rest `input` bytes: `who`, `currency_id_a`, `currency_id_b`,
+///   `remove_share`, `min_withdrawn_a`, `min_withdrawn_b`.
+///
+/// The used gas is converted from the weight of the corresponding `module_dex` call.
+pub struct DexPrecompile<AccountId, AddressMapping, CurrencyIdMapping, Dex, Runtime>(
+	PhantomData<(AccountId, AddressMapping, CurrencyIdMapping, Dex, Runtime)>,
 );
 
 #[derive(Debug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
@@ -51,27 +65,33 @@ pub enum Action {
 	RemoveLiquidity = 0x35315332,
 }
 
-impl<AccountId, AddressMapping, CurrencyIdMapping, Dex> Precompile
-	for DexPrecompile<AccountId, AddressMapping, CurrencyIdMapping, Dex>
+impl<AccountId, AddressMapping, CurrencyIdMapping, Dex, Runtime> Precompile
+	for DexPrecompile<AccountId, AddressMapping, CurrencyIdMapping, Dex, Runtime>
 where
 	AccountId: Debug + Clone,
 	AddressMapping: AddressMappingT<AccountId>,
 	CurrencyIdMapping: CurrencyIdMappingT,
 	Dex: DEXManager<AccountId, CurrencyId, Balance>,
+	Runtime: module_dex::Config,
 {
 	fn execute(
 		input: &[u8],
-		_target_gas: Option<u64>,
+		target_gas: Option<u64>,
 		_context: &Context,
 	) -> result::Result<(ExitSucceed, Vec<u8>, u64), ExitError> {
-		//TODO: evaluate cost
-
 		log::debug!(target: "evm", "dex: input: {:?}", input);
 
 		let input = Input::<Action, AccountId, AddressMapping, CurrencyIdMapping>::new(input);
 
 		let action = input.action()?;
 
+		let gas_cost = Self::gas_cost(&action, &input)?;
+		if let Some(gas_limit) = target_gas {
+			if gas_limit < gas_cost {
+				return Err(ExitError::OutOfGas);
+			}
+		}
+
 		match action {
 			Action::GetLiquidityPool => {
 				let currency_id_a = input.currency_id_at(1)?;
@@ -89,7 +109,7 @@ where
 				U256::from(balance_a).to_big_endian(&mut be_bytes[..32]);
 				U256::from(balance_b).to_big_endian(&mut be_bytes[32..64]);
 
-				Ok((ExitSucceed::Returned, be_bytes.to_vec(), 0))
+				Ok((ExitSucceed::Returned, be_bytes.to_vec(), gas_cost))
 			}
 			Action::GetLiquidityTokenAddress => {
 				let currency_id_a = input.currency_id_at(1)?;
@@ -107,7 +127,7 @@ where
 				let mut be_bytes = [0u8; 32];
 				U256::from(value.as_bytes()).to_big_endian(&mut be_bytes[..32]);
 
-				Ok((ExitSucceed::Returned, be_bytes.to_vec(), 0))
+				Ok((ExitSucceed::Returned, be_bytes.to_vec(), gas_cost))
 			}
 			Action::GetSwapTargetAmount => {
 				// solidity abi enocde array will add an offset at input[1]
@@ -130,7 +150,7 @@ where
 				let mut be_bytes = [0u8; 32];
 				U256::from(value).to_big_endian(&mut be_bytes[..32]);
 
-				Ok((ExitSucceed::Returned, be_bytes.to_vec(), 0))
+				Ok((ExitSucceed::Returned, be_bytes.to_vec(), gas_cost))
 			}
 			Action::GetSwapSupplyAmount => {
 				// solidity abi enocde array will add an offset at input[1]
@@ -153,7 +173,7 @@ where
 				let mut be_bytes = [0u8; 32];
 				U256::from(value).to_big_endian(&mut be_bytes[..32]);
 
-				Ok((ExitSucceed::Returned, be_bytes.to_vec(), 0))
+				Ok((ExitSucceed::Returned, be_bytes.to_vec(), gas_cost))
 			}
 			Action::SwapWithExactSupply => {
 				let who = input.account_id_at(1)?;
@@ -181,7 +201,7 @@ where
 				let mut be_bytes = [0u8; 32];
 				U256::from(value).to_big_endian(&mut be_bytes[..32]);
 
-				Ok((ExitSucceed::Returned, be_bytes.to_vec(), 0))
+				Ok((ExitSucceed::Returned, be_bytes.to_vec(), gas_cost))
 			}
 			Action::SwapWithExactTarget => {
 				let who = input.account_id_at(1)?;
@@ -209,7 +229,7 @@ where
 				let mut be_bytes = [0u8; 32];
 				U256::from(value).to_big_endian(&mut be_bytes[..32]);
 
-				Ok((ExitSucceed::Returned, be_bytes.to_vec(), 0))
+				Ok((ExitSucceed::Returned, be_bytes.to_vec(), gas_cost))
 			}
 			Action::AddLiquidity => {
 				let who = input.account_id_at(1)?;
@@ -239,7 +259,7 @@ where
 					ExitError::Other(err_msg.into())
 				})?;
 
-				Ok((ExitSucceed::Returned, vec![], 0))
+				Ok((ExitSucceed::Returned, vec![], gas_cost))
 			}
 			Action::RemoveLiquidity => {
 				let who = input.account_id_at(1)?;
@@ -269,12 +289,49 @@ where
 					ExitError::Other(err_msg.into())
 				})?;
 
-				Ok((ExitSucceed::Returned, vec![], 0))
+				Ok((ExitSucceed::Returned, vec![], gas_cost))
 			}
 		}
 	}
 }
 
+impl<AccountId, AddressMapping, CurrencyIdMapping, Dex, Runtime>
+	DexPrecompile<AccountId, AddressMapping, CurrencyIdMapping, Dex, Runtime>
+where
+	AccountId: Debug + Clone,
+	AddressMapping: AddressMappingT<AccountId>,
+	CurrencyIdMapping: CurrencyIdMappingT,
+	Runtime: module_dex::Config,
+{
+	fn gas_cost(
+		action: &Action,
+		input: &Input<'_, Action, AccountId, AddressMapping, CurrencyIdMapping>,
+	) -> result::Result<u64, ExitError> {
+		let db_weight = <Runtime as frame_system::Config>::DbWeight::get();
+		let weight = match action {
+			// read the trading pair status and the liquidity pool
+			Action::GetLiquidityPool | Action::GetLiquidityTokenAddress => db_weight.reads(2),
+			// read the trading pair status and the liquidity pool of every hop in the path
+			Action::GetSwapTargetAmount | Action::GetSwapSupplyAmount => {
+				let path_len = input.u32_at(3)?;
+				db_weight.reads(2u64.saturating_mul(path_len.into()))
+			}
+			Action::SwapWithExactSupply => {
+				let path_len = input.u32_at(5)?;
+				<Runtime as module_dex::Config>::WeightInfo::swap_with_exact_supply(path_len)
+			}
+			Action::SwapWithExactTarget => {
+				let path_len = input.u32_at(5)?;
+				<Runtime as module_dex::Config>::WeightInfo::swap_with_exact_target(path_len)
+			}
+			Action::AddLiquidity => <Runtime as module_dex::Config>::WeightInfo::add_liquidity(),
+			Action::RemoveLiquidity => <Runtime as module_dex::Config>::WeightInfo::remove_liquidity(),
+		};
+
+		Ok(WeightToGas::convert(weight))
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -0,0 +1,283 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::input::{Input, InputT, INPUT_BYTES_LENGTH};
+use crate::{nft_class_id_of, WeightToGas};
+use frame_support::{ensure, log, traits::Get};
+use module_evm::{Context, ExitError, ExitSucceed, Precompile};
+use module_nft::WeightInfo;
+use module_support::{AddressMapping as AddressMappingT, CurrencyIdMapping as CurrencyIdMappingT};
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use orml_traits::NFT as NFTT;
+use sp_core::{H160, H256, U256};
+use sp_runtime::traits::Convert;
+use sp_std::{borrow::Cow, fmt::Debug, marker::PhantomData, prelude::*, result};
+
+/// The `ERC721` impl precompile, presents the `module_nft` class `class_id` as an ERC-721
+/// contract at `nft_class_address(class_id)`.
+///
+/// Unlike the other precompiles, `input` data is the plain ABI encoding of the call, without the
+/// leading size, so the mirrored contracts can be called by wallets and contracts directly.
+///
+/// `input` data starts with `action`.
+///
+/// Actions:
+/// - Balance of. Rest `input` bytes: `owner`.
+/// - Owner of. Rest `input` bytes: `token_id`.
+/// - Transfer from. Rest `input` bytes: `from`, `to`, `token_id`. Approvals are not supported, the
+///   caller must be `from`. Emits `Transfer(address indexed from, address indexed to, uint256
+///   indexed tokenId)`.
+/// - Token URI. Rest `input` bytes: `token_id`. Returns the `tokenURI` attribute of the token if
+///   set, otherwise the token metadata.
+/// - Total supply.
+/// - Supports interface. Rest `input` bytes: `interface_id`.
+pub struct ERC721Precompile<AccountId, AddressMapping, CurrencyIdMapping, Runtime>(
+	PhantomData<(AccountId, AddressMapping, CurrencyIdMapping, Runtime)>,
+);
+
+#[derive(Debug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
+#[repr(u32)]
+pub enum Action {
+	BalanceOf = 0x70a08231,
+	OwnerOf = 0x6352211e,
+	TransferFrom = 0x23b872dd,
+	TokenURI = 0xc87b56dd,
+	TotalSupply = 0x18160ddd,
+	SupportsInterface = 0x01ffc9a7,
+}
+
+/// The interface ids of ERC-165, ERC-721 and ERC-721 metadata extension.
+pub const SUPPORTED_INTERFACES: [[u8; 4]; 3] = [
+	[0x01, 0xff, 0xc9, 0xa7],
+	[0x80, 0xac, 0x58, 0xcd],
+	[0x5b, 0x5e, 0x13, 0x9f],
+];
+
+impl<AccountId, AddressMapping, CurrencyIdMapping, Runtime> Precompile
+	for ERC721Precompile<AccountId, AddressMapping, CurrencyIdMapping, Runtime>
+where
+	AccountId: Debug + Clone,
+	AddressMapping: AddressMappingT<AccountId>,
+	CurrencyIdMapping: CurrencyIdMappingT,
+	Runtime: module_evm::Config
+		+ module_nft::Config
+		+ orml_nft::Config<ClassId = u32, TokenId = u64>
+		+ frame_system::Config<AccountId = AccountId>,
+{
+	fn execute(
+		input: &[u8],
+		target_gas: Option<u64>,
+		context: &Context,
+	) -> result::Result<(ExitSucceed, Vec<u8>, u64), ExitError> {
+		log::debug!(target: "evm", "erc721: input: {:?}", input);
+
+		let class_id = nft_class_id_of(context.address).ok_or_else(|| ExitError::Other("invalid address".into()))?;
+		let class_info =
+			orml_nft::Pallet::<Runtime>::classes(class_id).ok_or_else(|| ExitError::Other("class not found".into()))?;
+
+		// prepend the size expected by `Input` to the plain ABI encoded input
+		let input = [&[0u8; INPUT_BYTES_LENGTH][..], input].concat();
+		let input = Input::<Action, AccountId, AddressMapping, CurrencyIdMapping>::new(&input);
+
+		let action = input.action()?;
+
+		let gas_cost = Self::gas_cost(&action, &input)?;
+		if let Some(gas_limit) = target_gas {
+			if gas_limit < gas_cost {
+				return Err(ExitError::OutOfGas);
+			}
+		}
+
+		match action {
+			Action::BalanceOf => {
+				let owner = input.account_id_at(1)?;
+				log::debug!(target: "evm", "erc721: balance_of class_id: {:?}, owner: {:?}", class_id, owner);
+
+				let balance = module_nft::Pallet::<Runtime>::class_balance(&owner, class_id);
+
+				Ok((ExitSucceed::Returned, vec_u8_from_u256(balance.into()), gas_cost))
+			}
+			Action::OwnerOf => {
+				let token_id = input.u64_at(1)?;
+				log::debug!(target: "evm", "erc721: owner_of class_id: {:?}, token_id: {:?}", class_id, token_id);
+
+				let owner = <module_nft::Pallet<Runtime> as NFTT<AccountId>>::owner((class_id, token_id))
+					.ok_or_else(|| ExitError::Other("token not found".into()))?;
+				let owner: H160 = AddressMapping::get_evm_address(&owner)
+					.unwrap_or_else(|| AddressMapping::get_default_evm_address(&owner));
+
+				let mut address = [0u8; 32];
+				address[12..].copy_from_slice(owner.as_bytes());
+
+				Ok((ExitSucceed::Returned, address.to_vec(), gas_cost))
+			}
+			Action::TransferFrom => {
+				let from = input.evm_address_at(1)?;
+				let to = input.evm_address_at(2)?;
+				let token_id = input.u64_at(3)?;
+				log::debug!(
+					target: "evm",
+					"erc721: transfer_from class_id: {:?}, from: {:?}, to: {:?}, token_id: {:?}",
+					class_id, from, to, token_id
+				);
+
+				ensure!(from == context.caller, ExitError::Other("no permission".into()));
+
+				<module_nft::Pallet<Runtime> as NFTT<AccountId>>::transfer(
+					&input.account_id_at(1)?,
+					&input.account_id_at(2)?,
+					(class_id, token_id),
+				)
+				.map_err(|e| ExitError::Other(Cow::Borrowed(e.into())))?;
+
+				module_evm::Pallet::<Runtime>::deposit_log(
+					context.address,
+					vec![
+						H256(sp_io::hashing::keccak_256(b"Transfer(address,address,uint256)")),
+						from.into(),
+						to.into(),
+						H256::from_low_u64_be(token_id),
+					],
+					vec![],
+				);
+
+				Ok((ExitSucceed::Returned, vec![], gas_cost))
+			}
+			Action::TokenURI => {
+				let token_id = input.u64_at(1)?;
+				log::debug!(target: "evm", "erc721: token_uri class_id: {:?}, token_id: {:?}", class_id, token_id);
+
+				let uri = module_nft::Pallet::<Runtime>::token_uri((class_id, token_id))
+					.ok_or_else(|| ExitError::Other("token not found".into()))?;
+
+				Ok((ExitSucceed::Returned, vec_u8_from_bytes(&uri), gas_cost))
+			}
+			Action::TotalSupply => {
+				log::debug!(target: "evm", "erc721: total_supply class_id: {:?}", class_id);
+
+				Ok((
+					ExitSucceed::Returned,
+					vec_u8_from_u256(class_info.total_issuance.into()),
+					gas_cost,
+				))
+			}
+			Action::SupportsInterface => {
+				let interface_id = input.bytes_at(1, 4)?;
+				log::debug!(target: "evm", "erc721: supports_interface interface_id: {:?}", interface_id);
+
+				let supported = SUPPORTED_INTERFACES.iter().any(|id| id[..] == interface_id[..]);
+
+				Ok((
+					ExitSucceed::Returned,
+					vec_u8_from_u256(U256::from(supported as u8)),
+					gas_cost,
+				))
+			}
+		}
+	}
+}
+
+impl<AccountId, AddressMapping, CurrencyIdMapping, Runtime>
+	ERC721Precompile<AccountId, AddressMapping, CurrencyIdMapping, Runtime>
+where
+	AccountId: Debug + Clone,
+	AddressMapping: AddressMappingT<AccountId>,
+	CurrencyIdMapping: CurrencyIdMappingT,
+	Runtime: module_nft::Config
+		+ orml_nft::Config<ClassId = u32, TokenId = u64>
+		+ frame_system::Config<AccountId = AccountId>,
+{
+	fn gas_cost(
+		action: &Action,
+		input: &Input<'_, Action, AccountId, AddressMapping, CurrencyIdMapping>,
+	) -> result::Result<u64, ExitError> {
+		let db_weight = <Runtime as frame_system::Config>::DbWeight::get();
+		let weight = match action {
+			// read the class and all the tokens of the owner
+			Action::BalanceOf => {
+				let owner = input.account_id_at(1)?;
+				let tokens = <module_nft::Pallet<Runtime> as NFTT<AccountId>>::balance(&owner);
+				db_weight.reads(1u64.saturating_add(tokens as u64))
+			}
+			// read the class and the token
+			Action::OwnerOf => db_weight.reads(2),
+			Action::TransferFrom => <Runtime as module_nft::Config>::WeightInfo::transfer(),
+			// read the class, the token and the token URI attribute
+			Action::TokenURI => db_weight.reads(3),
+			// read the class
+			Action::TotalSupply | Action::SupportsInterface => db_weight.reads(1),
+		};
+
+		Ok(WeightToGas::convert(weight))
+	}
+}
+
+fn vec_u8_from_u256(value: U256) -> Vec<u8> {
+	let mut be_bytes = [0u8; 32];
+	value.to_big_endian(&mut be_bytes[..]);
+	be_bytes.to_vec()
+}
+
+// ABI encode `bytes` as the only returned `string`: offset, length and the bytes padded to 32 bytes.
+fn vec_u8_from_bytes(bytes: &[u8]) -> Vec<u8> {
+	let padded_len = (bytes.len() + 31) / 32 * 32;
+	let mut output = vec![0u8; 64 + padded_len];
+	U256::from(32).to_big_endian(&mut output[..32]);
+	U256::from(bytes.len()).to_big_endian(&mut output[32..64]);
+	output[64..64 + bytes.len()].copy_from_slice(bytes);
+	output
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::precompile::mock::get_function_selector;
+
+	#[test]
+	fn function_selector_match() {
+		assert_eq!(
+			u32::from_be_bytes(get_function_selector("balanceOf(address)")),
+			Into::<u32>::into(Action::BalanceOf)
+		);
+
+		assert_eq!(
+			u32::from_be_bytes(get_function_selector("ownerOf(uint256)")),
+			Into::<u32>::into(Action::OwnerOf)
+		);
+
+		assert_eq!(
+			u32::from_be_bytes(get_function_selector("transferFrom(address,address,uint256)")),
+			Into::<u32>::into(Action::TransferFrom)
+		);
+
+		assert_eq!(
+			u32::from_be_bytes(get_function_selector("tokenURI(uint256)")),
+			Into::<u32>::into(Action::TokenURI)
+		);
+
+		assert_eq!(
+			u32::from_be_bytes(get_function_selector("totalSupply()")),
+			Into::<u32>::into(Action::TotalSupply)
+		);
+
+		assert_eq!(
+			u32::from_be_bytes(get_function_selector("supportsInterface(bytes4)")),
+			Into::<u32>::into(Action::SupportsInterface)
+		);
+	}
+}
@@ -0,0 +1,214 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::input::{Input, InputT};
+use crate::WeightToGas;
+use frame_support::{log, traits::Get};
+use module_evm::{Context, ExitError, ExitSucceed, Precompile};
+use module_homa::WeightInfo;
+use module_support::{
+	AddressMapping as AddressMappingT, CurrencyIdMapping as CurrencyIdMappingT,
+	ExchangeRateProvider as ExchangeRateProviderT, HomaProtocol,
+};
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use primitives::{Balance, EraIndex};
+use sp_core::{H256, U256};
+use sp_runtime::{traits::Convert, FixedPointNumber};
+use sp_std::{fmt::Debug, marker::PhantomData, prelude::*, result};
+
+/// The `Homa` impl precompile.
+///
+///
+/// `input` data starts with `action`.
+///
+/// Actions:
+/// - Mint. Rest `input` bytes: `who`, `amount`. Emits `Minted(address indexed who, uint256 amount,
+///   uint256 liquidAmount)`.
+/// - Request redeem. Rest `input` bytes: `who`, `liquid_amount`. Emits `RedeemRequested(address
+///   indexed who, uint256 liquidAmount)`.
+/// - Get exchange rate from liquid currency to staking currency, with 18 decimals.
+///
+/// The used gas is converted from the weight of the corresponding `module_homa` call.
+pub struct HomaPrecompile<
+	AccountId,
+	AddressMapping,
+	CurrencyIdMapping,
+	Homa,
+	ExchangeRateProvider,
+	HomaWeightInfo,
+	Runtime,
+>(
+	PhantomData<(
+		AccountId,
+		AddressMapping,
+		CurrencyIdMapping,
+		Homa,
+		ExchangeRateProvider,
+		HomaWeightInfo,
+		Runtime,
+	)>,
+);
+
+#[derive(Debug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
+#[repr(u32)]
+pub enum Action {
+	Mint = 0x40c10f19,
+	RequestRedeem = 0x65b89f9f,
+	GetExchangeRate = 0xe6aa216c,
+}
+
+impl<AccountId, AddressMapping, CurrencyIdMapping, Homa, ExchangeRateProvider, HomaWeightInfo, Runtime> Precompile
+	for HomaPrecompile<AccountId, AddressMapping, CurrencyIdMapping, Homa, ExchangeRateProvider, HomaWeightInfo, Runtime>
+where
+	AccountId: Debug + Clone,
+	AddressMapping: AddressMappingT<AccountId>,
+	CurrencyIdMapping: CurrencyIdMappingT,
+	Homa: HomaProtocol<AccountId, Balance, EraIndex>,
+	ExchangeRateProvider: ExchangeRateProviderT,
+	HomaWeightInfo: WeightInfo,
+	Runtime: module_evm::Config,
+{
+	fn execute(
+		input: &[u8],
+		target_gas: Option<u64>,
+		context: &Context,
+	) -> result::Result<(ExitSucceed, Vec<u8>, u64), ExitError> {
+		log::debug!(target: "evm", "homa: input: {:?}", input);
+
+		let input = Input::<Action, AccountId, AddressMapping, CurrencyIdMapping>::new(input);
+
+		let action = input.action()?;
+
+		let gas_cost = Self::gas_cost(&action);
+		if let Some(gas_limit) = target_gas {
+			if gas_limit < gas_cost {
+				return Err(ExitError::OutOfGas);
+			}
+		}
+
+		match action {
+			Action::Mint => {
+				let who = input.account_id_at(1)?;
+				let amount = input.balance_at(2)?;
+				log::debug!(target: "evm", "homa: mint who: {:?}, amount: {:?}", who, amount);
+
+				let liquid_amount = Homa::mint(&who, amount).map_err(|e| {
+					let err_msg: &str = e.into();
+					ExitError::Other(err_msg.into())
+				})?;
+
+				module_evm::Pallet::<Runtime>::deposit_log(
+					context.address,
+					vec![
+						H256(sp_io::hashing::keccak_256(b"Minted(address,uint256,uint256)")),
+						input.evm_address_at(1)?.into(),
+					],
+					vec_u8_from_balances(&[amount, liquid_amount]),
+				);
+
+				Ok((ExitSucceed::Returned, vec_u8_from_balances(&[liquid_amount]), gas_cost))
+			}
+			Action::RequestRedeem => {
+				let who = input.account_id_at(1)?;
+				let liquid_amount = input.balance_at(2)?;
+				log::debug!(
+					target: "evm",
+					"homa: request_redeem who: {:?}, liquid_amount: {:?}",
+					who, liquid_amount
+				);
+
+				Homa::redeem_by_unbond(&who, liquid_amount).map_err(|e| {
+					let err_msg: &str = e.into();
+					ExitError::Other(err_msg.into())
+				})?;
+
+				module_evm::Pallet::<Runtime>::deposit_log(
+					context.address,
+					vec![
+						H256(sp_io::hashing::keccak_256(b"RedeemRequested(address,uint256)")),
+						input.evm_address_at(1)?.into(),
+					],
+					vec_u8_from_balances(&[liquid_amount]),
+				);
+
+				Ok((ExitSucceed::Returned, vec![], gas_cost))
+			}
+			Action::GetExchangeRate => {
+				let exchange_rate = ExchangeRateProvider::get_exchange_rate();
+				log::debug!(target: "evm", "homa: get_exchange_rate: {:?}", exchange_rate);
+
+				Ok((
+					ExitSucceed::Returned,
+					vec_u8_from_balances(&[exchange_rate.into_inner()]),
+					gas_cost,
+				))
+			}
+		}
+	}
+}
+
+impl<AccountId, AddressMapping, CurrencyIdMapping, Homa, ExchangeRateProvider, HomaWeightInfo, Runtime>
+	HomaPrecompile<AccountId, AddressMapping, CurrencyIdMapping, Homa, ExchangeRateProvider, HomaWeightInfo, Runtime>
+where
+	HomaWeightInfo: WeightInfo,
+	Runtime: module_evm::Config,
+{
+	fn gas_cost(action: &Action) -> u64 {
+		let db_weight = <Runtime as frame_system::Config>::DbWeight::get();
+		let weight = match action {
+			Action::Mint => HomaWeightInfo::mint(),
+			Action::RequestRedeem => HomaWeightInfo::redeem_wait_for_unbonding(),
+			// read the staking pool ledger and the total issuance of liquid currency
+			Action::GetExchangeRate => db_weight.reads(2),
+		};
+
+		WeightToGas::convert(weight)
+	}
+}
+
+fn vec_u8_from_balances(balances: &[Balance]) -> Vec<u8> {
+	let mut be_bytes = vec![0u8; 32 * balances.len()];
+	for (i, balance) in balances.iter().enumerate() {
+		U256::from(*balance).to_big_endian(&mut be_bytes[32 * i..32 * (i + 1)]);
+	}
+	be_bytes
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::precompile::mock::get_function_selector;
+
+	#[test]
+	fn function_selector_match() {
+		assert_eq!(
+			u32::from_be_bytes(get_function_selector("mint(address,uint256)")),
+			Into::<u32>::into(Action::Mint)
+		);
+
+		assert_eq!(
+			u32::from_be_bytes(get_function_selector("requestRedeem(address,uint256)")),
+			Into::<u32>::into(Action::RequestRedeem)
+		);
+
+		assert_eq!(
+			u32::from_be_bytes(get_function_selector("getExchangeRate()")),
+			Into::<u32>::into(Action::GetExchangeRate)
+		);
+	}
+}
@@ -0,0 +1,231 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::input::{Input, InputT};
+use crate::WeightToGas;
+use frame_support::{log, traits::Get};
+use module_evm::{Context, ExitError, ExitSucceed, Precompile};
+use module_support::{
+	AddressMapping as AddressMappingT, CurrencyIdMapping as CurrencyIdMappingT, HonzonInfo as HonzonInfoT,
+};
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use primitives::{Balance, CurrencyId};
+use sp_core::U256;
+use sp_runtime::{traits::Convert, FixedPointNumber};
+use sp_std::{fmt::Debug, marker::PhantomData, prelude::*, result};
+
+/// The `Honzon` info precompile, read-only.
+///
+///
+/// `input` data starts with `action`.
+///
+/// Actions:
+/// - Get liquidation ratio, with 18 decimals. Rest `input` bytes: `currency_id`.
+/// - Get interest rate per second, i.e. the stability fee, with 18 decimals. Rest `input` bytes:
+///   `currency_id`.
+/// - Get maximum total debit value, i.e. the debt ceiling. Rest `input` bytes: `currency_id`.
+/// - Get total debit value. Rest `input` bytes: `currency_id`.
+/// - Get total debit value of all the collaterals.
+/// - Get whether the system is emergency shutdown.
+///
+/// The debit values are in the stable currency.
+pub struct HonzonPrecompile<AccountId, AddressMapping, CurrencyIdMapping, HonzonInfo, Runtime>(
+	PhantomData<(AccountId, AddressMapping, CurrencyIdMapping, HonzonInfo, Runtime)>,
+);
+
+#[derive(Debug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
+#[repr(u32)]
+pub enum Action {
+	GetLiquidationRatio = 0xc4ba4c3a,
+	GetInterestRatePerSec = 0x4ce10df2,
+	GetMaximumTotalDebitValue = 0x40ed3f0a,
+	GetTotalDebitValue = 0x73b186b5,
+	GetSystemTotalDebitValue = 0x47178dda,
+	IsShutdown = 0xbf86d690,
+}
+
+impl<AccountId, AddressMapping, CurrencyIdMapping, HonzonInfo, Runtime> Precompile
+	for HonzonPrecompile<AccountId, AddressMapping, CurrencyIdMapping, HonzonInfo, Runtime>
+where
+	AccountId: Debug + Clone,
+	AddressMapping: AddressMappingT<AccountId>,
+	CurrencyIdMapping: CurrencyIdMappingT,
+	HonzonInfo: HonzonInfoT<CurrencyId, Balance>,
+	Runtime: frame_system::Config,
+{
+	fn execute(
+		input: &[u8],
+		target_gas: Option<u64>,
+		_context: &Context,
+	) -> result::Result<(ExitSucceed, Vec<u8>, u64), ExitError> {
+		log::debug!(target: "evm", "honzon: input: {:?}", input);
+
+		let input = Input::<Action, AccountId, AddressMapping, CurrencyIdMapping>::new(input);
+
+		let action = input.action()?;
+
+		let gas_cost = Self::gas_cost(&action);
+		if let Some(gas_limit) = target_gas {
+			if gas_limit < gas_cost {
+				return Err(ExitError::OutOfGas);
+			}
+		}
+
+		let value: U256 = match action {
+			Action::GetLiquidationRatio => {
+				let currency_id = input.currency_id_at(1)?;
+				let liquidation_ratio = HonzonInfo::liquidation_ratio(currency_id);
+				log::debug!(
+					target: "evm",
+					"honzon: get_liquidation_ratio currency_id: {:?}, liquidation_ratio: {:?}",
+					currency_id, liquidation_ratio
+				);
+
+				liquidation_ratio.into_inner().into()
+			}
+			Action::GetInterestRatePerSec => {
+				let currency_id = input.currency_id_at(1)?;
+				let interest_rate_per_sec = HonzonInfo::interest_rate_per_sec(currency_id);
+				log::debug!(
+					target: "evm",
+					"honzon: get_interest_rate_per_sec currency_id: {:?}, interest_rate_per_sec: {:?}",
+					currency_id, interest_rate_per_sec
+				);
+
+				interest_rate_per_sec.into_inner().into()
+			}
+			Action::GetMaximumTotalDebitValue => {
+				let currency_id = input.currency_id_at(1)?;
+				let maximum_total_debit_value = HonzonInfo::maximum_total_debit_value(currency_id);
+				log::debug!(
+					target: "evm",
+					"honzon: get_maximum_total_debit_value currency_id: {:?}, maximum_total_debit_value: {:?}",
+					currency_id, maximum_total_debit_value
+				);
+
+				maximum_total_debit_value.into()
+			}
+			Action::GetTotalDebitValue => {
+				let currency_id = input.currency_id_at(1)?;
+				let total_debit_value = HonzonInfo::total_debit_value(currency_id);
+				log::debug!(
+					target: "evm",
+					"honzon: get_total_debit_value currency_id: {:?}, total_debit_value: {:?}",
+					currency_id, total_debit_value
+				);
+
+				total_debit_value.into()
+			}
+			Action::GetSystemTotalDebitValue => {
+				let total_debit_value = HonzonInfo::collateral_currency_ids()
+					.into_iter()
+					.map(HonzonInfo::total_debit_value)
+					.fold(Balance::default(), Balance::saturating_add);
+				log::debug!(
+					target: "evm",
+					"honzon: get_system_total_debit_value: {:?}",
+					total_debit_value
+				);
+
+				total_debit_value.into()
+			}
+			Action::IsShutdown => {
+				let is_shutdown = HonzonInfo::is_shutdown();
+				log::debug!(target: "evm", "honzon: is_shutdown: {:?}", is_shutdown);
+
+				if is_shutdown {
+					U256::one()
+				} else {
+					U256::zero()
+				}
+			}
+		};
+
+		// output
+		let mut be_bytes = [0u8; 32];
+		value.to_big_endian(&mut be_bytes[..32]);
+
+		Ok((ExitSucceed::Returned, be_bytes.to_vec(), gas_cost))
+	}
+}
+
+impl<AccountId, AddressMapping, CurrencyIdMapping, HonzonInfo, Runtime>
+	HonzonPrecompile<AccountId, AddressMapping, CurrencyIdMapping, HonzonInfo, Runtime>
+where
+	HonzonInfo: HonzonInfoT<CurrencyId, Balance>,
+	Runtime: frame_system::Config,
+{
+	fn gas_cost(action: &Action) -> u64 {
+		let db_weight = <Runtime as frame_system::Config>::DbWeight::get();
+		let weight = match action {
+			// read the collateral params
+			Action::GetLiquidationRatio | Action::GetMaximumTotalDebitValue => db_weight.reads(1),
+			// read the collateral params and the global interest rate
+			Action::GetInterestRatePerSec => db_weight.reads(2),
+			// read the total positions and the debit exchange rate
+			Action::GetTotalDebitValue => db_weight.reads(2),
+			// read the total positions and the debit exchange rate of each collateral
+			Action::GetSystemTotalDebitValue => {
+				db_weight.reads(2u64.saturating_mul(HonzonInfo::collateral_currency_ids().len() as u64))
+			}
+			// read the shutdown flag
+			Action::IsShutdown => db_weight.reads(1),
+		};
+
+		WeightToGas::convert(weight)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::precompile::mock::get_function_selector;
+
+	#[test]
+	fn function_selector_match() {
+		assert_eq!(
+			u32::from_be_bytes(get_function_selector("getLiquidationRatio(address)")),
+			Into::<u32>::into(Action::GetLiquidationRatio)
+		);
+
+		assert_eq!(
+			u32::from_be_bytes(get_function_selector("getInterestRatePerSec(address)")),
+			Into::<u32>::into(Action::GetInterestRatePerSec)
+		);
+
+		assert_eq!(
+			u32::from_be_bytes(get_function_selector("getMaximumTotalDebitValue(address)")),
+			Into::<u32>::into(Action::GetMaximumTotalDebitValue)
+		);
+
+		assert_eq!(
+			u32::from_be_bytes(get_function_selector("getTotalDebitValue(address)")),
+			Into::<u32>::into(Action::GetTotalDebitValue)
+		);
+
+		assert_eq!(
+			u32::from_be_bytes(get_function_selector("getSystemTotalDebitValue()")),
+			Into::<u32>::into(Action::GetSystemTotalDebitValue)
+		);
+
+		assert_eq!(
+			u32::from_be_bytes(get_function_selector("isShutdown()")),
+			Into::<u32>::into(Action::IsShutdown)
+		);
+	}
+}
@@ -0,0 +1,238 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::input::{Input, InputT};
+use crate::WeightToGas;
+use frame_support::{log, traits::Get};
+use module_evm::{Context, ExitError, ExitSucceed, Precompile};
+use module_incentives::{PoolId, WeightInfo};
+use module_support::{
+	AddressMapping as AddressMappingT, CurrencyIdMapping as CurrencyIdMappingT, DEXIncentives, IncentivesRewards,
+};
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use primitives::{Balance, CurrencyId};
+use sp_core::U256;
+use sp_runtime::traits::Convert;
+use sp_std::{fmt::Debug, marker::PhantomData, prelude::*, result};
+
+/// The `Incentives` impl precompile.
+///
+///
+/// `input` data starts with `action`.
+///
+/// The pool is specified by `pool_type` and `currency_id`, `pool_type` is 0 for the loans
+/// incentive pool, 1 for the dex incentive pool, 2 for the homa incentive pool (`currency_id`
+/// is ignored), and 3 for the dex saving pool.
+///
+/// Actions:
+/// - Stake dex share. Rest `input` bytes: `who`, `lp_currency_id`, `amount`.
+/// - Unstake dex share. Rest `input` bytes: `who`, `lp_currency_id`, `amount`.
+/// - Claim rewards. Rest `input` bytes: `who`, `pool_type`, `currency_id`.
+/// - Get pending rewards. Rest `input` bytes: `who`, `pool_type`, `currency_id`,
+///   `reward_currency_id`.
+///
+/// The used gas is converted from the weight of the corresponding `module_incentives` call.
+pub struct IncentivesPrecompile<AccountId, AddressMapping, CurrencyIdMapping, Incentives, IncentivesWeightInfo, Runtime>(
+	PhantomData<(
+		AccountId,
+		AddressMapping,
+		CurrencyIdMapping,
+		Incentives,
+		IncentivesWeightInfo,
+		Runtime,
+	)>,
+);
+
+#[derive(Debug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
+#[repr(u32)]
+pub enum Action {
+	Stake = 0xbf6eac2f,
+	Unstake = 0x60829f8a,
+	ClaimRewards = 0x46016506,
+	PendingRewards = 0xaf90c473,
+}
+
+impl<AccountId, AddressMapping, CurrencyIdMapping, Incentives, IncentivesWeightInfo, Runtime> Precompile
+	for IncentivesPrecompile<AccountId, AddressMapping, CurrencyIdMapping, Incentives, IncentivesWeightInfo, Runtime>
+where
+	AccountId: Debug + Clone,
+	AddressMapping: AddressMappingT<AccountId>,
+	CurrencyIdMapping: CurrencyIdMappingT,
+	Incentives: DEXIncentives<AccountId, CurrencyId, Balance>
+		+ IncentivesRewards<AccountId, PoolId<AccountId>, CurrencyId, Balance>,
+	IncentivesWeightInfo: WeightInfo,
+	Runtime: frame_system::Config,
+{
+	fn execute(
+		input: &[u8],
+		target_gas: Option<u64>,
+		_context: &Context,
+	) -> result::Result<(ExitSucceed, Vec<u8>, u64), ExitError> {
+		log::debug!(target: "evm", "incentives: input: {:?}", input);
+
+		let input = Input::<Action, AccountId, AddressMapping, CurrencyIdMapping>::new(input);
+
+		let action = input.action()?;
+
+		let gas_cost = Self::gas_cost(&action);
+		if let Some(gas_limit) = target_gas {
+			if gas_limit < gas_cost {
+				return Err(ExitError::OutOfGas);
+			}
+		}
+
+		match action {
+			Action::Stake => {
+				let who = input.account_id_at(1)?;
+				let lp_currency_id = input.currency_id_at(2)?;
+				let amount = input.balance_at(3)?;
+				log::debug!(
+					target: "evm",
+					"incentives: stake who: {:?}, lp_currency_id: {:?}, amount: {:?}",
+					who, lp_currency_id, amount
+				);
+
+				Incentives::do_deposit_dex_share(&who, lp_currency_id, amount).map_err(|e| {
+					let err_msg: &str = e.into();
+					ExitError::Other(err_msg.into())
+				})?;
+
+				Ok((ExitSucceed::Returned, vec![], gas_cost))
+			}
+			Action::Unstake => {
+				let who = input.account_id_at(1)?;
+				let lp_currency_id = input.currency_id_at(2)?;
+				let amount = input.balance_at(3)?;
+				log::debug!(
+					target: "evm",
+					"incentives: unstake who: {:?}, lp_currency_id: {:?}, amount: {:?}",
+					who, lp_currency_id, amount
+				);
+
+				Incentives::do_withdraw_dex_share(&who, lp_currency_id, amount).map_err(|e| {
+					let err_msg: &str = e.into();
+					ExitError::Other(err_msg.into())
+				})?;
+
+				Ok((ExitSucceed::Returned, vec![], gas_cost))
+			}
+			Action::ClaimRewards => {
+				let who = input.account_id_at(1)?;
+				let pool_id = pool_id_at(&input, 2)?;
+				log::debug!(
+					target: "evm",
+					"incentives: claim_rewards who: {:?}, pool_id: {:?}",
+					who, pool_id
+				);
+
+				Incentives::claim_rewards(&who, &pool_id);
+
+				Ok((ExitSucceed::Returned, vec![], gas_cost))
+			}
+			Action::PendingRewards => {
+				let who = input.account_id_at(1)?;
+				let pool_id = pool_id_at(&input, 2)?;
+				let reward_currency_id = input.currency_id_at(4)?;
+				log::debug!(
+					target: "evm",
+					"incentives: pending_rewards who: {:?}, pool_id: {:?}, reward_currency_id: {:?}",
+					who, pool_id, reward_currency_id
+				);
+
+				let value = Incentives::pending_rewards(&who, &pool_id)
+					.into_iter()
+					.find(|(currency_id, _)| *currency_id == reward_currency_id)
+					.map_or(Default::default(), |(_, reward)| reward);
+
+				// output
+				let mut be_bytes = [0u8; 32];
+				U256::from(value).to_big_endian(&mut be_bytes[..32]);
+
+				Ok((ExitSucceed::Returned, be_bytes.to_vec(), gas_cost))
+			}
+		}
+	}
+}
+
+impl<AccountId, AddressMapping, CurrencyIdMapping, Incentives, IncentivesWeightInfo, Runtime>
+	IncentivesPrecompile<AccountId, AddressMapping, CurrencyIdMapping, Incentives, IncentivesWeightInfo, Runtime>
+where
+	IncentivesWeightInfo: WeightInfo,
+	Runtime: frame_system::Config,
+{
+	fn gas_cost(action: &Action) -> u64 {
+		let db_weight = <Runtime as frame_system::Config>::DbWeight::get();
+		let weight = match action {
+			Action::Stake => IncentivesWeightInfo::deposit_dex_share(),
+			Action::Unstake => IncentivesWeightInfo::withdraw_dex_share(),
+			Action::ClaimRewards => IncentivesWeightInfo::claim_rewards(),
+			// read the share, the pool, the extra reward pools and the deduction rate of the pool
+			Action::PendingRewards => db_weight.reads(6),
+		};
+
+		WeightToGas::convert(weight)
+	}
+}
+
+/// Decode the pool at `index` of `input`, from the `pool_type` and the `currency_id`.
+fn pool_id_at<AccountId, AddressMapping, CurrencyIdMapping>(
+	input: &Input<'_, Action, AccountId, AddressMapping, CurrencyIdMapping>,
+	index: usize,
+) -> result::Result<PoolId<AccountId>, ExitError>
+where
+	AddressMapping: AddressMappingT<AccountId>,
+	CurrencyIdMapping: CurrencyIdMappingT,
+{
+	let pool_type = input.u32_at(index)?;
+	match pool_type {
+		0 => Ok(PoolId::LoansIncentive(input.currency_id_at(index + 1)?)),
+		1 => Ok(PoolId::DexIncentive(input.currency_id_at(index + 1)?)),
+		2 => Ok(PoolId::HomaIncentive),
+		3 => Ok(PoolId::DexSaving(input.currency_id_at(index + 1)?)),
+		_ => Err(ExitError::Other("invalid pool type".into())),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::precompile::mock::get_function_selector;
+
+	#[test]
+	fn function_selector_match() {
+		assert_eq!(
+			u32::from_be_bytes(get_function_selector("stake(address,address,uint256)")),
+			Into::<u32>::into(Action::Stake)
+		);
+
+		assert_eq!(
+			u32::from_be_bytes(get_function_selector("unstake(address,address,uint256)")),
+			Into::<u32>::into(Action::Unstake)
+		);
+
+		assert_eq!(
+			u32::from_be_bytes(get_function_selector("claimRewards(address,uint256,address)")),
+			Into::<u32>::into(Action::ClaimRewards)
+		);
+
+		assert_eq!(
+			u32::from_be_bytes(get_function_selector("pendingRewards(address,uint256,address,address)")),
+			Into::<u32>::into(Action::PendingRewards)
+		);
+	}
+}
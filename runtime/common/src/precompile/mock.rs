@@ -28,19 +28,21 @@ use frame_support::{
 	PalletId, RuntimeDebug,
 };
 use frame_system::{EnsureRoot, EnsureSignedBy};
+use module_incentives::PoolId;
 use module_support::{
 	mocks::MockAddressMapping, AddressMapping as AddressMappingT, DEXIncentives, ExchangeRate, ExchangeRateProvider,
+	HomaProtocol, HonzonInfo, IncentivesRewards, Rate,
 };
-use orml_traits::{parameter_type_with_key, MultiReservableCurrency};
+use orml_traits::{parameter_type_with_key, MultiCurrency, MultiReservableCurrency};
 pub use primitives::{
-	evm::EvmAddress, Amount, BlockNumber, CurrencyId, DexShare, Header, Nonce, ReserveIdentifier, TokenSymbol,
-	TradingPair,
+	evm::EvmAddress, Amount, BlockNumber, CurrencyId, DexShare, EraIndex, Header, Nonce, ReserveIdentifier,
+	TokenSymbol, TradingPair,
 };
 use sha3::{Digest, Keccak256};
 use sp_core::{crypto::AccountId32, H160, H256};
 use sp_runtime::{
-	traits::{BlakeTwo256, Convert, IdentityLookup, One as OneT},
-	DispatchResult, FixedPointNumber, FixedU128, Perbill,
+	traits::{BlakeTwo256, Convert, IdentityLookup, One as OneT, Verify},
+	DispatchError, DispatchResult, FixedPointNumber, FixedU128, MultiSignature, Perbill,
 };
 use sp_std::{
 	collections::btree_map::BTreeMap,
@@ -164,6 +166,11 @@ parameter_types! {
 	pub const GetNativeCurrencyId: CurrencyId = ACA;
 }
 
+parameter_types! {
+	pub const MaxMemoLength: u32 = 32;
+	pub const MaxBatchTransfers: u32 = 4;
+}
+
 impl module_currencies::Config for Test {
 	type Event = Event;
 	type MultiCurrency = Tokens;
@@ -172,6 +179,10 @@ impl module_currencies::Config for Test {
 	type WeightInfo = ();
 	type AddressMapping = MockAddressMapping;
 	type EVMBridge = EVMBridge;
+	type UpdateOrigin = EnsureRoot<AccountId>;
+	type MaxMemoLength = MaxMemoLength;
+	type MaxBatchTransfers = MaxBatchTransfers;
+	type FreezeChecker = ();
 }
 
 impl module_evm_bridge::Config for Test {
@@ -179,8 +190,11 @@ impl module_evm_bridge::Config for Test {
 }
 
 impl module_evm_manager::Config for Test {
+	type Event = Event;
 	type Currency = Balances;
 	type EVMBridge = EVMBridge;
+	type RegisterOrigin = EnsureSignedBy<CouncilAccount, AccountId>;
+	type AssetRegistry = ();
 }
 
 parameter_types! {
@@ -188,6 +202,8 @@ parameter_types! {
 	pub const CreateTokenDeposit: Balance = 100;
 	pub const DataDepositPerByte: Balance = 10;
 	pub const NftPalletId: PalletId = PalletId(*b"aca/aNFT");
+	pub const MaxAttributeKeyLength: u32 = 64;
+	pub const MaxAttributeValueLength: u32 = 1024;
 }
 impl module_nft::Config for Test {
 	type Event = Event;
@@ -196,6 +212,10 @@ impl module_nft::Config for Test {
 	type CreateTokenDeposit = CreateTokenDeposit;
 	type DataDepositPerByte = DataDepositPerByte;
 	type PalletId = NftPalletId;
+	type MaxAttributeKeyLength = MaxAttributeKeyLength;
+	type MaxAttributeValueLength = MaxAttributeValueLength;
+	type UnixTime = Timestamp;
+	type OnNftTransfer = ();
 	type WeightInfo = ();
 }
 
@@ -218,9 +238,12 @@ parameter_types! {
 	pub const GetStableCurrencyId: CurrencyId = CurrencyId::Token(TokenSymbol::AUSD);
 	pub AllNonNativeCurrencyIds: Vec<CurrencyId> = vec![CurrencyId::Token(TokenSymbol::AUSD)];
 	pub MaxSlippageSwapWithDEX: Ratio = Ratio::one();
+	pub const FeeTankWithdrawalDelay: BlockNumber = 10;
 }
 
 impl module_transaction_payment::Config for Test {
+	type Event = Event;
+	type Call = Call;
 	type AllNonNativeCurrencyIds = AllNonNativeCurrencyIds;
 	type NativeCurrencyId = GetNativeCurrencyId;
 	type StableCurrencyId = GetStableCurrencyId;
@@ -232,6 +255,12 @@ impl module_transaction_payment::Config for Test {
 	type FeeMultiplierUpdate = ();
 	type DEX = ();
 	type MaxSlippageSwapWithDEX = MaxSlippageSwapWithDEX;
+	type OnFeePaid = ();
+	type SponsorCallClass = ProxyType;
+	type SponsorPublic = <MultiSignature as Verify>::Signer;
+	type SponsorSignature = MultiSignature;
+	type UpdateOrigin = EnsureRoot<AccountId>;
+	type FeeTankWithdrawalDelay = FeeTankWithdrawalDelay;
 	type WeightInfo = ();
 }
 pub type ChargeTransactionPayment = module_transaction_payment::ChargeTransactionPayment<Test>;
@@ -318,6 +347,58 @@ impl DEXIncentives<AccountId, CurrencyId, Balance> for MockDEXIncentives {
 	}
 }
 
+/// The pending rewards of the dex incentive pool are the staked dex share in AUSD, and paid out
+/// on claim.
+impl IncentivesRewards<AccountId, PoolId<AccountId>, CurrencyId, Balance> for MockDEXIncentives {
+	fn claim_rewards(who: &AccountId, pool_id: &PoolId<AccountId>) {
+		for (currency_id, reward) in Self::pending_rewards(who, pool_id) {
+			assert_ok!(Tokens::deposit(currency_id, who, reward));
+		}
+	}
+
+	fn pending_rewards(who: &AccountId, pool_id: &PoolId<AccountId>) -> Vec<(CurrencyId, Balance)> {
+		match pool_id {
+			PoolId::DexIncentive(lp_currency_id) => vec![(AUSD, Tokens::reserved_balance(*lp_currency_id, who))],
+			_ => vec![],
+		}
+	}
+}
+
+/// DOT and RENBTC are the collaterals, whose debits are fixed.
+pub struct MockHonzonInfo;
+impl HonzonInfo<CurrencyId, Balance> for MockHonzonInfo {
+	fn liquidation_ratio(_currency_id: CurrencyId) -> Ratio {
+		Ratio::saturating_from_rational(3, 2)
+	}
+
+	fn interest_rate_per_sec(_currency_id: CurrencyId) -> Rate {
+		Rate::saturating_from_rational(1, 10000)
+	}
+
+	fn maximum_total_debit_value(currency_id: CurrencyId) -> Balance {
+		match currency_id {
+			DOT | RENBTC => 10_000,
+			_ => 0,
+		}
+	}
+
+	fn total_debit_value(currency_id: CurrencyId) -> Balance {
+		match currency_id {
+			DOT => 1_000,
+			RENBTC => 500,
+			_ => 0,
+		}
+	}
+
+	fn collateral_currency_ids() -> Vec<CurrencyId> {
+		vec![DOT, RENBTC]
+	}
+
+	fn is_shutdown() -> bool {
+		false
+	}
+}
+
 ord_parameter_types! {
 	pub const ListingOrigin: AccountId = ALICE;
 }
@@ -338,6 +419,11 @@ impl module_dex::Config for Test {
 	type WeightInfo = ();
 	type DEXIncentives = MockDEXIncentives;
 	type ListingOrigin = EnsureSignedBy<ListingOrigin, AccountId>;
+	type OnSwap = ();
+	type EventTopics = ();
+	type SwapFeeShare = ();
+	type SwapFeeCurrencyId = GetNativeCurrencyId;
+	type SwapFeeReceiver = ();
 }
 
 pub type AdaptedBasicCurrency = module_currencies::BasicCurrencyAdapter<Test, Balances, Amount, BlockNumber>;
@@ -349,7 +435,8 @@ pub type MultiCurrencyPrecompile =
 pub type NFTPrecompile = crate::NFTPrecompile<AccountId, MockAddressMapping, EvmCurrencyIdMapping, NFTModule>;
 pub type StateRentPrecompile =
 	crate::StateRentPrecompile<AccountId, MockAddressMapping, EvmCurrencyIdMapping, ModuleEVM>;
-pub type OraclePrecompile = crate::OraclePrecompile<AccountId, MockAddressMapping, EvmCurrencyIdMapping, Prices>;
+pub type OraclePrecompile =
+	crate::OraclePrecompile<AccountId, MockAddressMapping, EvmCurrencyIdMapping, Prices, Oracle>;
 pub type ScheduleCallPrecompile = crate::ScheduleCallPrecompile<
 	AccountId,
 	MockAddressMapping,
@@ -361,7 +448,22 @@ pub type ScheduleCallPrecompile = crate::ScheduleCallPrecompile<
 	OriginCaller,
 	Test,
 >;
-pub type DexPrecompile = crate::DexPrecompile<AccountId, MockAddressMapping, EvmCurrencyIdMapping, DexModule>;
+pub type DexPrecompile = crate::DexPrecompile<AccountId, MockAddressMapping, EvmCurrencyIdMapping, DexModule, Test>;
+pub type IncentivesPrecompile =
+	crate::IncentivesPrecompile<AccountId, MockAddressMapping, EvmCurrencyIdMapping, MockDEXIncentives, (), Test>;
+pub type HonzonPrecompile =
+	crate::HonzonPrecompile<AccountId, MockAddressMapping, EvmCurrencyIdMapping, MockHonzonInfo, Test>;
+pub type MulticallPrecompile = crate::MulticallPrecompile<AccountId, MockAddressMapping, EvmCurrencyIdMapping, Test>;
+pub type ERC721Precompile = crate::ERC721Precompile<AccountId, MockAddressMapping, EvmCurrencyIdMapping, Test>;
+pub type HomaPrecompile = crate::HomaPrecompile<
+	AccountId,
+	MockAddressMapping,
+	EvmCurrencyIdMapping,
+	MockHoma,
+	MockLiquidStakingExchangeProvider,
+	(),
+	Test,
+>;
 
 parameter_types! {
 	pub NetworkContractSource: H160 = alice_evm_addr();
@@ -375,8 +477,10 @@ ord_parameter_types! {
 	pub const StorageDepositPerByte: u64 = 10;
 	pub const DeveloperDeposit: u64 = 1000;
 	pub const DeploymentFee: u64 = 200;
+	pub const EvictionNoticePeriod: BlockNumber = 10;
 	pub const MaxCodeSize: u32 = 60 * 1024;
 	pub const ChainId: u64 = 1;
+	pub const BlockGasTarget: u64 = 1_000_000;
 }
 
 pub struct GasToWeight;
@@ -402,9 +506,16 @@ impl module_evm::Config for Test {
 		OraclePrecompile,
 		ScheduleCallPrecompile,
 		DexPrecompile,
+		HomaPrecompile,
+		IncentivesPrecompile,
+		HonzonPrecompile,
+		MulticallPrecompile,
+		ERC721Precompile,
 	>;
 	type ChainId = ChainId;
 	type GasToWeight = GasToWeight;
+	type BlockGasTarget = BlockGasTarget;
+	type AccessListGasMetering = ();
 	type ChargeTransactionPayment = ChargeTransactionPayment;
 	type NetworkContractOrigin = EnsureSignedBy<NetworkContractAccount, AccountId>;
 	type NetworkContractSource = NetworkContractSource;
@@ -412,6 +523,9 @@ impl module_evm::Config for Test {
 	type DeploymentFee = DeploymentFee;
 	type TreasuryAccount = TreasuryAccount;
 	type FreeDeploymentOrigin = EnsureSignedBy<CouncilAccount, AccountId>;
+	type IdleScheduler = ();
+	type ContractFilter = ();
+	type EvictionNoticePeriod = EvictionNoticePeriod;
 	type WeightInfo = ();
 }
 
@@ -422,6 +536,50 @@ impl ExchangeRateProvider for MockLiquidStakingExchangeProvider {
 	}
 }
 
+/// Mints LDOT at the exchange rate of `MockLiquidStakingExchangeProvider`, and burns LDOT on
+/// redemption.
+pub struct MockHoma;
+impl HomaProtocol<AccountId, Balance, EraIndex> for MockHoma {
+	type Balance = Balance;
+
+	fn mint(who: &AccountId, amount: Balance) -> sp_std::result::Result<Balance, DispatchError> {
+		let liquid_amount = MockLiquidStakingExchangeProvider::get_exchange_rate()
+			.reciprocal()
+			.unwrap_or_default()
+			.saturating_mul_int(amount);
+		Currencies::withdraw(DOT, who, amount)?;
+		Currencies::deposit(LDOT, who, liquid_amount)?;
+		Ok(liquid_amount)
+	}
+	fn mint_at_next_era(who: &AccountId, amount: Balance) -> DispatchResult {
+		<() as HomaProtocol<AccountId, Balance, EraIndex>>::mint_at_next_era(who, amount)
+	}
+	fn redeem_by_unbond(who: &AccountId, amount: Balance) -> DispatchResult {
+		Currencies::withdraw(LDOT, who, amount)
+	}
+	fn redeem_by_free_unbonded(who: &AccountId, amount: Balance) -> DispatchResult {
+		<() as HomaProtocol<AccountId, Balance, EraIndex>>::redeem_by_free_unbonded(who, amount)
+	}
+	fn redeem_by_claim_unbonding(who: &AccountId, amount: Balance, target_era: EraIndex) -> DispatchResult {
+		<() as HomaProtocol<AccountId, Balance, EraIndex>>::redeem_by_claim_unbonding(who, amount, target_era)
+	}
+	fn withdraw_redemption(who: &AccountId) -> sp_std::result::Result<Balance, DispatchError> {
+		<() as HomaProtocol<AccountId, Balance, EraIndex>>::withdraw_redemption(who)
+	}
+	fn take_unbonding(who: &AccountId, target_era: EraIndex) -> Balance {
+		<() as HomaProtocol<AccountId, Balance, EraIndex>>::take_unbonding(who, target_era)
+	}
+	fn add_unbonding(who: &AccountId, target_era: EraIndex, amount: Balance) {
+		<() as HomaProtocol<AccountId, Balance, EraIndex>>::add_unbonding(who, target_era, amount)
+	}
+	fn update_queued_request(who: &AccountId, index: u32, new_amount: Balance) -> DispatchResult {
+		<() as HomaProtocol<AccountId, Balance, EraIndex>>::update_queued_request(who, index, new_amount)
+	}
+	fn cancel_redeem_by_unbond(who: &AccountId, amount: Balance) -> DispatchResult {
+		<() as HomaProtocol<AccountId, Balance, EraIndex>>::cancel_redeem_by_unbond(who, amount)
+	}
+}
+
 parameter_types! {
 	pub StableCurrencyFixedPrice: Price = Price::saturating_from_rational(1, 1);
 	pub const GetStakingCurrencyId: CurrencyId = DOT;
@@ -503,11 +661,12 @@ frame_support::construct_runtime!(
 		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
 		Tokens: orml_tokens::{Pallet, Storage, Event<T>, Config<T>},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
-		Currencies: module_currencies::{Pallet, Call, Event<T>},
+		Currencies: module_currencies::{Pallet, Call, Storage, Event<T>},
 		EVMBridge: module_evm_bridge::{Pallet},
-		EVMManager: module_evm_manager::{Pallet, Storage},
+		EVMManager: module_evm_manager::{Pallet, Call, Storage, Event<T>},
+		OrmlNFT: orml_nft::{Pallet, Storage, Config<T>},
 		NFTModule: module_nft::{Pallet, Call, Event<T>},
-		TransactionPayment: module_transaction_payment::{Pallet, Call, Storage},
+		TransactionPayment: module_transaction_payment::{Pallet, Call, Storage, Event<T>},
 		Prices: module_prices::{Pallet, Storage, Call, Event<T>},
 		Proxy: pallet_proxy::{Pallet, Call, Storage, Event<T>},
 		Utility: pallet_utility::{Pallet, Call, Event},
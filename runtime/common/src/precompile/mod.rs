@@ -18,13 +18,14 @@
 
 //! The precompiles for EVM, includes standard Ethereum precompiles, and more:
 //! - MultiCurrency at address `H160::from_low_u64_be(1024)`.
+//! - ERC-721 mirror of the NFT class `class_id` at address `nft_class_address(class_id)`.
 
 #![allow(clippy::upper_case_acronyms)]
 
 mod mock;
 mod tests;
 
-use crate::is_acala_precompile;
+use crate::{is_acala_precompile, nft_class_id_of};
 use frame_support::log;
 use module_evm::{
 	precompiles::{
@@ -39,7 +40,12 @@ use sp_core::H160;
 use sp_std::{marker::PhantomData, prelude::*};
 
 pub mod dex;
+pub mod erc721;
+pub mod homa;
+pub mod honzon;
+pub mod incentives;
 pub mod input;
+pub mod multicall;
 pub mod multicurrency;
 pub mod nft;
 pub mod oracle;
@@ -47,6 +53,11 @@ pub mod schedule_call;
 pub mod state_rent;
 
 pub use dex::DexPrecompile;
+pub use erc721::ERC721Precompile;
+pub use homa::HomaPrecompile;
+pub use honzon::HonzonPrecompile;
+pub use incentives::IncentivesPrecompile;
+pub use multicall::MulticallPrecompile;
 pub use multicurrency::MultiCurrencyPrecompile;
 pub use nft::NFTPrecompile;
 pub use oracle::OraclePrecompile;
@@ -61,6 +72,11 @@ pub struct AllPrecompiles<
 	OraclePrecompile,
 	ScheduleCallPrecompile,
 	DexPrecompile,
+	HomaPrecompile,
+	IncentivesPrecompile,
+	HonzonPrecompile,
+	MulticallPrecompile,
+	ERC721Precompile,
 >(
 	PhantomData<(
 		PrecompileCallerFilter,
@@ -70,6 +86,11 @@ pub struct AllPrecompiles<
 		OraclePrecompile,
 		ScheduleCallPrecompile,
 		DexPrecompile,
+		HomaPrecompile,
+		IncentivesPrecompile,
+		HonzonPrecompile,
+		MulticallPrecompile,
+		ERC721Precompile,
 	)>,
 );
 
@@ -81,6 +102,11 @@ impl<
 		OraclePrecompile,
 		ScheduleCallPrecompile,
 		DexPrecompile,
+		HomaPrecompile,
+		IncentivesPrecompile,
+		HonzonPrecompile,
+		MulticallPrecompile,
+		ERC721Precompile,
 	> Precompiles
 	for AllPrecompiles<
 		PrecompileCallerFilter,
@@ -90,6 +116,11 @@ impl<
 		OraclePrecompile,
 		ScheduleCallPrecompile,
 		DexPrecompile,
+		HomaPrecompile,
+		IncentivesPrecompile,
+		HonzonPrecompile,
+		MulticallPrecompile,
+		ERC721Precompile,
 	> where
 	MultiCurrencyPrecompile: Precompile,
 	NFTPrecompile: Precompile,
@@ -98,6 +129,11 @@ impl<
 	ScheduleCallPrecompile: Precompile,
 	PrecompileCallerFilter: PrecompileCallerFilterT,
 	DexPrecompile: Precompile,
+	HomaPrecompile: Precompile,
+	IncentivesPrecompile: Precompile,
+	HonzonPrecompile: Precompile,
+	MulticallPrecompile: Precompile,
+	ERC721Precompile: Precompile,
 {
 	#[allow(clippy::type_complexity)]
 	fn execute(
@@ -127,6 +163,16 @@ impl<
 				Some(ScheduleCallPrecompile::execute(input, target_gas, context))
 			} else if address == H160::from_low_u64_be(PRECOMPILE_ADDRESS_START + 5) {
 				Some(DexPrecompile::execute(input, target_gas, context))
+			} else if address == H160::from_low_u64_be(PRECOMPILE_ADDRESS_START + 6) {
+				Some(HomaPrecompile::execute(input, target_gas, context))
+			} else if address == H160::from_low_u64_be(PRECOMPILE_ADDRESS_START + 7) {
+				Some(IncentivesPrecompile::execute(input, target_gas, context))
+			} else if address == H160::from_low_u64_be(PRECOMPILE_ADDRESS_START + 8) {
+				Some(HonzonPrecompile::execute(input, target_gas, context))
+			} else if address == H160::from_low_u64_be(PRECOMPILE_ADDRESS_START + 9) {
+				Some(MulticallPrecompile::execute(input, target_gas, context))
+			} else if nft_class_id_of(address).is_some() {
+				Some(ERC721Precompile::execute(input, target_gas, context))
 			} else {
 				None
 			}
@@ -0,0 +1,256 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::input::{Input, InputT, PER_PARAM_BYTES};
+use frame_support::{
+	log,
+	traits::{Get, IsType},
+};
+use module_evm::{BalanceOf, Context, EVMTrait, ExitError, ExitSucceed, InvokeContext, Precompile};
+use module_support::{AddressMapping as AddressMappingT, CurrencyIdMapping as CurrencyIdMappingT, ExecutionMode};
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use primitives::{evm::EvmAddress, Balance};
+use sp_core::U256;
+use sp_runtime::{traits::UniqueSaturatedInto, TransactionOutcome};
+use sp_std::{fmt::Debug, marker::PhantomData, prelude::*, result};
+
+/// The `Multicall` impl precompile.
+///
+///
+/// `input` data starts with `action`.
+///
+/// Actions:
+/// - Multicall. Rest `input` bytes: `who`, `storage_limit`, `calls_len`, `calls`. Each call of
+///   `calls` is encoded as `target`, `value`, `allow_failure`, `input_len`, `input_data` padded
+///   to 32 bytes.
+///
+/// The calls are executed in order as if they're called by `who`, sharing the gas limit and the
+/// `storage_limit`. If a call without `allow_failure` fails, all the calls are reverted.
+/// Otherwise the output is `count`, then for each call `success`, `output_len` and `output`
+/// padded to 32 bytes.
+pub struct MulticallPrecompile<AccountId, AddressMapping, CurrencyIdMapping, Runtime>(
+	PhantomData<(AccountId, AddressMapping, CurrencyIdMapping, Runtime)>,
+);
+
+#[derive(Debug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
+#[repr(u32)]
+pub enum Action {
+	Multicall = 0xf6e58761,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+struct Call {
+	target: EvmAddress,
+	value: Balance,
+	allow_failure: bool,
+	input: Vec<u8>,
+}
+
+impl<AccountId, AddressMapping, CurrencyIdMapping, Runtime> Precompile
+	for MulticallPrecompile<AccountId, AddressMapping, CurrencyIdMapping, Runtime>
+where
+	AccountId: Debug + Clone + Default,
+	AddressMapping: AddressMappingT<AccountId>,
+	CurrencyIdMapping: CurrencyIdMappingT,
+	Runtime: module_evm::Config + frame_system::Config<AccountId = AccountId>,
+	BalanceOf<Runtime>: IsType<Balance>,
+{
+	fn execute(
+		input: &[u8],
+		target_gas: Option<u64>,
+		_context: &Context,
+	) -> result::Result<(ExitSucceed, Vec<u8>, u64), ExitError> {
+		log::debug!(target: "evm", "multicall: input: {:?}", input);
+
+		let input = Input::<Action, AccountId, AddressMapping, CurrencyIdMapping>::new(input);
+
+		let action = input.action()?;
+
+		match action {
+			Action::Multicall => {
+				let who = input.evm_address_at(1)?;
+				let storage_limit = input.u32_at(2)?;
+				// solidity abi enocde bytes will add an length at input[3]
+				let calls_len = input.u32_at(4)?;
+				let calls = decode_calls(&input, 5, calls_len as usize)?;
+
+				log::debug!(
+					target: "evm",
+					"multicall: who: {:?}, storage_limit: {:?}, calls: {:?}",
+					who, storage_limit, calls
+				);
+
+				// without a gas limit, the calls share the gas limit of a block
+				let gas_limit = target_gas.unwrap_or_else(|| {
+					<Runtime as module_evm::Config>::BlockGasTarget::get()
+						.saturating_mul(module_evm::ELASTICITY_MULTIPLIER)
+				});
+
+				let (output, used_gas) = frame_support::storage::with_transaction(|| {
+					match Self::execute_calls(who, calls, gas_limit, storage_limit) {
+						Ok(r) => TransactionOutcome::Commit(Ok(r)),
+						Err(e) => TransactionOutcome::Rollback(Err(e)),
+					}
+				})?;
+
+				Ok((ExitSucceed::Returned, output, used_gas))
+			}
+		}
+	}
+}
+
+impl<AccountId, AddressMapping, CurrencyIdMapping, Runtime>
+	MulticallPrecompile<AccountId, AddressMapping, CurrencyIdMapping, Runtime>
+where
+	AccountId: Default,
+	AddressMapping: AddressMappingT<AccountId>,
+	Runtime: module_evm::Config + frame_system::Config<AccountId = AccountId>,
+	BalanceOf<Runtime>: IsType<Balance>,
+{
+	/// Execute `calls` in order, returns the aggregated output and the used gas.
+	fn execute_calls(
+		who: EvmAddress,
+		calls: Vec<Call>,
+		gas_limit: u64,
+		storage_limit: u32,
+	) -> result::Result<(Vec<u8>, u64), ExitError> {
+		let origin = <module_evm::Pallet<Runtime> as EVMTrait<AccountId>>::get_origin().unwrap_or_default();
+		let origin_address = AddressMapping::get_or_create_evm_address(&origin);
+
+		let mut output = vec_u8_from_u256(calls.len().into());
+		let mut used_gas: u64 = 0;
+		let mut used_storage: i32 = 0;
+
+		for (index, call) in calls.into_iter().enumerate() {
+			let remaining_gas = gas_limit.saturating_sub(used_gas);
+			if remaining_gas == 0 {
+				return Err(ExitError::OutOfGas);
+			}
+			let remaining_storage = (storage_limit as i64).saturating_sub(used_storage as i64).max(0) as u32;
+
+			let result = <module_evm::Pallet<Runtime> as EVMTrait<AccountId>>::execute(
+				InvokeContext {
+					contract: call.target,
+					sender: who,
+					origin: origin_address,
+				},
+				call.input,
+				call.value.into(),
+				remaining_gas,
+				remaining_storage,
+				ExecutionMode::Execute,
+			);
+			log::debug!(target: "evm", "multicall: call {:?} result: {:?}", index, result);
+
+			// the changes of a failed call are already reverted
+			let (success, call_output) = match result {
+				Ok(info) => {
+					used_gas = used_gas.saturating_add(info.used_gas.unique_saturated_into());
+					if info.exit_reason.is_succeed() {
+						used_storage = used_storage.saturating_add(info.used_storage);
+						(true, info.output)
+					} else if call.allow_failure {
+						(false, info.output)
+					} else {
+						return Err(ExitError::Other("multicall failed".into()));
+					}
+				}
+				Err(_) if call.allow_failure => (false, vec![]),
+				Err(e) => {
+					let err_msg: &str = e.into();
+					return Err(ExitError::Other(err_msg.into()));
+				}
+			};
+
+			output.extend_from_slice(&vec_u8_from_u256(if success { U256::one() } else { U256::zero() }));
+			output.extend_from_slice(&vec_u8_from_u256(call_output.len().into()));
+			output.extend_from_slice(&padded(call_output));
+		}
+
+		if used_gas > gas_limit {
+			return Err(ExitError::OutOfGas);
+		}
+
+		Ok((output, used_gas))
+	}
+}
+
+/// Decode the calls encoded in the `len` bytes starting at the param `index`.
+fn decode_calls<AccountId, AddressMapping, CurrencyIdMapping>(
+	input: &Input<'_, Action, AccountId, AddressMapping, CurrencyIdMapping>,
+	mut index: usize,
+	len: usize,
+) -> result::Result<Vec<Call>, ExitError>
+where
+	AddressMapping: AddressMappingT<AccountId>,
+	CurrencyIdMapping: CurrencyIdMappingT,
+{
+	let mut calls = vec![];
+	let mut decoded_len = 0;
+	while decoded_len < len {
+		let target = input.evm_address_at(index)?;
+		let value = input.balance_at(index + 1)?;
+		let allow_failure = input.u32_at(index + 2)? != 0;
+		let input_len = input.u32_at(index + 3)? as usize;
+		let input_data = input.bytes_at(index + 4, input_len)?;
+
+		let params = 4 + (input_len + PER_PARAM_BYTES - 1) / PER_PARAM_BYTES;
+		index += params;
+		decoded_len += params * PER_PARAM_BYTES;
+
+		calls.push(Call {
+			target,
+			value,
+			allow_failure,
+			input: input_data,
+		});
+	}
+
+	if decoded_len != len {
+		return Err(ExitError::Other("invalid input".into()));
+	}
+
+	Ok(calls)
+}
+
+fn vec_u8_from_u256(value: U256) -> Vec<u8> {
+	let mut be_bytes = [0u8; 32];
+	value.to_big_endian(&mut be_bytes[..32]);
+	be_bytes.to_vec()
+}
+
+/// Pad `bytes` with zeros to the multiple of 32 bytes.
+fn padded(mut bytes: Vec<u8>) -> Vec<u8> {
+	let padded_len = (bytes.len() + PER_PARAM_BYTES - 1) / PER_PARAM_BYTES * PER_PARAM_BYTES;
+	bytes.resize(padded_len, 0);
+	bytes
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::precompile::mock::get_function_selector;
+
+	#[test]
+	fn function_selector_match() {
+		assert_eq!(
+			u32::from_be_bytes(get_function_selector("multicall(address,uint256,bytes)")),
+			Into::<u32>::into(Action::Multicall)
+		);
+	}
+}
@@ -16,15 +16,25 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use frame_support::log;
+use crate::WeightToGas;
+use frame_support::{
+	ensure, log,
+	traits::Get,
+	transactional,
+	weights::{constants::RocksDbWeight, Weight},
+};
 use module_evm::{Context, ExitError, ExitSucceed, Precompile};
-use module_support::{AddressMapping as AddressMappingT, CurrencyIdMapping as CurrencyIdMappingT};
+use module_support::{
+	AddressMapping as AddressMappingT, CurrencyIdMapping as CurrencyIdMappingT,
+	MultiCurrencyAllowance as MultiCurrencyAllowanceT,
+};
 use sp_core::U256;
+use sp_runtime::{traits::Convert, DispatchResult};
 use sp_std::{fmt::Debug, marker::PhantomData, prelude::*, result};
 
 use orml_traits::MultiCurrency as MultiCurrencyT;
 
-use super::input::{Input, InputT};
+use super::input::{Input, InputT, PER_PARAM_BYTES};
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use primitives::{Balance, CurrencyId};
 
@@ -37,10 +47,19 @@ use primitives::{Balance, CurrencyId};
 /// - Query total issuance.
 /// - Query balance. Rest `input` bytes: `account_id`.
 /// - Transfer. Rest `input` bytes: `from`, `to`, `amount`.
+/// - Query allowance. Rest `input` bytes: `owner`, `spender`.
+/// - Approve. Rest `input` bytes: `owner`, `spender`, `amount`.
+/// - Transfer from. Rest `input` bytes: `spender`, `from`, `to`, `amount`.
+/// - Batch transfer. Rest `input` bytes: `from`, `to[]`, `amount[]`. At most
+///   `MAX_BATCH_TRANSFERS` transfers, charged `batch_transfer_gas` gas.
+/// - Query existential deposit.
 pub struct MultiCurrencyPrecompile<AccountId, AddressMapping, CurrencyIdMapping, MultiCurrency>(
 	PhantomData<(AccountId, AddressMapping, CurrencyIdMapping, MultiCurrency)>,
 );
 
+/// The max number of transfers of a batch transfer.
+pub const MAX_BATCH_TRANSFERS: usize = 32;
+
 #[derive(Debug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
 #[repr(u32)]
 pub enum Action {
@@ -50,6 +69,11 @@ pub enum Action {
 	QueryTotalIssuance = 0x18160ddd,
 	QueryBalance = 0x70a08231,
 	Transfer = 0xbeabacc8,
+	QueryAllowance = 0xdd62ed3e,
+	Approve = 0xe1f21c67,
+	TransferFrom = 0x15dacbea,
+	BatchTransfer = 0x1239ec8c,
+	QueryExistentialDeposit = 0x3432dbc7,
 }
 
 impl<AccountId, AddressMapping, CurrencyIdMapping, MultiCurrency> Precompile
@@ -58,11 +82,12 @@ where
 	AccountId: Debug + Clone,
 	AddressMapping: AddressMappingT<AccountId>,
 	CurrencyIdMapping: CurrencyIdMappingT,
-	MultiCurrency: MultiCurrencyT<AccountId, Balance = Balance, CurrencyId = CurrencyId>,
+	MultiCurrency: MultiCurrencyT<AccountId, Balance = Balance, CurrencyId = CurrencyId>
+		+ MultiCurrencyAllowanceT<AccountId, Balance = Balance, CurrencyId = CurrencyId>,
 {
 	fn execute(
 		input: &[u8],
-		_target_gas: Option<u64>,
+		target_gas: Option<u64>,
 		context: &Context,
 	) -> result::Result<(ExitSucceed, Vec<u8>, u64), ExitError> {
 		//TODO: evaluate cost
@@ -132,10 +157,143 @@ where
 
 				Ok((ExitSucceed::Returned, vec![], 0))
 			}
+			Action::QueryAllowance => {
+				let owner = input.account_id_at(1)?;
+				let spender = input.account_id_at(2)?;
+
+				log::debug!(target: "evm", "multicurrency: owner: {:?}", owner);
+				log::debug!(target: "evm", "multicurrency: spender: {:?}", spender);
+
+				let allowance = vec_u8_from_balance(MultiCurrency::allowance(currency_id, &owner, &spender));
+				log::debug!(target: "evm", "multicurrency: allowance: {:?}", allowance);
+
+				Ok((ExitSucceed::Returned, allowance, 0))
+			}
+			Action::Approve => {
+				let owner = input.account_id_at(1)?;
+				let spender = input.account_id_at(2)?;
+				let amount = input.balance_at(3)?;
+
+				log::debug!(target: "evm", "multicurrency: owner: {:?}", owner);
+				log::debug!(target: "evm", "multicurrency: spender: {:?}", spender);
+				log::debug!(target: "evm", "multicurrency: amount: {:?}", amount);
+
+				MultiCurrency::approve(currency_id, &owner, &spender, amount).map_err(|e| {
+					let err_msg: &str = e.into();
+					ExitError::Other(err_msg.into())
+				})?;
+
+				log::debug!(target: "evm", "multicurrency: approve success!");
+
+				Ok((ExitSucceed::Returned, vec![], 0))
+			}
+			Action::TransferFrom => {
+				let spender = input.account_id_at(1)?;
+				let from = input.account_id_at(2)?;
+				let to = input.account_id_at(3)?;
+				let amount = input.balance_at(4)?;
+
+				log::debug!(target: "evm", "multicurrency: spender: {:?}", spender);
+				log::debug!(target: "evm", "multicurrency: from: {:?}", from);
+				log::debug!(target: "evm", "multicurrency: to: {:?}", to);
+				log::debug!(target: "evm", "multicurrency: amount: {:?}", amount);
+
+				MultiCurrency::transfer_from(currency_id, &spender, &from, &to, amount).map_err(|e| {
+					let err_msg: &str = e.into();
+					ExitError::Other(err_msg.into())
+				})?;
+
+				log::debug!(target: "evm", "multicurrency: transfer from success!");
+
+				Ok((ExitSucceed::Returned, vec![], 0))
+			}
+			Action::BatchTransfer => {
+				let from = input.account_id_at(1)?;
+				let (to_index, to_len) = array_at(&input, 2)?;
+				let (amount_index, amount_len) = array_at(&input, 3)?;
+				ensure!(
+					to_len == amount_len,
+					ExitError::Other("recipients and amounts length mismatch".into())
+				);
+				ensure!(
+					to_len <= MAX_BATCH_TRANSFERS,
+					ExitError::Other("too many transfers".into())
+				);
+
+				let gas_cost = batch_transfer_gas(to_len);
+				if let Some(gas_limit) = target_gas {
+					if gas_limit < gas_cost {
+						return Err(ExitError::OutOfGas);
+					}
+				}
+
+				let transfers = (0..to_len)
+					.map(|i| Ok((input.account_id_at(to_index + i)?, input.balance_at(amount_index + i)?)))
+					.collect::<result::Result<Vec<_>, ExitError>>()?;
+
+				log::debug!(target: "evm", "multicurrency: from: {:?}", from);
+				log::debug!(target: "evm", "multicurrency: transfers: {:?}", transfers);
+
+				batch_transfer::<AccountId, MultiCurrency>(currency_id, &from, &transfers).map_err(|e| {
+					let err_msg: &str = e.into();
+					ExitError::Other(err_msg.into())
+				})?;
+
+				log::debug!(target: "evm", "multicurrency: batch transfer success!");
+
+				Ok((ExitSucceed::Returned, vec![], gas_cost))
+			}
+			Action::QueryExistentialDeposit => {
+				let existential_deposit = vec_u8_from_balance(MultiCurrency::minimum_balance(currency_id));
+				log::debug!(target: "evm", "multicurrency: existential deposit: {:?}", existential_deposit);
+
+				Ok((ExitSucceed::Returned, existential_deposit, 0))
+			}
 		}
 	}
 }
 
+/// Returns the param index of the first element and the length of the dynamic
+/// array whose offset is the `index`th param.
+fn array_at<I: InputT<Error = ExitError>>(input: &I, index: usize) -> result::Result<(usize, usize), ExitError> {
+	let offset = input.u32_at(index)? as usize;
+	ensure!(
+		offset % PER_PARAM_BYTES == 0,
+		ExitError::Other("invalid array offset".into())
+	);
+
+	let len_index = offset / PER_PARAM_BYTES + 1;
+	let len = input.u32_at(len_index)? as usize;
+
+	Ok((len_index + 1, len))
+}
+
+/// The gas of a batch transfer of `transfers` transfers, each reads and writes the balances of
+/// both accounts.
+pub fn batch_transfer_gas(transfers: usize) -> u64 {
+	WeightToGas::convert(
+		RocksDbWeight::get()
+			.reads_writes(2, 2)
+			.saturating_mul(transfers as Weight),
+	)
+}
+
+/// All or none of the transfers are applied.
+#[transactional]
+fn batch_transfer<AccountId, MultiCurrency>(
+	currency_id: CurrencyId,
+	from: &AccountId,
+	transfers: &[(AccountId, Balance)],
+) -> DispatchResult
+where
+	MultiCurrency: MultiCurrencyT<AccountId, Balance = Balance, CurrencyId = CurrencyId>,
+{
+	for (to, amount) in transfers {
+		MultiCurrency::transfer(currency_id, from, to, *amount)?;
+	}
+	Ok(())
+}
+
 fn vec_u8_from_balance(balance: Balance) -> Vec<u8> {
 	let mut be_bytes = [0u8; 32];
 	U256::from(balance).to_big_endian(&mut be_bytes[..]);
@@ -190,5 +348,30 @@ mod tests {
 			u32::from_be_bytes(get_function_selector("transfer(address,address,uint256)")),
 			Into::<u32>::into(Action::Transfer)
 		);
+
+		assert_eq!(
+			u32::from_be_bytes(get_function_selector("allowance(address,address)")),
+			Into::<u32>::into(Action::QueryAllowance)
+		);
+
+		assert_eq!(
+			u32::from_be_bytes(get_function_selector("approve(address,address,uint256)")),
+			Into::<u32>::into(Action::Approve)
+		);
+
+		assert_eq!(
+			u32::from_be_bytes(get_function_selector("transferFrom(address,address,address,uint256)")),
+			Into::<u32>::into(Action::TransferFrom)
+		);
+
+		assert_eq!(
+			u32::from_be_bytes(get_function_selector("batchTransfer(address,address[],uint256[])")),
+			Into::<u32>::into(Action::BatchTransfer)
+		);
+
+		assert_eq!(
+			u32::from_be_bytes(get_function_selector("existentialDeposit()")),
+			Into::<u32>::into(Action::QueryExistentialDeposit)
+		);
 	}
 }
@@ -19,11 +19,13 @@
 use frame_support::{log, sp_runtime::FixedPointNumber};
 use module_evm::{Context, ExitError, ExitSucceed, Precompile};
 use num_enum::{IntoPrimitive, TryFromPrimitive};
-use primitives::CurrencyId;
+use orml_traits::DataProviderExtended;
+use primitives::{CurrencyId, Moment};
 use sp_core::U256;
 use sp_std::{fmt::Debug, marker::PhantomData, prelude::*, result};
 
 use super::input::{Input, InputT};
+use crate::TimeStampedPrice;
 use module_support::{
 	AddressMapping as AddressMappingT, CurrencyIdMapping as CurrencyIdMappingT, Price, PriceProvider as PriceProviderT,
 };
@@ -35,23 +37,32 @@ use module_support::{
 ///
 /// Actions:
 /// - Get price. Rest `input` bytes: `currency_id`.
-pub struct OraclePrecompile<AccountId, AddressMapping, CurrencyIdMapping, PriceProvider>(
-	PhantomData<(AccountId, AddressMapping, CurrencyIdMapping, PriceProvider)>,
+/// - Get timestamped price. Rest `input` bytes: `currency_id`.
+pub struct OraclePrecompile<AccountId, AddressMapping, CurrencyIdMapping, PriceProvider, DataProvider>(
+	PhantomData<(
+		AccountId,
+		AddressMapping,
+		CurrencyIdMapping,
+		PriceProvider,
+		DataProvider,
+	)>,
 );
 
 #[derive(Debug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
 #[repr(u32)]
 pub enum Action {
 	GetPrice = 0x41976e09,
+	GetTimestampedPrice = 0x59fcc4cb,
 }
 
-impl<AccountId, AddressMapping, CurrencyIdMapping, PriceProvider> Precompile
-	for OraclePrecompile<AccountId, AddressMapping, CurrencyIdMapping, PriceProvider>
+impl<AccountId, AddressMapping, CurrencyIdMapping, PriceProvider, DataProvider> Precompile
+	for OraclePrecompile<AccountId, AddressMapping, CurrencyIdMapping, PriceProvider, DataProvider>
 where
 	AccountId: Debug + Clone,
 	AddressMapping: AddressMappingT<AccountId>,
 	CurrencyIdMapping: CurrencyIdMappingT,
 	PriceProvider: PriceProviderT<CurrencyId>,
+	DataProvider: DataProviderExtended<CurrencyId, TimeStampedPrice>,
 {
 	fn execute(
 		input: &[u8],
@@ -69,29 +80,8 @@ where
 		match action {
 			Action::GetPrice => {
 				let currency_id = input.currency_id_at(1)?;
-				let mut price = PriceProvider::get_price(currency_id).unwrap_or_default();
-
-				let maybe_decimals = CurrencyIdMapping::decimals(currency_id);
-				let decimals = match maybe_decimals {
-					Some(decimals) => decimals,
-					None => {
-						// If the option is none, let price = 0 to return 0.
-						// Solidity should handle the situation of price 0.
-						price = Default::default();
-						Default::default()
-					}
-				};
-
-				let maybe_adjustment_multiplier = 10u128.checked_pow((18 - decimals).into());
-				let adjustment_multiplier = match maybe_adjustment_multiplier {
-					Some(adjustment_multiplier) => adjustment_multiplier,
-					None => {
-						// If the option is none, let price = 0 to return 0.
-						// Solidity should handle the situation of price 0.
-						price = Default::default();
-						Default::default()
-					}
-				};
+				let (price, adjustment_multiplier) =
+					get_adjusted_price::<CurrencyIdMapping, PriceProvider>(currency_id);
 
 				log::debug!(target: "evm", "oracle: getPrice currency_id: {:?}, price: {:?}, adjustment_multiplier: {:?}", currency_id, price, adjustment_multiplier);
 				Ok((
@@ -100,16 +90,74 @@ where
 					0,
 				))
 			}
+			Action::GetTimestampedPrice => {
+				let currency_id = input.currency_id_at(1)?;
+				let (price, adjustment_multiplier) =
+					get_adjusted_price::<CurrencyIdMapping, PriceProvider>(currency_id);
+				// The timestamp of the latest oracle feed of `currency_id`. It's 0 if the price is
+				// not fed by oracle directly, e.g. the stable currency or the dex share.
+				let timestamp = if price.is_zero() {
+					Default::default()
+				} else {
+					DataProvider::get_no_op(&currency_id)
+						.map(|value| value.timestamp)
+						.unwrap_or_default()
+				};
+
+				log::debug!(target: "evm", "oracle: getTimestampedPrice currency_id: {:?}, price: {:?}, adjustment_multiplier: {:?}, timestamp: {:?}", currency_id, price, adjustment_multiplier, timestamp);
+				let mut output = vec_u8_from_price(price, adjustment_multiplier);
+				output.extend_from_slice(&vec_u8_from_timestamp(timestamp));
+				Ok((ExitSucceed::Returned, output, 0))
+			}
 		}
 	}
 }
 
+/// Returns the price of `currency_id` and the multiplier to adjust it to 18 decimals.
+fn get_adjusted_price<CurrencyIdMapping, PriceProvider>(currency_id: CurrencyId) -> (Price, u128)
+where
+	CurrencyIdMapping: CurrencyIdMappingT,
+	PriceProvider: PriceProviderT<CurrencyId>,
+{
+	let mut price = PriceProvider::get_price(currency_id).unwrap_or_default();
+
+	let maybe_decimals = CurrencyIdMapping::decimals(currency_id);
+	let decimals = match maybe_decimals {
+		Some(decimals) => decimals,
+		None => {
+			// If the option is none, let price = 0 to return 0.
+			// Solidity should handle the situation of price 0.
+			price = Default::default();
+			Default::default()
+		}
+	};
+
+	let maybe_adjustment_multiplier = 10u128.checked_pow((18 - decimals).into());
+	let adjustment_multiplier = match maybe_adjustment_multiplier {
+		Some(adjustment_multiplier) => adjustment_multiplier,
+		None => {
+			// If the option is none, let price = 0 to return 0.
+			// Solidity should handle the situation of price 0.
+			price = Default::default();
+			Default::default()
+		}
+	};
+
+	(price, adjustment_multiplier)
+}
+
 fn vec_u8_from_price(price: Price, adjustment_multiplier: u128) -> Vec<u8> {
 	let mut be_bytes = [0u8; 32];
 	U256::from(price.into_inner().wrapping_div(adjustment_multiplier)).to_big_endian(&mut be_bytes[..32]);
 	be_bytes.to_vec()
 }
 
+fn vec_u8_from_timestamp(timestamp: Moment) -> Vec<u8> {
+	let mut be_bytes = [0u8; 32];
+	U256::from(timestamp).to_big_endian(&mut be_bytes[..32]);
+	be_bytes.to_vec()
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -121,5 +169,10 @@ mod tests {
 			u32::from_be_bytes(get_function_selector("getPrice(address)")),
 			Into::<u32>::into(Action::GetPrice)
 		);
+
+		assert_eq!(
+			u32::from_be_bytes(get_function_selector("getTimestampedPrice(address)")),
+			Into::<u32>::into(Action::GetTimestampedPrice)
+		);
 	}
 }
@@ -31,7 +31,7 @@ use module_evm::{Context, ExitError, ExitSucceed, Precompile};
 use module_support::{AddressMapping as AddressMappingT, CurrencyIdMapping as CurrencyIdMappingT, TransactionPayment};
 use primitives::{Balance, BlockNumber};
 use sp_core::{H160, U256};
-use sp_runtime::RuntimeDebug;
+use sp_runtime::{traits::UniqueSaturatedInto, RuntimeDebug};
 use sp_std::{fmt::Debug, marker::PhantomData, prelude::*, result};
 
 use super::input::{Input, InputT};
@@ -52,6 +52,38 @@ pub struct TaskInfo {
 	pub fee: Balance,
 }
 
+#[derive(RuntimeDebug, PartialEq, Encode, Decode)]
+pub struct PeriodicTaskInfo {
+	pub prefix: Vec<u8>,
+	pub id: u32,
+	pub sender: H160,
+	/// The fee reserved for each dispatch.
+	#[codec(compact)]
+	pub fee: Balance,
+	/// The block of the first dispatch.
+	pub start: BlockNumber,
+	pub period: BlockNumber,
+	pub count: u32,
+}
+
+impl PeriodicTaskInfo {
+	/// The fee reserved for the dispatches after the block `now`. The tasks of
+	/// `now` have already been dispatched when the precompile is called.
+	pub fn remaining_fee(&self, now: BlockNumber) -> Balance {
+		let dispatched = match now.checked_sub(self.start) {
+			Some(elapsed) => elapsed
+				.checked_div(self.period)
+				.map_or(self.count, |n| n.saturating_add(1).min(self.count)),
+			None => 0,
+		};
+		self.fee.saturating_mul(self.count.saturating_sub(dispatched).into())
+	}
+}
+
+fn is_periodic(task_id: &[u8]) -> bool {
+	Vec::<u8>::decode(&mut &task_id[..]).map_or(false, |prefix| prefix == b"SchedulePeriodicCall")
+}
+
 /// The `ScheduleCall` impl precompile.
 ///
 ///
@@ -60,6 +92,19 @@ pub struct TaskInfo {
 /// Actions:
 /// - ScheduleCall. Rest `input` bytes: `from`, `target`, `value`, `gas_limit`, `storage_limit`,
 ///   `min_delay`, `input_len`, `input_data`.
+/// - SchedulePeriodicCall. Rest `input` bytes: `from`, `target`, `value`, `gas_limit`,
+///   `storage_limit`, `min_delay`, `period`, `count`, `input_len`, `input_data`.
+/// - CancelCall. Rest `input` bytes: `from`, `task_id_len`, `task_id`.
+/// - RescheduleCall. Rest `input` bytes: `from`, `min_delay`, `task_id_len`, `task_id`.
+///
+/// The transaction fee for `gas_limit` is reserved from `from` when the call is scheduled, and
+/// unreserved when the call is dispatched or cancelled. Only `from` can cancel or reschedule
+/// the call.
+///
+/// A periodic call is dispatched `count` times, every `period` blocks after `min_delay`, e.g. to
+/// run the recurring logic of a contract without an off-chain keeper. The fees of all the
+/// dispatches are reserved when it's scheduled, and the fees of the remaining dispatches are
+/// unreserved when it's cancelled. A periodic call can't be rescheduled.
 pub struct ScheduleCallPrecompile<
 	AccountId,
 	AddressMapping,
@@ -88,6 +133,7 @@ pub struct ScheduleCallPrecompile<
 #[repr(u32)]
 pub enum Action {
 	Schedule = 0x64c91905,
+	SchedulePeriodic = 0x1f0ebd96,
 	Cancel = 0x93e32661,
 	Reschedule = 0x28302f34,
 }
@@ -228,6 +274,107 @@ impl<
 
 				Ok((ExitSucceed::Returned, task_id_with_len.to_vec(), 0))
 			}
+			Action::SchedulePeriodic => {
+				let from = input.evm_address_at(1)?;
+				let target = input.evm_address_at(2)?;
+
+				let value = input.balance_at(3)?;
+				let gas_limit = input.u64_at(4)?;
+				let storage_limit = input.u32_at(5)?;
+				let min_delay = input.u32_at(6)?;
+				let period = input.u32_at(7)?;
+				let count = input.u32_at(8)?;
+				// solidity abi enocde bytes will add an length at input[9]
+				let input_len = input.u32_at(10)?;
+				let input_data = input.bytes_at(11, input_len as usize)?;
+
+				log::debug!(
+					target: "evm",
+					"schedule periodic call: from: {:?}, target: {:?}, value: {:?}, gas_limit: {:?}, storage_limit: {:?}, min_delay: {:?}, period: {:?}, count: {:?}, input_len: {:?}, input_data: {:?}",
+					from,
+					target,
+					value,
+					gas_limit,
+					storage_limit,
+					min_delay,
+					period,
+					count,
+					input_len,
+					input_data,
+				);
+
+				ensure!(
+					period > 0 && count > 0,
+					ExitError::Other("Invalid period or count".into())
+				);
+
+				let mut _fee: PalletBalanceOf<Runtime> = Default::default();
+				#[cfg(not(feature = "with-ethereum-compatibility"))]
+				{
+					// reserve the transaction fees for gas_limit of all the dispatches
+					use sp_runtime::traits::Convert;
+					let from_account = AddressMapping::get_account_id(&from);
+					let weight = <Runtime as module_evm::Config>::GasToWeight::convert(gas_limit);
+					_fee = ChargeTransactionPayment::reserve_fees(&from_account, weight, count).map_err(|e| {
+						let err_msg: &str = e.into();
+						ExitError::Other(err_msg.into())
+					})?;
+				}
+
+				let call = module_evm::Call::<Runtime>::scheduled_call(
+					from,
+					target,
+					input_data,
+					value.into(),
+					gas_limit,
+					storage_limit,
+				)
+				.into();
+
+				let current_id = EvmSchedulerNextID::get();
+				let next_id = current_id
+					.checked_add(1)
+					.ok_or_else(|| ExitError::Other("Scheduler next id overflow".into()))?;
+				EvmSchedulerNextID::set(&next_id);
+
+				// the tasks of the current block have already been dispatched
+				let now: BlockNumber = frame_system::Pallet::<Runtime>::block_number().unique_saturated_into();
+				let start = now.saturating_add(min_delay).saturating_add(1);
+
+				let task_id = PeriodicTaskInfo {
+					prefix: b"SchedulePeriodicCall".to_vec(),
+					id: current_id,
+					sender: from,
+					fee: _fee.into(),
+					start,
+					period,
+					count,
+				}
+				.encode();
+
+				log::debug!(
+					target: "evm",
+					"schedule periodic call: task_id: {:?}",
+					task_id,
+				);
+
+				Scheduler::schedule_named(
+					task_id.clone(),
+					DispatchTime::At(start),
+					Some((period, count)),
+					0,
+					Origin::root().caller().clone(),
+					call,
+				)
+				.map_err(|_| ExitError::Other("Schedule failed".into()))?;
+
+				// add task_id len prefix
+				let mut task_id_with_len = [0u8; 128];
+				U256::from(task_id.len()).to_big_endian(&mut task_id_with_len[0..32]);
+				task_id_with_len[32..32 + task_id.len()].copy_from_slice(&task_id[..]);
+
+				Ok((ExitSucceed::Returned, task_id_with_len.to_vec(), 0))
+			}
 			Action::Cancel => {
 				let from = input.evm_address_at(1)?;
 				// solidity abi enocde bytes will add an length at input[2]
@@ -241,17 +388,25 @@ impl<
 					task_id,
 				);
 
-				let task_info = TaskInfo::decode(&mut &task_id[..])
-					.map_err(|_| ExitError::Other("Decode task_id failed".into()))?;
-				ensure!(task_info.sender == from, ExitError::Other("NoPermission".into()));
+				let (sender, _fee) = if is_periodic(&task_id) {
+					let task_info = PeriodicTaskInfo::decode(&mut &task_id[..])
+						.map_err(|_| ExitError::Other("Decode task_id failed".into()))?;
+					let now: BlockNumber = frame_system::Pallet::<Runtime>::block_number().unique_saturated_into();
+					(task_info.sender, task_info.remaining_fee(now))
+				} else {
+					let task_info = TaskInfo::decode(&mut &task_id[..])
+						.map_err(|_| ExitError::Other("Decode task_id failed".into()))?;
+					(task_info.sender, task_info.fee)
+				};
+				ensure!(sender == from, ExitError::Other("NoPermission".into()));
 
 				Scheduler::cancel_named(task_id).map_err(|_| ExitError::Other("Cancel schedule failed".into()))?;
 
 				#[cfg(not(feature = "with-ethereum-compatibility"))]
 				{
-					// unreserve the transaction fee for gas_limit
+					// unreserve the transaction fee for gas_limit of the remaining dispatches
 					let from_account = AddressMapping::get_account_id(&from);
-					ChargeTransactionPayment::unreserve_fee(&from_account, task_info.fee.into());
+					ChargeTransactionPayment::unreserve_fee(&from_account, _fee.into());
 				}
 
 				Ok((ExitSucceed::Returned, vec![], 0))
@@ -271,6 +426,12 @@ impl<
 					min_delay,
 				);
 
+				// the fees reserved for a periodic call are bound to its dispatch blocks
+				ensure!(
+					!is_periodic(&task_id),
+					ExitError::Other("Reschedule periodic call not supported".into())
+				);
+
 				let task_info = TaskInfo::decode(&mut &task_id[..])
 					.map_err(|_| ExitError::Other("Decode task_id failed".into()))?;
 				ensure!(task_info.sender == from, ExitError::Other("NoPermission".into()));
@@ -300,6 +461,13 @@ mod tests {
 			Into::<u32>::into(Action::Schedule)
 		);
 
+		assert_eq!(
+			u32::from_be_bytes(get_function_selector(
+				"schedulePeriodicCall(address,address,uint256,uint256,uint256,uint256,uint256,uint256,bytes)"
+			)),
+			Into::<u32>::into(Action::SchedulePeriodic)
+		);
+
 		assert_eq!(
 			u32::from_be_bytes(get_function_selector("cancelCall(address,bytes)")),
 			Into::<u32>::into(Action::Cancel)
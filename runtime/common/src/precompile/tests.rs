@@ -21,21 +21,24 @@ use super::*;
 use crate::precompile::{
 	mock::{
 		aca_evm_address, alice, alice_evm_addr, ausd_evm_address, bob, bob_evm_addr, erc20_address_not_exists,
-		get_task_id, lp_aca_ausd_evm_address, new_test_ext, renbtc_evm_address, run_to_block, Balances, DexModule,
-		DexPrecompile, Event as TestEvent, MultiCurrencyPrecompile, Oracle, OraclePrecompile, Origin, Price,
-		ScheduleCallPrecompile, System, Test, ALICE, AUSD, INITIAL_BALANCE, RENBTC,
+		get_task_id, lp_aca_ausd_evm_address, new_test_ext, renbtc_evm_address, run_to_block, AccountId, Balances,
+		Currencies, DexModule, DexPrecompile, ERC721Precompile, Event as TestEvent, HomaPrecompile, HonzonPrecompile,
+		IncentivesPrecompile, MultiCurrencyPrecompile, MulticallPrecompile, NFTModule, Oracle, OraclePrecompile,
+		Origin, Price, ScheduleCallPrecompile, System, Test, Timestamp, Tokens, ALICE, AUSD, DOT, INITIAL_BALANCE,
+		LDOT, LP_ACA_AUSD, RENBTC,
 	},
-	schedule_call::TaskInfo,
+	schedule_call::{PeriodicTaskInfo, TaskInfo},
 };
 use codec::Encode;
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{assert_noop, assert_ok, traits::Get};
 use hex_literal::hex;
+use module_dex::WeightInfo;
 use module_evm::ExitError;
-use module_support::AddressMapping;
-use orml_traits::DataFeeder;
+use module_support::{AddressMapping, Rate, Ratio};
+use orml_traits::{DataFeeder, MultiCurrency, MultiReservableCurrency};
 use primitives::{Balance, PREDEPLOY_ADDRESS_START};
-use sp_core::{H160, U256};
-use sp_runtime::FixedPointNumber;
+use sp_core::{H160, H256, U256};
+use sp_runtime::{traits::AccountIdConversion, FixedPointNumber};
 use std::str::FromStr;
 
 pub struct DummyPrecompile;
@@ -57,6 +60,11 @@ pub type WithSystemContractFilter = AllPrecompiles<
 	DummyPrecompile,
 	DummyPrecompile,
 	DummyPrecompile,
+	DummyPrecompile,
+	DummyPrecompile,
+	DummyPrecompile,
+	DummyPrecompile,
+	DummyPrecompile,
 >;
 
 #[test]
@@ -277,6 +285,137 @@ fn multicurrency_precompile_should_work() {
 			MultiCurrencyPrecompile::execute(&input, None, &context),
 			ExitError::Other("BalanceTooLow".into())
 		);
+
+		context.caller = aca_evm_address();
+
+		// 7.Approve
+		let mut input = [0u8; 4 + 4 * 32];
+		// action
+		input[1 * 32..4 + 1 * 32].copy_from_slice(&Into::<u32>::into(multicurrency::Action::Approve).to_be_bytes());
+		// owner
+		U256::from(alice_evm_addr().as_bytes()).to_big_endian(&mut input[4 + 1 * 32..4 + 2 * 32]);
+		// spender
+		U256::from(bob_evm_addr().as_bytes()).to_big_endian(&mut input[4 + 2 * 32..4 + 3 * 32]);
+		// amount
+		U256::from(3).to_big_endian(&mut input[4 + 3 * 32..4 + 4 * 32]);
+
+		let (reason, output, used_gas) = MultiCurrencyPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, Vec::<u8>::new());
+		assert_eq!(used_gas, 0);
+
+		// 8.QueryAllowance
+		let mut input = [0u8; 4 + 3 * 32];
+		// action
+		input[1 * 32..4 + 1 * 32]
+			.copy_from_slice(&Into::<u32>::into(multicurrency::Action::QueryAllowance).to_be_bytes());
+		// owner
+		U256::from(alice_evm_addr().as_bytes()).to_big_endian(&mut input[4 + 1 * 32..4 + 2 * 32]);
+		// spender
+		U256::from(bob_evm_addr().as_bytes()).to_big_endian(&mut input[4 + 2 * 32..4 + 3 * 32]);
+
+		let (reason, output, used_gas) = MultiCurrencyPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		let mut expected_output = [0u8; 32];
+		expected_output[31] = 3;
+		assert_eq!(output, expected_output);
+		assert_eq!(used_gas, 0);
+
+		// 9.TransferFrom
+		let mut input = [0u8; 4 + 5 * 32];
+		// action
+		input[1 * 32..4 + 1 * 32]
+			.copy_from_slice(&Into::<u32>::into(multicurrency::Action::TransferFrom).to_be_bytes());
+		// spender
+		U256::from(bob_evm_addr().as_bytes()).to_big_endian(&mut input[4 + 1 * 32..4 + 2 * 32]);
+		// from
+		U256::from(alice_evm_addr().as_bytes()).to_big_endian(&mut input[4 + 2 * 32..4 + 3 * 32]);
+		// to
+		U256::from(bob_evm_addr().as_bytes()).to_big_endian(&mut input[4 + 3 * 32..4 + 4 * 32]);
+		// amount
+		U256::from(2).to_big_endian(&mut input[4 + 4 * 32..4 + 5 * 32]);
+		let from_balance = Balances::free_balance(alice());
+		let to_balance = Balances::free_balance(bob());
+
+		let (reason, output, used_gas) = MultiCurrencyPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, Vec::<u8>::new());
+		assert_eq!(used_gas, 0);
+		assert_eq!(Balances::free_balance(alice()), from_balance - 2);
+		assert_eq!(Balances::free_balance(bob()), to_balance + 2);
+
+		// only 1 left in the allowance
+		assert_noop!(
+			MultiCurrencyPrecompile::execute(&input, None, &context),
+			ExitError::Other("AllowanceTooLow".into())
+		);
+
+		// 10.BatchTransfer
+		let mut input = [0u8; 4 + 10 * 32];
+		// action
+		input[1 * 32..4 + 1 * 32]
+			.copy_from_slice(&Into::<u32>::into(multicurrency::Action::BatchTransfer).to_be_bytes());
+		// from
+		U256::from(alice_evm_addr().as_bytes()).to_big_endian(&mut input[4 + 1 * 32..4 + 2 * 32]);
+		// to[] offset
+		U256::from(3 * 32).to_big_endian(&mut input[4 + 2 * 32..4 + 3 * 32]);
+		// amount[] offset
+		U256::from(6 * 32).to_big_endian(&mut input[4 + 3 * 32..4 + 4 * 32]);
+		// to[]
+		U256::from(2).to_big_endian(&mut input[4 + 4 * 32..4 + 5 * 32]);
+		U256::from(bob_evm_addr().as_bytes()).to_big_endian(&mut input[4 + 5 * 32..4 + 6 * 32]);
+		U256::from(bob_evm_addr().as_bytes()).to_big_endian(&mut input[4 + 6 * 32..4 + 7 * 32]);
+		// amount[]
+		U256::from(2).to_big_endian(&mut input[4 + 7 * 32..4 + 8 * 32]);
+		U256::from(1).to_big_endian(&mut input[4 + 8 * 32..4 + 9 * 32]);
+		U256::from(2).to_big_endian(&mut input[4 + 9 * 32..4 + 10 * 32]);
+		let from_balance = Balances::free_balance(alice());
+		let to_balance = Balances::free_balance(bob());
+
+		assert_noop!(
+			MultiCurrencyPrecompile::execute(&input, Some(multicurrency::batch_transfer_gas(2) - 1), &context),
+			ExitError::OutOfGas
+		);
+
+		let (reason, output, used_gas) = MultiCurrencyPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, Vec::<u8>::new());
+		assert_eq!(used_gas, multicurrency::batch_transfer_gas(2));
+		assert_eq!(Balances::free_balance(alice()), from_balance - 3);
+		assert_eq!(Balances::free_balance(bob()), to_balance + 3);
+
+		// the batch is capped
+		let len = multicurrency::MAX_BATCH_TRANSFERS + 1;
+		let mut input = vec![0u8; 4 + (6 + 2 * len) * 32];
+		// action
+		input[1 * 32..4 + 1 * 32]
+			.copy_from_slice(&Into::<u32>::into(multicurrency::Action::BatchTransfer).to_be_bytes());
+		// from
+		U256::from(alice_evm_addr().as_bytes()).to_big_endian(&mut input[4 + 1 * 32..4 + 2 * 32]);
+		// to[] offset
+		U256::from(3 * 32).to_big_endian(&mut input[4 + 2 * 32..4 + 3 * 32]);
+		// amount[] offset
+		U256::from((4 + len) * 32).to_big_endian(&mut input[4 + 3 * 32..4 + 4 * 32]);
+		// to[] and amount[] lengths
+		U256::from(len).to_big_endian(&mut input[4 + 4 * 32..4 + 5 * 32]);
+		U256::from(len).to_big_endian(&mut input[4 + (5 + len) * 32..4 + (6 + len) * 32]);
+		assert_noop!(
+			MultiCurrencyPrecompile::execute(&input, None, &context),
+			ExitError::Other("too many transfers".into())
+		);
+
+		// 11.QueryExistentialDeposit
+		let mut input = [0u8; 36];
+		// action
+		input[1 * 32..4 + 1 * 32]
+			.copy_from_slice(&Into::<u32>::into(multicurrency::Action::QueryExistentialDeposit).to_be_bytes());
+
+		let (reason, output, used_gas) = MultiCurrencyPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		let mut expected_output = [0u8; 32];
+		expected_output[31] = 1;
+		assert_eq!(output, expected_output);
+		assert_eq!(used_gas, 0);
 	});
 }
 
@@ -324,6 +463,46 @@ fn oracle_precompile_should_work() {
 	});
 }
 
+#[test]
+fn oracle_precompile_get_timestamped_price_should_work() {
+	new_test_ext().execute_with(|| {
+		let context = Context {
+			address: Default::default(),
+			caller: alice_evm_addr(),
+			apparent_value: Default::default(),
+		};
+
+		let price = Price::from(30_000);
+
+		// action + currency_id
+		let mut input = [0u8; 68];
+		// action
+		input[1 * 32..4 + 1 * 32]
+			.copy_from_slice(&Into::<u32>::into(oracle::Action::GetTimestampedPrice).to_be_bytes());
+		// RENBTC
+		U256::from_big_endian(&renbtc_evm_address().as_bytes()).to_big_endian(&mut input[4 + 1 * 32..4 + 2 * 32]);
+
+		// no price yet
+		let (reason, output, used_gas) = OraclePrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, [0u8; 64]);
+		assert_eq!(used_gas, 0);
+
+		Timestamp::set_timestamp(100);
+		assert_ok!(Oracle::feed_value(ALICE, RENBTC, price));
+
+		// returned price + timestamp
+		let mut expected_output = [0u8; 64];
+		U256::from(price.into_inner()).to_big_endian(&mut expected_output[..32]);
+		U256::from(100).to_big_endian(&mut expected_output[32..64]);
+
+		let (reason, output, used_gas) = OraclePrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, expected_output);
+		assert_eq!(used_gas, 0);
+	});
+}
+
 #[test]
 fn oracle_precompile_should_handle_invalid_input() {
 	new_test_ext().execute_with(|| {
@@ -500,6 +679,175 @@ fn schedule_call_precompile_should_work() {
 	});
 }
 
+#[test]
+fn schedule_periodic_call_precompile_should_work() {
+	new_test_ext().execute_with(|| {
+		let context = Context {
+			address: Default::default(),
+			caller: alice_evm_addr(),
+			apparent_value: Default::default(),
+		};
+
+		let mut input = [0u8; 14 * 32];
+		// array size
+		U256::default().to_big_endian(&mut input[0 * 32..1 * 32]);
+		// action
+		input[1 * 32..4 + 1 * 32]
+			.copy_from_slice(&Into::<u32>::into(schedule_call::Action::SchedulePeriodic).to_be_bytes());
+		// from
+		U256::from(alice_evm_addr().as_bytes()).to_big_endian(&mut input[4 + 1 * 32..4 + 2 * 32]);
+		// target
+		U256::from(aca_evm_address().as_bytes()).to_big_endian(&mut input[4 + 2 * 32..4 + 3 * 32]);
+		// value
+		U256::from(0).to_big_endian(&mut input[4 + 3 * 32..4 + 4 * 32]);
+		// gas_limit
+		U256::from(300000).to_big_endian(&mut input[4 + 4 * 32..4 + 5 * 32]);
+		// storage_limit
+		U256::from(100).to_big_endian(&mut input[4 + 5 * 32..4 + 6 * 32]);
+		// min_delay
+		U256::from(1).to_big_endian(&mut input[4 + 6 * 32..4 + 7 * 32]);
+		// period
+		U256::from(2).to_big_endian(&mut input[4 + 7 * 32..4 + 8 * 32]);
+		// count
+		U256::from(3).to_big_endian(&mut input[4 + 8 * 32..4 + 9 * 32]);
+		// skip offset
+		// input_len
+		U256::from(4 + 32 + 32).to_big_endian(&mut input[4 + 10 * 32..4 + 11 * 32]);
+
+		// input_data
+		let mut transfer_to_bob = [0u8; 68];
+		// transfer bytes4(keccak256(signature)) 0xa9059cbb
+		transfer_to_bob[0..4].copy_from_slice(&hex!("a9059cbb"));
+		// to address
+		U256::from(bob_evm_addr().as_bytes()).to_big_endian(&mut transfer_to_bob[4..36]);
+		// amount
+		U256::from(1000).to_big_endian(&mut transfer_to_bob[36..68]);
+
+		U256::from(&transfer_to_bob[0..32]).to_big_endian(&mut input[4 + 11 * 32..4 + 12 * 32]);
+		U256::from(&transfer_to_bob[32..64]).to_big_endian(&mut input[4 + 12 * 32..4 + 13 * 32]);
+		input[4 + 13 * 32..4 + 13 * 32 + 4].copy_from_slice(&transfer_to_bob[64..68]);
+
+		let (reason, output, used_gas) = ScheduleCallPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(used_gas, 0);
+		let event = TestEvent::Scheduler(pallet_scheduler::Event::<Test>::Scheduled(3, 0));
+		assert!(System::events().iter().any(|record| record.event == event));
+
+		let from_account = <Test as module_evm::Config>::AddressMapping::get_account_id(&alice_evm_addr());
+		let to_account = <Test as module_evm::Config>::AddressMapping::get_account_id(&bob_evm_addr());
+		#[cfg(not(feature = "with-ethereum-compatibility"))]
+		assert_eq!(Balances::reserved_balance(from_account.clone()), 3 * 300000);
+		#[cfg(feature = "with-ethereum-compatibility")]
+		assert_eq!(Balances::reserved_balance(from_account.clone()), 0);
+
+		// dispatched at 3 and 5
+		run_to_block(5);
+		assert_eq!(Balances::free_balance(to_account.clone()), 1000000002000);
+		#[cfg(not(feature = "with-ethereum-compatibility"))]
+		assert_eq!(Balances::reserved_balance(from_account.clone()), 300000);
+		#[cfg(feature = "with-ethereum-compatibility")]
+		assert_eq!(Balances::reserved_balance(from_account.clone()), 0);
+
+		let task_id = get_task_id(output);
+
+		// reschedule periodic call
+		let mut reschedule_input = [0u8; 8 * 32];
+		// array size
+		U256::default().to_big_endian(&mut reschedule_input[0 * 32..1 * 32]);
+		// action
+		reschedule_input[1 * 32..4 + 1 * 32]
+			.copy_from_slice(&Into::<u32>::into(schedule_call::Action::Reschedule).to_be_bytes());
+		// from
+		U256::from(alice_evm_addr().as_bytes()).to_big_endian(&mut reschedule_input[4 + 1 * 32..4 + 2 * 32]);
+		// min_delay
+		U256::from(2).to_big_endian(&mut reschedule_input[4 + 2 * 32..4 + 3 * 32]);
+		// skip offset
+		// task_id_len
+		U256::from(task_id.len()).to_big_endian(&mut reschedule_input[4 + 4 * 32..4 + 5 * 32]);
+		// task_id
+		reschedule_input[4 + 5 * 32..4 + 5 * 32 + task_id.len()].copy_from_slice(&task_id[..]);
+
+		assert_eq!(
+			ScheduleCallPrecompile::execute(&reschedule_input, None, &context),
+			Err(ExitError::Other("Reschedule periodic call not supported".into()))
+		);
+
+		// cancel periodic call
+		let mut cancel_input = [0u8; 7 * 32];
+		// array size
+		U256::default().to_big_endian(&mut cancel_input[0 * 32..1 * 32]);
+		// action
+		cancel_input[1 * 32..4 + 1 * 32]
+			.copy_from_slice(&Into::<u32>::into(schedule_call::Action::Cancel).to_be_bytes());
+		// from
+		U256::from(bob_evm_addr().as_bytes()).to_big_endian(&mut cancel_input[4 + 1 * 32..4 + 2 * 32]);
+		// skip offset
+		// task_id_len
+		U256::from(task_id.len()).to_big_endian(&mut cancel_input[4 + 3 * 32..4 + 4 * 32]);
+		// task_id
+		cancel_input[4 + 4 * 32..4 + 4 * 32 + task_id.len()].copy_from_slice(&task_id[..]);
+
+		assert_eq!(
+			ScheduleCallPrecompile::execute(&cancel_input, None, &context),
+			Err(ExitError::Other("NoPermission".into()))
+		);
+
+		// from
+		U256::from(alice_evm_addr().as_bytes()).to_big_endian(&mut cancel_input[4 + 1 * 32..4 + 2 * 32]);
+
+		let (reason, _output, used_gas) = ScheduleCallPrecompile::execute(&cancel_input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(used_gas, 0);
+		let event = TestEvent::Scheduler(pallet_scheduler::Event::<Test>::Canceled(7, 0));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_eq!(Balances::reserved_balance(from_account.clone()), 0);
+
+		// not dispatched at 7
+		run_to_block(7);
+		assert_eq!(Balances::free_balance(to_account), 1000000002000);
+		assert_eq!(Balances::reserved_balance(from_account), 0);
+	});
+}
+
+#[test]
+fn schedule_periodic_call_precompile_should_fail_with_zero_count() {
+	new_test_ext().execute_with(|| {
+		let context = Context {
+			address: Default::default(),
+			caller: alice_evm_addr(),
+			apparent_value: Default::default(),
+		};
+
+		let mut input = [0u8; 12 * 32];
+		// array size
+		U256::default().to_big_endian(&mut input[0 * 32..1 * 32]);
+		// action
+		input[1 * 32..4 + 1 * 32]
+			.copy_from_slice(&Into::<u32>::into(schedule_call::Action::SchedulePeriodic).to_be_bytes());
+		// from
+		U256::from(alice_evm_addr().as_bytes()).to_big_endian(&mut input[4 + 1 * 32..4 + 2 * 32]);
+		// target
+		U256::from(aca_evm_address().as_bytes()).to_big_endian(&mut input[4 + 2 * 32..4 + 3 * 32]);
+		// gas_limit
+		U256::from(300000).to_big_endian(&mut input[4 + 4 * 32..4 + 5 * 32]);
+		// min_delay
+		U256::from(1).to_big_endian(&mut input[4 + 6 * 32..4 + 7 * 32]);
+		// period
+		U256::from(2).to_big_endian(&mut input[4 + 7 * 32..4 + 8 * 32]);
+		// count is zero
+		// skip offset
+		// input_len
+		U256::from(1).to_big_endian(&mut input[4 + 10 * 32..4 + 11 * 32]);
+		// input_data = 0x12
+		input[4 + 11 * 32] = hex!("12")[0];
+
+		assert_eq!(
+			ScheduleCallPrecompile::execute(&input, None, &context),
+			Err(ExitError::Other("Invalid period or count".into()))
+		);
+	});
+}
+
 #[test]
 fn schedule_call_precompile_should_handle_invalid_input() {
 	new_test_ext().execute_with(|| {
@@ -831,10 +1179,18 @@ fn dex_precompile_swap_with_exact_supply_should_work() {
 		let mut expected_output = [0u8; 32];
 		U256::from(989).to_big_endian(&mut expected_output[..32]);
 
-		let (reason, output, used_gas) = DexPrecompile::execute(&input, None, &context).unwrap();
+		let expected_gas = <Test as module_dex::Config>::WeightInfo::swap_with_exact_supply(2);
+
+		// the gas limit is not enough
+		assert_noop!(
+			DexPrecompile::execute(&input, Some(expected_gas - 1), &context),
+			ExitError::OutOfGas
+		);
+
+		let (reason, output, used_gas) = DexPrecompile::execute(&input, Some(expected_gas), &context).unwrap();
 		assert_eq!(reason, ExitSucceed::Returned);
 		assert_eq!(output, expected_output);
-		assert_eq!(used_gas, 0);
+		assert_eq!(used_gas, expected_gas);
 	});
 }
 
@@ -884,13 +1240,490 @@ fn dex_precompile_swap_with_exact_target_should_work() {
 		let mut expected_output = [0u8; 32];
 		U256::from(1).to_big_endian(&mut expected_output[..32]);
 
-		let (reason, output, used_gas) = DexPrecompile::execute(&input, None, &context).unwrap();
+		let expected_gas = <Test as module_dex::Config>::WeightInfo::swap_with_exact_target(2);
+
+		// the gas limit is not enough
+		assert_noop!(
+			DexPrecompile::execute(&input, Some(expected_gas - 1), &context),
+			ExitError::OutOfGas
+		);
+
+		let (reason, output, used_gas) = DexPrecompile::execute(&input, Some(expected_gas), &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, expected_output);
+		assert_eq!(used_gas, expected_gas);
+	});
+}
+
+#[test]
+fn homa_precompile_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Currencies::update_balance(Origin::root(), alice(), DOT, 1_000));
+
+		let context = Context {
+			address: H160::from_low_u64_be(PRECOMPILE_ADDRESS_START + 6),
+			caller: alice_evm_addr(),
+			apparent_value: Default::default(),
+		};
+
+		// array_size + action + who + amount
+		let mut input = [0u8; 4 + 3 * 32];
+		// action
+		input[1 * 32..4 + 1 * 32].copy_from_slice(&Into::<u32>::into(homa::Action::Mint).to_be_bytes());
+		// who
+		U256::from(alice_evm_addr().as_bytes()).to_big_endian(&mut input[4 + 1 * 32..4 + 2 * 32]);
+		// amount
+		U256::from(1_000).to_big_endian(&mut input[4 + 2 * 32..4 + 3 * 32]);
+
+		let mut expected_output = [0u8; 32];
+		U256::from(2_000).to_big_endian(&mut expected_output[..32]);
+
+		let expected_gas = <() as module_homa::WeightInfo>::mint();
+
+		// the gas limit is not enough
+		assert_noop!(
+			HomaPrecompile::execute(&input, Some(expected_gas - 1), &context),
+			ExitError::OutOfGas
+		);
+
+		let (reason, output, used_gas) = HomaPrecompile::execute(&input, Some(expected_gas), &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, expected_output);
+		assert_eq!(used_gas, expected_gas);
+		assert_eq!(Currencies::free_balance(DOT, &alice()), 0);
+		assert_eq!(Currencies::free_balance(LDOT, &alice()), 2_000);
+
+		let mut data = [0u8; 64];
+		U256::from(1_000).to_big_endian(&mut data[..32]);
+		U256::from(2_000).to_big_endian(&mut data[32..64]);
+		System::assert_last_event(TestEvent::ModuleEVM(module_evm::Event::Log(module_evm::Log {
+			address: context.address,
+			topics: vec![
+				H256(sp_io::hashing::keccak_256(b"Minted(address,uint256,uint256)")),
+				alice_evm_addr().into(),
+			],
+			data: data.to_vec(),
+		})));
+
+		// not enough DOT
+		assert_noop!(
+			HomaPrecompile::execute(&input, None, &context),
+			ExitError::Other("BalanceTooLow".into())
+		);
+
+		// action
+		input[1 * 32..4 + 1 * 32].copy_from_slice(&Into::<u32>::into(homa::Action::RequestRedeem).to_be_bytes());
+		// liquid_amount
+		U256::from(500).to_big_endian(&mut input[4 + 2 * 32..4 + 3 * 32]);
+
+		let (reason, output, _) = HomaPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, Vec::<u8>::new());
+		assert_eq!(Currencies::free_balance(LDOT, &alice()), 1_500);
+
+		let mut data = [0u8; 32];
+		U256::from(500).to_big_endian(&mut data[..32]);
+		System::assert_last_event(TestEvent::ModuleEVM(module_evm::Event::Log(module_evm::Log {
+			address: context.address,
+			topics: vec![
+				H256(sp_io::hashing::keccak_256(b"RedeemRequested(address,uint256)")),
+				alice_evm_addr().into(),
+			],
+			data: data.to_vec(),
+		})));
+
+		// array_size + action
+		let mut input = [0u8; 4 + 32];
+		// action
+		input[1 * 32..4 + 1 * 32].copy_from_slice(&Into::<u32>::into(homa::Action::GetExchangeRate).to_be_bytes());
+
+		let mut expected_output = [0u8; 32];
+		U256::from(500_000_000_000_000_000u128).to_big_endian(&mut expected_output[..32]);
+
+		let (reason, output, used_gas) = HomaPrecompile::execute(&input, None, &context).unwrap();
 		assert_eq!(reason, ExitSucceed::Returned);
 		assert_eq!(output, expected_output);
 		assert_eq!(used_gas, 0);
 	});
 }
 
+#[test]
+fn incentives_precompile_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Currencies::update_balance(Origin::root(), alice(), LP_ACA_AUSD, 1_000));
+
+		let context = Context {
+			address: Default::default(),
+			caller: alice_evm_addr(),
+			apparent_value: Default::default(),
+		};
+
+		// array_size + action + who + lp_currency_id + amount
+		let mut input = [0u8; 4 + 4 * 32];
+		// action
+		input[1 * 32..4 + 1 * 32].copy_from_slice(&Into::<u32>::into(incentives::Action::Stake).to_be_bytes());
+		// who
+		U256::from(alice_evm_addr().as_bytes()).to_big_endian(&mut input[4 + 1 * 32..4 + 2 * 32]);
+		// lp_currency_id
+		U256::from(lp_aca_ausd_evm_address().as_bytes()).to_big_endian(&mut input[4 + 2 * 32..4 + 3 * 32]);
+		// amount
+		U256::from(100).to_big_endian(&mut input[4 + 3 * 32..4 + 4 * 32]);
+
+		let expected_gas = <() as module_incentives::WeightInfo>::deposit_dex_share();
+
+		// the gas limit is not enough
+		assert_noop!(
+			IncentivesPrecompile::execute(&input, Some(expected_gas - 1), &context),
+			ExitError::OutOfGas
+		);
+
+		let (reason, output, used_gas) = IncentivesPrecompile::execute(&input, Some(expected_gas), &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, Vec::<u8>::new());
+		assert_eq!(used_gas, expected_gas);
+		assert_eq!(Tokens::reserved_balance(LP_ACA_AUSD, &alice()), 100);
+
+		// array_size + action + who + pool_type + currency_id + reward_currency_id
+		let mut input = [0u8; 4 + 5 * 32];
+		// action
+		input[1 * 32..4 + 1 * 32].copy_from_slice(&Into::<u32>::into(incentives::Action::PendingRewards).to_be_bytes());
+		// who
+		U256::from(alice_evm_addr().as_bytes()).to_big_endian(&mut input[4 + 1 * 32..4 + 2 * 32]);
+		// pool_type of dex incentive
+		U256::from(1).to_big_endian(&mut input[4 + 2 * 32..4 + 3 * 32]);
+		// currency_id
+		U256::from(lp_aca_ausd_evm_address().as_bytes()).to_big_endian(&mut input[4 + 3 * 32..4 + 4 * 32]);
+		// reward_currency_id
+		U256::from(ausd_evm_address().as_bytes()).to_big_endian(&mut input[4 + 4 * 32..4 + 5 * 32]);
+
+		let mut expected_output = [0u8; 32];
+		U256::from(100).to_big_endian(&mut expected_output[..32]);
+
+		let (reason, output, _) = IncentivesPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, expected_output);
+
+		// no rewards of other currencies
+		U256::from(renbtc_evm_address().as_bytes()).to_big_endian(&mut input[4 + 4 * 32..4 + 5 * 32]);
+		let (reason, output, _) = IncentivesPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, [0u8; 32]);
+
+		// action
+		input[1 * 32..4 + 1 * 32].copy_from_slice(&Into::<u32>::into(incentives::Action::ClaimRewards).to_be_bytes());
+
+		let ausd_balance = Currencies::free_balance(AUSD, &alice());
+		let (reason, output, _) = IncentivesPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, Vec::<u8>::new());
+		assert_eq!(Currencies::free_balance(AUSD, &alice()), ausd_balance + 100);
+
+		// invalid pool type
+		U256::from(4).to_big_endian(&mut input[4 + 2 * 32..4 + 3 * 32]);
+		assert_noop!(
+			IncentivesPrecompile::execute(&input, None, &context),
+			ExitError::Other("invalid pool type".into())
+		);
+
+		// array_size + action + who + lp_currency_id + amount
+		let mut input = [0u8; 4 + 4 * 32];
+		// action
+		input[1 * 32..4 + 1 * 32].copy_from_slice(&Into::<u32>::into(incentives::Action::Unstake).to_be_bytes());
+		// who
+		U256::from(alice_evm_addr().as_bytes()).to_big_endian(&mut input[4 + 1 * 32..4 + 2 * 32]);
+		// lp_currency_id
+		U256::from(lp_aca_ausd_evm_address().as_bytes()).to_big_endian(&mut input[4 + 2 * 32..4 + 3 * 32]);
+		// amount
+		U256::from(100).to_big_endian(&mut input[4 + 3 * 32..4 + 4 * 32]);
+
+		let (reason, output, used_gas) = IncentivesPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, Vec::<u8>::new());
+		assert_eq!(used_gas, <() as module_incentives::WeightInfo>::withdraw_dex_share());
+		assert_eq!(Tokens::reserved_balance(LP_ACA_AUSD, &alice()), 0);
+	});
+}
+
+#[test]
+fn honzon_precompile_should_work() {
+	new_test_ext().execute_with(|| {
+		let context = Context {
+			address: Default::default(),
+			caller: alice_evm_addr(),
+			apparent_value: Default::default(),
+		};
+
+		// array_size + action + currency_id
+		let mut input = [0u8; 4 + 2 * 32];
+		// currency_id
+		U256::from(renbtc_evm_address().as_bytes()).to_big_endian(&mut input[4 + 1 * 32..4 + 2 * 32]);
+
+		for (action, value) in vec![
+			(
+				honzon::Action::GetLiquidationRatio,
+				U256::from(Ratio::saturating_from_rational(3, 2).into_inner()),
+			),
+			(
+				honzon::Action::GetInterestRatePerSec,
+				U256::from(Rate::saturating_from_rational(1, 10000).into_inner()),
+			),
+			(honzon::Action::GetMaximumTotalDebitValue, U256::from(10_000)),
+			(honzon::Action::GetTotalDebitValue, U256::from(500)),
+		] {
+			// action
+			input[1 * 32..4 + 1 * 32].copy_from_slice(&Into::<u32>::into(action).to_be_bytes());
+
+			let mut expected_output = [0u8; 32];
+			value.to_big_endian(&mut expected_output[..32]);
+
+			let (reason, output, _) = HonzonPrecompile::execute(&input, None, &context).unwrap();
+			assert_eq!(reason, ExitSucceed::Returned);
+			assert_eq!(output, expected_output);
+		}
+
+		// array_size + action
+		let mut input = [0u8; 4 + 32];
+		// action
+		input[1 * 32..4 + 1 * 32]
+			.copy_from_slice(&Into::<u32>::into(honzon::Action::GetSystemTotalDebitValue).to_be_bytes());
+
+		let mut expected_output = [0u8; 32];
+		U256::from(1_500).to_big_endian(&mut expected_output[..32]);
+
+		let (reason, output, _) = HonzonPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, expected_output);
+
+		// action
+		input[1 * 32..4 + 1 * 32].copy_from_slice(&Into::<u32>::into(honzon::Action::IsShutdown).to_be_bytes());
+
+		let (reason, output, _) = HonzonPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, [0u8; 32]);
+	});
+}
+
+#[test]
+fn multicall_precompile_should_work() {
+	new_test_ext().execute_with(|| {
+		let context = Context {
+			address: Default::default(),
+			caller: alice_evm_addr(),
+			apparent_value: Default::default(),
+		};
+
+		// target + value + allow_failure + input_len, calling an account without code transfers the value
+		let encode_call = |value: Balance, allow_failure: bool| {
+			let mut call = [0u8; 4 * 32];
+			U256::from(bob_evm_addr().as_bytes()).to_big_endian(&mut call[..32]);
+			U256::from(value).to_big_endian(&mut call[32..2 * 32]);
+			U256::from(allow_failure as u8).to_big_endian(&mut call[2 * 32..3 * 32]);
+			call
+		};
+		let encode_input = |calls: Vec<[u8; 4 * 32]>| {
+			// array_size + action + who + storage_limit + offset + calls_len + calls
+			let mut input = vec![0u8; 4 + 5 * 32];
+			// action
+			input[1 * 32..4 + 1 * 32].copy_from_slice(&Into::<u32>::into(multicall::Action::Multicall).to_be_bytes());
+			// who
+			U256::from(alice_evm_addr().as_bytes()).to_big_endian(&mut input[4 + 1 * 32..4 + 2 * 32]);
+			// storage_limit
+			U256::from(1_000).to_big_endian(&mut input[4 + 2 * 32..4 + 3 * 32]);
+			// calls_len
+			U256::from(calls.len() * 4 * 32).to_big_endian(&mut input[4 + 4 * 32..4 + 5 * 32]);
+			for call in calls {
+				input.extend_from_slice(&call);
+			}
+			input
+		};
+
+		let alice_balance = Balances::free_balance(alice());
+		let bob_balance = Balances::free_balance(bob());
+
+		// the failure of the second call is tolerated
+		let input = encode_input(vec![encode_call(100, false), encode_call(INITIAL_BALANCE * 2, true)]);
+
+		// count + (success + output_len) * 2
+		let mut expected_output = [0u8; 5 * 32];
+		U256::from(2).to_big_endian(&mut expected_output[..32]);
+		U256::from(1).to_big_endian(&mut expected_output[32..2 * 32]);
+
+		let (reason, output, used_gas) = MulticallPrecompile::execute(&input, Some(1_000_000), &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, expected_output);
+		assert_eq!(used_gas, 21_000);
+		assert_eq!(Balances::free_balance(alice()), alice_balance - 100);
+		assert_eq!(Balances::free_balance(bob()), bob_balance + 100);
+
+		// the gas limit is not enough
+		assert_noop!(
+			MulticallPrecompile::execute(&input, Some(20_000), &context),
+			ExitError::Other("OutOfGas".into())
+		);
+
+		// all the calls are reverted on the failure of the second call
+		let input = encode_input(vec![encode_call(100, false), encode_call(INITIAL_BALANCE * 2, false)]);
+		assert!(MulticallPrecompile::execute(&input, Some(1_000_000), &context).is_err());
+		assert_eq!(Balances::free_balance(alice()), alice_balance - 100);
+		assert_eq!(Balances::free_balance(bob()), bob_balance + 100);
+
+		// the calls are truncated
+		let mut input = encode_input(vec![encode_call(100, false)]);
+		input.pop();
+		assert_noop!(
+			MulticallPrecompile::execute(&input, Some(1_000_000), &context),
+			ExitError::Other("invalid input".into())
+		);
+	});
+}
+
+#[test]
+fn erc721_precompile_should_work() {
+	new_test_ext().execute_with(|| {
+		let class_account: AccountId = <Test as module_nft::Config>::PalletId::get().into_sub_account(0);
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(alice()),
+			vec![1],
+			module_nft::Properties(module_nft::ClassProperty::Transferable.into())
+		));
+		assert_ok!(Balances::transfer(
+			Origin::signed(alice()),
+			class_account.clone(),
+			1_000
+		));
+		assert_ok!(NFTModule::mint(
+			Origin::signed(class_account),
+			alice(),
+			0,
+			b"ipfs://token".to_vec(),
+			1
+		));
+
+		let context = Context {
+			address: crate::nft_class_address(0),
+			caller: alice_evm_addr(),
+			apparent_value: Default::default(),
+		};
+
+		// action + owner
+		let mut input = [0u8; 4 + 32];
+		input[..4].copy_from_slice(&Into::<u32>::into(erc721::Action::BalanceOf).to_be_bytes());
+		U256::from(alice_evm_addr().as_bytes()).to_big_endian(&mut input[4..4 + 32]);
+
+		let mut expected_output = [0u8; 32];
+		U256::from(1).to_big_endian(&mut expected_output[..32]);
+
+		let (reason, output, _) = ERC721Precompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, expected_output);
+
+		// action + token_id
+		let mut input = [0u8; 4 + 32];
+		input[..4].copy_from_slice(&Into::<u32>::into(erc721::Action::OwnerOf).to_be_bytes());
+
+		let mut expected_output = [0u8; 32];
+		expected_output[12..].copy_from_slice(alice_evm_addr().as_bytes());
+
+		let (reason, output, _) = ERC721Precompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, expected_output);
+
+		input[..4].copy_from_slice(&Into::<u32>::into(erc721::Action::TokenURI).to_be_bytes());
+
+		// offset + length + uri
+		let mut expected_output = [0u8; 3 * 32];
+		U256::from(32).to_big_endian(&mut expected_output[..32]);
+		U256::from(12).to_big_endian(&mut expected_output[32..2 * 32]);
+		expected_output[2 * 32..2 * 32 + 12].copy_from_slice(b"ipfs://token");
+
+		let (reason, output, _) = ERC721Precompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, expected_output);
+
+		U256::from(1).to_big_endian(&mut input[4..4 + 32]);
+		assert_noop!(
+			ERC721Precompile::execute(&input, None, &context),
+			ExitError::Other("token not found".into())
+		);
+
+		// action
+		let input = Into::<u32>::into(erc721::Action::TotalSupply).to_be_bytes();
+
+		let mut expected_output = [0u8; 32];
+		U256::from(1).to_big_endian(&mut expected_output[..32]);
+
+		let (reason, output, _) = ERC721Precompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, expected_output);
+
+		// action + interface_id
+		let mut input = [0u8; 4 + 32];
+		input[..4].copy_from_slice(&Into::<u32>::into(erc721::Action::SupportsInterface).to_be_bytes());
+		input[4..4 + 4].copy_from_slice(&hex!("80ac58cd"));
+
+		let (reason, output, _) = ERC721Precompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, expected_output);
+
+		input[4..4 + 4].copy_from_slice(&hex!("ffffffff"));
+		let (_, output, _) = ERC721Precompile::execute(&input, None, &context).unwrap();
+		assert_eq!(output, [0u8; 32]);
+
+		// action + from + to + token_id
+		let mut input = [0u8; 4 + 3 * 32];
+		input[..4].copy_from_slice(&Into::<u32>::into(erc721::Action::TransferFrom).to_be_bytes());
+		U256::from(alice_evm_addr().as_bytes()).to_big_endian(&mut input[4..4 + 32]);
+		U256::from(bob_evm_addr().as_bytes()).to_big_endian(&mut input[4 + 32..4 + 2 * 32]);
+
+		let expected_gas = <() as module_nft::WeightInfo>::transfer();
+
+		// the gas limit is not enough
+		assert_noop!(
+			ERC721Precompile::execute(&input, Some(expected_gas - 1), &context),
+			ExitError::OutOfGas
+		);
+
+		// only the owner can transfer
+		let bob_context = Context {
+			address: context.address,
+			caller: bob_evm_addr(),
+			apparent_value: Default::default(),
+		};
+		assert_noop!(
+			ERC721Precompile::execute(&input, None, &bob_context),
+			ExitError::Other("no permission".into())
+		);
+
+		let (reason, output, used_gas) = ERC721Precompile::execute(&input, Some(expected_gas), &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, Vec::<u8>::new());
+		assert_eq!(used_gas, expected_gas);
+		assert_eq!(<NFTModule as orml_traits::NFT<AccountId>>::owner((0, 0)), Some(bob()));
+
+		System::assert_last_event(TestEvent::ModuleEVM(module_evm::Event::Log(module_evm::Log {
+			address: context.address,
+			topics: vec![
+				H256(sp_io::hashing::keccak_256(b"Transfer(address,address,uint256)")),
+				alice_evm_addr().into(),
+				bob_evm_addr().into(),
+				H256::zero(),
+			],
+			data: vec![],
+		})));
+
+		// the class doesn't exist
+		let context = Context {
+			address: crate::nft_class_address(1),
+			caller: alice_evm_addr(),
+			apparent_value: Default::default(),
+		};
+		assert_noop!(
+			ERC721Precompile::execute(&input, None, &context),
+			ExitError::Other("class not found".into())
+		);
+	});
+}
+
 #[test]
 fn task_id_max_and_min() {
 	let task_id = TaskInfo {
@@ -912,4 +1745,37 @@ fn task_id_max_and_min() {
 	.encode();
 
 	assert_eq!(38, task_id.len());
+
+	let task_id = PeriodicTaskInfo {
+		prefix: b"SchedulePeriodicCall".to_vec(),
+		id: u32::MAX,
+		sender: H160::default(),
+		fee: Balance::MAX,
+		start: u32::MAX,
+		period: u32::MAX,
+		count: u32::MAX,
+	}
+	.encode();
+
+	assert_eq!(74, task_id.len());
+}
+
+#[test]
+fn periodic_task_remaining_fee() {
+	let task_info = PeriodicTaskInfo {
+		prefix: b"SchedulePeriodicCall".to_vec(),
+		id: 0,
+		sender: H160::default(),
+		fee: 10,
+		start: 3,
+		period: 2,
+		count: 3,
+	};
+
+	assert_eq!(task_info.remaining_fee(2), 30);
+	assert_eq!(task_info.remaining_fee(3), 20);
+	assert_eq!(task_info.remaining_fee(4), 20);
+	assert_eq!(task_info.remaining_fee(5), 10);
+	assert_eq!(task_info.remaining_fee(7), 0);
+	assert_eq!(task_info.remaining_fee(100), 0);
 }
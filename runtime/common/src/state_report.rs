@@ -0,0 +1,111 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! The report of the size of the storage items that grow with the usage of the chain.
+
+// The `unnecessary_mut_passed` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::{Decode, Encode};
+use sp_io::hashing::twox_128;
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+/// The storage items of the state report, by the name of the pallet in the runtime and the name
+/// of the storage item.
+pub const STATE_REPORT_ITEMS: &[(&str, &str)] = &[
+	("System", "Account"),
+	("Tokens", "Accounts"),
+	("Loans", "Positions"),
+	("Auction", "Auctions"),
+	("AuctionManager", "CollateralAuctions"),
+	("Rewards", "Pools"),
+	("Rewards", "ShareAndWithdrawnReward"),
+	("EvmAccounts", "Accounts"),
+	("EvmAccounts", "EvmAddresses"),
+	("EVM", "Accounts"),
+	("EVM", "AccountStorages"),
+	("EVM", "Codes"),
+	("OrmlNFT", "Tokens"),
+];
+
+/// The number of entries and the approximate size of a storage item.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct StorageItemReport {
+	/// The name of the pallet in the runtime.
+	pub pallet: Vec<u8>,
+	/// The name of the storage item.
+	pub item: Vec<u8>,
+	/// The number of the entries.
+	pub count: u32,
+	/// The total size of the keys and the encoded values of the entries in bytes, without the
+	/// overhead of the trie nodes.
+	pub size: u64,
+}
+
+/// Count the entries of the storage item `item` of `pallet` and sum up their size.
+///
+/// All the keys of the storage item are iterated, so this must only be called off-chain, e.g. in
+/// a runtime API.
+pub fn storage_item_report(pallet: &str, item: &str) -> StorageItemReport {
+	let prefix = [twox_128(pallet.as_bytes()), twox_128(item.as_bytes())].concat();
+
+	let mut count: u32 = 0;
+	let mut size: u64 = 0;
+	let mut key = prefix.clone();
+	// a storage value is stored at the prefix itself
+	let mut value_len = sp_io::storage::read(&key, &mut [], 0);
+	loop {
+		if let Some(len) = value_len {
+			count = count.saturating_add(1);
+			size = size.saturating_add(key.len() as u64).saturating_add(len.into());
+		}
+
+		match sp_io::storage::next_key(&key) {
+			Some(next_key) if next_key.starts_with(&prefix) => {
+				value_len = sp_io::storage::read(&next_key, &mut [], 0);
+				key = next_key;
+			}
+			_ => break,
+		}
+	}
+
+	StorageItemReport {
+		pallet: pallet.as_bytes().to_vec(),
+		item: item.as_bytes().to_vec(),
+		count,
+		size,
+	}
+}
+
+/// The reports of the storage `items`, by the name of the pallet and the name of the storage item.
+pub fn state_report(items: &[(&str, &str)]) -> Vec<StorageItemReport> {
+	items
+		.iter()
+		.map(|(pallet, item)| storage_item_report(pallet, item))
+		.collect()
+}
+
+sp_api::decl_runtime_apis! {
+	pub trait StateReportApi {
+		/// The number of entries and the approximate size of the storage items that grow with the
+		/// usage of the chain, e.g. the positions, the auctions, the EVM accounts and the reward
+		/// pools.
+		fn get_state_report() -> Vec<StorageItemReport>;
+	}
+}
@@ -0,0 +1,72 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! The background tasks of the modules, dispatched by the idle scheduler in the weight left in
+//! blocks.
+
+pub use frame_support::weights::Weight;
+pub use module_support::{DispatchableTask, TaskResult};
+
+/// Defines the combined task of the background tasks of the modules in runtime, which is the
+/// `Task` of the idle scheduler. Each variant wraps the task of a module, which is converted from
+/// it.
+///
+/// ```ignore
+/// define_combined_task! {
+/// 	#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug)]
+/// 	pub enum ScheduledTasks {
+/// 		EvmTask(EvmTask<Runtime>),
+/// 	}
+/// }
+/// ```
+#[macro_export]
+macro_rules! define_combined_task {
+	(
+		$(#[$meta:meta])*
+		$vis:vis enum $combined_name:ident {
+			$(
+				$task:ident ( $vtask:ident $(<$($generic:ty),*>)? )
+			),+ $(,)?
+		}
+	) => {
+		$(#[$meta])*
+		$vis enum $combined_name {
+			$(
+				$task($vtask $(<$($generic),*>)?),
+			)+
+		}
+
+		impl $crate::tasks::DispatchableTask for $combined_name {
+			fn dispatch(&mut self, weight: $crate::tasks::Weight) -> $crate::tasks::TaskResult {
+				match self {
+					$(
+						$combined_name::$task(task) => $crate::tasks::DispatchableTask::dispatch(task, weight),
+					)+
+				}
+			}
+		}
+
+		$(
+			impl From<$vtask $(<$($generic),*>)?> for $combined_name {
+				fn from(task: $vtask $(<$($generic),*>)?) -> Self {
+					$combined_name::$task(task)
+				}
+			}
+		)+
+	};
+}
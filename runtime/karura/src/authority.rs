@@ -35,6 +35,9 @@ parameter_types! {
 	pub const OneDay: BlockNumber = DAYS;
 }
 
+/// The emergency origin which may veto the dispatches scheduled by any origin during their delay.
+type VetoOrigin = EnsureRootOrTwoThirdsTechnicalCommittee;
+
 pub struct AuthorityConfigImpl;
 impl orml_authority::AuthorityConfig<Origin, OriginCaller, BlockNumber> for AuthorityConfigImpl {
 	fn check_schedule_dispatch(origin: Origin, _priority: Priority) -> DispatchResult {
@@ -70,7 +73,8 @@ impl orml_authority::AuthorityConfig<Origin, OriginCaller, BlockNumber> for Auth
 	fn check_cancel_schedule(origin: Origin, initial_origin: &OriginCaller) -> DispatchResult {
 		ensure_root(origin.clone()).or_else(|_| {
 			if origin.caller() == initial_origin
-				|| EnsureRootOrThreeFourthsGeneralCouncil::ensure_origin(origin).is_ok()
+				|| EnsureRootOrThreeFourthsGeneralCouncil::ensure_origin(origin.clone()).is_ok()
+				|| VetoOrigin::ensure_origin(origin).is_ok()
 			{
 				Ok(())
 			} else {
@@ -16,11 +16,12 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{dollar, AccountId, EvmAccounts, Runtime, KAR};
+use crate::{dollar, AccountId, EvmAccounts, EvmAccountsUnbindDelay, Runtime, System, KAR};
 
 use super::utils::set_aca_balance;
 use codec::Encode;
 use frame_benchmarking::{account, whitelisted_caller};
+use frame_support::traits::Get;
 use frame_system::RawOrigin;
 use orml_benchmarking::runtime_benchmarks;
 use sp_io::hashing::keccak_256;
@@ -55,6 +56,24 @@ runtime_benchmarks! {
 	claim_default_account {
 		let caller = whitelisted_caller();
   }: _(RawOrigin::Signed(caller))
+
+	request_unbind {
+		let caller: AccountId = whitelisted_caller();
+		EvmAccounts::claim_default_account(RawOrigin::Signed(caller.clone()).into())?;
+	}: _(RawOrigin::Signed(caller))
+
+	cancel_unbind {
+		let caller: AccountId = whitelisted_caller();
+		EvmAccounts::claim_default_account(RawOrigin::Signed(caller.clone()).into())?;
+		EvmAccounts::request_unbind(RawOrigin::Signed(caller.clone()).into())?;
+	}: _(RawOrigin::Signed(caller))
+
+	unbind_account {
+		let caller: AccountId = whitelisted_caller();
+		EvmAccounts::claim_default_account(RawOrigin::Signed(caller.clone()).into())?;
+		EvmAccounts::request_unbind(RawOrigin::Signed(caller.clone()).into())?;
+		System::set_block_number(System::block_number() + EvmAccountsUnbindDelay::get());
+	}: _(RawOrigin::Signed(caller))
 }
 
 #[cfg(test)]
@@ -17,18 +17,22 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-	dollar, AccountId, AccumulatePeriod, CollateralCurrencyIds, Currencies, CurrencyId, GetNativeCurrencyId,
-	GetStableCurrencyId, Incentives, Rate, Rewards, Runtime, System, TokenSymbol, KAR, KSM, KUSD, LKSM,
+	dollar, AccountId, AccumulatePeriod, BlockNumber, CollateralCurrencyIds, Currencies, CurrencyId, Dex,
+	GetNativeCurrencyId, GetStableCurrencyId, Incentives, MaxLoyaltyTiers, MaxTopUps, Rate, Rewards, Runtime, System,
+	TokenSymbol, KAR, KSM, KUSD, LKSM,
 };
 
 use super::utils::set_balance;
 use frame_benchmarking::{account, whitelisted_caller};
 use frame_support::traits::OnInitialize;
 use frame_system::RawOrigin;
-use module_incentives::PoolId;
+use module_incentives::{
+	DecayCurve, DepositLimits, EmissionSchedule, LoyaltyTier, OnUpdateLoan, PoolId, RewardsVestingParams,
+};
 use orml_benchmarking::runtime_benchmarks;
-use orml_traits::MultiCurrency;
-use primitives::DexShare;
+use orml_traits::{Happened, MultiCurrency};
+use primitives::{DexShare, TradingPair};
+use sp_runtime::FixedPointNumber;
 use sp_std::prelude::*;
 
 const SEED: u32 = 0;
@@ -48,7 +52,7 @@ runtime_benchmarks! {
 			let pool_id = PoolId::LoansIncentive(currency_id);
 
 			Incentives::update_incentive_rewards(RawOrigin::Root.into(), vec![(pool_id.clone(), 100 * dollar(KAR))])?;
-			orml_rewards::Pools::<Runtime>::mutate(pool_id, |pool_info| {
+			module_rewards::Pools::<Runtime>::mutate(pool_id, |pool_info| {
 				pool_info.total_shares += 100;
 			});
 		}
@@ -95,6 +99,188 @@ runtime_benchmarks! {
 		}
 	}: _(RawOrigin::Root, updates)
 
+	update_extra_incentive_rewards {
+		let c in 0 .. CollateralCurrencyIds::get().len().saturating_sub(1) as u32;
+		let currency_ids = CollateralCurrencyIds::get();
+		let mut updates = vec![];
+
+		for i in 0 .. c {
+			let currency_id = currency_ids[i as usize];
+			updates.push((PoolId::LoansIncentive(currency_id), GetStableCurrencyId::get(), 100 * dollar(KUSD)));
+		}
+	}: _(RawOrigin::Root, updates)
+
+	update_rewards_vesting {
+		let c in 0 .. CollateralCurrencyIds::get().len().saturating_sub(1) as u32;
+		let currency_ids = CollateralCurrencyIds::get();
+		let mut updates = vec![];
+
+		for i in 0 .. c {
+			let currency_id = currency_ids[i as usize];
+			updates.push((PoolId::LoansIncentive(currency_id), Some(RewardsVestingParams {
+				period: 100,
+				early_exit_penalty_rate: Rate::saturating_from_rational(50, 100),
+			})));
+		}
+	}: _(RawOrigin::Root, updates)
+
+	update_emission_schedules {
+		let c in 0 .. CollateralCurrencyIds::get().len().saturating_sub(1) as u32;
+		let currency_ids = CollateralCurrencyIds::get();
+		let mut updates = vec![];
+
+		for i in 0 .. c {
+			let currency_id = currency_ids[i as usize];
+			updates.push((PoolId::LoansIncentive(currency_id), Some(EmissionSchedule {
+				start: 100,
+				end: 1000,
+				initial_reward_amount: 100 * dollar(KAR),
+				curve: DecayCurve::Exponential {
+					period: 100,
+					rate: Rate::saturating_from_rational(10, 100),
+				},
+			})));
+		}
+	}: _(RawOrigin::Root, updates)
+
+	set_deposit_limits {
+	}: _(RawOrigin::Root, PoolId::DexIncentive(BTC_AUSD_LP), Some(DepositLimits {
+		max_total_shares: Some(1_000_000 * dollar(KUSD)),
+		max_account_shares: Some(10_000 * dollar(KUSD)),
+		whitelist_only: true,
+	}))
+
+	update_deposit_whitelist {
+		let c in 0 .. 100;
+		let updates: Vec<(AccountId, bool)> = (0 .. c).map(|i| (account("caller", i, SEED), true)).collect();
+	}: _(RawOrigin::Root, PoolId::DexIncentive(BTC_AUSD_LP), updates)
+
+	update_deduction_exemptions {
+		let c in 0 .. 100;
+		let updates: Vec<(AccountId, bool)> = (0 .. c).map(|i| (account("caller", i, SEED), true)).collect();
+	}: _(RawOrigin::Root, PoolId::DexIncentive(BTC_AUSD_LP), updates)
+
+	update_loyalty_tiers {
+		let c in 0 .. CollateralCurrencyIds::get().len().saturating_sub(1) as u32;
+		let currency_ids = CollateralCurrencyIds::get();
+		let tiers: Vec<LoyaltyTier<BlockNumber>> = (0 .. MaxLoyaltyTiers::get()).map(|i| LoyaltyTier {
+			min_duration: (i + 1) * 100,
+			deduction_rate: Rate::saturating_from_rational(MaxLoyaltyTiers::get() - i, 100),
+		}).collect();
+		let mut updates = vec![];
+
+		for i in 0 .. c {
+			let currency_id = currency_ids[i as usize];
+			updates.push((PoolId::LoansIncentive(currency_id), tiers.clone()));
+		}
+	}: _(RawOrigin::Root, updates)
+
+	update_loans_risk_weights {
+		let c in 0 .. CollateralCurrencyIds::get().len().saturating_sub(1) as u32;
+		let currency_ids = CollateralCurrencyIds::get();
+		let mut updates = vec![];
+
+		for i in 0 .. c {
+			let currency_id = currency_ids[i as usize];
+			updates.push((currency_id, Some(Rate::saturating_from_rational(50, 100))));
+		}
+	}: _(RawOrigin::Root, updates)
+
+	claim_escrowed_rewards {
+		let caller: AccountId = whitelisted_caller();
+		let pool_id = PoolId::LoansIncentive(KSM);
+		let native_currency_id = GetNativeCurrencyId::get();
+
+		Incentives::update_rewards_vesting(RawOrigin::Root.into(), vec![(pool_id.clone(), Some(RewardsVestingParams {
+			period: 100,
+			early_exit_penalty_rate: Rate::saturating_from_rational(50, 100),
+		}))])?;
+		Rewards::add_share(&caller, &pool_id, 100);
+		Currencies::deposit(native_currency_id, &<Runtime as module_incentives::Config>::RewardsVaultAccountId::get(), 80 * dollar(native_currency_id))?;
+		Rewards::accumulate_reward(&pool_id, 80 * dollar(native_currency_id));
+		Incentives::claim_rewards(RawOrigin::Signed(caller.clone()).into(), pool_id.clone())?;
+		System::set_block_number(50);
+	}: _(RawOrigin::Signed(caller), pool_id, native_currency_id, true)
+
+	update_boost {
+		let caller: AccountId = whitelisted_caller();
+		let pool_id = PoolId::LoansIncentive(KSM);
+
+		OnUpdateLoan::<Runtime>::happened(&(caller.clone(), KSM, 100, 0));
+	}: _(RawOrigin::Signed(caller.clone()), caller, pool_id)
+
+	claim_all_rewards {
+		let c in 0 .. CollateralCurrencyIds::get().len() as u32;
+		let currency_ids = CollateralCurrencyIds::get();
+		let caller: AccountId = whitelisted_caller();
+		let native_currency_id = GetNativeCurrencyId::get();
+
+		let mut pool_ids = vec![];
+
+		Currencies::deposit(native_currency_id, &<Runtime as module_incentives::Config>::RewardsVaultAccountId::get(), 100 * c as u128 * dollar(native_currency_id))?;
+		for i in 0 .. c {
+			let pool_id = PoolId::LoansIncentive(currency_ids[i as usize]);
+			Rewards::add_share(&caller, &pool_id, 100);
+			Rewards::accumulate_reward(&pool_id, 100 * dollar(native_currency_id));
+			pool_ids.push(pool_id);
+		}
+	}: _(RawOrigin::Signed(caller), pool_ids)
+
+	compound_rewards {
+		let caller: AccountId = whitelisted_caller();
+		let trading_pair = TradingPair::new(KUSD, KAR);
+		let pool_id = PoolId::DexIncentive(trading_pair.get_dex_share_currency_id().unwrap());
+
+		set_balance(trading_pair.0, &caller, 10_000 * dollar(trading_pair.0));
+		set_balance(trading_pair.1, &caller, 10_000 * dollar(trading_pair.1));
+		let _ = Dex::enable_trading_pair(RawOrigin::Root.into(), trading_pair.0, trading_pair.1);
+		Dex::add_liquidity(
+			RawOrigin::Signed(caller.clone()).into(),
+			trading_pair.0,
+			trading_pair.1,
+			10_000 * dollar(trading_pair.0),
+			10_000 * dollar(trading_pair.1),
+			Default::default(),
+			true,
+		)?;
+		Currencies::deposit(KAR, &<Runtime as module_incentives::Config>::RewardsVaultAccountId::get(), 100 * dollar(KAR))?;
+		Rewards::accumulate_reward(&pool_id, 100 * dollar(KAR));
+	}: _(RawOrigin::Signed(caller), pool_id, 0, 0)
+
+	top_up_rewards {
+		let caller: AccountId = whitelisted_caller();
+		let pool_id = PoolId::LoansIncentive(CollateralCurrencyIds::get()[0]);
+
+		set_balance(KUSD, &caller, 10_000 * dollar(KUSD));
+		Rewards::add_share(&caller, &pool_id, 100);
+		Incentives::update_top_up_currencies(RawOrigin::Root.into(), vec![(KUSD, Some(dollar(KUSD)))])?;
+		for _ in 1 .. MaxTopUps::get() {
+			Incentives::top_up_rewards(RawOrigin::Signed(caller.clone()).into(), pool_id.clone(), KUSD, dollar(KUSD), 10)?;
+		}
+	}: _(RawOrigin::Signed(caller), pool_id, KUSD, dollar(KUSD), 10)
+
+	update_top_up_currencies {
+		let c in 0 .. CollateralCurrencyIds::get().len() as u32;
+		let currency_ids = CollateralCurrencyIds::get();
+		let mut values = vec![];
+
+		for i in 0 .. c {
+			let currency_id = currency_ids[i as usize];
+			values.push((currency_id, Some(dollar(currency_id))));
+		}
+	}: _(RawOrigin::Root, values)
+
+	cancel_top_up {
+		let caller: AccountId = whitelisted_caller();
+		let pool_id = PoolId::LoansIncentive(CollateralCurrencyIds::get()[0]);
+
+		set_balance(KUSD, &caller, 10_000 * dollar(KUSD));
+		Rewards::add_share(&caller, &pool_id, 100);
+		Incentives::update_top_up_currencies(RawOrigin::Root.into(), vec![(KUSD, Some(dollar(KUSD)))])?;
+		Incentives::top_up_rewards(RawOrigin::Signed(caller).into(), pool_id.clone(), KUSD, dollar(KUSD), 10)?;
+		let top_up_id = Incentives::next_top_up_id() - 1;
+	}: _(RawOrigin::Root, pool_id, top_up_id)
+
 	update_dex_saving_rewards {
 		let c in 0 .. CollateralCurrencyIds::get().len().saturating_sub(1) as u32;
 		let currency_ids = CollateralCurrencyIds::get();
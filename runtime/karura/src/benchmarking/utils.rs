@@ -67,9 +67,14 @@ pub fn feed_price(currency_id: CurrencyId, price: Price) -> DispatchResult {
 #[cfg(test)]
 pub mod tests {
 	pub fn new_test_ext() -> sp_io::TestExternalities {
-		frame_system::GenesisConfig::default()
+		let mut ext: sp_io::TestExternalities = frame_system::GenesisConfig::default()
 			.build_storage::<crate::Runtime>()
 			.unwrap()
-			.into()
+			.into();
+		// the session keys are generated in the keystore
+		ext.register_extension(sp_keystore::KeystoreExt(std::sync::Arc::new(
+			sp_keystore::testing::KeyStore::new(),
+		)));
+		ext
 	}
 }
@@ -38,7 +38,7 @@ use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_core::{
 	crypto::KeyTypeId,
 	u32_trait::{_1, _2, _3, _4},
-	OpaqueMetadata, H160,
+	OpaqueMetadata, H160, H256,
 };
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
@@ -105,12 +105,15 @@ pub use sp_runtime::{Perbill, Percent, Permill, Perquintill};
 pub use authority::AuthorityConfigImpl;
 pub use constants::{fee::*, time::*};
 pub use primitives::{
-	evm::EstimateResourcesRequest, AccountId, AccountIndex, Amount, AuctionId, AuthoritysOriginId, Balance,
-	BlockNumber, CurrencyId, DataProviderId, EraIndex, Hash, Moment, Nonce, ReserveIdentifier, Share, Signature,
-	TokenSymbol, TradingPair,
+	evm::{
+		BlockFeeInfo, ContractMetadata, ContractStatus, DryRunInfo, EstimateFeeInfo, EstimateResourcesRequest,
+		EvmTransaction, LogsBloom, Receipt, TraceInfo, TracerConfig,
+	},
+	AccountId, AccountIndex, Amount, AuctionId, AuthoritysOriginId, Balance, BlockNumber, CurrencyId, DataProviderId,
+	EraIndex, Hash, Moment, Nonce, ReserveIdentifier, Share, Signature, TokenSymbol, TradingPair,
 };
 pub use runtime_common::{
-	cent, dollar, microcent, millicent, CurveFeeModel, ExchangeRate, GasToWeight, Price, Rate, Ratio,
+	cent, dollar, microcent, millicent, BlockGasTarget, CurveFeeModel, ExchangeRate, GasToWeight, Price, Rate, Ratio,
 	RelaychainBlockNumberProvider, RuntimeBlockLength, RuntimeBlockWeights, SystemContractsFilter, TimeStampedPrice,
 	KAR, KSM, KUSD, LKSM, RENBTC,
 };
@@ -155,6 +158,8 @@ parameter_types! {
 	pub const CDPTreasuryPalletId: PalletId = PalletId(*b"aca/cdpt");
 	pub const StakingPoolPalletId: PalletId = PalletId(*b"aca/stkp");
 	pub const HonzonTreasuryPalletId: PalletId = PalletId(*b"aca/hztr");
+	pub const SavingRatePalletId: PalletId = PalletId(*b"aca/svrt");
+	pub const StabilityBufferPalletId: PalletId = PalletId(*b"aca/stbf");
 	pub const HomaTreasuryPalletId: PalletId = PalletId(*b"aca/hmtr");
 	pub const IncentivesPalletId: PalletId = PalletId(*b"aca/inct");
 	// Treasury reserve
@@ -172,6 +177,8 @@ pub fn get_all_module_accounts() -> Vec<AccountId> {
 		CDPTreasuryPalletId::get().into_account(),
 		StakingPoolPalletId::get().into_account(),
 		HonzonTreasuryPalletId::get().into_account(),
+		SavingRatePalletId::get().into_account(),
+		StabilityBufferPalletId::get().into_account(),
 		HomaTreasuryPalletId::get().into_account(),
 		IncentivesPalletId::get().into_account(),
 		TreasuryReservePalletId::get().into_account(),
@@ -274,12 +281,24 @@ impl pallet_session::Config for Runtime {
 	type WeightInfo = ();
 }
 
+/// The session keys prove the possession with the signature of the aura key.
+pub struct SessionKeysOwnershipProof;
+impl module_collator_selection::KeysOwnershipProof<AccountId, SessionKeys> for SessionKeysOwnershipProof {
+	fn verify(who: &AccountId, keys: &SessionKeys, proof: &[u8]) -> bool {
+		let payload = module_collator_selection::keys_ownership_payload(who);
+		<AuraId as sp_runtime::RuntimeAppPublic>::Signature::decode(&mut &proof[..]).map_or(false, |signature| {
+			sp_runtime::RuntimeAppPublic::verify(&keys.aura, &payload, &signature)
+		})
+	}
+}
+
 parameter_types! {
 	pub const PotId: PalletId = PalletId(*b"PotStake");
 	pub const MinCandidates: u32 = 5;
 	pub const MaxCandidates: u32 = 200;
 	pub const MaxInvulnerables: u32 = 50;
 	pub const CollatorKickThreshold: Permill = Permill::from_percent(50);
+	pub const CollatorUnbondingDelay: u32 = 28;
 }
 
 impl module_collator_selection::Config for Runtime {
@@ -292,6 +311,8 @@ impl module_collator_selection::Config for Runtime {
 	type MaxCandidates = MaxCandidates;
 	type MaxInvulnerables = MaxInvulnerables;
 	type CollatorKickThreshold = CollatorKickThreshold;
+	type UnbondingDelay = CollatorUnbondingDelay;
+	type KeysOwnershipProof = SessionKeysOwnershipProof;
 	type WeightInfo = weights::module_collator_selection::WeightInfo<Runtime>;
 }
 
@@ -792,6 +813,9 @@ parameter_type_with_key! {
 			},
 			CurrencyId::Erc20(_) => Balance::max_value(), // not handled by orml-tokens
 			CurrencyId::ChainSafe(_) => Balance::max_value(), // TODO: update this before we enable ChainSafe bridge
+			CurrencyId::ForeignAsset(_) => Balance::max_value(), // TODO: update this before we enable asset registry
+			CurrencyId::LaunchpadToken(_) => Balance::max_value(), // not supported
+			CurrencyId::LiquidCrowdloan(_) => Balance::max_value(), // not supported
 		}
 	};
 }
@@ -807,7 +831,7 @@ impl orml_tokens::Config for Runtime {
 	type CurrencyId = CurrencyId;
 	type WeightInfo = weights::orml_tokens::WeightInfo<Runtime>;
 	type ExistentialDeposits = ExistentialDeposits;
-	type OnDust = orml_tokens::TransferDust<Runtime, KaruraTreasuryAccount>;
+	type OnDust = module_currencies::SweepDust<Runtime, Dex, KaruraTreasuryAccount>;
 	type MaxLocks = MaxLocks;
 }
 
@@ -844,6 +868,11 @@ parameter_types! {
 	pub const GetStakingCurrencyId: CurrencyId = KSM;
 }
 
+parameter_types! {
+	pub const MaxMemoLength: u32 = 128;
+	pub const MaxBatchTransfers: u32 = 100;
+}
+
 impl module_currencies::Config for Runtime {
 	type Event = Event;
 	type MultiCurrency = Tokens;
@@ -852,6 +881,10 @@ impl module_currencies::Config for Runtime {
 	type WeightInfo = weights::module_currencies::WeightInfo<Runtime>;
 	type AddressMapping = EvmAddressMapping<Runtime>;
 	type EVMBridge = EVMBridge;
+	type UpdateOrigin = EnsureRootOrHalfFinancialCouncil;
+	type MaxMemoLength = MaxMemoLength;
+	type MaxBatchTransfers = MaxBatchTransfers;
+	type FreezeChecker = ();
 }
 
 parameter_types! {
@@ -934,6 +967,8 @@ impl module_auction_manager::Config for Runtime {
 	type PriceSource = Prices;
 	type UnsignedPriority = runtime_common::AuctionManagerUnsignedPriority;
 	type EmergencyShutdown = EmergencyShutdown;
+	type EventTopics = EventTopics;
+	type IdleScheduler = ();
 	type WeightInfo = weights::module_auction_manager::WeightInfo<Runtime>;
 }
 
@@ -945,6 +980,7 @@ impl module_loans::Config for Runtime {
 	type CDPTreasury = CdpTreasury;
 	type PalletId = LoansPalletId;
 	type OnUpdateLoan = module_incentives::OnUpdateLoan<Runtime>;
+	type EventTopics = EventTopics;
 }
 
 impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Runtime
@@ -1013,6 +1049,7 @@ parameter_types! {
 	pub DefaultLiquidationPenalty: Rate = Rate::saturating_from_rational(8, 100);
 	pub MinimumDebitValue: Balance = dollar(KUSD);
 	pub MaxSlippageSwapWithDEX: Ratio = Ratio::saturating_from_rational(15, 100);
+	pub const FeeTankWithdrawalDelay: BlockNumber = 7 * DAYS;
 }
 
 impl module_cdp_engine::Config for Runtime {
@@ -1051,6 +1088,8 @@ impl module_emergency_shutdown::Config for Runtime {
 	type CDPTreasury = CdpTreasury;
 	type AuctionManagerHandler = AuctionManager;
 	type ShutdownOrigin = EnsureRoot<AccountId>;
+	// the DEX swaps are kept open in the emergency shutdown
+	type OnShutdown = Incentives;
 	type WeightInfo = weights::module_emergency_shutdown::WeightInfo<Runtime>;
 }
 
@@ -1069,11 +1108,21 @@ impl module_dex::Config for Runtime {
 	type DEXIncentives = Incentives;
 	type WeightInfo = weights::module_dex::WeightInfo<Runtime>;
 	type ListingOrigin = EnsureRootOrHalfGeneralCouncil;
+	type OnSwap = ();
+	type EventTopics = EventTopics;
+	type SwapFeeShare = ();
+	type SwapFeeCurrencyId = GetNativeCurrencyId;
+	type SwapFeeReceiver = KaruraTreasuryAccount;
 }
 
+impl module_event_topics::Config for Runtime {}
+
 parameter_types! {
 	pub const MaxAuctionsCount: u32 = 100;
 	pub HonzonTreasuryAccount: AccountId = HonzonTreasuryPalletId::get().into_account();
+	pub SavingRateAccount: AccountId = SavingRatePalletId::get().into_account();
+	pub StabilityBufferAccount: AccountId = StabilityBufferPalletId::get().into_account();
+	pub const SurplusAllocationPeriod: BlockNumber = DAYS;
 }
 
 impl module_cdp_treasury::Config for Runtime {
@@ -1086,6 +1135,11 @@ impl module_cdp_treasury::Config for Runtime {
 	type MaxAuctionsCount = MaxAuctionsCount;
 	type PalletId = CDPTreasuryPalletId;
 	type TreasuryAccount = HonzonTreasuryAccount;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type SavingRateAccount = SavingRateAccount;
+	type StabilityBufferAccount = StabilityBufferAccount;
+	type SurplusAllocationPeriod = SurplusAllocationPeriod;
+	type MaxSlippageSwapWithDEX = MaxSlippageSwapWithDEX;
 	type WeightInfo = weights::module_cdp_treasury::WeightInfo<Runtime>;
 }
 
@@ -1109,6 +1163,8 @@ impl OnUnbalanced<NegativeImbalance> for DealWithFees {
 }
 
 impl module_transaction_payment::Config for Runtime {
+	type Event = Event;
+	type Call = Call;
 	type AllNonNativeCurrencyIds = AllNonNativeCurrencyIds;
 	type NativeCurrencyId = GetNativeCurrencyId;
 	type StableCurrencyId = GetStableCurrencyId;
@@ -1120,23 +1176,37 @@ impl module_transaction_payment::Config for Runtime {
 	type FeeMultiplierUpdate = SlowAdjustingFeeUpdate<Self>;
 	type DEX = Dex;
 	type MaxSlippageSwapWithDEX = MaxSlippageSwapWithDEX;
+	type OnFeePaid = ();
+	type SponsorCallClass = ProxyType;
+	type SponsorPublic = <Signature as sp_runtime::traits::Verify>::Signer;
+	type SponsorSignature = Signature;
+	type UpdateOrigin = EnsureRootOrHalfGeneralCouncil;
+	type FeeTankWithdrawalDelay = FeeTankWithdrawalDelay;
 	type WeightInfo = weights::module_transaction_payment::WeightInfo<Runtime>;
 }
 
+parameter_types! {
+	pub const EvmAccountsUnbindDelay: BlockNumber = 7 * DAYS;
+}
+
 impl module_evm_accounts::Config for Runtime {
 	type Event = Event;
 	type Currency = Balances;
 	type AddressMapping = EvmAddressMapping<Runtime>;
 	type TransferAll = Currencies;
+	type UnbindDelay = EvmAccountsUnbindDelay;
 	type WeightInfo = weights::module_evm_accounts::WeightInfo<Runtime>;
 }
 
 impl module_evm_manager::Config for Runtime {
+	type Event = Event;
 	type Currency = Balances;
 	type EVMBridge = EVMBridge;
+	type RegisterOrigin = EnsureRootOrHalfGeneralCouncil;
+	type AssetRegistry = ();
 }
 
-impl orml_rewards::Config for Runtime {
+impl module_rewards::Config for Runtime {
 	type Share = Balance;
 	type Balance = Balance;
 	type PoolId = module_incentives::PoolId<AccountId>;
@@ -1145,12 +1215,19 @@ impl orml_rewards::Config for Runtime {
 
 parameter_types! {
 	pub const AccumulatePeriod: BlockNumber = MINUTES;
+	pub const MaxClaimAllPools: u32 = 50;
+	pub const MaxLoyaltyTiers: u32 = 10;
+	pub const MaxTopUps: u32 = 20;
+	pub const MaxEscrowedRewards: u32 = 20;
+	pub const MaxLoansRiskWeights: u32 = 20;
+	pub const IncentiveRewardsBudget: Balance = Balance::max_value();
 }
 
 impl module_incentives::Config for Runtime {
 	type Event = Event;
 	type RelaychainAccountId = AccountId;
 	type NativeRewardsSource = UnreleasedNativeVaultAccountId;
+	type ExtraRewardsSource = KaruraTreasuryAccount;
 	type RewardsVaultAccountId = ZeroAccountId;
 	type NativeCurrencyId = GetNativeCurrencyId;
 	type StableCurrencyId = GetStableCurrencyId;
@@ -1160,14 +1237,23 @@ impl module_incentives::Config for Runtime {
 	type CDPTreasury = CdpTreasury;
 	type Currency = Currencies;
 	type DEX = Dex;
-	type EmergencyShutdown = EmergencyShutdown;
+	type VotingEscrow = ();
+	type IncentiveRewardsBudget = IncentiveRewardsBudget;
+	type MaxClaimAllPools = MaxClaimAllPools;
+	type MaxLoyaltyTiers = MaxLoyaltyTiers;
+	type MaxTopUps = MaxTopUps;
+	type MaxEscrowedRewards = MaxEscrowedRewards;
+	type MaxLoansRiskWeights = MaxLoansRiskWeights;
 	type PalletId = IncentivesPalletId;
+	type IdleScheduler = ();
 	type WeightInfo = weights::module_incentives::WeightInfo<Runtime>;
 }
 
 parameter_types! {
 	pub CreateClassDeposit: Balance = 20 * dollar(KAR);
 	pub CreateTokenDeposit: Balance = 2 * dollar(KAR);
+	pub MaxAttributeKeyLength: u32 = 64;
+	pub MaxAttributeValueLength: u32 = 1024;
 }
 
 impl module_nft::Config for Runtime {
@@ -1177,6 +1263,10 @@ impl module_nft::Config for Runtime {
 	type CreateTokenDeposit = CreateTokenDeposit;
 	type DataDepositPerByte = DataDepositPerByte;
 	type PalletId = NftPalletId;
+	type MaxAttributeKeyLength = MaxAttributeKeyLength;
+	type MaxAttributeValueLength = MaxAttributeValueLength;
+	type UnixTime = Timestamp;
+	type OnNftTransfer = ();
 	type WeightInfo = weights::module_nft::WeightInfo<Runtime>;
 }
 
@@ -1250,6 +1340,8 @@ parameter_types! {
 	// https://eips.ethereum.org/EIPS/eip-170
 	pub const MaxCodeSize: u32 = 0x6000;
 	pub NetworkContractSource: H160 = H160::from_low_u64_be(0);
+	pub const AccessListGasMetering: bool = true;
+	pub const EvictionNoticePeriod: BlockNumber = 7 * DAYS;
 	pub DeveloperDeposit: Balance = 100 * dollar(KAR);
 	pub DeploymentFee: Balance = 10000 * dollar(KAR);
 }
@@ -1265,8 +1357,13 @@ pub type NFTPrecompile =
 	runtime_common::NFTPrecompile<AccountId, EvmAddressMapping<Runtime>, EvmCurrencyIdMapping<Runtime>, NFT>;
 pub type StateRentPrecompile =
 	runtime_common::StateRentPrecompile<AccountId, EvmAddressMapping<Runtime>, EvmCurrencyIdMapping<Runtime>, EVM>;
-pub type OraclePrecompile =
-	runtime_common::OraclePrecompile<AccountId, EvmAddressMapping<Runtime>, EvmCurrencyIdMapping<Runtime>, Prices>;
+pub type OraclePrecompile = runtime_common::OraclePrecompile<
+	AccountId,
+	EvmAddressMapping<Runtime>,
+	EvmCurrencyIdMapping<Runtime>,
+	Prices,
+	AggregatedDataProvider,
+>;
 pub type ScheduleCallPrecompile = runtime_common::ScheduleCallPrecompile<
 	AccountId,
 	EvmAddressMapping<Runtime>,
@@ -1279,7 +1376,35 @@ pub type ScheduleCallPrecompile = runtime_common::ScheduleCallPrecompile<
 	Runtime,
 >;
 pub type DexPrecompile =
-	runtime_common::DexPrecompile<AccountId, EvmAddressMapping<Runtime>, EvmCurrencyIdMapping<Runtime>, Dex>;
+	runtime_common::DexPrecompile<AccountId, EvmAddressMapping<Runtime>, EvmCurrencyIdMapping<Runtime>, Dex, Runtime>;
+pub type HomaPrecompile = runtime_common::HomaPrecompile<
+	AccountId,
+	EvmAddressMapping<Runtime>,
+	EvmCurrencyIdMapping<Runtime>,
+	(),
+	LiquidStakingExchangeRateProvider,
+	(),
+	Runtime,
+>;
+pub type IncentivesPrecompile = runtime_common::IncentivesPrecompile<
+	AccountId,
+	EvmAddressMapping<Runtime>,
+	EvmCurrencyIdMapping<Runtime>,
+	Incentives,
+	weights::module_incentives::WeightInfo<Runtime>,
+	Runtime,
+>;
+pub type HonzonPrecompile = runtime_common::HonzonPrecompile<
+	AccountId,
+	EvmAddressMapping<Runtime>,
+	EvmCurrencyIdMapping<Runtime>,
+	CdpEngine,
+	Runtime,
+>;
+pub type MulticallPrecompile =
+	runtime_common::MulticallPrecompile<AccountId, EvmAddressMapping<Runtime>, EvmCurrencyIdMapping<Runtime>, Runtime>;
+pub type ERC721Precompile =
+	runtime_common::ERC721Precompile<AccountId, EvmAddressMapping<Runtime>, EvmCurrencyIdMapping<Runtime>, Runtime>;
 
 impl module_evm::Config for Runtime {
 	type AddressMapping = EvmAddressMapping<Runtime>;
@@ -1297,9 +1422,16 @@ impl module_evm::Config for Runtime {
 		OraclePrecompile,
 		ScheduleCallPrecompile,
 		DexPrecompile,
+		HomaPrecompile,
+		IncentivesPrecompile,
+		HonzonPrecompile,
+		MulticallPrecompile,
+		ERC721Precompile,
 	>;
 	type ChainId = ChainId;
 	type GasToWeight = GasToWeight;
+	type BlockGasTarget = BlockGasTarget;
+	type AccessListGasMetering = AccessListGasMetering;
 	type ChargeTransactionPayment = module_transaction_payment::ChargeTransactionPayment<Runtime>;
 	type NetworkContractOrigin = EnsureRootOrTwoThirdsTechnicalCommittee;
 	type NetworkContractSource = NetworkContractSource;
@@ -1307,6 +1439,9 @@ impl module_evm::Config for Runtime {
 	type DeploymentFee = DeploymentFee;
 	type TreasuryAccount = KaruraTreasuryAccount;
 	type FreeDeploymentOrigin = EnsureRootOrHalfGeneralCouncil;
+	type IdleScheduler = ();
+	type ContractFilter = ();
+	type EvictionNoticePeriod = EvictionNoticePeriod;
 	type WeightInfo = weights::module_evm::WeightInfo<Runtime>;
 }
 
@@ -1551,9 +1686,9 @@ construct_runtime!(
 		// Tokens & Related
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>} = 10,
 		Tokens: orml_tokens::{Pallet, Storage, Event<T>, Config<T>} = 11,
-		Currencies: module_currencies::{Pallet, Call, Event<T>} = 12,
+		Currencies: module_currencies::{Pallet, Call, Storage, Event<T>} = 12,
 		Vesting: orml_vesting::{Pallet, Storage, Call, Event<T>, Config<T>} = 13,
-		TransactionPayment: module_transaction_payment::{Pallet, Call, Storage} = 14,
+		TransactionPayment: module_transaction_payment::{Pallet, Call, Storage, Event<T>} = 14,
 
 		// Treasury
 		Treasury: pallet_treasury::{Pallet, Call, Storage, Config, Event<T>} = 20,
@@ -1599,7 +1734,7 @@ construct_runtime!(
 
 		// ORML Core
 		Auction: orml_auction::{Pallet, Storage, Call, Event<T>} = 80,
-		Rewards: orml_rewards::{Pallet, Storage, Call} = 81,
+		Rewards: module_rewards::{Pallet, Storage, Call} = 81,
 		OrmlNFT: orml_nft::{Pallet, Storage, Config<T>} = 82,
 
 		// Karura Core
@@ -1622,14 +1757,15 @@ construct_runtime!(
 		// HomaValidatorListModule: module_homa_validator_list::{Pallet, Call, Storage, Event<T>} = 114,
 
 		// Karura Other
-		Incentives: module_incentives::{Pallet, Storage, Call, Event<T>} = 120,
+		Incentives: module_incentives::{Pallet, Storage, Call, Config<T>, Event<T>} = 120,
 		NFT: module_nft::{Pallet, Call, Event<T>} = 121,
+		EventTopics: module_event_topics::{Pallet, Storage} = 122,
 
 		// Smart contracts
 		EVM: module_evm::{Pallet, Config<T>, Call, Storage, Event<T>} = 130,
 		EVMBridge: module_evm_bridge::{Pallet} = 131,
 		EvmAccounts: module_evm_accounts::{Pallet, Call, Storage, Event<T>} = 132,
-		EvmManager: module_evm_manager::{Pallet, Storage} = 133,
+		EvmManager: module_evm_manager::{Pallet, Call, Storage, Event<T>} = 133,
 
 		// Temporary
 		Sudo: pallet_sudo::{Pallet, Call, Config<T>, Storage, Event<T>} = 255,
@@ -1803,6 +1939,174 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl module_honzon_rpc_runtime_api::HonzonApi<
+		Block,
+		AccountId,
+		CurrencyId,
+		Balance,
+	> for Runtime {
+		fn get_positions(
+			currency_id: CurrencyId,
+		) -> Vec<module_honzon_rpc_runtime_api::CdpPosition<AccountId, Balance>> {
+			module_loans::Positions::<Runtime>::iter_prefix(currency_id)
+				.map(|(owner, position)| module_honzon_rpc_runtime_api::CdpPosition {
+					owner,
+					collateral: position.collateral,
+					debit: position.debit,
+				})
+				.collect()
+		}
+
+		fn get_collateral_states() -> Vec<module_honzon_rpc_runtime_api::CollateralState<CurrencyId, Balance>> {
+			module_cdp_engine::CollateralParams::<Runtime>::iter_keys()
+				.map(|currency_id| {
+					let total_positions = Loans::total_positions(currency_id);
+					module_honzon_rpc_runtime_api::CollateralState {
+						currency_id,
+						total_collateral: total_positions.collateral,
+						total_debit: total_positions.debit,
+						debit_exchange_rate: CdpEngine::get_debit_exchange_rate(currency_id),
+					}
+				})
+				.collect()
+		}
+
+		fn simulate_liquidation(
+			currency_id: CurrencyId,
+			price_drop: Permill,
+		) -> Option<module_honzon_rpc_runtime_api::LiquidationSimulation<AccountId, Balance>> {
+			CdpEngine::simulate_liquidation(currency_id, price_drop).map(|simulation| {
+				module_honzon_rpc_runtime_api::LiquidationSimulation {
+					unsafe_positions: simulation
+						.unsafe_positions
+						.into_iter()
+						.map(|(owner, position)| module_honzon_rpc_runtime_api::CdpPosition {
+							owner,
+							collateral: position.collateral,
+							debit: position.debit,
+						})
+						.collect(),
+					total_collateral: simulation.total_collateral,
+					total_debit_value: simulation.total_debit_value,
+					bad_debt_value: simulation.bad_debt_value,
+				}
+			})
+		}
+	}
+
+	impl module_dex_rpc_runtime_api::DexApi<
+		Block,
+		CurrencyId,
+		Balance,
+	> for Runtime {
+		fn get_liquidity_pools() -> Vec<module_dex_rpc_runtime_api::LiquidityPool<CurrencyId, Balance>> {
+			module_dex::LiquidityPool::<Runtime>::iter()
+				.map(|(trading_pair, (reserve_0, reserve_1))| module_dex_rpc_runtime_api::LiquidityPool {
+					currency_id_0: trading_pair.0,
+					currency_id_1: trading_pair.1,
+					reserve_0,
+					reserve_1,
+					total_shares: trading_pair
+						.get_dex_share_currency_id()
+						.map_or(0, orml_tokens::TotalIssuance::<Runtime>::get),
+				})
+				.collect()
+		}
+	}
+
+	impl module_incentives_rpc_runtime_api::IncentivesApi<
+		Block,
+		AccountId,
+		module_incentives::PoolId<AccountId>,
+		CurrencyId,
+		Balance,
+	> for Runtime {
+		fn get_pending_rewards(
+			account: AccountId,
+		) -> Vec<module_incentives_rpc_runtime_api::PendingReward<module_incentives::PoolId<AccountId>, CurrencyId, Balance>> {
+			Incentives::pending_rewards(&account)
+				.into_iter()
+				.map(|(pool_id, currency_id, amount)| module_incentives_rpc_runtime_api::PendingReward {
+					pool_id,
+					currency_id,
+					amount,
+				})
+				.collect()
+		}
+
+		fn get_apr_estimates() -> Vec<module_incentives_rpc_runtime_api::AprEstimate<module_incentives::PoolId<AccountId>>> {
+			use module_incentives::PoolId;
+			use module_support::PriceProvider;
+			use sp_runtime::traits::Saturating;
+
+			Incentives::apr_estimates(
+				System::block_number(),
+				365 * DAYS / AccumulatePeriod::get(),
+				Prices::get_price,
+				|pool_id| match pool_id {
+					// the share of loans incentive pool is the debit amount
+					PoolId::LoansIncentive(currency_id) => Prices::get_price(GetStableCurrencyId::get())
+						.map(|price| price.saturating_mul(CdpEngine::get_debit_exchange_rate(*currency_id))),
+					PoolId::DexIncentive(currency_id) | PoolId::DexSaving(currency_id) => Prices::get_price(*currency_id),
+					PoolId::HomaValidatorAllowance(_) => Prices::get_price(GetLiquidCurrencyId::get()),
+					PoolId::HomaIncentive => None,
+				},
+			)
+			.into_iter()
+			.map(|(pool_id, apr)| module_incentives_rpc_runtime_api::AprEstimate { pool_id, apr })
+			.collect()
+		}
+	}
+
+	impl module_homa_rpc_runtime_api::HomaApi<
+		Block,
+		AccountId,
+		Balance,
+	> for Runtime {
+		fn get_exchange_rate() -> ExchangeRate {
+			ExchangeRate::zero()
+		}
+
+		fn get_pending_unbonding() -> Vec<(EraIndex, Balance)> {
+			Vec::new()
+		}
+
+		fn get_claimable(_account: AccountId) -> module_homa_rpc_runtime_api::BalanceInfo<Balance> {
+			module_homa_rpc_runtime_api::BalanceInfo {
+				amount: Zero::zero()
+			}
+		}
+
+		fn get_unbondings(_account: AccountId) -> Vec<(EraIndex, Balance)> {
+			Vec::new()
+		}
+
+		fn get_exchange_rate_history() -> Vec<(EraIndex, ExchangeRate)> {
+			Vec::new()
+		}
+	}
+
+	impl runtime_common::AuthorityApi<Block, BlockNumber, Hash, OriginCaller> for Runtime {
+		fn get_scheduled_calls() -> Vec<runtime_common::ScheduledCall<BlockNumber, Hash, OriginCaller>> {
+			runtime_common::authority_scheduled_calls::<Runtime>()
+		}
+	}
+
+	impl runtime_common::AccountPortfolioApi<Block, AccountId, BlockNumber, module_incentives::PoolId<AccountId>> for Runtime {
+		fn get_account_portfolio(
+			account: AccountId,
+		) -> runtime_common::AccountPortfolio<BlockNumber, module_incentives::PoolId<AccountId>> {
+			let homa_claims = Default::default();
+			runtime_common::account_portfolio::<Runtime>(&account, homa_claims)
+		}
+	}
+
+	impl runtime_common::StateReportApi<Block> for Runtime {
+		fn get_state_report() -> Vec<runtime_common::StorageItemReport> {
+			runtime_common::state_report(runtime_common::STATE_REPORT_ITEMS)
+		}
+	}
+
 	impl module_evm_rpc_runtime_api::EVMRuntimeRPCApi<Block, Balance> for Runtime {
 		fn call(
 			from: H160,
@@ -1889,6 +2193,110 @@ impl_runtime_apis! {
 
 			request.ok_or(sp_runtime::DispatchError::Other("Invalid parameter extrinsic, not evm Call"))
 		}
+
+		fn estimate_fee(
+			from: H160,
+			to: Option<H160>,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			storage_limit: u32,
+			fee_currency_id: Option<CurrencyId>,
+		) -> Result<EstimateFeeInfo<Balance>, sp_runtime::DispatchError> {
+			EVM::estimate_fee(from, to, data, value, gas_limit, storage_limit, fee_currency_id)
+		}
+
+		fn block_fee_info() -> BlockFeeInfo {
+			EVM::block_fee_info()
+		}
+
+		fn contract_status(address: H160) -> Option<ContractStatus> {
+			EVM::contract_status(&address)
+		}
+
+		fn contract_metadata(code_hash: H256) -> Option<ContractMetadata> {
+			EVM::contract_metadata(&code_hash)
+		}
+
+		fn block_logs_bloom() -> Option<LogsBloom> {
+			EVM::block_logs_bloom()
+		}
+
+		fn block_receipts() -> Vec<Receipt> {
+			EVM::block_receipts()
+		}
+	}
+
+	impl module_evm_rpc_runtime_api::EVMTraceRuntimeApi<Block> for Runtime {
+		fn trace_extrinsic(
+			header: &<Block as BlockT>::Header,
+			extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+			index: u32,
+			config: TracerConfig,
+		) -> Result<TraceInfo, sp_runtime::DispatchError> {
+			let index = index as usize;
+			let extrinsic = extrinsics
+				.get(index)
+				.cloned()
+				.ok_or(sp_runtime::DispatchError::Other("Invalid parameter index, extrinsic not found"))?;
+
+			Executive::initialize_block(header);
+			for extrinsic in extrinsics.into_iter().take(index) {
+				let _ = Executive::apply_extrinsic(extrinsic);
+			}
+
+			let mut tracer = module_evm::runner::tracing::Tracer::new(config);
+			module_evm::runner::tracing::using(&mut tracer, || {
+				let _ = Executive::apply_extrinsic(extrinsic);
+			});
+
+			tracer.finalize().ok_or(sp_runtime::DispatchError::Other("Invalid parameter extrinsic, not evm Call"))
+		}
+	}
+
+	impl module_evm_rpc_runtime_api::DryRunRuntimeApi<Block, Balance> for Runtime {
+		fn dry_run_extrinsic(extrinsic: <Block as BlockT>::Extrinsic) -> DryRunInfo<Balance> {
+			let len = extrinsic.encoded_size() as u32;
+			let fee = TransactionPayment::query_info(extrinsic.clone(), len).partial_fee;
+			let event_count = System::event_count() as usize;
+
+			let ((result, events), storage_changes) = EVM::dry_run(|| {
+				let result = Executive::apply_extrinsic(extrinsic);
+				let events = System::events()
+					.into_iter()
+					.skip(event_count)
+					.map(|record| record.event.encode())
+					.collect();
+				(result, events)
+			});
+
+			DryRunInfo {
+				result,
+				events,
+				storage_changes,
+				fee,
+			}
+		}
+	}
+
+	impl module_evm_rpc_runtime_api::EVMTransactionPoolApi<Block, AccountId, Balance> for Runtime {
+		fn evm_account_nonce(address: H160) -> (AccountId, Nonce) {
+			use module_support::AddressMapping;
+
+			let account = EvmAddressMapping::<Runtime>::get_account_id(&address);
+			let nonce = System::account_nonce(&account);
+			(account, nonce)
+		}
+
+		fn evm_transaction(extrinsic: <Block as BlockT>::Extrinsic) -> Option<EvmTransaction<Balance>> {
+			let (address, _, extra) = extrinsic.signature?;
+			let who = <Runtime as frame_system::Config>::Lookup::lookup(address).ok()?;
+			match extrinsic.function {
+				// the nonce of `frame_system::CheckNonce` in `SignedExtra`
+				Call::EVM(call) => EVM::evm_transaction(&who, (extra.4).0, &call),
+				_ => None,
+			}
+		}
 	}
 
 	impl cumulus_primitives_core::CollectCollationInfo<Block> for Runtime {
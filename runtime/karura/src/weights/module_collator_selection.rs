@@ -82,6 +82,19 @@ impl<T: frame_system::Config> module_collator_selection::WeightInfo for WeightIn
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	fn schedule_session_keys() -> Weight {
+		(41_732_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn rotate_scheduled_keys(k: u32, ) -> Weight {
+		(1_986_000 as Weight)
+			// Standard Error: 11_000
+			.saturating_add((31_208_000 as Weight).saturating_mul(k as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((4 as Weight).saturating_mul(k as Weight)))
+			.saturating_add(T::DbWeight::get().writes((5 as Weight).saturating_mul(k as Weight)))
+	}
 	fn note_author() -> Weight {
 		(24_802_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
@@ -102,6 +115,14 @@ impl<T: frame_system::Config> module_collator_selection::WeightInfo for WeightIn
 			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
 			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
 	}
+	fn release_bonds(u: u32, ) -> Weight {
+		(2_104_000 as Weight)
+			// Standard Error: 9_000
+			.saturating_add((24_371_000 as Weight).saturating_mul(u as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(u as Weight)))
+	}
 	fn end_session(r: u32, c: u32, ) -> Weight {
 		(0 as Weight)
 			// Standard Error: 517_000
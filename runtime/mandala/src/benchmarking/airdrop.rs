@@ -0,0 +1,82 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{dollar, AccountId, AirDrop, Hash, Runtime, System, TreasuryAccount, AUSD};
+
+use super::utils::set_balance;
+use frame_benchmarking::{account, whitelisted_caller};
+use frame_support::traits::Get;
+use frame_system::RawOrigin;
+use orml_benchmarking::runtime_benchmarks;
+use sp_runtime::traits::{BlakeTwo256, Hash as HashT};
+use sp_std::prelude::*;
+
+const SEED: u32 = 0;
+const MAX_PROOF_LENGTH: u32 = 32;
+
+// create an airdrop of 1_000 AUSD which `recipient` can claim 100 AUSD from with the proof of
+// `proof_length` hashes, returns the proof
+fn create_airdrop(recipient: &AccountId, proof_length: u32) -> Result<Vec<Hash>, sp_runtime::DispatchError> {
+	set_balance(AUSD, &TreasuryAccount::get(), 1_000 * dollar(AUSD));
+	let leaf = BlakeTwo256::hash_of(&(0u32, recipient, 100 * dollar(AUSD)));
+	let proof: Vec<Hash> = (0..proof_length).map(|i| BlakeTwo256::hash_of(&i)).collect();
+	let root = AirDrop::merkle_root(leaf, &proof);
+	AirDrop::create_airdrop(
+		RawOrigin::Root.into(),
+		AUSD,
+		root,
+		1u32 << proof_length.min(31),
+		1_000 * dollar(AUSD),
+		System::block_number() + 100,
+	)?;
+	Ok(proof)
+}
+
+runtime_benchmarks! {
+	{ Runtime, module_airdrop }
+
+	create_airdrop {
+		set_balance(AUSD, &TreasuryAccount::get(), 1_000 * dollar(AUSD));
+		let expiry = System::block_number() + 100;
+	}: _(RawOrigin::Root, AUSD, Hash::default(), 1_000, 1_000 * dollar(AUSD), expiry)
+
+	claim {
+		let p in 0 .. MAX_PROOF_LENGTH;
+
+		let caller: AccountId = whitelisted_caller();
+		let recipient: AccountId = account("recipient", 0, SEED);
+		let proof = create_airdrop(&recipient, p)?;
+	}: _(RawOrigin::Signed(caller), 0, 0, recipient, 100 * dollar(AUSD), proof)
+
+	sweep {
+		let caller: AccountId = whitelisted_caller();
+		let recipient: AccountId = account("recipient", 0, SEED);
+		let proof = create_airdrop(&recipient, 0)?;
+		AirDrop::claim(RawOrigin::Signed(caller.clone()).into(), 0, 0, recipient, 100 * dollar(AUSD), proof)?;
+		System::set_block_number(System::block_number() + 100);
+	}: _(RawOrigin::Signed(caller), 0)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::benchmarking::utils::tests::new_test_ext;
+	use orml_benchmarking::impl_benchmark_test_suite;
+
+	impl_benchmark_test_suite!(new_test_ext(),);
+}
@@ -0,0 +1,85 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{dollar, AssetRegistry, Runtime, DOT, EVM};
+
+use frame_support::dispatch::DispatchError;
+use frame_system::RawOrigin;
+use module_support::AssetMetadata;
+use orml_benchmarking::runtime_benchmarks;
+use primitives::{evm::EvmAddress, CurrencyId};
+use sp_std::{boxed::Box, prelude::*};
+use xcm::v0::{Junction, MultiLocation};
+
+fn dot_location() -> MultiLocation {
+	MultiLocation::X1(Junction::Parent)
+}
+
+fn metadata() -> AssetMetadata<u128> {
+	AssetMetadata {
+		name: b"Token Name".to_vec(),
+		symbol: b"TN".to_vec(),
+		decimals: 12,
+		minimal_balance: 1,
+	}
+}
+
+// deploy the erc20 demo contract as network contract
+fn deploy_erc20_contract() -> Result<EvmAddress, DispatchError> {
+	let code = include!("../../../../modules/evm-bridge/src/erc20_demo_contract")
+		.trim_start_matches("0x")
+		.as_bytes()
+		.chunks(2)
+		.map(|c| u8::from_str_radix(sp_std::str::from_utf8(c).unwrap(), 16).unwrap())
+		.collect::<Vec<u8>>();
+	let address = EvmAddress::from_low_u64_be(EVM::network_contract_index());
+	EVM::create_network_contract(RawOrigin::Root.into(), code, 0, 2_100_000, 100_000)?;
+	EVM::deploy_free(RawOrigin::Root.into(), address)?;
+	Ok(address)
+}
+
+runtime_benchmarks! {
+	{ Runtime, module_asset_registry }
+
+	register_foreign_asset {
+	}: _(RawOrigin::Root, Box::new(dot_location()), Box::new(metadata()))
+
+	update_foreign_asset {
+		AssetRegistry::register_foreign_asset(RawOrigin::Root.into(), Box::new(dot_location()), Box::new(metadata()))?;
+		let new_location = MultiLocation::X2(Junction::Parent, Junction::Parachain(1000));
+	}: _(RawOrigin::Root, 0, Box::new(new_location), Box::new(metadata()))
+
+	register_erc20_asset {
+		let contract = deploy_erc20_contract()?;
+	}: _(RawOrigin::Root, contract, dollar(DOT))
+	verify {
+		assert!(AssetRegistry::asset_metadatas(CurrencyId::Erc20(contract)).is_some());
+	}
+
+	update_asset_metadata {
+	}: _(RawOrigin::Root, DOT, Box::new(metadata()))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::benchmarking::utils::tests::new_test_ext;
+	use orml_benchmarking::impl_benchmark_test_suite;
+
+	impl_benchmark_test_suite!(new_test_ext(),);
+}
@@ -16,12 +16,68 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{dollar, CdpTreasury, Currencies, CurrencyId, Runtime, AUSD, DOT};
+use crate::{
+	dollar, AccountId, Balance, CdpTreasury, Currencies, CurrencyId, Dex, Runtime, SurplusAllocationPeriod, ACA, AUSD,
+	DOT, LDOT, RENBTC,
+};
 
+use super::utils::set_balance;
+use frame_benchmarking::account;
+use frame_support::traits::OnInitialize;
 use frame_system::RawOrigin;
+use module_cdp_treasury::{SurplusAllocation, SurplusStrategy};
 use module_support::CDPTreasury;
 use orml_benchmarking::runtime_benchmarks;
 use orml_traits::MultiCurrency;
+use sp_runtime::Permill;
+use sp_std::prelude::*;
+
+const SEED: u32 = 0;
+
+const SURPLUS_STRATEGIES: [SurplusStrategy; 5] = [
+	SurplusStrategy::SavingRate,
+	SurplusStrategy::BuybackAndBurn,
+	SurplusStrategy::StabilityBuffer(DOT),
+	SurplusStrategy::StabilityBuffer(LDOT),
+	SurplusStrategy::StabilityBuffer(RENBTC),
+];
+
+fn inject_liquidity(
+	maker: AccountId,
+	currency_id: CurrencyId,
+	amount: Balance,
+	stable_amount: Balance,
+) -> Result<(), &'static str> {
+	set_balance(currency_id, &maker, amount);
+	set_balance(AUSD, &maker, stable_amount);
+	let _ = Dex::enable_trading_pair(RawOrigin::Root.into(), currency_id, AUSD);
+	Dex::add_liquidity(
+		RawOrigin::Signed(maker).into(),
+		AUSD,
+		currency_id,
+		stable_amount,
+		amount,
+		Default::default(),
+		false,
+	)?;
+	Ok(())
+}
+
+fn surplus_allocations(c: u32) -> Vec<(SurplusStrategy, Option<SurplusAllocation>)> {
+	SURPLUS_STRATEGIES
+		.iter()
+		.take(c as usize)
+		.map(|strategy| {
+			(
+				*strategy,
+				Some(SurplusAllocation {
+					proportion: Permill::from_percent(10),
+					cap: 1_000 * dollar(AUSD),
+				}),
+			)
+		})
+		.collect()
+}
 
 runtime_benchmarks! {
 	{ Runtime, module_cdp_treasury }
@@ -38,6 +94,27 @@ runtime_benchmarks! {
 	extract_surplus_to_treasury {
 		CdpTreasury::on_system_surplus(1_000 * dollar(AUSD))?;
 	}: _(RawOrigin::Root, 200 * dollar(AUSD))
+
+	set_surplus_allocations {
+		let c in 1 .. SURPLUS_STRATEGIES.len() as u32;
+	}: _(RawOrigin::Root, surplus_allocations(c))
+
+	on_initialize {
+		let c in 1 .. SURPLUS_STRATEGIES.len() as u32;
+		let maker: AccountId = account("maker", 0, SEED);
+		for currency_id in [ACA, DOT, LDOT, RENBTC].iter() {
+			inject_liquidity(
+				maker.clone(),
+				*currency_id,
+				100_000 * dollar(*currency_id),
+				100_000 * dollar(AUSD),
+			)?;
+		}
+		CdpTreasury::on_system_surplus(10_000 * dollar(AUSD))?;
+		CdpTreasury::set_surplus_allocations(RawOrigin::Root.into(), surplus_allocations(c))?;
+	}: {
+		CdpTreasury::on_initialize(SurplusAllocationPeriod::get());
+	}
 }
 
 #[cfg(test)]
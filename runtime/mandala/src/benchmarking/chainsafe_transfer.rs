@@ -22,6 +22,7 @@ use crate::{
 };
 
 use super::utils::set_balance;
+use ecosystem_chainsafe::BridgeLimits;
 use frame_benchmarking::account;
 use frame_support::{traits::EnsureOrigin, weights::DispatchClass};
 use frame_system::RawOrigin;
@@ -45,6 +46,26 @@ runtime_benchmarks! {
 		ChainSafeTransfer::register_resource_id(RawOrigin::Root.into(), resource_id, currency_id)?;
 	}: _(RawOrigin::Root, resource_id)
 
+	set_bridge_limits {
+		let currency_id: CurrencyId = ACA;
+		let resource_id: chainbridge::ResourceId = chainbridge::derive_resource_id(LocalChainId::get(), b"aca");
+		let limits = BridgeLimits {
+			fee: dollar(currency_id),
+			min_amount: 10 * dollar(currency_id),
+			max_amount: 1_000 * dollar(currency_id),
+			daily_limit: 10_000 * dollar(currency_id),
+		};
+
+		ChainSafeTransfer::register_resource_id(RawOrigin::Root.into(), resource_id, currency_id)?;
+	}: _(RawOrigin::Root, resource_id, Some(limits))
+
+	set_resource_paused {
+		let currency_id: CurrencyId = ACA;
+		let resource_id: chainbridge::ResourceId = chainbridge::derive_resource_id(LocalChainId::get(), b"aca");
+
+		ChainSafeTransfer::register_resource_id(RawOrigin::Root.into(), resource_id, currency_id)?;
+	}: _(RawOrigin::Root, resource_id, true)
+
 	transfer_origin_chain_token_to_bridge {
 		let b in 0 .. *RuntimeBlockLength::get().max.get(DispatchClass::Normal) as u32;
 		let dest = vec![1; b as usize];
@@ -0,0 +1,111 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{AccountId, CircuitBreaker, EmergencyActionDuration, MaxEmergencyKeys, Runtime, System};
+
+use frame_benchmarking::account;
+use frame_support::traits::OnInitialize;
+use frame_system::RawOrigin;
+use module_circuit_breaker::EmergencyAction;
+use orml_benchmarking::runtime_benchmarks;
+use sp_runtime::DispatchResult;
+use sp_std::prelude::*;
+
+const SEED: u32 = 0;
+const PALLETS: [&[u8]; 7] = [
+	b"Dex",
+	b"Honzon",
+	b"CdpEngine",
+	b"Incentives",
+	b"StakingPool",
+	b"NFT",
+	b"EVM",
+];
+
+fn keys() -> Vec<AccountId> {
+	let mut keys: Vec<AccountId> = (0..MaxEmergencyKeys::get()).map(|i| account("key", i, SEED)).collect();
+	keys.sort();
+	keys
+}
+
+fn pause(i: usize) -> EmergencyAction {
+	EmergencyAction::PausePallet(PALLETS[i].to_vec())
+}
+
+fn take_action(keys: &[AccountId], action: EmergencyAction) -> DispatchResult {
+	let action_id = CircuitBreaker::next_action_id();
+	CircuitBreaker::propose(RawOrigin::Signed(keys[0].clone()).into(), action)?;
+	CircuitBreaker::approve(RawOrigin::Signed(keys[1].clone()).into(), action_id)?;
+	CircuitBreaker::approve(RawOrigin::Signed(keys[2].clone()).into(), action_id)?;
+	Ok(())
+}
+
+runtime_benchmarks! {
+	{ Runtime, module_circuit_breaker }
+
+	on_initialize {
+		let c in 1 .. PALLETS.len() as u32;
+
+		let keys = keys();
+		CircuitBreaker::set_emergency_keys(RawOrigin::Root.into(), keys.clone())?;
+		for i in 0 .. c {
+			take_action(&keys, pause(i as usize))?;
+		}
+		let expiry = System::block_number() + EmergencyActionDuration::get();
+		System::set_block_number(expiry);
+	}: {
+		CircuitBreaker::on_initialize(expiry);
+	}
+
+	set_emergency_keys {
+	}: _(RawOrigin::Root, keys())
+
+	propose {
+		let keys = keys();
+		CircuitBreaker::set_emergency_keys(RawOrigin::Root.into(), keys.clone())?;
+	}: _(RawOrigin::Signed(keys[0].clone()), pause(0))
+
+	// approve which takes the action
+	approve {
+		let keys = keys();
+		CircuitBreaker::set_emergency_keys(RawOrigin::Root.into(), keys.clone())?;
+		CircuitBreaker::propose(RawOrigin::Signed(keys[0].clone()).into(), pause(0))?;
+		CircuitBreaker::approve(RawOrigin::Signed(keys[1].clone()).into(), 0)?;
+	}: _(RawOrigin::Signed(keys[2].clone()), 0)
+
+	ratify {
+		let keys = keys();
+		CircuitBreaker::set_emergency_keys(RawOrigin::Root.into(), keys.clone())?;
+		take_action(&keys, pause(0))?;
+	}: _(RawOrigin::Root, 0)
+
+	reject {
+		let keys = keys();
+		CircuitBreaker::set_emergency_keys(RawOrigin::Root.into(), keys.clone())?;
+		take_action(&keys, pause(0))?;
+	}: _(RawOrigin::Root, 0)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::benchmarking::utils::tests::new_test_ext;
+	use orml_benchmarking::impl_benchmark_test_suite;
+
+	impl_benchmark_test_suite!(new_test_ext(),);
+}
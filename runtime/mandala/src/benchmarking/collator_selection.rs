@@ -17,17 +17,22 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-	AccountId, Balance, Balances, CollatorKickThreshold, CollatorSelection, Event, MaxCandidates, MaxInvulnerables,
-	MinCandidates, Period, Runtime, Session, SessionKeys, System,
+	AccountId, AuraId, Balance, Balances, CollatorKickThreshold, CollatorSelection, CollatorUnbondingDelay, Event,
+	MaxCandidates, MaxInvulnerables, MinCandidates, Period, Runtime, Session, SessionKeys, System,
 };
 
 use frame_benchmarking::{account, whitelisted_caller};
-use frame_support::{assert_ok, pallet_prelude::Decode, traits::Currency};
+use frame_support::{
+	assert_ok,
+	pallet_prelude::{Decode, Encode},
+	traits::{Currency, NamedReservableCurrency},
+};
 use frame_system::RawOrigin;
-use module_collator_selection::POINT_PER_BLOCK;
+use module_collator_selection::{keys_ownership_payload, POINT_PER_BLOCK};
 use orml_benchmarking::{runtime_benchmarks, whitelist_account};
 use pallet_authorship::EventHandler;
 use pallet_session::SessionManager;
+use sp_runtime::RuntimeAppPublic;
 use sp_std::prelude::*;
 
 const SEED: u32 = 0;
@@ -143,7 +148,34 @@ runtime_benchmarks! {
 		whitelist_account!(leaving);
 	}: _(RawOrigin::Signed(leaving.clone()))
 	verify {
-		assert_last_event(module_collator_selection::Event::CandidateRemoved(leaving).into());
+		assert_last_event(module_collator_selection::Event::BondUnbonding(leaving, CollatorUnbondingDelay::get()).into());
+	}
+
+	schedule_session_keys {
+		let caller: AccountId = whitelisted_caller();
+		let aura = AuraId::generate_pair(None);
+		let proof = aura.sign(&keys_ownership_payload(&caller)).unwrap().encode();
+		let keys = SessionKeys { aura };
+	}: _(RawOrigin::Signed(caller.clone()), keys, proof, 2)
+	verify {
+		assert_last_event(module_collator_selection::Event::SessionKeysScheduled(caller, 2).into());
+	}
+
+	// worse case is rotating the keys of all the candidates.
+	rotate_scheduled_keys {
+		let k in 0 .. MaxCandidates::get();
+
+		for i in 0..k {
+			let who: AccountId = account("candidate", i, SEED);
+			Balances::make_free_balance_be(&who, Balances::minimum_balance());
+			let mut keys = [2u8; 128];
+			keys[0..4].copy_from_slice(&i.to_be_bytes());
+			let keys: SessionKeys = Decode::decode(&mut &keys[..]).unwrap();
+			module_collator_selection::KeysScheduledAt::<Runtime>::insert(&who, 2);
+			module_collator_selection::ScheduledKeys::<Runtime>::insert(2, &who, keys);
+		}
+	}: {
+		CollatorSelection::rotate_scheduled_keys(2);
 	}
 
 	// worse case is paying a non-existing candidate account.
@@ -203,6 +235,22 @@ runtime_benchmarks! {
 		CollatorSelection::start_session(2)
 	}
 
+	// worse case is releasing the bonds of all the candidates.
+	release_bonds {
+		let u in 0 .. MaxCandidates::get();
+
+		let bond: Balance = Balances::minimum_balance();
+		for i in 0..u {
+			let who: AccountId = account("candidate", i, SEED);
+			Balances::make_free_balance_be(&who, bond.checked_mul(2u32.into()).unwrap());
+			assert_ok!(Balances::reserve_named(&module_collator_selection::RESERVE_ID, &who, bond));
+			module_collator_selection::Unbonding::<Runtime>::insert(&who, 2);
+			module_collator_selection::UnbondingQueue::<Runtime>::insert(2, &who, ());
+		}
+	}: {
+		CollatorSelection::release_bonds(2);
+	}
+
 	end_session {
 		// MinCandidates = 5, so begin with 5.
 		let r in 5 .. MaxCandidates::get();
@@ -17,7 +17,9 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use super::utils::{lookup_of_account, set_balance};
-use crate::{dollar, AccountId, Amount, Balance, Currencies, NativeTokenExistentialDeposit, Runtime, ACA, DOT};
+use crate::{
+	dollar, AccountId, Amount, Balance, Currencies, MaxBatchTransfers, NativeTokenExistentialDeposit, Runtime, ACA, DOT,
+};
 
 use sp_std::prelude::*;
 
@@ -120,6 +122,27 @@ runtime_benchmarks! {
 	verify {
 		assert_eq!(<Currencies as MultiCurrency<_>>::free_balance(native_currency_id, &who), 0);
 	}
+
+	set_dust_conversion {}: _(RawOrigin::Root, DOT, true)
+	verify {
+		assert!(Currencies::dust_conversion_disabled(DOT));
+	}
+
+	batch_transfer {
+		let c in 1 .. MaxBatchTransfers::get();
+
+		let currency_id = DOT;
+		let amount: Balance = 1_000 * dollar(currency_id);
+		let from: AccountId = whitelisted_caller();
+		set_balance(currency_id, &from, amount * c as Balance);
+
+		let transfers = (0 .. c)
+			.map(|i| (currency_id, lookup_of_account(account("to", i, SEED)), amount))
+			.collect::<Vec<_>>();
+	}: _(RawOrigin::Signed(from), transfers, true)
+	verify {
+		assert_eq!(<Currencies as MultiCurrency<_>>::total_balance(currency_id, &account("to", 0, SEED)), amount);
+	}
 }
 
 #[cfg(test)]
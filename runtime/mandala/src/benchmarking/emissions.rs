@@ -0,0 +1,65 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{dollar, EmissionPeriod, Emissions, Runtime, System, UnreleasedNativeVaultAccountId, ACA};
+
+use super::utils::set_balance;
+use frame_support::traits::OnInitialize;
+use frame_system::RawOrigin;
+use module_emissions::EmissionTarget;
+use orml_benchmarking::runtime_benchmarks;
+use sp_runtime::Permill;
+use sp_std::prelude::*;
+
+runtime_benchmarks! {
+	{ Runtime, module_emissions }
+
+	on_initialize {
+		set_balance(ACA, &UnreleasedNativeVaultAccountId::get(), 10_000 * dollar(ACA));
+		Emissions::set_emission_budget(RawOrigin::Root.into(), 1_000 * dollar(ACA))?;
+		Emissions::set_emission_proportions(
+			RawOrigin::Root.into(),
+			EmissionTarget::ALL.iter().map(|target| (*target, Permill::from_percent(30))).collect(),
+		)?;
+		System::set_block_number(EmissionPeriod::get());
+	}: {
+		Emissions::on_initialize(System::block_number());
+	}
+
+	set_emission_budget {
+	}: _(RawOrigin::Root, 1_000 * dollar(ACA))
+
+	set_emission_proportions {
+		let c in 1 .. EmissionTarget::ALL.len() as u32;
+
+		let proportions = EmissionTarget::ALL
+			.iter()
+			.take(c as usize)
+			.map(|target| (*target, Permill::from_percent(30)))
+			.collect::<Vec<_>>();
+	}: _(RawOrigin::Root, proportions)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::benchmarking::utils::tests::new_test_ext;
+	use orml_benchmarking::impl_benchmark_test_suite;
+
+	impl_benchmark_test_suite!(new_test_ext(),);
+}
@@ -16,13 +16,14 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{dollar, AccountId, Event, EvmAccounts, Origin, Runtime, System, ACA, EVM};
+use crate::{dollar, AccountId, Event, EvictionNoticePeriod, EvmAccounts, Origin, Runtime, System, ACA, EVM};
 
 use super::utils::set_aca_balance;
-use frame_support::dispatch::DispatchError;
+use frame_support::{dispatch::DispatchError, traits::Get};
 use frame_system::RawOrigin;
+use module_evm::runner::handler::STORAGE_SIZE;
 use orml_benchmarking::{runtime_benchmarks, whitelist_account};
-use sp_core::H160;
+use sp_core::{H160, H256};
 use sp_io::hashing::keccak_256;
 use sp_std::str::FromStr;
 
@@ -77,6 +78,18 @@ pub fn bob_account_id() -> AccountId {
 	AccountId::from(Into::<[u8; 32]>::into(data))
 }
 
+// insert storage entries without reserving their storage deposit
+fn insert_storages(contract: H160, c: u32) -> Vec<(H256, H256)> {
+	let storages = (0..c)
+		.map(|i| (H256::from_low_u64_be(i.into()), H256::from_low_u64_be(1)))
+		.collect::<Vec<_>>();
+	for (index, value) in &storages {
+		module_evm::AccountStorages::<Runtime>::insert(contract, index, value);
+	}
+	EVM::update_contract_storage_size(&contract, c.saturating_mul(STORAGE_SIZE) as i32);
+	storages
+}
+
 runtime_benchmarks! {
 	{ Runtime, module_evm }
 
@@ -145,6 +158,66 @@ runtime_benchmarks! {
 
 		whitelist_account!(alice_account);
 	}: _(RawOrigin::Signed(alice_account_id()), contract)
+
+	notify_eviction {
+		let alice_account = alice_account_id();
+
+		set_aca_balance(&alice_account, 1_000 * dollar(ACA));
+		let contract = deploy_contract(alice_account_id())?;
+		insert_storages(contract, 1);
+
+		whitelist_account!(alice_account);
+	}: _(RawOrigin::Signed(alice_account_id()), contract)
+
+	top_up_storage_deposit {
+		let alice_account = alice_account_id();
+
+		set_aca_balance(&alice_account, 1_000 * dollar(ACA));
+		let contract = deploy_contract(alice_account_id())?;
+		insert_storages(contract, 1);
+
+		whitelist_account!(alice_account);
+	}: _(RawOrigin::Signed(alice_account_id()), contract)
+
+	evict_contract {
+		let c in 0 .. 100;
+		let alice_account = alice_account_id();
+
+		set_aca_balance(&alice_account, 1_000 * dollar(ACA));
+		let contract = deploy_contract(alice_account_id())?;
+		insert_storages(contract, 100);
+		EVM::notify_eviction(Origin::signed(alice_account_id()), contract)?;
+		System::set_block_number(System::block_number() + EvictionNoticePeriod::get());
+
+		whitelist_account!(alice_account);
+	}: _(RawOrigin::Signed(alice_account_id()), contract, c)
+
+	resurrect_contract {
+		let c in 1 .. 100;
+		let alice_account = alice_account_id();
+
+		set_aca_balance(&alice_account, 1_000 * dollar(ACA));
+		let contract = deploy_contract(alice_account_id())?;
+		insert_storages(contract, c);
+		// the storage entries are resurrected in the order of removal
+		let storages = module_evm::AccountStorages::<Runtime>::iter_prefix(contract).collect::<Vec<_>>();
+		EVM::notify_eviction(Origin::signed(alice_account_id()), contract)?;
+		System::set_block_number(System::block_number() + EvictionNoticePeriod::get());
+		EVM::evict_contract(Origin::signed(alice_account_id()), contract, storages.len() as u32)?;
+
+		whitelist_account!(alice_account);
+	}: _(RawOrigin::Signed(alice_account_id()), contract, storages, H256::zero())
+
+	submit_contract_metadata {
+		let alice_account = alice_account_id();
+
+		set_aca_balance(&alice_account, 1_000 * dollar(ACA));
+		let contract = deploy_contract(alice_account_id())?;
+		let source_cid = vec![1u8; module_evm::MAX_SOURCE_CID_LENGTH];
+		let compiler_version = vec![1u8; module_evm::MAX_COMPILER_VERSION_LENGTH];
+
+		whitelist_account!(alice_account);
+	}: _(RawOrigin::Signed(alice_account_id()), contract, source_cid, compiler_version, H256::repeat_byte(1))
 }
 
 #[cfg(test)]
@@ -0,0 +1,128 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	dollar, AccountId, FederatedBridge, FederatedBridgeBondingDuration, MaxFederatedBridgeSigners, Runtime, System,
+	ACA, RENBTC,
+};
+
+use super::utils::set_aca_balance;
+use frame_benchmarking::account;
+use frame_support::traits::Get;
+use frame_system::RawOrigin;
+use module_federated_bridge::LockEvent;
+use orml_benchmarking::runtime_benchmarks;
+use sp_runtime::DispatchResult;
+use sp_std::prelude::*;
+
+const SEED: u32 = 0;
+
+fn bonded_signer(i: u32) -> Result<AccountId, sp_runtime::DispatchError> {
+	let signer: AccountId = account("signer", i, SEED);
+	set_aca_balance(&signer, 100_000 * dollar(ACA));
+	FederatedBridge::bond(RawOrigin::Signed(signer.clone()).into())?;
+	Ok(signer)
+}
+
+// set `n` bonded signers with the threshold `n`, and register RENBTC
+fn setup_signers(n: u32) -> Result<Vec<AccountId>, sp_runtime::DispatchError> {
+	let signers = (0..n).map(bonded_signer).collect::<Result<Vec<_>, _>>()?;
+	FederatedBridge::set_signers(RawOrigin::Root.into(), signers.clone(), n)?;
+	FederatedBridge::set_asset_cap(RawOrigin::Root.into(), RENBTC, Some(1_000 * dollar(RENBTC)))?;
+	Ok(signers)
+}
+
+fn lock_event(recipient: &AccountId) -> LockEvent<AccountId> {
+	LockEvent {
+		nonce: 0,
+		currency_id: RENBTC,
+		recipient: recipient.clone(),
+		amount: 10 * dollar(RENBTC),
+	}
+}
+
+fn attest_by(signers: &[AccountId], event: &LockEvent<AccountId>) -> DispatchResult {
+	for signer in signers {
+		FederatedBridge::attest(RawOrigin::Signed(signer.clone()).into(), event.clone())?;
+	}
+	Ok(())
+}
+
+runtime_benchmarks! {
+	{ Runtime, module_federated_bridge }
+
+	bond {
+		let caller: AccountId = account("caller", 0, SEED);
+		set_aca_balance(&caller, 100_000 * dollar(ACA));
+	}: _(RawOrigin::Signed(caller))
+
+	unbond {
+		let caller = bonded_signer(0)?;
+	}: _(RawOrigin::Signed(caller))
+
+	set_signers {
+		let n in 1 .. MaxFederatedBridgeSigners::get();
+		let signers = (0..n).map(bonded_signer).collect::<Result<Vec<_>, _>>()?;
+	}: _(RawOrigin::Root, signers, n)
+
+	set_asset_cap {
+	}: _(RawOrigin::Root, RENBTC, Some(1_000 * dollar(RENBTC)))
+
+	// attest which mints the lock event and clears the attestations of `n` signers
+	attest {
+		let n in 1 .. MaxFederatedBridgeSigners::get();
+		let signers = setup_signers(n)?;
+		let recipient: AccountId = account("recipient", 0, SEED);
+		let event = lock_event(&recipient);
+		attest_by(&signers[..(n as usize - 1)], &event)?;
+	}: _(RawOrigin::Signed(signers[n as usize - 1].clone()), event)
+
+	burn {
+		let signers = setup_signers(1)?;
+		let recipient: AccountId = account("recipient", 0, SEED);
+		attest_by(&signers, &lock_event(&recipient))?;
+	}: _(RawOrigin::Signed(recipient), RENBTC, 10 * dollar(RENBTC), vec![1u8; 32])
+
+	report_fraud {
+		let signers = setup_signers(1)?;
+		let caller: AccountId = account("caller", 0, SEED);
+		set_aca_balance(&caller, 100_000 * dollar(ACA));
+	}: _(RawOrigin::Signed(caller), signers[0].clone(), 0)
+
+	resolve_fraud_report {
+		let signers = setup_signers(1)?;
+		let caller: AccountId = account("caller", 0, SEED);
+		set_aca_balance(&caller, 100_000 * dollar(ACA));
+		FederatedBridge::report_fraud(RawOrigin::Signed(caller).into(), signers[0].clone(), 0)?;
+	}: _(RawOrigin::Root, 0, true)
+
+	withdraw_unbonded {
+		let caller = bonded_signer(0)?;
+		FederatedBridge::unbond(RawOrigin::Signed(caller.clone()).into())?;
+		System::set_block_number(System::block_number() + FederatedBridgeBondingDuration::get());
+	}: _(RawOrigin::Signed(caller))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::benchmarking::utils::tests::new_test_ext;
+	use orml_benchmarking::impl_benchmark_test_suite;
+
+	impl_benchmark_test_suite!(new_test_ext(),);
+}
@@ -0,0 +1,70 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{dollar, AccountId, FeeRebates, MaxRebateTiers, MaxRebatesPerBlock, Runtime, ACA};
+
+use super::utils::set_aca_balance;
+use frame_benchmarking::account;
+use frame_support::traits::{Get, OnInitialize};
+use frame_system::RawOrigin;
+use module_fee_rebates::{CurrentPeriod, RebateTier};
+use module_support::OnTransactionFeePaid;
+use orml_benchmarking::runtime_benchmarks;
+use sp_runtime::Permill;
+use sp_std::prelude::*;
+
+const SEED: u32 = 0;
+
+fn tiers() -> Vec<RebateTier> {
+	(0..MaxRebateTiers::get())
+		.map(|i| RebateTier {
+			volume_threshold: i.into(),
+			rebate_rate: Permill::from_percent(10),
+		})
+		.collect()
+}
+
+runtime_benchmarks! {
+	{ Runtime, module_fee_rebates }
+
+	set_rebate_tiers {
+	}: _(RawOrigin::Root, tiers())
+
+	on_initialize {
+		let c in 1 .. MaxRebatesPerBlock::get();
+
+		FeeRebates::set_rebate_tiers(RawOrigin::Root.into(), tiers())?;
+		set_aca_balance(&FeeRebates::account_id(), 1_000 * dollar(ACA));
+		for i in 0 .. c {
+			let who: AccountId = account("who", i, SEED);
+			FeeRebates::on_fee_paid(&who, 100 * dollar(ACA));
+		}
+		CurrentPeriod::<Runtime>::put(1);
+	}: {
+		FeeRebates::on_initialize(1);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::benchmarking::utils::tests::new_test_ext;
+	use orml_benchmarking::impl_benchmark_test_suite;
+
+	impl_benchmark_test_suite!(new_test_ext(),);
+}
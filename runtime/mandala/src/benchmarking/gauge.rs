@@ -0,0 +1,103 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	dollar, AccountId, CurrencyId, Emissions, Gauge, GaugeVotingPeriod, MaxGaugeVotes, MaxGauges, Runtime, System,
+	TokenSymbol, VoteEscrow, VoteEscrowMaxLockPeriod, ACA,
+};
+
+use super::utils::set_balance;
+use frame_benchmarking::{account, whitelisted_caller};
+use frame_support::traits::OnInitialize;
+use frame_system::RawOrigin;
+use module_emissions::EmissionTarget;
+use module_incentives::PoolId;
+use orml_benchmarking::runtime_benchmarks;
+use primitives::DexShare;
+use sp_core::H160;
+use sp_runtime::Permill;
+use sp_std::prelude::*;
+
+const SEED: u32 = 0;
+
+fn pool(i: u32) -> PoolId<AccountId> {
+	PoolId::DexIncentive(CurrencyId::DexShare(
+		DexShare::Token(TokenSymbol::AUSD),
+		DexShare::Erc20(H160::from_low_u64_be(i as u64)),
+	))
+}
+
+fn lock(who: &AccountId) -> Result<(), sp_runtime::DispatchError> {
+	set_balance(ACA, who, 1_000 * dollar(ACA));
+	VoteEscrow::create_lock(
+		RawOrigin::Signed(who.clone()).into(),
+		100 * dollar(ACA),
+		VoteEscrowMaxLockPeriod::get(),
+	)?;
+	Ok(())
+}
+
+runtime_benchmarks! {
+	{ Runtime, module_gauge }
+
+	on_initialize {
+		let c in 1 .. MaxGauges::get();
+
+		Emissions::set_emission_budget(RawOrigin::Root.into(), 1_000 * dollar(ACA))?;
+		Emissions::set_emission_proportions(RawOrigin::Root.into(), vec![(EmissionTarget::Incentives, Permill::one())])?;
+		for i in 0 .. c {
+			Gauge::add_gauge(RawOrigin::Root.into(), pool(i))?;
+			let voter: AccountId = account("voter", i, SEED);
+			lock(&voter)?;
+			Gauge::vote(RawOrigin::Signed(voter).into(), vec![(pool(i), Permill::one())])?;
+		}
+		System::set_block_number(GaugeVotingPeriod::get());
+	}: {
+		Gauge::on_initialize(System::block_number());
+	}
+
+	add_gauge {
+	}: _(RawOrigin::Root, pool(0))
+
+	remove_gauge {
+		Gauge::add_gauge(RawOrigin::Root.into(), pool(0))?;
+	}: _(RawOrigin::Root, pool(0))
+
+	vote {
+		let c in 1 .. MaxGaugeVotes::get();
+
+		let caller: AccountId = whitelisted_caller();
+		lock(&caller)?;
+		let mut votes = vec![];
+		for i in 0 .. c {
+			Gauge::add_gauge(RawOrigin::Root.into(), pool(i))?;
+			votes.push((pool(i), Permill::from_rational(1u32, c)));
+		}
+		// vote first to benchmark revoking the previous votes
+		Gauge::vote(RawOrigin::Signed(caller.clone()).into(), votes.clone())?;
+	}: _(RawOrigin::Signed(caller), votes)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::benchmarking::utils::tests::new_test_ext;
+	use orml_benchmarking::impl_benchmark_test_suite;
+
+	impl_benchmark_test_suite!(new_test_ext(),);
+}
@@ -0,0 +1,80 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{dollar, AccountId, Grants, Runtime, System, TreasuryAccount, AUSD};
+
+use super::utils::{lookup_of_account, set_balance};
+use frame_benchmarking::{account, whitelisted_caller};
+use frame_support::traits::Get;
+use frame_system::RawOrigin;
+use orml_benchmarking::runtime_benchmarks;
+use sp_std::prelude::*;
+
+const SEED: u32 = 0;
+
+// approve a grant of 10 periods of 100 AUSD to `beneficiary`, with `milestones`
+fn create_grant(beneficiary: &AccountId, milestones: Vec<u32>) -> Result<(), sp_runtime::DispatchError> {
+	set_balance(AUSD, &TreasuryAccount::get(), 1_000 * dollar(AUSD));
+	Grants::create_grant(
+		RawOrigin::Root.into(),
+		lookup_of_account(beneficiary.clone()),
+		AUSD,
+		100 * dollar(AUSD),
+		System::block_number(),
+		100,
+		10,
+		milestones,
+	)?;
+	Ok(())
+}
+
+runtime_benchmarks! {
+	{ Runtime, module_grants }
+
+	create_grant {
+		let beneficiary: AccountId = account("beneficiary", 0, SEED);
+		set_balance(AUSD, &TreasuryAccount::get(), 1_000 * dollar(AUSD));
+		let now = System::block_number();
+	}: _(RawOrigin::Root, lookup_of_account(beneficiary), AUSD, 100 * dollar(AUSD), now, 100, 10, vec![3, 6])
+
+	confirm_milestone {
+		let beneficiary: AccountId = account("beneficiary", 0, SEED);
+		create_grant(&beneficiary, vec![3, 6])?;
+	}: _(RawOrigin::Root, 0)
+
+	claim {
+		let caller: AccountId = whitelisted_caller();
+		create_grant(&caller, vec![])?;
+		System::set_block_number(System::block_number() + 500);
+	}: _(RawOrigin::Signed(caller), 0)
+
+	clawback {
+		let beneficiary: AccountId = account("beneficiary", 0, SEED);
+		create_grant(&beneficiary, vec![])?;
+		System::set_block_number(System::block_number() + 500);
+	}: _(RawOrigin::Root, 0)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::benchmarking::utils::tests::new_test_ext;
+	use orml_benchmarking::impl_benchmark_test_suite;
+
+	impl_benchmark_test_suite!(new_test_ext(),);
+}
@@ -24,6 +24,7 @@ use crate::{
 use frame_benchmarking::account;
 use frame_system::RawOrigin;
 use module_homa::RedeemStrategy;
+use module_support::HomaProtocol;
 use orml_benchmarking::runtime_benchmarks;
 use orml_traits::MultiCurrency;
 use sp_std::prelude::*;
@@ -47,6 +48,13 @@ runtime_benchmarks! {
 		set_balance(currency_id, &caller, 1_000 * dollar(currency_id));
 	}: _(RawOrigin::Signed(caller), 1_000 * dollar(currency_id))
 
+	// inject DOT to staking pool and mint LDOT at next era
+	mint_at_next_era {
+		let caller: AccountId = account("caller", 0, SEED);
+		let currency_id = GetStakingCurrencyId::get();
+		set_balance(currency_id, &caller, 1_000 * dollar(currency_id));
+	}: _(RawOrigin::Signed(caller), 1_000 * dollar(currency_id))
+
 	// redeem DOT from free pool
 	redeem_immediately {
 		let caller: AccountId = account("caller", 0, SEED);
@@ -94,6 +102,47 @@ runtime_benchmarks! {
 	verify {
 		assert!(<Currencies as MultiCurrency<_>>::total_balance(GetStakingCurrencyId::get(), &caller) > 0);
 	}
+
+	issue_unbonding_receipt {
+		let caller: AccountId = account("caller", 0, SEED);
+		let currency_id = GetStakingCurrencyId::get();
+		<StakingPool as HomaProtocol<_, _, _>>::add_unbonding(&caller, 10, dollar(currency_id));
+	}: _(RawOrigin::Signed(caller), 10)
+
+	claim_unbonding_receipt {
+		let caller: AccountId = account("caller", 0, SEED);
+		let currency_id = GetStakingCurrencyId::get();
+		<StakingPool as HomaProtocol<_, _, _>>::add_unbonding(&caller, 10, dollar(currency_id));
+		Homa::issue_unbonding_receipt(RawOrigin::Signed(caller.clone()).into(), 10)?;
+	}: _(RawOrigin::Signed(caller.clone()), 0)
+	verify {
+		assert_eq!(StakingPool::unbondings(&caller, 10), dollar(currency_id));
+	}
+
+	// top up the queued request, which will be re-queued at the tail
+	update_queued_request {
+		let caller: AccountId = account("caller", 0, SEED);
+		let currency_id = GetStakingCurrencyId::get();
+		set_balance(currency_id, &caller, 100 * dollar(currency_id));
+		module_staking_pool::HomaVolumeLimits::<Runtime>::put(module_staking_pool::VolumeLimits {
+			mint_per_block: Some(10 * dollar(currency_id)),
+			..Default::default()
+		});
+		Homa::mint(RawOrigin::Signed(caller.clone()).into(), 10 * dollar(currency_id))?;
+		Homa::mint(RawOrigin::Signed(caller.clone()).into(), dollar(currency_id))?;
+	}: _(RawOrigin::Signed(caller), 0, 2 * dollar(currency_id))
+	verify {
+		assert_eq!(StakingPool::request_queue_range(), (0, 2));
+	}
+
+	cancel_redeem_by_unbond {
+		let caller: AccountId = account("caller", 0, SEED);
+		let currency_id = GetStakingCurrencyId::get();
+		set_balance(currency_id, &caller, 1_000 * dollar(currency_id));
+		Homa::mint(RawOrigin::Signed(caller.clone()).into(), 1_000 * dollar(currency_id))?;
+		new_era();
+		Homa::redeem(RawOrigin::Signed(caller.clone()).into(), 10 * dollar(currency_id), RedeemStrategy::WaitForUnbonding)?;
+	}: _(RawOrigin::Signed(caller.clone()), dollar(currency_id))
 }
 
 #[cfg(test)]
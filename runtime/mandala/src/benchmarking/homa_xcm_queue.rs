@@ -0,0 +1,74 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{dollar, HomaXcmQueue, RelaychainLocation, Runtime, System, DOT};
+
+use frame_support::traits::OnInitialize;
+use frame_system::RawOrigin;
+use module_homa_xcm_queue::{FailureReason, HomaXcmOperation, OperationId, OperationStatus, Operations};
+use orml_benchmarking::runtime_benchmarks;
+use sp_std::prelude::*;
+use xcm::v0::Response;
+use xcm_executor::traits::OnResponse;
+
+fn enqueue_operation(status: OperationStatus<crate::BlockNumber>) -> OperationId {
+	let operation_id = HomaXcmQueue::enqueue(HomaXcmOperation::BondExtra {
+		account_index: 0,
+		amount: dollar(DOT),
+	});
+	Operations::<Runtime>::mutate(operation_id, |maybe_queued| {
+		if let Some(queued) = maybe_queued {
+			queued.status = status;
+		}
+	});
+	operation_id
+}
+
+runtime_benchmarks! {
+	{ Runtime, module_homa_xcm_queue }
+
+	// send the operation at the head of the queue
+	on_initialize {
+		enqueue_operation(OperationStatus::Pending { next_attempt: System::block_number() });
+	}: {
+		HomaXcmQueue::on_initialize(System::block_number());
+	}
+
+	on_response {
+		let operation_id = enqueue_operation(OperationStatus::Sent { timeout: System::block_number() + 10 });
+	}: {
+		HomaXcmQueue::on_response(RelaychainLocation::get(), operation_id, Response::Assets(vec![]));
+	}
+
+	retry_operation {
+		let operation_id = enqueue_operation(OperationStatus::Failed(FailureReason::Timeout));
+	}: _(RawOrigin::Root, operation_id)
+
+	cancel_operation {
+		let operation_id = enqueue_operation(OperationStatus::Failed(FailureReason::Timeout));
+	}: _(RawOrigin::Root, operation_id)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::benchmarking::utils::tests::new_test_ext;
+	use orml_benchmarking::impl_benchmark_test_suite;
+
+	impl_benchmark_test_suite!(new_test_ext(),);
+}
@@ -0,0 +1,98 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{dollar, AccountId, Launchpad, LaunchpadMaxNameLength, Runtime, ACA, AUSD};
+
+use super::utils::set_balance;
+use frame_benchmarking::{account, whitelisted_caller};
+use frame_support::BoundedVec;
+use frame_system::RawOrigin;
+use module_launchpad::BondingCurve;
+use module_support::Price;
+use orml_benchmarking::runtime_benchmarks;
+use sp_runtime::FixedPointNumber;
+use sp_std::{convert::TryFrom, prelude::*};
+
+const SEED: u32 = 0;
+
+fn name() -> BoundedVec<u8, LaunchpadMaxNameLength> {
+	BoundedVec::try_from(vec![b'A'; LaunchpadMaxNameLength::get() as usize]).unwrap()
+}
+
+// the price increases from 0.01 to 0.02 AUSD over the target supply of 1_000 tokens
+fn curve() -> BondingCurve {
+	BondingCurve::Linear {
+		initial_price: Price::saturating_from_rational(1, 100),
+		slope: Price::from_inner(10),
+	}
+}
+
+fn create_launch(creator: &AccountId) -> Result<(), sp_runtime::DispatchError> {
+	set_balance(ACA, creator, 1_000 * dollar(ACA));
+	Launchpad::create_launch(
+		RawOrigin::Signed(creator.clone()).into(),
+		name(),
+		name(),
+		curve(),
+		1_000 * dollar(ACA),
+		1_000 * dollar(ACA),
+	)?;
+	Ok(())
+}
+
+runtime_benchmarks! {
+	{ Runtime, module_launchpad }
+
+	create_launch {
+		let caller: AccountId = whitelisted_caller();
+		set_balance(ACA, &caller, 1_000 * dollar(ACA));
+	}: _(RawOrigin::Signed(caller), name(), name(), curve(), 1_000 * dollar(ACA), 1_000 * dollar(ACA))
+
+	buy {
+		let creator: AccountId = account("creator", 0, SEED);
+		let caller: AccountId = whitelisted_caller();
+		create_launch(&creator)?;
+		set_balance(AUSD, &caller, 1_000 * dollar(AUSD));
+		Launchpad::buy(RawOrigin::Signed(caller.clone()).into(), 0, 100 * dollar(ACA), 1_000 * dollar(AUSD))?;
+	}: _(RawOrigin::Signed(caller), 0, 100 * dollar(ACA), 1_000 * dollar(AUSD))
+
+	sell {
+		let creator: AccountId = account("creator", 0, SEED);
+		let caller: AccountId = whitelisted_caller();
+		create_launch(&creator)?;
+		set_balance(AUSD, &caller, 1_000 * dollar(AUSD));
+		Launchpad::buy(RawOrigin::Signed(caller.clone()).into(), 0, 100 * dollar(ACA), 1_000 * dollar(AUSD))?;
+	}: _(RawOrigin::Signed(caller), 0, 100 * dollar(ACA), 0)
+
+	finalize {
+		let creator: AccountId = account("creator", 0, SEED);
+		let caller: AccountId = whitelisted_caller();
+		create_launch(&creator)?;
+		set_balance(AUSD, &caller, 1_000 * dollar(AUSD));
+		Launchpad::buy(RawOrigin::Signed(caller.clone()).into(), 0, 1_000 * dollar(ACA), 1_000 * dollar(AUSD))?;
+	}: _(RawOrigin::Signed(caller), 0)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::benchmarking::utils::tests::new_test_ext;
+	use orml_benchmarking::impl_benchmark_test_suite;
+
+	impl_benchmark_test_suite!(new_test_ext(),);
+}
@@ -19,20 +19,45 @@
 #![cfg(feature = "runtime-benchmarks")]
 
 // module benchmarking
+pub mod account_freeze;
+pub mod airdrop;
+pub mod asset_registry;
 pub mod auction_manager;
 pub mod cdp_engine;
 pub mod cdp_treasury;
+pub mod circuit_breaker;
 pub mod collator_selection;
 pub mod dex;
 pub mod emergency_shutdown;
+pub mod emissions;
 pub mod evm;
 pub mod evm_accounts;
+pub mod federated_bridge;
+pub mod fee_rebates;
+pub mod gauge;
+pub mod grants;
 pub mod homa;
+pub mod homa_xcm_queue;
 pub mod honzon;
+pub mod idle_scheduler;
 pub mod incentives;
+pub mod launchpad;
+pub mod nft_marketplace;
 pub mod nominees_election;
+pub mod parameters;
 pub mod prices;
+pub mod proof_of_reserve;
+pub mod psm;
+pub mod rate_limit;
+pub mod scheduled_transfers;
+pub mod streaming;
+pub mod transaction_pause;
 pub mod transaction_payment;
+pub mod vesting_manager;
+pub mod vote_escrow;
+pub mod xcm_dead_letter;
+pub mod xcm_evm;
+pub mod xcm_transfer;
 
 // orml benchmarking
 pub mod auction;
@@ -46,3 +71,5 @@ pub mod vesting;
 
 // ecosystem benchmarking
 pub mod chainsafe_transfer;
+pub mod renvm_bridge;
+pub mod starport;
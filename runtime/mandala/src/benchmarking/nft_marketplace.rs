@@ -0,0 +1,149 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	dollar, AccountId, NFTMarketplace, NftMinAuctionDuration, NftPalletId, OrmlNFT, Runtime, System, ACA, NFT,
+};
+
+use super::utils::{lookup_of_account, set_balance};
+use frame_benchmarking::{account, whitelisted_caller};
+use frame_system::RawOrigin;
+use orml_benchmarking::runtime_benchmarks;
+use sp_runtime::{traits::AccountIdConversion, DispatchError};
+use sp_std::prelude::*;
+
+const SEED: u32 = 0;
+
+// create a class with royalty and mint a token of it to `owner`
+fn create_token(owner: &AccountId) -> Result<(u32, u64), DispatchError> {
+	let class_id = OrmlNFT::next_class_id();
+	let class_account: AccountId = NftPalletId::get().into_sub_account(class_id);
+	set_balance(ACA, owner, 1_000 * dollar(ACA));
+	NFT::create_class(
+		RawOrigin::Signed(owner.clone()).into(),
+		vec![1],
+		module_nft::Properties(module_nft::ClassProperty::Transferable | module_nft::ClassProperty::Burnable),
+	)?;
+	set_balance(ACA, &class_account, 1_000 * dollar(ACA));
+	NFT::mint(
+		RawOrigin::Signed(class_account.clone()).into(),
+		lookup_of_account(owner.clone()),
+		class_id,
+		vec![1],
+		1,
+	)?;
+	NFT::set_class_royalty(
+		RawOrigin::Signed(class_account).into(),
+		class_id,
+		Some(module_nft::RoyaltyInfo {
+			beneficiary: account("beneficiary", 0, SEED),
+			basis_points: 500,
+		}),
+	)?;
+	Ok((class_id, 0))
+}
+
+runtime_benchmarks! {
+	{ Runtime, module_nft_marketplace }
+
+	list {
+		let caller: AccountId = whitelisted_caller();
+		let token = create_token(&caller)?;
+	}: _(RawOrigin::Signed(caller), token, 100 * dollar(ACA))
+
+	cancel_listing {
+		let caller: AccountId = whitelisted_caller();
+		let token = create_token(&caller)?;
+		NFTMarketplace::list(RawOrigin::Signed(caller.clone()).into(), token, 100 * dollar(ACA))?;
+	}: _(RawOrigin::Signed(caller), token)
+
+	buy {
+		let seller: AccountId = account("seller", 0, SEED);
+		let caller: AccountId = whitelisted_caller();
+		let token = create_token(&seller)?;
+		NFTMarketplace::list(RawOrigin::Signed(seller).into(), token, 100 * dollar(ACA))?;
+		set_balance(ACA, &caller, 1_000 * dollar(ACA));
+	}: _(RawOrigin::Signed(caller), token, 100 * dollar(ACA))
+
+	create_auction {
+		let caller: AccountId = whitelisted_caller();
+		let token = create_token(&caller)?;
+	}: _(RawOrigin::Signed(caller), token, 100 * dollar(ACA), NftMinAuctionDuration::get())
+
+	bid {
+		let seller: AccountId = account("seller", 0, SEED);
+		let bidder: AccountId = account("bidder", 0, SEED);
+		let caller: AccountId = whitelisted_caller();
+		let token = create_token(&seller)?;
+		NFTMarketplace::create_auction(RawOrigin::Signed(seller).into(), token, 100 * dollar(ACA), NftMinAuctionDuration::get())?;
+		set_balance(ACA, &bidder, 1_000 * dollar(ACA));
+		NFTMarketplace::bid(RawOrigin::Signed(bidder).into(), token, 100 * dollar(ACA))?;
+		set_balance(ACA, &caller, 1_000 * dollar(ACA));
+	}: _(RawOrigin::Signed(caller), token, 200 * dollar(ACA))
+
+	cancel_auction {
+		let caller: AccountId = whitelisted_caller();
+		let token = create_token(&caller)?;
+		NFTMarketplace::create_auction(RawOrigin::Signed(caller.clone()).into(), token, 100 * dollar(ACA), NftMinAuctionDuration::get())?;
+	}: _(RawOrigin::Signed(caller), token)
+
+	settle_auction {
+		let seller: AccountId = account("seller", 0, SEED);
+		let bidder: AccountId = account("bidder", 0, SEED);
+		let caller: AccountId = whitelisted_caller();
+		let token = create_token(&seller)?;
+		NFTMarketplace::create_auction(RawOrigin::Signed(seller).into(), token, 100 * dollar(ACA), NftMinAuctionDuration::get())?;
+		set_balance(ACA, &bidder, 1_000 * dollar(ACA));
+		NFTMarketplace::bid(RawOrigin::Signed(bidder).into(), token, 100 * dollar(ACA))?;
+		System::set_block_number(System::block_number() + NftMinAuctionDuration::get());
+	}: _(RawOrigin::Signed(caller), token)
+
+	make_offer {
+		let seller: AccountId = account("seller", 0, SEED);
+		let caller: AccountId = whitelisted_caller();
+		let token = create_token(&seller)?;
+		set_balance(ACA, &caller, 1_000 * dollar(ACA));
+		NFTMarketplace::make_offer(RawOrigin::Signed(caller.clone()).into(), token, 100 * dollar(ACA))?;
+	}: _(RawOrigin::Signed(caller), token, 200 * dollar(ACA))
+
+	withdraw_offer {
+		let seller: AccountId = account("seller", 0, SEED);
+		let caller: AccountId = whitelisted_caller();
+		let token = create_token(&seller)?;
+		set_balance(ACA, &caller, 1_000 * dollar(ACA));
+		NFTMarketplace::make_offer(RawOrigin::Signed(caller.clone()).into(), token, 100 * dollar(ACA))?;
+	}: _(RawOrigin::Signed(caller), token)
+
+	accept_offer {
+		let buyer: AccountId = account("buyer", 0, SEED);
+		let caller: AccountId = whitelisted_caller();
+		let token = create_token(&caller)?;
+		NFTMarketplace::list(RawOrigin::Signed(caller.clone()).into(), token, 100 * dollar(ACA))?;
+		set_balance(ACA, &buyer, 1_000 * dollar(ACA));
+		NFTMarketplace::make_offer(RawOrigin::Signed(buyer.clone()).into(), token, 100 * dollar(ACA))?;
+	}: _(RawOrigin::Signed(caller), token, buyer)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::benchmarking::utils::tests::new_test_ext;
+	use orml_benchmarking::impl_benchmark_test_suite;
+
+	impl_benchmark_test_suite!(new_test_ext(),);
+}
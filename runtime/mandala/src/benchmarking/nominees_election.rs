@@ -17,8 +17,8 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-	AccountId, MaxUnlockingChunks, MinCouncilBondThreshold, NominateesCount, NomineesElection, PolkadotBondingDuration,
-	Runtime, LDOT,
+	dollar, AccountId, MaxUnlockingChunks, MinCouncilBondThreshold, NominateesCount, NomineesElection,
+	PolkadotBondingDuration, Runtime, ACA, LDOT,
 };
 
 use super::utils::set_balance;
@@ -73,6 +73,7 @@ runtime_benchmarks! {
 
 		let caller: AccountId = whitelisted_caller();
 		set_balance(LDOT, &caller, 2*MinCouncilBondThreshold::get());
+		set_balance(ACA, &caller, 100 * dollar(ACA));
 		NomineesElection::bond(RawOrigin::Signed(caller.clone()).into(), MinCouncilBondThreshold::get())?;
 	}: _(RawOrigin::Signed(caller), targets)
 
@@ -82,9 +83,49 @@ runtime_benchmarks! {
 
 		let caller: AccountId = whitelisted_caller();
 		set_balance(LDOT, &caller, 2*MinCouncilBondThreshold::get());
+		set_balance(ACA, &caller, 100 * dollar(ACA));
 		NomineesElection::bond(RawOrigin::Signed(caller.clone()).into(), MinCouncilBondThreshold::get())?;
 		NomineesElection::nominate(RawOrigin::Signed(caller.clone()).into(), targets)?;
 	}: _(RawOrigin::Signed(caller))
+
+	refresh_vote {
+		let targets = (0..NominateesCount::get()).map(|c| account("nominatees", c, SEED)).collect::<Vec<_>>();
+
+		let caller: AccountId = whitelisted_caller();
+		set_balance(LDOT, &caller, 2*MinCouncilBondThreshold::get());
+		set_balance(ACA, &caller, 100 * dollar(ACA));
+		NomineesElection::bond(RawOrigin::Signed(caller.clone()).into(), MinCouncilBondThreshold::get())?;
+		NomineesElection::nominate(RawOrigin::Signed(caller.clone()).into(), targets)?;
+	}: _(RawOrigin::Signed(caller))
+
+	vote_council {
+		let caller: AccountId = whitelisted_caller();
+		set_balance(LDOT, &caller, 2*MinCouncilBondThreshold::get());
+		set_balance(ACA, &caller, 100 * dollar(ACA));
+		NomineesElection::bond(RawOrigin::Signed(caller.clone()).into(), MinCouncilBondThreshold::get())?;
+	}: _(RawOrigin::Signed(caller), account("candidate", 0, SEED))
+
+	revoke_council_vote {
+		let caller: AccountId = whitelisted_caller();
+		set_balance(LDOT, &caller, 2*MinCouncilBondThreshold::get());
+		set_balance(ACA, &caller, 100 * dollar(ACA));
+		NomineesElection::bond(RawOrigin::Signed(caller.clone()).into(), MinCouncilBondThreshold::get())?;
+		NomineesElection::vote_council(RawOrigin::Signed(caller.clone()).into(), account("candidate", 0, SEED))?;
+	}: _(RawOrigin::Signed(caller))
+
+	adjust_nominees {
+		let c in 1 .. NominateesCount::get();
+		let removes = (0..c).map(|c| account("nominatees", c, SEED)).collect::<Vec<_>>();
+		let adds = (0..c).map(|c| account("new_nominatees", c, SEED)).collect::<Vec<_>>();
+
+		let caller: AccountId = whitelisted_caller();
+		set_balance(LDOT, &caller, 2*MinCouncilBondThreshold::get());
+		set_balance(ACA, &caller, 100 * dollar(ACA));
+		NomineesElection::bond(RawOrigin::Signed(caller.clone()).into(), MinCouncilBondThreshold::get())?;
+		NomineesElection::nominate(RawOrigin::Signed(caller.clone()).into(), removes.clone())?;
+		NomineesElection::vote_council(RawOrigin::Signed(caller.clone()).into(), caller.clone())?;
+		NomineesElection::on_new_era(1);
+	}: _(RawOrigin::Signed(caller), removes, adds)
 }
 
 #[cfg(test)]
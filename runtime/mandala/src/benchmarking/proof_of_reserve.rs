@@ -0,0 +1,90 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{dollar, AccountId, Currencies, ProofOfReserve, ReserveCheckPeriod, Runtime, System, RENBTC};
+
+use super::utils::set_balance;
+use frame_benchmarking::account;
+use frame_support::traits::OnInitialize;
+use frame_system::RawOrigin;
+use module_proof_of_reserve::{ReserveInfo, Reserves};
+use orml_benchmarking::runtime_benchmarks;
+use orml_traits::MultiCurrency;
+use primitives::CurrencyId;
+use sp_std::prelude::*;
+
+const SEED: u32 = 0;
+
+fn monitor_at_issuance(currency_id: CurrencyId) -> Result<(), &'static str> {
+	let locked = Currencies::total_issuance(currency_id);
+	ProofOfReserve::monitor_asset(RawOrigin::Root.into(), currency_id, locked)?;
+	Ok(())
+}
+
+runtime_benchmarks! {
+	{ Runtime, module_proof_of_reserve }
+
+	// all the monitored assets fail the check
+	on_initialize {
+		let n in 0 .. 20;
+
+		let who: AccountId = account("who", 0, SEED);
+		for i in 0 .. n {
+			let currency_id = CurrencyId::LaunchpadToken(i);
+			monitor_at_issuance(currency_id)?;
+			set_balance(currency_id, &who, dollar(RENBTC));
+		}
+		System::set_block_number(ReserveCheckPeriod::get());
+	}: {
+		ProofOfReserve::on_initialize(System::block_number());
+	}
+
+	monitor_asset {
+	}: _(RawOrigin::Root, RENBTC, 1_000 * dollar(RENBTC))
+
+	unmonitor_asset {
+		monitor_at_issuance(RENBTC)?;
+	}: _(RawOrigin::Root, RENBTC)
+
+	// the report pauses the mints
+	report_reserve {
+		let caller: AccountId = account("caller", 0, SEED);
+		crate::OperatorMembershipAcala::add_member(RawOrigin::Root.into(), caller.clone())?;
+		set_balance(RENBTC, &caller, 1_000 * dollar(RENBTC));
+		monitor_at_issuance(RENBTC)?;
+	}: _(RawOrigin::Signed(caller), RENBTC, 0)
+
+	resume_mints {
+		let who: AccountId = account("who", 0, SEED);
+		set_balance(RENBTC, &who, 1_000 * dollar(RENBTC));
+		ProofOfReserve::monitor_asset(RawOrigin::Root.into(), RENBTC, 0)?;
+		Reserves::<Runtime>::insert(RENBTC, ReserveInfo {
+			locked: 1_000 * dollar(RENBTC),
+			updated_at: System::block_number(),
+		});
+	}: _(RawOrigin::Root, RENBTC)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::benchmarking::utils::tests::new_test_ext;
+	use orml_benchmarking::impl_benchmark_test_suite;
+
+	impl_benchmark_test_suite!(new_test_ext(),);
+}
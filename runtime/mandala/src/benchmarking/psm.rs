@@ -0,0 +1,73 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{dollar, AccountId, Psm, Runtime, AUSD, RENBTC};
+
+use super::utils::set_balance;
+use frame_benchmarking::whitelisted_caller;
+use frame_system::RawOrigin;
+use orml_benchmarking::runtime_benchmarks;
+use sp_runtime::Permill;
+use sp_std::prelude::*;
+
+fn approve_renbtc() -> Result<(), &'static str> {
+	Psm::approve_asset(
+		RawOrigin::Root.into(),
+		RENBTC,
+		Permill::from_percent(1),
+		1_000_000 * dollar(AUSD),
+	)?;
+	Ok(())
+}
+
+runtime_benchmarks! {
+	{ Runtime, module_psm }
+
+	approve_asset {
+	}: _(RawOrigin::Root, RENBTC, Permill::from_percent(1), 1_000_000 * dollar(AUSD))
+
+	remove_asset {
+		approve_renbtc()?;
+	}: _(RawOrigin::Root, RENBTC)
+
+	set_paused {
+		approve_renbtc()?;
+	}: _(RawOrigin::Root, RENBTC, true)
+
+	mint {
+		let caller: AccountId = whitelisted_caller();
+		approve_renbtc()?;
+		set_balance(RENBTC, &caller, 100 * dollar(RENBTC));
+	}: _(RawOrigin::Signed(caller), RENBTC, 100 * dollar(RENBTC))
+
+	redeem {
+		let caller: AccountId = whitelisted_caller();
+		approve_renbtc()?;
+		set_balance(RENBTC, &caller, 200 * dollar(RENBTC));
+		Psm::mint(RawOrigin::Signed(caller.clone()).into(), RENBTC, 200 * dollar(RENBTC))?;
+	}: _(RawOrigin::Signed(caller), RENBTC, 100 * dollar(RENBTC))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::benchmarking::utils::tests::new_test_ext;
+	use orml_benchmarking::impl_benchmark_test_suite;
+
+	impl_benchmark_test_suite!(new_test_ext(),);
+}
@@ -0,0 +1,71 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{dollar, AccountId, RenVmBridge, Runtime, ACA, RENBTC};
+
+use super::utils::{set_aca_balance, set_balance};
+use frame_benchmarking::account;
+use frame_system::RawOrigin;
+use orml_benchmarking::runtime_benchmarks;
+use sp_core::ecdsa;
+use sp_runtime::Permill;
+use sp_std::vec;
+
+const SEED: u32 = 0;
+
+runtime_benchmarks! {
+	{ Runtime, ecosystem_renvm_bridge }
+
+	mint {
+		let who: AccountId = account("who", 0, SEED);
+		let sig = ecdsa::Signature::from_raw([1u8; 65]);
+
+		// the mint fee is charged from the native token
+		set_aca_balance(&who, dollar(ACA) * 1_000);
+		RenVmBridge::set_fees(RawOrigin::Root.into(), Permill::from_percent(1), Permill::from_percent(1))?;
+		RenVmBridge::set_mint_volume_cap(RawOrigin::Root.into(), Some(dollar(RENBTC) * 1_000))?;
+	}: _(RawOrigin::None, who, [0u8; 32], dollar(RENBTC) * 10, [0u8; 32], sig)
+
+	burn {
+		let sender: AccountId = account("sender", 0, SEED);
+
+		set_balance(RENBTC, &sender, dollar(RENBTC) * 100);
+		RenVmBridge::set_fees(RawOrigin::Root.into(), Permill::from_percent(1), Permill::from_percent(1))?;
+	}: _(RawOrigin::Signed(sender), vec![1u8; 32], dollar(RENBTC) * 10)
+
+	rotate_key {
+		let previous_sig = ecdsa::Signature::from_raw([1u8; 65]);
+		let sig = ecdsa::Signature::from_raw([2u8; 65]);
+
+		// the previous key is kept for the overlap window
+		RenVmBridge::rotate_key(RawOrigin::None.into(), [1u8; 20], previous_sig)?;
+	}: _(RawOrigin::None, [2u8; 20], sig)
+
+	set_fees {}: _(RawOrigin::Root, Permill::from_percent(1), Permill::from_percent(1))
+
+	set_mint_volume_cap {}: _(RawOrigin::Root, Some(dollar(RENBTC) * 1_000))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::benchmarking::utils::tests::new_test_ext;
+	use orml_benchmarking::impl_benchmark_test_suite;
+
+	impl_benchmark_test_suite!(new_test_ext(),);
+}
@@ -0,0 +1,87 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{dollar, AccountId, Runtime, ScheduledTransfers, System, ACA, AUSD};
+
+use super::utils::{lookup_of_account, set_aca_balance, set_balance};
+use frame_benchmarking::{account, whitelisted_caller};
+use frame_support::traits::OnInitialize;
+use frame_system::RawOrigin;
+use orml_benchmarking::runtime_benchmarks;
+use sp_std::prelude::*;
+
+const SEED: u32 = 0;
+
+runtime_benchmarks! {
+	{ Runtime, module_scheduled_transfers }
+
+	on_initialize {
+		let c in 1 .. 100;
+
+		let caller: AccountId = account("caller", 0, SEED);
+		set_balance(AUSD, &caller, 1_000 * dollar(AUSD) * c as u128);
+		set_aca_balance(&caller, 1_000 * dollar(ACA));
+		for i in 0 .. c {
+			let to: AccountId = account("to", i, SEED);
+			ScheduledTransfers::schedule_transfer(
+				RawOrigin::Signed(caller.clone()).into(),
+				lookup_of_account(to),
+				AUSD,
+				100 * dollar(AUSD),
+				System::block_number() + 1,
+				Some(10),
+				10,
+			)?;
+		}
+		System::set_block_number(System::block_number() + 1);
+	}: {
+		ScheduledTransfers::on_initialize(System::block_number());
+	}
+
+	schedule_transfer {
+		let caller: AccountId = whitelisted_caller();
+		let to: AccountId = account("to", 0, SEED);
+		set_balance(AUSD, &caller, 1_000 * dollar(AUSD));
+		set_aca_balance(&caller, 1_000 * dollar(ACA));
+	}: _(RawOrigin::Signed(caller), lookup_of_account(to), AUSD, 100 * dollar(AUSD), System::block_number() + 1, Some(10), 10)
+
+	cancel_transfer {
+		let caller: AccountId = whitelisted_caller();
+		let to: AccountId = account("to", 0, SEED);
+		set_balance(AUSD, &caller, 1_000 * dollar(AUSD));
+		set_aca_balance(&caller, 1_000 * dollar(ACA));
+		ScheduledTransfers::schedule_transfer(RawOrigin::Signed(caller.clone()).into(), lookup_of_account(to), AUSD, 100 * dollar(AUSD), System::block_number() + 1, Some(10), 10)?;
+	}: _(RawOrigin::Signed(caller), 0)
+
+	modify_transfer {
+		let caller: AccountId = whitelisted_caller();
+		let to: AccountId = account("to", 0, SEED);
+		set_balance(AUSD, &caller, 2_000 * dollar(AUSD));
+		set_aca_balance(&caller, 1_000 * dollar(ACA));
+		ScheduledTransfers::schedule_transfer(RawOrigin::Signed(caller.clone()).into(), lookup_of_account(to), AUSD, 100 * dollar(AUSD), System::block_number() + 1, Some(10), 5)?;
+	}: _(RawOrigin::Signed(caller), 0, 100 * dollar(AUSD), Some(10), 10)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::benchmarking::utils::tests::new_test_ext;
+	use orml_benchmarking::impl_benchmark_test_suite;
+
+	impl_benchmark_test_suite!(new_test_ext(),);
+}
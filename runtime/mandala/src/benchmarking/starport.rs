@@ -0,0 +1,119 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{dollar, AccountId, BlockNumber, MaxGatewayAuthorityCount, Runtime, Starport, StarportPalletId, DOT};
+
+use super::utils::set_balance;
+use ecosystem_starport::{
+	GatewayAuthorities, GatewayLimits, GatewayNotice, GatewayNoticePayload, PendingWithdrawal, PendingWithdrawals,
+	SupplyCaps,
+};
+use frame_benchmarking::account;
+use frame_support::{traits::Get, BoundedVec};
+use frame_system::RawOrigin;
+use orml_benchmarking::runtime_benchmarks;
+use sp_runtime::traits::AccountIdConversion;
+use sp_std::{convert::TryFrom, prelude::*};
+
+const SEED: u32 = 0;
+
+fn gateway_limits() -> GatewayLimits {
+	GatewayLimits {
+		upload_cap: dollar(DOT) * 1_000,
+		download_cap: dollar(DOT) * 1_000,
+		account_upload_cap: dollar(DOT) * 1_000,
+		account_download_cap: dollar(DOT) * 1_000,
+		large_withdrawal_threshold: dollar(DOT) * 100,
+	}
+}
+
+fn pending_withdrawal(who: AccountId) -> PendingWithdrawal<AccountId, BlockNumber> {
+	PendingWithdrawal {
+		currency_id: DOT,
+		amount: dollar(DOT) * 100,
+		who,
+		unlock_at: 0,
+	}
+}
+
+runtime_benchmarks! {
+	{ Runtime, ecosystem_starport }
+
+	lock {
+		let who: AccountId = account("who", 0, SEED);
+
+		set_balance(DOT, &who, dollar(DOT) * 100);
+		SupplyCaps::<Runtime>::insert(DOT, dollar(DOT) * 1_000);
+		Starport::set_gateway_limits(RawOrigin::Root.into(), DOT, Some(gateway_limits()))?;
+	}: _(RawOrigin::Signed(who), DOT, dollar(DOT) * 10)
+
+	lock_to {
+		let who: AccountId = account("who", 0, SEED);
+		let to: AccountId = account("to", 0, SEED);
+
+		set_balance(DOT, &who, dollar(DOT) * 100);
+		SupplyCaps::<Runtime>::insert(DOT, dollar(DOT) * 1_000);
+		Starport::set_gateway_limits(RawOrigin::Root.into(), DOT, Some(gateway_limits()))?;
+	}: _(RawOrigin::Signed(who), to, DOT, dollar(DOT) * 10)
+
+	// unlock the asset signed by all the authorities
+	invoke {
+		let s in 1 .. MaxGatewayAuthorityCount::get();
+
+		let invoker: AccountId = account("invoker", 0, SEED);
+		let who: AccountId = account("who", 0, SEED);
+		let authorities: Vec<AccountId> = (0..s).map(|i| account("authority", i, SEED)).collect();
+		let notice = GatewayNotice::new(0, GatewayNoticePayload::Unlock {
+			currency_id: DOT,
+			amount: dollar(DOT) * 10,
+			who,
+		});
+
+		GatewayAuthorities::<Runtime>::put(BoundedVec::try_from(authorities.clone()).unwrap());
+		set_balance(DOT, &StarportPalletId::get().into_account(), dollar(DOT) * 100);
+		Starport::set_gateway_limits(RawOrigin::Root.into(), DOT, Some(gateway_limits()))?;
+	}: _(RawOrigin::Signed(invoker), notice, authorities)
+
+	set_gateway_limits {}: _(RawOrigin::Root, DOT, Some(gateway_limits()))
+
+	set_max_supply_cap {
+		SupplyCaps::<Runtime>::insert(DOT, dollar(DOT) * 1_000);
+	}: _(RawOrigin::Root, DOT, Some(dollar(DOT) * 100))
+
+	claim_withdrawal {
+		let who: AccountId = account("who", 0, SEED);
+
+		set_balance(DOT, &StarportPalletId::get().into_account(), dollar(DOT) * 100);
+		PendingWithdrawals::<Runtime>::insert(0, pending_withdrawal(who.clone()));
+	}: _(RawOrigin::Signed(who), 0)
+
+	cancel_withdrawal {
+		let who: AccountId = account("who", 0, SEED);
+
+		PendingWithdrawals::<Runtime>::insert(0, pending_withdrawal(who));
+	}: _(RawOrigin::Root, 0)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::benchmarking::utils::tests::new_test_ext;
+	use orml_benchmarking::impl_benchmark_test_suite;
+
+	impl_benchmark_test_suite!(new_test_ext(),);
+}
@@ -0,0 +1,83 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{dollar, AccountId, Runtime, Streaming, System, AUSD};
+
+use super::utils::{lookup_of_account, set_balance};
+use frame_benchmarking::{account, whitelisted_caller};
+use frame_system::RawOrigin;
+use orml_benchmarking::runtime_benchmarks;
+use sp_std::prelude::*;
+
+const SEED: u32 = 0;
+
+// create a stream of 1_000 AUSD from `sender` to `recipient` over 100 blocks
+fn create_stream(sender: &AccountId, recipient: &AccountId) -> Result<(), sp_runtime::DispatchError> {
+	set_balance(AUSD, sender, 1_000 * dollar(AUSD));
+	let now = System::block_number();
+	Streaming::create_stream(
+		RawOrigin::Signed(sender.clone()).into(),
+		lookup_of_account(recipient.clone()),
+		AUSD,
+		1_000 * dollar(AUSD),
+		now,
+		now + 100,
+	)?;
+	Ok(())
+}
+
+runtime_benchmarks! {
+	{ Runtime, module_streaming }
+
+	create_stream {
+		let caller: AccountId = whitelisted_caller();
+		let recipient: AccountId = account("recipient", 0, SEED);
+		set_balance(AUSD, &caller, 1_000 * dollar(AUSD));
+		let now = System::block_number();
+	}: _(RawOrigin::Signed(caller), lookup_of_account(recipient), AUSD, 1_000 * dollar(AUSD), now, now + 100)
+
+	withdraw_from_stream {
+		let sender: AccountId = account("sender", 0, SEED);
+		let caller: AccountId = whitelisted_caller();
+		create_stream(&sender, &caller)?;
+		System::set_block_number(System::block_number() + 50);
+	}: _(RawOrigin::Signed(caller), 0, 100 * dollar(AUSD))
+
+	cancel_stream {
+		let caller: AccountId = whitelisted_caller();
+		let recipient: AccountId = account("recipient", 0, SEED);
+		create_stream(&caller, &recipient)?;
+		System::set_block_number(System::block_number() + 50);
+	}: _(RawOrigin::Signed(caller), 0)
+
+	transfer_stream {
+		let sender: AccountId = account("sender", 0, SEED);
+		let caller: AccountId = whitelisted_caller();
+		let to: AccountId = account("to", 0, SEED);
+		create_stream(&sender, &caller)?;
+	}: _(RawOrigin::Signed(caller), 0, lookup_of_account(to))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::benchmarking::utils::tests::new_test_ext;
+	use orml_benchmarking::impl_benchmark_test_suite;
+
+	impl_benchmark_test_suite!(new_test_ext(),);
+}
@@ -16,13 +16,44 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{AccountId, CurrencyId, Runtime, System, TokenSymbol, TransactionPayment};
-use frame_benchmarking::whitelisted_caller;
-use frame_support::traits::OnFinalize;
+use super::utils::{lookup_of_account, set_aca_balance};
+use crate::{
+	dollar, AccountId, Call, CurrencyId, FeeTankWithdrawalDelay, ProxyType, Runtime, Signature, System, TokenSymbol,
+	TransactionPayment, ACA,
+};
+use codec::Encode;
+use frame_benchmarking::{account, whitelisted_caller};
+use frame_support::{
+	traits::{Get, OnFinalize},
+	weights::DispatchClass,
+};
 use frame_system::RawOrigin;
+use module_transaction_payment::{Multiplier, SponsorPermit, SponsorPermitOf, SponsoredCalls, SPONSOR_PERMIT_PREFIX};
 use orml_benchmarking::runtime_benchmarks;
+use sp_io::hashing::{blake2_256, keccak_256};
+use sp_runtime::FixedPointNumber;
 use sp_std::prelude::*;
 
+const SEED: u32 = 0;
+
+fn sponsor() -> secp256k1::SecretKey {
+	secp256k1::SecretKey::parse(&keccak_256(b"Sponsor")).unwrap()
+}
+
+fn sponsor_account_id() -> AccountId {
+	let public = secp256k1::PublicKey::from_secret_key(&sponsor());
+	AccountId::from(blake2_256(&public.serialize_compressed()))
+}
+
+fn sign_permit(permit: &SponsorPermitOf<Runtime>) -> Signature {
+	let message = (SPONSOR_PERMIT_PREFIX, System::block_hash(0), permit).encode();
+	let (signature, recovery_id) = secp256k1::sign(&secp256k1::Message::parse(&blake2_256(&message)), &sponsor());
+	let mut data = [0u8; 65];
+	data[0..64].copy_from_slice(&signature.serialize()[..]);
+	data[64] = recovery_id.serialize();
+	Signature::Ecdsa(sp_core::ecdsa::Signature::from_raw(data))
+}
+
 runtime_benchmarks! {
 	{ Runtime, module_transaction_payment }
 
@@ -34,6 +65,70 @@ runtime_benchmarks! {
 		assert_eq!(TransactionPayment::default_fee_currency_id(&caller), Some(currency_id));
 	}
 
+	set_sponsor_budget {
+		let caller: AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller.clone()), dollar(ACA))
+	verify {
+		assert_eq!(TransactionPayment::sponsor_budgets(&caller), dollar(ACA));
+	}
+
+	sponsored_call {
+		let caller: AccountId = whitelisted_caller();
+		let call = Box::new(Call::System(frame_system::Call::remark(vec![])));
+		let permit = SponsorPermit {
+			sponsor: sponsor_account_id(),
+			beneficiary: caller.clone(),
+			calls: SponsoredCalls::Class(ProxyType::Any),
+			max_fee: dollar(ACA),
+			nonce: 0,
+			deadline: System::block_number() + 1,
+		};
+		let signature = sign_permit(&permit);
+	}: _(RawOrigin::Signed(caller), permit, signature, call)
+
+	set_class_fee_multiplier {
+		let multiplier = Multiplier::saturating_from_rational(1, 2);
+	}: _(RawOrigin::Root, DispatchClass::Operational, Some(multiplier))
+	verify {
+		assert_eq!(TransactionPayment::class_fee_multipliers(DispatchClass::Operational), Some(multiplier));
+	}
+
+	set_pallet_fee_multiplier {
+		let multiplier = Multiplier::saturating_from_integer(2);
+	}: _(RawOrigin::Root, b"EVM".to_vec(), Some(multiplier))
+	verify {
+		assert_eq!(TransactionPayment::pallet_fee_multipliers(b"EVM".to_vec()), Some(multiplier));
+	}
+
+	deposit_fee_tank {
+		let caller: AccountId = whitelisted_caller();
+		let who: AccountId = account("who", 0, SEED);
+		set_aca_balance(&caller, 2 * dollar(ACA));
+	}: _(RawOrigin::Signed(caller), lookup_of_account(who.clone()), dollar(ACA))
+	verify {
+		assert_eq!(TransactionPayment::fee_tank_balance(&who), dollar(ACA));
+	}
+
+	request_fee_tank_withdrawal {
+		let caller: AccountId = whitelisted_caller();
+		set_aca_balance(&caller, 2 * dollar(ACA));
+		TransactionPayment::deposit_fee_tank(RawOrigin::Signed(caller.clone()).into(), lookup_of_account(caller.clone()), dollar(ACA))?;
+	}: _(RawOrigin::Signed(caller.clone()), lookup_of_account(caller.clone()), dollar(ACA))
+	verify {
+		assert!(TransactionPayment::fee_tank_withdrawals(&caller, &caller).is_some());
+	}
+
+	withdraw_fee_tank {
+		let caller: AccountId = whitelisted_caller();
+		set_aca_balance(&caller, 2 * dollar(ACA));
+		TransactionPayment::deposit_fee_tank(RawOrigin::Signed(caller.clone()).into(), lookup_of_account(caller.clone()), dollar(ACA))?;
+		TransactionPayment::request_fee_tank_withdrawal(RawOrigin::Signed(caller.clone()).into(), lookup_of_account(caller.clone()), dollar(ACA))?;
+		System::set_block_number(System::block_number() + FeeTankWithdrawalDelay::get());
+	}: _(RawOrigin::Signed(caller.clone()), lookup_of_account(caller.clone()))
+	verify {
+		assert_eq!(TransactionPayment::fee_tank_balance(&caller), 0);
+	}
+
 	on_finalize {
 	}: {
 		TransactionPayment::on_finalize(System::block_number());
@@ -45,9 +45,14 @@ pub fn set_aca_balance(who: &AccountId, balance: Balance) {
 #[cfg(test)]
 pub mod tests {
 	pub fn new_test_ext() -> sp_io::TestExternalities {
-		frame_system::GenesisConfig::default()
+		let mut ext: sp_io::TestExternalities = frame_system::GenesisConfig::default()
 			.build_storage::<crate::Runtime>()
 			.unwrap()
-			.into()
+			.into();
+		// the session keys are generated in the keystore
+		ext.register_extension(sp_keystore::KeystoreExt(std::sync::Arc::new(
+			sp_keystore::testing::KeyStore::new(),
+		)));
+		ext
 	}
 }
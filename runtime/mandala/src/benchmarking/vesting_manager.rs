@@ -0,0 +1,86 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::utils::{lookup_of_account, set_aca_balance};
+use crate::{dollar, AccountId, AccountIdConversion, BlockNumber, Runtime, TreasuryPalletId, Vesting, ACA};
+
+use frame_benchmarking::{account, whitelisted_caller};
+use frame_system::RawOrigin;
+use orml_benchmarking::runtime_benchmarks;
+use orml_vesting::VestingSchedule;
+use sp_std::prelude::*;
+
+const SEED: u32 = 0;
+
+fn vested_transfer(to: &AccountId, count: u32) -> Result<(), &'static str> {
+	let schedule = VestingSchedule::<BlockNumber, _> {
+		start: 0,
+		period: 2,
+		period_count: 10,
+		per_period: 10 * dollar(ACA),
+	};
+
+	// extra 1 dollar to pay fees
+	let from: AccountId = TreasuryPalletId::get().into_account();
+	set_aca_balance(&from, schedule.total_amount().unwrap() * count as u128 + dollar(ACA));
+
+	for _ in 0..count {
+		Vesting::vested_transfer(
+			RawOrigin::Signed(from.clone()).into(),
+			lookup_of_account(to.clone()),
+			schedule.clone(),
+		)?;
+	}
+	Ok(())
+}
+
+runtime_benchmarks! {
+	{ Runtime, module_vesting_manager }
+
+	merge_schedules {
+		let caller: AccountId = whitelisted_caller();
+		vested_transfer(&caller, 2)?;
+	}: _(RawOrigin::Signed(caller), vec![0, 1])
+
+	split_schedule {
+		let caller: AccountId = whitelisted_caller();
+		let beneficiary: AccountId = account("beneficiary", 0, SEED);
+		vested_transfer(&caller, 1)?;
+	}: _(RawOrigin::Signed(caller), 0, lookup_of_account(beneficiary), 5 * dollar(ACA))
+
+	force_split_schedule {
+		let who: AccountId = account("who", 0, SEED);
+		let beneficiary: AccountId = account("beneficiary", 0, SEED);
+		vested_transfer(&who, 1)?;
+	}: _(RawOrigin::Root, lookup_of_account(who), 0, lookup_of_account(beneficiary), 5 * dollar(ACA))
+
+	clawback {
+		let who: AccountId = account("who", 0, SEED);
+		let dest: AccountId = TreasuryPalletId::get().into_account();
+		vested_transfer(&who, 1)?;
+	}: _(RawOrigin::Root, lookup_of_account(who), 0, lookup_of_account(dest))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::benchmarking::utils::tests::new_test_ext;
+	use orml_benchmarking::impl_benchmark_test_suite;
+
+	impl_benchmark_test_suite!(new_test_ext(),);
+}
@@ -0,0 +1,75 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	dollar, AccountId, Runtime, System, VoteEscrow, VoteEscrowLockRoundingPeriod, VoteEscrowMaxLockPeriod, ACA,
+};
+
+use super::utils::set_balance;
+use frame_benchmarking::whitelisted_caller;
+use frame_support::traits::OnInitialize;
+use frame_system::RawOrigin;
+use orml_benchmarking::runtime_benchmarks;
+use sp_std::prelude::*;
+
+runtime_benchmarks! {
+	{ Runtime, module_vote_escrow }
+
+	on_initialize {
+		let caller: AccountId = whitelisted_caller();
+		set_balance(ACA, &caller, 1_000 * dollar(ACA));
+		VoteEscrow::create_lock(RawOrigin::Signed(caller).into(), 100 * dollar(ACA), VoteEscrowLockRoundingPeriod::get())?;
+		System::set_block_number(VoteEscrowLockRoundingPeriod::get());
+	}: {
+		VoteEscrow::on_initialize(System::block_number());
+	}
+
+	create_lock {
+		let caller: AccountId = whitelisted_caller();
+		set_balance(ACA, &caller, 1_000 * dollar(ACA));
+	}: _(RawOrigin::Signed(caller), 100 * dollar(ACA), VoteEscrowMaxLockPeriod::get())
+
+	increase_amount {
+		let caller: AccountId = whitelisted_caller();
+		set_balance(ACA, &caller, 1_000 * dollar(ACA));
+		VoteEscrow::create_lock(RawOrigin::Signed(caller.clone()).into(), 100 * dollar(ACA), VoteEscrowMaxLockPeriod::get())?;
+	}: _(RawOrigin::Signed(caller), 100 * dollar(ACA))
+
+	extend_lock {
+		let caller: AccountId = whitelisted_caller();
+		set_balance(ACA, &caller, 1_000 * dollar(ACA));
+		VoteEscrow::create_lock(RawOrigin::Signed(caller.clone()).into(), 100 * dollar(ACA), VoteEscrowLockRoundingPeriod::get() * 2)?;
+	}: _(RawOrigin::Signed(caller), VoteEscrowMaxLockPeriod::get())
+
+	withdraw {
+		let caller: AccountId = whitelisted_caller();
+		set_balance(ACA, &caller, 1_000 * dollar(ACA));
+		VoteEscrow::create_lock(RawOrigin::Signed(caller.clone()).into(), 100 * dollar(ACA), VoteEscrowLockRoundingPeriod::get() * 2)?;
+		System::set_block_number(VoteEscrowLockRoundingPeriod::get() * 2);
+		VoteEscrow::on_initialize(System::block_number());
+	}: _(RawOrigin::Signed(caller))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::benchmarking::utils::tests::new_test_ext;
+	use orml_benchmarking::impl_benchmark_test_suite;
+
+	impl_benchmark_test_suite!(new_test_ext(),);
+}
@@ -0,0 +1,70 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{dollar, native_currency_location, AccountId, AccountIdToMultiLocation, Runtime, ACA};
+
+use frame_benchmarking::{account, whitelisted_caller};
+use frame_system::RawOrigin;
+use module_xcm_dead_letter::{FailedTransfer, FailedTransferCount, FailedTransferId, FailedTransfers};
+use orml_benchmarking::runtime_benchmarks;
+use sp_runtime::traits::Convert;
+use sp_std::prelude::*;
+use xcm::v0::{Error as XcmError, MultiAsset};
+
+const SEED: u32 = 0;
+
+fn failed_transfer_to(who: AccountId) -> FailedTransferId {
+	let id = 0;
+	FailedTransfers::<Runtime>::insert(
+		id,
+		FailedTransfer {
+			asset: MultiAsset::ConcreteFungible {
+				id: native_currency_location(ACA),
+				amount: 10 * dollar(ACA),
+			},
+			beneficiary: AccountIdToMultiLocation::convert(who),
+			error: XcmError::FailedToTransactAsset("FailedToDeposit"),
+		},
+	);
+	FailedTransferCount::<Runtime>::put(1);
+	id
+}
+
+runtime_benchmarks! {
+	{ Runtime, module_xcm_dead_letter }
+
+	claim_failed_transfer {
+		let caller: AccountId = whitelisted_caller();
+		let id = failed_transfer_to(caller.clone());
+	}: _(RawOrigin::Signed(caller), id)
+
+	recover_failed_transfer {
+		let beneficiary: AccountId = account("beneficiary", 0, SEED);
+		let dest: AccountId = account("dest", 0, SEED);
+		let id = failed_transfer_to(beneficiary);
+	}: _(RawOrigin::Root, id, dest)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::benchmarking::utils::tests::new_test_ext;
+	use orml_benchmarking::impl_benchmark_test_suite;
+
+	impl_benchmark_test_suite!(new_test_ext(),);
+}
@@ -0,0 +1,50 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{dollar, AccountId, Runtime, XcmEvm, ACA, AUSD};
+
+use super::utils::set_aca_balance;
+use frame_system::RawOrigin;
+use orml_benchmarking::runtime_benchmarks;
+use primitives::evm::EvmAddress;
+use sp_std::prelude::*;
+
+const SIBLING_PARA_ID: u32 = 2000;
+const ACCOUNT: [u8; 32] = [1u8; 32];
+
+runtime_benchmarks! {
+	{ Runtime, module_xcm_evm }
+
+	call {
+		let who: AccountId = XcmEvm::derived_account(SIBLING_PARA_ID, &ACCOUNT);
+		set_aca_balance(&who, 1_000 * dollar(ACA));
+		let origin = cumulus_pallet_xcm::Origin::SiblingParachain(SIBLING_PARA_ID.into());
+	}: _(origin, ACCOUNT, EvmAddress::from_low_u64_be(1), vec![], 0, 21_000, 0)
+
+	set_fee_per_second {
+	}: _(RawOrigin::Root, AUSD, Some(dollar(AUSD)))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::benchmarking::utils::tests::new_test_ext;
+	use orml_benchmarking::impl_benchmark_test_suite;
+
+	impl_benchmark_test_suite!(new_test_ext(),);
+}
@@ -0,0 +1,60 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{dollar, AccountId, Runtime, XcmTransfer, ACA, AUSD};
+
+use super::utils::set_balance;
+use frame_benchmarking::whitelisted_caller;
+use frame_system::RawOrigin;
+use module_xcm_transfer::{DestChain, DestinationInfo};
+use orml_benchmarking::runtime_benchmarks;
+use sp_std::prelude::*;
+
+const DEST: DestChain = DestChain::Parachain(2000);
+
+fn destination_info() -> DestinationInfo {
+	DestinationInfo {
+		fee_currency_id: ACA,
+		fee_amount: dollar(ACA),
+		dest_weight: 4_000_000_000,
+	}
+}
+
+runtime_benchmarks! {
+	{ Runtime, module_xcm_transfer }
+
+	set_destination {
+	}: _(RawOrigin::Root, DEST, Some(destination_info()))
+
+	// transfer which pays the fee in the other asset
+	transfer {
+		let caller: AccountId = whitelisted_caller();
+		set_balance(AUSD, &caller, 1_000 * dollar(AUSD));
+		set_balance(ACA, &caller, 1_000 * dollar(ACA));
+		XcmTransfer::set_destination(RawOrigin::Root.into(), DEST, Some(destination_info()))?;
+	}: _(RawOrigin::Signed(caller), AUSD, 100 * dollar(AUSD), DEST, [1u8; 32])
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::benchmarking::utils::tests::new_test_ext;
+	use orml_benchmarking::impl_benchmark_test_suite;
+
+	impl_benchmark_test_suite!(new_test_ext(),);
+}
@@ -48,20 +48,27 @@ pub use frame_support::{
 };
 use frame_system::{EnsureOneOf, EnsureRoot, RawOrigin};
 use hex_literal::hex;
-use module_currencies::{BasicCurrencyAdapter, Currency};
-use module_evm::{CallInfo, CreateInfo};
+use module_auction_manager::AuctionManagerTask;
+use module_circuit_breaker::EmergencyAction;
+use module_currencies::{BasicCurrencyAdapter, Currency, DustTask};
+use module_evm::{CallInfo, CreateInfo, EvmTask};
 use module_evm_accounts::EvmAddressMapping;
 pub use module_evm_manager::EvmCurrencyIdMapping;
+use module_incentives::IncentivesTask;
+use module_parameters::ParameterKey;
 use module_transaction_payment::{Multiplier, TargetedFeeAdjustment};
+use module_xcm_transfer::DestChain;
 use orml_tokens::CurrencyAdapter;
-use orml_traits::{create_median_value_data_provider, parameter_type_with_key, DataFeeder, DataProviderExtended};
+use orml_traits::{
+	create_median_value_data_provider, parameter_type_with_key, Change, DataFeeder, DataProviderExtended,
+};
 use pallet_transaction_payment::{FeeDetails, RuntimeDispatchInfo};
 use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_core::{
 	crypto::KeyTypeId,
 	u32_trait::{_1, _2, _3, _4},
-	OpaqueMetadata, H160,
+	OpaqueMetadata, H160, H256,
 };
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
@@ -85,12 +92,12 @@ pub use xcm::v0::{
 	Junction::{AccountId32, GeneralKey, Parachain, Parent},
 	MultiAsset,
 	MultiLocation::{self, X1, X2, X3},
-	NetworkId, Xcm,
+	NetworkId, Order, OriginKind, SendXcm, Xcm,
 };
 pub use xcm_builder::{
-	AccountId32Aliases, AllowTopLevelPaidExecutionFrom, AllowUnpaidExecutionFrom, EnsureXcmOrigin,
-	FixedRateOfConcreteFungible, FixedWeightBounds, IsConcrete, LocationInverter, NativeAsset, ParentAsSuperuser,
-	ParentIsDefault, RelayChainAsNative, SiblingParachainAsNative, SiblingParachainConvertsVia,
+	AccountId32Aliases, AllowKnownQueryResponses, AllowTopLevelPaidExecutionFrom, AllowUnpaidExecutionFrom,
+	EnsureXcmOrigin, FixedRateOfConcreteFungible, FixedWeightBounds, IsConcrete, LocationInverter, NativeAsset,
+	ParentAsSuperuser, ParentIsDefault, RelayChainAsNative, SiblingParachainAsNative, SiblingParachainConvertsVia,
 	SignedAccountId32AsNative, SignedToAccountId32, SovereignSignedViaLocation, TakeWeightCredit,
 };
 pub use xcm_executor::{Config, XcmExecutor};
@@ -107,14 +114,19 @@ pub use sp_runtime::{Perbill, Percent, Permill, Perquintill};
 pub use authority::AuthorityConfigImpl;
 pub use constants::{fee::*, time::*};
 pub use primitives::{
-	evm::EstimateResourcesRequest, AccountId, AccountIndex, AirDropCurrencyId, Amount, AuctionId, AuthoritysOriginId,
-	Balance, BlockNumber, CurrencyId, DataProviderId, EraIndex, Hash, Moment, Nonce, ReserveIdentifier, Share,
-	Signature, TokenSymbol, TradingPair,
+	evm::{
+		BlockFeeInfo, ContractMetadata, ContractStatus, DryRunInfo, EstimateFeeInfo, EstimateResourcesRequest,
+		EvmTransaction, LogsBloom, Receipt, TraceInfo, TracerConfig,
+	},
+	AccountId, AccountIndex, AirDropCurrencyId, Amount, AuctionId, AuthoritysOriginId, Balance, BlockNumber,
+	CurrencyId, DataProviderId, EraIndex, Hash, Moment, Nonce, ReserveIdentifier, Share, Signature, TokenSymbol,
+	TradingPair,
 };
 pub use runtime_common::{
-	cent, dollar, microcent, millicent, CurveFeeModel, ExchangeRate, GasToWeight, OffchainSolutionWeightLimit, Price,
-	Rate, Ratio, RelaychainBlockNumberProvider, RuntimeBlockLength, RuntimeBlockWeights, SystemContractsFilter,
-	TimeStampedPrice, ACA, AUSD, DOT, LDOT, RENBTC,
+	cent, dollar, microcent, millicent, BlockGasTarget, CurveFeeModel, ExchangeRate, GasToWeight,
+	OffchainSolutionWeightLimit, Price, Rate, Ratio, RelaychainBlockNumberProvider, RelaychainStateRootProvider,
+	RuntimeBlockLength, RuntimeBlockWeights, SystemContractsFilter, TimeStampedPrice, VotingPowerCurrency, ACA, AUSD,
+	DOT, LDOT, RENBTC,
 };
 
 mod authority;
@@ -157,14 +169,26 @@ parameter_types! {
 	pub const CDPTreasuryPalletId: PalletId = PalletId(*b"aca/cdpt");
 	pub const StakingPoolPalletId: PalletId = PalletId(*b"aca/stkp");
 	pub const HonzonTreasuryPalletId: PalletId = PalletId(*b"aca/hztr");
+	pub const StabilityBufferPalletId: PalletId = PalletId(*b"aca/stbf");
 	pub const HomaTreasuryPalletId: PalletId = PalletId(*b"aca/hmtr");
+	pub const HomaPalletId: PalletId = PalletId(*b"aca/homa");
+	pub const HomaValidatorListPalletId: PalletId = PalletId(*b"aca/hmvl");
 	pub const IncentivesPalletId: PalletId = PalletId(*b"aca/inct");
 	// Treasury reserve
 	pub const TreasuryReservePalletId: PalletId = PalletId(*b"aca/reve");
 	pub const ElectionsPhragmenPalletId: LockIdentifier = *b"aca/phre";
 	pub const NftPalletId: PalletId = PalletId(*b"aca/aNFT");
 	pub const NomineesElectionId: LockIdentifier = *b"aca/nome";
+	pub const VoteEscrowId: LockIdentifier = *b"aca/vesc";
 	pub UnreleasedNativeVaultAccountId: AccountId = PalletId(*b"aca/urls").into_account();
+	pub const IncentivesEmissionPalletId: PalletId = PalletId(*b"aca/emin");
+	pub const SavingRatePalletId: PalletId = PalletId(*b"aca/svrt");
+	pub const ScheduledTransfersPalletId: PalletId = PalletId(*b"aca/sctr");
+	pub const StreamingPalletId: PalletId = PalletId(*b"aca/strm");
+	pub const LaunchpadPalletId: PalletId = PalletId(*b"aca/lpad");
+	pub const FeeRebatesPalletId: PalletId = PalletId(*b"aca/fbrt");
+	pub const GrantsPalletId: PalletId = PalletId(*b"aca/grnt");
+	pub const AirdropPalletId: PalletId = PalletId(*b"aca/aird");
 	// Ecosystem modules
 	pub const StarportPalletId: PalletId = PalletId(*b"aca/stpt");
 }
@@ -176,10 +200,20 @@ pub fn get_all_module_accounts() -> Vec<AccountId> {
 		DEXPalletId::get().into_account(),
 		CDPTreasuryPalletId::get().into_account(),
 		StakingPoolPalletId::get().into_account(),
+		HomaValidatorListPalletId::get().into_account(),
 		HonzonTreasuryPalletId::get().into_account(),
+		StabilityBufferPalletId::get().into_account(),
 		HomaTreasuryPalletId::get().into_account(),
 		IncentivesPalletId::get().into_account(),
 		TreasuryReservePalletId::get().into_account(),
+		IncentivesEmissionPalletId::get().into_account(),
+		SavingRatePalletId::get().into_account(),
+		ScheduledTransfersPalletId::get().into_account(),
+		StreamingPalletId::get().into_account(),
+		LaunchpadPalletId::get().into_account(),
+		FeeRebatesPalletId::get().into_account(),
+		GrantsPalletId::get().into_account(),
+		AirdropPalletId::get().into_account(),
 		ZeroAccountId::get(),
 		StarportPalletId::get().into_account(),
 	]
@@ -195,6 +229,7 @@ pub struct BaseCallFilter;
 impl Filter<Call> for BaseCallFilter {
 	fn filter(call: &Call) -> bool {
 		!matches!(call, Call::Democracy(pallet_democracy::Call::propose(..)),)
+			&& module_transaction_pause::NonPausedTransactionFilter::<Runtime>::filter(call)
 	}
 }
 
@@ -263,12 +298,24 @@ impl pallet_session::Config for Runtime {
 	type WeightInfo = ();
 }
 
+/// The session keys prove the possession with the signature of the aura key.
+pub struct SessionKeysOwnershipProof;
+impl module_collator_selection::KeysOwnershipProof<AccountId, SessionKeys> for SessionKeysOwnershipProof {
+	fn verify(who: &AccountId, keys: &SessionKeys, proof: &[u8]) -> bool {
+		let payload = module_collator_selection::keys_ownership_payload(who);
+		<AuraId as sp_runtime::RuntimeAppPublic>::Signature::decode(&mut &proof[..]).map_or(false, |signature| {
+			sp_runtime::RuntimeAppPublic::verify(&keys.aura, &payload, &signature)
+		})
+	}
+}
+
 parameter_types! {
 	pub const PotId: PalletId = PalletId(*b"PotStake");
 	pub const MinCandidates: u32 = 5;
 	pub const MaxCandidates: u32 = 200;
 	pub const MaxInvulnerables: u32 = 50;
 	pub const CollatorKickThreshold: Permill = Permill::from_percent(50);
+	pub const CollatorUnbondingDelay: u32 = 2;
 }
 
 impl module_collator_selection::Config for Runtime {
@@ -281,6 +328,8 @@ impl module_collator_selection::Config for Runtime {
 	type MaxCandidates = MaxCandidates;
 	type MaxInvulnerables = MaxInvulnerables;
 	type CollatorKickThreshold = CollatorKickThreshold;
+	type UnbondingDelay = CollatorUnbondingDelay;
+	type KeysOwnershipProof = SessionKeysOwnershipProof;
 	type WeightInfo = weights::module_collator_selection::WeightInfo<Runtime>;
 }
 
@@ -508,6 +557,23 @@ impl pallet_collective::Config<TechnicalCommitteeInstance> for Runtime {
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	pub const NominatorsCouncilMotionDuration: BlockNumber = 7 * DAYS;
+	pub const NominatorsCouncilMaxProposals: u32 = 100;
+}
+
+type NominatorsCouncilInstance = pallet_collective::Instance5;
+impl pallet_collective::Config<NominatorsCouncilInstance> for Runtime {
+	type Origin = Origin;
+	type Proposal = Call;
+	type Event = Event;
+	type MotionDuration = NominatorsCouncilMotionDuration;
+	type MaxProposals = NominatorsCouncilMaxProposals;
+	type MaxMembers = NominatorsCouncilSize;
+	type DefaultVote = pallet_collective::PrimeDefaultVote;
+	type WeightInfo = ();
+}
+
 type TechnicalCommitteeMembershipInstance = pallet_membership::Instance4;
 impl pallet_membership::Config<TechnicalCommitteeMembershipInstance> for Runtime {
 	type Event = Event;
@@ -692,7 +758,9 @@ parameter_types! {
 impl pallet_democracy::Config for Runtime {
 	type Proposal = Call;
 	type Event = Event;
-	type Currency = Balances;
+	/// The Homa staked voting power counts toward the votes, on top of the native free balance which
+	/// includes the vesting and escrow locked balances.
+	type Currency = VotingPowerCurrency<Balances, StakedVotingPower>;
 	type EnactmentPeriod = EnactmentPeriod;
 	type LaunchPeriod = LaunchPeriod;
 	type VotingPeriod = VotingPeriod;
@@ -827,8 +895,12 @@ impl DataFeeder<CurrencyId, Price, AccountId> for AggregatedDataProvider {
 }
 
 parameter_type_with_key! {
-	pub ExistentialDeposits: |_currency_id: CurrencyId| -> Balance {
-		Zero::zero()
+	pub ExistentialDeposits: |currency_id: CurrencyId| -> Balance {
+		match currency_id {
+			CurrencyId::ForeignAsset(_) => AssetRegistry::asset_metadatas(currency_id)
+				.map_or_else(Balance::max_value, |metadata| metadata.minimal_balance),
+			_ => Zero::zero(),
+		}
 	};
 }
 
@@ -843,7 +915,7 @@ impl orml_tokens::Config for Runtime {
 	type CurrencyId = CurrencyId;
 	type WeightInfo = weights::orml_tokens::WeightInfo<Runtime>;
 	type ExistentialDeposits = ExistentialDeposits;
-	type OnDust = orml_tokens::TransferDust<Runtime, TreasuryAccount>;
+	type OnDust = module_currencies::SweepDust<Runtime, Dex, TreasuryAccount, IdleScheduler>;
 	type MaxLocks = MaxLocks;
 }
 
@@ -878,6 +950,11 @@ parameter_types! {
 	pub const GetStableCurrencyId: CurrencyId = AUSD;
 }
 
+parameter_types! {
+	pub const MaxMemoLength: u32 = 128;
+	pub const MaxBatchTransfers: u32 = 100;
+}
+
 impl module_currencies::Config for Runtime {
 	type Event = Event;
 	type MultiCurrency = Tokens;
@@ -886,6 +963,10 @@ impl module_currencies::Config for Runtime {
 	type WeightInfo = weights::module_currencies::WeightInfo<Runtime>;
 	type AddressMapping = EvmAddressMapping<Runtime>;
 	type EVMBridge = EVMBridge;
+	type UpdateOrigin = EnsureRootOrHalfFinancialCouncil;
+	type MaxMemoLength = MaxMemoLength;
+	type MaxBatchTransfers = MaxBatchTransfers;
+	type FreezeChecker = AccountFreeze;
 }
 
 pub struct EnsureRootOrTreasury;
@@ -979,6 +1060,8 @@ impl module_auction_manager::Config for Runtime {
 	type PriceSource = Prices;
 	type UnsignedPriority = runtime_common::AuctionManagerUnsignedPriority;
 	type EmergencyShutdown = EmergencyShutdown;
+	type EventTopics = EventTopics;
+	type IdleScheduler = IdleScheduler;
 	type WeightInfo = weights::module_auction_manager::WeightInfo<Runtime>;
 }
 
@@ -990,6 +1073,7 @@ impl module_loans::Config for Runtime {
 	type CDPTreasury = CdpTreasury;
 	type PalletId = LoansPalletId;
 	type OnUpdateLoan = module_incentives::OnUpdateLoan<Runtime>;
+	type EventTopics = EventTopics;
 }
 
 impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Runtime
@@ -1025,6 +1109,7 @@ where
 			frame_system::CheckWeight::<Runtime>::new(),
 			module_transaction_payment::ChargeTransactionPayment::<Runtime>::from(tip),
 			module_evm::SetEvmOrigin::<Runtime>::new(),
+			module_rate_limit::CheckRateLimit::<Runtime>::new(),
 		);
 		let raw_payload = SignedPayload::new(call, extra)
 			.map_err(|e| {
@@ -1058,6 +1143,7 @@ parameter_types! {
 	pub DefaultLiquidationPenalty: Rate = Rate::saturating_from_rational(5, 100);
 	pub MinimumDebitValue: Balance = dollar(AUSD);
 	pub MaxSlippageSwapWithDEX: Ratio = Ratio::saturating_from_rational(5, 100);
+	pub const FeeTankWithdrawalDelay: BlockNumber = 2 * DAYS;
 }
 
 impl module_cdp_engine::Config for Runtime {
@@ -1096,12 +1182,25 @@ impl module_emergency_shutdown::Config for Runtime {
 	type CDPTreasury = CdpTreasury;
 	type AuctionManagerHandler = AuctionManager;
 	type ShutdownOrigin = EnsureRootOrHalfGeneralCouncil;
+	type OnShutdown = (Homa, Dex, Incentives, FederatedBridge, Psm, AuctionManager);
 	type WeightInfo = weights::module_emergency_shutdown::WeightInfo<Runtime>;
 }
 
+impl module_psm::Config for Runtime {
+	type Event = Event;
+	type CDPTreasury = CdpTreasury;
+	type CurrencyIdMapping = EvmCurrencyIdMapping<Runtime>;
+	type GetStableCurrencyId = GetStableCurrencyId;
+	type UpdateOrigin = EnsureRootOrHalfFinancialCouncil;
+	type PauseOrigin = EnsureRootOrHalfGeneralCouncil;
+	type WeightInfo = weights::module_psm::WeightInfo<Runtime>;
+}
+
 parameter_types! {
 	pub const GetExchangeFee: (u32, u32) = (1, 1000);	// 0.1%
 	pub const TradingPathLimit: u32 = 3;
+	pub const DexSwapFeeShare: Permill = Permill::from_percent(10);
+	pub FeeRebatesAccount: AccountId = FeeRebatesPalletId::get().into_account();
 	pub EnabledTradingPairs: Vec<TradingPair> = vec![
 		TradingPair::new(AUSD, ACA),
 		TradingPair::new(AUSD, DOT),
@@ -1120,11 +1219,21 @@ impl module_dex::Config for Runtime {
 	type DEXIncentives = Incentives;
 	type WeightInfo = weights::module_dex::WeightInfo<Runtime>;
 	type ListingOrigin = EnsureRootOrHalfGeneralCouncil;
+	type OnSwap = FeeRebates;
+	type EventTopics = EventTopics;
+	type SwapFeeShare = DexSwapFeeShare;
+	type SwapFeeCurrencyId = GetNativeCurrencyId;
+	type SwapFeeReceiver = FeeRebatesAccount;
 }
 
+impl module_event_topics::Config for Runtime {}
+
 parameter_types! {
 	pub const MaxAuctionsCount: u32 = 100;
 	pub HonzonTreasuryAccount: AccountId = HonzonTreasuryPalletId::get().into_account();
+	pub StabilityBufferAccount: AccountId = StabilityBufferPalletId::get().into_account();
+	pub SavingRateAccount: AccountId = SavingRatePalletId::get().into_account();
+	pub const SurplusAllocationPeriod: BlockNumber = DAYS;
 }
 
 impl module_cdp_treasury::Config for Runtime {
@@ -1137,6 +1246,11 @@ impl module_cdp_treasury::Config for Runtime {
 	type MaxAuctionsCount = MaxAuctionsCount;
 	type PalletId = CDPTreasuryPalletId;
 	type TreasuryAccount = HonzonTreasuryAccount;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type SavingRateAccount = SavingRateAccount;
+	type StabilityBufferAccount = StabilityBufferAccount;
+	type SurplusAllocationPeriod = SurplusAllocationPeriod;
+	type MaxSlippageSwapWithDEX = MaxSlippageSwapWithDEX;
 	type WeightInfo = weights::module_cdp_treasury::WeightInfo<Runtime>;
 }
 
@@ -1167,6 +1281,8 @@ impl OnUnbalanced<NegativeImbalance> for DealWithFees {
 }
 
 impl module_transaction_payment::Config for Runtime {
+	type Event = Event;
+	type Call = Call;
 	type AllNonNativeCurrencyIds = AllNonNativeCurrencyIds;
 	type NativeCurrencyId = GetNativeCurrencyId;
 	type StableCurrencyId = GetStableCurrencyId;
@@ -1178,23 +1294,44 @@ impl module_transaction_payment::Config for Runtime {
 	type FeeMultiplierUpdate = TargetedFeeAdjustment<Self, TargetBlockFullness, AdjustmentVariable, MinimumMultiplier>;
 	type DEX = Dex;
 	type MaxSlippageSwapWithDEX = MaxSlippageSwapWithDEX;
+	type OnFeePaid = FeeRebates;
+	type SponsorCallClass = ProxyType;
+	type SponsorPublic = <Signature as sp_runtime::traits::Verify>::Signer;
+	type SponsorSignature = Signature;
+	type UpdateOrigin = EnsureRootOrHalfGeneralCouncil;
+	type FeeTankWithdrawalDelay = FeeTankWithdrawalDelay;
 	type WeightInfo = weights::module_transaction_payment::WeightInfo<Runtime>;
 }
 
+parameter_types! {
+	pub const EvmAccountsUnbindDelay: BlockNumber = 7 * DAYS;
+}
+
 impl module_evm_accounts::Config for Runtime {
 	type Event = Event;
 	type Currency = Balances;
 	type AddressMapping = EvmAddressMapping<Runtime>;
 	type TransferAll = Currencies;
+	type UnbindDelay = EvmAccountsUnbindDelay;
 	type WeightInfo = weights::module_evm_accounts::WeightInfo<Runtime>;
 }
 
 impl module_evm_manager::Config for Runtime {
+	type Event = Event;
 	type Currency = Balances;
 	type EVMBridge = EVMBridge;
+	type RegisterOrigin = EnsureRootOrHalfGeneralCouncil;
+	type AssetRegistry = AssetRegistry;
+}
+
+impl module_asset_registry::Config for Runtime {
+	type Event = Event;
+	type CurrencyIdMapping = EvmCurrencyIdMapping<Runtime>;
+	type RegisterOrigin = EnsureRootOrHalfGeneralCouncil;
+	type WeightInfo = weights::module_asset_registry::WeightInfo<Runtime>;
 }
 
-impl orml_rewards::Config for Runtime {
+impl module_rewards::Config for Runtime {
 	type Share = Balance;
 	type Balance = Balance;
 	type PoolId = module_incentives::PoolId<AccountId>;
@@ -1203,12 +1340,18 @@ impl orml_rewards::Config for Runtime {
 
 parameter_types! {
 	pub const AccumulatePeriod: BlockNumber = MINUTES;
+	pub const MaxClaimAllPools: u32 = 50;
+	pub const MaxLoyaltyTiers: u32 = 10;
+	pub const MaxTopUps: u32 = 20;
+	pub const MaxEscrowedRewards: u32 = 20;
+	pub const MaxLoansRiskWeights: u32 = 20;
 }
 
 impl module_incentives::Config for Runtime {
 	type Event = Event;
 	type RelaychainAccountId = AccountId;
-	type NativeRewardsSource = UnreleasedNativeVaultAccountId;
+	type NativeRewardsSource = IncentivesEmissionAccountId;
+	type ExtraRewardsSource = TreasuryAccount;
 	type RewardsVaultAccountId = ZeroAccountId;
 	type NativeCurrencyId = GetNativeCurrencyId;
 	type StableCurrencyId = GetStableCurrencyId;
@@ -1218,28 +1361,195 @@ impl module_incentives::Config for Runtime {
 	type CDPTreasury = CdpTreasury;
 	type Currency = Currencies;
 	type DEX = Dex;
-	type EmergencyShutdown = EmergencyShutdown;
+	type VotingEscrow = VoteEscrow;
+	type IncentiveRewardsBudget =
+		module_emissions::TargetEmissionBudget<Runtime, IncentivesEmissionTarget, AccumulatePeriod>;
+	type MaxClaimAllPools = MaxClaimAllPools;
+	type MaxLoyaltyTiers = MaxLoyaltyTiers;
+	type MaxTopUps = MaxTopUps;
+	type MaxEscrowedRewards = MaxEscrowedRewards;
+	type MaxLoansRiskWeights = MaxLoansRiskWeights;
 	type PalletId = IncentivesPalletId;
+	type IdleScheduler = IdleScheduler;
 	type WeightInfo = weights::module_incentives::WeightInfo<Runtime>;
 }
 
+parameter_types! {
+	pub const VoteEscrowMaxLockPeriod: BlockNumber = 4 * 365 * DAYS;
+	pub const VoteEscrowLockRoundingPeriod: BlockNumber = 7 * DAYS;
+	pub MinVoteEscrowLockAmount: Balance = dollar(ACA);
+}
+
+impl module_vote_escrow::Config for Runtime {
+	type Event = Event;
+	type Currency = Currency<Runtime, GetNativeCurrencyId>;
+	type PalletId = VoteEscrowId;
+	type MaxLockPeriod = VoteEscrowMaxLockPeriod;
+	type LockRoundingPeriod = VoteEscrowLockRoundingPeriod;
+	type MinLockAmount = MinVoteEscrowLockAmount;
+	type WeightInfo = weights::module_vote_escrow::WeightInfo<Runtime>;
+}
+
+parameter_types! {
+	pub const VestingVotingPowerDiscount: Permill = Permill::from_percent(50);
+	pub const StakedVotingPowerDiscount: Permill = Permill::from_percent(25);
+}
+
+/// The voting power of the liquid currency staked by Homa is its native value with discount. The
+/// free liquid currency is counted and locked while voting.
+pub struct StakedVotingPower;
+impl module_support::VotingPowerProvider<AccountId, Balance> for StakedVotingPower {
+	fn voting_power(who: &AccountId) -> Balance {
+		use module_support::PriceProvider;
+		use orml_traits::MultiCurrency;
+
+		let staked = Prices::get_relative_price(GetLiquidCurrencyId::get(), GetNativeCurrencyId::get())
+			.map_or(0, |price| {
+				price.saturating_mul_int(Currencies::free_balance(GetLiquidCurrencyId::get(), who))
+			});
+		StakedVotingPowerDiscount::get().left_from_one().mul_floor(staked)
+	}
+
+	fn lock_voting_power(lock_id: LockIdentifier, who: &AccountId) -> DispatchResult {
+		use orml_traits::{MultiCurrency, MultiLockableCurrency};
+
+		let staked = Currencies::free_balance(GetLiquidCurrencyId::get(), who);
+		Currencies::set_lock(lock_id, GetLiquidCurrencyId::get(), who, staked)
+	}
+
+	fn unlock_voting_power(lock_id: LockIdentifier, who: &AccountId) -> DispatchResult {
+		use orml_traits::MultiLockableCurrency;
+
+		Currencies::remove_lock(lock_id, GetLiquidCurrencyId::get(), who)
+	}
+}
+
+/// The voting power of governance is the voting escrow power, plus the vesting locked native
+/// currency with discount and the `StakedVotingPower`. The vesting locked native currency is locked
+/// again while voting, so it can't be counted again after being vested and transferred.
+pub struct GovernanceVotingPower;
+impl GovernanceVotingPower {
+	fn vesting_locked(who: &AccountId) -> Balance {
+		Balances::locks(who)
+			.iter()
+			.find(|lock| lock.id == orml_vesting::VESTING_LOCK_ID)
+			.map_or(0, |lock| lock.amount)
+	}
+}
+
+impl module_support::VotingPowerProvider<AccountId, Balance> for GovernanceVotingPower {
+	fn voting_power(who: &AccountId) -> Balance {
+		use module_support::{VotingEscrow, VotingPowerProvider};
+
+		let vesting_power = VestingVotingPowerDiscount::get()
+			.left_from_one()
+			.mul_floor(Self::vesting_locked(who));
+		VoteEscrow::balance_of(who)
+			.saturating_add(vesting_power)
+			.saturating_add(StakedVotingPower::voting_power(who))
+	}
+
+	fn lock_voting_power(lock_id: LockIdentifier, who: &AccountId) -> DispatchResult {
+		use frame_support::traits::LockableCurrency;
+		use module_support::VotingPowerProvider;
+
+		let vesting = Self::vesting_locked(who);
+		if vesting.is_zero() {
+			Balances::remove_lock(lock_id, who);
+		} else {
+			Balances::set_lock(lock_id, who, vesting, WithdrawReasons::all());
+		}
+		StakedVotingPower::lock_voting_power(lock_id, who)
+	}
+
+	fn unlock_voting_power(lock_id: LockIdentifier, who: &AccountId) -> DispatchResult {
+		use frame_support::traits::LockableCurrency;
+		use module_support::VotingPowerProvider;
+
+		Balances::remove_lock(lock_id, who);
+		StakedVotingPower::unlock_voting_power(lock_id, who)
+	}
+}
+
+parameter_types! {
+	pub const GaugeVotingPeriod: BlockNumber = 7 * DAYS;
+	pub const MaxGauges: u32 = 50;
+	pub const MaxGaugeVotes: u32 = 10;
+	pub const IncentivesEmissionTarget: module_emissions::EmissionTarget = module_emissions::EmissionTarget::Incentives;
+}
+
+impl module_gauge::Config for Runtime {
+	type Event = Event;
+	type PoolId = module_incentives::PoolId<AccountId>;
+	type VotingPower = GovernanceVotingPower;
+	type Incentives = Incentives;
+	type EmissionBudget = module_emissions::TargetEmissionBudget<Runtime, IncentivesEmissionTarget, AccumulatePeriod>;
+	type UpdateOrigin = EnsureRootOrThreeFourthsGeneralCouncil;
+	type VotingPeriod = GaugeVotingPeriod;
+	type MaxGauges = MaxGauges;
+	type MaxVotes = MaxGaugeVotes;
+	type WeightInfo = weights::module_gauge::WeightInfo<Runtime>;
+}
+
+parameter_types! {
+	pub IncentivesEmissionAccountId: AccountId = IncentivesEmissionPalletId::get().into_account();
+	pub CollatorRewardsAccountId: AccountId = PotId::get().into_account();
+	pub const EmissionPeriod: BlockNumber = DAYS;
+}
+
+impl module_emissions::Config for Runtime {
+	type Event = Event;
+	type Currency = Currencies;
+	type NativeCurrencyId = GetNativeCurrencyId;
+	type EmissionSource = UnreleasedNativeVaultAccountId;
+	type IncentivesAccount = IncentivesEmissionAccountId;
+	type SavingRateAccount = SavingRateAccount;
+	type CollatorRewardsAccount = CollatorRewardsAccountId;
+	type EmissionPeriod = EmissionPeriod;
+	type UpdateOrigin = EnsureRootOrThreeFourthsGeneralCouncil;
+	type WeightInfo = weights::module_emissions::WeightInfo<Runtime>;
+}
+
 impl module_airdrop::Config for Runtime {
 	type Event = Event;
+	type Currency = Currencies;
+	type TreasuryAccount = TreasuryAccount;
+	type PalletId = AirdropPalletId;
+	type CreateOrigin = EnsureRootOrHalfGeneralCouncil;
+	type WeightInfo = weights::module_airdrop::WeightInfo<Runtime>;
 }
 
 parameter_types! {
 	pub const PolkadotBondingDuration: EraIndex = 7;
 	pub const EraLength: BlockNumber = DAYS;
 	pub const MaxUnbonding: u32 = 1000;
+	pub const MaxRelaychainProofSize: u32 = 16 * 1024;
+}
+
+/// The sub account of the parachain sovereign account on relaychain, derived the same way as
+/// `pallet_utility::as_derivative`.
+pub struct RelaychainSubAccount;
+impl Convert<u32, AccountId> for RelaychainSubAccount {
+	fn convert(account_index: u32) -> AccountId {
+		let sovereign: AccountId = ParachainInfo::parachain_id().into_account();
+		let entropy =
+			(b"modlpy/utilisuba", sovereign, account_index as u16).using_encoded(sp_core::hashing::blake2_256);
+		AccountId::decode(&mut &entropy[..]).unwrap_or_default()
+	}
 }
 
 impl module_polkadot_bridge::Config for Runtime {
+	type Event = Event;
 	type DOTCurrency = Currency<Runtime, GetStakingCurrencyId>;
 	type OnNewEra = (NomineesElection, StakingPool);
 	type BondingDuration = PolkadotBondingDuration;
 	type EraLength = EraLength;
 	type PolkadotAccountId = AccountId;
 	type MaxUnbonding = MaxUnbonding;
+	type RelaychainState = RelaychainLightClient;
+	type RelaychainSubAccount = RelaychainSubAccount;
+	type MaxProofSize = MaxRelaychainProofSize;
+	type WeightInfo = weights::module_polkadot_bridge::WeightInfo<Runtime>;
 }
 
 parameter_types! {
@@ -1247,6 +1557,11 @@ parameter_types! {
 	pub const GetStakingCurrencyId: CurrencyId = DOT;
 	pub DefaultExchangeRate: ExchangeRate = ExchangeRate::saturating_from_rational(10, 100);	// 1 : 10
 	pub PoolAccountIndexes: Vec<u32> = vec![1, 2, 3, 4];
+	pub const MaxExchangeRateHistory: EraIndex = 84;
+	pub const MaxQueuedRequestsPerBlock: u32 = 50;
+	pub const MaxNextEraMintsPerBlock: u32 = 50;
+	pub MinNextEraMintAmount: Balance = dollar(DOT);
+	pub GuarantorRewardRatio: Ratio = Ratio::saturating_from_rational(5, 100);
 }
 
 impl module_staking_pool::Config for Runtime {
@@ -1259,12 +1574,20 @@ impl module_staking_pool::Config for Runtime {
 	type UpdateOrigin = EnsureRootOrHalfHomaCouncil;
 	type FeeModel = CurveFeeModel;
 	type Nominees = NomineesElection;
-	type Bridge = PolkadotBridge;
+	type Bridge = module_homa_xcm_queue::QueuedPolkadotBridge<Runtime, PolkadotBridge>;
 	type Currency = Currencies;
+	type MaxExchangeRateHistory = MaxExchangeRateHistory;
+	type MaxQueuedRequestsPerBlock = MaxQueuedRequestsPerBlock;
+	type MaxNextEraMintsPerBlock = MaxNextEraMintsPerBlock;
+	type MinNextEraMintAmount = MinNextEraMintAmount;
+	type GuarantorRewardRatio = GuarantorRewardRatio;
+	type ValidatorRewards = HomaValidatorListModule;
 }
 
 impl module_homa::Config for Runtime {
+	type Event = Event;
 	type Homa = StakingPool;
+	type PalletId = HomaPalletId;
 	type WeightInfo = weights::module_homa::WeightInfo<Runtime>;
 }
 
@@ -1273,18 +1596,34 @@ parameter_types! {
 	pub const NominateesCount: u32 = 7;
 	pub const MaxUnlockingChunks: u32 = 7;
 	pub const NomineesElectionBondingDuration: EraIndex = 7;
+	pub const NomineesElectionVoteDecayPeriod: EraIndex = 28;
+	pub const NominatorsCouncilSize: u32 = 5;
+	pub const NomineesElectionMaxNominators: u32 = 2_000;
+	pub const NomineesElectionMaxCouncilVoters: u32 = 2_000;
+	pub NomineesElectionNominatorDeposit: Balance = 10 * dollar(ACA);
+	pub NomineesElectionCouncilVoterDeposit: Balance = 10 * dollar(ACA);
 }
 
 impl module_nominees_election::Config for Runtime {
 	type Event = Event;
 	type Currency = Currency<Runtime, GetLiquidCurrencyId>;
+	type DepositCurrency = Balances;
 	type NomineeId = AccountId;
 	type PalletId = NomineesElectionId;
 	type MinBondThreshold = MinCouncilBondThreshold;
 	type BondingDuration = NomineesElectionBondingDuration;
 	type NominateesCount = NominateesCount;
 	type MaxUnlockingChunks = MaxUnlockingChunks;
-	type NomineeFilter = runtime_common::DummyNomineeFilter;
+	type NomineeFilter = HomaValidatorListModule;
+	type VoteDecayPeriod = NomineesElectionVoteDecayPeriod;
+	type CouncilSize = NominatorsCouncilSize;
+	type MaxNominators = NomineesElectionMaxNominators;
+	type MaxCouncilVoters = NomineesElectionMaxCouncilVoters;
+	type NominatorDeposit = NomineesElectionNominatorDeposit;
+	type CouncilVoterDeposit = NomineesElectionCouncilVoterDeposit;
+	type CouncilMembersChanged = NominatorsCouncil;
+	type AdjustOrigin = pallet_collective::EnsureProportionMoreThan<_1, _2, AccountId, NominatorsCouncilInstance>;
+	type OnNomineesAdjusted = module_staking_pool::OnNomineesAdjusted<Runtime>;
 	type WeightInfo = weights::module_nominees_election::WeightInfo<Runtime>;
 }
 
@@ -1308,11 +1647,188 @@ impl module_homa_validator_list::Config for Runtime {
 	type OnIncreaseGuarantee = module_incentives::OnIncreaseGuarantee<Runtime>;
 	type OnDecreaseGuarantee = module_incentives::OnDecreaseGuarantee<Runtime>;
 	type BlockNumberProvider = RelaychainBlockNumberProvider<Runtime>;
+	type PalletId = HomaValidatorListPalletId;
+}
+
+/// The calls of crowdloan module on relaychain.
+#[derive(Encode, Decode)]
+pub enum CrowdloanCall {
+	#[codec(index = 1)]
+	Contribute(#[codec(compact)] ParaId, #[codec(compact)] Balance, Option<Signature>),
+	#[codec(index = 2)]
+	Withdraw(AccountId, #[codec(compact)] ParaId),
+}
+
+/// The calls of balances module on relaychain.
+#[derive(Encode, Decode)]
+pub enum BalancesCall {
+	#[codec(index = 3)]
+	TransferKeepAlive(sp_runtime::MultiAddress<AccountId, ()>, #[codec(compact)] Balance),
+}
+
+/// The calls of staking module on relaychain.
+#[derive(Encode, Decode)]
+pub enum StakingCall {
+	#[codec(index = 1)]
+	BondExtra(#[codec(compact)] Balance),
+	#[codec(index = 2)]
+	Unbond(#[codec(compact)] Balance),
+	#[codec(index = 3)]
+	WithdrawUnbonded(u32),
+	#[codec(index = 19)]
+	Rebond(#[codec(compact)] Balance),
+}
+
+/// The calls of utility module on relaychain.
+#[derive(Encode, Decode)]
+pub enum UtilityCall {
+	#[codec(index = 1)]
+	AsDerivative(u16, Box<RelaychainCall>),
+}
+
+/// The relaychain calls which may be dispatched through XCM `Transact`.
+#[derive(Encode, Decode)]
+pub enum RelaychainCall {
+	#[codec(index = 5)]
+	Balances(BalancesCall),
+	#[codec(index = 7)]
+	Staking(StakingCall),
+	#[codec(index = 26)]
+	Utility(UtilityCall),
+	#[codec(index = 73)]
+	Crowdloan(CrowdloanCall),
+}
+
+parameter_types! {
+	pub CrowdloanXcmFee: Balance = 10 * cent(DOT);
+	pub const CrowdloanTransactWeight: Weight = 1_000_000_000;
+	pub const MinCrowdloanContribution: Balance = 5 * dollar(DOT);
+}
+
+/// Contribute to and withdraw from the crowdloans on relaychain by the
+/// parachain sovereign account, through XCM `Transact`.
+pub struct RelaychainCrowdloanBridge;
+impl RelaychainCrowdloanBridge {
+	fn transact(call: RelaychainCall) -> DispatchResult {
+		let message = Xcm::WithdrawAsset {
+			assets: vec![MultiAsset::ConcreteFungible {
+				id: MultiLocation::Null,
+				amount: CrowdloanXcmFee::get(),
+			}],
+			effects: vec![Order::BuyExecution {
+				fees: MultiAsset::All,
+				weight: CrowdloanTransactWeight::get(),
+				debt: 10 * UnitWeightCost::get(),
+				halt_on_error: true,
+				xcm: vec![Xcm::Transact {
+					origin_type: OriginKind::SovereignAccount,
+					require_weight_at_most: CrowdloanTransactWeight::get(),
+					call: call.encode().into(),
+				}],
+			}],
+		};
+		XcmRouter::send_xcm(X1(Parent), message)
+			.map_err(|_| sp_runtime::DispatchError::Other("send crowdloan XCM failed"))
+	}
+}
+
+impl module_liquid_crowdloan::CrowdloanBridge<Balance> for RelaychainCrowdloanBridge {
+	fn contribute(para_id: module_liquid_crowdloan::ParaId, amount: Balance) -> DispatchResult {
+		Self::transact(RelaychainCall::Crowdloan(CrowdloanCall::Contribute(
+			para_id.into(),
+			amount,
+			None,
+		)))
+	}
+
+	fn withdraw(para_id: module_liquid_crowdloan::ParaId) -> DispatchResult {
+		Self::transact(RelaychainCall::Crowdloan(CrowdloanCall::Withdraw(
+			ParachainInfo::parachain_id().into_account(),
+			para_id.into(),
+		)))
+	}
+}
+
+parameter_types! {
+	pub HomaXcmFee: Balance = 10 * cent(DOT);
+	pub const HomaTransactWeight: Weight = 2_000_000_000;
+}
+
+/// Send the Homa operations to relaychain through XCM `Transact`, dispatched by the sub accounts
+/// of the parachain sovereign account, and report back to `HomaXcmQueue` when executed.
+pub struct RelaychainHomaTransactor;
+
+impl module_homa_xcm_queue::HomaXcmTransactor for RelaychainHomaTransactor {
+	fn send(operation: module_homa_xcm_queue::HomaXcmOperation, query_id: u64) -> Result<(), xcm::v0::Error> {
+		use module_homa_xcm_queue::HomaXcmOperation;
+
+		let as_derivative = |account_index: u32, call: RelaychainCall| {
+			RelaychainCall::Utility(UtilityCall::AsDerivative(account_index as u16, Box::new(call)))
+		};
+		let call = match operation {
+			HomaXcmOperation::BondExtra { account_index, amount } => {
+				as_derivative(account_index, RelaychainCall::Staking(StakingCall::BondExtra(amount)))
+			}
+			HomaXcmOperation::Unbond { account_index, amount } => {
+				as_derivative(account_index, RelaychainCall::Staking(StakingCall::Unbond(amount)))
+			}
+			HomaXcmOperation::Rebond { account_index, amount } => {
+				as_derivative(account_index, RelaychainCall::Staking(StakingCall::Rebond(amount)))
+			}
+			HomaXcmOperation::WithdrawUnbonded { account_index } => {
+				as_derivative(account_index, RelaychainCall::Staking(StakingCall::WithdrawUnbonded(0)))
+			}
+			HomaXcmOperation::TransferToSubAccount { account_index, amount } => RelaychainCall::Balances(
+				BalancesCall::TransferKeepAlive(RelaychainSubAccount::convert(account_index).into(), amount),
+			),
+		};
+
+		let message = Xcm::WithdrawAsset {
+			assets: vec![MultiAsset::ConcreteFungible {
+				id: MultiLocation::Null,
+				amount: HomaXcmFee::get(),
+			}],
+			effects: vec![
+				Order::BuyExecution {
+					fees: MultiAsset::All,
+					weight: HomaTransactWeight::get(),
+					debt: 10 * UnitWeightCost::get(),
+					halt_on_error: true,
+					xcm: vec![Xcm::Transact {
+						origin_type: OriginKind::SovereignAccount,
+						require_weight_at_most: HomaTransactWeight::get(),
+						call: call.encode().into(),
+					}],
+				},
+				// only reached if the execution above succeeds
+				Order::QueryHolding {
+					query_id,
+					dest: X1(Parachain(ParachainInfo::parachain_id().into())),
+					assets: vec![MultiAsset::All],
+				},
+			],
+		};
+		XcmRouter::send_xcm(X1(Parent), message)
+	}
+}
+
+impl module_liquid_crowdloan::Config for Runtime {
+	type Event = Event;
+	type Currency = Currencies;
+	type RelaychainCurrencyId = GetStakingCurrencyId;
+	type MinContribution = MinCrowdloanContribution;
+	type Bridge = RelaychainCrowdloanBridge;
+	type XcmFee = CrowdloanXcmFee;
+	type TreasuryAccount = TreasuryAccount;
+	type UpdateOrigin = EnsureRootOrHalfGeneralCouncil;
+	type WeightInfo = weights::module_liquid_crowdloan::WeightInfo<Runtime>;
 }
 
 parameter_types! {
 	pub CreateClassDeposit: Balance = 500 * millicent(ACA);
 	pub CreateTokenDeposit: Balance = 100 * millicent(ACA);
+	pub MaxAttributeKeyLength: u32 = 64;
+	pub MaxAttributeValueLength: u32 = 1024;
 }
 
 impl module_nft::Config for Runtime {
@@ -1322,6 +1838,10 @@ impl module_nft::Config for Runtime {
 	type CreateTokenDeposit = CreateTokenDeposit;
 	type DataDepositPerByte = DataDepositPerByte;
 	type PalletId = NftPalletId;
+	type MaxAttributeKeyLength = MaxAttributeKeyLength;
+	type MaxAttributeValueLength = MaxAttributeValueLength;
+	type UnixTime = Timestamp;
+	type OnNftTransfer = NFTMarketplace;
 	type WeightInfo = weights::module_nft::WeightInfo<Runtime>;
 }
 
@@ -1339,6 +1859,324 @@ impl orml_nft::Config for Runtime {
 	type MaxTokenMetadata = MaxTokenMetadata;
 }
 
+parameter_types! {
+	pub const NftMarketplaceFee: Permill = Permill::from_percent(2);
+	pub const NftMinBidIncrement: Permill = Permill::from_percent(5);
+	pub const NftMinAuctionDuration: BlockNumber = HOURS;
+	pub const NftMaxAuctionDuration: BlockNumber = 30 * DAYS;
+}
+
+impl module_nft_marketplace::Config for Runtime {
+	type Event = Event;
+	type MarketplaceFee = NftMarketplaceFee;
+	type TreasuryAccount = TreasuryAccount;
+	type MinBidIncrement = NftMinBidIncrement;
+	type MinAuctionDuration = NftMinAuctionDuration;
+	type MaxAuctionDuration = NftMaxAuctionDuration;
+	type WeightInfo = weights::module_nft_marketplace::WeightInfo<Runtime>;
+}
+
+parameter_types! {
+	pub const MaxScheduledTransferExecutions: u32 = 1_000;
+	pub ScheduledTransferDeposit: Balance = deposit(2, 200);
+	pub MaxScheduledTransferExecutionWeight: Weight = Perbill::from_percent(10) * RuntimeBlockWeights::get().max_block;
+}
+
+impl module_scheduled_transfers::Config for Runtime {
+	type Event = Event;
+	type Currency = Currencies;
+	type DepositCurrency = Balances;
+	type TransferDeposit = ScheduledTransferDeposit;
+	type PalletId = ScheduledTransfersPalletId;
+	type MaxExecutions = MaxScheduledTransferExecutions;
+	type MaxExecutionWeight = MaxScheduledTransferExecutionWeight;
+	type WeightInfo = weights::module_scheduled_transfers::WeightInfo<Runtime>;
+}
+
+impl module_streaming::Config for Runtime {
+	type Event = Event;
+	type Currency = Currencies;
+	type PalletId = StreamingPalletId;
+	type WeightInfo = weights::module_streaming::WeightInfo<Runtime>;
+}
+
+parameter_types! {
+	pub LaunchpadCreationDeposit: Balance = 100 * dollar(ACA);
+	pub const LaunchpadMaxNameLength: u32 = 32;
+}
+
+impl module_launchpad::Config for Runtime {
+	type Event = Event;
+	type Currency = Currencies;
+	type DEX = Dex;
+	type GetStableCurrencyId = GetStableCurrencyId;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type CreationDeposit = LaunchpadCreationDeposit;
+	type PalletId = LaunchpadPalletId;
+	type MaxNameLength = LaunchpadMaxNameLength;
+	type WeightInfo = weights::module_launchpad::WeightInfo<Runtime>;
+}
+
+impl module_account_freeze::Config for Runtime {
+	type Event = Event;
+	type FreezeOrigin = EnsureRootOrHalfGeneralCouncil;
+	type WeightInfo = weights::module_account_freeze::WeightInfo<Runtime>;
+}
+
+impl module_vesting_manager::Config for Runtime {
+	type Event = Event;
+	type ClawbackOrigin = EnsureRootOrTwoThirdsGeneralCouncil;
+	type WeightInfo = weights::module_vesting_manager::WeightInfo<Runtime>;
+}
+
+runtime_common::define_combined_task! {
+	#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug)]
+	pub enum ScheduledTasks {
+		EvmTask(EvmTask<Runtime>),
+		DustTask(DustTask<Runtime, Dex, TreasuryAccount>),
+		AuctionManagerTask(AuctionManagerTask<Runtime>),
+		IncentivesTask(IncentivesTask<Runtime>),
+	}
+}
+
+parameter_types! {
+	pub MinimumWeightRemainInBlock: Weight = RuntimeBlockWeights::get().max_block / 20;
+}
+
+impl module_idle_scheduler::Config for Runtime {
+	type Event = Event;
+	type Task = ScheduledTasks;
+	type MinimumWeightRemainInBlock = MinimumWeightRemainInBlock;
+	type WeightInfo = weights::module_idle_scheduler::WeightInfo<Runtime>;
+}
+
+parameter_types! {
+	pub const FeeRebatePeriod: BlockNumber = 7 * DAYS;
+	pub const MaxRebateTiers: u32 = 8;
+	pub const MaxRebatesPerBlock: u32 = 50;
+}
+
+impl module_fee_rebates::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type PriceSource = Prices;
+	type RebatePeriod = FeeRebatePeriod;
+	type MaxRebateTiers = MaxRebateTiers;
+	type MaxRebatesPerBlock = MaxRebatesPerBlock;
+	type PalletId = FeeRebatesPalletId;
+	type UpdateOrigin = EnsureRootOrHalfGeneralCouncil;
+	type WeightInfo = weights::module_fee_rebates::WeightInfo<Runtime>;
+}
+
+impl module_transaction_pause::Config for Runtime {
+	type Event = Event;
+	type UpdateOrigin = EnsureRootOrTwoThirdsTechnicalCommittee;
+	type WeightInfo = weights::module_transaction_pause::WeightInfo<Runtime>;
+}
+
+/// Visits the calls dispatched by the batch, derivative, proxy, multisig and sponsored calls. The
+/// multisig calls approved by hash only are not visited.
+pub struct RateLimitNestedCalls;
+impl module_rate_limit::NestedCalls<Call> for RateLimitNestedCalls {
+	fn visit_nested_calls(call: &Call, f: &mut dyn FnMut(&Call)) {
+		match call {
+			Call::Utility(pallet_utility::Call::batch(calls))
+			| Call::Utility(pallet_utility::Call::batch_all(calls)) => calls.iter().for_each(f),
+			Call::Utility(pallet_utility::Call::as_derivative(_, call))
+			| Call::Proxy(pallet_proxy::Call::proxy(_, _, call))
+			| Call::Proxy(pallet_proxy::Call::proxy_announced(_, _, _, call))
+			| Call::Multisig(pallet_multisig::Call::as_multi_threshold_1(_, call))
+			| Call::TransactionPayment(module_transaction_payment::Call::sponsored_call(_, _, call)) => f(call),
+			Call::Multisig(pallet_multisig::Call::as_multi(_, _, _, call, _, _)) => {
+				if let Ok(call) = Call::decode(&mut &call[..]) {
+					f(&call)
+				}
+			}
+			_ => {}
+		}
+	}
+}
+
+parameter_types! {
+	pub const RateLimitMaxNestedCalls: u32 = 256;
+}
+
+impl module_rate_limit::Config for Runtime {
+	type Event = Event;
+	type UpdateOrigin = EnsureRootOrHalfGeneralCouncil;
+	type NestedCalls = RateLimitNestedCalls;
+	type MaxNestedCalls = RateLimitMaxNestedCalls;
+	type WeightInfo = weights::module_rate_limit::WeightInfo<Runtime>;
+}
+
+pub struct RiskParameterHandler;
+impl module_parameters::ParameterHandler for RiskParameterHandler {
+	fn get(key: &ParameterKey) -> Option<sp_runtime::FixedU128> {
+		match *key {
+			ParameterKey::StabilityFee(currency_id) => CdpEngine::collateral_params(currency_id).interest_rate_per_sec,
+			ParameterKey::LiquidationRatio(currency_id) => CdpEngine::collateral_params(currency_id).liquidation_ratio,
+			ParameterKey::DexSavingRewardRate(currency_id) => Some(Incentives::dex_saving_reward_rate(
+				module_incentives::PoolId::DexSaving(currency_id),
+			)),
+		}
+	}
+
+	fn set(key: &ParameterKey, value: sp_runtime::FixedU128) -> DispatchResult {
+		match *key {
+			ParameterKey::StabilityFee(currency_id) => CdpEngine::set_collateral_params(
+				Origin::root(),
+				currency_id,
+				Change::NewValue(Some(value)),
+				Change::NoChange,
+				Change::NoChange,
+				Change::NoChange,
+				Change::NoChange,
+			),
+			ParameterKey::LiquidationRatio(currency_id) => CdpEngine::set_collateral_params(
+				Origin::root(),
+				currency_id,
+				Change::NoChange,
+				Change::NewValue(Some(value)),
+				Change::NoChange,
+				Change::NoChange,
+				Change::NoChange,
+			),
+			ParameterKey::DexSavingRewardRate(currency_id) => Incentives::update_dex_saving_rewards(
+				Origin::root(),
+				vec![(module_incentives::PoolId::DexSaving(currency_id), value)],
+			),
+		}
+		.map(|_| ())
+		.map_err(|e| e.error)
+	}
+}
+
+impl module_parameters::Config for Runtime {
+	type Event = Event;
+	type BoundsOrigin = EnsureRoot<AccountId>;
+	type UpdateOrigin = EnsureRootOrHalfFinancialCouncil;
+	type ParameterHandler = RiskParameterHandler;
+	type WeightInfo = weights::module_parameters::WeightInfo<Runtime>;
+}
+
+parameter_types! {
+	pub const MaxGrantMilestones: u32 = 16;
+}
+
+impl module_grants::Config for Runtime {
+	type Event = Event;
+	type Currency = Currencies;
+	type TreasuryAccount = TreasuryAccount;
+	type PalletId = GrantsPalletId;
+	type MaxMilestones = MaxGrantMilestones;
+	type ApproveOrigin = EnsureRootOrHalfGeneralCouncil;
+	type MilestoneOrigin = EnsureRootOrHalfGeneralCouncil;
+	type WeightInfo = weights::module_grants::WeightInfo<Runtime>;
+}
+
+parameter_types! {
+	pub const EmergencyKeyThreshold: u32 = 3;
+	pub const MaxEmergencyKeys: u32 = 5;
+	pub const EmergencyActionDuration: BlockNumber = 4 * HOURS;
+}
+
+/// The emergency keys may pause the financial pallets and halt the bridges.
+pub struct EmergencyActionFilter;
+impl module_support::Contains<EmergencyAction> for EmergencyActionFilter {
+	fn contains(action: &EmergencyAction) -> bool {
+		match action {
+			EmergencyAction::PausePallet(pallet_name) => matches!(
+				&pallet_name[..],
+				b"Dex" | b"Honzon" | b"CdpEngine" | b"Incentives" | b"StakingPool" | b"NFT" | b"EVM"
+			),
+			EmergencyAction::LockPrice(_) => true,
+			EmergencyAction::HaltBridge(pallet_name) => matches!(
+				&pallet_name[..],
+				b"RenVmBridge" | b"ChainBridge" | b"XTokens" | b"PolkadotXcm"
+			),
+		}
+	}
+}
+
+/// Pause the pallets and lock the prices which are not paused or locked by the governance. The
+/// pauses never expire by themselves, the circuit breaker unpauses them when the actions expire.
+pub struct EmergencyActionHandler;
+impl module_circuit_breaker::EmergencyActionHandler for EmergencyActionHandler {
+	fn take(action: &EmergencyAction) -> Result<bool, sp_runtime::DispatchError> {
+		match action {
+			EmergencyAction::PausePallet(pallet_name) | EmergencyAction::HaltBridge(pallet_name) => {
+				let now = System::block_number();
+				if TransactionPause::paused_pallets(pallet_name).map_or(false, |info| info.is_active(now)) {
+					return Ok(false);
+				}
+				TransactionPause::pause_transaction(
+					Origin::root(),
+					module_transaction_pause::PauseFilter::Pallet(pallet_name.clone()),
+					None,
+				)
+			}
+			EmergencyAction::LockPrice(currency_id) => {
+				if Prices::locked_price(currency_id).is_some() {
+					return Ok(false);
+				}
+				Prices::lock_price(Origin::root(), *currency_id)
+			}
+		}
+		.map(|_| true)
+		.map_err(|e| e.error)
+	}
+
+	fn revert(action: &EmergencyAction) -> DispatchResult {
+		match action {
+			EmergencyAction::PausePallet(pallet_name) | EmergencyAction::HaltBridge(pallet_name) => {
+				TransactionPause::unpause_transaction(
+					Origin::root(),
+					module_transaction_pause::PauseFilter::Pallet(pallet_name.clone()),
+				)
+			}
+			EmergencyAction::LockPrice(currency_id) => Prices::unlock_price(Origin::root(), *currency_id),
+		}
+		.map(|_| ())
+		.map_err(|e| e.error)
+	}
+}
+
+impl module_circuit_breaker::Config for Runtime {
+	type Event = Event;
+	type KeysOrigin = EnsureRootOrHalfGeneralCouncil;
+	type RatifyOrigin = EnsureRootOrHalfGeneralCouncil;
+	type ActionFilter = EmergencyActionFilter;
+	type ActionHandler = EmergencyActionHandler;
+	type KeyThreshold = EmergencyKeyThreshold;
+	type MaxKeys = MaxEmergencyKeys;
+	type ActionDuration = EmergencyActionDuration;
+	type WeightInfo = weights::module_circuit_breaker::WeightInfo<Runtime>;
+}
+
+parameter_types! {
+	pub FederatedBridgeSignerBond: Balance = 10_000 * dollar(ACA);
+	pub const FederatedBridgeBondingDuration: BlockNumber = 7 * DAYS;
+	pub FraudReportDeposit: Balance = 100 * dollar(ACA);
+	pub const FraudReporterReward: Permill = Permill::from_percent(10);
+	pub const MaxFederatedBridgeSigners: u32 = 20;
+}
+
+impl module_federated_bridge::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type BridgeAsset = module_federated_bridge::MultiCurrencyBridgeAsset<Currencies>;
+	type MintGuard = ProofOfReserve;
+	type UpdateOrigin = EnsureRootOrHalfGeneralCouncil;
+	type FraudOrigin = EnsureRootOrTwoThirdsGeneralCouncil;
+	type SignerBond = FederatedBridgeSignerBond;
+	type BondingDuration = FederatedBridgeBondingDuration;
+	type ReportDeposit = FraudReportDeposit;
+	type ReporterReward = FraudReporterReward;
+	type TreasuryAccount = TreasuryAccount;
+	type MaxSigners = MaxFederatedBridgeSigners;
+	type WeightInfo = weights::module_federated_bridge::WeightInfo<Runtime>;
+}
+
 parameter_types! {
 	// One storage item; key size 32, value size 8; .
 	pub ProxyDepositBase: Balance = deposit(1, 8);
@@ -1381,6 +2219,8 @@ impl InstanceFilter<Call> for ProxyType {
 				Call::Vesting(orml_vesting::Call::claim(..)) |
 				Call::Vesting(orml_vesting::Call::update_vesting_schedules(..)) |
 				// Specifically omitting Vesting `vested_transfer`
+				Call::VestingManager(module_vesting_manager::Call::merge_schedules(..)) |
+				// Specifically omitting VestingManager `split_schedule`
 				Call::TransactionPayment(..) |
 				Call::Treasury(..) |
 				Call::Bounties(..) |
@@ -1401,6 +2241,7 @@ impl InstanceFilter<Call> for ProxyType {
 				Call::HomaCouncilMembership(..) |
 				Call::TechnicalCommittee(..) |
 				Call::TechnicalCommitteeMembership(..) |
+				Call::NominatorsCouncil(..) |
 				Call::AcalaOracle(..) |
 				Call::OperatorMembershipAcala(..) |
 				Call::BandOracle(..) |
@@ -1415,12 +2256,17 @@ impl InstanceFilter<Call> for ProxyType {
 				Call::CdpTreasury(..) |
 				Call::CdpEngine(..) |
 				Call::EmergencyShutdown(..) |
+				Call::Psm(..) |
 				Call::Homa(..) |
 				Call::NomineesElection(..) |
 				Call::StakingPool(..) |
 				Call::PolkadotBridge(..) |
 				Call::HomaValidatorListModule(..) |
+				Call::LiquidCrowdloan(..) |
 				Call::Incentives(..) |
+				Call::VoteEscrow(..) |
+				Call::Gauge(..) |
+				Call::Emissions(..) |
 				Call::AirDrop(..) |
 				Call::EvmAccounts(..)
 			),
@@ -1431,6 +2277,7 @@ impl InstanceFilter<Call> for ProxyType {
 					| Call::FinancialCouncil(..)
 					| Call::HomaCouncil(..)
 					| Call::TechnicalCommittee(..)
+					| Call::NominatorsCouncil(..)
 					| Call::Treasury(..) | Call::Bounties(..)
 					| Call::Tips(..) | Call::Utility(..)
 			),
@@ -1466,21 +2313,32 @@ impl pallet_proxy::Config for Runtime {
 parameter_types! {
 	pub const RENBTCCurrencyId: CurrencyId = RENBTC;
 	pub const RENBTCIdentifier: [u8; 32] = hex!["f6b5b360905f856404bd4cf39021b82209908faa44159e68ea207ab8a5e13197"];
+	pub const RenVmKeyRotationOverlap: BlockNumber = DAYS;
+	pub const RenVmMintEpochLength: BlockNumber = DAYS;
 }
 
 impl ecosystem_renvm_bridge::Config for Runtime {
 	type Event = Event;
 	type Currency = Balances;
 	type BridgedTokenCurrency = Currency<Runtime, RENBTCCurrencyId>;
+	type BridgedTokenCurrencyId = RENBTCCurrencyId;
+	type MintGuard = ProofOfReserve;
 	type CurrencyIdentifier = RENBTCIdentifier;
 	type UnsignedPriority = runtime_common::RenvmBridgeUnsignedPriority;
 	type ChargeTransactionPayment = module_transaction_payment::ChargeTransactionPayment<Runtime>;
+	type UpdateOrigin = EnsureRootOrHalfFinancialCouncil;
+	type TreasuryAccount = TreasuryAccount;
+	type KeyRotationOverlap = RenVmKeyRotationOverlap;
+	type EpochLength = RenVmMintEpochLength;
+	type WeightInfo = weights::ecosystem_renvm_bridge::WeightInfo<Runtime>;
 }
 
 parameter_types! {
 	pub const CashCurrencyId: CurrencyId = CurrencyId::Token(TokenSymbol::CASH);
 	pub const MaxGatewayAuthorityCount: u32 = 8;
 	pub const PercentThresholdForGatewayAuthoritySignature: Perbill = Perbill::from_percent(50);
+	pub const StarportVolumePeriod: BlockNumber = DAYS;
+	pub const StarportWithdrawalDelay: BlockNumber = 12 * HOURS;
 }
 
 impl ecosystem_starport::Config for Runtime {
@@ -1491,6 +2349,10 @@ impl ecosystem_starport::Config for Runtime {
 	type MaxGatewayAuthorities = MaxGatewayAuthorityCount;
 	type PercentThresholdForAuthoritySignature = PercentThresholdForGatewayAuthoritySignature;
 	type Cash = CompoundCash;
+	type UpdateOrigin = EnsureRootOrHalfFinancialCouncil;
+	type VolumePeriod = StarportVolumePeriod;
+	type WithdrawalDelay = StarportWithdrawalDelay;
+	type WeightInfo = weights::ecosystem_starport::WeightInfo<Runtime>;
 }
 
 impl ecosystem_compound_cash::Config for Runtime {
@@ -1501,6 +2363,8 @@ impl ecosystem_compound_cash::Config for Runtime {
 parameter_types! {
 	pub const ChainId: u64 = 595;
 	pub NetworkContractSource: H160 = H160::from_low_u64_be(0);
+	pub const AccessListGasMetering: bool = true;
+	pub const EvictionNoticePeriod: BlockNumber = 7 * DAYS;
 }
 
 #[cfg(feature = "with-ethereum-compatibility")]
@@ -1534,8 +2398,13 @@ pub type NFTPrecompile =
 	runtime_common::NFTPrecompile<AccountId, EvmAddressMapping<Runtime>, EvmCurrencyIdMapping<Runtime>, NFT>;
 pub type StateRentPrecompile =
 	runtime_common::StateRentPrecompile<AccountId, EvmAddressMapping<Runtime>, EvmCurrencyIdMapping<Runtime>, EVM>;
-pub type OraclePrecompile =
-	runtime_common::OraclePrecompile<AccountId, EvmAddressMapping<Runtime>, EvmCurrencyIdMapping<Runtime>, Prices>;
+pub type OraclePrecompile = runtime_common::OraclePrecompile<
+	AccountId,
+	EvmAddressMapping<Runtime>,
+	EvmCurrencyIdMapping<Runtime>,
+	Prices,
+	AggregatedDataProvider,
+>;
 pub type ScheduleCallPrecompile = runtime_common::ScheduleCallPrecompile<
 	AccountId,
 	EvmAddressMapping<Runtime>,
@@ -1548,7 +2417,35 @@ pub type ScheduleCallPrecompile = runtime_common::ScheduleCallPrecompile<
 	Runtime,
 >;
 pub type DexPrecompile =
-	runtime_common::DexPrecompile<AccountId, EvmAddressMapping<Runtime>, EvmCurrencyIdMapping<Runtime>, Dex>;
+	runtime_common::DexPrecompile<AccountId, EvmAddressMapping<Runtime>, EvmCurrencyIdMapping<Runtime>, Dex, Runtime>;
+pub type HomaPrecompile = runtime_common::HomaPrecompile<
+	AccountId,
+	EvmAddressMapping<Runtime>,
+	EvmCurrencyIdMapping<Runtime>,
+	StakingPool,
+	LiquidStakingExchangeRateProvider,
+	weights::module_homa::WeightInfo<Runtime>,
+	Runtime,
+>;
+pub type IncentivesPrecompile = runtime_common::IncentivesPrecompile<
+	AccountId,
+	EvmAddressMapping<Runtime>,
+	EvmCurrencyIdMapping<Runtime>,
+	Incentives,
+	weights::module_incentives::WeightInfo<Runtime>,
+	Runtime,
+>;
+pub type HonzonPrecompile = runtime_common::HonzonPrecompile<
+	AccountId,
+	EvmAddressMapping<Runtime>,
+	EvmCurrencyIdMapping<Runtime>,
+	CdpEngine,
+	Runtime,
+>;
+pub type MulticallPrecompile =
+	runtime_common::MulticallPrecompile<AccountId, EvmAddressMapping<Runtime>, EvmCurrencyIdMapping<Runtime>, Runtime>;
+pub type ERC721Precompile =
+	runtime_common::ERC721Precompile<AccountId, EvmAddressMapping<Runtime>, EvmCurrencyIdMapping<Runtime>, Runtime>;
 
 #[cfg(feature = "with-ethereum-compatibility")]
 static ISTANBUL_CONFIG: evm::Config = evm::Config::istanbul();
@@ -1570,9 +2467,16 @@ impl module_evm::Config for Runtime {
 		OraclePrecompile,
 		ScheduleCallPrecompile,
 		DexPrecompile,
+		HomaPrecompile,
+		IncentivesPrecompile,
+		HonzonPrecompile,
+		MulticallPrecompile,
+		ERC721Precompile,
 	>;
 	type ChainId = ChainId;
 	type GasToWeight = GasToWeight;
+	type BlockGasTarget = BlockGasTarget;
+	type AccessListGasMetering = AccessListGasMetering;
 	type ChargeTransactionPayment = module_transaction_payment::ChargeTransactionPayment<Runtime>;
 	type NetworkContractOrigin = EnsureRootOrTwoThirdsTechnicalCommittee;
 	type NetworkContractSource = NetworkContractSource;
@@ -1580,6 +2484,9 @@ impl module_evm::Config for Runtime {
 	type DeploymentFee = DeploymentFee;
 	type TreasuryAccount = TreasuryAccount;
 	type FreeDeploymentOrigin = EnsureRootOrHalfGeneralCouncil;
+	type IdleScheduler = IdleScheduler;
+	type ContractFilter = module_transaction_pause::NonPausedContractFilter<Runtime>;
+	type EvictionNoticePeriod = EvictionNoticePeriod;
 	type WeightInfo = weights::module_evm::WeightInfo<Runtime>;
 
 	#[cfg(feature = "with-ethereum-compatibility")]
@@ -1605,12 +2512,18 @@ impl chainbridge::Config for Runtime {
 	type ProposalLifetime = ProposalLifetime;
 }
 
+parameter_types! {
+	pub const ChainBridgeVolumePeriod: BlockNumber = DAYS;
+}
+
 impl ecosystem_chainsafe::Config for Runtime {
 	type Event = Event;
 	type Currency = Currencies;
 	type NativeCurrencyId = GetNativeCurrencyId;
 	type RegistorOrigin = EnsureRootOrHalfGeneralCouncil;
 	type BridgeOrigin = chainbridge::EnsureBridge<Runtime>;
+	type TreasuryAccount = TreasuryAccount;
+	type VolumePeriod = ChainBridgeVolumePeriod;
 	type WeightInfo = weights::ecosystem_chainsafe::WeightInfo<Runtime>;
 }
 
@@ -1632,6 +2545,15 @@ impl cumulus_pallet_parachain_system::Config for Runtime {
 
 impl parachain_info::Config for Runtime {}
 
+parameter_types! {
+	pub const MaxRelaychainStateRoots: u32 = 20;
+}
+
+impl module_relaychain_light_client::Config for Runtime {
+	type RelaychainStateRootProvider = RelaychainStateRootProvider<Runtime>;
+	type MaxRelaychainStateRoots = MaxRelaychainStateRoots;
+}
+
 parameter_types! {
 	pub const DotLocation: MultiLocation = MultiLocation::X1(Parent);
 	pub const RelayNetwork: NetworkId = NetworkId::Polkadot;
@@ -1649,6 +2571,8 @@ pub type LocationToAccountId = (
 	SiblingParachainConvertsVia<Sibling, AccountId>,
 	// Straight up local `AccountId32` origins just alias directly to `AccountId`.
 	AccountId32Aliases<RelayNetwork, AccountId>,
+	// The accounts of sibling parachains convert to the accounts derived by `XcmEvm`.
+	module_xcm_evm::DerivedAccountConvert<Runtime>,
 );
 
 /// This is the type we use to convert an (incoming) XCM origin into a local `Origin` instance,
@@ -1678,14 +2602,20 @@ parameter_types! {
 	pub DotPerSecond: (MultiLocation, u128) = (X1(Parent), dot_per_second());
 }
 
-pub type Barrier = (TakeWeightCredit, AllowTopLevelPaidExecutionFrom<All<MultiLocation>>);
+pub type Barrier = (
+	TakeWeightCredit,
+	AllowTopLevelPaidExecutionFrom<All<MultiLocation>>,
+	// Expected responses of the Homa operations are OK.
+	AllowKnownQueryResponses<HomaXcmQueue>,
+);
 
 pub struct XcmConfig;
 impl Config for XcmConfig {
 	type Call = Call;
 	type XcmSender = XcmRouter;
-	// How to withdraw and deposit an asset.
-	type AssetTransactor = LocalAssetTransactor;
+	// How to withdraw and deposit an asset, the failed deposits are recorded into the dead letter
+	// queue.
+	type AssetTransactor = module_xcm_dead_letter::DeadLetterAssetTransactor<Runtime>;
 	type OriginConverter = XcmOriginToCallOrigin;
 	type IsReserve = MultiNativeAsset;
 	// Teleporting is disabled.
@@ -1693,10 +2623,11 @@ impl Config for XcmConfig {
 	type LocationInverter = LocationInverter<Ancestry>;
 	type Barrier = Barrier;
 	type Weigher = FixedWeightBounds<UnitWeightCost, Call>;
-	// Only receiving DOT is handled, and all fees must be paid in DOT.
+	// The fees can be paid in the currencies with the fee rates of `XcmEvm`, or in DOT.
 	//TODO: make treasury take revenue
-	type Trader = FixedRateOfConcreteFungible<DotPerSecond, ()>;
-	type ResponseHandler = (); // Don't handle responses for now.
+	type Trader = module_xcm_evm::FeeRateTrader<Runtime, FixedRateOfConcreteFungible<DotPerSecond, ()>, ()>;
+	// The responses of the Homa operations on relaychain.
+	type ResponseHandler = HomaXcmQueue;
 }
 
 parameter_types! {
@@ -1767,6 +2698,7 @@ impl Convert<CurrencyId, Option<MultiLocation>> for CurrencyIdConvert {
 		match id {
 			Token(DOT) => Some(X1(Parent)),
 			Token(ACA) | Token(AUSD) | Token(LDOT) | Token(RENBTC) => Some(native_currency_location(id)),
+			CurrencyId::ForeignAsset(foreign_asset_id) => AssetRegistry::foreign_asset_locations(foreign_asset_id),
 			_ => None,
 		}
 	}
@@ -1775,6 +2707,9 @@ impl Convert<MultiLocation, Option<CurrencyId>> for CurrencyIdConvert {
 	fn convert(location: MultiLocation) -> Option<CurrencyId> {
 		use CurrencyId::Token;
 		use TokenSymbol::*;
+		if let Some(currency_id) = AssetRegistry::location_to_currency_ids(location.clone()) {
+			return Some(currency_id);
+		}
 		match location {
 			X1(Parent) => Some(Token(DOT)),
 			X3(Parent, Parachain(id), GeneralKey(key)) if ParaId::from(id) == ParachainInfo::get() => {
@@ -1828,6 +2763,158 @@ impl orml_xtokens::Config for Runtime {
 	type Weigher = FixedWeightBounds<UnitWeightCost, Call>;
 }
 
+fn dest_chain_location(dest: DestChain) -> MultiLocation {
+	match dest {
+		DestChain::Relay => X1(Parent),
+		DestChain::Parachain(id) => X2(Parent, Parachain(id)),
+	}
+}
+
+fn is_self_reserve(location: &MultiLocation) -> bool {
+	matches!(location, X3(Parent, Parachain(id), GeneralKey(_)) if ParaId::from(*id) == ParachainInfo::get())
+}
+
+pub struct XcmTransferHandler;
+impl module_xcm_transfer::XcmTransferHandler<AccountId> for XcmTransferHandler {
+	fn transfer(
+		who: &AccountId,
+		currency_id: CurrencyId,
+		amount: Balance,
+		fee: Option<(CurrencyId, Balance)>,
+		dest: DestChain,
+		recipient: [u8; 32],
+		dest_weight: Weight,
+	) -> DispatchResult {
+		let beneficiary = AccountId32 {
+			network: NetworkId::Any,
+			id: recipient,
+		};
+
+		match fee {
+			// the fee is deducted from the transferred asset
+			None => {
+				let dest = match dest {
+					DestChain::Relay => X2(Parent, beneficiary),
+					DestChain::Parachain(id) => X3(Parent, Parachain(id), beneficiary),
+				};
+				XTokens::transfer(Origin::signed(who.clone()), currency_id, amount, dest, dest_weight)
+					.map(|_| ())
+					.map_err(|e| e.error)
+			}
+			// the fee asset is transferred along with the asset to buy the execution, which is
+			// only supported if this chain is the reserve of both
+			Some((fee_currency_id, fee_amount)) => {
+				let asset_location = CurrencyIdConvert::convert(currency_id).filter(is_self_reserve);
+				let fee_location = CurrencyIdConvert::convert(fee_currency_id).filter(is_self_reserve);
+				let (asset_location, fee_location) = asset_location
+					.zip(fee_location)
+					.ok_or(module_xcm_transfer::Error::<Runtime>::FeeNotSupported)?;
+
+				PolkadotXcm::reserve_transfer_assets(
+					Origin::signed(who.clone()),
+					dest_chain_location(dest),
+					X1(beneficiary),
+					vec![
+						MultiAsset::ConcreteFungible {
+							id: asset_location,
+							amount,
+						},
+						MultiAsset::ConcreteFungible {
+							id: fee_location,
+							amount: fee_amount,
+						},
+					],
+					dest_weight,
+				)
+			}
+		}
+	}
+}
+
+impl module_xcm_transfer::Config for Runtime {
+	type Event = Event;
+	type UpdateOrigin = EnsureRootOrHalfGeneralCouncil;
+	type XcmTransfer = XcmTransferHandler;
+	type WeightInfo = weights::module_xcm_transfer::WeightInfo<Runtime>;
+}
+
+parameter_types! {
+	pub const MaxFailedTransfers: u32 = 10_000;
+}
+
+impl module_xcm_dead_letter::Config for Runtime {
+	type Event = Event;
+	type AssetTransactor = LocalAssetTransactor;
+	type LocationToAccountId = LocationToAccountId;
+	type AccountIdToMultiLocation = AccountIdToMultiLocation;
+	type RecoveryOrigin = EnsureRootOrHalfGeneralCouncil;
+	type MaxFailedTransfers = MaxFailedTransfers;
+	type WeightInfo = weights::module_xcm_dead_letter::WeightInfo<Runtime>;
+}
+
+/// Ensures the origin is a sibling parachain, returns the parachain id.
+pub struct EnsureSiblingParachain;
+impl EnsureOrigin<Origin> for EnsureSiblingParachain {
+	type Success = u32;
+
+	fn try_origin(o: Origin) -> Result<u32, Origin> {
+		Into::<Result<cumulus_pallet_xcm::Origin, Origin>>::into(o).and_then(|o| match o {
+			cumulus_pallet_xcm::Origin::SiblingParachain(id) => Ok(id.into()),
+			r => Err(Origin::from(r)),
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> Origin {
+		Origin::from(cumulus_pallet_xcm::Origin::SiblingParachain(2000.into()))
+	}
+}
+
+impl module_xcm_evm::Config for Runtime {
+	type Event = Event;
+	type EVM = EVM;
+	type SiblingOrigin = EnsureSiblingParachain;
+	type GasToWeight = GasToWeight;
+	type CurrencyIdConvert = CurrencyIdConvert;
+	type UpdateOrigin = EnsureRootOrHalfGeneralCouncil;
+	type WeightInfo = weights::module_xcm_evm::WeightInfo<Runtime>;
+}
+
+parameter_types! {
+	pub const ReserveCheckPeriod: BlockNumber = HOURS;
+	pub const MaxReserveAge: BlockNumber = DAYS;
+	pub const MaxMonitoredAssets: u32 = 20;
+}
+
+impl module_proof_of_reserve::Config for Runtime {
+	type Event = Event;
+	type Currency = Currencies;
+	type ReporterOrigin = frame_system::EnsureSignedBy<OperatorMembershipAcala, AccountId>;
+	type UpdateOrigin = EnsureRootOrHalfFinancialCouncil;
+	type CheckPeriod = ReserveCheckPeriod;
+	type MaxReserveAge = MaxReserveAge;
+	type MaxMonitoredAssets = MaxMonitoredAssets;
+	type WeightInfo = weights::module_proof_of_reserve::WeightInfo<Runtime>;
+}
+
+parameter_types! {
+	pub RelaychainLocation: MultiLocation = X1(Parent);
+	pub const HomaXcmResponseTimeout: BlockNumber = 10 * MINUTES;
+	pub const HomaXcmRetryBackoff: BlockNumber = MINUTES;
+	pub const HomaXcmMaxRetries: u32 = 5;
+}
+
+impl module_homa_xcm_queue::Config for Runtime {
+	type Event = Event;
+	type Transactor = RelaychainHomaTransactor;
+	type ResponseOrigin = RelaychainLocation;
+	type ResponseTimeout = HomaXcmResponseTimeout;
+	type RetryBackoff = HomaXcmRetryBackoff;
+	type MaxRetries = HomaXcmMaxRetries;
+	type UpdateOrigin = EnsureRootOrHalfHomaCouncil;
+	type WeightInfo = weights::module_homa_xcm_queue::WeightInfo<Runtime>;
+}
+
 impl orml_unknown_tokens::Config for Runtime {
 	type Event = Event;
 }
@@ -1854,6 +2941,7 @@ pub type SignedExtra = (
 	frame_system::CheckWeight<Runtime>,
 	module_transaction_payment::ChargeTransactionPayment<Runtime>,
 	module_evm::SetEvmOrigin<Runtime>,
+	module_rate_limit::CheckRateLimit<Runtime>,
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
@@ -1862,8 +2950,48 @@ pub type SignedPayload = generic::SignedPayload<Call, SignedExtra>;
 /// Extrinsic type that has already been checked.
 pub type CheckedExtrinsic = generic::CheckedExtrinsic<AccountId, Call, SignedExtra>;
 /// Executive: handles dispatch to the various modules.
-pub type Executive =
-	frame_executive::Executive<Runtime, Block, frame_system::ChainContext<Runtime>, Runtime, AllPallets, ()>;
+pub type Executive = frame_executive::Executive<
+	Runtime,
+	Block,
+	frame_system::ChainContext<Runtime>,
+	Runtime,
+	AllPallets,
+	SeedEmissionBudget,
+>;
+
+/// Seed the emission budget with the current incentive reward amounts of the pools and fund the
+/// incentives of the first emission period, so the incentives keep paying after their rewards
+/// source moved to the emissions.
+pub struct SeedEmissionBudget;
+impl frame_support::traits::OnRuntimeUpgrade for SeedEmissionBudget {
+	fn on_runtime_upgrade() -> Weight {
+		if !Emissions::emission_budget().is_zero() {
+			return RocksDbWeight::get().reads(1);
+		}
+
+		let mut count: Weight = 0;
+		let reward_amount_per_period = module_incentives::IncentiveRewardAmount::<Runtime>::iter_values().fold(
+			Balance::zero(),
+			|total, amount| {
+				count += 1;
+				total.saturating_add(amount)
+			},
+		);
+		let budget = reward_amount_per_period.saturating_mul((EmissionPeriod::get() / AccumulatePeriod::get()).into());
+		module_emissions::EmissionBudget::<Runtime>::put(budget);
+		module_emissions::EmissionProportions::<Runtime>::insert(
+			module_emissions::EmissionTarget::Incentives,
+			Permill::one(),
+		);
+		Emissions::distribute_emission();
+
+		RocksDbWeight::get()
+			.reads_writes(count.saturating_add(1), 2)
+			.saturating_add(
+				<weights::module_emissions::WeightInfo<Runtime> as module_emissions::WeightInfo>::on_initialize(),
+			)
+	}
+}
 
 #[allow(clippy::large_enum_variant)]
 construct_runtime! {
@@ -1880,9 +3008,10 @@ construct_runtime! {
 		// Tokens & Related
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>} = 10,
 		Tokens: orml_tokens::{Pallet, Storage, Event<T>, Config<T>} = 11,
-		Currencies: module_currencies::{Pallet, Call, Event<T>} = 12,
+		Currencies: module_currencies::{Pallet, Call, Storage, Event<T>} = 12,
 		Vesting: orml_vesting::{Pallet, Storage, Call, Event<T>, Config<T>} = 13,
-		TransactionPayment: module_transaction_payment::{Pallet, Call, Storage} = 14,
+		TransactionPayment: module_transaction_payment::{Pallet, Call, Storage, Event<T>} = 14,
+		AssetRegistry: module_asset_registry::{Pallet, Call, Storage, Event<T>} = 15,
 
 		// Treasury
 		Treasury: pallet_treasury::{Pallet, Call, Storage, Config, Event<T>} = 20,
@@ -1907,6 +3036,7 @@ construct_runtime! {
 		HomaCouncilMembership: pallet_membership::<Instance3>::{Pallet, Call, Storage, Event<T>, Config<T>} = 55,
 		TechnicalCommittee: pallet_collective::<Instance4>::{Pallet, Call, Storage, Origin<T>, Event<T>, Config<T>} = 56,
 		TechnicalCommitteeMembership: pallet_membership::<Instance4>::{Pallet, Call, Storage, Event<T>, Config<T>} = 57,
+		NominatorsCouncil: pallet_collective::<Instance5>::{Pallet, Call, Storage, Origin<T>, Event<T>} = 58,
 
 		Authority: orml_authority::{Pallet, Call, Storage, Event<T>, Origin<T>} = 70,
 		ElectionsPhragmen: pallet_elections_phragmen::{Pallet, Call, Storage, Event<T>} = 71,
@@ -1922,7 +3052,7 @@ construct_runtime! {
 
 		// ORML Core
 		Auction: orml_auction::{Pallet, Storage, Call, Event<T>} = 100,
-		Rewards: orml_rewards::{Pallet, Storage, Call} = 101,
+		Rewards: module_rewards::{Pallet, Storage, Call} = 101,
 		OrmlNFT: orml_nft::{Pallet, Storage, Config<T>} = 102,
 
 		// Acala Core
@@ -1938,16 +3068,41 @@ construct_runtime! {
 		EmergencyShutdown: module_emergency_shutdown::{Pallet, Storage, Call, Event<T>} = 125,
 
 		// Homa
-		Homa: module_homa::{Pallet, Call} = 130,
+		Homa: module_homa::{Pallet, Call, Storage, Event<T>} = 130,
 		NomineesElection: module_nominees_election::{Pallet, Call, Storage, Event<T>} = 131,
 		StakingPool: module_staking_pool::{Pallet, Call, Storage, Event<T>, Config} = 132,
-		PolkadotBridge: module_polkadot_bridge::{Pallet, Call, Storage} = 133,
+		PolkadotBridge: module_polkadot_bridge::{Pallet, Call, Storage, Event<T>} = 133,
 		HomaValidatorListModule: module_homa_validator_list::{Pallet, Call, Storage, Event<T>} = 134,
+		LiquidCrowdloan: module_liquid_crowdloan::{Pallet, Call, Storage, Event<T>} = 135,
 
 		// Acala Other
-		Incentives: module_incentives::{Pallet, Storage, Call, Event<T>} = 140,
+		Incentives: module_incentives::{Pallet, Storage, Call, Config<T>, Event<T>} = 140,
 		NFT: module_nft::{Pallet, Call, Event<T>} = 141,
 		AirDrop: module_airdrop::{Pallet, Call, Storage, Event<T>, Config<T>} = 142,
+		VoteEscrow: module_vote_escrow::{Pallet, Call, Storage, Event<T>} = 143,
+		Gauge: module_gauge::{Pallet, Call, Storage, Event<T>} = 144,
+		Emissions: module_emissions::{Pallet, Call, Storage, Event<T>} = 145,
+		NFTMarketplace: module_nft_marketplace::{Pallet, Call, Storage, Event<T>} = 146,
+		ScheduledTransfers: module_scheduled_transfers::{Pallet, Call, Storage, Event<T>} = 147,
+		Streaming: module_streaming::{Pallet, Call, Storage, Event<T>} = 148,
+		Launchpad: module_launchpad::{Pallet, Call, Storage, Event<T>} = 149,
+		AccountFreeze: module_account_freeze::{Pallet, Call, Storage, Event<T>} = 155,
+		FeeRebates: module_fee_rebates::{Pallet, Call, Storage, Event<T>} = 156,
+		TransactionPause: module_transaction_pause::{Pallet, Call, Storage, Event<T>} = 157,
+		RateLimit: module_rate_limit::{Pallet, Call, Storage, Event<T>} = 158,
+		Parameters: module_parameters::{Pallet, Call, Storage, Event<T>} = 159,
+		Grants: module_grants::{Pallet, Call, Storage, Event<T>} = 200,
+		CircuitBreaker: module_circuit_breaker::{Pallet, Call, Storage, Event<T>} = 201,
+		FederatedBridge: module_federated_bridge::{Pallet, Call, Storage, Event<T>} = 202,
+		XcmTransfer: module_xcm_transfer::{Pallet, Call, Storage, Event<T>} = 203,
+		XcmDeadLetter: module_xcm_dead_letter::{Pallet, Call, Storage, Event<T>} = 204,
+		XcmEvm: module_xcm_evm::{Pallet, Call, Storage, Event<T>} = 205,
+		ProofOfReserve: module_proof_of_reserve::{Pallet, Call, Storage, Event<T>} = 206,
+		HomaXcmQueue: module_homa_xcm_queue::{Pallet, Call, Storage, Event<T>} = 207,
+		EventTopics: module_event_topics::{Pallet, Storage} = 208,
+		VestingManager: module_vesting_manager::{Pallet, Call, Event<T>} = 209,
+		IdleScheduler: module_idle_scheduler::{Pallet, Call, Storage, Event<T>} = 210,
+		Psm: module_psm::{Pallet, Call, Storage, Event<T>} = 211,
 
 		// Ecosystem modules
 		RenVmBridge: ecosystem_renvm_bridge::{Pallet, Call, Config, Storage, Event<T>, ValidateUnsigned} = 150,
@@ -1959,6 +3114,7 @@ construct_runtime! {
 		// Parachain
 		ParachainSystem: cumulus_pallet_parachain_system::{Pallet, Call, Storage, Inherent, Config, Event<T>} = 160,
 		ParachainInfo: parachain_info::{Pallet, Storage, Config} = 161,
+		RelaychainLightClient: module_relaychain_light_client::{Pallet, Storage} = 162,
 
 		// XCM
 		XcmpQueue: cumulus_pallet_xcmp_queue::{Pallet, Call, Storage, Event<T>} = 170,
@@ -1972,7 +3128,7 @@ construct_runtime! {
 		EVM: module_evm::{Pallet, Config<T>, Call, Storage, Event<T>} = 180,
 		EVMBridge: module_evm_bridge::{Pallet} = 181,
 		EvmAccounts: module_evm_accounts::{Pallet, Call, Storage, Event<T>} = 182,
-		EvmManager: module_evm_manager::{Pallet, Storage} = 183,
+		EvmManager: module_evm_manager::{Pallet, Call, Storage, Event<T>} = 183,
 
 		// Collator support. the order of these 4 are important and shall not change.
 		Authorship: pallet_authorship::{Pallet, Call, Storage} = 190,
@@ -2121,6 +3277,177 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl module_honzon_rpc_runtime_api::HonzonApi<
+		Block,
+		AccountId,
+		CurrencyId,
+		Balance,
+	> for Runtime {
+		fn get_positions(
+			currency_id: CurrencyId,
+		) -> Vec<module_honzon_rpc_runtime_api::CdpPosition<AccountId, Balance>> {
+			module_loans::Positions::<Runtime>::iter_prefix(currency_id)
+				.map(|(owner, position)| module_honzon_rpc_runtime_api::CdpPosition {
+					owner,
+					collateral: position.collateral,
+					debit: position.debit,
+				})
+				.collect()
+		}
+
+		fn get_collateral_states() -> Vec<module_honzon_rpc_runtime_api::CollateralState<CurrencyId, Balance>> {
+			module_cdp_engine::CollateralParams::<Runtime>::iter_keys()
+				.map(|currency_id| {
+					let total_positions = Loans::total_positions(currency_id);
+					module_honzon_rpc_runtime_api::CollateralState {
+						currency_id,
+						total_collateral: total_positions.collateral,
+						total_debit: total_positions.debit,
+						debit_exchange_rate: CdpEngine::get_debit_exchange_rate(currency_id),
+					}
+				})
+				.collect()
+		}
+
+		fn simulate_liquidation(
+			currency_id: CurrencyId,
+			price_drop: Permill,
+		) -> Option<module_honzon_rpc_runtime_api::LiquidationSimulation<AccountId, Balance>> {
+			CdpEngine::simulate_liquidation(currency_id, price_drop).map(|simulation| {
+				module_honzon_rpc_runtime_api::LiquidationSimulation {
+					unsafe_positions: simulation
+						.unsafe_positions
+						.into_iter()
+						.map(|(owner, position)| module_honzon_rpc_runtime_api::CdpPosition {
+							owner,
+							collateral: position.collateral,
+							debit: position.debit,
+						})
+						.collect(),
+					total_collateral: simulation.total_collateral,
+					total_debit_value: simulation.total_debit_value,
+					bad_debt_value: simulation.bad_debt_value,
+				}
+			})
+		}
+	}
+
+	impl module_dex_rpc_runtime_api::DexApi<
+		Block,
+		CurrencyId,
+		Balance,
+	> for Runtime {
+		fn get_liquidity_pools() -> Vec<module_dex_rpc_runtime_api::LiquidityPool<CurrencyId, Balance>> {
+			module_dex::LiquidityPool::<Runtime>::iter()
+				.map(|(trading_pair, (reserve_0, reserve_1))| module_dex_rpc_runtime_api::LiquidityPool {
+					currency_id_0: trading_pair.0,
+					currency_id_1: trading_pair.1,
+					reserve_0,
+					reserve_1,
+					total_shares: trading_pair
+						.get_dex_share_currency_id()
+						.map_or(0, orml_tokens::TotalIssuance::<Runtime>::get),
+				})
+				.collect()
+		}
+	}
+
+	impl module_incentives_rpc_runtime_api::IncentivesApi<
+		Block,
+		AccountId,
+		module_incentives::PoolId<AccountId>,
+		CurrencyId,
+		Balance,
+	> for Runtime {
+		fn get_pending_rewards(
+			account: AccountId,
+		) -> Vec<module_incentives_rpc_runtime_api::PendingReward<module_incentives::PoolId<AccountId>, CurrencyId, Balance>> {
+			Incentives::pending_rewards(&account)
+				.into_iter()
+				.map(|(pool_id, currency_id, amount)| module_incentives_rpc_runtime_api::PendingReward {
+					pool_id,
+					currency_id,
+					amount,
+				})
+				.collect()
+		}
+
+		fn get_apr_estimates() -> Vec<module_incentives_rpc_runtime_api::AprEstimate<module_incentives::PoolId<AccountId>>> {
+			use module_incentives::PoolId;
+			use module_support::PriceProvider;
+			use sp_runtime::traits::Saturating;
+
+			Incentives::apr_estimates(
+				System::block_number(),
+				365 * DAYS / AccumulatePeriod::get(),
+				Prices::get_price,
+				|pool_id| match pool_id {
+					// the share of loans incentive pool is the debit amount
+					PoolId::LoansIncentive(currency_id) => Prices::get_price(GetStableCurrencyId::get())
+						.map(|price| price.saturating_mul(CdpEngine::get_debit_exchange_rate(*currency_id))),
+					PoolId::DexIncentive(currency_id) | PoolId::DexSaving(currency_id) => Prices::get_price(*currency_id),
+					PoolId::HomaValidatorAllowance(_) => Prices::get_price(GetLiquidCurrencyId::get()),
+					PoolId::HomaIncentive => None,
+				},
+			)
+			.into_iter()
+			.map(|(pool_id, apr)| module_incentives_rpc_runtime_api::AprEstimate { pool_id, apr })
+			.collect()
+		}
+	}
+
+	impl module_homa_rpc_runtime_api::HomaApi<
+		Block,
+		AccountId,
+		Balance,
+	> for Runtime {
+		fn get_exchange_rate() -> ExchangeRate {
+			StakingPool::liquid_exchange_rate()
+		}
+
+		fn get_pending_unbonding() -> Vec<(EraIndex, Balance)> {
+			StakingPool::get_pending_unbonding()
+		}
+
+		fn get_claimable(account: AccountId) -> module_homa_rpc_runtime_api::BalanceInfo<Balance> {
+			module_homa_rpc_runtime_api::BalanceInfo {
+				amount: StakingPool::get_available_unbonded(&account)
+			}
+		}
+
+		fn get_unbondings(account: AccountId) -> Vec<(EraIndex, Balance)> {
+			StakingPool::get_unbondings(&account)
+		}
+
+		fn get_exchange_rate_history() -> Vec<(EraIndex, ExchangeRate)> {
+			StakingPool::get_exchange_rate_history()
+		}
+	}
+
+	impl runtime_common::AuthorityApi<Block, BlockNumber, Hash, OriginCaller> for Runtime {
+		fn get_scheduled_calls() -> Vec<runtime_common::ScheduledCall<BlockNumber, Hash, OriginCaller>> {
+			runtime_common::authority_scheduled_calls::<Runtime>()
+		}
+	}
+
+	impl runtime_common::AccountPortfolioApi<Block, AccountId, BlockNumber, module_incentives::PoolId<AccountId>> for Runtime {
+		fn get_account_portfolio(
+			account: AccountId,
+		) -> runtime_common::AccountPortfolio<BlockNumber, module_incentives::PoolId<AccountId>> {
+			let homa_claims = runtime_common::HomaClaims {
+				claimable: StakingPool::get_available_unbonded(&account),
+				unbondings: StakingPool::get_unbondings(&account),
+			};
+			runtime_common::account_portfolio::<Runtime>(&account, homa_claims)
+		}
+	}
+
+	impl runtime_common::StateReportApi<Block> for Runtime {
+		fn get_state_report() -> Vec<runtime_common::StorageItemReport> {
+			runtime_common::state_report(runtime_common::STATE_REPORT_ITEMS)
+		}
+	}
+
 	impl module_evm_rpc_runtime_api::EVMRuntimeRPCApi<Block, Balance> for Runtime {
 		fn call(
 			from: H160,
@@ -2207,6 +3534,110 @@ impl_runtime_apis! {
 
 			request.ok_or(sp_runtime::DispatchError::Other("Invalid parameter extrinsic, not evm Call"))
 		}
+
+		fn estimate_fee(
+			from: H160,
+			to: Option<H160>,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			storage_limit: u32,
+			fee_currency_id: Option<CurrencyId>,
+		) -> Result<EstimateFeeInfo<Balance>, sp_runtime::DispatchError> {
+			EVM::estimate_fee(from, to, data, value, gas_limit, storage_limit, fee_currency_id)
+		}
+
+		fn block_fee_info() -> BlockFeeInfo {
+			EVM::block_fee_info()
+		}
+
+		fn contract_status(address: H160) -> Option<ContractStatus> {
+			EVM::contract_status(&address)
+		}
+
+		fn contract_metadata(code_hash: H256) -> Option<ContractMetadata> {
+			EVM::contract_metadata(&code_hash)
+		}
+
+		fn block_logs_bloom() -> Option<LogsBloom> {
+			EVM::block_logs_bloom()
+		}
+
+		fn block_receipts() -> Vec<Receipt> {
+			EVM::block_receipts()
+		}
+	}
+
+	impl module_evm_rpc_runtime_api::EVMTraceRuntimeApi<Block> for Runtime {
+		fn trace_extrinsic(
+			header: &<Block as BlockT>::Header,
+			extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+			index: u32,
+			config: TracerConfig,
+		) -> Result<TraceInfo, sp_runtime::DispatchError> {
+			let index = index as usize;
+			let extrinsic = extrinsics
+				.get(index)
+				.cloned()
+				.ok_or(sp_runtime::DispatchError::Other("Invalid parameter index, extrinsic not found"))?;
+
+			Executive::initialize_block(header);
+			for extrinsic in extrinsics.into_iter().take(index) {
+				let _ = Executive::apply_extrinsic(extrinsic);
+			}
+
+			let mut tracer = module_evm::runner::tracing::Tracer::new(config);
+			module_evm::runner::tracing::using(&mut tracer, || {
+				let _ = Executive::apply_extrinsic(extrinsic);
+			});
+
+			tracer.finalize().ok_or(sp_runtime::DispatchError::Other("Invalid parameter extrinsic, not evm Call"))
+		}
+	}
+
+	impl module_evm_rpc_runtime_api::DryRunRuntimeApi<Block, Balance> for Runtime {
+		fn dry_run_extrinsic(extrinsic: <Block as BlockT>::Extrinsic) -> DryRunInfo<Balance> {
+			let len = extrinsic.encoded_size() as u32;
+			let fee = TransactionPayment::query_info(extrinsic.clone(), len).partial_fee;
+			let event_count = System::event_count() as usize;
+
+			let ((result, events), storage_changes) = EVM::dry_run(|| {
+				let result = Executive::apply_extrinsic(extrinsic);
+				let events = System::events()
+					.into_iter()
+					.skip(event_count)
+					.map(|record| record.event.encode())
+					.collect();
+				(result, events)
+			});
+
+			DryRunInfo {
+				result,
+				events,
+				storage_changes,
+				fee,
+			}
+		}
+	}
+
+	impl module_evm_rpc_runtime_api::EVMTransactionPoolApi<Block, AccountId, Balance> for Runtime {
+		fn evm_account_nonce(address: H160) -> (AccountId, Nonce) {
+			use module_support::AddressMapping;
+
+			let account = EvmAddressMapping::<Runtime>::get_account_id(&address);
+			let nonce = System::account_nonce(&account);
+			(account, nonce)
+		}
+
+		fn evm_transaction(extrinsic: <Block as BlockT>::Extrinsic) -> Option<EvmTransaction<Balance>> {
+			let (address, _, extra) = extrinsic.signature?;
+			let who = <Runtime as frame_system::Config>::Lookup::lookup(address).ok()?;
+			match extrinsic.function {
+				// the nonce of `frame_system::CheckNonce` in `SignedExtra`
+				Call::EVM(call) => EVM::evm_transaction(&who, (extra.4).0, &call),
+				_ => None,
+			}
+		}
 	}
 
 	impl cumulus_primitives_core::CollectCollationInfo<Block> for Runtime {
@@ -2260,6 +3691,31 @@ impl_runtime_apis! {
 			orml_add_benchmark!(params, batches, module_cdp_engine, benchmarking::cdp_engine);
 			orml_add_benchmark!(params, batches, module_collator_selection, benchmarking::collator_selection);
 			orml_add_benchmark!(params, batches, module_nominees_election, benchmarking::nominees_election);
+			orml_add_benchmark!(params, batches, module_vote_escrow, benchmarking::vote_escrow);
+			orml_add_benchmark!(params, batches, module_gauge, benchmarking::gauge);
+			orml_add_benchmark!(params, batches, module_asset_registry, benchmarking::asset_registry);
+			orml_add_benchmark!(params, batches, module_emissions, benchmarking::emissions);
+			orml_add_benchmark!(params, batches, module_nft_marketplace, benchmarking::nft_marketplace);
+			orml_add_benchmark!(params, batches, module_scheduled_transfers, benchmarking::scheduled_transfers);
+			orml_add_benchmark!(params, batches, module_streaming, benchmarking::streaming);
+			orml_add_benchmark!(params, batches, module_launchpad, benchmarking::launchpad);
+			orml_add_benchmark!(params, batches, module_account_freeze, benchmarking::account_freeze);
+			orml_add_benchmark!(params, batches, module_vesting_manager, benchmarking::vesting_manager);
+			orml_add_benchmark!(params, batches, module_idle_scheduler, benchmarking::idle_scheduler);
+			orml_add_benchmark!(params, batches, module_fee_rebates, benchmarking::fee_rebates);
+			orml_add_benchmark!(params, batches, module_transaction_pause, benchmarking::transaction_pause);
+			orml_add_benchmark!(params, batches, module_rate_limit, benchmarking::rate_limit);
+			orml_add_benchmark!(params, batches, module_parameters, benchmarking::parameters);
+			orml_add_benchmark!(params, batches, module_grants, benchmarking::grants);
+			orml_add_benchmark!(params, batches, module_airdrop, benchmarking::airdrop);
+			orml_add_benchmark!(params, batches, module_circuit_breaker, benchmarking::circuit_breaker);
+			orml_add_benchmark!(params, batches, module_federated_bridge, benchmarking::federated_bridge);
+			orml_add_benchmark!(params, batches, module_xcm_transfer, benchmarking::xcm_transfer);
+			orml_add_benchmark!(params, batches, module_xcm_dead_letter, benchmarking::xcm_dead_letter);
+			orml_add_benchmark!(params, batches, module_xcm_evm, benchmarking::xcm_evm);
+			orml_add_benchmark!(params, batches, module_proof_of_reserve, benchmarking::proof_of_reserve);
+			orml_add_benchmark!(params, batches, module_psm, benchmarking::psm);
+			orml_add_benchmark!(params, batches, module_homa_xcm_queue, benchmarking::homa_xcm_queue);
 			orml_add_benchmark!(params, batches, module_emergency_shutdown, benchmarking::emergency_shutdown);
 			orml_add_benchmark!(params, batches, module_evm, benchmarking::evm);
 			orml_add_benchmark!(params, batches, module_honzon, benchmarking::honzon);
@@ -2280,6 +3736,8 @@ impl_runtime_apis! {
 			orml_add_benchmark!(params, batches, orml_oracle, benchmarking::oracle);
 
 			orml_add_benchmark!(params, batches, ecosystem_chainsafe, benchmarking::chainsafe_transfer);
+			orml_add_benchmark!(params, batches, ecosystem_renvm_bridge, benchmarking::renvm_bridge);
+			orml_add_benchmark!(params, batches, ecosystem_starport, benchmarking::starport);
 
 			if batches.is_empty() { return Err("Benchmark not found for this module.".into()) }
 			Ok(batches)
@@ -19,22 +19,49 @@
 //! A list of the different weight modules for our runtime.
 #![allow(clippy::unnecessary_cast)]
 
+pub mod module_account_freeze;
+pub mod module_airdrop;
+pub mod module_asset_registry;
 pub mod module_auction_manager;
 pub mod module_cdp_engine;
 pub mod module_cdp_treasury;
+pub mod module_circuit_breaker;
 pub mod module_collator_selection;
 pub mod module_currencies;
 pub mod module_dex;
 pub mod module_emergency_shutdown;
+pub mod module_emissions;
 pub mod module_evm;
 pub mod module_evm_accounts;
+pub mod module_federated_bridge;
+pub mod module_fee_rebates;
+pub mod module_gauge;
+pub mod module_grants;
 pub mod module_homa;
+pub mod module_homa_xcm_queue;
 pub mod module_honzon;
+pub mod module_idle_scheduler;
 pub mod module_incentives;
+pub mod module_launchpad;
+pub mod module_liquid_crowdloan;
 pub mod module_nft;
+pub mod module_nft_marketplace;
 pub mod module_nominees_election;
+pub mod module_parameters;
+pub mod module_polkadot_bridge;
 pub mod module_prices;
+pub mod module_proof_of_reserve;
+pub mod module_psm;
+pub mod module_rate_limit;
+pub mod module_scheduled_transfers;
+pub mod module_streaming;
+pub mod module_transaction_pause;
 pub mod module_transaction_payment;
+pub mod module_vesting_manager;
+pub mod module_vote_escrow;
+pub mod module_xcm_dead_letter;
+pub mod module_xcm_evm;
+pub mod module_xcm_transfer;
 
 pub mod orml_auction;
 pub mod orml_authority;
@@ -44,3 +71,5 @@ pub mod orml_tokens;
 pub mod orml_vesting;
 
 pub mod ecosystem_chainsafe;
+pub mod ecosystem_renvm_bridge;
+pub mod ecosystem_starport;
@@ -29,15 +29,15 @@ use frame_system::RawOrigin;
 use mandala_runtime::{
 	dollar, get_all_module_accounts, AccountId, AuthoritysOriginId, Balance, Balances, BlockNumber, Call,
 	CreateClassDeposit, CreateTokenDeposit, CurrencyId, DataDepositPerByte, EVMBridge, EnabledTradingPairs, Event,
-	EvmAccounts, EvmCurrencyIdMapping, GetNativeCurrencyId, NativeTokenExistentialDeposit, NftPalletId, Origin,
-	OriginCaller, ParachainSystem, Perbill, Proxy, Runtime, SevenDays, System, TokenSymbol, TreasuryPalletId,
-	TreasuryReservePalletId, Vesting, ACA, AUSD, DOT, EVM, LDOT, NFT, RENBTC,
+	EvmAccounts, EvmCurrencyIdMapping, GetNativeCurrencyId, GovernanceVotingPower, NativeTokenExistentialDeposit,
+	NftPalletId, Origin, OriginCaller, ParachainSystem, Perbill, Proxy, Runtime, SevenDays, System, TokenSymbol,
+	TreasuryPalletId, TreasuryReservePalletId, Vesting, ACA, AUSD, DOT, EVM, LDOT, NFT, RENBTC,
 };
 use module_cdp_engine::LiquidationStrategy;
 use module_evm_accounts::EvmAddressMapping;
 use module_support::{
 	mocks::MockAddressMapping, AddressMapping, CDPTreasury, CurrencyIdMapping, DEXManager, EVMBridge as EVMBridgeT,
-	Price, Rate, Ratio, RiskManager, EVM as EVMTrait,
+	Price, Rate, Ratio, RiskManager, VotingPowerProvider, EVM as EVMTrait,
 };
 use orml_authority::DelayedOrigin;
 use orml_traits::{Change, MultiCurrency};
@@ -46,10 +46,11 @@ use orml_vesting::VestingSchedule;
 use acala_service::chain_spec::evm_genesis;
 use cumulus_test_relay_sproof_builder::RelayStateSproofBuilder;
 pub use primitives::{evm::EvmAddress, DexShare, TradingPair};
+use runtime_common::ScheduledCall;
 use sp_core::{bytes::from_hex, H160};
 use sp_io::hashing::keccak_256;
 use sp_runtime::{
-	traits::{AccountIdConversion, BadOrigin, Zero},
+	traits::{AccountIdConversion, BadOrigin, BlakeTwo256, Hash, Zero},
 	DispatchError, DispatchResult, FixedPointNumber, MultiAddress,
 };
 use std::str::FromStr;
@@ -1122,6 +1123,209 @@ fn test_authority_module() {
 		});
 }
 
+#[test]
+fn test_authority_module_veto_and_scheduled_calls() {
+	ExtBuilder::default().build().execute_with(|| {
+		let call = Call::System(frame_system::Call::fill_block(Perbill::one()));
+		let tech_committee_origin = |yes: u32| -> Origin {
+			pallet_collective::RawOrigin::<AccountId, pallet_collective::Instance4>::Members(yes, 3).into()
+		};
+
+		assert_ok!(AuthorityModule::schedule_dispatch(
+			Origin::root(),
+			DispatchTime::At(20),
+			0,
+			false,
+			Box::new(call.clone())
+		));
+		assert_ok!(AuthorityModule::schedule_dispatch(
+			Origin::root(),
+			DispatchTime::At(10),
+			0,
+			true,
+			Box::new(call.clone())
+		));
+		let delayed_origin = OriginCaller::Authority(DelayedOrigin {
+			delay: 9,
+			origin: Box::new(OriginCaller::system(RawOrigin::Root)),
+		});
+		assert_eq!(
+			runtime_common::authority_scheduled_calls::<Runtime>(),
+			vec![
+				ScheduledCall {
+					origin: delayed_origin.clone(),
+					task_id: 1,
+					when: 10,
+					call_hash: BlakeTwo256::hash_of(&call),
+				},
+				ScheduledCall {
+					origin: OriginCaller::system(RawOrigin::Root),
+					task_id: 0,
+					when: 20,
+					call_hash: BlakeTwo256::hash_of(&call),
+				},
+			]
+		);
+
+		assert_noop!(
+			AuthorityModule::cancel_scheduled_dispatch(tech_committee_origin(2), delayed_origin.clone(), 1),
+			BadOrigin
+		);
+		assert_ok!(AuthorityModule::cancel_scheduled_dispatch(
+			tech_committee_origin(3),
+			delayed_origin.clone(),
+			1
+		));
+		System::assert_last_event(Event::Authority(orml_authority::Event::Cancelled(delayed_origin, 1)));
+
+		assert_eq!(
+			runtime_common::authority_scheduled_calls::<Runtime>()
+				.into_iter()
+				.map(|scheduled| scheduled.task_id)
+				.collect::<Vec<_>>(),
+			vec![0]
+		);
+
+		run_to_block(20);
+		assert_eq!(runtime_common::authority_scheduled_calls::<Runtime>(), vec![]);
+	});
+}
+
+#[test]
+fn test_account_portfolio() {
+	ExtBuilder::default()
+		.balances(vec![
+			(AccountId::from(ALICE), ACA, 1_000 * dollar(ACA)),
+			(AccountId::from(ALICE), AUSD, 1_000_000 * dollar(AUSD)),
+			(AccountId::from(ALICE), RENBTC, 100 * dollar(RENBTC)),
+		])
+		.build()
+		.execute_with(|| {
+			assert_ok!(set_oracle_price(vec![(
+				RENBTC,
+				Price::saturating_from_rational(10000, 1)
+			)])); // 10000 usd
+
+			assert_ok!(DexModule::add_liquidity(
+				Origin::signed(AccountId::from(ALICE)),
+				RENBTC,
+				AUSD,
+				10 * dollar(RENBTC),
+				100_000 * dollar(AUSD),
+				0,
+				false,
+			));
+
+			assert_ok!(CdpEngineModule::set_collateral_params(
+				Origin::root(),
+				RENBTC,
+				Change::NewValue(Some(Rate::zero())),
+				Change::NewValue(Some(Ratio::saturating_from_rational(200, 100))),
+				Change::NewValue(Some(Rate::saturating_from_rational(20, 100))),
+				Change::NewValue(Some(Ratio::saturating_from_rational(200, 100))),
+				Change::NewValue(1_000_000 * dollar(AUSD)),
+			));
+			assert_ok!(CdpEngineModule::adjust_position(
+				&AccountId::from(ALICE),
+				RENBTC,
+				(10 * dollar(RENBTC)) as i128,
+				(100_000 * dollar(AUSD)) as i128,
+			));
+
+			let portfolio = runtime_common::account_portfolio::<Runtime>(&AccountId::from(ALICE), Default::default());
+
+			assert_eq!(
+				portfolio
+					.token_balances
+					.iter()
+					.map(|balance| (balance.currency_id, balance.free))
+					.collect::<Vec<_>>(),
+				vec![
+					(ACA, 1_000 * dollar(ACA)),
+					(AUSD, Currencies::free_balance(AUSD, &AccountId::from(ALICE))),
+					(RENBTC, 80 * dollar(RENBTC)),
+					(LPTOKEN, Currencies::free_balance(LPTOKEN, &AccountId::from(ALICE))),
+				]
+			);
+
+			assert_eq!(
+				portfolio.lp_positions,
+				vec![runtime_common::portfolio::LpPosition {
+					currency_id: LPTOKEN,
+					share: Currencies::total_issuance(LPTOKEN),
+					deposited_share: 0,
+					underlying_amounts: DexModule::get_liquidity_pool(AUSD, RENBTC),
+				}]
+			);
+
+			let debit_value = CdpEngineModule::get_debit_value(RENBTC, 100_000 * dollar(AUSD));
+			assert_eq!(
+				portfolio.cdp_positions,
+				vec![runtime_common::portfolio::CdpPosition {
+					currency_id: RENBTC,
+					collateral: 10 * dollar(RENBTC),
+					debit: 100_000 * dollar(AUSD),
+					debit_value,
+					collateral_ratio: Some(Ratio::saturating_from_rational(100_000 * dollar(AUSD), debit_value)),
+					liquidation_ratio: Ratio::saturating_from_rational(200, 100),
+					is_unsafe: false,
+				}]
+			);
+
+			assert_eq!(portfolio.homa_claims, Default::default());
+			assert_eq!(portfolio.vesting_schedules, vec![]);
+		});
+}
+
+#[test]
+fn test_state_report() {
+	ExtBuilder::default()
+		.balances(vec![
+			(AccountId::from(ALICE), AUSD, 1_000_000 * dollar(AUSD)),
+			(AccountId::from(ALICE), RENBTC, 100 * dollar(RENBTC)),
+			(AccountId::from(BOB), RENBTC, 100 * dollar(RENBTC)),
+		])
+		.build()
+		.execute_with(|| {
+			let report = |pallet: &str, item: &str| runtime_common::state_report::storage_item_report(pallet, item);
+			assert_eq!(report("Loans", "Positions").count, 0);
+			assert_eq!(report("Loans", "Positions").size, 0);
+			assert_eq!(report("Tokens", "Accounts").count, 3);
+
+			assert_ok!(CdpEngineModule::set_collateral_params(
+				Origin::root(),
+				RENBTC,
+				Change::NewValue(Some(Rate::zero())),
+				Change::NewValue(Some(Ratio::saturating_from_rational(200, 100))),
+				Change::NewValue(Some(Rate::saturating_from_rational(20, 100))),
+				Change::NewValue(Some(Ratio::saturating_from_rational(200, 100))),
+				Change::NewValue(1_000_000 * dollar(AUSD)),
+			));
+			assert_ok!(LoansModule::adjust_position(
+				&AccountId::from(ALICE),
+				RENBTC,
+				(10 * dollar(RENBTC)) as i128,
+				0
+			));
+			assert_ok!(LoansModule::adjust_position(
+				&AccountId::from(BOB),
+				RENBTC,
+				(10 * dollar(RENBTC)) as i128,
+				0
+			));
+
+			let positions = report("Loans", "Positions");
+			assert_eq!(positions.pallet, b"Loans".to_vec());
+			assert_eq!(positions.item, b"Positions".to_vec());
+			assert_eq!(positions.count, 2);
+			assert!(positions.size > 0);
+
+			let reports = runtime_common::state_report(runtime_common::STATE_REPORT_ITEMS);
+			assert_eq!(reports.len(), runtime_common::STATE_REPORT_ITEMS.len());
+			assert!(reports.contains(&positions));
+		});
+}
+
 #[test]
 fn test_nft_module() {
 	ExtBuilder::default()
@@ -1618,6 +1822,78 @@ fn test_vesting_use_relaychain_block_number() {
 	});
 }
 
+#[test]
+fn test_vesting_locked_balance_counts_toward_voting_power() {
+	ExtBuilder::default().build().execute_with(|| {
+		let treasury: AccountId = TreasuryPalletId::get().into_account();
+
+		assert_ok!(Balances::set_balance(
+			Origin::root(),
+			treasury.clone().into(),
+			1_000 * dollar(ACA),
+			0
+		));
+		assert_eq!(GovernanceVotingPower::voting_power(&alice()), 0);
+
+		assert_ok!(Vesting::vested_transfer(
+			Origin::signed(treasury),
+			alice().into(),
+			VestingSchedule {
+				start: 10,
+				period: 2,
+				period_count: 5,
+				per_period: 3 * dollar(ACA),
+			}
+		));
+
+		// vesting locked balance is discounted by 50%
+		assert_eq!(GovernanceVotingPower::voting_power(&alice()), 15 * dollar(ACA) / 2);
+
+		set_relaychain_block_number(20);
+		assert_ok!(Vesting::claim(Origin::signed(alice())));
+		assert_eq!(GovernanceVotingPower::voting_power(&alice()), 0);
+	});
+}
+
+#[test]
+fn test_counted_voting_power_is_locked_while_voting() {
+	ExtBuilder::default().build().execute_with(|| {
+		let treasury: AccountId = TreasuryPalletId::get().into_account();
+
+		assert_ok!(Balances::set_balance(
+			Origin::root(),
+			treasury.clone().into(),
+			1_000 * dollar(ACA),
+			0
+		));
+		assert_ok!(Vesting::vested_transfer(
+			Origin::signed(treasury),
+			alice().into(),
+			VestingSchedule {
+				start: 10,
+				period: 2,
+				period_count: 5,
+				per_period: 3 * dollar(ACA),
+			}
+		));
+		assert_ok!(GovernanceVotingPower::lock_voting_power(
+			module_gauge::GAUGE_VOTE_LOCK_ID,
+			&alice()
+		));
+
+		// the vested balance stays locked until the vote is revoked
+		set_relaychain_block_number(20);
+		assert_ok!(Vesting::claim(Origin::signed(alice())));
+		assert_eq!(Balances::usable_balance(&alice()), 0);
+
+		assert_ok!(GovernanceVotingPower::unlock_voting_power(
+			module_gauge::GAUGE_VOTE_LOCK_ID,
+			&alice()
+		));
+		assert_eq!(Balances::usable_balance(&alice()), 15 * dollar(ACA));
+	});
+}
+
 // #[test]
 // fn receive_cross_chain_assets() {
 // 	ExtBuilder::default().build().execute_with(|| {